@@ -0,0 +1,165 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::Context as _;
+use clap::{Parser, Subcommand};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::{
+    api::BaseSystemContractKind,
+    bytecode::{validate_bytecode, BytecodeHash},
+    url::SensitiveUrl,
+    ProtocolVersionId,
+};
+
+#[derive(Debug, Parser)]
+#[command(name = "Base system contracts stager", author = "Matter Labs")]
+struct Cli {
+    /// PostgreSQL connection string for the main node database.
+    #[arg(short, long, global = true)]
+    database_url: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Stages base system contracts for the specified protocol version. Bytecode files may contain either
+    /// raw bytes or a hex string (optionally `0x`-prefixed).
+    Stage {
+        /// Minor protocol version the contracts are staged for.
+        #[arg(long)]
+        protocol_version: u16,
+        /// Path to the bootloader bytecode.
+        #[arg(long)]
+        bootloader: Option<PathBuf>,
+        /// Path to the default account bytecode.
+        #[arg(long)]
+        default_aa: Option<PathBuf>,
+        /// Path to the EVM emulator bytecode.
+        #[arg(long)]
+        evm_emulator: Option<PathBuf>,
+    },
+    /// Lists staged contracts together with their validation status.
+    List {
+        /// Minor protocol version to list contracts for. If not specified, contracts for all versions are listed.
+        #[arg(long)]
+        protocol_version: Option<u16>,
+    },
+}
+
+fn parse_protocol_version(version: u16) -> anyhow::Result<ProtocolVersionId> {
+    ProtocolVersionId::try_from(version)
+        .map_err(|err| anyhow::anyhow!("invalid protocol version {version}: {err}"))
+}
+
+fn read_bytecode(path: &Path) -> anyhow::Result<Vec<u8>> {
+    let contents =
+        fs::read(path).with_context(|| format!("failed reading bytecode from {path:?}"))?;
+    let bytecode = match std::str::from_utf8(&contents) {
+        Ok(text) => {
+            let text = text.trim();
+            let text = text.strip_prefix("0x").unwrap_or(text);
+            hex::decode(text).with_context(|| format!("bytecode in {path:?} is not valid hex"))?
+        }
+        Err(_) => contents,
+    };
+    validate_bytecode(&bytecode).with_context(|| format!("bytecode in {path:?} is invalid"))?;
+    Ok(bytecode)
+}
+
+/// The `base_system_contracts_stager` tool allows staging base system contracts (bootloader, default account
+/// and EVM emulator) in the main node database ahead of a protocol upgrade.
+///
+/// Staged contracts are validated against the base system contract hashes of the protocol upgrade once it is
+/// observed on L1 (or immediately, if the upgrade is already known). Validated contracts are used starting from
+/// the first batch with the upgraded protocol version.
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let db_url = cli
+        .database_url
+        .or_else(|| std::env::var("DATABASE_URL").ok())
+        .context("Specify the database connection string in either a CLI argument or in the DATABASE_URL environment variable")?;
+    let pool = ConnectionPool::<Core>::singleton(SensitiveUrl::from_str(&db_url)?)
+        .build()
+        .await?;
+    let mut storage = pool.connection().await?;
+
+    match cli.command {
+        Command::Stage {
+            protocol_version,
+            bootloader,
+            default_aa,
+            evm_emulator,
+        } => {
+            let protocol_version = parse_protocol_version(protocol_version)?;
+            let contracts = [
+                (BaseSystemContractKind::Bootloader, bootloader),
+                (BaseSystemContractKind::DefaultAccount, default_aa),
+                (BaseSystemContractKind::EvmEmulator, evm_emulator),
+            ];
+            let contracts: Vec<_> = contracts
+                .into_iter()
+                .filter_map(|(kind, path)| Some((kind, path?)))
+                .collect();
+            anyhow::ensure!(!contracts.is_empty(), "No contracts to stage specified");
+
+            let mut transaction = storage.start_transaction().await?;
+            for (kind, path) in contracts {
+                let bytecode = read_bytecode(&path)?;
+                let bytecode_hash = BytecodeHash::for_bytecode(&bytecode).value();
+                transaction
+                    .staged_base_system_contracts_dal()
+                    .stage_contract(protocol_version, kind, bytecode_hash, &bytecode)
+                    .await?;
+                println!("Staged {kind} with hash {bytecode_hash:?} for protocol version {protocol_version}");
+            }
+
+            // If the upgrade is already observed on L1, it won't be processed again, so validate contracts right away.
+            let upgrade = transaction
+                .protocol_versions_dal()
+                .get_protocol_version_with_latest_patch(protocol_version)
+                .await?;
+            if let Some(upgrade) = upgrade {
+                let validated = transaction
+                    .staged_base_system_contracts_dal()
+                    .validate_staged_contracts(
+                        protocol_version,
+                        &upgrade.base_system_contracts_hashes,
+                    )
+                    .await?;
+                for contract in validated {
+                    println!(
+                        "Protocol upgrade is already known; {} status: {}",
+                        contract.kind, contract.status
+                    );
+                }
+            }
+            transaction.commit().await?;
+        }
+        Command::List { protocol_version } => {
+            let protocol_version = protocol_version.map(parse_protocol_version).transpose()?;
+            let contracts = storage
+                .staged_base_system_contracts_dal()
+                .get_staged_contracts(protocol_version)
+                .await?;
+            for contract in contracts {
+                println!(
+                    "{} {}: hash {:?}, {} bytes, {}, staged at {}",
+                    contract.protocol_version,
+                    contract.kind,
+                    contract.bytecode_hash,
+                    contract.bytecode_len,
+                    contract.status,
+                    contract.staged_at
+                );
+            }
+        }
+    }
+    Ok(())
+}
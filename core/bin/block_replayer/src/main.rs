@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use clap::Parser;
+use zksync_config::{
+    configs::{chain::NetworkConfig, DatabaseSecrets, ObservabilityConfig},
+    GenesisConfig,
+};
+use zksync_core_leftovers::temp_config_store::read_yaml_repr;
+use zksync_dal::{ConnectionPool, Core};
+use zksync_env_config::FromEnv;
+use zksync_types::{vm::FastVmMode, L1BatchNumber};
+use zksync_vm_runner::impls::BatchReplayer;
+
+fn parse_vm_mode(s: &str) -> anyhow::Result<FastVmMode> {
+    Ok(match s {
+        "old" => FastVmMode::Old,
+        "new" => FastVmMode::New,
+        "shadow" => FastVmMode::Shadow,
+        _ => anyhow::bail!("unknown VM mode `{s}`; expected one of `old`, `new` or `shadow`"),
+    })
+}
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Re-executes sealed L1 batches and checks that they produce identical state",
+    long_about = None
+)]
+struct Cli {
+    /// First L1 batch to replay.
+    #[arg(long)]
+    from_l1_batch: u32,
+    /// Last L1 batch to replay (inclusive). If not specified, only `from_l1_batch` is replayed.
+    #[arg(long)]
+    to_l1_batch: Option<u32>,
+    /// VM mode to replay batches with: `old`, `new` or `shadow`. Useful to check VM upgrades.
+    #[arg(long, default_value = "old", value_parser = parse_vm_mode)]
+    vm_mode: FastVmMode,
+    /// Path to yaml secrets config. If set, it will be used instead of env vars
+    #[arg(long)]
+    secrets_path: Option<PathBuf>,
+    /// Path to yaml genesis config. If set, it will be used instead of env vars
+    #[arg(long)]
+    genesis_path: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opts = Cli::parse();
+    let observability_config =
+        ObservabilityConfig::from_env().context("ObservabilityConfig::from_env()")?;
+    let _observability_guard = observability_config.install()?;
+
+    let database_secrets = match &opts.secrets_path {
+        Some(path) => read_yaml_repr::<zksync_protobuf_config::proto::secrets::Secrets>(path)
+            .context("failed decoding secrets YAML config")?
+            .database
+            .context("Failed to find database config")?,
+        None => DatabaseSecrets::from_env().context("DatabaseSecrets::from_env()")?,
+    };
+    let chain_id = match &opts.genesis_path {
+        Some(path) => {
+            let genesis_config: GenesisConfig =
+                read_yaml_repr::<zksync_protobuf_config::proto::genesis::Genesis>(path)
+                    .context("failed decoding genesis YAML config")?;
+            genesis_config.l2_chain_id
+        }
+        None => {
+            NetworkConfig::from_env()
+                .context("NetworkConfig::from_env()")?
+                .zksync_network_id
+        }
+    };
+
+    // Batches are replayed sequentially, so a small pool is sufficient.
+    let pool = ConnectionPool::<Core>::builder(database_secrets.replica_url()?, 4)
+        .build()
+        .await
+        .context("failed to build a connection pool")?;
+    let mut replayer = BatchReplayer::new(pool, chain_id, opts.vm_mode).await?;
+
+    let from = L1BatchNumber(opts.from_l1_batch);
+    let to = L1BatchNumber(opts.to_l1_batch.unwrap_or(opts.from_l1_batch));
+    tracing::info!(
+        "Replaying L1 batches #{from}..=#{to} in {:?} VM mode",
+        opts.vm_mode
+    );
+    replayer.replay_range(from..=to).await?;
+    tracing::info!("All L1 batches #{from}..=#{to} were replayed without divergences");
+    Ok(())
+}
@@ -1,10 +1,13 @@
 use std::{path::PathBuf, time::Duration};
 
 use anyhow::Context as _;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use tokio::sync::watch;
 use zksync_config::configs::PrometheusConfig;
-use zksync_contract_verifier_lib::ContractVerifier;
+use zksync_contract_verifier_lib::{
+    migration::{export_verification_info, import_verification_info, BytecodeMismatchPolicy},
+    ContractVerifier,
+};
 use zksync_core_leftovers::temp_config_store::{load_database_secrets, load_general_config};
 use zksync_dal::{ConnectionPool, Core};
 use zksync_queued_job_processor::JobProcessor;
@@ -23,6 +26,80 @@ struct Opt {
     /// Path to the secrets file.
     #[arg(long)]
     secrets_path: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Exports all verification info using the contract verification API of the source environment.
+    ExportInfo {
+        /// URL of the contract verification API to export from.
+        #[arg(long)]
+        api_url: String,
+        /// Path to the output JSON file.
+        #[arg(long)]
+        output: PathBuf,
+    },
+    /// Imports verification info produced by `export-info` into the database of this environment.
+    ImportInfo {
+        /// Path to the JSON file produced by `export-info`.
+        #[arg(long)]
+        input: PathBuf,
+        /// What to do with contracts whose deployed bytecode differs from the exported one: `skip`, `fail` or `force`.
+        #[arg(long, default_value = "skip")]
+        on_bytecode_mismatch: BytecodeMismatchPolicy,
+        /// Replace verification info for contracts that are already verified.
+        #[arg(long)]
+        overwrite: bool,
+    },
+}
+
+async fn run_command(command: Command, secrets_path: Option<PathBuf>) -> anyhow::Result<()> {
+    match command {
+        Command::ExportInfo { api_url, output } => {
+            let exported = export_verification_info(&api_url).await?;
+            let json = serde_json::to_vec_pretty(&exported)
+                .context("failed serializing verification info")?;
+            tokio::fs::write(&output, json)
+                .await
+                .with_context(|| format!("failed writing to `{}`", output.display()))?;
+            tracing::info!(
+                "Exported {} verification info entries to `{}`",
+                exported.len(),
+                output.display()
+            );
+        }
+        Command::ImportInfo {
+            input,
+            on_bytecode_mismatch,
+            overwrite,
+        } => {
+            let json = tokio::fs::read(&input)
+                .await
+                .with_context(|| format!("failed reading `{}`", input.display()))?;
+            let entries: Vec<_> = serde_json::from_slice(&json)
+                .context("failed parsing exported verification info")?;
+            let database_secrets =
+                load_database_secrets(secrets_path).context("database secrets")?;
+            let pool = ConnectionPool::<Core>::singleton(
+                database_secrets
+                    .master_url()
+                    .context("Master DB URL is absent")?,
+            )
+            .build()
+            .await?;
+            let mut storage = pool.connection().await?;
+            let stats =
+                import_verification_info(&mut storage, &entries, on_bytecode_mismatch, overwrite)
+                    .await?;
+            tracing::info!(
+                "Imported verification info from `{}`: {stats}",
+                input.display()
+            );
+        }
+    }
+    Ok(())
 }
 
 #[tokio::main]
@@ -34,6 +111,9 @@ async fn main() -> anyhow::Result<()> {
         .observability
         .context("ObservabilityConfig")?;
     let _observability_guard = observability_config.install()?;
+    if let Some(command) = opt.command {
+        return run_command(command, opt.secrets_path).await;
+    }
 
     let database_secrets = load_database_secrets(opt.secrets_path).context("database secrets")?;
     let verifier_config = general_config
@@ -11,9 +11,10 @@ use anyhow::Context;
 use serde::Deserialize;
 use zksync_config::{
     configs::{
-        api::{MaxResponseSize, MaxResponseSizeOverrides},
+        api::{MaxResponseSize, MaxResponseSizeOverrides, SlowSubscriberPolicy},
         consensus::{ConsensusConfig, ConsensusSecrets},
-        en_config::ENConfig,
+        database::RocksdbProfile,
+        en_config::{ENConfig, RootHashMismatchPolicy},
         GeneralConfig, Secrets,
     },
     ObjectStoreConfig,
@@ -25,6 +26,7 @@ use zksync_core_leftovers::temp_config_store::read_yaml_repr;
 use zksync_dal::{ConnectionPool, Core};
 use zksync_metadata_calculator::MetadataCalculatorRecoveryConfig;
 use zksync_node_api_server::{
+    execution_sandbox::VmConcurrencyLimiterConfig,
     tx_sender::{TimestampAsserterParams, TxSenderConfig},
     web3::{state::InternalApiConfig, Namespace},
 };
@@ -32,7 +34,7 @@ use zksync_protobuf_config::proto;
 use zksync_snapshots_applier::SnapshotsApplierConfig;
 use zksync_types::{
     api::BridgeAddresses, commitment::L1BatchCommitmentMode, url::SensitiveUrl, Address,
-    L1BatchNumber, L1ChainId, L2ChainId, SLChainId, ETHEREUM_ADDRESS,
+    L1BatchNumber, L1ChainId, L2ChainId, SLChainId, ETHEREUM_ADDRESS, H256,
 };
 use zksync_web3_decl::{
     client::{DynClient, L2},
@@ -273,6 +275,16 @@ pub(crate) struct OptionalENConfig {
     /// Max possible limit of subscriptions to be in the API state at once.
     #[serde(default = "OptionalENConfig::default_subscriptions_limit")]
     pub subscriptions_limit: usize,
+    /// Maximum number of active subscriptions per WebSocket connection. Default is 1024.
+    pub max_subscriptions_per_connection: Option<NonZeroU32>,
+    /// Maximum number of notifications buffered for a single subscription. Default is 1024.
+    pub subscription_buffer_capacity: Option<NonZeroUsize>,
+    /// Maximum total number of notifications buffered for all subscriptions of a single WebSocket connection.
+    /// Default is 4096.
+    pub max_buffered_notifications_per_connection: Option<NonZeroUsize>,
+    /// Policy applied to subscriptions that receive notifications faster than the client consumes them.
+    #[serde(default)]
+    pub slow_subscriber_policy: SlowSubscriberPolicy,
     /// Max possible limit of entities to be requested via API at once.
     #[serde(default = "OptionalENConfig::default_req_entities_limit")]
     pub req_entities_limit: usize,
@@ -312,6 +324,21 @@ pub(crate) struct OptionalENConfig {
     /// This option can be tweaked down if the API server is running out of memory.
     #[serde(default = "OptionalENConfig::default_vm_concurrency_limit")]
     pub vm_concurrency_limit: usize,
+    /// Max number of VM instances to be concurrently spawned by the API server for requests from the same client
+    /// (identified by a known API key or the IP address). If not set, such requests are only limited by `vm_concurrency_limit`.
+    #[serde(default)]
+    pub vm_concurrency_limit_per_client: Option<usize>,
+    /// Max total number of VM instances to be concurrently spawned by the API server for requests from clients
+    /// that cannot be identified. If not set, `vm_concurrency_limit_per_client` is used.
+    #[serde(default)]
+    pub vm_concurrency_limit_anonymous: Option<usize>,
+    /// Relative share of VM instances spawned for interactive requests if there are queued requests of different kinds.
+    #[serde(default)]
+    pub vm_interactive_permit_weight: Option<usize>,
+    /// Relative share of VM instances spawned for heavy requests (tracing, simulation etc.)
+    /// if there are queued requests of different kinds.
+    #[serde(default)]
+    pub vm_heavy_permit_weight: Option<usize>,
     /// Smart contract bytecode cache size for the API server. Default value is 128 MiB.
     #[serde(default = "OptionalENConfig::default_factory_deps_cache_size_mb")]
     factory_deps_cache_size_mb: usize,
@@ -322,6 +349,14 @@ pub(crate) struct OptionalENConfig {
     /// values cache will be disabled.
     #[serde(default = "OptionalENConfig::default_latest_values_cache_size_mb")]
     latest_values_cache_size_mb: usize,
+    /// Historical storage views cache size in MiBs. Historical views allow serving storage reads for recent historical
+    /// blocks using the latest values cache. The default value is 0, i.e., historical views are disabled.
+    #[serde(default = "OptionalENConfig::default_historical_views_cache_size_mb")]
+    historical_views_cache_size_mb: usize,
+    /// Maximum lag in the number of blocks relative to the latest values cache for which historical storage views
+    /// are materialized. Greater values make historical views more expensive to materialize. The default value is 1,000.
+    #[serde(default = "OptionalENConfig::default_historical_views_max_block_lag")]
+    pub historical_views_max_block_lag: NonZeroU32,
     /// Enabled JSON RPC API namespaces.
     api_namespaces: Option<Vec<Namespace>>,
     /// Whether to support HTTP methods that install filters and query filter changes.
@@ -344,6 +379,13 @@ pub(crate) struct OptionalENConfig {
     /// Maximum number of transactions to be stored in the mempool cache.
     #[serde(default = "OptionalENConfig::default_mempool_cache_size")]
     pub mempool_cache_size: usize,
+    /// Maximum number of `eth_call` / `eth_estimateGas` results to be stored in the call result cache.
+    /// The cache is disabled by default.
+    #[serde(default)]
+    pub call_result_cache_size: usize,
+    /// RPC methods (e.g., `eth_estimateGas`) excluded from caching by the call result cache.
+    #[serde(default)]
+    pub call_result_cache_excluded_methods: Vec<String>,
     /// Enables extended tracing of RPC calls. This may negatively impact performance for nodes under high load
     /// (hundreds or thousands RPS).
     #[serde(default = "OptionalENConfig::default_extended_api_tracing")]
@@ -482,6 +524,11 @@ pub(crate) struct OptionalENConfig {
     /// If set to 0, L1 batches will not be retained based on their timestamp. The default value is 7 days.
     #[serde(default = "OptionalENConfig::default_pruning_data_retention_sec")]
     pruning_data_retention_sec: u64,
+    /// If set, L1 batches will be pruned once the batch timestamp is this old (in seconds) even if there are less
+    /// than `pruning_chunk_size` such batches, so that the node doesn't retain data older than the specified age
+    /// (subject to other pruning criteria, e.g. batch execution on L1). Should be greater than `pruning_data_retention_sec`.
+    /// Pruning of the Merkle tree follows Postgres pruning, so it uses the same retention.
+    pruning_max_data_age_sec: Option<NonZeroU64>,
     /// Gateway RPC URL, needed for operating during migration.
     pub gateway_url: Option<SensitiveUrl>,
     /// Interval for bridge addresses refreshing in seconds.
@@ -500,6 +547,10 @@ impl OptionalENConfig {
         let api_namespaces = load_config!(general_config.api_config, web3_json_rpc.api_namespaces)
             .map(|a: Vec<String>| a.iter().map(|a| a.parse()).collect::<Result<_, _>>())
             .transpose()?;
+        let subscription_limits = general_config
+            .api_config
+            .as_ref()
+            .and_then(|api| api.web3_json_rpc.subscription_limits.clone());
 
         Ok(OptionalENConfig {
             filters_limit: load_optional_config_or_default!(
@@ -512,6 +563,19 @@ impl OptionalENConfig {
                 web3_json_rpc.subscriptions_limit,
                 default_subscriptions_limit
             ),
+            max_subscriptions_per_connection: subscription_limits
+                .as_ref()
+                .and_then(|limits| limits.max_subscriptions_per_connection),
+            subscription_buffer_capacity: subscription_limits
+                .as_ref()
+                .and_then(|limits| limits.buffer_capacity),
+            max_buffered_notifications_per_connection: subscription_limits
+                .as_ref()
+                .and_then(|limits| limits.max_buffered_notifications_per_connection),
+            slow_subscriber_policy: subscription_limits
+                .as_ref()
+                .map(|limits| limits.slow_subscriber_policy)
+                .unwrap_or_default(),
             req_entities_limit: load_optional_config_or_default!(
                 general_config.api_config,
                 web3_json_rpc.req_entities_limit,
@@ -561,6 +625,22 @@ impl OptionalENConfig {
                 web3_json_rpc.vm_concurrency_limit,
                 default_vm_concurrency_limit
             ),
+            vm_concurrency_limit_per_client: general_config
+                .api_config
+                .as_ref()
+                .and_then(|a| a.web3_json_rpc.vm_concurrency_limit_per_client),
+            vm_concurrency_limit_anonymous: general_config
+                .api_config
+                .as_ref()
+                .and_then(|a| a.web3_json_rpc.vm_concurrency_limit_anonymous),
+            vm_interactive_permit_weight: general_config
+                .api_config
+                .as_ref()
+                .and_then(|a| a.web3_json_rpc.vm_interactive_permit_weight),
+            vm_heavy_permit_weight: general_config
+                .api_config
+                .as_ref()
+                .and_then(|a| a.web3_json_rpc.vm_heavy_permit_weight),
             factory_deps_cache_size_mb: load_optional_config_or_default!(
                 general_config.api_config,
                 web3_json_rpc.factory_deps_cache_size_mb,
@@ -576,6 +656,16 @@ impl OptionalENConfig {
                 web3_json_rpc.latest_values_cache_size_mb,
                 default_latest_values_cache_size_mb
             ),
+            historical_views_cache_size_mb: load_optional_config_or_default!(
+                general_config.api_config,
+                web3_json_rpc.historical_views_cache_size_mb,
+                default_historical_views_cache_size_mb
+            ),
+            historical_views_max_block_lag: load_optional_config_or_default!(
+                general_config.api_config,
+                web3_json_rpc.historical_views_max_block_lag,
+                default_historical_views_max_block_lag
+            ),
             filters_disabled: general_config
                 .api_config
                 .as_ref()
@@ -591,6 +681,16 @@ impl OptionalENConfig {
                 web3_json_rpc.mempool_cache_size,
                 default_mempool_cache_size
             ),
+            call_result_cache_size: general_config
+                .api_config
+                .as_ref()
+                .map(|a| a.web3_json_rpc.call_result_cache_size())
+                .unwrap_or_default(),
+            call_result_cache_excluded_methods: general_config
+                .api_config
+                .as_ref()
+                .map(|a| a.web3_json_rpc.call_result_cache_excluded_methods.clone())
+                .unwrap_or_default(),
 
             healthcheck_slow_time_limit_ms: load_config!(
                 general_config.api_config,
@@ -703,6 +803,10 @@ impl OptionalENConfig {
                 data_retention_sec,
                 default_pruning_data_retention_sec
             ),
+            pruning_max_data_age_sec: general_config
+                .pruning
+                .as_ref()
+                .and_then(|a| a.max_data_age_sec),
             protective_reads_persistence_enabled: general_config
                 .db_config
                 .as_ref()
@@ -804,6 +908,14 @@ impl OptionalENConfig {
         128
     }
 
+    const fn default_historical_views_cache_size_mb() -> usize {
+        0
+    }
+
+    fn default_historical_views_max_block_lag() -> NonZeroU32 {
+        NonZeroU32::new(1_000).unwrap()
+    }
+
     const fn default_merkle_tree_multi_get_chunk_size() -> usize {
         500
     }
@@ -907,6 +1019,11 @@ impl OptionalENConfig {
         self.latest_values_cache_size_mb * BYTES_IN_MEGABYTE
     }
 
+    /// Returns the size of historical storage views cache in bytes.
+    pub fn historical_views_cache_size(&self) -> usize {
+        self.historical_views_cache_size_mb * BYTES_IN_MEGABYTE
+    }
+
     /// Returns the size of block cache for Merkle tree in bytes.
     pub fn merkle_tree_block_cache_size(&self) -> usize {
         self.merkle_tree_block_cache_size_mb * BYTES_IN_MEGABYTE
@@ -946,6 +1063,18 @@ impl OptionalENConfig {
         }
     }
 
+    pub fn vm_concurrency_limiter_config(&self) -> VmConcurrencyLimiterConfig {
+        let default = VmConcurrencyLimiterConfig::default();
+        VmConcurrencyLimiterConfig {
+            max_concurrency_per_client: self.vm_concurrency_limit_per_client,
+            max_concurrency_anonymous: self.vm_concurrency_limit_anonymous,
+            interactive_weight: self
+                .vm_interactive_permit_weight
+                .unwrap_or(default.interactive_weight),
+            heavy_weight: self.vm_heavy_permit_weight.unwrap_or(default.heavy_weight),
+        }
+    }
+
     pub fn healthcheck_slow_time_limit(&self) -> Option<Duration> {
         self.healthcheck_slow_time_limit_ms
             .map(Duration::from_millis)
@@ -968,6 +1097,11 @@ impl OptionalENConfig {
         Duration::from_secs(self.pruning_data_retention_sec)
     }
 
+    pub fn pruning_max_data_age(&self) -> Option<Duration> {
+        self.pruning_max_data_age_sec
+            .map(|age| Duration::from_secs(age.get()))
+    }
+
     pub fn bridge_addresses_refresh_interval(&self) -> Option<Duration> {
         self.bridge_addresses_refresh_interval_sec
             .map(|n| Duration::from_secs(n.get()))
@@ -1122,10 +1256,25 @@ pub(crate) struct ExperimentalENConfig {
     /// Maximum number of files concurrently opened by state keeper cache RocksDB. Useful to fit into OS limits; can be used
     /// as a rudimentary way to control RAM usage of the cache.
     pub state_keeper_db_max_open_files: Option<NonZeroU32>,
+    /// RocksDB tuning profile for the state keeper cache.
+    #[serde(default)]
+    pub state_keeper_db_profile: RocksdbProfile,
+
+    // Merkle tree config
+    /// RocksDB tuning profile for the Merkle tree. The write-heavy profile can speed up tree recovery,
+    /// while the read-heavy one is better suited for nodes serving the tree API.
+    #[serde(default)]
+    pub merkle_tree_rocksdb_profile: RocksdbProfile,
 
     // Snapshot recovery
     /// L1 batch number of the snapshot to use during recovery. Specifying this parameter is mostly useful for testing.
     pub snapshots_recovery_l1_batch: Option<L1BatchNumber>,
+    /// Expected manifest hash of the snapshot to recover from, obtained from a trusted source. If not set,
+    /// the snapshot manifest is only checked against the unsigned hash returned by the main node.
+    pub snapshots_recovery_manifest_hash: Option<H256>,
+    /// JSON-RPC URL of another external node to recover from instead of the main node. Snapshot data is still read
+    /// from the snapshot object store, which must be shared with the peer.
+    pub snapshots_recovery_peer_url: Option<SensitiveUrl>,
     /// Enables dropping storage key preimages when recovering storage logs from a snapshot with version 0.
     /// This is a temporary flag that will eventually be removed together with version 0 snapshot support.
     #[serde(default)]
@@ -1148,6 +1297,12 @@ pub(crate) struct ExperimentalENConfig {
     /// Maximum degree of parallelism during commitment generation, i.e., the maximum number of L1 batches being processed in parallel.
     /// If not specified, commitment generator will use a value roughly equal to the number of CPU cores with some clamping applied.
     pub commitment_generator_max_parallelism: Option<NonZeroU32>,
+
+    // Tree data fetcher
+    /// Policy applied by the tree data fetcher if a root hash fetched from the main node differs from the root hash
+    /// committed on L1. By default, only root hashes verified against L1 are persisted, and the node halts on a mismatch.
+    #[serde(default)]
+    pub tree_data_fetcher_root_hash_mismatch_policy: RootHashMismatchPolicy,
 }
 
 impl ExperimentalENConfig {
@@ -1165,11 +1320,16 @@ impl ExperimentalENConfig {
             state_keeper_db_block_cache_capacity_mb:
                 Self::default_state_keeper_db_block_cache_capacity_mb(),
             state_keeper_db_max_open_files: None,
+            state_keeper_db_profile: RocksdbProfile::default(),
+            merkle_tree_rocksdb_profile: RocksdbProfile::default(),
             snapshots_recovery_l1_batch: None,
+            snapshots_recovery_manifest_hash: None,
+            snapshots_recovery_peer_url: None,
             snapshots_recovery_drop_storage_key_preimages: false,
             snapshots_recovery_tree_chunk_size: Self::default_snapshots_recovery_tree_chunk_size(),
             snapshots_recovery_tree_parallel_persistence_buffer: None,
             commitment_generator_max_parallelism: None,
+            tree_data_fetcher_root_hash_mismatch_policy: RootHashMismatchPolicy::default(),
         }
     }
 
@@ -1178,7 +1338,10 @@ impl ExperimentalENConfig {
         self.state_keeper_db_block_cache_capacity_mb * BYTES_IN_MEGABYTE
     }
 
-    pub fn from_configs(general_config: &GeneralConfig) -> anyhow::Result<Self> {
+    pub fn from_configs(
+        general_config: &GeneralConfig,
+        en_config: &ENConfig,
+    ) -> anyhow::Result<Self> {
         Ok(Self {
             state_keeper_db_block_cache_capacity_mb: load_config_or_default!(
                 general_config.db_config,
@@ -1189,7 +1352,22 @@ impl ExperimentalENConfig {
                 general_config.db_config,
                 experimental.state_keeper_db_max_open_files
             ),
+            state_keeper_db_profile: general_config
+                .db_config
+                .as_ref()
+                .map(|config| config.experimental.state_keeper_db_profile)
+                .unwrap_or_default(),
+            merkle_tree_rocksdb_profile: general_config
+                .db_config
+                .as_ref()
+                .map(|config| config.merkle_tree.rocksdb_profile)
+                .unwrap_or_default(),
             snapshots_recovery_l1_batch: load_config!(general_config.snapshot_recovery, l1_batch),
+            snapshots_recovery_manifest_hash: load_config!(
+                general_config.snapshot_recovery,
+                manifest_hash
+            ),
+            snapshots_recovery_peer_url: load_config!(general_config.snapshot_recovery, peer_url),
             snapshots_recovery_tree_chunk_size: load_optional_config_or_default!(
                 general_config.snapshot_recovery,
                 tree.chunk_size,
@@ -1207,6 +1385,8 @@ impl ExperimentalENConfig {
                 .commitment_generator
                 .as_ref()
                 .map(|a| a.max_parallelism),
+            tree_data_fetcher_root_hash_mismatch_policy: en_config
+                .tree_data_fetcher_root_hash_mismatch_policy,
         })
     }
 }
@@ -1370,7 +1550,8 @@ impl ExternalNodeConfig<()> {
                 .max_connections()?,
         };
         let observability = ObservabilityENConfig::from_configs(&general_config)?;
-        let experimental = ExperimentalENConfig::from_configs(&general_config)?;
+        let experimental =
+            ExperimentalENConfig::from_configs(&general_config, &external_node_config)?;
 
         let api_component = ApiComponentConfig::from_configs(&general_config);
         let tree_component = TreeComponentConfig::from_configs(&general_config);
@@ -28,6 +28,9 @@ pub(crate) struct ObservabilityENConfig {
     pub log_format: LogFormat,
     // Log directives in format that is used in `RUST_LOG`
     pub log_directives: Option<String>,
+    /// Whether to add trace / span correlation IDs to JSON logs.
+    #[serde(default)]
+    pub log_correlation_ids: bool,
 }
 
 impl ObservabilityENConfig {
@@ -82,7 +85,8 @@ impl ObservabilityENConfig {
 
     pub fn build_observability(&self) -> anyhow::Result<zksync_vlog::ObservabilityGuard> {
         let logs = zksync_vlog::Logs::from(self.log_format)
-            .with_log_directives(self.log_directives.clone());
+            .with_log_directives(self.log_directives.clone())
+            .with_correlation_ids(self.log_correlation_ids);
 
         // Some legacy deployments use `unset` as an equivalent of `None`.
         let sentry_url = self.sentry_url.as_deref().filter(|&url| url != "unset");
@@ -102,7 +106,7 @@ impl ObservabilityENConfig {
     }
 
     pub(crate) fn from_configs(general_config: &GeneralConfig) -> anyhow::Result<Self> {
-        let (sentry_url, sentry_environment, log_format, log_directives) =
+        let (sentry_url, sentry_environment, log_format, log_directives, log_correlation_ids) =
             if let Some(observability) = general_config.observability.as_ref() {
                 (
                     observability.sentry_url.clone(),
@@ -112,9 +116,10 @@ impl ObservabilityENConfig {
                         .parse()
                         .context("Invalid log format")?,
                     observability.log_directives.clone(),
+                    observability.log_correlation_ids,
                 )
             } else {
-                (None, None, LogFormat::default(), None)
+                (None, None, LogFormat::default(), None, false)
             };
         let (prometheus_port, prometheus_pushgateway_url, prometheus_push_interval_ms) =
             if let Some(prometheus) = general_config.prometheus_config.as_ref() {
@@ -134,6 +139,7 @@ impl ObservabilityENConfig {
             sentry_environment,
             log_format,
             log_directives,
+            log_correlation_ids,
         })
     }
 }
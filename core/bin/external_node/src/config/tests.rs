@@ -200,6 +200,12 @@ fn parsing_experimental_config_from_empty_env() {
     let config: ExperimentalENConfig = envy::prefixed("EN_EXPERIMENTAL_").from_iter([]).unwrap();
     assert_eq!(config.state_keeper_db_block_cache_capacity(), 128 << 20);
     assert_eq!(config.state_keeper_db_max_open_files, None);
+    assert_eq!(config.state_keeper_db_profile, RocksdbProfile::Balanced);
+    assert_eq!(config.merkle_tree_rocksdb_profile, RocksdbProfile::Balanced);
+    assert_eq!(
+        config.tree_data_fetcher_root_hash_mismatch_policy,
+        RootHashMismatchPolicy::Halt
+    );
 }
 
 #[test]
@@ -210,6 +216,12 @@ fn parsing_experimental_config_from_env() {
             "64",
         ),
         ("EN_EXPERIMENTAL_STATE_KEEPER_DB_MAX_OPEN_FILES", "100"),
+        ("EN_EXPERIMENTAL_STATE_KEEPER_DB_PROFILE", "read_heavy"),
+        ("EN_EXPERIMENTAL_MERKLE_TREE_ROCKSDB_PROFILE", "write_heavy"),
+        (
+            "EN_EXPERIMENTAL_TREE_DATA_FETCHER_ROOT_HASH_MISMATCH_POLICY",
+            "log",
+        ),
     ];
     let env_vars = env_vars
         .into_iter()
@@ -220,4 +232,13 @@ fn parsing_experimental_config_from_env() {
         .unwrap();
     assert_eq!(config.state_keeper_db_block_cache_capacity(), 64 << 20);
     assert_eq!(config.state_keeper_db_max_open_files, NonZeroU32::new(100));
+    assert_eq!(config.state_keeper_db_profile, RocksdbProfile::ReadHeavy);
+    assert_eq!(
+        config.merkle_tree_rocksdb_profile,
+        RocksdbProfile::WriteHeavy
+    );
+    assert_eq!(
+        config.tree_data_fetcher_root_hash_mismatch_policy,
+        RootHashMismatchPolicy::Log
+    );
 }
@@ -14,7 +14,7 @@ use zksync_config::{
 use zksync_metadata_calculator::{
     MerkleTreeReaderConfig, MetadataCalculatorConfig, MetadataCalculatorRecoveryConfig,
 };
-use zksync_node_api_server::web3::Namespace;
+use zksync_node_api_server::web3::{Namespace, ResponseCompression};
 use zksync_node_framework::{
     implementations::layers::{
         batch_status_updater::BatchStatusUpdaterLayer,
@@ -29,7 +29,9 @@ use zksync_node_framework::{
         main_node_fee_params_fetcher::MainNodeFeeParamsFetcherLayer,
         metadata_calculator::{MetadataCalculatorLayer, TreeApiServerLayer},
         node_storage_init::{
-            external_node_strategy::{ExternalNodeInitStrategyLayer, SnapshotRecoveryConfig},
+            external_node_strategy::{
+                ExternalNodeInitStrategyLayer, RecoverySource, SnapshotRecoveryConfig,
+            },
             NodeStorageInitializerLayer,
         },
         pools_layer::PoolsLayerBuilder,
@@ -47,7 +49,7 @@ use zksync_node_framework::{
         tree_data_fetcher::TreeDataFetcherLayer,
         validate_chain_ids::ValidateChainIdsLayer,
         web3_api::{
-            caches::MempoolCacheLayer,
+            caches::{CallResultCacheLayer, MempoolCacheLayer},
             server::{Web3ServerLayer, Web3ServerOptionalConfig},
             tree_api_client::TreeApiClientLayer,
             tx_sender::{PostgresStorageCachesConfig, TxSenderLayer},
@@ -109,12 +111,14 @@ impl ExternalNodeBuilder {
                 .optional
                 .slow_query_threshold()
                 .map(|d| d.as_millis() as u64),
+            leak_detection_threshold_ms: None,
             test_server_url: None,
             test_prover_url: None,
         };
         let secrets = DatabaseSecrets {
             server_url: Some(self.config.postgres.database_url()),
             server_replica_url: Some(self.config.postgres.database_url()),
+            server_replica_urls: vec![],
             prover_url: None,
         };
         let pools_layer = PoolsLayerBuilder::empty(config, secrets)
@@ -214,7 +218,8 @@ impl ExternalNodeBuilder {
         .with_pre_insert_txs(true) // EN requires txs to be pre-inserted.
         .with_protective_reads_persistence_enabled(
             self.config.optional.protective_reads_persistence_enabled,
-        );
+        )
+        .with_commitment_mode(self.config.optional.l1_batch_commit_data_generator_mode);
 
         let io_layer = ExternalIOLayer::new(self.config.required.l2_chain_id);
 
@@ -233,6 +238,7 @@ impl ExternalNodeBuilder {
                 .experimental
                 .state_keeper_db_block_cache_capacity(),
             max_open_files: self.config.experimental.state_keeper_db_max_open_files,
+            profile: self.config.experimental.state_keeper_db_profile,
         };
         let state_keeper_layer = StateKeeperLayer::new(
             self.config.required.state_cache_path.clone(),
@@ -266,7 +272,8 @@ impl ExternalNodeBuilder {
                 self.config.optional.pruning_removal_delay(),
                 self.config.optional.pruning_chunk_size,
                 self.config.optional.pruning_data_retention(),
-            );
+            )
+            .with_maximum_l1_batch_age(self.config.optional.pruning_max_data_age());
             self.node.add_layer(layer);
         } else {
             tracing::info!("Pruning is disabled");
@@ -326,6 +333,11 @@ impl ExternalNodeBuilder {
         let layer = TreeDataFetcherLayer::new(
             self.config.l1_diamond_proxy_address(),
             self.config.required.l2_chain_id,
+        )
+        .with_root_hash_mismatch_policy(
+            self.config
+                .experimental
+                .tree_data_fetcher_root_hash_mismatch_policy,
         );
         self.node.add_layer(layer);
         Ok(self)
@@ -352,6 +364,7 @@ impl ExternalNodeBuilder {
                 .merkle_tree_include_indices_and_filters_in_block_cache,
             memtable_capacity: self.config.optional.merkle_tree_memtable_capacity(),
             stalled_writes_timeout: self.config.optional.merkle_tree_stalled_writes_timeout(),
+            rocksdb_profile: self.config.experimental.merkle_tree_rocksdb_profile,
             sealed_batches_have_protective_reads: self
                 .config
                 .optional
@@ -423,6 +436,12 @@ impl ExternalNodeBuilder {
             initial_writes_cache_size: self.config.optional.initial_writes_cache_size() as u64,
             latest_values_cache_size: self.config.optional.latest_values_cache_size() as u64,
             latest_values_max_block_lag: 20, // reasonable default
+            historical_views_cache_size: self.config.optional.historical_views_cache_size() as u64,
+            historical_views_max_block_lag: self
+                .config
+                .optional
+                .historical_views_max_block_lag
+                .get(),
         };
         let max_vm_concurrency = self.config.optional.vm_concurrency_limit;
         let tx_sender_layer = TxSenderLayer::new(
@@ -430,6 +449,7 @@ impl ExternalNodeBuilder {
             postgres_storage_config,
             max_vm_concurrency,
         )
+        .with_vm_concurrency_limiter_config(self.config.optional.vm_concurrency_limiter_config())
         .with_whitelisted_tokens_for_aa_cache(true);
 
         self.node.add_layer(ProxySinkLayer);
@@ -442,6 +462,13 @@ impl ExternalNodeBuilder {
             self.config.optional.mempool_cache_size,
             self.config.optional.mempool_cache_update_interval(),
         ));
+        self.node.add_layer(CallResultCacheLayer::new(
+            self.config.optional.call_result_cache_size,
+            self.config
+                .optional
+                .call_result_cache_excluded_methods
+                .clone(),
+        ));
         Ok(self)
     }
 
@@ -471,14 +498,26 @@ impl ExternalNodeBuilder {
             namespaces: Some(self.config.optional.api_namespaces()),
             filters_limit: Some(self.config.optional.filters_limit),
             subscriptions_limit: Some(self.config.optional.subscriptions_limit),
+            max_subscriptions_per_connection: self.config.optional.max_subscriptions_per_connection,
+            subscription_buffer_capacity: self.config.optional.subscription_buffer_capacity,
+            max_buffered_notifications_per_connection: self
+                .config
+                .optional
+                .max_buffered_notifications_per_connection,
+            slow_subscriber_policy: self.config.optional.slow_subscriber_policy,
             batch_request_size_limit: Some(self.config.optional.max_batch_request_size),
+            request_body_size_limit: None, // Uses the default limit
             response_body_size_limit: Some(self.config.optional.max_response_body_size()),
+            cors_allowed_origins: vec![], // Allows all origins
+            response_compression: ResponseCompression::default(),
             with_extended_tracing: self.config.optional.extended_rpc_tracing,
             pruning_info_refresh_interval: Some(pruning_info_refresh_interval),
             bridge_addresses_refresh_interval: self
                 .config
                 .optional
                 .bridge_addresses_refresh_interval(),
+            // The main node retains all data, so it can serve requests for data pruned on the external node.
+            archive_peer_available: true,
             polling_interval: Some(self.config.optional.polling_interval()),
             websocket_requests_per_minute_limit: None, // To be set by WS server layer method if required.
             replication_lag_limit: None,               // TODO: Support replication lag limit
@@ -546,6 +585,11 @@ impl ExternalNodeBuilder {
                 .snapshots_recovery_enabled
                 .then_some(SnapshotRecoveryConfig {
                     snapshot_l1_batch_override: config.experimental.snapshots_recovery_l1_batch,
+                    manifest_hash: config.experimental.snapshots_recovery_manifest_hash,
+                    source: match &config.experimental.snapshots_recovery_peer_url {
+                        Some(url) => RecoverySource::Peer(url.clone()),
+                        None => RecoverySource::MainNode,
+                    },
                     drop_storage_key_preimages: config
                         .experimental
                         .snapshots_recovery_drop_storage_key_preimages,
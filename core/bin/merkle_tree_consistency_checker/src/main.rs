@@ -2,11 +2,15 @@ use std::{path::Path, time::Instant};
 
 use anyhow::Context as _;
 use clap::Parser;
-use zksync_config::{configs::ObservabilityConfig, DBConfig};
+use zksync_config::{
+    configs::{DatabaseSecrets, ObservabilityConfig},
+    DBConfig,
+};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_env_config::FromEnv;
 use zksync_merkle_tree::domain::ZkSyncTree;
 use zksync_storage::RocksDB;
-use zksync_types::L1BatchNumber;
+use zksync_types::{L1BatchNumber, H256};
 
 #[derive(Debug, Parser)]
 #[command(
@@ -20,6 +24,14 @@ struct Cli {
     /// applied to it last. If not specified, the latest tree version is checked.
     #[arg(long = "l1-batch")]
     l1_batch: Option<u32>,
+    /// Walk the entire tree and report all divergent key ranges instead of stopping on the first
+    /// encountered inconsistency.
+    #[arg(long)]
+    report_all: bool,
+    /// Compare the tree root hash with the root hash of the L1 batch stored in Postgres.
+    /// Postgres URL is read from the `DATABASE_URL` env variable.
+    #[arg(long)]
+    compare_with_postgres: bool,
 }
 
 impl Cli {
@@ -44,13 +56,68 @@ impl Cli {
         };
 
         tracing::info!("L1 batch number to check: {l1_batch_number}");
-        tree.verify_consistency(l1_batch_number)
-            .context("Merkle tree is inconsistent")?;
+        if self.report_all {
+            let errors = tree
+                .collect_inconsistencies(l1_batch_number)
+                .context("cannot check Merkle tree consistency")?;
+            for err in &errors {
+                if let Some(key_range) = err.key_range() {
+                    tracing::error!(
+                        "Divergent key range {:#066x}..={:#066x}: {err}",
+                        key_range.start(),
+                        key_range.end()
+                    );
+                } else {
+                    tracing::error!("Inconsistency affecting the entire tree: {err}");
+                }
+            }
+            anyhow::ensure!(
+                errors.is_empty(),
+                "Merkle tree is inconsistent: found {} divergent subtrees",
+                errors.len()
+            );
+        } else {
+            tree.verify_consistency(l1_batch_number)
+                .context("Merkle tree is inconsistent")?;
+        }
         tracing::info!("Merkle tree verified in {:?}", start.elapsed());
+
+        if self.compare_with_postgres {
+            let (tree_root_hash, _) = tree
+                .root_info(l1_batch_number)
+                .context("tree root is missing")?;
+            let postgres_root_hash = load_postgres_root_hash(l1_batch_number)?;
+            anyhow::ensure!(
+                tree_root_hash == postgres_root_hash,
+                "Merkle tree root hash {tree_root_hash:?} for L1 batch #{l1_batch_number} differs \
+                 from the root hash {postgres_root_hash:?} stored in Postgres"
+            );
+            tracing::info!("Merkle tree root hash matches the root hash stored in Postgres");
+        }
         Ok(())
     }
 }
 
+fn load_postgres_root_hash(l1_batch_number: L1BatchNumber) -> anyhow::Result<H256> {
+    let database_secrets = DatabaseSecrets::from_env().context("DatabaseSecrets::from_env()")?;
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("failed initializing Tokio runtime")?;
+    runtime.block_on(async {
+        let pool = ConnectionPool::<Core>::singleton(database_secrets.replica_url()?)
+            .build()
+            .await
+            .context("failed building connection pool")?;
+        let mut storage = pool.connection().await?;
+        storage
+            .blocks_dal()
+            .get_l1_batch_state_root(l1_batch_number)
+            .await?
+            .with_context(|| format!("L1 batch #{l1_batch_number} has no root hash in Postgres"))
+    })
+}
+
 fn main() -> anyhow::Result<()> {
     let observability_config =
         ObservabilityConfig::from_env().context("ObservabilityConfig::from_env()")?;
@@ -7,13 +7,14 @@ use tokio::sync::Semaphore;
 use zksync_config::SnapshotsCreatorConfig;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalResult};
 use zksync_object_store::{ObjectStore, StoredObject};
+use zksync_protobuf::ProtoFmt;
 use zksync_types::{
     snapshots::{
         uniform_hashed_keys_chunk, SnapshotFactoryDependencies, SnapshotFactoryDependency,
         SnapshotMetadata, SnapshotStorageLog, SnapshotStorageLogsChunk,
         SnapshotStorageLogsStorageKey, SnapshotVersion,
     },
-    L1BatchNumber, L2BlockNumber,
+    L1BatchNumber, L2BlockNumber, H256,
 };
 
 use crate::metrics::{FactoryDepsStage, StorageChunkStage, METRICS};
@@ -98,7 +99,7 @@ impl SnapshotCreator {
 
         let latency =
             METRICS.storage_logs_processing_duration[&StorageChunkStage::LoadFromPostgres].start();
-        let (output_filepath, latency) = match progress.version {
+        let (output_filepath, checksum, latency) = match progress.version {
             SnapshotVersion::Version0 => {
                 #[allow(deprecated)] // support of version 0 snapshots will be removed eventually
                 let logs = conn
@@ -144,7 +145,12 @@ impl SnapshotCreator {
             .await?;
         master_conn
             .snapshots_dal()
-            .add_storage_logs_filepath_for_snapshot(l1_batch_number, chunk_id, &output_filepath)
+            .add_storage_logs_filepath_for_snapshot(
+                l1_batch_number,
+                chunk_id,
+                &output_filepath,
+                checksum,
+            )
             .await?;
         #[cfg(test)]
         self.event_listener.on_chunk_saved();
@@ -162,13 +168,15 @@ impl SnapshotCreator {
         l1_batch_number: L1BatchNumber,
         chunk_id: u64,
         logs: Vec<SnapshotStorageLog<K>>,
-    ) -> anyhow::Result<(String, Duration)>
+    ) -> anyhow::Result<(String, H256, Duration)>
     where
-        for<'a> SnapshotStorageLogsChunk<K>: StoredObject<Key<'a> = SnapshotStorageLogsStorageKey>,
+        for<'a> SnapshotStorageLogsChunk<K>:
+            StoredObject<Key<'a> = SnapshotStorageLogsStorageKey> + ProtoFmt,
     {
         let latency =
             METRICS.storage_logs_processing_duration[&StorageChunkStage::SaveToGcs].start();
         let storage_logs_chunk = SnapshotStorageLogsChunk { storage_logs: logs };
+        let checksum = storage_logs_chunk.checksum();
         let key = SnapshotStorageLogsStorageKey {
             l1_batch_number,
             chunk_id,
@@ -183,7 +191,7 @@ impl SnapshotCreator {
             .get_storage_prefix::<SnapshotStorageLogsChunk<K>>();
         let output_filepath = format!("{output_filepath_prefix}/{filename}");
         let latency = latency.observe();
-        Ok((output_filepath, latency))
+        Ok((output_filepath, checksum, latency))
     }
 
     async fn process_factory_deps(
@@ -13,7 +13,9 @@ use anyhow::Context as _;
 use structopt::StructOpt;
 use tokio::{sync::watch, task::JoinHandle};
 use zksync_config::configs::PrometheusConfig;
-use zksync_core_leftovers::temp_config_store::{load_database_secrets, load_general_config};
+use zksync_core_leftovers::temp_config_store::{
+    load_database_secrets, load_general_config, load_s3_secrets,
+};
 use zksync_dal::{ConnectionPool, Core};
 use zksync_object_store::ObjectStoreFactory;
 use zksync_vlog::prometheus::PrometheusExporterConfig;
@@ -65,6 +67,7 @@ async fn main() -> anyhow::Result<()> {
 
     let opt = Opt::from_args();
     let general_config = load_general_config(opt.config_path).context("general config")?;
+    let s3_secrets = load_s3_secrets(opt.secrets_path.clone()).context("S3 secrets")?;
     let database_secrets = load_database_secrets(opt.secrets_path).context("database secrets")?;
 
     let observability_config = general_config
@@ -85,6 +88,7 @@ async fn main() -> anyhow::Result<()> {
         .context("snapshot creator object storage config")?;
 
     let blob_store = ObjectStoreFactory::new(object_store_config)
+        .with_s3_secrets(s3_secrets)
         .create_store()
         .await?;
 
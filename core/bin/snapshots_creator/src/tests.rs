@@ -285,7 +285,7 @@ async fn persisting_snapshot_metadata() {
     let mut conn = pool.connection().await.unwrap();
     prepare_postgres(&mut rng, &mut conn, 10).await;
 
-    SnapshotCreator::for_tests(object_store, pool.clone())
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
         .run(TEST_CONFIG, MIN_CHUNK_COUNT)
         .await
         .unwrap();
@@ -321,6 +321,19 @@ async fn persisting_snapshot_metadata() {
             .unwrap();
         assert!(path.ends_with(".proto.gzip"));
     }
+
+    assert_eq!(
+        snapshot_metadata.storage_logs_checksums.len(),
+        MIN_CHUNK_COUNT as usize
+    );
+    for (chunk_id, checksum) in snapshot_metadata.storage_logs_checksums.iter().enumerate() {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: snapshot_l1_batch_number,
+            chunk_id: chunk_id as u64,
+        };
+        let chunk: SnapshotStorageLogsChunk = object_store.get(key).await.unwrap();
+        assert_eq!(*checksum, Some(chunk.checksum()));
+    }
 }
 
 #[tokio::test]
@@ -0,0 +1,151 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context as _;
+use clap::Parser;
+use zksync_config::{
+    configs::{chain::NetworkConfig, DatabaseSecrets, ObservabilityConfig},
+    GenesisConfig,
+};
+use zksync_core_leftovers::temp_config_store::read_yaml_repr;
+use zksync_dal::{ConnectionPool, Core};
+use zksync_env_config::FromEnv;
+use zksync_types::{ProtocolUpgrade, H256};
+use zksync_vm_interface::ExecutionResult;
+use zksync_vm_runner::impls::{UpgradeDryRunReport, UpgradeDryRunner};
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Executes a pending protocol upgrade on top of the current state without persisting it",
+    long_about = None
+)]
+struct Cli {
+    /// Path to the hex-encoded upgrade data. Either ABI-encoded `DiamondCutData` (as emitted in the `NewUpgradeCutData`
+    /// event), or calldata of an admin call taking it as the only argument (e.g., `executeUpgrade`).
+    #[arg(long)]
+    calldata_path: PathBuf,
+    /// Path to yaml secrets config. If set, it will be used instead of env vars
+    #[arg(long)]
+    secrets_path: Option<PathBuf>,
+    /// Path to yaml genesis config. If set, it will be used instead of env vars
+    #[arg(long)]
+    genesis_path: Option<PathBuf>,
+}
+
+fn read_upgrade(path: &Path) -> anyhow::Result<ProtocolUpgrade> {
+    let calldata = fs::read_to_string(path)
+        .with_context(|| format!("failed reading upgrade calldata from {path:?}"))?;
+    let calldata = calldata.trim();
+    let calldata = hex::decode(calldata.strip_prefix("0x").unwrap_or(calldata))
+        .context("upgrade calldata is not valid hex")?;
+    // ABI-encoded data always consists of 32-byte words; function calldata is additionally prefixed with a 4-byte selector.
+    let diamond_cut = if calldata.len() % 32 == 4 {
+        &calldata[4..]
+    } else {
+        &calldata
+    };
+    ProtocolUpgrade::try_from_diamond_cut(diamond_cut).context("failed parsing upgrade calldata")
+}
+
+fn format_result(result: &ExecutionResult) -> String {
+    match result {
+        ExecutionResult::Success { .. } => "success".to_owned(),
+        ExecutionResult::Revert { output } => format!("reverted: {output}"),
+        ExecutionResult::Halt { reason } => format!("halted: {reason}"),
+    }
+}
+
+fn format_hash(hash: Option<H256>) -> String {
+    hash.map_or_else(|| "-".to_owned(), |hash| format!("{hash:?}"))
+}
+
+fn print_report(report: &UpgradeDryRunReport) {
+    println!("Upgrade {} -> {}", report.from_version, report.to_version);
+    let (old, new) = (
+        &report.old_base_system_contracts,
+        &report.new_base_system_contracts,
+    );
+    println!("Base system contracts:");
+    println!("  bootloader: {:?} -> {:?}", old.bootloader, new.bootloader);
+    println!(
+        "  default account: {:?} -> {:?}",
+        old.default_aa, new.default_aa
+    );
+    println!(
+        "  EVM emulator: {} -> {}",
+        format_hash(old.evm_emulator),
+        format_hash(new.evm_emulator)
+    );
+
+    if let Some(tx) = &report.upgrade_tx {
+        println!(
+            "Upgrade transaction {:?}: {} (gas used: {})",
+            tx.hash,
+            format_result(&tx.result),
+            tx.gas_used
+        );
+    } else {
+        println!("Upgrade doesn't have an upgrade transaction");
+    }
+    println!("Batch tip: {}", format_result(&report.batch_tip_result));
+
+    println!("Contract code changes ({}):", report.code_changes.len());
+    for change in &report.code_changes {
+        println!(
+            "  {:?}: {} -> {:?}",
+            change.address,
+            format_hash(change.old_hash),
+            change.new_hash
+        );
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opts = Cli::parse();
+    let observability_config =
+        ObservabilityConfig::from_env().context("ObservabilityConfig::from_env()")?;
+    let _observability_guard = observability_config.install()?;
+
+    let database_secrets = match &opts.secrets_path {
+        Some(path) => read_yaml_repr::<zksync_protobuf_config::proto::secrets::Secrets>(path)
+            .context("failed decoding secrets YAML config")?
+            .database
+            .context("Failed to find database config")?,
+        None => DatabaseSecrets::from_env().context("DatabaseSecrets::from_env()")?,
+    };
+    let chain_id = match &opts.genesis_path {
+        Some(path) => {
+            let genesis_config: GenesisConfig =
+                read_yaml_repr::<zksync_protobuf_config::proto::genesis::Genesis>(path)
+                    .context("failed decoding genesis YAML config")?;
+            genesis_config.l2_chain_id
+        }
+        None => {
+            NetworkConfig::from_env()
+                .context("NetworkConfig::from_env()")?
+                .zksync_network_id
+        }
+    };
+
+    let upgrade = read_upgrade(&opts.calldata_path)?;
+    tracing::info!(
+        "Dry-running upgrade to protocol version {}",
+        upgrade.version
+    );
+
+    let pool = ConnectionPool::<Core>::builder(database_secrets.replica_url()?, 2)
+        .build()
+        .await
+        .context("failed to build a connection pool")?;
+    let report = UpgradeDryRunner::new(pool, chain_id)
+        .dry_run(upgrade)
+        .await?;
+    print_report(&report);
+    anyhow::ensure!(report.is_successful(), "upgrade execution failed");
+    Ok(())
+}
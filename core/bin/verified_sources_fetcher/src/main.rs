@@ -51,7 +51,8 @@ async fn main() {
                         .unwrap();
                 file.write_all(content.as_bytes()).unwrap();
             }
-            SourceCodeData::StandardJsonInput(input) => {
+            SourceCodeData::StandardJsonInput(input)
+            | SourceCodeData::VyperStandardJsonInput(input) => {
                 let sources = input.get("sources").unwrap().clone();
                 for (key, val) in sources.as_object().unwrap() {
                     let p = format!("{}/{}", &dir, key);
@@ -1,7 +1,7 @@
 //! This module provides a "builder" for the main node,
 //! as well as an interface to run the node with the specified components.
 
-use std::time::Duration;
+use std::{num::NonZeroU64, time::Duration};
 
 use anyhow::{bail, Context};
 use zksync_config::{
@@ -12,24 +12,31 @@ use zksync_config::{
     ContractsConfig, GenesisConfig,
 };
 use zksync_core_leftovers::Component;
-use zksync_metadata_calculator::MetadataCalculatorConfig;
+use zksync_metadata_calculator::{MerkleTreePruningOptions, MetadataCalculatorConfig};
 use zksync_node_api_server::{
     tx_sender::{TimestampAsserterParams, TxSenderConfig},
-    web3::{state::InternalApiConfig, Namespace},
+    web3::{state::InternalApiConfig, Namespace, ResponseCompression},
 };
 use zksync_node_framework::{
     implementations::layers::{
+        admin_api::AdminApiLayer,
         base_token::{
             base_token_ratio_persister::BaseTokenRatioPersisterLayer,
             base_token_ratio_provider::BaseTokenRatioProviderLayer, ExternalPriceApiLayer,
         },
+        bridge_indexer::BridgeIndexerLayer,
         circuit_breaker_checker::CircuitBreakerCheckerLayer,
         commitment_generator::CommitmentGeneratorLayer,
         consensus::MainNodeConsensusLayer,
         contract_verification_api::ContractVerificationApiLayer,
         da_clients::{
-            avail::AvailWiringLayer, celestia::CelestiaWiringLayer, eigen::EigenWiringLayer,
-            no_da::NoDAClientWiringLayer, object_store::ObjectStorageClientWiringLayer,
+            avail::AvailWiringLayer,
+            celestia::CelestiaWiringLayer,
+            eigen::EigenWiringLayer,
+            no_da::NoDAClientWiringLayer,
+            object_store::{
+                FallbackObjectStorageClientWiringLayer, ObjectStorageClientWiringLayer,
+            },
         },
         da_dispatcher::DataAvailabilityDispatcherLayer,
         eth_sender::{EthTxAggregatorLayer, EthTxManagerLayer},
@@ -40,6 +47,7 @@ use zksync_node_framework::{
         house_keeper::HouseKeeperLayer,
         l1_batch_commitment_mode_validation::L1BatchCommitmentModeValidationLayer,
         l1_gas::L1GasLayer,
+        leader_election::LeaderElectionLayer,
         logs_bloom_backfill::LogsBloomBackfillLayer,
         metadata_calculator::MetadataCalculatorLayer,
         node_storage_init::{
@@ -51,28 +59,36 @@ use zksync_node_framework::{
         postgres::PostgresLayer,
         prometheus_exporter::PrometheusExporterLayer,
         proof_data_handler::ProofDataHandlerLayer,
+        pruning::PruningLayer,
         query_eth_client::QueryEthClientLayer,
         sigint::SigintHandlerLayer,
         state_keeper::{
             main_batch_executor::MainBatchExecutorLayer, mempool_io::MempoolIOLayer,
-            output_handler::OutputHandlerLayer, RocksdbStorageOptions, StateKeeperLayer,
+            output_handler::OutputHandlerLayer, tx_policy::TxPolicyLayer, RocksdbStorageOptions,
+            StateKeeperLayer,
         },
+        storage_analytics::StorageAnalyticsLayer,
         vm_runner::{
             bwip::BasicWitnessInputProducerLayer, playground::VmPlaygroundLayer,
             protective_reads::ProtectiveReadsWriterLayer,
+            protective_reads_backfill::ProtectiveReadsBackfillLayer,
         },
         web3_api::{
-            caches::MempoolCacheLayer,
+            caches::{CallResultCacheLayer, MempoolCacheLayer},
+            rate_limiter::ApiRateLimiterLayer,
+            replica_pools::ReplicaPoolsLayer,
             server::{Web3ServerLayer, Web3ServerOptionalConfig},
             tree_api_client::TreeApiClientLayer,
             tx_sender::{PostgresStorageCachesConfig, TxSenderLayer},
             tx_sink::MasterPoolSinkLayer,
         },
+        withdrawal_finalizer::WithdrawalFinalizerLayer,
     },
     service::{ZkStackService, ZkStackServiceBuilder},
 };
 use zksync_types::{
-    pubdata_da::PubdataSendingMode, settlement::SettlementMode, SHARED_BRIDGE_ETHER_TOKEN_ADDRESS,
+    commitment::L1BatchCommitmentMode, pubdata_da::PubdataSendingMode, settlement::SettlementMode,
+    SHARED_BRIDGE_ETHER_TOKEN_ADDRESS,
 };
 use zksync_vlog::prometheus::PrometheusExporterConfig;
 
@@ -194,10 +210,24 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_tx_policy_layer(mut self) -> anyhow::Result<Self> {
+        // Mempool config may be missing for API-only nodes, in which case no policy is applied.
+        let tx_policy_path = self
+            .configs
+            .mempool_config
+            .as_ref()
+            .and_then(|config| config.tx_policy_path.clone());
+        if let Some(path) = tx_policy_path {
+            self.node.add_layer(TxPolicyLayer::new(path));
+        }
+        Ok(self)
+    }
+
     fn add_object_store_layer(mut self) -> anyhow::Result<Self> {
         let object_store_config = try_load_config!(self.configs.core_object_store);
-        self.node
-            .add_layer(ObjectStoreLayer::new(object_store_config));
+        self.node.add_layer(
+            ObjectStoreLayer::new(object_store_config).with_s3_secrets(self.secrets.s3.clone()),
+        );
         Ok(self)
     }
 
@@ -211,6 +241,8 @@ impl MainNodeBuilder {
     }
 
     fn add_metadata_calculator_layer(mut self, with_tree_api: bool) -> anyhow::Result<Self> {
+        const PRUNING_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
         let merkle_tree_env_config = try_load_config!(self.configs.db_config).merkle_tree;
         let operations_manager_env_config =
             try_load_config!(self.configs.operations_manager_config);
@@ -225,6 +257,17 @@ impl MainNodeBuilder {
             let merkle_tree_api_config = try_load_config!(self.configs.api_config).merkle_tree;
             layer = layer.with_tree_api_config(merkle_tree_api_config);
         }
+        if let Some(retained_versions) = merkle_tree_env_config.pruning_retained_versions {
+            let retained_versions = NonZeroU64::new(retained_versions)
+                .context("`pruning_retained_versions` must be positive")?;
+            layer = layer
+                .with_pruning_config(PRUNING_POLL_INTERVAL)
+                .with_pruning_options(MerkleTreePruningOptions {
+                    retained_versions: Some(retained_versions),
+                    iteration_delay: merkle_tree_env_config.pruning_iteration_delay(),
+                    online_compaction: merkle_tree_env_config.pruning_online_compaction,
+                });
+        }
         self.node.add_layer(layer);
         Ok(self)
     }
@@ -239,7 +282,8 @@ impl MainNodeBuilder {
             self.contracts_config.l2_legacy_shared_bridge_addr,
             sk_config.l2_block_seal_queue_capacity,
         )
-        .with_protective_reads_persistence_enabled(sk_config.protective_reads_persistence_enabled);
+        .with_protective_reads_persistence_enabled(sk_config.protective_reads_persistence_enabled)
+        .with_commitment_mode(self.genesis_config.l1_batch_commit_data_generator_mode);
         let mempool_io_layer = MempoolIOLayer::new(
             self.genesis_config.l2_chain_id,
             sk_config.clone(),
@@ -256,13 +300,17 @@ impl MainNodeBuilder {
             .unwrap_or_default();
         let main_node_batch_executor_builder_layer =
             MainBatchExecutorLayer::new(sk_config.save_call_traces, OPTIONAL_BYTECODE_COMPRESSION)
-                .with_fast_vm_mode(experimental_vm_config.state_keeper_fast_vm_mode);
+                .with_fast_vm_mode(experimental_vm_config.state_keeper_fast_vm_mode)
+                .with_shadow_sampling_ratio(
+                    experimental_vm_config.state_keeper_shadow_sampling_ratio,
+                );
 
         let rocksdb_options = RocksdbStorageOptions {
             block_cache_capacity: db_config
                 .experimental
                 .state_keeper_db_block_cache_capacity(),
             max_open_files: db_config.experimental.state_keeper_db_max_open_files,
+            profile: db_config.experimental.state_keeper_db_profile,
         };
         let state_keeper_layer =
             StateKeeperLayer::new(db_config.state_keeper_db_path, rocksdb_options);
@@ -274,13 +322,17 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
-    fn add_eth_watch_layer(mut self) -> anyhow::Result<Self> {
+    fn add_eth_watch_layer(mut self, with_bridge_indexer: bool) -> anyhow::Result<Self> {
         let eth_config = try_load_config!(self.configs.eth);
-        self.node.add_layer(EthWatchLayer::new(
+        let mut layer = EthWatchLayer::new(
             try_load_config!(eth_config.watcher),
             self.contracts_config.clone(),
             self.genesis_config.l2_chain_id,
-        ));
+        );
+        if with_bridge_indexer {
+            layer = layer.with_bridge_indexer();
+        }
+        self.node.add_layer(layer);
         Ok(self)
     }
 
@@ -321,6 +373,8 @@ impl MainNodeBuilder {
             initial_writes_cache_size: rpc_config.initial_writes_cache_size() as u64,
             latest_values_cache_size: rpc_config.latest_values_cache_size() as u64,
             latest_values_max_block_lag: rpc_config.latest_values_max_block_lag(),
+            historical_views_cache_size: rpc_config.historical_views_cache_size() as u64,
+            historical_views_max_block_lag: rpc_config.historical_views_max_block_lag(),
         };
         let vm_config = self
             .configs
@@ -344,7 +398,9 @@ impl MainNodeBuilder {
             postgres_storage_caches_config,
             rpc_config.vm_concurrency_limit(),
         );
-        let layer = layer.with_vm_mode(vm_config.api_fast_vm_mode);
+        let layer = layer
+            .with_vm_mode(vm_config.api_fast_vm_mode)
+            .with_vm_concurrency_limiter_config((&rpc_config).into());
         self.node.add_layer(layer);
         Ok(self)
     }
@@ -355,6 +411,28 @@ impl MainNodeBuilder {
             rpc_config.mempool_cache_size(),
             rpc_config.mempool_cache_update_interval(),
         ));
+        self.node.add_layer(CallResultCacheLayer::new(
+            rpc_config.call_result_cache_size(),
+            rpc_config.call_result_cache_excluded_methods,
+        ));
+        Ok(self)
+    }
+
+    fn add_api_rate_limiter_layer(mut self) -> anyhow::Result<Self> {
+        let rpc_config = try_load_config!(self.configs.api_config).web3_json_rpc;
+        if let Some(rate_limits) = rpc_config.rate_limits {
+            self.node.add_layer(ApiRateLimiterLayer::new(rate_limits));
+        }
+        Ok(self)
+    }
+
+    fn add_api_replica_pools_layer(mut self) -> anyhow::Result<Self> {
+        let config = try_load_config!(self.configs.postgres_config);
+        let secrets = try_load_config!(self.secrets.database);
+        if !secrets.server_replica_urls.is_empty() {
+            self.node
+                .add_layer(ReplicaPoolsLayer::new(config, secrets.api_replica_urls()?));
+        }
         Ok(self)
     }
 
@@ -383,12 +461,23 @@ impl MainNodeBuilder {
         }
         namespaces.push(Namespace::Snapshots);
 
+        let policy = rpc_config.http_policy();
         let optional_config = Web3ServerOptionalConfig {
             namespaces: Some(namespaces),
             filters_limit: Some(rpc_config.filters_limit()),
             subscriptions_limit: Some(rpc_config.subscriptions_limit()),
-            batch_request_size_limit: Some(rpc_config.max_batch_request_size()),
+            batch_request_size_limit: Some(
+                policy
+                    .max_batch_request_size
+                    .unwrap_or_else(|| rpc_config.max_batch_request_size()),
+            ),
+            request_body_size_limit: Some(policy.max_request_body_size()),
             response_body_size_limit: Some(rpc_config.max_response_body_size()),
+            cors_allowed_origins: policy.cors_allowed_origins,
+            response_compression: ResponseCompression {
+                gzip: policy.gzip_compression,
+                brotli: policy.brotli_compression,
+            },
             with_extended_tracing: rpc_config.extended_api_tracing,
             ..Default::default()
         };
@@ -420,11 +509,26 @@ impl MainNodeBuilder {
         }
         namespaces.push(Namespace::Snapshots);
 
+        let policy = rpc_config.ws_policy();
+        let subscription_limits = rpc_config.subscription_limits();
         let optional_config = Web3ServerOptionalConfig {
             namespaces: Some(namespaces),
             filters_limit: Some(rpc_config.filters_limit()),
             subscriptions_limit: Some(rpc_config.subscriptions_limit()),
-            batch_request_size_limit: Some(rpc_config.max_batch_request_size()),
+            max_subscriptions_per_connection: Some(
+                subscription_limits.max_subscriptions_per_connection(),
+            ),
+            subscription_buffer_capacity: Some(subscription_limits.buffer_capacity()),
+            max_buffered_notifications_per_connection: Some(
+                subscription_limits.max_buffered_notifications_per_connection(),
+            ),
+            slow_subscriber_policy: subscription_limits.slow_subscriber_policy,
+            batch_request_size_limit: Some(
+                policy
+                    .max_batch_request_size
+                    .unwrap_or_else(|| rpc_config.max_batch_request_size()),
+            ),
+            request_body_size_limit: Some(policy.max_request_body_size()),
             response_body_size_limit: Some(rpc_config.max_response_body_size()),
             websocket_requests_per_minute_limit: Some(
                 rpc_config.websocket_requests_per_minute_limit(),
@@ -560,6 +664,19 @@ impl MainNodeBuilder {
 
         let state_keeper_config = try_load_config!(self.configs.state_keeper_config);
         let da_config = try_load_config!(self.configs.da_dispatcher_config);
+        if let Some(fallback_object_store) = da_config.fallback_object_store.clone() {
+            // Inclusion data from the fallback client is passed to L1 as is, which is only safe
+            // if the L1 DA validator doesn't verify it.
+            anyhow::ensure!(
+                self.genesis_config.l1_batch_commit_data_generator_mode
+                    == L1BatchCommitmentMode::Validium,
+                "DA dispatcher fallback object store is only supported for validium chains"
+            );
+            self.node
+                .add_layer(FallbackObjectStorageClientWiringLayer::new(
+                    fallback_object_store,
+                ));
+        }
         self.node.add_layer(DataAvailabilityDispatcherLayer::new(
             state_keeper_config,
             da_config,
@@ -579,6 +696,17 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_vm_runner_protective_reads_backfill_layer(mut self) -> anyhow::Result<Self> {
+        let protective_reads_writer_config =
+            try_load_config!(self.configs.protective_reads_writer_config);
+        self.node.add_layer(ProtectiveReadsBackfillLayer::new(
+            &protective_reads_writer_config,
+            self.genesis_config.l2_chain_id,
+        ));
+
+        Ok(self)
+    }
+
     fn add_external_api_client_layer(mut self) -> anyhow::Result<Self> {
         let config = try_load_config!(self.configs.external_price_api_client_config);
         self.node
@@ -597,6 +725,26 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_pruning_layer(mut self) -> anyhow::Result<Self> {
+        let config = try_load_config!(self.configs.pruning);
+        if !config.enabled {
+            tracing::info!("Pruning is disabled");
+            return Ok(self);
+        }
+
+        let layer = PruningLayer::new(
+            config.removal_delay(),
+            config.chunk_size(),
+            config.data_retention(),
+        )
+        // The main node doesn't run the consistency checker; L1 batches are still required to be executed on L1.
+        .without_consistency_check()
+        .with_maximum_l1_batch_age(config.max_data_age())
+        .with_dry_run(config.dry_run);
+        self.node.add_layer(layer);
+        Ok(self)
+    }
+
     fn add_vm_playground_layer(mut self) -> anyhow::Result<Self> {
         let vm_config = self
             .configs
@@ -636,12 +784,58 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_admin_api_layer(mut self) -> anyhow::Result<Self> {
+        let config = try_load_config!(self.configs.admin_api_config);
+        let secrets = try_load_config!(self.secrets.admin_api);
+        let tee_support = self
+            .configs
+            .proof_data_handler_config
+            .as_ref()
+            .is_some_and(|config| config.tee_config.tee_support);
+        self.node.add_layer(
+            AdminApiLayer::new(config, secrets).with_tee_attestation_policy(tee_support),
+        );
+        Ok(self)
+    }
+
+    /// Adds leader election among main node instances sharing the same database. Must be added before the state keeper
+    /// and eth sender, so that all their writes are fenced by the leader lease.
+    fn add_leader_election_layer(mut self) -> anyhow::Result<Self> {
+        if let Some(config) = self.configs.leader_election_config.clone() {
+            self.node.add_layer(LeaderElectionLayer::new(config));
+        }
+        Ok(self)
+    }
+
     fn add_logs_bloom_backfill_layer(mut self) -> anyhow::Result<Self> {
         self.node.add_layer(LogsBloomBackfillLayer);
 
         Ok(self)
     }
 
+    fn add_storage_analytics_layer(mut self) -> anyhow::Result<Self> {
+        self.node.add_layer(StorageAnalyticsLayer);
+        Ok(self)
+    }
+
+    fn add_withdrawal_finalizer_layer(mut self) -> anyhow::Result<Self> {
+        let config = try_load_config!(self.configs.withdrawal_finalizer_config);
+        self.node.add_layer(WithdrawalFinalizerLayer::new(
+            config,
+            self.contracts_config.clone(),
+            self.wallets.withdrawal_finalizer.clone(),
+            self.genesis_config.l1_chain_id,
+            self.genesis_config.l2_chain_id,
+        ));
+        Ok(self)
+    }
+
+    fn add_bridge_indexer_layer(mut self) -> anyhow::Result<Self> {
+        self.node
+            .add_layer(BridgeIndexerLayer::new(self.contracts_config.clone()));
+        Ok(self)
+    }
+
     /// This layer will make sure that the database is initialized correctly,
     /// e.g. genesis will be performed if it's required.
     ///
@@ -692,12 +886,15 @@ impl MainNodeBuilder {
         // Add preconditions for all the components.
         self = self
             .add_l1_batch_commitment_mode_validation_layer()?
-            .add_storage_initialization_layer(LayerKind::Precondition)?;
+            .add_storage_initialization_layer(LayerKind::Precondition)?
+            .add_leader_election_layer()?;
 
         // Sort the components, so that the components they may depend on each other are added in the correct order.
         components.sort_unstable_by_key(|component| match component {
             // API consumes the resources provided by other layers (multiple ones), so it has to come the last.
             Component::HttpApi | Component::WsApi => 1,
+            // Admin API optionally consumes resources provided by other components (e.g., the state keeper).
+            Component::AdminApi => 2,
             // Default priority.
             _ => 0,
         });
@@ -712,23 +909,30 @@ impl MainNodeBuilder {
                     self = self
                         .add_l1_gas_layer()?
                         .add_storage_initialization_layer(LayerKind::Task)?
+                        .add_tx_policy_layer()?
                         .add_state_keeper_layer()?
                         .add_logs_bloom_backfill_layer()?;
                 }
                 Component::HttpApi => {
                     self = self
                         .add_l1_gas_layer()?
+                        .add_tx_policy_layer()?
                         .add_tx_sender_layer()?
                         .add_tree_api_client_layer()?
                         .add_api_caches_layer()?
+                        .add_api_rate_limiter_layer()?
+                        .add_api_replica_pools_layer()?
                         .add_http_web3_api_layer()?;
                 }
                 Component::WsApi => {
                     self = self
                         .add_l1_gas_layer()?
+                        .add_tx_policy_layer()?
                         .add_tx_sender_layer()?
                         .add_tree_api_client_layer()?
                         .add_api_caches_layer()?
+                        .add_api_rate_limiter_layer()?
+                        .add_api_replica_pools_layer()?
                         .add_ws_web3_api_layer()?;
                 }
                 Component::ContractVerificationApi => {
@@ -746,7 +950,8 @@ impl MainNodeBuilder {
                     // Do nothing, will be handled by the `Tree` component.
                 }
                 Component::EthWatcher => {
-                    self = self.add_eth_watch_layer()?;
+                    let with_bridge_indexer = components.contains(&Component::BridgeIndexer);
+                    self = self.add_eth_watch_layer(with_bridge_indexer)?;
                 }
                 Component::EthTxAggregator => {
                     self = self
@@ -774,6 +979,9 @@ impl MainNodeBuilder {
                 Component::VmRunnerProtectiveReads => {
                     self = self.add_vm_runner_protective_reads_layer()?;
                 }
+                Component::VmRunnerProtectiveReadsBackfill => {
+                    self = self.add_vm_runner_protective_reads_backfill_layer()?;
+                }
                 Component::BaseTokenRatioPersister => {
                     self = self
                         .add_l1_gas_layer()?
@@ -789,6 +997,25 @@ impl MainNodeBuilder {
                 Component::ExternalProofIntegrationApi => {
                     self = self.add_external_proof_integration_api_layer()?;
                 }
+                Component::DbPruner => {
+                    self = self.add_pruning_layer()?;
+                }
+                Component::AdminApi => {
+                    self = self.add_admin_api_layer()?;
+                }
+                Component::StorageAnalytics => {
+                    self = self.add_storage_analytics_layer()?;
+                }
+                Component::WithdrawalFinalizer => {
+                    self = self.add_l1_gas_layer()?.add_withdrawal_finalizer_layer()?;
+                }
+                Component::BridgeIndexer => {
+                    anyhow::ensure!(
+                        components.contains(&Component::EthWatcher),
+                        "Bridge indexer cannot be started without an ETH watcher component"
+                    );
+                    self = self.add_bridge_indexer_layer()?;
+                }
             }
         }
         Ok(self.node.build())
@@ -143,6 +143,17 @@ impl AggregationRound {
         AggregationRound::Scheduler,
     ];
 
+    /// Returns the snake-cased name of the round.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::BasicCircuits => "basic_circuits",
+            Self::LeafAggregation => "leaf_aggregation",
+            Self::NodeAggregation => "node_aggregation",
+            Self::RecursionTip => "recursion_tip",
+            Self::Scheduler => "scheduler",
+        }
+    }
+
     pub fn next(&self) -> Option<AggregationRound> {
         match self {
             AggregationRound::BasicCircuits => Some(AggregationRound::LeafAggregation),
@@ -179,13 +190,7 @@ impl AggregationRound {
 
 impl std::fmt::Display for AggregationRound {
     fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        formatter.write_str(match self {
-            Self::BasicCircuits => "basic_circuits",
-            Self::LeafAggregation => "leaf_aggregation",
-            Self::NodeAggregation => "node_aggregation",
-            Self::RecursionTip => "recursion_tip",
-            Self::Scheduler => "scheduler",
-        })
+        formatter.write_str(self.as_str())
     }
 }
 
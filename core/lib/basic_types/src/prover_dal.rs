@@ -71,6 +71,23 @@ impl JobCountStatistics {
     }
 }
 
+/// Proof dependencies of a recursion tip or scheduler witness generator job waiting for proofs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofDependencies {
+    pub l1_batch_number: L1BatchNumber,
+    /// Number of proofs the job depends on.
+    pub expected: usize,
+    /// Number of dependencies that are already proven.
+    pub proven: usize,
+}
+
+impl ProofDependencies {
+    /// Returns the number of dependencies that are not proven yet.
+    pub fn missing(&self) -> usize {
+        self.expected.saturating_sub(self.proven)
+    }
+}
+
 #[derive(Debug)]
 pub struct StuckJobs {
     pub id: u64,
@@ -453,6 +470,7 @@ pub struct ProofCompressionJobInfo {
     pub processing_started_at: Option<NaiveDateTime>,
     pub time_taken: Option<NaiveTime>,
     pub picked_by: Option<String>,
+    pub heartbeat_at: Option<NaiveDateTime>,
 }
 
 // Used for transferring information about L1 Batches from DAL to public interfaces (currently prover_cli stats).
@@ -26,6 +26,8 @@ pub enum PubdataSendingMode {
 pub struct DataAvailabilityBlob {
     pub l1_batch_number: L1BatchNumber,
     pub blob_id: String,
+    /// ID of the blob dispatched to the fallback DA client, if the redundancy mode is enabled.
+    pub fallback_blob_id: Option<String>,
     pub inclusion_data: Option<Vec<u8>>,
     pub sent_at: DateTime<Utc>,
 }
@@ -0,0 +1,18 @@
+use serde::Deserialize;
+use zksync_basic_types::secrets::APIKey;
+
+/// Configuration for the admin API, an authenticated HTTP server allowing to control a running node
+/// (e.g., pause the state keeper or change log directives) without restarting it.
+///
+/// The bearer token authenticating requests is specified in [`AdminApiSecrets`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AdminApiConfig {
+    /// Port to bind the admin API server to.
+    pub http_port: u16,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdminApiSecrets {
+    /// Bearer token that must be provided in the `Authorization` header of all admin API requests.
+    pub auth_token: APIKey,
+}
@@ -182,6 +182,19 @@ pub struct Web3JsonRpcConfig {
     /// This option can be tweaked down if the API server is running out of memory.
     /// If not set, the VM concurrency limit will be efficiently disabled.
     pub vm_concurrency_limit: Option<usize>,
+    /// Max number of VM instances to be concurrently spawned by the API server for requests from the same client.
+    /// Clients are identified by the API key (provided via the `x-api-key` header) if it is configured in [`Self::rate_limits`],
+    /// or by the IP address otherwise. If not set, requests are only limited by [`Self::vm_concurrency_limit`].
+    pub vm_concurrency_limit_per_client: Option<usize>,
+    /// Max total number of VM instances to be concurrently spawned by the API server for requests from clients
+    /// that cannot be identified. If not set, [`Self::vm_concurrency_limit_per_client`] is used.
+    pub vm_concurrency_limit_anonymous: Option<usize>,
+    /// Relative share of VM instances spawned for interactive requests (`eth_call`, gas estimation etc.)
+    /// if there are queued requests of different kinds. The default value is 4.
+    pub vm_interactive_permit_weight: Option<usize>,
+    /// Relative share of VM instances spawned for heavy requests (call tracing, transaction simulation etc.)
+    /// if there are queued requests of different kinds. The default value is 1.
+    pub vm_heavy_permit_weight: Option<usize>,
     /// Smart contract cache size in MiBs. The default value is 128 MiB.
     pub factory_deps_cache_size_mb: Option<usize>,
     /// Initial writes cache size in MiBs. The default value is 32 MiB.
@@ -193,6 +206,13 @@ pub struct Web3JsonRpcConfig {
     /// lead to increased the cache update latency, i.e., less storage queries being processed by the cache. OTOH, smaller values
     /// can lead to spurious resets when Postgres lags for whatever reason (e.g., when sealing L1 batches).
     pub latest_values_max_block_lag: Option<NonZeroU32>,
+    /// Historical storage views cache size in MiBs. Historical views allow serving storage reads for recent historical
+    /// blocks (e.g., for `eth_call` or `eth_getStorageAt`) using the latest values cache. The default value is 0,
+    /// i.e., historical views are disabled. Has no effect if the latest values cache is disabled.
+    pub historical_views_cache_size_mb: Option<usize>,
+    /// Maximum lag in the number of blocks relative to the latest values cache for which historical storage views
+    /// are materialized. Greater values make historical views more expensive to materialize. The default value is 1,000.
+    pub historical_views_max_block_lag: Option<NonZeroU32>,
     /// Limit for fee history block range.
     pub fee_history_limit: Option<u64>,
     /// Maximum number of requests in a single batch JSON RPC request. Default is 500.
@@ -213,6 +233,12 @@ pub struct Web3JsonRpcConfig {
     pub mempool_cache_update_interval: Option<u64>,
     /// Maximum number of transactions to be stored in the mempool cache. Default is 10000.
     pub mempool_cache_size: Option<usize>,
+    /// Maximum number of `eth_call` / `eth_estimateGas` results to be stored in the call result cache.
+    /// Default is 0 (the cache is disabled).
+    pub call_result_cache_size: Option<usize>,
+    /// RPC methods (e.g., `eth_estimateGas`) excluded from caching by the call result cache.
+    #[serde(default)]
+    pub call_result_cache_excluded_methods: Vec<String>,
     /// List of L2 token addresses that are white-listed to use by paymasters
     /// (additionally to natively bridged tokens).
     #[serde(default)]
@@ -224,6 +250,155 @@ pub struct Web3JsonRpcConfig {
     /// (hundreds or thousands RPS).
     #[serde(default)]
     pub extended_api_tracing: bool,
+    /// Per-client rate limits for both HTTP and WebSocket servers. If not set, requests are not rate-limited
+    /// (apart from [`Self::websocket_requests_per_minute_limit`]).
+    #[serde(default)]
+    pub rate_limits: Option<Web3RateLimitsConfig>,
+    /// Transport-level policies for the HTTP server. If not set, default policies are used.
+    #[serde(default)]
+    pub http_policy: Option<Web3TransportPolicyConfig>,
+    /// Transport-level policies for the WebSocket server. If not set, default policies are used.
+    #[serde(default)]
+    pub ws_policy: Option<Web3TransportPolicyConfig>,
+    /// Limits for WebSocket subscriptions. If not set, default limits are used.
+    #[serde(default)]
+    pub subscription_limits: Option<Web3SubscriptionLimitsConfig>,
+}
+
+/// Limits for subscriptions (`eth_subscribe`, `zks_subscribeBatchEvents`) on the WebSocket server.
+#[derive(Debug, Default, Deserialize, Clone, PartialEq)]
+pub struct Web3SubscriptionLimitsConfig {
+    /// Maximum number of active subscriptions per WebSocket connection. Default is 1024.
+    pub max_subscriptions_per_connection: Option<NonZeroU32>,
+    /// Maximum number of notifications buffered for a single subscription. Default is 1024.
+    pub buffer_capacity: Option<NonZeroUsize>,
+    /// Maximum total number of notifications buffered for all subscriptions of a single WebSocket connection.
+    /// Once this budget is exhausted, subscriptions of the connection are treated as having a full buffer.
+    /// Default is 4096.
+    pub max_buffered_notifications_per_connection: Option<NonZeroUsize>,
+    /// Policy applied to subscriptions that receive notifications faster than the client consumes them.
+    #[serde(default)]
+    pub slow_subscriber_policy: SlowSubscriberPolicy,
+}
+
+impl Web3SubscriptionLimitsConfig {
+    pub fn max_subscriptions_per_connection(&self) -> NonZeroU32 {
+        self.max_subscriptions_per_connection
+            .unwrap_or(NonZeroU32::new(1_024).unwrap())
+    }
+
+    pub fn buffer_capacity(&self) -> NonZeroUsize {
+        self.buffer_capacity
+            .unwrap_or(NonZeroUsize::new(1_024).unwrap())
+    }
+
+    pub fn max_buffered_notifications_per_connection(&self) -> NonZeroUsize {
+        self.max_buffered_notifications_per_connection
+            .unwrap_or(NonZeroUsize::new(4_096).unwrap())
+    }
+}
+
+/// Policy applied to a subscription once its notification buffer is full.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlowSubscriberPolicy {
+    /// Closes the subscription; the client needs to resubscribe and catch up using other methods.
+    #[default]
+    Close,
+    /// Drops the oldest buffered notification to make room for the new one, keeping the subscription open.
+    DropOldest,
+}
+
+/// Transport-level policies for a single Web3 JSON-RPC server (HTTP or WebSocket).
+#[derive(Debug, Default, Deserialize, Clone, PartialEq)]
+pub struct Web3TransportPolicyConfig {
+    /// Origins allowed to make cross-origin requests. If empty, requests from any origin are allowed.
+    /// Only applies to the HTTP server.
+    #[serde(default)]
+    pub cors_allowed_origins: Vec<String>,
+    /// Enables gzip compression of responses for clients accepting it. Only applies to the HTTP server.
+    #[serde(default)]
+    pub gzip_compression: bool,
+    /// Enables brotli compression of responses for clients accepting it. Only applies to the HTTP server.
+    #[serde(default)]
+    pub brotli_compression: bool,
+    /// Maximum request body size in MiBs. Default is 10 MiB.
+    pub max_request_body_size_mb: Option<usize>,
+    /// Maximum number of requests in a single batch JSON RPC request. If not set,
+    /// [`Web3JsonRpcConfig::max_batch_request_size`] is used.
+    pub max_batch_request_size: Option<usize>,
+}
+
+impl Web3TransportPolicyConfig {
+    /// Returns the maximum request body size in bytes.
+    pub fn max_request_body_size(&self) -> usize {
+        self.max_request_body_size_mb.unwrap_or(10) * super::BYTES_IN_MEGABYTE
+    }
+}
+
+/// Per-client rate limits for the Web3 JSON-RPC servers.
+///
+/// A client is identified by its API key (passed in the `x-api-key` header) if the key is listed in [`Self::api_keys`],
+/// or by its IP address otherwise. Since the IP address is taken from the `x-forwarded-for` / `x-real-ip` headers,
+/// per-IP limits only work if the server is behind [`Self::trusted_proxy_hops`] reverse proxies setting these headers.
+/// Clients without an identifiable IP address share a single anonymous quota equal to the per-IP one.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Web3RateLimitsConfig {
+    /// Maximum number of requests to cheap methods per minute for a single IP address.
+    pub requests_per_minute_per_ip: NonZeroU32,
+    /// Maximum number of requests to expensive methods per minute for a single IP address.
+    pub expensive_requests_per_minute_per_ip: NonZeroU32,
+    /// Methods considered expensive. A name ending with `*` matches all methods with the specified prefix.
+    /// If empty, [`Self::DEFAULT_EXPENSIVE_METHODS`] are used.
+    #[serde(default)]
+    pub expensive_methods: Vec<String>,
+    /// Quotas for API keys. Requests with a known API key are not subject to per-IP limits.
+    #[serde(default)]
+    pub api_keys: Vec<ApiKeyQuota>,
+    /// Number of trusted reverse proxies in front of the server, each of which appends the address of its peer
+    /// to `x-forwarded-for`. The client IP address is taken as the entry at this position from the right;
+    /// entries to the left of it may be forged by the client. If set to 0, proxy headers are ignored
+    /// and all clients without a known API key share the anonymous quota. Default is 1.
+    #[serde(default = "Web3RateLimitsConfig::default_trusted_proxy_hops")]
+    pub trusted_proxy_hops: usize,
+}
+
+impl Web3RateLimitsConfig {
+    pub const DEFAULT_EXPENSIVE_METHODS: &'static [&'static str] = &[
+        "eth_call",
+        "eth_estimateGas",
+        "eth_getLogs",
+        "debug_trace*",
+        "zks_estimateFee",
+        "zks_estimateGasL1ToL2",
+        "zks_getProof",
+        "unstable_gasProfile",
+    ];
+
+    const fn default_trusted_proxy_hops() -> usize {
+        1
+    }
+
+    pub fn expensive_methods(&self) -> Vec<String> {
+        if self.expensive_methods.is_empty() {
+            Self::DEFAULT_EXPENSIVE_METHODS
+                .iter()
+                .map(|&method| method.to_owned())
+                .collect()
+        } else {
+            self.expensive_methods.clone()
+        }
+    }
+}
+
+/// Rate limits for a single API key.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ApiKeyQuota {
+    pub key: String,
+    /// Maximum number of requests to cheap methods per minute.
+    pub requests_per_minute: NonZeroU32,
+    /// Maximum number of requests to expensive methods per minute.
+    pub expensive_requests_per_minute: NonZeroU32,
 }
 
 impl Web3JsonRpcConfig {
@@ -249,10 +424,16 @@ impl Web3JsonRpcConfig {
             max_tx_size: 1000000,
             vm_execution_cache_misses_limit: None,
             vm_concurrency_limit: None,
+            vm_concurrency_limit_per_client: None,
+            vm_concurrency_limit_anonymous: None,
+            vm_interactive_permit_weight: None,
+            vm_heavy_permit_weight: None,
             factory_deps_cache_size_mb: None,
             initial_writes_cache_size_mb: None,
             latest_values_cache_size_mb: None,
             latest_values_max_block_lag: None,
+            historical_views_cache_size_mb: None,
+            historical_views_max_block_lag: None,
             fee_history_limit: None,
             max_batch_request_size: None,
             max_response_body_size_mb: None,
@@ -260,10 +441,16 @@ impl Web3JsonRpcConfig {
             websocket_requests_per_minute_limit: None,
             mempool_cache_update_interval: None,
             mempool_cache_size: None,
+            call_result_cache_size: None,
+            call_result_cache_excluded_methods: vec![],
             tree_api_url: None,
             whitelisted_tokens_for_aa: vec![],
             api_namespaces: None,
             extended_api_tracing: false,
+            rate_limits: None,
+            http_policy: None,
+            ws_policy: None,
+            subscription_limits: None,
         }
     }
 
@@ -318,6 +505,17 @@ impl Web3JsonRpcConfig {
         self.latest_values_max_block_lag.map_or(20, NonZeroU32::get)
     }
 
+    /// Returns the size of historical storage views cache in bytes.
+    pub fn historical_views_cache_size(&self) -> usize {
+        self.historical_views_cache_size_mb.unwrap_or(0) * super::BYTES_IN_MEGABYTE
+    }
+
+    /// Returns the maximum lag in the number of blocks for historical storage views.
+    pub fn historical_views_max_block_lag(&self) -> u32 {
+        self.historical_views_max_block_lag
+            .map_or(1_000, NonZeroU32::get)
+    }
+
     pub fn fee_history_limit(&self) -> u64 {
         self.fee_history_limit.unwrap_or(1024)
     }
@@ -327,6 +525,21 @@ impl Web3JsonRpcConfig {
         self.max_batch_request_size.unwrap_or(500)
     }
 
+    /// Returns transport policies for the HTTP server.
+    pub fn http_policy(&self) -> Web3TransportPolicyConfig {
+        self.http_policy.clone().unwrap_or_default()
+    }
+
+    /// Returns transport policies for the WebSocket server.
+    pub fn ws_policy(&self) -> Web3TransportPolicyConfig {
+        self.ws_policy.clone().unwrap_or_default()
+    }
+
+    /// Returns limits for WebSocket subscriptions.
+    pub fn subscription_limits(&self) -> Web3SubscriptionLimitsConfig {
+        self.subscription_limits.clone().unwrap_or_default()
+    }
+
     pub fn max_response_body_size(&self) -> MaxResponseSize {
         let scale = NonZeroUsize::new(super::BYTES_IN_MEGABYTE).unwrap();
         MaxResponseSize {
@@ -352,6 +565,10 @@ impl Web3JsonRpcConfig {
     pub fn mempool_cache_size(&self) -> usize {
         self.mempool_cache_size.unwrap_or(10_000)
     }
+
+    pub fn call_result_cache_size(&self) -> usize {
+        self.call_result_cache_size.unwrap_or(0)
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -115,6 +115,15 @@ pub struct StateKeeperConfig {
 
     /// The version of the fee model to use.
     pub fee_model_version: FeeModelVersion,
+    /// L1 gas prices (in wei) at the points of the custom pubdata pricing curve. Must be strictly increasing.
+    /// If empty (the default), pubdata is priced based on the L1 pubdata price estimate.
+    /// Only applies to the V2 fee model.
+    #[serde(default)]
+    pub pubdata_price_curve_l1_gas_prices: Vec<u64>,
+    /// Pubdata prices (in wei per byte) at the points of the custom pubdata pricing curve. Must have the same length
+    /// as `pubdata_price_curve_l1_gas_prices`. The price is linearly interpolated between the points.
+    #[serde(default)]
+    pub pubdata_price_curve_pubdata_prices: Vec<u64>,
 
     /// Max number of computational gas that validation step is allowed to take.
     pub validation_computational_gas_limit: u32,
@@ -124,6 +133,12 @@ pub struct StateKeeperConfig {
     /// Note, that this number corresponds to the "base layer" circuits, i.e. it does not include
     /// the recursion layers' circuits.
     pub max_circuits_per_batch: usize,
+    /// Whether to seal the batch in advance if the next transaction is expected to exceed `max_circuits_per_batch`.
+    /// The expected circuit usage of the next transaction is estimated based on the transactions already executed
+    /// in the batch. This reduces the number of transactions rolled back and re-executed in the next batch because
+    /// of the circuits limit, at the cost of potentially sealing batches earlier than necessary.
+    #[serde(default)]
+    pub seal_on_predicted_circuits: bool,
 
     /// Configures whether to persist protective reads when persisting L1 batches in the state keeper.
     /// Protective reads can be written asynchronously in VM runner instead.
@@ -132,6 +147,21 @@ pub struct StateKeeperConfig {
     #[serde(default)]
     pub protective_reads_persistence_enabled: bool,
 
+    /// Max number of sealed L1 batches not proven on L1. If exceeded, the proof pipeline is considered congested:
+    /// the state keeper slows down L1 batch production, and the API bumps the L2 gas price.
+    /// If not set, the number of unproven batches is not limited.
+    #[serde(default)]
+    pub congestion_max_unproven_l1_batches: Option<u32>,
+    /// Max number of sealed L1 batches not executed on L1. Works similarly to `congestion_max_unproven_l1_batches`.
+    #[serde(default)]
+    pub congestion_max_unexecuted_l1_batches: Option<u32>,
+    /// Minimum interval between opening L1 batches while the pipeline is congested. Default: 60 seconds.
+    #[serde(default)]
+    pub congestion_min_l1_batch_interval_ms: Option<u64>,
+    /// Multiplier applied to the L2 gas price returned by the API while the pipeline is congested. Default: 1.5.
+    #[serde(default)]
+    pub congestion_gas_price_multiplier: Option<f64>,
+
     // Base system contract hashes, required only for generating genesis config.
     // #PLA-811
     #[deprecated(note = "Use GenesisConfig::bootloader_hash instead")]
@@ -174,10 +204,17 @@ impl StateKeeperConfig {
             max_pubdata_per_batch: 100_000,
             minimal_l2_gas_price: 100000000,
             fee_model_version: FeeModelVersion::V2,
+            pubdata_price_curve_l1_gas_prices: vec![],
+            pubdata_price_curve_pubdata_prices: vec![],
             validation_computational_gas_limit: 300000,
             save_call_traces: true,
             max_circuits_per_batch: 24100,
+            seal_on_predicted_circuits: false,
             protective_reads_persistence_enabled: true,
+            congestion_max_unproven_l1_batches: None,
+            congestion_max_unexecuted_l1_batches: None,
+            congestion_min_l1_batch_interval_ms: None,
+            congestion_gas_price_multiplier: None,
             bootloader_hash: None,
             default_aa_hash: None,
             evm_emulator_hash: None,
@@ -229,6 +266,9 @@ pub struct MempoolConfig {
     pub stuck_tx_timeout: u64,
     pub remove_stuck_txs: bool,
     pub delay_interval: u64,
+    /// Path to the JSON file with the transaction policy (sender / contract / selector deny and allow lists).
+    /// The file is periodically reloaded, so the policy can be changed without restarting the node.
+    pub tx_policy_path: Option<String>,
 }
 
 impl MempoolConfig {
@@ -1,4 +1,7 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    num::NonZeroU64,
+};
 
 use secrecy::ExposeSecret as _;
 pub use secrecy::Secret;
@@ -92,6 +95,10 @@ pub struct GenesisSpec {
     pub leader: ValidatorPublicKey,
     /// Address of the registry contract.
     pub registry_address: Option<ethabi::Address>,
+    /// Length of an attester committee epoch in L1 batches. The attester committee is read from the registry contract
+    /// at the first batch of an epoch and is used for all batches of the epoch, so that committee changes only
+    /// take effect at epoch boundaries. If not set, the committee is read for each batch.
+    pub attester_committee_epoch: Option<NonZeroU64>,
     /// Recommended list of peers to connect to.
     pub seed_peers: BTreeMap<NodePublicKey, Host>,
 }
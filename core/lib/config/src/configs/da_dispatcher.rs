@@ -2,6 +2,8 @@ use std::time::Duration;
 
 use serde::Deserialize;
 
+use crate::ObjectStoreConfig;
+
 pub const DEFAULT_POLLING_INTERVAL_MS: u32 = 5000;
 pub const DEFAULT_MAX_ROWS_TO_DISPATCH: u32 = 100;
 pub const DEFAULT_MAX_RETRIES: u16 = 5;
@@ -19,6 +21,13 @@ pub struct DADispatcherConfig {
     // TODO: run a verification task to check if the L1 contract expects the inclusion proofs to
     // avoid the scenario where contracts expect real proofs, and server is using dummy proofs.
     pub use_dummy_inclusion_data: Option<bool>,
+    /// Object store to which pubdata is additionally dispatched (redundancy / dual-write mode).
+    /// If not set, pubdata is only dispatched to the primary DA client.
+    pub fallback_object_store: Option<ObjectStoreConfig>,
+    /// Maximum time to wait for the inclusion data from the primary DA client after the blob was dispatched.
+    /// Once it elapses, inclusion data is taken from the fallback DA client. Only has effect
+    /// if `fallback_object_store` is set; if not specified, the dispatcher never fails over.
+    pub inclusion_sla_ms: Option<u64>,
 }
 
 impl DADispatcherConfig {
@@ -28,6 +37,8 @@ impl DADispatcherConfig {
             max_rows_to_dispatch: Some(DEFAULT_MAX_ROWS_TO_DISPATCH),
             max_retries: Some(DEFAULT_MAX_RETRIES),
             use_dummy_inclusion_data: Some(DEFAULT_USE_DUMMY_INCLUSION_DATA),
+            fallback_object_store: None,
+            inclusion_sla_ms: None,
         }
     }
 
@@ -51,4 +62,8 @@ impl DADispatcherConfig {
         self.use_dummy_inclusion_data
             .unwrap_or(DEFAULT_USE_DUMMY_INCLUSION_DATA)
     }
+
+    pub fn inclusion_sla(&self) -> Option<Duration> {
+        self.inclusion_sla_ms.map(Duration::from_millis)
+    }
 }
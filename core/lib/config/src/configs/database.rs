@@ -20,6 +20,19 @@ pub enum MerkleTreeMode {
     Lightweight,
 }
 
+/// Named RocksDB tuning profile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RocksdbProfile {
+    /// Default options suitable for mixed workloads.
+    #[default]
+    Balanced,
+    /// Options optimized for write-heavy workloads, e.g. Merkle tree recovery or catching up with the chain.
+    WriteHeavy,
+    /// Options optimized for read-heavy workloads, e.g. API nodes.
+    ReadHeavy,
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct MerkleTreeConfig {
     /// Path to the RocksDB data directory for Merkle tree.
@@ -46,6 +59,21 @@ pub struct MerkleTreeConfig {
     /// Maximum number of L1 batches to be processed by the Merkle tree at a time.
     #[serde(default = "MerkleTreeConfig::default_max_l1_batches_per_iter")]
     pub max_l1_batches_per_iter: usize,
+    /// Number of latest Merkle tree versions (= L1 batches) to retain when pruning the tree. If not specified,
+    /// the tree is pruned based on the data retained in Postgres only.
+    #[serde(default)]
+    pub pruning_retained_versions: Option<u64>,
+    /// Delay between consecutive Merkle tree pruning iterations in milliseconds. Can be used to throttle
+    /// I/O produced by pruning. The default value is 0 (no throttling).
+    #[serde(default)]
+    pub pruning_iteration_delay_ms: u64,
+    /// Whether to compact the pruned part of the Merkle tree RocksDB once pruning catches up, reclaiming
+    /// the space occupied by pruned nodes. Enabled by default.
+    #[serde(default = "MerkleTreeConfig::default_pruning_online_compaction")]
+    pub pruning_online_compaction: bool,
+    /// RocksDB tuning profile for the Merkle tree. If not specified, the balanced profile will be used.
+    #[serde(default)]
+    pub rocksdb_profile: RocksdbProfile,
 }
 
 impl Default for MerkleTreeConfig {
@@ -58,6 +86,10 @@ impl Default for MerkleTreeConfig {
             memtable_capacity_mb: Self::default_memtable_capacity_mb(),
             stalled_writes_timeout_sec: Self::default_stalled_writes_timeout_sec(),
             max_l1_batches_per_iter: Self::default_max_l1_batches_per_iter(),
+            pruning_retained_versions: None,
+            pruning_iteration_delay_ms: 0,
+            pruning_online_compaction: Self::default_pruning_online_compaction(),
+            rocksdb_profile: RocksdbProfile::default(),
         }
     }
 }
@@ -87,6 +119,10 @@ impl MerkleTreeConfig {
         20
     }
 
+    pub const fn default_pruning_online_compaction() -> bool {
+        true
+    }
+
     /// Returns the size of block cache size for Merkle tree in bytes.
     pub fn block_cache_size(&self) -> usize {
         self.block_cache_size_mb * super::BYTES_IN_MEGABYTE
@@ -101,6 +137,11 @@ impl MerkleTreeConfig {
     pub fn stalled_writes_timeout(&self) -> Duration {
         Duration::from_secs(self.stalled_writes_timeout_sec)
     }
+
+    /// Returns the delay between consecutive Merkle tree pruning iterations.
+    pub fn pruning_iteration_delay(&self) -> Duration {
+        Duration::from_millis(self.pruning_iteration_delay_ms)
+    }
 }
 
 /// Database configuration.
@@ -145,6 +186,9 @@ pub struct PostgresConfig {
     pub long_connection_threshold_ms: Option<u64>,
     /// Threshold in milliseconds to denote a DB query as "slow" and log its details.
     pub slow_query_threshold_ms: Option<u64>,
+    /// Threshold in milliseconds for a DB connection being held to report it as a possible leak, together with
+    /// the backtrace of the code that has acquired it. If not set, leak detection is disabled.
+    pub leak_detection_threshold_ms: Option<u64>,
     pub test_server_url: Option<String>,
     pub test_prover_url: Option<String>,
 }
@@ -176,4 +220,8 @@ impl PostgresConfig {
     pub fn slow_query_threshold(&self) -> Option<Duration> {
         self.slow_query_threshold_ms.map(Duration::from_millis)
     }
+
+    pub fn leak_detection_threshold(&self) -> Option<Duration> {
+        self.leak_detection_threshold_ms.map(Duration::from_millis)
+    }
 }
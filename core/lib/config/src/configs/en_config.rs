@@ -19,4 +19,23 @@ pub struct ENConfig {
     pub main_node_rate_limit_rps: Option<NonZeroUsize>,
 
     pub bridge_addresses_refresh_interval_sec: Option<NonZeroU64>,
+
+    /// Policy applied by the tree data fetcher if a root hash fetched from the main node differs from the root hash
+    /// committed on L1.
+    #[serde(default)]
+    pub tree_data_fetcher_root_hash_mismatch_policy: RootHashMismatchPolicy,
+}
+
+/// Policy applied by the tree data fetcher if a root hash fetched from the main node differs from the root hash
+/// committed on L1.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RootHashMismatchPolicy {
+    /// Persist root hashes fetched from the main node immediately and verify them against L1 afterwards.
+    /// A mismatch is logged, and the fetcher is marked as affected in its health check.
+    Log,
+    /// Only persist root hashes that are verified against L1, waiting for the corresponding L1 batches to be committed
+    /// if necessary. A mismatch terminates the fetcher with an error (which will lead to the node shutting down).
+    #[default]
+    Halt,
 }
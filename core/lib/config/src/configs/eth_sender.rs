@@ -57,10 +57,17 @@ impl EthConfig {
                 internal_pubdata_pricing_multiplier: 1.0,
                 max_blob_base_fee: None,
                 settlement_mode: Default::default(),
+                fee_smoothing_strategy: FeeSmoothingStrategy::Median,
+                fee_smoothing_ema_alpha: GasAdjusterConfig::default_fee_smoothing_ema_alpha(),
+                fee_smoothing_percentile: GasAdjusterConfig::default_fee_smoothing_percentile(),
+                max_fee_change_per_update: None,
+                blob_base_fee_prediction_blocks: None,
             }),
             watcher: Some(EthWatchConfig {
                 confirmations_for_eth_event: None,
                 eth_node_poll_interval: 0,
+                custom_events: vec![],
+                bridge_events_start_block: None,
             }),
         }
     }
@@ -205,6 +212,33 @@ pub struct GasAdjusterConfig {
     /// It offers a runtime check for correctly provided values.
     #[serde(default)]
     pub settlement_mode: SettlementMode,
+    /// Strategy used to smooth fee samples before they are used to price L2 transactions.
+    #[serde(default)]
+    pub fee_smoothing_strategy: FeeSmoothingStrategy,
+    /// Weight of the newest sample for [`FeeSmoothingStrategy::Ema`]. Must be in `(0, 1]`.
+    #[serde(default = "GasAdjusterConfig::default_fee_smoothing_ema_alpha")]
+    pub fee_smoothing_ema_alpha: f64,
+    /// Percentile of samples used by [`FeeSmoothingStrategy::Percentile`]. Must be in `[0, 100]`.
+    #[serde(default = "GasAdjusterConfig::default_fee_smoothing_percentile")]
+    pub fee_smoothing_percentile: f64,
+    /// If set, bounds the relative change of smoothed fees per gas adjuster update. E.g., 0.1 means that a smoothed fee
+    /// can change by at most 10% per update.
+    pub max_fee_change_per_update: Option<f64>,
+    /// If set, the blob base fee used for pricing is no less than the value predicted this number of L1 blocks ahead
+    /// by extrapolating the recent trend.
+    pub blob_base_fee_prediction_blocks: Option<u64>,
+}
+
+/// Strategy of smoothing L1 fee samples in the gas adjuster.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum FeeSmoothingStrategy {
+    /// Median of the samples.
+    #[default]
+    Median,
+    /// Exponential moving average of the samples.
+    Ema,
+    /// Configurable percentile of the samples.
+    Percentile,
 }
 
 impl GasAdjusterConfig {
@@ -236,4 +270,12 @@ impl GasAdjusterConfig {
     pub const fn default_pricing_formula_parameter_b() -> f64 {
         1.001
     }
+
+    pub const fn default_fee_smoothing_ema_alpha() -> f64 {
+        0.2
+    }
+
+    pub const fn default_fee_smoothing_percentile() -> f64 {
+        50.0
+    }
 }
@@ -1,16 +1,37 @@
 use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
+use zksync_basic_types::Address;
 
 /// Configuration for the Ethereum watch crate.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct EthWatchConfig {
     /// Amount of confirmations for the priority operation to be processed.
-    /// If not specified operation will be processed once its block is finalized.
+    /// If not specified operation will be processed once its block is finalized. Otherwise, reorgs of processed
+    /// non-finalized blocks are detected and rolled back.
     pub confirmations_for_eth_event: Option<u64>,
     /// How often we want to poll the Ethereum node.
     /// Value in milliseconds.
     pub eth_node_poll_interval: u64,
+    /// Additional L1 events to watch, e.g. ones emitted by a chain-specific governance executor.
+    #[serde(default)]
+    pub custom_events: Vec<EthWatchCustomEvent>,
+    /// L1 block to start indexing bridge events from, usually the deployment block of the L1 shared bridge.
+    /// Required if the bridge indexer is enabled.
+    #[serde(default)]
+    pub bridge_events_start_block: Option<u64>,
+}
+
+/// Custom L1 event watched by the Ethereum watcher.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct EthWatchCustomEvent {
+    /// Address of the L1 contract emitting the event.
+    pub contract_address: Address,
+    /// Event signature, e.g. `Paused(address)`. Used to compute the event topic.
+    pub event_signature: String,
+    /// Name of the handler processing the event. Handlers are registered by the node; the `log` handler
+    /// is always available.
+    pub handler: String,
 }
 
 impl EthWatchConfig {
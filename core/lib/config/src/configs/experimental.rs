@@ -5,6 +5,8 @@ use std::num::NonZeroU32;
 use serde::Deserialize;
 use zksync_basic_types::{vm::FastVmMode, L1BatchNumber};
 
+use crate::configs::database::RocksdbProfile;
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct ExperimentalDBConfig {
     /// Block cache capacity of the state keeper RocksDB cache. The default value is 128 MB.
@@ -13,6 +15,9 @@ pub struct ExperimentalDBConfig {
     /// Maximum number of files concurrently opened by state keeper cache RocksDB. Useful to fit into OS limits; can be used
     /// as a rudimentary way to control RAM usage of the cache.
     pub state_keeper_db_max_open_files: Option<NonZeroU32>,
+    /// RocksDB tuning profile for the state keeper cache. If not specified, the balanced profile will be used.
+    #[serde(default)]
+    pub state_keeper_db_profile: RocksdbProfile,
     /// Configures whether to persist protective reads when persisting L1 batches in the state keeper.
     /// Protective reads are never required by full nodes so far, not until such a node runs a full Merkle tree
     /// (presumably, to participate in L1 batch proving).
@@ -40,6 +45,7 @@ impl Default for ExperimentalDBConfig {
             state_keeper_db_block_cache_capacity_mb:
                 Self::default_state_keeper_db_block_cache_capacity_mb(),
             state_keeper_db_max_open_files: None,
+            state_keeper_db_profile: RocksdbProfile::default(),
             protective_reads_persistence_enabled: false,
             processing_delay_ms: Self::default_merkle_tree_processing_delay_ms(),
             include_indices_and_filters_in_block_cache: false,
@@ -115,4 +121,10 @@ pub struct ExperimentalVmConfig {
     /// or transaction validation), so the legacy VM will always be used for them.
     #[serde(default)]
     pub api_fast_vm_mode: FastVmMode,
+
+    /// Fraction of L1 batches (in `[0, 1]`) executed by the state keeper in the shadow mode if `state_keeper_fast_vm_mode`
+    /// is `old`. In sampled batches, the legacy VM output is used, and divergences with the fast VM are persisted
+    /// to Postgres and reported via metrics instead of panicking.
+    #[serde(default)]
+    pub state_keeper_shadow_sampling_ratio: f64,
 }
@@ -1,11 +1,16 @@
 use std::time::Duration;
 
 use serde::Deserialize;
+use zksync_basic_types::Address;
 
 pub const DEFAULT_TIMEOUT_MS: u64 = 10_000;
 
 pub const DEFAULT_FORCED_NEXT_VALUE_FLUCTUATION: u32 = 3;
 
+pub const DEFAULT_AGGREGATED_MAX_STALENESS_MS: u64 = 3_600_000;
+
+pub const DEFAULT_AGGREGATED_MIN_SOURCES: u32 = 1;
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct ForcedPriceClientConfig {
     /// Forced conversion ratio
@@ -21,6 +26,37 @@ pub struct ForcedPriceClientConfig {
     pub next_value_fluctuation: u32,
 }
 
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ChainlinkPriceClientConfig {
+    /// L1 JSON-RPC URL used to query the price feed.
+    pub rpc_url: Option<String>,
+    /// Address of the Chainlink price feed (`AggregatorV3Interface`) on L1 quoting the base token in ETH.
+    pub feed_address: Option<Address>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AggregatedPriceClientConfig {
+    /// Sources to aggregate quotes from, e.g. `["coingecko", "chainlink"]`. The median of fresh quotes is used.
+    pub sources: Vec<String>,
+    /// Quotes older than this are considered stale and are ignored. If not set, a default of 1 hour is used.
+    pub max_staleness_ms: Option<u64>,
+    /// Minimum number of fresh quotes required to produce a ratio. If not set, a single quote is enough.
+    pub min_sources: Option<u32>,
+}
+
+impl AggregatedPriceClientConfig {
+    pub fn max_staleness(&self) -> Duration {
+        Duration::from_millis(
+            self.max_staleness_ms
+                .unwrap_or(DEFAULT_AGGREGATED_MAX_STALENESS_MS),
+        )
+    }
+
+    pub fn min_sources(&self) -> u32 {
+        self.min_sources.unwrap_or(DEFAULT_AGGREGATED_MIN_SOURCES)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct ExternalPriceApiClientConfig {
     pub source: String,
@@ -29,6 +65,8 @@ pub struct ExternalPriceApiClientConfig {
     #[serde(default = "ExternalPriceApiClientConfig::default_timeout")]
     pub client_timeout_ms: u64,
     pub forced: Option<ForcedPriceClientConfig>,
+    pub chainlink: Option<ChainlinkPriceClientConfig>,
+    pub aggregated: Option<AggregatedPriceClientConfig>,
 }
 
 impl ExternalPriceApiClientConfig {
@@ -25,10 +25,26 @@ pub struct FriProofCompressorConfig {
 
     // Whether to verify wrapper proof or not.
     pub verify_wrapper_proof: bool,
+
+    /// Interval at which a compressor instance reports that it is still working on the picked job.
+    #[serde(default)]
+    pub heartbeat_interval_in_secs: Option<u16>,
+    /// Time without heartbeats after which an in-progress job is considered abandoned
+    /// and can be picked by another compressor instance.
+    #[serde(default)]
+    pub heartbeat_timeout_in_secs: Option<u16>,
 }
 
 impl FriProofCompressorConfig {
     pub fn generation_timeout(&self) -> Duration {
         Duration::from_secs(self.generation_timeout_in_secs as u64)
     }
+
+    pub fn heartbeat_interval(&self) -> Duration {
+        Duration::from_secs(self.heartbeat_interval_in_secs.unwrap_or(10) as u64)
+    }
+
+    pub fn heartbeat_timeout(&self) -> Duration {
+        Duration::from_secs(self.heartbeat_timeout_in_secs.unwrap_or(60) as u64)
+    }
 }
@@ -44,6 +44,12 @@ pub struct FriProverConfig {
     pub public_object_store: Option<ObjectStoreConfig>,
     #[serde(default)]
     pub cloud_type: CloudConnectionMode,
+    /// Location to download missing setup data from on startup: an `http(s)://` base URL with setup data
+    /// for the current protocol version, or a `gs://<bucket>` URL of a GCS bucket.
+    /// Downloaded files are verified against the setup data manifest embedded into the prover binary.
+    /// If not set, setup data must be present in `setup_data_path`.
+    #[serde(default)]
+    pub setup_data_download_url: Option<String>,
 }
 
 impl FriProverConfig {
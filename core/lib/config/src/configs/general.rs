@@ -10,17 +10,19 @@ use crate::{
         da_dispatcher::DADispatcherConfig,
         fri_prover_group::FriProverGroupConfig,
         house_keeper::HouseKeeperConfig,
+        leader_election::LeaderElectionConfig,
         prover_job_monitor::ProverJobMonitorConfig,
         pruning::PruningConfig,
         snapshot_recovery::SnapshotRecoveryConfig,
         vm_runner::{BasicWitnessInputProducerConfig, ProtectiveReadsWriterConfig},
+        withdrawal_finalizer::WithdrawalFinalizerConfig,
         CommitmentGeneratorConfig, ExperimentalVmConfig, ExternalPriceApiClientConfig,
         FriProofCompressorConfig, FriProverConfig, FriProverGatewayConfig,
         FriWitnessGeneratorConfig, FriWitnessVectorGeneratorConfig, ObservabilityConfig,
         PrometheusConfig, ProofDataHandlerConfig,
     },
-    ApiConfig, ContractVerifierConfig, DBConfig, EthConfig, ExternalProofIntegrationApiConfig,
-    ObjectStoreConfig, PostgresConfig, SnapshotsCreatorConfig,
+    AdminApiConfig, ApiConfig, ContractVerifierConfig, DBConfig, EthConfig,
+    ExternalProofIntegrationApiConfig, ObjectStoreConfig, PostgresConfig, SnapshotsCreatorConfig,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -60,4 +62,7 @@ pub struct GeneralConfig {
     pub experimental_vm_config: Option<ExperimentalVmConfig>,
     pub prover_job_monitor_config: Option<ProverJobMonitorConfig>,
     pub timestamp_asserter_config: Option<TimestampAsserterConfig>,
+    pub admin_api_config: Option<AdminApiConfig>,
+    pub leader_election_config: Option<LeaderElectionConfig>,
+    pub withdrawal_finalizer_config: Option<WithdrawalFinalizerConfig>,
 }
@@ -1,7 +1,24 @@
+use std::time::Duration;
+
 use serde::Deserialize;
 
 /// Configuration for the house keeper.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct HouseKeeperConfig {
     pub l1_batch_metrics_reporting_interval_ms: u64,
+    /// Number of L2 blocks in each partition of the partitioned tables (`events` and `storage_logs`). If set,
+    /// the house keeper will create partitions ahead of the sealed L2 blocks. If not set, partitions are not created
+    /// automatically, and data for new L2 blocks is stored in the default partitions.
+    #[serde(default)]
+    pub table_partition_size: Option<u32>,
+    /// Retention period for `eth_txs_history` entries superseded by confirmed L1 transactions. If set, the house keeper
+    /// will move such entries older than the retention period to the `eth_txs_history_archive` table.
+    #[serde(default)]
+    pub eth_txs_history_retention_secs: Option<u64>,
+}
+
+impl HouseKeeperConfig {
+    pub fn eth_txs_history_retention(&self) -> Option<Duration> {
+        self.eth_txs_history_retention_secs.map(Duration::from_secs)
+    }
 }
@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// By default, the lease is valid for 10 seconds after it was acquired or renewed.
+const DEFAULT_LEASE_TTL_MS: u64 = 10_000;
+
+/// Configuration for the high-availability mode of the main node. In this mode, multiple main node instances
+/// share the same Postgres database, and only the instance holding a lease in the database is active
+/// (i.e., runs the sequencer, ETH sender etc.); other instances wait until the lease is released or expires.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LeaderElectionConfig {
+    /// Name of the lease; must be the same for all instances.
+    pub lease_name: String,
+    /// Human-readable name of this instance. Used as a prefix of the lease holder ID,
+    /// which additionally contains a random suffix generated on each node start.
+    pub instance_name: Option<String>,
+    /// Time-to-live of the lease. The active instance renews the lease several times during this interval;
+    /// if it fails to do so, another instance takes over once the lease expires.
+    #[serde(default = "LeaderElectionConfig::default_lease_ttl_ms")]
+    pub lease_ttl_ms: u64,
+}
+
+impl LeaderElectionConfig {
+    pub const fn default_lease_ttl_ms() -> u64 {
+        DEFAULT_LEASE_TTL_MS
+    }
+
+    pub fn lease_ttl(&self) -> Duration {
+        Duration::from_millis(self.lease_ttl_ms)
+    }
+}
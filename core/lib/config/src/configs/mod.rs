@@ -1,5 +1,6 @@
 // Public re-exports
 pub use self::{
+    admin_api::{AdminApiConfig, AdminApiSecrets},
     api::ApiConfig,
     base_token_adjuster::BaseTokenAdjusterConfig,
     commitment_generator::CommitmentGeneratorConfig,
@@ -20,6 +21,7 @@ pub use self::{
     fri_witness_vector_generator::FriWitnessVectorGeneratorConfig,
     general::GeneralConfig,
     genesis::GenesisConfig,
+    leader_election::LeaderElectionConfig,
     object_store::ObjectStoreConfig,
     observability::{ObservabilityConfig, OpentelemetryConfig},
     proof_data_handler::{ProofDataHandlerConfig, TeeConfig},
@@ -30,8 +32,10 @@ pub use self::{
     snapshots_creator::SnapshotsCreatorConfig,
     utils::PrometheusConfig,
     vm_runner::{BasicWitnessInputProducerConfig, ProtectiveReadsWriterConfig},
+    withdrawal_finalizer::WithdrawalFinalizerConfig,
 };
 
+pub mod admin_api;
 pub mod api;
 pub mod base_token_adjuster;
 pub mod chain;
@@ -57,6 +61,7 @@ pub mod fri_witness_vector_generator;
 mod general;
 pub mod genesis;
 pub mod house_keeper;
+pub mod leader_election;
 pub mod object_store;
 pub mod observability;
 pub mod proof_data_handler;
@@ -68,5 +73,6 @@ pub mod snapshots_creator;
 pub mod utils;
 pub mod vm_runner;
 pub mod wallets;
+pub mod withdrawal_finalizer;
 
 const BYTES_IN_MEGABYTE: usize = 1_024 * 1_024;
@@ -1,4 +1,7 @@
+use std::time::Duration;
+
 use serde::Deserialize;
+use zksync_basic_types::secrets::APIKey;
 
 /// Configuration for the object store
 #[derive(Debug, Clone, PartialEq, Deserialize)]
@@ -16,6 +19,10 @@ pub struct ObjectStoreConfig {
     /// **Important.** Mirroring logic assumes that objects in the underlying store are immutable. If this is not the case,
     /// the mirrored objects may become stale.
     pub local_mirror_path: Option<String>,
+    /// Tiers routing artifacts of specific classes to dedicated stores. Artifacts not covered by any tier
+    /// are stored using [`Self::mode`].
+    #[serde(default)]
+    pub tiers: Vec<ObjectStoreTierConfig>,
 }
 
 impl ObjectStoreConfig {
@@ -24,6 +31,50 @@ impl ObjectStoreConfig {
     }
 }
 
+/// Class of artifacts placed into an object store, used to route artifacts to store tiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactClass {
+    /// Final and intermediate proofs.
+    Proofs,
+    /// Witness inputs and aggregation witness jobs.
+    WitnessInputs,
+    /// Storage snapshots.
+    Snapshots,
+}
+
+/// Object store tier for a single [`ArtifactClass`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ObjectStoreTierConfig {
+    pub artifact_class: ArtifactClass,
+    /// Store used for new artifacts of this class. Must not specify tiers itself.
+    pub store: ObjectStoreConfig,
+    /// Store for artifacts older than [`Self::move_to_cold_after_days`] (e.g., a bucket with a cheaper storage class).
+    pub cold_store: Option<ObjectStoreConfig>,
+    /// Age after which artifacts are moved from the main tier store to the cold store.
+    pub move_to_cold_after_days: Option<u32>,
+    /// Age after which artifacts are removed from both tier stores. If not set, artifacts are retained indefinitely.
+    pub expire_after_days: Option<u32>,
+    /// Minimum size of artifacts moved to the cold store. Smaller artifacts are kept in the main tier store
+    /// until they expire, since cold storage classes usually bill small objects as if they had a minimum size.
+    /// If not set, artifacts of any size are moved.
+    pub min_cold_object_size_bytes: Option<u64>,
+}
+
+impl ObjectStoreTierConfig {
+    pub fn move_to_cold_after(&self) -> Option<Duration> {
+        self.move_to_cold_after_days.map(days_to_duration)
+    }
+
+    pub fn expire_after(&self) -> Option<Duration> {
+        self.expire_after_days.map(days_to_duration)
+    }
+}
+
+fn days_to_duration(days: u32) -> Duration {
+    Duration::from_secs(u64::from(days) * 86_400)
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 #[serde(tag = "mode")]
 pub enum ObjectStoreMode {
@@ -40,4 +91,53 @@ pub enum ObjectStoreMode {
     FileBacked {
         file_backed_base_path: String,
     },
+    /// Store using an S3-compatible API (AWS S3, MinIO, Cloudflare R2, etc.).
+    ///
+    /// Credentials must be provided in [`S3Secrets`]; the store cannot be initialized without them.
+    S3 {
+        /// Endpoint URL, e.g. `https://s3.us-east-1.amazonaws.com` or `http://localhost:9000`.
+        endpoint: String,
+        /// Region used for request signing. Most non-AWS implementations accept `us-east-1` or `auto`.
+        region: String,
+        bucket_name: String,
+        #[serde(default)]
+        addressing_style: S3AddressingStyle,
+        /// Server-side encryption to request for stored objects. If not set, the bucket default is used.
+        server_side_encryption: Option<S3ServerSideEncryption>,
+        /// KMS key ID for [`S3ServerSideEncryption::Kms`]. If not set, the AWS-managed key is used.
+        sse_kms_key_id: Option<String>,
+        /// Objects larger than this size are uploaded using multipart uploads with parts of this size.
+        /// Must be at least 5 MiB (the S3 minimum part size). Defaults to 16 MiB.
+        multipart_part_size_bytes: Option<u64>,
+    },
+}
+
+/// Addressing style for S3-compatible object stores.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum S3AddressingStyle {
+    /// Bucket is a part of the URL path: `https://endpoint/bucket/key`. Supported by most S3-compatible stores.
+    #[default]
+    Path,
+    /// Bucket is a part of the host: `https://bucket.endpoint/key`.
+    VirtualHosted,
+}
+
+/// Server-side encryption modes for S3-compatible object stores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum S3ServerSideEncryption {
+    /// Encryption with keys managed by the store (`AES256`, aka SSE-S3).
+    Aes256,
+    /// Encryption with keys managed by AWS KMS (`aws:kms`, aka SSE-KMS).
+    Kms,
+}
+
+/// Credentials for S3-compatible object stores.
+#[derive(Debug, Clone, PartialEq)]
+pub struct S3Secrets {
+    pub access_key_id: String,
+    pub secret_access_key: APIKey,
+    /// Session token for temporary credentials.
+    pub session_token: Option<APIKey>,
 }
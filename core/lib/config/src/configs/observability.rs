@@ -1,4 +1,5 @@
 use serde::Deserialize;
+use zksync_basic_types::L2ChainId;
 
 /// Configuration for the essential observability stack, like
 /// logging and sentry integration.
@@ -15,6 +16,9 @@ pub struct ObservabilityConfig {
     pub log_format: String,
     /// Log directives in format that is used in `RUST_LOG`
     pub log_directives: Option<String>,
+    /// Whether to add trace / span correlation IDs to JSON logs.
+    #[serde(default)]
+    pub log_correlation_ids: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
@@ -29,4 +33,9 @@ pub struct OpentelemetryConfig {
     /// Important: sending logs via OTLP has only been tested locally, and the performance may be
     /// suboptimal in production environments.
     pub logs_endpoint: Option<String>,
+    /// Ratio of sampled traces in the `[0, 1]` range. If not specified, all traces are sampled.
+    pub sampling_ratio: Option<f64>,
+    /// L2 chain ID used to derive IDs of L1 batch lifecycle traces. Must be set to the same value for all components
+    /// of the chain (including the prover subsystem), so that their spans end up in the same traces.
+    pub l2_chain_id: Option<L2ChainId>,
 }
@@ -15,6 +15,16 @@ pub struct TeeConfig {
     pub tee_proof_generation_timeout_in_secs: u16,
     /// Timeout in hours after which a batch will be permanently ignored if repeated retries failed.
     pub tee_batch_permanently_ignored_timeout_in_hours: u16,
+    /// If true, attestation quotes registered by TEE provers are checked against the attestation policy, i.e.,
+    /// the quote must be signed by a genuine Intel platform with an acceptable TCB status, and the enclave measurement
+    /// in a quote must be in the allowed list stored in Postgres (managed via the admin API). Requires the server
+    /// to be built with DCAP quote verification support.
+    #[serde(default = "TeeConfig::default_tee_attestation_verification")]
+    pub tee_attestation_verification: bool,
+    /// Maximum age of the TCB level of an attested platform, in days. Quotes from platforms whose TCB level
+    /// was issued earlier are rejected, even if their TCB status is otherwise acceptable.
+    #[serde(default = "TeeConfig::default_tee_max_tcb_age_in_days")]
+    pub tee_max_tcb_age_in_days: u32,
 }
 
 impl Default for TeeConfig {
@@ -26,6 +36,8 @@ impl Default for TeeConfig {
                 Self::default_tee_proof_generation_timeout_in_secs(),
             tee_batch_permanently_ignored_timeout_in_hours:
                 Self::default_tee_batch_permanently_ignored_timeout_in_hours(),
+            tee_attestation_verification: Self::default_tee_attestation_verification(),
+            tee_max_tcb_age_in_days: Self::default_tee_max_tcb_age_in_days(),
         }
     }
 }
@@ -47,6 +59,14 @@ impl TeeConfig {
         10 * 24
     }
 
+    pub fn default_tee_attestation_verification() -> bool {
+        false
+    }
+
+    pub fn default_tee_max_tcb_age_in_days() -> u32 {
+        365
+    }
+
     pub fn tee_proof_generation_timeout(&self) -> Duration {
         Duration::from_secs(self.tee_proof_generation_timeout_in_secs.into())
     }
@@ -54,6 +74,10 @@ impl TeeConfig {
     pub fn tee_batch_permanently_ignored_timeout(&self) -> Duration {
         Duration::from_secs(3600 * u64::from(self.tee_batch_permanently_ignored_timeout_in_hours))
     }
+
+    pub fn tee_max_tcb_age(&self) -> Duration {
+        Duration::from_secs(86_400 * u64::from(self.tee_max_tcb_age_in_days))
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -61,6 +61,10 @@ pub struct ProverJobMonitorConfig {
     /// The interval between runs for Witness Job Queuer.
     #[serde(default = "ProverJobMonitorConfig::default_witness_job_queuer_run_interval_ms")]
     pub witness_job_queuer_run_interval_ms: u64,
+    /// Time in which the current prover backlog should be cleared; used to compute the desired number of GPU provers
+    /// in scaling signals.
+    #[serde(default = "ProverJobMonitorConfig::default_scaling_signals_target_backlog_ms")]
+    pub scaling_signals_target_backlog_ms: u64,
     /// HTTP port of the ProverJobMonitor to send requests to.
     pub http_port: u16,
 }
@@ -184,4 +188,14 @@ impl ProverJobMonitorConfig {
     pub fn default_witness_job_queuer_run_interval_ms() -> u64 {
         10_000
     }
+
+    /// Time in which the current prover backlog should be cleared.
+    pub fn scaling_signals_target_backlog(&self) -> Duration {
+        Duration::from_millis(self.scaling_signals_target_backlog_ms)
+    }
+
+    /// Default scaling_signals_target_backlog_ms -- 10 minutes
+    pub fn default_scaling_signals_target_backlog_ms() -> u64 {
+        600_000
+    }
 }
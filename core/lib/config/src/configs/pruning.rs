@@ -1,4 +1,4 @@
-use std::num::NonZeroU64;
+use std::{num::NonZeroU64, time::Duration};
 
 use serde::Deserialize;
 
@@ -16,4 +16,41 @@ pub struct PruningConfig {
     /// the retention period greater than that implicitly imposed by other criteria (e.g., 7 or 30 days).
     /// If set to 0, L1 batches will not be retained based on their timestamp. The default value is 1 hour.
     pub data_retention_sec: Option<u64>,
+    /// If set, L1 batches older than this age (in seconds) will be pruned even if there are less than `chunk_size`
+    /// of them, provided that they satisfy other pruning criteria (e.g., are executed on L1). Should be greater than
+    /// `data_retention_sec` to have any effect.
+    pub max_data_age_sec: Option<NonZeroU64>,
+    /// If set, the pruner doesn't remove any data; instead, it periodically estimates how much data would be removed
+    /// given the current configuration and reports the estimates via logs and metrics. Only respected by the main node.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl PruningConfig {
+    const DEFAULT_CHUNK_SIZE: u32 = 10;
+    const DEFAULT_REMOVAL_DELAY_SEC: u64 = 60;
+    const DEFAULT_DATA_RETENTION_SEC: u64 = 3_600;
+
+    pub fn chunk_size(&self) -> u32 {
+        self.chunk_size.unwrap_or(Self::DEFAULT_CHUNK_SIZE)
+    }
+
+    pub fn removal_delay(&self) -> Duration {
+        Duration::from_secs(
+            self.removal_delay_sec
+                .map_or(Self::DEFAULT_REMOVAL_DELAY_SEC, NonZeroU64::get),
+        )
+    }
+
+    pub fn data_retention(&self) -> Duration {
+        Duration::from_secs(
+            self.data_retention_sec
+                .unwrap_or(Self::DEFAULT_DATA_RETENTION_SEC),
+        )
+    }
+
+    pub fn max_data_age(&self) -> Option<Duration> {
+        self.max_data_age_sec
+            .map(|age| Duration::from_secs(age.get()))
+    }
 }
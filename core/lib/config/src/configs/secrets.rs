@@ -2,8 +2,10 @@ use anyhow::Context;
 use zksync_basic_types::url::SensitiveUrl;
 
 use crate::configs::{
+    admin_api::AdminApiSecrets,
     consensus::ConsensusSecrets,
     da_client::{avail::AvailSecrets, celestia::CelestiaSecrets, eigen::EigenSecrets},
+    object_store::S3Secrets,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -11,6 +13,9 @@ pub struct DatabaseSecrets {
     pub server_url: Option<SensitiveUrl>,
     pub prover_url: Option<SensitiveUrl>,
     pub server_replica_url: Option<SensitiveUrl>,
+    /// Replica databases used by Web3 API servers. If non-empty, read queries of API servers are routed
+    /// to the least-lagged healthy replica from this list instead of the `server_replica_url`.
+    pub server_replica_urls: Vec<SensitiveUrl>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -32,6 +37,8 @@ pub struct Secrets {
     pub database: Option<DatabaseSecrets>,
     pub l1: Option<L1Secrets>,
     pub data_availability: Option<DataAvailabilitySecrets>,
+    pub admin_api: Option<AdminApiSecrets>,
+    pub s3: Option<S3Secrets>,
 }
 
 impl DatabaseSecrets {
@@ -49,6 +56,16 @@ impl DatabaseSecrets {
         }
     }
 
+    /// Returns replica database URLs used by Web3 API servers, falling back to the single replica URL if
+    /// the list is not configured.
+    pub fn api_replica_urls(&self) -> anyhow::Result<Vec<SensitiveUrl>> {
+        if self.server_replica_urls.is_empty() {
+            Ok(vec![self.replica_url()?])
+        } else {
+            Ok(self.server_replica_urls.clone())
+        }
+    }
+
     /// Returns a copy of the prover database URL as a `Result` to simplify error propagation.
     pub fn prover_url(&self) -> anyhow::Result<SensitiveUrl> {
         self.prover_url.clone().context("Prover DB URL is absent")
@@ -1,7 +1,7 @@
 use std::num::NonZeroUsize;
 
 use serde::Deserialize;
-use zksync_basic_types::L1BatchNumber;
+use zksync_basic_types::{url::SensitiveUrl, L1BatchNumber, H256};
 
 use crate::ObjectStoreConfig;
 
@@ -38,6 +38,14 @@ pub struct SnapshotRecoveryConfig {
     pub enabled: bool,
     /// L1 batch number of the snapshot to use during recovery. Specifying this parameter is mostly useful for testing.
     pub l1_batch: Option<L1BatchNumber>,
+    /// Expected manifest hash of the snapshot to recover from, obtained from a trusted source. The manifest hash
+    /// returned by the main node is not signed, so it only protects against accidental corruption; pinning the hash
+    /// additionally protects against a compromised main node or object store.
+    pub manifest_hash: Option<H256>,
+    /// JSON-RPC URL of another external node to recover from instead of the main node. The peer must serve
+    /// the `snapshots` namespace; snapshot data is still read from [`Self::object_store`], which must be shared
+    /// with the peer. Recovery by reconstructing the state from L1 is not supported.
+    pub peer_url: Option<SensitiveUrl>,
     /// Enables dropping storage key preimages when recovering storage logs from a snapshot with version 0.
     /// This is a temporary flag that will eventually be removed together with version 0 snapshot support.
     #[serde(default)]
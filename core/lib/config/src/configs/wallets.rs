@@ -74,11 +74,17 @@ pub struct TokenMultiplierSetter {
     pub wallet: Wallet,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct WithdrawalFinalizer {
+    pub wallet: Wallet,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Wallets {
     pub eth_sender: Option<EthSender>,
     pub state_keeper: Option<StateKeeper>,
     pub token_multiplier_setter: Option<TokenMultiplierSetter>,
+    pub withdrawal_finalizer: Option<WithdrawalFinalizer>,
 }
 
 impl Wallets {
@@ -96,6 +102,9 @@ impl Wallets {
             token_multiplier_setter: Some(TokenMultiplierSetter {
                 wallet: Wallet::from_private_key_bytes(H256::repeat_byte(0x4), None).unwrap(),
             }),
+            withdrawal_finalizer: Some(WithdrawalFinalizer {
+                wallet: Wallet::from_private_key_bytes(H256::repeat_byte(0x5), None).unwrap(),
+            }),
         }
     }
 }
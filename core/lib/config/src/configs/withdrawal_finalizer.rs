@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+use zksync_basic_types::{Address, L1BatchNumber, U256};
+
+/// By default, the finalizer checks for new executed L1 batches every 10 seconds.
+const DEFAULT_POLL_INTERVAL_MS: u64 = 10_000;
+
+/// Default max amount of gas that a single `finalizeWithdrawal` transaction can consume.
+const DEFAULT_MAX_TX_GAS: u64 = 500_000;
+
+/// Default priority fee per gas used to instantiate the signing client.
+const DEFAULT_PRIORITY_FEE_PER_GAS: u64 = 1_000_000_000;
+
+/// By default, a finalization transaction not mined in 10 L1 blocks is resent with bumped fees.
+const DEFAULT_RESEND_AFTER_L1_BLOCKS: u64 = 10;
+
+/// By default, at most 100 finalization transactions are sent per hour.
+const DEFAULT_MAX_FINALIZATIONS_PER_HOUR: u32 = 100;
+
+/// Token allowed to be finalized automatically, together with limits on the withdrawal amount.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct WithdrawalTokenLimit {
+    /// Address of the token on L1. For the base token, this is the base token address from the contracts config.
+    pub l1_token_address: Address,
+    /// Minimum amount of a single withdrawal (in the smallest token units). Smaller withdrawals are not finalized
+    /// automatically, so that the finalizer cannot be drained by dust withdrawals.
+    #[serde(default)]
+    pub min_amount: U256,
+    /// Maximum amount of a single withdrawal (in the smallest token units). Larger withdrawals are not finalized
+    /// automatically and must be finalized by their recipients.
+    pub max_amount: U256,
+}
+
+/// Configuration for the withdrawal finalizer, a component finalizing L2 -> L1 withdrawals on L1
+/// after the L1 batch containing a withdrawal is executed.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct WithdrawalFinalizerConfig {
+    /// Interval between checks for new executed L1 batches and for the status of the sent finalization transaction.
+    #[serde(default = "WithdrawalFinalizerConfig::default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// Max amount of gas that a single `finalizeWithdrawal` transaction can consume.
+    #[serde(default = "WithdrawalFinalizerConfig::default_max_tx_gas")]
+    pub max_tx_gas: u64,
+    /// Default priority fee per gas used to instantiate the signing client.
+    #[serde(default = "WithdrawalFinalizerConfig::default_priority_fee_per_gas")]
+    pub default_priority_fee_per_gas: u64,
+    /// Number of L1 blocks after which a finalization transaction that isn't mined is resent with the same nonce
+    /// and bumped fees.
+    #[serde(default = "WithdrawalFinalizerConfig::default_resend_after_l1_blocks")]
+    pub resend_after_l1_blocks: u64,
+    /// Tokens allowed to be finalized automatically together with per-token limits on the withdrawal amount.
+    /// Withdrawals of tokens not in this list are not finalized.
+    #[serde(default)]
+    pub token_limits: Vec<WithdrawalTokenLimit>,
+    /// Max number of withdrawals with a finalization transaction sent per hour. Caps the L1 fees spent
+    /// by the finalizer. If set to 0, the number of transactions is not limited.
+    #[serde(default = "WithdrawalFinalizerConfig::default_max_finalizations_per_hour")]
+    pub max_finalizations_per_hour: u32,
+    /// First L1 batch to process withdrawals from. If not set, withdrawals are processed starting from
+    /// the earliest L1 batch in the database.
+    #[serde(default)]
+    pub start_l1_batch: Option<L1BatchNumber>,
+}
+
+impl Default for WithdrawalFinalizerConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_ms: Self::default_poll_interval_ms(),
+            max_tx_gas: Self::default_max_tx_gas(),
+            default_priority_fee_per_gas: Self::default_priority_fee_per_gas(),
+            resend_after_l1_blocks: Self::default_resend_after_l1_blocks(),
+            token_limits: vec![],
+            max_finalizations_per_hour: Self::default_max_finalizations_per_hour(),
+            start_l1_batch: None,
+        }
+    }
+}
+
+impl WithdrawalFinalizerConfig {
+    pub const fn default_poll_interval_ms() -> u64 {
+        DEFAULT_POLL_INTERVAL_MS
+    }
+
+    pub const fn default_max_tx_gas() -> u64 {
+        DEFAULT_MAX_TX_GAS
+    }
+
+    pub const fn default_priority_fee_per_gas() -> u64 {
+        DEFAULT_PRIORITY_FEE_PER_GAS
+    }
+
+    pub const fn default_resend_after_l1_blocks() -> u64 {
+        DEFAULT_RESEND_AFTER_L1_BLOCKS
+    }
+
+    pub const fn default_max_finalizations_per_hour() -> u32 {
+        DEFAULT_MAX_FINALIZATIONS_PER_HOUR
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms)
+    }
+}
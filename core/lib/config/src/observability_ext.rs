@@ -27,7 +27,9 @@ impl TryFrom<ObservabilityConfig> for zksync_vlog::Logs {
     type Error = anyhow::Error;
 
     fn try_from(config: ObservabilityConfig) -> Result<Self, Self::Error> {
-        Ok(zksync_vlog::Logs::new(&config.log_format)?.with_log_directives(config.log_directives))
+        Ok(zksync_vlog::Logs::new(&config.log_format)?
+            .with_log_directives(config.log_directives)
+            .with_correlation_ids(config.log_correlation_ids))
     }
 }
 
@@ -50,11 +52,16 @@ impl TryFrom<ObservabilityConfig> for Option<zksync_vlog::OpenTelemetry> {
         Ok(config
             .opentelemetry
             .map(|config| {
-                zksync_vlog::OpenTelemetry::new(
+                let opentelemetry = zksync_vlog::OpenTelemetry::new(
                     &config.level,
                     Some(config.endpoint),
                     config.logs_endpoint,
-                )
+                )?
+                .with_l2_chain_id(config.l2_chain_id.map(|id| id.as_u64()));
+                match config.sampling_ratio {
+                    Some(ratio) => opentelemetry.with_sampling_ratio(ratio),
+                    None => Ok(opentelemetry),
+                }
             })
             .transpose()?)
     }
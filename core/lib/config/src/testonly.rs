@@ -1,4 +1,4 @@
-use std::num::NonZeroUsize;
+use std::num::{NonZeroU32, NonZeroUsize};
 
 use rand::{distributions::Distribution, Rng};
 use secrecy::Secret;
@@ -10,7 +10,7 @@ use zksync_basic_types::{
     pubdata_da::PubdataSendingMode,
     secrets::{APIKey, SeedPhrase},
     vm::FastVmMode,
-    L1BatchNumber, L1ChainId, L2ChainId,
+    L1BatchNumber, L1ChainId, L2ChainId, U256,
 };
 use zksync_consensus_utils::EncodeDist;
 use zksync_crypto_primitives::K256PrivateKey;
@@ -23,7 +23,9 @@ use crate::{
             avail::{AvailClientConfig, AvailDefaultConfig},
             DAClientConfig::Avail,
         },
-        external_price_api_client::ForcedPriceClientConfig,
+        external_price_api_client::{
+            AggregatedPriceClientConfig, ChainlinkPriceClientConfig, ForcedPriceClientConfig,
+        },
     },
     AvailConfig,
 };
@@ -89,10 +91,16 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
             max_tx_size: self.sample(rng),
             vm_execution_cache_misses_limit: self.sample(rng),
             vm_concurrency_limit: self.sample(rng),
+            vm_concurrency_limit_per_client: self.sample(rng),
+            vm_concurrency_limit_anonymous: self.sample(rng),
+            vm_interactive_permit_weight: self.sample(rng),
+            vm_heavy_permit_weight: self.sample(rng),
             factory_deps_cache_size_mb: self.sample(rng),
             initial_writes_cache_size_mb: self.sample(rng),
             latest_values_cache_size_mb: self.sample(rng),
             latest_values_max_block_lag: self.sample(rng),
+            historical_views_cache_size_mb: self.sample(rng),
+            historical_views_max_block_lag: self.sample(rng),
             fee_history_limit: self.sample(rng),
             max_batch_request_size: self.sample(rng),
             max_response_body_size_mb: self.sample(rng),
@@ -112,10 +120,75 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
             tree_api_url: self.sample(rng),
             mempool_cache_update_interval: self.sample(rng),
             mempool_cache_size: self.sample(rng),
+            call_result_cache_size: self.sample(rng),
+            call_result_cache_excluded_methods: self
+                .sample_range(rng)
+                .map(|_| self.sample(rng))
+                .collect(),
             whitelisted_tokens_for_aa: self.sample_range(rng).map(|_| rng.gen()).collect(),
             api_namespaces: self
                 .sample_opt(|| self.sample_range(rng).map(|_| self.sample(rng)).collect()),
             extended_api_tracing: self.sample(rng),
+            rate_limits: self.sample_opt(|| self.sample(rng)),
+            http_policy: self.sample_opt(|| self.sample(rng)),
+            ws_policy: self.sample_opt(|| self.sample(rng)),
+            subscription_limits: self.sample_opt(|| self.sample(rng)),
+        }
+    }
+}
+
+impl Distribution<configs::api::Web3SubscriptionLimitsConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::api::Web3SubscriptionLimitsConfig {
+        configs::api::Web3SubscriptionLimitsConfig {
+            max_subscriptions_per_connection: self.sample(rng),
+            buffer_capacity: self.sample_opt(|| rng.gen()),
+            max_buffered_notifications_per_connection: self.sample_opt(|| rng.gen()),
+            slow_subscriber_policy: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::api::SlowSubscriberPolicy> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::api::SlowSubscriberPolicy {
+        type T = configs::api::SlowSubscriberPolicy;
+        match rng.gen_range(0..2) {
+            0 => T::Close,
+            _ => T::DropOldest,
+        }
+    }
+}
+
+impl Distribution<configs::api::Web3TransportPolicyConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::api::Web3TransportPolicyConfig {
+        configs::api::Web3TransportPolicyConfig {
+            cors_allowed_origins: self.sample_range(rng).map(|_| self.sample(rng)).collect(),
+            gzip_compression: self.sample(rng),
+            brotli_compression: self.sample(rng),
+            max_request_body_size_mb: self.sample(rng),
+            max_batch_request_size: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::api::Web3RateLimitsConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::api::Web3RateLimitsConfig {
+        configs::api::Web3RateLimitsConfig {
+            requests_per_minute_per_ip: NonZeroU32::new(rng.gen_range(1..10_000)).unwrap(),
+            expensive_requests_per_minute_per_ip: NonZeroU32::new(rng.gen_range(1..10_000))
+                .unwrap(),
+            expensive_methods: self.sample_range(rng).map(|_| self.sample(rng)).collect(),
+            api_keys: self.sample_range(rng).map(|_| self.sample(rng)).collect(),
+            trusted_proxy_hops: rng.gen_range(0..4),
+        }
+    }
+}
+
+impl Distribution<configs::api::ApiKeyQuota> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::api::ApiKeyQuota {
+        configs::api::ApiKeyQuota {
+            key: self.sample(rng),
+            requests_per_minute: NonZeroU32::new(rng.gen_range(1..10_000)).unwrap(),
+            expensive_requests_per_minute: NonZeroU32::new(rng.gen_range(1..10_000)).unwrap(),
         }
     }
 }
@@ -191,10 +264,17 @@ impl Distribution<configs::chain::StateKeeperConfig> for EncodeDist {
             max_gas_per_batch: self.sample(rng),
             max_pubdata_per_batch: self.sample(rng),
             fee_model_version: self.sample(rng),
+            pubdata_price_curve_l1_gas_prices: self.sample_collect(rng),
+            pubdata_price_curve_pubdata_prices: self.sample_collect(rng),
             validation_computational_gas_limit: self.sample(rng),
             save_call_traces: self.sample(rng),
             max_circuits_per_batch: self.sample(rng),
+            seal_on_predicted_circuits: self.sample(rng),
             protective_reads_persistence_enabled: self.sample(rng),
+            congestion_max_unproven_l1_batches: self.sample(rng),
+            congestion_max_unexecuted_l1_batches: self.sample(rng),
+            congestion_min_l1_batch_interval_ms: self.sample(rng),
+            congestion_gas_price_multiplier: self.sample(rng),
             // These values are not involved into files serialization skip them
             fee_account_addr: None,
             bootloader_hash: None,
@@ -233,6 +313,7 @@ impl Distribution<configs::chain::MempoolConfig> for EncodeDist {
             stuck_tx_timeout: self.sample(rng),
             remove_stuck_txs: self.sample(rng),
             delay_interval: self.sample(rng),
+            tx_policy_path: self.sample(rng),
         }
     }
 }
@@ -283,6 +364,17 @@ impl Distribution<configs::database::MerkleTreeMode> for EncodeDist {
     }
 }
 
+impl Distribution<configs::database::RocksdbProfile> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::database::RocksdbProfile {
+        type T = configs::database::RocksdbProfile;
+        match rng.gen_range(0..3) {
+            0 => T::Balanced,
+            1 => T::WriteHeavy,
+            _ => T::ReadHeavy,
+        }
+    }
+}
+
 impl Distribution<configs::database::MerkleTreeConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::database::MerkleTreeConfig {
         configs::database::MerkleTreeConfig {
@@ -293,6 +385,10 @@ impl Distribution<configs::database::MerkleTreeConfig> for EncodeDist {
             memtable_capacity_mb: self.sample(rng),
             stalled_writes_timeout_sec: self.sample(rng),
             max_l1_batches_per_iter: self.sample(rng),
+            pruning_retained_versions: self.sample(rng),
+            pruning_iteration_delay_ms: self.sample(rng),
+            pruning_online_compaction: self.sample(rng),
+            rocksdb_profile: self.sample(rng),
         }
     }
 }
@@ -302,6 +398,7 @@ impl Distribution<configs::ExperimentalDBConfig> for EncodeDist {
         configs::ExperimentalDBConfig {
             state_keeper_db_block_cache_capacity_mb: self.sample(rng),
             state_keeper_db_max_open_files: self.sample(rng),
+            state_keeper_db_profile: self.sample(rng),
             protective_reads_persistence_enabled: self.sample(rng),
             processing_delay_ms: self.sample(rng),
             include_indices_and_filters_in_block_cache: self.sample(rng),
@@ -336,6 +433,7 @@ impl Distribution<configs::ExperimentalVmConfig> for EncodeDist {
             playground: self.sample(rng),
             state_keeper_fast_vm_mode: gen_fast_vm_mode(rng),
             api_fast_vm_mode: gen_fast_vm_mode(rng),
+            state_keeper_shadow_sampling_ratio: rng.gen(),
         }
     }
 }
@@ -359,6 +457,7 @@ impl Distribution<configs::database::PostgresConfig> for EncodeDist {
             statement_timeout_sec: self.sample(rng),
             long_connection_threshold_ms: self.sample(rng),
             slow_query_threshold_ms: self.sample(rng),
+            leak_detection_threshold_ms: self.sample(rng),
             test_server_url: self.sample(rng),
             test_prover_url: self.sample(rng),
         }
@@ -386,6 +485,17 @@ impl Distribution<configs::eth_sender::ProofSendingMode> for EncodeDist {
     }
 }
 
+impl Distribution<configs::eth_sender::FeeSmoothingStrategy> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::eth_sender::FeeSmoothingStrategy {
+        type T = configs::eth_sender::FeeSmoothingStrategy;
+        match rng.gen_range(0..3) {
+            0 => T::Median,
+            1 => T::Ema,
+            _ => T::Percentile,
+        }
+    }
+}
+
 impl Distribution<configs::eth_sender::ProofLoadingMode> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::eth_sender::ProofLoadingMode {
         type T = configs::eth_sender::ProofLoadingMode;
@@ -439,6 +549,11 @@ impl Distribution<configs::eth_sender::GasAdjusterConfig> for EncodeDist {
             max_blob_base_fee: self.sample(rng),
             // TODO(EVM-676): generate it randomly once this value is used
             settlement_mode: Default::default(),
+            fee_smoothing_strategy: self.sample(rng),
+            fee_smoothing_ema_alpha: self.sample(rng),
+            fee_smoothing_percentile: self.sample(rng),
+            max_fee_change_per_update: self.sample(rng),
+            blob_base_fee_prediction_blocks: self.sample(rng),
         }
     }
 }
@@ -448,6 +563,18 @@ impl Distribution<configs::EthWatchConfig> for EncodeDist {
         configs::EthWatchConfig {
             confirmations_for_eth_event: self.sample(rng),
             eth_node_poll_interval: self.sample(rng),
+            custom_events: self.sample_range(rng).map(|_| self.sample(rng)).collect(),
+            bridge_events_start_block: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::eth_watch::EthWatchCustomEvent> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::eth_watch::EthWatchCustomEvent {
+        configs::eth_watch::EthWatchCustomEvent {
+            contract_address: rng.gen(),
+            event_signature: self.sample(rng),
+            handler: self.sample(rng),
         }
     }
 }
@@ -464,6 +591,8 @@ impl Distribution<configs::FriProofCompressorConfig> for EncodeDist {
             universal_setup_path: self.sample(rng),
             universal_setup_download_url: self.sample(rng),
             verify_wrapper_proof: self.sample(rng),
+            heartbeat_interval_in_secs: self.sample(rng),
+            heartbeat_timeout_in_secs: self.sample(rng),
         }
     }
 }
@@ -505,6 +634,7 @@ impl Distribution<configs::FriProverConfig> for EncodeDist {
             prover_object_store: self.sample(rng),
             public_object_store: self.sample(rng),
             cloud_type: self.sample(rng),
+            setup_data_download_url: self.sample(rng),
         }
     }
 }
@@ -636,6 +766,8 @@ impl Distribution<configs::house_keeper::HouseKeeperConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::house_keeper::HouseKeeperConfig {
         configs::house_keeper::HouseKeeperConfig {
             l1_batch_metrics_reporting_interval_ms: self.sample(rng),
+            table_partition_size: self.sample(rng),
+            eth_txs_history_retention_secs: self.sample(rng),
         }
     }
 }
@@ -643,7 +775,7 @@ impl Distribution<configs::house_keeper::HouseKeeperConfig> for EncodeDist {
 impl Distribution<configs::object_store::ObjectStoreMode> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::object_store::ObjectStoreMode {
         type T = configs::object_store::ObjectStoreMode;
-        match rng.gen_range(0..4) {
+        match rng.gen_range(0..5) {
             0 => T::GCS {
                 bucket_base_url: self.sample(rng),
             },
@@ -654,6 +786,15 @@ impl Distribution<configs::object_store::ObjectStoreMode> for EncodeDist {
             2 => T::FileBacked {
                 file_backed_base_path: self.sample(rng),
             },
+            3 => T::S3 {
+                endpoint: self.sample(rng),
+                region: self.sample(rng),
+                bucket_name: self.sample(rng),
+                addressing_style: self.sample(rng),
+                server_side_encryption: self.sample(rng),
+                sse_kms_key_id: self.sample(rng),
+                multipart_part_size_bytes: self.sample(rng),
+            },
             _ => T::GCSAnonymousReadOnly {
                 bucket_base_url: self.sample(rng),
             },
@@ -661,12 +802,71 @@ impl Distribution<configs::object_store::ObjectStoreMode> for EncodeDist {
     }
 }
 
+impl Distribution<configs::object_store::S3AddressingStyle> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::object_store::S3AddressingStyle {
+        type T = configs::object_store::S3AddressingStyle;
+        match rng.gen_range(0..2) {
+            0 => T::Path,
+            _ => T::VirtualHosted,
+        }
+    }
+}
+
+impl Distribution<configs::object_store::S3ServerSideEncryption> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> configs::object_store::S3ServerSideEncryption {
+        type T = configs::object_store::S3ServerSideEncryption;
+        match rng.gen_range(0..2) {
+            0 => T::Aes256,
+            _ => T::Kms,
+        }
+    }
+}
+
 impl Distribution<configs::ObjectStoreConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::ObjectStoreConfig {
         configs::ObjectStoreConfig {
-            mode: self.sample(rng),
-            max_retries: self.sample(rng),
-            local_mirror_path: self.sample(rng),
+            tiers: self.sample_range(rng).map(|_| self.sample(rng)).collect(),
+            ..sample_untiered_object_store_config(self, rng)
+        }
+    }
+}
+
+/// Tier stores cannot have tiers themselves, so they are sampled separately to avoid unbounded recursion.
+fn sample_untiered_object_store_config<R: Rng + ?Sized>(
+    dist: &EncodeDist,
+    rng: &mut R,
+) -> configs::ObjectStoreConfig {
+    configs::ObjectStoreConfig {
+        mode: dist.sample(rng),
+        max_retries: dist.sample(rng),
+        local_mirror_path: dist.sample(rng),
+        tiers: vec![],
+    }
+}
+
+impl Distribution<configs::object_store::ArtifactClass> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::object_store::ArtifactClass {
+        type T = configs::object_store::ArtifactClass;
+        match rng.gen_range(0..3) {
+            0 => T::Proofs,
+            1 => T::WitnessInputs,
+            _ => T::Snapshots,
+        }
+    }
+}
+
+impl Distribution<configs::object_store::ObjectStoreTierConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::object_store::ObjectStoreTierConfig {
+        configs::object_store::ObjectStoreTierConfig {
+            artifact_class: self.sample(rng),
+            store: sample_untiered_object_store_config(self, rng),
+            cold_store: self.sample_opt(|| sample_untiered_object_store_config(self, rng)),
+            move_to_cold_after_days: self.sample(rng),
+            expire_after_days: self.sample(rng),
+            min_cold_object_size_bytes: self.sample(rng),
         }
     }
 }
@@ -681,6 +881,8 @@ impl Distribution<configs::ProofDataHandlerConfig> for EncodeDist {
                 first_tee_processed_batch: L1BatchNumber(rng.gen()),
                 tee_proof_generation_timeout_in_secs: self.sample(rng),
                 tee_batch_permanently_ignored_timeout_in_hours: self.sample(rng),
+                tee_attestation_verification: self.sample(rng),
+                tee_max_tcb_age_in_days: self.sample(rng),
             },
         }
     }
@@ -706,6 +908,7 @@ impl Distribution<configs::ObservabilityConfig> for EncodeDist {
             log_format: self.sample(rng),
             opentelemetry: self.sample(rng),
             log_directives: self.sample(rng),
+            log_correlation_ids: self.sample(rng),
         }
     }
 }
@@ -716,6 +919,8 @@ impl Distribution<configs::OpentelemetryConfig> for EncodeDist {
             level: self.sample(rng),
             endpoint: self.sample(rng),
             logs_endpoint: self.sample(rng),
+            sampling_ratio: self.sample_opt(|| rng.gen_range(0.0..=1.0)),
+            l2_chain_id: self.sample_opt(|| L2ChainId::default()),
         }
     }
 }
@@ -793,6 +998,7 @@ impl Distribution<configs::consensus::GenesisSpec> for EncodeDist {
             attesters: self.sample_collect(rng),
             leader: ValidatorPublicKey(self.sample(rng)),
             registry_address: self.sample_opt(|| rng.gen()),
+            attester_committee_epoch: self.sample_opt(|| rng.gen()),
             seed_peers: self
                 .sample_range(rng)
                 .map(|_| (NodePublicKey(self.sample(rng)), Host(self.sample(rng))))
@@ -864,6 +1070,9 @@ impl Distribution<configs::secrets::DatabaseSecrets> for EncodeDist {
         DatabaseSecrets {
             server_url: Some(format!("localhost:{}", rng.gen::<u16>()).parse().unwrap()),
             server_replica_url: Some(format!("localhost:{}", rng.gen::<u16>()).parse().unwrap()),
+            server_replica_urls: (0..rng.gen_range(0..3))
+                .map(|_| format!("localhost:{}", rng.gen::<u16>()).parse().unwrap())
+                .collect(),
             prover_url: Some(format!("localhost:{}", rng.gen::<u16>()).parse().unwrap()),
         }
     }
@@ -877,6 +1086,8 @@ impl Distribution<configs::secrets::Secrets> for EncodeDist {
             database: self.sample_opt(|| self.sample(rng)),
             l1: self.sample_opt(|| self.sample(rng)),
             data_availability: self.sample_opt(|| self.sample(rng)),
+            admin_api: self.sample_opt(|| self.sample(rng)),
+            s3: self.sample_opt(|| self.sample(rng)),
         }
     }
 }
@@ -918,12 +1129,21 @@ impl Distribution<configs::wallets::TokenMultiplierSetter> for EncodeDist {
     }
 }
 
+impl Distribution<configs::wallets::WithdrawalFinalizer> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::wallets::WithdrawalFinalizer {
+        configs::wallets::WithdrawalFinalizer {
+            wallet: self.sample(rng),
+        }
+    }
+}
+
 impl Distribution<configs::wallets::Wallets> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::wallets::Wallets {
         configs::wallets::Wallets {
             state_keeper: self.sample_opt(|| self.sample(rng)),
             eth_sender: self.sample_opt(|| self.sample(rng)),
             token_multiplier_setter: self.sample_opt(|| self.sample(rng)),
+            withdrawal_finalizer: self.sample_opt(|| self.sample(rng)),
         }
     }
 }
@@ -941,6 +1161,10 @@ impl Distribution<configs::en_config::ENConfig> for EncodeDist {
             },
             main_node_rate_limit_rps: self.sample_opt(|| rng.gen()),
             bridge_addresses_refresh_interval_sec: self.sample_opt(|| rng.gen()),
+            tree_data_fetcher_root_hash_mismatch_policy: match rng.gen_range(0..2) {
+                0 => configs::en_config::RootHashMismatchPolicy::Log,
+                _ => configs::en_config::RootHashMismatchPolicy::Halt,
+            },
         }
     }
 }
@@ -974,6 +1198,8 @@ impl Distribution<configs::da_dispatcher::DADispatcherConfig> for EncodeDist {
             max_rows_to_dispatch: self.sample(rng),
             max_retries: self.sample(rng),
             use_dummy_inclusion_data: self.sample(rng),
+            fallback_object_store: self.sample(rng),
+            inclusion_sla_ms: self.sample(rng),
         }
     }
 }
@@ -1045,6 +1271,9 @@ impl Distribution<configs::snapshot_recovery::SnapshotRecoveryConfig> for Encode
         SnapshotRecoveryConfig {
             enabled: self.sample(rng),
             l1_batch: self.sample_opt(|| L1BatchNumber(rng.gen())),
+            manifest_hash: self.sample_opt(|| rng.gen()),
+            peer_url: self
+                .sample_opt(|| format!("localhost:{}", rng.gen::<u16>()).parse().unwrap()),
             drop_storage_key_preimages: (tree != TreeRecoveryConfig::default()) && self.sample(rng),
             tree,
             postgres: self.sample(rng),
@@ -1060,6 +1289,8 @@ impl Distribution<configs::pruning::PruningConfig> for EncodeDist {
             chunk_size: self.sample(rng),
             removal_delay_sec: self.sample_opt(|| rng.gen()),
             data_retention_sec: self.sample(rng),
+            dry_run: self.sample(rng),
+            max_data_age_sec: self.sample_opt(|| rng.gen()),
         }
     }
 }
@@ -1087,6 +1318,69 @@ impl Distribution<configs::base_token_adjuster::BaseTokenAdjusterConfig> for Enc
     }
 }
 
+impl Distribution<configs::LeaderElectionConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::LeaderElectionConfig {
+        configs::LeaderElectionConfig {
+            lease_name: self.sample(rng),
+            instance_name: self.sample(rng),
+            lease_ttl_ms: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::withdrawal_finalizer::WithdrawalTokenLimit> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> configs::withdrawal_finalizer::WithdrawalTokenLimit {
+        configs::withdrawal_finalizer::WithdrawalTokenLimit {
+            l1_token_address: rng.gen(),
+            min_amount: U256(rng.gen()),
+            max_amount: U256(rng.gen()),
+        }
+    }
+}
+
+impl Distribution<configs::WithdrawalFinalizerConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::WithdrawalFinalizerConfig {
+        configs::WithdrawalFinalizerConfig {
+            poll_interval_ms: self.sample(rng),
+            max_tx_gas: self.sample(rng),
+            default_priority_fee_per_gas: self.sample(rng),
+            resend_after_l1_blocks: self.sample(rng),
+            token_limits: self.sample_range(rng).map(|_| self.sample(rng)).collect(),
+            max_finalizations_per_hour: self.sample(rng),
+            start_l1_batch: self.sample_opt(|| L1BatchNumber(rng.gen())),
+        }
+    }
+}
+
+impl Distribution<configs::AdminApiConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::AdminApiConfig {
+        configs::AdminApiConfig {
+            http_port: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::object_store::S3Secrets> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::object_store::S3Secrets {
+        configs::object_store::S3Secrets {
+            access_key_id: self.sample(rng),
+            secret_access_key: APIKey(Secret::new(self.sample(rng))),
+            session_token: self.sample_opt(|| APIKey(Secret::new(self.sample(rng)))),
+        }
+    }
+}
+
+impl Distribution<configs::AdminApiSecrets> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::AdminApiSecrets {
+        configs::AdminApiSecrets {
+            auth_token: APIKey(Secret::new(self.sample(rng))),
+        }
+    }
+}
+
 impl Distribution<configs::external_proof_integration_api::ExternalProofIntegrationApiConfig>
     for EncodeDist
 {
@@ -1116,6 +1410,15 @@ impl Distribution<configs::external_price_api_client::ExternalPriceApiClientConf
                 fluctuation: self.sample(rng),
                 next_value_fluctuation: self.sample(rng),
             }),
+            chainlink: Some(ChainlinkPriceClientConfig {
+                rpc_url: self.sample(rng),
+                feed_address: self.sample_opt(|| rng.gen()),
+            }),
+            aggregated: Some(AggregatedPriceClientConfig {
+                sources: self.sample_range(rng).map(|_| self.sample(rng)).collect(),
+                max_staleness_ms: self.sample(rng),
+                min_sources: self.sample(rng),
+            }),
         }
     }
 }
@@ -1140,6 +1443,7 @@ impl Distribution<configs::prover_job_monitor::ProverJobMonitorConfig> for Encod
             prover_queue_reporter_run_interval_ms: self.sample(rng),
             witness_generator_queue_reporter_run_interval_ms: self.sample(rng),
             witness_job_queuer_run_interval_ms: self.sample(rng),
+            scaling_signals_target_backlog_ms: self.sample(rng),
             http_port: self.sample(rng),
         }
     }
@@ -1183,6 +1487,9 @@ impl Distribution<configs::GeneralConfig> for EncodeDist {
             experimental_vm_config: self.sample(rng),
             prover_job_monitor_config: self.sample(rng),
             timestamp_asserter_config: self.sample(rng),
+            admin_api_config: self.sample(rng),
+            leader_election_config: self.sample(rng),
+            withdrawal_finalizer_config: self.sample(rng),
         }
     }
 }
@@ -24,6 +24,17 @@ impl VyperInput {
 
         let sources = match req.source_code_data {
             SourceCodeData::VyperMultiFile(s) => s,
+            SourceCodeData::VyperStandardJsonInput(input) => {
+                // Only sources are taken from the standard JSON input (e.g., produced by Hardhat or Foundry);
+                // compiler settings are provided by the request fields, same as for multi-file input.
+                let input: StandardJson = serde_json::from_value(serde_json::Value::Object(input))
+                    .map_err(|_| ContractVerifierError::FailedToDeserializeInput)?;
+                input
+                    .sources
+                    .into_iter()
+                    .map(|(name, source)| (name, source.content))
+                    .collect()
+            }
             other => unreachable!("unexpected `SourceCodeData` variant: {other:?}"),
         };
         Ok(Self {
@@ -112,3 +123,45 @@ impl Compiler<VyperInput> for Vyper {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{contract_verification_api::CompilerVersions, Address};
+
+    use super::*;
+
+    #[test]
+    fn building_input_from_standard_json() {
+        let input = serde_json::json!({
+            "language": "Vyper",
+            "sources": {
+                "contracts/Counter.vy": { "content": "# counter" },
+            },
+            "settings": { "optimize": "gas" },
+        });
+        let req = VerificationIncomingRequest {
+            contract_address: Address::repeat_byte(1),
+            source_code_data: SourceCodeData::VyperStandardJsonInput(
+                input.as_object().unwrap().clone(),
+            ),
+            contract_name: "contracts/Counter.vy:Counter".to_owned(),
+            compiler_versions: CompilerVersions::Vyper {
+                compiler_vyper_version: "0.3.10".to_owned(),
+                compiler_zkvyper_version: None,
+            },
+            optimization_used: true,
+            optimizer_mode: None,
+            constructor_arguments: Default::default(),
+            is_system: false,
+            force_evmla: false,
+        };
+
+        let input = VyperInput::new(req).unwrap();
+        assert_eq!(input.file_name, "contracts/Counter.vy");
+        assert_eq!(input.contract_name, "Counter");
+        assert_eq!(
+            input.sources,
+            HashMap::from([("contracts/Counter.vy".to_owned(), "# counter".to_owned())])
+        );
+    }
+}
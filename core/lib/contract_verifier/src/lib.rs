@@ -32,6 +32,7 @@ use crate::{
 mod compilers;
 pub mod error;
 mod metrics;
+pub mod migration;
 mod resolver;
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,164 @@
+//! Export and import of verification info between environments (e.g., when migrating explorers or spinning up a fork).
+
+use std::{fmt, str::FromStr};
+
+use anyhow::Context as _;
+use zksync_dal::{Connection, Core, CoreDal};
+use zksync_types::{contract_verification_api::ExportedVerificationInfo, Address};
+
+/// Number of entries requested from the export API at once. Matches the maximum page size of the API.
+const EXPORT_PAGE_SIZE: usize = 100;
+
+/// Exports all verification info using the contract verification API at `api_url`.
+pub async fn export_verification_info(
+    api_url: &str,
+) -> anyhow::Result<Vec<ExportedVerificationInfo>> {
+    let client = reqwest::Client::new();
+    let url = format!(
+        "{}/contract_verification/export",
+        api_url.trim_end_matches('/')
+    );
+    let mut exported = vec![];
+    let mut after: Option<Address> = None;
+    loop {
+        let mut request = client
+            .get(&url)
+            .query(&[("limit", EXPORT_PAGE_SIZE.to_string())]);
+        if let Some(after) = after {
+            request = request.query(&[("after", format!("{after:?}"))]);
+        }
+        let response = request
+            .send()
+            .await
+            .context("failed requesting verification info export")?
+            .error_for_status()
+            .context("verification info export failed")?;
+        let body = response.bytes().await.context("failed reading response")?;
+        let page: Vec<ExportedVerificationInfo> =
+            serde_json::from_slice(&body).context("failed parsing exported verification info")?;
+
+        let Some(last) = page.last() else {
+            break;
+        };
+        after = Some(last.info.request.req.contract_address);
+        let is_last_page = page.len() < EXPORT_PAGE_SIZE;
+        exported.extend(page);
+        tracing::info!("Exported {} verification info entries", exported.len());
+        if is_last_page {
+            break;
+        }
+    }
+    Ok(exported)
+}
+
+/// Policy for imported entries with a bytecode hash that doesn't match the bytecode deployed in the importing environment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BytecodeMismatchPolicy {
+    /// Skip mismatched entries.
+    #[default]
+    Skip,
+    /// Abort the import on the first mismatched entry.
+    Fail,
+    /// Import mismatched entries anyway.
+    Force,
+}
+
+impl FromStr for BytecodeMismatchPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "skip" => Ok(Self::Skip),
+            "fail" => Ok(Self::Fail),
+            "force" => Ok(Self::Force),
+            _ => Err(format!(
+                "unknown bytecode mismatch policy `{s}`, expected one of `skip`, `fail`, `force`"
+            )),
+        }
+    }
+}
+
+/// Outcome of a verification info import.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ImportStats {
+    /// Number of persisted entries.
+    pub imported: usize,
+    /// Number of entries skipped because the contract is already verified in the importing environment.
+    pub already_verified: usize,
+    /// Number of entries skipped because of a bytecode mismatch.
+    pub mismatched: usize,
+}
+
+impl fmt::Display for ImportStats {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "imported: {}, already verified: {}, skipped due to bytecode mismatch: {}",
+            self.imported, self.already_verified, self.mismatched
+        )
+    }
+}
+
+/// Imports exported verification info into the storage. An entry is considered mismatched if the contract
+/// is not deployed in the importing environment, or if its bytecode hash differs from the exported one.
+/// Existing verification info is only replaced if `overwrite` is set.
+pub async fn import_verification_info(
+    storage: &mut Connection<'_, Core>,
+    entries: &[ExportedVerificationInfo],
+    mismatch_policy: BytecodeMismatchPolicy,
+    overwrite: bool,
+) -> anyhow::Result<ImportStats> {
+    let mut transaction = storage.start_transaction().await?;
+    let deployed_contracts = transaction
+        .storage_logs_dal()
+        .filter_deployed_contracts(
+            entries
+                .iter()
+                .map(|entry| entry.info.request.req.contract_address),
+            None,
+        )
+        .await?;
+
+    let mut stats = ImportStats::default();
+    for entry in entries {
+        let address = entry.info.request.req.contract_address;
+        let deployed_hash = deployed_contracts.get(&address).map(|&(_, hash)| hash);
+        let is_mismatched = deployed_hash.is_none() || deployed_hash != entry.bytecode_hash;
+        if is_mismatched {
+            match mismatch_policy {
+                BytecodeMismatchPolicy::Skip => {
+                    tracing::warn!(
+                        "Skipping verification info for {address:?}: exported bytecode hash {:?}, deployed {deployed_hash:?}",
+                        entry.bytecode_hash
+                    );
+                    stats.mismatched += 1;
+                    continue;
+                }
+                BytecodeMismatchPolicy::Fail => {
+                    anyhow::bail!(
+                        "bytecode mismatch for {address:?}: exported bytecode hash {:?}, deployed {deployed_hash:?}",
+                        entry.bytecode_hash
+                    );
+                }
+                BytecodeMismatchPolicy::Force => {
+                    tracing::warn!(
+                        "Importing verification info for {address:?} despite bytecode mismatch: exported bytecode hash {:?}, deployed {deployed_hash:?}",
+                        entry.bytecode_hash
+                    );
+                }
+            }
+        }
+
+        let persisted = transaction
+            .contract_verification_dal()
+            .import_verification_info(&entry.info, overwrite)
+            .await?;
+        if persisted {
+            stats.imported += 1;
+        } else {
+            stats.already_verified += 1;
+        }
+    }
+    transaction.commit().await?;
+    Ok(stats)
+}
@@ -22,6 +22,10 @@ pub(super) enum CompilerGitHubRelease {
     ZkSolc,
     /// Vyper compiler for EraVM
     ZkVyper,
+    /// Binary-only releases of older ZkSolc versions, which predate the open-source compiler repository
+    ZkSolcLegacy,
+    /// Binary-only releases of older ZkVyper versions, which predate the open-source compiler repository
+    ZkVyperLegacy,
 }
 
 impl CompilerGitHubRelease {
@@ -32,6 +36,8 @@ impl CompilerGitHubRelease {
             Self::ZkVmSolc => "matter-labs",
             Self::ZkSolc => "matter-labs",
             Self::ZkVyper => "matter-labs",
+            Self::ZkSolcLegacy => "matter-labs",
+            Self::ZkVyperLegacy => "matter-labs",
         }
     }
 
@@ -42,6 +48,8 @@ impl CompilerGitHubRelease {
             Self::ZkVmSolc => "era-solidity",
             Self::ZkSolc => "era-compiler-solidity",
             Self::ZkVyper => "era-compiler-vyper",
+            Self::ZkSolcLegacy => "zksolc-bin",
+            Self::ZkVyperLegacy => "zkvyper-bin",
         }
     }
 
@@ -114,6 +122,14 @@ impl CompilerGitHubRelease {
                     None
                 }
             }
+            Self::ZkSolcLegacy | Self::ZkVyperLegacy => {
+                // Binary releases are tagged with version numbers in form of `vX.Y.Z`, which is already
+                // the format expected by our API.
+                tag_name
+                    .strip_prefix('v')
+                    .filter(|v| semver::Version::parse(v).is_ok())
+                    .map(|_| tag_name.to_owned())
+            }
         }
     }
 
@@ -122,8 +138,8 @@ impl CompilerGitHubRelease {
             Self::Solc => asset_url.contains("solc-static-linux"),
             Self::Vyper => asset_url.contains(".linux"),
             Self::ZkVmSolc => asset_url.contains("solc-linux-amd64"),
-            Self::ZkSolc => asset_url.contains("zksolc-linux-amd64-musl"),
-            Self::ZkVyper => asset_url.contains("zkvyper-linux-amd64-musl"),
+            Self::ZkSolc | Self::ZkSolcLegacy => asset_url.contains("zksolc-linux-amd64-musl"),
+            Self::ZkVyper | Self::ZkVyperLegacy => asset_url.contains("zkvyper-linux-amd64-musl"),
         }
     }
 }
@@ -179,16 +195,38 @@ impl GitHubApi {
         Ok(versions)
     }
 
+    /// Returns zksolc versions from both the legacy binary releases and the compiler repository.
+    /// If a version is present in both, the compiler repository takes precedence.
     pub async fn zksolc_versions(&self) -> anyhow::Result<HashMap<String, reqwest::Url>> {
-        self.extract_versions(CompilerGitHubRelease::ZkSolc).await
+        let mut versions = self
+            .extract_versions(CompilerGitHubRelease::ZkSolcLegacy)
+            .await
+            .context("Can't fetch legacy zksolc versions")?;
+        versions.extend(
+            self.extract_versions(CompilerGitHubRelease::ZkSolc)
+                .await
+                .context("Can't fetch zksolc versions")?,
+        );
+        Ok(versions)
     }
 
     pub async fn vyper_versions(&self) -> anyhow::Result<HashMap<String, reqwest::Url>> {
         self.extract_versions(CompilerGitHubRelease::Vyper).await
     }
 
+    /// Returns zkvyper versions from both the legacy binary releases and the compiler repository.
+    /// If a version is present in both, the compiler repository takes precedence.
     pub async fn zkvyper_versions(&self) -> anyhow::Result<HashMap<String, reqwest::Url>> {
-        self.extract_versions(CompilerGitHubRelease::ZkVyper).await
+        let mut versions = self
+            .extract_versions(CompilerGitHubRelease::ZkVyperLegacy)
+            .await
+            .context("Can't fetch legacy zkvyper versions")?;
+        versions.extend(
+            self.extract_versions(CompilerGitHubRelease::ZkVyper)
+                .await
+                .context("Can't fetch zkvyper versions")?,
+        );
+        Ok(versions)
     }
 
     /// Will scan all the releases for a specific compiler.
@@ -241,3 +279,26 @@ impl GitHubApi {
         Ok(versions)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracting_versions() {
+        let version = CompilerGitHubRelease::Solc.extract_version("v0.8.24");
+        assert_eq!(version.as_deref(), Some("0.8.24"));
+        let version = CompilerGitHubRelease::ZkVmSolc.extract_version("0.8.24-1.0.1");
+        assert_eq!(version.as_deref(), Some("zkVM-0.8.24-1.0.1"));
+        let version = CompilerGitHubRelease::ZkSolc.extract_version("1.5.7");
+        assert_eq!(version.as_deref(), Some("v1.5.7"));
+        let version = CompilerGitHubRelease::ZkSolcLegacy.extract_version("v1.3.13");
+        assert_eq!(version.as_deref(), Some("v1.3.13"));
+        let version = CompilerGitHubRelease::ZkVyperLegacy.extract_version("v1.3.9");
+        assert_eq!(version.as_deref(), Some("v1.3.9"));
+        assert_eq!(
+            CompilerGitHubRelease::ZkSolcLegacy.extract_version("1.3.13"),
+            None
+        );
+    }
+}
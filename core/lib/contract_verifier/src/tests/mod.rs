@@ -9,7 +9,10 @@ use zksync_node_test_utils::{create_l1_batch, create_l2_block};
 use zksync_types::{
     address_to_h256,
     bytecode::{pad_evm_bytecode, BytecodeHash},
-    contract_verification_api::{CompilerVersions, SourceCodeData, VerificationIncomingRequest},
+    contract_verification_api::{
+        testonly::mock_exported_verification_info, CompilerVersions, SourceCodeData,
+        VerificationIncomingRequest,
+    },
     get_code_key, get_known_code_key,
     l2::L2Tx,
     tx::IncludedTxLocation,
@@ -21,6 +24,7 @@ use zksync_vm_interface::{tracer::ValidationTraces, TransactionExecutionMetrics,
 use super::*;
 use crate::{
     compilers::{SolcInput, VyperInput, ZkSolcInput},
+    migration::{import_verification_info, BytecodeMismatchPolicy, ImportStats},
     resolver::{Compiler, SupportedCompilerVersions},
 };
 
@@ -784,3 +788,76 @@ async fn no_compiler_version() {
     let error = status.error.unwrap();
     assert!(error.contains("solc version"), "{error}");
 }
+
+#[test_casing(3, [BytecodeMismatchPolicy::Skip, BytecodeMismatchPolicy::Fail, BytecodeMismatchPolicy::Force])]
+#[tokio::test]
+async fn importing_verification_info(mismatch_policy: BytecodeMismatchPolicy) {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    prepare_storage(&mut storage).await;
+    let bytecode = vec![0_u8; 32];
+    let bytecode_hash = BytecodeHash::for_bytecode(&bytecode).value();
+    let address = Address::repeat_byte(1);
+    mock_deployment(&mut storage, address, bytecode, &[]).await;
+    let mismatched_address = Address::repeat_byte(2);
+    mock_deployment(&mut storage, mismatched_address, vec![1; 32], &[]).await;
+
+    let entries = [
+        mock_exported_verification_info(address, Some(bytecode_hash)),
+        mock_exported_verification_info(mismatched_address, Some(bytecode_hash)),
+    ];
+    let result = import_verification_info(&mut storage, &entries, mismatch_policy, false).await;
+    let stats = match mismatch_policy {
+        BytecodeMismatchPolicy::Fail => {
+            let err = result.unwrap_err().to_string();
+            assert!(err.contains("bytecode mismatch"), "{err}");
+            // The import is transactional, so nothing should be persisted.
+            let info = storage
+                .contract_verification_dal()
+                .get_contract_verification_info(address)
+                .await
+                .unwrap();
+            assert!(info.is_none());
+            return;
+        }
+        BytecodeMismatchPolicy::Skip => {
+            let stats = result.unwrap();
+            assert_eq!(
+                stats,
+                ImportStats {
+                    imported: 1,
+                    already_verified: 0,
+                    mismatched: 1,
+                }
+            );
+            stats
+        }
+        BytecodeMismatchPolicy::Force => {
+            let stats = result.unwrap();
+            assert_eq!(
+                stats,
+                ImportStats {
+                    imported: 2,
+                    already_verified: 0,
+                    mismatched: 0,
+                }
+            );
+            stats
+        }
+    };
+
+    let info = storage
+        .contract_verification_dal()
+        .get_contract_verification_info(address)
+        .await
+        .unwrap()
+        .expect("verification info is not imported");
+    assert_eq!(info.request.req.contract_address, address);
+
+    // Repeated import shouldn't overwrite existing info.
+    let repeated_stats = import_verification_info(&mut storage, &entries, mismatch_policy, false)
+        .await
+        .unwrap();
+    assert_eq!(repeated_stats.imported, 0);
+    assert_eq!(repeated_stats.already_verified, stats.imported);
+}
@@ -1435,3 +1435,36 @@ pub static MESSAGE_ROOT_CONTRACT: Lazy<Contract> = Lazy::new(|| {
     }]"#;
     serde_json::from_str(abi).unwrap()
 });
+
+/// Subset of the L1 shared bridge ABI used to finalize L2 -> L1 withdrawals.
+pub static L1_SHARED_BRIDGE_CONTRACT: Lazy<Contract> = Lazy::new(|| {
+    let abi = r#"
+    [{
+      "inputs": [
+        { "internalType": "uint256", "name": "_chainId", "type": "uint256" },
+        { "internalType": "uint256", "name": "_l2BatchNumber", "type": "uint256" },
+        { "internalType": "uint256", "name": "_l2MessageIndex", "type": "uint256" },
+        { "internalType": "uint16", "name": "_l2TxNumberInBatch", "type": "uint16" },
+        { "internalType": "bytes", "name": "_message", "type": "bytes" },
+        { "internalType": "bytes32[]", "name": "_merkleProof", "type": "bytes32[]" }
+      ],
+      "name": "finalizeWithdrawal",
+      "outputs": [],
+      "stateMutability": "nonpayable",
+      "type": "function"
+    },
+    {
+      "inputs": [
+        { "internalType": "uint256", "name": "_chainId", "type": "uint256" },
+        { "internalType": "uint256", "name": "_l2BatchNumber", "type": "uint256" },
+        { "internalType": "uint256", "name": "_l2MessageIndex", "type": "uint256" }
+      ],
+      "name": "isWithdrawalFinalized",
+      "outputs": [
+        { "internalType": "bool", "name": "", "type": "bool" }
+      ],
+      "stateMutability": "view",
+      "type": "function"
+    }]"#;
+    serde_json::from_str(abi).unwrap()
+});
@@ -0,0 +1,2 @@
+ALTER TABLE events DROP CONSTRAINT IF EXISTS events_partition_bound;
+ALTER TABLE storage_logs DROP CONSTRAINT IF EXISTS storage_logs_partition_bound;
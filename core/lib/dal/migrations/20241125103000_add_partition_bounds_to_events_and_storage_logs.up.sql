@@ -0,0 +1,26 @@
+-- First step of partitioning `events` and `storage_logs` by `miniblock_number` ranges (see the
+-- `partition_events_and_storage_logs` migration).
+--
+-- Adds a check constraint bounding L2 blocks in the tables so that they can be attached as a partition without a full scan.
+-- The constraint is added as `NOT VALID`, so this doesn't scan the tables either; it's validated by a separate migration
+-- that doesn't block reads or writes. The bound leaves headroom of a full partition (10^6 L2 blocks) after the last stored
+-- L2 block, so that L2 blocks sealed while migrations are applied still satisfy the constraint.
+CREATE FUNCTION add_partition_bound(tbl TEXT, partition_size BIGINT) RETURNS VOID AS $$
+DECLARE
+    upper_bound BIGINT;
+BEGIN
+    EXECUTE format(
+        'SELECT (COALESCE(MAX(miniblock_number), -1) / %s + 2) * %s FROM %I',
+        partition_size, partition_size, tbl
+    ) INTO upper_bound;
+    EXECUTE format(
+        'ALTER TABLE %I ADD CONSTRAINT %I CHECK (miniblock_number < %s) NOT VALID',
+        tbl, tbl || '_partition_bound', upper_bound
+    );
+END;
+$$ LANGUAGE plpgsql;
+
+SELECT add_partition_bound('events', 1000000);
+SELECT add_partition_bound('storage_logs', 1000000);
+
+DROP FUNCTION add_partition_bound;
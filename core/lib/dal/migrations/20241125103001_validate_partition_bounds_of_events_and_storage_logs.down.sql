@@ -0,0 +1 @@
+-- Postgres doesn't allow to invalidate a constraint; it's dropped by the down migration adding it.
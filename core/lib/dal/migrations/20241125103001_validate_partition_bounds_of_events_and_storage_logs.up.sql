@@ -0,0 +1,5 @@
+-- Validating a constraint scans the table, but only takes a `SHARE UPDATE EXCLUSIVE` lock, so reads and writes
+-- can proceed concurrently. This must be a separate migration: each migration runs in a transaction, and the lock
+-- taken when adding the constraint would otherwise be held for the entire scan.
+ALTER TABLE events VALIDATE CONSTRAINT events_partition_bound;
+ALTER TABLE storage_logs VALIDATE CONSTRAINT storage_logs_partition_bound;
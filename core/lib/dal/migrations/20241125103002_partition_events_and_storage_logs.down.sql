@@ -0,0 +1,34 @@
+-- Converts partitioned `events` and `storage_logs` back to ordinary tables. Unlike the up migration,
+-- this copies all data, so it may take a long time on large databases.
+CREATE FUNCTION unpartition_table(tbl TEXT) RETURNS VOID AS $$
+DECLARE
+    plain_tbl TEXT := tbl || '_unpartitioned';
+    pkey_name TEXT;
+    pkey_def TEXT;
+    index_defs TEXT[];
+    index_def TEXT;
+BEGIN
+    SELECT conname, pg_get_constraintdef(oid) INTO pkey_name, pkey_def FROM pg_constraint
+    WHERE conrelid = tbl::regclass AND contype = 'p';
+    SELECT array_agg(indexdef) INTO index_defs FROM pg_indexes
+    WHERE schemaname = current_schema() AND tablename = tbl AND indexname <> pkey_name;
+
+    EXECUTE format('CREATE TABLE %I (LIKE %I INCLUDING DEFAULTS INCLUDING STORAGE)', plain_tbl, tbl);
+    EXECUTE format('INSERT INTO %I SELECT * FROM %I', plain_tbl, tbl);
+    EXECUTE format('DROP TABLE %I', tbl);
+    EXECUTE format('ALTER TABLE %I RENAME TO %I', plain_tbl, tbl);
+
+    IF pkey_name IS NOT NULL THEN
+        EXECUTE format('ALTER TABLE %I ADD CONSTRAINT %I %s', tbl, pkey_name, pkey_def);
+    END IF;
+    FOREACH index_def IN ARRAY COALESCE(index_defs, '{}') LOOP
+        -- Indexes on partitioned tables are defined `ON ONLY` the parent table.
+        EXECUTE replace(index_def, ' ON ONLY ', ' ON ');
+    END LOOP;
+END;
+$$ LANGUAGE plpgsql;
+
+SELECT unpartition_table('events');
+SELECT unpartition_table('storage_logs');
+
+DROP FUNCTION unpartition_table;
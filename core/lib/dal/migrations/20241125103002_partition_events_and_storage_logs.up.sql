@@ -0,0 +1,68 @@
+-- Converts `events` and `storage_logs` to tables partitioned by `miniblock_number` ranges.
+--
+-- The existing table is attached as the first partition (`<table>_legacy`) covering all existing L2 blocks, so no data is copied.
+-- Its bound is taken from the `<table>_partition_bound` constraint validated by the preceding migrations, which allows
+-- Postgres to skip scanning the table when attaching it. Thus, this migration only changes the catalog and holds
+-- exclusive locks for a short time.
+-- L2 blocks after the legacy partition go to the default partition (`<table>_default`) until range partitions
+-- are created by the partition maintenance task.
+--
+-- `transactions` is intentionally not partitioned. Its rows are inserted into the mempool with a `NULL` `miniblock_number`
+-- and get the L2 block number only when included, which would move each row between partitions. Besides, unique constraints
+-- on a partitioned table must include the partition key, so `transactions.hash` couldn't remain the primary key
+-- referenced by other tables (e.g., `call_traces`).
+CREATE FUNCTION partition_by_miniblock_number(tbl TEXT) RETURNS VOID AS $$
+DECLARE
+    legacy_tbl TEXT := tbl || '_legacy';
+    bound_constraint TEXT := tbl || '_partition_bound';
+    upper_bound BIGINT;
+    pkey RECORD;
+    idx RECORD;
+BEGIN
+    SELECT (REGEXP_MATCH(pg_get_constraintdef(oid), '<\s*(\d+)'))[1]::BIGINT INTO upper_bound FROM pg_constraint
+    WHERE conrelid = tbl::regclass AND conname = bound_constraint AND convalidated;
+    IF upper_bound IS NULL THEN
+        RAISE EXCEPTION 'table % has no validated constraint %', tbl, bound_constraint;
+    END IF;
+
+    EXECUTE format('ALTER TABLE %I RENAME TO %I', tbl, legacy_tbl);
+    EXECUTE format(
+        'CREATE TABLE %I (LIKE %I INCLUDING DEFAULTS INCLUDING STORAGE) PARTITION BY RANGE (miniblock_number)',
+        tbl, legacy_tbl
+    );
+    EXECUTE format(
+        'ALTER TABLE %I ATTACH PARTITION %I FOR VALUES FROM (MINVALUE) TO (%s)',
+        tbl, legacy_tbl, upper_bound
+    );
+    EXECUTE format('ALTER TABLE %I DROP CONSTRAINT %I', legacy_tbl, bound_constraint);
+    EXECUTE format('CREATE TABLE %I PARTITION OF %I DEFAULT', tbl || '_default', tbl);
+
+    -- Recreate the primary key and indexes on the partitioned table. Matching indexes on the legacy partition
+    -- are attached to the new ones rather than rebuilt.
+    FOR pkey IN
+        SELECT conname, pg_get_constraintdef(oid) AS def FROM pg_constraint
+        WHERE conrelid = legacy_tbl::regclass AND contype = 'p'
+    LOOP
+        EXECUTE format('ALTER TABLE %I RENAME CONSTRAINT %I TO %I', legacy_tbl, pkey.conname, legacy_tbl || '_pkey');
+        EXECUTE format('ALTER TABLE %I ADD CONSTRAINT %I %s', tbl, pkey.conname, pkey.def);
+    END LOOP;
+
+    FOR idx IN
+        SELECT schemaname, indexname, indexdef FROM pg_indexes
+        WHERE schemaname = current_schema() AND tablename = legacy_tbl
+            AND indexname <> legacy_tbl || '_pkey'
+    LOOP
+        EXECUTE format('ALTER INDEX %I RENAME TO %I', idx.indexname, idx.indexname || '_legacy');
+        EXECUTE replace(
+            idx.indexdef,
+            format(' ON %s.%s ', idx.schemaname, legacy_tbl),
+            format(' ON %s.%s ', idx.schemaname, tbl)
+        );
+    END LOOP;
+END;
+$$ LANGUAGE plpgsql;
+
+SELECT partition_by_miniblock_number('events');
+SELECT partition_by_miniblock_number('storage_logs');
+
+DROP FUNCTION partition_by_miniblock_number;
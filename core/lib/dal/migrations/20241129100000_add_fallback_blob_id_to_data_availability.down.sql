@@ -0,0 +1 @@
+ALTER TABLE data_availability DROP COLUMN IF EXISTS fallback_blob_id;
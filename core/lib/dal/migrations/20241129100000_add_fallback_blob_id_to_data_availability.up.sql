@@ -0,0 +1 @@
+ALTER TABLE data_availability ADD COLUMN IF NOT EXISTS fallback_blob_id TEXT;
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS vm_divergences (
+    id BIGSERIAL PRIMARY KEY,
+    l1_batch_number BIGINT NOT NULL,
+    tx_hash BYTEA,
+    errors TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS vm_divergences_l1_batch_number_idx ON vm_divergences (l1_batch_number);
@@ -0,0 +1,19 @@
+-- Cold storage for `eth_txs_history` entries superseded by the confirmed transaction (e.g., transactions
+-- resent with increased fees). Entries can be moved back to `eth_txs_history` if necessary.
+CREATE TABLE IF NOT EXISTS eth_txs_history_archive (
+    id INT PRIMARY KEY,
+    eth_tx_id INT NOT NULL REFERENCES eth_txs (id) ON DELETE CASCADE,
+    tx_hash TEXT NOT NULL,
+    base_fee_per_gas BIGINT NOT NULL,
+    priority_fee_per_gas BIGINT NOT NULL,
+    blob_base_fee_per_gas BIGINT,
+    signed_raw_tx BYTEA,
+    sent_at_block INT,
+    sent_at TIMESTAMP,
+    confirmed_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    archived_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS eth_txs_history_archive_eth_tx_id_idx ON eth_txs_history_archive (eth_tx_id);
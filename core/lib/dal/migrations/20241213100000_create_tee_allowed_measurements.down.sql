@@ -0,0 +1 @@
+DROP TABLE IF EXISTS tee_allowed_measurements;
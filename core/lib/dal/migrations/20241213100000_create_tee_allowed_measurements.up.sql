@@ -0,0 +1,5 @@
+-- Enclave measurements (SGX MRENCLAVE or TDX MRTD) allowed in TEE attestation quotes.
+CREATE TABLE IF NOT EXISTS tee_allowed_measurements (
+    measurement BYTEA PRIMARY KEY,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,7 @@
+-- Leases used to elect a single active instance among several main node instances sharing the database.
+CREATE TABLE IF NOT EXISTS node_leases (
+    name TEXT PRIMARY KEY,
+    holder TEXT NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    acquired_at TIMESTAMP NOT NULL
+);
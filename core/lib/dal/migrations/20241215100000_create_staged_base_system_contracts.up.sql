@@ -0,0 +1,14 @@
+-- Base system contracts (bootloader, default account, EVM emulator) staged by operators ahead of a protocol upgrade.
+CREATE TABLE IF NOT EXISTS staged_base_system_contracts (
+    protocol_version INT NOT NULL,
+    kind TEXT NOT NULL,
+    bytecode_hash BYTEA NOT NULL,
+    bytecode BYTEA NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    staged_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (protocol_version, kind)
+);
+
+CREATE INDEX IF NOT EXISTS staged_base_system_contracts_bytecode_hash_idx
+ON staged_base_system_contracts (bytecode_hash);
@@ -0,0 +1,7 @@
+-- Raw price quotes base token ratios were derived from, kept to audit conversion ratios applied in batch fee inputs.
+CREATE TABLE IF NOT EXISTS base_token_ratio_audit (
+    ratio_id INT PRIMARY KEY REFERENCES base_token_ratios (id) ON DELETE CASCADE,
+    source TEXT NOT NULL,
+    quotes JSONB NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS l1_batch_storage_analytics;
+DROP TABLE IF EXISTS contract_storage_analytics;
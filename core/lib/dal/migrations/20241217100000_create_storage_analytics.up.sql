@@ -0,0 +1,18 @@
+-- Storage usage aggregated per contract and L1 batch by the storage analytics component.
+CREATE TABLE IF NOT EXISTS contract_storage_analytics (
+    l1_batch_number BIGINT NOT NULL REFERENCES l1_batches (number) ON DELETE CASCADE,
+    address BYTEA NOT NULL,
+    storage_writes INT NOT NULL,
+    initial_writes INT NOT NULL,
+    pubdata_bytes BIGINT NOT NULL,
+    PRIMARY KEY (l1_batch_number, address)
+);
+
+-- L1 batches processed by the storage analytics component together with per-batch totals.
+CREATE TABLE IF NOT EXISTS l1_batch_storage_analytics (
+    l1_batch_number BIGINT PRIMARY KEY REFERENCES l1_batches (number) ON DELETE CASCADE,
+    contracts INT NOT NULL,
+    storage_writes INT NOT NULL,
+    pubdata_bytes BIGINT NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1 @@
+-- postgres doesn't allow dropping enum variant, so nothing is done here
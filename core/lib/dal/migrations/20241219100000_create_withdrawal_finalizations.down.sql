@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS l1_batch_withdrawal_finalizations;
+DROP TABLE IF EXISTS withdrawal_finalizations;
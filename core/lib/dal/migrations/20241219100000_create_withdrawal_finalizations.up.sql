@@ -0,0 +1,24 @@
+-- Withdrawals processed by the withdrawal finalizer component.
+CREATE TABLE IF NOT EXISTS withdrawal_finalizations (
+    l1_batch_number BIGINT NOT NULL REFERENCES l1_batches (number) ON DELETE CASCADE,
+    l2_message_index INT NOT NULL,
+    l2_tx_number_in_batch INT NOT NULL,
+    l1_token BYTEA NOT NULL,
+    l1_receiver BYTEA NOT NULL,
+    amount NUMERIC(80) NOT NULL,
+    status TEXT NOT NULL,
+    l1_tx_hash BYTEA,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (l1_batch_number, l2_message_index)
+);
+
+CREATE INDEX IF NOT EXISTS withdrawal_finalizations_sent_idx
+ON withdrawal_finalizations (l1_batch_number, l2_message_index) WHERE status = 'sent';
+
+-- L1 batches with all withdrawals processed by the withdrawal finalizer component.
+CREATE TABLE IF NOT EXISTS l1_batch_withdrawal_finalizations (
+    l1_batch_number BIGINT PRIMARY KEY REFERENCES l1_batches (number) ON DELETE CASCADE,
+    withdrawals INT NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
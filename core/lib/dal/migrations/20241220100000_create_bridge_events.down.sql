@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS bridge_indexer_l2_checkpoints;
+DROP TABLE IF EXISTS bridge_events;
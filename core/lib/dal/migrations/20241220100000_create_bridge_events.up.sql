@@ -0,0 +1,32 @@
+-- L1 and L2 bridge events indexed by the bridge indexer. Events emitted on L2 are removed together
+-- with their L2 block on reverts and pruning; events emitted on L1 are rolled back on L1 reorgs.
+CREATE TABLE IF NOT EXISTS bridge_events (
+    id BIGSERIAL PRIMARY KEY,
+    kind TEXT NOT NULL,
+    l1_address BYTEA,
+    l2_address BYTEA,
+    l1_token BYTEA,
+    l2_token BYTEA,
+    amount NUMERIC(80) NOT NULL,
+    tx_hash BYTEA NOT NULL,
+    l1_block_number BIGINT,
+    l1_log_index INT,
+    l2_block_number BIGINT REFERENCES miniblocks (number) ON DELETE CASCADE,
+    l2_event_index INT,
+    created_at TIMESTAMP NOT NULL,
+    UNIQUE (l1_block_number, l1_log_index),
+    UNIQUE (l2_block_number, l2_event_index),
+    CHECK ((l1_block_number IS NULL) <> (l2_block_number IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS bridge_events_l1_address_idx
+ON bridge_events (l1_address, id) WHERE l1_address IS NOT NULL;
+CREATE INDEX IF NOT EXISTS bridge_events_l2_address_idx
+ON bridge_events (l2_address, id) WHERE l2_address IS NOT NULL;
+
+-- L2 blocks up to which events were indexed by the bridge indexer.
+CREATE TABLE IF NOT EXISTS bridge_indexer_l2_checkpoints (
+    l2_block_number BIGINT PRIMARY KEY REFERENCES miniblocks (number) ON DELETE CASCADE,
+    l2_block_hash BYTEA NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
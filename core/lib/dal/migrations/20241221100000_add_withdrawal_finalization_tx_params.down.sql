@@ -0,0 +1,5 @@
+ALTER TABLE withdrawal_finalizations
+    DROP COLUMN IF EXISTS l1_tx_nonce,
+    DROP COLUMN IF EXISTS l1_tx_sent_at_block,
+    DROP COLUMN IF EXISTS l1_tx_max_fee_per_gas,
+    DROP COLUMN IF EXISTS l1_tx_priority_fee_per_gas;
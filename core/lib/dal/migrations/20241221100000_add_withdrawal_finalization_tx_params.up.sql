@@ -0,0 +1,6 @@
+-- Parameters of the finalization transaction allowing to resend it with bumped fees if it isn't mined.
+ALTER TABLE withdrawal_finalizations
+    ADD COLUMN IF NOT EXISTS l1_tx_nonce BIGINT,
+    ADD COLUMN IF NOT EXISTS l1_tx_sent_at_block BIGINT,
+    ADD COLUMN IF NOT EXISTS l1_tx_max_fee_per_gas BIGINT,
+    ADD COLUMN IF NOT EXISTS l1_tx_priority_fee_per_gas BIGINT;
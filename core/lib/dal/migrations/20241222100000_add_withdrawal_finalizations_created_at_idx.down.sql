@@ -0,0 +1 @@
+DROP INDEX IF EXISTS withdrawal_finalizations_with_tx_created_at_idx;
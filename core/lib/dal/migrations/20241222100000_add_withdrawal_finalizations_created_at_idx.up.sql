@@ -0,0 +1,3 @@
+-- Used to limit the number of finalization transactions sent per hour.
+CREATE INDEX IF NOT EXISTS withdrawal_finalizations_with_tx_created_at_idx
+ON withdrawal_finalizations (created_at) WHERE l1_tx_hash IS NOT NULL;
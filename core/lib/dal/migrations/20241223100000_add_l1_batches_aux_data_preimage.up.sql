@@ -0,0 +1,2 @@
+-- Preimage of `aux_data_hash`, allowing to verify batch commitments off-chain.
+ALTER TABLE l1_batches ADD COLUMN IF NOT EXISTS aux_data_preimage BYTEA;
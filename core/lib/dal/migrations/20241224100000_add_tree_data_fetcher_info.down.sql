@@ -0,0 +1 @@
+DROP TABLE tree_data_fetcher_info;
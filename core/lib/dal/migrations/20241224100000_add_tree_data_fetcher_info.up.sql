@@ -0,0 +1,12 @@
+CREATE TABLE tree_data_fetcher_info
+(
+    last_verified_l1_batch BIGINT NOT NULL,
+    created_at             TIMESTAMP NOT NULL,
+    updated_at             TIMESTAMP NOT NULL
+);
+
+-- Root hashes persisted before the cursor was introduced are considered verified.
+INSERT INTO tree_data_fetcher_info(last_verified_l1_batch, created_at, updated_at)
+SELECT COALESCE(MAX(number), 0), NOW(), NOW()
+FROM l1_batches
+WHERE hash IS NOT NULL;
@@ -0,0 +1,8 @@
+ALTER TABLE data_availability ADD COLUMN IF NOT EXISTS fallback_blob_id TEXT;
+
+UPDATE data_availability
+SET fallback_blob_id = data_availability_fallback.blob_id
+FROM data_availability_fallback
+WHERE data_availability_fallback.l1_batch_number = data_availability.l1_batch_number;
+
+DROP TABLE IF EXISTS data_availability_fallback;
@@ -0,0 +1,19 @@
+-- Blobs dispatched to the fallback DA client are tracked separately from the primary ones, so that
+-- dispatching to the fallback client doesn't depend on the primary dispatch succeeding.
+CREATE TABLE IF NOT EXISTS data_availability_fallback
+(
+    l1_batch_number BIGINT PRIMARY KEY REFERENCES l1_batches (number) ON DELETE CASCADE,
+    blob_id         TEXT      NOT NULL,
+    sent_at         TIMESTAMP NOT NULL,
+
+    created_at      TIMESTAMP NOT NULL,
+    updated_at      TIMESTAMP NOT NULL
+);
+
+INSERT INTO data_availability_fallback (l1_batch_number, blob_id, sent_at, created_at, updated_at)
+SELECT l1_batch_number, fallback_blob_id, sent_at, NOW(), NOW()
+FROM data_availability
+WHERE fallback_blob_id IS NOT NULL
+ON CONFLICT DO NOTHING;
+
+ALTER TABLE data_availability DROP COLUMN IF EXISTS fallback_blob_id;
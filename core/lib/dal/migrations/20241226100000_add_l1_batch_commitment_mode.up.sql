@@ -0,0 +1,3 @@
+-- Commitment mode used by the L1 batch, persisted when the batch is sealed. `NULL` for batches sealed before
+-- the column was added; for these, the mode is derived from L2 blocks or taken from the node config.
+ALTER TABLE l1_batches ADD COLUMN IF NOT EXISTS commitment_mode TEXT;
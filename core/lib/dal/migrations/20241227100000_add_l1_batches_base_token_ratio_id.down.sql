@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS l1_batches_base_token_ratio_id_idx;
+ALTER TABLE l1_batches DROP COLUMN IF EXISTS base_token_ratio_id;
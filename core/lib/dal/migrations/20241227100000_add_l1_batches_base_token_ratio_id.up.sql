@@ -0,0 +1,4 @@
+-- ID of the base token ratio (from `base_token_ratios`) used to compute the batch fee input.
+ALTER TABLE l1_batches ADD COLUMN IF NOT EXISTS base_token_ratio_id INT;
+CREATE INDEX IF NOT EXISTS l1_batches_base_token_ratio_id_idx ON l1_batches (base_token_ratio_id)
+    WHERE base_token_ratio_id IS NOT NULL;
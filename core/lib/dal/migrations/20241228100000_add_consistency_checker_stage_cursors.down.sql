@@ -0,0 +1,3 @@
+ALTER TABLE consistency_checker_info
+    DROP COLUMN IF EXISTS last_processed_prove_l1_batch,
+    DROP COLUMN IF EXISTS last_processed_execute_l1_batch;
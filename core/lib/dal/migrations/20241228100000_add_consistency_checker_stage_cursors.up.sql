@@ -0,0 +1,4 @@
+-- Last L1 batches with prove / execute transactions checked by the consistency checker.
+ALTER TABLE consistency_checker_info
+    ADD COLUMN IF NOT EXISTS last_processed_prove_l1_batch BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS last_processed_execute_l1_batch BIGINT NOT NULL DEFAULT 0;
@@ -0,0 +1,4 @@
+ALTER TABLE transactions
+DROP COLUMN IF EXISTS l2_block_number_range_start,
+DROP COLUMN IF EXISTS l2_block_number_range_end,
+DROP COLUMN IF EXISTS expected_storage_values;
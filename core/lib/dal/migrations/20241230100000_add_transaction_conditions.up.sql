@@ -0,0 +1,4 @@
+ALTER TABLE transactions
+ADD COLUMN l2_block_number_range_start BIGINT DEFAULT NULL,
+ADD COLUMN l2_block_number_range_end BIGINT DEFAULT NULL,
+ADD COLUMN expected_storage_values JSONB DEFAULT NULL;
@@ -2,9 +2,16 @@ use std::num::NonZeroU64;
 
 use bigdecimal::{BigDecimal, FromPrimitive};
 use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
-use zksync_types::base_token_ratio::BaseTokenRatio;
+use zksync_types::{
+    api::BaseTokenRatioAuditEntry,
+    base_token_ratio::{BaseTokenRatio, BaseTokenRatioQuote},
+    L1BatchNumber,
+};
 
-use crate::{models::storage_base_token_ratio::StorageBaseTokenRatio, Core};
+use crate::{
+    models::storage_base_token_ratio::{StorageBaseTokenRatio, StorageBaseTokenRatioAuditEntry},
+    Core,
+};
 
 #[derive(Debug)]
 pub struct BaseTokenDal<'a, 'c> {
@@ -60,4 +67,219 @@ impl BaseTokenDal<'_, '_> {
 
         Ok(row.map(|r| r.into()))
     }
+
+    /// Records the price source and raw quotes the ratio with the specified ID was derived from.
+    pub async fn insert_ratio_audit(
+        &mut self,
+        ratio_id: usize,
+        source: &str,
+        quotes: &[BaseTokenRatioQuote],
+    ) -> DalResult<()> {
+        let quotes_json = serde_json::to_value(quotes).expect("failed serializing quotes");
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            base_token_ratio_audit (ratio_id, source, quotes, created_at)
+            VALUES
+            ($1, $2, $3, NOW())
+            "#,
+            ratio_id as i32,
+            source,
+            quotes_json
+        )
+        .instrument("insert_ratio_audit")
+        .with_arg("ratio_id", &ratio_id)
+        .with_arg("source", &source)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Records that the ratio with the specified ID was used to compute the fee input for the L1 batch.
+    pub async fn set_l1_batch_ratio_id(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        ratio_id: u32,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE l1_batches
+            SET
+                base_token_ratio_id = $2
+            WHERE
+                number = $1
+            "#,
+            i64::from(l1_batch_number.0),
+            ratio_id as i32
+        )
+        .instrument("set_l1_batch_ratio_id")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("ratio_id", &ratio_id)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns up to `limit` audited ratios with the ratio timestamp not earlier than `from`,
+    /// ordered by the ratio timestamp. Each entry includes the range of L1 batches with fee inputs computed using the ratio.
+    pub async fn get_ratio_audit(
+        &mut self,
+        from: &chrono::NaiveDateTime,
+        limit: usize,
+    ) -> DalResult<Vec<BaseTokenRatioAuditEntry>> {
+        let rows = sqlx::query_as!(
+            StorageBaseTokenRatioAuditEntry,
+            r#"
+            SELECT
+                base_token_ratios.id AS ratio_id,
+                base_token_ratios.ratio_timestamp,
+                base_token_ratios.numerator,
+                base_token_ratios.denominator,
+                base_token_ratio_audit.source,
+                base_token_ratio_audit.quotes,
+                base_token_ratio_audit.created_at,
+                batches.first_l1_batch_number,
+                batches.last_l1_batch_number
+            FROM
+                base_token_ratio_audit
+            INNER JOIN base_token_ratios ON base_token_ratios.id = base_token_ratio_audit.ratio_id
+            CROSS JOIN LATERAL (
+                SELECT
+                    MIN(number) AS first_l1_batch_number,
+                    MAX(number) AS last_l1_batch_number
+                FROM
+                    l1_batches
+                WHERE
+                    base_token_ratio_id = base_token_ratios.id
+            ) batches
+            WHERE
+                base_token_ratios.ratio_timestamp >= $1
+            ORDER BY
+                base_token_ratios.ratio_timestamp,
+                base_token_ratios.id
+            LIMIT
+                $2
+            "#,
+            from,
+            limit as i64
+        )
+        .instrument("get_ratio_audit")
+        .with_arg("from", from)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use super::*;
+    use crate::{tests::create_l1_batch_header, ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn persisting_ratio_audit() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let ratio_timestamp = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+        let (numerator, denominator) = (NonZeroU64::new(300).unwrap(), NonZeroU64::new(1).unwrap());
+        let ratio_id = conn
+            .base_token_dal()
+            .insert_token_ratio(numerator, denominator, &ratio_timestamp.naive_utc())
+            .await
+            .unwrap();
+
+        let quotes = [
+            BaseTokenRatioQuote {
+                source: "coingecko".to_owned(),
+                numerator,
+                denominator,
+                ratio_timestamp,
+                accepted: true,
+            },
+            BaseTokenRatioQuote {
+                source: "chainlink".to_owned(),
+                numerator: NonZeroU64::new(250).unwrap(),
+                denominator,
+                ratio_timestamp: ratio_timestamp - chrono::Duration::hours(2),
+                accepted: false,
+            },
+        ];
+        conn.base_token_dal()
+            .insert_ratio_audit(ratio_id, "aggregated", &quotes)
+            .await
+            .unwrap();
+
+        let entries = conn
+            .base_token_dal()
+            .get_ratio_audit(&ratio_timestamp.naive_utc(), 10)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.ratio_id as usize, ratio_id);
+        assert_eq!(
+            (entry.numerator, entry.denominator),
+            (numerator, denominator)
+        );
+        assert_eq!(entry.ratio_timestamp, ratio_timestamp);
+        assert_eq!(entry.source, "aggregated");
+        assert_eq!(entry.quotes, quotes);
+
+        let later = ratio_timestamp + chrono::Duration::seconds(1);
+        let entries = conn
+            .base_token_dal()
+            .get_ratio_audit(&later.naive_utc(), 10)
+            .await
+            .unwrap();
+        assert!(entries.is_empty());
+    }
+
+    #[tokio::test]
+    async fn linking_ratios_to_l1_batches() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let ratio_timestamp = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+        let one = NonZeroU64::new(1).unwrap();
+        let ratio_id = conn
+            .base_token_dal()
+            .insert_token_ratio(one, one, &ratio_timestamp.naive_utc())
+            .await
+            .unwrap();
+        conn.base_token_dal()
+            .insert_ratio_audit(ratio_id, "forced", &[])
+            .await
+            .unwrap();
+
+        let entries = conn
+            .base_token_dal()
+            .get_ratio_audit(&ratio_timestamp.naive_utc(), 10)
+            .await
+            .unwrap();
+        assert_eq!(entries[0].first_l1_batch_number, None);
+        assert_eq!(entries[0].last_l1_batch_number, None);
+
+        for number in 1..=3 {
+            conn.blocks_dal()
+                .insert_mock_l1_batch(&create_l1_batch_header(number))
+                .await
+                .unwrap();
+        }
+        for number in [1, 2] {
+            conn.base_token_dal()
+                .set_l1_batch_ratio_id(L1BatchNumber(number), ratio_id as u32)
+                .await
+                .unwrap();
+        }
+
+        let entries = conn
+            .base_token_dal()
+            .get_ratio_audit(&ratio_timestamp.naive_utc(), 10)
+            .await
+            .unwrap();
+        assert_eq!(entries[0].first_l1_batch_number, Some(L1BatchNumber(1)));
+        assert_eq!(entries[0].last_l1_batch_number, Some(L1BatchNumber(2)));
+    }
 }
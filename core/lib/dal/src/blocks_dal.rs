@@ -19,7 +19,7 @@ use zksync_types::{
         L1BatchHeader, L1BatchStatistics, L1BatchTreeData, L2BlockHeader, StorageOracleInfo,
         UnsealedL1BatchHeader,
     },
-    commitment::{L1BatchCommitmentArtifacts, L1BatchWithMetadata},
+    commitment::{L1BatchCommitmentArtifacts, L1BatchCommitmentMode, L1BatchWithMetadata},
     fee_model::BatchFeeInput,
     l2_to_l1_log::{BatchAndChainMerklePath, UserL2ToL1Log},
     writes::TreeWrite,
@@ -85,6 +85,112 @@ impl BlocksDal<'_, '_> {
         Ok(())
     }
 
+    /// Returns the last L1 batch with the transaction for the specified `action` checked by the consistency checker.
+    pub async fn get_consistency_checker_last_processed_l1_batch_for_action(
+        &mut self,
+        action: AggregatedActionType,
+    ) -> DalResult<L1BatchNumber> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                (
+                    CASE $1
+                        WHEN 'PublishProofBlocksOnchain' THEN last_processed_prove_l1_batch
+                        WHEN 'ExecuteBlocks' THEN last_processed_execute_l1_batch
+                        ELSE last_processed_l1_batch
+                    END
+                ) AS "last_processed_l1_batch!"
+            FROM
+                consistency_checker_info
+            "#,
+            action.as_str()
+        )
+        .instrument("get_consistency_checker_last_processed_l1_batch_for_action")
+        .report_latency()
+        .with_arg("action", &action)
+        .fetch_one(self.storage)
+        .await?;
+        Ok(L1BatchNumber(row.last_processed_l1_batch as u32))
+    }
+
+    pub async fn set_consistency_checker_last_processed_l1_batch_for_action(
+        &mut self,
+        action: AggregatedActionType,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE consistency_checker_info
+            SET
+                last_processed_l1_batch = (
+                    CASE WHEN $2 = 'CommitBlocks' THEN $1 ELSE last_processed_l1_batch END
+                ),
+                last_processed_prove_l1_batch = (
+                    CASE
+                        WHEN $2 = 'PublishProofBlocksOnchain' THEN $1
+                        ELSE last_processed_prove_l1_batch
+                    END
+                ),
+                last_processed_execute_l1_batch = (
+                    CASE
+                        WHEN $2 = 'ExecuteBlocks' THEN $1
+                        ELSE last_processed_execute_l1_batch
+                    END
+                ),
+                updated_at = NOW()
+            "#,
+            i64::from(l1_batch_number.0),
+            action.as_str()
+        )
+        .instrument("set_consistency_checker_last_processed_l1_batch_for_action")
+        .report_latency()
+        .with_arg("action", &action)
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the last L1 batch with the root hash verified against L1 by the tree data fetcher.
+    pub async fn get_tree_data_fetcher_last_verified_l1_batch(
+        &mut self,
+    ) -> DalResult<L1BatchNumber> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                last_verified_l1_batch AS "last_verified_l1_batch!"
+            FROM
+                tree_data_fetcher_info
+            "#
+        )
+        .instrument("get_tree_data_fetcher_last_verified_l1_batch")
+        .report_latency()
+        .fetch_one(self.storage)
+        .await?;
+        Ok(L1BatchNumber(row.last_verified_l1_batch as u32))
+    }
+
+    pub async fn set_tree_data_fetcher_last_verified_l1_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE tree_data_fetcher_info
+            SET
+                last_verified_l1_batch = $1,
+                updated_at = NOW()
+            "#,
+            l1_batch_number.0 as i32,
+        )
+        .instrument("set_tree_data_fetcher_last_verified_l1_batch")
+        .report_latency()
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
     pub async fn is_genesis_needed(&mut self) -> DalResult<bool> {
         let count = sqlx::query!(
             r#"
@@ -1079,9 +1185,10 @@ impl BlocksDal<'_, '_> {
                 state_diff_hash = $10,
                 aggregation_root = $11,
                 local_root = $12,
+                aux_data_preimage = $13,
                 updated_at = NOW()
             WHERE
-                number = $13
+                number = $14
                 AND commitment IS NULL
             "#,
             commitment_artifacts.commitment_hash.commitment.as_bytes(),
@@ -1102,6 +1209,7 @@ impl BlocksDal<'_, '_> {
             commitment_artifacts.state_diff_hash.as_bytes(),
             commitment_artifacts.aggregation_root.as_bytes(),
             commitment_artifacts.local_root.as_bytes(),
+            commitment_artifacts.aux_output_preimage.as_deref(),
             i64::from(number.0),
         )
         .instrument("save_l1_batch_commitment_artifacts")
@@ -1310,6 +1418,52 @@ impl BlocksDal<'_, '_> {
         Ok(row.and_then(|row| row.eth_commit_tx_id.map(|n| n as u64)))
     }
 
+    pub async fn get_eth_prove_tx_id(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<u64>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                eth_prove_tx_id
+            FROM
+                l1_batches
+            WHERE
+                number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_eth_prove_tx_id")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.and_then(|row| row.eth_prove_tx_id.map(|n| n as u64)))
+    }
+
+    pub async fn get_eth_execute_tx_id(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<u64>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                eth_execute_tx_id
+            FROM
+                l1_batches
+            WHERE
+                number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_eth_execute_tx_id")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.and_then(|row| row.eth_execute_tx_id.map(|n| n as u64)))
+    }
+
     /// Returns the number of the last L1 batch for which an Ethereum prove tx was sent and confirmed.
     pub async fn get_number_of_last_l1_batch_proven_on_eth(
         &mut self,
@@ -1945,6 +2099,30 @@ impl BlocksDal<'_, '_> {
         .map(|hash| H256::from_slice(&hash)))
     }
 
+    /// Returns the preimage of the auxiliary output hash for the specified L1 batch. The preimage is only persisted
+    /// for batches processed by the commitment generator after it started persisting preimages.
+    pub async fn get_l1_batch_aux_data_preimage(
+        &mut self,
+        number: L1BatchNumber,
+    ) -> DalResult<Option<Vec<u8>>> {
+        Ok(sqlx::query!(
+            r#"
+            SELECT
+                aux_data_preimage
+            FROM
+                l1_batches
+            WHERE
+                number = $1
+            "#,
+            i64::from(number.0)
+        )
+        .instrument("get_l1_batch_aux_data_preimage")
+        .with_arg("number", &number)
+        .fetch_optional(self.storage)
+        .await?
+        .and_then(|row| row.aux_data_preimage))
+    }
+
     pub async fn get_l1_batch_state_root_and_timestamp(
         &mut self,
         number: L1BatchNumber,
@@ -2319,6 +2497,24 @@ impl BlocksDal<'_, '_> {
         .with_arg("l1_batch_number", &l1_batch_number)
         .execute(self.storage)
         .await?;
+
+        // Root hashes of reverted batches may be re-fetched, so they must be verified again.
+        let last_verified_l1_batch = l1_batch_number.max(0);
+        sqlx::query!(
+            r#"
+            UPDATE tree_data_fetcher_info
+            SET
+                last_verified_l1_batch = $1,
+                updated_at = NOW()
+            WHERE
+                last_verified_l1_batch > $1
+            "#,
+            last_verified_l1_batch
+        )
+        .instrument("delete_l1_batches#reset_tree_data_fetcher_cursor")
+        .with_arg("last_verified_l1_batch", &last_verified_l1_batch)
+        .execute(self.storage)
+        .await?;
         Ok(())
     }
 
@@ -2389,6 +2585,116 @@ impl BlocksDal<'_, '_> {
         Ok(Some((L2BlockNumber(min as u32), L2BlockNumber(max as u32))))
     }
 
+    /// Persists the commitment mode used for the specified sealed L1 batch.
+    pub async fn set_l1_batch_commitment_mode(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        mode: L1BatchCommitmentMode,
+    ) -> DalResult<()> {
+        let instrumentation = Instrumented::new("set_l1_batch_commitment_mode")
+            .with_arg("l1_batch_number", &l1_batch_number)
+            .with_arg("mode", &mode);
+        let query = sqlx::query!(
+            r#"
+            UPDATE l1_batches
+            SET
+                commitment_mode = $2,
+                updated_at = NOW()
+            WHERE
+                number = $1
+            "#,
+            i64::from(l1_batch_number.0),
+            mode.to_string()
+        );
+        let result = instrumentation
+            .clone()
+            .with(query)
+            .execute(self.storage)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            let err = instrumentation.constraint_error(anyhow::anyhow!(
+                "L1 batch #{l1_batch_number} does not exist"
+            ));
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Returns the commitment mode used for the specified L1 batch, so that it remains correct after the chain
+    /// switches between rollup and validium modes. The mode persisted when sealing the batch is returned if present;
+    /// otherwise, the mode is determined based on the pubdata type of L2 blocks in the batch.
+    ///
+    /// Returns `None` if the batch doesn't exist, or if its mode is not reliably known. The latter is the case
+    /// for batches sealed without a persisted mode that have pre-gateway protocol versions, since pubdata params
+    /// are not used for their L2 blocks and are always set to default values. Callers should fall back
+    /// to the configured commitment mode in this case.
+    pub async fn get_l1_batch_commitment_mode(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<L1BatchCommitmentMode>> {
+        let persisted_mode = sqlx::query!(
+            r#"
+            SELECT
+                commitment_mode
+            FROM
+                l1_batches
+            WHERE
+                number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .try_map(|row| {
+            row.commitment_mode
+                .map(|mode| mode.parse::<L1BatchCommitmentMode>())
+                .transpose()
+                .decode_column("commitment_mode")
+        })
+        .instrument("get_l1_batch_commitment_mode#persisted")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_optional(self.storage)
+        .await?
+        .flatten();
+        if persisted_mode.is_some() {
+            return Ok(persisted_mode);
+        }
+
+        let mode = sqlx::query!(
+            r#"
+            SELECT
+                protocol_version,
+                pubdata_type
+            FROM
+                miniblocks
+            WHERE
+                l1_batch_number = $1
+            ORDER BY
+                number
+            LIMIT
+                1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .try_map(|row| {
+            let protocol_version = row
+                .protocol_version
+                .map(parse_protocol_version)
+                .transpose()?;
+            if protocol_version.map_or(true, |version| version.is_pre_gateway()) {
+                return Ok(None);
+            }
+            row.pubdata_type
+                .parse::<L1BatchCommitmentMode>()
+                .map(Some)
+                .decode_column("pubdata_type")
+        })
+        .instrument("get_l1_batch_commitment_mode")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(mode.flatten())
+    }
+
     /// Returns `true` if there exists a non-sealed batch (i.e. there is one+ stored L2 block that isn't assigned
     /// to any batch yet).
     pub async fn pending_batch_exists(&mut self) -> DalResult<bool> {
@@ -2957,7 +3263,9 @@ impl BlocksDal<'_, '_> {
 
 #[cfg(test)]
 mod tests {
-    use zksync_types::{tx::IncludedTxLocation, Address, ProtocolVersion};
+    use zksync_types::{
+        protocol_version::ProtocolSemanticVersion, tx::IncludedTxLocation, Address, ProtocolVersion,
+    };
 
     use super::*;
     use crate::{
@@ -3172,4 +3480,89 @@ mod tests {
             .unwrap()
             .is_none());
     }
+
+    #[tokio::test]
+    async fn getting_l1_batch_commitment_mode() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let gateway_version = ProtocolVersionId::Version27;
+        for minor in [ProtocolVersionId::latest(), gateway_version] {
+            let version = ProtocolVersion {
+                version: ProtocolSemanticVersion {
+                    minor,
+                    patch: 0.into(),
+                },
+                ..ProtocolVersion::default()
+            };
+            conn.protocol_versions_dal()
+                .save_protocol_version_with_tx(&version)
+                .await
+                .unwrap();
+        }
+
+        // The first batch has a pre-gateway protocol version, so its commitment mode is unknown.
+        let mut first_l2_block = create_l2_block_header(1);
+        first_l2_block.pubdata_params.pubdata_type = L1BatchCommitmentMode::Validium;
+        let mut second_l2_block = create_l2_block_header(2);
+        second_l2_block.protocol_version = Some(gateway_version);
+        second_l2_block.pubdata_params.pubdata_type = L1BatchCommitmentMode::Validium;
+        let mut third_l2_block = create_l2_block_header(3);
+        third_l2_block.protocol_version = Some(gateway_version);
+        for (l1_batch_number, l2_block) in [first_l2_block, second_l2_block, third_l2_block]
+            .iter()
+            .enumerate()
+        {
+            let l1_batch_number = L1BatchNumber(l1_batch_number as u32 + 1);
+            conn.blocks_dal().insert_l2_block(l2_block).await.unwrap();
+            conn.blocks_dal()
+                .insert_mock_l1_batch(&create_l1_batch_header(l1_batch_number.0))
+                .await
+                .unwrap();
+            conn.blocks_dal()
+                .mark_l2_blocks_as_executed_in_l1_batch(l1_batch_number)
+                .await
+                .unwrap();
+        }
+
+        let mut modes = vec![];
+        for l1_batch_number in 1..=4 {
+            let mode = conn
+                .blocks_dal()
+                .get_l1_batch_commitment_mode(L1BatchNumber(l1_batch_number))
+                .await
+                .unwrap();
+            modes.push(mode);
+        }
+        assert_eq!(
+            modes,
+            [
+                None,
+                Some(L1BatchCommitmentMode::Validium),
+                Some(L1BatchCommitmentMode::Rollup),
+                None
+            ]
+        );
+
+        // The persisted mode takes precedence over the one derived from L2 blocks.
+        conn.blocks_dal()
+            .set_l1_batch_commitment_mode(L1BatchNumber(1), L1BatchCommitmentMode::Validium)
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .set_l1_batch_commitment_mode(L1BatchNumber(3), L1BatchCommitmentMode::Validium)
+            .await
+            .unwrap();
+        for l1_batch_number in [1, 3] {
+            let mode = conn
+                .blocks_dal()
+                .get_l1_batch_commitment_mode(L1BatchNumber(l1_batch_number))
+                .await
+                .unwrap();
+            assert_eq!(mode, Some(L1BatchCommitmentMode::Validium));
+        }
+        conn.blocks_dal()
+            .set_l1_batch_commitment_mode(L1BatchNumber(4), L1BatchCommitmentMode::Validium)
+            .await
+            .unwrap_err();
+    }
 }
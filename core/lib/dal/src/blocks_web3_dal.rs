@@ -653,6 +653,53 @@ impl BlocksWeb3Dal<'_, '_> {
         Ok((base_fee_per_gas, effective_pubdata_price))
     }
 
+    /// Returns `base_fee_per_gas` and the batch fee input for L2 blocks in the specified range
+    /// in ascending order of L2 block numbers.
+    pub async fn get_fee_inputs_for_l2_blocks(
+        &mut self,
+        from_block: L2BlockNumber,
+        to_block: L2BlockNumber,
+    ) -> DalResult<Vec<(U256, BatchFeeInput)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                base_fee_per_gas,
+                l2_fair_gas_price,
+                fair_pubdata_price,
+                protocol_version,
+                l1_gas_price
+            FROM
+                miniblocks
+            WHERE
+                number BETWEEN $1 AND $2
+            ORDER BY
+                number
+            "#,
+            i64::from(from_block.0),
+            i64::from(to_block.0)
+        )
+        .instrument("get_fee_inputs_for_l2_blocks")
+        .with_arg("from_block", &from_block)
+        .with_arg("to_block", &to_block)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let fee_input = BatchFeeInput::for_protocol_version(
+                    row.protocol_version
+                        .map(|x| (x as u16).try_into().unwrap())
+                        .unwrap_or_else(ProtocolVersionId::last_potentially_undefined),
+                    row.l2_fair_gas_price as u64,
+                    row.fair_pubdata_price.map(|x| x as u64),
+                    row.l1_gas_price as u64,
+                );
+                (bigdecimal_to_u256(row.base_fee_per_gas), fee_input)
+            })
+            .collect())
+    }
+
     pub async fn get_block_details(
         &mut self,
         block_number: L2BlockNumber,
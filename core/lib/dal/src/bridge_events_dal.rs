@@ -0,0 +1,401 @@
+use zksync_db_connection::{
+    connection::Connection,
+    error::{DalResult, SqlxContext},
+    instrument::InstrumentExt,
+};
+use zksync_types::{
+    api::{BridgeTransfer, BridgeTransferKind},
+    Address, L2BlockNumber, H256, U256,
+};
+
+use crate::{
+    models::{bigdecimal_to_u256, u256_to_big_decimal},
+    Core,
+};
+
+/// Location of an indexed bridge event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BridgeEventLocation {
+    /// Event emitted on L1.
+    L1 { block_number: u64, log_index: u32 },
+    /// Event emitted on L2.
+    L2 {
+        block_number: L2BlockNumber,
+        event_index: u32,
+    },
+}
+
+/// Bridge event indexed by the bridge indexer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BridgeEvent {
+    pub kind: BridgeTransferKind,
+    pub l1_address: Option<Address>,
+    pub l2_address: Option<Address>,
+    pub l1_token: Option<Address>,
+    pub l2_token: Option<Address>,
+    pub amount: U256,
+    pub tx_hash: H256,
+    pub location: BridgeEventLocation,
+}
+
+/// DAL methods related to indexed L1 and L2 bridge events.
+#[derive(Debug)]
+pub struct BridgeEventsDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl BridgeEventsDal<'_, '_> {
+    /// Inserts the provided events. Events that are already indexed (i.e., have the same location) are skipped,
+    /// so it's safe to insert the same events multiple times.
+    pub async fn insert_bridge_events(&mut self, events: &[BridgeEvent]) -> DalResult<()> {
+        for event in events {
+            let (l1_block_number, l1_log_index, l2_block_number, l2_event_index) =
+                match event.location {
+                    BridgeEventLocation::L1 {
+                        block_number,
+                        log_index,
+                    } => (
+                        Some(block_number as i64),
+                        Some(log_index as i32),
+                        None,
+                        None,
+                    ),
+                    BridgeEventLocation::L2 {
+                        block_number,
+                        event_index,
+                    } => (
+                        None,
+                        None,
+                        Some(i64::from(block_number.0)),
+                        Some(event_index as i32),
+                    ),
+                };
+
+            sqlx::query!(
+                r#"
+                INSERT INTO
+                bridge_events (
+                    kind,
+                    l1_address,
+                    l2_address,
+                    l1_token,
+                    l2_token,
+                    amount,
+                    tx_hash,
+                    l1_block_number,
+                    l1_log_index,
+                    l2_block_number,
+                    l2_event_index,
+                    created_at
+                )
+                VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, NOW())
+                ON CONFLICT DO NOTHING
+                "#,
+                event.kind.as_ref(),
+                event.l1_address.as_ref().map(Address::as_bytes),
+                event.l2_address.as_ref().map(Address::as_bytes),
+                event.l1_token.as_ref().map(Address::as_bytes),
+                event.l2_token.as_ref().map(Address::as_bytes),
+                u256_to_big_decimal(event.amount),
+                event.tx_hash.as_bytes(),
+                l1_block_number,
+                l1_log_index,
+                l2_block_number,
+                l2_event_index
+            )
+            .instrument("insert_bridge_events")
+            .with_arg("location", &event.location)
+            .execute(self.storage)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Removes events emitted in L1 blocks starting from `from_l1_block`. Used to roll back events
+    /// from L1 blocks removed by a reorg. Returns the number of removed events.
+    pub async fn rollback_l1_events(&mut self, from_l1_block: u64) -> DalResult<u64> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM bridge_events
+            WHERE
+                l1_block_number >= $1
+            "#,
+            from_l1_block as i64
+        )
+        .instrument("rollback_l1_events")
+        .with_arg("from_l1_block", &from_l1_block)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Returns the latest L2 block (its number and hash) up to which events were indexed.
+    pub async fn get_last_l2_checkpoint(&mut self) -> DalResult<Option<(L2BlockNumber, H256)>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                l2_block_number,
+                l2_block_hash
+            FROM
+                bridge_indexer_l2_checkpoints
+            ORDER BY
+                l2_block_number DESC
+            LIMIT
+                1
+            "#
+        )
+        .instrument("get_last_l2_checkpoint")
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(|row| {
+            (
+                L2BlockNumber(row.l2_block_number as u32),
+                H256::from_slice(&row.l2_block_hash),
+            )
+        }))
+    }
+
+    /// Marks events up to the specified L2 block (inclusive) as indexed.
+    pub async fn insert_l2_checkpoint(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+        l2_block_hash: H256,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            bridge_indexer_l2_checkpoints (l2_block_number, l2_block_hash, created_at)
+            VALUES
+            ($1, $2, NOW())
+            "#,
+            i64::from(l2_block_number.0),
+            l2_block_hash.as_bytes()
+        )
+        .instrument("insert_l2_checkpoint")
+        .with_arg("l2_block_number", &l2_block_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Removes events emitted in L2 blocks after `last_retained_l2_block` together with the corresponding
+    /// checkpoints. If `last_retained_l2_block` is `None`, all L2 events are removed.
+    pub async fn rollback_l2_events(
+        &mut self,
+        last_retained_l2_block: Option<L2BlockNumber>,
+    ) -> DalResult<()> {
+        let last_retained = last_retained_l2_block.map_or(-1, |number| i64::from(number.0));
+        sqlx::query!(
+            r#"
+            DELETE FROM bridge_events
+            WHERE
+                l2_block_number > $1
+            "#,
+            last_retained
+        )
+        .instrument("rollback_l2_events")
+        .with_arg("last_retained_l2_block", &last_retained_l2_block)
+        .execute(self.storage)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM bridge_indexer_l2_checkpoints
+            WHERE
+                l2_block_number > $1
+            "#,
+            last_retained
+        )
+        .instrument("rollback_l2_events#remove_checkpoints")
+        .with_arg("last_retained_l2_block", &last_retained_l2_block)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns up to `limit` events affecting the specified account (i.e., having it as the L1 or L2 address)
+    /// in the indexing order, starting after the event with `after_id` ID. Each event is returned with its ID,
+    /// which can be used to get the following events.
+    pub async fn get_bridge_transfers(
+        &mut self,
+        account: Address,
+        after_id: Option<u64>,
+        limit: usize,
+    ) -> DalResult<Vec<(u64, BridgeTransfer)>> {
+        sqlx::query!(
+            r#"
+            SELECT
+                id,
+                kind,
+                l1_address,
+                l2_address,
+                l1_token,
+                l2_token,
+                amount,
+                tx_hash,
+                l1_block_number,
+                l2_block_number
+            FROM
+                bridge_events
+            WHERE
+                (
+                    l1_address = $1
+                    OR l2_address = $1
+                )
+                AND id > $2
+            ORDER BY
+                id
+            LIMIT
+                $3
+            "#,
+            account.as_bytes(),
+            after_id.map_or(0, |id| id as i64),
+            limit as i64
+        )
+        .try_map(|row| {
+            let transfer = BridgeTransfer {
+                kind: row.kind.parse().decode_column("kind")?,
+                l1_address: row.l1_address.as_deref().map(Address::from_slice),
+                l2_address: row.l2_address.as_deref().map(Address::from_slice),
+                l1_token: row.l1_token.as_deref().map(Address::from_slice),
+                l2_token: row.l2_token.as_deref().map(Address::from_slice),
+                amount: bigdecimal_to_u256(row.amount),
+                tx_hash: H256::from_slice(&row.tx_hash),
+                l1_block_number: row.l1_block_number.map(|number| (number as u64).into()),
+                l2_block_number: row
+                    .l2_block_number
+                    .map(|number| L2BlockNumber(number as u32)),
+            };
+            Ok((row.id as u64, transfer))
+        })
+        .instrument("get_bridge_transfers")
+        .with_arg("account", &account)
+        .with_arg("after_id", &after_id)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::ProtocolVersion;
+
+    use super::*;
+    use crate::{tests::create_l2_block_header, ConnectionPool, CoreDal};
+
+    const ACCOUNT: Address = Address::repeat_byte(0x11);
+
+    fn l1_event(kind: BridgeTransferKind, block_number: u64) -> BridgeEvent {
+        BridgeEvent {
+            kind,
+            l1_address: Some(ACCOUNT),
+            l2_address: None,
+            l1_token: Some(Address::repeat_byte(0x22)),
+            l2_token: None,
+            amount: 100.into(),
+            tx_hash: H256::from_low_u64_be(block_number),
+            location: BridgeEventLocation::L1 {
+                block_number,
+                log_index: 0,
+            },
+        }
+    }
+
+    fn l2_event(kind: BridgeTransferKind, block_number: u32) -> BridgeEvent {
+        BridgeEvent {
+            kind,
+            l1_address: Some(Address::repeat_byte(0x33)),
+            l2_address: Some(ACCOUNT),
+            l1_token: None,
+            l2_token: Some(Address::repeat_byte(0x44)),
+            amount: U256::from(10).pow(30.into()),
+            tx_hash: H256::repeat_byte(block_number as u8),
+            location: BridgeEventLocation::L2 {
+                block_number: L2BlockNumber(block_number),
+                event_index: 1,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn indexing_and_rolling_back_bridge_events() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in 1..=2 {
+            conn.blocks_dal()
+                .insert_l2_block(&create_l2_block_header(number))
+                .await
+                .unwrap();
+        }
+
+        let mut dal = conn.bridge_events_dal();
+        let events = [
+            l1_event(BridgeTransferKind::DepositInitiated, 10),
+            l2_event(BridgeTransferKind::DepositFinalized, 1),
+            l2_event(BridgeTransferKind::WithdrawalInitiated, 2),
+            l1_event(BridgeTransferKind::WithdrawalFinalized, 20),
+        ];
+        dal.insert_bridge_events(&events).await.unwrap();
+        // Repeated insertion should be a no-op.
+        dal.insert_bridge_events(&events[..2]).await.unwrap();
+        dal.insert_l2_checkpoint(L2BlockNumber(2), H256::repeat_byte(2))
+            .await
+            .unwrap();
+        assert_eq!(
+            dal.get_last_l2_checkpoint().await.unwrap(),
+            Some((L2BlockNumber(2), H256::repeat_byte(2)))
+        );
+
+        let transfers = dal.get_bridge_transfers(ACCOUNT, None, 10).await.unwrap();
+        let kinds: Vec<_> = transfers
+            .iter()
+            .map(|(_, transfer)| transfer.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                BridgeTransferKind::DepositInitiated,
+                BridgeTransferKind::DepositFinalized,
+                BridgeTransferKind::WithdrawalInitiated,
+                BridgeTransferKind::WithdrawalFinalized,
+            ]
+        );
+        assert_eq!(transfers[1].1.l2_block_number, Some(L2BlockNumber(1)));
+        assert_eq!(transfers[1].1.amount, events[1].amount);
+
+        let page = dal
+            .get_bridge_transfers(ACCOUNT, Some(transfers[1].0), 1)
+            .await
+            .unwrap();
+        assert_eq!(page, [transfers[2].clone()]);
+        let other_transfers = dal
+            .get_bridge_transfers(Address::repeat_byte(0x33), None, 10)
+            .await
+            .unwrap();
+        assert_eq!(other_transfers.len(), 2);
+
+        assert_eq!(dal.rollback_l1_events(15).await.unwrap(), 1);
+        dal.rollback_l2_events(Some(L2BlockNumber(1)))
+            .await
+            .unwrap();
+        assert_eq!(dal.get_last_l2_checkpoint().await.unwrap(), None);
+        let transfers = dal.get_bridge_transfers(ACCOUNT, None, 10).await.unwrap();
+        let kinds: Vec<_> = transfers
+            .iter()
+            .map(|(_, transfer)| transfer.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                BridgeTransferKind::DepositInitiated,
+                BridgeTransferKind::DepositFinalized
+            ]
+        );
+    }
+}
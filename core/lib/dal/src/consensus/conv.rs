@@ -62,6 +62,11 @@ impl ProtoFmt for GlobalConfig {
                 .map(|a| parse_h160(a))
                 .transpose()
                 .context("registry_address")?,
+            attester_committee_epoch: r
+                .attester_committee_epoch
+                .map(|x| NonZeroU64::new(x).context("must be positive"))
+                .transpose()
+                .context("attester_committee_epoch")?,
             seed_peers: r
                 .seed_peers
                 .iter()
@@ -76,6 +81,7 @@ impl ProtoFmt for GlobalConfig {
         Self::Proto {
             genesis: Some(self.genesis.build()),
             registry_address: self.registry_address.map(|a| a.as_bytes().to_vec()),
+            attester_committee_epoch: self.attester_committee_epoch.map(NonZeroU64::get),
             seed_peers: self
                 .seed_peers
                 .iter()
@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, num::NonZeroU64};
 
 use zksync_concurrency::net;
 use zksync_consensus_roles::{attester, node, validator};
@@ -23,6 +23,9 @@ pub struct BlockMetadata {
 pub struct GlobalConfig {
     pub genesis: validator::Genesis,
     pub registry_address: Option<ethabi::Address>,
+    /// Length of an attester committee epoch in L1 batches. See `GenesisSpec::attester_committee_epoch`
+    /// in the consensus config.
+    pub attester_committee_epoch: Option<NonZeroU64>,
     pub seed_peers: BTreeMap<node::PublicKey, net::Host>,
 }
 
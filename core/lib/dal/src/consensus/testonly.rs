@@ -25,6 +25,7 @@ impl Distribution<GlobalConfig> for EncodeDist {
         GlobalConfig {
             genesis: rng.gen(),
             registry_address: Some(rng.gen()),
+            attester_committee_epoch: NonZeroU64::new(rng.gen()),
             seed_peers: self
                 .sample_range(rng)
                 .map(|_| (rng.gen(), self.sample(rng)))
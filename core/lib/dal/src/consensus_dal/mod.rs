@@ -50,6 +50,7 @@ pub fn verify_config_transition(old: &GlobalConfig, new: &GlobalConfig) -> anyho
         == (&GlobalConfig {
             genesis: old.genesis.clone(),
             registry_address: None,
+            attester_committee_epoch: None,
             seed_peers: [].into(),
         })
     {
@@ -112,6 +113,7 @@ impl ConsensusDal<'_, '_> {
             return Ok(Some(GlobalConfig {
                 genesis,
                 registry_address: None,
+                attester_committee_epoch: None,
                 seed_peers: [].into(),
             }));
         }
@@ -239,6 +241,7 @@ impl ConsensusDal<'_, '_> {
             }
             .with_hash(),
             registry_address: old.registry_address,
+            attester_committee_epoch: old.attester_committee_epoch,
             seed_peers: old.seed_peers,
         };
         txn.consensus_dal().try_update_global_config(&new).await?;
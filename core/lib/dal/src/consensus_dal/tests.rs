@@ -26,6 +26,7 @@ async fn replica_state_read_write() {
         let cfg = GlobalConfig {
             genesis: genesis.with_hash(),
             registry_address: Some(rng.gen()),
+            attester_committee_epoch: None,
             seed_peers: [].into(), // TODO: rng.gen() for Host
         };
         conn.consensus_dal()
@@ -61,6 +62,7 @@ async fn test_batch_certificate() {
     let cfg = GlobalConfig {
         genesis: setup.genesis.clone(),
         registry_address: Some(rng.gen()),
+        attester_committee_epoch: None,
         seed_peers: [].into(),
     };
     conn.consensus_dal()
@@ -134,6 +136,7 @@ async fn test_batch_certificate() {
                     aggregation_root: rng.gen(),
                     local_root: rng.gen(),
                     state_diff_hash: rng.gen(),
+                    aux_output_preimage: None,
                 },
             )
             .await
@@ -560,6 +560,79 @@ impl ContractVerificationDal<'_, '_> {
         .await?
         .flatten())
     }
+
+    /// Returns up to `limit` verification info entries ordered by contract address, starting after the `after` address
+    /// (or from the start if `after` is not specified). Used to export verification info.
+    pub async fn get_verification_info_page(
+        &mut self,
+        after: Option<Address>,
+        limit: usize,
+    ) -> DalResult<Vec<VerificationInfo>> {
+        let after_bytes = after.as_ref().map_or(&[][..], Address::as_bytes);
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                verification_info
+            FROM
+                contracts_verification_info
+            WHERE
+                address > $1
+            ORDER BY
+                address
+            LIMIT
+                $2
+            "#,
+            after_bytes,
+            limit as i64
+        )
+        .try_map(|row| {
+            row.verification_info
+                .map(|info| serde_json::from_value(info).decode_column("verification_info"))
+                .transpose()
+        })
+        .instrument("get_verification_info_page")
+        .with_arg("after", &after)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+        Ok(rows.into_iter().flatten().collect())
+    }
+
+    /// Inserts verification info obtained from another environment. Existing info for the same address
+    /// is only replaced if `overwrite` is set. Returns whether the info was persisted.
+    pub async fn import_verification_info(
+        &mut self,
+        verification_info: &VerificationInfo,
+        overwrite: bool,
+    ) -> DalResult<bool> {
+        let address = verification_info.request.req.contract_address;
+        // Serialization should always succeed.
+        let verification_info_json = serde_json::to_value(verification_info)
+            .expect("Failed to serialize verification info into serde_json");
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO
+            contracts_verification_info (address, verification_info)
+            VALUES
+            ($1, $2)
+            ON CONFLICT (address) DO
+            UPDATE
+            SET
+            verification_info = $2
+            WHERE
+            $3
+            "#,
+            address.as_bytes(),
+            &verification_info_json,
+            overwrite
+        )
+        .instrument("import_verification_info")
+        .with_arg("address", &address)
+        .with_arg("overwrite", &overwrite)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
 }
 
 #[cfg(test)]
@@ -568,7 +641,9 @@ mod tests {
 
     use zksync_types::{
         bytecode::BytecodeHash,
-        contract_verification_api::{CompilerVersions, SourceCodeData},
+        contract_verification_api::{
+            testonly::mock_verification_info, CompilerVersions, SourceCodeData,
+        },
         tx::IncludedTxLocation,
         Execute, L1BatchNumber, L2BlockNumber, ProtocolVersion,
     };
@@ -709,4 +784,59 @@ mod tests {
         test_working_with_verification_requests(None).await;
         test_working_with_verification_requests(Some("1.5.7")).await;
     }
+
+    #[tokio::test]
+    async fn importing_and_exporting_verification_info() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.contract_verification_dal();
+
+        let addresses = [
+            Address::zero(),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+        ];
+        for address in addresses {
+            let info = mock_verification_info(address);
+            assert!(dal.import_verification_info(&info, false).await.unwrap());
+        }
+
+        let page = dal.get_verification_info_page(None, 2).await.unwrap();
+        let page_addresses: Vec<_> = page
+            .iter()
+            .map(|info| info.request.req.contract_address)
+            .collect();
+        assert_eq!(page_addresses, addresses[..2]);
+        let page = dal
+            .get_verification_info_page(Some(addresses[1]), 2)
+            .await
+            .unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].request.req.contract_address, addresses[2]);
+
+        // Existing info must not be replaced unless requested.
+        let mut updated_info = mock_verification_info(addresses[0]);
+        updated_info.request.req.contract_name = "Updated".to_owned();
+        assert!(!dal
+            .import_verification_info(&updated_info, false)
+            .await
+            .unwrap());
+        let info = dal
+            .get_contract_verification_info(addresses[0])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.request.req.contract_name, "Test");
+
+        assert!(dal
+            .import_verification_info(&updated_info, true)
+            .await
+            .unwrap());
+        let info = dal
+            .get_contract_verification_info(addresses[0])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.request.req.contract_name, "Updated");
+    }
 }
@@ -83,6 +83,36 @@ impl DataAvailabilityDal<'_, '_> {
         Ok(())
     }
 
+    /// Inserts the ID of the blob dispatched to the fallback DA client for the given L1 batch. Independent
+    /// of [`Self::insert_l1_batch_da()`], so the fallback blob can be stored before or without the primary one.
+    /// If the fallback blob is already present, the call is a no-op.
+    pub async fn insert_l1_batch_fallback_da(
+        &mut self,
+        number: L1BatchNumber,
+        blob_id: &str,
+        sent_at: chrono::NaiveDateTime,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            data_availability_fallback (l1_batch_number, blob_id, sent_at, created_at, updated_at)
+            VALUES
+            ($1, $2, $3, NOW(), NOW())
+            ON CONFLICT DO NOTHING
+            "#,
+            i64::from(number.0),
+            blob_id,
+            sent_at,
+        )
+        .instrument("insert_l1_batch_fallback_da")
+        .with_arg("number", &number)
+        .with_arg("blob_id", &blob_id)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
     /// Saves the inclusion data for the given L1 batch. If the inclusion data is already present,
     /// verifies that it matches the one provided in the function arguments
     /// (meaning that the inclusion data corresponds to the same DA blob)
@@ -155,16 +185,20 @@ impl DataAvailabilityDal<'_, '_> {
             StorageDABlob,
             r#"
             SELECT
-                l1_batch_number,
-                blob_id,
-                inclusion_data,
-                sent_at
+                data_availability.l1_batch_number,
+                data_availability.blob_id,
+                data_availability_fallback.blob_id AS "fallback_blob_id?",
+                data_availability.inclusion_data,
+                data_availability.sent_at
             FROM
                 data_availability
+            LEFT JOIN
+                data_availability_fallback
+                ON data_availability_fallback.l1_batch_number = data_availability.l1_batch_number
             WHERE
-                inclusion_data IS NULL
+                data_availability.inclusion_data IS NULL
             ORDER BY
-                l1_batch_number
+                data_availability.l1_batch_number
             LIMIT
                 1
             "#,
@@ -216,4 +250,47 @@ impl DataAvailabilityDal<'_, '_> {
             })
             .collect())
     }
+
+    /// Fetches the pubdata and `l1_batch_number` for the L1 batches that are ready for dispatch
+    /// to the fallback DA client. Doesn't depend on whether the batches were dispatched to the primary client.
+    pub async fn get_ready_for_fallback_da_dispatch_l1_batches(
+        &mut self,
+        limit: usize,
+    ) -> DalResult<Vec<L1BatchDA>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                number,
+                pubdata_input
+            FROM
+                l1_batches
+            LEFT JOIN
+                data_availability_fallback
+                ON data_availability_fallback.l1_batch_number = l1_batches.number
+            WHERE
+                eth_commit_tx_id IS NULL
+                AND number != 0
+                AND data_availability_fallback.blob_id IS NULL
+                AND pubdata_input IS NOT NULL
+            ORDER BY
+                number
+            LIMIT
+                $1
+            "#,
+            limit as i64,
+        )
+        .instrument("get_ready_for_fallback_da_dispatch_l1_batches")
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| L1BatchDA {
+                // `unwrap` is safe here because we have a `WHERE` clause that filters out `NULL` values
+                pubdata: row.pubdata_input.unwrap(),
+                l1_batch_number: L1BatchNumber(row.number as u32),
+            })
+            .collect())
+    }
 }
@@ -1,10 +1,10 @@
-use std::{convert::TryFrom, str::FromStr};
+use std::{convert::TryFrom, str::FromStr, time::Duration};
 
 use anyhow::Context as _;
 use sqlx::types::chrono::{DateTime, Utc};
 use zksync_db_connection::{
     connection::Connection, error::DalResult, instrument::InstrumentExt, interpolate_query,
-    match_query_as,
+    match_query_as, utils::pg_interval_from_duration,
 };
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
@@ -445,6 +445,25 @@ impl EthSenderDal<'_, '_> {
         Ok(row.and_then(|r| r.chain_id).map(|id| SLChainId(id as u64)))
     }
 
+    pub async fn get_eth_tx_chain_id(&mut self, eth_tx_id: u32) -> DalResult<Option<SLChainId>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                chain_id
+            FROM
+                eth_txs
+            WHERE
+                id = $1
+            "#,
+            eth_tx_id as i32
+        )
+        .instrument("get_eth_tx_chain_id")
+        .with_arg("eth_tx_id", &eth_tx_id)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.and_then(|r| r.chain_id).map(|id| SLChainId(id as u64)))
+    }
+
     pub async fn get_confirmed_tx_hash_by_eth_tx_id(
         &mut self,
         eth_tx_id: u32,
@@ -774,6 +793,138 @@ impl EthSenderDal<'_, '_> {
 
         Ok(())
     }
+
+    /// Moves up to `limit` history entries created more than `retention` ago to the `eth_txs_history_archive` table.
+    /// Only entries superseded by the confirmed history entry of a transaction are archived; confirmed entries
+    /// and entries of unconfirmed transactions are always kept. Returns the number of archived entries.
+    pub async fn archive_tx_history(
+        &mut self,
+        retention: Duration,
+        limit: usize,
+    ) -> DalResult<usize> {
+        let retention = pg_interval_from_duration(retention);
+        let result = sqlx::query!(
+            r#"
+            WITH
+            archived_history AS (
+                DELETE FROM eth_txs_history
+                WHERE
+                    id IN (
+                        SELECT
+                            eth_txs_history.id
+                        FROM
+                            eth_txs_history
+                        JOIN eth_txs ON eth_txs.id = eth_txs_history.eth_tx_id
+                        WHERE
+                            eth_txs.confirmed_eth_tx_history_id IS NOT NULL
+                            AND eth_txs_history.id != eth_txs.confirmed_eth_tx_history_id
+                            AND eth_txs_history.created_at < NOW() - $1::INTERVAL
+                        ORDER BY
+                            eth_txs_history.id
+                        LIMIT
+                            $2
+                    )
+                RETURNING
+                *
+            )
+            
+            INSERT INTO
+            eth_txs_history_archive (
+                id,
+                eth_tx_id,
+                tx_hash,
+                base_fee_per_gas,
+                priority_fee_per_gas,
+                blob_base_fee_per_gas,
+                signed_raw_tx,
+                sent_at_block,
+                sent_at,
+                confirmed_at,
+                created_at,
+                updated_at,
+                archived_at
+            )
+            SELECT
+                id,
+                eth_tx_id,
+                tx_hash,
+                base_fee_per_gas,
+                priority_fee_per_gas,
+                blob_base_fee_per_gas,
+                signed_raw_tx,
+                sent_at_block,
+                sent_at,
+                confirmed_at,
+                created_at,
+                updated_at,
+                NOW()
+            FROM
+                archived_history
+            "#,
+            retention,
+            limit as i64
+        )
+        .instrument("archive_tx_history")
+        .with_arg("retention", &retention)
+        .with_arg("limit", &limit)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected() as usize)
+    }
+
+    /// Moves archived history entries for the specified transaction back to the `eth_txs_history` table.
+    /// Returns the number of restored entries.
+    pub async fn restore_archived_tx_history(&mut self, eth_tx_id: u32) -> DalResult<usize> {
+        let result = sqlx::query!(
+            r#"
+            WITH
+            restored_history AS (
+                DELETE FROM eth_txs_history_archive
+                WHERE
+                    eth_tx_id = $1
+                RETURNING
+                *
+            )
+            
+            INSERT INTO
+            eth_txs_history (
+                id,
+                eth_tx_id,
+                tx_hash,
+                base_fee_per_gas,
+                priority_fee_per_gas,
+                blob_base_fee_per_gas,
+                signed_raw_tx,
+                sent_at_block,
+                sent_at,
+                confirmed_at,
+                created_at,
+                updated_at
+            )
+            SELECT
+                id,
+                eth_tx_id,
+                tx_hash,
+                base_fee_per_gas,
+                priority_fee_per_gas,
+                blob_base_fee_per_gas,
+                signed_raw_tx,
+                sent_at_block,
+                sent_at,
+                confirmed_at,
+                created_at,
+                updated_at
+            FROM
+                restored_history
+            "#,
+            eth_tx_id as i32
+        )
+        .instrument("restore_archived_tx_history")
+        .with_arg("eth_tx_id", &eth_tx_id)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected() as usize)
+    }
 }
 
 /// These methods should only be used for tests.
@@ -827,3 +978,94 @@ impl EthSenderDal<'_, '_> {
         self.get_last_sent_eth_tx(eth_tx_id).await.unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn archiving_and_restoring_tx_history() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+
+        let mut eth_tx_ids = vec![];
+        for nonce in 0..2 {
+            let eth_tx = conn
+                .eth_sender_dal()
+                .save_eth_tx(
+                    nonce,
+                    vec![],
+                    AggregatedActionType::Execute,
+                    Address::repeat_byte(1),
+                    None,
+                    None,
+                    None,
+                    false,
+                )
+                .await
+                .unwrap();
+            for i in 0..3 {
+                let tx_hash = H256::from_low_u64_be(nonce * 10 + i);
+                conn.eth_sender_dal()
+                    .insert_tx_history(eth_tx.id, 100 + i, 10, None, tx_hash, &[], 1)
+                    .await
+                    .unwrap();
+            }
+            eth_tx_ids.push(eth_tx.id);
+        }
+        // Only the first transaction is confirmed.
+        conn.eth_sender_dal()
+            .confirm_tx(H256::from_low_u64_be(2), 21_000.into())
+            .await
+            .unwrap();
+
+        let archived = conn
+            .eth_sender_dal()
+            .archive_tx_history(Duration::from_secs(3_600), 100)
+            .await
+            .unwrap();
+        assert_eq!(archived, 0);
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let archived = conn
+            .eth_sender_dal()
+            .archive_tx_history(Duration::ZERO, 1)
+            .await
+            .unwrap();
+        assert_eq!(archived, 1);
+        let archived = conn
+            .eth_sender_dal()
+            .archive_tx_history(Duration::ZERO, 100)
+            .await
+            .unwrap();
+        assert_eq!(archived, 1);
+
+        let history = conn
+            .eth_sender_dal()
+            .get_tx_history_to_check(eth_tx_ids[0])
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].tx_hash, H256::from_low_u64_be(2));
+        let history = conn
+            .eth_sender_dal()
+            .get_tx_history_to_check(eth_tx_ids[1])
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 3);
+
+        let restored = conn
+            .eth_sender_dal()
+            .restore_archived_tx_history(eth_tx_ids[0])
+            .await
+            .unwrap();
+        assert_eq!(restored, 2);
+        let history = conn
+            .eth_sender_dal()
+            .get_tx_history_to_check(eth_tx_ids[0])
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 3);
+    }
+}
@@ -13,6 +13,8 @@ pub enum EventType {
     ProtocolUpgrades,
     PriorityTransactions,
     ChainBatchRoot,
+    CustomEvents,
+    BridgeEvents,
 }
 
 impl EthWatcherDal<'_, '_> {
@@ -64,9 +64,25 @@ impl EventsWeb3Dal<'_, '_> {
     }
 
     /// Returns logs for given filter.
-    #[allow(clippy::type_complexity)]
     pub async fn get_logs(&mut self, filter: GetLogsFilter, limit: usize) -> DalResult<Vec<Log>> {
-        let (where_sql, arg_index) = self.build_get_logs_where_clause(&filter);
+        self.get_logs_after(&filter, None, limit).await
+    }
+
+    /// Returns logs for given filter that are located strictly after the `after` position, which is specified
+    /// as the L2 block number and the log index in this block. Used for paginating over logs.
+    pub async fn get_logs_after(
+        &mut self,
+        filter: &GetLogsFilter,
+        after: Option<(L2BlockNumber, u32)>,
+        limit: usize,
+    ) -> DalResult<Vec<Log>> {
+        let (mut where_sql, arg_index) = self.build_get_logs_where_clause(filter);
+        if let Some((block_number, log_index)) = after {
+            where_sql += &format!(
+                " AND ((miniblock_number, event_index_in_block) > ({}, {}))",
+                block_number.0, log_index
+            );
+        }
         let query = format!(
             r#"
             WITH events_select AS (
@@ -107,7 +123,8 @@ impl EventsWeb3Dal<'_, '_> {
         let db_logs: Vec<StorageWeb3Log> = query
             .instrument("get_logs")
             .report_latency()
-            .with_arg("filter", &filter)
+            .with_arg("filter", filter)
+            .with_arg("after", &after)
             .with_arg("limit", &limit)
             .fetch_all(self.storage)
             .await?;
@@ -5,7 +5,7 @@ use zksync_contracts::{BaseSystemContracts, SystemContractCode};
 use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
 use zksync_types::{L2BlockNumber, H256, U256};
 
-use crate::Core;
+use crate::{Core, CoreDal};
 
 /// DAL methods related to factory dependencies.
 #[derive(Debug)]
@@ -89,6 +89,22 @@ impl FactoryDepsDal<'_, '_> {
         .map(|row| row.bytecode))
     }
 
+    /// Returns bytecode of a base system contract with the specified hash. Besides factory deps from sealed miniblocks,
+    /// looks up contracts staged ahead of a protocol upgrade and validated against the upgrade on L1. The latter
+    /// allows executing the upgrade batch before the new base system contracts are published in a miniblock.
+    async fn get_base_system_contract_bytecode(
+        &mut self,
+        hash: H256,
+    ) -> DalResult<Option<Vec<u8>>> {
+        if let Some(bytecode) = self.get_sealed_factory_dep(hash).await? {
+            return Ok(Some(bytecode));
+        }
+        self.storage
+            .staged_base_system_contracts_dal()
+            .get_validated_bytecode(hash)
+            .await
+    }
+
     pub async fn get_base_system_contracts(
         &mut self,
         bootloader_hash: H256,
@@ -96,7 +112,7 @@ impl FactoryDepsDal<'_, '_> {
         evm_emulator_hash: Option<H256>,
     ) -> anyhow::Result<BaseSystemContracts> {
         let bootloader_bytecode = self
-            .get_sealed_factory_dep(bootloader_hash)
+            .get_base_system_contract_bytecode(bootloader_hash)
             .await
             .context("failed loading bootloader code")?
             .with_context(|| format!("bootloader code with hash {bootloader_hash:?} should be present in the database"))?;
@@ -106,7 +122,7 @@ impl FactoryDepsDal<'_, '_> {
         };
 
         let default_aa_bytecode = self
-            .get_sealed_factory_dep(default_aa_hash)
+            .get_base_system_contract_bytecode(default_aa_hash)
             .await
             .context("failed loading default account code")?
             .with_context(|| format!("default account code with hash {default_aa_hash:?} should be present in the database"))?;
@@ -118,7 +134,7 @@ impl FactoryDepsDal<'_, '_> {
 
         let evm_emulator_code = if let Some(evm_emulator_hash) = evm_emulator_hash {
             let evm_emulator_bytecode = self
-                .get_sealed_factory_dep(evm_emulator_hash)
+                .get_base_system_contract_bytecode(evm_emulator_hash)
                 .await
                 .context("failed loading EVM emulator code")?
                 .with_context(|| format!("EVM emulator code with hash {evm_emulator_hash:?} should be present in the database"))?;
@@ -0,0 +1,781 @@
+//! Deterministic fixtures for tests working with Postgres. Not intended to be used in production code.
+//!
+//! [`ChainFixture`] generates a consistent chain of L1 batches and L2 blocks (with transactions, events,
+//! storage logs and proof generation jobs) from a seed, and inserts it into Postgres. Fixtures generated
+//! with the same [`ChainFixtureParams`] are identical, so tests can rely on specific generated values.
+
+use std::collections::HashSet;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use zksync_contracts::BaseSystemContractsHashes;
+use zksync_system_constants::ZKPORTER_IS_AVAILABLE;
+use zksync_types::{
+    aggregated_operations::AggregatedActionType,
+    block::{build_bloom, L1BatchHeader, L2BlockHasher, L2BlockHeader},
+    commitment::{
+        AuxCommitments, L1BatchCommitmentArtifacts, L1BatchCommitmentHash, L1BatchMetaParameters,
+        L1BatchMetadata,
+    },
+    fee::Fee,
+    fee_model::BatchFeeInput,
+    l2::L2Tx,
+    l2_to_l1_log::UserL2ToL1Log,
+    protocol_version::ProtocolSemanticVersion,
+    transaction_request::PaymasterParams,
+    tx::IncludedTxLocation,
+    AccountTreeId, Address, BloomInput, K256PrivateKey, L1BatchNumber, L2BlockNumber, L2ChainId,
+    Nonce, ProtocolVersion, ProtocolVersionId, SLChainId, StorageKey, StorageLog, H256, U256,
+};
+use zksync_vm_interface::{
+    TransactionExecutionResult, TxExecutionStatus, VmEvent, VmExecutionMetrics,
+};
+
+use crate::{Connection, Core, CoreDal};
+
+/// Value for recent protocol versions.
+const MAX_GAS_PER_PUBDATA_BYTE: u64 = 50_000;
+
+/// Number of accounts sending generated transactions.
+const ACCOUNT_COUNT: usize = 4;
+/// Number of contracts called by generated transactions.
+const CONTRACT_COUNT: usize = 4;
+/// Number of distinct event topics. Kept small so that log filters match multiple events.
+const TOPIC_COUNT: usize = 8;
+/// Number of storage slots per contract. Kept small so that generated storage logs contain repeated writes.
+const SLOTS_PER_CONTRACT: u64 = 16;
+const BASE_FEE_PER_GAS: u64 = 100;
+
+/// Parameters of a generated [`ChainFixture`].
+#[derive(Debug, Clone)]
+pub struct ChainFixtureParams {
+    /// Seed for the RNG used to generate the fixture.
+    pub seed: u64,
+    /// Number of the first generated L1 batch.
+    pub first_l1_batch: L1BatchNumber,
+    /// Number of the first generated L2 block.
+    pub first_l2_block: L2BlockNumber,
+    /// Hash of the L2 block preceding the first generated block; used to chain L2 block hashes.
+    pub prev_l2_block_hash: H256,
+    /// Timestamp of the first generated L2 block. Each following L2 block has the timestamp incremented by 1.
+    pub first_timestamp: u64,
+    pub l1_batch_count: u32,
+    pub l2_blocks_per_l1_batch: u32,
+    pub txs_per_l2_block: usize,
+    pub events_per_tx: usize,
+    pub storage_logs_per_tx: usize,
+    pub protocol_version: ProtocolVersionId,
+    /// Proof generation jobs inserted for generated L1 batches.
+    pub proof_generation: ProofGenerationFixture,
+    /// If set, generated L1 batches are marked as committed and executed on the settlement layer
+    /// with the specified chain ID.
+    pub executed_on: Option<SLChainId>,
+}
+
+impl Default for ChainFixtureParams {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            first_l1_batch: L1BatchNumber(1),
+            first_l2_block: L2BlockNumber(1),
+            prev_l2_block_hash: H256::zero(),
+            first_timestamp: 1,
+            l1_batch_count: 3,
+            l2_blocks_per_l1_batch: 2,
+            txs_per_l2_block: 3,
+            events_per_tx: 2,
+            storage_logs_per_tx: 2,
+            protocol_version: ProtocolVersionId::latest(),
+            proof_generation: ProofGenerationFixture::None,
+            executed_on: None,
+        }
+    }
+}
+
+/// Proof generation jobs inserted for each L1 batch in a [`ChainFixture`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofGenerationFixture {
+    /// No jobs are inserted.
+    None,
+    /// An unpicked job without input artifacts is inserted, as done by the Merkle tree.
+    Unpicked,
+    /// An unpicked job with VM run data and Merkle paths artifacts is inserted, so that the batch
+    /// can be locked for proving.
+    ReadyForProving,
+}
+
+impl ProofGenerationFixture {
+    /// Returns the URL of the VM run data artifact saved for the specified L1 batch.
+    pub fn vm_run_data_blob_url(l1_batch_number: L1BatchNumber) -> String {
+        format!("vm_run_data_{l1_batch_number}.bin")
+    }
+
+    /// Returns the URL of the Merkle paths artifact saved for the specified L1 batch.
+    pub fn merkle_paths_blob_url(l1_batch_number: L1BatchNumber) -> String {
+        format!("merkle_paths_{l1_batch_number}.bin")
+    }
+}
+
+/// Generated L2 block together with its contents.
+#[derive(Debug)]
+pub struct L2BlockFixture {
+    pub header: L2BlockHeader,
+    pub transactions: Vec<TransactionExecutionResult>,
+    /// Events emitted by each transaction in the block, in the format accepted by the events DAL.
+    pub events: Vec<(IncludedTxLocation, Vec<VmEvent>)>,
+    /// User L2-to-L1 logs sent by each transaction in the block. Not generated; tests may add logs
+    /// before inserting the fixture.
+    pub l2_to_l1_logs: Vec<(IncludedTxLocation, Vec<UserL2ToL1Log>)>,
+    pub storage_logs: Vec<StorageLog>,
+}
+
+/// Generated L1 batch together with its L2 blocks.
+#[derive(Debug)]
+pub struct L1BatchFixture {
+    pub header: L1BatchHeader,
+    pub metadata: L1BatchMetadata,
+    pub l2_blocks: Vec<L2BlockFixture>,
+}
+
+/// Deterministically generated chain of L1 batches and L2 blocks.
+///
+/// The generated chain is internally consistent: L2 block hashes are chained and commit to transaction hashes,
+/// transaction nonces are sequential for each account, and event indices are sequential within each L1 batch.
+/// Initial writes are derived from storage logs on insertion, so tests may amend generated storage logs
+/// (e.g., generate batches without transactions and add specific logs to them). The chain doesn't include
+/// genesis; it should be inserted after the L2 block and L1 batch preceding [`ChainFixtureParams::first_l2_block`]
+/// and [`ChainFixtureParams::first_l1_batch`] (if any). Accounts and contracts are randomly derived from the seed,
+/// so they are not expected to overlap with system contracts or data generated with other seeds.
+#[derive(Debug)]
+pub struct ChainFixture {
+    pub params: ChainFixtureParams,
+    pub l1_batches: Vec<L1BatchFixture>,
+}
+
+impl ChainFixture {
+    /// Generates a fixture with the specified params.
+    pub fn generate(params: ChainFixtureParams) -> Self {
+        let mut generator = FixtureGenerator::new(&params);
+        let l1_batches = (0..params.l1_batch_count)
+            .map(|_| generator.l1_batch())
+            .collect();
+        Self { params, l1_batches }
+    }
+
+    /// Iterates over all generated L2 blocks.
+    pub fn l2_blocks(&self) -> impl Iterator<Item = &L2BlockFixture> + '_ {
+        self.l1_batches.iter().flat_map(|batch| &batch.l2_blocks)
+    }
+
+    /// Iterates over all generated transactions.
+    pub fn transactions(&self) -> impl Iterator<Item = &TransactionExecutionResult> + '_ {
+        self.l2_blocks().flat_map(|block| &block.transactions)
+    }
+
+    /// Returns the header of the last generated L2 block.
+    pub fn last_l2_block(&self) -> Option<&L2BlockHeader> {
+        self.l2_blocks().last().map(|block| &block.header)
+    }
+
+    /// Returns the header of the last generated L1 batch.
+    pub fn last_l1_batch(&self) -> Option<&L1BatchHeader> {
+        self.l1_batches.last().map(|batch| &batch.header)
+    }
+
+    /// Inserts the fixture into the storage in a single DB transaction. Inserts the protocol version used by the fixture
+    /// if it's not present.
+    pub async fn insert(&self, storage: &mut Connection<'_, Core>) {
+        let mut storage = storage.start_transaction().await.unwrap();
+        let protocol_version = self.params.protocol_version;
+        let existing_version = storage
+            .protocol_versions_dal()
+            .get_protocol_version_with_latest_patch(protocol_version)
+            .await
+            .unwrap();
+        if existing_version.is_none() {
+            storage
+                .protocol_versions_dal()
+                .save_protocol_version_with_tx(&ProtocolVersion {
+                    version: ProtocolSemanticVersion {
+                        minor: protocol_version,
+                        patch: 0.into(),
+                    },
+                    ..ProtocolVersion::default()
+                })
+                .await
+                .unwrap();
+        }
+
+        for l1_batch in &self.l1_batches {
+            for l2_block in &l1_batch.l2_blocks {
+                Self::insert_l2_block(&mut storage, l2_block, protocol_version).await;
+            }
+            Self::seal_l1_batch(&mut storage, l1_batch).await;
+            self.insert_proof_generation_job(&mut storage, l1_batch.header.number)
+                .await;
+            if let Some(chain_id) = self.params.executed_on {
+                Self::execute_l1_batch(&mut storage, l1_batch.header.number, chain_id).await;
+            }
+        }
+        storage.commit().await.unwrap();
+    }
+
+    async fn insert_l2_block(
+        storage: &mut Connection<'_, Core>,
+        l2_block: &L2BlockFixture,
+        protocol_version: ProtocolVersionId,
+    ) {
+        let number = l2_block.header.number;
+        storage
+            .blocks_dal()
+            .insert_l2_block(&l2_block.header)
+            .await
+            .unwrap();
+        storage
+            .transactions_dal()
+            .mark_txs_as_executed_in_l2_block(
+                number,
+                &l2_block.transactions,
+                l2_block.header.base_fee_per_gas.into(),
+                protocol_version,
+                true,
+            )
+            .await
+            .unwrap();
+
+        let events: Vec<_> = l2_block
+            .events
+            .iter()
+            .map(|(location, events)| (*location, events.iter().collect()))
+            .collect();
+        storage
+            .events_dal()
+            .save_events(number, &events)
+            .await
+            .unwrap();
+        let l2_to_l1_logs: Vec<_> = l2_block
+            .l2_to_l1_logs
+            .iter()
+            .map(|(location, logs)| (*location, logs.iter().collect()))
+            .collect();
+        storage
+            .events_dal()
+            .save_user_l2_to_l1_logs(number, &l2_to_l1_logs)
+            .await
+            .unwrap();
+        storage
+            .storage_logs_dal()
+            .insert_storage_logs(number, &l2_block.storage_logs)
+            .await
+            .unwrap();
+    }
+
+    async fn seal_l1_batch(storage: &mut Connection<'_, Core>, l1_batch: &L1BatchFixture) {
+        let number = l1_batch.header.number;
+        storage
+            .blocks_dal()
+            .insert_mock_l1_batch(&l1_batch.header)
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .mark_l2_blocks_as_executed_in_l1_batch(number)
+            .await
+            .unwrap();
+
+        let mut hashed_keys: Vec<_> = l1_batch
+            .l2_blocks
+            .iter()
+            .flat_map(|block| &block.storage_logs)
+            .map(|log| log.key.hashed_key())
+            .collect();
+        let existing_writes = storage
+            .storage_logs_dal()
+            .get_l1_batches_and_indices_for_initial_writes(&hashed_keys)
+            .await
+            .unwrap();
+        let mut new_keys = HashSet::new();
+        hashed_keys.retain(|key| !existing_writes.contains_key(key) && new_keys.insert(*key));
+        storage
+            .storage_logs_dedup_dal()
+            .insert_initial_writes(number, &hashed_keys)
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .save_l1_batch_tree_data(number, &l1_batch.metadata.tree_data())
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .save_l1_batch_commitment_artifacts(
+                number,
+                &l1_batch_metadata_to_commitment_artifacts(&l1_batch.metadata),
+            )
+            .await
+            .unwrap();
+    }
+
+    async fn insert_proof_generation_job(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        number: L1BatchNumber,
+    ) {
+        if self.params.proof_generation == ProofGenerationFixture::None {
+            return;
+        }
+        let mut dal = storage.proof_generation_dal();
+        dal.insert_proof_generation_details(number).await.unwrap();
+        if self.params.proof_generation == ProofGenerationFixture::ReadyForProving {
+            dal.save_vm_runner_artifacts_metadata(
+                number,
+                &ProofGenerationFixture::vm_run_data_blob_url(number),
+            )
+            .await
+            .unwrap();
+            dal.save_merkle_paths_artifacts_metadata(
+                number,
+                &ProofGenerationFixture::merkle_paths_blob_url(number),
+            )
+            .await
+            .unwrap();
+        }
+    }
+
+    async fn execute_l1_batch(
+        storage: &mut Connection<'_, Core>,
+        number: L1BatchNumber,
+        chain_id: SLChainId,
+    ) {
+        for (i, action) in [AggregatedActionType::Commit, AggregatedActionType::Execute]
+            .into_iter()
+            .enumerate()
+        {
+            storage
+                .eth_sender_dal()
+                .insert_bogus_confirmed_eth_tx(
+                    number,
+                    action,
+                    H256::from_low_u64_be(u64::from(number.0) * 2 + i as u64),
+                    chrono::Utc::now(),
+                    Some(chain_id),
+                )
+                .await
+                .unwrap();
+        }
+    }
+}
+
+/// Mutable state used during fixture generation.
+#[derive(Debug)]
+struct FixtureGenerator {
+    rng: StdRng,
+    protocol_version: ProtocolVersionId,
+    l2_blocks_per_l1_batch: u32,
+    txs_per_l2_block: usize,
+    events_per_tx: usize,
+    storage_logs_per_tx: usize,
+    accounts: Vec<(K256PrivateKey, Nonce)>,
+    contracts: Vec<Address>,
+    topics: Vec<H256>,
+    next_l1_batch: L1BatchNumber,
+    next_l2_block: L2BlockNumber,
+    prev_l2_block_hash: H256,
+    next_timestamp: u64,
+}
+
+impl FixtureGenerator {
+    fn new(params: &ChainFixtureParams) -> Self {
+        let mut rng = StdRng::seed_from_u64(params.seed);
+        let accounts = (0..ACCOUNT_COUNT)
+            .map(|_| (K256PrivateKey::random_using(&mut rng), Nonce(0)))
+            .collect();
+        let contracts = (0..CONTRACT_COUNT)
+            .map(|_| Address::from(rng.gen::<[u8; 20]>()))
+            .collect();
+        let topics = (0..TOPIC_COUNT).map(|_| H256(rng.gen())).collect();
+        Self {
+            rng,
+            protocol_version: params.protocol_version,
+            l2_blocks_per_l1_batch: params.l2_blocks_per_l1_batch,
+            txs_per_l2_block: params.txs_per_l2_block,
+            events_per_tx: params.events_per_tx,
+            storage_logs_per_tx: params.storage_logs_per_tx,
+            accounts,
+            contracts,
+            topics,
+            next_l1_batch: params.first_l1_batch,
+            next_l2_block: params.first_l2_block,
+            prev_l2_block_hash: params.prev_l2_block_hash,
+            next_timestamp: params.first_timestamp,
+        }
+    }
+
+    fn l1_batch(&mut self) -> L1BatchFixture {
+        let number = self.next_l1_batch;
+        self.next_l1_batch += 1;
+        let mut header = L1BatchHeader::new(
+            number,
+            self.next_timestamp,
+            BaseSystemContractsHashes::default(),
+            self.protocol_version,
+        );
+
+        let mut event_index_in_batch = 0;
+        let l2_blocks: Vec<_> = (0..self.l2_blocks_per_l1_batch)
+            .map(|_| self.l2_block(number, &mut event_index_in_batch))
+            .collect();
+        header.l2_tx_count = l2_blocks.iter().map(|block| block.header.l2_tx_count).sum();
+
+        L1BatchFixture {
+            header,
+            metadata: create_l1_batch_metadata(number.0),
+            l2_blocks,
+        }
+    }
+
+    fn l2_block(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        event_index_in_batch: &mut u32,
+    ) -> L2BlockFixture {
+        let number = self.next_l2_block;
+        self.next_l2_block += 1;
+        let timestamp = self.next_timestamp;
+        self.next_timestamp += 1;
+
+        let mut hasher = L2BlockHasher::new(number, timestamp, self.prev_l2_block_hash);
+        let mut transactions = Vec::with_capacity(self.txs_per_l2_block);
+        let mut events = Vec::with_capacity(self.txs_per_l2_block);
+        let mut storage_logs = vec![];
+        for tx_index in 0..self.txs_per_l2_block {
+            let tx = self.l2_transaction();
+            let contract = tx.execute.contract_address.unwrap();
+            let location = IncludedTxLocation {
+                tx_hash: tx.hash(),
+                tx_index_in_l2_block: tx_index as u32,
+                tx_initiator_address: tx.initiator_account(),
+            };
+            hasher.push_tx_hash(tx.hash());
+            transactions.push(execute_l2_transaction(tx));
+
+            let tx_events = (0..self.events_per_tx)
+                .map(|_| {
+                    let event = self.event(contract, (l1_batch_number, *event_index_in_batch));
+                    *event_index_in_batch += 1;
+                    event
+                })
+                .collect();
+            events.push((location, tx_events));
+
+            for _ in 0..self.storage_logs_per_tx {
+                let log = self.storage_log(contract);
+                storage_logs.push(log);
+            }
+        }
+
+        let logs_bloom = build_bloom(events.iter().flat_map(|(_, tx_events)| {
+            tx_events.iter().flat_map(|event: &VmEvent| {
+                event
+                    .indexed_topics
+                    .iter()
+                    .map(|topic| BloomInput::Raw(topic.as_bytes()))
+                    .chain([BloomInput::Raw(event.address.as_bytes())])
+            })
+        }));
+        let hash = hasher.finalize(self.protocol_version);
+        self.prev_l2_block_hash = hash;
+
+        let header = L2BlockHeader {
+            number,
+            timestamp,
+            hash,
+            l1_tx_count: 0,
+            l2_tx_count: transactions.len() as u16,
+            fee_account_address: Address::zero(),
+            base_fee_per_gas: BASE_FEE_PER_GAS,
+            batch_fee_input: BatchFeeInput::l1_pegged(100, 100),
+            gas_per_pubdata_limit: MAX_GAS_PER_PUBDATA_BYTE,
+            base_system_contracts_hashes: BaseSystemContractsHashes::default(),
+            protocol_version: Some(self.protocol_version),
+            virtual_blocks: 1,
+            gas_limit: 0,
+            logs_bloom,
+            pubdata_params: Default::default(),
+        };
+        L2BlockFixture {
+            header,
+            transactions,
+            events,
+            l2_to_l1_logs: vec![],
+            storage_logs,
+        }
+    }
+
+    fn l2_transaction(&mut self) -> L2Tx {
+        let account_index = self.rng.gen_range(0..self.accounts.len());
+        let contract = *self.contracts.choose(&mut self.rng).unwrap();
+        let calldata: Vec<u8> = (0..36).map(|_| self.rng.gen()).collect();
+        let input_data = H256(self.rng.gen());
+        let input_hash = H256(self.rng.gen());
+        let fee = Fee {
+            gas_limit: 1_000_000.into(),
+            max_fee_per_gas: BASE_FEE_PER_GAS.into(),
+            max_priority_fee_per_gas: U256::zero(),
+            gas_per_pubdata_limit: MAX_GAS_PER_PUBDATA_BYTE.into(),
+        };
+
+        let (private_key, nonce) = &mut self.accounts[account_index];
+        let mut tx = L2Tx::new_signed(
+            Some(contract),
+            calldata,
+            *nonce,
+            fee,
+            U256::zero(),
+            L2ChainId::default(),
+            private_key,
+            vec![],
+            PaymasterParams::default(),
+        )
+        .unwrap();
+        *nonce += 1;
+        // The hash of a transaction is determined by its input; see `create_l2_transaction()`.
+        tx.set_input(input_data.0.to_vec(), input_hash);
+        tx
+    }
+
+    fn event(&mut self, address: Address, location: (L1BatchNumber, u32)) -> VmEvent {
+        let topic_count = self.rng.gen_range(1..=3);
+        let indexed_topics = self
+            .topics
+            .choose_multiple(&mut self.rng, topic_count)
+            .copied()
+            .collect();
+        VmEvent {
+            location,
+            address,
+            indexed_topics,
+            value: self.rng.gen::<[u8; 32]>().to_vec(),
+        }
+    }
+
+    fn storage_log(&mut self, contract: Address) -> StorageLog {
+        let slot = H256::from_low_u64_be(self.rng.gen_range(0..SLOTS_PER_CONTRACT));
+        let key = StorageKey::new(AccountTreeId::new(contract), slot);
+        StorageLog::new_write_log(key, H256(self.rng.gen()))
+    }
+}
+
+/// Creates metadata for an L1 batch with the specified number.
+fn create_l1_batch_metadata(number: u32) -> L1BatchMetadata {
+    L1BatchMetadata {
+        root_hash: H256::from_low_u64_be(number.into()),
+        rollup_last_leaf_index: u64::from(number) + 20,
+        initial_writes_compressed: Some(vec![]),
+        repeated_writes_compressed: Some(vec![]),
+        commitment: H256::from_low_u64_be(number.into()),
+        l2_l1_merkle_root: H256::from_low_u64_be(number.into()),
+        block_meta_params: L1BatchMetaParameters {
+            zkporter_is_available: ZKPORTER_IS_AVAILABLE,
+            bootloader_code_hash: BaseSystemContractsHashes::default().bootloader,
+            default_aa_code_hash: BaseSystemContractsHashes::default().default_aa,
+            evm_emulator_code_hash: BaseSystemContractsHashes::default().evm_emulator,
+            protocol_version: Some(ProtocolVersionId::latest()),
+        },
+        aux_data_hash: H256::zero(),
+        meta_parameters_hash: H256::zero(),
+        pass_through_data_hash: H256::zero(),
+        events_queue_commitment: Some(H256::zero()),
+        bootloader_initial_content_commitment: Some(H256::zero()),
+        state_diffs_compressed: vec![],
+        state_diff_hash: Some(H256::zero()),
+        local_root: Some(H256::zero()),
+        aggregation_root: Some(H256::zero()),
+        da_inclusion_data: Some(vec![]),
+    }
+}
+
+fn l1_batch_metadata_to_commitment_artifacts(
+    metadata: &L1BatchMetadata,
+) -> L1BatchCommitmentArtifacts {
+    L1BatchCommitmentArtifacts {
+        commitment_hash: L1BatchCommitmentHash {
+            pass_through_data: metadata.pass_through_data_hash,
+            aux_output: metadata.aux_data_hash,
+            meta_parameters: metadata.meta_parameters_hash,
+            commitment: metadata.commitment,
+        },
+        l2_l1_merkle_root: metadata.l2_l1_merkle_root,
+        compressed_state_diffs: Some(metadata.state_diffs_compressed.clone()),
+        compressed_initial_writes: metadata.initial_writes_compressed.clone(),
+        compressed_repeated_writes: metadata.repeated_writes_compressed.clone(),
+        zkporter_is_available: ZKPORTER_IS_AVAILABLE,
+        aux_commitments: match (
+            metadata.bootloader_initial_content_commitment,
+            metadata.events_queue_commitment,
+        ) {
+            (Some(bootloader_initial_content_commitment), Some(events_queue_commitment)) => {
+                Some(AuxCommitments {
+                    bootloader_initial_content_commitment,
+                    events_queue_commitment,
+                })
+            }
+            _ => None,
+        },
+        local_root: metadata.local_root.unwrap(),
+        aggregation_root: metadata.aggregation_root.unwrap(),
+        state_diff_hash: metadata.state_diff_hash.unwrap(),
+        aux_output_preimage: None,
+    }
+}
+
+/// Wraps the specified transaction into a successful execution result.
+fn execute_l2_transaction(transaction: L2Tx) -> TransactionExecutionResult {
+    TransactionExecutionResult {
+        hash: transaction.hash(),
+        transaction: transaction.into(),
+        execution_info: VmExecutionMetrics::default(),
+        execution_status: TxExecutionStatus::Success,
+        refunded_gas: 0,
+        operator_suggested_refund: 0,
+        compressed_bytecodes: vec![],
+        call_traces: vec![],
+        revert_reason: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::ConnectionPool;
+
+    fn l2_block_hashes(fixture: &ChainFixture) -> Vec<H256> {
+        fixture.l2_blocks().map(|block| block.header.hash).collect()
+    }
+
+    #[test]
+    fn fixture_generation_is_deterministic() {
+        let params = ChainFixtureParams {
+            seed: 123,
+            ..ChainFixtureParams::default()
+        };
+        let fixture = ChainFixture::generate(params.clone());
+        assert_eq!(fixture.l1_batches.len(), 3);
+        assert_eq!(fixture.l2_blocks().count(), 6);
+        assert_eq!(fixture.transactions().count(), 18);
+        assert_eq!(fixture.last_l2_block().unwrap().number, L2BlockNumber(6));
+
+        let other_fixture = ChainFixture::generate(params);
+        assert_eq!(l2_block_hashes(&fixture), l2_block_hashes(&other_fixture));
+        let other_fixture = ChainFixture::generate(ChainFixtureParams {
+            seed: 321,
+            ..ChainFixtureParams::default()
+        });
+        assert_ne!(l2_block_hashes(&fixture), l2_block_hashes(&other_fixture));
+    }
+
+    #[tokio::test]
+    async fn inserting_fixture() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        let fixture = ChainFixture::generate(ChainFixtureParams {
+            proof_generation: ProofGenerationFixture::ReadyForProving,
+            executed_on: Some(SLChainId(9)),
+            ..ChainFixtureParams::default()
+        });
+        fixture.insert(&mut storage).await;
+
+        let last_l1_batch = fixture.last_l1_batch().unwrap().number;
+        let last_l2_block = fixture.last_l2_block().unwrap();
+        assert_eq!(
+            storage
+                .blocks_dal()
+                .get_sealed_l1_batch_number()
+                .await
+                .unwrap(),
+            Some(last_l1_batch)
+        );
+        let header = storage
+            .blocks_dal()
+            .get_last_sealed_l2_block_header()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(header.hash, last_l2_block.hash);
+        assert_eq!(header.l2_tx_count, 3);
+
+        for tx in fixture.transactions() {
+            let receipt = storage
+                .transactions_web3_dal()
+                .get_transaction_receipts(&[tx.hash])
+                .await
+                .unwrap();
+            assert_eq!(receipt.len(), 1);
+        }
+        let oldest_unpicked_batch = storage
+            .proof_generation_dal()
+            .get_oldest_unpicked_batch()
+            .await
+            .unwrap();
+        assert_eq!(oldest_unpicked_batch, Some(L1BatchNumber(1)));
+        let locked_batch = storage
+            .proof_generation_dal()
+            .lock_batch_for_proving(Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(locked_batch, Some(L1BatchNumber(1)));
+
+        let executed_batch = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await
+            .unwrap();
+        assert_eq!(executed_batch, Some(last_l1_batch));
+
+        let written_keys: HashSet<_> = fixture
+            .l2_blocks()
+            .flat_map(|block| &block.storage_logs)
+            .map(|log| log.key.hashed_key())
+            .collect();
+        let initial_writes = storage
+            .storage_logs_dal()
+            .get_l1_batches_and_indices_for_initial_writes(
+                &written_keys.iter().copied().collect::<Vec<_>>(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(initial_writes.len(), written_keys.len());
+    }
+
+    #[tokio::test]
+    async fn inserting_fixture_with_custom_contents() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        let mut fixture = ChainFixture::generate(ChainFixtureParams {
+            l1_batch_count: 2,
+            l2_blocks_per_l1_batch: 1,
+            txs_per_l2_block: 0,
+            ..ChainFixtureParams::default()
+        });
+        let key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(1)), H256::zero());
+        for (i, l1_batch) in fixture.l1_batches.iter_mut().enumerate() {
+            let log = StorageLog::new_write_log(key, H256::from_low_u64_be(i as u64 + 1));
+            l1_batch.l2_blocks[0].storage_logs.push(log);
+        }
+        fixture.insert(&mut storage).await;
+
+        let initial_writes = storage
+            .storage_logs_dal()
+            .get_l1_batches_and_indices_for_initial_writes(&[key.hashed_key()])
+            .await
+            .unwrap();
+        assert_eq!(initial_writes[&key.hashed_key()].0, L1BatchNumber(1));
+        let value = storage
+            .storage_web3_dal()
+            .get_historical_value_unchecked(key.hashed_key(), L2BlockNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(value, H256::from_low_u64_be(2));
+    }
+}
@@ -13,23 +13,28 @@ pub use zksync_db_connection::{
 
 use crate::{
     base_token_dal::BaseTokenDal, blocks_dal::BlocksDal, blocks_web3_dal::BlocksWeb3Dal,
-    consensus_dal::ConsensusDal, contract_verification_dal::ContractVerificationDal,
+    bridge_events_dal::BridgeEventsDal, consensus_dal::ConsensusDal,
+    contract_verification_dal::ContractVerificationDal,
     custom_genesis_export_dal::CustomGenesisExportDal, data_availability_dal::DataAvailabilityDal,
     eth_sender_dal::EthSenderDal, eth_watcher_dal::EthWatcherDal, events_dal::EventsDal,
     events_web3_dal::EventsWeb3Dal, factory_deps_dal::FactoryDepsDal,
+    node_leases_dal::NodeLeasesDal, partitions_dal::PartitionsDal,
     proof_generation_dal::ProofGenerationDal, protocol_versions_dal::ProtocolVersionsDal,
     protocol_versions_web3_dal::ProtocolVersionsWeb3Dal, pruning_dal::PruningDal,
     snapshot_recovery_dal::SnapshotRecoveryDal, snapshots_creator_dal::SnapshotsCreatorDal,
-    snapshots_dal::SnapshotsDal, storage_logs_dal::StorageLogsDal,
+    snapshots_dal::SnapshotsDal, staged_base_system_contracts_dal::StagedBaseSystemContractsDal,
+    storage_analytics_dal::StorageAnalyticsDal, storage_logs_dal::StorageLogsDal,
     storage_logs_dedup_dal::StorageLogsDedupDal, storage_web3_dal::StorageWeb3Dal,
     sync_dal::SyncDal, system_dal::SystemDal, tee_proof_generation_dal::TeeProofGenerationDal,
     tokens_dal::TokensDal, tokens_web3_dal::TokensWeb3Dal, transactions_dal::TransactionsDal,
-    transactions_web3_dal::TransactionsWeb3Dal, vm_runner_dal::VmRunnerDal,
+    transactions_web3_dal::TransactionsWeb3Dal, vm_divergences_dal::VmDivergencesDal,
+    vm_runner_dal::VmRunnerDal, withdrawal_finalizer_dal::WithdrawalFinalizerDal,
 };
 
 pub mod base_token_dal;
 pub mod blocks_dal;
 pub mod blocks_web3_dal;
+pub mod bridge_events_dal;
 pub mod consensus;
 pub mod consensus_dal;
 pub mod contract_verification_dal;
@@ -40,9 +45,13 @@ pub mod eth_watcher_dal;
 pub mod events_dal;
 pub mod events_web3_dal;
 pub mod factory_deps_dal;
+#[cfg(any(test, feature = "testonly"))]
+pub mod fixtures;
 pub mod helpers;
 pub mod metrics;
 mod models;
+pub mod node_leases_dal;
+pub mod partitions_dal;
 pub mod proof_generation_dal;
 pub mod protocol_versions_dal;
 pub mod protocol_versions_web3_dal;
@@ -50,6 +59,8 @@ pub mod pruning_dal;
 pub mod snapshot_recovery_dal;
 pub mod snapshots_creator_dal;
 pub mod snapshots_dal;
+pub mod staged_base_system_contracts_dal;
+pub mod storage_analytics_dal;
 pub mod storage_logs_dal;
 pub mod storage_logs_dedup_dal;
 pub mod storage_web3_dal;
@@ -60,7 +71,9 @@ pub mod tokens_dal;
 pub mod tokens_web3_dal;
 pub mod transactions_dal;
 pub mod transactions_web3_dal;
+pub mod vm_divergences_dal;
 pub mod vm_runner_dal;
+pub mod withdrawal_finalizer_dal;
 
 #[cfg(test)]
 mod tests;
@@ -126,6 +139,8 @@ where
 
     fn pruning_dal(&mut self) -> PruningDal<'_, 'a>;
 
+    fn partitions_dal(&mut self) -> PartitionsDal<'_, 'a>;
+
     fn data_availability_dal(&mut self) -> DataAvailabilityDal<'_, 'a>;
 
     fn vm_runner_dal(&mut self) -> VmRunnerDal<'_, 'a>;
@@ -135,6 +150,18 @@ where
     fn eth_watcher_dal(&mut self) -> EthWatcherDal<'_, 'a>;
 
     fn custom_genesis_export_dal(&mut self) -> CustomGenesisExportDal<'_, 'a>;
+
+    fn vm_divergences_dal(&mut self) -> VmDivergencesDal<'_, 'a>;
+
+    fn node_leases_dal(&mut self) -> NodeLeasesDal<'_, 'a>;
+
+    fn staged_base_system_contracts_dal(&mut self) -> StagedBaseSystemContractsDal<'_, 'a>;
+
+    fn storage_analytics_dal(&mut self) -> StorageAnalyticsDal<'_, 'a>;
+
+    fn withdrawal_finalizer_dal(&mut self) -> WithdrawalFinalizerDal<'_, 'a>;
+
+    fn bridge_events_dal(&mut self) -> BridgeEventsDal<'_, 'a>;
 }
 
 #[derive(Clone, Debug)]
@@ -246,6 +273,10 @@ impl<'a> CoreDal<'a> for Connection<'a, Core> {
         PruningDal { storage: self }
     }
 
+    fn partitions_dal(&mut self) -> PartitionsDal<'_, 'a> {
+        PartitionsDal { storage: self }
+    }
+
     fn data_availability_dal(&mut self) -> DataAvailabilityDal<'_, 'a> {
         DataAvailabilityDal { storage: self }
     }
@@ -265,4 +296,28 @@ impl<'a> CoreDal<'a> for Connection<'a, Core> {
     fn custom_genesis_export_dal(&mut self) -> CustomGenesisExportDal<'_, 'a> {
         CustomGenesisExportDal { storage: self }
     }
+
+    fn vm_divergences_dal(&mut self) -> VmDivergencesDal<'_, 'a> {
+        VmDivergencesDal { storage: self }
+    }
+
+    fn node_leases_dal(&mut self) -> NodeLeasesDal<'_, 'a> {
+        NodeLeasesDal { storage: self }
+    }
+
+    fn staged_base_system_contracts_dal(&mut self) -> StagedBaseSystemContractsDal<'_, 'a> {
+        StagedBaseSystemContractsDal { storage: self }
+    }
+
+    fn storage_analytics_dal(&mut self) -> StorageAnalyticsDal<'_, 'a> {
+        StorageAnalyticsDal { storage: self }
+    }
+
+    fn withdrawal_finalizer_dal(&mut self) -> WithdrawalFinalizerDal<'_, 'a> {
+        WithdrawalFinalizerDal { storage: self }
+    }
+
+    fn bridge_events_dal(&mut self) -> BridgeEventsDal<'_, 'a> {
+        BridgeEventsDal { storage: self }
+    }
 }
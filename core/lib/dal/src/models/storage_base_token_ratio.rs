@@ -2,7 +2,9 @@ use std::num::NonZeroU64;
 
 use bigdecimal::{BigDecimal, ToPrimitive};
 use chrono::NaiveDateTime;
-use zksync_types::base_token_ratio::BaseTokenRatio;
+use zksync_types::{
+    api::BaseTokenRatioAuditEntry, base_token_ratio::BaseTokenRatio, L1BatchNumber,
+};
 
 /// Represents a row in the `base_token_ratios` table.
 #[derive(Debug, Clone)]
@@ -29,3 +31,35 @@ impl From<StorageBaseTokenRatio> for BaseTokenRatio {
         }
     }
 }
+
+/// Represents a row in the `base_token_ratio_audit` table joined with the corresponding base token ratio.
+#[derive(Debug, Clone)]
+pub struct StorageBaseTokenRatioAuditEntry {
+    pub ratio_id: i32,
+    pub ratio_timestamp: NaiveDateTime,
+    pub numerator: BigDecimal,
+    pub denominator: BigDecimal,
+    pub source: String,
+    pub quotes: serde_json::Value,
+    pub created_at: NaiveDateTime,
+    pub first_l1_batch_number: Option<i64>,
+    pub last_l1_batch_number: Option<i64>,
+}
+
+impl From<StorageBaseTokenRatioAuditEntry> for BaseTokenRatioAuditEntry {
+    fn from(row: StorageBaseTokenRatioAuditEntry) -> Self {
+        Self {
+            ratio_id: row.ratio_id as u32,
+            numerator: NonZeroU64::new(row.numerator.to_u64().expect("numerator is not u64"))
+                .unwrap(),
+            denominator: NonZeroU64::new(row.denominator.to_u64().expect("denominator is not u64"))
+                .unwrap(),
+            ratio_timestamp: row.ratio_timestamp.and_utc(),
+            source: row.source,
+            quotes: serde_json::from_value(row.quotes).expect("invalid base token ratio quotes"),
+            created_at: row.created_at.and_utc(),
+            first_l1_batch_number: row.first_l1_batch_number.map(|n| L1BatchNumber(n as u32)),
+            last_l1_batch_number: row.last_l1_batch_number.map(|n| L1BatchNumber(n as u32)),
+        }
+    }
+}
@@ -470,6 +470,8 @@ impl From<StorageL1BatchDetails> for api::L1BatchDetails {
         api::L1BatchDetails {
             base,
             number: L1BatchNumber(details.number as u32),
+            // Not stored in the `l1_batches` table; should be set by the caller.
+            commitment_mode: None,
         }
     }
 }
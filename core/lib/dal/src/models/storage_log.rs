@@ -1,4 +1,4 @@
-use zksync_types::{L1BatchNumber, L2BlockNumber, H160, H256, U256};
+use zksync_types::{writes::StateDiffRecord, L1BatchNumber, L2BlockNumber, H160, H256, U256};
 
 /// Model of the initial write record from the `initial_writes` table. Should only be used in tests.
 #[derive(Debug, PartialEq)]
@@ -19,6 +19,17 @@ pub struct DbStorageLog {
     pub l2_block_number: L2BlockNumber,
 }
 
+/// Storage slot with the value changed by an L1 batch, i.e., an entry of the batch state diff.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateDiffWrite {
+    /// Record as published in the state diff. Initial writes are published without an enumeration index.
+    pub record: StateDiffRecord,
+    /// Enumeration index of the slot. Unlike in `record`, it is set for initial writes as well.
+    pub enumeration_index: u64,
+    /// Whether the slot is written for the first time in the batch.
+    pub is_initial: bool,
+}
+
 // We don't want to rely on the Merkle tree crate to import a single type, so we duplicate `TreeEntry` here.
 #[derive(Debug, Clone, Copy)]
 pub struct StorageRecoveryLogEntry {
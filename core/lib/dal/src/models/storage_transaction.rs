@@ -70,6 +70,9 @@ pub struct StorageTransaction {
 
     pub timestamp_asserter_range_start: Option<NaiveDateTime>,
     pub timestamp_asserter_range_end: Option<NaiveDateTime>,
+    pub l2_block_number_range_start: Option<i64>,
+    pub l2_block_number_range_end: Option<i64>,
+    pub expected_storage_values: Option<serde_json::Value>,
 
     // DEPRECATED.
     pub l1_block_number: Option<i32>,
@@ -326,12 +329,25 @@ impl From<StorageTransaction> for Transaction {
 
 impl From<&StorageTransaction> for TransactionTimeRangeConstraint {
     fn from(tx: &StorageTransaction) -> Self {
+        let expected_storage_values = tx.expected_storage_values.clone().map(|values| {
+            serde_json::from_value(values).unwrap_or_else(|_| {
+                panic!(
+                    "invalid expected storage values in database for tx {:?}",
+                    H256::from_slice(&tx.hash)
+                )
+            })
+        });
         Self {
             timestamp_asserter_range: tx.timestamp_asserter_range_start.and_then(|start| {
                 tx.timestamp_asserter_range_end.map(|end| {
                     (start.and_utc().timestamp() as u64)..(end.and_utc().timestamp() as u64)
                 })
             }),
+            l2_block_number_range: tx.l2_block_number_range_start.and_then(|start| {
+                tx.l2_block_number_range_end
+                    .map(|end| (start as u64)..(end as u64))
+            }),
+            expected_storage_values: expected_storage_values.unwrap_or_default(),
         }
     }
 }
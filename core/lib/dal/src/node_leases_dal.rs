@@ -0,0 +1,264 @@
+use std::time::Duration;
+
+use zksync_db_connection::{
+    connection::Connection, error::DalResult, instrument::InstrumentExt,
+    utils::pg_interval_from_duration,
+};
+
+use crate::Core;
+
+/// Lease with the specified name held by a specific node instance.
+///
+/// Leases are used to ensure that only a single instance among several ones sharing the database performs
+/// a certain role (e.g., sequencing). Lease expiration is checked using the database clock, so the clocks
+/// of node instances don't need to be synchronized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeLease {
+    pub name: String,
+    /// Unique identifier of the node instance holding the lease.
+    pub holder: String,
+}
+
+#[derive(Debug)]
+pub struct NodeLeasesDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl NodeLeasesDal<'_, '_> {
+    /// Tries to acquire the lease for the specified duration. Succeeds if the lease is not held by anyone,
+    /// has expired, or is already held by the same holder. Returns whether the lease was acquired.
+    pub async fn try_acquire_lease(&mut self, lease: &NodeLease, ttl: Duration) -> DalResult<bool> {
+        let ttl = pg_interval_from_duration(ttl);
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO
+            node_leases (name, holder, expires_at, acquired_at)
+            VALUES
+            ($1, $2, NOW() + $3::INTERVAL, NOW())
+            ON CONFLICT (name) DO
+            UPDATE
+            SET
+            holder = excluded.holder,
+            expires_at = excluded.expires_at,
+            acquired_at = excluded.acquired_at
+            WHERE
+            node_leases.holder = excluded.holder
+            OR node_leases.expires_at <= NOW()
+            "#,
+            &lease.name,
+            &lease.holder,
+            ttl
+        )
+        .instrument("try_acquire_lease")
+        .with_arg("lease", lease)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Extends the lease for the specified duration from now. Returns `false` if the lease has expired
+    /// or is held by another holder; in this case, the lease must be considered lost.
+    pub async fn renew_lease(&mut self, lease: &NodeLease, ttl: Duration) -> DalResult<bool> {
+        let ttl = pg_interval_from_duration(ttl);
+        let result = sqlx::query!(
+            r#"
+            UPDATE node_leases
+            SET
+                expires_at = NOW() + $3::INTERVAL
+            WHERE
+                name = $1
+                AND holder = $2
+                AND expires_at > NOW()
+            "#,
+            &lease.name,
+            &lease.holder,
+            ttl
+        )
+        .instrument("renew_lease")
+        .with_arg("lease", lease)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Checks whether the lease is currently held by the specified holder. The lease row is locked
+    /// until the end of the current transaction, so the lease cannot be taken over by another holder
+    /// while the transaction is in progress.
+    pub async fn check_lease(&mut self, lease: &NodeLease) -> DalResult<bool> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                holder
+            FROM
+                node_leases
+            WHERE
+                name = $1
+                AND holder = $2
+                AND expires_at > NOW()
+            FOR SHARE
+            "#,
+            &lease.name,
+            &lease.holder
+        )
+        .instrument("check_lease")
+        .with_arg("lease", lease)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.is_some())
+    }
+
+    /// Returns the current holder of the lease with the specified name, provided that the lease hasn't expired.
+    pub async fn get_lease_holder(&mut self, name: &str) -> DalResult<Option<String>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                holder
+            FROM
+                node_leases
+            WHERE
+                name = $1
+                AND expires_at > NOW()
+            "#,
+            name
+        )
+        .instrument("get_lease_holder")
+        .with_arg("name", &name)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(|row| row.holder))
+    }
+
+    /// Releases the lease if it's held by the specified holder, so that it can be acquired by another holder
+    /// without waiting for its expiration.
+    pub async fn release_lease(&mut self, lease: &NodeLease) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE node_leases
+            SET
+                expires_at = NOW()
+            WHERE
+                name = $1
+                AND holder = $2
+            "#,
+            &lease.name,
+            &lease.holder
+        )
+        .instrument("release_lease")
+        .with_arg("lease", lease)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    fn lease(holder: &str) -> NodeLease {
+        NodeLease {
+            name: "sequencer".to_owned(),
+            holder: holder.to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn acquiring_and_releasing_lease() {
+        const TTL: Duration = Duration::from_secs(60);
+
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let (first, second) = (lease("first"), lease("second"));
+
+        let mut dal = conn.node_leases_dal();
+        assert_eq!(dal.get_lease_holder("sequencer").await.unwrap(), None);
+        assert!(dal.try_acquire_lease(&first, TTL).await.unwrap());
+        assert!(dal.try_acquire_lease(&first, TTL).await.unwrap());
+        assert!(!dal.try_acquire_lease(&second, TTL).await.unwrap());
+        assert_eq!(
+            dal.get_lease_holder("sequencer").await.unwrap().as_deref(),
+            Some("first")
+        );
+        assert!(dal.check_lease(&first).await.unwrap());
+        assert!(!dal.check_lease(&second).await.unwrap());
+        assert!(dal.renew_lease(&first, TTL).await.unwrap());
+        assert!(!dal.renew_lease(&second, TTL).await.unwrap());
+
+        dal.release_lease(&second).await.unwrap(); // should be a no-op
+        assert!(dal.check_lease(&first).await.unwrap());
+        dal.release_lease(&first).await.unwrap();
+        assert!(!dal.check_lease(&first).await.unwrap());
+        assert!(!dal.renew_lease(&first, TTL).await.unwrap());
+        assert_eq!(dal.get_lease_holder("sequencer").await.unwrap(), None);
+
+        assert!(dal.try_acquire_lease(&second, TTL).await.unwrap());
+        assert!(!dal.try_acquire_lease(&first, TTL).await.unwrap());
+        assert!(dal.check_lease(&second).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn expired_lease_can_be_taken_over() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let (first, second) = (lease("first"), lease("second"));
+
+        let mut dal = conn.node_leases_dal();
+        assert!(dal
+            .try_acquire_lease(&first, Duration::from_millis(1))
+            .await
+            .unwrap());
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!dal.check_lease(&first).await.unwrap());
+        assert!(!dal
+            .renew_lease(&first, Duration::from_secs(60))
+            .await
+            .unwrap());
+        assert!(dal
+            .try_acquire_lease(&second, Duration::from_secs(60))
+            .await
+            .unwrap());
+        assert_eq!(
+            dal.get_lease_holder("sequencer").await.unwrap().as_deref(),
+            Some("second")
+        );
+    }
+
+    #[tokio::test]
+    async fn lease_cannot_be_taken_over_during_fenced_transaction() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let (first, second) = (lease("first"), lease("second"));
+        let mut conn = pool.connection().await.unwrap();
+        assert!(conn
+            .node_leases_dal()
+            .try_acquire_lease(&first, Duration::from_millis(100))
+            .await
+            .unwrap());
+
+        let mut fenced_conn = pool.connection().await.unwrap();
+        let mut transaction = fenced_conn.start_transaction().await.unwrap();
+        assert!(transaction
+            .node_leases_dal()
+            .check_lease(&first)
+            .await
+            .unwrap());
+        tokio::time::sleep(Duration::from_millis(150)).await;
+
+        // The lease has expired, but the takeover must wait until the fenced transaction is finished.
+        let takeover = tokio::spawn(async move {
+            conn.node_leases_dal()
+                .try_acquire_lease(&second, Duration::from_secs(60))
+                .await
+        });
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(!takeover.is_finished());
+
+        transaction.commit().await.unwrap();
+        assert!(takeover.await.unwrap().unwrap());
+        assert!(!fenced_conn
+            .node_leases_dal()
+            .check_lease(&first)
+            .await
+            .unwrap());
+    }
+}
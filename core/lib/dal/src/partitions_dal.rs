@@ -0,0 +1,382 @@
+//! Management of partitions for tables partitioned by L2 block number ranges.
+
+use std::{ops, time::Duration};
+
+use zksync_db_connection::{
+    connection::Connection,
+    error::{DalError, DalResult},
+    instrument::InstrumentExt,
+};
+use zksync_types::L2BlockNumber;
+
+use crate::Core;
+
+/// Table partitioned by ranges of L2 block numbers (the `miniblock_number` column).
+///
+/// Each table has a default partition storing data for L2 blocks not covered by range partitions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PartitionedTable {
+    Events,
+    StorageLogs,
+}
+
+impl PartitionedTable {
+    /// All partitioned tables.
+    pub const ALL: [Self; 2] = [Self::Events, Self::StorageLogs];
+
+    /// Returns the table name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Events => "events",
+            Self::StorageLogs => "storage_logs",
+        }
+    }
+
+    fn default_partition_name(self) -> String {
+        format!("{}_default", self.name())
+    }
+}
+
+/// Partition of a [`PartitionedTable`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TablePartition {
+    /// Name of the partition table.
+    pub name: String,
+    /// Range of L2 blocks stored in the partition. `None` for the default partition.
+    pub l2_blocks: Option<ops::Range<L2BlockNumber>>,
+}
+
+#[derive(Debug)]
+pub struct PartitionsDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl PartitionsDal<'_, '_> {
+    /// Timeout for acquiring a lock on a partitioned table when detaching its partition.
+    pub const DETACH_LOCK_TIMEOUT: Duration = Duration::from_secs(1);
+
+    /// Returns partitions of the specified table ordered by the covered L2 block range. The default partition
+    /// (if any) is returned last.
+    pub async fn get_partitions(
+        &mut self,
+        table: PartitionedTable,
+    ) -> DalResult<Vec<TablePartition>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                child.relname::TEXT AS "name!",
+                PG_GET_EXPR(child.relpartbound, child.oid) = 'DEFAULT' AS "is_default!",
+                (
+                    REGEXP_MATCH(PG_GET_EXPR(child.relpartbound, child.oid), 'FROM \(''(\d+)''\)')
+                )[1]::BIGINT AS start_l2_block,
+                (
+                    REGEXP_MATCH(PG_GET_EXPR(child.relpartbound, child.oid), 'TO \(''(\d+)''\)')
+                )[1]::BIGINT AS end_l2_block
+            FROM
+                pg_inherits
+            JOIN pg_class parent ON pg_inherits.inhparent = parent.oid
+            JOIN pg_class child ON pg_inherits.inhrelid = child.oid
+            WHERE
+                parent.relname = $1
+            "#,
+            table.name()
+        )
+        .instrument("get_partitions")
+        .with_arg("table", &table.name())
+        .fetch_all(self.storage)
+        .await?;
+
+        let mut partitions: Vec<_> = rows
+            .into_iter()
+            .map(|row| {
+                let l2_blocks = (!row.is_default).then(|| {
+                    // The start bound is `MINVALUE` for the partition containing legacy data.
+                    let start = row.start_l2_block.unwrap_or(0);
+                    let end = row.end_l2_block.unwrap_or(i64::from(u32::MAX));
+                    L2BlockNumber(start as u32)..L2BlockNumber(end as u32)
+                });
+                TablePartition {
+                    name: row.name,
+                    l2_blocks,
+                }
+            })
+            .collect();
+        partitions.sort_unstable_by_key(|partition| {
+            partition
+                .l2_blocks
+                .as_ref()
+                .map_or(L2BlockNumber(u32::MAX), |range| range.start)
+        });
+        Ok(partitions)
+    }
+
+    /// Creates a partition for the specified range of L2 blocks. Data for these blocks already stored
+    /// in the default partition is moved to the created partition.
+    ///
+    /// Returns the name of the created partition.
+    pub async fn create_partition(
+        &mut self,
+        table: PartitionedTable,
+        l2_blocks: ops::Range<L2BlockNumber>,
+    ) -> DalResult<String> {
+        let table_name = table.name();
+        let default_partition = table.default_partition_name();
+        let partition = format!("{table_name}_p{}", l2_blocks.start.0);
+        let (start, end) = (l2_blocks.start.0, l2_blocks.end.0);
+        let range_condition = format!("miniblock_number >= {start} AND miniblock_number < {end}");
+
+        // Postgres refuses to create a partition if the default partition contains rows belonging to it,
+        // so such rows are temporarily moved out.
+        let statements = [
+            format!(
+                "CREATE TEMPORARY TABLE {partition}_moved ON COMMIT DROP AS \
+                 SELECT * FROM {default_partition} WHERE {range_condition}"
+            ),
+            format!("DELETE FROM {default_partition} WHERE {range_condition}"),
+            format!(
+                "CREATE TABLE {partition} PARTITION OF {table_name} FOR VALUES FROM ({start}) TO ({end})"
+            ),
+            format!("INSERT INTO {table_name} SELECT * FROM {partition}_moved"),
+        ];
+
+        let mut transaction = self.storage.start_transaction().await?;
+        for statement in &statements {
+            sqlx::query(statement)
+                .instrument("create_partition")
+                .with_arg("table", &table_name)
+                .with_arg("l2_blocks", &l2_blocks)
+                .report_latency()
+                .execute(&mut transaction)
+                .await?;
+        }
+        transaction.commit().await?;
+        Ok(partition)
+    }
+
+    /// Drops range partitions that only contain data for L2 blocks before `l2_block` (exclusive). Intended to be used
+    /// when pruning data, since dropping a partition is much cheaper than deleting rows from it.
+    ///
+    /// Each partition is detached and dropped in a separate short transaction, so this method must not be called
+    /// within a transaction. Detaching a partition requires an `ACCESS EXCLUSIVE` lock on the partitioned table
+    /// (`DETACH PARTITION CONCURRENTLY` isn't supported by Postgres for tables with a default partition). To not block
+    /// queries to the table while waiting for this lock, it's acquired with [`Self::DETACH_LOCK_TIMEOUT`]. If the lock
+    /// cannot be acquired in time, the method stops and returns partitions dropped so far; remaining partitions
+    /// will be dropped on the next call.
+    ///
+    /// Returns names of the dropped partitions.
+    pub async fn drop_partitions_before(
+        &mut self,
+        table: PartitionedTable,
+        l2_block: L2BlockNumber,
+    ) -> DalResult<Vec<String>> {
+        let partitions = self.get_partitions(table).await?;
+        let partitions_to_drop = partitions.into_iter().filter_map(|partition| {
+            let range = partition.l2_blocks?;
+            (range.end <= l2_block).then_some(partition.name)
+        });
+        let partitions_to_drop: Vec<_> = partitions_to_drop.collect();
+
+        let table_name = table.name();
+        let lock_timeout_ms = Self::DETACH_LOCK_TIMEOUT.as_millis();
+        let mut dropped_partitions = Vec::with_capacity(partitions_to_drop.len());
+        for partition in partitions_to_drop {
+            let statements = [
+                format!("SET LOCAL lock_timeout = {lock_timeout_ms}"),
+                format!("ALTER TABLE {table_name} DETACH PARTITION {partition}"),
+                // The detached table only has its own lock taken, so dropping it doesn't affect queries to `table`.
+                format!("DROP TABLE {partition}"),
+            ];
+
+            let mut transaction = self.storage.start_transaction().await?;
+            for statement in &statements {
+                let result = sqlx::query(statement)
+                    .instrument("drop_partitions_before")
+                    .with_arg("table", &table_name)
+                    .with_arg("partition", &partition)
+                    .report_latency()
+                    .execute(&mut transaction)
+                    .await;
+                if let Err(err) = result {
+                    if !Self::is_lock_timeout(&err) {
+                        return Err(err);
+                    }
+                    tracing::info!(
+                        "Timed out acquiring lock to detach partition `{partition}` of table `{table_name}`; \
+                         will retry later"
+                    );
+                    transaction.rollback().await?;
+                    return Ok(dropped_partitions);
+                }
+            }
+            transaction.commit().await?;
+            dropped_partitions.push(partition);
+        }
+        Ok(dropped_partitions)
+    }
+
+    fn is_lock_timeout(err: &DalError) -> bool {
+        const LOCK_NOT_AVAILABLE_CODE: &str = "55P03";
+
+        err.inner()
+            .as_database_error()
+            .and_then(|err| err.code())
+            .is_some_and(|code| code == LOCK_NOT_AVAILABLE_CODE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{
+        tx::IncludedTxLocation, AccountTreeId, Address, StorageKey, StorageLog, H256,
+    };
+
+    use super::*;
+    use crate::{
+        tests::{create_l2_block_header, mock_vm_event},
+        ConnectionPool, CoreDal,
+    };
+
+    async fn insert_l2_block(conn: &mut Connection<'_, Core>, number: L2BlockNumber) {
+        conn.blocks_dal()
+            .insert_l2_block(&create_l2_block_header(number.0))
+            .await
+            .unwrap();
+        let location = IncludedTxLocation {
+            tx_hash: H256([1; 32]),
+            tx_index_in_l2_block: 0,
+            tx_initiator_address: Address::default(),
+        };
+        let events = [mock_vm_event(0), mock_vm_event(1)];
+        conn.events_dal()
+            .save_events(number, &[(location, events.iter().collect())])
+            .await
+            .unwrap();
+        let key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(1)), H256::zero());
+        let logs = [StorageLog::new_write_log(key, H256::repeat_byte(1))];
+        conn.storage_logs_dal()
+            .insert_storage_logs(number, &logs)
+            .await
+            .unwrap();
+    }
+
+    async fn count_rows(conn: &mut Connection<'_, Core>, table: &str) -> i64 {
+        sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {table}"))
+            .fetch_one(conn.conn())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn creating_and_dropping_partitions() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+
+        for table in PartitionedTable::ALL {
+            let partitions = conn.partitions_dal().get_partitions(table).await.unwrap();
+            let default_partition = partitions.last().unwrap();
+            assert_eq!(default_partition.l2_blocks, None);
+            assert_eq!(default_partition.name, table.default_partition_name());
+        }
+
+        // Blocks not covered by range partitions must be stored in the default partitions.
+        let first_new_block = conn
+            .partitions_dal()
+            .get_partitions(PartitionedTable::Events)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter_map(|partition| partition.l2_blocks)
+            .map(|range| range.end)
+            .max()
+            .unwrap();
+        for number in first_new_block.0..first_new_block.0 + 3 {
+            insert_l2_block(&mut conn, L2BlockNumber(number)).await;
+        }
+        assert_eq!(count_rows(&mut conn, "events_default").await, 6);
+        assert_eq!(count_rows(&mut conn, "storage_logs_default").await, 3);
+
+        let new_range = first_new_block..first_new_block + 2;
+        for table in PartitionedTable::ALL {
+            let partition = conn
+                .partitions_dal()
+                .create_partition(table, new_range.clone())
+                .await
+                .unwrap();
+            assert_eq!(
+                partition,
+                format!("{}_p{}", table.name(), first_new_block.0)
+            );
+            let partitions = conn.partitions_dal().get_partitions(table).await.unwrap();
+            assert!(partitions
+                .iter()
+                .any(|partition| partition.l2_blocks == Some(new_range.clone())));
+        }
+        assert_eq!(count_rows(&mut conn, "events_default").await, 2);
+        assert_eq!(
+            count_rows(&mut conn, &format!("events_p{}", first_new_block.0)).await,
+            4
+        );
+        assert_eq!(count_rows(&mut conn, "events").await, 6);
+        assert_eq!(count_rows(&mut conn, "storage_logs_default").await, 1);
+
+        let dropped = conn
+            .partitions_dal()
+            .drop_partitions_before(PartitionedTable::Events, first_new_block + 1)
+            .await
+            .unwrap();
+        assert_eq!(dropped.len(), 1); // the partition with legacy data
+        let dropped = conn
+            .partitions_dal()
+            .drop_partitions_before(PartitionedTable::Events, new_range.end)
+            .await
+            .unwrap();
+        assert_eq!(dropped, [format!("events_p{}", first_new_block.0)]);
+        assert_eq!(count_rows(&mut conn, "events").await, 2);
+    }
+
+    #[tokio::test]
+    async fn dropping_partitions_does_not_wait_for_table_lock() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let legacy_range_end = conn
+            .partitions_dal()
+            .get_partitions(PartitionedTable::Events)
+            .await
+            .unwrap()[0]
+            .l2_blocks
+            .clone()
+            .unwrap()
+            .end;
+
+        // Emulate a long-running query to the `events` table.
+        let mut other_conn = pool.connection().await.unwrap();
+        let mut other_transaction = other_conn.start_transaction().await.unwrap();
+        sqlx::query("LOCK TABLE events IN ACCESS SHARE MODE")
+            .execute(other_transaction.conn())
+            .await
+            .unwrap();
+
+        let dropped = conn
+            .partitions_dal()
+            .drop_partitions_before(PartitionedTable::Events, legacy_range_end)
+            .await
+            .unwrap();
+        assert!(dropped.is_empty(), "{dropped:?}");
+        assert_eq!(
+            conn.partitions_dal()
+                .get_partitions(PartitionedTable::Events)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        other_transaction.rollback().await.unwrap();
+        let dropped = conn
+            .partitions_dal()
+            .drop_partitions_before(PartitionedTable::Events, legacy_range_end)
+            .await
+            .unwrap();
+        assert_eq!(dropped, ["events_legacy"]);
+    }
+}
@@ -134,6 +134,33 @@ impl ProofGenerationDal<'_, '_> {
         Ok(())
     }
 
+    /// Checks whether the batch is currently locked for proving, i.e., is picked by a prover and its proof
+    /// is neither generated nor skipped.
+    pub async fn is_batch_locked_for_proving(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<bool> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT
+                EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        proof_generation_details
+                    WHERE
+                        l1_batch_number = $1
+                        AND status = 'picked_by_prover'
+                ) AS "exists!"
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("is_batch_locked_for_proving")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_one(self.storage)
+        .await
+    }
+
     pub async fn save_proof_artifacts_metadata(
         &mut self,
         batch_number: L1BatchNumber,
@@ -452,12 +479,22 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(unpicked_l1_batch, None);
+        assert!(conn
+            .proof_generation_dal()
+            .is_batch_locked_for_proving(L1BatchNumber(1))
+            .await
+            .unwrap());
 
         // Check that we can unlock the batch and then pick it again.
         conn.proof_generation_dal()
             .unlock_batch(L1BatchNumber(1))
             .await
             .unwrap();
+        assert!(!conn
+            .proof_generation_dal()
+            .is_batch_locked_for_proving(L1BatchNumber(1))
+            .await
+            .unwrap());
         let picked_l1_batch = conn
             .proof_generation_dal()
             .lock_batch_for_proving(Duration::MAX)
@@ -490,5 +527,10 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(unpicked_l1_batch, None);
+        assert!(!conn
+            .proof_generation_dal()
+            .is_batch_locked_for_proving(L1BatchNumber(1))
+            .await
+            .unwrap());
     }
 }
@@ -3,7 +3,7 @@ use std::ops;
 use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
 use zksync_types::{L1BatchNumber, L2BlockNumber, H256};
 
-use crate::Core;
+use crate::{Core, CoreDal};
 
 #[cfg(test)]
 mod tests;
@@ -49,6 +49,18 @@ pub struct HardPruningStats {
     pub deleted_events: u64,
     pub deleted_call_traces: u64,
     pub deleted_l2_to_l1_logs: u64,
+    /// Number of dropped `events` partitions. Events in dropped partitions are not counted in `deleted_events`.
+    /// Not set by [`PruningDal::hard_prune_batches_range()`]; partitions should be dropped by the caller
+    /// outside the pruning transaction using [`PartitionsDal`](crate::partitions_dal::PartitionsDal).
+    pub dropped_event_partitions: u64,
+}
+
+/// Estimated amount of data that would be removed by hard-pruning a range of L2 blocks. Used in the dry-run pruning mode.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct HardPruningEstimate {
+    pub storage_logs: u64,
+    pub events: u64,
+    pub call_traces: u64,
 }
 
 #[derive(Debug)]
@@ -242,10 +254,69 @@ impl PruningDal<'_, '_> {
             deleted_l2_to_l1_logs,
             deleted_call_traces,
             deleted_storage_logs,
+            dropped_event_partitions: 0,
         };
         Ok(stats)
     }
 
+    /// Estimates the amount of data that would be removed by [`Self::hard_prune_batches_range()`] for the specified L2 blocks.
+    /// Storage logs are estimated assuming that the entire range is pruned at once; pruning it in chunks
+    /// may remove slightly fewer logs.
+    pub async fn estimate_hard_pruning(
+        &mut self,
+        l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<HardPruningEstimate> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        events
+                    WHERE
+                        miniblock_number BETWEEN $1 AND $2
+                ) AS "events!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        call_traces
+                    WHERE
+                        tx_hash IN (
+                            SELECT
+                                hash
+                            FROM
+                                transactions
+                            WHERE
+                                miniblock_number BETWEEN $1 AND $2
+                        )
+                ) AS "call_traces!",
+                (
+                    SELECT
+                        COUNT(*) - COUNT(DISTINCT hashed_key)
+                    FROM
+                        storage_logs
+                    WHERE
+                        miniblock_number BETWEEN $1 AND $2
+                ) AS "storage_logs!"
+            "#,
+            i64::from(l2_blocks_to_prune.start().0),
+            i64::from(l2_blocks_to_prune.end().0)
+        )
+        .instrument("estimate_hard_pruning")
+        .with_arg("l2_blocks_to_prune", &l2_blocks_to_prune)
+        .report_latency()
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok(HardPruningEstimate {
+            storage_logs: row.storage_logs as u64,
+            events: row.events as u64,
+            call_traces: row.call_traces as u64,
+        })
+    }
+
     async fn delete_events(
         &mut self,
         l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
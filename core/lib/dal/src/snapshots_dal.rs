@@ -5,7 +5,7 @@ use zksync_db_connection::{
 };
 use zksync_types::{
     snapshots::{AllSnapshots, SnapshotMetadata, SnapshotVersion},
-    L1BatchNumber,
+    L1BatchNumber, H256,
 };
 
 use crate::Core;
@@ -15,6 +15,7 @@ struct StorageSnapshotMetadata {
     version: i32,
     l1_batch_number: i64,
     storage_logs_filepaths: Vec<String>,
+    storage_logs_checksums: Option<Vec<Vec<u8>>>,
     factory_deps_filepath: String,
 }
 
@@ -24,6 +25,19 @@ impl TryFrom<StorageSnapshotMetadata> for SnapshotMetadata {
     fn try_from(row: StorageSnapshotMetadata) -> Result<Self, Self::Error> {
         let int_version = u16::try_from(row.version).decode_column("version")?;
         let version = SnapshotVersion::try_from(int_version).decode_column("version")?;
+        // Checksums are not set for snapshots created before they were introduced.
+        let storage_logs_checksums = match row.storage_logs_checksums {
+            Some(checksums) => checksums
+                .into_iter()
+                .map(|checksum| match checksum.len() {
+                    0 => Ok(None),
+                    32 => Ok(Some(H256::from_slice(&checksum))),
+                    len => Err(anyhow::anyhow!("invalid checksum length: {len}")),
+                })
+                .collect::<anyhow::Result<_>>()
+                .decode_column("storage_logs_checksums")?,
+            None => vec![None; row.storage_logs_filepaths.len()],
+        };
 
         Ok(Self {
             version,
@@ -33,6 +47,7 @@ impl TryFrom<StorageSnapshotMetadata> for SnapshotMetadata {
                 .into_iter()
                 .map(|path| (!path.is_empty()).then_some(path))
                 .collect(),
+            storage_logs_checksums,
             factory_deps_filepath: row.factory_deps_filepath,
         })
     }
@@ -58,12 +73,21 @@ impl SnapshotsDal<'_, '_> {
                 version,
                 l1_batch_number,
                 storage_logs_filepaths,
+                storage_logs_checksums,
                 factory_deps_filepath,
                 created_at,
                 updated_at
             )
             VALUES
-            ($1, $2, ARRAY_FILL(''::TEXT, ARRAY[$3::INTEGER]), $4, NOW(), NOW())
+            (
+                $1,
+                $2,
+                ARRAY_FILL(''::TEXT, ARRAY[$3::INTEGER]),
+                ARRAY_FILL(''::BYTEA, ARRAY[$3::INTEGER]),
+                $4,
+                NOW(),
+                NOW()
+            )
             "#,
             version as i32,
             l1_batch_number.0 as i32,
@@ -84,12 +108,14 @@ impl SnapshotsDal<'_, '_> {
         l1_batch_number: L1BatchNumber,
         chunk_id: u64,
         storage_logs_filepath: &str,
+        storage_logs_checksum: H256,
     ) -> DalResult<()> {
         sqlx::query!(
             r#"
             UPDATE snapshots
             SET
                 storage_logs_filepaths[$2] = $3,
+                storage_logs_checksums[$2] = $4,
                 updated_at = NOW()
             WHERE
                 l1_batch_number = $1
@@ -97,6 +123,7 @@ impl SnapshotsDal<'_, '_> {
             l1_batch_number.0 as i32,
             chunk_id as i32 + 1,
             storage_logs_filepath,
+            storage_logs_checksum.as_bytes(),
         )
         .instrument("add_storage_logs_filepath_for_snapshot")
         .with_arg("l1_batch_number", &l1_batch_number)
@@ -135,6 +162,31 @@ impl SnapshotsDal<'_, '_> {
         })
     }
 
+    /// Returns the L1 batch of the oldest snapshot that is still being created (i.e., has storage log chunks
+    /// not persisted yet).
+    pub async fn get_oldest_incomplete_snapshot_l1_batch(
+        &mut self,
+    ) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MIN(l1_batch_number) AS "l1_batch_number"
+            FROM
+                snapshots
+            WHERE
+                ''::TEXT = ANY(storage_logs_filepaths)
+            "#
+        )
+        .instrument("get_oldest_incomplete_snapshot_l1_batch")
+        .report_latency()
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok(row
+            .l1_batch_number
+            .map(|number| L1BatchNumber(number as u32)))
+    }
+
     pub async fn get_newest_snapshot_metadata(&mut self) -> DalResult<Option<SnapshotMetadata>> {
         sqlx::query_as!(
             StorageSnapshotMetadata,
@@ -143,7 +195,8 @@ impl SnapshotsDal<'_, '_> {
                 VERSION,
                 L1_BATCH_NUMBER,
                 FACTORY_DEPS_FILEPATH,
-                STORAGE_LOGS_FILEPATHS
+                STORAGE_LOGS_FILEPATHS,
+                STORAGE_LOGS_CHECKSUMS
             FROM
                 SNAPSHOTS
             ORDER BY
@@ -170,7 +223,8 @@ impl SnapshotsDal<'_, '_> {
                 VERSION,
                 L1_BATCH_NUMBER,
                 FACTORY_DEPS_FILEPATH,
-                STORAGE_LOGS_FILEPATHS
+                STORAGE_LOGS_FILEPATHS,
+                STORAGE_LOGS_CHECKSUMS
             FROM
                 SNAPSHOTS
             WHERE
@@ -201,7 +255,8 @@ impl SnapshotsDal<'_, '_> {
             version,
             l1_batch_number,
             factory_deps_filepath,
-            storage_logs_filepaths
+            storage_logs_filepaths,
+            storage_logs_checksums
             "#,
             last_retained_l1_batch_number.0 as i32
         )
@@ -218,7 +273,7 @@ impl SnapshotsDal<'_, '_> {
 
 #[cfg(test)]
 mod tests {
-    use zksync_types::{snapshots::SnapshotVersion, L1BatchNumber};
+    use zksync_types::{snapshots::SnapshotVersion, L1BatchNumber, H256};
 
     use crate::{ConnectionPool, Core, CoreDal};
 
@@ -242,12 +297,17 @@ mod tests {
             .await
             .expect("Failed to retrieve snapshots");
         assert_eq!(snapshots.snapshots_l1_batch_numbers, []);
+        assert_eq!(
+            dal.get_oldest_incomplete_snapshot_l1_batch().await.unwrap(),
+            Some(l1_batch_number)
+        );
 
         for i in 0..2 {
             dal.add_storage_logs_filepath_for_snapshot(
                 l1_batch_number,
                 i,
                 "gs:///bucket/chunk.bin",
+                H256::repeat_byte(i as u8 + 1),
             )
             .await
             .unwrap();
@@ -258,6 +318,10 @@ mod tests {
             .await
             .expect("Failed to retrieve snapshots");
         assert_eq!(snapshots.snapshots_l1_batch_numbers, [l1_batch_number]);
+        assert_eq!(
+            dal.get_oldest_incomplete_snapshot_l1_batch().await.unwrap(),
+            None
+        );
 
         let snapshot_metadata = dal
             .get_snapshot_metadata(l1_batch_number)
@@ -265,6 +329,10 @@ mod tests {
             .unwrap()
             .expect("snapshot is not persisted");
         assert_eq!(snapshot_metadata.l1_batch_number, l1_batch_number);
+        assert_eq!(
+            snapshot_metadata.storage_logs_checksums,
+            [Some(H256::repeat_byte(1)), Some(H256::repeat_byte(2))]
+        );
     }
 
     #[tokio::test]
@@ -287,6 +355,7 @@ mod tests {
                 l1_batch_number,
                 i,
                 "gs:///bucket/chunk.bin",
+                H256::repeat_byte(i as u8 + 1),
             )
             .await
             .unwrap();
@@ -346,24 +415,37 @@ mod tests {
         .expect("Failed to add snapshot");
 
         let storage_log_filepaths = ["gs:///bucket/test_file1.bin", "gs:///bucket/test_file2.bin"];
-        dal.add_storage_logs_filepath_for_snapshot(l1_batch_number, 1, storage_log_filepaths[1])
-            .await
-            .unwrap();
+        dal.add_storage_logs_filepath_for_snapshot(
+            l1_batch_number,
+            1,
+            storage_log_filepaths[1],
+            H256::repeat_byte(2),
+        )
+        .await
+        .unwrap();
 
-        let files = dal
+        let metadata = dal
             .get_snapshot_metadata(l1_batch_number)
             .await
             .expect("Failed to retrieve snapshot")
-            .unwrap()
-            .storage_logs_filepaths;
+            .unwrap();
         assert_eq!(
-            files,
+            metadata.storage_logs_filepaths,
             [None, Some("gs:///bucket/test_file2.bin".to_string())]
         );
+        assert_eq!(
+            metadata.storage_logs_checksums,
+            [None, Some(H256::repeat_byte(2))]
+        );
 
-        dal.add_storage_logs_filepath_for_snapshot(l1_batch_number, 0, storage_log_filepaths[0])
-            .await
-            .unwrap();
+        dal.add_storage_logs_filepath_for_snapshot(
+            l1_batch_number,
+            0,
+            storage_log_filepaths[0],
+            H256::repeat_byte(1),
+        )
+        .await
+        .unwrap();
 
         let files = dal
             .get_snapshot_metadata(l1_batch_number)
@@ -0,0 +1,310 @@
+use sqlx::types::chrono::NaiveDateTime;
+use zksync_contracts::BaseSystemContractsHashes;
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{
+    api::{BaseSystemContractKind, StagedBaseSystemContract, StagedContractStatus},
+    ProtocolVersionId, H256,
+};
+
+use crate::Core;
+
+#[derive(Debug)]
+struct StorageStagedBaseSystemContract {
+    protocol_version: i32,
+    kind: String,
+    bytecode_hash: Vec<u8>,
+    bytecode_len: i32,
+    status: String,
+    staged_at: NaiveDateTime,
+}
+
+impl From<StorageStagedBaseSystemContract> for StagedBaseSystemContract {
+    fn from(row: StorageStagedBaseSystemContract) -> Self {
+        Self {
+            protocol_version: (row.protocol_version as u16)
+                .try_into()
+                .expect("invalid protocol version"),
+            kind: row.kind.parse().expect("invalid base system contract kind"),
+            bytecode_hash: H256::from_slice(&row.bytecode_hash),
+            bytecode_len: row.bytecode_len as usize,
+            status: row.status.parse().expect("invalid staged contract status"),
+            staged_at: row.staged_at.and_utc(),
+        }
+    }
+}
+
+/// DAL methods related to base system contracts staged by operators ahead of a protocol upgrade.
+///
+/// A staged contract starts in the [`StagedContractStatus::Pending`] status. Once the upgrade for its protocol version
+/// is observed on L1, the staged bytecode hash is compared to the one in the upgrade, and the contract is either
+/// validated or marked as mismatched. Only validated bytecodes are used when loading base system contracts,
+/// so they are switched to atomically at the first batch with the upgraded protocol version.
+#[derive(Debug)]
+pub struct StagedBaseSystemContractsDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl StagedBaseSystemContractsDal<'_, '_> {
+    /// Stages a contract for the specified protocol version. If a contract of the same kind is already staged
+    /// for this version, it is replaced, and its status is reset to pending.
+    ///
+    /// The caller is responsible for `bytecode_hash` matching the `bytecode`.
+    pub async fn stage_contract(
+        &mut self,
+        protocol_version: ProtocolVersionId,
+        kind: BaseSystemContractKind,
+        bytecode_hash: H256,
+        bytecode: &[u8],
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            staged_base_system_contracts (
+                protocol_version, kind, bytecode_hash, bytecode, status, staged_at, updated_at
+            )
+            VALUES
+            ($1, $2, $3, $4, 'pending', NOW(), NOW())
+            ON CONFLICT (protocol_version, kind) DO
+            UPDATE
+            SET
+            bytecode_hash = excluded.bytecode_hash,
+            bytecode = excluded.bytecode,
+            status = 'pending',
+            staged_at = NOW(),
+            updated_at = NOW()
+            "#,
+            protocol_version as i32,
+            kind.as_ref(),
+            bytecode_hash.as_bytes(),
+            bytecode
+        )
+        .instrument("stage_contract")
+        .with_arg("protocol_version", &protocol_version)
+        .with_arg("kind", &kind)
+        .with_arg("bytecode_hash", &bytecode_hash)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns contracts staged for the specified protocol version, or for all versions if it's not specified.
+    pub async fn get_staged_contracts(
+        &mut self,
+        protocol_version: Option<ProtocolVersionId>,
+    ) -> DalResult<Vec<StagedBaseSystemContract>> {
+        let rows = sqlx::query_as!(
+            StorageStagedBaseSystemContract,
+            r#"
+            SELECT
+                protocol_version,
+                kind,
+                bytecode_hash,
+                LENGTH(bytecode) AS "bytecode_len!",
+                status,
+                staged_at
+            FROM
+                staged_base_system_contracts
+            WHERE
+                $1::INT IS NULL
+                OR protocol_version = $1
+            ORDER BY
+                protocol_version,
+                kind
+            "#,
+            protocol_version.map(|version| version as i32)
+        )
+        .instrument("get_staged_contracts")
+        .with_arg("protocol_version", &protocol_version)
+        .fetch_all(self.storage)
+        .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Compares contracts staged for the specified protocol version with the base system contract hashes
+    /// from the protocol upgrade and updates their statuses accordingly. Returns the updated contracts.
+    pub async fn validate_staged_contracts(
+        &mut self,
+        protocol_version: ProtocolVersionId,
+        hashes: &BaseSystemContractsHashes,
+    ) -> DalResult<Vec<StagedBaseSystemContract>> {
+        let rows = sqlx::query_as!(
+            StorageStagedBaseSystemContract,
+            r#"
+            UPDATE staged_base_system_contracts
+            SET
+                status = CASE
+                    WHEN bytecode_hash = (
+                        CASE kind
+                            WHEN 'bootloader' THEN $2::BYTEA
+                            WHEN 'default_account' THEN $3::BYTEA
+                            WHEN 'evm_emulator' THEN $4::BYTEA
+                        END
+                    ) THEN 'validated'
+                    ELSE 'mismatch'
+                END,
+                updated_at = NOW()
+            WHERE
+                protocol_version = $1
+            RETURNING
+            protocol_version,
+            kind,
+            bytecode_hash,
+            LENGTH(bytecode) AS "bytecode_len!",
+            status,
+            staged_at
+            "#,
+            protocol_version as i32,
+            hashes.bootloader.as_bytes(),
+            hashes.default_aa.as_bytes(),
+            hashes.evm_emulator.as_ref().map(H256::as_bytes)
+        )
+        .instrument("validate_staged_contracts")
+        .with_arg("protocol_version", &protocol_version)
+        .with_arg("hashes", hashes)
+        .fetch_all(self.storage)
+        .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns a staged bytecode with the specified hash regardless of its validation status, if any.
+    /// Can be used to inspect the effects of upgrades before they are observed on L1.
+    pub async fn get_staged_bytecode(&mut self, hash: H256) -> DalResult<Option<Vec<u8>>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                bytecode
+            FROM
+                staged_base_system_contracts
+            WHERE
+                bytecode_hash = $1
+            LIMIT
+                1
+            "#,
+            hash.as_bytes()
+        )
+        .instrument("get_staged_bytecode")
+        .with_arg("hash", &hash)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(|row| row.bytecode))
+    }
+
+    /// Returns a validated staged bytecode with the specified hash, if any.
+    pub async fn get_validated_bytecode(&mut self, hash: H256) -> DalResult<Option<Vec<u8>>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                bytecode
+            FROM
+                staged_base_system_contracts
+            WHERE
+                bytecode_hash = $1
+                AND status = 'validated'
+            LIMIT
+                1
+            "#,
+            hash.as_bytes()
+        )
+        .instrument("get_validated_bytecode")
+        .with_arg("hash", &hash)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(|row| row.bytecode))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::bytecode::BytecodeHash;
+
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    fn bytecode(fill: u8) -> (H256, Vec<u8>) {
+        let bytecode = vec![fill; 32];
+        (BytecodeHash::for_bytecode(&bytecode).value(), bytecode)
+    }
+
+    #[tokio::test]
+    async fn staging_and_validating_contracts() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let version = ProtocolVersionId::next();
+        let (bootloader_hash, bootloader) = bytecode(1);
+        let (default_aa_hash, default_aa) = bytecode(2);
+
+        let mut dal = conn.staged_base_system_contracts_dal();
+        dal.stage_contract(
+            version,
+            BaseSystemContractKind::Bootloader,
+            bootloader_hash,
+            &bootloader,
+        )
+        .await
+        .unwrap();
+        dal.stage_contract(
+            version,
+            BaseSystemContractKind::DefaultAccount,
+            default_aa_hash,
+            &default_aa,
+        )
+        .await
+        .unwrap();
+
+        let staged = dal.get_staged_contracts(Some(version)).await.unwrap();
+        assert_eq!(staged.len(), 2);
+        assert!(staged
+            .iter()
+            .all(|contract| contract.status == StagedContractStatus::Pending));
+        assert_eq!(dal.get_staged_contracts(None).await.unwrap().len(), 2);
+        assert!(dal
+            .get_staged_contracts(Some(ProtocolVersionId::latest()))
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            dal.get_validated_bytecode(bootloader_hash).await.unwrap(),
+            None
+        );
+
+        let hashes = BaseSystemContractsHashes {
+            bootloader: bootloader_hash,
+            default_aa: H256::repeat_byte(0xff),
+            evm_emulator: None,
+        };
+        let validated = dal
+            .validate_staged_contracts(version, &hashes)
+            .await
+            .unwrap();
+        assert_eq!(validated.len(), 2);
+        for contract in &validated {
+            let expected_status = match contract.kind {
+                BaseSystemContractKind::Bootloader => StagedContractStatus::Validated,
+                _ => StagedContractStatus::Mismatch,
+            };
+            assert_eq!(contract.status, expected_status, "{contract:?}");
+        }
+        assert_eq!(
+            dal.get_validated_bytecode(bootloader_hash).await.unwrap(),
+            Some(bootloader.clone())
+        );
+        assert_eq!(
+            dal.get_validated_bytecode(default_aa_hash).await.unwrap(),
+            None
+        );
+
+        // Re-staging resets the status.
+        dal.stage_contract(
+            version,
+            BaseSystemContractKind::Bootloader,
+            bootloader_hash,
+            &bootloader,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            dal.get_validated_bytecode(bootloader_hash).await.unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use std::ops;
+
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{api::ContractStorageUsage, Address, L1BatchNumber};
+
+use crate::Core;
+
+/// Storage usage of a single contract in a single L1 batch.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ContractStorageStats {
+    pub address: Address,
+    /// Number of storage slots of the contract with the value changed by the batch.
+    pub storage_writes: u32,
+    /// Number of storage slots of the contract written to for the first time.
+    pub initial_writes: u32,
+    /// Size of compressed state diffs for the contract published by the batch.
+    pub pubdata_bytes: u64,
+}
+
+/// DAL methods related to per-contract storage usage analytics.
+#[derive(Debug)]
+pub struct StorageAnalyticsDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl StorageAnalyticsDal<'_, '_> {
+    /// Returns the latest L1 batch processed by the storage analytics component.
+    pub async fn get_last_processed_l1_batch(&mut self) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MAX(l1_batch_number) AS "number"
+            FROM
+                l1_batch_storage_analytics
+            "#
+        )
+        .instrument("get_last_processed_l1_batch")
+        .report_latency()
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row.number.map(|number| L1BatchNumber(number as u32)))
+    }
+
+    /// Saves storage usage for all contracts that changed storage in the specified L1 batch, and marks the batch
+    /// as processed. Should be called in a transaction.
+    pub async fn insert_l1_batch_stats(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        stats: &[ContractStorageStats],
+    ) -> DalResult<()> {
+        let addresses: Vec<_> = stats.iter().map(|stats| stats.address.as_bytes()).collect();
+        let storage_writes: Vec<_> = stats
+            .iter()
+            .map(|stats| stats.storage_writes as i32)
+            .collect();
+        let initial_writes: Vec<_> = stats
+            .iter()
+            .map(|stats| stats.initial_writes as i32)
+            .collect();
+        let pubdata_bytes: Vec<_> = stats
+            .iter()
+            .map(|stats| stats.pubdata_bytes as i64)
+            .collect();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            contract_storage_analytics (
+                l1_batch_number, address, storage_writes, initial_writes, pubdata_bytes
+            )
+            SELECT
+                $1,
+                u.address,
+                u.storage_writes,
+                u.initial_writes,
+                u.pubdata_bytes
+            FROM
+                UNNEST($2::bytea [], $3::int [], $4::int [], $5::bigint [])
+                AS u (address, storage_writes, initial_writes, pubdata_bytes)
+            "#,
+            i64::from(l1_batch_number.0),
+            &addresses as &[&[u8]],
+            &storage_writes,
+            &initial_writes,
+            &pubdata_bytes
+        )
+        .instrument("insert_l1_batch_stats#contracts")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("stats.len", &stats.len())
+        .execute(self.storage)
+        .await?;
+
+        let total_storage_writes: i32 = storage_writes.iter().sum();
+        let total_pubdata_bytes: i64 = pubdata_bytes.iter().sum();
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            l1_batch_storage_analytics (
+                l1_batch_number, contracts, storage_writes, pubdata_bytes, created_at
+            )
+            VALUES
+            ($1, $2, $3, $4, NOW())
+            "#,
+            i64::from(l1_batch_number.0),
+            stats.len() as i32,
+            total_storage_writes,
+            total_pubdata_bytes
+        )
+        .instrument("insert_l1_batch_stats")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns up to `limit` contracts with the largest published pubdata in the specified range of L1 batches.
+    pub async fn get_top_pubdata_consumers(
+        &mut self,
+        l1_batches: ops::RangeInclusive<L1BatchNumber>,
+        limit: usize,
+    ) -> DalResult<Vec<ContractStorageUsage>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                address,
+                SUM(storage_writes)::BIGINT AS "storage_writes!",
+                SUM(initial_writes)::BIGINT AS "initial_writes!",
+                SUM(pubdata_bytes)::BIGINT AS "pubdata_bytes!",
+                COUNT(*) AS "l1_batch_count!"
+            FROM
+                contract_storage_analytics
+            WHERE
+                l1_batch_number BETWEEN $1 AND $2
+            GROUP BY
+                address
+            ORDER BY
+                SUM(pubdata_bytes) DESC,
+                address
+            LIMIT
+                $3
+            "#,
+            i64::from(l1_batches.start().0),
+            i64::from(l1_batches.end().0),
+            limit as i64
+        )
+        .instrument("get_top_pubdata_consumers")
+        .with_arg("l1_batches", &l1_batches)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ContractStorageUsage {
+                address: Address::from_slice(&row.address),
+                storage_writes: row.storage_writes as u64,
+                initial_writes: row.initial_writes as u64,
+                pubdata_bytes: row.pubdata_bytes as u64,
+                l1_batch_count: row.l1_batch_count as u64,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::ProtocolVersion;
+
+    use super::*;
+    use crate::{tests::create_l1_batch_header, ConnectionPool, CoreDal};
+
+    fn stats(address: u8, pubdata_bytes: u64) -> ContractStorageStats {
+        ContractStorageStats {
+            address: Address::repeat_byte(address),
+            storage_writes: 2,
+            initial_writes: 1,
+            pubdata_bytes,
+        }
+    }
+
+    #[tokio::test]
+    async fn saving_and_querying_storage_stats() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in 1..=3 {
+            conn.blocks_dal()
+                .insert_mock_l1_batch(&create_l1_batch_header(number))
+                .await
+                .unwrap();
+        }
+
+        let mut dal = conn.storage_analytics_dal();
+        assert_eq!(dal.get_last_processed_l1_batch().await.unwrap(), None);
+        dal.insert_l1_batch_stats(L1BatchNumber(1), &[stats(1, 100), stats(2, 50)])
+            .await
+            .unwrap();
+        dal.insert_l1_batch_stats(L1BatchNumber(2), &[stats(2, 80)])
+            .await
+            .unwrap();
+        dal.insert_l1_batch_stats(L1BatchNumber(3), &[])
+            .await
+            .unwrap();
+        assert_eq!(
+            dal.get_last_processed_l1_batch().await.unwrap(),
+            Some(L1BatchNumber(3))
+        );
+
+        let top = dal
+            .get_top_pubdata_consumers(L1BatchNumber(1)..=L1BatchNumber(3), 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            top,
+            [
+                ContractStorageUsage {
+                    address: Address::repeat_byte(2),
+                    storage_writes: 4,
+                    initial_writes: 2,
+                    pubdata_bytes: 130,
+                    l1_batch_count: 2,
+                },
+                ContractStorageUsage {
+                    address: Address::repeat_byte(1),
+                    storage_writes: 2,
+                    initial_writes: 1,
+                    pubdata_bytes: 100,
+                    l1_batch_count: 1,
+                },
+            ]
+        );
+
+        let top = dal
+            .get_top_pubdata_consumers(L1BatchNumber(1)..=L1BatchNumber(1), 1)
+            .await
+            .unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].address, Address::repeat_byte(1));
+    }
+}
@@ -1,18 +1,23 @@
-use std::{collections::HashMap, ops, time::Instant};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops,
+    time::Instant,
+};
 
 use sqlx::types::chrono::Utc;
 use zksync_db_connection::{
     connection::Connection,
     error::DalResult,
-    instrument::{CopyStatement, InstrumentExt},
+    instrument::{CopyStatement, InstrumentExt, Instrumented},
     write_str, writeln_str,
 };
 use zksync_types::{
-    get_code_key, snapshots::SnapshotStorageLog, AccountTreeId, Address, L1BatchNumber,
-    L2BlockNumber, StorageKey, StorageLog, FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H160, H256,
+    get_code_key, h256_to_u256, snapshots::SnapshotStorageLog, writes::StateDiffRecord,
+    AccountTreeId, Address, L1BatchNumber, L2BlockNumber, StorageKey, StorageLog,
+    FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H160, H256,
 };
 
-pub use crate::models::storage_log::{DbStorageLog, StorageRecoveryLogEntry};
+pub use crate::models::storage_log::{DbStorageLog, StateDiffWrite, StorageRecoveryLogEntry};
 use crate::{Core, CoreDal};
 
 #[derive(Debug)]
@@ -343,6 +348,60 @@ impl StorageLogsDal<'_, '_> {
         Ok(touched_slots.collect())
     }
 
+    /// Returns latest values for all slots written to in each L2 block of the specified L1 batch.
+    /// L2 blocks without writes are omitted.
+    pub async fn get_touched_slots_by_l2_block(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<BTreeMap<L2BlockNumber, HashMap<H256, H256>>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                miniblock_number,
+                hashed_key,
+                value
+            FROM
+                storage_logs
+            WHERE
+                miniblock_number BETWEEN (
+                    SELECT
+                        MIN(number)
+                    FROM
+                        miniblocks
+                    WHERE
+                        l1_batch_number = $1
+                ) AND (
+                    SELECT
+                        MAX(number)
+                    FROM
+                        miniblocks
+                    WHERE
+                        l1_batch_number = $1
+                )
+            ORDER BY
+                miniblock_number,
+                operation_number
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_touched_slots_by_l2_block")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_all(self.storage)
+        .await?;
+
+        let mut touched_slots = BTreeMap::<_, HashMap<_, _>>::new();
+        for row in rows {
+            touched_slots
+                .entry(L2BlockNumber(row.miniblock_number as u32))
+                .or_default()
+                .insert(
+                    H256::from_slice(&row.hashed_key),
+                    H256::from_slice(&row.value),
+                );
+        }
+        Ok(touched_slots)
+    }
+
     /// Same as [`Self::get_touched_slots_for_l1_batch()`], but loads key preimages instead of hashed keys.
     /// Correspondingly, this method is safe to call for locally executed L1 batches, for which key preimages
     /// are known; otherwise, it will error.
@@ -481,6 +540,68 @@ impl StorageLogsDal<'_, '_> {
         Ok(output)
     }
 
+    /// Returns the state diff of the specified executed L1 batch, i.e., storage slots with the value changed
+    /// by the batch. Writes are ordered by address and key, as in the published state diff.
+    pub async fn get_l1_batch_state_diff(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<StateDiffWrite>> {
+        let touched_slots = self
+            .get_touched_slots_for_executed_l1_batch(l1_batch_number)
+            .await?;
+        let hashed_keys: Vec<_> = touched_slots.keys().map(|key| key.hashed_key()).collect();
+        let previous_values = self
+            .get_previous_storage_values(&hashed_keys, l1_batch_number)
+            .await?;
+        let initial_writes = self
+            .get_l1_batches_and_indices_for_initial_writes(&hashed_keys)
+            .await?;
+        let instrumentation = Instrumented::new("get_l1_batch_state_diff")
+            .with_arg("l1_batch_number", &l1_batch_number);
+
+        let mut writes = Vec::with_capacity(touched_slots.len());
+        for (key, value) in touched_slots {
+            let hashed_key = key.hashed_key();
+            let prev_value = previous_values
+                .get(&hashed_key)
+                .copied()
+                .flatten()
+                .unwrap_or_default();
+            if prev_value == value {
+                continue;
+            }
+            let Some(&(initial_write_l1_batch, index)) = initial_writes.get(&hashed_key) else {
+                let err = anyhow::anyhow!("initial write for slot {hashed_key:?} is missing");
+                return Err(instrumentation.constraint_error(err));
+            };
+            if initial_write_l1_batch > l1_batch_number {
+                let err = anyhow::anyhow!(
+                    "slot {hashed_key:?} was changed in L1 batch #{l1_batch_number}, but its initial write \
+                     is in a later L1 batch #{initial_write_l1_batch}"
+                );
+                return Err(instrumentation.constraint_error(err));
+            }
+
+            let is_initial = initial_write_l1_batch == l1_batch_number;
+            let record = StateDiffRecord {
+                address: *key.address(),
+                key: h256_to_u256(*key.key()),
+                derived_key: hashed_key.0,
+                // Initial writes are published without an enumeration index.
+                enumeration_index: if is_initial { 0 } else { index },
+                initial_value: h256_to_u256(prev_value),
+                final_value: h256_to_u256(value),
+            };
+            writes.push(StateDiffWrite {
+                record,
+                enumeration_index: index,
+                is_initial,
+            });
+        }
+        writes.sort_unstable_by_key(|write| (write.record.address, write.record.key));
+        Ok(writes)
+    }
+
     pub async fn get_l1_batches_and_indices_for_initial_writes(
         &mut self,
         hashed_keys: &[H256],
@@ -771,7 +892,11 @@ mod tests {
     };
 
     use super::*;
-    use crate::{tests::create_l2_block_header, ConnectionPool, Core};
+    use crate::{
+        fixtures::{ChainFixture, ChainFixtureParams},
+        tests::create_l2_block_header,
+        ConnectionPool, Core,
+    };
 
     async fn insert_l2_block(conn: &mut Connection<'_, Core>, number: u32, logs: Vec<StorageLog>) {
         let header = L1BatchHeader::new(
@@ -1244,4 +1369,60 @@ mod tests {
             );
         }
     }
+
+    #[tokio::test]
+    async fn getting_l1_batch_state_diff() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut fixture = ChainFixture::generate(ChainFixtureParams {
+            l1_batch_count: 2,
+            l2_blocks_per_l1_batch: 1,
+            txs_per_l2_block: 0,
+            ..ChainFixtureParams::default()
+        });
+        let first_key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(2)), H256::zero());
+        let second_key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(1)), H256::zero());
+        fixture.l1_batches[0].l2_blocks[0].storage_logs = vec![
+            StorageLog::new_write_log(first_key, H256::repeat_byte(1)),
+            StorageLog::new_write_log(second_key, H256::repeat_byte(2)),
+        ];
+        fixture.l1_batches[1].l2_blocks[0].storage_logs = vec![
+            // Repeated write
+            StorageLog::new_write_log(first_key, H256::repeat_byte(3)),
+            // No-op write; isn't a part of the state diff
+            StorageLog::new_write_log(second_key, H256::repeat_byte(2)),
+        ];
+        fixture.insert(&mut conn).await;
+
+        let state_diff = conn
+            .storage_logs_dal()
+            .get_l1_batch_state_diff(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(state_diff.len(), 2);
+        // Writes must be ordered by address.
+        assert_eq!(state_diff[0].record.address, *second_key.address());
+        assert_eq!(state_diff[1].record.address, *first_key.address());
+        for write in &state_diff {
+            assert!(write.is_initial);
+            assert_eq!(write.record.enumeration_index, 0);
+            assert_ne!(write.enumeration_index, 0);
+        }
+
+        let state_diff = conn
+            .storage_logs_dal()
+            .get_l1_batch_state_diff(L1BatchNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(state_diff.len(), 1);
+        let write = &state_diff[0];
+        assert!(!write.is_initial);
+        assert_eq!(write.record.derived_key, first_key.hashed_key().0);
+        assert_eq!(write.record.enumeration_index, write.enumeration_index);
+        assert_eq!(
+            write.record.initial_value,
+            h256_to_u256(H256::repeat_byte(1))
+        );
+        assert_eq!(write.record.final_value, h256_to_u256(H256::repeat_byte(3)));
+    }
 }
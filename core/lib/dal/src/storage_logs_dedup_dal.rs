@@ -148,6 +148,51 @@ impl StorageLogsDedupDal<'_, '_> {
             .collect())
     }
 
+    /// Returns sealed L1 batches in the specified range that have no protective reads persisted, in the ascending order.
+    pub async fn get_l1_batches_without_protective_reads(
+        &mut self,
+        from_l1_batch: L1BatchNumber,
+        to_l1_batch: L1BatchNumber,
+        limit: usize,
+    ) -> DalResult<Vec<L1BatchNumber>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                number
+            FROM
+                l1_batches
+            WHERE
+                number BETWEEN $1 AND $2
+                AND is_sealed
+                AND NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        protective_reads
+                    WHERE
+                        protective_reads.l1_batch_number = l1_batches.number
+                )
+            ORDER BY
+                number
+            LIMIT
+                $3
+            "#,
+            i64::from(from_l1_batch.0),
+            i64::from(to_l1_batch.0),
+            limit as i64
+        )
+        .instrument("get_l1_batches_without_protective_reads")
+        .with_arg("from_l1_batch", &from_l1_batch)
+        .with_arg("to_l1_batch", &to_l1_batch)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| L1BatchNumber(row.number as u32))
+            .collect())
+    }
+
     async fn max_enumeration_index(&mut self) -> DalResult<Option<u64>> {
         Ok(sqlx::query!(
             r#"
@@ -343,6 +343,58 @@ impl TeeProofGenerationDal<'_, '_> {
         Ok(())
     }
 
+    /// Returns enclave measurements allowed in TEE attestation quotes, in the lexicographic order.
+    pub async fn get_allowed_measurements(&mut self) -> DalResult<Vec<Vec<u8>>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                measurement
+            FROM
+                tee_allowed_measurements
+            ORDER BY
+                measurement
+            "#
+        )
+        .instrument("get_allowed_measurements")
+        .fetch_all(self.storage)
+        .await?;
+        Ok(rows.into_iter().map(|row| row.measurement).collect())
+    }
+
+    /// Atomically replaces the set of enclave measurements allowed in TEE attestation quotes.
+    pub async fn set_allowed_measurements(&mut self, measurements: &[Vec<u8>]) -> DalResult<()> {
+        let mut transaction = self.storage.start_transaction().await?;
+        sqlx::query!(
+            r#"
+            DELETE FROM tee_allowed_measurements
+            "#
+        )
+        .instrument("set_allowed_measurements#delete")
+        .execute(&mut transaction)
+        .await?;
+
+        let measurements: Vec<_> = measurements.iter().map(Vec::as_slice).collect();
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            tee_allowed_measurements (measurement, created_at)
+            SELECT
+                u.measurement,
+                NOW()
+            FROM
+                UNNEST($1::bytea []) AS u (measurement)
+            ON CONFLICT (measurement) DO NOTHING
+            "#,
+            &measurements as &[&[u8]]
+        )
+        .instrument("set_allowed_measurements#insert")
+        .with_arg("measurements.len", &measurements.len())
+        .execute(&mut transaction)
+        .await?;
+
+        transaction.commit().await
+    }
+
     /// For testing purposes only.
     pub async fn get_oldest_picked_by_prover_batch(&mut self) -> DalResult<Option<L1BatchNumber>> {
         let query = sqlx::query!(
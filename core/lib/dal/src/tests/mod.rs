@@ -14,7 +14,7 @@ use zksync_types::{
     protocol_upgrade::{ProtocolUpgradeTx, ProtocolUpgradeTxCommonData},
     snapshots::SnapshotRecoveryStatus,
     Address, Execute, K256PrivateKey, L1BatchNumber, L1BlockNumber, L1TxCommonData, L2BlockNumber,
-    L2ChainId, PriorityOpId, ProtocolVersion, ProtocolVersionId, H160, H256, U256,
+    L2ChainId, PriorityOpId, ProtocolVersion, ProtocolVersionId, Transaction, H160, H256, U256,
 };
 use zksync_vm_interface::{
     tracer::ValidationTraces, TransactionExecutionMetrics, TransactionExecutionResult,
@@ -155,10 +155,13 @@ pub(crate) fn mock_protocol_upgrade_transaction() -> ProtocolUpgradeTx {
     }
 }
 
-pub(crate) fn mock_execution_result(transaction: L2Tx) -> TransactionExecutionResult {
+pub(crate) fn mock_execution_result(
+    transaction: impl Into<Transaction>,
+) -> TransactionExecutionResult {
+    let transaction = transaction.into();
     TransactionExecutionResult {
         hash: transaction.hash(),
-        transaction: transaction.into(),
+        transaction,
         execution_info: VmExecutionMetrics::default(),
         execution_status: TxExecutionStatus::Success,
         refunded_gas: 0,
@@ -326,6 +326,20 @@ impl TransactionsDal<'_, '_> {
         let timestamp_asserter_range_end = validation_traces.timestamp_asserter_range.map(|x| {
             NaiveDateTime::from_timestamp_opt(min(x.end, max_timestamp) as i64, 0).unwrap()
         });
+        let l2_block_number_range_start = validation_traces
+            .l2_block_number_range
+            .as_ref()
+            .map(|x| min(x.start, i64::MAX as u64) as i64);
+        let l2_block_number_range_end = validation_traces
+            .l2_block_number_range
+            .as_ref()
+            .map(|x| min(x.end, i64::MAX as u64) as i64);
+        let expected_storage_values =
+            (!validation_traces.expected_storage_values.is_empty()).then(|| {
+                serde_json::to_value(&validation_traces.expected_storage_values).unwrap_or_else(
+                    |_| panic!("cannot serialize expected storage values for tx {tx_hash:?}"),
+                )
+            });
         // Besides just adding or updating(on conflict) the record, we want to extract some info
         // from the query below, to indicate what actually happened:
         // 1) transaction is added
@@ -360,6 +374,9 @@ impl TransactionsDal<'_, '_> {
                 received_at,
                 timestamp_asserter_range_start,
                 timestamp_asserter_range_end,
+                l2_block_number_range_start,
+                l2_block_number_range_end,
+                expected_storage_values,
                 created_at,
                 updated_at
             )
@@ -392,6 +409,9 @@ impl TransactionsDal<'_, '_> {
                 $19,
                 $20,
                 $21,
+                $22,
+                $23,
+                $24,
                 NOW(),
                 NOW()
             )
@@ -424,6 +444,9 @@ impl TransactionsDal<'_, '_> {
             received_at = $19,
             timestamp_asserter_range_start = $20,
             timestamp_asserter_range_end = $21,
+            l2_block_number_range_start = $22,
+            l2_block_number_range_end = $23,
+            expected_storage_values = $24,
             created_at = NOW(),
             updated_at = NOW(),
             error = NULL
@@ -462,6 +485,9 @@ impl TransactionsDal<'_, '_> {
             received_at,
             timestamp_asserter_range_start,
             timestamp_asserter_range_end,
+            l2_block_number_range_start,
+            l2_block_number_range_end,
+            expected_storage_values,
         )
         .instrument("insert_transaction_l2")
         .with_arg("tx_hash", &tx_hash)
@@ -1358,7 +1384,21 @@ impl TransactionsDal<'_, '_> {
             &l1_effective_gas_prices,
         );
 
-        instrumentation.with(query).execute(self.storage).await?;
+        let result = instrumentation
+            .clone()
+            .with(query)
+            .execute(self.storage)
+            .await?;
+        // Priority operations can be removed by the Ethereum watcher if their L1 blocks are reorged. Sealing such operations
+        // must fail rather than silently include them in an L2 block.
+        if result.rows_affected() != l1_txs_len as u64 {
+            let err = instrumentation.constraint_error(anyhow::anyhow!(
+                "only {} out of {l1_txs_len} executed L1 transactions are present in the DB; \
+                 some of them were likely removed by an L1 reorg",
+                result.rows_affected()
+            ));
+            return Err(err);
+        }
         Ok(())
     }
 
@@ -1739,6 +1779,72 @@ impl TransactionsDal<'_, '_> {
         Ok(rows.len())
     }
 
+    /// Locks priority operations originating from L1 blocks with number `>= l1_block` until the end of the current
+    /// DB transaction, so that they cannot be concurrently fetched into the mempool or marked as executed.
+    /// Returns serial IDs of the locked operations together with flags whether an operation was executed in an L2 block.
+    pub async fn lock_l1_txs_since(
+        &mut self,
+        l1_block: L1BlockNumber,
+    ) -> DalResult<Vec<(PriorityOpId, bool)>> {
+        // Note, that transactions are locked in order of their hashes to avoid deadlocks with other UPDATE queries.
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                priority_op_id AS "priority_op_id!",
+                miniblock_number IS NOT NULL AS "is_executed!"
+            FROM
+                transactions
+            WHERE
+                is_priority = TRUE
+                AND l1_block_number >= $1
+            ORDER BY
+                hash
+            FOR UPDATE
+            "#,
+            l1_block.0 as i32
+        )
+        .instrument("lock_l1_txs_since")
+        .with_arg("l1_block", &l1_block)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (PriorityOpId(row.priority_op_id as u64), row.is_executed))
+            .collect())
+    }
+
+    /// Removes priority operations originating from L1 blocks with number `>= l1_block` that were not executed yet.
+    /// Returns serial IDs of the removed operations together with flags whether an operation was fetched
+    /// into the state keeper mempool.
+    pub async fn remove_unexecuted_l1_txs_since(
+        &mut self,
+        l1_block: L1BlockNumber,
+    ) -> DalResult<Vec<(PriorityOpId, bool)>> {
+        let rows = sqlx::query!(
+            r#"
+            DELETE FROM transactions
+            WHERE
+                is_priority = TRUE
+                AND l1_block_number >= $1
+                AND miniblock_number IS NULL
+            RETURNING
+            priority_op_id AS "priority_op_id!",
+            in_mempool
+            "#,
+            l1_block.0 as i32
+        )
+        .instrument("remove_unexecuted_l1_txs_since")
+        .with_arg("l1_block", &l1_block)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (PriorityOpId(row.priority_op_id as u64), row.in_mempool))
+            .collect())
+    }
+
     /// Fetches new updates for mempool. Returns new transactions and current nonces for related accounts;
     /// the latter are only used to bootstrap mempool for given account.
     pub async fn sync_mempool(
@@ -2269,7 +2375,9 @@ mod tests {
 
     use super::*;
     use crate::{
-        tests::{create_l2_block_header, mock_execution_result, mock_l2_transaction},
+        tests::{
+            create_l2_block_header, mock_execution_result, mock_l1_execute, mock_l2_transaction,
+        },
         ConnectionPool, Core, CoreDal,
     };
 
@@ -2354,4 +2462,47 @@ mod tests {
             .unwrap();
         assert_eq!(tx_from_db[0].hash, tx_hash);
     }
+
+    #[tokio::test]
+    async fn marking_removed_l1_tx_as_executed_fails() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .insert_l2_block(&create_l2_block_header(1))
+            .await
+            .unwrap();
+
+        let tx = mock_l1_execute();
+        conn.transactions_dal()
+            .insert_transaction_l1(&tx, L1BlockNumber(10))
+            .await
+            .unwrap();
+        let locked_ops = conn
+            .transactions_dal()
+            .lock_l1_txs_since(L1BlockNumber(10))
+            .await
+            .unwrap();
+        assert_eq!(locked_ops, [(tx.serial_id(), false)]);
+        let removed_ops = conn
+            .transactions_dal()
+            .remove_unexecuted_l1_txs_since(L1BlockNumber(10))
+            .await
+            .unwrap();
+        assert_eq!(removed_ops, [(tx.serial_id(), false)]);
+
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_l2_block(
+                L2BlockNumber(1),
+                &[mock_execution_result(tx)],
+                1.into(),
+                ProtocolVersionId::latest(),
+                false,
+            )
+            .await
+            .unwrap_err();
+    }
 }
@@ -9,8 +9,9 @@ use zksync_db_connection::{
     interpolate_query, match_query_as,
 };
 use zksync_types::{
-    api, api::TransactionReceipt, block::build_bloom, Address, BloomInput, L2BlockNumber,
-    L2ChainId, Transaction, CONTRACT_DEPLOYER_ADDRESS, H256, U256,
+    api, api::TransactionReceipt, block::build_bloom, Address, BloomInput, L1BatchNumber,
+    L1BlockNumber, L2BlockNumber, L2ChainId, PriorityOpId, Transaction, CONTRACT_DEPLOYER_ADDRESS,
+    H256, U256,
 };
 use zksync_vm_interface::VmEvent;
 
@@ -372,6 +373,181 @@ impl TransactionsWeb3Dal<'_, '_> {
         Ok(hashes)
     }
 
+    /// Returns priority operations starting from `from_id` that are not removed from the L1 priority queue yet,
+    /// i.e., are not included into an L1 batch executed on L1. Operations are ordered by their serial ID.
+    pub async fn get_priority_queue(
+        &mut self,
+        from_id: PriorityOpId,
+        limit: usize,
+    ) -> DalResult<Vec<api::PriorityOpInfo>> {
+        // Priority operations are executed in the order of their serial IDs, so operations executed on L1 are skipped
+        // by bounding serial IDs from below rather than by filtering out all historical operations.
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                priority_op_id AS "priority_op_id!",
+                hash,
+                l1_block_number,
+                miniblock_number,
+                l1_batch_number,
+                received_at,
+                COALESCE(
+                    (
+                        SELECT
+                            MAX(number) + 1
+                        FROM
+                            l1_batches
+                        WHERE
+                            is_sealed
+                    ),
+                    (
+                        SELECT
+                            MAX(l1_batch_number) + 1
+                        FROM
+                            snapshot_recovery
+                    ),
+                    0
+                ) AS "pending_batch!"
+            FROM
+                transactions
+            WHERE
+                is_priority = TRUE
+                AND priority_op_id >= GREATEST(
+                    $1,
+                    COALESCE(
+                        (
+                            SELECT
+                                priority_op_id + 1
+                            FROM
+                                transactions
+                            WHERE
+                                priority_op_id IS NOT NULL
+                                AND l1_batch_number <= (
+                                    SELECT
+                                        number
+                                    FROM
+                                        l1_batches
+                                    JOIN
+                                        eth_txs_history AS execute_tx
+                                        ON l1_batches.eth_execute_tx_id = execute_tx.eth_tx_id
+                                    WHERE
+                                        execute_tx.confirmed_at IS NOT NULL
+                                    ORDER BY
+                                        number DESC
+                                    LIMIT
+                                        1
+                                )
+                            ORDER BY
+                                priority_op_id DESC
+                            LIMIT
+                                1
+                        ),
+                        0
+                    )
+                )
+            ORDER BY
+                priority_op_id
+            LIMIT
+                $2
+            "#,
+            from_id.0 as i64,
+            limit as i64
+        )
+        .instrument("get_priority_queue")
+        .with_arg("from_id", &from_id)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let l2_block_number = row.miniblock_number.map(|n| L2BlockNumber(n as u32));
+                let (status, l1_batch_number) = match (l2_block_number, row.l1_batch_number) {
+                    (_, Some(batch)) => (api::PriorityOpStatus::IncludedInL1Batch, batch),
+                    (Some(_), None) => {
+                        (api::PriorityOpStatus::IncludedInL2Block, row.pending_batch)
+                    }
+                    (None, None) => (api::PriorityOpStatus::Pending, row.pending_batch),
+                };
+                api::PriorityOpInfo {
+                    serial_id: PriorityOpId(row.priority_op_id as u64),
+                    tx_hash: H256::from_slice(&row.hash),
+                    eth_block: row.l1_block_number.map(|n| L1BlockNumber(n as u32)),
+                    received_at: row.received_at.and_utc(),
+                    status,
+                    l2_block_number,
+                    l1_batch_number: L1BatchNumber(l1_batch_number as u32),
+                }
+            })
+            .collect())
+    }
+
+    /// Returns the processing status of an L1-originated transaction (a priority operation or a protocol upgrade
+    /// transaction) with the specified canonical hash. Returns `None` if the node doesn't know such a transaction.
+    pub async fn get_l1_tx_processing_status(
+        &mut self,
+        hash: H256,
+    ) -> DalResult<Option<api::L1TxProcessingStatus>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                transactions.priority_op_id,
+                transactions.upgrade_id,
+                transactions.l1_block_number,
+                transactions.received_at,
+                transactions.miniblock_number,
+                transactions.l1_batch_number,
+                transactions.error,
+                execute_tx.tx_hash AS "eth_execute_tx_hash?"
+            FROM
+                transactions
+            LEFT JOIN l1_batches ON l1_batches.number = transactions.l1_batch_number
+            LEFT JOIN eth_txs_history AS execute_tx
+                ON (
+                    l1_batches.eth_execute_tx_id = execute_tx.eth_tx_id
+                    AND execute_tx.confirmed_at IS NOT NULL
+                )
+            WHERE
+                transactions.hash = $1
+                AND transactions.is_priority = TRUE
+            "#,
+            hash.as_bytes()
+        )
+        .instrument("get_l1_tx_processing_status")
+        .with_arg("hash", &hash)
+        .fetch_optional(self.storage)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let eth_execute_tx_hash = row
+            .eth_execute_tx_hash
+            .map(|hash| hash.parse::<H256>().unwrap());
+        let status = if row.error.is_some() {
+            api::TransactionStatus::Failed
+        } else if eth_execute_tx_hash.is_some() {
+            api::TransactionStatus::Verified
+        } else if row.miniblock_number.is_some() {
+            api::TransactionStatus::Included
+        } else {
+            api::TransactionStatus::Pending
+        };
+
+        Ok(Some(api::L1TxProcessingStatus {
+            tx_hash: hash,
+            serial_id: row.priority_op_id.map(|id| PriorityOpId(id as u64)),
+            upgrade_id: row.upgrade_id.map(|id| (id as u16).try_into().unwrap()),
+            eth_block: row.l1_block_number.map(|n| L1BlockNumber(n as u32)),
+            received_at: row.received_at.and_utc(),
+            status,
+            l2_block_number: row.miniblock_number.map(|n| L2BlockNumber(n as u32)),
+            l1_batch_number: row.l1_batch_number.map(|n| L1BatchNumber(n as u32)),
+            eth_execute_tx_hash,
+        }))
+    }
+
     /// `committed_next_nonce` should equal the nonce for `initiator_address` in the storage.
     pub async fn next_nonce_by_initiator_account(
         &mut self,
@@ -490,14 +666,20 @@ impl TransactionsWeb3Dal<'_, '_> {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::{collections::HashMap, slice};
 
-    use zksync_types::{l2::L2Tx, Nonce, ProtocolVersion, ProtocolVersionId};
+    use zksync_types::{
+        aggregated_operations::AggregatedActionType, l2::L2Tx, Nonce, ProtocolVersion,
+        ProtocolVersionId,
+    };
     use zksync_vm_interface::{tracer::ValidationTraces, TransactionExecutionMetrics};
 
     use super::*;
     use crate::{
-        tests::{create_l2_block_header, mock_execution_result, mock_l2_transaction},
+        tests::{
+            create_l1_batch_header, create_l2_block_header, mock_execution_result, mock_l1_execute,
+            mock_l2_transaction, mock_protocol_upgrade_transaction,
+        },
         ConnectionPool, Core, CoreDal,
     };
 
@@ -726,6 +908,262 @@ mod tests {
         assert_eq!(raw_txs[0].hash(), tx_hash);
     }
 
+    #[tokio::test]
+    async fn getting_priority_queue() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+
+        let l1_txs: Vec<_> = (0..3)
+            .map(|serial_id| {
+                let mut tx = mock_l1_execute();
+                tx.common_data.serial_id = PriorityOpId(serial_id);
+                tx.common_data.canonical_tx_hash = H256::from_low_u64_be(serial_id + 1);
+                tx
+            })
+            .collect();
+        for tx in &l1_txs {
+            conn.transactions_dal()
+                .insert_transaction_l1(tx, L1BlockNumber(10))
+                .await
+                .unwrap();
+        }
+
+        // Execute the first 2 transactions in separate L2 blocks and seal them in an L1 batch.
+        let tx_results: Vec<_> = l1_txs[..2]
+            .iter()
+            .map(|tx| mock_execution_result(tx.clone()))
+            .collect();
+        for (number, tx_result) in (0..).zip(&tx_results) {
+            conn.blocks_dal()
+                .insert_l2_block(&create_l2_block_header(number))
+                .await
+                .unwrap();
+            conn.transactions_dal()
+                .mark_txs_as_executed_in_l2_block(
+                    L2BlockNumber(number),
+                    slice::from_ref(tx_result),
+                    1.into(),
+                    ProtocolVersionId::latest(),
+                    false,
+                )
+                .await
+                .unwrap();
+        }
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch_header(0))
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .mark_l2_blocks_as_executed_in_l1_batch(L1BatchNumber(0))
+            .await
+            .unwrap();
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_l1_batch(L1BatchNumber(0), &tx_results)
+            .await
+            .unwrap();
+
+        let queue = conn
+            .transactions_web3_dal()
+            .get_priority_queue(PriorityOpId(0), 10)
+            .await
+            .unwrap();
+        let queue: Vec<_> = queue
+            .iter()
+            .map(|op| {
+                (
+                    op.serial_id,
+                    op.tx_hash,
+                    op.status,
+                    op.l2_block_number,
+                    op.l1_batch_number,
+                )
+            })
+            .collect();
+        assert_eq!(
+            queue,
+            [
+                (
+                    PriorityOpId(0),
+                    l1_txs[0].hash(),
+                    api::PriorityOpStatus::IncludedInL1Batch,
+                    Some(L2BlockNumber(0)),
+                    L1BatchNumber(0)
+                ),
+                (
+                    PriorityOpId(1),
+                    l1_txs[1].hash(),
+                    api::PriorityOpStatus::IncludedInL1Batch,
+                    Some(L2BlockNumber(1)),
+                    L1BatchNumber(0)
+                ),
+                (
+                    PriorityOpId(2),
+                    l1_txs[2].hash(),
+                    api::PriorityOpStatus::Pending,
+                    None,
+                    L1BatchNumber(1)
+                ),
+            ]
+        );
+
+        let queue = conn
+            .transactions_web3_dal()
+            .get_priority_queue(PriorityOpId(1), 1)
+            .await
+            .unwrap();
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].serial_id, PriorityOpId(1));
+        assert_eq!(queue[0].eth_block, Some(L1BlockNumber(10)));
+
+        // Execute the L1 batch on L1; its operations are removed from the queue.
+        let eth_tx_id = conn
+            .eth_sender_dal()
+            .save_eth_tx(
+                0,
+                vec![],
+                AggregatedActionType::Execute,
+                Address::default(),
+                Some(1),
+                None,
+                None,
+                false,
+            )
+            .await
+            .unwrap()
+            .id;
+        conn.blocks_dal()
+            .set_eth_tx_id(
+                L1BatchNumber(0)..=L1BatchNumber(0),
+                eth_tx_id,
+                AggregatedActionType::Execute,
+            )
+            .await
+            .unwrap();
+        let execute_tx_hash = H256::repeat_byte(0xee);
+        conn.eth_sender_dal()
+            .insert_tx_history(eth_tx_id, 0, 0, None, execute_tx_hash, &[], 0)
+            .await
+            .unwrap();
+        conn.eth_sender_dal()
+            .confirm_tx(execute_tx_hash, 0.into())
+            .await
+            .unwrap();
+
+        let queue = conn
+            .transactions_web3_dal()
+            .get_priority_queue(PriorityOpId(0), 10)
+            .await
+            .unwrap();
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].serial_id, PriorityOpId(2));
+        assert_eq!(queue[0].status, api::PriorityOpStatus::Pending);
+    }
+
+    #[tokio::test]
+    async fn getting_l1_tx_processing_status() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+
+        let mut l1_tx = mock_l1_execute();
+        l1_tx.common_data.serial_id = PriorityOpId(5);
+        l1_tx.common_data.canonical_tx_hash = H256::repeat_byte(0x11);
+        conn.transactions_dal()
+            .insert_transaction_l1(&l1_tx, L1BlockNumber(10))
+            .await
+            .unwrap();
+        let upgrade_tx = mock_protocol_upgrade_transaction();
+        conn.transactions_dal()
+            .insert_system_transaction(&upgrade_tx)
+            .await
+            .unwrap();
+
+        let missing_status = conn
+            .transactions_web3_dal()
+            .get_l1_tx_processing_status(H256::repeat_byte(0xff))
+            .await
+            .unwrap();
+        assert_eq!(missing_status, None);
+
+        let status = conn
+            .transactions_web3_dal()
+            .get_l1_tx_processing_status(l1_tx.hash())
+            .await
+            .unwrap()
+            .expect("no status for L1 tx");
+        assert_eq!(status.tx_hash, l1_tx.hash());
+        assert_eq!(status.serial_id, Some(PriorityOpId(5)));
+        assert_eq!(status.upgrade_id, None);
+        assert_eq!(status.eth_block, Some(L1BlockNumber(10)));
+        assert_eq!(status.status, api::TransactionStatus::Pending);
+        assert_eq!(status.l2_block_number, None);
+        assert_eq!(status.l1_batch_number, None);
+
+        let status = conn
+            .transactions_web3_dal()
+            .get_l1_tx_processing_status(upgrade_tx.common_data.hash())
+            .await
+            .unwrap()
+            .expect("no status for upgrade tx");
+        assert_eq!(status.serial_id, None);
+        assert_eq!(status.upgrade_id, Some(upgrade_tx.common_data.upgrade_id));
+        assert_eq!(status.status, api::TransactionStatus::Pending);
+
+        let tx_result = mock_execution_result(l1_tx.clone());
+        conn.blocks_dal()
+            .insert_l2_block(&create_l2_block_header(0))
+            .await
+            .unwrap();
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_l2_block(
+                L2BlockNumber(0),
+                slice::from_ref(&tx_result),
+                1.into(),
+                ProtocolVersionId::latest(),
+                false,
+            )
+            .await
+            .unwrap();
+        let status = conn
+            .transactions_web3_dal()
+            .get_l1_tx_processing_status(l1_tx.hash())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(status.status, api::TransactionStatus::Included);
+        assert_eq!(status.l2_block_number, Some(L2BlockNumber(0)));
+        assert_eq!(status.l1_batch_number, None);
+
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch_header(0))
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .mark_l2_blocks_as_executed_in_l1_batch(L1BatchNumber(0))
+            .await
+            .unwrap();
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_l1_batch(L1BatchNumber(0), slice::from_ref(&tx_result))
+            .await
+            .unwrap();
+        let status = conn
+            .transactions_web3_dal()
+            .get_l1_tx_processing_status(l1_tx.hash())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(status.status, api::TransactionStatus::Included);
+        assert_eq!(status.l1_batch_number, Some(L1BatchNumber(0)));
+        assert_eq!(status.eth_execute_tx_hash, None);
+    }
+
     #[tokio::test]
     async fn getting_next_nonce_by_initiator_account() {
         let connection_pool = ConnectionPool::<Core>::test_pool().await;
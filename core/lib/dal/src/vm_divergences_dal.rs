@@ -0,0 +1,177 @@
+use std::time::Duration;
+
+use zksync_db_connection::{
+    connection::Connection, error::DalResult, instrument::InstrumentExt,
+    utils::pg_interval_from_duration,
+};
+use zksync_types::{L1BatchNumber, H256};
+
+use crate::Core;
+
+/// Divergence between the legacy and fast VM detected during shadow execution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VmDivergence {
+    pub l1_batch_number: L1BatchNumber,
+    /// Hash of the last transaction executed before the divergence was detected. `None` if the divergence
+    /// was detected before executing any transactions in the batch.
+    pub tx_hash: Option<H256>,
+    /// Human-readable description of diverging outputs.
+    pub errors: String,
+}
+
+#[derive(Debug)]
+pub struct VmDivergencesDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl VmDivergencesDal<'_, '_> {
+    pub async fn insert_divergence(&mut self, divergence: &VmDivergence) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            vm_divergences (l1_batch_number, tx_hash, errors, created_at)
+            VALUES
+            ($1, $2, $3, NOW())
+            "#,
+            i64::from(divergence.l1_batch_number.0),
+            divergence.tx_hash.as_ref().map(H256::as_bytes),
+            &divergence.errors,
+        )
+        .instrument("insert_divergence")
+        .with_arg("l1_batch_number", &divergence.l1_batch_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Deletes divergences persisted more than `retention` ago. Returns the number of deleted divergences.
+    pub async fn delete_divergences_older_than(&mut self, retention: Duration) -> DalResult<u64> {
+        let retention = pg_interval_from_duration(retention);
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM vm_divergences
+            WHERE
+                created_at < NOW() - $1::INTERVAL
+            "#,
+            &retention
+        )
+        .instrument("delete_divergences_older_than")
+        .with_arg("retention", &retention)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Returns divergences detected in the specified L1 batch, in the order of their detection.
+    pub async fn get_divergences(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<VmDivergence>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                tx_hash,
+                errors
+            FROM
+                vm_divergences
+            WHERE
+                l1_batch_number = $1
+            ORDER BY
+                id
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_divergences")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| VmDivergence {
+                l1_batch_number,
+                tx_hash: row.tx_hash.as_deref().map(H256::from_slice),
+                errors: row.errors,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn inserting_and_getting_divergences() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+
+        let divergences = [
+            VmDivergence {
+                l1_batch_number: L1BatchNumber(1),
+                tx_hash: None,
+                errors: "final_state.events mismatch".to_owned(),
+            },
+            VmDivergence {
+                l1_batch_number: L1BatchNumber(1),
+                tx_hash: Some(H256::repeat_byte(1)),
+                errors: "refunds mismatch".to_owned(),
+            },
+        ];
+        for divergence in &divergences {
+            conn.vm_divergences_dal()
+                .insert_divergence(divergence)
+                .await
+                .unwrap();
+        }
+
+        let persisted = conn
+            .vm_divergences_dal()
+            .get_divergences(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(persisted, divergences);
+        let persisted = conn
+            .vm_divergences_dal()
+            .get_divergences(L1BatchNumber(2))
+            .await
+            .unwrap();
+        assert!(persisted.is_empty());
+    }
+
+    #[tokio::test]
+    async fn pruning_divergences() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let divergence = VmDivergence {
+            l1_batch_number: L1BatchNumber(1),
+            tx_hash: None,
+            errors: "refunds mismatch".to_owned(),
+        };
+        conn.vm_divergences_dal()
+            .insert_divergence(&divergence)
+            .await
+            .unwrap();
+
+        let deleted_count = conn
+            .vm_divergences_dal()
+            .delete_divergences_older_than(Duration::from_secs(3_600))
+            .await
+            .unwrap();
+        assert_eq!(deleted_count, 0);
+
+        let deleted_count = conn
+            .vm_divergences_dal()
+            .delete_divergences_older_than(Duration::ZERO)
+            .await
+            .unwrap();
+        assert_eq!(deleted_count, 1);
+        let persisted = conn
+            .vm_divergences_dal()
+            .get_divergences(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert!(persisted.is_empty());
+    }
+}
@@ -0,0 +1,500 @@
+use strum::{Display, EnumString};
+use zksync_db_connection::{
+    connection::Connection,
+    error::{DalResult, SqlxContext},
+    instrument::InstrumentExt,
+};
+use zksync_types::{Address, L1BatchNumber, H256, U256};
+
+use crate::{
+    models::{bigdecimal_to_u256, u256_to_big_decimal},
+    Core,
+};
+
+/// Status of a withdrawal processed by the withdrawal finalizer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+pub enum WithdrawalFinalizationStatus {
+    /// Finalization transaction was sent to L1, but it's not mined yet.
+    #[strum(serialize = "sent")]
+    Sent,
+    /// Withdrawal was finalized by the transaction sent by the finalizer.
+    #[strum(serialize = "finalized")]
+    Finalized,
+    /// Finalization transaction sent by the finalizer has reverted.
+    #[strum(serialize = "failed")]
+    Failed,
+    /// Withdrawal was finalized by a third party (e.g., by the recipient) before the finalizer got to it.
+    #[strum(serialize = "already_finalized")]
+    AlreadyFinalized,
+    /// Withdrawal amount exceeds the limit for the withdrawn token, so it wasn't finalized.
+    #[strum(serialize = "over_limit")]
+    OverLimit,
+    /// Withdrawal amount is below the minimum amount for the withdrawn token, so it wasn't finalized.
+    #[strum(serialize = "below_limit")]
+    BelowLimit,
+    /// Withdrawn token isn't allowed to be finalized automatically, so the withdrawal wasn't finalized.
+    #[strum(serialize = "unlisted_token")]
+    UnlistedToken,
+}
+
+/// Withdrawal processed by the withdrawal finalizer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WithdrawalFinalization {
+    pub l1_batch_number: L1BatchNumber,
+    /// Index of the L2-to-L1 log for the withdrawal message among all L2-to-L1 logs in the batch.
+    pub l2_message_index: u32,
+    pub l2_tx_number_in_batch: u16,
+    pub l1_token: Address,
+    pub l1_receiver: Address,
+    pub amount: U256,
+    pub status: WithdrawalFinalizationStatus,
+    /// Finalization transaction sent by the finalizer, if any.
+    pub l1_tx: Option<WithdrawalFinalizationTx>,
+}
+
+/// Finalization transaction sent by the withdrawal finalizer. If the transaction is resent with bumped fees,
+/// this information corresponds to the latest sent transaction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WithdrawalFinalizationTx {
+    pub hash: H256,
+    pub nonce: u64,
+    /// L1 block number at which the transaction was sent.
+    pub sent_at_block: u64,
+    pub max_fee_per_gas: u64,
+    pub priority_fee_per_gas: u64,
+}
+
+impl WithdrawalFinalizationTx {
+    fn from_columns(
+        hash: Option<Vec<u8>>,
+        nonce: Option<i64>,
+        sent_at_block: Option<i64>,
+        max_fee_per_gas: Option<i64>,
+        priority_fee_per_gas: Option<i64>,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let Some(hash) = hash else {
+            return Ok(None);
+        };
+        let nonce = nonce.ok_or("missing value").decode_column("l1_tx_nonce")?;
+        let sent_at_block = sent_at_block
+            .ok_or("missing value")
+            .decode_column("l1_tx_sent_at_block")?;
+        let max_fee_per_gas = max_fee_per_gas
+            .ok_or("missing value")
+            .decode_column("l1_tx_max_fee_per_gas")?;
+        let priority_fee_per_gas = priority_fee_per_gas
+            .ok_or("missing value")
+            .decode_column("l1_tx_priority_fee_per_gas")?;
+        Ok(Some(Self {
+            hash: H256::from_slice(&hash),
+            nonce: nonce as u64,
+            sent_at_block: sent_at_block as u64,
+            max_fee_per_gas: max_fee_per_gas as u64,
+            priority_fee_per_gas: priority_fee_per_gas as u64,
+        }))
+    }
+}
+
+/// DAL methods related to finalizing L2 -> L1 withdrawals.
+#[derive(Debug)]
+pub struct WithdrawalFinalizerDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl WithdrawalFinalizerDal<'_, '_> {
+    /// Returns the latest L1 batch with all withdrawals processed by the withdrawal finalizer.
+    pub async fn get_last_processed_l1_batch(&mut self) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MAX(l1_batch_number) AS "number"
+            FROM
+                l1_batch_withdrawal_finalizations
+            "#
+        )
+        .instrument("get_last_processed_l1_batch")
+        .report_latency()
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row.number.map(|number| L1BatchNumber(number as u32)))
+    }
+
+    /// Marks all withdrawals in the specified L1 batch as processed.
+    pub async fn mark_l1_batch_as_processed(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        withdrawals: usize,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            l1_batch_withdrawal_finalizations (l1_batch_number, withdrawals, created_at)
+            VALUES
+            ($1, $2, NOW())
+            "#,
+            i64::from(l1_batch_number.0),
+            withdrawals as i32
+        )
+        .instrument("mark_l1_batch_as_processed")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns all processed withdrawals in the specified L1 batch ordered by the message index.
+    pub async fn get_withdrawal_finalizations(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<WithdrawalFinalization>> {
+        sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                l2_message_index,
+                l2_tx_number_in_batch,
+                l1_token,
+                l1_receiver,
+                amount,
+                status,
+                l1_tx_hash,
+                l1_tx_nonce,
+                l1_tx_sent_at_block,
+                l1_tx_max_fee_per_gas,
+                l1_tx_priority_fee_per_gas
+            FROM
+                withdrawal_finalizations
+            WHERE
+                l1_batch_number = $1
+            ORDER BY
+                l2_message_index
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .try_map(|row| {
+            Ok(WithdrawalFinalization {
+                l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+                l2_message_index: row.l2_message_index as u32,
+                l2_tx_number_in_batch: row.l2_tx_number_in_batch as u16,
+                l1_token: Address::from_slice(&row.l1_token),
+                l1_receiver: Address::from_slice(&row.l1_receiver),
+                amount: bigdecimal_to_u256(row.amount),
+                status: row.status.parse().decode_column("status")?,
+                l1_tx: WithdrawalFinalizationTx::from_columns(
+                    row.l1_tx_hash,
+                    row.l1_tx_nonce,
+                    row.l1_tx_sent_at_block,
+                    row.l1_tx_max_fee_per_gas,
+                    row.l1_tx_priority_fee_per_gas,
+                )?,
+            })
+        })
+        .instrument("get_withdrawal_finalizations")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_all(self.storage)
+        .await
+    }
+
+    /// Returns the earliest withdrawal with a sent, but not yet mined finalization transaction.
+    pub async fn get_pending_withdrawal_finalization(
+        &mut self,
+    ) -> DalResult<Option<WithdrawalFinalization>> {
+        sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                l2_message_index,
+                l2_tx_number_in_batch,
+                l1_token,
+                l1_receiver,
+                amount,
+                status,
+                l1_tx_hash,
+                l1_tx_nonce,
+                l1_tx_sent_at_block,
+                l1_tx_max_fee_per_gas,
+                l1_tx_priority_fee_per_gas
+            FROM
+                withdrawal_finalizations
+            WHERE
+                status = 'sent'
+            ORDER BY
+                l1_batch_number,
+                l2_message_index
+            LIMIT
+                1
+            "#
+        )
+        .try_map(|row| {
+            Ok(WithdrawalFinalization {
+                l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+                l2_message_index: row.l2_message_index as u32,
+                l2_tx_number_in_batch: row.l2_tx_number_in_batch as u16,
+                l1_token: Address::from_slice(&row.l1_token),
+                l1_receiver: Address::from_slice(&row.l1_receiver),
+                amount: bigdecimal_to_u256(row.amount),
+                status: row.status.parse().decode_column("status")?,
+                l1_tx: WithdrawalFinalizationTx::from_columns(
+                    row.l1_tx_hash,
+                    row.l1_tx_nonce,
+                    row.l1_tx_sent_at_block,
+                    row.l1_tx_max_fee_per_gas,
+                    row.l1_tx_priority_fee_per_gas,
+                )?,
+            })
+        })
+        .instrument("get_pending_withdrawal_finalization")
+        .fetch_optional(self.storage)
+        .await
+    }
+
+    pub async fn insert_withdrawal_finalization(
+        &mut self,
+        withdrawal: &WithdrawalFinalization,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            withdrawal_finalizations (
+                l1_batch_number,
+                l2_message_index,
+                l2_tx_number_in_batch,
+                l1_token,
+                l1_receiver,
+                amount,
+                status,
+                l1_tx_hash,
+                l1_tx_nonce,
+                l1_tx_sent_at_block,
+                l1_tx_max_fee_per_gas,
+                l1_tx_priority_fee_per_gas,
+                created_at,
+                updated_at
+            )
+            VALUES
+            ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, NOW(), NOW())
+            "#,
+            i64::from(withdrawal.l1_batch_number.0),
+            withdrawal.l2_message_index as i32,
+            i32::from(withdrawal.l2_tx_number_in_batch),
+            withdrawal.l1_token.as_bytes(),
+            withdrawal.l1_receiver.as_bytes(),
+            u256_to_big_decimal(withdrawal.amount),
+            withdrawal.status.to_string(),
+            withdrawal.l1_tx.as_ref().map(|tx| tx.hash.as_bytes()),
+            withdrawal.l1_tx.map(|tx| tx.nonce as i64),
+            withdrawal.l1_tx.map(|tx| tx.sent_at_block as i64),
+            withdrawal.l1_tx.map(|tx| tx.max_fee_per_gas as i64),
+            withdrawal.l1_tx.map(|tx| tx.priority_fee_per_gas as i64)
+        )
+        .instrument("insert_withdrawal_finalization")
+        .with_arg("l1_batch_number", &withdrawal.l1_batch_number)
+        .with_arg("l2_message_index", &withdrawal.l2_message_index)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Updates the finalization transaction after it was resent with bumped fees.
+    pub async fn set_withdrawal_finalization_tx(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        l2_message_index: u32,
+        tx: &WithdrawalFinalizationTx,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE withdrawal_finalizations
+            SET
+                l1_tx_hash = $3,
+                l1_tx_nonce = $4,
+                l1_tx_sent_at_block = $5,
+                l1_tx_max_fee_per_gas = $6,
+                l1_tx_priority_fee_per_gas = $7,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $1
+                AND l2_message_index = $2
+                AND status = 'sent'
+            "#,
+            i64::from(l1_batch_number.0),
+            l2_message_index as i32,
+            tx.hash.as_bytes(),
+            tx.nonce as i64,
+            tx.sent_at_block as i64,
+            tx.max_fee_per_gas as i64,
+            tx.priority_fee_per_gas as i64
+        )
+        .instrument("set_withdrawal_finalization_tx")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("l2_message_index", &l2_message_index)
+        .with_arg("tx_hash", &tx.hash)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the number of withdrawals with a finalization transaction sent in the last hour.
+    pub async fn count_finalizations_sent_in_last_hour(&mut self) -> DalResult<usize> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                withdrawal_finalizations
+            WHERE
+                l1_tx_hash IS NOT NULL
+                AND created_at > NOW() - INTERVAL '1 hour'
+            "#
+        )
+        .instrument("count_finalizations_sent_in_last_hour")
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row.count as usize)
+    }
+
+    pub async fn set_withdrawal_finalization_status(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        l2_message_index: u32,
+        status: WithdrawalFinalizationStatus,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE withdrawal_finalizations
+            SET
+                status = $3,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $1
+                AND l2_message_index = $2
+            "#,
+            i64::from(l1_batch_number.0),
+            l2_message_index as i32,
+            status.to_string()
+        )
+        .instrument("set_withdrawal_finalization_status")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("l2_message_index", &l2_message_index)
+        .with_arg("status", &status)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::ProtocolVersion;
+
+    use super::*;
+    use crate::{tests::create_l1_batch_header, ConnectionPool, CoreDal};
+
+    fn withdrawal(
+        l2_message_index: u32,
+        status: WithdrawalFinalizationStatus,
+    ) -> WithdrawalFinalization {
+        WithdrawalFinalization {
+            l1_batch_number: L1BatchNumber(1),
+            l2_message_index,
+            l2_tx_number_in_batch: 3,
+            l1_token: Address::repeat_byte(1),
+            l1_receiver: Address::repeat_byte(2),
+            amount: U256::from(10).pow(30.into()),
+            status,
+            l1_tx: (status == WithdrawalFinalizationStatus::Sent).then(|| {
+                WithdrawalFinalizationTx {
+                    hash: H256::repeat_byte(l2_message_index as u8),
+                    nonce: l2_message_index.into(),
+                    sent_at_block: 100,
+                    max_fee_per_gas: 20,
+                    priority_fee_per_gas: 2,
+                }
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn persisting_withdrawal_finalizations() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch_header(1))
+            .await
+            .unwrap();
+
+        let mut dal = conn.withdrawal_finalizer_dal();
+        assert_eq!(dal.get_last_processed_l1_batch().await.unwrap(), None);
+        assert_eq!(
+            dal.get_pending_withdrawal_finalization().await.unwrap(),
+            None
+        );
+
+        let over_limit = withdrawal(2, WithdrawalFinalizationStatus::OverLimit);
+        dal.insert_withdrawal_finalization(&over_limit)
+            .await
+            .unwrap();
+        let sent = withdrawal(5, WithdrawalFinalizationStatus::Sent);
+        dal.insert_withdrawal_finalization(&sent).await.unwrap();
+        assert_eq!(
+            dal.count_finalizations_sent_in_last_hour().await.unwrap(),
+            1
+        );
+        assert_eq!(
+            dal.get_pending_withdrawal_finalization().await.unwrap(),
+            Some(sent.clone())
+        );
+        assert_eq!(
+            dal.get_withdrawal_finalizations(L1BatchNumber(1))
+                .await
+                .unwrap(),
+            [over_limit.clone(), sent.clone()]
+        );
+
+        let resent_tx = WithdrawalFinalizationTx {
+            hash: H256::repeat_byte(0xff),
+            sent_at_block: 110,
+            max_fee_per_gas: 30,
+            priority_fee_per_gas: 3,
+            ..sent.l1_tx.unwrap()
+        };
+        dal.set_withdrawal_finalization_tx(L1BatchNumber(1), 5, &resent_tx)
+            .await
+            .unwrap();
+        let pending = dal.get_pending_withdrawal_finalization().await.unwrap();
+        assert_eq!(pending.unwrap().l1_tx, Some(resent_tx));
+
+        dal.set_withdrawal_finalization_status(
+            L1BatchNumber(1),
+            5,
+            WithdrawalFinalizationStatus::Finalized,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            dal.get_pending_withdrawal_finalization().await.unwrap(),
+            None
+        );
+        let finalizations = dal
+            .get_withdrawal_finalizations(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(
+            finalizations[1].status,
+            WithdrawalFinalizationStatus::Finalized
+        );
+        assert_eq!(finalizations[1].l1_tx, Some(resent_tx));
+
+        dal.mark_l1_batch_as_processed(L1BatchNumber(1), 2)
+            .await
+            .unwrap();
+        assert_eq!(
+            dal.get_last_processed_l1_batch().await.unwrap(),
+            Some(L1BatchNumber(1))
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    backtrace::Backtrace,
     collections::HashMap,
     fmt, io,
     marker::PhantomData,
@@ -47,9 +48,12 @@ impl fmt::Display for ConnectionTags {
     }
 }
 
-struct TracedConnectionInfo {
-    tags: Option<ConnectionTags>,
-    created_at: Instant,
+pub(crate) struct TracedConnectionInfo {
+    pub tags: Option<ConnectionTags>,
+    pub created_at: Instant,
+    /// Backtrace of the code acquiring the connection. Only captured if the pool is monitored for leaks.
+    pub backtrace: Option<Backtrace>,
+    pub reported_as_leaked: bool,
 }
 
 impl fmt::Debug for TracedConnectionInfo {
@@ -66,6 +70,7 @@ impl fmt::Debug for TracedConnectionInfo {
 pub struct TracedConnections {
     connections: Mutex<HashMap<usize, TracedConnectionInfo>>,
     next_id: AtomicUsize,
+    capture_backtraces: bool,
 }
 
 impl fmt::Debug for TracedConnections {
@@ -79,17 +84,43 @@ impl fmt::Debug for TracedConnections {
 }
 
 impl TracedConnections {
+    /// Creates traced connections that capture backtraces on acquisition (useful for leak detection).
+    pub(crate) fn with_backtraces() -> Self {
+        Self {
+            capture_backtraces: true,
+            ..Self::default()
+        }
+    }
+
     fn acquire(&self, tags: Option<ConnectionTags>, created_at: Instant) -> usize {
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        // Capture the backtrace before locking connections to keep the critical section short.
+        let backtrace = self.capture_backtraces.then(Backtrace::force_capture);
         let mut guard = self
             .connections
             .lock()
             .expect("`TracedConnections` is poisoned");
-        let info = TracedConnectionInfo { tags, created_at };
+        let info = TracedConnectionInfo {
+            tags,
+            created_at,
+            backtrace,
+            reported_as_leaked: false,
+        };
         guard.insert(id, info);
         id
     }
 
+    /// Visits information about all active connections.
+    pub(crate) fn for_each_active(&self, mut visit: impl FnMut(&mut TracedConnectionInfo)) {
+        let mut guard = self
+            .connections
+            .lock()
+            .expect("`TracedConnections` is poisoned");
+        for info in guard.values_mut() {
+            visit(info);
+        }
+    }
+
     fn mark_as_dropped(&self, connection_id: usize) {
         let mut guard = self
             .connections
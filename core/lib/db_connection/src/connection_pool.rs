@@ -22,6 +22,7 @@ use crate::{
     connection::{Connection, ConnectionTags, DbMarker, TracedConnections},
     error::{DalConnectionError, DalResult},
     metrics::CONNECTION_METRICS,
+    monitor,
 };
 
 /// Builder for [`ConnectionPool`]s.
@@ -96,11 +97,19 @@ impl<DB: DbMarker> ConnectionPoolBuilder<DB> {
             .await
             .context("Failed connecting to database")?;
         tracing::info!("Created DB pool with parameters {self:?}");
+
+        let leak_detection_threshold =
+            ConnectionPool::<DB>::global_config().leak_detection_threshold();
+        let traced_connections = leak_detection_threshold.map(|_| {
+            let connections = Arc::new(TracedConnections::with_backtraces());
+            monitor::register_pool(&connections);
+            connections
+        });
         Ok(ConnectionPool {
             database_url: self.database_url.clone(),
             inner: pool,
             max_size: self.max_size,
-            traced_connections: None,
+            traced_connections,
             _db: PhantomData,
         })
     }
@@ -217,6 +226,8 @@ pub struct GlobalConnectionPoolConfig {
     // We consider millisecond precision to be enough for config purposes.
     long_connection_threshold_ms: AtomicU64,
     slow_query_threshold_ms: AtomicU64,
+    /// 0 means that leak detection is disabled.
+    leak_detection_threshold_ms: AtomicU64,
 }
 
 impl GlobalConnectionPoolConfig {
@@ -224,6 +235,7 @@ impl GlobalConnectionPoolConfig {
         Self {
             long_connection_threshold_ms: AtomicU64::new(5_000), // 5 seconds
             slow_query_threshold_ms: AtomicU64::new(100),        // 0.1 seconds
+            leak_detection_threshold_ms: AtomicU64::new(0),
         }
     }
 
@@ -235,6 +247,12 @@ impl GlobalConnectionPoolConfig {
         Duration::from_millis(self.slow_query_threshold_ms.load(Ordering::Relaxed))
     }
 
+    /// Returns the leak detection threshold, or `None` if leak detection is disabled.
+    pub fn leak_detection_threshold(&self) -> Option<Duration> {
+        let millis = self.leak_detection_threshold_ms.load(Ordering::Relaxed);
+        (millis > 0).then(|| Duration::from_millis(millis))
+    }
+
     /// Sets the threshold for the DB connection lifetime to denote a connection as long-living and log its details.
     pub fn set_long_connection_threshold(&self, threshold: Duration) -> anyhow::Result<&Self> {
         let millis = u64::try_from(threshold.as_millis())
@@ -254,6 +272,19 @@ impl GlobalConnectionPoolConfig {
         tracing::info!("Set slow query threshold to {threshold:?}");
         Ok(self)
     }
+
+    /// Enables connection leak detection for pools created after this call. Such pools trace their active connections
+    /// together with backtraces of the code acquiring them, and connections held longer than `threshold`
+    /// are reported by [`ConnectionPoolsMonitor`](crate::monitor::ConnectionPoolsMonitor).
+    pub fn set_leak_detection_threshold(&self, threshold: Duration) -> anyhow::Result<&Self> {
+        let millis = u64::try_from(threshold.as_millis())
+            .context("leak_detection_threshold is unreasonably large")?;
+        anyhow::ensure!(millis > 0, "leak_detection_threshold must be positive");
+        self.leak_detection_threshold_ms
+            .store(millis, Ordering::Relaxed);
+        tracing::info!("Set connection leak detection threshold to {threshold:?}");
+        Ok(self)
+    }
 }
 
 /// Pool of reusable database connections.
@@ -5,6 +5,7 @@ pub mod connection_pool;
 pub mod error;
 pub mod instrument;
 pub mod metrics;
+pub mod monitor;
 #[macro_use]
 pub mod macro_utils;
 pub mod utils;
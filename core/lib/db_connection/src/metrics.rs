@@ -90,6 +90,9 @@ pub(crate) struct ConnectionMetrics {
     /// Lifetime of a DB connection, tagged with the requester label.
     #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds, labels = ["requester"])]
     pub lifetime: LabeledFamily<&'static str, Histogram<Duration>>,
+    /// Number of DB connections held longer than the leak detection threshold, tagged with the requester label.
+    #[metrics(labels = ["requester"])]
+    pub leaked: LabeledFamily<&'static str, Counter>,
 }
 
 #[vise::register]
@@ -0,0 +1,203 @@
+//! Monitoring of active DB connections across connection pools.
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex, Weak},
+    time::Duration,
+};
+
+use serde::Serialize;
+use tokio::sync::watch;
+
+use crate::{
+    connection::{ConnectionTags, TracedConnections},
+    connection_pool::ConnectionPool,
+    metrics::CONNECTION_METRICS,
+    utils::InternalMarker,
+};
+
+/// Requester label used for connections acquired without tags.
+const UNTAGGED_REQUESTER: &str = "untagged";
+
+/// Traced connections for all pools built with leak detection enabled.
+static MONITORED_POOLS: Mutex<Vec<Weak<TracedConnections>>> = Mutex::new(Vec::new());
+
+pub(crate) fn register_pool(connections: &Arc<TracedConnections>) {
+    let mut pools = MONITORED_POOLS
+        .lock()
+        .expect("monitored pools are poisoned");
+    pools.retain(|pool| pool.strong_count() > 0);
+    pools.push(Arc::downgrade(connections));
+}
+
+fn monitored_pools() -> Vec<Arc<TracedConnections>> {
+    let pools = MONITORED_POOLS
+        .lock()
+        .expect("monitored pools are poisoned");
+    pools.iter().filter_map(Weak::upgrade).collect()
+}
+
+/// Summary of active connections for a single requester (i.e., the `requester` tag supplied
+/// to [`ConnectionPool::connection_tagged()`]).
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RequesterConnectionsSummary {
+    /// Number of active connections.
+    pub active: usize,
+    /// Longest time an active connection is held, in milliseconds.
+    pub longest_held_ms: u64,
+    /// Number of active connections held longer than the leak detection threshold.
+    pub leaked: usize,
+}
+
+/// Summary of active connections across all monitored connection pools.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ConnectionPoolsSummary {
+    /// Number of monitored pools.
+    pub pools: usize,
+    /// Total number of active connections.
+    pub active_connections: usize,
+    /// Total number of active connections held longer than the leak detection threshold.
+    pub leaked_connections: usize,
+    /// Active connections grouped by requester.
+    pub requesters: BTreeMap<&'static str, RequesterConnectionsSummary>,
+}
+
+/// Monitors active connections in all pools built while connection leak detection is enabled
+/// (see [`GlobalConnectionPoolConfig::set_leak_detection_threshold()`]).
+///
+/// [`GlobalConnectionPoolConfig::set_leak_detection_threshold()`]: crate::connection_pool::GlobalConnectionPoolConfig::set_leak_detection_threshold()
+#[derive(Debug, Clone, Copy)]
+pub struct ConnectionPoolsMonitor {
+    leak_threshold: Duration,
+}
+
+impl ConnectionPoolsMonitor {
+    /// Minimum interval between leak detection runs.
+    const MIN_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// Creates a monitor if leak detection is enabled in the global connection pool config.
+    pub fn new() -> Option<Self> {
+        let leak_threshold =
+            ConnectionPool::<InternalMarker>::global_config().leak_detection_threshold()?;
+        Some(Self { leak_threshold })
+    }
+
+    /// Summarizes active connections across all monitored pools.
+    pub fn summary(&self) -> ConnectionPoolsSummary {
+        let pools = monitored_pools();
+        let mut summary = ConnectionPoolsSummary {
+            pools: pools.len(),
+            ..ConnectionPoolsSummary::default()
+        };
+        for pool in pools {
+            pool.for_each_active(|info| {
+                let requester = info.tags.map_or(UNTAGGED_REQUESTER, |tags| tags.requester);
+                let lifetime = info.created_at.elapsed();
+                let is_leaked = lifetime > self.leak_threshold;
+
+                let requester_summary = summary.requesters.entry(requester).or_default();
+                requester_summary.active += 1;
+                requester_summary.longest_held_ms = requester_summary
+                    .longest_held_ms
+                    .max(lifetime.as_millis() as u64);
+                summary.active_connections += 1;
+                if is_leaked {
+                    requester_summary.leaked += 1;
+                    summary.leaked_connections += 1;
+                }
+            });
+        }
+        summary
+    }
+
+    /// Logs connections that are held longer than the leak detection threshold, together with backtraces of the code
+    /// acquiring them. Each connection is reported at most once. Returns the number of newly reported connections.
+    pub fn detect_leaks(&self) -> usize {
+        let mut new_leaks = 0;
+        for pool in monitored_pools() {
+            pool.for_each_active(|info| {
+                let lifetime = info.created_at.elapsed();
+                if info.reported_as_leaked || lifetime <= self.leak_threshold {
+                    return;
+                }
+                info.reported_as_leaked = true;
+                new_leaks += 1;
+
+                let requester = info.tags.map_or(UNTAGGED_REQUESTER, |tags| tags.requester);
+                CONNECTION_METRICS.leaked[&requester].inc();
+                let tags_display = ConnectionTags::display(info.tags.as_ref());
+                if let Some(backtrace) = &info.backtrace {
+                    tracing::warn!(
+                        "Possible DB connection leak: connection {tags_display} is held for {lifetime:?}; \
+                         acquired at:\n{backtrace}"
+                    );
+                } else {
+                    tracing::warn!(
+                        "Possible DB connection leak: connection {tags_display} is held for {lifetime:?}"
+                    );
+                }
+            });
+        }
+        new_leaks
+    }
+
+    /// Periodically runs leak detection until a stop signal is received.
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let poll_interval = (self.leak_threshold / 2).max(Self::MIN_POLL_INTERVAL);
+        tracing::info!(
+            "Starting DB connection leak detection with threshold {:?} and poll interval {poll_interval:?}",
+            self.leak_threshold
+        );
+        while !*stop_receiver.borrow() {
+            self.detect_leaks();
+            if tokio::time::timeout(poll_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, DB connection leak detection is shutting down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn detecting_connection_leaks() {
+        let mut pool = ConnectionPool::<InternalMarker>::constrained_test_pool(2).await;
+        let traced = Arc::new(TracedConnections::with_backtraces());
+        register_pool(&traced);
+        pool.traced_connections = Some(traced.clone());
+
+        let monitor = ConnectionPoolsMonitor {
+            leak_threshold: Duration::from_millis(50),
+        };
+        let connection = pool.connection_tagged("test").await.unwrap();
+        let short_lived_connection = pool.connection_tagged("test").await.unwrap();
+        drop(short_lived_connection);
+
+        let summary = monitor.summary();
+        let requester_summary = &summary.requesters["test"];
+        assert_eq!(requester_summary.active, 1);
+        assert_eq!(requester_summary.leaked, 0);
+        assert_eq!(monitor.detect_leaks(), 0);
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let summary = monitor.summary();
+        let requester_summary = &summary.requesters["test"];
+        assert_eq!(requester_summary.active, 1);
+        assert_eq!(requester_summary.leaked, 1);
+        assert!(requester_summary.longest_held_ms >= 100);
+        assert_eq!(monitor.detect_leaks(), 1);
+        // The connection must be reported only once.
+        assert_eq!(monitor.detect_leaks(), 0);
+
+        drop(connection);
+        let summary = monitor.summary();
+        assert!(!summary.requesters.contains_key("test"));
+    }
+}
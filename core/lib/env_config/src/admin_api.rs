@@ -0,0 +1,43 @@
+use anyhow::Context as _;
+use zksync_config::configs::{AdminApiConfig, AdminApiSecrets};
+
+use crate::{envy_load, FromEnv};
+
+impl FromEnv for AdminApiConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("admin_api", "ADMIN_API_")
+    }
+}
+
+impl FromEnv for AdminApiSecrets {
+    fn from_env() -> anyhow::Result<Self> {
+        Ok(Self {
+            auth_token: std::env::var("ADMIN_API_AUTH_TOKEN")
+                .context("ADMIN_API_AUTH_TOKEN")?
+                .parse()
+                .context("ADMIN_API_AUTH_TOKEN")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::EnvMutex;
+
+    static MUTEX: EnvMutex = EnvMutex::new();
+
+    #[test]
+    fn from_env() {
+        let config = r#"
+            ADMIN_API_HTTP_PORT="3322"
+            ADMIN_API_AUTH_TOKEN="admin-secret"
+        "#;
+        let mut lock = MUTEX.lock();
+        lock.set_env(config);
+        let actual = AdminApiConfig::from_env().unwrap();
+        assert_eq!(actual, AdminApiConfig { http_port: 3322 });
+        let secrets = AdminApiSecrets::from_env().unwrap();
+        assert_eq!(secrets.auth_token, "admin-secret".parse().unwrap());
+    }
+}
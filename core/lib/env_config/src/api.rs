@@ -73,10 +73,16 @@ mod tests {
                 max_tx_size: 1000000,
                 vm_execution_cache_misses_limit: None,
                 vm_concurrency_limit: Some(512),
+                vm_concurrency_limit_per_client: Some(64),
+                vm_concurrency_limit_anonymous: Some(128),
+                vm_interactive_permit_weight: Some(8),
+                vm_heavy_permit_weight: Some(2),
                 factory_deps_cache_size_mb: Some(128),
                 initial_writes_cache_size_mb: Some(32),
                 latest_values_cache_size_mb: Some(256),
                 latest_values_max_block_lag: Some(NonZeroU32::new(50).unwrap()),
+                historical_views_cache_size_mb: Some(64),
+                historical_views_max_block_lag: Some(NonZeroU32::new(500).unwrap()),
                 fee_history_limit: Some(100),
                 max_batch_request_size: Some(200),
                 max_response_body_size_mb: Some(10),
@@ -91,12 +97,18 @@ mod tests {
                 tree_api_url: None,
                 mempool_cache_update_interval: Some(50),
                 mempool_cache_size: Some(10000),
+                call_result_cache_size: Some(5000),
+                call_result_cache_excluded_methods: vec!["eth_estimateGas".to_owned()],
                 whitelisted_tokens_for_aa: vec![
                     addr("0x0000000000000000000000000000000000000001"),
                     addr("0x0000000000000000000000000000000000000002"),
                 ],
                 api_namespaces: Some(vec!["debug".to_string()]),
                 extended_api_tracing: true,
+                rate_limits: None,
+                http_policy: None,
+                ws_policy: None,
+                subscription_limits: None,
             },
             prometheus: PrometheusConfig {
                 listener_port: 3312,
@@ -134,15 +146,23 @@ mod tests {
             API_WEB3_JSON_RPC_ESTIMATE_GAS_ACCEPTABLE_OVERESTIMATION=1000
             API_WEB3_JSON_RPC_MAX_TX_SIZE=1000000
             API_WEB3_JSON_RPC_VM_CONCURRENCY_LIMIT=512
+            API_WEB3_JSON_RPC_VM_CONCURRENCY_LIMIT_PER_CLIENT=64
+            API_WEB3_JSON_RPC_VM_CONCURRENCY_LIMIT_ANONYMOUS=128
+            API_WEB3_JSON_RPC_VM_INTERACTIVE_PERMIT_WEIGHT=8
+            API_WEB3_JSON_RPC_VM_HEAVY_PERMIT_WEIGHT=2
             API_WEB3_JSON_RPC_FACTORY_DEPS_CACHE_SIZE_MB=128
             API_WEB3_JSON_RPC_INITIAL_WRITES_CACHE_SIZE_MB=32
             API_WEB3_JSON_RPC_LATEST_VALUES_CACHE_SIZE_MB=256
             API_WEB3_JSON_RPC_LATEST_VALUES_MAX_BLOCK_LAG=50
+            API_WEB3_JSON_RPC_HISTORICAL_VIEWS_CACHE_SIZE_MB=64
+            API_WEB3_JSON_RPC_HISTORICAL_VIEWS_MAX_BLOCK_LAG=500
             API_WEB3_JSON_RPC_FEE_HISTORY_LIMIT=100
             API_WEB3_JSON_RPC_MAX_BATCH_REQUEST_SIZE=200
             API_WEB3_JSON_RPC_WEBSOCKET_REQUESTS_PER_MINUTE_LIMIT=10
             API_WEB3_JSON_RPC_MEMPOOL_CACHE_SIZE=10000
             API_WEB3_JSON_RPC_MEMPOOL_CACHE_UPDATE_INTERVAL=50
+            API_WEB3_JSON_RPC_CALL_RESULT_CACHE_SIZE=5000
+            API_WEB3_JSON_RPC_CALL_RESULT_CACHE_EXCLUDED_METHODS=eth_estimateGas
             API_CONTRACT_VERIFICATION_PORT="3070"
             API_CONTRACT_VERIFICATION_URL="http://127.0.0.1:3070"
             API_WEB3_JSON_RPC_MAX_RESPONSE_BODY_SIZE_MB=10
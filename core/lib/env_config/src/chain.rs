@@ -94,6 +94,8 @@ mod tests {
             max_gas_per_batch: 200_000_000,
             max_pubdata_per_batch: 100_000,
             fee_model_version: FeeModelVersion::V2,
+            pubdata_price_curve_l1_gas_prices: vec![1_000_000_000, 100_000_000_000],
+            pubdata_price_curve_pubdata_prices: vec![1_000, 100_000],
             validation_computational_gas_limit: 10_000_000,
             save_call_traces: false,
             bootloader_hash: Some(hash(
@@ -105,7 +107,12 @@ mod tests {
             evm_emulator_hash: None,
             l1_batch_commit_data_generator_mode,
             max_circuits_per_batch: 24100,
+            seal_on_predicted_circuits: true,
             protective_reads_persistence_enabled: true,
+            congestion_max_unproven_l1_batches: Some(100),
+            congestion_max_unexecuted_l1_batches: Some(500),
+            congestion_min_l1_batch_interval_ms: Some(30_000),
+            congestion_gas_price_multiplier: Some(2.0),
         }
     }
 
@@ -133,7 +140,14 @@ mod tests {
             CHAIN_STATE_KEEPER_MAX_GAS_PER_BATCH="200000000"
             CHAIN_STATE_KEEPER_MAX_PUBDATA_PER_BATCH="100000"
             CHAIN_STATE_KEEPER_MAX_CIRCUITS_PER_BATCH="24100"
+            CHAIN_STATE_KEEPER_SEAL_ON_PREDICTED_CIRCUITS="true"
             CHAIN_STATE_KEEPER_FEE_MODEL_VERSION="V2"
+            CHAIN_STATE_KEEPER_PUBDATA_PRICE_CURVE_L1_GAS_PRICES="1000000000,100000000000"
+            CHAIN_STATE_KEEPER_PUBDATA_PRICE_CURVE_PUBDATA_PRICES="1000,100000"
+            CHAIN_STATE_KEEPER_CONGESTION_MAX_UNPROVEN_L1_BATCHES="100"
+            CHAIN_STATE_KEEPER_CONGESTION_MAX_UNEXECUTED_L1_BATCHES="500"
+            CHAIN_STATE_KEEPER_CONGESTION_MIN_L1_BATCH_INTERVAL_MS="30000"
+            CHAIN_STATE_KEEPER_CONGESTION_GAS_PRICE_MULTIPLIER="2.0"
             CHAIN_STATE_KEEPER_VALIDATION_COMPUTATIONAL_GAS_LIMIT="10000000"
             CHAIN_STATE_KEEPER_SAVE_CALL_TRACES="false"
             CHAIN_STATE_KEEPER_BOOTLOADER_HASH=0x010007ede999d096c84553fb514d3d6ca76fbf39789dda76bfeda9f3ae06236e
@@ -172,6 +186,7 @@ mod tests {
             stuck_tx_timeout: 10,
             remove_stuck_txs: true,
             delay_interval: 100,
+            tx_policy_path: Some("/etc/zksync/tx_policy.json".to_owned()),
         }
     }
 
@@ -185,6 +200,7 @@ mod tests {
             CHAIN_MEMPOOL_REMOVE_STUCK_TXS="true"
             CHAIN_MEMPOOL_DELAY_INTERVAL="100"
             CHAIN_MEMPOOL_CAPACITY="1000000"
+            CHAIN_MEMPOOL_TX_POLICY_PATH="/etc/zksync/tx_policy.json"
         "#;
         lock.set_env(config);
 
@@ -113,6 +113,7 @@ mod tests {
             },
             max_retries,
             local_mirror_path: None,
+            tiers: vec![],
         })
     }
 
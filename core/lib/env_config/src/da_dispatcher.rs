@@ -27,6 +27,8 @@ mod tests {
             max_rows_to_dispatch: Some(rows_limit),
             max_retries: Some(max_retries),
             use_dummy_inclusion_data: Some(true),
+            fallback_object_store: None,
+            inclusion_sla_ms: Some(600_000),
         }
     }
 
@@ -38,6 +40,7 @@ mod tests {
             DA_DISPATCHER_MAX_ROWS_TO_DISPATCH=60
             DA_DISPATCHER_MAX_RETRIES=7
             DA_DISPATCHER_USE_DUMMY_INCLUSION_DATA="true"
+            DA_DISPATCHER_INCLUSION_SLA_MS=600000
         "#;
         lock.set_env(config);
         let actual = DADispatcherConfig::from_env().unwrap();
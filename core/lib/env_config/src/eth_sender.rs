@@ -45,7 +45,7 @@ impl FromEnv for GasAdjusterConfig {
 #[cfg(test)]
 mod tests {
     use zksync_basic_types::pubdata_da::PubdataSendingMode;
-    use zksync_config::configs::eth_sender::ProofSendingMode;
+    use zksync_config::configs::eth_sender::{FeeSmoothingStrategy, ProofSendingMode};
 
     use super::*;
     use crate::test_utils::{hash, EnvMutex};
@@ -91,10 +91,17 @@ mod tests {
                     internal_pubdata_pricing_multiplier: 1.0,
                     max_blob_base_fee: None,
                     settlement_mode: Default::default(),
+                    fee_smoothing_strategy: FeeSmoothingStrategy::Ema,
+                    fee_smoothing_ema_alpha: 0.3,
+                    fee_smoothing_percentile: GasAdjusterConfig::default_fee_smoothing_percentile(),
+                    max_fee_change_per_update: Some(0.1),
+                    blob_base_fee_prediction_blocks: None,
                 }),
                 watcher: Some(EthWatchConfig {
                     confirmations_for_eth_event: Some(0),
                     eth_node_poll_interval: 300,
+                    custom_events: vec![],
+                    bridge_events_start_block: None,
                 }),
             },
             L1Secrets {
@@ -126,6 +133,9 @@ mod tests {
             ETH_SENDER_GAS_ADJUSTER_MAX_L1_GAS_PRICE="100000000"
             ETH_SENDER_GAS_ADJUSTER_MAX_BLOB_BASE_FEE_SAMPLES="10"
             ETH_SENDER_GAS_ADJUSTER_INTERNAL_PUBDATA_PRICING_MULTIPLIER="1.0"
+            ETH_SENDER_GAS_ADJUSTER_FEE_SMOOTHING_STRATEGY="Ema"
+            ETH_SENDER_GAS_ADJUSTER_FEE_SMOOTHING_EMA_ALPHA="0.3"
+            ETH_SENDER_GAS_ADJUSTER_MAX_FEE_CHANGE_PER_UPDATE="0.1"
             ETH_SENDER_WAIT_FOR_PROOFS="false"
             ETH_SENDER_SENDER_MAX_AGGREGATED_BLOCKS_TO_COMMIT="3"
             ETH_SENDER_SENDER_MAX_AGGREGATED_BLOCKS_TO_EXECUTE="4"
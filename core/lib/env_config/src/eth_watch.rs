@@ -19,6 +19,8 @@ mod tests {
         EthWatchConfig {
             confirmations_for_eth_event: Some(0),
             eth_node_poll_interval: 300,
+            custom_events: vec![],
+            bridge_events_start_block: None,
         }
     }
 
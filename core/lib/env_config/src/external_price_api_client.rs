@@ -1,5 +1,8 @@
 use zksync_config::configs::{
-    external_price_api_client::ForcedPriceClientConfig, ExternalPriceApiClientConfig,
+    external_price_api_client::{
+        AggregatedPriceClientConfig, ChainlinkPriceClientConfig, ForcedPriceClientConfig,
+    },
+    ExternalPriceApiClientConfig,
 };
 
 use crate::{envy_load, FromEnv};
@@ -9,10 +12,30 @@ impl FromEnv for ExternalPriceApiClientConfig {
         let mut config: ExternalPriceApiClientConfig =
             envy_load("external_price_api_client", "EXTERNAL_PRICE_API_CLIENT_")?;
         config.forced = ForcedPriceClientConfig::from_env().ok();
+        config.chainlink = ChainlinkPriceClientConfig::from_env().ok();
+        config.aggregated = AggregatedPriceClientConfig::from_env().ok();
         Ok(config)
     }
 }
 
+impl FromEnv for ChainlinkPriceClientConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load(
+            "external_price_api_client_chainlink",
+            "EXTERNAL_PRICE_API_CLIENT_CHAINLINK_",
+        )
+    }
+}
+
+impl FromEnv for AggregatedPriceClientConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load(
+            "external_price_api_client_aggregated",
+            "EXTERNAL_PRICE_API_CLIENT_AGGREGATED_",
+        )
+    }
+}
+
 impl FromEnv for ForcedPriceClientConfig {
     fn from_env() -> anyhow::Result<Self> {
         envy_load(
@@ -25,7 +48,8 @@ impl FromEnv for ForcedPriceClientConfig {
 #[cfg(test)]
 mod tests {
     use zksync_config::configs::external_price_api_client::{
-        ExternalPriceApiClientConfig, ForcedPriceClientConfig, DEFAULT_TIMEOUT_MS,
+        AggregatedPriceClientConfig, ChainlinkPriceClientConfig, ExternalPriceApiClientConfig,
+        ForcedPriceClientConfig, DEFAULT_TIMEOUT_MS,
     };
 
     use super::*;
@@ -45,6 +69,19 @@ mod tests {
                 fluctuation: Some(10),
                 next_value_fluctuation: 1,
             }),
+            chainlink: Some(ChainlinkPriceClientConfig {
+                rpc_url: Some("http://127.0.0.1:8545".to_string()),
+                feed_address: Some(
+                    "0x0000000000000000000000000000000000000001"
+                        .parse()
+                        .unwrap(),
+                ),
+            }),
+            aggregated: Some(AggregatedPriceClientConfig {
+                sources: vec!["coingecko".to_string(), "chainlink".to_string()],
+                max_staleness_ms: Some(600_000),
+                min_sources: Some(2),
+            }),
         }
     }
 
@@ -59,6 +96,11 @@ mod tests {
             EXTERNAL_PRICE_API_CLIENT_FORCED_DENOMINATOR=1
             EXTERNAL_PRICE_API_CLIENT_FORCED_FLUCTUATION=10
             EXTERNAL_PRICE_API_CLIENT_FORCED_NEXT_VALUE_FLUCTUATION=1
+            EXTERNAL_PRICE_API_CLIENT_CHAINLINK_RPC_URL=http://127.0.0.1:8545
+            EXTERNAL_PRICE_API_CLIENT_CHAINLINK_FEED_ADDRESS=0x0000000000000000000000000000000000000001
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATED_SOURCES=coingecko,chainlink
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATED_MAX_STALENESS_MS=600000
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATED_MIN_SOURCES=2
         "#;
         lock.set_env(config);
 
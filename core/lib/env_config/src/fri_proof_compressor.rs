@@ -28,6 +28,8 @@ mod tests {
                 "https://storage.googleapis.com/matterlabs-setup-keys-us/setup-keys/setup_2^26.key"
                     .to_string(),
             verify_wrapper_proof: false,
+            heartbeat_interval_in_secs: Some(10),
+            heartbeat_timeout_in_secs: Some(60),
         }
     }
 
@@ -44,6 +46,8 @@ mod tests {
             FRI_PROOF_COMPRESSOR_UNIVERSAL_SETUP_PATH="keys/setup/setup_2^26.key"
             FRI_PROOF_COMPRESSOR_UNIVERSAL_SETUP_DOWNLOAD_URL="https://storage.googleapis.com/matterlabs-setup-keys-us/setup-keys/setup_2^26.key"
             FRI_PROOF_COMPRESSOR_VERIFY_WRAPPER_PROOF=false
+            FRI_PROOF_COMPRESSOR_HEARTBEAT_INTERVAL_IN_SECS=10
+            FRI_PROOF_COMPRESSOR_HEARTBEAT_TIMEOUT_IN_SECS=60
         "#;
         lock.set_env(config);
 
@@ -18,6 +18,8 @@ mod tests {
     fn expected_config() -> HouseKeeperConfig {
         HouseKeeperConfig {
             l1_batch_metrics_reporting_interval_ms: 10_000,
+            table_partition_size: Some(1_000_000),
+            eth_txs_history_retention_secs: Some(604_800),
         }
     }
 
@@ -26,6 +28,8 @@ mod tests {
         let mut lock = MUTEX.lock();
         let config = r#"
             HOUSE_KEEPER_L1_BATCH_METRICS_REPORTING_INTERVAL_MS="10000"
+            HOUSE_KEEPER_TABLE_PARTITION_SIZE="1000000"
+            HOUSE_KEEPER_ETH_TXS_HISTORY_RETENTION_SECS="604800"
         "#;
         lock.set_env(config);
 
@@ -0,0 +1,65 @@
+use zksync_config::configs::LeaderElectionConfig;
+
+use crate::{envy_load, FromEnv};
+
+impl FromEnv for LeaderElectionConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("leader_election", "LEADER_ELECTION_")
+    }
+}
+
+/// Loads the leader election config from the environment. Leader election is disabled (i.e., `Ok(None)` is returned)
+/// iff `LEADER_ELECTION_LEASE_NAME` is not set; other errors, such as a malformed lease TTL, are propagated
+/// rather than silently disabling leader election.
+pub fn leader_election_config_from_env() -> anyhow::Result<Option<LeaderElectionConfig>> {
+    if std::env::var_os("LEADER_ELECTION_LEASE_NAME").is_none() {
+        return Ok(None);
+    }
+    LeaderElectionConfig::from_env().map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::EnvMutex;
+
+    static MUTEX: EnvMutex = EnvMutex::new();
+
+    #[test]
+    fn from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            LEADER_ELECTION_LEASE_NAME="sequencer"
+            LEADER_ELECTION_INSTANCE_NAME="main-node-0"
+            LEADER_ELECTION_LEASE_TTL_MS="5000"
+        "#;
+        lock.set_env(config);
+        let actual = LeaderElectionConfig::from_env().unwrap();
+        assert_eq!(
+            actual,
+            LeaderElectionConfig {
+                lease_name: "sequencer".to_owned(),
+                instance_name: Some("main-node-0".to_owned()),
+                lease_ttl_ms: 5_000,
+            }
+        );
+
+        lock.remove_env(&[
+            "LEADER_ELECTION_INSTANCE_NAME",
+            "LEADER_ELECTION_LEASE_TTL_MS",
+        ]);
+        let actual = LeaderElectionConfig::from_env().unwrap();
+        assert_eq!(actual.instance_name, None);
+        assert_eq!(actual.lease_ttl_ms, 10_000);
+
+        lock.set_env(r#"LEADER_ELECTION_LEASE_TTL_MS="5s""#);
+        leader_election_config_from_env().unwrap_err();
+        lock.remove_env(&["LEADER_ELECTION_LEASE_TTL_MS"]);
+        assert!(leader_election_config_from_env().unwrap().is_some());
+
+        // The lease name is required, so that leader election is disabled by default.
+        lock.remove_env(&["LEADER_ELECTION_LEASE_NAME"]);
+        LeaderElectionConfig::from_env().unwrap_err();
+        assert_eq!(leader_election_config_from_env().unwrap(), None);
+    }
+}
@@ -15,12 +15,14 @@ mod fri_prover_group;
 mod fri_witness_generator;
 mod fri_witness_vector_generator;
 mod house_keeper;
+mod leader_election;
 pub mod object_store;
 mod observability;
 mod proof_data_handler;
 mod snapshots_creator;
 mod utils;
 
+mod admin_api;
 mod base_token_adjuster;
 mod da_dispatcher;
 mod external_price_api_client;
@@ -31,6 +33,9 @@ mod prover_job_monitor;
 mod test_utils;
 mod vm_runner;
 mod wallets;
+mod withdrawal_finalizer;
+
+pub use crate::leader_election::leader_election_config_from_env;
 
 mod da_client;
 mod timestamp_asserter;
@@ -1,3 +1,5 @@
+use anyhow::Context as _;
+use zksync_basic_types::L2ChainId;
 use zksync_config::configs::{ObservabilityConfig, OpentelemetryConfig};
 
 use crate::FromEnv;
@@ -36,16 +38,38 @@ impl FromEnv for ObservabilityConfig {
         let opentelemetry_level = std::env::var("OPENTELEMETRY_LEVEL").ok();
         let otlp_endpoint = std::env::var("OTLP_ENDPOINT").ok();
         let logs_endpoint = std::env::var("OTLP_LOGS_ENDPOINT").ok(); // OK to be absent.
+        let sampling_ratio = std::env::var("OTLP_SAMPLING_RATIO")
+            .ok()
+            .map(|value| value.parse())
+            .transpose()
+            .context("OTLP_SAMPLING_RATIO")?;
+        let l2_chain_id = std::env::var("OTLP_L2_CHAIN_ID")
+            .ok()
+            .map(|value| {
+                value
+                    .parse::<L2ChainId>()
+                    .map_err(|err| anyhow::anyhow!(err))
+            })
+            .transpose()
+            .context("OTLP_L2_CHAIN_ID")?;
         let opentelemetry = match (opentelemetry_level, otlp_endpoint) {
             (Some(level), Some(endpoint)) => Some(OpentelemetryConfig {
                 level,
                 endpoint,
                 logs_endpoint,
+                sampling_ratio,
+                l2_chain_id,
             }),
             _ => None,
         };
 
         let log_directives = std::env::var("RUST_LOG").ok();
+        let log_correlation_ids = std::env::var("MISC_LOG_CORRELATION_IDS")
+            .ok()
+            .map(|value| value.parse())
+            .transpose()
+            .context("MISC_LOG_CORRELATION_IDS")?
+            .unwrap_or(false);
 
         Ok(ObservabilityConfig {
             sentry_url,
@@ -53,6 +77,7 @@ impl FromEnv for ObservabilityConfig {
             log_format,
             opentelemetry,
             log_directives,
+            log_correlation_ids,
         })
     }
 }
@@ -30,6 +30,8 @@ mod tests {
                 first_tee_processed_batch: L1BatchNumber(1337),
                 tee_proof_generation_timeout_in_secs: 600,
                 tee_batch_permanently_ignored_timeout_in_hours: 240,
+                tee_attestation_verification: true,
+                tee_max_tcb_age_in_days: 90,
             },
         }
     }
@@ -43,6 +45,8 @@ mod tests {
             PROOF_DATA_HANDLER_FIRST_TEE_PROCESSED_BATCH="1337"
             PROOF_DATA_HANDLER_TEE_PROOF_GENERATION_TIMEOUT_IN_SECS="600"
             PROOF_DATA_HANDLER_TEE_BATCH_PERMANENTLY_IGNORED_TIMEOUT_IN_HOURS="240"
+            PROOF_DATA_HANDLER_TEE_ATTESTATION_VERIFICATION="true"
+            PROOF_DATA_HANDLER_TEE_MAX_TCB_AGE_IN_DAYS="90"
         "#;
         let mut lock = MUTEX.lock();
         lock.set_env(config);
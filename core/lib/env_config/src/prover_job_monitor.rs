@@ -31,6 +31,7 @@ mod tests {
             prover_queue_reporter_run_interval_ms: 10000,
             witness_generator_queue_reporter_run_interval_ms: 10000,
             witness_job_queuer_run_interval_ms: 10000,
+            scaling_signals_target_backlog_ms: 600000,
             http_port: 3074,
         }
     }
@@ -49,6 +50,7 @@ mod tests {
         config.prover_queue_reporter_run_interval_ms += 1;
         config.witness_generator_queue_reporter_run_interval_ms += 1;
         config.witness_job_queuer_run_interval_ms += 1;
+        config.scaling_signals_target_backlog_ms += 1;
         config
     }
 
@@ -82,6 +84,7 @@ mod tests {
             PROVER_JOB_MONITOR_PROVER_QUEUE_REPORTER_RUN_INTERVAL_MS=10001
             PROVER_JOB_MONITOR_WITNESS_GENERATOR_QUEUE_REPORTER_RUN_INTERVAL_MS=10001
             PROVER_JOB_MONITOR_WITNESS_JOB_QUEUER_RUN_INTERVAL_MS=10001
+            PROVER_JOB_MONITOR_SCALING_SIGNALS_TARGET_BACKLOG_MS=600001
             PROVER_JOB_MONITOR_HTTP_PORT=3074
         "#;
         let mut lock = MUTEX.lock();
@@ -18,10 +18,15 @@ impl FromEnv for BasicWitnessInputProducerConfig {
 
 impl FromEnv for ExperimentalVmConfig {
     fn from_env() -> anyhow::Result<Self> {
-        Ok(Self {
+        let config = Self {
             playground: envy_load("experimental_vm.playground", "EXPERIMENTAL_VM_PLAYGROUND_")?,
             ..envy_load("experimental_vm", "EXPERIMENTAL_VM_")?
-        })
+        };
+        anyhow::ensure!(
+            (0.0..=1.0).contains(&config.state_keeper_shadow_sampling_ratio),
+            "state_keeper_shadow_sampling_ratio must be in [0, 1]"
+        );
+        Ok(config)
     }
 }
 
@@ -56,6 +61,7 @@ mod tests {
         let config = r#"
             EXPERIMENTAL_VM_STATE_KEEPER_FAST_VM_MODE=new
             EXPERIMENTAL_VM_API_FAST_VM_MODE=shadow
+            EXPERIMENTAL_VM_STATE_KEEPER_SHADOW_SAMPLING_RATIO=0.01
             EXPERIMENTAL_VM_PLAYGROUND_FAST_VM_MODE=shadow
             EXPERIMENTAL_VM_PLAYGROUND_DB_PATH=/db/vm_playground
             EXPERIMENTAL_VM_PLAYGROUND_FIRST_PROCESSED_BATCH=123
@@ -66,6 +72,7 @@ mod tests {
         let config = ExperimentalVmConfig::from_env().unwrap();
         assert_eq!(config.state_keeper_fast_vm_mode, FastVmMode::New);
         assert_eq!(config.api_fast_vm_mode, FastVmMode::Shadow);
+        assert_eq!(config.state_keeper_shadow_sampling_ratio, 0.01);
         assert_eq!(config.playground.fast_vm_mode, FastVmMode::Shadow);
         assert_eq!(config.playground.db_path.unwrap(), "/db/vm_playground");
         assert_eq!(config.playground.first_processed_batch, L1BatchNumber(123));
@@ -86,5 +93,13 @@ mod tests {
         lock.remove_env(&["EXPERIMENTAL_VM_PLAYGROUND_DB_PATH"]);
         let config = ExperimentalVmConfig::from_env().unwrap();
         assert!(config.playground.db_path.is_none());
+
+        lock.set_env("EXPERIMENTAL_VM_STATE_KEEPER_SHADOW_SAMPLING_RATIO=1.5");
+        let err = ExperimentalVmConfig::from_env().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("state_keeper_shadow_sampling_ratio"),
+            "{err:#}"
+        );
     }
 }
@@ -4,6 +4,7 @@ use anyhow::Context;
 use zksync_basic_types::{Address, H256};
 use zksync_config::configs::wallets::{
     AddressWallet, EthSender, StateKeeper, TokenMultiplierSetter, Wallet, Wallets,
+    WithdrawalFinalizer,
 };
 
 use crate::FromEnv;
@@ -61,10 +62,22 @@ impl FromEnv for Wallets {
                 None
             };
 
+        let withdrawal_finalizer_pk = pk_from_env(
+            "WITHDRAWAL_FINALIZER_PRIVATE_KEY",
+            "Malformed withdrawal finalizer pk",
+        )?;
+        let withdrawal_finalizer = if let Some(withdrawal_finalizer_pk) = withdrawal_finalizer_pk {
+            let wallet = Wallet::from_private_key_bytes(withdrawal_finalizer_pk, None)?;
+            Some(WithdrawalFinalizer { wallet })
+        } else {
+            None
+        };
+
         Ok(Self {
             eth_sender,
             state_keeper,
             token_multiplier_setter,
+            withdrawal_finalizer,
         })
     }
 }
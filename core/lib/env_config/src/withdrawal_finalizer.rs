@@ -0,0 +1,57 @@
+use zksync_config::configs::WithdrawalFinalizerConfig;
+
+use crate::{envy_load, FromEnv};
+
+impl FromEnv for WithdrawalFinalizerConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("withdrawal_finalizer", "WITHDRAWAL_FINALIZER_")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_basic_types::L1BatchNumber;
+
+    use super::*;
+    use crate::test_utils::EnvMutex;
+
+    static MUTEX: EnvMutex = EnvMutex::new();
+
+    #[test]
+    fn from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            WITHDRAWAL_FINALIZER_POLL_INTERVAL_MS="5000"
+            WITHDRAWAL_FINALIZER_MAX_TX_GAS="300000"
+            WITHDRAWAL_FINALIZER_DEFAULT_PRIORITY_FEE_PER_GAS="2000000000"
+            WITHDRAWAL_FINALIZER_RESEND_AFTER_L1_BLOCKS="5"
+            WITHDRAWAL_FINALIZER_MAX_FINALIZATIONS_PER_HOUR="20"
+            WITHDRAWAL_FINALIZER_START_L1_BATCH="1000"
+        "#;
+        lock.set_env(config);
+        let actual = WithdrawalFinalizerConfig::from_env().unwrap();
+        assert_eq!(
+            actual,
+            WithdrawalFinalizerConfig {
+                poll_interval_ms: 5_000,
+                max_tx_gas: 300_000,
+                default_priority_fee_per_gas: 2_000_000_000,
+                resend_after_l1_blocks: 5,
+                token_limits: vec![],
+                max_finalizations_per_hour: 20,
+                start_l1_batch: Some(L1BatchNumber(1_000)),
+            }
+        );
+
+        lock.remove_env(&[
+            "WITHDRAWAL_FINALIZER_POLL_INTERVAL_MS",
+            "WITHDRAWAL_FINALIZER_MAX_TX_GAS",
+            "WITHDRAWAL_FINALIZER_DEFAULT_PRIORITY_FEE_PER_GAS",
+            "WITHDRAWAL_FINALIZER_RESEND_AFTER_L1_BLOCKS",
+            "WITHDRAWAL_FINALIZER_MAX_FINALIZATIONS_PER_HOUR",
+            "WITHDRAWAL_FINALIZER_START_L1_BATCH",
+        ]);
+        let actual = WithdrawalFinalizerConfig::from_env().unwrap();
+        assert_eq!(actual, WithdrawalFinalizerConfig::default());
+    }
+}
@@ -0,0 +1,200 @@
+use std::{cmp::Ordering, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use zksync_config::configs::external_price_api_client::AggregatedPriceClientConfig;
+use zksync_types::{
+    base_token_ratio::{BaseTokenAPIRatio, BaseTokenRatioQuote},
+    Address,
+};
+
+use crate::PriceAPIClient;
+
+/// Price client aggregating quotes from multiple sources. Failed and stale quotes are ignored,
+/// and the median of the remaining ones is returned.
+#[derive(Debug)]
+pub struct AggregatedPriceClient {
+    sources: Vec<(String, Arc<dyn PriceAPIClient>)>,
+    max_staleness: Duration,
+    min_sources: usize,
+}
+
+impl AggregatedPriceClient {
+    pub fn new(
+        sources: Vec<(String, Arc<dyn PriceAPIClient>)>,
+        config: &AggregatedPriceClientConfig,
+    ) -> Self {
+        Self {
+            sources,
+            max_staleness: config.max_staleness(),
+            min_sources: config.min_sources() as usize,
+        }
+    }
+}
+
+#[async_trait]
+impl PriceAPIClient for AggregatedPriceClient {
+    fn source_name(&self) -> &str {
+        "aggregated"
+    }
+
+    async fn fetch_ratio(&self, token_address: Address) -> anyhow::Result<BaseTokenAPIRatio> {
+        let (ratio, _) = self.fetch_ratio_with_quotes(token_address).await?;
+        Ok(ratio)
+    }
+
+    async fn fetch_ratio_with_quotes(
+        &self,
+        token_address: Address,
+    ) -> anyhow::Result<(BaseTokenAPIRatio, Vec<BaseTokenRatioQuote>)> {
+        let mut quotes = Vec::with_capacity(self.sources.len());
+        let mut raw_quotes = Vec::with_capacity(self.sources.len());
+        for (name, source) in &self.sources {
+            let quote = match source.fetch_ratio(token_address).await {
+                Ok(quote) => quote,
+                Err(err) => {
+                    tracing::warn!("Failed to fetch price from source `{name}`: {err:#}");
+                    continue;
+                }
+            };
+
+            let age = Utc::now().signed_duration_since(quote.ratio_timestamp);
+            if age.to_std().map_or(false, |age| age > self.max_staleness) {
+                tracing::warn!(
+                    "Price from source `{name}` is stale: quoted at {}, max staleness is {:?}",
+                    quote.ratio_timestamp,
+                    self.max_staleness
+                );
+                raw_quotes.push(BaseTokenRatioQuote::new(name, &quote, false));
+                continue;
+            }
+            raw_quotes.push(BaseTokenRatioQuote::new(name, &quote, true));
+            quotes.push(quote);
+        }
+
+        anyhow::ensure!(
+            !quotes.is_empty() && quotes.len() >= self.min_sources,
+            "not enough fresh price quotes: got {}, required {}",
+            quotes.len(),
+            self.min_sources
+        );
+        quotes.sort_by(compare_ratios);
+        // For an even number of quotes, the lower median is used, so that the result is always an actual quote.
+        Ok((quotes[(quotes.len() - 1) / 2], raw_quotes))
+    }
+}
+
+fn compare_ratios(lhs: &BaseTokenAPIRatio, rhs: &BaseTokenAPIRatio) -> Ordering {
+    let lhs_value = u128::from(lhs.numerator.get()) * u128::from(rhs.denominator.get());
+    let rhs_value = u128::from(rhs.numerator.get()) * u128::from(lhs.denominator.get());
+    lhs_value.cmp(&rhs_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use super::*;
+    use crate::NoOpPriceAPIClient;
+
+    #[derive(Debug)]
+    struct MockPriceClient(anyhow::Result<BaseTokenAPIRatio>);
+
+    #[async_trait]
+    impl PriceAPIClient for MockPriceClient {
+        fn source_name(&self) -> &str {
+            "mock"
+        }
+
+        async fn fetch_ratio(&self, _token_address: Address) -> anyhow::Result<BaseTokenAPIRatio> {
+            match &self.0 {
+                Ok(ratio) => Ok(*ratio),
+                Err(err) => Err(anyhow::anyhow!("{err}")),
+            }
+        }
+    }
+
+    fn quote(numerator: u64, denominator: u64, age: Duration) -> Arc<dyn PriceAPIClient> {
+        Arc::new(MockPriceClient(Ok(BaseTokenAPIRatio {
+            numerator: NonZeroU64::new(numerator).unwrap(),
+            denominator: NonZeroU64::new(denominator).unwrap(),
+            ratio_timestamp: Utc::now() - chrono::Duration::from_std(age).unwrap(),
+        })))
+    }
+
+    fn create_client(
+        sources: Vec<Arc<dyn PriceAPIClient>>,
+        min_sources: u32,
+    ) -> AggregatedPriceClient {
+        let sources = sources
+            .into_iter()
+            .enumerate()
+            .map(|(i, source)| (format!("source{i}"), source))
+            .collect();
+        let config = AggregatedPriceClientConfig {
+            sources: vec![],
+            max_staleness_ms: Some(60_000),
+            min_sources: Some(min_sources),
+        };
+        AggregatedPriceClient::new(sources, &config)
+    }
+
+    #[tokio::test]
+    async fn aggregating_median_ratio() {
+        let client = create_client(
+            vec![
+                quote(300, 1, Duration::ZERO),
+                quote(1000, 3, Duration::ZERO),
+                quote(320, 1, Duration::ZERO),
+            ],
+            1,
+        );
+        let ratio = client.fetch_ratio(Address::zero()).await.unwrap();
+        assert_eq!((ratio.numerator.get(), ratio.denominator.get()), (320, 1));
+    }
+
+    #[tokio::test]
+    async fn ignoring_failed_and_stale_quotes() {
+        let client = create_client(
+            vec![
+                quote(300, 1, Duration::from_secs(3_600)),
+                Arc::new(MockPriceClient(Err(anyhow::anyhow!("API is down")))),
+                quote(310, 1, Duration::ZERO),
+                Arc::new(NoOpPriceAPIClient),
+            ],
+            2,
+        );
+        let (ratio, quotes) = client
+            .fetch_ratio_with_quotes(Address::zero())
+            .await
+            .unwrap();
+        assert_eq!((ratio.numerator.get(), ratio.denominator.get()), (1, 1));
+
+        // Failed sources don't produce quotes; stale quotes are recorded, but not accepted.
+        let quotes: Vec<_> = quotes
+            .iter()
+            .map(|quote| (quote.source.as_str(), quote.numerator.get(), quote.accepted))
+            .collect();
+        assert_eq!(
+            quotes,
+            [
+                ("source0", 300, false),
+                ("source2", 310, true),
+                ("source3", 1, true)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn erroring_on_insufficient_quotes() {
+        let client = create_client(
+            vec![
+                quote(300, 1, Duration::from_secs(3_600)),
+                quote(310, 1, Duration::ZERO),
+            ],
+            2,
+        );
+        let err = client.fetch_ratio(Address::zero()).await.unwrap_err();
+        assert!(err.to_string().contains("not enough fresh"), "{err}");
+    }
+}
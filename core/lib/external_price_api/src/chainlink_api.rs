@@ -0,0 +1,265 @@
+use std::num::NonZeroU64;
+
+use async_trait::async_trait;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use url::Url;
+use zksync_config::configs::ExternalPriceApiClientConfig;
+use zksync_types::{base_token_ratio::BaseTokenAPIRatio, web3::Bytes, Address, U256};
+
+use crate::PriceAPIClient;
+
+/// Selector of `latestRoundData()` of the `AggregatorV3Interface`.
+const LATEST_ROUND_DATA_SELECTOR: &str = "0xfeaf968c";
+/// Selector of `decimals()` of the `AggregatorV3Interface`.
+const DECIMALS_SELECTOR: &str = "0x313ce567";
+
+/// Client reading the base token price from a Chainlink price feed on L1. The feed must quote
+/// the base token in ETH (i.e., `answer / 10^decimals` ETH = 1 base token).
+#[derive(Debug)]
+pub struct ChainlinkPriceClient {
+    rpc_url: Url,
+    feed_address: Address,
+    client: reqwest::Client,
+}
+
+impl ChainlinkPriceClient {
+    pub fn new(config: ExternalPriceApiClientConfig) -> Self {
+        let chainlink_config = config
+            .chainlink
+            .as_ref()
+            .expect("chainlink price client started with no config");
+        let rpc_url = chainlink_config
+            .rpc_url
+            .as_deref()
+            .expect("chainlink price client started with no RPC URL");
+        let feed_address = chainlink_config
+            .feed_address
+            .expect("chainlink price client started with no feed address");
+        let client = reqwest::Client::builder()
+            .timeout(config.client_timeout())
+            .build()
+            .expect("Failed to build reqwest client");
+
+        Self {
+            rpc_url: Url::parse(rpc_url).expect("Failed to parse L1 RPC URL"),
+            feed_address,
+            client,
+        }
+    }
+
+    async fn eth_call(&self, data: &str) -> anyhow::Result<Vec<u8>> {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            id: 1,
+            method: "eth_call",
+            params: (
+                CallRequest {
+                    to: self.feed_address,
+                    data,
+                },
+                "latest",
+            ),
+        };
+        let response = self
+            .client
+            .post(self.rpc_url.clone())
+            .json(&request)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Http error while calling Chainlink feed {:?}. Status: {}, msg: {}",
+                self.feed_address,
+                response.status(),
+                response.text().await.unwrap_or(String::new())
+            ));
+        }
+
+        let response = response.json::<JsonRpcResponse>().await?;
+        if let Some(error) = response.error {
+            anyhow::bail!(
+                "Chainlink feed {:?} call failed: {}",
+                self.feed_address,
+                error.message
+            );
+        }
+        let result = response
+            .result
+            .ok_or_else(|| anyhow::anyhow!("No result in eth_call response"))?;
+        Ok(result.0)
+    }
+}
+
+#[async_trait]
+impl PriceAPIClient for ChainlinkPriceClient {
+    fn source_name(&self) -> &str {
+        "chainlink"
+    }
+
+    async fn fetch_ratio(&self, _token_address: Address) -> anyhow::Result<BaseTokenAPIRatio> {
+        let decimals = self.eth_call(DECIMALS_SELECTOR).await?;
+        let round_data = self.eth_call(LATEST_ROUND_DATA_SELECTOR).await?;
+        ratio_from_round_data(&decimals, &round_data)
+    }
+}
+
+/// Converts `latestRoundData()` output to the ratio. As the feed returns the price of 1 base token in ETH
+/// (`answer / 10^decimals`), the ratio is its reciprocal: `10^decimals / answer`.
+fn ratio_from_round_data(decimals: &[u8], round_data: &[u8]) -> anyhow::Result<BaseTokenAPIRatio> {
+    anyhow::ensure!(
+        decimals.len() == 32,
+        "unexpected decimals() output length: {}",
+        decimals.len()
+    );
+    anyhow::ensure!(
+        round_data.len() == 5 * 32,
+        "unexpected latestRoundData() output length: {}",
+        round_data.len()
+    );
+
+    let decimals = U256::from_big_endian(decimals);
+    anyhow::ensure!(
+        decimals <= U256::from(18),
+        "unsupported number of feed decimals: {decimals}"
+    );
+    let answer = U256::from_big_endian(&round_data[32..64]);
+    // `answer` is `int256`; negative values have the highest bit set.
+    anyhow::ensure!(!answer.bit(255), "feed answer is negative");
+    anyhow::ensure!(
+        answer <= U256::from(u64::MAX),
+        "feed answer {answer} doesn't fit into u64"
+    );
+    let updated_at = U256::from_big_endian(&round_data[96..128]);
+    anyhow::ensure!(
+        updated_at <= U256::from(i64::MAX as u64),
+        "invalid feed update timestamp {updated_at}"
+    );
+
+    let numerator = NonZeroU64::new(10_u64.pow(decimals.as_u32())).unwrap();
+    let denominator =
+        NonZeroU64::new(answer.as_u64()).ok_or_else(|| anyhow::anyhow!("feed answer is zero"))?;
+    let ratio_timestamp = DateTime::from_timestamp(updated_at.as_u64() as i64, 0)
+        .ok_or_else(|| anyhow::anyhow!("invalid feed update timestamp {updated_at}"))?;
+    Ok(BaseTokenAPIRatio {
+        numerator,
+        denominator,
+        ratio_timestamp,
+    })
+}
+
+#[derive(Debug, Serialize)]
+struct CallRequest<'a> {
+    to: Address,
+    data: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRpcRequest<'a> {
+    jsonrpc: &'static str,
+    id: u64,
+    method: &'static str,
+    params: (CallRequest<'a>, &'static str),
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse {
+    result: Option<Bytes>,
+    error: Option<JsonRpcError>,
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Utc;
+    use httpmock::MockServer;
+    use zksync_config::configs::external_price_api_client::{
+        ChainlinkPriceClientConfig, DEFAULT_TIMEOUT_MS,
+    };
+
+    use super::*;
+    use crate::tests::{approximate_value, TEST_TOKEN_ADDRESS};
+
+    fn encode_words(words: &[U256]) -> String {
+        let mut bytes = vec![0_u8; 32 * words.len()];
+        for (i, word) in words.iter().enumerate() {
+            word.to_big_endian(&mut bytes[32 * i..32 * (i + 1)]);
+        }
+        let hex: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+        format!("0x{hex}")
+    }
+
+    fn add_mock(server: &MockServer, selector: &str, result: String) {
+        server.mock(|when, then| {
+            when.method(httpmock::Method::POST)
+                .path("/")
+                .body_contains(selector);
+            then.status(200)
+                .body(format!(r#"{{"jsonrpc":"2.0","id":1,"result":"{result}"}}"#));
+        });
+    }
+
+    fn create_client(server: &MockServer) -> ChainlinkPriceClient {
+        ChainlinkPriceClient::new(ExternalPriceApiClientConfig {
+            source: "chainlink".to_string(),
+            base_url: None,
+            api_key: None,
+            client_timeout_ms: DEFAULT_TIMEOUT_MS,
+            forced: None,
+            chainlink: Some(ChainlinkPriceClientConfig {
+                rpc_url: Some(server.url("/")),
+                feed_address: Some(Address::repeat_byte(1)),
+            }),
+            aggregated: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn fetching_ratio_from_feed() {
+        let server = MockServer::start();
+        let updated_at = Utc::now().timestamp() as u64;
+        add_mock(&server, DECIMALS_SELECTOR, encode_words(&[18.into()]));
+        // 1 token = 0.00269 ETH
+        add_mock(
+            &server,
+            LATEST_ROUND_DATA_SELECTOR,
+            encode_words(&[
+                1.into(),
+                2_690_000_000_000_000_u64.into(),
+                updated_at.into(),
+                updated_at.into(),
+                1.into(),
+            ]),
+        );
+
+        let client = create_client(&server);
+        let ratio = client
+            .fetch_ratio(TEST_TOKEN_ADDRESS.parse().unwrap())
+            .await
+            .unwrap();
+        assert!((approximate_value(&ratio) - 371.747).abs() < 0.01);
+        assert_eq!(ratio.ratio_timestamp.timestamp() as u64, updated_at);
+    }
+
+    #[tokio::test]
+    async fn fetching_negative_answer_from_feed() {
+        let server = MockServer::start();
+        add_mock(&server, DECIMALS_SELECTOR, encode_words(&[8.into()]));
+        add_mock(
+            &server,
+            LATEST_ROUND_DATA_SELECTOR,
+            encode_words(&[1.into(), U256::MAX, 0.into(), 0.into(), 1.into()]),
+        );
+
+        let client = create_client(&server);
+        let err = client
+            .fetch_ratio(TEST_TOKEN_ADDRESS.parse().unwrap())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("negative"), "{err}");
+    }
+}
@@ -166,6 +166,10 @@ struct CryptocurrencyPlatform {
 
 #[async_trait]
 impl PriceAPIClient for CmcPriceApiClient {
+    fn source_name(&self) -> &str {
+        "coinmarketcap"
+    }
+
     async fn fetch_ratio(&self, token_address: Address) -> anyhow::Result<BaseTokenAPIRatio> {
         let base_token_in_eth = self.get_token_price_by_address(token_address).await?;
         let (term_ether, term_base_token) = get_fraction(base_token_in_eth)?;
@@ -193,6 +197,8 @@ mod tests {
             api_key,
             client_timeout_ms: 5000,
             forced: None,
+            chainlink: None,
+            aggregated: None,
         }))
     }
 
@@ -344,6 +350,8 @@ mod tests {
             client_timeout_ms: 5000,
             source: "coinmarketcap".to_string(),
             forced: None,
+            chainlink: None,
+            aggregated: None,
         });
 
         let tether: Address = "0xdac17f958d2ee523a2206206994597c13d831ec7"
@@ -86,6 +86,10 @@ impl CoinGeckoPriceAPIClient {
 
 #[async_trait]
 impl PriceAPIClient for CoinGeckoPriceAPIClient {
+    fn source_name(&self) -> &str {
+        "coingecko"
+    }
+
     async fn fetch_ratio(&self, token_address: Address) -> anyhow::Result<BaseTokenAPIRatio> {
         let base_token_in_eth = self.get_token_price_by_address(token_address).await?;
         let (num_in_eth, denom_in_eth) = get_fraction(base_token_in_eth)?;
@@ -171,6 +175,8 @@ mod test {
             source: "coingecko".to_string(),
             client_timeout_ms: DEFAULT_TIMEOUT_MS,
             forced: None,
+            chainlink: None,
+            aggregated: None,
         }
     }
 
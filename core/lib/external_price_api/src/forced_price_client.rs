@@ -65,6 +65,10 @@ impl ForcedPriceClient {
 
 #[async_trait]
 impl PriceAPIClient for ForcedPriceClient {
+    fn source_name(&self) -> &str {
+        "forced"
+    }
+
     /// Returns the configured ratio with fluctuation applied if enabled
     async fn fetch_ratio(&self, _token_address: Address) -> anyhow::Result<BaseTokenAPIRatio> {
         if let Some(fluctation) = self.fluctuation {
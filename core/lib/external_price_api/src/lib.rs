@@ -1,3 +1,5 @@
+pub mod aggregated_price_client;
+pub mod chainlink_api;
 pub mod cmc_api;
 pub mod coingecko_api;
 pub mod forced_price_client;
@@ -8,15 +10,32 @@ mod utils;
 use std::fmt;
 
 use async_trait::async_trait;
-use zksync_types::{base_token_ratio::BaseTokenAPIRatio, Address};
+use zksync_types::{
+    base_token_ratio::{BaseTokenAPIRatio, BaseTokenRatioQuote},
+    Address,
+};
 
 /// Trait that defines the interface for a client connecting with an external API to get prices.
 #[async_trait]
 pub trait PriceAPIClient: Sync + Send + fmt::Debug + 'static {
+    /// Returns the name of the price source, which is recorded together with fetched ratios.
+    fn source_name(&self) -> &str;
+
     /// Returns the BaseToken<->ETH ratio for the input token address.
     /// The returned value is rational number X such that X BaseToken = 1 ETH.
     /// Example if 1 BaseToken = 0.002 ETH, then ratio is 500/1 (500 BaseToken = 1ETH)
     async fn fetch_ratio(&self, token_address: Address) -> anyhow::Result<BaseTokenAPIRatio>;
+
+    /// Same as [`Self::fetch_ratio()`], but additionally returns raw quotes the ratio was derived from,
+    /// so that they can be audited. By default, the returned ratio is the only quote.
+    async fn fetch_ratio_with_quotes(
+        &self,
+        token_address: Address,
+    ) -> anyhow::Result<(BaseTokenAPIRatio, Vec<BaseTokenRatioQuote>)> {
+        let ratio = self.fetch_ratio(token_address).await?;
+        let quote = BaseTokenRatioQuote::new(self.source_name(), &ratio, true);
+        Ok((ratio, vec![quote]))
+    }
 }
 
 // Struct for a no-op PriceAPIClient (conversion ratio is always 1:1).
@@ -25,6 +44,10 @@ pub struct NoOpPriceAPIClient;
 
 #[async_trait]
 impl PriceAPIClient for NoOpPriceAPIClient {
+    fn source_name(&self) -> &str {
+        "no-op"
+    }
+
     async fn fetch_ratio(&self, _token_address: Address) -> anyhow::Result<BaseTokenAPIRatio> {
         Ok(BaseTokenAPIRatio::default())
     }
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     fmt,
     sync::{Arc, Mutex},
     thread,
@@ -101,6 +101,11 @@ pub enum AppHealthCheckError {
     /// Component is redefined.
     #[error("cannot insert health check for component `{0}`: it is redefined")]
     RedefinedComponent(&'static str),
+    /// Declared component dependency would introduce a cycle in the dependency graph.
+    #[error(
+        "cannot make component `{0}` depend on `{1}`: this would introduce a dependency cycle"
+    )]
+    DependencyCycle(&'static str, &'static str),
 }
 
 /// Application health check aggregating health from multiple components.
@@ -114,6 +119,8 @@ struct AppHealthCheckInner {
     /// Application-level health details.
     app_details: Option<serde_json::Value>,
     components: Vec<Arc<dyn CheckHealth>>,
+    /// Dependencies among components, keyed by the dependent component name.
+    dependencies: HashMap<&'static str, Vec<&'static str>>,
     slow_time_limit: Duration,
     hard_time_limit: Duration,
 }
@@ -135,6 +142,7 @@ impl AppHealthCheck {
 
         let inner = AppHealthCheckInner {
             components: Vec::default(),
+            dependencies: HashMap::new(),
             app_details: None,
             slow_time_limit,
             hard_time_limit,
@@ -222,11 +230,61 @@ impl AppHealthCheck {
         Ok(())
     }
 
+    /// Declares that `component` depends on the `depends_on` components (e.g., the API server depends on
+    /// the database and the Merkle tree). Dependencies are used to build the dependency graph in [`AppHealth`],
+    /// which pinpoints root causes of degraded component states. Components may be declared before
+    /// their health checks are inserted; dependencies on components that are never inserted are ignored
+    /// when determining root causes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the added dependencies would introduce a cycle. In this case, no dependencies are added.
+    pub fn add_dependencies(
+        &self,
+        component: &'static str,
+        depends_on: &[&'static str],
+    ) -> Result<(), AppHealthCheckError> {
+        let mut guard = self.inner.lock().expect("`AppHealthCheck` is poisoned");
+        for &dependency in depends_on {
+            if Self::depends_on(&guard.dependencies, dependency, component) {
+                return Err(AppHealthCheckError::DependencyCycle(component, dependency));
+            }
+        }
+
+        let existing = guard.dependencies.entry(component).or_default();
+        for &dependency in depends_on {
+            if !existing.contains(&dependency) {
+                existing.push(dependency);
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks whether `component` (transitively) depends on `target`, or is the `target` itself.
+    fn depends_on(
+        dependencies: &HashMap<&'static str, Vec<&'static str>>,
+        component: &'static str,
+        target: &'static str,
+    ) -> bool {
+        let mut stack = vec![component];
+        let mut visited = BTreeSet::new();
+        while let Some(name) = stack.pop() {
+            if name == target {
+                return true;
+            }
+            if visited.insert(name) {
+                stack.extend(dependencies.get(name).into_iter().flatten().copied());
+            }
+        }
+        false
+    }
+
     /// Checks the overall application health. This will query all component checks concurrently.
     pub async fn check_health(&self) -> AppHealth {
         // Clone `inner` so that we don't hold a lock for them across a wait point.
         let AppHealthCheckInner {
             components,
+            dependencies,
             app_details,
             slow_time_limit,
             hard_time_limit,
@@ -249,7 +307,14 @@ impl AppHealthCheck {
         let mut inner = Health::from(aggregated_status);
         inner.details = app_details.clone();
 
-        let health = AppHealth { inner, components };
+        let dependency_graph = DependencyGraph::new(&components, &dependencies);
+        let root_causes = dependency_graph.root_causes();
+        let health = AppHealth {
+            inner,
+            components,
+            dependency_graph: dependency_graph.nodes,
+            root_causes,
+        };
         if !health.inner.status.is_healthy() {
             // Only log non-ready application health so that logs are not spammed without a reason.
             tracing::debug!("Aggregated application health: {health:?}");
@@ -323,12 +388,122 @@ impl AppHealthCheck {
     }
 }
 
+/// Node of the component dependency graph included into [`AppHealth`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ComponentNode {
+    status: HealthStatus,
+    /// Names of the components this component directly depends on.
+    depends_on: Vec<&'static str>,
+    /// Components causing this component to be degraded (i.e., to have a status other than [`HealthStatus::Ready`]).
+    /// If none of the dependencies is degraded, the component itself is the root cause.
+    /// Empty if the component is not degraded.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    root_causes: Vec<&'static str>,
+}
+
+impl ComponentNode {
+    /// Returns the health status of the component.
+    pub fn status(&self) -> HealthStatus {
+        self.status
+    }
+
+    /// Returns names of the components this component directly depends on.
+    pub fn depends_on(&self) -> &[&'static str] {
+        &self.depends_on
+    }
+
+    /// Returns root causes of the component being degraded.
+    pub fn root_causes(&self) -> &[&'static str] {
+        &self.root_causes
+    }
+}
+
+/// Dependency graph of components, with root causes of degraded states resolved for each node.
+#[derive(Debug)]
+struct DependencyGraph {
+    nodes: HashMap<&'static str, ComponentNode>,
+}
+
+impl DependencyGraph {
+    fn new(
+        components: &HashMap<&'static str, Health>,
+        dependencies: &HashMap<&'static str, Vec<&'static str>>,
+    ) -> Self {
+        if dependencies.is_empty() {
+            // Don't output a trivial graph if no dependencies are declared.
+            return Self {
+                nodes: HashMap::new(),
+            };
+        }
+
+        let mut nodes = HashMap::with_capacity(components.len());
+        for &name in components.keys() {
+            Self::resolve(name, components, dependencies, &mut nodes);
+        }
+        Self { nodes }
+    }
+
+    fn resolve(
+        name: &'static str,
+        components: &HashMap<&'static str, Health>,
+        dependencies: &HashMap<&'static str, Vec<&'static str>>,
+        nodes: &mut HashMap<&'static str, ComponentNode>,
+    ) -> Option<Vec<&'static str>> {
+        if let Some(node) = nodes.get(name) {
+            return Some(node.root_causes.clone());
+        }
+        let status = components.get(name)?.status;
+        let depends_on = dependencies.get(name).cloned().unwrap_or_default();
+
+        let root_causes = if status == HealthStatus::Ready {
+            vec![]
+        } else {
+            // Cycles are prevented when adding dependencies, so recursion always terminates.
+            let inherited_causes: BTreeSet<_> = depends_on
+                .iter()
+                .filter_map(|&dependency| {
+                    Self::resolve(dependency, components, dependencies, nodes)
+                })
+                .flatten()
+                .collect();
+            if inherited_causes.is_empty() {
+                vec![name]
+            } else {
+                inherited_causes.into_iter().collect()
+            }
+        };
+
+        let node = ComponentNode {
+            status,
+            depends_on,
+            root_causes: root_causes.clone(),
+        };
+        nodes.insert(name, node);
+        Some(root_causes)
+    }
+
+    fn root_causes(&self) -> Vec<&'static str> {
+        let causes: BTreeSet<_> = self
+            .nodes
+            .values()
+            .flat_map(|node| node.root_causes.iter().copied())
+            .collect();
+        causes.into_iter().collect()
+    }
+}
+
 /// Health information for an application consisting of multiple components.
 #[derive(Debug, Serialize)]
 pub struct AppHealth {
     #[serde(flatten)]
     inner: Health,
     components: HashMap<&'static str, Health>,
+    /// Component dependency graph. Only present if component dependencies are declared.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    dependency_graph: HashMap<&'static str, ComponentNode>,
+    /// Root causes of degraded component states, as determined from the dependency graph.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    root_causes: Vec<&'static str>,
 }
 
 impl AppHealth {
@@ -345,6 +520,16 @@ impl AppHealth {
     pub fn components(&self) -> &HashMap<&'static str, Health> {
         &self.components
     }
+
+    /// Returns the component dependency graph. Empty if no component dependencies are declared.
+    pub fn dependency_graph(&self) -> &HashMap<&'static str, ComponentNode> {
+        &self.dependency_graph
+    }
+
+    /// Returns root causes of degraded component states.
+    pub fn root_causes(&self) -> &[&'static str] {
+        &self.root_causes
+    }
 }
 
 /// Interface to be used for health checks.
@@ -84,6 +84,7 @@ async fn aggregating_health_checks() {
     let inner = AppHealthCheckInner {
         app_details: None,
         components: vec![Arc::new(first_check), Arc::new(second_check)],
+        dependencies: HashMap::new(),
         slow_time_limit: AppHealthCheck::DEFAULT_SLOW_TIME_LIMIT,
         hard_time_limit: AppHealthCheck::DEFAULT_HARD_TIME_LIMIT,
     };
@@ -153,3 +154,95 @@ fn adding_duplicate_component() {
         .unwrap_err();
     assert_matches!(err, AppHealthCheckError::RedefinedComponent("test"));
 }
+
+#[test]
+fn adding_cyclic_dependencies() {
+    let checks = AppHealthCheck::default();
+    checks
+        .add_dependencies("api", &["tree", "database"])
+        .unwrap();
+    checks.add_dependencies("tree", &["database"]).unwrap();
+
+    let err = checks.add_dependencies("database", &["api"]).unwrap_err();
+    assert_matches!(err, AppHealthCheckError::DependencyCycle("database", "api"));
+    let err = checks.add_dependencies("tree", &["tree"]).unwrap_err();
+    assert_matches!(err, AppHealthCheckError::DependencyCycle("tree", "tree"));
+    // Dependencies must not be updated on error.
+    let inner = checks.inner.lock().unwrap();
+    assert!(!inner.dependencies.contains_key("database"));
+    assert_eq!(inner.dependencies["tree"], ["database"]);
+}
+
+#[tokio::test]
+async fn resolving_root_causes_using_dependency_graph() {
+    let checks = AppHealthCheck::default();
+    let (api_check, api_updater) = ReactiveHealthCheck::new("api");
+    let (tree_check, tree_updater) = ReactiveHealthCheck::new("tree");
+    let (db_check, db_updater) = ReactiveHealthCheck::new("database");
+    checks.insert_component(api_check).unwrap();
+    checks.insert_component(tree_check).unwrap();
+    checks.insert_component(db_check).unwrap();
+    checks
+        .add_dependencies("api", &["tree", "database", "missing"])
+        .unwrap();
+    checks.add_dependencies("tree", &["database"]).unwrap();
+
+    api_updater.update(HealthStatus::Ready.into());
+    tree_updater.update(HealthStatus::Ready.into());
+    db_updater.update(HealthStatus::Ready.into());
+    let app_health = checks.check_health().await;
+    assert!(app_health.root_causes().is_empty());
+    let api_node = &app_health.dependency_graph()["api"];
+    assert_matches!(api_node.status(), HealthStatus::Ready);
+    assert_eq!(api_node.depends_on(), ["tree", "database", "missing"]);
+    assert!(api_node.root_causes().is_empty());
+
+    // A component without degraded dependencies is the root cause of its own state.
+    tree_updater.update(HealthStatus::NotReady.into());
+    api_updater.update(HealthStatus::Affected.into());
+    let app_health = checks.check_health().await;
+    assert_eq!(app_health.root_causes(), ["tree"]);
+    assert_eq!(app_health.dependency_graph()["api"].root_causes(), ["tree"]);
+    assert_eq!(
+        app_health.dependency_graph()["tree"].root_causes(),
+        ["tree"]
+    );
+    assert!(app_health.dependency_graph()["database"]
+        .root_causes()
+        .is_empty());
+
+    // Degradation is traced through transitive dependencies.
+    db_updater.update(HealthStatus::NotReady.into());
+    let app_health = checks.check_health().await;
+    assert_eq!(app_health.root_causes(), ["database"]);
+    for component in ["api", "tree", "database"] {
+        assert_eq!(
+            app_health.dependency_graph()[component].root_causes(),
+            ["database"]
+        );
+    }
+
+    let json = serde_json::to_value(&app_health).unwrap();
+    assert_eq!(json["root_causes"], serde_json::json!(["database"]));
+    assert_eq!(
+        json["dependency_graph"]["tree"],
+        serde_json::json!({
+            "status": "not_ready",
+            "depends_on": ["database"],
+            "root_causes": ["database"],
+        })
+    );
+}
+
+#[tokio::test]
+async fn dependency_graph_is_omitted_without_dependencies() {
+    let checks = AppHealthCheck::default();
+    let (health_check, _health_updater) = ReactiveHealthCheck::new("test");
+    checks.insert_component(health_check).unwrap();
+
+    let app_health = checks.check_health().await;
+    assert!(app_health.dependency_graph().is_empty());
+    let json = serde_json::to_value(&app_health).unwrap();
+    assert!(json.get("dependency_graph").is_none());
+    assert!(json.get("root_causes").is_none());
+}
@@ -0,0 +1,71 @@
+//! Pluggable generation of L1 batch data submitted in `commitBatches` calls.
+
+use std::{fmt, sync::Arc};
+
+use zksync_types::{
+    commitment::{L1BatchCommitmentMode, L1BatchWithMetadata},
+    ethabi::{ParamType, Token},
+    pubdata_da::PubdataSendingMode,
+};
+
+use crate::{i_executor::structures::CommitBatchInfo, Tokenizable};
+
+/// Generator of `CommitBatchInfo` tokens for L1 batches committed on L1. The same generator must be used
+/// when committing batches (by `eth_sender`) and when checking the committed data (by the consistency checker),
+/// and it must match the layout expected by the L1 contracts of the chain.
+///
+/// The default implementations are provided for rollups and validiums; chains with modified pubdata layouts
+/// can supply their own implementation.
+pub trait L1BatchCommitDataGenerator: fmt::Debug + Send + Sync + 'static {
+    /// Encodes commit data for a single L1 batch.
+    fn l1_commit_batch(
+        &self,
+        l1_batch: &L1BatchWithMetadata,
+        pubdata_da: PubdataSendingMode,
+    ) -> Token;
+
+    /// Returns the ABI schema of commit data for a single post-gateway L1 batch, i.e. of tokens returned by
+    /// [`Self::l1_commit_batch()`]. Used to decode commit data from `commitBatches` calldata, so implementations
+    /// changing the layout of commit data must override this method as well.
+    fn commit_batch_schema(&self) -> ParamType {
+        CommitBatchInfo::post_gateway_schema()
+    }
+}
+
+impl dyn L1BatchCommitDataGenerator {
+    /// Returns the default generator for the specified commitment mode.
+    pub fn for_mode(mode: L1BatchCommitmentMode) -> Arc<Self> {
+        match mode {
+            L1BatchCommitmentMode::Rollup => Arc::new(RollupModeL1BatchCommitDataGenerator),
+            L1BatchCommitmentMode::Validium => Arc::new(ValidiumModeL1BatchCommitDataGenerator),
+        }
+    }
+}
+
+/// Default commit data generator for rollups.
+#[derive(Debug, Clone, Copy)]
+pub struct RollupModeL1BatchCommitDataGenerator;
+
+impl L1BatchCommitDataGenerator for RollupModeL1BatchCommitDataGenerator {
+    fn l1_commit_batch(
+        &self,
+        l1_batch: &L1BatchWithMetadata,
+        pubdata_da: PubdataSendingMode,
+    ) -> Token {
+        CommitBatchInfo::new(L1BatchCommitmentMode::Rollup, l1_batch, pubdata_da).into_token()
+    }
+}
+
+/// Default commit data generator for validiums.
+#[derive(Debug, Clone, Copy)]
+pub struct ValidiumModeL1BatchCommitDataGenerator;
+
+impl L1BatchCommitDataGenerator for ValidiumModeL1BatchCommitDataGenerator {
+    fn l1_commit_batch(
+        &self,
+        l1_batch: &L1BatchWithMetadata,
+        pubdata_da: PubdataSendingMode,
+    ) -> Token {
+        CommitBatchInfo::new(L1BatchCommitmentMode::Validium, l1_batch, pubdata_da).into_token()
+    }
+}
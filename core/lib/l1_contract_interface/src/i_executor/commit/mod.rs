@@ -1 +1,2 @@
+pub mod data_generator;
 pub mod kzg;
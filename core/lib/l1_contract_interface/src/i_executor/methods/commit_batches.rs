@@ -1,11 +1,14 @@
 use zksync_types::{
-    commitment::{L1BatchCommitmentMode, L1BatchWithMetadata},
+    commitment::L1BatchWithMetadata,
     ethabi::{encode, Token},
     pubdata_da::PubdataSendingMode,
 };
 
 use crate::{
-    i_executor::structures::{CommitBatchInfo, StoredBatchInfo, SUPPORTED_ENCODING_VERSION},
+    i_executor::{
+        commit::data_generator::L1BatchCommitDataGenerator,
+        structures::{StoredBatchInfo, SUPPORTED_ENCODING_VERSION},
+    },
     Tokenizable, Tokenize,
 };
 
@@ -15,7 +18,7 @@ pub struct CommitBatches<'a> {
     pub last_committed_l1_batch: &'a L1BatchWithMetadata,
     pub l1_batches: &'a [L1BatchWithMetadata],
     pub pubdata_da: PubdataSendingMode,
-    pub mode: L1BatchCommitmentMode,
+    pub data_generator: &'a dyn L1BatchCommitDataGenerator,
 }
 
 impl Tokenize for CommitBatches<'_> {
@@ -25,7 +28,7 @@ impl Tokenize for CommitBatches<'_> {
         let l1_batches_to_commit = self
             .l1_batches
             .iter()
-            .map(|batch| CommitBatchInfo::new(self.mode, batch, self.pubdata_da).into_token())
+            .map(|batch| self.data_generator.l1_commit_batch(batch, self.pubdata_da))
             .collect();
         if protocol_version.is_pre_gateway() {
             vec![stored_batch_info, Token::Array(l1_batches_to_commit)]
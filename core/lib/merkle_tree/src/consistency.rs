@@ -1,13 +1,19 @@
 //! Consistency verification for the Merkle tree.
 
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::{
+    ops,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
 
 use rayon::prelude::*;
 
 use crate::{
     errors::DeserializeError,
     hasher::{HashTree, HasherWithStats},
-    types::{LeafNode, Nibbles, Node, NodeKey, Root},
+    types::{ChildRef, LeafNode, Nibbles, Node, NodeKey, Root},
     Database, Key, MerkleTree, ValueHash,
 };
 
@@ -66,6 +72,35 @@ pub enum ConsistencyError {
     RootVersionMismatch { max_child_version: u64 },
 }
 
+impl ConsistencyError {
+    /// Returns the range of tree keys affected by this error, or `None` if the error affects the entire tree
+    /// (e.g., the tree root is missing).
+    pub fn key_range(&self) -> Option<ops::RangeInclusive<Key>> {
+        match self {
+            Self::MissingNode { key, .. }
+            | Self::TerminalInternalNode { key }
+            | Self::FullKeyMismatch { key, .. }
+            | Self::EmptyInternalNode { key }
+            | Self::KeyVersionMismatch { key, .. } => Some(key.key_range()),
+            Self::HashMismatch { key, nibble, .. } => {
+                let child_nibbles = key.nibbles.push(*nibble)?;
+                Some(child_nibbles.key_range())
+            }
+            Self::ZeroIndex { full_key }
+            | Self::LeafIndexOverflow { full_key, .. }
+            | Self::DuplicateLeafIndex { full_key, .. } => Some(*full_key..=*full_key),
+            Self::Deserialize(_)
+            | Self::MissingVersion(_)
+            | Self::MissingRoot(_)
+            | Self::LeafCountMismatch { .. }
+            | Self::RootVersionMismatch { .. } => None,
+        }
+    }
+}
+
+/// Sink for errors encountered during a non-failing consistency check.
+type ErrorSink = Mutex<Vec<ConsistencyError>>;
+
 impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
     /// Verifies the internal tree consistency as stored in the database.
     ///
@@ -79,6 +114,47 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
         &self,
         version: u64,
         validate_indices: bool,
+    ) -> Result<(), ConsistencyError> {
+        self.check_consistency(version, validate_indices, None)
+    }
+
+    /// Checks the internal tree consistency similarly to [`Self::verify_consistency()`], but doesn't stop
+    /// on the first encountered error. Instead, all inconsistent subtrees are reported; nodes in an inconsistent
+    /// subtree are not checked further. Use [`ConsistencyError::key_range()`] to get tree keys affected by each error.
+    ///
+    /// Returned errors are ordered by the affected key range, with errors affecting the entire tree going first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the checked tree version is missing or cannot be loaded.
+    pub fn collect_inconsistencies(
+        &self,
+        version: u64,
+        validate_indices: bool,
+    ) -> Result<Vec<ConsistencyError>, ConsistencyError> {
+        let sink = ErrorSink::default();
+        match self.check_consistency(version, validate_indices, Some(&sink)) {
+            Ok(()) => { /* all errors (if any) are in the sink */ }
+            // Errors in subtrees are reported to the sink, so these errors can only be produced
+            // when loading the tree root.
+            Err(
+                err @ (ConsistencyError::MissingVersion(_)
+                | ConsistencyError::MissingRoot(_)
+                | ConsistencyError::Deserialize(_)),
+            ) => return Err(err),
+            Err(err) => sink.lock().unwrap().push(err),
+        }
+
+        let mut errors = sink.into_inner().unwrap();
+        errors.sort_by_key(|err| err.key_range().map(|range| *range.start()));
+        Ok(errors)
+    }
+
+    fn check_consistency(
+        &self,
+        version: u64,
+        validate_indices: bool,
+        sink: Option<&ErrorSink>,
     ) -> Result<(), ConsistencyError> {
         let manifest = self.db.try_manifest()?;
         let manifest = manifest.ok_or(ConsistencyError::MissingVersion(version))?;
@@ -99,8 +175,10 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
         // much in memory.
         let root_key = Nibbles::EMPTY.with_version(version);
         let leaf_data = validate_indices.then(|| LeafConsistencyData::new(leaf_count));
-        self.validate_node(&root_node, root_key, leaf_data.as_ref())?;
-        if let Some(leaf_data) = leaf_data {
+        self.validate_node(&root_node, root_key, leaf_data.as_ref(), sink)?;
+        // If some subtrees are inconsistent, the actual leaf count is unreliable, so we don't check it.
+        let has_subtree_errors = sink.is_some_and(|sink| !sink.lock().unwrap().is_empty());
+        if let Some(leaf_data) = leaf_data.filter(|_| !has_subtree_errors) {
             leaf_data.validate_count()?;
         }
         Ok(())
@@ -111,6 +189,7 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
         node: &Node,
         key: NodeKey,
         leaf_data: Option<&LeafConsistencyData>,
+        sink: Option<&ErrorSink>,
     ) -> Result<ValueHash, ConsistencyError> {
         match node {
             Node::Leaf(leaf) => {
@@ -148,31 +227,13 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
                 children
                     .into_par_iter()
                     .try_for_each(|(nibble, child_ref)| {
-                        let child_key = key
-                            .nibbles
-                            .push(nibble)
-                            .ok_or(ConsistencyError::TerminalInternalNode { key })?;
-                        let child_key = child_key.with_version(child_ref.version);
-                        let child = self
-                            .db
-                            .try_tree_node(&child_key, child_ref.is_leaf)?
-                            .ok_or(ConsistencyError::MissingNode {
-                                key: child_key,
-                                is_leaf: child_ref.is_leaf,
-                            })?;
-
-                        // Recursion here is OK; the tree isn't that deep (approximately 8 nibbles for a tree with
-                        // approximately 1B entries).
-                        let child_hash = self.validate_node(&child, child_key, leaf_data)?;
-                        if child_hash == child_ref.hash {
-                            Ok(())
-                        } else {
-                            Err(ConsistencyError::HashMismatch {
-                                key,
-                                nibble,
-                                expected: child_ref.hash,
-                                actual: child_hash,
-                            })
+                        let result = self.validate_child(key, nibble, child_ref, leaf_data, sink);
+                        match (result, sink) {
+                            (Err(err), Some(sink)) => {
+                                sink.lock().unwrap().push(err);
+                                Ok(())
+                            }
+                            (result, _) => result,
                         }
                     })?;
             }
@@ -181,6 +242,42 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
         let level = key.nibbles.nibble_count() * 4;
         Ok(node.hash(&mut HasherWithStats::new(&self.hasher), level))
     }
+
+    fn validate_child(
+        &self,
+        key: NodeKey,
+        nibble: u8,
+        child_ref: &ChildRef,
+        leaf_data: Option<&LeafConsistencyData>,
+        sink: Option<&ErrorSink>,
+    ) -> Result<(), ConsistencyError> {
+        let child_key = key
+            .nibbles
+            .push(nibble)
+            .ok_or(ConsistencyError::TerminalInternalNode { key })?;
+        let child_key = child_key.with_version(child_ref.version);
+        let child = self
+            .db
+            .try_tree_node(&child_key, child_ref.is_leaf)?
+            .ok_or(ConsistencyError::MissingNode {
+                key: child_key,
+                is_leaf: child_ref.is_leaf,
+            })?;
+
+        // Recursion here is OK; the tree isn't that deep (approximately 8 nibbles for a tree with
+        // approximately 1B entries).
+        let child_hash = self.validate_node(&child, child_key, leaf_data, sink)?;
+        if child_hash == child_ref.hash {
+            Ok(())
+        } else {
+            Err(ConsistencyError::HashMismatch {
+                key,
+                nibble,
+                expected: child_ref.hash,
+                actual: child_hash,
+            })
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -354,6 +451,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn collecting_multiple_inconsistencies() {
+        let mut db = prepare_database();
+        let leaf_keys: Vec<_> = db
+            .nodes_mut()
+            .filter_map(|(key, node)| matches!(node, Node::Leaf(_)).then(|| *key))
+            .collect();
+        assert_eq!(leaf_keys.len(), 2);
+        for leaf_key in &leaf_keys {
+            db.remove_node(leaf_key);
+        }
+
+        let tree = MerkleTree::new(db).unwrap();
+        let errors = tree.collect_inconsistencies(0, true).unwrap();
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        for err in &errors {
+            assert_matches!(err, ConsistencyError::MissingNode { is_leaf: true, .. });
+        }
+        // Errors should be ordered by the key range, and the leaf count mismatch shouldn't be reported.
+        assert!(errors[0].key_range().unwrap().contains(&FIRST_KEY));
+        assert!(errors[1].key_range().unwrap().contains(&SECOND_KEY));
+        assert!(!errors[0].key_range().unwrap().contains(&SECOND_KEY));
+    }
+
+    #[test]
+    fn collecting_inconsistencies_for_consistent_tree() {
+        let tree = MerkleTree::new(prepare_database()).unwrap();
+        let errors = tree.collect_inconsistencies(0, true).unwrap();
+        assert!(errors.is_empty(), "{errors:?}");
+
+        let err = tree.collect_inconsistencies(1, true).unwrap_err();
+        assert_matches!(err, ConsistencyError::MissingVersion(1));
+    }
+
+    #[test]
+    fn hash_mismatch_key_range() {
+        let err = ConsistencyError::HashMismatch {
+            key: NodeKey::empty(0),
+            nibble: 0xd,
+            expected: ValueHash::zero(),
+            actual: ValueHash::zero(),
+        };
+        let key_range = err.key_range().unwrap();
+        assert!(key_range.contains(&FIRST_KEY));
+        assert_eq!(*key_range.start(), U256([0, 0, 0, 0xd000_0000_0000_0000]));
+    }
+
     #[test]
     fn leaf_count_mismatch_error() {
         let mut db = prepare_database();
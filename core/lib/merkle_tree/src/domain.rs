@@ -215,6 +215,20 @@ impl ZkSyncTree {
         self.tree.verify_consistency(version, true)
     }
 
+    /// Checks tree consistency without stopping on the first encountered error. `l1_batch_number` specifies
+    /// the version of the tree to be checked, expressed as the number of latest L1 batch applied to the tree.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the specified tree version cannot be loaded.
+    pub fn collect_inconsistencies(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Vec<ConsistencyError>, ConsistencyError> {
+        let version = u64::from(l1_batch_number.0);
+        self.tree.collect_inconsistencies(version, true)
+    }
+
     /// Processes an iterator of storage logs comprising a single L1 batch.
     ///
     /// # Errors
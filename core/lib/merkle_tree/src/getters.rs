@@ -30,7 +30,8 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
     ///
     /// # Errors
     ///
-    /// Returns an error if the tree `version` is missing.
+    /// Returns an error if the tree `version` is missing or was pruned, including the case when it is pruned
+    /// concurrently with this call.
     pub fn entries_with_proofs(
         &self,
         version: u64,
@@ -67,18 +68,24 @@ fn load_and_transform_entries<T>(
     leaf_keys: &[Key],
     mut transform: impl FnMut(&mut WorkingPatchSet, &Key, &Nibbles) -> T,
 ) -> Result<Vec<T>, NoVersionError> {
-    let root = db.root(version).ok_or_else(|| {
+    let no_version_error = || {
         let manifest = db.manifest().unwrap_or_default();
         NoVersionError {
             missing_version: version,
             version_count: manifest.version_count,
         }
-    })?;
+    };
+
+    let root = db.root(version).ok_or_else(no_version_error)?;
     let sorted_keys = SortedKeys::new(leaf_keys.iter().copied());
     let mut patch_set = WorkingPatchSet::new(version, root);
+    // Nodes may be missing if the version is pruned while we're loading them; in this case,
+    // we return the same error as if the version was pruned before the call.
     let LoadAncestorsResult {
         longest_prefixes, ..
-    } = patch_set.load_ancestors(&sorted_keys, db);
+    } = patch_set
+        .try_load_ancestors(&sorted_keys, db)
+        .map_err(|_| no_version_error())?;
 
     Ok(leaf_keys
         .iter()
@@ -116,7 +123,10 @@ impl<DB: PruneDatabase, H: HashTree> MerkleTreeRecovery<DB, H> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::PatchSet;
+    use crate::{
+        storage::{PruneDatabase, PrunePatchSet},
+        PatchSet,
+    };
 
     #[test]
     fn entries_in_empty_tree() {
@@ -157,4 +167,68 @@ mod tests {
         assert!(entries[1].base.is_empty());
         entries[1].verify(&tree.hasher, output.root_hash).unwrap();
     }
+
+    fn create_tree_with_two_versions() -> (MerkleTree<PatchSet>, Vec<Key>) {
+        let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+        let keys: Vec<_> = (0_u64..20).map(|i| Key::from(i * 0x1_0000)).collect();
+        let entries = keys
+            .iter()
+            .zip(1..)
+            .map(|(&key, idx)| TreeEntry::new(key, idx, ValueHash::repeat_byte(1)));
+        tree.extend(entries.collect()).unwrap();
+        let updates = keys
+            .iter()
+            .zip(1..)
+            .map(|(&key, idx)| TreeEntry::new(key, idx, ValueHash::repeat_byte(2)));
+        tree.extend(updates.collect()).unwrap();
+        (tree, keys)
+    }
+
+    #[test]
+    fn entries_for_historical_version() {
+        let (tree, keys) = create_tree_with_two_versions();
+        let root_hash = tree.root_hash(0).unwrap();
+
+        let entries = tree.entries_with_proofs(0, &keys).unwrap();
+        for entry in &entries {
+            assert_eq!(entry.base.value, ValueHash::repeat_byte(1));
+            entry.verify(&tree.hasher, root_hash).unwrap();
+        }
+    }
+
+    #[test]
+    fn entries_for_pruned_version() {
+        let (mut tree, keys) = create_tree_with_two_versions();
+        let pruned_keys = tree.db.stale_keys(1);
+        tree.db
+            .prune(PrunePatchSet::new(pruned_keys, 1..2))
+            .unwrap();
+        assert!(tree.db.root(0).is_none());
+
+        let err = tree.entries_with_proofs(0, &keys).unwrap_err();
+        assert_eq!(err.missing_version, 0);
+        assert_eq!(err.version_count, 2);
+        assert!(err.to_string().contains("was pruned"), "{err}");
+        tree.entries_with_proofs(1, &keys).unwrap();
+    }
+
+    #[test]
+    fn entries_for_partially_pruned_version() {
+        let (mut tree, keys) = create_tree_with_two_versions();
+        // Emulate pruning concurrent with loading entries: all stale nodes except for the root are removed.
+        let pruned_keys = tree
+            .db
+            .stale_keys(1)
+            .into_iter()
+            .filter(|key| !key.is_empty())
+            .collect();
+        tree.db
+            .prune(PrunePatchSet::new(pruned_keys, 1..1))
+            .unwrap();
+        assert!(tree.db.root(0).is_some());
+
+        let err = tree.entries_with_proofs(0, &keys).unwrap_err();
+        assert_eq!(err.missing_version, 0);
+        assert!(err.to_string().contains("was pruned"), "{err}");
+    }
 }
@@ -7,7 +7,8 @@ use std::{
 };
 
 use vise::{
-    Buckets, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Global, Histogram, Metrics, Unit,
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Global, Histogram, Metrics,
+    Unit,
 };
 
 use crate::types::Nibbles;
@@ -336,6 +337,11 @@ struct PruningMetrics {
     /// Lower and upper boundaries on the new stale key versions deleted
     /// during a pruning iteration. The lower boundary is inclusive, the upper one is exclusive.
     deleted_stale_key_versions: Family<Bound, Gauge<u64>>,
+    /// Number of versions with stale keys remaining to be pruned to reach the target retained version.
+    remaining_versions: Gauge<u64>,
+    /// Estimated number of bytes reclaimed by compacting the database after pruning.
+    #[metrics(unit = Unit::Bytes)]
+    reclaimed_bytes: Counter,
 }
 
 #[vise::register]
@@ -358,9 +364,21 @@ impl PruningStats {
             .set(self.deleted_stale_key_versions.start);
         PRUNING_METRICS.deleted_stale_key_versions[&Bound::End]
             .set(self.deleted_stale_key_versions.end);
+        PRUNING_METRICS
+            .remaining_versions
+            .set(self.remaining_versions());
+    }
+
+    /// Returns the number of versions with stale keys that remain to be pruned after this iteration.
+    pub fn remaining_versions(&self) -> u64 {
+        (self.target_retained_version + 1).saturating_sub(self.deleted_stale_key_versions.end)
     }
 }
 
+pub(crate) fn report_reclaimed_bytes(bytes: u64) {
+    PRUNING_METRICS.reclaimed_bytes.inc_by(bytes);
+}
+
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "merkle_tree_pruning")]
 pub(crate) struct PruningTimings {
@@ -370,6 +388,9 @@ pub(crate) struct PruningTimings {
     /// Time spent removing stale keys from RocksDB per pruning iteration.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub apply_patch: Histogram<Duration>,
+    /// Time spent compacting the database after pruning.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub compact: Histogram<Duration>,
 }
 
 #[vise::register]
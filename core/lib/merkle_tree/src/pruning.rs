@@ -2,6 +2,8 @@
 
 use std::{
     fmt,
+    num::NonZeroU64,
+    ops,
     sync::{
         atomic::{AtomicU64, Ordering},
         mpsc, Arc, Weak,
@@ -10,7 +12,7 @@ use std::{
 };
 
 use crate::{
-    metrics::{PruningStats, PRUNING_TIMINGS},
+    metrics::{report_reclaimed_bytes, PruningStats, PRUNING_TIMINGS},
     storage::{PruneDatabase, PrunePatchSet},
 };
 
@@ -32,6 +34,7 @@ impl fmt::Display for PrunerStoppedError {
 pub struct MerkleTreePrunerHandle {
     _aborted_sender: mpsc::Sender<()>,
     target_retained_version: Weak<AtomicU64>,
+    max_retention_version: Weak<AtomicU64>,
 }
 
 impl MerkleTreePrunerHandle {
@@ -50,6 +53,25 @@ impl MerkleTreePrunerHandle {
             Err(PrunerStoppedError(()))
         }
     }
+
+    /// Sets the maximum version that the retained version count policy (see
+    /// [`MerkleTreePruner::set_retained_version_count()`]) may prune to. This can be used to guarantee that versions
+    /// that are still needed (e.g., ones for L1 batches not executed on L1) are not pruned regardless of the policy.
+    /// Unlike [`Self::set_target_retained_version()`], the limit may decrease.
+    ///
+    /// By default, the retained version count policy is not limited.
+    ///
+    /// # Errors
+    ///
+    /// If the pruner has stopped (e.g., due to a panic), this method will return an error.
+    pub fn set_max_retention_version(&self, version: u64) -> Result<(), PrunerStoppedError> {
+        if let Some(limit) = self.max_retention_version.upgrade() {
+            limit.store(version, Ordering::Relaxed);
+            Ok(())
+        } else {
+            Err(PrunerStoppedError(()))
+        }
+    }
 }
 
 /// Component responsible for Merkle tree pruning, i.e. removing nodes not referenced by new versions
@@ -63,13 +85,21 @@ impl MerkleTreePrunerHandle {
 /// stale keys are recorded in a separate column family. A pruner takes stale keys that were produced
 /// by a certain range of tree versions, and removes the corresponding nodes from the tree
 /// (in RocksDB, this uses simple pointwise `delete_cf()` operations). The range of versions
-/// depends on pruning policies; for now, it's passed via the pruner handle.
+/// depends on pruning policies: it's passed via the pruner handle, and can be additionally bounded
+/// by the number of retained versions (see [`Self::set_retained_version_count()`]).
+///
+/// Pruning only marks nodes as deleted; to reclaim the disk space occupied by them, the pruner can compact
+/// the pruned part of the database once it has caught up with the target (see [`Self::set_online_compaction()`]).
 pub struct MerkleTreePruner<DB> {
     db: DB,
     target_pruned_key_count: usize,
     poll_interval: Duration,
+    iteration_delay: Duration,
+    retained_version_count: Option<NonZeroU64>,
+    online_compaction: bool,
     aborted_receiver: mpsc::Receiver<()>,
     target_retained_version: Arc<AtomicU64>,
+    max_retention_version: Arc<AtomicU64>,
 }
 
 impl<DB> fmt::Debug for MerkleTreePruner<DB> {
@@ -78,7 +108,11 @@ impl<DB> fmt::Debug for MerkleTreePruner<DB> {
             .debug_struct("MerkleTreePruner")
             .field("target_pruned_key_count", &self.target_pruned_key_count)
             .field("poll_interval", &self.poll_interval)
+            .field("iteration_delay", &self.iteration_delay)
+            .field("retained_version_count", &self.retained_version_count)
+            .field("online_compaction", &self.online_compaction)
             .field("target_retained_version", &self.target_retained_version)
+            .field("max_retention_version", &self.max_retention_version)
             .finish_non_exhaustive()
     }
 }
@@ -92,16 +126,22 @@ impl<DB: PruneDatabase> MerkleTreePruner<DB> {
     pub fn new(db: DB) -> (Self, MerkleTreePrunerHandle) {
         let (aborted_sender, aborted_receiver) = mpsc::channel();
         let target_retained_version = Arc::new(AtomicU64::new(0));
+        let max_retention_version = Arc::new(AtomicU64::new(u64::MAX));
         let handle = MerkleTreePrunerHandle {
             _aborted_sender: aborted_sender,
             target_retained_version: Arc::downgrade(&target_retained_version),
+            max_retention_version: Arc::downgrade(&max_retention_version),
         };
         let this = Self {
             db,
             target_pruned_key_count: 500_000,
             poll_interval: Duration::from_secs(60),
+            iteration_delay: Duration::ZERO,
+            retained_version_count: None,
+            online_compaction: false,
             aborted_receiver,
             target_retained_version,
+            max_retention_version,
         };
         (this, handle)
     }
@@ -123,6 +163,32 @@ impl<DB: PruneDatabase> MerkleTreePruner<DB> {
         self.poll_interval = poll_interval;
     }
 
+    /// Sets the sleep duration between pruning iterations when the pruner has more work to do.
+    /// Larger values throttle pruning I/O at the cost of slower pruning progress.
+    ///
+    /// The default value is zero (i.e., no throttling).
+    pub fn set_iteration_delay(&mut self, delay: Duration) {
+        self.iteration_delay = delay;
+    }
+
+    /// Sets the number of latest tree versions retained by the pruner regardless of the target retained version
+    /// set via [`MerkleTreePrunerHandle`]. If set, the pruner will prune all versions older than
+    /// the last `count` versions even if the handle requests to retain them.
+    ///
+    /// By default, the retained version count is not set, i.e., pruning is fully controlled by the handle.
+    pub fn set_retained_version_count(&mut self, count: Option<NonZeroU64>) {
+        self.retained_version_count = count;
+    }
+
+    /// Sets whether the pruner should compact the pruned part of the database once it has caught up
+    /// with the target retained version. Compaction reclaims disk space occupied by pruned nodes,
+    /// but is I/O-intensive.
+    ///
+    /// Compaction is disabled by default.
+    pub fn set_online_compaction(&mut self, enabled: bool) {
+        self.online_compaction = enabled;
+    }
+
     /// Returns max version number that can be safely pruned, so that there is at least one version present after pruning.
     #[doc(hidden)] // Used in integration tests; logically private
     pub fn last_prunable_version(&self) -> Option<u64> {
@@ -130,6 +196,48 @@ impl<DB: PruneDatabase> MerkleTreePruner<DB> {
         manifest.version_count.checked_sub(1)
     }
 
+    /// Returns the version the pruner should prune to, taking into account both the target retained version
+    /// set via the handle and the retained version count. The latter is clamped by the max retention version
+    /// set via the handle.
+    fn target_retained_version(&self) -> u64 {
+        let handle_target = self.target_retained_version.load(Ordering::Relaxed);
+        let Some(retained_count) = self.retained_version_count else {
+            return handle_target;
+        };
+        let Some(manifest) = self.db.manifest() else {
+            return handle_target;
+        };
+        let retention_target = manifest
+            .version_count
+            .saturating_sub(retained_count.get())
+            .min(self.max_retention_version.load(Ordering::Relaxed));
+        handle_target.max(retention_target)
+    }
+
+    /// Compacts the database up to the specified version, reporting the reclaimed space.
+    fn compact(
+        &mut self,
+        pruned_versions: ops::RangeTo<u64>,
+        size_before_pruning: Option<u64>,
+    ) -> anyhow::Result<()> {
+        tracing::info!("Compacting pruned Merkle tree versions {pruned_versions:?}");
+        let latency = PRUNING_TIMINGS.compact.start();
+        self.db.compact(pruned_versions)?;
+        let latency = latency.observe();
+
+        let reclaimed_bytes = size_before_pruning
+            .zip(self.db.estimated_size())
+            .map(|(before, after)| before.saturating_sub(after));
+        if let Some(reclaimed_bytes) = reclaimed_bytes {
+            report_reclaimed_bytes(reclaimed_bytes);
+        }
+        tracing::info!(
+            "Compacted pruned Merkle tree versions {pruned_versions:?} in {latency:?}; \
+             estimated reclaimed space: {reclaimed_bytes:?} bytes"
+        );
+        Ok(())
+    }
+
     #[doc(hidden)] // Used in integration tests; logically private
     #[allow(clippy::range_plus_one)] // exclusive range is required by `PrunePatchSet` constructor
     pub fn prune_up_to(
@@ -210,16 +318,35 @@ impl<DB: PruneDatabase> MerkleTreePruner<DB> {
         tracing::info!("Started Merkle tree pruner {self:?}");
 
         let mut wait_interval = Duration::ZERO;
+        // Estimated DB size before the current pruning cycle, and the exclusive upper bound of pruned versions
+        // not compacted yet.
+        let mut size_before_pruning = None;
+        let mut uncompacted_versions = None;
         while !self.wait_for_abort(wait_interval) {
-            let retained_version = self.target_retained_version.load(Ordering::Relaxed);
-            wait_interval = if let Some(stats) = self.prune_up_to(retained_version)? {
+            let retained_version = self.target_retained_version();
+            if uncompacted_versions.is_none() {
+                size_before_pruning = self.db.estimated_size();
+            }
+            let stats = self.prune_up_to(retained_version)?;
+            if let Some(stats) = &stats {
+                uncompacted_versions = Some(stats.deleted_stale_key_versions.end);
+            }
+
+            let has_more_work = stats.as_ref().is_some_and(PruningStats::has_more_work);
+            if !has_more_work && self.online_compaction {
+                if let Some(end) = uncompacted_versions.take() {
+                    self.compact(..end, size_before_pruning.take())?;
+                }
+            }
+
+            wait_interval = if let Some(stats) = stats {
                 tracing::debug!(
                     "Performed pruning for target retained version {retained_version}: {stats:?}"
                 );
                 stats.report();
-                if stats.has_more_work() {
-                    // Continue pruning right away instead of waiting for abort.
-                    Duration::ZERO
+                if has_more_work {
+                    // Continue pruning after the (possibly zero) throttling delay instead of waiting for the poll interval.
+                    self.iteration_delay
                 } else {
                     self.poll_interval
                 }
@@ -238,7 +365,7 @@ impl<DB: PruneDatabase> MerkleTreePruner<DB> {
 
 impl PruningStats {
     fn has_more_work(&self) -> bool {
-        self.target_retained_version + 1 > self.deleted_stale_key_versions.end
+        self.remaining_versions() > 0
     }
 }
 
@@ -315,6 +442,92 @@ mod tests {
         }
     }
 
+    #[test]
+    fn pruner_with_retained_version_count() {
+        let mut db = create_db();
+        let (mut pruner, _handle) = MerkleTreePruner::new(&mut db);
+        pruner.set_retained_version_count(NonZeroU64::new(2));
+        // The target set via the handle is 0, so the target is determined by the retained version count.
+        let target_retained_version = pruner.target_retained_version();
+        assert_eq!(target_retained_version, 3);
+
+        let stats = pruner
+            .prune_up_to(target_retained_version)
+            .unwrap()
+            .expect("tree was not pruned");
+        assert_eq!(stats.deleted_stale_key_versions, 1..4);
+        assert_eq!(stats.target_retained_version, 3);
+        assert_eq!(stats.remaining_versions(), 0);
+
+        for version in 0..3 {
+            assert!(db.root_mut(version).is_none());
+        }
+        assert!(db.root_mut(3).is_some());
+        assert!(db.root_mut(4).is_some());
+    }
+
+    #[test]
+    fn retained_version_count_does_not_override_larger_handle_target() {
+        let mut db = create_db();
+        let (mut pruner, handle) = MerkleTreePruner::new(&mut db);
+        pruner.set_retained_version_count(NonZeroU64::new(3));
+        assert_eq!(pruner.target_retained_version(), 2);
+        handle.set_target_retained_version(4).unwrap();
+        assert_eq!(pruner.target_retained_version(), 4);
+    }
+
+    #[test]
+    fn retained_version_count_is_clamped_by_max_retention_version() {
+        let mut db = create_db();
+        let (mut pruner, handle) = MerkleTreePruner::new(&mut db);
+        pruner.set_retained_version_count(NonZeroU64::new(1));
+        assert_eq!(pruner.target_retained_version(), 4);
+        handle.set_max_retention_version(2).unwrap();
+        assert_eq!(pruner.target_retained_version(), 2);
+        // The limit doesn't apply to the target set via the handle.
+        handle.set_target_retained_version(3).unwrap();
+        assert_eq!(pruner.target_retained_version(), 3);
+    }
+
+    #[test]
+    fn pruner_with_retention_and_compaction_on_rocksdb() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut db = RocksDBWrapper::new(temp_dir.path()).unwrap();
+        let mut tree = MerkleTree::new(&mut db).unwrap();
+        for chunk in generate_key_value_pairs(0..100).chunks(10) {
+            tree.extend(chunk.to_vec()).unwrap();
+        }
+
+        let (mut pruner, pruner_handle) = MerkleTreePruner::new(db.clone());
+        pruner.set_retained_version_count(NonZeroU64::new(1));
+        pruner.set_target_pruned_key_count(10);
+        pruner.set_iteration_delay(Duration::from_millis(1));
+        pruner.set_online_compaction(true);
+        pruner.set_poll_interval(Duration::from_millis(10));
+        let join_handle = thread::spawn(|| pruner.run());
+
+        let started_at = Instant::now();
+        loop {
+            let tree = MerkleTree::new(&mut db).unwrap();
+            if tree.first_retained_version() == Some(9) {
+                break;
+            }
+            assert!(
+                started_at.elapsed() < Duration::from_secs(10),
+                "pruning timed out"
+            );
+            thread::sleep(Duration::from_millis(10));
+        }
+        drop(pruner_handle);
+        join_handle.join().unwrap().unwrap();
+
+        let tree = MerkleTree::new(&mut db).unwrap();
+        tree.verify_consistency(9, true).unwrap();
+        for version in 0..9 {
+            assert!(db.root(version).is_none());
+        }
+    }
+
     #[test]
     fn pruner_is_aborted_immediately_when_requested() {
         let (mut pruner, pruner_handle) = MerkleTreePruner::new(PatchSet::default());
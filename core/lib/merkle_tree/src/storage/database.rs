@@ -411,6 +411,22 @@ pub trait PruneDatabase: Database {
         manifest: Manifest,
         truncated_versions: ops::RangeTo<u64>,
     ) -> anyhow::Result<()>;
+
+    /// Returns the estimated size of the tree data in bytes, or `None` if the database doesn't support
+    /// size estimation.
+    fn estimated_size(&self) -> Option<u64> {
+        None
+    }
+
+    /// Compacts the part of the database containing nodes for the specified versions, so that the space
+    /// occupied by pruned nodes is reclaimed. Blocks until compaction is completed. By default, does nothing.
+    ///
+    /// # Errors
+    ///
+    /// Propagates database I/O errors.
+    fn compact(&mut self, _versions: ops::RangeTo<u64>) -> anyhow::Result<()> {
+        Ok(())
+    }
 }
 
 impl<T: PruneDatabase + ?Sized> PruneDatabase for &mut T {
@@ -433,6 +449,14 @@ impl<T: PruneDatabase + ?Sized> PruneDatabase for &mut T {
     ) -> anyhow::Result<()> {
         (**self).truncate(manifest, truncated_versions)
     }
+
+    fn estimated_size(&self) -> Option<u64> {
+        (**self).estimated_size()
+    }
+
+    fn compact(&mut self, versions: ops::RangeTo<u64>) -> anyhow::Result<()> {
+        (**self).compact(versions)
+    }
 }
 
 impl PruneDatabase for PatchSet {
@@ -386,6 +386,16 @@ impl<DB: PruneDatabase> PruneDatabase for ParallelDatabase<DB> {
             .context("failed synchronizing database before truncation")?;
         self.inner.truncate(manifest, truncated_versions)
     }
+
+    fn estimated_size(&self) -> Option<u64> {
+        self.inner.estimated_size()
+    }
+
+    fn compact(&mut self, versions: ops::RangeTo<u64>) -> anyhow::Result<()> {
+        self.wait_sync()
+            .context("failed synchronizing database before compaction")?;
+        self.inner.compact(versions)
+    }
 }
 
 /// Database with either sequential or parallel persistence.
@@ -501,6 +511,20 @@ impl<DB: PruneDatabase> PruneDatabase for MaybeParallel<DB> {
             Self::Parallel(db) => db.truncate(manifest, truncated_versions),
         }
     }
+
+    fn estimated_size(&self) -> Option<u64> {
+        match self {
+            Self::Sequential(db) => db.estimated_size(),
+            Self::Parallel(db) => db.estimated_size(),
+        }
+    }
+
+    fn compact(&mut self, versions: ops::RangeTo<u64>) -> anyhow::Result<()> {
+        match self {
+            Self::Sequential(db) => db.compact(versions),
+            Self::Parallel(db) => db.compact(versions),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -506,11 +506,22 @@ impl WorkingPatchSet {
         sorted_keys: &SortedKeys,
         db: &DB,
     ) -> LoadAncestorsResult {
+        self.try_load_ancestors(sorted_keys, db)
+            .unwrap_or_else(|key| panic!("node {key} referenced by its parent is missing in DB"))
+    }
+
+    /// Same as [`Self::load_ancestors()`], but returns the key of the first encountered missing node
+    /// instead of panicking. Nodes may be missing if the loaded tree version is being concurrently pruned.
+    pub fn try_load_ancestors<DB: Database + ?Sized>(
+        &mut self,
+        sorted_keys: &SortedKeys,
+        db: &DB,
+    ) -> Result<LoadAncestorsResult, NodeKey> {
         let Some(Node::Internal(_)) = self.get(&Nibbles::EMPTY) else {
-            return LoadAncestorsResult {
+            return Ok(LoadAncestorsResult {
                 longest_prefixes: vec![Nibbles::EMPTY; sorted_keys.0.len()],
                 db_reads: 0,
-            };
+            });
         };
 
         // Longest prefix for each key in `key_value_pairs` (i.e., what we'll return from
@@ -561,25 +572,32 @@ impl WorkingPatchSet {
             let new_nodes = db.tree_nodes(&requested_keys);
             db_reads += new_nodes.len() as u64;
 
+            // All requested nodes are referenced by their parents, so they can only be missing
+            // if they were removed after loading the parents.
+            if let Some(((missing_key, _), _)) = requested_keys
+                .iter()
+                .zip(&new_nodes)
+                .find(|(_, node)| node.is_none())
+            {
+                return Err(*missing_key);
+            }
+
             // Since we load nodes level by level, we can update `patch_set` more efficiently
             // by pushing entire `HashMap`s into `changes_by_nibble_count`.
             let level = requested_keys
                 .iter()
                 .zip(new_nodes)
-                .map(|((key, _), node)| {
-                    (key, node.unwrap())
-                    // ^ `unwrap()` is safe: all requested nodes are referenced by their parents
-                });
+                .map(|((key, _), node)| (key, node.unwrap())); // `unwrap()` is safe: checked above
             self.push_level_from_db(level);
         }
 
         // All parents must be set at this point.
         let longest_prefixes = longest_prefixes.into_iter().map(Option::unwrap).collect();
 
-        LoadAncestorsResult {
+        Ok(LoadAncestorsResult {
             longest_prefixes,
             db_reads,
-        }
+        })
     }
 
     pub(super) fn traverse(&self, key: Key, parent_nibbles: &Nibbles) -> TraverseOutcome {
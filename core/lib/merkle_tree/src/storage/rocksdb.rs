@@ -473,6 +473,20 @@ impl PruneDatabase for RocksDBWrapper {
             .write(write_batch)
             .context("Failed writing a batch to RocksDB")
     }
+
+    fn estimated_size(&self) -> Option<u64> {
+        self.db
+            .estimated_live_data_size(MerkleTreeColumnFamily::Tree)
+    }
+
+    fn compact(&mut self, versions: ops::RangeTo<u64>) -> anyhow::Result<()> {
+        // Node keys start with the big-endian version, so nodes for the specified versions form a contiguous key range.
+        let start = 0_u64.to_be_bytes();
+        let end = versions.end.to_be_bytes();
+        self.db
+            .compact_range_cf(MerkleTreeColumnFamily::Tree, &start[..]..&end[..]);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -2,7 +2,7 @@
 //! some of these types are declared as public and can be even exported using the `unstable` module.
 //! Still, logically these types are private, so adding them to new public APIs etc. is a logical error.
 
-use std::{collections::HashMap, fmt, num::NonZeroU64, str::FromStr};
+use std::{collections::HashMap, fmt, num::NonZeroU64, ops, str::FromStr};
 
 use anyhow::Context;
 
@@ -255,6 +255,18 @@ impl Nibbles {
         }
         self
     }
+
+    /// Returns the range of tree keys starting with this sequence of nibbles.
+    pub fn key_range(&self) -> ops::RangeInclusive<Key> {
+        let start = Key::from_big_endian(&self.bytes);
+        let free_bits = 4 * (2 * KEY_SIZE - self.nibble_count);
+        let end = if free_bits == 0 {
+            start
+        } else {
+            start | (Key::MAX >> (TREE_DEPTH - free_bits))
+        };
+        start..=end
+    }
 }
 
 impl fmt::Display for Nibbles {
@@ -366,6 +378,11 @@ impl NodeKey {
         self.nibbles.nibble_count == 0
     }
 
+    /// Returns the range of tree keys covered by the node with this key.
+    pub fn key_range(&self) -> ops::RangeInclusive<Key> {
+        self.nibbles.key_range()
+    }
+
     #[allow(clippy::cast_possible_truncation)]
     pub(crate) fn to_db_key(self) -> Vec<u8> {
         let nibbles_byte_len = (self.nibbles.nibble_count + 1) / 2;
@@ -705,6 +722,29 @@ mod tests {
         assert!(nibbles.push(0xb).is_none());
     }
 
+    #[test]
+    fn nibbles_key_range() {
+        let range = Nibbles::EMPTY.key_range();
+        assert_eq!(range, Key::zero()..=Key::MAX);
+
+        let range = Nibbles::new(&TEST_KEY, 4).key_range();
+        assert_eq!(*range.start(), U256([0, 0, 0, 0x_dead_0000_0000_0000]));
+        assert_eq!(
+            *range.end(),
+            U256([u64::MAX, u64::MAX, u64::MAX, 0x_dead_ffff_ffff_ffff])
+        );
+
+        let range = Nibbles::new(&TEST_KEY, 5).key_range();
+        assert_eq!(*range.start(), U256([0, 0, 0, 0x_deadb_000_0000_0000]));
+        assert_eq!(
+            *range.end(),
+            U256([u64::MAX, u64::MAX, u64::MAX, 0x_deadb_fff_ffff_ffff])
+        );
+
+        let range = Nibbles::new(&TEST_KEY, 64).key_range();
+        assert_eq!(range, TEST_KEY..=TEST_KEY);
+    }
+
     #[test]
     fn nibbles_prefix() {
         let nibbles = Nibbles::new(&TEST_KEY, 6);
@@ -0,0 +1,168 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    glue::tracers::IntoOldVmTracer,
+    interface::{ExecutionFrame, ExecutionFrameSink, RegisterDelta},
+    tracers::old::OldTracers,
+};
+
+pub mod vm_1_4_1;
+pub mod vm_1_4_2;
+pub mod vm_boojum_integration;
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+/// Tracer streaming executed VM instructions to an [`ExecutionFrameSink`]. Intended for debugging single transactions;
+/// the tracer is very expensive and must not be used during normal execution.
+///
+/// Only supported for the latest VM version; for older versions, the tracer doesn't produce any frames.
+#[derive(Debug, Clone)]
+pub struct ExecutionTracer {
+    sink: Arc<dyn ExecutionFrameSink>,
+    next_index: u64,
+    pending: Option<PendingFrame>,
+}
+
+/// Frame for an instruction that has started but not finished executing.
+#[derive(Debug, Clone)]
+struct PendingFrame {
+    frame: ExecutionFrame,
+    registers: Vec<RegisterDelta>,
+}
+
+impl ExecutionTracer {
+    pub fn new(sink: Arc<dyn ExecutionFrameSink>) -> Self {
+        Self {
+            sink,
+            next_index: 0,
+            pending: None,
+        }
+    }
+
+    fn next_index(&mut self) -> u64 {
+        let index = self.next_index;
+        self.next_index += 1;
+        index
+    }
+}
+
+impl IntoOldVmTracer for ExecutionTracer {
+    fn old_tracer(&self) -> OldTracers {
+        OldTracers::None
+    }
+}
+
+#[derive(Debug, Default)]
+struct CollectedFrames {
+    frames: Vec<ExecutionFrame>,
+    truncated: bool,
+}
+
+/// [`ExecutionFrameSink`] collecting a page of frames in memory: up to `max_frames` frames starting
+/// from the frame with index `from_frame`.
+#[derive(Debug)]
+pub struct ExecutionFrameCollector {
+    from_frame: u64,
+    max_frames: usize,
+    inner: Mutex<CollectedFrames>,
+}
+
+impl ExecutionFrameCollector {
+    pub fn new(from_frame: u64, max_frames: usize) -> Self {
+        Self {
+            from_frame,
+            max_frames,
+            inner: Mutex::default(),
+        }
+    }
+
+    /// Returns collected frames and a flag whether some frames were dropped because of the limit.
+    pub fn take(&self) -> (Vec<ExecutionFrame>, bool) {
+        let mut inner = self.inner.lock().expect("collector is poisoned");
+        let inner = std::mem::take(&mut *inner);
+        (inner.frames, inner.truncated)
+    }
+}
+
+impl ExecutionFrameSink for ExecutionFrameCollector {
+    fn accepts(&self, index: u64) -> bool {
+        if index < self.from_frame {
+            return false;
+        }
+        let mut inner = self.inner.lock().expect("collector is poisoned");
+        if inner.frames.len() < self.max_frames {
+            true
+        } else {
+            inner.truncated = true;
+            false
+        }
+    }
+
+    fn push(&self, frame: ExecutionFrame) {
+        if self.accepts(frame.index) {
+            let mut inner = self.inner.lock().expect("collector is poisoned");
+            inner.frames.push(frame);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::Address;
+
+    use super::*;
+
+    fn frame(index: u64) -> ExecutionFrame {
+        ExecutionFrame {
+            index,
+            pc: index,
+            opcode: "Nop".to_owned(),
+            depth: 1,
+            address: Address::repeat_byte(1),
+            code_address: Address::repeat_byte(1),
+            gas: 1_000,
+            gas_cost: 6,
+            sp: 0,
+            register_deltas: vec![],
+            heap_write: None,
+            far_call: None,
+        }
+    }
+
+    #[test]
+    fn collector_truncates_frames() {
+        let collector = ExecutionFrameCollector::new(0, 2);
+        for i in 0..5 {
+            collector.push(frame(i));
+        }
+        let (frames, truncated) = collector.take();
+        assert_eq!(frames, [frame(0), frame(1)]);
+        assert!(truncated);
+
+        let (frames, truncated) = collector.take();
+        assert!(frames.is_empty());
+        assert!(!truncated);
+    }
+
+    #[test]
+    fn collector_returns_requested_page() {
+        let collector = ExecutionFrameCollector::new(2, 2);
+        let accepted: Vec<_> = (0..5).filter(|&i| collector.accepts(i)).collect();
+        assert_eq!(accepted, [2, 3, 4]);
+        for i in 0..5 {
+            collector.push(frame(i));
+        }
+        let (frames, truncated) = collector.take();
+        assert_eq!(frames, [frame(2), frame(3)]);
+        assert!(truncated);
+
+        let collector = ExecutionFrameCollector::new(3, 2);
+        for i in 0..5 {
+            collector.push(frame(i));
+        }
+        let (frames, truncated) = collector.take();
+        assert_eq!(frames, [frame(3), frame(4)]);
+        assert!(!truncated);
+    }
+}
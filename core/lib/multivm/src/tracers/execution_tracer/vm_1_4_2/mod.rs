@@ -0,0 +1,9 @@
+use crate::{
+    interface::storage::WriteStorage,
+    tracers::{dynamic::vm_1_4_1::DynTracer, ExecutionTracer},
+    vm_1_4_2::{HistoryMode, SimpleMemory, VmTracer},
+};
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for ExecutionTracer {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for ExecutionTracer {}
@@ -0,0 +1,9 @@
+use crate::{
+    interface::storage::WriteStorage,
+    tracers::{dynamic::vm_1_4_0::DynTracer, ExecutionTracer},
+    vm_boojum_integration::{HistoryMode, SimpleMemory, VmTracer},
+};
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for ExecutionTracer {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for ExecutionTracer {}
@@ -0,0 +1,133 @@
+use zk_evm_1_5_0::{
+    tracing::{AfterExecutionData, BeforeExecutionData, VmLocalStateData},
+    zkevm_opcode_defs::{decoding::AllowedPcOrImm, FatPointer, Opcode, UMAOpcode},
+};
+
+use super::PendingFrame;
+use crate::{
+    interface::{
+        storage::{StoragePtr, WriteStorage},
+        tracer::VmExecutionStopReason,
+        ExecutionFrame, FarCallFrame, HeapWrite, RegisterDelta,
+    },
+    tracers::{dynamic::vm_1_5_0::DynTracer, ExecutionTracer},
+    vm_latest::{BootloaderState, HistoryMode, SimpleMemory, VmTracer, ZkSyncVmState},
+};
+
+fn registers(state: &VmLocalStateData<'_>) -> Vec<RegisterDelta> {
+    state
+        .vm_local_state
+        .registers
+        .iter()
+        .enumerate()
+        .map(|(i, register)| RegisterDelta {
+            register: i as u8 + 1,
+            value: register.value,
+            is_pointer: register.is_pointer,
+        })
+        .collect()
+}
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for ExecutionTracer {
+    fn before_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: BeforeExecutionData,
+        _memory: &SimpleMemory<H>,
+        _storage: StoragePtr<S>,
+    ) {
+        let index = self.next_index();
+        if !self.sink.accepts(index) {
+            return;
+        }
+
+        let current = &state.vm_local_state.callstack.current;
+        let heap_write = match data.opcode.variant.opcode {
+            Opcode::UMA(opcode @ (UMAOpcode::HeapWrite | UMAOpcode::AuxHeapWrite)) => {
+                Some(HeapWrite {
+                    aux: matches!(opcode, UMAOpcode::AuxHeapWrite),
+                    offset: FatPointer::from_u256(data.src0_value.value).offset,
+                    value: data.src1_value.value,
+                })
+            }
+            _ => None,
+        };
+
+        let frame = ExecutionFrame {
+            index,
+            pc: current.pc.as_u64(),
+            opcode: format!("{:?}", data.opcode.variant.opcode),
+            depth: state.vm_local_state.callstack.inner.len(),
+            address: current.this_address,
+            code_address: current.code_address,
+            gas: current.ergs_remaining.into(),
+            gas_cost: 0,
+            sp: current.sp.as_u64(),
+            register_deltas: vec![],
+            heap_write,
+            far_call: None,
+        };
+        self.pending = Some(PendingFrame {
+            frame,
+            registers: registers(&state),
+        });
+    }
+
+    fn after_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: AfterExecutionData,
+        _memory: &SimpleMemory<H>,
+        _storage: StoragePtr<S>,
+    ) {
+        let Some(PendingFrame {
+            mut frame,
+            registers: registers_before,
+        }) = self.pending.take()
+        else {
+            return;
+        };
+
+        let current = &state.vm_local_state.callstack.current;
+        frame.register_deltas = registers(&state)
+            .into_iter()
+            .zip(registers_before)
+            .filter(|(after, before)| after != before)
+            .map(|(after, _)| after)
+            .collect();
+        frame.sp = current.sp.as_u64();
+
+        if matches!(data.opcode.variant.opcode, Opcode::FarCall(_)) {
+            // The callee frame is the current one; the caller frame is the last one in the call stack.
+            let caller_gas = state
+                .vm_local_state
+                .callstack
+                .inner
+                .last()
+                .map_or(0, |caller| u64::from(caller.ergs_remaining));
+            frame.gas_cost = frame.gas.saturating_sub(caller_gas);
+            frame.far_call = Some(FarCallFrame {
+                callee: current.this_address,
+                code_address: current.code_address,
+                gas: current.ergs_remaining.into(),
+            });
+        } else if frame.depth == state.vm_local_state.callstack.inner.len() {
+            frame.gas_cost = frame.gas.saturating_sub(u64::from(current.ergs_remaining));
+        }
+        // For returns and near calls, gas is moved between frames, so the instruction cost cannot be determined
+        // from the remaining gas alone; we leave the cost as zero in this case.
+
+        self.sink.push(frame);
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for ExecutionTracer {
+    fn after_vm_execution(
+        &mut self,
+        _state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        self.sink.finish();
+    }
+}
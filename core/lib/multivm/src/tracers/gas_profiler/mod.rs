@@ -0,0 +1,234 @@
+use std::{collections::HashMap, sync::Arc};
+
+use once_cell::sync::OnceCell;
+use zksync_types::{vm::VmVersion, web3::Bytes, Address, StorageKey, H256};
+
+use crate::{
+    glue::tracers::IntoOldVmTracer, interface::ContractGasUsage, tracers::old::OldTracers,
+};
+
+pub mod vm_1_4_1;
+pub mod vm_1_4_2;
+pub mod vm_boojum_integration;
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+/// Contract function usage is attributed to: the contract address and the function selector.
+type FunctionKey = (Address, Option<[u8; 4]>);
+
+/// Tracer attributing gas and pubdata usage to called contract functions.
+///
+/// Gas is attributed using far call frames: each function is charged for the gas used by its frames, excluding
+/// gas used by nested far calls. Pubdata for a storage slot is charged to the function that has written the slot last.
+/// Only far calls made while an L2 transaction is processed (i.e., from the start of account validation until
+/// the end of the transaction) are profiled, so bootloader calls setting up the L2 block are not attributed.
+/// Only supported for the latest VM version; for older versions, the profile is empty.
+#[derive(Debug, Clone)]
+pub struct GasProfiler {
+    vm_version: VmVersion,
+    is_profiling: bool,
+    stack: Vec<ProfiledFrame>,
+    usage: HashMap<FunctionKey, FunctionUsage>,
+    storage_writes: HashMap<StorageKey, StorageWrite>,
+    result: Arc<OnceCell<Vec<ContractGasUsage>>>,
+}
+
+#[derive(Debug, Clone)]
+struct ProfiledFrame {
+    function: FunctionKey,
+    /// Gas available to the caller before the call; used to compute gas used by the call.
+    parent_gas: u64,
+    /// Gas used by nested far calls.
+    nested_gas: u64,
+    near_calls_after: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct FunctionUsage {
+    calls: u64,
+    gas_used: u64,
+    self_gas_used: u64,
+    pubdata_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct StorageWrite {
+    initial_value: H256,
+    last_writer: Option<FunctionKey>,
+}
+
+impl GasProfiler {
+    pub fn new(result: Arc<OnceCell<Vec<ContractGasUsage>>>, vm_version: VmVersion) -> Self {
+        Self {
+            vm_version,
+            is_profiling: false,
+            stack: vec![],
+            usage: HashMap::new(),
+            storage_writes: HashMap::new(),
+            result,
+        }
+    }
+
+    /// Starts or stops profiling far calls made by the bootloader.
+    fn set_profiling(&mut self, is_profiling: bool) {
+        self.is_profiling = is_profiling;
+    }
+
+    fn push_far_call(&mut self, function: FunctionKey, parent_gas: u64) {
+        if self.stack.is_empty() && !self.is_profiling {
+            return;
+        }
+        self.stack.push(ProfiledFrame {
+            function,
+            parent_gas,
+            nested_gas: 0,
+            near_calls_after: 0,
+        });
+    }
+
+    fn push_near_call(&mut self) {
+        if let Some(frame) = self.stack.last_mut() {
+            frame.near_calls_after += 1;
+        }
+    }
+
+    /// Handles a return with the specified gas remaining in the frame the execution has returned to.
+    fn pop_frame(&mut self, gas_remaining: u64) {
+        let Some(frame) = self.stack.last_mut() else {
+            return;
+        };
+        if frame.near_calls_after > 0 {
+            frame.near_calls_after -= 1;
+            return;
+        }
+
+        let frame = self.stack.pop().unwrap();
+        let gas_used = frame.parent_gas.saturating_sub(gas_remaining);
+        let usage = self.usage.entry(frame.function).or_default();
+        usage.calls += 1;
+        usage.gas_used += gas_used;
+        usage.self_gas_used += gas_used.saturating_sub(frame.nested_gas);
+        if let Some(parent) = self.stack.last_mut() {
+            parent.nested_gas += gas_used;
+        }
+    }
+
+    /// Records a write to a storage slot. `read_initial_value` is only called on the first write to the slot.
+    fn record_storage_write(&mut self, key: StorageKey, read_initial_value: impl FnOnce() -> H256) {
+        let last_writer = self.stack.last().map(|frame| frame.function);
+        self.storage_writes
+            .entry(key)
+            .or_insert_with(|| StorageWrite {
+                initial_value: read_initial_value(),
+                last_writer: None,
+            })
+            .last_writer = last_writer;
+    }
+
+    /// Charges pubdata for written storage slots and stores the profile. `pubdata_bytes` returns the number of pubdata bytes
+    /// for a slot given its initial value (i.e., the value before the first write).
+    fn store_result(&mut self, mut pubdata_bytes: impl FnMut(&StorageKey, H256) -> u32) {
+        for (key, write) in std::mem::take(&mut self.storage_writes) {
+            if let Some(writer) = write.last_writer {
+                let bytes = pubdata_bytes(&key, write.initial_value);
+                self.usage.entry(writer).or_default().pubdata_bytes += u64::from(bytes);
+            }
+        }
+
+        let mut profile: Vec<_> = std::mem::take(&mut self.usage)
+            .into_iter()
+            .map(|((contract, selector), usage)| ContractGasUsage {
+                contract,
+                selector: selector.map(|selector| Bytes(selector.to_vec())),
+                calls: usage.calls,
+                gas_used: usage.gas_used,
+                self_gas_used: usage.self_gas_used,
+                pubdata_bytes: usage.pubdata_bytes,
+            })
+            .collect();
+        profile.sort_unstable_by(|a, b| {
+            b.self_gas_used
+                .cmp(&a.self_gas_used)
+                .then_with(|| a.contract.cmp(&b.contract))
+                .then_with(|| {
+                    a.selector
+                        .as_ref()
+                        .map(|s| &s.0)
+                        .cmp(&b.selector.as_ref().map(|s| &s.0))
+                })
+        });
+        // The result may already be set if the tracer is reused; in this case, we keep the first result.
+        self.result.set(profile).ok();
+    }
+}
+
+impl IntoOldVmTracer for GasProfiler {
+    fn old_tracer(&self) -> OldTracers {
+        OldTracers::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::AccountTreeId;
+
+    use super::*;
+
+    #[test]
+    fn attributing_gas_to_functions() {
+        let result = Arc::default();
+        let mut profiler = GasProfiler::new(Arc::clone(&result), VmVersion::latest());
+        let system = (Address::repeat_byte(0xff), None);
+        let account = (Address::repeat_byte(1), Some([1, 2, 3, 4]));
+        let token = (Address::repeat_byte(2), Some([5, 6, 7, 8]));
+
+        // Calls before the transaction is processed are not profiled.
+        profiler.push_far_call(system, 2_000);
+        profiler.pop_frame(1_900);
+        profiler.set_profiling(true);
+
+        profiler.push_far_call(account, 1_000);
+        profiler.push_near_call();
+        profiler.push_far_call(token, 800);
+        profiler.pop_frame(700); // token call has used 100 gas
+        profiler.pop_frame(650); // near call return
+        profiler.push_far_call(token, 600);
+        profiler.pop_frame(550); // token call has used 50 gas
+        profiler.pop_frame(400); // account call has used 600 gas
+
+        let key = StorageKey::new(AccountTreeId::new(token.0), H256::repeat_byte(1));
+        profiler.push_far_call(token, 300);
+        profiler.record_storage_write(key, H256::zero);
+        profiler.pop_frame(250);
+
+        profiler.set_profiling(false);
+        profiler.push_far_call(system, 200);
+        profiler.pop_frame(100);
+
+        profiler.store_result(|_, _| 40);
+
+        let profile = result.get().unwrap();
+        assert_eq!(
+            *profile,
+            [
+                ContractGasUsage {
+                    contract: account.0,
+                    selector: Some(Bytes(vec![1, 2, 3, 4])),
+                    calls: 1,
+                    gas_used: 600,
+                    self_gas_used: 450,
+                    pubdata_bytes: 0,
+                },
+                ContractGasUsage {
+                    contract: token.0,
+                    selector: Some(Bytes(vec![5, 6, 7, 8])),
+                    calls: 3,
+                    gas_used: 200,
+                    self_gas_used: 200,
+                    pubdata_bytes: 40,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,9 @@
+use crate::{
+    interface::storage::WriteStorage,
+    tracers::{dynamic::vm_1_4_1::DynTracer, GasProfiler},
+    vm_1_4_2::{HistoryMode, SimpleMemory, VmTracer},
+};
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for GasProfiler {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for GasProfiler {}
@@ -0,0 +1,114 @@
+use zk_evm_1_5_0::{
+    tracing::{AfterExecutionData, BeforeExecutionData, VmLocalStateData},
+    zkevm_opcode_defs::{FarCallABI, LogOpcode, Opcode, CALL_IMPLICIT_CALLDATA_FAT_PTR_REGISTER},
+};
+use zksync_types::{h256_to_u256, u256_to_h256, AccountTreeId, StorageKey};
+
+use crate::{
+    interface::{
+        storage::{StoragePtr, WriteStorage},
+        tracer::VmExecutionStopReason,
+    },
+    tracers::{dynamic::vm_1_5_0::DynTracer, GasProfiler},
+    vm_latest::{
+        tracers::utils::VmHook, BootloaderState, HistoryMode, MultiVmSubversion, SimpleMemory,
+        VmTracer, ZkSyncVmState,
+    },
+};
+
+/// Reads the function selector from the calldata of the current far call.
+fn read_selector<H: HistoryMode>(
+    state: &VmLocalStateData<'_>,
+    memory: &SimpleMemory<H>,
+) -> Option<[u8; 4]> {
+    let current = &state.vm_local_state.callstack.current;
+    if current.code_page.0 == 0 || current.ergs_remaining == 0 {
+        return None;
+    }
+    let packed_abi =
+        state.vm_local_state.registers[CALL_IMPLICIT_CALLDATA_FAT_PTR_REGISTER as usize];
+    if !packed_abi.is_pointer {
+        return None;
+    }
+    let pointer = FarCallABI::from_u256(packed_abi.value).memory_quasi_fat_pointer;
+    if pointer.length < 4 {
+        return None;
+    }
+    let selector =
+        memory.read_unaligned_bytes(pointer.memory_page as usize, pointer.start as usize, 4);
+    selector.try_into().ok()
+}
+
+impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for GasProfiler {
+    fn before_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: BeforeExecutionData,
+        _memory: &SimpleMemory<H>,
+        storage: StoragePtr<S>,
+    ) {
+        let Ok(subversion) = MultiVmSubversion::try_from(self.vm_version) else {
+            return;
+        };
+        match VmHook::from_opcode_memory(&state, &data, subversion) {
+            VmHook::AccountValidationEntered => self.set_profiling(true),
+            VmHook::TxHasEnded => self.set_profiling(false),
+            _ => {}
+        }
+
+        if let Opcode::Log(LogOpcode::StorageWrite) = data.opcode.variant.opcode {
+            let address = state.vm_local_state.callstack.current.this_address;
+            let key = StorageKey::new(
+                AccountTreeId::new(address),
+                u256_to_h256(data.src0_value.value),
+            );
+            self.record_storage_write(key, || storage.borrow_mut().read_value(&key));
+        }
+    }
+
+    fn after_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: AfterExecutionData,
+        memory: &SimpleMemory<H>,
+        _storage: StoragePtr<S>,
+    ) {
+        let current = &state.vm_local_state.callstack.current;
+        match data.opcode.variant.opcode {
+            Opcode::NearCall(_) => self.push_near_call(),
+            Opcode::FarCall(_) => {
+                // Same as in the call tracer, we use parent gas to account for the gas spent on the call itself.
+                let current_ergs = current.ergs_remaining;
+                let parent_gas = state
+                    .vm_local_state
+                    .callstack
+                    .inner
+                    .last()
+                    .map(|call| call.ergs_remaining + current_ergs)
+                    .unwrap_or(current_ergs) as u64;
+                let function = (current.this_address, read_selector(&state, memory));
+                self.push_far_call(function, parent_gas);
+            }
+            Opcode::Ret(_) => self.pop_frame(current.ergs_remaining.into()),
+            _ => {}
+        }
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for GasProfiler {
+    fn after_vm_execution(
+        &mut self,
+        state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        self.store_result(|key, initial_value| {
+            let final_value = state.storage.storage.get_ptr().borrow_mut().read_value(key);
+            state.storage.base_price_for_write(
+                key,
+                h256_to_u256(initial_value),
+                h256_to_u256(final_value),
+            )
+        });
+    }
+}
@@ -0,0 +1,9 @@
+use crate::{
+    interface::storage::WriteStorage,
+    tracers::{dynamic::vm_1_3_3::DynTracer, GasProfiler},
+    vm_refunds_enhancement::{HistoryMode, SimpleMemory, VmTracer},
+};
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for GasProfiler {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for GasProfiler {}
@@ -0,0 +1,15 @@
+use crate::{
+    interface::storage::WriteStorage,
+    tracers::{dynamic::vm_1_3_3::DynTracer, GasProfiler},
+    vm_virtual_blocks::{
+        ExecutionEndTracer, ExecutionProcessing, HistoryMode, SimpleMemory, VmTracer,
+    },
+};
+
+impl<H: HistoryMode> ExecutionEndTracer<H> for GasProfiler {}
+
+impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for GasProfiler {}
+
+impl<S: WriteStorage, H: HistoryMode> ExecutionProcessing<S, H> for GasProfiler {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for GasProfiler {}
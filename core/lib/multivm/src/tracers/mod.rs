@@ -1,5 +1,7 @@
 pub use self::{
     call_tracer::CallTracer,
+    execution_tracer::{ExecutionFrameCollector, ExecutionTracer},
+    gas_profiler::GasProfiler,
     multivm_dispatcher::TracerDispatcher,
     prestate_tracer::PrestateTracer,
     storage_invocation::StorageInvocations,
@@ -8,6 +10,8 @@ pub use self::{
 
 mod call_tracer;
 pub mod dynamic;
+mod execution_tracer;
+mod gas_profiler;
 mod multivm_dispatcher;
 pub mod old;
 mod prestate_tracer;
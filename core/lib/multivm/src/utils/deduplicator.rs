@@ -51,6 +51,13 @@ impl StorageWritesDeduplicator {
         self.modified_key_values
     }
 
+    /// Returns the current value of the slot if it was written to by the applied logs, or `None` otherwise.
+    pub fn written_value(&self, key: &StorageKey) -> Option<H256> {
+        let initial_value = *self.initial_values.get(key)?;
+        let modified_value = self.modified_key_values.get(key).map(|slot| slot.value);
+        Some(modified_value.unwrap_or(initial_value))
+    }
+
     /// Applies storage logs to the state.
     pub fn apply<'a, I: IntoIterator<Item = &'a StorageLogWithPreviousValue>>(&mut self, logs: I) {
         self.process_storage_logs(logs);
@@ -546,4 +553,44 @@ mod tests {
         deduplicator.apply(&logs);
         assert_eq!(expected, deduplicator.modified_key_values);
     }
+
+    #[test]
+    fn test_written_value() {
+        let mut deduplicator = StorageWritesDeduplicator::new();
+        let logs = [
+            storage_log(
+                U256::from(1u32),
+                U256::from(1u32),
+                U256::from(2u32),
+                false,
+                false,
+            ),
+            storage_log(
+                U256::from(2u32),
+                U256::from(3u32),
+                U256::from(4u32),
+                false,
+                false,
+            ),
+            storage_log(
+                U256::from(2u32),
+                U256::from(4u32),
+                U256::from(3u32),
+                false,
+                false,
+            ),
+        ];
+        deduplicator.apply(&logs);
+
+        assert_eq!(
+            deduplicator.written_value(&new_storage_key(0, 1)),
+            Some(H256::from_low_u64_be(2))
+        );
+        // The slot is reverted to its initial value.
+        assert_eq!(
+            deduplicator.written_value(&new_storage_key(0, 2)),
+            Some(H256::from_low_u64_be(3))
+        );
+        assert_eq!(deduplicator.written_value(&new_storage_key(0, 3)), None);
+    }
 }
@@ -0,0 +1,104 @@
+use std::sync::{Arc, Mutex};
+
+use zksync_test_contracts::TestContract;
+use zksync_types::{Address, Execute};
+
+use super::TestedLatestVm;
+use crate::{
+    interface::{
+        ExecutionFrame, ExecutionFrameSink, InspectExecutionMode, TxExecutionMode, VmInterface,
+    },
+    tracers::{ExecutionFrameCollector, ExecutionTracer},
+    versions::testonly::{ContractToDeploy, VmTesterBuilder},
+    vm_latest::{constants::BATCH_COMPUTATIONAL_GAS_LIMIT, ToTracerPointer},
+};
+
+const COUNTER_ADDRESS: Address = Address::repeat_byte(1);
+
+/// Sink retaining only far call frames.
+#[derive(Debug, Default)]
+struct FarCallSink {
+    frames: Mutex<Vec<ExecutionFrame>>,
+    total_count: Mutex<u64>,
+}
+
+impl ExecutionFrameSink for FarCallSink {
+    fn push(&self, frame: ExecutionFrame) {
+        *self.total_count.lock().unwrap() += 1;
+        if frame.far_call.is_some() {
+            self.frames.lock().unwrap().push(frame);
+        }
+    }
+}
+
+fn trace_counter_increment(sink: Arc<dyn ExecutionFrameSink>) {
+    let contract = TestContract::counter().bytecode.to_vec();
+    let mut vm = VmTesterBuilder::new()
+        .with_empty_in_memory_storage()
+        .with_rich_accounts(1)
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![ContractToDeploy::account(contract, COUNTER_ADDRESS)])
+        .build::<TestedLatestVm>();
+
+    let increment_by_6_calldata =
+        "7cf5dab00000000000000000000000000000000000000000000000000000000000000006";
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: Some(COUNTER_ADDRESS),
+            calldata: hex::decode(increment_by_6_calldata).unwrap(),
+            value: Default::default(),
+            factory_deps: vec![],
+        },
+        None,
+    );
+
+    let tracer = ExecutionTracer::new(sink).into_tracer_pointer();
+    vm.vm.push_transaction(tx);
+    let res = vm
+        .vm
+        .inspect(&mut tracer.into(), InspectExecutionMode::OneTx);
+    assert!(!res.result.is_failed(), "{:#?}", res.result);
+}
+
+#[test]
+fn tracing_execution() {
+    let sink = Arc::new(FarCallSink::default());
+    trace_counter_increment(sink.clone());
+
+    let total_count = *sink.total_count.lock().unwrap();
+    assert!(total_count > 1_000, "{total_count}");
+    let far_calls = sink.frames.lock().unwrap();
+    let counter_call = far_calls
+        .iter()
+        .find(|frame| frame.far_call.as_ref().unwrap().callee == COUNTER_ADDRESS)
+        .expect("no far call to the counter");
+    assert!(
+        counter_call.opcode.starts_with("FarCall"),
+        "{counter_call:?}"
+    );
+    let far_call = counter_call.far_call.as_ref().unwrap();
+    assert_eq!(far_call.code_address, COUNTER_ADDRESS);
+    assert!(far_call.gas > 0);
+    assert!(counter_call.gas_cost >= far_call.gas);
+}
+
+#[test]
+fn paging_through_execution_trace() {
+    let collector = Arc::new(ExecutionFrameCollector::new(0, 1_000));
+    trace_counter_increment(collector.clone());
+    let (first_page, truncated) = collector.take();
+    assert!(truncated);
+    assert_eq!(first_page.len(), 1_000);
+    for (i, frame) in first_page.iter().enumerate() {
+        assert_eq!(frame.index, i as u64);
+    }
+
+    // Execution is deterministic, so frames in the requested page must match the first run.
+    let collector = Arc::new(ExecutionFrameCollector::new(500, 10));
+    trace_counter_increment(collector.clone());
+    let (page, truncated) = collector.take();
+    assert!(truncated);
+    assert_eq!(page, first_page[500..510]);
+}
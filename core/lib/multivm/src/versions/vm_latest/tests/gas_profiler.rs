@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use once_cell::sync::OnceCell;
+use zksync_test_contracts::TestContract;
+use zksync_types::{vm::VmVersion, web3::Bytes, Address, Execute};
+
+use super::TestedLatestVm;
+use crate::{
+    interface::{InspectExecutionMode, TxExecutionMode, VmInterface},
+    tracers::GasProfiler,
+    versions::testonly::{ContractToDeploy, VmTesterBuilder},
+    vm_latest::{constants::BATCH_COMPUTATIONAL_GAS_LIMIT, ToTracerPointer},
+};
+
+#[test]
+fn profiling_gas() {
+    let contract = TestContract::counter().bytecode.to_vec();
+    let address = Address::repeat_byte(1);
+    let mut vm = VmTesterBuilder::new()
+        .with_empty_in_memory_storage()
+        .with_rich_accounts(1)
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![ContractToDeploy::account(contract, address)])
+        .build::<TestedLatestVm>();
+
+    let increment_by_6_calldata =
+        "7cf5dab00000000000000000000000000000000000000000000000000000000000000006";
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: Some(address),
+            calldata: hex::decode(increment_by_6_calldata).unwrap(),
+            value: Default::default(),
+            factory_deps: vec![],
+        },
+        None,
+    );
+
+    let result = Arc::new(OnceCell::new());
+    let profiler = GasProfiler::new(result.clone(), VmVersion::Vm1_5_0IncreasedBootloaderMemory)
+        .into_tracer_pointer();
+    vm.vm.push_transaction(tx);
+    let res = vm
+        .vm
+        .inspect(&mut profiler.into(), InspectExecutionMode::OneTx);
+    assert!(!res.result.is_failed(), "{:#?}", res.result);
+
+    let profile = result.get().unwrap();
+    let counter_usage = profile
+        .iter()
+        .find(|usage| usage.contract == address)
+        .expect("counter is not profiled");
+    assert_eq!(
+        counter_usage.selector,
+        Some(Bytes(vec![0x7c, 0xf5, 0xda, 0xb0]))
+    );
+    assert_eq!(counter_usage.calls, 1);
+    assert!(counter_usage.self_gas_used > 0);
+    assert!(counter_usage.self_gas_used <= counter_usage.gas_used);
+    // The counter value is written by the counter itself.
+    assert!(counter_usage.pubdata_bytes > 0);
+
+    // Account validation is profiled as well, since it's a part of transaction processing.
+    let account_address = vm.rich_accounts[0].address;
+    assert!(profile
+        .iter()
+        .any(|usage| usage.contract == account_address));
+
+    // Profiled far calls are made by the bootloader, so they cannot use more gas than the VM.
+    let attributed_gas: u64 = profile.iter().map(|usage| usage.self_gas_used).sum();
+    assert!(attributed_gas <= res.statistics.gas_used);
+}
@@ -50,7 +50,9 @@ mod circuits;
 mod code_oracle;
 mod constants;
 mod evm_emulator;
+mod execution_tracer;
 mod gas_limit;
+mod gas_profiler;
 mod get_used_contracts;
 mod is_write_initial;
 mod l1_messenger;
@@ -1,8 +1,8 @@
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use anyhow::Context as _;
 use tokio::sync::OnceCell;
-use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode};
+use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode, S3Secrets};
 
 use crate::{
     file::FileBackedObjectStore,
@@ -10,6 +10,8 @@ use crate::{
     mirror::MirroringObjectStore,
     raw::{ObjectStore, ObjectStoreError},
     retries::StoreWithRetries,
+    s3::S3Store,
+    tiered::{TierLifecycle, TieredObjectStore},
 };
 
 /// Factory of [`ObjectStore`]s that caches the store instance once it's created. Used mainly for legacy reasons.
@@ -19,7 +21,9 @@ use crate::{
 #[derive(Debug)]
 pub struct ObjectStoreFactory {
     config: ObjectStoreConfig,
+    s3_secrets: Option<S3Secrets>,
     store: OnceCell<Arc<dyn ObjectStore>>,
+    tiered_store: OnceCell<Arc<TieredObjectStore>>,
 }
 
 impl ObjectStoreFactory {
@@ -27,10 +31,19 @@ impl ObjectStoreFactory {
     pub fn new(config: ObjectStoreConfig) -> Self {
         Self {
             config,
+            s3_secrets: None,
             store: OnceCell::new(),
+            tiered_store: OnceCell::new(),
         }
     }
 
+    /// Sets credentials used by S3 stores. S3 stores cannot be created without credentials.
+    #[must_use]
+    pub fn with_s3_secrets(mut self, secrets: Option<S3Secrets>) -> Self {
+        self.s3_secrets = secrets;
+        self
+    }
+
     /// Creates an [`ObjectStore`] or returns a cached store if one was created previously.
     ///
     /// # Errors
@@ -39,7 +52,10 @@ impl ObjectStoreFactory {
     pub async fn create_store(&self) -> anyhow::Result<Arc<dyn ObjectStore>> {
         self.store
             .get_or_try_init(|| async {
-                Self::create_from_config(&self.config)
+                if let Some(store) = self.create_tiered_store().await? {
+                    return Ok(store as Arc<dyn ObjectStore>);
+                }
+                self.create_from_config(&self.config)
                     .await
                     .with_context(|| {
                         format!(
@@ -52,12 +68,75 @@ impl ObjectStoreFactory {
             .cloned()
     }
 
+    /// Creates a [`TieredObjectStore`] or returns a cached store if one was created previously. Returns `None`
+    /// if the configuration doesn't specify tiers. The returned store is the same as the one returned
+    /// by [`Self::create_store()`]; it can be used to run lifecycle management.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if store initialization fails (e.g., because of incorrect configuration).
+    pub async fn create_tiered_store(&self) -> anyhow::Result<Option<Arc<TieredObjectStore>>> {
+        if self.config.tiers.is_empty() {
+            return Ok(None);
+        }
+
+        let store = self
+            .tiered_store
+            .get_or_try_init(|| async {
+                let default_store = self
+                    .create_from_config(&self.config)
+                    .await
+                    .context("failed creating default store for tiered object store")?;
+                let mut store = TieredObjectStore::new(default_store);
+                let mut artifact_classes = HashSet::new();
+                for tier in &self.config.tiers {
+                    let class = tier.artifact_class;
+                    anyhow::ensure!(
+                        artifact_classes.insert(class),
+                        "multiple object store tiers for {class:?} artifacts"
+                    );
+                    let nested_tiers = tier.store.tiers.len()
+                        + tier
+                            .cold_store
+                            .as_ref()
+                            .map_or(0, |config| config.tiers.len());
+                    anyhow::ensure!(
+                        nested_tiers == 0,
+                        "object store tier for {class:?} artifacts has nested tiers"
+                    );
+
+                    let tier_store = self
+                        .create_from_config(&tier.store)
+                        .await
+                        .with_context(|| format!("failed creating store for {class:?} tier"))?;
+                    let cold_store = if let Some(config) = &tier.cold_store {
+                        let store = self.create_from_config(config).await.with_context(|| {
+                            format!("failed creating cold store for {class:?} tier")
+                        })?;
+                        Some(store)
+                    } else {
+                        None
+                    };
+                    let lifecycle = TierLifecycle {
+                        move_to_cold_after: tier.move_to_cold_after(),
+                        expire_after: tier.expire_after(),
+                        min_cold_object_size: tier.min_cold_object_size_bytes.unwrap_or(0),
+                    };
+                    store = store.with_tier(class, tier_store, cold_store, lifecycle);
+                }
+                anyhow::Ok(Arc::new(store))
+            })
+            .await?;
+        Ok(Some(store.clone()))
+    }
+
     /// Creates an [`ObjectStore`] based on the provided `config`.
     ///
     /// # Errors
     ///
     /// Returns an error if store initialization fails (e.g., because of incorrect configuration).
     async fn create_from_config(
+        &self,
         config: &ObjectStoreConfig,
     ) -> Result<Arc<dyn ObjectStore>, ObjectStoreError> {
         tracing::trace!("Initializing object store with configuration {config:?}");
@@ -97,6 +176,44 @@ impl ObjectStoreFactory {
                 .await?;
                 Self::wrap_mirroring(store, config.local_mirror_path.as_ref()).await
             }
+            ObjectStoreMode::S3 {
+                endpoint,
+                region,
+                bucket_name,
+                addressing_style,
+                server_side_encryption,
+                sse_kms_key_id,
+                multipart_part_size_bytes,
+            } => {
+                let secrets =
+                    self.s3_secrets
+                        .as_ref()
+                        .ok_or_else(|| ObjectStoreError::Initialization {
+                            source: "S3 object store requires credentials to be set in S3 secrets"
+                                .into(),
+                            is_retriable: false,
+                        })?;
+                let store = StoreWithRetries::try_new(config.max_retries, || async {
+                    let mut builder = S3Store::builder(
+                        endpoint,
+                        region.clone(),
+                        bucket_name,
+                        *addressing_style,
+                        secrets.clone(),
+                    );
+                    if let Some(encryption) = server_side_encryption {
+                        builder = builder
+                            .with_server_side_encryption(*encryption, sse_kms_key_id.clone());
+                    }
+                    if let Some(part_size) = multipart_part_size_bytes {
+                        let part_size = usize::try_from(*part_size).unwrap_or(usize::MAX);
+                        builder = builder.with_multipart_part_size(part_size);
+                    }
+                    builder.build()
+                })
+                .await?;
+                Self::wrap_mirroring(store, config.local_mirror_path.as_ref()).await
+            }
 
             ObjectStoreMode::FileBacked {
                 file_backed_base_path,
@@ -3,7 +3,7 @@ use std::fmt::Debug;
 use async_trait::async_trait;
 use tokio::{fs, io};
 
-use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+use crate::raw::{Bucket, ObjectMetadata, ObjectStore, ObjectStoreError};
 
 impl From<io::Error> for ObjectStoreError {
     fn from(err: io::Error) -> Self {
@@ -41,8 +41,10 @@ impl FileBackedObjectStore {
             Bucket::NodeAggregationWitnessJobsFri,
             Bucket::SchedulerWitnessJobsFri,
             Bucket::ProofsFri,
+            Bucket::ProofUploads,
             Bucket::StorageSnapshot,
             Bucket::VmDumps,
+            Bucket::SetupData,
         ] {
             let bucket_path = format!("{base_dir}/{bucket}");
             fs::create_dir_all(&bucket_path).await?;
@@ -77,6 +79,41 @@ impl ObjectStore for FileBackedObjectStore {
         fs::remove_file(filename).await.map_err(From::from)
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<ObjectMetadata>, ObjectStoreError> {
+        let mut entries = match fs::read_dir(self.storage_prefix_raw(bucket)).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err.into()),
+        };
+        let mut objects = vec![];
+        while let Some(entry) = entries.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            if !metadata.is_file() {
+                continue;
+            }
+            let Ok(key) = entry.file_name().into_string() else {
+                continue;
+            };
+            if start_after.is_some_and(|start_after| key.as_str() <= start_after) {
+                continue;
+            }
+            objects.push(ObjectMetadata {
+                key,
+                last_modified: metadata.modified().ok(),
+                size: metadata.len(),
+            });
+        }
+        // The directory is read in an unspecified order, so objects need to be sorted before applying the limit.
+        objects.sort_unstable_by(|a, b| a.key.cmp(&b.key));
+        objects.truncate(limit);
+        Ok(objects)
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         format!("{}/{}", self.base_dir, bucket)
     }
@@ -117,6 +154,47 @@ mod test {
             .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_list() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.into_path().into_os_string().into_string().unwrap();
+        let object_store = FileBackedObjectStore::new(path).await.unwrap();
+        for key in ["first.bin", "second.bin"] {
+            object_store
+                .put_raw(Bucket::ProverJobs, key, vec![0, 1])
+                .await
+                .unwrap();
+        }
+
+        let objects = object_store
+            .list_raw(Bucket::ProverJobs, None, 10)
+            .await
+            .unwrap();
+        let keys: Vec<_> = objects.iter().map(|object| object.key.as_str()).collect();
+        assert_eq!(keys, ["first.bin", "second.bin"]);
+        assert!(objects.iter().all(|object| object.last_modified.is_some()));
+        assert!(objects.iter().all(|object| object.size == 2));
+
+        let objects = object_store
+            .list_raw(Bucket::ProverJobs, None, 1)
+            .await
+            .unwrap();
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0].key, "first.bin");
+        let objects = object_store
+            .list_raw(Bucket::ProverJobs, Some("first.bin"), 10)
+            .await
+            .unwrap();
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0].key, "second.bin");
+
+        assert!(object_store
+            .list_raw(Bucket::ProofsFri, None, 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
     #[tokio::test]
     async fn test_remove() {
         let dir = TempDir::new().unwrap();
@@ -1,6 +1,10 @@
 //! GCS-based [`ObjectStore`] implementation.
 
-use std::{error::Error as StdError, fmt, io};
+use std::{
+    error::Error as StdError,
+    fmt, io,
+    time::{Duration, SystemTime},
+};
 
 use async_trait::async_trait;
 use google_cloud_auth::{credentials::CredentialsFile, error::Error as AuthError};
@@ -11,6 +15,7 @@ use google_cloud_storage::{
             delete::DeleteObjectRequest,
             download::Range,
             get::GetObjectRequest,
+            list::ListObjectsRequest,
             upload::{Media, UploadObjectRequest, UploadType},
         },
         Error as HttpError,
@@ -18,7 +23,7 @@ use google_cloud_storage::{
 };
 use http::StatusCode;
 
-use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+use crate::raw::{Bucket, ObjectMetadata, ObjectStore, ObjectStoreError};
 
 /// [`ObjectStore`] implementation based on GCS.
 pub struct GoogleCloudStore {
@@ -96,7 +101,7 @@ impl From<AuthError> for ObjectStoreError {
     }
 }
 
-fn is_retriable_http_error(err: &reqwest::Error) -> bool {
+pub(crate) fn is_retriable_http_error(err: &reqwest::Error) -> bool {
     err.is_timeout()
         || err.is_connect()
         // Not all request errors are logically transient, but a significant part of them are (e.g.,
@@ -215,6 +220,62 @@ impl ObjectStore for GoogleCloudStore {
         Ok(())
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<ObjectMetadata>, ObjectStoreError> {
+        /// Maximum number of objects returned by GCS in a single page.
+        const MAX_PAGE_SIZE: usize = 1_000;
+
+        let prefix = Self::filename(bucket.as_str(), "");
+        tracing::trace!(
+            "Listing objects in GCS with prefix {prefix} after {start_after:?} from bucket {}",
+            self.bucket_prefix
+        );
+        // `start_offset` is inclusive, so the `start_after` object itself is filtered out below.
+        let start_offset = start_after.map(|key| Self::filename(bucket.as_str(), key));
+
+        let mut objects = vec![];
+        let mut page_token = None;
+        while objects.len() < limit {
+            let page_size = (limit - objects.len() + 1).min(MAX_PAGE_SIZE);
+            let request = ListObjectsRequest {
+                bucket: self.bucket_prefix.clone(),
+                prefix: Some(prefix.clone()),
+                start_offset: start_offset.clone(),
+                max_results: Some(page_size as i32),
+                page_token,
+                ..ListObjectsRequest::default()
+            };
+            let response = self.client.list_objects(&request).await?;
+            let items = response.items.unwrap_or_default().into_iter();
+            objects.extend(items.filter_map(|object| {
+                let key = object.name.strip_prefix(&prefix)?.to_owned();
+                if start_after == Some(key.as_str()) {
+                    return None;
+                }
+                let last_modified = object.updated.and_then(|updated| {
+                    let timestamp = u64::try_from(updated.unix_timestamp()).ok()?;
+                    Some(SystemTime::UNIX_EPOCH + Duration::from_secs(timestamp))
+                });
+                Some(ObjectMetadata {
+                    key,
+                    last_modified,
+                    size: u64::try_from(object.size).unwrap_or(0),
+                })
+            }));
+
+            page_token = response.next_page_token;
+            if page_token.is_none() {
+                break;
+            }
+        }
+        objects.truncate(limit);
+        Ok(objects)
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         format!(
             "https://storage.googleapis.com/{}/{}",
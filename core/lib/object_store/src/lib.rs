@@ -3,8 +3,12 @@
 //!
 //! - [File-backed store](FileBackedObjectStore) saving blobs as separate files in the local filesystem
 //! - [GCS-based store](GoogleCloudStore)
+//! - [Store for S3-compatible APIs](S3Store) (AWS S3, MinIO, Cloudflare R2, etc.)
 //! - [Mock in-memory store](MockObjectStore)
 //!
+//! Stores can be combined into a [tiered store](TieredObjectStore) routing objects to different stores
+//! depending on their [artifact class](ArtifactClass), with optional lifecycle management.
+//!
 //! Normally, these implementations are not used directly. Instead, a store trait object (`Arc<dyn ObjectStore>`)
 //! can be constructed using an [`ObjectStoreFactory`] based on the configuration.
 //! This trait object is what should be used for dependency injection.
@@ -32,6 +36,8 @@ mod mock;
 mod objects;
 mod raw;
 mod retries;
+mod s3;
+mod tiered;
 
 // Re-export `bincode` crate so that client binaries can conveniently use it.
 pub use bincode;
@@ -47,5 +53,7 @@ pub use self::{
     gcs::{GoogleCloudStore, GoogleCloudStoreAuthMode},
     mock::MockObjectStore,
     objects::StoredObject,
-    raw::{Bucket, ObjectStore, ObjectStoreError},
+    raw::{Bucket, ObjectMetadata, ObjectStore, ObjectStoreError},
+    s3::{S3AddressingStyle, S3Secrets, S3ServerSideEncryption, S3Store, S3StoreBuilder},
+    tiered::{ArtifactClass, TierLifecycle, TierLifecycleStats, TieredObjectStore},
 };
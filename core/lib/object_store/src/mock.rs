@@ -1,13 +1,13 @@
 //! Mock implementation of [`ObjectStore`].
 
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::SystemTime};
 
 use async_trait::async_trait;
 use tokio::sync::Mutex;
 
-use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+use crate::raw::{Bucket, ObjectMetadata, ObjectStore, ObjectStoreError};
 
-type BucketMap = HashMap<String, Vec<u8>>;
+type BucketMap = HashMap<String, (Vec<u8>, SystemTime)>;
 
 /// Mock [`ObjectStore`] implementation.
 #[derive(Debug, Default)]
@@ -27,7 +27,7 @@ impl ObjectStore for MockObjectStore {
     async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
         let lock = self.inner.lock().await;
         let maybe_bytes = lock.get(&bucket).and_then(|bucket_map| bucket_map.get(key));
-        maybe_bytes.cloned().ok_or_else(|| {
+        maybe_bytes.map(|(bytes, _)| bytes.clone()).ok_or_else(|| {
             let error_message = format!("missing key: {key} in bucket {bucket}");
             ObjectStoreError::KeyNotFound(error_message.into())
         })
@@ -41,7 +41,7 @@ impl ObjectStore for MockObjectStore {
     ) -> Result<(), ObjectStoreError> {
         let mut lock = self.inner.lock().await;
         let bucket_map = lock.entry(bucket).or_default();
-        bucket_map.insert(key.to_owned(), value);
+        bucket_map.insert(key.to_owned(), (value, SystemTime::now()));
         Ok(())
     }
 
@@ -54,6 +54,30 @@ impl ObjectStore for MockObjectStore {
         Ok(())
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<ObjectMetadata>, ObjectStoreError> {
+        let lock = self.inner.lock().await;
+        let Some(bucket_map) = lock.get(&bucket) else {
+            return Ok(vec![]);
+        };
+        let mut objects: Vec<_> = bucket_map
+            .iter()
+            .filter(|(key, _)| start_after.map_or(true, |start_after| key.as_str() > start_after))
+            .map(|(key, (value, last_modified))| ObjectMetadata {
+                key: key.clone(),
+                last_modified: Some(*last_modified),
+                size: value.len() as u64,
+            })
+            .collect();
+        objects.sort_unstable_by(|a, b| a.key.cmp(&b.key));
+        objects.truncate(limit);
+        Ok(objects)
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         bucket.to_string()
     }
@@ -1,6 +1,7 @@
-use std::{error, fmt};
+use std::{error, fmt, time::SystemTime};
 
 use async_trait::async_trait;
+use zksync_config::configs::object_store::ArtifactClass;
 
 /// Bucket for [`ObjectStore`] in which objects can be placed.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -17,9 +18,11 @@ pub enum Bucket {
     SchedulerWitnessJobsFri,
     ProofsFri,
     ProofsTee,
+    ProofUploads,
     StorageSnapshot,
     DataAvailability,
     VmDumps,
+    SetupData,
 }
 
 impl Bucket {
@@ -36,11 +39,67 @@ impl Bucket {
             Self::SchedulerWitnessJobsFri => "scheduler_witness_jobs_fri",
             Self::ProofsFri => "proofs_fri",
             Self::ProofsTee => "proofs_tee",
+            Self::ProofUploads => "proof_uploads",
             Self::StorageSnapshot => "storage_logs_snapshots",
             Self::DataAvailability => "data_availability",
             Self::VmDumps => "vm_dumps",
+            Self::SetupData => "setup_data",
         }
     }
+
+    /// Returns the artifact class used to route objects in this bucket to a store tier, or `None`
+    /// if the bucket is not covered by tiering.
+    pub(crate) fn artifact_class(self) -> Option<ArtifactClass> {
+        match self {
+            Self::ProofsFri | Self::ProofsTee => Some(ArtifactClass::Proofs),
+            Self::ProverJobs
+            | Self::WitnessInput
+            | Self::LeafAggregationWitnessJobs
+            | Self::NodeAggregationWitnessJobs
+            | Self::SchedulerWitnessJobs
+            | Self::ProverJobsFri
+            | Self::LeafAggregationWitnessJobsFri
+            | Self::NodeAggregationWitnessJobsFri
+            | Self::SchedulerWitnessJobsFri => Some(ArtifactClass::WitnessInputs),
+            Self::StorageSnapshot => Some(ArtifactClass::Snapshots),
+            Self::ProofUploads | Self::DataAvailability | Self::VmDumps | Self::SetupData => None,
+        }
+    }
+
+    /// Returns all buckets with the specified artifact class.
+    pub(crate) fn with_artifact_class(class: ArtifactClass) -> impl Iterator<Item = Self> {
+        const ALL: [Bucket; 16] = [
+            Bucket::ProverJobs,
+            Bucket::WitnessInput,
+            Bucket::LeafAggregationWitnessJobs,
+            Bucket::NodeAggregationWitnessJobs,
+            Bucket::SchedulerWitnessJobs,
+            Bucket::ProverJobsFri,
+            Bucket::LeafAggregationWitnessJobsFri,
+            Bucket::NodeAggregationWitnessJobsFri,
+            Bucket::SchedulerWitnessJobsFri,
+            Bucket::ProofsFri,
+            Bucket::ProofsTee,
+            Bucket::ProofUploads,
+            Bucket::StorageSnapshot,
+            Bucket::DataAvailability,
+            Bucket::VmDumps,
+            Bucket::SetupData,
+        ];
+        ALL.into_iter()
+            .filter(move |bucket| bucket.artifact_class() == Some(class))
+    }
+}
+
+/// Metadata of a stored object returned by [`ObjectStore::list_raw()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectMetadata {
+    /// Object key within the bucket.
+    pub key: String,
+    /// Last modification time of the object, if reported by the store.
+    pub last_modified: Option<SystemTime>,
+    /// Object size in bytes.
+    pub size: u64,
 }
 
 impl fmt::Display for Bucket {
@@ -157,5 +216,23 @@ pub trait ObjectStore: 'static + fmt::Debug + Send + Sync {
     /// Returns an error if removal fails.
     async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError>;
 
+    /// Lists up to `limit` objects in the given bucket ordered by key, starting after the `start_after` key (exclusive)
+    /// if it's specified. Used for lifecycle management; stores not supporting listing return an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if listing fails or is not supported by the store.
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<ObjectMetadata>, ObjectStoreError> {
+        Err(ObjectStoreError::Other {
+            is_retriable: false,
+            source: format!("listing bucket {bucket} is not supported by {self:?}").into(),
+        })
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String;
 }
@@ -5,7 +5,7 @@ use rand::Rng;
 
 use crate::{
     metrics::OBJECT_STORE_METRICS,
-    raw::{Bucket, ObjectStore, ObjectStoreError},
+    raw::{Bucket, ObjectMetadata, ObjectStore, ObjectStoreError},
 };
 
 /// Information about request added to logs.
@@ -16,6 +16,7 @@ enum Request<'a> {
     Get(Bucket, &'a str),
     Put(Bucket, &'a str),
     Remove(Bucket, &'a str),
+    List(Bucket),
 }
 
 impl Request<'_> {
@@ -126,6 +127,19 @@ impl<S: ObjectStore> ObjectStore for StoreWithRetries<S> {
             .await
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<ObjectMetadata>, ObjectStoreError> {
+        Request::List(bucket)
+            .retry(&self.inner, self.max_retries, || {
+                self.inner.list_raw(bucket, start_after, limit)
+            })
+            .await
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         self.inner.storage_prefix_raw(bucket)
     }
@@ -0,0 +1,680 @@
+//! Tiered object store routing objects to different stores depending on their artifact class.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+
+use async_trait::async_trait;
+pub use zksync_config::configs::object_store::ArtifactClass;
+
+use crate::raw::{Bucket, ObjectMetadata, ObjectStore, ObjectStoreError};
+
+/// Maximum number of objects requested from a store in a single listing call.
+const LIST_PAGE_SIZE: usize = 1_000;
+/// Default maximum number of objects processed per store and bucket in a single lifecycle run.
+const DEFAULT_MAX_OBJECTS_PER_RUN: usize = 10_000;
+
+/// Lifecycle settings for a [`TieredObjectStore`] tier.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TierLifecycle {
+    /// Age after which objects are moved from the main tier store to the cold store.
+    pub move_to_cold_after: Option<Duration>,
+    /// Age after which objects are removed from the tier.
+    pub expire_after: Option<Duration>,
+    /// Minimum size of objects moved to the cold store. Smaller objects stay in the main tier store until they expire.
+    pub min_cold_object_size: u64,
+}
+
+impl TierLifecycle {
+    fn should_move_to_cold(&self, object: &ObjectMetadata, now: SystemTime) -> bool {
+        let Some(move_after) = self.move_to_cold_after else {
+            return false;
+        };
+        object.size >= self.min_cold_object_size && is_older(object, now.checked_sub(move_after))
+    }
+}
+
+#[derive(Debug)]
+struct StoreTier {
+    store: Arc<dyn ObjectStore>,
+    cold_store: Option<Arc<dyn ObjectStore>>,
+    lifecycle: TierLifecycle,
+}
+
+/// Kind of store processed by lifecycle management; used to track listing progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum StoreKind {
+    Default,
+    Main,
+    Cold,
+}
+
+/// Statistics for a single [`TieredObjectStore::run_lifecycle()`] run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TierLifecycleStats {
+    /// Number of objects moved from the default store to tier stores.
+    pub migrated_to_tier: usize,
+    /// Number of objects moved to cold stores.
+    pub moved_to_cold: usize,
+    /// Number of expired objects removed from tier stores.
+    pub expired: usize,
+}
+
+/// [`ObjectStore`] routing objects to tier stores based on the [`ArtifactClass`] of their bucket.
+///
+/// New objects are always put into the main store of the tier (or the default store if the bucket is not covered
+/// by a tier). Reads fall back to the cold store and then to the default store, so that objects remain accessible
+/// while they are being migrated.
+#[derive(Debug)]
+pub struct TieredObjectStore {
+    default_store: Arc<dyn ObjectStore>,
+    tiers: HashMap<ArtifactClass, StoreTier>,
+    max_objects_per_run: usize,
+    /// Key of the last processed object for each store and bucket. Lifecycle runs continue listing after this key,
+    /// so that each run only lists a bounded number of objects.
+    listing_cursors: Mutex<HashMap<(StoreKind, Bucket), String>>,
+}
+
+impl TieredObjectStore {
+    /// Creates a store without tiers that delegates all operations to `default_store`.
+    pub fn new(default_store: Arc<dyn ObjectStore>) -> Self {
+        Self {
+            default_store,
+            tiers: HashMap::new(),
+            max_objects_per_run: DEFAULT_MAX_OBJECTS_PER_RUN,
+            listing_cursors: Mutex::default(),
+        }
+    }
+
+    /// Adds a tier for the specified artifact class, replacing the existing tier if any.
+    #[must_use]
+    pub fn with_tier(
+        mut self,
+        class: ArtifactClass,
+        store: Arc<dyn ObjectStore>,
+        cold_store: Option<Arc<dyn ObjectStore>>,
+        lifecycle: TierLifecycle,
+    ) -> Self {
+        if lifecycle.move_to_cold_after.is_some() && cold_store.is_none() {
+            tracing::warn!(
+                "Tier for {class:?} artifacts specifies moving to cold store, but has no cold store; objects won't be moved"
+            );
+        }
+        let tier = StoreTier {
+            store,
+            cold_store,
+            lifecycle,
+        };
+        self.tiers.insert(class, tier);
+        self
+    }
+
+    /// Sets the maximum number of objects processed per store and bucket in a single [lifecycle run](Self::run_lifecycle()).
+    /// The default value is 10,000.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_objects` is zero.
+    #[must_use]
+    pub fn with_max_objects_per_lifecycle_run(mut self, max_objects: usize) -> Self {
+        assert!(
+            max_objects > 0,
+            "max objects per lifecycle run must be positive"
+        );
+        self.max_objects_per_run = max_objects;
+        self
+    }
+
+    fn tier(&self, bucket: Bucket) -> Option<&StoreTier> {
+        self.tiers.get(&bucket.artifact_class()?)
+    }
+
+    fn main_store(&self, bucket: Bucket) -> &dyn ObjectStore {
+        self.tier(bucket)
+            .map_or(self.default_store.as_ref(), |tier| tier.store.as_ref())
+    }
+
+    /// Stores to check (in order) if an object is not found in the main store of the tier.
+    fn fallback_stores<'a>(
+        &'a self,
+        tier: &'a StoreTier,
+    ) -> impl Iterator<Item = &'a dyn ObjectStore> + 'a {
+        let cold_store = tier.cold_store.as_deref();
+        cold_store.into_iter().chain([self.default_store.as_ref()])
+    }
+
+    /// Runs a single iteration of lifecycle management for all tiers:
+    ///
+    /// 1. Moves objects from the default store to tier stores (needed after a tier is introduced).
+    /// 2. Removes expired objects.
+    /// 3. Moves sufficiently old and large objects to cold stores.
+    ///
+    /// Objects are listed incrementally: each run processes at most the [configured](Self::with_max_objects_per_lifecycle_run())
+    /// number of objects per store and bucket, continuing after the last object processed by the previous run
+    /// and wrapping around once the end of the bucket is reached.
+    ///
+    /// Object age is determined based on its last modification time; objects without it are skipped.
+    /// Objects in cold stores expire after `expire_after - move_to_cold_after` since they were moved.
+    ///
+    /// # Errors
+    ///
+    /// Propagates object store errors. Since objects are copied before being removed, an interrupted run
+    /// never loses objects and can be safely repeated.
+    pub async fn run_lifecycle(
+        &self,
+        now: SystemTime,
+    ) -> Result<TierLifecycleStats, ObjectStoreError> {
+        let mut stats = TierLifecycleStats::default();
+        for (&class, tier) in &self.tiers {
+            for bucket in Bucket::with_artifact_class(class) {
+                stats.migrated_to_tier += self.migrate_to_tier(tier, bucket).await?;
+                let (expired, moved_to_cold) = self.process_main_store(tier, bucket, now).await?;
+                stats.expired += expired;
+                stats.moved_to_cold += moved_to_cold;
+                stats.expired += self.remove_expired_cold(tier, bucket, now).await?;
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Lists the next objects in `store` to be processed by lifecycle management. Returns the listed objects
+    /// and the cursor to be saved via [`Self::save_cursor()`] once the objects are processed.
+    async fn list_next_objects(
+        &self,
+        store: &dyn ObjectStore,
+        kind: StoreKind,
+        bucket: Bucket,
+    ) -> Result<(Vec<ObjectMetadata>, Option<String>), ObjectStoreError> {
+        let mut cursor = self
+            .listing_cursors
+            .lock()
+            .expect("listing cursors are poisoned")
+            .get(&(kind, bucket))
+            .cloned();
+        let mut objects = vec![];
+        while objects.len() < self.max_objects_per_run {
+            let page_size = LIST_PAGE_SIZE.min(self.max_objects_per_run - objects.len());
+            let page = store.list_raw(bucket, cursor.as_deref(), page_size).await?;
+            let is_last_page = page.len() < page_size;
+            if let Some(last_object) = page.last() {
+                cursor = Some(last_object.key.clone());
+            }
+            objects.extend(page);
+            if is_last_page {
+                // Start from the beginning of the bucket during the next run.
+                cursor = None;
+                break;
+            }
+        }
+        Ok((objects, cursor))
+    }
+
+    fn save_cursor(&self, kind: StoreKind, bucket: Bucket, cursor: Option<String>) {
+        let mut cursors = self
+            .listing_cursors
+            .lock()
+            .expect("listing cursors are poisoned");
+        if let Some(cursor) = cursor {
+            cursors.insert((kind, bucket), cursor);
+        } else {
+            cursors.remove(&(kind, bucket));
+        }
+    }
+
+    async fn migrate_to_tier(
+        &self,
+        tier: &StoreTier,
+        bucket: Bucket,
+    ) -> Result<usize, ObjectStoreError> {
+        let default_store = self.default_store.as_ref();
+        if default_store.storage_prefix_raw(bucket) == tier.store.storage_prefix_raw(bucket) {
+            return Ok(0); // The tier uses the same storage as the default store
+        }
+
+        let (objects, cursor) = self
+            .list_next_objects(default_store, StoreKind::Default, bucket)
+            .await?;
+        for object in &objects {
+            move_object(default_store, tier.store.as_ref(), bucket, &object.key).await?;
+        }
+        self.save_cursor(StoreKind::Default, bucket, cursor);
+        if !objects.is_empty() {
+            tracing::info!(
+                "Migrated {} objects in bucket {bucket} from default store to tier store",
+                objects.len()
+            );
+        }
+        Ok(objects.len())
+    }
+
+    /// Removes expired objects from the main tier store and moves objects to the cold store.
+    /// Returns the number of removed and moved objects.
+    async fn process_main_store(
+        &self,
+        tier: &StoreTier,
+        bucket: Bucket,
+        now: SystemTime,
+    ) -> Result<(usize, usize), ObjectStoreError> {
+        let lifecycle = &tier.lifecycle;
+        let cold_store = tier.cold_store.as_deref();
+        let can_move_to_cold = cold_store.is_some() && lifecycle.move_to_cold_after.is_some();
+        if lifecycle.expire_after.is_none() && !can_move_to_cold {
+            return Ok((0, 0));
+        }
+
+        let store = tier.store.as_ref();
+        let (objects, cursor) = self
+            .list_next_objects(store, StoreKind::Main, bucket)
+            .await?;
+        let expire_cutoff = lifecycle
+            .expire_after
+            .and_then(|expire_after| now.checked_sub(expire_after));
+        let (mut removed_count, mut moved_count) = (0, 0);
+        for object in &objects {
+            if lifecycle.expire_after.is_some() && is_older(object, expire_cutoff) {
+                store.remove_raw(bucket, &object.key).await?;
+                removed_count += 1;
+            } else if let Some(cold_store) = cold_store {
+                if lifecycle.should_move_to_cold(object, now) {
+                    move_object(store, cold_store, bucket, &object.key).await?;
+                    moved_count += 1;
+                }
+            }
+        }
+        self.save_cursor(StoreKind::Main, bucket, cursor);
+
+        if removed_count > 0 {
+            tracing::info!("Removed {removed_count} expired objects in bucket {bucket}");
+        }
+        if moved_count > 0 {
+            tracing::info!("Moved {moved_count} objects in bucket {bucket} to cold store");
+        }
+        Ok((removed_count, moved_count))
+    }
+
+    async fn remove_expired_cold(
+        &self,
+        tier: &StoreTier,
+        bucket: Bucket,
+        now: SystemTime,
+    ) -> Result<usize, ObjectStoreError> {
+        let (Some(cold_store), Some(expire_after)) =
+            (&tier.cold_store, tier.lifecycle.expire_after)
+        else {
+            return Ok(0);
+        };
+        // Cold objects are re-created when moved, so their age is counted from the move.
+        let time_in_main_store = tier.lifecycle.move_to_cold_after.unwrap_or_default();
+        let cutoff = now.checked_sub(expire_after.saturating_sub(time_in_main_store));
+
+        let (objects, cursor) = self
+            .list_next_objects(cold_store.as_ref(), StoreKind::Cold, bucket)
+            .await?;
+        let mut removed_count = 0;
+        for object in objects.iter().filter(|object| is_older(object, cutoff)) {
+            cold_store.remove_raw(bucket, &object.key).await?;
+            removed_count += 1;
+        }
+        self.save_cursor(StoreKind::Cold, bucket, cursor);
+        if removed_count > 0 {
+            tracing::info!(
+                "Removed {removed_count} expired objects in bucket {bucket} from cold store"
+            );
+        }
+        Ok(removed_count)
+    }
+}
+
+fn is_older(object: &ObjectMetadata, cutoff: Option<SystemTime>) -> bool {
+    match (object.last_modified, cutoff) {
+        (Some(last_modified), Some(cutoff)) => last_modified < cutoff,
+        _ => false,
+    }
+}
+
+/// Moves an object between stores. The object is removed from the source store only after it's been stored in the target one.
+async fn move_object(
+    from: &dyn ObjectStore,
+    to: &dyn ObjectStore,
+    bucket: Bucket,
+    key: &str,
+) -> Result<(), ObjectStoreError> {
+    let value = match from.get_raw(bucket, key).await {
+        Ok(value) => value,
+        // The object may have been removed concurrently.
+        Err(ObjectStoreError::KeyNotFound(_)) => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    to.put_raw(bucket, key, value).await?;
+    from.remove_raw(bucket, key).await
+}
+
+#[async_trait]
+impl ObjectStore for TieredObjectStore {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let Some(tier) = self.tier(bucket) else {
+            return self.default_store.get_raw(bucket, key).await;
+        };
+
+        let mut result = tier.store.get_raw(bucket, key).await;
+        for store in self.fallback_stores(tier) {
+            if !matches!(result, Err(ObjectStoreError::KeyNotFound(_))) {
+                break;
+            }
+            result = store.get_raw(bucket, key).await;
+        }
+        result
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        self.main_store(bucket).put_raw(bucket, key, value).await
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        let Some(tier) = self.tier(bucket) else {
+            return self.default_store.remove_raw(bucket, key).await;
+        };
+
+        // The object may reside in any of the stores, so we remove it from all of them.
+        let stores = [tier.store.as_ref()]
+            .into_iter()
+            .chain(self.fallback_stores(tier));
+        for store in stores {
+            match store.remove_raw(bucket, key).await {
+                Ok(()) | Err(ObjectStoreError::KeyNotFound(_)) => { /* continue */ }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+
+    /// Lists objects in the main store for the bucket. Objects in cold or default stores are not listed.
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<ObjectMetadata>, ObjectStoreError> {
+        self.main_store(bucket)
+            .list_raw(bucket, start_after, limit)
+            .await
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        self.main_store(bucket).storage_prefix_raw(bucket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+    use crate::MockObjectStore;
+
+    const DAY: Duration = Duration::from_secs(86_400);
+
+    /// Mock store with a distinct storage prefix, so that it's not considered to share storage with other stores.
+    #[derive(Debug, Default)]
+    struct NamedStore(MockObjectStore, &'static str);
+
+    #[async_trait]
+    impl ObjectStore for NamedStore {
+        async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+            self.0.get_raw(bucket, key).await
+        }
+
+        async fn put_raw(
+            &self,
+            bucket: Bucket,
+            key: &str,
+            value: Vec<u8>,
+        ) -> Result<(), ObjectStoreError> {
+            self.0.put_raw(bucket, key, value).await
+        }
+
+        async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+            self.0.remove_raw(bucket, key).await
+        }
+
+        async fn list_raw(
+            &self,
+            bucket: Bucket,
+            start_after: Option<&str>,
+            limit: usize,
+        ) -> Result<Vec<ObjectMetadata>, ObjectStoreError> {
+            self.0.list_raw(bucket, start_after, limit).await
+        }
+
+        fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+            format!("{}/{bucket}", self.1)
+        }
+    }
+
+    fn named_store(name: &'static str) -> Arc<NamedStore> {
+        Arc::new(NamedStore(MockObjectStore::default(), name))
+    }
+
+    #[tokio::test]
+    async fn routing_objects_by_class() {
+        let default_store = named_store("default");
+        let proofs_store = named_store("proofs");
+        let store = TieredObjectStore::new(default_store.clone()).with_tier(
+            ArtifactClass::Proofs,
+            proofs_store.clone(),
+            None,
+            TierLifecycle::default(),
+        );
+
+        store
+            .put_raw(Bucket::ProofsFri, "proof", vec![1])
+            .await
+            .unwrap();
+        store
+            .put_raw(Bucket::WitnessInput, "witness", vec![2])
+            .await
+            .unwrap();
+        assert_eq!(
+            proofs_store
+                .get_raw(Bucket::ProofsFri, "proof")
+                .await
+                .unwrap(),
+            [1]
+        );
+        assert_eq!(
+            default_store
+                .get_raw(Bucket::WitnessInput, "witness")
+                .await
+                .unwrap(),
+            [2]
+        );
+        assert_eq!(
+            store.storage_prefix_raw(Bucket::ProofsFri),
+            "proofs/proofs_fri"
+        );
+
+        // Objects not yet migrated to the tier should be accessible.
+        default_store
+            .put_raw(Bucket::ProofsFri, "old_proof", vec![3])
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get_raw(Bucket::ProofsFri, "old_proof").await.unwrap(),
+            [3]
+        );
+        let err = store
+            .get_raw(Bucket::ProofsFri, "missing")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+
+        store
+            .remove_raw(Bucket::ProofsFri, "old_proof")
+            .await
+            .unwrap();
+        let err = default_store
+            .get_raw(Bucket::ProofsFri, "old_proof")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+    }
+
+    #[tokio::test]
+    async fn lifecycle_management() {
+        let default_store = named_store("default");
+        let proofs_store = named_store("proofs");
+        let cold_store = named_store("cold");
+        let lifecycle = TierLifecycle {
+            move_to_cold_after: Some(7 * DAY),
+            expire_after: Some(30 * DAY),
+            min_cold_object_size: 0,
+        };
+        let store = TieredObjectStore::new(default_store.clone()).with_tier(
+            ArtifactClass::Proofs,
+            proofs_store.clone(),
+            Some(cold_store.clone()),
+            lifecycle,
+        );
+
+        default_store
+            .put_raw(Bucket::ProofsFri, "existing", vec![1])
+            .await
+            .unwrap();
+        default_store
+            .put_raw(Bucket::StorageSnapshot, "snapshot", vec![2])
+            .await
+            .unwrap();
+        let now = SystemTime::now();
+        let stats = store.run_lifecycle(now).await.unwrap();
+        assert_eq!(
+            stats,
+            TierLifecycleStats {
+                migrated_to_tier: 1,
+                ..TierLifecycleStats::default()
+            }
+        );
+        assert_eq!(
+            proofs_store
+                .get_raw(Bucket::ProofsFri, "existing")
+                .await
+                .unwrap(),
+            [1]
+        );
+        // Objects not covered by tiers must not be touched.
+        default_store
+            .get_raw(Bucket::StorageSnapshot, "snapshot")
+            .await
+            .unwrap();
+
+        let stats = store.run_lifecycle(now + 8 * DAY).await.unwrap();
+        assert_eq!(stats.moved_to_cold, 1);
+        assert_eq!(
+            cold_store
+                .get_raw(Bucket::ProofsFri, "existing")
+                .await
+                .unwrap(),
+            [1]
+        );
+        assert_eq!(
+            store.get_raw(Bucket::ProofsFri, "existing").await.unwrap(),
+            [1]
+        );
+
+        // The object was moved to the cold store at `now`, so it expires 23 days after that.
+        let stats = store.run_lifecycle(now + 22 * DAY).await.unwrap();
+        assert_eq!(stats.expired, 0);
+        let stats = store.run_lifecycle(now + 24 * DAY).await.unwrap();
+        assert_eq!(stats.expired, 1);
+        let err = store
+            .get_raw(Bucket::ProofsFri, "existing")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+    }
+
+    #[tokio::test]
+    async fn incremental_lifecycle_runs() {
+        let default_store = named_store("default");
+        let proofs_store = named_store("proofs");
+        let store = TieredObjectStore::new(default_store.clone())
+            .with_tier(
+                ArtifactClass::Proofs,
+                proofs_store.clone(),
+                None,
+                TierLifecycle::default(),
+            )
+            .with_max_objects_per_lifecycle_run(2);
+        for i in 0..5 {
+            default_store
+                .put_raw(Bucket::ProofsFri, &format!("{i}.bin"), vec![i])
+                .await
+                .unwrap();
+        }
+
+        let now = SystemTime::now();
+        for expected_migrated in [2, 2, 1, 0] {
+            let stats = store.run_lifecycle(now).await.unwrap();
+            assert_eq!(stats.migrated_to_tier, expected_migrated);
+        }
+        let objects = proofs_store
+            .list_raw(Bucket::ProofsFri, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(objects.len(), 5);
+
+        // Listing should wrap around after reaching the end of the bucket.
+        default_store
+            .put_raw(Bucket::ProofsFri, "0.bin", vec![0])
+            .await
+            .unwrap();
+        let stats = store.run_lifecycle(now).await.unwrap();
+        assert_eq!(stats.migrated_to_tier, 1);
+    }
+
+    #[tokio::test]
+    async fn small_objects_are_not_moved_to_cold_store() {
+        let default_store = named_store("default");
+        let proofs_store = named_store("proofs");
+        let cold_store = named_store("cold");
+        let lifecycle = TierLifecycle {
+            move_to_cold_after: Some(7 * DAY),
+            expire_after: None,
+            min_cold_object_size: 3,
+        };
+        let store = TieredObjectStore::new(default_store).with_tier(
+            ArtifactClass::Proofs,
+            proofs_store.clone(),
+            Some(cold_store.clone()),
+            lifecycle,
+        );
+        store
+            .put_raw(Bucket::ProofsFri, "small", vec![1])
+            .await
+            .unwrap();
+        store
+            .put_raw(Bucket::ProofsFri, "large", vec![1; 3])
+            .await
+            .unwrap();
+
+        let stats = store
+            .run_lifecycle(SystemTime::now() + 8 * DAY)
+            .await
+            .unwrap();
+        assert_eq!(stats.moved_to_cold, 1);
+        cold_store
+            .get_raw(Bucket::ProofsFri, "large")
+            .await
+            .unwrap();
+        proofs_store
+            .get_raw(Bucket::ProofsFri, "small")
+            .await
+            .unwrap();
+    }
+}
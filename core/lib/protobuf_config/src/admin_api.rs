@@ -0,0 +1,22 @@
+use anyhow::Context;
+use zksync_config::AdminApiConfig;
+use zksync_protobuf::{required, ProtoRepr};
+
+use crate::proto::admin_api as proto;
+
+impl ProtoRepr for proto::AdminApi {
+    type Type = AdminApiConfig;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            http_port: required(&self.http_port)
+                .and_then(|p| Ok((*p).try_into()?))
+                .context("http_port")?,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            http_port: Some(this.http_port.into()),
+        }
+    }
+}
@@ -7,7 +7,7 @@ use zksync_protobuf::{
     required,
 };
 
-use crate::{parse_h160, proto::api as proto};
+use crate::{parse_h160, proto::api as proto, read_optional_repr};
 
 impl ProtoRepr for proto::Api {
     type Type = ApiConfig;
@@ -98,6 +98,26 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .map(|x| x.try_into())
                 .transpose()
                 .context("vm_concurrency_limit")?,
+            vm_concurrency_limit_per_client: self
+                .vm_concurrency_limit_per_client
+                .map(|x| x.try_into())
+                .transpose()
+                .context("vm_concurrency_limit_per_client")?,
+            vm_concurrency_limit_anonymous: self
+                .vm_concurrency_limit_anonymous
+                .map(|x| x.try_into())
+                .transpose()
+                .context("vm_concurrency_limit_anonymous")?,
+            vm_interactive_permit_weight: self
+                .vm_interactive_permit_weight
+                .map(|x| x.try_into())
+                .transpose()
+                .context("vm_interactive_permit_weight")?,
+            vm_heavy_permit_weight: self
+                .vm_heavy_permit_weight
+                .map(|x| x.try_into())
+                .transpose()
+                .context("vm_heavy_permit_weight")?,
             factory_deps_cache_size_mb: self
                 .factory_deps_cache_size_mb
                 .map(|x| x.try_into())
@@ -118,6 +138,16 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .map(|x| x.try_into())
                 .transpose()
                 .context("latest_values_max_block_lag")?,
+            historical_views_cache_size_mb: self
+                .historical_views_cache_size_mb
+                .map(|x| x.try_into())
+                .transpose()
+                .context("historical_views_cache_size_mb")?,
+            historical_views_max_block_lag: self
+                .historical_views_max_block_lag
+                .map(|x| x.try_into())
+                .transpose()
+                .context("historical_views_max_block_lag")?,
             fee_history_limit: self.fee_history_limit,
             max_batch_request_size: self
                 .max_batch_request_size
@@ -142,6 +172,12 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .map(|x| x.try_into())
                 .transpose()
                 .context("mempool_cache_size")?,
+            call_result_cache_size: self
+                .call_result_cache_size
+                .map(|x| x.try_into())
+                .transpose()
+                .context("call_result_cache_size")?,
+            call_result_cache_excluded_methods: self.call_result_cache_excluded_methods.clone(),
             whitelisted_tokens_for_aa: self
                 .whitelisted_tokens_for_aa
                 .iter()
@@ -151,6 +187,10 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .context("whitelisted_tokens_for_aa")?,
             extended_api_tracing: self.extended_api_tracing.unwrap_or_default(),
             api_namespaces,
+            rate_limits: read_optional_repr(&self.rate_limits),
+            http_policy: read_optional_repr(&self.http_policy),
+            ws_policy: read_optional_repr(&self.ws_policy),
+            subscription_limits: read_optional_repr(&self.subscription_limits),
         })
     }
 
@@ -164,6 +204,8 @@ impl ProtoRepr for proto::Web3JsonRpc {
             filters_disabled: Some(this.filters_disabled),
             mempool_cache_update_interval: this.mempool_cache_update_interval,
             mempool_cache_size: this.mempool_cache_size.map(|x| x.try_into().unwrap()),
+            call_result_cache_size: this.call_result_cache_size.map(|x| x.try_into().unwrap()),
+            call_result_cache_excluded_methods: this.call_result_cache_excluded_methods.clone(),
             filters_limit: this.filters_limit,
             subscriptions_limit: this.subscriptions_limit,
             pubsub_polling_interval: this.pubsub_polling_interval,
@@ -179,6 +221,16 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .vm_execution_cache_misses_limit
                 .map(|x| x.try_into().unwrap()),
             vm_concurrency_limit: this.vm_concurrency_limit.map(|x| x.try_into().unwrap()),
+            vm_concurrency_limit_per_client: this
+                .vm_concurrency_limit_per_client
+                .map(|x| x.try_into().unwrap()),
+            vm_concurrency_limit_anonymous: this
+                .vm_concurrency_limit_anonymous
+                .map(|x| x.try_into().unwrap()),
+            vm_interactive_permit_weight: this
+                .vm_interactive_permit_weight
+                .map(|x| x.try_into().unwrap()),
+            vm_heavy_permit_weight: this.vm_heavy_permit_weight.map(|x| x.try_into().unwrap()),
             factory_deps_cache_size_mb: this
                 .factory_deps_cache_size_mb
                 .map(|x| x.try_into().unwrap()),
@@ -189,6 +241,12 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .latest_values_cache_size_mb
                 .map(|x| x.try_into().unwrap()),
             latest_values_max_block_lag: this.latest_values_max_block_lag.map(NonZeroU32::get),
+            historical_views_cache_size_mb: this
+                .historical_views_cache_size_mb
+                .map(|x| x.try_into().unwrap()),
+            historical_views_max_block_lag: this
+                .historical_views_max_block_lag
+                .map(NonZeroU32::get),
             fee_history_limit: this.fee_history_limit,
             max_batch_request_size: this.max_batch_request_size.map(|x| x.try_into().unwrap()),
             max_response_body_size_mb: this
@@ -217,6 +275,172 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .collect(),
             extended_api_tracing: Some(this.extended_api_tracing),
             api_namespaces: this.api_namespaces.clone().unwrap_or_default(),
+            rate_limits: this.rate_limits.as_ref().map(ProtoRepr::build),
+            http_policy: this.http_policy.as_ref().map(ProtoRepr::build),
+            ws_policy: this.ws_policy.as_ref().map(ProtoRepr::build),
+            subscription_limits: this.subscription_limits.as_ref().map(ProtoRepr::build),
+        }
+    }
+}
+
+impl ProtoRepr for proto::Web3TransportPolicy {
+    type Type = api::Web3TransportPolicyConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            cors_allowed_origins: self.cors_allowed_origins.clone(),
+            gzip_compression: self.gzip_compression.unwrap_or_default(),
+            brotli_compression: self.brotli_compression.unwrap_or_default(),
+            max_request_body_size_mb: self
+                .max_request_body_size_mb
+                .map(|x| x.try_into())
+                .transpose()
+                .context("max_request_body_size_mb")?,
+            max_batch_request_size: self
+                .max_batch_request_size
+                .map(|x| x.try_into())
+                .transpose()
+                .context("max_batch_request_size")?,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            cors_allowed_origins: this.cors_allowed_origins.clone(),
+            gzip_compression: Some(this.gzip_compression),
+            brotli_compression: Some(this.brotli_compression),
+            max_request_body_size_mb: this.max_request_body_size_mb.map(|x| x.try_into().unwrap()),
+            max_batch_request_size: this.max_batch_request_size.map(|x| x.try_into().unwrap()),
+        }
+    }
+}
+
+impl proto::SlowSubscriberPolicy {
+    fn new(x: &api::SlowSubscriberPolicy) -> Self {
+        use api::SlowSubscriberPolicy as From;
+        match x {
+            From::Close => Self::Close,
+            From::DropOldest => Self::DropOldest,
+        }
+    }
+
+    fn parse(&self) -> api::SlowSubscriberPolicy {
+        use api::SlowSubscriberPolicy as To;
+        match self {
+            Self::Close => To::Close,
+            Self::DropOldest => To::DropOldest,
+        }
+    }
+}
+
+impl ProtoRepr for proto::Web3SubscriptionLimits {
+    type Type = api::Web3SubscriptionLimitsConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            max_subscriptions_per_connection: self
+                .max_subscriptions_per_connection
+                .map(|x| NonZeroU32::new(x).context("cannot be zero"))
+                .transpose()
+                .context("max_subscriptions_per_connection")?,
+            buffer_capacity: self
+                .buffer_capacity
+                .map(|x| NonZeroUsize::new(x.try_into()?).context("cannot be zero"))
+                .transpose()
+                .context("buffer_capacity")?,
+            max_buffered_notifications_per_connection: self
+                .max_buffered_notifications_per_connection
+                .map(|x| NonZeroUsize::new(x.try_into()?).context("cannot be zero"))
+                .transpose()
+                .context("max_buffered_notifications_per_connection")?,
+            slow_subscriber_policy: self
+                .slow_subscriber_policy
+                .map(proto::SlowSubscriberPolicy::try_from)
+                .transpose()
+                .context("slow_subscriber_policy")?
+                .map_or_else(Default::default, |policy| policy.parse()),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            max_subscriptions_per_connection: this
+                .max_subscriptions_per_connection
+                .map(NonZeroU32::get),
+            buffer_capacity: this.buffer_capacity.map(|x| x.get().try_into().unwrap()),
+            max_buffered_notifications_per_connection: this
+                .max_buffered_notifications_per_connection
+                .map(|x| x.get().try_into().unwrap()),
+            slow_subscriber_policy: Some(
+                proto::SlowSubscriberPolicy::new(&this.slow_subscriber_policy).into(),
+            ),
+        }
+    }
+}
+
+impl ProtoRepr for proto::Web3RateLimits {
+    type Type = api::Web3RateLimitsConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            requests_per_minute_per_ip: required(&self.requests_per_minute_per_ip)
+                .and_then(|&x| NonZeroU32::new(x).context("cannot be zero"))
+                .context("requests_per_minute_per_ip")?,
+            expensive_requests_per_minute_per_ip: required(
+                &self.expensive_requests_per_minute_per_ip,
+            )
+            .and_then(|&x| NonZeroU32::new(x).context("cannot be zero"))
+            .context("expensive_requests_per_minute_per_ip")?,
+            expensive_methods: self.expensive_methods.clone(),
+            api_keys: self
+                .api_keys
+                .iter()
+                .enumerate()
+                .map(|(i, quota)| quota.read().context(i))
+                .collect::<anyhow::Result<_>>()
+                .context("api_keys")?,
+            trusted_proxy_hops: self
+                .trusted_proxy_hops
+                .map(|x| x.try_into())
+                .transpose()
+                .context("trusted_proxy_hops")?
+                .unwrap_or(1),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            requests_per_minute_per_ip: Some(this.requests_per_minute_per_ip.get()),
+            expensive_requests_per_minute_per_ip: Some(
+                this.expensive_requests_per_minute_per_ip.get(),
+            ),
+            expensive_methods: this.expensive_methods.clone(),
+            api_keys: this.api_keys.iter().map(ProtoRepr::build).collect(),
+            trusted_proxy_hops: Some(this.trusted_proxy_hops as u64),
+        }
+    }
+}
+
+impl ProtoRepr for proto::ApiKeyQuota {
+    type Type = api::ApiKeyQuota;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            key: required(&self.key).context("key")?.clone(),
+            requests_per_minute: required(&self.requests_per_minute)
+                .and_then(|&x| NonZeroU32::new(x).context("cannot be zero"))
+                .context("requests_per_minute")?,
+            expensive_requests_per_minute: required(&self.expensive_requests_per_minute)
+                .and_then(|&x| NonZeroU32::new(x).context("cannot be zero"))
+                .context("expensive_requests_per_minute")?,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            key: Some(this.key.clone()),
+            requests_per_minute: Some(this.requests_per_minute.get()),
+            expensive_requests_per_minute: Some(this.expensive_requests_per_minute.get()),
         }
     }
 }
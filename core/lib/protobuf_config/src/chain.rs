@@ -72,15 +72,22 @@ impl ProtoRepr for proto::StateKeeper {
                 .and_then(|x| Ok(proto::FeeModelVersion::try_from(*x)?))
                 .context("fee_model_version")?
                 .parse(),
+            pubdata_price_curve_l1_gas_prices: self.pubdata_price_curve_l1_gas_prices.clone(),
+            pubdata_price_curve_pubdata_prices: self.pubdata_price_curve_pubdata_prices.clone(),
             validation_computational_gas_limit: *required(&self.validation_computational_gas_limit)
                 .context("validation_computational_gas_limit")?,
             save_call_traces: *required(&self.save_call_traces).context("save_call_traces")?,
             max_circuits_per_batch: required(&self.max_circuits_per_batch)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("max_circuits_per_batch")?,
+            seal_on_predicted_circuits: self.seal_on_predicted_circuits.unwrap_or_default(),
             protective_reads_persistence_enabled: self
                 .protective_reads_persistence_enabled
                 .unwrap_or_default(),
+            congestion_max_unproven_l1_batches: self.congestion_max_unproven_l1_batches,
+            congestion_max_unexecuted_l1_batches: self.congestion_max_unexecuted_l1_batches,
+            congestion_min_l1_batch_interval_ms: self.congestion_min_l1_batch_interval_ms,
+            congestion_gas_price_multiplier: self.congestion_gas_price_multiplier,
 
             // We need these values only for instantiating configs from environmental variables, so it's not
             // needed during the initialization from files
@@ -116,10 +123,17 @@ impl ProtoRepr for proto::StateKeeper {
             max_gas_per_batch: Some(this.max_gas_per_batch),
             max_pubdata_per_batch: Some(this.max_pubdata_per_batch),
             fee_model_version: Some(proto::FeeModelVersion::new(&this.fee_model_version).into()),
+            pubdata_price_curve_l1_gas_prices: this.pubdata_price_curve_l1_gas_prices.clone(),
+            pubdata_price_curve_pubdata_prices: this.pubdata_price_curve_pubdata_prices.clone(),
             validation_computational_gas_limit: Some(this.validation_computational_gas_limit),
             save_call_traces: Some(this.save_call_traces),
             max_circuits_per_batch: Some(this.max_circuits_per_batch.try_into().unwrap()),
+            seal_on_predicted_circuits: Some(this.seal_on_predicted_circuits),
             protective_reads_persistence_enabled: Some(this.protective_reads_persistence_enabled),
+            congestion_max_unproven_l1_batches: this.congestion_max_unproven_l1_batches,
+            congestion_max_unexecuted_l1_batches: this.congestion_max_unexecuted_l1_batches,
+            congestion_min_l1_batch_interval_ms: this.congestion_min_l1_batch_interval_ms,
+            congestion_gas_price_multiplier: this.congestion_gas_price_multiplier,
         }
     }
 }
@@ -129,12 +143,14 @@ impl ProtoRepr for proto::OperationsManager {
     fn read(&self) -> anyhow::Result<Self::Type> {
         Ok(Self::Type {
             delay_interval: *required(&self.delay_interval).context("delay_interval")?,
+            tx_policy_path: self.tx_policy_path.clone(),
         })
     }
 
     fn build(this: &Self::Type) -> Self {
         Self {
             delay_interval: Some(this.delay_interval),
+            tx_policy_path: this.tx_policy_path.clone(),
         }
     }
 }
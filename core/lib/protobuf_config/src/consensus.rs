@@ -1,3 +1,5 @@
+use std::num::NonZeroU64;
+
 use anyhow::Context as _;
 use zksync_basic_types::L2ChainId;
 use zksync_concurrency::time;
@@ -72,6 +74,11 @@ impl ProtoRepr for proto::GenesisSpec {
                 .map(|x| parse_h160(x))
                 .transpose()
                 .context("registry_address")?,
+            attester_committee_epoch: self
+                .attester_committee_epoch
+                .map(|x| NonZeroU64::new(x).context("must be positive"))
+                .transpose()
+                .context("attester_committee_epoch")?,
             seed_peers: self
                 .seed_peers
                 .iter()
@@ -89,6 +96,7 @@ impl ProtoRepr for proto::GenesisSpec {
             attesters: this.attesters.iter().map(ProtoRepr::build).collect(),
             leader: Some(this.leader.0.clone()),
             registry_address: this.registry_address.map(|a| format!("{:?}", a)),
+            attester_committee_epoch: this.attester_committee_epoch.map(NonZeroU64::get),
             seed_peers: this
                 .seed_peers
                 .iter()
@@ -7,11 +7,18 @@ impl ProtoRepr for proto::DataAvailabilityDispatcher {
     type Type = configs::da_dispatcher::DADispatcherConfig;
 
     fn read(&self) -> anyhow::Result<Self::Type> {
+        let fallback_object_store = if let Some(object_store) = &self.fallback_object_store {
+            Some(object_store.read()?)
+        } else {
+            None
+        };
         Ok(configs::da_dispatcher::DADispatcherConfig {
             polling_interval_ms: self.polling_interval_ms,
             max_rows_to_dispatch: self.max_rows_to_dispatch,
             max_retries: self.max_retries.map(|x| x as u16),
             use_dummy_inclusion_data: self.use_dummy_inclusion_data,
+            fallback_object_store,
+            inclusion_sla_ms: self.inclusion_sla_ms,
         })
     }
 
@@ -21,6 +28,8 @@ impl ProtoRepr for proto::DataAvailabilityDispatcher {
             max_rows_to_dispatch: this.max_rows_to_dispatch,
             max_retries: this.max_retries.map(Into::into),
             use_dummy_inclusion_data: this.use_dummy_inclusion_data,
+            fallback_object_store: this.fallback_object_store.as_ref().map(ProtoRepr::build),
+            inclusion_sla_ms: this.inclusion_sla_ms,
         }
     }
 }
@@ -5,7 +5,10 @@ use zksync_protobuf::{
     required,
 };
 
-use crate::proto::database as proto;
+use crate::{
+    experimental::parse_rocksdb_profile,
+    proto::{database as proto, experimental as experimental_proto},
+};
 
 impl proto::MerkleTreeMode {
     fn new(x: &configs::database::MerkleTreeMode) -> Self {
@@ -48,6 +51,13 @@ impl ProtoRepr for proto::MerkleTree {
             max_l1_batches_per_iter: required(&self.max_l1_batches_per_iter)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("max_l1_batches_per_iter")?,
+            pruning_retained_versions: self.pruning_retained_versions,
+            pruning_iteration_delay_ms: self.pruning_iteration_delay_ms.unwrap_or(0),
+            pruning_online_compaction: self
+                .pruning_online_compaction
+                .unwrap_or(Self::Type::default_pruning_online_compaction()),
+            rocksdb_profile: parse_rocksdb_profile(self.rocksdb_profile)
+                .context("rocksdb_profile")?,
         })
     }
 
@@ -60,6 +70,12 @@ impl ProtoRepr for proto::MerkleTree {
             memtable_capacity_mb: Some(this.memtable_capacity_mb.try_into().unwrap()),
             stalled_writes_timeout_sec: Some(this.stalled_writes_timeout_sec),
             max_l1_batches_per_iter: Some(this.max_l1_batches_per_iter.try_into().unwrap()),
+            pruning_retained_versions: this.pruning_retained_versions,
+            pruning_iteration_delay_ms: Some(this.pruning_iteration_delay_ms),
+            pruning_online_compaction: Some(this.pruning_online_compaction),
+            rocksdb_profile: Some(
+                experimental_proto::RocksdbProfile::new(this.rocksdb_profile).into(),
+            ),
         }
     }
 }
@@ -109,6 +125,7 @@ impl ProtoRepr for proto::Postgres {
             statement_timeout_sec: self.statement_timeout_sec,
             long_connection_threshold_ms: self.long_connection_threshold_ms,
             slow_query_threshold_ms: self.slow_query_threshold_ms,
+            leak_detection_threshold_ms: self.leak_detection_threshold_ms,
             test_server_url,
             test_prover_url,
         })
@@ -122,6 +139,7 @@ impl ProtoRepr for proto::Postgres {
             statement_timeout_sec: this.statement_timeout_sec,
             long_connection_threshold_ms: this.long_connection_threshold_ms,
             slow_query_threshold_ms: this.slow_query_threshold_ms,
+            leak_detection_threshold_ms: this.leak_detection_threshold_ms,
             test: Some(proto::TestDatabase {
                 server_url: this.test_server_url.clone(),
                 prover_url: this.test_prover_url.clone(),
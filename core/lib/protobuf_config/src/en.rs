@@ -5,7 +5,7 @@ use std::{
 
 use anyhow::Context;
 use zksync_basic_types::{url::SensitiveUrl, L1ChainId, L2ChainId};
-use zksync_config::configs::en_config::ENConfig;
+use zksync_config::configs::en_config::{ENConfig, RootHashMismatchPolicy};
 use zksync_protobuf::{required, ProtoRepr};
 
 use crate::proto::en as proto;
@@ -37,6 +37,12 @@ impl ProtoRepr for proto::ExternalNode {
             bridge_addresses_refresh_interval_sec: self
                 .bridge_addresses_refresh_interval_sec
                 .and_then(NonZeroU64::new),
+            tree_data_fetcher_root_hash_mismatch_policy: self
+                .tree_data_fetcher_root_hash_mismatch_policy
+                .map(proto::RootHashMismatchPolicy::try_from)
+                .transpose()
+                .context("tree_data_fetcher_root_hash_mismatch_policy")?
+                .map_or_else(Default::default, |policy| policy.parse()),
         })
     }
 
@@ -55,6 +61,28 @@ impl ProtoRepr for proto::ExternalNode {
             bridge_addresses_refresh_interval_sec: this
                 .bridge_addresses_refresh_interval_sec
                 .map(|a| a.get()),
+            tree_data_fetcher_root_hash_mismatch_policy: Some(
+                proto::RootHashMismatchPolicy::new(
+                    this.tree_data_fetcher_root_hash_mismatch_policy,
+                )
+                .into(),
+            ),
+        }
+    }
+}
+
+impl proto::RootHashMismatchPolicy {
+    fn new(source: RootHashMismatchPolicy) -> Self {
+        match source {
+            RootHashMismatchPolicy::Log => Self::Log,
+            RootHashMismatchPolicy::Halt => Self::Halt,
+        }
+    }
+
+    fn parse(&self) -> RootHashMismatchPolicy {
+        match self {
+            Self::Log => RootHashMismatchPolicy::Log,
+            Self::Halt => RootHashMismatchPolicy::Halt,
         }
     }
 }
@@ -3,7 +3,7 @@ use zksync_config::configs::{self};
 use zksync_protobuf::{required, ProtoRepr};
 use zksync_types::pubdata_da::PubdataSendingMode;
 
-use crate::{proto::eth as proto, read_optional_repr};
+use crate::{parse_h160, proto::eth as proto, read_optional_repr};
 
 impl proto::ProofSendingMode {
     fn new(x: &configs::eth_sender::ProofSendingMode) -> Self {
@@ -25,6 +25,26 @@ impl proto::ProofSendingMode {
     }
 }
 
+impl proto::FeeSmoothingStrategy {
+    fn new(x: &configs::eth_sender::FeeSmoothingStrategy) -> Self {
+        use configs::eth_sender::FeeSmoothingStrategy as From;
+        match x {
+            From::Median => Self::Median,
+            From::Ema => Self::Ema,
+            From::Percentile => Self::Percentile,
+        }
+    }
+
+    fn parse(&self) -> configs::eth_sender::FeeSmoothingStrategy {
+        use configs::eth_sender::FeeSmoothingStrategy as To;
+        match self {
+            Self::Median => To::Median,
+            Self::Ema => To::Ema,
+            Self::Percentile => To::Percentile,
+        }
+    }
+}
+
 impl proto::PubdataSendingMode {
     fn new(x: &PubdataSendingMode) -> Self {
         match x {
@@ -173,6 +193,20 @@ impl ProtoRepr for proto::GasAdjuster {
             max_blob_base_fee: self.max_blob_base_fee,
             // TODO(EVM-676): support this field
             settlement_mode: Default::default(),
+            fee_smoothing_strategy: self
+                .fee_smoothing_strategy
+                .map(proto::FeeSmoothingStrategy::try_from)
+                .transpose()
+                .context("fee_smoothing_strategy")?
+                .map_or_else(Default::default, |strategy| strategy.parse()),
+            fee_smoothing_ema_alpha: self
+                .fee_smoothing_ema_alpha
+                .unwrap_or_else(Self::Type::default_fee_smoothing_ema_alpha),
+            fee_smoothing_percentile: self
+                .fee_smoothing_percentile
+                .unwrap_or_else(Self::Type::default_fee_smoothing_percentile),
+            max_fee_change_per_update: self.max_fee_change_per_update,
+            blob_base_fee_prediction_blocks: self.blob_base_fee_prediction_blocks,
         })
     }
 
@@ -194,6 +228,13 @@ impl ProtoRepr for proto::GasAdjuster {
             ),
             internal_pubdata_pricing_multiplier: Some(this.internal_pubdata_pricing_multiplier),
             max_blob_base_fee: this.max_blob_base_fee,
+            fee_smoothing_strategy: Some(
+                proto::FeeSmoothingStrategy::new(&this.fee_smoothing_strategy).into(),
+            ),
+            fee_smoothing_ema_alpha: Some(this.fee_smoothing_ema_alpha),
+            fee_smoothing_percentile: Some(this.fee_smoothing_percentile),
+            max_fee_change_per_update: this.max_fee_change_per_update,
+            blob_base_fee_prediction_blocks: this.blob_base_fee_prediction_blocks,
         }
     }
 }
@@ -206,6 +247,14 @@ impl ProtoRepr for proto::EthWatch {
             confirmations_for_eth_event: self.confirmations_for_eth_event,
             eth_node_poll_interval: *required(&self.eth_node_poll_interval)
                 .context("eth_node_poll_interval")?,
+            custom_events: self
+                .custom_events
+                .iter()
+                .enumerate()
+                .map(|(i, event)| event.read().context(i))
+                .collect::<anyhow::Result<_>>()
+                .context("custom_events")?,
+            bridge_events_start_block: self.bridge_events_start_block,
         })
     }
 
@@ -213,6 +262,32 @@ impl ProtoRepr for proto::EthWatch {
         Self {
             confirmations_for_eth_event: this.confirmations_for_eth_event,
             eth_node_poll_interval: Some(this.eth_node_poll_interval),
+            custom_events: this.custom_events.iter().map(ProtoRepr::build).collect(),
+            bridge_events_start_block: this.bridge_events_start_block,
+        }
+    }
+}
+
+impl ProtoRepr for proto::EthWatchCustomEvent {
+    type Type = configs::eth_watch::EthWatchCustomEvent;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            contract_address: required(&self.contract_address)
+                .and_then(|x| parse_h160(x))
+                .context("contract_address")?,
+            event_signature: required(&self.event_signature)
+                .context("event_signature")?
+                .clone(),
+            handler: required(&self.handler).context("handler")?.clone(),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            contract_address: Some(format!("{:?}", this.contract_address)),
+            event_signature: Some(this.event_signature.clone()),
+            handler: Some(this.handler.clone()),
         }
     }
 }
@@ -15,6 +15,15 @@ fn parse_vm_mode(raw: Option<i32>) -> anyhow::Result<FastVmMode> {
         .map_or_else(FastVmMode::default, |mode| mode.parse()))
 }
 
+pub(crate) fn parse_rocksdb_profile(
+    raw: Option<i32>,
+) -> anyhow::Result<configs::database::RocksdbProfile> {
+    Ok(raw
+        .map(proto::RocksdbProfile::try_from)
+        .transpose()?
+        .map_or_else(Default::default, |profile| profile.parse()))
+}
+
 impl ProtoRepr for proto::Db {
     type Type = configs::ExperimentalDBConfig;
 
@@ -36,6 +45,8 @@ impl ProtoRepr for proto::Db {
                 .include_indices_and_filters_in_block_cache
                 .unwrap_or(false),
             merkle_tree_repair_stale_keys: self.merkle_tree_repair_stale_keys.unwrap_or(false),
+            state_keeper_db_profile: parse_rocksdb_profile(self.state_keeper_db_profile)
+                .context("state_keeper_db_profile")?,
         })
     }
 
@@ -55,6 +66,29 @@ impl ProtoRepr for proto::Db {
                 this.include_indices_and_filters_in_block_cache,
             ),
             merkle_tree_repair_stale_keys: Some(this.merkle_tree_repair_stale_keys),
+            state_keeper_db_profile: Some(
+                proto::RocksdbProfile::new(this.state_keeper_db_profile).into(),
+            ),
+        }
+    }
+}
+
+impl proto::RocksdbProfile {
+    pub(crate) fn new(source: configs::database::RocksdbProfile) -> Self {
+        use configs::database::RocksdbProfile as From;
+        match source {
+            From::Balanced => Self::Balanced,
+            From::WriteHeavy => Self::WriteHeavy,
+            From::ReadHeavy => Self::ReadHeavy,
+        }
+    }
+
+    fn parse(&self) -> configs::database::RocksdbProfile {
+        use configs::database::RocksdbProfile as To;
+        match self {
+            Self::Balanced => To::Balanced,
+            Self::WriteHeavy => To::WriteHeavy,
+            Self::ReadHeavy => To::ReadHeavy,
         }
     }
 }
@@ -111,10 +145,17 @@ impl ProtoRepr for proto::Vm {
     type Type = configs::ExperimentalVmConfig;
 
     fn read(&self) -> anyhow::Result<Self::Type> {
+        let state_keeper_shadow_sampling_ratio =
+            self.state_keeper_shadow_sampling_ratio.unwrap_or(0.0);
+        anyhow::ensure!(
+            (0.0..=1.0).contains(&state_keeper_shadow_sampling_ratio),
+            "state_keeper_shadow_sampling_ratio must be in [0, 1]"
+        );
         Ok(Self::Type {
             playground: read_optional_repr(&self.playground).unwrap_or_default(),
             state_keeper_fast_vm_mode: parse_vm_mode(self.state_keeper_fast_vm_mode)?,
             api_fast_vm_mode: parse_vm_mode(self.api_fast_vm_mode)?,
+            state_keeper_shadow_sampling_ratio,
         })
     }
 
@@ -125,6 +166,7 @@ impl ProtoRepr for proto::Vm {
                 proto::FastVmMode::new(this.state_keeper_fast_vm_mode).into(),
             ),
             api_fast_vm_mode: Some(proto::FastVmMode::new(this.api_fast_vm_mode).into()),
+            state_keeper_shadow_sampling_ratio: Some(this.state_keeper_shadow_sampling_ratio),
         }
     }
 }
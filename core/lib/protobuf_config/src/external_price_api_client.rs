@@ -1,7 +1,13 @@
-use zksync_config::configs::{self, external_price_api_client::ForcedPriceClientConfig};
+use anyhow::Context as _;
+use zksync_config::configs::{
+    self,
+    external_price_api_client::{
+        AggregatedPriceClientConfig, ChainlinkPriceClientConfig, ForcedPriceClientConfig,
+    },
+};
 use zksync_protobuf::ProtoRepr;
 
-use crate::proto::external_price_api_client as proto;
+use crate::{parse_h160, proto::external_price_api_client as proto};
 
 impl ProtoRepr for proto::ExternalPriceApiClient {
     type Type = configs::external_price_api_client::ExternalPriceApiClientConfig;
@@ -21,6 +27,20 @@ impl ProtoRepr for proto::ExternalPriceApiClient {
                         configs::external_price_api_client::DEFAULT_FORCED_NEXT_VALUE_FLUCTUATION,
                     ),
                 }),
+                chainlink: Some(ChainlinkPriceClientConfig {
+                    rpc_url: self.chainlink_rpc_url.clone(),
+                    feed_address: self
+                        .chainlink_feed_address
+                        .as_ref()
+                        .map(|x| parse_h160(x))
+                        .transpose()
+                        .context("chainlink_feed_address")?,
+                }),
+                aggregated: Some(AggregatedPriceClientConfig {
+                    sources: self.aggregated_sources.clone(),
+                    max_staleness_ms: self.aggregated_max_staleness_ms,
+                    min_sources: self.aggregated_min_sources,
+                }),
             },
         )
     }
@@ -40,6 +60,19 @@ impl ProtoRepr for proto::ExternalPriceApiClient {
             forced_denominator: denominator,
             forced_fluctuation: fluctuation,
             forced_next_value_fluctuation: next_value_fluctuation,
+            chainlink_rpc_url: this.chainlink.as_ref().and_then(|x| x.rpc_url.clone()),
+            chainlink_feed_address: this
+                .chainlink
+                .as_ref()
+                .and_then(|x| x.feed_address)
+                .map(|x| format!("{x:?}")),
+            aggregated_sources: this
+                .aggregated
+                .as_ref()
+                .map(|x| x.sources.clone())
+                .unwrap_or_default(),
+            aggregated_max_staleness_ms: this.aggregated.as_ref().and_then(|x| x.max_staleness_ms),
+            aggregated_min_sources: this.aggregated.as_ref().and_then(|x| x.min_sources),
         }
     }
 }
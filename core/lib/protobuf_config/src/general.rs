@@ -47,6 +47,9 @@ impl ProtoRepr for proto::GeneralConfig {
             experimental_vm_config: read_optional_repr(&self.experimental_vm),
             prover_job_monitor_config: read_optional_repr(&self.prover_job_monitor),
             timestamp_asserter_config: read_optional_repr(&self.timestamp_asserter),
+            admin_api_config: read_optional_repr(&self.admin_api),
+            leader_election_config: read_optional_repr(&self.leader_election),
+            withdrawal_finalizer_config: read_optional_repr(&self.withdrawal_finalizer),
         })
     }
 
@@ -111,6 +114,12 @@ impl ProtoRepr for proto::GeneralConfig {
                 .timestamp_asserter_config
                 .as_ref()
                 .map(ProtoRepr::build),
+            admin_api: this.admin_api_config.as_ref().map(ProtoRepr::build),
+            leader_election: this.leader_election_config.as_ref().map(ProtoRepr::build),
+            withdrawal_finalizer: this
+                .withdrawal_finalizer_config
+                .as_ref()
+                .map(ProtoRepr::build),
         }
     }
 }
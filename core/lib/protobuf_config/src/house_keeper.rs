@@ -12,6 +12,8 @@ impl ProtoRepr for proto::HouseKeeper {
                 &self.l1_batch_metrics_reporting_interval_ms,
             )
             .context("l1_batch_metrics_reporting_interval_ms")?,
+            table_partition_size: self.table_partition_size,
+            eth_txs_history_retention_secs: self.eth_txs_history_retention_secs,
         })
     }
 
@@ -20,6 +22,8 @@ impl ProtoRepr for proto::HouseKeeper {
             l1_batch_metrics_reporting_interval_ms: Some(
                 this.l1_batch_metrics_reporting_interval_ms,
             ),
+            table_partition_size: this.table_partition_size,
+            eth_txs_history_retention_secs: this.eth_txs_history_retention_secs,
         }
     }
 }
@@ -0,0 +1,27 @@
+use anyhow::Context;
+use zksync_config::configs::LeaderElectionConfig;
+use zksync_protobuf::{required, ProtoRepr};
+
+use crate::proto::leader_election as proto;
+
+impl ProtoRepr for proto::LeaderElection {
+    type Type = LeaderElectionConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            lease_name: required(&self.lease_name).context("lease_name")?.clone(),
+            instance_name: self.instance_name.clone(),
+            lease_ttl_ms: self
+                .lease_ttl_ms
+                .unwrap_or(Self::Type::default_lease_ttl_ms()),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            lease_name: Some(this.lease_name.clone()),
+            instance_name: this.instance_name.clone(),
+            lease_ttl_ms: Some(this.lease_ttl_ms),
+        }
+    }
+}
@@ -4,6 +4,7 @@
 //! * protobuf text format
 //! * protobuf json format
 
+mod admin_api;
 mod api;
 mod base_token_adjuster;
 mod chain;
@@ -23,6 +24,7 @@ mod external_proof_integration_api;
 mod general;
 mod genesis;
 mod house_keeper;
+mod leader_election;
 mod object_store;
 mod observability;
 mod proof_data_handler;
@@ -39,6 +41,7 @@ mod timestamp_asserter;
 mod utils;
 mod vm_runner;
 mod wallets;
+mod withdrawal_finalizer;
 
 use std::{path::PathBuf, str::FromStr};
 
@@ -1,9 +1,65 @@
 use anyhow::Context as _;
-use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode};
-use zksync_protobuf::{repr::ProtoRepr, required};
+use zksync_config::configs::object_store::{
+    ArtifactClass, ObjectStoreConfig, ObjectStoreMode, ObjectStoreTierConfig, S3AddressingStyle,
+    S3ServerSideEncryption,
+};
+use zksync_protobuf::{
+    repr::{read_required_repr, ProtoRepr},
+    required,
+};
 
 use crate::proto::object_store as proto;
 
+impl proto::ArtifactClass {
+    fn new(x: &ArtifactClass) -> Self {
+        match x {
+            ArtifactClass::Proofs => Self::Proofs,
+            ArtifactClass::WitnessInputs => Self::WitnessInputs,
+            ArtifactClass::Snapshots => Self::Snapshots,
+        }
+    }
+
+    fn parse(&self) -> ArtifactClass {
+        match self {
+            Self::Proofs => ArtifactClass::Proofs,
+            Self::WitnessInputs => ArtifactClass::WitnessInputs,
+            Self::Snapshots => ArtifactClass::Snapshots,
+        }
+    }
+}
+
+impl proto::S3AddressingStyle {
+    fn new(x: &S3AddressingStyle) -> Self {
+        match x {
+            S3AddressingStyle::Path => Self::Path,
+            S3AddressingStyle::VirtualHosted => Self::VirtualHosted,
+        }
+    }
+
+    fn parse(&self) -> S3AddressingStyle {
+        match self {
+            Self::Path => S3AddressingStyle::Path,
+            Self::VirtualHosted => S3AddressingStyle::VirtualHosted,
+        }
+    }
+}
+
+impl proto::S3ServerSideEncryption {
+    fn new(x: &S3ServerSideEncryption) -> Self {
+        match x {
+            S3ServerSideEncryption::Aes256 => Self::Aes256,
+            S3ServerSideEncryption::Kms => Self::Kms,
+        }
+    }
+
+    fn parse(&self) -> S3ServerSideEncryption {
+        match self {
+            Self::Aes256 => S3ServerSideEncryption::Aes256,
+            Self::Kms => S3ServerSideEncryption::Kms,
+        }
+    }
+}
+
 impl ProtoRepr for proto::ObjectStore {
     type Type = ObjectStoreConfig;
 
@@ -37,6 +93,25 @@ impl ProtoRepr for proto::ObjectStore {
                     .context("file_backed_base_path")?
                     .clone(),
             },
+            proto::object_store::Mode::S3(mode) => ObjectStoreMode::S3 {
+                endpoint: required(&mode.endpoint).context("endpoint")?.clone(),
+                region: required(&mode.region).context("region")?.clone(),
+                bucket_name: required(&mode.bucket_name).context("bucket_name")?.clone(),
+                addressing_style: mode
+                    .addressing_style
+                    .map(proto::S3AddressingStyle::try_from)
+                    .transpose()
+                    .context("addressing_style")?
+                    .map_or_else(Default::default, |style| style.parse()),
+                server_side_encryption: mode
+                    .server_side_encryption
+                    .map(proto::S3ServerSideEncryption::try_from)
+                    .transpose()
+                    .context("server_side_encryption")?
+                    .map(|sse| sse.parse()),
+                sse_kms_key_id: mode.sse_kms_key_id.clone(),
+                multipart_part_size_bytes: mode.multipart_part_size_bytes,
+            },
         };
 
         Ok(Self::Type {
@@ -45,6 +120,13 @@ impl ProtoRepr for proto::ObjectStore {
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("max_retries")?,
             local_mirror_path: self.local_mirror_path.clone(),
+            tiers: self
+                .tiers
+                .iter()
+                .enumerate()
+                .map(|(i, tier)| tier.read().context(i))
+                .collect::<anyhow::Result<_>>()
+                .context("tiers")?,
         })
     }
 
@@ -76,12 +158,66 @@ impl ProtoRepr for proto::ObjectStore {
             } => proto::object_store::Mode::FileBacked(proto::object_store::FileBacked {
                 file_backed_base_path: Some(file_backed_base_path.clone()),
             }),
+            ObjectStoreMode::S3 {
+                endpoint,
+                region,
+                bucket_name,
+                addressing_style,
+                server_side_encryption,
+                sse_kms_key_id,
+                multipart_part_size_bytes,
+            } => proto::object_store::Mode::S3(proto::object_store::S3 {
+                endpoint: Some(endpoint.clone()),
+                region: Some(region.clone()),
+                bucket_name: Some(bucket_name.clone()),
+                addressing_style: Some(proto::S3AddressingStyle::new(addressing_style).into()),
+                server_side_encryption: server_side_encryption
+                    .as_ref()
+                    .map(|sse| proto::S3ServerSideEncryption::new(sse).into()),
+                sse_kms_key_id: sse_kms_key_id.clone(),
+                multipart_part_size_bytes: *multipart_part_size_bytes,
+            }),
         };
 
         Self {
             mode: Some(mode),
             max_retries: Some(this.max_retries.into()),
             local_mirror_path: this.local_mirror_path.clone(),
+            tiers: this.tiers.iter().map(ProtoRepr::build).collect(),
+        }
+    }
+}
+
+impl ProtoRepr for proto::ObjectStoreTier {
+    type Type = ObjectStoreTierConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            artifact_class: required(&self.artifact_class)
+                .and_then(|x| Ok(proto::ArtifactClass::try_from(*x)?))
+                .context("artifact_class")?
+                .parse(),
+            store: read_required_repr(&self.store).context("store")?,
+            cold_store: self
+                .cold_store
+                .as_ref()
+                .map(ProtoRepr::read)
+                .transpose()
+                .context("cold_store")?,
+            move_to_cold_after_days: self.move_to_cold_after_days,
+            expire_after_days: self.expire_after_days,
+            min_cold_object_size_bytes: self.min_cold_object_size_bytes,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            artifact_class: Some(proto::ArtifactClass::new(&this.artifact_class).into()),
+            store: Some(ProtoRepr::build(&this.store)),
+            cold_store: this.cold_store.as_ref().map(ProtoRepr::build),
+            move_to_cold_after_days: this.move_to_cold_after_days,
+            expire_after_days: this.expire_after_days,
+            min_cold_object_size_bytes: this.min_cold_object_size_bytes,
         }
     }
 }
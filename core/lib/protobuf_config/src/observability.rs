@@ -1,4 +1,5 @@
 use anyhow::Context as _;
+use zksync_basic_types::L2ChainId;
 use zksync_config::configs::{self};
 use zksync_protobuf::{required, ProtoRepr};
 
@@ -32,6 +33,7 @@ impl ProtoRepr for proto::Observability {
             log_format: required(&self.log_format).context("log_format")?.clone(),
             opentelemetry: self.opentelemetry.as_ref().and_then(|cfg| cfg.read().ok()),
             log_directives: self.log_directives.clone(),
+            log_correlation_ids: self.log_correlation_ids.unwrap_or(false),
         })
     }
 
@@ -51,6 +53,7 @@ impl ProtoRepr for proto::Observability {
             log_format: Some(this.log_format.clone()),
             opentelemetry: this.opentelemetry.as_ref().map(ProtoRepr::build),
             log_directives: this.log_directives.clone(),
+            log_correlation_ids: Some(this.log_correlation_ids),
         }
     }
 }
@@ -63,6 +66,12 @@ impl ProtoRepr for proto::Opentelemetry {
             level: required(&self.level).context("level")?.clone(),
             endpoint: required(&self.endpoint).context("endpoint")?.clone(),
             logs_endpoint: self.logs_endpoint.clone(),
+            sampling_ratio: self.sampling_ratio,
+            l2_chain_id: self
+                .l2_chain_id
+                .map(|id| L2ChainId::try_from(id).map_err(|err| anyhow::anyhow!(err)))
+                .transpose()
+                .context("l2_chain_id")?,
         })
     }
 
@@ -71,6 +80,8 @@ impl ProtoRepr for proto::Opentelemetry {
             level: Some(this.level.clone()),
             endpoint: Some(this.endpoint.clone()),
             logs_endpoint: this.logs_endpoint.clone(),
+            sampling_ratio: this.sampling_ratio,
+            l2_chain_id: this.l2_chain_id.map(|id| id.as_u64()),
         }
     }
 }
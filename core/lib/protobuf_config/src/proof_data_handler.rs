@@ -35,6 +35,12 @@ impl ProtoRepr for proto::ProofDataHandler {
                     .unwrap_or_else(
                         configs::TeeConfig::default_tee_batch_permanently_ignored_timeout_in_hours,
                     ),
+                tee_attestation_verification: self
+                    .tee_attestation_verification
+                    .unwrap_or_else(configs::TeeConfig::default_tee_attestation_verification),
+                tee_max_tcb_age_in_days: self
+                    .tee_max_tcb_age_in_days
+                    .unwrap_or_else(configs::TeeConfig::default_tee_max_tcb_age_in_days),
             },
         })
     }
@@ -53,6 +59,8 @@ impl ProtoRepr for proto::ProofDataHandler {
                     .tee_batch_permanently_ignored_timeout_in_hours
                     .into(),
             ),
+            tee_attestation_verification: Some(this.tee_config.tee_attestation_verification),
+            tee_max_tcb_age_in_days: Some(this.tee_config.tee_max_tcb_age_in_days),
         }
     }
 }
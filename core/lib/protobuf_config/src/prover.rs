@@ -33,6 +33,16 @@ impl ProtoRepr for proto::ProofCompressor {
                 .clone(),
             verify_wrapper_proof: *required(&self.verify_wrapper_proof)
                 .context("verify_wrapper_proof")?,
+            heartbeat_interval_in_secs: self
+                .heartbeat_interval_in_secs
+                .map(|x| x.try_into())
+                .transpose()
+                .context("heartbeat_interval_in_secs")?,
+            heartbeat_timeout_in_secs: self
+                .heartbeat_timeout_in_secs
+                .map(|x| x.try_into())
+                .transpose()
+                .context("heartbeat_timeout_in_secs")?,
         })
     }
 
@@ -47,6 +57,8 @@ impl ProtoRepr for proto::ProofCompressor {
             universal_setup_path: Some(this.universal_setup_path.clone()),
             universal_setup_download_url: Some(this.universal_setup_download_url.clone()),
             verify_wrapper_proof: Some(this.verify_wrapper_proof),
+            heartbeat_interval_in_secs: this.heartbeat_interval_in_secs.map(Into::into),
+            heartbeat_timeout_in_secs: this.heartbeat_timeout_in_secs.map(Into::into),
         }
     }
 }
@@ -367,6 +379,7 @@ impl ProtoRepr for proto::Prover {
                 .context("cloud_type")?
                 .map(|x| x.parse())
                 .unwrap_or_default(),
+            setup_data_download_url: self.setup_data_download_url.clone(),
         })
     }
 
@@ -386,6 +399,7 @@ impl ProtoRepr for proto::Prover {
             prover_object_store: this.prover_object_store.as_ref().map(ProtoRepr::build),
             public_object_store: this.public_object_store.as_ref().map(ProtoRepr::build),
             cloud_type: Some(proto::CloudType::new(&this.cloud_type).into()),
+            setup_data_download_url: this.setup_data_download_url.clone(),
         }
     }
 }
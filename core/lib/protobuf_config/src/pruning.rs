@@ -14,6 +14,8 @@ impl ProtoRepr for proto::Pruning {
             chunk_size: self.chunk_size,
             removal_delay_sec: self.removal_delay_sec.and_then(NonZeroU64::new),
             data_retention_sec: self.data_retention_sec,
+            dry_run: self.dry_run.unwrap_or_default(),
+            max_data_age_sec: self.max_data_age_sec.and_then(NonZeroU64::new),
         })
     }
 
@@ -23,6 +25,8 @@ impl ProtoRepr for proto::Pruning {
             chunk_size: this.chunk_size,
             removal_delay_sec: this.removal_delay_sec.map(|a| a.get()),
             data_retention_sec: this.data_retention_sec,
+            dry_run: Some(this.dry_run),
+            max_data_age_sec: this.max_data_age_sec.map(NonZeroU64::get),
         }
     }
 }
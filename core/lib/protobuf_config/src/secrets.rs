@@ -7,8 +7,10 @@ use zksync_basic_types::{
     url::SensitiveUrl,
 };
 use zksync_config::configs::{
+    admin_api::AdminApiSecrets,
     consensus::{AttesterSecretKey, ConsensusSecrets, NodeSecretKey, ValidatorSecretKey},
     da_client::{avail::AvailSecrets, celestia::CelestiaSecrets, eigen::EigenSecrets},
+    object_store::S3Secrets,
     secrets::{DataAvailabilitySecrets, Secrets},
     DatabaseSecrets, L1Secrets,
 };
@@ -28,6 +30,8 @@ impl ProtoRepr for proto::Secrets {
             database: read_optional_repr(&self.database),
             l1: read_optional_repr(&self.l1),
             data_availability: read_optional_repr(&self.da),
+            admin_api: read_optional_repr(&self.admin_api),
+            s3: read_optional_repr(&self.s3),
         })
     }
 
@@ -37,6 +41,56 @@ impl ProtoRepr for proto::Secrets {
             l1: this.l1.as_ref().map(ProtoRepr::build),
             consensus: this.consensus.as_ref().map(ProtoRepr::build),
             da: this.data_availability.as_ref().map(ProtoRepr::build),
+            admin_api: this.admin_api.as_ref().map(ProtoRepr::build),
+            s3: this.s3.as_ref().map(ProtoRepr::build),
+        }
+    }
+}
+
+impl ProtoRepr for proto::S3Secrets {
+    type Type = S3Secrets;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            access_key_id: required(&self.access_key_id)
+                .context("access_key_id")?
+                .clone(),
+            secret_access_key: APIKey::from_str(
+                required(&self.secret_access_key).context("secret_access_key")?,
+            )?,
+            session_token: self
+                .session_token
+                .as_deref()
+                .map(APIKey::from_str)
+                .transpose()
+                .context("session_token")?,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            access_key_id: Some(this.access_key_id.clone()),
+            secret_access_key: Some(this.secret_access_key.0.expose_secret().clone()),
+            session_token: this
+                .session_token
+                .as_ref()
+                .map(|token| token.0.expose_secret().clone()),
+        }
+    }
+}
+
+impl ProtoRepr for proto::AdminApiSecrets {
+    type Type = AdminApiSecrets;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            auth_token: APIKey::from_str(required(&self.auth_token).context("auth_token")?)?,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            auth_token: Some(this.auth_token.0.expose_secret().clone()),
         }
     }
 }
@@ -56,6 +110,13 @@ impl ProtoRepr for proto::DatabaseSecrets {
             .map(str::parse::<SensitiveUrl>)
             .transpose()
             .context("replica_url")?;
+        let server_replica_urls = self
+            .server_replica_urls
+            .iter()
+            .enumerate()
+            .map(|(i, url)| url.parse::<SensitiveUrl>().context(i))
+            .collect::<anyhow::Result<_>>()
+            .context("server_replica_urls")?;
         let prover_url = self
             .prover_url
             .as_deref()
@@ -66,6 +127,7 @@ impl ProtoRepr for proto::DatabaseSecrets {
             server_url,
             prover_url,
             server_replica_url,
+            server_replica_urls,
         })
     }
 
@@ -76,6 +138,11 @@ impl ProtoRepr for proto::DatabaseSecrets {
                 .server_replica_url
                 .as_ref()
                 .map(|a| a.expose_str().to_string()),
+            server_replica_urls: this
+                .server_replica_urls
+                .iter()
+                .map(|a| a.expose_str().to_string())
+                .collect(),
             prover_url: this.prover_url.as_ref().map(|a| a.expose_str().to_string()),
         }
     }
@@ -1,13 +1,14 @@
 use std::num::NonZeroUsize;
 
-use zksync_basic_types::L1BatchNumber;
+use anyhow::Context as _;
+use zksync_basic_types::{url::SensitiveUrl, L1BatchNumber};
 use zksync_config::configs::{
     snapshot_recovery::{PostgresRecoveryConfig, TreeRecoveryConfig},
     SnapshotRecoveryConfig,
 };
 use zksync_protobuf::ProtoRepr;
 
-use crate::{proto::snapshot_recovery as proto, read_optional_repr};
+use crate::{parse_h256, proto::snapshot_recovery as proto, read_optional_repr};
 
 impl ProtoRepr for proto::Postgres {
     type Type = PostgresRecoveryConfig;
@@ -56,6 +57,18 @@ impl ProtoRepr for proto::SnapshotRecovery {
             tree,
             postgres: read_optional_repr(&self.postgres).unwrap_or_default(),
             l1_batch: self.l1_batch.map(L1BatchNumber),
+            manifest_hash: self
+                .manifest_hash
+                .as_deref()
+                .map(parse_h256)
+                .transpose()
+                .context("manifest_hash")?,
+            peer_url: self
+                .peer_url
+                .as_deref()
+                .map(str::parse::<SensitiveUrl>)
+                .transpose()
+                .context("peer_url")?,
             object_store: read_optional_repr(&self.object_store),
             drop_storage_key_preimages: self
                 .experimental
@@ -93,6 +106,11 @@ impl ProtoRepr for proto::SnapshotRecovery {
             tree,
             experimental,
             l1_batch: this.l1_batch.map(|a| a.0),
+            manifest_hash: this.manifest_hash.map(|x| format!("{:?}", x)),
+            peer_url: this
+                .peer_url
+                .as_ref()
+                .map(|url| url.expose_str().to_string()),
             object_store: this.object_store.as_ref().map(ProtoRepr::build),
         }
     }
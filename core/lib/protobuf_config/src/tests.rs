@@ -23,6 +23,9 @@ fn test_encoding() {
     test_encode_all_formats::<ReprConv<proto::secrets::ConsensusSecrets>>(rng);
     test_encode_all_formats::<ReprConv<proto::secrets::Secrets>>(rng);
     test_encode_all_formats::<ReprConv<proto::contract_verifier::ContractVerifier>>(rng);
+    test_encode_all_formats::<ReprConv<proto::admin_api::AdminApi>>(rng);
+    test_encode_all_formats::<ReprConv<proto::leader_election::LeaderElection>>(rng);
+    test_encode_all_formats::<ReprConv<proto::withdrawal_finalizer::WithdrawalFinalizer>>(rng);
     test_encode_all_formats::<ReprConv<proto::contracts::Contracts>>(rng);
     test_encode_all_formats::<ReprConv<proto::database::MerkleTree>>(rng);
     test_encode_all_formats::<ReprConv<proto::database::Db>>(rng);
@@ -1,7 +1,9 @@
 use anyhow::Context;
 use zksync_config::configs::{
     self,
-    wallets::{AddressWallet, EthSender, StateKeeper, TokenMultiplierSetter, Wallet},
+    wallets::{
+        AddressWallet, EthSender, StateKeeper, TokenMultiplierSetter, Wallet, WithdrawalFinalizer,
+    },
 };
 use zksync_protobuf::{required, ProtoRepr};
 use zksync_types::{Address, K256PrivateKey};
@@ -71,10 +73,26 @@ impl ProtoRepr for proto::Wallets {
                 None
             };
 
+        let withdrawal_finalizer = if let Some(withdrawal_finalizer) = &self.withdrawal_finalizer {
+            let wallet = Wallet::from_private_key_bytes(
+                parse_h256(
+                    required(&withdrawal_finalizer.private_key).context("withdrawal_finalizer")?,
+                )?,
+                withdrawal_finalizer
+                    .address
+                    .as_ref()
+                    .and_then(|a| parse_h160(a).ok()),
+            )?;
+            Some(WithdrawalFinalizer { wallet })
+        } else {
+            None
+        };
+
         Ok(Self::Type {
             eth_sender,
             state_keeper,
             token_multiplier_setter,
+            withdrawal_finalizer,
         })
     }
 
@@ -119,11 +137,22 @@ impl ProtoRepr for proto::Wallets {
                     )
                 });
 
+        let withdrawal_finalizer = this
+            .withdrawal_finalizer
+            .as_ref()
+            .map(|withdrawal_finalizer| {
+                create_pk_wallet(
+                    withdrawal_finalizer.wallet.address(),
+                    withdrawal_finalizer.wallet.private_key(),
+                )
+            });
+
         Self {
             blob_operator,
             operator,
             fee_account,
             token_multiplier_setter,
+            withdrawal_finalizer,
         }
     }
 }
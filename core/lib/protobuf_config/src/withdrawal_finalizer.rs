@@ -0,0 +1,78 @@
+use anyhow::Context;
+use zksync_config::configs::{self, withdrawal_finalizer::WithdrawalTokenLimit};
+use zksync_protobuf::{required, ProtoRepr};
+use zksync_types::{L1BatchNumber, U256};
+
+use crate::{parse_h160, proto::withdrawal_finalizer as proto};
+
+impl ProtoRepr for proto::WithdrawalFinalizer {
+    type Type = configs::WithdrawalFinalizerConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            poll_interval_ms: self
+                .poll_interval_ms
+                .unwrap_or(Self::Type::default_poll_interval_ms()),
+            max_tx_gas: self.max_tx_gas.unwrap_or(Self::Type::default_max_tx_gas()),
+            default_priority_fee_per_gas: self
+                .default_priority_fee_per_gas
+                .unwrap_or(Self::Type::default_priority_fee_per_gas()),
+            resend_after_l1_blocks: self
+                .resend_after_l1_blocks
+                .unwrap_or(Self::Type::default_resend_after_l1_blocks()),
+            token_limits: self
+                .token_limits
+                .iter()
+                .enumerate()
+                .map(|(i, limit)| limit.read().context(i))
+                .collect::<anyhow::Result<_>>()
+                .context("token_limits")?,
+            max_finalizations_per_hour: self
+                .max_finalizations_per_hour
+                .unwrap_or(Self::Type::default_max_finalizations_per_hour()),
+            start_l1_batch: self.start_l1_batch.map(L1BatchNumber),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            poll_interval_ms: Some(this.poll_interval_ms),
+            max_tx_gas: Some(this.max_tx_gas),
+            default_priority_fee_per_gas: Some(this.default_priority_fee_per_gas),
+            resend_after_l1_blocks: Some(this.resend_after_l1_blocks),
+            token_limits: this.token_limits.iter().map(ProtoRepr::build).collect(),
+            max_finalizations_per_hour: Some(this.max_finalizations_per_hour),
+            start_l1_batch: this.start_l1_batch.map(|number| number.0),
+        }
+    }
+}
+
+impl ProtoRepr for proto::WithdrawalTokenLimit {
+    type Type = WithdrawalTokenLimit;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            l1_token_address: required(&self.l1_token_address)
+                .and_then(|x| parse_h160(x))
+                .context("l1_token_address")?,
+            min_amount: self
+                .min_amount
+                .as_deref()
+                .map(U256::from_dec_str)
+                .transpose()
+                .context("min_amount")?
+                .unwrap_or_default(),
+            max_amount: required(&self.max_amount)
+                .and_then(|x| Ok(U256::from_dec_str(x)?))
+                .context("max_amount")?,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            l1_token_address: Some(format!("{:?}", this.l1_token_address)),
+            min_amount: Some(this.min_amount.to_string()),
+            max_amount: Some(this.max_amount.to_string()),
+        }
+    }
+}
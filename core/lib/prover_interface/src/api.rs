@@ -6,7 +6,7 @@ use serde_with::{hex::Hex, serde_as};
 use zksync_types::{
     protocol_version::{L1VerifierConfig, ProtocolSemanticVersion},
     tee_types::TeeType,
-    L1BatchNumber,
+    L1BatchNumber, H256, U256,
 };
 
 use crate::{
@@ -33,6 +33,71 @@ pub enum ProofGenerationDataResponse {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TeeProofGenerationDataResponse(pub Box<TeeVerifierInput>);
 
+/// Binary artifact transferred in chunks via the v2 proof data handler API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactInfo {
+    /// Size of the artifact in bytes.
+    pub size: u64,
+    /// Keccak-256 hash of the artifact bytes.
+    pub content_hash: H256,
+}
+
+/// Proof generation data locked for proving by the v2 API. The data itself is a bincode-serialized
+/// [`ProofGenerationData`] that can be downloaded using range requests.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProofGenerationDataInfo {
+    pub l1_batch_number: L1BatchNumber,
+    pub artifact: ArtifactInfo,
+}
+
+/// State of a resumable upload in the v2 API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UploadStatus {
+    pub artifact: ArtifactInfo,
+    /// Number of bytes received so far. The next uploaded chunk must start at this offset.
+    pub offset: u64,
+}
+
+/// Self-contained data allowing to verify the final proof for an L1 batch executed on L1 off-chain,
+/// e.g. by third-party auditors.
+///
+/// The proof is verified against the `public_input` using the SNARK wrapper verification key with the hash
+/// from `l1_verifier_config`. The public input is derived from the commitments of the batch and the previous batch
+/// the same way as the L1 contract does it: `uint256(keccak256(previous_batch_commitment ++ commitment)) >> 32`.
+/// The batch commitment can be recomputed from the preimages in `commitment`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofVerificationBundle {
+    pub l1_batch_number: L1BatchNumber,
+    pub protocol_version: ProtocolSemanticVersion,
+    pub l1_verifier_config: L1VerifierConfig,
+    pub proof: Box<L1BatchProofForL1>,
+    pub public_input: U256,
+    pub previous_batch_commitment: H256,
+    pub commitment: BatchCommitmentPreimage,
+}
+
+/// Batch commitment together with its preimage: `commitment = keccak256(pass_through_data_hash ++ meta_parameters_hash ++ aux_data_hash)`.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchCommitmentPreimage {
+    pub commitment: H256,
+    pub pass_through_data_hash: H256,
+    pub meta_parameters_hash: H256,
+    pub aux_data_hash: H256,
+    /// State root hash after the batch; committed to in the pass-through data.
+    pub root_hash: H256,
+    /// Index of the last leaf in the state tree after the batch; committed to in the pass-through data.
+    pub rollup_last_leaf_index: u64,
+    /// Preimage of `meta_parameters_hash`: `zkporter_is_available` flag, bootloader, default AA and (since
+    /// protocol version 1.5.0) EVM emulator code hashes.
+    #[serde_as(as = "Hex")]
+    pub meta_parameters: Vec<u8>,
+    /// Preimage of `aux_data_hash`: system logs linear hash, state diffs hash, bootloader initial content
+    /// and events queue commitments, followed by linear hashes and commitments of pubdata blobs.
+    #[serde_as(as = "Hex")]
+    pub aux_data: Vec<u8>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub enum SubmitProofResponse {
     Success,
@@ -72,6 +137,14 @@ pub struct VerifyProofRequest(pub Box<L1BatchProofForL1>);
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct SubmitTeeProofRequest(pub Box<L1BatchTeeProofForL1>);
 
+/// Enclave measurements (SGX `MRENCLAVE` or TDX `MRTD`) allowed in TEE attestation quotes.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TeeAllowedMeasurements {
+    #[serde_as(as = "Vec<Hex>")]
+    pub measurements: Vec<Vec<u8>>,
+}
+
 #[serde_as]
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct RegisterTeeAttestationRequest {
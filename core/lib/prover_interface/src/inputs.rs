@@ -1,6 +1,10 @@
-use std::{collections::HashMap, convert::TryInto, fmt::Debug};
+use std::{
+    collections::{HashMap, VecDeque},
+    convert::TryInto,
+    fmt::Debug,
+};
 
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use serde_with::{serde_as, Bytes};
 use zksync_object_store::{_reexports::BoxedError, serialize_using_bincode, Bucket, StoredObject};
 use zksync_types::{
@@ -115,24 +119,122 @@ impl WitnessInputMerklePaths {
         self.merkle_paths.push(path);
     }
 
-    /// Converts this job into an iterator over the contained Merkle paths.
+    /// Converts this job into an iterator over the contained Merkle paths. Paths are expanded lazily
+    /// as the iterator is advanced.
     pub fn into_merkle_paths(self) -> impl ExactSizeIterator<Item = StorageLogMetadata> {
-        let mut merkle_paths = self.merkle_paths;
-        if let [first, rest @ ..] = merkle_paths.as_mut_slice() {
-            for path in rest {
-                assert!(
-                    path.merkle_paths.len() <= first.merkle_paths.len(),
-                    "Merkle paths in `PrepareBasicCircuitsJob` are malformed; the first path is not \
-                     the longest one"
-                );
-                let spliced_len = first.merkle_paths.len() - path.merkle_paths.len();
-                let spliced_hashes = &first.merkle_paths[0..spliced_len];
-                path.merkle_paths
-                    .splice(0..0, spliced_hashes.iter().cloned());
-                debug_assert_eq!(path.merkle_paths.len(), first.merkle_paths.len());
+        self.into_compact_merkle_paths()
+    }
+
+    /// Converts this job into Merkle paths retaining the compact form.
+    pub fn into_compact_merkle_paths(self) -> CompactMerklePaths {
+        let reference_path = self
+            .merkle_paths
+            .first()
+            .map(|path| path.merkle_paths.clone())
+            .unwrap_or_default();
+        CompactMerklePaths {
+            reference_path,
+            paths: self.merkle_paths.into(),
+        }
+    }
+}
+
+/// Merkle paths in the compact form (see [`WitnessInputMerklePaths`] docs) that are expanded one by one
+/// when iterated over. Unlike expanding all paths at once, this keeps memory usage close to the compact form,
+/// which matters for large L1 batches since a full Merkle path takes 8 KiB.
+///
+/// Note that the compact paths for the entire batch are still loaded into memory; they are not streamed
+/// from the object store.
+///
+/// # Serialization
+///
+/// Paths are serialized in the expanded form, i.e. exactly as `Vec<StorageLogMetadata>`, which was used
+/// to store pending logs before this type was introduced. Hence, the serialization format of types
+/// containing this type is unchanged, and previously serialized data can be deserialized.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactMerklePaths {
+    /// Full Merkle path of the first log, which is used to restore skipped hashes in the following paths.
+    reference_path: Vec<[u8; HASH_LEN]>,
+    paths: VecDeque<StorageLogMetadata>,
+}
+
+impl CompactMerklePaths {
+    /// Iterates over the remaining logs. Merkle paths in the returned logs are *not* expanded.
+    pub fn iter(&self) -> impl Iterator<Item = &StorageLogMetadata> + '_ {
+        self.paths.iter()
+    }
+
+    /// Mutably iterates over the remaining logs. Merkle paths in the returned logs are *not* expanded.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut StorageLogMetadata> + '_ {
+        self.paths.iter_mut()
+    }
+
+    /// Returns a reference to the next log without expanding its Merkle path.
+    pub fn front(&self) -> Option<&StorageLogMetadata> {
+        self.paths.front()
+    }
+
+    /// Returns a copy of the next log with the expanded Merkle path without advancing the iterator.
+    pub fn peek_expanded(&self) -> Option<StorageLogMetadata> {
+        let log = self.paths.front()?.clone();
+        Some(self.expand(log))
+    }
+
+    fn expand(&self, mut log: StorageLogMetadata) -> StorageLogMetadata {
+        assert!(
+            log.merkle_paths.len() <= self.reference_path.len(),
+            "Merkle paths in `PrepareBasicCircuitsJob` are malformed; the first path is not \
+             the longest one"
+        );
+        let spliced_len = self.reference_path.len() - log.merkle_paths.len();
+        let spliced_hashes = &self.reference_path[0..spliced_len];
+        log.merkle_paths
+            .splice(0..0, spliced_hashes.iter().cloned());
+        log
+    }
+}
+
+impl Iterator for CompactMerklePaths {
+    type Item = StorageLogMetadata;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let log = self.paths.pop_front()?;
+        Some(self.expand(log))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.paths.len(), Some(self.paths.len()))
+    }
+}
+
+impl ExactSizeIterator for CompactMerklePaths {}
+
+impl Serialize for CompactMerklePaths {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.paths.iter().map(|log| self.expand(log.clone())))
+    }
+}
+
+impl<'de> Deserialize<'de> for CompactMerklePaths {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let logs = Vec::<StorageLogMetadata>::deserialize(deserializer)?;
+        if let Some(first_log) = logs.first() {
+            let path_len = first_log.merkle_paths.len();
+            if let Some(log) = logs.iter().find(|log| log.merkle_paths.len() != path_len) {
+                return Err(de::Error::custom(format_args!(
+                    "Merkle paths have differing lengths: {path_len} and {}",
+                    log.merkle_paths.len()
+                )));
             }
         }
-        merkle_paths.into_iter()
+
+        // The enumeration index is not used by compact paths.
+        let mut job = WitnessInputMerklePaths::new(0);
+        job.reserve(logs.len());
+        for log in logs {
+            job.push_merkle_path(log);
+        }
+        Ok(job.into_compact_merkle_paths())
     }
 }
 
@@ -346,4 +448,88 @@ mod tests {
         let logs_from_job: Vec<_> = job.into_merkle_paths().collect();
         assert_eq!(logs_from_job, logs);
     }
+
+    #[test]
+    fn compact_merkle_paths_are_expanded_on_demand() {
+        let zero_hash = [0_u8; 32];
+        let mut job = WitnessInputMerklePaths::new(4);
+        for i in 0..3 {
+            let mut merkle_paths = vec![zero_hash; 255];
+            merkle_paths.push([i; 32]);
+            job.push_merkle_path(StorageLogMetadata {
+                root_hash: zero_hash,
+                is_write: i != 1,
+                first_write: false,
+                merkle_paths,
+                leaf_hashed_key: U256::from(i),
+                leaf_enumeration_index: i.into(),
+                value_written: [0; 32],
+                value_read: [0; 32],
+            });
+        }
+
+        let mut paths = job.into_compact_merkle_paths();
+        assert_eq!(paths.len(), 3);
+        let compact_lengths: Vec<_> = paths.iter().map(|log| log.merkle_paths.len()).collect();
+        assert_eq!(compact_lengths, [256, 1, 1]);
+
+        paths.next().unwrap();
+        assert!(!paths.front().unwrap().is_write);
+        let peeked = paths.peek_expanded().unwrap();
+        assert_eq!(peeked.merkle_paths.len(), 256);
+        assert_eq!(peeked.merkle_paths[255], [1; 32]);
+        // Peeking must not advance the iterator.
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths.next().unwrap(), peeked);
+
+        let last = paths.next().unwrap();
+        assert_eq!(last.merkle_paths[..255], [zero_hash; 255]);
+        assert_eq!(last.merkle_paths[255], [2; 32]);
+        assert!(paths.next().is_none());
+    }
+
+    #[test]
+    fn compact_merkle_paths_are_serialized_as_expanded_logs() {
+        let zero_hash = [0_u8; 32];
+        let logs: Vec<_> = (0..3)
+            .map(|i| {
+                let mut merkle_paths = vec![zero_hash; 255];
+                merkle_paths.push([i; 32]);
+                StorageLogMetadata {
+                    root_hash: [i; 32],
+                    is_write: true,
+                    first_write: false,
+                    merkle_paths,
+                    leaf_hashed_key: U256::from(i),
+                    leaf_enumeration_index: i.into(),
+                    value_written: [0; 32],
+                    value_read: [0; 32],
+                }
+            })
+            .collect();
+        let mut job = WitnessInputMerklePaths::new(4);
+        for log in logs.clone() {
+            job.push_merkle_path(log);
+        }
+        let mut paths = job.into_compact_merkle_paths();
+        paths.next().unwrap();
+
+        let serialized = zksync_object_store::bincode::serialize(&paths).unwrap();
+        let expected_serialized = zksync_object_store::bincode::serialize(&logs[1..]).unwrap();
+        assert_eq!(serialized, expected_serialized);
+
+        let deserialized: CompactMerklePaths =
+            zksync_object_store::bincode::deserialize(&serialized).unwrap();
+        let compact_lengths: Vec<_> = deserialized
+            .iter()
+            .map(|log| log.merkle_paths.len())
+            .collect();
+        assert_eq!(compact_lengths, [256, 1]);
+        assert_eq!(deserialized.collect::<Vec<_>>(), logs[1..]);
+
+        let mut malformed_logs = logs;
+        malformed_logs[1].merkle_paths.pop();
+        let serialized = zksync_object_store::bincode::serialize(&malformed_logs).unwrap();
+        zksync_object_store::bincode::deserialize::<CompactMerklePaths>(&serialized).unwrap_err();
+    }
 }
@@ -10,7 +10,7 @@ use serde::Serialize;
 use tokio::sync::{watch, Semaphore};
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError, SqlxError};
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
-use zksync_object_store::{ObjectStore, ObjectStoreError};
+use zksync_object_store::{ObjectStore, ObjectStoreError, StoredObject};
 use zksync_types::{
     api,
     bytecode::BytecodeHash,
@@ -254,6 +254,7 @@ pub struct SnapshotApplierTaskStats {
 #[derive(Debug)]
 pub struct SnapshotsApplierTask {
     snapshot_l1_batch: Option<L1BatchNumber>,
+    expected_manifest_hash: Option<H256>,
     drop_storage_key_preimages: bool,
     config: SnapshotsApplierConfig,
     health_updater: HealthUpdater,
@@ -271,6 +272,7 @@ impl SnapshotsApplierTask {
     ) -> Self {
         Self {
             snapshot_l1_batch: None,
+            expected_manifest_hash: None,
             drop_storage_key_preimages: false,
             config,
             health_updater: ReactiveHealthCheck::new("snapshot_recovery").1,
@@ -324,6 +326,14 @@ impl SnapshotsApplierTask {
         self.snapshot_l1_batch = Some(number);
     }
 
+    /// Pins the expected manifest hash of the snapshot (see [`SnapshotHeader::compute_manifest_hash()`]),
+    /// obtained from a trusted source. The manifest hash returned by the main node is unsigned, so without pinning,
+    /// it only protects against accidental corruption of the snapshot, and not against a compromised main node
+    /// or object store. With a pinned hash, recovery fails if the snapshot manifest doesn't match it.
+    pub fn set_expected_manifest_hash(&mut self, hash: H256) {
+        self.expected_manifest_hash = Some(hash);
+    }
+
     /// Enables dropping storage key preimages when recovering storage logs from a snapshot with version 0.
     /// This is a temporary flag that will eventually be removed together with version 0 snapshot support.
     pub fn drop_storage_key_preimages(&mut self) {
@@ -335,6 +345,89 @@ impl SnapshotsApplierTask {
         self.health_updater.subscribe()
     }
 
+    /// Performs a pre-flight check of the recovery source, i.e. that the main node has the snapshot to recover from,
+    /// the snapshot version is supported, and the snapshot data is accessible in the object store. This allows
+    /// to fail fast on misconfiguration (e.g., a wrong object store bucket) before the node starts writing to the DB.
+    ///
+    /// The check is skipped if the recovery was already started. Transient errors (e.g., network errors) are logged,
+    /// but do not fail the check since they are retried during recovery.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the recovery source is unavailable.
+    pub async fn check_source_availability(&self) -> anyhow::Result<()> {
+        match self.check_source_availability_inner().await {
+            Ok(()) => Ok(()),
+            Err(SnapshotsApplierError::Fatal(err)) => Err(err),
+            Err(SnapshotsApplierError::Retryable(err)) => {
+                tracing::warn!(
+                    "Transient error checking snapshot recovery source; continuing anyway: {err:#}"
+                );
+                Ok(())
+            }
+            Err(SnapshotsApplierError::Canceled) => Ok(()),
+        }
+    }
+
+    async fn check_source_availability_inner(&self) -> Result<(), SnapshotsApplierError> {
+        let mut storage = self
+            .connection_pool
+            .connection_tagged("snapshots_applier")
+            .await?;
+        let applied_snapshot_status = storage
+            .snapshot_recovery_dal()
+            .get_applied_snapshot_status()
+            .await?;
+        drop(storage);
+        if let Some(status) = applied_snapshot_status {
+            tracing::info!(
+                "Snapshot recovery was already started from L1 batch #{}; skipping source check",
+                status.l1_batch_number
+            );
+            return Ok(());
+        }
+
+        let l1_batch_number = match self.snapshot_l1_batch {
+            Some(number) => number,
+            None => self
+                .main_node_client
+                .fetch_newest_snapshot_l1_batch_number()
+                .await?
+                .context("no snapshots on main node; snapshot recovery is impossible")?,
+        };
+        let snapshot = self
+            .main_node_client
+            .fetch_snapshot(l1_batch_number)
+            .await?
+            .with_context(|| {
+                format!("snapshot for L1 batch #{l1_batch_number} is not present on main node")
+            })?;
+        SnapshotRecoveryStrategy::check_snapshot_version(snapshot.version)?;
+        SnapshotRecoveryStrategy::check_snapshot_manifest(&snapshot, self.expected_manifest_hash)?;
+
+        if !snapshot.storage_logs_chunks.is_empty() {
+            // Check a single storage logs chunk; it's significantly smaller than factory deps.
+            let key = SnapshotStorageLogsStorageKey {
+                l1_batch_number,
+                chunk_id: 0,
+            };
+            let key_str = <SnapshotStorageLogsChunk>::encode_key(key);
+            self.blob_store
+                .get_raw(<SnapshotStorageLogsChunk>::BUCKET, &key_str)
+                .await
+                .map_err(|err| {
+                    let context = format!(
+                        "cannot access storage logs {key:?} in object store; check the snapshot object store configuration"
+                    );
+                    SnapshotsApplierError::object_store(err, context)
+                })?;
+        }
+        tracing::info!(
+            "Checked snapshot recovery source: snapshot for L1 batch #{l1_batch_number} is available"
+        );
+        Ok(())
+    }
+
     /// Runs the snapshot applier with these options.
     ///
     /// # Errors
@@ -419,11 +512,14 @@ enum SnapshotRecoveryStrategy {
 }
 
 impl SnapshotRecoveryStrategy {
+    /// Returns the chosen strategy, the recovery status and expected checksums of storage log chunks
+    /// (empty if the recovery is completed).
     async fn new(
         storage: &mut Connection<'_, Core>,
         main_node_client: &dyn SnapshotsApplierMainNodeClient,
         snapshot_l1_batch: Option<L1BatchNumber>,
-    ) -> Result<(Self, SnapshotRecoveryStatus), SnapshotsApplierError> {
+        expected_manifest_hash: Option<H256>,
+    ) -> Result<(Self, SnapshotRecoveryStatus, Vec<Option<H256>>), SnapshotsApplierError> {
         let latency =
             METRICS.initial_stage_duration[&InitialStage::FetchMetadataFromMainNode].start();
         let applied_snapshot_status = storage
@@ -434,7 +530,7 @@ impl SnapshotRecoveryStrategy {
         if let Some(applied_snapshot_status) = applied_snapshot_status {
             let sealed_l2_block_number = storage.blocks_dal().get_sealed_l2_block_number().await?;
             if sealed_l2_block_number.is_some() {
-                return Ok((Self::Completed, applied_snapshot_status));
+                return Ok((Self::Completed, applied_snapshot_status, vec![]));
             }
 
             let l1_batch_number = applied_snapshot_status.l1_batch_number;
@@ -447,10 +543,25 @@ impl SnapshotRecoveryStrategy {
             // Old snapshots can theoretically be removed by the node, but in this case the snapshot data may be removed as well,
             // so returning an error looks appropriate here.
             let snapshot_version = Self::check_snapshot_version(snapshot_header.version)?;
+            let checksums =
+                Self::check_snapshot_manifest(&snapshot_header, expected_manifest_hash)?;
+            let expected_chunk_count = applied_snapshot_status.storage_logs_chunks_processed.len();
+            if checksums.len() != expected_chunk_count {
+                let err = anyhow::anyhow!(
+                    "snapshot for L1 batch #{l1_batch_number} returned by main node has {} storage log chunks, \
+                     while the recovery status expects {expected_chunk_count}",
+                    checksums.len()
+                );
+                return Err(SnapshotsApplierError::Fatal(err));
+            }
 
             let latency = latency.observe();
             tracing::info!("Re-initialized snapshots applier after reset/failure in {latency:?}");
-            Ok((Self::Resumed(snapshot_version), applied_snapshot_status))
+            Ok((
+                Self::Resumed(snapshot_version),
+                applied_snapshot_status,
+                checksums,
+            ))
         } else {
             let is_genesis_needed = storage.blocks_dal().is_genesis_needed().await?;
             if !is_genesis_needed {
@@ -460,8 +571,13 @@ impl SnapshotRecoveryStrategy {
                 return Err(SnapshotsApplierError::Fatal(err));
             }
 
-            let (recovery_status, snapshot_version) =
-                Self::create_fresh_recovery_status(main_node_client, snapshot_l1_batch).await?;
+            let (recovery_status, snapshot_version, checksums) =
+                Self::create_fresh_recovery_status(
+                    main_node_client,
+                    snapshot_l1_batch,
+                    expected_manifest_hash,
+                )
+                .await?;
 
             let storage_logs_count = storage
                 .storage_logs_dal()
@@ -478,14 +594,16 @@ impl SnapshotRecoveryStrategy {
 
             let latency = latency.observe();
             tracing::info!("Initialized fresh snapshots applier in {latency:?}");
-            Ok((Self::New(snapshot_version), recovery_status))
+            Ok((Self::New(snapshot_version), recovery_status, checksums))
         }
     }
 
     async fn create_fresh_recovery_status(
         main_node_client: &dyn SnapshotsApplierMainNodeClient,
         snapshot_l1_batch: Option<L1BatchNumber>,
-    ) -> Result<(SnapshotRecoveryStatus, SnapshotVersion), SnapshotsApplierError> {
+        expected_manifest_hash: Option<H256>,
+    ) -> Result<(SnapshotRecoveryStatus, SnapshotVersion, Vec<Option<H256>>), SnapshotsApplierError>
+    {
         let l1_batch_number = match snapshot_l1_batch {
             Some(num) => num,
             None => main_node_client
@@ -506,6 +624,7 @@ impl SnapshotRecoveryStrategy {
             chunk_count = snapshot.storage_logs_chunks.len()
         );
         let snapshot_version = Self::check_snapshot_version(snapshot.version)?;
+        let checksums = Self::check_snapshot_manifest(&snapshot, expected_manifest_hash)?;
 
         let l1_batch = main_node_client
             .fetch_l1_batch_details(l1_batch_number)
@@ -543,7 +662,7 @@ impl SnapshotRecoveryStrategy {
             protocol_version,
             storage_logs_chunks_processed: vec![false; snapshot.storage_logs_chunks.len()],
         };
-        Ok((status, snapshot_version))
+        Ok((status, snapshot_version, checksums))
     }
 
     fn check_snapshot_version(raw_version: u16) -> anyhow::Result<SnapshotVersion> {
@@ -559,6 +678,55 @@ impl SnapshotRecoveryStrategy {
         );
         Ok(version)
     }
+
+    /// Checks the snapshot manifest hash (if present) against the header and the pinned hash (if any),
+    /// and returns checksums of storage log chunks ordered by chunk ID.
+    fn check_snapshot_manifest(
+        header: &SnapshotHeader,
+        expected_manifest_hash: Option<H256>,
+    ) -> anyhow::Result<Vec<Option<H256>>> {
+        for (i, chunk) in header.storage_logs_chunks.iter().enumerate() {
+            anyhow::ensure!(
+                chunk.chunk_id == i as u64,
+                "storage log chunks in the snapshot header are not ordered by chunk ID: {:?}",
+                header.storage_logs_chunks
+            );
+        }
+
+        if let Some(expected_hash) = header.manifest_hash {
+            let actual_hash = header.compute_manifest_hash();
+            anyhow::ensure!(
+                actual_hash == Some(expected_hash),
+                "snapshot manifest hash mismatch: expected {expected_hash:?}, computed {actual_hash:?}; \
+                 the snapshot header may be corrupted"
+            );
+        }
+        if let Some(expected_hash) = expected_manifest_hash {
+            let actual_hash = header.compute_manifest_hash();
+            anyhow::ensure!(
+                actual_hash == Some(expected_hash),
+                "snapshot manifest hash doesn't match the pinned hash: expected {expected_hash:?}, computed {actual_hash:?}; \
+                 the snapshot returned by the main node cannot be trusted"
+            );
+        }
+
+        let checksums: Vec<_> = header
+            .storage_logs_chunks
+            .iter()
+            .map(|chunk| chunk.checksum)
+            .collect();
+        let missing_checksum_count = checksums
+            .iter()
+            .filter(|checksum| checksum.is_none())
+            .count();
+        if missing_checksum_count > 0 {
+            tracing::warn!(
+                "{missing_checksum_count} storage log chunk(s) in the snapshot don't have checksums; \
+                 integrity of these chunks won't be verified"
+            );
+        }
+        Ok(checksums)
+    }
 }
 
 /// Versioned storage logs chunk.
@@ -569,19 +737,22 @@ enum StorageLogs {
 }
 
 impl StorageLogs {
+    /// Loads a chunk from the object store. Returns the loaded logs together with the chunk checksum.
     async fn load(
         blob_store: &dyn ObjectStore,
         key: SnapshotStorageLogsStorageKey,
         version: SnapshotVersion,
-    ) -> Result<Self, ObjectStoreError> {
+    ) -> Result<(Self, H256), ObjectStoreError> {
         match version {
             SnapshotVersion::Version0 => {
                 let logs: SnapshotStorageLogsChunk<StorageKey> = blob_store.get(key).await?;
-                Ok(Self::V0(logs.storage_logs))
+                let checksum = logs.checksum();
+                Ok((Self::V0(logs.storage_logs), checksum))
             }
             SnapshotVersion::Version1 => {
                 let logs: SnapshotStorageLogsChunk = blob_store.get(key).await?;
-                Ok(Self::V1(logs.storage_logs))
+                let checksum = logs.checksum();
+                Ok((Self::V1(logs.storage_logs), checksum))
             }
         }
     }
@@ -650,6 +821,8 @@ struct SnapshotsApplier<'a> {
     main_node_client: &'a dyn SnapshotsApplierMainNodeClient,
     blob_store: &'a dyn ObjectStore,
     applied_snapshot_status: SnapshotRecoveryStatus,
+    /// Expected checksums of storage log chunks ordered by chunk ID.
+    storage_logs_checksums: Vec<Option<H256>>,
     health_updater: &'a HealthUpdater,
     snapshot_version: SnapshotVersion,
     max_concurrency: usize,
@@ -677,12 +850,14 @@ impl<'a> SnapshotsApplier<'a> {
             .await?;
         let mut storage_transaction = storage.start_transaction().await?;
 
-        let (strategy, applied_snapshot_status) = SnapshotRecoveryStrategy::new(
-            &mut storage_transaction,
-            main_node_client,
-            task.snapshot_l1_batch,
-        )
-        .await?;
+        let (strategy, applied_snapshot_status, storage_logs_checksums) =
+            SnapshotRecoveryStrategy::new(
+                &mut storage_transaction,
+                main_node_client,
+                task.snapshot_l1_batch,
+                task.expected_manifest_hash,
+            )
+            .await?;
         tracing::info!("Chosen snapshot recovery strategy: {strategy:?} with status: {applied_snapshot_status:?}");
         let (created_from_scratch, snapshot_version) = match strategy {
             SnapshotRecoveryStrategy::Completed => return Ok((strategy, applied_snapshot_status)),
@@ -695,6 +870,7 @@ impl<'a> SnapshotsApplier<'a> {
             main_node_client,
             blob_store: task.blob_store.as_ref(),
             applied_snapshot_status,
+            storage_logs_checksums,
             health_updater,
             snapshot_version,
             max_concurrency: task.config.max_concurrency.get(),
@@ -884,7 +1060,7 @@ impl<'a> SnapshotsApplier<'a> {
             chunk_id,
             l1_batch_number: self.applied_snapshot_status.l1_batch_number,
         };
-        let mut storage_logs =
+        let (mut storage_logs, checksum) =
             StorageLogs::load(self.blob_store, storage_key, self.snapshot_version)
                 .await
                 .map_err(|err| {
@@ -893,6 +1069,15 @@ impl<'a> SnapshotsApplier<'a> {
                     SnapshotsApplierError::object_store(err, context)
                 })?;
 
+        if let Some(expected_checksum) = self.storage_logs_checksums[chunk_id as usize] {
+            if checksum != expected_checksum {
+                let err = anyhow::anyhow!(
+                    "storage logs chunk {chunk_id} ({storage_key:?}) is corrupted: expected checksum {expected_checksum:?}, \
+                     got {checksum:?}"
+                );
+                return Err(SnapshotsApplierError::Fatal(err));
+            }
+        }
         storage_logs.validate(&self.applied_snapshot_status)?;
         if self.drop_storage_key_preimages {
             storage_logs.drop_key_preimages();
@@ -571,6 +571,142 @@ async fn applier_returns_error_after_too_many_object_store_retries() {
     }));
 }
 
+#[tokio::test]
+async fn checking_source_availability() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let expected_status = mock_recovery_status();
+    let storage_logs = random_storage_logs::<H256>(expected_status.l1_batch_number, 100);
+    let (object_store, client) = prepare_clients(&expected_status, &storage_logs).await;
+
+    let task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        Box::new(client.clone()),
+        object_store,
+    );
+    task.check_source_availability().await.unwrap();
+
+    // Emulate a misconfigured object store.
+    let task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        Box::new(client.clone()),
+        MockObjectStore::arc(),
+    );
+    let err = task.check_source_availability().await.unwrap_err();
+    assert!(
+        format!("{err:#}").contains("cannot access storage logs"),
+        "{err:#}"
+    );
+
+    let mut task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool,
+        Box::new(client),
+        MockObjectStore::arc(),
+    );
+    task.set_snapshot_l1_batch(expected_status.l1_batch_number + 1);
+    let err = task.check_source_availability().await.unwrap_err();
+    assert!(
+        format!("{err:#}").contains("not present on main node"),
+        "{err:#}"
+    );
+}
+
+#[tokio::test]
+async fn applier_errors_on_corrupted_storage_logs_chunk() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let expected_status = mock_recovery_status();
+    let storage_logs = random_storage_logs::<H256>(expected_status.l1_batch_number, 100);
+    let (object_store, client) = prepare_clients(&expected_status, &storage_logs).await;
+
+    // Replace a chunk with a chunk having a modified log value.
+    let corrupted_key = SnapshotStorageLogsStorageKey {
+        l1_batch_number: expected_status.l1_batch_number,
+        chunk_id: 1,
+    };
+    let mut corrupted_chunk: SnapshotStorageLogsChunk =
+        object_store.get(corrupted_key).await.unwrap();
+    corrupted_chunk.storage_logs[0].value = H256::repeat_byte(0xff);
+    object_store
+        .put(corrupted_key, &corrupted_chunk)
+        .await
+        .unwrap();
+
+    let task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        Box::new(client),
+        object_store,
+    );
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let err = task.run(stop_receiver).await.unwrap_err();
+    assert!(
+        format!("{err:#}").contains("storage logs chunk 1"),
+        "{err:#}"
+    );
+
+    // Check that the corrupted chunk wasn't applied.
+    let mut storage = pool.connection().await.unwrap();
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert!(all_storage_logs
+        .iter()
+        .all(|log| log.value != H256::repeat_byte(0xff)));
+}
+
+#[tokio::test]
+async fn applier_errors_on_snapshot_manifest_mismatch() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let expected_status = mock_recovery_status();
+    let storage_logs = random_storage_logs::<H256>(expected_status.l1_batch_number, 100);
+    let (object_store, mut client) = prepare_clients(&expected_status, &storage_logs).await;
+    let snapshot_header = client.fetch_newest_snapshot_response.as_mut().unwrap();
+    snapshot_header.storage_logs_chunks[0].checksum = Some(H256::zero());
+
+    let task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool,
+        Box::new(client),
+        object_store,
+    );
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let err = task.run(stop_receiver).await.unwrap_err();
+    assert!(
+        format!("{err:#}").contains("manifest hash mismatch"),
+        "{err:#}"
+    );
+}
+
+#[tokio::test]
+async fn applier_errors_on_snapshot_not_matching_pinned_manifest_hash() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let expected_status = mock_recovery_status();
+    let storage_logs = random_storage_logs::<H256>(expected_status.l1_batch_number, 100);
+    let (object_store, mut client) = prepare_clients(&expected_status, &storage_logs).await;
+    let snapshot_header = client.fetch_newest_snapshot_response.as_mut().unwrap();
+    let pinned_hash = snapshot_header.manifest_hash.unwrap();
+    // Emulate a tampered snapshot with a self-consistent manifest.
+    snapshot_header.storage_logs_chunks[0].checksum = Some(H256::zero());
+    snapshot_header.manifest_hash = snapshot_header.compute_manifest_hash();
+
+    let mut task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool,
+        Box::new(client),
+        object_store,
+    );
+    task.set_expected_manifest_hash(pinned_hash);
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let err = task.run(stop_receiver).await.unwrap_err();
+    assert!(
+        format!("{err:#}").contains("doesn't match the pinned hash"),
+        "{err:#}"
+    );
+}
+
 #[tokio::test]
 async fn recovering_tokens() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -9,6 +9,7 @@ use std::{
 use async_trait::async_trait;
 use tokio::sync::watch;
 use zksync_object_store::{Bucket, MockObjectStore, ObjectStore, ObjectStoreError, StoredObject};
+use zksync_protobuf::ProtoFmt;
 use zksync_types::{
     api,
     block::L2BlockHeader,
@@ -227,6 +228,7 @@ fn l1_batch_details(number: L1BatchNumber, root_hash: H256) -> api::L1BatchDetai
     api::L1BatchDetails {
         number,
         base: block_details_base(root_hash),
+        commitment_mode: None,
     }
 }
 
@@ -292,9 +294,11 @@ pub(super) fn mock_snapshot_header(
             .map(|chunk_id| SnapshotStorageLogsChunkMetadata {
                 chunk_id,
                 filepath: format!("file{chunk_id}"),
+                checksum: None,
             })
             .collect(),
         factory_deps_filepath: "some_filepath".to_string(),
+        manifest_hash: None,
     }
 }
 
@@ -304,7 +308,8 @@ pub(super) async fn prepare_clients<K>(
 ) -> (Arc<dyn ObjectStore>, MockMainNodeClient)
 where
     K: SnapshotLogKey,
-    for<'a> SnapshotStorageLogsChunk<K>: StoredObject<Key<'a> = SnapshotStorageLogsStorageKey>,
+    for<'a> SnapshotStorageLogsChunk<K>:
+        StoredObject<Key<'a> = SnapshotStorageLogsStorageKey> + ProtoFmt,
 {
     let object_store = MockObjectStore::arc();
     let mut client = MockMainNodeClient::default();
@@ -324,6 +329,7 @@ where
         .div_ceil(status.storage_logs_chunks_processed.len());
     assert!(chunk_size > 0);
 
+    let mut snapshot_header = mock_snapshot_header(K::VERSION.into(), status);
     for (chunk_id, chunk) in logs.chunks(chunk_size).enumerate() {
         let chunk_storage_logs = SnapshotStorageLogsChunk {
             storage_logs: chunk.to_vec(),
@@ -336,9 +342,12 @@ where
             .put(chunk_key, &chunk_storage_logs)
             .await
             .unwrap();
+        snapshot_header.storage_logs_chunks[chunk_id].checksum =
+            Some(chunk_storage_logs.checksum());
     }
+    snapshot_header.manifest_hash = snapshot_header.compute_manifest_hash();
 
-    client.fetch_newest_snapshot_response = Some(mock_snapshot_header(K::VERSION.into(), status));
+    client.fetch_newest_snapshot_response = Some(snapshot_header);
     client.fetch_l1_batch_responses.insert(
         status.l1_batch_number,
         l1_batch_details(status.l1_batch_number, status.l1_batch_root_hash),
@@ -9,6 +9,7 @@
     clippy::doc_markdown // frequent false positive: RocksDB
 )]
 
+pub use zksync_storage::RocksdbProfile;
 pub use zksync_vm_interface::storage as interface;
 
 pub use self::{
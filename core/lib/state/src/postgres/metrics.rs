@@ -29,6 +29,12 @@ pub(super) struct ValuesCacheMetrics {
     /// Number of times the negative initial writes cache was successfully used. This is distinct
     /// from cache hits (we can hit the cache, but the cached value may be outdated).
     pub effective_values: Counter,
+    /// Latency of materializing a historical storage view.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub historical_view_load_latency: Histogram<Duration>,
+    /// Number of keys modified after the L2 block of a materialized historical storage view.
+    #[metrics(buckets = Buckets::exponential(10.0..=1_000_000.0, 10.0))]
+    pub historical_view_modified_keys: Histogram<usize>,
 }
 
 #[vise::register]
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     mem,
     sync::{Arc, RwLock},
     time::Duration,
@@ -255,6 +256,79 @@ struct ValuesCacheAndUpdater {
     command_sender: mpsc::UnboundedSender<L2BlockNumber>,
 }
 
+/// VM storage snapshot for a historical L2 block materialized relative to a later ("base") L2 block.
+///
+/// The view is built by reverse-applying storage logs from the L2 blocks after the historical one up to and including
+/// the base block. Keys not modified in these blocks have the same values as in the base block; thus, they can be served
+/// by the [`ValuesCache`] as long as it is valid for the base block. Values for modified keys are stored in the view.
+#[derive(Debug, Clone)]
+struct HistoricalView {
+    base_l2_block: L2BlockNumber,
+    /// Hash of the base L2 block at the time the view was materialized. Since L2 block hashes are chained,
+    /// a changed hash means that the base block or one of its ancestors was reverted, i.e. the view is stale.
+    base_l2_block_hash: H256,
+    /// Values as of the historical L2 block for keys modified after it.
+    reverted_values: Arc<HashMap<H256, StorageValue>>,
+}
+
+impl CacheValue<L2BlockNumber> for HistoricalView {
+    fn cache_weight(&self) -> u32 {
+        const ENTRY_WEIGHT: usize = mem::size_of::<H256>() + mem::size_of::<StorageValue>();
+
+        let weight = mem::size_of::<Self>() + self.reverted_values.len() * ENTRY_WEIGHT;
+        weight.try_into().unwrap_or(u32::MAX)
+    }
+}
+
+impl HistoricalView {
+    async fn load(
+        connection: &mut Connection<'_, Core>,
+        l2_block_number: L2BlockNumber,
+        base_l2_block: L2BlockNumber,
+    ) -> anyhow::Result<Self> {
+        let latency = CACHE_METRICS.historical_view_load_latency.start();
+        let base_l2_block_hash = connection
+            .blocks_web3_dal()
+            .get_l2_block_hash(base_l2_block)
+            .await?
+            .with_context(|| format!("base L2 block #{base_l2_block} is not persisted"))?;
+        let l2_blocks = (l2_block_number + 1)..=base_l2_block;
+        let modified_keys = connection
+            .storage_logs_dal()
+            .modified_keys_in_l2_blocks(l2_blocks.clone())
+            .await?;
+        let reverted_values = connection
+            .storage_logs_dal()
+            .get_storage_values(&modified_keys, l2_block_number)
+            .await?;
+        let reverted_values: HashMap<_, _> = reverted_values
+            .into_iter()
+            .map(|(hashed_key, value)| (hashed_key, value.unwrap_or_default()))
+            .collect();
+
+        let elapsed = latency.observe();
+        CACHE_METRICS
+            .historical_view_modified_keys
+            .observe(reverted_values.len());
+        tracing::debug!(
+            "Materialized historical storage view for L2 block #{l2_block_number} relative to L2 block #{base_l2_block} \
+             with {} modified keys; took {elapsed:?}",
+            reverted_values.len()
+        );
+        Ok(Self {
+            base_l2_block,
+            base_l2_block_hash,
+            reverted_values: Arc::new(reverted_values),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct HistoricalViewsCache {
+    views: LruCache<L2BlockNumber, HistoricalView>,
+    max_l2_blocks_lag: u32,
+}
+
 /// Caches used during VM execution.
 ///
 /// Currently, this struct includes the following caches:
@@ -263,6 +337,7 @@ struct ValuesCacheAndUpdater {
 /// - Cache for L1 batch numbers of initial writes for storage keys (never invalidated, except after
 ///   reverting L1 batch execution)
 /// - Cache of the VM storage snapshot corresponding to the latest sealed L2 block
+/// - Cache of historical VM storage snapshots materialized relative to the latest snapshot (optional)
 #[derive(Debug, Clone)]
 pub struct PostgresStorageCaches {
     factory_deps: FactoryDepsCache,
@@ -276,6 +351,7 @@ pub struct PostgresStorageCaches {
     // it wasn't written to at the point that interests us.
     negative_initial_writes: InitialWritesCache,
     values: Option<ValuesCacheAndUpdater>,
+    historical_views: Option<HistoricalViewsCache>,
 }
 
 impl PostgresStorageCaches {
@@ -297,6 +373,7 @@ impl PostgresStorageCaches {
                 initial_writes_capacity / 2,
             ),
             values: None,
+            historical_views: None,
         }
     }
 
@@ -339,6 +416,36 @@ impl PostgresStorageCaches {
         }
     }
 
+    /// Configures the cache of historical VM storage snapshots. A snapshot for a historical L2 block is materialized
+    /// on the first read from it by reverse-applying storage logs to the snapshot held by the values cache; thus, reads
+    /// of keys not modified since the historical L2 block are served by the values cache. Snapshots are only materialized
+    /// for L2 blocks lagging behind the values cache by at most `max_l2_blocks_lag` blocks; reads for older blocks
+    /// are served directly by Postgres.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the values cache is not [configured](Self::configure_storage_values_cache()), or if provided
+    /// `capacity` is zero.
+    pub fn configure_historical_views_cache(&mut self, capacity: u64, max_l2_blocks_lag: u32) {
+        assert!(
+            self.values.is_some(),
+            "Storage values cache must be configured before historical views"
+        );
+        assert!(
+            capacity > 0,
+            "Historical views cache capacity must be positive"
+        );
+        tracing::debug!(
+            "Initializing historical storage views cache with {capacity}B capacity \
+             and {max_l2_blocks_lag} max L2 blocks lag"
+        );
+
+        self.historical_views = Some(HistoricalViewsCache {
+            views: LruCache::new("historical_views_cache", capacity),
+            max_l2_blocks_lag,
+        });
+    }
+
     /// Schedules an update of the VM storage values cache to the specified L2 block. If the values cache is not configured,
     /// this is a no-op.
     ///
@@ -425,6 +532,8 @@ pub struct PostgresStorage<'a> {
     pending_l1_batch_number: L1BatchNumber,
     consider_new_l1_batch: bool,
     caches: Option<PostgresStorageCaches>,
+    /// Lazily resolved historical view for `l2_block_number`; `None` if not resolved yet.
+    historical_view: Option<Option<HistoricalView>>,
 }
 
 impl<'a> PostgresStorage<'a> {
@@ -476,6 +585,7 @@ impl<'a> PostgresStorage<'a> {
             pending_l1_batch_number: resolved.pending_l1_batch,
             consider_new_l1_batch,
             caches: None,
+            historical_view: None,
         })
     }
 
@@ -503,6 +613,123 @@ impl<'a> PostgresStorage<'a> {
     fn values_cache(&self) -> Option<&ValuesCache> {
         Some(&self.caches.as_ref()?.values.as_ref()?.cache)
     }
+
+    /// Resolves the historical view for `l2_block_number` (if historical views are configured), materializing it
+    /// if necessary. Calling this method is optional; if it's not called, the view is resolved on the first
+    /// storage read, with errors logged and reads falling back to Postgres.
+    ///
+    /// # Errors
+    ///
+    /// Propagates Postgres errors.
+    pub async fn resolve_historical_view(&mut self) -> anyhow::Result<()> {
+        if self.historical_view.is_none() {
+            self.historical_view = Some(self.load_historical_view().await?);
+        }
+        Ok(())
+    }
+
+    fn historical_view(&mut self) -> Option<HistoricalView> {
+        if let Some(view) = &self.historical_view {
+            return view.clone();
+        }
+        let rt_handle = self.rt_handle.clone();
+        let view = rt_handle
+            .block_on(self.load_historical_view())
+            .unwrap_or_else(|err| {
+                tracing::warn!(
+                    "Failed materializing historical storage view for L2 block #{}, falling back to Postgres: {err:#}",
+                    self.l2_block_number
+                );
+                None
+            });
+        self.historical_view = Some(view.clone());
+        view
+    }
+
+    async fn load_historical_view(&mut self) -> anyhow::Result<Option<HistoricalView>> {
+        let Some(caches) = &self.caches else {
+            return Ok(None);
+        };
+        let Some(views) = caches.historical_views.clone() else {
+            return Ok(None);
+        };
+        let Some(values) = &caches.values else {
+            return Ok(None);
+        };
+        let base_l2_block = values.cache.valid_for();
+        if base_l2_block <= self.l2_block_number
+            || base_l2_block.0 - self.l2_block_number.0 > views.max_l2_blocks_lag
+        {
+            return Ok(None);
+        }
+
+        if let Some(view) = views.views.get(&self.l2_block_number) {
+            let base_l2_block_hash = self
+                .connection
+                .blocks_web3_dal()
+                .get_l2_block_hash(view.base_l2_block)
+                .await?;
+            if base_l2_block_hash == Some(view.base_l2_block_hash) {
+                return Ok(Some(view));
+            }
+            tracing::info!(
+                "Base L2 block #{} for historical storage view for L2 block #{} was reverted; evicting the view",
+                view.base_l2_block,
+                self.l2_block_number
+            );
+            views.views.remove(&self.l2_block_number);
+        }
+        let view =
+            HistoricalView::load(&mut self.connection, self.l2_block_number, base_l2_block).await?;
+        views.views.insert(self.l2_block_number, view.clone());
+        Ok(Some(view))
+    }
+
+    /// Reads a value using the historical view for `l2_block_number`, if one is available.
+    fn read_value_from_historical_view(&mut self, hashed_key: H256) -> Option<StorageValue> {
+        let view = self.historical_view()?;
+        if let Some(&value) = view.reverted_values.get(&hashed_key) {
+            return Some(value);
+        }
+
+        // The key wasn't modified after `l2_block_number`, so its value is the same as for the base L2 block.
+        let values_cache = self.values_cache()?;
+        if let Some(value) = values_cache.get(view.base_l2_block, hashed_key) {
+            return Some(value);
+        }
+        let value = self.read_value_from_postgres(hashed_key, view.base_l2_block);
+        if let Some(cache) = self.values_cache() {
+            cache.insert(view.base_l2_block, hashed_key, value);
+        }
+        Some(value)
+    }
+
+    fn read_value_from_postgres(
+        &mut self,
+        hashed_key: H256,
+        l2_block_number: L2BlockNumber,
+    ) -> StorageValue {
+        const RETRY_INTERVAL: Duration = Duration::from_millis(500);
+        const MAX_TRIES: usize = 20;
+
+        let mut dal = self.connection.storage_web3_dal();
+        (|| {
+            self.rt_handle
+                .block_on(dal.get_historical_value_unchecked(hashed_key, l2_block_number))
+        })
+        .retry(
+            &ConstantBuilder::default()
+                .with_delay(RETRY_INTERVAL)
+                .with_max_times(MAX_TRIES),
+        )
+        .when(|e| {
+            e.inner()
+                .as_database_error()
+                .is_some_and(|e| e.message() == "canceling statement due to statement timeout")
+        })
+        .call()
+        .expect("Failed executing `read_value`")
+    }
 }
 
 impl ReadStorage for PostgresStorage<'_> {
@@ -513,32 +740,15 @@ impl ReadStorage for PostgresStorage<'_> {
         let cached_value =
             values_cache.and_then(|cache| cache.get(self.l2_block_number, hashed_key));
 
-        let value = cached_value.unwrap_or_else(|| {
-            const RETRY_INTERVAL: Duration = Duration::from_millis(500);
-            const MAX_TRIES: usize = 20;
-
-            let mut dal = self.connection.storage_web3_dal();
-            let value = (|| {
-                self.rt_handle
-                    .block_on(dal.get_historical_value_unchecked(hashed_key, self.l2_block_number))
-            })
-            .retry(
-                &ConstantBuilder::default()
-                    .with_delay(RETRY_INTERVAL)
-                    .with_max_times(MAX_TRIES),
-            )
-            .when(|e| {
-                e.inner()
-                    .as_database_error()
-                    .is_some_and(|e| e.message() == "canceling statement due to statement timeout")
-            })
-            .call()
-            .expect("Failed executing `read_value`");
-            if let Some(cache) = self.values_cache() {
-                cache.insert(self.l2_block_number, hashed_key, value);
-            }
-            value
-        });
+        let value = cached_value
+            .or_else(|| self.read_value_from_historical_view(hashed_key))
+            .unwrap_or_else(|| {
+                let value = self.read_value_from_postgres(hashed_key, self.l2_block_number);
+                if let Some(cache) = self.values_cache() {
+                    cache.insert(self.l2_block_number, hashed_key, value);
+                }
+                value
+            });
 
         latency.observe();
         value
@@ -11,7 +11,9 @@ use zksync_dal::ConnectionPool;
 use zksync_types::StorageLog;
 
 use super::*;
-use crate::test_utils::{create_l1_batch, create_l2_block, gen_storage_logs, prepare_postgres};
+use crate::test_utils::{
+    create_l1_batch, create_l2_block, create_l2_block_with_hash, gen_storage_logs, prepare_postgres,
+};
 
 fn test_postgres_storage_basics(
     pool: &ConnectionPool<Core>,
@@ -587,15 +589,154 @@ async fn using_values_cache() {
         .unwrap();
 }
 
+fn test_historical_views(pool: &ConnectionPool<Core>, rt_handle: Handle) {
+    let mut caches = PostgresStorageCaches::new(1_024, 1_024);
+    let _ = caches.configure_storage_values_cache(1_024 * 1_024, 5, pool.clone());
+    caches.configure_historical_views_cache(1_024 * 1_024, 10);
+    let values_cache = caches.values.as_ref().unwrap().cache.clone();
+
+    let mut connection = rt_handle.block_on(pool.connection()).unwrap();
+    rt_handle.block_on(prepare_postgres(&mut connection));
+
+    let initial_logs = gen_storage_logs(0..20);
+    let modified_key = initial_logs[1].key;
+    let unmodified_key = initial_logs[2].key;
+    let new_key = gen_storage_logs(100..120)[0].key;
+    let logs = vec![
+        StorageLog::new_write_log(modified_key, H256::repeat_byte(1)),
+        StorageLog::new_write_log(new_key, H256::repeat_byte(2)),
+    ];
+    rt_handle.block_on(create_l2_block(&mut connection, L2BlockNumber(1), logs));
+    rt_handle
+        .block_on(values_cache.update(L2BlockNumber(0), L2BlockNumber(1), &mut connection))
+        .unwrap();
+
+    let mut storage = PostgresStorage::new(rt_handle, connection, L2BlockNumber(0), false)
+        .with_caches(caches.clone());
+    assert_eq!(storage.read_value(&modified_key), initial_logs[1].value);
+    assert_eq!(storage.read_value(&new_key), H256::zero());
+    assert_eq!(storage.read_value(&unmodified_key), initial_logs[2].value);
+
+    let views = &caches.historical_views.as_ref().unwrap().views;
+    let view = views.get(&L2BlockNumber(0)).unwrap();
+    assert_eq!(view.base_l2_block, L2BlockNumber(1));
+    let expected_reverted_values = HashMap::from([
+        (modified_key.hashed_key(), initial_logs[1].value),
+        (new_key.hashed_key(), H256::zero()),
+    ]);
+    assert_eq!(*view.reverted_values, expected_reverted_values);
+
+    // The unmodified key must be read via the values cache for the base L2 block.
+    values_cache.assertions(L2BlockNumber(1)).assert_entries(&[
+        (unmodified_key, Some(initial_logs[2].value)),
+        (modified_key, None),
+        (new_key, None),
+    ]);
+
+    // Views must not be materialized for the L2 block for which the values cache is valid.
+    let mut storage = PostgresStorage::new(
+        storage.rt_handle,
+        storage.connection,
+        L2BlockNumber(1),
+        false,
+    )
+    .with_caches(caches.clone());
+    assert_eq!(storage.read_value(&modified_key), H256::repeat_byte(1));
+    assert!(views.get(&L2BlockNumber(1)).is_none());
+}
+
+#[tokio::test]
+async fn using_historical_views() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let handle = Handle::current();
+    tokio::task::spawn_blocking(move || test_historical_views(&pool, handle))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn historical_views_are_invalidated_on_revert() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut caches = PostgresStorageCaches::new(1_024, 1_024);
+    let _ = caches.configure_storage_values_cache(1_024 * 1_024, 5, pool.clone());
+    caches.configure_historical_views_cache(1_024 * 1_024, 10);
+    let values_cache = caches.values.as_ref().unwrap().cache.clone();
+
+    let mut connection = pool.connection().await.unwrap();
+    prepare_postgres(&mut connection).await;
+    let initial_logs = gen_storage_logs(0..20);
+    let key = initial_logs[1].key;
+    let logs = vec![StorageLog::new_write_log(key, H256::repeat_byte(1))];
+    create_l2_block(&mut connection, L2BlockNumber(1), logs).await;
+    values_cache
+        .update(L2BlockNumber(0), L2BlockNumber(1), &mut connection)
+        .await
+        .unwrap();
+
+    let mut storage = PostgresStorage::new_async(
+        Handle::current(),
+        pool.connection().await.unwrap(),
+        L2BlockNumber(0),
+        false,
+    )
+    .await
+    .unwrap()
+    .with_caches(caches.clone());
+    storage.resolve_historical_view().await.unwrap();
+    let view = storage.historical_view.clone().unwrap().unwrap();
+    assert_eq!(view.base_l2_block, L2BlockNumber(1));
+    assert_eq!(view.base_l2_block_hash, H256::from_low_u64_be(1));
+    assert!(view.reverted_values.contains_key(&key.hashed_key()));
+
+    // Revert L2 block #1 and replace it with a block modifying another key.
+    connection
+        .storage_logs_dal()
+        .roll_back_storage_logs(L2BlockNumber(0))
+        .await
+        .unwrap();
+    connection
+        .blocks_dal()
+        .delete_l2_blocks(L2BlockNumber(0))
+        .await
+        .unwrap();
+    let other_key = initial_logs[2].key;
+    let logs = vec![StorageLog::new_write_log(other_key, H256::repeat_byte(2))];
+    let new_hash = H256::repeat_byte(0xff);
+    create_l2_block_with_hash(&mut connection, L2BlockNumber(1), new_hash, logs).await;
+
+    let mut storage = PostgresStorage::new_async(
+        Handle::current(),
+        pool.connection().await.unwrap(),
+        L2BlockNumber(0),
+        false,
+    )
+    .await
+    .unwrap()
+    .with_caches(caches.clone());
+    storage.resolve_historical_view().await.unwrap();
+    let view = storage.historical_view.clone().unwrap().unwrap();
+    assert_eq!(view.base_l2_block_hash, new_hash);
+    assert!(!view.reverted_values.contains_key(&key.hashed_key()));
+    assert!(view.reverted_values.contains_key(&other_key.hashed_key()));
+
+    let views = &caches.historical_views.as_ref().unwrap().views;
+    let cached_view = views.get(&L2BlockNumber(0)).unwrap();
+    assert_eq!(cached_view.base_l2_block_hash, new_hash);
+}
+
 /// (Sort of) fuzzes [`ValuesCache`] by comparing outputs of [`PostgresStorage`] with and without caching
 /// on randomly generated `read_value()` queries.
 fn mini_fuzz_values_cache_inner(
     rng: &mut impl Rng,
     pool: &ConnectionPool<Core>,
     mut rt_handle: Handle,
+    with_historical_views: bool,
 ) {
     let mut caches = PostgresStorageCaches::new(1_024, 1_024);
     let _ = caches.configure_storage_values_cache(1_024 * 1_024, 5, pool.clone());
+    if with_historical_views {
+        caches.configure_historical_views_cache(1_024 * 1_024, 5);
+    }
     let values_cache = caches.values.as_ref().unwrap().cache.clone();
 
     let mut connection = rt_handle.block_on(pool.connection()).unwrap();
@@ -680,7 +821,23 @@ async fn mini_fuzz_values_cache() {
 
     let handle = Handle::current();
     let mut rng = StdRng::seed_from_u64(RNG_SEED);
-    tokio::task::spawn_blocking(move || mini_fuzz_values_cache_inner(&mut rng, &pool, handle))
-        .await
-        .unwrap();
+    tokio::task::spawn_blocking(move || {
+        mini_fuzz_values_cache_inner(&mut rng, &pool, handle, false);
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn mini_fuzz_values_cache_with_historical_views() {
+    const RNG_SEED: u64 = 321;
+    let pool = ConnectionPool::<Core>::test_pool().await;
+
+    let handle = Handle::current();
+    let mut rng = StdRng::seed_from_u64(RNG_SEED);
+    tokio::task::spawn_blocking(move || {
+        mini_fuzz_values_cache_inner(&mut rng, &pool, handle, true);
+    })
+    .await
+    .unwrap();
 }
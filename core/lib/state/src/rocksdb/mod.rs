@@ -31,7 +31,7 @@ use anyhow::Context as _;
 use itertools::{Either, Itertools};
 use tokio::sync::watch;
 use zksync_dal::{Connection, Core, CoreDal, DalError};
-use zksync_storage::{db::NamedColumnFamily, RocksDB, RocksDBOptions};
+use zksync_storage::{db::NamedColumnFamily, RocksDB, RocksDBOptions, RocksdbProfile};
 use zksync_types::{L1BatchNumber, StorageKey, StorageValue, H256};
 use zksync_vm_interface::storage::ReadStorage;
 
@@ -133,6 +133,8 @@ pub struct RocksdbStorageOptions {
     /// Number of open files that can be simultaneously opened by RocksDB. Default is `None`, for no limit.
     /// Can be used to restrict memory usage of RocksDB.
     pub max_open_files: Option<NonZeroU32>,
+    /// RocksDB tuning profile. The default value is [`RocksdbProfile::Balanced`].
+    pub profile: RocksdbProfile,
 }
 
 impl Default for RocksdbStorageOptions {
@@ -140,6 +142,7 @@ impl Default for RocksdbStorageOptions {
         Self {
             block_cache_capacity: 128 << 20,
             max_open_files: None,
+            profile: RocksdbProfile::default(),
         }
     }
 }
@@ -149,6 +152,7 @@ impl RocksdbStorageOptions {
         RocksDBOptions {
             block_cache_capacity: Some(self.block_cache_capacity),
             max_open_files: self.max_open_files,
+            profile: self.profile,
             ..RocksDBOptions::default()
         }
     }
@@ -72,11 +72,23 @@ pub(crate) async fn create_l2_block(
     conn: &mut Connection<'_, Core>,
     l2_block_number: L2BlockNumber,
     block_logs: Vec<StorageLog>,
+) {
+    let hash = H256::from_low_u64_be(u64::from(l2_block_number.0));
+    create_l2_block_with_hash(conn, l2_block_number, hash, block_logs).await;
+}
+
+#[allow(clippy::default_trait_access)]
+// ^ `BaseSystemContractsHashes::default()` would require a new direct dependency
+pub(crate) async fn create_l2_block_with_hash(
+    conn: &mut Connection<'_, Core>,
+    l2_block_number: L2BlockNumber,
+    hash: H256,
+    block_logs: Vec<StorageLog>,
 ) {
     let l2_block_header = L2BlockHeader {
         number: l2_block_number,
         timestamp: 0,
-        hash: H256::from_low_u64_be(u64::from(l2_block_number.0)),
+        hash,
         l1_tx_count: 0,
         l2_tx_count: 0,
         fee_account_address: Address::default(),
@@ -20,6 +20,7 @@ use rocksdb::{
     DBPinnableSlice, Direction, IteratorMode, Options, PrefixRange, ReadOptions, WriteOptions, DB,
 };
 use thread_local::ThreadLocal;
+use zksync_config::configs::database::RocksdbProfile;
 
 use crate::metrics::{
     BlockCacheKind, RocksdbBlockCacheLabels, RocksdbLabels, RocksdbProfilingLabels,
@@ -145,6 +146,13 @@ impl RocksDBInner {
             if let Some(pending_compactions) = pending_compactions {
                 metrics.pending_compactions[&labels].set(pending_compactions);
             }
+            let compaction_pending = self.int_property(cf, properties::COMPACTION_PENDING);
+            let compaction_pending = compaction_pending == Some(1);
+            metrics.compaction_pending[&labels].set(compaction_pending.into());
+            let delayed_write_rate = self.int_property(cf, properties::ACTUAL_DELAYED_WRITE_RATE);
+            if let Some(rate) = delayed_write_rate {
+                metrics.delayed_write_rate[&labels].set(rate);
+            }
 
             let live_data_size = self.int_property(cf, properties::ESTIMATE_LIVE_DATA_SIZE);
             if let Some(size) = live_data_size {
@@ -154,6 +162,18 @@ impl RocksDBInner {
             if let Some(size) = total_sst_file_size {
                 metrics.total_sst_size[&labels].set(size);
             }
+            let live_sst_file_size = self.int_property(cf, properties::LIVE_SST_FILES_SIZE);
+            if let Some(size) = live_sst_file_size {
+                metrics.live_sst_size[&labels].set(size);
+            }
+            let estimated_keys = self.int_property(cf, properties::ESTIMATE_NUM_KEYS);
+            if let Some(count) = estimated_keys {
+                metrics.estimated_keys[&labels].set(count);
+            }
+            let live_versions = self.int_property(cf, properties::NUM_LIVE_VERSIONS);
+            if let Some(count) = live_versions {
+                metrics.live_versions[&labels].set(count);
+            }
             let total_mem_table_size = self.int_property(cf, properties::SIZE_ALL_MEM_TABLES);
             if let Some(size) = total_mem_table_size {
                 metrics.total_mem_table_size[&labels].set(size);
@@ -278,6 +298,37 @@ impl StalledWritesRetries {
     }
 }
 
+/// Applies a tuning profile on top of other [`RocksDBOptions`]. Profiles adjust memtable, compaction
+/// and table options for a specific workload.
+fn apply_profile(
+    profile: RocksdbProfile,
+    options: &mut Options,
+    block_based_options: Option<&mut BlockBasedOptions>,
+) {
+    match profile {
+        RocksdbProfile::Balanced => { /* use default options */ }
+        RocksdbProfile::WriteHeavy => {
+            // Use more memtables and tolerate more level-0 files before stalling writes, at the cost of read amplification.
+            options.set_max_write_buffer_number(6);
+            options.set_min_write_buffer_number_to_merge(2);
+            options.set_level_zero_slowdown_writes_trigger(40);
+            options.set_level_zero_stop_writes_trigger(64);
+            options.set_soft_pending_compaction_bytes_limit(256 << 30); // 256 GiB
+            options.set_hard_pending_compaction_bytes_limit(1 << 40); // 1 TiB
+            options.set_max_subcompactions(4);
+            options.set_bytes_per_sync(1 << 20); // 1 MiB
+        }
+        RocksdbProfile::ReadHeavy => {
+            // Compact level-0 files more eagerly and use smaller blocks to reduce read amplification.
+            options.set_level_zero_file_num_compaction_trigger(2);
+            if let Some(block_based_options) = block_based_options {
+                block_based_options.set_block_size(4 << 10); // 4 KiB
+                block_based_options.set_pin_top_level_index_and_filter(true);
+            }
+        }
+    }
+}
+
 /// [`RocksDB`] options.
 #[derive(Debug, Clone, Copy)]
 pub struct RocksDBOptions {
@@ -298,6 +349,8 @@ pub struct RocksDBOptions {
     pub stalled_writes_retries: StalledWritesRetries,
     /// Number of open files that can be used by the DB. Default is None, for no limit.
     pub max_open_files: Option<NonZeroU32>,
+    /// Tuning profile applied to all column families.
+    pub profile: RocksdbProfile,
 }
 
 impl Default for RocksDBOptions {
@@ -308,6 +361,7 @@ impl Default for RocksDBOptions {
             large_memtable_capacity: None,
             stalled_writes_retries: StalledWritesRetries::new(Duration::from_secs(10)),
             max_open_files: None,
+            profile: RocksdbProfile::default(),
         }
     }
 }
@@ -341,7 +395,7 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
 
     pub fn with_options(path: &Path, options: RocksDBOptions) -> Result<Self, rocksdb::Error> {
         let caches = RocksDBCaches::new(options.block_cache_capacity);
-        let mut db_options = Self::rocksdb_options(None, None);
+        let mut db_options = Self::rocksdb_options(options.profile, None, None);
         let max_open_files = if let Some(non_zero) = options.max_open_files {
             i32::try_from(non_zero.get()).unwrap_or(i32::MAX)
         } else {
@@ -398,7 +452,11 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
             }
 
             let memtable_capacity = options.large_memtable_capacity.filter(|_| requires_tuning);
-            let cf_options = Self::rocksdb_options(memtable_capacity, Some(block_based_options));
+            let cf_options = Self::rocksdb_options(
+                options.profile,
+                memtable_capacity,
+                Some(block_based_options),
+            );
             ColumnFamilyDescriptor::new(cf_name, cf_options)
         });
 
@@ -436,8 +494,9 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
     }
 
     fn rocksdb_options(
+        profile: RocksdbProfile,
         memtable_capacity: Option<usize>,
-        block_based_options: Option<BlockBasedOptions>,
+        mut block_based_options: Option<BlockBasedOptions>,
     ) -> Options {
         let mut options = Options::default();
         options.create_missing_column_families(true);
@@ -452,6 +511,7 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
         // https://www.pingcap.com/blog/how-to-troubleshoot-rocksdb-write-stalls-in-tikv/
         let max_background_jobs = (num_cpus - 1).clamp(1, 8);
         options.set_max_background_jobs(max_background_jobs);
+        apply_profile(profile, &mut options, block_based_options.as_mut());
 
         if let Some(block_based_options) = block_based_options {
             options.set_block_based_table_factory(&block_based_options);
@@ -479,6 +539,23 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
             .unwrap_or(0)
     }
 
+    /// Returns the estimated size of live data in the specified column family in bytes, or `None`
+    /// if the estimate is unavailable.
+    pub fn estimated_live_data_size(&self, cf: CF) -> Option<u64> {
+        let cf = self.column_family(cf);
+        self.inner
+            .int_property(cf, properties::ESTIMATE_LIVE_DATA_SIZE)
+    }
+
+    /// Manually compacts the specified key range in the column family. This blocks the current thread
+    /// until compaction is completed.
+    pub fn compact_range_cf(&self, cf: CF, keys: ops::Range<&[u8]>) {
+        let cf = self.column_family(cf);
+        self.inner
+            .db
+            .compact_range_cf(cf, Some(keys.start), Some(keys.end));
+    }
+
     pub fn multi_get<K, I>(&self, keys: I) -> Vec<Result<Option<Vec<u8>>, rocksdb::Error>>
     where
         K: AsRef<[u8]>,
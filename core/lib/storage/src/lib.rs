@@ -3,3 +3,4 @@ mod metrics;
 
 pub use db::{RocksDB, RocksDBOptions, StalledWritesRetries, WeakRocksDB};
 pub use rocksdb;
+pub use zksync_config::configs::database::RocksdbProfile;
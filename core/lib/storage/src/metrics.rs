@@ -109,11 +109,24 @@ pub(crate) struct RocksdbSizeMetrics {
     /// Estimated number of bytes for pending compactions.
     #[metrics(unit = Unit::Bytes)]
     pub pending_compactions: Family<RocksdbLabels, Gauge<u64>>,
+    /// Boolean gauge indicating whether at least one compaction is pending for the column family.
+    pub compaction_pending: Family<RocksdbLabels, Gauge<u64>>,
+    /// Current rate of delayed writes (bytes per second). A non-zero value means that writes are being slowed down
+    /// to let compactions catch up, i.e. a write stall is in progress.
+    pub delayed_write_rate: Family<RocksdbLabels, Gauge<u64>>,
 
     /// Estimated size of all live data in the column family of a RocksDB instance.
     pub live_data_size: Family<RocksdbLabels, Gauge<u64>>,
     /// Total size of all SST files in the column family of a RocksDB instance.
     pub total_sst_size: Family<RocksdbLabels, Gauge<u64>>,
+    /// Total size of SST files belonging to the latest version of the column family of a RocksDB instance.
+    /// Differs from `total_sst_size` if there are obsolete SST files that are not yet deleted.
+    pub live_sst_size: Family<RocksdbLabels, Gauge<u64>>,
+    /// Estimated number of keys in the column family of a RocksDB instance.
+    pub estimated_keys: Family<RocksdbLabels, Gauge<u64>>,
+    /// Number of live versions of the column family. A large value means that iterators or snapshots
+    /// are preventing obsolete SST files from being deleted.
+    pub live_versions: Family<RocksdbLabels, Gauge<u64>>,
     /// Total size of all memory tables in the column family of a RocksDB instance.
     pub total_mem_table_size: Family<RocksdbLabels, Gauge<u64>>,
     /// Total size of block cache in the column family of a RocksDB instance.
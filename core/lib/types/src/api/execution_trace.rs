@@ -0,0 +1,102 @@
+//! Types for instruction-level VM execution traces returned by `debug_traceCallExecution`
+//! and `debug_traceTransactionExecution`.
+
+use serde::{Deserialize, Serialize};
+
+use super::state_override::StateOverride;
+use crate::{Address, U256};
+
+/// Single executed VM instruction, as reported by the execution tracer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionFrame {
+    /// Sequential number of the frame in the traced execution.
+    pub index: u64,
+    /// Program counter of the instruction.
+    pub pc: u64,
+    /// Human-readable instruction opcode.
+    pub opcode: String,
+    /// Depth of the call stack (including near calls) at which the instruction is executed.
+    pub depth: usize,
+    /// Address of the contract executing the instruction.
+    pub address: Address,
+    /// Address of the contract whose code is executed (differs from `address` for delegate calls).
+    pub code_address: Address,
+    /// Gas remaining in the current frame before the instruction is executed.
+    pub gas: u64,
+    /// Gas spent on the instruction. For far calls, includes gas passed to the callee.
+    pub gas_cost: u64,
+    /// Stack pointer after the instruction is executed.
+    pub sp: u64,
+    /// Registers modified by the instruction.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub register_deltas: Vec<RegisterDelta>,
+    /// Heap write performed by the instruction, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heap_write: Option<HeapWrite>,
+    /// Far call performed by the instruction, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub far_call: Option<FarCallFrame>,
+}
+
+/// New value of a VM register modified by an instruction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterDelta {
+    /// 1-based register index (`r1`..`r15`).
+    pub register: u8,
+    pub value: U256,
+    pub is_pointer: bool,
+}
+
+/// Write to the heap or auxiliary heap of the current frame.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeapWrite {
+    /// Whether the write is to the auxiliary heap.
+    pub aux: bool,
+    pub offset: u32,
+    pub value: U256,
+}
+
+/// Far call performed by an instruction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FarCallFrame {
+    /// Address of the called contract.
+    pub callee: Address,
+    /// Address of the contract whose code is executed by the call.
+    pub code_address: Address,
+    /// Gas passed to the callee.
+    pub gas: u64,
+}
+
+/// Options for `debug_traceCallExecution` and `debug_traceTransactionExecution`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionTraceConfig {
+    /// Index of the first returned frame; used to page through long traces. Defaults to 0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_frame: Option<u64>,
+    /// Maximum number of returned frames. If not specified or exceeds the server-side limit, the server-side limit is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_frames: Option<usize>,
+    /// State overrides for the traced call. Ignored by `debug_traceTransactionExecution`, which replays
+    /// the transaction on top of the original state.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+}
+
+/// Page of the instruction-level execution trace of a call or transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionTrace {
+    pub frames: Vec<ExecutionFrame>,
+    /// Whether some frames after the returned ones were omitted because of the frame limit.
+    pub truncated: bool,
+    /// Index of the first omitted frame, to be passed as `fromFrame` to get the next page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_frame: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+}
@@ -0,0 +1,47 @@
+//! Types for per-contract gas attribution returned by `unstable_gasProfile`.
+
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::{web3::Bytes, U256, U64};
+
+use crate::Address;
+
+/// Gas and pubdata usage attributed to a contract function (i.e., a contract address and a function selector).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractGasUsage {
+    pub contract: Address,
+    /// First 4 bytes of the calldata. `None` if the calldata is shorter than 4 bytes (e.g., for base token transfers).
+    pub selector: Option<Bytes>,
+    /// Number of far calls to the function.
+    pub calls: u64,
+    /// Gas used by the function calls, including nested calls. Recursive calls are counted multiple times.
+    pub gas_used: u64,
+    /// Gas used by the function calls, excluding nested far calls.
+    pub self_gas_used: u64,
+    /// Pubdata bytes for storage slots last written by the function.
+    pub pubdata_bytes: u64,
+}
+
+/// Gas profile of a transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasProfile {
+    /// Whether the transaction has succeeded.
+    pub success: bool,
+    /// Human-readable revert / halt reason if the transaction has failed.
+    pub revert_reason: Option<String>,
+    /// Total gas charged to the transaction, i.e. its gas limit minus the refunded gas.
+    pub gas_used: U256,
+    /// Part of `gas_used` charged for published pubdata.
+    pub pubdata_gas: U256,
+    /// Part of `gas_used` not spent by profiled far calls or on pubdata (e.g., intrinsic transaction overhead
+    /// and fee processing by the bootloader).
+    pub bootloader_gas: U256,
+    /// Total number of pubdata bytes published by the transaction, including ones not attributed to contracts
+    /// (e.g., L2-to-L1 messages and published bytecodes).
+    pub pubdata_published: U64,
+    pub gas_per_pubdata: U64,
+    /// Usage by contract functions, ordered by decreasing self gas usage. Includes account validation
+    /// and paymaster functions called by the bootloader.
+    pub contracts: Vec<ContractGasUsage>,
+}
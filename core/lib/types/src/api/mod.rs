@@ -1,11 +1,17 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    num::NonZeroU64,
+};
+
 use chrono::{DateTime, Utc};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 use serde_with::{hex::Hex, serde_as};
 use strum::Display;
 use zksync_basic_types::{
+    commitment::L1BatchCommitmentMode,
     web3::{AccessList, Bytes, Index},
-    Bloom, L1BatchNumber, SLChainId, H160, H256, H64, U256, U64,
+    Bloom, L1BatchNumber, L1BlockNumber, PriorityOpId, SLChainId, H160, H256, H64, U256, U64,
 };
 use zksync_contracts::BaseSystemContractsHashes;
 
@@ -13,6 +19,7 @@ pub use crate::transaction_request::{
     Eip712Meta, SerializationTransactionError, TransactionRequest,
 };
 use crate::{
+    base_token_ratio::BaseTokenRatioQuote,
     debug_flat_call::{DebugCallFlat, ResultDebugCallFlat},
     protocol_version::L1VerifierConfig,
     tee_types::TeeType,
@@ -20,6 +27,8 @@ use crate::{
 };
 
 pub mod en;
+pub mod execution_trace;
+pub mod gas_profile;
 pub mod state_override;
 
 /// Block Number
@@ -614,7 +623,7 @@ pub struct Transaction {
     pub l1_batch_tx_index: Option<U64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum TransactionStatus {
     Pending,
@@ -645,6 +654,52 @@ pub struct GetLogsFilter {
     pub topics: Vec<(u32, Vec<H256>)>,
 }
 
+/// Position of the last log returned by `unstable_getLogsPaginated`. Should be passed to the next call
+/// to get the following page of logs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogsCursor {
+    pub block_number: L2BlockNumber,
+    /// Index of the log in the block.
+    pub log_index: u32,
+}
+
+/// Page of logs returned by `unstable_getLogsPaginated`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogsPage {
+    pub logs: Vec<Log>,
+    /// Cursor to get the next page of logs. `None` if there are no more logs matching the filter.
+    pub next_cursor: Option<LogsCursor>,
+}
+
+/// Expected state of an account for `eth_sendRawTransactionConditional`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KnownAccountState {
+    /// Storage root of the account. Not supported by ZKsync since it doesn't have per-account storage tries.
+    StorageRoot(H256),
+    /// Expected values of the account storage slots.
+    Slots(HashMap<H256, H256>),
+}
+
+/// Preconditions for a transaction submitted via `eth_sendRawTransactionConditional`.
+/// The transaction is rejected if any of the conditions is not met.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionConditions {
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub known_accounts: HashMap<Address, KnownAccountState>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number_min: Option<U64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number_max: Option<U64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_min: Option<U64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_max: Option<U64>,
+}
+
 /// Result of debugging block
 /// For some reasons geth returns result as {result: DebugCall}
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -767,17 +822,54 @@ impl ProtocolVersion {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+/// Built-in tracers supported by `debug_trace*` methods. Tracer names and outputs match Geth.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum SupportedTracers {
     CallTracer,
     FlatCallTracer,
+    /// Returns the state accessed by the call before its execution, or the state modified by the call
+    /// before and after its execution in the diff mode.
+    PrestateTracer,
+    /// Counts called function selectors together with the call data size.
+    #[serde(rename = "4byteTracer")]
+    FourByteTracer,
 }
 
+impl SupportedTracers {
+    /// Returns the tracer name as specified in RPC requests.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::CallTracer => "callTracer",
+            Self::FlatCallTracer => "flatCallTracer",
+            Self::PrestateTracer => "prestateTracer",
+            Self::FourByteTracer => "4byteTracer",
+        }
+    }
+}
+
+/// Options for built-in tracers. Each tracer only uses options relevant to it and ignores the rest.
 #[derive(Debug, Serialize, Deserialize, Clone, Default, Copy)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct CallTracerConfig {
+    /// Only return the top-level call. Used by `callTracer` and `flatCallTracer`.
     pub only_top_call: bool,
+    /// Maximum depth of returned calls, with the top-level call having depth 0. Used by `callTracer` and `flatCallTracer`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_depth: Option<usize>,
+    /// Return only modified state before and after the call. Used by `prestateTracer`.
+    pub diff_mode: bool,
+}
+
+impl CallTracerConfig {
+    /// Returns the maximum depth of returned calls, taking [`Self::only_top_call`] into account.
+    pub fn effective_max_depth(&self) -> Option<usize> {
+        if self.only_top_call {
+            Some(0)
+        } else {
+            self.max_depth
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
@@ -792,13 +884,22 @@ impl Default for TracerConfig {
     fn default() -> Self {
         TracerConfig {
             tracer: SupportedTracers::CallTracer,
-            tracer_config: CallTracerConfig {
-                only_top_call: false,
-            },
+            tracer_config: CallTracerConfig::default(),
         }
     }
 }
 
+/// Options for `debug_traceCall`. In addition to the tracer options, allows overriding the state
+/// the call is executed on top of (same as in Geth).
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceCallConfig {
+    #[serde(flatten)]
+    pub tracer: TracerConfig,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<state_override::StateOverride>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum BlockStatus {
@@ -813,20 +914,36 @@ pub enum BlockStatus {
 pub enum CallTracerBlockResult {
     CallTrace(Vec<ResultDebugCall>),
     FlatCallTrace(Vec<ResultDebugCallFlat>),
+    FourByteTrace(Vec<ResultFourByteTrace>),
+    PrestateTrace(Vec<ResultPrestateTrace>),
 }
 
 impl CallTracerBlockResult {
     pub fn unwrap_flat(self) -> Vec<ResultDebugCallFlat> {
         match self {
-            Self::CallTrace(_) => panic!("Result is a FlatCallTrace"),
             Self::FlatCallTrace(trace) => trace,
+            _ => panic!("Result is not a FlatCallTrace"),
         }
     }
 
     pub fn unwrap_default(self) -> Vec<ResultDebugCall> {
         match self {
             Self::CallTrace(trace) => trace,
-            Self::FlatCallTrace(_) => panic!("Result is a CallTrace"),
+            _ => panic!("Result is not a CallTrace"),
+        }
+    }
+
+    pub fn unwrap_four_byte(self) -> Vec<ResultFourByteTrace> {
+        match self {
+            Self::FourByteTrace(trace) => trace,
+            _ => panic!("Result is not a FourByteTrace"),
+        }
+    }
+
+    pub fn unwrap_prestate(self) -> Vec<ResultPrestateTrace> {
+        match self {
+            Self::PrestateTrace(trace) => trace,
+            _ => panic!("Result is not a PrestateTrace"),
         }
     }
 }
@@ -836,22 +953,87 @@ impl CallTracerBlockResult {
 pub enum CallTracerResult {
     CallTrace(DebugCall),
     FlatCallTrace(Vec<DebugCallFlat>),
+    FourByteTrace(FourByteTrace),
+    PrestateTrace(PrestateTrace),
 }
 
 impl CallTracerResult {
     pub fn unwrap_flat(self) -> Vec<DebugCallFlat> {
         match self {
-            Self::CallTrace(_) => panic!("Result is a FlatCallTrace"),
             Self::FlatCallTrace(trace) => trace,
+            _ => panic!("Result is not a FlatCallTrace"),
         }
     }
 
     pub fn unwrap_default(self) -> DebugCall {
         match self {
             Self::CallTrace(trace) => trace,
-            Self::FlatCallTrace(_) => panic!("Result is a CallTrace"),
+            _ => panic!("Result is not a CallTrace"),
         }
     }
+
+    pub fn unwrap_four_byte(self) -> FourByteTrace {
+        match self {
+            Self::FourByteTrace(trace) => trace,
+            _ => panic!("Result is not a FourByteTrace"),
+        }
+    }
+
+    pub fn unwrap_prestate(self) -> PrestateTrace {
+        match self {
+            Self::PrestateTrace(trace) => trace,
+            _ => panic!("Result is not a PrestateTrace"),
+        }
+    }
+}
+
+/// Output of `4byteTracer`: numbers of calls keyed by the function selector and the size of call data
+/// excluding the selector, e.g. `0x27dc297e-128`.
+pub type FourByteTrace = BTreeMap<String, usize>;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultFourByteTrace {
+    pub tx_hash: H256,
+    pub result: FourByteTrace,
+}
+
+/// Account state returned by `prestateTracer`. Fields not accessed during execution are omitted.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PrestateAccount {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    /// Transaction nonce of the account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    /// Versioned bytecode hash of the account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_hash: Option<H256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<H256, H256>,
+}
+
+/// Output of `prestateTracer`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum PrestateTrace {
+    /// State accessed during execution, as it was before the execution.
+    Default(BTreeMap<Address, PrestateAccount>),
+    /// State modified during execution, before and after the execution (`diffMode`).
+    Diff {
+        pre: BTreeMap<Address, PrestateAccount>,
+        post: BTreeMap<Address, PrestateAccount>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultPrestateTrace {
+    pub tx_hash: H256,
+    pub result: PrestateTrace,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -896,6 +1078,9 @@ pub struct L1BatchDetails {
     pub number: L1BatchNumber,
     #[serde(flatten)]
     pub base: BlockDetailsBase,
+    /// Commitment mode used for the batch. May differ between batches if the chain has switched
+    /// between rollup and validium modes. `None` if returned by a server that doesn't support this field.
+    pub commitment_mode: Option<L1BatchCommitmentMode>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -914,6 +1099,39 @@ pub struct Proof {
     pub storage_proof: Vec<StorageProof>,
 }
 
+/// Storage slot write included into the state diff of an L1 batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateDiffEntry {
+    pub address: Address,
+    pub key: H256,
+    /// Hashed storage key, i.e. `blake2s(address, key)`.
+    pub derived_key: H256,
+    /// Enumeration index of the slot in the Merkle tree. For initial writes, this is the index assigned
+    /// to the slot in the batch; note that initial writes are published without an index.
+    pub enumeration_index: u64,
+    /// Slot value before the batch. Always zero for initial writes.
+    pub initial_value: H256,
+    /// Slot value after the batch.
+    pub final_value: H256,
+    /// Write as encoded in the published compressed state diff.
+    pub compressed: Bytes,
+}
+
+/// State diff of an L1 batch returned by `zks_getBatchStateDiff`. Matches the state diff published
+/// as a part of the batch pubdata.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchStateDiff {
+    pub l1_batch_number: L1BatchNumber,
+    /// Writes to slots written for the first time, in the order they are published.
+    pub initial_writes: Vec<StateDiffEntry>,
+    /// Writes to previously written slots, in the order they are published.
+    pub repeated_writes: Vec<StateDiffEntry>,
+    /// Compressed state diff (including the header) exactly as published in the batch pubdata.
+    pub compressed_state_diffs: Bytes,
+}
+
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -956,6 +1174,315 @@ pub struct TransactionExecutionInfo {
     pub execution_info: Value,
 }
 
+/// Result of `unstable_simulateTransaction`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionSimulation {
+    /// Whether the transaction has succeeded.
+    pub success: bool,
+    /// Data returned by the transaction, or the revert data if it has reverted.
+    pub output: Bytes,
+    /// Human-readable revert / halt reason if the transaction has failed.
+    pub revert_reason: Option<String>,
+    /// Events emitted by the transaction in the emission order.
+    pub logs: Vec<SimulatedLog>,
+    /// Token balance changes aggregated from transfers in [`Self::logs`].
+    pub balance_changes: Vec<BalanceChange>,
+    /// Storage slots modified by the transaction.
+    pub storage_diffs: Vec<StorageDiff>,
+    pub gas: SimulatedGasBreakdown,
+}
+
+/// Event emitted during transaction simulation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedLog {
+    pub address: Address,
+    pub topics: Vec<H256>,
+    pub data: Bytes,
+    /// Decoded token transfer if the event is an ERC-20 `Transfer` event (including base token transfers).
+    pub transfer: Option<TokenTransfer>,
+}
+
+/// Decoded token transfer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTransfer {
+    /// Token address. Base token transfers have the L2 base token contract address.
+    pub token: Address,
+    pub from: Address,
+    pub to: Address,
+    pub amount: U256,
+}
+
+/// Balance change of an account for a specific token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceChange {
+    pub token: Address,
+    pub account: Address,
+    /// Total amount received by the account.
+    pub received: U256,
+    /// Total amount sent by the account.
+    pub sent: U256,
+}
+
+/// Change of a storage slot value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageDiff {
+    pub address: Address,
+    pub key: H256,
+    pub previous_value: H256,
+    pub new_value: H256,
+}
+
+/// Breakdown of the gas used by a simulated transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedGasBreakdown {
+    /// Gas spent on computations.
+    pub execution_gas: U256,
+    /// Gas spent on publishing pubdata, i.e. `pubdata_published * gas_per_pubdata`.
+    pub pubdata_gas: U256,
+    /// Number of pubdata bytes published by the transaction.
+    pub pubdata_published: U64,
+    pub gas_per_pubdata: U64,
+}
+
+/// Processing status of a priority operation as seen by the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PriorityOpStatus {
+    /// Operation is in the mempool and wasn't executed yet.
+    Pending,
+    /// Operation is executed in an L2 block of the pending L1 batch.
+    IncludedInL2Block,
+    /// Operation is included into a sealed L1 batch, which isn't executed on L1 yet.
+    IncludedInL1Batch,
+}
+
+/// Priority operation (L1->L2 transaction) in the L1 priority queue returned by `unstable_getPriorityQueue`.
+///
+/// Operations are removed from the queue on L1 once the L1 batch including them is executed.
+/// Expiration timestamps of operations are not provided since the node doesn't persist them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriorityOpInfo {
+    pub serial_id: PriorityOpId,
+    pub tx_hash: H256,
+    /// L1 block in which the operation was requested.
+    pub eth_block: Option<L1BlockNumber>,
+    /// Time when the operation was received by the node.
+    pub received_at: DateTime<Utc>,
+    pub status: PriorityOpStatus,
+    /// L2 block including the operation, if any.
+    pub l2_block_number: Option<L2BlockNumber>,
+    /// L1 batch including the operation. For operations not included into a sealed L1 batch,
+    /// this is the pending L1 batch, in which they are expected to be executed.
+    pub l1_batch_number: L1BatchNumber,
+}
+
+/// Processing status of an L1-originated transaction (a priority operation or a protocol upgrade transaction)
+/// returned by `unstable_getL1TxProcessingStatus`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1TxProcessingStatus {
+    pub tx_hash: H256,
+    /// Serial ID of the priority operation; `None` for protocol upgrade transactions.
+    pub serial_id: Option<PriorityOpId>,
+    /// Protocol version introduced by the upgrade; `None` for priority operations.
+    pub upgrade_id: Option<ProtocolVersionId>,
+    /// L1 block in which the transaction was requested.
+    pub eth_block: Option<L1BlockNumber>,
+    /// Time when the transaction was received by the node.
+    pub received_at: DateTime<Utc>,
+    /// Transaction status. `Verified` means that the L1 batch including the transaction is executed on L1.
+    pub status: TransactionStatus,
+    /// L2 block including the transaction, if any.
+    pub l2_block_number: Option<L2BlockNumber>,
+    /// Sealed L1 batch including the transaction, if any.
+    pub l1_batch_number: Option<L1BatchNumber>,
+    /// Hash of the confirmed L1 transaction executing the L1 batch, if any.
+    pub eth_execute_tx_hash: Option<H256>,
+}
+
+/// Congestion of the proof / execution pipeline returned by `unstable_getPipelineCongestion`.
+///
+/// While the pipeline is congested, the state keeper slows down L1 batch production, and the API multiplies
+/// the L2 gas price by `gas_price_multiplier`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PipelineCongestion {
+    /// Latest sealed L1 batch.
+    pub sealed_l1_batch: L1BatchNumber,
+    /// Number of sealed L1 batches not proven on L1.
+    pub unproven_l1_batches: u32,
+    /// Number of sealed L1 batches not executed on L1.
+    pub unexecuted_l1_batches: u32,
+    pub max_unproven_l1_batches: Option<u32>,
+    pub max_unexecuted_l1_batches: Option<u32>,
+    pub is_congested: bool,
+    /// Multiplier currently applied to the L2 gas price; 1 if the pipeline isn't congested.
+    pub gas_price_multiplier: f64,
+}
+
+/// Kind of a base system contract that can be staged ahead of a protocol upgrade.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+    strum::AsRefStr
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "snake_case")]
+pub enum BaseSystemContractKind {
+    Bootloader,
+    DefaultAccount,
+    EvmEmulator,
+}
+
+/// Validation status of a staged base system contract.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+    strum::AsRefStr
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "snake_case")]
+pub enum StagedContractStatus {
+    /// Upgrade for the protocol version wasn't observed on L1 yet.
+    Pending,
+    /// Bytecode hash matches the one in the upgrade on L1; the bytecode will be used starting from the upgrade batch.
+    Validated,
+    /// Bytecode hash differs from the one in the upgrade on L1; the bytecode will never be used.
+    Mismatch,
+}
+
+/// Base system contract staged ahead of a protocol upgrade returned by `unstable_getStagedBaseSystemContracts`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StagedBaseSystemContract {
+    /// Protocol version the contract is staged for.
+    pub protocol_version: ProtocolVersionId,
+    pub kind: BaseSystemContractKind,
+    pub bytecode_hash: H256,
+    /// Bytecode length in bytes.
+    pub bytecode_len: usize,
+    pub status: StagedContractStatus,
+    /// Time when the contract was (last) staged.
+    pub staged_at: DateTime<Utc>,
+}
+
+/// Base token conversion ratio together with the raw quotes it was derived from, returned by
+/// `unstable_getBaseTokenRatioAudit`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaseTokenRatioAuditEntry {
+    /// ID of the persisted ratio.
+    pub ratio_id: u32,
+    pub numerator: NonZeroU64,
+    pub denominator: NonZeroU64,
+    /// Time of the quote (or of the request to the price source if the source doesn't provide quote times).
+    pub ratio_timestamp: DateTime<Utc>,
+    /// Configured price source, e.g. `coingecko` or `aggregated`.
+    pub source: String,
+    /// Raw quotes from individual price sources.
+    pub quotes: Vec<BaseTokenRatioQuote>,
+    /// Time when the ratio was persisted.
+    pub created_at: DateTime<Utc>,
+    /// First L1 batch with the fee input computed using this ratio, if any.
+    pub first_l1_batch_number: Option<L1BatchNumber>,
+    /// Last L1 batch with the fee input computed using this ratio, if any.
+    pub last_l1_batch_number: Option<L1BatchNumber>,
+}
+
+/// Storage usage of a contract aggregated over a range of L1 batches, returned by `unstable_getTopPubdataConsumers`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractStorageUsage {
+    pub address: Address,
+    /// Number of storage slot changes, i.e. storage slots with the value changed by a batch summed over all batches.
+    pub storage_writes: u64,
+    /// Number of storage slots written to for the first time.
+    pub initial_writes: u64,
+    /// Total size of compressed state diffs published for the contract.
+    pub pubdata_bytes: u64,
+    /// Number of L1 batches in which the contract changed its storage.
+    pub l1_batch_count: u64,
+}
+
+/// Kind of a bridge event indexed by the bridge indexer.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+    strum::AsRefStr
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "snake_case")]
+pub enum BridgeTransferKind {
+    /// Deposit initiated on L1 via the shared bridge.
+    DepositInitiated,
+    /// Deposit finalized on L2, i.e. tokens were minted or released to the L2 receiver.
+    DepositFinalized,
+    /// Withdrawal initiated on L2.
+    WithdrawalInitiated,
+    /// Withdrawal finalized on L1 via the shared bridge.
+    WithdrawalFinalized,
+}
+
+/// Bridge event affecting an account returned by `zks_getBridgeTransfers`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BridgeTransfer {
+    pub kind: BridgeTransferKind,
+    /// L1 sender (for deposits) or receiver (for withdrawals). May be unknown for base token deposits finalized on L2.
+    pub l1_address: Option<Address>,
+    /// L2 receiver (for deposits) or sender (for withdrawals). Unknown for withdrawals finalized on L1.
+    pub l2_address: Option<Address>,
+    /// Token address on L1. Set for events emitted on L1.
+    pub l1_token: Option<Address>,
+    /// Token address on L2. Set for events emitted on L2.
+    pub l2_token: Option<Address>,
+    pub amount: U256,
+    /// Hash of the L1 or L2 transaction emitting the event.
+    pub tx_hash: H256,
+    /// L1 block containing the event. Set for events emitted on L1.
+    pub l1_block_number: Option<U64>,
+    /// L2 block containing the event. Set for events emitted on L2.
+    pub l2_block_number: Option<L2BlockNumber>,
+}
+
+/// Page of bridge transfers returned by `zks_getBridgeTransfers`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BridgeTransfersPage {
+    pub transfers: Vec<BridgeTransfer>,
+    /// Cursor to get the next page of transfers. `None` if there are no more transfers for the account.
+    pub next_cursor: Option<U64>,
+}
+
 /// The fee history type returned from `eth_feeHistory` call.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -967,6 +1494,25 @@ pub struct FeeHistory {
     pub l2_pubdata_price: Vec<U256>,
 }
 
+/// The fee history type returned from `zks_getFeeHistory` call.
+///
+/// Contains the components of the L2 gas price used for each L2 block of the range, in ascending order
+/// of L2 block numbers.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZksFeeHistory {
+    /// Lowest L2 block number of the returned range.
+    pub oldest_block: L2BlockNumber,
+    pub base_fee_per_gas: Vec<U256>,
+    pub fair_l2_gas_price: Vec<U64>,
+    /// Fair pubdata prices. For blocks produced before the pubdata price was introduced,
+    /// the price derived from the L1 gas price is returned.
+    pub fair_pubdata_price: Vec<U64>,
+    /// L1 gas prices used when producing the blocks. For chains using blobs, the L1 blob price
+    /// is included in `fair_pubdata_price`.
+    pub l1_gas_price: Vec<U64>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1001,4 +1547,63 @@ mod tests {
         serde_json::from_str::<OldProtocolVersion>(&serde_json::to_string(&new_version).unwrap())
             .unwrap();
     }
+
+    #[test]
+    fn deserializing_trace_call_config() {
+        let config: TraceCallConfig = serde_json::from_value(serde_json::json!({
+            "tracer": "callTracer",
+            "tracerConfig": { "onlyTopCall": true },
+            "stateOverrides": {
+                "0x0000000000000000000000000000000000000123": {
+                    "balance": "0x1000",
+                },
+            },
+        }))
+        .unwrap();
+        assert!(config.tracer.tracer_config.only_top_call);
+        let state_overrides = config.state_overrides.unwrap();
+        let account = state_overrides
+            .get(&Address::from_low_u64_be(0x123))
+            .unwrap();
+        assert_eq!(account.balance, Some(U256::from(0x1000)));
+
+        let config: TraceCallConfig =
+            serde_json::from_value(serde_json::json!({ "tracer": "flatCallTracer" })).unwrap();
+        assert!(matches!(
+            config.tracer.tracer,
+            SupportedTracers::FlatCallTracer
+        ));
+        assert!(config.state_overrides.is_none());
+    }
+
+    #[test]
+    fn deserializing_transaction_conditions() {
+        let conditions: TransactionConditions = serde_json::from_value(serde_json::json!({
+            "knownAccounts": {
+                "0x0000000000000000000000000000000000000123": {
+                    "0x0000000000000000000000000000000000000000000000000000000000000001":
+                        "0x0000000000000000000000000000000000000000000000000000000000000002",
+                },
+                "0x0000000000000000000000000000000000000456":
+                    "0x0000000000000000000000000000000000000000000000000000000000000003",
+            },
+            "blockNumberMax": "0x10",
+            "timestampMin": "0x5",
+        }))
+        .unwrap();
+
+        let account = &conditions.known_accounts[&Address::from_low_u64_be(0x123)];
+        let KnownAccountState::Slots(slots) = account else {
+            panic!("unexpected account state: {account:?}");
+        };
+        assert_eq!(slots[&H256::from_low_u64_be(1)], H256::from_low_u64_be(2));
+        assert_eq!(
+            conditions.known_accounts[&Address::from_low_u64_be(0x456)],
+            KnownAccountState::StorageRoot(H256::from_low_u64_be(3))
+        );
+        assert_eq!(conditions.block_number_min, None);
+        assert_eq!(conditions.block_number_max, Some(U64::from(16)));
+        assert_eq!(conditions.timestamp_min, Some(U64::from(5)));
+        assert_eq!(conditions.timestamp_max, None);
+    }
 }
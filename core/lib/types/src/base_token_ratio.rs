@@ -1,6 +1,7 @@
 use std::num::NonZeroU64;
 
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
 /// Represents the base token to ETH conversion ratio at a given point in time.
 #[derive(Debug, Clone)]
@@ -21,6 +22,31 @@ pub struct BaseTokenAPIRatio {
     pub ratio_timestamp: DateTime<Utc>,
 }
 
+/// Raw quote returned by a price source, persisted together with the base token ratio derived from it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaseTokenRatioQuote {
+    /// Name of the price source, e.g. `coingecko`.
+    pub source: String,
+    pub numerator: NonZeroU64,
+    pub denominator: NonZeroU64,
+    pub ratio_timestamp: DateTime<Utc>,
+    /// Whether the quote was used to derive the ratio. E.g., stale quotes are ignored by the aggregated client.
+    pub accepted: bool,
+}
+
+impl BaseTokenRatioQuote {
+    pub fn new(source: impl Into<String>, ratio: &BaseTokenAPIRatio, accepted: bool) -> Self {
+        Self {
+            source: source.into(),
+            numerator: ratio.numerator,
+            denominator: ratio.denominator,
+            ratio_timestamp: ratio.ratio_timestamp,
+            accepted,
+        }
+    }
+}
+
 impl Default for BaseTokenAPIRatio {
     fn default() -> Self {
         Self {
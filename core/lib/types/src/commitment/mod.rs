@@ -721,6 +721,7 @@ impl L1BatchCommitment {
             local_root: self.auxiliary_output.local_root(),
             aggregation_root: self.auxiliary_output.aggregation_root(),
             state_diff_hash: self.auxiliary_output.state_diff_hash(),
+            aux_output_preimage: Some(self.auxiliary_output.to_bytes()),
         }
     }
 }
@@ -822,4 +823,6 @@ pub struct L1BatchCommitmentArtifacts {
     pub aggregation_root: H256,
     pub local_root: H256,
     pub state_diff_hash: H256,
+    /// Preimage of the auxiliary output hash (`commitment_hash.aux_output`).
+    pub aux_output_preimage: Option<Vec<u8>>,
 }
@@ -7,7 +7,7 @@ use serde::{
 };
 
 pub use crate::Execute as ExecuteData;
-use crate::{web3::Bytes, Address};
+use crate::{web3::Bytes, Address, H256};
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "codeFormat", content = "sourceCode")]
@@ -18,6 +18,8 @@ pub enum SourceCodeData {
     StandardJsonInput(serde_json::Map<String, serde_json::Value>),
     #[serde(rename = "vyper-multi-file")]
     VyperMultiFile(HashMap<String, String>),
+    #[serde(rename = "vyper-standard-json-input")]
+    VyperStandardJsonInput(serde_json::Map<String, serde_json::Value>),
     #[serde(rename = "yul-single-file")]
     YulSingleFile(String),
 }
@@ -28,7 +30,9 @@ impl SourceCodeData {
             SourceCodeData::SolSingleFile(_)
             | SourceCodeData::StandardJsonInput(_)
             | SourceCodeData::YulSingleFile(_) => CompilerType::Solc,
-            SourceCodeData::VyperMultiFile(_) => CompilerType::Vyper,
+            SourceCodeData::VyperMultiFile(_) | SourceCodeData::VyperStandardJsonInput(_) => {
+                CompilerType::Vyper
+            }
         }
     }
 }
@@ -99,6 +103,17 @@ impl<'de> Visitor<'de> for SourceCodeVisitor {
                         .clone(),
                 )
             }
+            Some("vyper-standard-json-input") => {
+                let value = source_code.ok_or_else(|| A::Error::missing_field("source_code"))?;
+                SourceCodeData::VyperStandardJsonInput(
+                    value
+                        .as_object()
+                        .ok_or_else(|| {
+                            A::Error::invalid_type(Unexpected::Other(&value.to_string()), &self)
+                        })?
+                        .clone(),
+                )
+            }
             Some("vyper-multi-file") => {
                 let value = source_code.ok_or_else(|| A::Error::missing_field("source_code"))?;
                 let obj = value
@@ -119,6 +134,7 @@ impl<'de> Visitor<'de> for SourceCodeVisitor {
                         "solidity-standard-json-input",
                         "yul-single-file",
                         "vyper-multi-file",
+                        "vyper-standard-json-input",
                     ],
                 ))
             }
@@ -240,6 +256,15 @@ pub struct VerificationInfo {
     pub verified_at: DateTime<Utc>,
 }
 
+/// Verification info exported to be imported into another environment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedVerificationInfo {
+    pub info: VerificationInfo,
+    /// Hash of the bytecode deployed at the contract address in the exporting environment, if any.
+    pub bytecode_hash: Option<H256>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct VerificationRequestStatus {
@@ -250,6 +275,51 @@ pub struct VerificationRequestStatus {
     pub compilation_errors: Option<Vec<String>>,
 }
 
+#[doc(hidden)] // only useful for tests
+pub mod testonly {
+    use super::*;
+
+    /// Creates verification info for a trivial Solidity contract deployed at `address`.
+    pub fn mock_verification_info(address: Address) -> VerificationInfo {
+        VerificationInfo {
+            request: VerificationRequest {
+                id: 1,
+                req: VerificationIncomingRequest {
+                    contract_address: address,
+                    source_code_data: SourceCodeData::SolSingleFile("contract Test {}".to_owned()),
+                    contract_name: "Test".to_owned(),
+                    compiler_versions: CompilerVersions::Solc {
+                        compiler_zksolc_version: Some("1.5.7".to_owned()),
+                        compiler_solc_version: "0.8.27".to_owned(),
+                    },
+                    optimization_used: true,
+                    optimizer_mode: None,
+                    constructor_arguments: Bytes(vec![]),
+                    is_system: false,
+                    force_evmla: false,
+                },
+            },
+            artifacts: CompilationArtifacts {
+                bytecode: vec![0; 32],
+                deployed_bytecode: None,
+                abi: serde_json::Value::Array(vec![]),
+            },
+            verified_at: Utc::now(),
+        }
+    }
+
+    /// Creates exported verification info based on [`mock_verification_info()`].
+    pub fn mock_exported_verification_info(
+        address: Address,
+        bytecode_hash: Option<H256>,
+    ) -> ExportedVerificationInfo {
+        ExportedVerificationInfo {
+            info: mock_verification_info(address),
+            bytecode_hash,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
@@ -270,6 +340,14 @@ mod tests {
             Ok(SourceCodeData::StandardJsonInput(_))
         );
 
+        let vyper_json_input_str =
+            r#"{"codeFormat": "vyper-standard-json-input", "sourceCode": {}}"#;
+        let vyper_json_input_result = serde_json::from_str::<SourceCodeData>(vyper_json_input_str);
+        assert_matches!(
+            vyper_json_input_result,
+            Ok(SourceCodeData::VyperStandardJsonInput(_))
+        );
+
         let type_not_specified_str = r#"{"sourceCode": "text"}"#;
         let type_not_specified_result =
             serde_json::from_str::<SourceCodeData>(type_not_specified_str);
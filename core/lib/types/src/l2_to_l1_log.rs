@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
+use zksync_crypto_primitives::hasher::{keccak::KeccakHasher, Hasher};
+use zksync_mini_merkle_tree::MiniMerkleTree;
 use zksync_system_constants::{BLOB1_LINEAR_HASH_KEY_PRE_GATEWAY, PUBDATA_CHUNK_PUBLISHER_ADDRESS};
 
 use crate::{
+    api::L2ToL1LogProof,
     blob::{num_blobs_created, num_blobs_required},
     commitment::SerializeCommitment,
     Address, ProtocolVersionId, H256,
@@ -99,6 +102,62 @@ pub const CHAIN_ID_LEAF_PADDING: H256 = H256([
     0x95, 0xcf, 0x17, 0x5c, 0xfb, 0xcf, 0x1a, 0xde, 0x1a, 0x47, 0xa2, 0x53, 0xb4, 0xbf, 0x7f, 0x61,
 ]);
 
+/// Builds a proof for the L2-to-L1 log with the specified index among all L2-to-L1 logs of an L1 batch.
+/// The proof has the format expected by L1 contracts (e.g., by `finalizeWithdrawal` of the shared bridge).
+///
+/// `aggregation_root` must be provided for post-gateway protocol versions. `batch_chain_proof` must be provided
+/// if the batch was settled on a gateway rather than on L1.
+pub fn l2_to_l1_log_proof(
+    logs: &[L2ToL1Log],
+    log_index: usize,
+    protocol_version: ProtocolVersionId,
+    aggregation_root: Option<H256>,
+    batch_chain_proof: Option<BatchAndChainMerklePath>,
+) -> L2ToL1LogProof {
+    let merkle_tree_leaves = logs.iter().map(L2ToL1Log::to_bytes);
+    let tree_size = l2_to_l1_logs_tree_size(protocol_version);
+    let (local_root, proof) =
+        MiniMerkleTree::new(merkle_tree_leaves, Some(tree_size)).merkle_root_and_path(log_index);
+
+    if protocol_version.is_pre_gateway() {
+        return L2ToL1LogProof {
+            proof,
+            root: local_root,
+            id: log_index as u32,
+        };
+    }
+
+    let aggregation_root =
+        aggregation_root.expect("`aggregation_root` must be present for post-gateway branch");
+    let root = KeccakHasher.compress(&local_root, &aggregation_root);
+
+    let mut log_leaf_proof = proof;
+    log_leaf_proof.push(aggregation_root);
+
+    let (batch_proof_len, batch_chain_proof) =
+        batch_chain_proof.map_or((0, Vec::new()), |path| (path.batch_proof_len, path.proof));
+
+    let proof = {
+        let mut metadata = [0u8; 32];
+        metadata[0] = LOG_PROOF_SUPPORTED_METADATA_VERSION;
+        metadata[1] = log_leaf_proof.len() as u8;
+        metadata[2] = batch_proof_len as u8;
+
+        let mut result = vec![H256(metadata)];
+
+        result.extend(log_leaf_proof);
+        result.extend(batch_chain_proof);
+
+        result
+    };
+
+    L2ToL1LogProof {
+        proof,
+        root,
+        id: log_index as u32,
+    }
+}
+
 /// Returns the blob hashes parsed out from the system logs
 pub fn parse_system_logs_for_blob_hashes_pre_gateway(
     protocol_version: &ProtocolVersionId,
@@ -415,7 +415,12 @@ impl Transaction {
     }
 }
 
+/// Constraints on the L2 block including a transaction. Transactions violating them are rejected by the state keeper.
 #[derive(Clone, Serialize, Debug, Default, Eq, PartialEq, Hash)]
 pub struct TransactionTimeRangeConstraint {
     pub timestamp_asserter_range: Option<Range<u64>>,
+    /// Range of L2 block numbers the transaction may be included in (`eth_sendRawTransactionConditional`).
+    pub l2_block_number_range: Option<Range<u64>>,
+    /// Storage values expected at the time of the transaction execution (`eth_sendRawTransactionConditional`).
+    pub expected_storage_values: Vec<(StorageKey, H256)>,
 }
@@ -6,7 +6,11 @@ use serde::{Deserialize, Serialize};
 use zksync_basic_types::{AccountTreeId, L1BatchNumber, L2BlockNumber, H256};
 use zksync_protobuf::{required, ProtoFmt};
 
-use crate::{u256_to_h256, utils, web3::Bytes, ProtocolVersionId, StorageKey, StorageValue, U256};
+use crate::{
+    u256_to_h256, utils,
+    web3::{keccak256, Bytes},
+    ProtocolVersionId, StorageKey, StorageValue, U256,
+};
 
 /// Information about all snapshots persisted by the node.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,6 +44,9 @@ pub struct SnapshotMetadata {
     /// Paths to the storage log blobs. Ordered by the chunk ID. If a certain chunk is not produced yet,
     /// the corresponding path is `None`.
     pub storage_logs_filepaths: Vec<Option<String>>,
+    /// Checksums of the storage log chunks (see [`SnapshotStorageLogsChunk::checksum()`]). Ordered by the chunk ID.
+    /// The checksum is `None` if the chunk is not produced yet, or if the snapshot was created before checksums were introduced.
+    pub storage_logs_checksums: Vec<Option<H256>>,
 }
 
 impl SnapshotMetadata {
@@ -63,6 +70,32 @@ pub struct SnapshotHeader {
     /// Ordered by chunk IDs.
     pub storage_logs_chunks: Vec<SnapshotStorageLogsChunkMetadata>,
     pub factory_deps_filepath: String,
+    /// Digest of the snapshot manifest, i.e. the header fields and storage log chunk checksums.
+    /// See [`Self::compute_manifest_hash()`]. `None` for snapshots created before checksums were introduced.
+    ///
+    /// The digest is not signed, so on its own it only detects accidental corruption. To protect against
+    /// a malicious snapshot source, the expected digest must be pinned by the recovering node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest_hash: Option<H256>,
+}
+
+impl SnapshotHeader {
+    /// Computes the manifest digest for this header. Returns `None` if any of the storage log chunks
+    /// doesn't have a checksum.
+    ///
+    /// The digest is `keccak256(version || l1_batch_number || l2_block_number || chunk_count || chunk_checksums)`,
+    /// with all integers encoded as big-endian and checksums ordered by the chunk ID.
+    pub fn compute_manifest_hash(&self) -> Option<H256> {
+        let mut preimage = Vec::with_capacity(18 + 32 * self.storage_logs_chunks.len());
+        preimage.extend_from_slice(&self.version.to_be_bytes());
+        preimage.extend_from_slice(&self.l1_batch_number.0.to_be_bytes());
+        preimage.extend_from_slice(&self.l2_block_number.0.to_be_bytes());
+        preimage.extend_from_slice(&(self.storage_logs_chunks.len() as u64).to_be_bytes());
+        for chunk in &self.storage_logs_chunks {
+            preimage.extend_from_slice(chunk.checksum?.as_bytes());
+        }
+        Some(H256(keccak256(&preimage)))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -71,6 +104,9 @@ pub struct SnapshotStorageLogsChunkMetadata {
     pub chunk_id: u64,
     // can be either be a file available under HTTP(s) or local filesystem path
     pub filepath: String,
+    /// Checksum of the chunk contents; see [`SnapshotStorageLogsChunk::checksum()`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<H256>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -85,6 +121,17 @@ pub struct SnapshotStorageLogsChunk<K = H256> {
     pub storage_logs: Vec<SnapshotStorageLog<K>>,
 }
 
+impl<K> SnapshotStorageLogsChunk<K>
+where
+    Self: ProtoFmt,
+{
+    /// Computes the checksum of this chunk as keccak256 of its Protobuf encoding.
+    /// The checksum doesn't depend on the compression used when storing the chunk.
+    pub fn checksum(&self) -> H256 {
+        H256(keccak256(&zksync_protobuf::encode(self)))
+    }
+}
+
 /// Storage log record in a storage snapshot.
 ///
 /// Version 0 and version 1 snapshots differ in the key type; version 0 uses full [`StorageKey`]s (i.e., storage key preimages),
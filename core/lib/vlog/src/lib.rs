@@ -135,9 +135,13 @@ impl ObservabilityBuilder {
 
         // For now we use logs filter as a global filter for subscriber.
         // Later we may want to enforce each layer to have its own filter.
-        let global_filter = logs.build_filter();
+        // Both filters are reloadable so that log directives can be changed at runtime.
+        let mut directives_handle = logs.directives_handle();
+        let global_filter = logs.build_reloadable_filter(&mut directives_handle);
+        let logs_filter = logs.build_reloadable_filter(&mut directives_handle);
 
-        let logs_layer = logs.into_layer();
+        let correlation_layer = logs.correlation_layer();
+        let logs_layer = logs.into_layer_with_filter(logs_filter);
         let (otlp_tracing_provider, otlp_tracing_layer) = self
             .opentelemetry_layer
             .as_ref()
@@ -150,11 +154,13 @@ impl ObservabilityBuilder {
 
         tracing_subscriber::registry()
             .with(global_filter)
+            .with(correlation_layer)
             .with(logs_layer)
             .with(otlp_tracing_layer)
             .with(otlp_logging_layer)
             .try_init()
             .context("failed installing global tracer / logger")?;
+        directives_handle.install();
 
         let sentry_guard = self.sentry.map(|sentry| sentry.install());
 
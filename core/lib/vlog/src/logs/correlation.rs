@@ -0,0 +1,194 @@
+//! Trace / span correlation IDs for JSON logs.
+
+use std::{
+    collections::hash_map::RandomState,
+    fmt,
+    hash::{BuildHasher, Hasher},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use tracing::{span, Event, Subscriber};
+use tracing_subscriber::{
+    fmt::{
+        format::{self, FormatEvent, FormatFields, Writer},
+        time::FormatTime,
+        FmtContext,
+    },
+    layer::Context,
+    registry::LookupSpan,
+    Layer,
+};
+
+/// Correlation IDs assigned to each span.
+///
+/// The trace ID is inherited from the parent span (or generated for root spans), so that all logs produced
+/// within a logical operation share it. To propagate IDs across spawned Tokio tasks, instrument the spawned futures
+/// with the parent span, e.g. using [`tracing::Instrument::in_current_span()`].
+#[derive(Debug, Clone, Copy)]
+struct CorrelationIds {
+    trace_id: u128,
+    span_id: u64,
+}
+
+fn random_u64() -> u64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    // `RandomState` is seeded randomly; hashing a counter ensures that the output differs between calls
+    // even if the seed is reused.
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    hasher.finish()
+}
+
+/// Layer assigning [`CorrelationIds`] to spans.
+#[derive(Debug, Default)]
+pub(crate) struct CorrelationLayer(());
+
+impl<S> Layer<S> for CorrelationLayer
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    fn on_new_span(&self, _attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let parent_trace_id = span.parent().and_then(|parent| {
+            let extensions = parent.extensions();
+            extensions.get::<CorrelationIds>().map(|ids| ids.trace_id)
+        });
+        let trace_id = parent_trace_id
+            .unwrap_or_else(|| (u128::from(random_u64()) << 64) | u128::from(random_u64()));
+        let ids = CorrelationIds {
+            trace_id,
+            span_id: random_u64(),
+        };
+        span.extensions_mut().insert(ids);
+    }
+}
+
+/// JSON event format that adds `trace_id` and `span_id` fields for the innermost span containing the event.
+#[derive(Debug)]
+pub(crate) struct CorrelatedJson<T> {
+    inner: format::Format<format::Json, T>,
+}
+
+impl<T> CorrelatedJson<T> {
+    pub(crate) fn new(inner: format::Format<format::Json, T>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<S, N, T> FormatEvent<S, N> for CorrelatedJson<T>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    N: for<'writer> FormatFields<'writer> + 'static,
+    T: FormatTime,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let ids = ctx
+            .event_scope()
+            .and_then(|mut scope| scope.next())
+            .and_then(|span| span.extensions().get::<CorrelationIds>().copied());
+        let Some(ids) = ids else {
+            return self.inner.format_event(ctx, writer, event);
+        };
+
+        let mut buffer = String::new();
+        self.inner
+            .format_event(ctx, Writer::new(&mut buffer), event)?;
+        // The inner format outputs a single JSON object; we splice correlation IDs as its first fields.
+        let Some(rest) = buffer.strip_prefix('{') else {
+            return writer.write_str(&buffer);
+        };
+        write!(
+            writer,
+            "{{\"trace_id\":\"{:032x}\",\"span_id\":\"{:016x}\",{rest}",
+            ids.trace_id, ids.span_id
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+
+    use tracing_subscriber::{fmt::MakeWriter, layer::SubscriberExt};
+
+    use super::*;
+
+    #[derive(Debug, Clone, Default)]
+    struct BufferWriter(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for BufferWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for BufferWriter {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    impl BufferWriter {
+        fn lines(&self) -> Vec<serde_json::Value> {
+            let buffer = self.0.lock().unwrap();
+            let buffer = std::str::from_utf8(&buffer).unwrap();
+            buffer
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect()
+        }
+    }
+
+    #[test]
+    fn correlation_ids_are_propagated_to_child_spans() {
+        let writer = BufferWriter::default();
+        let json_layer = tracing_subscriber::fmt::layer()
+            .json()
+            .event_format(CorrelatedJson::new(format::format().json()))
+            .with_writer(writer.clone());
+        let subscriber = tracing_subscriber::registry()
+            .with(CorrelationLayer::default())
+            .with(json_layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("outside span");
+            let root = tracing::info_span!("root");
+            root.in_scope(|| {
+                tracing::info!("in root");
+                tracing::info_span!("child").in_scope(|| tracing::info!("in child"));
+            });
+            tracing::info_span!("other_root").in_scope(|| tracing::info!("in other root"));
+        });
+
+        let lines = writer.lines();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].get("trace_id").is_none());
+        assert_eq!(lines[0]["fields"]["message"], "outside span");
+
+        let root_trace_id = lines[1]["trace_id"].as_str().unwrap();
+        assert_eq!(root_trace_id.len(), 32);
+        assert_eq!(lines[2]["trace_id"], root_trace_id);
+        assert_ne!(lines[2]["span_id"], lines[1]["span_id"]);
+        assert_eq!(lines[2]["fields"]["message"], "in child");
+        assert_eq!(lines[2]["span"]["name"], "child");
+        assert_ne!(lines[3]["trace_id"], root_trace_id);
+    }
+}
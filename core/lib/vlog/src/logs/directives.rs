@@ -0,0 +1,162 @@
+//! Runtime reloading of log directives.
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use tracing_subscriber::{filter::ParseError, reload, EnvFilter};
+
+/// Errors that can occur when reloading log directives.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum LogDirectivesError {
+    #[error("invalid log directives: {0}")]
+    Parse(#[from] ParseError),
+    #[error("failed reloading log filter: {0}")]
+    Reload(#[from] reload::Error),
+}
+
+type Reloader = Box<dyn Fn(EnvFilter) -> Result<(), reload::Error> + Send + Sync>;
+
+struct HandleInner {
+    default_directives: &'static str,
+    initial_directives: String,
+    current_directives: Mutex<String>,
+    reloaders: Vec<Reloader>,
+}
+
+/// Handle allowing to change log directives (in the `RUST_LOG` format) at runtime, e.g. to enable debug logs
+/// for a single module without restarting the node.
+///
+/// The handle is installed globally when the observability subsystem is initialized; it can be obtained
+/// using [`Self::global()`].
+#[derive(Clone)]
+pub struct LogDirectivesHandle {
+    inner: Arc<HandleInner>,
+}
+
+impl fmt::Debug for LogDirectivesHandle {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("LogDirectivesHandle")
+            .field("default_directives", &self.inner.default_directives)
+            .field("current_directives", &self.current())
+            .finish_non_exhaustive()
+    }
+}
+
+static GLOBAL_HANDLE: OnceLock<LogDirectivesHandle> = OnceLock::new();
+
+impl LogDirectivesHandle {
+    pub(super) fn new(default_directives: &'static str, initial_directives: String) -> Self {
+        Self {
+            inner: Arc::new(HandleInner {
+                default_directives,
+                current_directives: Mutex::new(initial_directives.clone()),
+                initial_directives,
+                reloaders: vec![],
+            }),
+        }
+    }
+
+    /// Adds a reloadable filter controlled by this handle. Must be called before the handle is shared.
+    pub(super) fn add_filter<S: 'static>(&mut self, handle: reload::Handle<EnvFilter, S>) {
+        let inner = Arc::get_mut(&mut self.inner).expect("handle is shared");
+        inner
+            .reloaders
+            .push(Box::new(move |filter| handle.reload(filter)));
+    }
+
+    pub(super) fn full_directives(default_directives: &str, directives: &str) -> String {
+        format!("{default_directives}{directives}")
+    }
+
+    /// Installs this handle globally. Logs a warning if a handle is already installed.
+    pub(crate) fn install(self) {
+        if GLOBAL_HANDLE.set(self).is_err() {
+            tracing::warn!("Log directives handle is already installed; new handle is ignored");
+        }
+    }
+
+    /// Returns the globally installed handle, or `None` if the observability subsystem is not initialized.
+    pub fn global() -> Option<&'static Self> {
+        GLOBAL_HANDLE.get()
+    }
+
+    /// Returns currently applied directives. Doesn't include the default directives (`zksync=info`)
+    /// which are always applied unless disabled.
+    pub fn current(&self) -> String {
+        self.inner
+            .current_directives
+            .lock()
+            .expect("log directives are poisoned")
+            .clone()
+    }
+
+    /// Reloads log directives. The provided directives extend / override the default ones,
+    /// similar to directives provided on node start.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directives cannot be parsed; in this case, the previously applied directives are retained.
+    pub fn reload(&self, directives: &str) -> Result<(), LogDirectivesError> {
+        let full_directives = Self::full_directives(self.inner.default_directives, directives);
+        // Validate directives before applying them to any filter.
+        EnvFilter::try_new(&full_directives)?;
+
+        let mut current = self
+            .inner
+            .current_directives
+            .lock()
+            .expect("log directives are poisoned");
+        for reloader in &self.inner.reloaders {
+            reloader(EnvFilter::new(&full_directives))?;
+        }
+        tracing::info!("Reloaded log directives: `{current}` -> `{directives}`");
+        *current = directives.to_owned();
+        Ok(())
+    }
+
+    /// Resets log directives to the ones the node was started with.
+    pub fn reset(&self) -> Result<(), LogDirectivesError> {
+        self.reload(&self.inner.initial_directives)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing::Level;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    #[test]
+    fn reloading_directives() {
+        let mut handle = LogDirectivesHandle::new("zksync=info,", String::new());
+        let (filter, filter_handle) = reload::Layer::new(EnvFilter::new(
+            LogDirectivesHandle::full_directives("zksync=info,", ""),
+        ));
+        handle.add_filter(filter_handle);
+        let subscriber = tracing_subscriber::registry().with(filter);
+
+        tracing::subscriber::with_default(subscriber, || {
+            assert!(tracing::enabled!(target: "zksync_core", Level::INFO));
+            assert!(!tracing::enabled!(target: "zksync_core", Level::DEBUG));
+
+            handle.reload("zksync_core=debug").unwrap();
+            assert_eq!(handle.current(), "zksync_core=debug");
+            assert!(tracing::enabled!(target: "zksync_core", Level::DEBUG));
+            assert!(!tracing::enabled!(target: "zksync_other", Level::DEBUG));
+
+            let err = handle.reload("zksync_core=???").unwrap_err();
+            assert!(matches!(err, LogDirectivesError::Parse(_)), "{err:?}");
+            assert_eq!(handle.current(), "zksync_core=debug");
+            assert!(tracing::enabled!(target: "zksync_core", Level::DEBUG));
+
+            handle.reset().unwrap();
+            assert_eq!(handle.current(), "");
+            assert!(!tracing::enabled!(target: "zksync_core", Level::DEBUG));
+        });
+    }
+}
@@ -123,18 +123,18 @@ where
 // If this type has to be changed, the easiest way to figure it out is to attempt
 // constructing the object, e.g.:
 // ```
-// let layer = fmt::Layer::default()
+// let format = fmt::format()
+//     .json()
 //     .with_file(true)
 //     .with_line_number(true)
-//     .with_timer(timer)
-//     .json();
+//     .with_timer(timer);
+// let layer = fmt::Layer::default().json().event_format(CorrelatedJson::new(format));
 // ```
 // Compiler will complain and tell the type for you.
 type JsonLayer<S> = tracing_subscriber::fmt::Layer<
     S,
     tracing_subscriber::fmt::format::JsonFields,
-    tracing_subscriber::fmt::format::Format<
-        tracing_subscriber::fmt::format::Json,
+    super::correlation::CorrelatedJson<
         tracing_subscriber::fmt::time::UtcTime<time::format_description::well_known::Rfc3339>,
     >,
 >;
@@ -1,8 +1,13 @@
 use std::{backtrace::Backtrace, str::FromStr};
 
 use serde::Deserialize;
-use tracing_subscriber::{fmt, registry::LookupSpan, EnvFilter, Layer};
+use tracing_subscriber::{fmt, layer::Filter, registry::LookupSpan, reload, EnvFilter, Layer};
 
+use self::correlation::CorrelationLayer;
+pub use self::directives::{LogDirectivesError, LogDirectivesHandle};
+
+mod correlation;
+mod directives;
 mod layer;
 
 /// Specifies the format of the logs in stdout.
@@ -38,6 +43,7 @@ pub struct Logs {
     format: LogFormat,
     log_directives: Option<String>,
     disable_default_logs: bool,
+    correlation_ids: bool,
 }
 
 impl From<LogFormat> for Logs {
@@ -46,6 +52,7 @@ impl From<LogFormat> for Logs {
             format,
             log_directives: None,
             disable_default_logs: false,
+            correlation_ids: false,
         }
     }
 }
@@ -56,6 +63,7 @@ impl Logs {
             format: format.parse()?,
             log_directives: None,
             disable_default_logs: false,
+            correlation_ids: false,
         })
     }
 
@@ -72,17 +80,40 @@ impl Logs {
     ///
     /// [1]: https://docs.rs/tracing-subscriber/0.3.18/tracing_subscriber/filter/targets/struct.Targets.html#filtering-with-targets
     pub(super) fn build_filter(&self) -> EnvFilter {
-        let mut directives = if self.disable_default_logs {
-            "".to_string()
+        EnvFilter::new(LogDirectivesHandle::full_directives(
+            self.default_directives(),
+            &self.user_directives(),
+        ))
+    }
+
+    fn default_directives(&self) -> &'static str {
+        if self.disable_default_logs {
+            ""
         } else {
-            "zksync=info,".to_string()
-        };
+            "zksync=info,"
+        }
+    }
+
+    fn user_directives(&self) -> String {
         if let Some(log_directives) = &self.log_directives {
-            directives.push_str(log_directives);
-        } else if let Ok(env_directives) = std::env::var(EnvFilter::DEFAULT_ENV) {
-            directives.push_str(&env_directives);
-        };
-        EnvFilter::new(directives)
+            log_directives.clone()
+        } else {
+            std::env::var(EnvFilter::DEFAULT_ENV).unwrap_or_default()
+        }
+    }
+
+    /// Builds a filter for the logs that can be reloaded at runtime using the returned handle.
+    pub(super) fn build_reloadable_filter<S: 'static>(
+        &self,
+        handle: &mut LogDirectivesHandle,
+    ) -> reload::Layer<EnvFilter, S> {
+        let (filter, filter_handle) = reload::Layer::new(self.build_filter());
+        handle.add_filter(filter_handle);
+        filter
+    }
+
+    pub(super) fn directives_handle(&self) -> LogDirectivesHandle {
+        LogDirectivesHandle::new(self.default_directives(), self.user_directives())
     }
 
     pub fn with_log_directives(mut self, log_directives: Option<String>) -> Self {
@@ -95,6 +126,21 @@ impl Logs {
         self
     }
 
+    /// Enables adding trace / span correlation IDs (`trace_id` and `span_id` fields) to JSON logs.
+    /// The trace ID is shared by all spans within a single span tree, which allows to correlate logs
+    /// produced by a logical operation (incl. across Tokio tasks if spawned futures are instrumented with the parent span).
+    /// Has no effect for the plain log format.
+    pub fn with_correlation_ids(mut self, correlation_ids: bool) -> Self {
+        self.correlation_ids = correlation_ids;
+        self
+    }
+
+    /// Returns a layer assigning correlation IDs to spans if they are enabled for the log format.
+    pub(super) fn correlation_layer(&self) -> Option<CorrelationLayer> {
+        let is_enabled = self.correlation_ids && matches!(self.format, LogFormat::Json);
+        is_enabled.then(CorrelationLayer::default)
+    }
+
     pub fn install_panic_hook(&self) {
         // Check whether we need to change the default panic handler.
         // Note that this must happen before we initialize Sentry, since otherwise
@@ -113,15 +159,27 @@ impl Logs {
         S: tracing::Subscriber + for<'span> LookupSpan<'span> + Send + Sync,
     {
         let filter = self.build_filter();
+        self.into_layer_with_filter(filter)
+    }
+
+    pub(super) fn into_layer_with_filter<S, F>(self, filter: F) -> impl Layer<S>
+    where
+        S: tracing::Subscriber + for<'span> LookupSpan<'span> + Send + Sync,
+        F: Filter<S> + Send + Sync + 'static,
+    {
         let layer = match self.format {
             LogFormat::Plain => layer::LogsLayer::Plain(fmt::Layer::new()),
             LogFormat::Json => {
                 let timer = tracing_subscriber::fmt::time::UtcTime::rfc_3339();
-                let json_layer = fmt::Layer::default()
+                let format = fmt::format()
+                    .json()
                     .with_file(true)
                     .with_line_number(true)
-                    .with_timer(timer)
-                    .json();
+                    .with_timer(timer);
+                // Correlation IDs are only output if spans are processed by `CorrelationLayer`,
+                // so the format is equivalent to the standard one if correlation IDs are disabled.
+                let format = correlation::CorrelatedJson::new(format);
+                let json_layer = fmt::Layer::default().json().event_format(format);
                 layer::LogsLayer::Json(json_layer)
             }
         };
@@ -0,0 +1,304 @@
+//! OpenTelemetry tracing for the L1 batch lifecycle.
+//!
+//! L1 batches are processed by multiple components (potentially running in different processes): the state keeper
+//! seals a batch, the metadata calculator computes its Merkle tree metadata, the prover pipeline proves it,
+//! and the ETH sender commits, proves and executes it on L1. To see an end-to-end latency breakdown for a batch,
+//! spans for all these stages are attached to a single trace, with the trace ID deterministically derived
+//! from the L2 chain ID and the batch number. Thus, no context propagation between components is required.
+//!
+//! Since there is no component that would observe the entire batch lifecycle, the root span of the trace is never exported;
+//! the stage spans have a (virtual) remote parent with a deterministic span ID instead. The sampling decision
+//! for the virtual parent is derived from the trace ID in the same way as by the ratio-based sampler, so all components
+//! with the same sampling ratio agree on whether a batch trace is sampled.
+
+use std::{ops, sync::OnceLock};
+
+use opentelemetry::{
+    trace::{SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState},
+    Context,
+};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Stage of the L1 batch lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum L1BatchStage {
+    /// Sealing the batch in the state keeper.
+    Seal,
+    /// Computing the batch metadata in the metadata calculator (incl. saving witness inputs).
+    TreeUpdate,
+    /// Serving witness inputs for the batch to the prover subsystem.
+    ProofGenerationDataServed,
+    /// Generating witnesses in the prover subsystem for the specified aggregation round.
+    WitnessGeneration(&'static str),
+    /// Receiving the final proof for the batch from the prover subsystem.
+    ProofSubmitted,
+    /// Creating a commit L1 transaction covering the batch.
+    CommitTxCreated,
+    /// Creating a prove L1 transaction covering the batch.
+    ProveTxCreated,
+    /// Creating an execute L1 transaction covering the batch.
+    ExecuteTxCreated,
+    /// Confirming a commit L1 transaction covering the batch.
+    CommitTxConfirmed,
+    /// Confirming a prove L1 transaction covering the batch.
+    ProveTxConfirmed,
+    /// Confirming an execute L1 transaction covering the batch.
+    ExecuteTxConfirmed,
+}
+
+impl L1BatchStage {
+    fn span_name(self) -> String {
+        match self {
+            Self::Seal => "l1_batch.seal".to_owned(),
+            Self::TreeUpdate => "l1_batch.tree_update".to_owned(),
+            Self::ProofGenerationDataServed => "l1_batch.proof_generation_data_served".to_owned(),
+            Self::WitnessGeneration(round) => format!("l1_batch.witness_generation.{round}"),
+            Self::ProofSubmitted => "l1_batch.proof_submitted".to_owned(),
+            Self::CommitTxCreated => "l1_batch.commit_tx_created".to_owned(),
+            Self::ProveTxCreated => "l1_batch.prove_tx_created".to_owned(),
+            Self::ExecuteTxCreated => "l1_batch.execute_tx_created".to_owned(),
+            Self::CommitTxConfirmed => "l1_batch.commit_tx_confirmed".to_owned(),
+            Self::ProveTxConfirmed => "l1_batch.prove_tx_confirmed".to_owned(),
+            Self::ExecuteTxConfirmed => "l1_batch.execute_tx_confirmed".to_owned(),
+        }
+    }
+}
+
+/// Process-wide settings for L1 batch traces, set when the OpenTelemetry tracing layer is installed.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct L1BatchTraceSettings {
+    pub l2_chain_id: u64,
+    pub sampling_ratio: f64,
+}
+
+impl Default for L1BatchTraceSettings {
+    fn default() -> Self {
+        Self {
+            l2_chain_id: 0,
+            sampling_ratio: 1.0,
+        }
+    }
+}
+
+static SETTINGS: OnceLock<L1BatchTraceSettings> = OnceLock::new();
+
+pub(super) fn configure(settings: L1BatchTraceSettings) {
+    if SETTINGS.set(settings).is_err() {
+        tracing::warn!("L1 batch trace settings are already configured");
+    }
+}
+
+/// Salt mixed into the batch number (`zksyncb1` in ASCII), so that the lower half of a trace ID is never zero.
+const TRACE_ID_SALT: u64 = u64::from_be_bytes(*b"zksyncb1");
+
+/// Bijective mixing function (the `splitmix64` finalizer).
+fn mix(mut value: u64) -> u64 {
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    value ^ (value >> 31)
+}
+
+/// The upper half of the trace ID is the chain ID, and the lower half is the mixed batch number. The lower half
+/// is uniformly distributed as expected by the ratio-based sampler, and is unique for each batch since mixing is bijective.
+fn l1_batch_trace_id(l2_chain_id: u64, l1_batch_number: u32) -> TraceId {
+    let low = mix(TRACE_ID_SALT ^ u64::from(l1_batch_number));
+    TraceId::from((u128::from(l2_chain_id) << 64) | u128::from(low))
+}
+
+fn l1_batch_root_span_id(l1_batch_number: u32) -> SpanId {
+    // Span ID must be non-zero.
+    SpanId::from((1 << 63) | u64::from(l1_batch_number))
+}
+
+/// Replicates the sampling decision of `Sampler::TraceIdRatioBased`.
+fn is_sampled(trace_id: TraceId, sampling_ratio: f64) -> bool {
+    if sampling_ratio >= 1.0 {
+        return true;
+    }
+    let upper_bound = (sampling_ratio.max(0.0) * (1_u64 << 63) as f64) as u64;
+    let trace_id_low = u128::from_be_bytes(trace_id.to_bytes()) as u64;
+    (trace_id_low >> 1) < upper_bound
+}
+
+fn l1_batch_context_with_settings(settings: L1BatchTraceSettings, l1_batch_number: u32) -> Context {
+    let trace_id = l1_batch_trace_id(settings.l2_chain_id, l1_batch_number);
+    let trace_flags = if is_sampled(trace_id, settings.sampling_ratio) {
+        TraceFlags::SAMPLED
+    } else {
+        TraceFlags::default()
+    };
+    let span_context = SpanContext::new(
+        trace_id,
+        l1_batch_root_span_id(l1_batch_number),
+        trace_flags,
+        true,
+        TraceState::default(),
+    );
+    Context::new().with_remote_span_context(span_context)
+}
+
+/// Returns OpenTelemetry context for the L1 batch trace.
+pub fn l1_batch_context(l1_batch_number: u32) -> Context {
+    let settings = SETTINGS.get().copied().unwrap_or_default();
+    l1_batch_context_with_settings(settings, l1_batch_number)
+}
+
+/// Creates a span for the specified L1 batch lifecycle stage. The span is attached to the L1 batch trace
+/// if OpenTelemetry tracing is enabled; otherwise, it's a usual `tracing` span.
+///
+/// The returned span should be entered (or used to instrument a future) for the duration of the stage.
+pub fn l1_batch_span(stage: L1BatchStage, l1_batch_number: u32) -> tracing::Span {
+    let span_name = stage.span_name();
+    let span = tracing::info_span!(
+        "l1_batch_stage",
+        otel.name = span_name.as_str(),
+        l1_batch.number = l1_batch_number,
+        l1_batch.last_number = tracing::field::Empty
+    );
+    span.set_parent(l1_batch_context(l1_batch_number));
+    span
+}
+
+/// Creates a span for a stage covering a range of L1 batches (e.g., creating an L1 transaction that commits
+/// multiple batches). The span is attached to the trace of the first batch in the range and is linked to the traces
+/// of the other batches.
+pub fn l1_batch_range_span(
+    stage: L1BatchStage,
+    l1_batch_numbers: ops::RangeInclusive<u32>,
+) -> tracing::Span {
+    let (first_l1_batch_number, last_l1_batch_number) = l1_batch_numbers.into_inner();
+    let span = l1_batch_span(stage, first_l1_batch_number);
+    span.record("l1_batch.last_number", last_l1_batch_number);
+    for l1_batch_number in first_l1_batch_number.saturating_add(1)..=last_l1_batch_number {
+        let context = l1_batch_context(l1_batch_number);
+        span.add_link(context.span().span_context().clone());
+    }
+    span
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context_for(settings: L1BatchTraceSettings, l1_batch_number: u32) -> SpanContext {
+        l1_batch_context_with_settings(settings, l1_batch_number)
+            .span()
+            .span_context()
+            .clone()
+    }
+
+    #[test]
+    fn l1_batch_trace_ids_are_deterministic() {
+        let settings = L1BatchTraceSettings {
+            l2_chain_id: 270,
+            sampling_ratio: 1.0,
+        };
+        let span_context = context_for(settings, 42);
+        assert!(span_context.is_valid());
+        assert!(span_context.is_remote());
+        assert!(span_context.is_sampled());
+        assert!(span_context
+            .trace_id()
+            .to_string()
+            .starts_with("000000000000010e"));
+        assert_eq!(span_context.span_id().to_string(), "800000000000002a");
+
+        assert_eq!(context_for(settings, 42), span_context);
+        assert_ne!(
+            context_for(settings, 43).trace_id(),
+            span_context.trace_id()
+        );
+        let other_chain_settings = L1BatchTraceSettings {
+            l2_chain_id: 271,
+            ..settings
+        };
+        assert_ne!(
+            context_for(other_chain_settings, 42).trace_id(),
+            span_context.trace_id()
+        );
+
+        let genesis_context = context_for(L1BatchTraceSettings::default(), 0);
+        assert!(genesis_context.is_valid());
+    }
+
+    #[test]
+    fn l1_batch_traces_respect_sampling_ratio() {
+        use opentelemetry::trace::{SamplingDecision, SpanKind};
+        use opentelemetry_sdk::trace::{Sampler, ShouldSample};
+
+        let settings = L1BatchTraceSettings {
+            l2_chain_id: 270,
+            sampling_ratio: 0.25,
+        };
+        let sampler = Sampler::TraceIdRatioBased(settings.sampling_ratio);
+        let mut sampled_count = 0;
+        for l1_batch_number in 0..1_000 {
+            let span_context = context_for(settings, l1_batch_number);
+            let decision = sampler
+                .should_sample(
+                    None,
+                    span_context.trace_id(),
+                    "test",
+                    &SpanKind::Internal,
+                    &[],
+                    &[],
+                )
+                .decision;
+            assert_eq!(
+                span_context.is_sampled(),
+                decision == SamplingDecision::RecordAndSample,
+                "{l1_batch_number}"
+            );
+            sampled_count += usize::from(span_context.is_sampled());
+        }
+        assert!((150..350).contains(&sampled_count), "{sampled_count}");
+
+        let settings = L1BatchTraceSettings {
+            sampling_ratio: 0.0,
+            ..settings
+        };
+        assert!(!context_for(settings, 42).is_sampled());
+    }
+
+    #[test]
+    fn exporting_l1_batch_spans() {
+        use opentelemetry::trace::TracerProvider as _;
+        use opentelemetry_sdk::{testing::trace::InMemorySpanExporter, trace::TracerProvider};
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let exporter = InMemorySpanExporter::default();
+        let provider = TracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let layer = tracing_opentelemetry::layer().with_tracer(provider.tracer("test"));
+        let subscriber = tracing_subscriber::registry().with(layer);
+        tracing::subscriber::with_default(subscriber, || {
+            l1_batch_span(L1BatchStage::Seal, 42).in_scope(|| {
+                tracing::info!("sealing batch");
+            });
+            l1_batch_range_span(L1BatchStage::CommitTxCreated, 42..=44).in_scope(|| {
+                tracing::info!("creating commit tx");
+            });
+        });
+
+        let spans = exporter.get_finished_spans().unwrap();
+        assert_eq!(spans.len(), 2);
+        let expected_trace_id = l1_batch_trace_id(0, 42);
+        assert_eq!(spans[0].name, "l1_batch.seal");
+        assert_eq!(spans[0].span_context.trace_id(), expected_trace_id);
+        assert_eq!(spans[0].parent_span_id, l1_batch_root_span_id(42));
+
+        assert_eq!(spans[1].name, "l1_batch.commit_tx_created");
+        assert_eq!(spans[1].span_context.trace_id(), expected_trace_id);
+        let linked_trace_ids: Vec<_> = spans[1]
+            .links
+            .iter()
+            .map(|link| link.span_context.trace_id())
+            .collect();
+        assert_eq!(
+            linked_trace_ids,
+            [l1_batch_trace_id(0, 43), l1_batch_trace_id(0, 44)]
+        );
+    }
+}
@@ -13,6 +13,11 @@ use opentelemetry_semantic_conventions::resource::{
 use tracing_subscriber::{registry::LookupSpan, EnvFilter, Layer};
 use url::Url;
 
+use self::l1_batch::L1BatchTraceSettings;
+pub use self::l1_batch::{l1_batch_context, l1_batch_range_span, l1_batch_span, L1BatchStage};
+
+mod l1_batch;
+
 /// Information about the service.
 ///
 /// This information is initially filled as follows:
@@ -132,6 +137,10 @@ pub struct OpenTelemetry {
     pub logging_endpoint: Option<Url>,
     /// Information about service
     pub service: ServiceDescriptor,
+    /// Ratio of sampled traces in the `[0, 1]` range.
+    pub sampling_ratio: f64,
+    /// L2 chain ID used to derive IDs of L1 batch lifecycle traces.
+    pub l2_chain_id: Option<u64>,
 }
 
 impl OpenTelemetry {
@@ -153,9 +162,26 @@ impl OpenTelemetry {
             tracing_endpoint: parse_url(tracing_endpoint)?,
             logging_endpoint: parse_url(logging_endpoint)?,
             service: ServiceDescriptor::new(),
+            sampling_ratio: 1.0,
+            l2_chain_id: None,
         })
     }
 
+    /// Sets the ratio of sampled traces. By default, all traces are sampled.
+    pub fn with_sampling_ratio(mut self, ratio: f64) -> Result<Self, OpenTelemetryLayerError> {
+        if !(0.0..=1.0).contains(&ratio) {
+            return Err(OpenTelemetryLayerError::InvalidSamplingRatio(ratio));
+        }
+        self.sampling_ratio = ratio;
+        Ok(self)
+    }
+
+    /// Sets the L2 chain ID for L1 batch lifecycle traces (see [`l1_batch_span()`]).
+    pub fn with_l2_chain_id(mut self, l2_chain_id: Option<u64>) -> Self {
+        self.l2_chain_id = l2_chain_id;
+        self
+    }
+
     /// Can be used to override the service descriptor used by the layer.
     pub fn with_service_descriptor(mut self, service: ServiceDescriptor) -> Self {
         self.service = service;
@@ -232,9 +258,20 @@ impl OpenTelemetry {
             .build_span_exporter()
             .expect("Failed to create OTLP exporter"); // URL is validated.
 
+        // Spans follow the sampling decision of their parent, so that traces are either sampled entirely or not at all.
+        // L1 batch traces have a virtual parent, which sampling decision is made in the same way by all components.
+        let sampler = if self.sampling_ratio >= 1.0 {
+            Sampler::AlwaysOn
+        } else {
+            Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(self.sampling_ratio)))
+        };
+        l1_batch::configure(L1BatchTraceSettings {
+            l2_chain_id: self.l2_chain_id.unwrap_or(0),
+            sampling_ratio: self.sampling_ratio,
+        });
         let config = opentelemetry_sdk::trace::Config::default()
             .with_id_generator(RandomIdGenerator::default())
-            .with_sampler(Sampler::AlwaysOn)
+            .with_sampler(sampler)
             .with_resource(resource);
 
         let provider = opentelemetry_sdk::trace::TracerProvider::builder()
@@ -283,6 +320,8 @@ pub enum OpenTelemetryLayerError {
     InvalidFormat,
     #[error("Invalid URL: \"{0}\" - {1}")]
     InvalidUrl(String, url::ParseError),
+    #[error("Invalid sampling ratio {0}; expected a value in the [0, 1] range")]
+    InvalidSamplingRatio(f64),
 }
 
 impl FromStr for OpenTelemetryLevel {
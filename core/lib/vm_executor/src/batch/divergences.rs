@@ -0,0 +1,114 @@
+//! Persistence of VM divergences detected in the shadow VM mode.
+
+use std::time::Duration;
+
+use tokio::sync::{mpsc, watch};
+use zksync_dal::{vm_divergences_dal::VmDivergence, ConnectionPool, Core, CoreDal};
+use zksync_multivm::interface::utils::{DivergenceErrors, DivergenceHandler, VmDump};
+
+/// Maximum number of divergences buffered for persistence. If the buffer is full, new divergences are logged
+/// and otherwise dropped, so that VM execution is never blocked on Postgres.
+const BUFFER_CAPACITY: usize = 128;
+/// Default retention period for persisted divergences.
+const DEFAULT_RETENTION: Duration = Duration::from_secs(30 * 86_400);
+/// Interval between pruning old divergences.
+const PRUNING_INTERVAL: Duration = Duration::from_secs(3_600);
+
+fn to_divergence(errors: &DivergenceErrors, dump: &VmDump) -> VmDivergence {
+    let tx_hash = dump
+        .l2_blocks
+        .iter()
+        .flat_map(|block| &block.txs)
+        .last()
+        .map(|tx| tx.hash());
+    VmDivergence {
+        l1_batch_number: dump.l1_batch_number(),
+        tx_hash,
+        errors: errors.to_string(),
+    }
+}
+
+/// Creates a divergence handler persisting divergences to Postgres. The handler never blocks; divergences are sent
+/// to the returned [`VmDivergencesPersister`], which must be run for divergences to be persisted.
+pub fn persisting_divergence_handler(
+    pool: ConnectionPool<Core>,
+) -> (DivergenceHandler, VmDivergencesPersister) {
+    let (sender, receiver) = mpsc::channel(BUFFER_CAPACITY);
+    let handler = DivergenceHandler::new(move |errors, dump| {
+        let divergence = to_divergence(&errors, &dump);
+        if let Err(err) = sender.try_send(divergence) {
+            let l1_batch_number = dump.l1_batch_number();
+            tracing::error!(
+                "Cannot queue VM divergence for L1 batch #{l1_batch_number} for persistence: {err}"
+            );
+        }
+    });
+    let persister = VmDivergencesPersister {
+        pool,
+        receiver,
+        retention: DEFAULT_RETENTION,
+    };
+    (handler, persister)
+}
+
+/// Asynchronous task persisting VM divergences sent by the [handler](persisting_divergence_handler()) and pruning
+/// old divergences.
+#[derive(Debug)]
+pub struct VmDivergencesPersister {
+    pool: ConnectionPool<Core>,
+    receiver: mpsc::Receiver<VmDivergence>,
+    retention: Duration,
+}
+
+impl VmDivergencesPersister {
+    /// Sets the retention period for persisted divergences. Divergences older than this period are periodically pruned.
+    /// The default retention period is 30 days.
+    #[must_use]
+    pub fn with_retention(mut self, retention: Duration) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Runs this persister.
+    ///
+    /// # Errors
+    ///
+    /// Propagates Postgres errors.
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let mut pruning_timer = tokio::time::interval(PRUNING_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = stop_receiver.changed() => break,
+                Some(divergence) = self.receiver.recv() => {
+                    self.persist(&divergence).await?;
+                }
+                _ = pruning_timer.tick() => {
+                    self.prune().await?;
+                }
+            }
+        }
+        tracing::info!("Stop signal received, VM divergences persister is shutting down");
+        Ok(())
+    }
+
+    async fn persist(&self, divergence: &VmDivergence) -> anyhow::Result<()> {
+        let mut conn = self.pool.connection_tagged("vm_executor").await?;
+        conn.vm_divergences_dal()
+            .insert_divergence(divergence)
+            .await?;
+        Ok(())
+    }
+
+    async fn prune(&self) -> anyhow::Result<()> {
+        let retention = self.retention;
+        let mut conn = self.pool.connection_tagged("vm_executor").await?;
+        let deleted_count = conn
+            .vm_divergences_dal()
+            .delete_divergences_older_than(retention)
+            .await?;
+        if deleted_count > 0 {
+            tracing::info!("Pruned {deleted_count} VM divergences older than {retention:?}");
+        }
+        Ok(())
+    }
+}
@@ -20,7 +20,7 @@ use zksync_multivm::{
     vm_latest::HistoryEnabled,
     FastVmInstance, LegacyVmInstance, MultiVmTracer,
 };
-use zksync_types::{commitment::PubdataParams, vm::FastVmMode, Transaction};
+use zksync_types::{commitment::PubdataParams, vm::FastVmMode, L1BatchNumber, Transaction};
 
 use super::{
     executor::{Command, MainBatchExecutor},
@@ -73,6 +73,7 @@ pub struct MainBatchExecutorFactory<Tr> {
     fast_vm_mode: FastVmMode,
     observe_storage_metrics: bool,
     divergence_handler: Option<DivergenceHandler>,
+    shadow_sampling_ratio: f64,
     _tracer: PhantomData<Tr>,
 }
 
@@ -83,6 +84,7 @@ impl<Tr: BatchTracer> MainBatchExecutorFactory<Tr> {
             fast_vm_mode: FastVmMode::Old,
             observe_storage_metrics: false,
             divergence_handler: None,
+            shadow_sampling_ratio: 0.0,
             _tracer: PhantomData,
         }
     }
@@ -108,6 +110,66 @@ impl<Tr: BatchTracer> MainBatchExecutorFactory<Tr> {
         tracing::info!("Set VM divergence handler");
         self.divergence_handler = Some(handler);
     }
+
+    /// Sets the fraction of L1 batches executed in the shadow mode if the fast VM mode is [`FastVmMode::Old`].
+    /// In sampled batches, the legacy VM remains the source of truth, and the fast VM is run alongside it
+    /// with outputs compared after each operation. Divergences in sampled batches are reported to the divergence handler
+    /// (if one is set) and never lead to a panic.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is not in `[0, 1]`.
+    pub fn set_shadow_sampling_ratio(&mut self, ratio: f64) {
+        assert!(
+            (0.0..=1.0).contains(&ratio),
+            "shadow sampling ratio must be in [0, 1], got {ratio}"
+        );
+        if ratio > 0.0 {
+            tracing::info!("Set VM shadow sampling ratio to {ratio}");
+        }
+        self.shadow_sampling_ratio = ratio;
+    }
+
+    /// Selects the VM mode and divergence handler for a specific L1 batch.
+    fn select_vm_mode(
+        &self,
+        l1_batch_env: &L1BatchEnv,
+        system_env: &SystemEnv,
+    ) -> (FastVmMode, Option<DivergenceHandler>) {
+        let is_sampled = matches!(self.fast_vm_mode, FastVmMode::Old)
+            && is_supported_by_fast_vm(system_env.version)
+            && is_sampled_for_shadowing(l1_batch_env.number, self.shadow_sampling_ratio);
+        if is_sampled {
+            tracing::info!(
+                "Executing L1 batch #{} in the shadow mode",
+                l1_batch_env.number
+            );
+            EXECUTOR_METRICS.shadowed_batches.inc();
+        } else if !matches!(self.fast_vm_mode, FastVmMode::Shadow)
+            || self.divergence_handler.is_none()
+        {
+            // If there's no custom handler for the shadow mode, retain the default (panicking) one.
+            return (self.fast_vm_mode, self.divergence_handler.clone());
+        }
+
+        let inner_handler = self.divergence_handler.clone();
+        let handler = DivergenceHandler::new(move |errors, dump| {
+            EXECUTOR_METRICS.vm_divergences.inc();
+            if let Some(handler) = &inner_handler {
+                handler.handle(errors, dump);
+            }
+        });
+        (FastVmMode::Shadow, Some(handler))
+    }
+}
+
+/// Deterministically selects approximately `ratio` of L1 batches, evenly spread across batch numbers.
+fn is_sampled_for_shadowing(l1_batch_number: L1BatchNumber, ratio: f64) -> bool {
+    if ratio <= 0.0 {
+        return false;
+    }
+    let number = f64::from(l1_batch_number.0);
+    (number * ratio).floor() != ((number - 1.0) * ratio).floor()
 }
 
 impl<S: ReadStorage + Send + 'static, Tr: BatchTracer> BatchExecutorFactory<S>
@@ -123,11 +185,12 @@ impl<S: ReadStorage + Send + 'static, Tr: BatchTracer> BatchExecutorFactory<S>
         // Since we process `BatchExecutor` commands one-by-one (the next command is never enqueued
         // until a previous command is processed), capacity 1 is enough for the commands channel.
         let (commands_sender, commands_receiver) = mpsc::channel(1);
+        let (fast_vm_mode, divergence_handler) = self.select_vm_mode(&l1_batch_params, &system_env);
         let executor = CommandReceiver {
             optional_bytecode_compression: self.optional_bytecode_compression,
-            fast_vm_mode: self.fast_vm_mode,
+            fast_vm_mode,
             observe_storage_metrics: self.observe_storage_metrics,
-            divergence_handler: self.divergence_handler.clone(),
+            divergence_handler,
             commands: commands_receiver,
             _storage: PhantomData,
             _tracer: PhantomData::<Tr>,
@@ -508,4 +571,37 @@ mod tests {
         let vm = BatchVm::<_, ()>::new(l1_batch_env, system_env, storage, FastVmMode::Shadow);
         assert_matches!(vm, BatchVm::Fast(FastVmInstance::Shadowed(_)));
     }
+
+    #[test]
+    fn sampling_batches_for_shadowing() {
+        let sampled_count = |ratio| {
+            (1..=1_000)
+                .filter(|&number| is_sampled_for_shadowing(L1BatchNumber(number), ratio))
+                .count()
+        };
+        assert_eq!(sampled_count(0.0), 0);
+        assert_eq!(sampled_count(0.01), 10);
+        assert_eq!(sampled_count(0.25), 250);
+        assert_eq!(sampled_count(1.0), 1_000);
+
+        assert!(is_sampled_for_shadowing(L1BatchNumber(10), 0.1));
+        assert!(!is_sampled_for_shadowing(L1BatchNumber(11), 0.1));
+    }
+
+    #[test]
+    fn selecting_vm_mode_for_sampled_batches() {
+        let mut factory = MainBatchExecutorFactory::<()>::new(false);
+        factory.set_shadow_sampling_ratio(0.5);
+        let system_env = default_system_env(TxExecutionMode::VerifyExecute);
+        let (mode, handler) = factory.select_vm_mode(&default_l1_batch_env(2), &system_env);
+        assert_eq!(mode, FastVmMode::Shadow);
+        assert!(handler.is_some());
+        let (mode, handler) = factory.select_vm_mode(&default_l1_batch_env(3), &system_env);
+        assert_eq!(mode, FastVmMode::Old);
+        assert!(handler.is_none());
+
+        factory.set_fast_vm_mode(FastVmMode::New);
+        let (mode, _) = factory.select_vm_mode(&default_l1_batch_env(2), &system_env);
+        assert_eq!(mode, FastVmMode::New);
+    }
 }
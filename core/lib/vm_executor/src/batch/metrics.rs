@@ -2,7 +2,7 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics};
+use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics};
 use zksync_multivm::interface::VmExecutionResultAndLogs;
 
 use crate::shared::InteractionType;
@@ -53,6 +53,10 @@ pub(super) struct ExecutorMetrics {
     /// in the batch executor.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub batch_storage_interaction_duration: Family<InteractionType, Histogram<Duration>>,
+    /// Number of L1 batches sampled for execution in the shadow VM mode.
+    pub shadowed_batches: Counter,
+    /// Number of divergences between the legacy and fast VMs detected in the shadow VM mode.
+    pub vm_divergences: Counter,
 }
 
 #[vise::register]
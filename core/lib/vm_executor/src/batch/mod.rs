@@ -3,10 +3,12 @@
 //! This implementation is used by various ZKsync components, like the state keeper and components based on the VM runner.
 
 pub use self::{
+    divergences::{persisting_divergence_handler, VmDivergencesPersister},
     executor::MainBatchExecutor,
     factory::{BatchTracer, MainBatchExecutorFactory, TraceCalls},
 };
 
+mod divergences;
 mod executor;
 mod factory;
 mod metrics;
@@ -99,4 +99,23 @@ impl OneshotEnvParameters<CallOrExecute> {
         )
         .await
     }
+
+    /// Prepares environment for re-executing a transaction already included into an L2 block, which had
+    /// the specified `base_fee`.
+    pub async fn to_replay_env(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        resolved_block_info: &ResolvedBlockInfo,
+        fee_input: BatchFeeInput,
+        base_fee: u64,
+    ) -> anyhow::Result<OneshotEnv> {
+        self.to_env_inner(
+            connection,
+            TxExecutionMode::VerifyExecute,
+            resolved_block_info,
+            fee_input,
+            Some(base_fee),
+        )
+        .await
+    }
 }
@@ -20,12 +20,15 @@ use zksync_multivm::{
         storage::{ReadStorage, StorageView, StorageWithOverrides, WriteStorage},
         tracer::{ValidationError, ValidationParams, ValidationTraces},
         utils::{DivergenceHandler, ShadowMut, ShadowVm},
-        Call, ExecutionResult, InspectExecutionMode, OneshotEnv, OneshotTracingParams,
-        OneshotTransactionExecutionResult, StoredL2BlockEnv, TxExecutionArgs, TxExecutionMode,
-        VmFactory, VmInterface,
+        Call, ContractGasUsage, ExecutionFrameSink, ExecutionResult, InspectExecutionMode,
+        OneshotEnv, OneshotTracingParams, OneshotTransactionExecutionResult, StoredL2BlockEnv,
+        TxExecutionArgs, TxExecutionMode, VmFactory, VmInterface,
     },
     is_supported_by_fast_vm,
-    tracers::{CallTracer, StorageInvocations, TracerDispatcher, ValidationTracer},
+    tracers::{
+        CallTracer, ExecutionTracer, GasProfiler, StorageInvocations, TracerDispatcher,
+        ValidationTracer,
+    },
     utils::adjust_pubdata_price_for_tx,
     vm_fast,
     vm_latest::{HistoryDisabled, HistoryEnabled},
@@ -110,8 +113,12 @@ impl MainOneshotExecutor {
         env: &OneshotEnv,
         tracing_params: &OneshotTracingParams,
     ) -> FastVmMode {
-        if tracing_params.trace_calls || !is_supported_by_fast_vm(env.system.version) {
-            FastVmMode::Old // the fast VM doesn't support call tracing or old protocol versions
+        if tracing_params.trace_calls
+            || tracing_params.trace_execution.is_some()
+            || tracing_params.profile_gas
+            || !is_supported_by_fast_vm(env.system.version)
+        {
+            FastVmMode::Old // the fast VM doesn't support call / execution tracing, gas profiling or old protocol versions
         } else {
             self.fast_vm_mode
         }
@@ -147,12 +154,14 @@ where
         };
 
         tokio::task::spawn_blocking(move || {
+            let version = sandbox.env.system.version.into();
             sandbox.execute_in_vm(|vm, transaction| {
                 vm.inspect_transaction_with_bytecode_compression(
                     missed_storage_invocation_limit,
                     tracing_params,
                     transaction,
                     true,
+                    version,
                 )
             })
         })
@@ -242,13 +251,18 @@ impl<S: ReadStorage> Vm<S, (), ()> {
         params: OneshotTracingParams,
         tx: Transaction,
         with_compression: bool,
+        version: VmVersion,
     ) -> OneshotTransactionExecutionResult {
         let mut calls_result = Arc::<OnceCell<_>>::default();
+        let mut gas_profile_result = Arc::<OnceCell<_>>::default();
         let (compression_result, tx_result) = match self {
             Self::Legacy(vm) => {
                 let mut tracers = Self::create_legacy_tracers(
                     missed_storage_invocation_limit,
                     params.trace_calls.then(|| calls_result.clone()),
+                    params.trace_execution,
+                    params.profile_gas.then(|| gas_profile_result.clone()),
+                    version,
                 );
                 vm.inspect_transaction_with_bytecode_compression(&mut tracers, tx, with_compression)
             }
@@ -257,9 +271,20 @@ impl<S: ReadStorage> Vm<S, (), ()> {
                     !params.trace_calls,
                     "Call tracing is not supported by fast VM yet"
                 );
+                assert!(
+                    params.trace_execution.is_none(),
+                    "Execution tracing is not supported by fast VM"
+                );
+                assert!(
+                    !params.profile_gas,
+                    "Gas profiling is not supported by fast VM"
+                );
                 let legacy_tracers = Self::create_legacy_tracers::<HistoryEnabled>(
                     missed_storage_invocation_limit,
                     None,
+                    None,
+                    None,
+                    version,
                 );
                 let mut full_tracer = (legacy_tracers.into(), ((), ()));
                 vm.inspect_transaction_with_bytecode_compression(
@@ -274,17 +299,29 @@ impl<S: ReadStorage> Vm<S, (), ()> {
             tx_result: Box::new(tx_result),
             compression_result: compression_result.map(drop),
             call_traces: Arc::make_mut(&mut calls_result).take().unwrap_or_default(),
+            gas_profile: Arc::make_mut(&mut gas_profile_result)
+                .take()
+                .unwrap_or_default(),
         }
     }
 
     fn create_legacy_tracers<H: HistoryMode>(
         missed_storage_invocation_limit: usize,
         calls_result: Option<Arc<OnceCell<Vec<Call>>>>,
+        execution_sink: Option<Arc<dyn ExecutionFrameSink>>,
+        gas_profile_result: Option<Arc<OnceCell<Vec<ContractGasUsage>>>>,
+        version: VmVersion,
     ) -> TracerDispatcher<StorageView<S>, H> {
         let mut tracers = vec![];
         if let Some(calls_result) = calls_result {
             tracers.push(CallTracer::new(calls_result).into_tracer_pointer());
         }
+        if let Some(sink) = execution_sink {
+            tracers.push(ExecutionTracer::new(sink).into_tracer_pointer());
+        }
+        if let Some(gas_profile_result) = gas_profile_result {
+            tracers.push(GasProfiler::new(gas_profile_result, version).into_tracer_pointer());
+        }
         tracers
             .push(StorageInvocations::new(missed_storage_invocation_limit).into_tracer_pointer());
         tracers.into()
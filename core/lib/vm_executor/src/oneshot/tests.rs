@@ -31,7 +31,13 @@ fn selecting_vm_for_execution() {
         assert_matches!(mode, FastVmMode::New);
 
         // Tracing calls is not supported by the new VM.
-        let mode = executor.select_fast_vm_mode(&env, &OneshotTracingParams { trace_calls: true });
+        let mode = executor.select_fast_vm_mode(
+            &env,
+            &OneshotTracingParams {
+                trace_calls: true,
+                ..OneshotTracingParams::default()
+            },
+        );
         assert_matches!(mode, FastVmMode::Old);
 
         // Old protocol versions are not supported either.
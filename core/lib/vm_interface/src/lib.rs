@@ -29,9 +29,10 @@ pub use crate::{
         },
         outputs::{
             BatchTransactionExecutionResult, BootloaderMemory, Call, CallType, CircuitStatistic,
-            CompressedBytecodeInfo, CurrentExecutionState, DeduplicatedWritesMetrics,
-            ExecutionResult, FinishedL1Batch, L2Block, OneshotTransactionExecutionResult,
-            PushTransactionResult, Refunds, TransactionExecutionMetrics,
+            CompressedBytecodeInfo, ContractGasUsage, CurrentExecutionState,
+            DeduplicatedWritesMetrics, ExecutionFrame, ExecutionFrameSink, ExecutionResult,
+            FarCallFrame, FinishedL1Batch, HeapWrite, L2Block, OneshotTransactionExecutionResult,
+            PushTransactionResult, Refunds, RegisterDelta, TransactionExecutionMetrics,
             TransactionExecutionResult, TxExecutionStatus, VmEvent, VmExecutionLogs,
             VmExecutionMetrics, VmExecutionResultAndLogs, VmExecutionStatistics, VmMemoryMetrics,
         },
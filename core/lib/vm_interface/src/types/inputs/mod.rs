@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use zksync_types::{
     l2::L2Tx, ExecuteTransactionCommon, Nonce, PackedEthSignature, Transaction, U256,
 };
@@ -8,6 +10,7 @@ pub use self::{
     l2_block::{L2BlockEnv, StoredL2BlockEnv},
     system_env::{SystemEnv, TxExecutionMode},
 };
+use crate::ExecutionFrameSink;
 
 mod execution_mode;
 mod l1_batch_env;
@@ -92,4 +95,8 @@ impl TxExecutionArgs {
 pub struct OneshotTracingParams {
     /// Whether to trace contract calls.
     pub trace_calls: bool,
+    /// Sink for instruction-level execution frames. If set, every executed instruction is reported to the sink.
+    pub trace_execution: Option<Arc<dyn ExecutionFrameSink>>,
+    /// Whether to attribute gas and pubdata usage to called contract functions.
+    pub profile_gas: bool,
 }
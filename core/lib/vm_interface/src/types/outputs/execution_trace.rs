@@ -0,0 +1,21 @@
+use std::fmt;
+
+pub use zksync_types::api::execution_trace::{
+    ExecutionFrame, FarCallFrame, HeapWrite, RegisterDelta,
+};
+
+/// Receiver of frames produced by the execution tracer. Frames are pushed synchronously from the VM,
+/// so implementations should be cheap (e.g., buffer frames or forward them to a channel).
+pub trait ExecutionFrameSink: fmt::Debug + Send + Sync {
+    /// Checks whether the sink needs the frame with the specified index. Frames that are not needed
+    /// are skipped by the tracer without being built, which makes tracing past them much cheaper.
+    fn accepts(&self, _index: u64) -> bool {
+        true
+    }
+
+    /// Handles a single execution frame.
+    fn push(&self, frame: ExecutionFrame);
+
+    /// Called once the traced execution is finished.
+    fn finish(&self) {}
+}
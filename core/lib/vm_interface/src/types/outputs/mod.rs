@@ -1,5 +1,7 @@
 use std::borrow::Cow;
 
+pub use zksync_types::api::gas_profile::ContractGasUsage;
+
 pub use self::{
     bytecode::CompressedBytecodeInfo,
     execution_result::{
@@ -8,6 +10,7 @@ pub use self::{
         VmEvent, VmExecutionLogs, VmExecutionResultAndLogs,
     },
     execution_state::{BootloaderMemory, CurrentExecutionState},
+    execution_trace::{ExecutionFrame, ExecutionFrameSink, FarCallFrame, HeapWrite, RegisterDelta},
     finished_l1batch::FinishedL1Batch,
     l2_block::L2Block,
     statistic::{
@@ -19,6 +22,7 @@ pub use self::{
 mod bytecode;
 mod execution_result;
 mod execution_state;
+mod execution_trace;
 mod finished_l1batch;
 mod l2_block;
 mod statistic;
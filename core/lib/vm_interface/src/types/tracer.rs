@@ -1,6 +1,6 @@
 use std::{collections::HashSet, fmt, ops::Range, time};
 
-use zksync_types::{Address, U256};
+use zksync_types::{Address, StorageKey, H256, U256};
 
 use crate::Halt;
 
@@ -124,9 +124,16 @@ pub enum ValidationError {
 /// For instance, the `timestamp_asserter_range` represent the range within which the transaction might make
 /// assertions on `block.timestamp`. This information is crucial for the caller, as expired transactions should
 /// be excluded from the mempool.
+///
+/// Besides the tracing output, the traces hold conditions of transactions submitted via `eth_sendRawTransactionConditional`,
+/// which are persisted and enforced in the same way.
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct ValidationTraces {
     pub timestamp_asserter_range: Option<Range<u64>>,
+    /// Range of L2 block numbers the transaction may be included in.
+    pub l2_block_number_range: Option<Range<u64>>,
+    /// Storage values expected at the time of the transaction execution.
+    pub expected_storage_values: Vec<(StorageKey, H256)>,
 }
 
 impl ValidationTraces {
@@ -165,6 +172,7 @@ mod tests {
     fn test_apply_range_when_none() {
         let mut validation_traces = ValidationTraces {
             timestamp_asserter_range: None,
+            ..ValidationTraces::default()
         };
         let new_range = 10..20;
         validation_traces.apply_timestamp_asserter_range(new_range.clone());
@@ -175,6 +183,7 @@ mod tests {
     fn test_apply_range_with_overlap_narrower_result() {
         let mut validation_traces = ValidationTraces {
             timestamp_asserter_range: Some(5..25),
+            ..ValidationTraces::default()
         };
         validation_traces.apply_timestamp_asserter_range(10..20);
         assert_eq!(validation_traces.timestamp_asserter_range, Some(10..20));
@@ -184,6 +193,7 @@ mod tests {
     fn test_apply_range_with_partial_overlap() {
         let mut validation_traces = ValidationTraces {
             timestamp_asserter_range: Some(10..30),
+            ..ValidationTraces::default()
         };
         validation_traces.apply_timestamp_asserter_range(20..40);
         assert_eq!(validation_traces.timestamp_asserter_range, Some(20..30));
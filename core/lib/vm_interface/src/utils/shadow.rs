@@ -48,7 +48,8 @@ impl DivergenceHandler {
         Self(Arc::new(f))
     }
 
-    fn handle(&self, err: DivergenceErrors, dump: VmDump) {
+    /// Invokes the handler.
+    pub fn handle(&self, err: DivergenceErrors, dump: VmDump) {
         self.0(err, dump);
     }
 }
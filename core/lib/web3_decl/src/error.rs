@@ -13,6 +13,7 @@ use std::{
 
 use jsonrpsee::{core::ClientError, types::error::ErrorCode};
 use pin_project_lite::pin_project;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use zksync_types::{api::SerializationTransactionError, L1BatchNumber, L2BlockNumber};
 
@@ -21,10 +22,9 @@ use zksync_types::{api::SerializationTransactionError, L1BatchNumber, L2BlockNum
 pub enum Web3Error {
     #[error("Block with such an ID doesn't exist yet")]
     NoBlock,
-    #[error("Block with such an ID is pruned; the first retained block is {0}")]
-    PrunedBlock(L2BlockNumber),
-    #[error("L1 batch with such an ID is pruned; the first retained L1 batch is {0}")]
-    PrunedL1Batch(L1BatchNumber),
+    /// Requested data is pruned on the node. The details are returned to the client as the error data.
+    #[error("{0}")]
+    Pruned(PrunedData),
     #[error("{}", _0.as_ref())]
     ProxyError(#[from] EnrichedClientError),
     #[error("{0}")]
@@ -39,6 +39,9 @@ pub enum Web3Error {
     LogsLimitExceeded(usize, u32, u32),
     #[error("invalid filter: if blockHash is supplied fromBlock and toBlock must not be")]
     InvalidFilterBlockHash,
+    /// Requested tracer is not supported by the method, e.g. because the method doesn't re-execute transactions.
+    #[error("Tracer `{0}` is not supported by this method")]
+    UnsupportedTracer(&'static str),
     /// Weaker form of a "method not found" error; the method implementation is technically present,
     /// but the node configuration prevents the method from functioning.
     #[error("Method not implemented")]
@@ -50,6 +53,50 @@ pub enum Web3Error {
     InternalError(#[from] anyhow::Error),
 }
 
+/// Kind of data requested from the node that turned out to be pruned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PrunedDataKind {
+    L2Block,
+    L1Batch,
+}
+
+/// Details about pruned data returned in [`Web3Error::Pruned`]. Serialized as the error data, so that clients
+/// can figure out which data the node can still serve, and where to get the pruned data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrunedData {
+    /// Kind of the requested data.
+    pub kind: PrunedDataKind,
+    /// Earliest L2 block retained by the node.
+    pub first_retained_block: L2BlockNumber,
+    /// Earliest L1 batch retained by the node. For Merkle tree data, this is the earliest batch retained by the tree,
+    /// which may be greater than the earliest batch retained in Postgres.
+    pub first_retained_l1_batch: L1BatchNumber,
+    /// Whether a snapshot for the requested L1 batch can be obtained via the `snapshots_` namespace.
+    /// Always `false` for L2 blocks.
+    pub snapshot_available: bool,
+    /// Whether the request can be served by an archive peer of the node (e.g., the main node for external nodes).
+    pub archive_peer_available: bool,
+}
+
+impl fmt::Display for PrunedData {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            PrunedDataKind::L2Block => write!(
+                formatter,
+                "Block with such an ID is pruned; the first retained block is {}",
+                self.first_retained_block
+            ),
+            PrunedDataKind::L1Batch => write!(
+                formatter,
+                "L1 batch with such an ID is pruned; the first retained L1 batch is {}",
+                self.first_retained_l1_batch
+            ),
+        }
+    }
+}
+
 /// Client RPC error with additional details: the method name and arguments of the called method.
 ///
 /// The wrapped error can be accessed using [`AsRef`].
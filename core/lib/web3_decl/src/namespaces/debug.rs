@@ -2,7 +2,11 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
-    api::{BlockId, BlockNumber, CallTracerBlockResult, CallTracerResult, TracerConfig},
+    api::{
+        execution_trace::{ExecutionTrace, ExecutionTraceConfig},
+        BlockId, BlockNumber, CallTracerBlockResult, CallTracerResult, TraceCallConfig,
+        TracerConfig,
+    },
     transaction_request::CallRequest,
 };
 
@@ -39,13 +43,28 @@ pub trait DebugNamespace {
         &self,
         request: CallRequest,
         block: Option<BlockId>,
-        options: Option<TracerConfig>,
+        options: Option<TraceCallConfig>,
     ) -> RpcResult<CallTracerResult>;
 
+    #[method(name = "traceCallExecution")]
+    async fn trace_call_execution(
+        &self,
+        request: CallRequest,
+        block: Option<BlockId>,
+        options: Option<ExecutionTraceConfig>,
+    ) -> RpcResult<ExecutionTrace>;
+
     #[method(name = "traceTransaction")]
     async fn trace_transaction(
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
     ) -> RpcResult<Option<CallTracerResult>>;
+
+    #[method(name = "traceTransactionExecution")]
+    async fn trace_transaction_execution(
+        &self,
+        tx_hash: H256,
+        options: Option<ExecutionTraceConfig>,
+    ) -> RpcResult<Option<ExecutionTrace>>;
 }
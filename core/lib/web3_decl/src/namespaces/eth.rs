@@ -4,7 +4,7 @@ use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
     api::{
         state_override::StateOverride, BlockId, BlockIdVariant, BlockNumber, FeeHistory,
-        Transaction, TransactionVariant,
+        Transaction, TransactionConditions, TransactionVariant,
     },
     transaction_request::CallRequest,
     Address, H256,
@@ -153,6 +153,13 @@ pub trait EthNamespace {
     #[method(name = "sendRawTransaction")]
     async fn send_raw_transaction(&self, tx_bytes: Bytes) -> RpcResult<H256>;
 
+    #[method(name = "sendRawTransactionConditional")]
+    async fn send_raw_transaction_conditional(
+        &self,
+        tx_bytes: Bytes,
+        conditions: TransactionConditions,
+    ) -> RpcResult<H256>;
+
     #[method(name = "syncing")]
     async fn syncing(&self) -> RpcResult<SyncState>;
 
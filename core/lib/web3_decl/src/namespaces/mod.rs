@@ -8,6 +8,7 @@ pub use self::{
     debug::DebugNamespaceServer, en::EnNamespaceServer, eth::EthNamespaceServer,
     eth::EthPubSubServer, net::NetNamespaceServer, snapshots::SnapshotsNamespaceServer,
     unstable::UnstableNamespaceServer, web3::Web3NamespaceServer, zks::ZksNamespaceServer,
+    zks::ZksPubSubServer,
 };
 
 mod debug;
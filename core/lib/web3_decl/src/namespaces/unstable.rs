@@ -2,12 +2,21 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
-    api::{ChainAggProof, TeeProof, TransactionExecutionInfo},
+    api::{
+        gas_profile::GasProfile, state_override::StateOverride, BaseTokenRatioAuditEntry,
+        ChainAggProof, ContractStorageUsage, L1TxProcessingStatus, LogsCursor, LogsPage,
+        PipelineCongestion, PriorityOpInfo, StagedBaseSystemContract, TeeProof,
+        TransactionExecutionInfo, TransactionSimulation,
+    },
     tee_types::TeeType,
-    L1BatchNumber, L2ChainId, H256,
+    transaction_request::CallRequest,
+    L1BatchNumber, L2ChainId, PriorityOpId, ProtocolVersionId, H256,
 };
 
-use crate::client::{ForWeb3Network, L2};
+use crate::{
+    client::{ForWeb3Network, L2},
+    types::Filter,
+};
 
 /// RPCs in this namespace are experimental, and their interface is unstable, and it WILL change.
 #[cfg_attr(
@@ -38,4 +47,88 @@ pub trait UnstableNamespace {
         l1_batch_number: L1BatchNumber,
         chain_id: L2ChainId,
     ) -> RpcResult<Option<ChainAggProof>>;
+
+    /// Returns logs matching the filter in pages of bounded size. Unlike `eth_getLogs`, the block range
+    /// of the filter is not limited by the number of matching logs.
+    #[method(name = "getLogsPaginated")]
+    async fn get_logs_paginated(
+        &self,
+        filter: Filter,
+        cursor: Option<LogsCursor>,
+        page_size: Option<usize>,
+    ) -> RpcResult<LogsPage>;
+
+    /// Executes a transaction on top of the pending state without persisting its effects, and returns
+    /// emitted events with decoded token transfers, balance changes, storage diffs and a gas breakdown.
+    /// The transaction doesn't need to be signed.
+    #[method(name = "simulateTransaction")]
+    async fn simulate_transaction(
+        &self,
+        req: CallRequest,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<TransactionSimulation>;
+
+    /// Executes a transaction on top of the pending state without persisting its effects, and attributes
+    /// gas and pubdata usage to called contract functions, including account validation and paymaster functions.
+    /// The transaction doesn't need to be signed.
+    #[method(name = "gasProfile")]
+    async fn gas_profile(
+        &self,
+        req: CallRequest,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<GasProfile>;
+
+    /// Returns priority operations (L1->L2 transactions) in the L1 priority queue as seen by the node, i.e.,
+    /// operations not included into an L1 batch executed on L1, starting from the operation with `from_id`
+    /// serial ID (by default, from the first operation in the queue).
+    #[method(name = "getPriorityQueue")]
+    async fn get_priority_queue(
+        &self,
+        from_id: Option<PriorityOpId>,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<PriorityOpInfo>>;
+
+    /// Returns the processing status of an L1-originated transaction (a priority operation or a protocol upgrade
+    /// transaction) by its canonical hash, including the L2 block and L1 batch it's included in. Returns `null`
+    /// if the node doesn't know such a transaction, e.g. if it wasn't picked up from L1 yet.
+    #[method(name = "getL1TxProcessingStatus")]
+    async fn get_l1_tx_processing_status(
+        &self,
+        tx_hash: H256,
+    ) -> RpcResult<Option<L1TxProcessingStatus>>;
+
+    /// Returns congestion of the proof / execution pipeline, i.e. the backlog of sealed L1 batches not yet proven
+    /// or executed on L1, together with the L2 gas price multiplier applied because of it. Returns `null`
+    /// if pipeline backpressure is not configured on the node.
+    #[method(name = "getPipelineCongestion")]
+    async fn get_pipeline_congestion(&self) -> RpcResult<Option<PipelineCongestion>>;
+
+    /// Returns base system contracts staged ahead of a protocol upgrade for the specified protocol version
+    /// (by default, for all versions), together with their validation status against the upgrade on L1.
+    #[method(name = "getStagedBaseSystemContracts")]
+    async fn get_staged_base_system_contracts(
+        &self,
+        protocol_version: Option<ProtocolVersionId>,
+    ) -> RpcResult<Vec<StagedBaseSystemContract>>;
+
+    /// Returns base token conversion ratios persisted by the node together with the raw price quotes
+    /// they were derived from, starting from the ratio quoted at `from_timestamp` (Unix seconds; by default,
+    /// from the first ratio). Allows auditing exchange rates applied in batch fee inputs.
+    #[method(name = "getBaseTokenRatioAudit")]
+    async fn get_base_token_ratio_audit(
+        &self,
+        from_timestamp: Option<u64>,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<BaseTokenRatioAuditEntry>>;
+
+    /// Returns contracts with the largest pubdata published for their storage writes in the specified
+    /// inclusive range of L1 batches, ordered by pubdata size. Requires the storage analytics component
+    /// to be running; L1 batches not yet processed by it are not taken into account.
+    #[method(name = "getTopPubdataConsumers")]
+    async fn get_top_pubdata_consumers(
+        &self,
+        from_batch: L1BatchNumber,
+        to_batch: L1BatchNumber,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<ContractStorageUsage>>;
 }
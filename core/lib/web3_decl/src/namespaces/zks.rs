@@ -5,8 +5,9 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
     api::{
-        state_override::StateOverride, BlockDetails, BridgeAddresses, L1BatchDetails,
-        L2ToL1LogProof, Proof, ProtocolVersion, TransactionDetailedResult, TransactionDetails,
+        state_override::StateOverride, BatchStateDiff, BlockDetails, BlockNumber, BridgeAddresses,
+        BridgeTransfersPage, L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion,
+        TransactionDetailedResult, TransactionDetails, ZksFeeHistory,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -111,6 +112,10 @@ pub trait ZksNamespace {
     async fn get_l1_batch_details(&self, batch: L1BatchNumber)
         -> RpcResult<Option<L1BatchDetails>>;
 
+    #[method(name = "getBatchStateDiff")]
+    async fn get_batch_state_diff(&self, batch: L1BatchNumber)
+        -> RpcResult<Option<BatchStateDiff>>;
+
     #[method(name = "getBytecodeByHash")]
     async fn get_bytecode_by_hash(&self, hash: H256) -> RpcResult<Option<Vec<u8>>>;
 
@@ -137,9 +142,57 @@ pub trait ZksNamespace {
     #[method(name = "getBatchFeeInput")]
     async fn get_batch_fee_input(&self) -> RpcResult<PubdataIndependentBatchFeeModelInput>;
 
+    #[method(name = "getFeeHistory")]
+    async fn get_fee_history(
+        &self,
+        block_count: U64,
+        newest_block: BlockNumber,
+    ) -> RpcResult<ZksFeeHistory>;
+
     #[method(name = "sendRawTransactionWithDetailedOutput")]
     async fn send_raw_transaction_with_detailed_output(
         &self,
         tx_bytes: Bytes,
     ) -> RpcResult<TransactionDetailedResult>;
+
+    /// Returns bridge transfers (deposits and withdrawals) of the specified account indexed by the bridge indexer,
+    /// in pages of bounded size. `cursor` is taken from the previous page; if not specified, the first page is returned.
+    #[method(name = "getBridgeTransfers")]
+    async fn get_bridge_transfers(
+        &self,
+        account: Address,
+        cursor: Option<U64>,
+        page_size: Option<usize>,
+    ) -> RpcResult<BridgeTransfersPage>;
+}
+
+#[cfg(feature = "server")]
+mod pub_sub {
+    use jsonrpsee::{core::SubscriptionResult, proc_macros::rpc};
+    use zksync_types::L2BlockNumber;
+
+    use crate::types::PubSubFilter;
+
+    #[rpc(server, namespace = "zks")]
+    pub trait ZksPubSub {
+        /// Subscribes to L1 batches being sealed, committed, proven and executed on L1.
+        #[subscription(name = "subscribeBatchEvents" => "batchEvents", unsubscribe = "unsubscribeBatchEvents", item = PubSubResult)]
+        async fn subscribe_batch_events(&self) -> SubscriptionResult;
+
+        /// Subscribes to logs matching the filter starting from the specified L2 block. Historical logs are streamed first,
+        /// after which the subscription seamlessly switches to logs in newly sealed L2 blocks. Each matching log is
+        /// delivered exactly once, in the order of L2 blocks.
+        ///
+        /// Historical logs are subject to the same limit as `eth_getLogs`: the subscription is rejected if it would replay
+        /// more logs than allowed for a single `eth_getLogs` call (unless only a single L2 block is replayed).
+        #[subscription(name = "subscribeLogsFrom" => "logs", unsubscribe = "unsubscribeLogsFrom", item = PubSubResult)]
+        async fn subscribe_logs_from(
+            &self,
+            from_block: L2BlockNumber,
+            filter: Option<PubSubFilter>,
+        ) -> SubscriptionResult;
+    }
 }
+
+#[cfg(feature = "server")]
+pub use self::pub_sub::ZksPubSubServer;
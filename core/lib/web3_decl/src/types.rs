@@ -16,7 +16,7 @@ pub use zksync_types::{
         BlockHeader, Bytes, CallRequest, FeeHistory, Index, SyncState, TraceFilter, U64Number,
         ValueOrArray, Work,
     },
-    Address, Transaction, H160, H256, H64, U256, U64,
+    Address, L1BatchNumber, Transaction, H160, H256, H64, U256, U64,
 };
 
 /// Token in the ZKsync network
@@ -295,10 +295,33 @@ fn topic_to_option<T>(topic: ethabi::Topic<T>) -> Option<Vec<T>> {
 pub enum PubSubResult {
     Header(BlockHeader),
     Log(Log),
+    L1BatchEvent(L1BatchEvent),
     TxHash(H256),
     Syncing(bool),
 }
 
+/// Stage of the L1 batch lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum L1BatchEventKind {
+    Sealed,
+    Committed,
+    Proven,
+    Executed,
+}
+
+/// Notification sent by the `zks_subscribeBatchEvents` subscription when an L1 batch advances
+/// through its lifecycle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchEvent {
+    pub l1_batch_number: L1BatchNumber,
+    pub event: L1BatchEventKind,
+    /// Hash of the L1 transaction that committed, proved or executed the batch.
+    /// Not set for sealed batches.
+    pub l1_tx_hash: Option<H256>,
+}
+
 #[cfg(test)]
 mod tests {
     use zksync_types::api::{BlockId, BlockIdVariant};
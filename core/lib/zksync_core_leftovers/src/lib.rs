@@ -55,6 +55,8 @@ pub enum Component {
     DADispatcher,
     /// VM runner-based component that saves protective reads to Postgres.
     VmRunnerProtectiveReads,
+    /// One-off component that backfills protective reads for historical L1 batches that don't have them.
+    VmRunnerProtectiveReadsBackfill,
     /// A component to fetch and persist ETH<->BaseToken conversion ratios for chains with custom base tokens.
     BaseTokenRatioPersister,
     /// VM runner-based component that saves VM execution data for basic witness generation.
@@ -63,6 +65,16 @@ pub enum Component {
     ExternalProofIntegrationApi,
     /// VM runner-based component that allows to test experimental VM features. Doesn't save any data to Postgres.
     VmPlayground,
+    /// Component pruning historical data from Postgres for L1 batches executed on L1.
+    DbPruner,
+    /// Authenticated admin API allowing to control the running node.
+    AdminApi,
+    /// Component aggregating storage writes and published pubdata per contract for sealed L1 batches.
+    StorageAnalytics,
+    /// Component finalizing L2 -> L1 withdrawals on L1 once the containing L1 batches are executed.
+    WithdrawalFinalizer,
+    /// Component indexing bridge events emitted on L2. Requires the ETH watcher to index bridge events emitted on L1.
+    BridgeIndexer,
 }
 
 #[derive(Debug)]
@@ -100,6 +112,9 @@ impl FromStr for Components {
             "vm_runner_protective_reads" => {
                 Ok(Components(vec![Component::VmRunnerProtectiveReads]))
             }
+            "vm_runner_protective_reads_backfill" => {
+                Ok(Components(vec![Component::VmRunnerProtectiveReadsBackfill]))
+            }
             "base_token_ratio_persister" => {
                 Ok(Components(vec![Component::BaseTokenRatioPersister]))
             }
@@ -108,6 +123,11 @@ impl FromStr for Components {
             "external_proof_integration_api" => {
                 Ok(Components(vec![Component::ExternalProofIntegrationApi]))
             }
+            "db_pruner" => Ok(Components(vec![Component::DbPruner])),
+            "admin_api" => Ok(Components(vec![Component::AdminApi])),
+            "storage_analytics" => Ok(Components(vec![Component::StorageAnalytics])),
+            "withdrawal_finalizer" => Ok(Components(vec![Component::WithdrawalFinalizer])),
+            "bridge_indexer" => Ok(Components(vec![Component::BridgeIndexer])),
             other => Err(format!("{} is not a valid component name", other)),
         }
     }
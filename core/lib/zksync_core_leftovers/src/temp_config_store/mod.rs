@@ -10,19 +10,24 @@ use zksync_config::{
         },
         fri_prover_group::FriProverGroupConfig,
         house_keeper::HouseKeeperConfig,
+        object_store::S3Secrets,
         vm_runner::BasicWitnessInputProducerConfig,
-        wallets::{AddressWallet, EthSender, StateKeeper, TokenMultiplierSetter, Wallet, Wallets},
+        wallets::{
+            AddressWallet, EthSender, StateKeeper, TokenMultiplierSetter, Wallet, Wallets,
+            WithdrawalFinalizer,
+        },
         CommitmentGeneratorConfig, DatabaseSecrets, ExperimentalVmConfig,
         ExternalPriceApiClientConfig, FriProofCompressorConfig, FriProverConfig,
         FriProverGatewayConfig, FriWitnessGeneratorConfig, FriWitnessVectorGeneratorConfig,
-        GeneralConfig, ObservabilityConfig, PrometheusConfig, ProofDataHandlerConfig,
-        ProtectiveReadsWriterConfig, ProverJobMonitorConfig, PruningConfig, SnapshotRecoveryConfig,
+        GeneralConfig, LeaderElectionConfig, ObservabilityConfig, PrometheusConfig,
+        ProofDataHandlerConfig, ProtectiveReadsWriterConfig, ProverJobMonitorConfig, PruningConfig,
+        SnapshotRecoveryConfig, WithdrawalFinalizerConfig,
     },
-    ApiConfig, BaseTokenAdjusterConfig, ContractVerifierConfig, DAClientConfig, DADispatcherConfig,
-    DBConfig, EthConfig, EthWatchConfig, ExternalProofIntegrationApiConfig, GasAdjusterConfig,
-    ObjectStoreConfig, PostgresConfig, SnapshotsCreatorConfig,
+    AdminApiConfig, ApiConfig, BaseTokenAdjusterConfig, ContractVerifierConfig, DAClientConfig,
+    DADispatcherConfig, DBConfig, EthConfig, EthWatchConfig, ExternalProofIntegrationApiConfig,
+    GasAdjusterConfig, ObjectStoreConfig, PostgresConfig, SnapshotsCreatorConfig,
 };
-use zksync_env_config::FromEnv;
+use zksync_env_config::{leader_election_config_from_env, FromEnv};
 use zksync_protobuf::repr::ProtoRepr;
 use zksync_protobuf_config::proto::secrets::Secrets;
 
@@ -82,6 +87,9 @@ pub struct TempConfigStore {
     pub experimental_vm_config: Option<ExperimentalVmConfig>,
     pub prover_job_monitor_config: Option<ProverJobMonitorConfig>,
     pub timestamp_asserter_config: Option<TimestampAsserterConfig>,
+    pub admin_api_config: Option<AdminApiConfig>,
+    pub leader_election_config: Option<LeaderElectionConfig>,
+    pub withdrawal_finalizer_config: Option<WithdrawalFinalizerConfig>,
 }
 
 impl TempConfigStore {
@@ -124,6 +132,9 @@ impl TempConfigStore {
             experimental_vm_config: self.experimental_vm_config.clone(),
             prover_job_monitor_config: self.prover_job_monitor_config.clone(),
             timestamp_asserter_config: self.timestamp_asserter_config.clone(),
+            admin_api_config: self.admin_api_config.clone(),
+            leader_election_config: self.leader_election_config.clone(),
+            withdrawal_finalizer_config: self.withdrawal_finalizer_config.clone(),
         }
     }
 
@@ -156,10 +167,19 @@ impl TempConfigStore {
             let wallet = Wallet::new(pk);
             Some(TokenMultiplierSetter { wallet })
         });
+        let withdrawal_finalizer = self
+            .withdrawal_finalizer_config
+            .as_ref()
+            .and_then(|config| {
+                let pk = config.private_key().ok()??;
+                let wallet = Wallet::new(pk);
+                Some(WithdrawalFinalizer { wallet })
+            });
         Wallets {
             eth_sender,
             state_keeper,
             token_multiplier_setter,
+            withdrawal_finalizer,
         }
     }
 }
@@ -206,6 +226,9 @@ fn load_env_config() -> anyhow::Result<TempConfigStore> {
         experimental_vm_config: ExperimentalVmConfig::from_env().ok(),
         prover_job_monitor_config: ProverJobMonitorConfig::from_env().ok(),
         timestamp_asserter_config: TimestampAsserterConfig::from_env().ok(),
+        admin_api_config: AdminApiConfig::from_env().ok(),
+        leader_election_config: leader_election_config_from_env()?,
+        withdrawal_finalizer_config: WithdrawalFinalizerConfig::from_env().ok(),
     })
 }
 
@@ -231,3 +254,11 @@ pub fn load_database_secrets(path: Option<PathBuf>) -> anyhow::Result<DatabaseSe
         None => DatabaseSecrets::from_env(),
     }
 }
+
+/// Loads credentials for S3-compatible object stores. Returns `None` if they are not specified.
+pub fn load_s3_secrets(path: Option<PathBuf>) -> anyhow::Result<Option<S3Secrets>> {
+    match path {
+        Some(path) => Ok(read_yaml_repr::<Secrets>(&path)?.s3),
+        None => Ok(S3Secrets::from_env().ok()),
+    }
+}
@@ -0,0 +1,494 @@
+//! Admin API allowing to control a running node without restarting it or manipulating its database.
+//!
+//! All endpoints require the `Authorization: Bearer <token>` header with the token specified in the server secrets.
+//! The following endpoints are exposed:
+//!
+//! - `GET /components`: health of all node components (same as `GET /health` on the healthcheck server).
+//! - `GET /state_keeper`, `POST /state_keeper/pause`, `POST /state_keeper/resume`: inspect the state keeper status
+//!   and pause / resume transaction processing.
+//! - `GET /log_directives`, `PUT /log_directives`: inspect and change log directives (in the `RUST_LOG` format).
+//!   An empty `PUT` body resets directives to the ones the node was started with.
+//! - `GET /tee/allowed_measurements`, `PUT /tee/allowed_measurements`: inspect and replace enclave measurements
+//!   allowed by the TEE attestation policy of the proof data handler. Measurements are hex-encoded.
+//! - `POST /shutdown`: initiate graceful node shutdown.
+
+use std::{net::SocketAddr, sync::Arc};
+
+use anyhow::Context as _;
+use axum::{
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use secrecy::ExposeSecret;
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_health_check::{AppHealth, AppHealthCheck};
+use zksync_state_keeper::StateKeeperPauseHandle;
+use zksync_types::secrets::APIKey;
+use zksync_vlog::logs::{LogDirectivesError, LogDirectivesHandle};
+
+#[derive(Debug, Serialize)]
+struct StateKeeperStatus {
+    paused: bool,
+}
+
+/// Hex-encoded enclave measurements; the format is compatible with `TeeAllowedMeasurements` from the prover interface.
+#[derive(Debug, Serialize, Deserialize)]
+struct TeeAllowedMeasurements {
+    measurements: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct AdminState {
+    auth_token: Arc<str>,
+    app_health_check: Arc<AppHealthCheck>,
+    state_keeper: Option<StateKeeperPauseHandle>,
+    tee_pool: Option<ConnectionPool<Core>>,
+    shutdown_sender: Arc<watch::Sender<bool>>,
+}
+
+impl AdminState {
+    fn state_keeper(&self) -> Result<&StateKeeperPauseHandle, (StatusCode, &'static str)> {
+        self.state_keeper.as_ref().ok_or((
+            StatusCode::NOT_FOUND,
+            "state keeper is not running on this node",
+        ))
+    }
+
+    fn tee_pool(&self) -> Result<&ConnectionPool<Core>, (StatusCode, String)> {
+        self.tee_pool.as_ref().ok_or((
+            StatusCode::NOT_FOUND,
+            "TEE attestation policy is not managed by this node".to_owned(),
+        ))
+    }
+}
+
+fn internal_error(err: impl std::fmt::Display) -> (StatusCode, String) {
+    (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+}
+
+/// Compares tokens in constant time (w.r.t. the token contents) to prevent timing attacks.
+fn tokens_match(expected: &[u8], actual: &[u8]) -> bool {
+    expected.len() == actual.len()
+        && expected
+            .iter()
+            .zip(actual)
+            .fold(0_u8, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+async fn authorize(State(state): State<AdminState>, request: Request, next: Next) -> Response {
+    let token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    match token {
+        Some(token) if tokens_match(state.auth_token.as_bytes(), token.as_bytes()) => {
+            next.run(request).await
+        }
+        _ => (StatusCode::UNAUTHORIZED, "invalid or missing bearer token").into_response(),
+    }
+}
+
+async fn get_components(State(state): State<AdminState>) -> Json<AppHealth> {
+    Json(state.app_health_check.check_health().await)
+}
+
+async fn get_state_keeper(
+    State(state): State<AdminState>,
+) -> Result<Json<StateKeeperStatus>, (StatusCode, &'static str)> {
+    let paused = state.state_keeper()?.is_paused();
+    Ok(Json(StateKeeperStatus { paused }))
+}
+
+async fn pause_state_keeper(
+    State(state): State<AdminState>,
+) -> Result<Json<StateKeeperStatus>, (StatusCode, &'static str)> {
+    if state.state_keeper()?.pause() {
+        tracing::warn!("State keeper was paused via admin API");
+    }
+    Ok(Json(StateKeeperStatus { paused: true }))
+}
+
+async fn resume_state_keeper(
+    State(state): State<AdminState>,
+) -> Result<Json<StateKeeperStatus>, (StatusCode, &'static str)> {
+    if state.state_keeper()?.resume() {
+        tracing::info!("State keeper was resumed via admin API");
+    }
+    Ok(Json(StateKeeperStatus { paused: false }))
+}
+
+/// Returns log directives currently applied by the node.
+async fn get_log_directives() -> (StatusCode, String) {
+    match LogDirectivesHandle::global() {
+        Some(handle) => (StatusCode::OK, handle.current()),
+        None => log_directives_not_available(),
+    }
+}
+
+/// Reloads log directives (in the `RUST_LOG` format) provided in the request body.
+/// An empty body resets directives to the ones the node was started with.
+async fn put_log_directives(directives: String) -> (StatusCode, String) {
+    let Some(handle) = LogDirectivesHandle::global() else {
+        return log_directives_not_available();
+    };
+    let directives = directives.trim();
+    let result = if directives.is_empty() {
+        handle.reset()
+    } else {
+        handle.reload(directives)
+    };
+    match result {
+        Ok(()) => (StatusCode::OK, handle.current()),
+        Err(err @ LogDirectivesError::Parse(_)) => (StatusCode::BAD_REQUEST, err.to_string()),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+fn log_directives_not_available() -> (StatusCode, String) {
+    (
+        StatusCode::NOT_FOUND,
+        "log directives cannot be changed: observability is not initialized".to_owned(),
+    )
+}
+
+async fn get_allowed_measurements(
+    State(state): State<AdminState>,
+) -> Result<Json<TeeAllowedMeasurements>, (StatusCode, String)> {
+    let measurements = state
+        .tee_pool()?
+        .connection_tagged("admin_api")
+        .await
+        .map_err(internal_error)?
+        .tee_proof_generation_dal()
+        .get_allowed_measurements()
+        .await
+        .map_err(internal_error)?;
+    Ok(Json(TeeAllowedMeasurements {
+        measurements: measurements.iter().map(hex::encode).collect(),
+    }))
+}
+
+async fn set_allowed_measurements(
+    State(state): State<AdminState>,
+    Json(payload): Json<TeeAllowedMeasurements>,
+) -> Result<Json<TeeAllowedMeasurements>, (StatusCode, String)> {
+    let pool = state.tee_pool()?;
+    let measurements = payload
+        .measurements
+        .iter()
+        .map(|measurement| {
+            let bytes = hex::decode(measurement.strip_prefix("0x").unwrap_or(measurement))
+                .map_err(|err| {
+                    (
+                        StatusCode::BAD_REQUEST,
+                        format!("measurement {measurement} is not valid hex: {err}"),
+                    )
+                })?;
+            // SGX `MRENCLAVE` is a SHA-256 digest, and TDX `MRTD` is a SHA-384 digest.
+            if !matches!(bytes.len(), 32 | 48) {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    format!(
+                        "measurement {measurement} has unexpected length; expected 32 (SGX) or 48 (TDX) bytes"
+                    ),
+                ));
+            }
+            Ok(bytes)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    tracing::warn!(
+        "Allowed TEE enclave measurements were updated via admin API: {:?}",
+        payload.measurements
+    );
+    pool.connection_tagged("admin_api")
+        .await
+        .map_err(internal_error)?
+        .tee_proof_generation_dal()
+        .set_allowed_measurements(&measurements)
+        .await
+        .map_err(internal_error)?;
+    get_allowed_measurements(State(state)).await
+}
+
+async fn shutdown(State(state): State<AdminState>) -> StatusCode {
+    tracing::info!("Graceful shutdown was requested via admin API");
+    state.shutdown_sender.send_replace(true);
+    StatusCode::ACCEPTED
+}
+
+/// Authenticated HTTP server allowing to control a running node.
+#[derive(Debug)]
+pub struct AdminServer {
+    bind_address: SocketAddr,
+    auth_token: APIKey,
+    app_health_check: Arc<AppHealthCheck>,
+    state_keeper: Option<StateKeeperPauseHandle>,
+    tee_pool: Option<ConnectionPool<Core>>,
+}
+
+impl AdminServer {
+    pub fn new(
+        bind_address: SocketAddr,
+        auth_token: APIKey,
+        app_health_check: Arc<AppHealthCheck>,
+    ) -> Self {
+        Self {
+            bind_address,
+            auth_token,
+            app_health_check,
+            state_keeper: None,
+            tee_pool: None,
+        }
+    }
+
+    /// Enables endpoints controlling the state keeper.
+    pub fn with_state_keeper(mut self, handle: StateKeeperPauseHandle) -> Self {
+        self.state_keeper = Some(handle);
+        self
+    }
+
+    /// Enables endpoints managing the TEE attestation policy of the proof data handler.
+    pub fn with_tee_attestation_policy(mut self, pool: ConnectionPool<Core>) -> Self {
+        self.tee_pool = Some(pool);
+        self
+    }
+
+    fn router(self, shutdown_sender: Arc<watch::Sender<bool>>) -> Router {
+        let state = AdminState {
+            auth_token: self.auth_token.0.expose_secret().as_str().into(),
+            app_health_check: self.app_health_check,
+            state_keeper: self.state_keeper,
+            tee_pool: self.tee_pool,
+            shutdown_sender,
+        };
+        Router::new()
+            .route("/components", get(get_components))
+            .route("/state_keeper", get(get_state_keeper))
+            .route("/state_keeper/pause", post(pause_state_keeper))
+            .route("/state_keeper/resume", post(resume_state_keeper))
+            .route(
+                "/log_directives",
+                get(get_log_directives).put(put_log_directives),
+            )
+            .route(
+                "/tee/allowed_measurements",
+                get(get_allowed_measurements).put(set_allowed_measurements),
+            )
+            .route("/shutdown", post(shutdown))
+            .route_layer(middleware::from_fn_with_state(state.clone(), authorize))
+            .with_state(state)
+    }
+
+    /// Runs the server until a stop signal is received, or until graceful shutdown is requested via the API.
+    /// In the latter case, the caller is responsible for shutting down the node.
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let bind_address = self.bind_address;
+        let (shutdown_sender, mut shutdown_receiver) = watch::channel(false);
+        let app = self.router(Arc::new(shutdown_sender));
+
+        let listener = tokio::net::TcpListener::bind(bind_address)
+            .await
+            .with_context(|| format!("failed binding admin API server to {bind_address}"))?;
+        tracing::info!("Starting admin API server on {bind_address}");
+        axum::serve(listener, app)
+            .with_graceful_shutdown(async move {
+                tokio::select! {
+                    _ = stop_receiver.changed() => {
+                        tracing::info!("Stop signal received, admin API server is shutting down");
+                    }
+                    _ = shutdown_receiver.changed() => {}
+                }
+            })
+            .await?;
+        tracing::info!("Admin API server shut down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn test_router(
+        state_keeper: Option<StateKeeperPauseHandle>,
+    ) -> (Router, watch::Receiver<bool>) {
+        let mut server = AdminServer::new(
+            ([127, 0, 0, 1], 0).into(),
+            "secret".parse().unwrap(),
+            Arc::new(AppHealthCheck::default()),
+        );
+        server.state_keeper = state_keeper;
+        let (shutdown_sender, shutdown_receiver) = watch::channel(false);
+        (server.router(Arc::new(shutdown_sender)), shutdown_receiver)
+    }
+
+    fn request(method: &str, uri: &str, token: Option<&str>) -> Request {
+        let mut builder = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            builder = builder.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        builder.body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn requests_require_authorization() {
+        let (router, _) = test_router(None);
+        for token in [None, Some("wrong"), Some("secret2")] {
+            let response = router
+                .clone()
+                .oneshot(request("GET", "/components", token))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+
+        let response = router
+            .oneshot(request("GET", "/components", Some("secret")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn changing_log_directives_requires_authorization() {
+        let (router, _) = test_router(None);
+        let put_request = |token: Option<&str>| {
+            let mut builder = Request::builder().method("PUT").uri("/log_directives");
+            if let Some(token) = token {
+                builder = builder.header(header::AUTHORIZATION, format!("Bearer {token}"));
+            }
+            builder.body(Body::from("zksync=debug")).unwrap()
+        };
+
+        for token in [None, Some("wrong")] {
+            let response = router.clone().oneshot(put_request(token)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+        // Observability is not initialized in tests, so the handler reports that directives cannot be changed.
+        let response = router.oneshot(put_request(Some("secret"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(
+            String::from_utf8_lossy(&body).contains("observability is not initialized"),
+            "{body:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn pausing_and_resuming_state_keeper() {
+        let (router, _) = test_router(None);
+        let response = router
+            .oneshot(request("POST", "/state_keeper/pause", Some("secret")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let handle = StateKeeperPauseHandle::default();
+        let (router, _) = test_router(Some(handle.clone()));
+        let response = router
+            .clone()
+            .oneshot(request("POST", "/state_keeper/pause", Some("secret")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(handle.is_paused());
+
+        let response = router
+            .oneshot(request("POST", "/state_keeper/resume", Some("secret")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!handle.is_paused());
+    }
+
+    #[tokio::test]
+    async fn managing_tee_allowed_measurements() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let server = AdminServer::new(
+            ([127, 0, 0, 1], 0).into(),
+            "secret".parse().unwrap(),
+            Arc::new(AppHealthCheck::default()),
+        )
+        .with_tee_attestation_policy(pool.clone());
+        let (shutdown_sender, _) = watch::channel(false);
+        let router = server.router(Arc::new(shutdown_sender));
+
+        let put_request = |body: serde_json::Value, token: &str| {
+            Request::builder()
+                .method("PUT")
+                .uri("/tee/allowed_measurements")
+                .header(header::AUTHORIZATION, format!("Bearer {token}"))
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+        let measurement = hex::encode([1_u8; 32]);
+        let body = serde_json::json!({ "measurements": [measurement] });
+        let response = router
+            .clone()
+            .oneshot(put_request(body.clone(), "wrong"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let invalid_body = serde_json::json!({ "measurements": [hex::encode([1_u8; 20])] });
+        let response = router
+            .clone()
+            .oneshot(put_request(invalid_body, "secret"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = router
+            .clone()
+            .oneshot(put_request(body, "secret"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let stored = pool
+            .connection()
+            .await
+            .unwrap()
+            .tee_proof_generation_dal()
+            .get_allowed_measurements()
+            .await
+            .unwrap();
+        assert_eq!(stored, [vec![1; 32]]);
+
+        let response = router
+            .oneshot(request("GET", "/tee/allowed_measurements", Some("secret")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: TeeAllowedMeasurements = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body.measurements, [measurement]);
+    }
+
+    #[tokio::test]
+    async fn requesting_shutdown() {
+        let (router, shutdown_receiver) = test_router(None);
+        let response = router
+            .oneshot(request("POST", "/shutdown", Some("secret")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        assert!(*shutdown_receiver.borrow());
+    }
+}
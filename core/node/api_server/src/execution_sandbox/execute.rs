@@ -10,12 +10,12 @@ use zksync_multivm::interface::{
     executor::{OneshotExecutor, TransactionValidator},
     storage::{ReadStorage, StorageWithOverrides},
     tracer::{TimestampAsserterParams, ValidationError, ValidationParams, ValidationTraces},
-    Call, OneshotEnv, OneshotTracingParams, OneshotTransactionExecutionResult,
+    Call, ContractGasUsage, OneshotEnv, OneshotTracingParams, OneshotTransactionExecutionResult,
     TransactionExecutionMetrics, TxExecutionArgs, VmExecutionResultAndLogs,
 };
 use zksync_state::{PostgresStorage, PostgresStorageCaches};
 use zksync_types::{
-    api::state_override::StateOverride, fee_model::BatchFeeInput, l2::L2Tx, Transaction,
+    api::state_override::StateOverride, fee_model::BatchFeeInput, l2::L2Tx, Transaction, U256,
 };
 use zksync_vm_executor::oneshot::{MainOneshotExecutor, MockOneshotExecutor};
 
@@ -43,6 +43,14 @@ pub(crate) enum SandboxAction {
         fee_input: BatchFeeInput,
         base_fee: u64,
     },
+    /// Re-execute a transaction already included into an L2 block, possibly with tracing. Unlike other actions,
+    /// any transaction type (e.g., an L1 transaction) can be replayed.
+    Replay {
+        tx: Transaction,
+        fee_input: BatchFeeInput,
+        base_fee: u64,
+        tracing_params: OneshotTracingParams,
+    },
 }
 
 impl SandboxAction {
@@ -51,7 +59,9 @@ impl SandboxAction {
             Self::Execution { tx, .. } | Self::Call { call: tx, .. } => {
                 tx.execute.factory_deps.len()
             }
-            Self::GasEstimation { tx, .. } => tx.execute.factory_deps.len(),
+            Self::GasEstimation { tx, .. } | Self::Replay { tx, .. } => {
+                tx.execute.factory_deps.len()
+            }
         }
     }
 
@@ -70,6 +80,17 @@ impl SandboxAction {
                 tracing_params,
                 ..
             } => (TxExecutionArgs::for_eth_call(call), tracing_params),
+            Self::Replay {
+                tx, tracing_params, ..
+            } => {
+                let args = TxExecutionArgs {
+                    transaction: tx,
+                    enforced_nonce: None,
+                    added_balance: U256::zero(),
+                    adjust_pubdata_price: false,
+                };
+                (args, tracing_params)
+            }
         }
     }
 }
@@ -81,6 +102,8 @@ pub(crate) struct SandboxExecutionOutput {
     pub vm: VmExecutionResultAndLogs,
     /// Traced calls if requested.
     pub call_traces: Vec<Call>,
+    /// Gas usage by contract functions if requested.
+    pub gas_profile: Vec<ContractGasUsage>,
     /// Execution metrics.
     pub metrics: TransactionExecutionMetrics,
     /// Were published bytecodes OK?
@@ -174,6 +197,7 @@ impl SandboxExecutor {
         Ok(SandboxExecutionOutput {
             vm: *result.tx_result,
             call_traces: result.call_traces,
+            gas_profile: result.gas_profile,
             metrics,
             are_published_bytecodes_ok: result.compression_result.is_ok(),
         })
@@ -226,6 +250,16 @@ impl SandboxExecutor {
                     .to_env(&mut connection, resolved_block_info, fee_input, base_fee)
                     .await?
             }
+            &SandboxAction::Replay {
+                fee_input,
+                base_fee,
+                ..
+            } => {
+                self.options
+                    .eth_call
+                    .to_replay_env(&mut connection, resolved_block_info, fee_input, base_fee)
+                    .await?
+            }
         };
 
         if block_args.resolves_to_latest_sealed_l2_block() {
@@ -245,6 +279,10 @@ impl SandboxExecutor {
 
         if let Some(caches) = &self.storage_caches {
             storage = storage.with_caches(caches.clone());
+            storage
+                .resolve_historical_view()
+                .await
+                .context("cannot resolve historical storage view")?;
         }
         initialization_stage.observe();
         Ok((env, storage))
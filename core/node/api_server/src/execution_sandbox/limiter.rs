@@ -0,0 +1,596 @@
+//! VM concurrency limiter with weighted fair queueing among request classes and per-client limits.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    net::IpAddr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::{sync::oneshot, task::futures::TaskLocalFuture};
+use vise::{EncodeLabelSet, EncodeLabelValue};
+use zksync_config::configs::api::Web3JsonRpcConfig;
+
+use super::vm_metrics::{SandboxStage, SANDBOX_METRICS};
+
+/// Identity of the client on whose behalf VM code is executed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum VmClient {
+    /// Client with an API key known to the server. Self-asserted API keys must not be used here, since otherwise
+    /// a client could bypass per-client limits by rotating keys.
+    ApiKey(Arc<str>),
+    /// Client identified by its IP address.
+    Ip(IpAddr),
+    /// Client that cannot be identified. All such clients share a single bucket of permits.
+    Anonymous,
+}
+
+tokio::task_local! {
+    /// Client on whose behalf the current task executes VM code.
+    static VM_CLIENT: VmClient;
+}
+
+/// Wraps the provided future so that VM permits acquired within it are attributed to the specified client.
+pub(crate) fn with_vm_client<F: Future>(
+    client: VmClient,
+    future: F,
+) -> TaskLocalFuture<VmClient, F> {
+    VM_CLIENT.scope(client, future)
+}
+
+/// Returns the client for the current task, or `None` if VM code is executed outside a client request
+/// (e.g., in tests or internal tasks); such executions are not subject to per-client limits.
+fn current_vm_client() -> Option<VmClient> {
+    VM_CLIENT.try_with(Clone::clone).ok()
+}
+
+/// Configuration of a [`VmConcurrencyLimiter`] in addition to the total concurrency limit.
+#[derive(Debug, Clone)]
+pub struct VmConcurrencyLimiterConfig {
+    /// Max number of permits held by a single identified client (by a known API key or the IP address).
+    pub max_concurrency_per_client: Option<usize>,
+    /// Max total number of permits held by clients that cannot be identified. If not set,
+    /// [`Self::max_concurrency_per_client`] is used, i.e., all such clients are treated as a single client.
+    pub max_concurrency_anonymous: Option<usize>,
+    /// Weight of [`VmPermitClass::Interactive`] requests. Clamped to be at least 1.
+    pub interactive_weight: usize,
+    /// Weight of [`VmPermitClass::Heavy`] requests. Clamped to be at least 1.
+    pub heavy_weight: usize,
+}
+
+impl Default for VmConcurrencyLimiterConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrency_per_client: None,
+            max_concurrency_anonymous: None,
+            interactive_weight: 4,
+            heavy_weight: 1,
+        }
+    }
+}
+
+impl From<&Web3JsonRpcConfig> for VmConcurrencyLimiterConfig {
+    fn from(config: &Web3JsonRpcConfig) -> Self {
+        let default = Self::default();
+        Self {
+            max_concurrency_per_client: config.vm_concurrency_limit_per_client,
+            max_concurrency_anonymous: config.vm_concurrency_limit_anonymous,
+            interactive_weight: config
+                .vm_interactive_permit_weight
+                .unwrap_or(default.interactive_weight),
+            heavy_weight: config
+                .vm_heavy_permit_weight
+                .unwrap_or(default.heavy_weight),
+        }
+    }
+}
+
+/// Class of a request acquiring a [`VmPermit`]. If there are requests of multiple classes waiting for a permit,
+/// permits are distributed among classes proportionally to their weights.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "class", rename_all = "snake_case")]
+pub enum VmPermitClass {
+    /// Latency-sensitive requests: `eth_call`, gas estimation, transaction submission etc.
+    Interactive,
+    /// Heavy requests, such as call tracing.
+    Heavy,
+}
+
+impl VmPermitClass {
+    const ALL: [Self; 2] = [Self::Interactive, Self::Heavy];
+
+    fn index(self) -> usize {
+        match self {
+            Self::Interactive => 0,
+            Self::Heavy => 1,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Waiter {
+    client: Option<VmClient>,
+    sender: oneshot::Sender<PermitGuard>,
+}
+
+#[derive(Debug)]
+struct LimiterState {
+    available_permits: usize,
+    is_closed: bool,
+    queues: [VecDeque<Waiter>; 2],
+    /// Remaining number of permits that can be issued to each class in the current weighted round-robin round.
+    credits: [usize; 2],
+    permits_per_client: HashMap<VmClient, usize>,
+}
+
+#[derive(Debug)]
+struct LimiterInner {
+    max_concurrency: usize,
+    max_concurrency_per_client: Option<usize>,
+    max_concurrency_anonymous: Option<usize>,
+    /// Weights of permit classes indexed by [`VmPermitClass::index()`].
+    weights: [usize; 2],
+    state: Mutex<LimiterState>,
+}
+
+impl LimiterInner {
+    fn new(max_concurrency: usize, config: VmConcurrencyLimiterConfig) -> Self {
+        let weights = [config.interactive_weight.max(1), config.heavy_weight.max(1)];
+        Self {
+            max_concurrency,
+            max_concurrency_per_client: config.max_concurrency_per_client,
+            max_concurrency_anonymous: config
+                .max_concurrency_anonymous
+                .or(config.max_concurrency_per_client),
+            weights,
+            state: Mutex::new(LimiterState {
+                available_permits: max_concurrency,
+                is_closed: false,
+                queues: Default::default(),
+                credits: weights,
+                permits_per_client: HashMap::new(),
+            }),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LimiterState> {
+        self.state
+            .lock()
+            .expect("VM concurrency limiter is poisoned")
+    }
+
+    fn client_limit(&self, client: &VmClient) -> Option<usize> {
+        match client {
+            VmClient::ApiKey(_) | VmClient::Ip(_) => self.max_concurrency_per_client,
+            VmClient::Anonymous => self.max_concurrency_anonymous,
+        }
+    }
+
+    fn can_issue(&self, state: &LimiterState, client: Option<&VmClient>) -> bool {
+        if state.available_permits == 0 {
+            return false;
+        }
+        let Some(client) = client else {
+            return true;
+        };
+        match self.client_limit(client) {
+            Some(limit) => state.permits_per_client.get(client).copied().unwrap_or(0) < limit,
+            None => true,
+        }
+    }
+
+    fn issue(self: &Arc<Self>, state: &mut LimiterState, client: Option<VmClient>) -> PermitGuard {
+        state.available_permits -= 1;
+        if let Some(client) = &client {
+            *state.permits_per_client.entry(client.clone()).or_default() += 1;
+        }
+        PermitGuard {
+            limiter: Arc::clone(self),
+            client,
+        }
+    }
+
+    fn release(&self, state: &mut LimiterState, client: Option<&VmClient>) {
+        state.available_permits += 1;
+        if let Some(client) = client {
+            if let Some(count) = state.permits_per_client.get_mut(client) {
+                *count -= 1;
+                if *count == 0 {
+                    state.permits_per_client.remove(client);
+                }
+            }
+        }
+    }
+
+    /// Returns the position of the first waiter in the class queue that can be issued a permit.
+    fn first_eligible_waiter(&self, state: &LimiterState, class: VmPermitClass) -> Option<usize> {
+        state.queues[class.index()]
+            .iter()
+            .position(|waiter| self.can_issue(state, waiter.client.as_ref()))
+    }
+
+    /// Selects the next waiter to issue a permit to using weighted round-robin among classes.
+    fn pop_next_waiter(&self, state: &mut LimiterState) -> Option<Waiter> {
+        for queue in &mut state.queues {
+            queue.retain(|waiter| !waiter.sender.is_closed());
+        }
+        let eligible = VmPermitClass::ALL.map(|class| self.first_eligible_waiter(state, class));
+        let has_credits = |state: &LimiterState, class: VmPermitClass| {
+            eligible[class.index()].is_some() && state.credits[class.index()] > 0
+        };
+
+        let mut selected = VmPermitClass::ALL
+            .into_iter()
+            .find(|&class| has_credits(state, class));
+        if selected.is_none() {
+            // Start a new round if all classes with eligible waiters have exhausted their credits.
+            state.credits = self.weights;
+            selected = VmPermitClass::ALL
+                .into_iter()
+                .find(|&class| has_credits(state, class));
+        }
+        let class = selected?;
+        state.credits[class.index()] -= 1;
+        let position = eligible[class.index()]?;
+        let waiter = state.queues[class.index()].remove(position)?;
+        SANDBOX_METRICS.vm_queue_len[&class].set(state.queues[class.index()].len());
+        Some(waiter)
+    }
+
+    /// Issues permits to waiters while possible. Permits must be sent *after* the state lock is released
+    /// since a permit may be dropped during sending, which would lead to a deadlock.
+    fn dispatch(self: &Arc<Self>, state: &mut LimiterState) -> Vec<(Waiter, PermitGuard)> {
+        let mut issued = vec![];
+        while state.available_permits > 0 {
+            let Some(waiter) = self.pop_next_waiter(state) else {
+                break;
+            };
+            let guard = self.issue(state, waiter.client.clone());
+            issued.push((waiter, guard));
+        }
+        issued
+    }
+
+    fn send_permits(issued: Vec<(Waiter, PermitGuard)>) {
+        for (waiter, guard) in issued {
+            // If the waiter has been cancelled, the permit is dropped, which returns it to the limiter.
+            waiter.sender.send(guard).ok();
+        }
+    }
+}
+
+/// Guard returning a permit to the limiter on drop.
+#[derive(Debug)]
+struct PermitGuard {
+    limiter: Arc<LimiterInner>,
+    client: Option<VmClient>,
+}
+
+impl Drop for PermitGuard {
+    fn drop(&mut self) {
+        let issued = {
+            let mut state = self.limiter.lock();
+            self.limiter.release(&mut state, self.client.as_ref());
+            self.limiter.dispatch(&mut state)
+        };
+        LimiterInner::send_permits(issued);
+    }
+}
+
+/// Permit to invoke VM code.
+///
+/// Any publicly-facing method that invokes VM is expected to accept a reference to this structure,
+/// as a proof that the caller obtained a token from `VmConcurrencyLimiter`,
+#[derive(Debug, Clone)]
+pub struct VmPermit {
+    _permit: Arc<PermitGuard>,
+}
+
+/// Barrier-like synchronization primitive allowing to close a [`VmConcurrencyLimiter`] it's attached to
+/// so that it doesn't issue new permits, and to wait for all permits to drop.
+#[derive(Debug, Clone)]
+pub struct VmConcurrencyBarrier {
+    limiter: Arc<LimiterInner>,
+}
+
+impl VmConcurrencyBarrier {
+    /// Shuts down the related VM concurrency limiter so that it won't issue new permits.
+    pub fn close(&self) {
+        let waiters = {
+            let mut state = self.limiter.lock();
+            state.is_closed = true;
+            std::mem::take(&mut state.queues)
+        };
+        // Dropping waiters notifies the corresponding `acquire()` calls that the limiter is closed.
+        drop(waiters);
+        tracing::info!("VM concurrency limiter closed");
+    }
+
+    /// Waits until all permits issued by the VM concurrency limiter are dropped.
+    pub async fn wait_until_stopped(self) {
+        const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+        let max_concurrency = self.limiter.max_concurrency;
+        loop {
+            let (is_closed, current_permits) = {
+                let state = self.limiter.lock();
+                (state.is_closed, state.available_permits)
+            };
+            assert!(
+                is_closed,
+                "Cannot wait on non-closed VM concurrency limiter"
+            );
+
+            tracing::debug!(
+                "Waiting until all VM permits are dropped; currently remaining: {} / {max_concurrency}",
+                max_concurrency - current_permits
+            );
+            if current_permits == max_concurrency {
+                return;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+/// Synchronization primitive that limits the number of concurrent VM executions.
+/// This is required to prevent the server from being overloaded with the VM calls.
+///
+/// This structure is expected to be used in every method that executes VM code, on a topmost
+/// level (i.e. before any async calls are made or VM is instantiated),
+///
+/// If there are no free permits, requests are queued. Permits are distributed among queued requests of different
+/// [classes](VmPermitClass) using weighted round-robin, so that heavy requests cannot starve interactive ones.
+/// Optionally, the number of concurrent VM executions for a single client (identified by a known API key
+/// or the IP address) can be limited as well; clients that cannot be identified share a single limit.
+///
+/// Note that the actual limit on the number of VMs is a minimum of the limit in this structure,
+/// *and* the size of the blocking tokio threadpool. So, even if the limit is set to 1024, but
+/// tokio is configured to have no more than 512 blocking threads, the actual limit will be 512.
+#[derive(Debug)]
+pub struct VmConcurrencyLimiter {
+    inner: Arc<LimiterInner>,
+}
+
+impl VmConcurrencyLimiter {
+    /// Creates a limiter together with a barrier allowing to control its shutdown.
+    pub fn new(max_concurrency: usize) -> (Self, VmConcurrencyBarrier) {
+        Self::with_config(max_concurrency, VmConcurrencyLimiterConfig::default())
+    }
+
+    /// Creates a limiter with per-client limits and permit class weights specified in `config`.
+    pub fn with_config(
+        max_concurrency: usize,
+        config: VmConcurrencyLimiterConfig,
+    ) -> (Self, VmConcurrencyBarrier) {
+        tracing::info!(
+            "Initializing the VM concurrency limiter with max concurrency {max_concurrency}: {config:?}"
+        );
+        let inner = Arc::new(LimiterInner::new(max_concurrency, config));
+        let barrier = VmConcurrencyBarrier {
+            limiter: Arc::clone(&inner),
+        };
+        (Self { inner }, barrier)
+    }
+
+    /// Waits until there is a free slot in the concurrency limiter.
+    /// Returns a permit that should be dropped when the VM execution is finished.
+    pub async fn acquire(&self, class: VmPermitClass) -> Option<VmPermit> {
+        let client = current_vm_client();
+        let latency = SANDBOX_METRICS.sandbox[&SandboxStage::VmConcurrencyLimiterAcquire].start();
+        let queue_latency = SANDBOX_METRICS.vm_queue_wait_time[&class].start();
+
+        let (receiver, available_permits) = {
+            let mut state = self.inner.lock();
+            let available_permits = state.available_permits;
+            SANDBOX_METRICS
+                .sandbox_execution_permits
+                .observe(available_permits);
+            if state.is_closed {
+                return None;
+            }
+
+            // All queued waiters are ineligible for a permit (otherwise, they would've been dispatched already),
+            // so issuing a permit immediately doesn't violate fairness.
+            if self.inner.can_issue(&state, client.as_ref()) {
+                let guard = self.inner.issue(&mut state, client);
+                drop(state);
+                latency.observe();
+                queue_latency.observe();
+                return Some(VmPermit {
+                    _permit: Arc::new(guard),
+                });
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            let queue = &mut state.queues[class.index()];
+            queue.push_back(Waiter { client, sender });
+            SANDBOX_METRICS.vm_queue_len[&class].set(queue.len());
+            (receiver, available_permits)
+        };
+
+        let guard = receiver.await.ok()?;
+        let elapsed = latency.observe();
+        queue_latency.observe();
+        // We don't want to emit too many logs.
+        if elapsed > Duration::from_millis(10) {
+            tracing::debug!(
+                "Permit is obtained. Available permits: {available_permits}. Took {elapsed:?}"
+            );
+        }
+        Some(VmPermit {
+            _permit: Arc::new(guard),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::pin;
+
+    use futures::poll;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn interactive_requests_are_prioritized() {
+        let (limiter, _) = VmConcurrencyLimiter::new(1);
+        let permit = limiter.acquire(VmPermitClass::Heavy).await.unwrap();
+
+        let mut heavy = pin!(limiter.acquire(VmPermitClass::Heavy));
+        assert!(poll!(heavy.as_mut()).is_pending());
+        let mut interactive: Vec<_> = (0..5)
+            .map(|_| Box::pin(limiter.acquire(VmPermitClass::Interactive)))
+            .collect();
+        for future in &mut interactive {
+            assert!(poll!(future.as_mut()).is_pending());
+        }
+
+        // The first 4 permits must be issued to interactive requests, and the 5th one to the heavy request.
+        drop(permit);
+        for future in interactive.iter_mut().take(4) {
+            let permit = future.await.unwrap();
+            assert!(poll!(heavy.as_mut()).is_pending());
+            drop(permit);
+        }
+        let permit = heavy.await.unwrap();
+        assert!(poll!(interactive[4].as_mut()).is_pending());
+        drop(permit);
+        interactive.pop().unwrap().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn per_client_limit_is_enforced() {
+        let config = VmConcurrencyLimiterConfig {
+            max_concurrency_per_client: Some(1),
+            max_concurrency_anonymous: Some(2),
+            ..VmConcurrencyLimiterConfig::default()
+        };
+        let (limiter, _) = VmConcurrencyLimiter::with_config(5, config);
+        let client = VmClient::ApiKey("indexer".into());
+        let permit = with_vm_client(client.clone(), limiter.acquire(VmPermitClass::Interactive))
+            .await
+            .unwrap();
+
+        let mut same_client = pin!(with_vm_client(
+            client,
+            limiter.acquire(VmPermitClass::Interactive)
+        ));
+        assert!(poll!(same_client.as_mut()).is_pending());
+        // Requests from other clients must not be blocked.
+        let ip_client = VmClient::Ip([127, 0, 0, 1].into());
+        let _ip_permit = with_vm_client(ip_client.clone(), limiter.acquire(VmPermitClass::Heavy))
+            .await
+            .unwrap();
+        let mut same_ip = pin!(with_vm_client(
+            ip_client,
+            limiter.acquire(VmPermitClass::Heavy)
+        ));
+        assert!(poll!(same_ip.as_mut()).is_pending());
+
+        // Anonymous clients share a single bucket.
+        let _anonymous_permits = [
+            with_vm_client(
+                VmClient::Anonymous,
+                limiter.acquire(VmPermitClass::Interactive),
+            )
+            .await
+            .unwrap(),
+            with_vm_client(
+                VmClient::Anonymous,
+                limiter.acquire(VmPermitClass::Interactive),
+            )
+            .await
+            .unwrap(),
+        ];
+        let mut anonymous = pin!(with_vm_client(
+            VmClient::Anonymous,
+            limiter.acquire(VmPermitClass::Interactive)
+        ));
+        assert!(poll!(anonymous.as_mut()).is_pending());
+        // Internal executions are not subject to per-client limits.
+        let _internal_permit = limiter.acquire(VmPermitClass::Interactive).await.unwrap();
+
+        drop(permit);
+        same_client.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn anonymous_limit_defaults_to_per_client_limit() {
+        let config = VmConcurrencyLimiterConfig {
+            max_concurrency_per_client: Some(1),
+            ..VmConcurrencyLimiterConfig::default()
+        };
+        let (limiter, _) = VmConcurrencyLimiter::with_config(5, config);
+        let permit = with_vm_client(VmClient::Anonymous, limiter.acquire(VmPermitClass::Heavy))
+            .await
+            .unwrap();
+        let mut anonymous = pin!(with_vm_client(
+            VmClient::Anonymous,
+            limiter.acquire(VmPermitClass::Heavy)
+        ));
+        assert!(poll!(anonymous.as_mut()).is_pending());
+        drop(permit);
+        anonymous.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn permit_class_weights_are_configurable() {
+        let config = VmConcurrencyLimiterConfig {
+            interactive_weight: 1,
+            heavy_weight: 2,
+            ..VmConcurrencyLimiterConfig::default()
+        };
+        let (limiter, _) = VmConcurrencyLimiter::with_config(1, config);
+        let permit = limiter.acquire(VmPermitClass::Interactive).await.unwrap();
+
+        let mut interactive: Vec<_> = (0..2)
+            .map(|_| Box::pin(limiter.acquire(VmPermitClass::Interactive)))
+            .collect();
+        let mut heavy: Vec<_> = (0..3)
+            .map(|_| Box::pin(limiter.acquire(VmPermitClass::Heavy)))
+            .collect();
+        for future in interactive.iter_mut().chain(&mut heavy) {
+            assert!(poll!(future.as_mut()).is_pending());
+        }
+
+        // Each round must issue 1 permit to an interactive request, and then 2 permits to heavy requests.
+        drop(permit);
+        let permit = interactive[0].as_mut().await.unwrap();
+        assert!(poll!(heavy[0].as_mut()).is_pending());
+        drop(permit);
+        for future in heavy.iter_mut().take(2) {
+            let permit = future.await.unwrap();
+            assert!(poll!(interactive[1].as_mut()).is_pending());
+            drop(permit);
+        }
+        let permit = interactive[1].as_mut().await.unwrap();
+        assert!(poll!(heavy[2].as_mut()).is_pending());
+        drop(permit);
+        heavy.pop().unwrap().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn cancelled_waiters_do_not_leak_permits() {
+        let (limiter, barrier) = VmConcurrencyLimiter::new(1);
+        let permit = limiter.acquire(VmPermitClass::Interactive).await.unwrap();
+        {
+            let mut cancelled = pin!(limiter.acquire(VmPermitClass::Interactive));
+            assert!(poll!(cancelled.as_mut()).is_pending());
+        }
+        drop(permit);
+        let permit = limiter.acquire(VmPermitClass::Interactive).await.unwrap();
+
+        let mut waiting = pin!(limiter.acquire(VmPermitClass::Heavy));
+        assert!(poll!(waiting.as_mut()).is_pending());
+        barrier.close();
+        assert!(waiting.await.is_none());
+        assert!(limiter.acquire(VmPermitClass::Heavy).await.is_none());
+
+        drop(permit);
+        tokio::time::timeout(Duration::from_secs(1), barrier.wait_until_stopped())
+            .await
+            .unwrap();
+    }
+}
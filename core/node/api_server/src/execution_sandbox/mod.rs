@@ -11,11 +11,15 @@ use zksync_types::{
     api, fee_model::BatchFeeInput, L1BatchNumber, L2BlockNumber, ProtocolVersionId, U256,
 };
 use zksync_vm_executor::oneshot::{BlockInfo, ResolvedBlockInfo};
+use zksync_web3_decl::error::{PrunedData, PrunedDataKind};
 
-use self::vm_metrics::SandboxStage;
+pub(crate) use self::limiter::{with_vm_client, VmClient};
+pub use self::limiter::{
+    VmConcurrencyBarrier, VmConcurrencyLimiter, VmConcurrencyLimiterConfig, VmPermit, VmPermitClass,
+};
 pub(super) use self::{
     error::SandboxExecutionError,
-    execute::{SandboxAction, SandboxExecutor},
+    execute::{SandboxAction, SandboxExecutionOutput, SandboxExecutor},
     validate::ValidationError,
     vm_metrics::{SubmitTxStage, SANDBOX_METRICS},
 };
@@ -23,117 +27,13 @@ pub(super) use self::{
 // Note: keep the modules private, and instead re-export functions that make public interface.
 mod error;
 mod execute;
+mod limiter;
 mod storage;
 #[cfg(test)]
 mod tests;
 mod validate;
 mod vm_metrics;
 
-/// Permit to invoke VM code.
-///
-/// Any publicly-facing method that invokes VM is expected to accept a reference to this structure,
-/// as a proof that the caller obtained a token from `VmConcurrencyLimiter`,
-#[derive(Debug, Clone)]
-pub struct VmPermit {
-    _permit: Arc<tokio::sync::OwnedSemaphorePermit>,
-}
-
-/// Barrier-like synchronization primitive allowing to close a [`VmConcurrencyLimiter`] it's attached to
-/// so that it doesn't issue new permits, and to wait for all permits to drop.
-#[derive(Debug, Clone)]
-pub struct VmConcurrencyBarrier {
-    limiter: Arc<tokio::sync::Semaphore>,
-    max_concurrency: usize,
-}
-
-impl VmConcurrencyBarrier {
-    /// Shuts down the related VM concurrency limiter so that it won't issue new permits.
-    pub fn close(&self) {
-        self.limiter.close();
-        tracing::info!("VM concurrency limiter closed");
-    }
-
-    /// Waits until all permits issued by the VM concurrency limiter are dropped.
-    pub async fn wait_until_stopped(self) {
-        const POLL_INTERVAL: Duration = Duration::from_millis(50);
-
-        assert!(
-            self.limiter.is_closed(),
-            "Cannot wait on non-closed VM concurrency limiter"
-        );
-
-        loop {
-            let current_permits = self.limiter.available_permits();
-            tracing::debug!(
-                "Waiting until all VM permits are dropped; currently remaining: {} / {}",
-                self.max_concurrency - current_permits,
-                self.max_concurrency
-            );
-            if current_permits == self.max_concurrency {
-                return;
-            }
-            tokio::time::sleep(POLL_INTERVAL).await;
-        }
-    }
-}
-
-/// Synchronization primitive that limits the number of concurrent VM executions.
-/// This is required to prevent the server from being overloaded with the VM calls.
-///
-/// This structure is expected to be used in every method that executes VM code, on a topmost
-/// level (i.e. before any async calls are made or VM is instantiated),
-///
-/// Note that the actual limit on the number of VMs is a minimum of the limit in this structure,
-/// *and* the size of the blocking tokio threadpool. So, even if the limit is set to 1024, but
-/// tokio is configured to have no more than 512 blocking threads, the actual limit will be 512.
-#[derive(Debug)]
-pub struct VmConcurrencyLimiter {
-    /// Semaphore that limits the number of concurrent VM executions.
-    limiter: Arc<tokio::sync::Semaphore>,
-}
-
-impl VmConcurrencyLimiter {
-    /// Creates a limiter together with a barrier allowing to control its shutdown.
-    pub fn new(max_concurrency: usize) -> (Self, VmConcurrencyBarrier) {
-        tracing::info!(
-            "Initializing the VM concurrency limiter with max concurrency {max_concurrency}"
-        );
-        let limiter = Arc::new(tokio::sync::Semaphore::new(max_concurrency));
-
-        let this = Self {
-            limiter: Arc::clone(&limiter),
-        };
-        let barrier = VmConcurrencyBarrier {
-            limiter,
-            max_concurrency,
-        };
-        (this, barrier)
-    }
-
-    /// Waits until there is a free slot in the concurrency limiter.
-    /// Returns a permit that should be dropped when the VM execution is finished.
-    pub async fn acquire(&self) -> Option<VmPermit> {
-        let available_permits = self.limiter.available_permits();
-        SANDBOX_METRICS
-            .sandbox_execution_permits
-            .observe(available_permits);
-
-        let latency = SANDBOX_METRICS.sandbox[&SandboxStage::VmConcurrencyLimiterAcquire].start();
-        let permit = Arc::clone(&self.limiter).acquire_owned().await.ok()?;
-        let elapsed = latency.observe();
-        // We don't want to emit too many logs.
-        if elapsed > Duration::from_millis(10) {
-            tracing::debug!(
-                "Permit is obtained. Available permits: {available_permits}. Took {elapsed:?}"
-            );
-        }
-
-        Some(VmPermit {
-            _permit: Arc::new(permit),
-        })
-    }
-}
-
 #[derive(Debug, Clone, Copy)]
 struct BlockStartInfoInner {
     info: PruningInfo,
@@ -162,6 +62,7 @@ impl BlockStartInfoInner {
 pub struct BlockStartInfo {
     cached_pruning_info: Arc<RwLock<BlockStartInfoInner>>,
     max_cache_age: Duration,
+    archive_peer_available: bool,
 }
 
 impl BlockStartInfo {
@@ -176,9 +77,17 @@ impl BlockStartInfo {
                 cached_at: Instant::now(),
             })),
             max_cache_age,
+            archive_peer_available: false,
         })
     }
 
+    /// Sets whether pruned data can be requested from an archive peer of the node (e.g., the main node
+    /// for external nodes). This is reported to clients in pruned data errors.
+    pub fn with_archive_peer(mut self, available: bool) -> Self {
+        self.archive_peer_available = available;
+        self
+    }
+
     fn copy_inner(&self) -> BlockStartInfoInner {
         *self
             .cached_pruning_info
@@ -223,15 +132,20 @@ impl BlockStartInfo {
         }
     }
 
+    fn first_retained(info: &PruningInfo) -> (L2BlockNumber, L1BatchNumber) {
+        if let Some(pruned) = info.last_soft_pruned {
+            (pruned.l2_block + 1, pruned.l1_batch + 1)
+        } else {
+            (L2BlockNumber(0), L1BatchNumber(0))
+        }
+    }
+
     pub async fn first_l2_block(
         &self,
         storage: &mut Connection<'_, Core>,
     ) -> anyhow::Result<L2BlockNumber> {
         let cached_pruning_info = self.get_pruning_info(storage).await?;
-        if let Some(pruned) = cached_pruning_info.last_soft_pruned {
-            return Ok(pruned.l2_block + 1);
-        }
-        Ok(L2BlockNumber(0))
+        Ok(Self::first_retained(&cached_pruning_info).0)
     }
 
     pub async fn first_l1_batch(
@@ -239,43 +153,62 @@ impl BlockStartInfo {
         storage: &mut Connection<'_, Core>,
     ) -> anyhow::Result<L1BatchNumber> {
         let cached_pruning_info = self.get_pruning_info(storage).await?;
-        if let Some(pruned) = cached_pruning_info.last_soft_pruned {
-            return Ok(pruned.l1_batch + 1);
+        Ok(Self::first_retained(&cached_pruning_info).1)
+    }
+
+    /// Returns details about pruned data of the specified kind as of the latest known pruning info.
+    /// `snapshot_available` is always set to `false`; it's the caller's responsibility to set it if necessary.
+    pub async fn pruned_data(
+        &self,
+        kind: PrunedDataKind,
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<PrunedData> {
+        let cached_pruning_info = self.get_pruning_info(storage).await?;
+        Ok(self.pruned_data_from_info(kind, &cached_pruning_info))
+    }
+
+    fn pruned_data_from_info(&self, kind: PrunedDataKind, info: &PruningInfo) -> PrunedData {
+        let (first_retained_block, first_retained_l1_batch) = Self::first_retained(info);
+        PrunedData {
+            kind,
+            first_retained_block,
+            first_retained_l1_batch,
+            snapshot_available: false,
+            archive_peer_available: self.archive_peer_available,
         }
-        Ok(L1BatchNumber(0))
     }
 
     /// Checks whether a block with the specified ID is pruned and returns an error if it is.
-    /// The `Err` variant wraps the first non-pruned L2 block.
+    /// The `Err` variant wraps details about the pruned data, such as the first non-pruned L2 block.
     pub async fn ensure_not_pruned_block(
         &self,
         block: api::BlockId,
         storage: &mut Connection<'_, Core>,
     ) -> Result<(), BlockArgsError> {
-        let first_l2_block = self
-            .first_l2_block(storage)
+        let cached_pruning_info = self
+            .get_pruning_info(storage)
             .await
             .map_err(BlockArgsError::Database)?;
-        match block {
-            api::BlockId::Number(api::BlockNumber::Number(number))
-                if number < first_l2_block.0.into() =>
-            {
-                Err(BlockArgsError::Pruned(first_l2_block))
+        let (first_l2_block, _) = Self::first_retained(&cached_pruning_info);
+        let is_pruned = match block {
+            api::BlockId::Number(api::BlockNumber::Number(number)) => {
+                number < first_l2_block.0.into()
             }
-            api::BlockId::Number(api::BlockNumber::Earliest)
-                if first_l2_block > L2BlockNumber(0) =>
-            {
-                Err(BlockArgsError::Pruned(first_l2_block))
-            }
-            _ => Ok(()),
+            api::BlockId::Number(api::BlockNumber::Earliest) => first_l2_block > L2BlockNumber(0),
+            _ => false,
+        };
+        if is_pruned {
+            let pruned = self.pruned_data_from_info(PrunedDataKind::L2Block, &cached_pruning_info);
+            return Err(BlockArgsError::Pruned(pruned));
         }
+        Ok(())
     }
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum BlockArgsError {
-    #[error("Block is pruned; first retained block is {0}")]
-    Pruned(L2BlockNumber),
+    #[error("Block is pruned; first retained block is {}", _0.first_retained_block)]
+    Pruned(PrunedData),
     #[error("Block is missing, but can appear in the future")]
     Missing,
     #[error("Database error")]
@@ -347,7 +280,7 @@ impl BlockArgs {
         self.inner.block_number()
     }
 
-    fn is_pending(&self) -> bool {
+    pub fn is_pending(&self) -> bool {
         matches!(
             self.block_id,
             api::BlockId::Number(api::BlockNumber::Pending)
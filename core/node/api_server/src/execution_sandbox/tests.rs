@@ -175,12 +175,22 @@ async fn creating_block_args_after_snapshot_recovery() {
         Some(l2_block.timestamp)
     );
 
+    let start_info = start_info.with_archive_peer(true);
     for pruned_block in pruned_blocks {
         let pruned_block = api::BlockId::Number(pruned_block);
         let err = BlockArgs::new(&mut storage, pruned_block, &start_info)
             .await
             .unwrap_err();
-        assert_matches!(err, BlockArgsError::Pruned(_));
+        assert_matches!(
+            err,
+            BlockArgsError::Pruned(pruned) if pruned == PrunedData {
+                kind: PrunedDataKind::L2Block,
+                first_retained_block: snapshot_recovery.l2_block_number + 1,
+                first_retained_l1_batch: snapshot_recovery.l1_batch_number + 1,
+                snapshot_available: false,
+                archive_peer_available: true,
+            }
+        );
     }
     for missing_block in missing_blocks {
         let missing_block = api::BlockId::Number(missing_block);
@@ -235,7 +245,7 @@ async fn test_instantiating_vm(connection: Connection<'static, Core>, block_args
     let tx = Transaction::from(tx);
 
     let (limiter, _) = VmConcurrencyLimiter::new(1);
-    let vm_permit = limiter.acquire().await.unwrap();
+    let vm_permit = limiter.acquire(VmPermitClass::Interactive).await.unwrap();
     let action = SandboxAction::GasEstimation {
         fee_input,
         base_fee,
@@ -283,7 +293,7 @@ async fn validating_transaction(set_balance: bool) {
     );
 
     let (limiter, _) = VmConcurrencyLimiter::new(1);
-    let vm_permit = limiter.acquire().await.unwrap();
+    let vm_permit = limiter.acquire(VmPermitClass::Interactive).await.unwrap();
     let state_override = if set_balance {
         let account_override = OverrideAccount {
             balance: Some(U256::from(1) << 128),
@@ -9,6 +9,7 @@ use zksync_multivm::{
 };
 use zksync_types::{bytecode::BytecodeHash, H256};
 
+use super::VmPermitClass;
 use crate::utils::ReportFilter;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
@@ -86,6 +87,11 @@ pub(crate) struct SandboxMetrics {
     pub(super) sandbox: Family<SandboxStage, Histogram<Duration>>,
     #[metrics(buckets = Buckets::linear(0.0..=2_000.0, 200.0))]
     pub(super) sandbox_execution_permits: Histogram<usize>,
+    /// Time spent waiting for a VM permit, grouped by the request class.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub(super) vm_queue_wait_time: Family<VmPermitClass, Histogram<Duration>>,
+    /// Number of requests waiting for a VM permit, grouped by the request class.
+    pub(super) vm_queue_len: Family<VmPermitClass, Gauge<usize>>,
     #[metrics(buckets = Buckets::LATENCIES)]
     submit_tx: Family<SubmitTxStage, Histogram<Duration>>,
 
@@ -16,6 +16,14 @@ async fn check_health(
     (response_code, Json(response))
 }
 
+/// Creates the healthcheck router. The healthcheck server is unauthenticated and is often publicly reachable,
+/// so it must only expose read-only health information; node control endpoints belong to the admin API.
+fn router(app_health_check: Arc<AppHealthCheck>) -> Router {
+    Router::new()
+        .route("/health", get(check_health))
+        .with_state(app_health_check)
+}
+
 async fn run_server(
     bind_address: &SocketAddr,
     app_health_check: Arc<AppHealthCheck>,
@@ -26,9 +34,7 @@ async fn run_server(
     );
 
     app_health_check.expose_metrics();
-    let app = Router::new()
-        .route("/health", get(check_health))
-        .with_state(app_health_check);
+    let app = router(app_health_check);
     let listener = tokio::net::TcpListener::bind(bind_address)
         .await
         .unwrap_or_else(|err| panic!("Failed binding healthcheck server to {bind_address}: {err}"));
@@ -78,3 +84,32 @@ impl HealthCheckHandle {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, http::Request};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn healthcheck_server_does_not_expose_log_directives() {
+        let router = router(Arc::new(AppHealthCheck::default()));
+        let response = router
+            .clone()
+            .oneshot(Request::get("/health").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        for method in ["GET", "PUT"] {
+            let request = Request::builder()
+                .method(method)
+                .uri("/log_directives")
+                .body(Body::from("zksync=debug"))
+                .unwrap();
+            let response = router.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+    }
+}
@@ -16,7 +16,9 @@ use zksync_types::{
 };
 
 use super::{result::ApiCallResult, SubmitTxError, TxSender};
-use crate::execution_sandbox::{BlockArgs, SandboxAction, VmPermit, SANDBOX_METRICS};
+use crate::execution_sandbox::{
+    BlockArgs, SandboxAction, VmPermit, VmPermitClass, SANDBOX_METRICS,
+};
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum BinarySearchKind {
@@ -337,7 +339,11 @@ impl<'a> GasEstimator<'a> {
         }
 
         // Acquire the vm token for the whole duration of the binary search.
-        let vm_permit = sender.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = sender
+            .0
+            .vm_concurrency_limiter
+            .acquire(VmPermitClass::Interactive)
+            .await;
         let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
 
         Ok(Self {
@@ -19,18 +19,19 @@ use zksync_node_fee_model::{ApiFeeInputProvider, BatchFeeModelInputProvider};
 use zksync_state::PostgresStorageCaches;
 use zksync_state_keeper::{
     seal_criteria::{ConditionalSealer, NoopSealer, SealData},
+    tx_policy::TxPolicyEngine,
     SequencerSealer,
 };
 use zksync_types::{
-    api::state_override::StateOverride,
+    api::{state_override::StateOverride, KnownAccountState, TransactionConditions},
     fee_model::BatchFeeInput,
-    get_intrinsic_constants, h256_to_u256,
+    get_intrinsic_constants, h256_to_u256, helpers,
     l2::{error::TxCheckError::TxDuplication, L2Tx},
     transaction_request::CallOverrides,
     utils::storage_key_for_eth_balance,
     vm::FastVmMode,
-    AccountTreeId, Address, L2ChainId, Nonce, ProtocolVersionId, Transaction, H160, H256,
-    MAX_NEW_FACTORY_DEPS, U256,
+    AccountTreeId, Address, L2ChainId, Nonce, ProtocolVersionId, StorageKey, Transaction, H160,
+    H256, MAX_NEW_FACTORY_DEPS, U256,
 };
 use zksync_vm_executor::oneshot::{
     CallOrExecute, EstimateGas, MultiVmBaseSystemContracts, OneshotEnvParameters,
@@ -39,8 +40,8 @@ use zksync_vm_executor::oneshot::{
 pub(super) use self::{gas_estimation::BinarySearchKind, result::SubmitTxError};
 use self::{master_pool_sink::MasterPoolSink, result::ApiCallResult, tx_sink::TxSink};
 use crate::execution_sandbox::{
-    BlockArgs, SandboxAction, SandboxExecutor, SubmitTxStage, VmConcurrencyBarrier,
-    VmConcurrencyLimiter, SANDBOX_METRICS,
+    BlockArgs, SandboxAction, SandboxExecutionOutput, SandboxExecutor, SubmitTxStage,
+    VmConcurrencyBarrier, VmConcurrencyLimiter, VmPermitClass, SANDBOX_METRICS,
 };
 
 mod gas_estimation;
@@ -70,7 +71,8 @@ pub async fn build_tx_sender(
     .with_sealer(Arc::new(sequencer_sealer));
 
     let max_concurrency = web3_json_config.vm_concurrency_limit();
-    let (vm_concurrency_limiter, vm_barrier) = VmConcurrencyLimiter::new(max_concurrency);
+    let (vm_concurrency_limiter, vm_barrier) =
+        VmConcurrencyLimiter::with_config(max_concurrency, web3_json_config.into());
 
     let batch_fee_input_provider =
         ApiFeeInputProvider::new(batch_fee_model_input_provider, replica_pool);
@@ -159,6 +161,8 @@ pub struct TxSenderBuilder {
     sealer: Option<Arc<dyn ConditionalSealer>>,
     /// Cache for tokens that are white-listed for AA.
     whitelisted_tokens_for_aa_cache: Option<Arc<RwLock<Vec<Address>>>>,
+    /// Policy applied to submitted transactions.
+    tx_policy: Option<TxPolicyEngine>,
 }
 
 impl TxSenderBuilder {
@@ -173,6 +177,7 @@ impl TxSenderBuilder {
             tx_sink,
             sealer: None,
             whitelisted_tokens_for_aa_cache: None,
+            tx_policy: None,
         }
     }
 
@@ -186,6 +191,11 @@ impl TxSenderBuilder {
         self
     }
 
+    pub fn with_tx_policy(mut self, tx_policy: TxPolicyEngine) -> Self {
+        self.tx_policy = Some(tx_policy);
+        self
+    }
+
     pub fn build(
         self,
         batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
@@ -223,6 +233,7 @@ impl TxSenderBuilder {
             vm_concurrency_limiter,
             whitelisted_tokens_for_aa_cache,
             sealer,
+            tx_policy: self.tx_policy,
             executor,
         }))
     }
@@ -287,6 +298,8 @@ pub struct TxSenderInner {
     pub(super) whitelisted_tokens_for_aa_cache: Arc<RwLock<Vec<Address>>>,
     /// Batch sealer used to check whether transaction can be executed by the sequencer.
     pub(super) sealer: Arc<dyn ConditionalSealer>,
+    /// Policy applied to submitted transactions.
+    pub(super) tx_policy: Option<TxPolicyEngine>,
     pub(super) executor: SandboxExecutor,
 }
 
@@ -321,6 +334,114 @@ impl TxSender {
         &self,
         tx: L2Tx,
         block_args: BlockArgs,
+    ) -> Result<(L2TxSubmissionResult, VmExecutionResultAndLogs), SubmitTxError> {
+        self.submit_tx_inner(tx, None, block_args).await
+    }
+
+    /// Submits a transaction with preconditions (see `eth_sendRawTransactionConditional`).
+    ///
+    /// All conditions are checked against the pending state before the transaction is validated. Additionally,
+    /// they are persisted together with the transaction and re-checked by the state keeper against the L2 block
+    /// including the transaction.
+    #[tracing::instrument(level = "debug", skip_all, fields(tx.hash = ?tx.hash()))]
+    pub async fn submit_conditional_tx(
+        &self,
+        tx: L2Tx,
+        conditions: &TransactionConditions,
+        block_args: BlockArgs,
+    ) -> Result<(L2TxSubmissionResult, VmExecutionResultAndLogs), SubmitTxError> {
+        self.check_tx_conditions(conditions, &block_args).await?;
+        self.submit_tx_inner(tx, Some(conditions), block_args).await
+    }
+
+    /// Converts storage conditions into expected values of storage slots.
+    fn expected_storage_values(
+        conditions: &TransactionConditions,
+    ) -> Result<Vec<(StorageKey, H256)>, SubmitTxError> {
+        let mut expected_values = vec![];
+        for (&address, state) in &conditions.known_accounts {
+            let KnownAccountState::Slots(slots) = state else {
+                return Err(SubmitTxError::UnsupportedConditions(format!(
+                    "storage root conditions are not supported (account {address:?})"
+                )));
+            };
+            for (&slot, &value) in slots {
+                let key = StorageKey::new(AccountTreeId::new(address), slot);
+                expected_values.push((key, value));
+            }
+        }
+        Ok(expected_values)
+    }
+
+    /// Fails fast if the conditions are not met for the pending state.
+    async fn check_tx_conditions(
+        &self,
+        conditions: &TransactionConditions,
+        block_args: &BlockArgs,
+    ) -> Result<(), SubmitTxError> {
+        let block_number = u64::from(block_args.resolved_block_number().0);
+        if conditions
+            .block_number_min
+            .is_some_and(|min| block_number < min.as_u64())
+            || conditions
+                .block_number_max
+                .is_some_and(|max| block_number > max.as_u64())
+        {
+            return Err(SubmitTxError::ConditionsNotMet(format!(
+                "pending block number {block_number} is outside of the allowed range"
+            )));
+        }
+
+        let timestamp = helpers::unix_timestamp_ms() / 1_000;
+        if conditions
+            .timestamp_min
+            .is_some_and(|min| timestamp < min.as_u64())
+            || conditions
+                .timestamp_max
+                .is_some_and(|max| timestamp > max.as_u64())
+        {
+            return Err(SubmitTxError::ConditionsNotMet(format!(
+                "current timestamp {timestamp} is outside of the allowed range"
+            )));
+        }
+
+        let expected_values = Self::expected_storage_values(conditions)?;
+        if expected_values.is_empty() {
+            return Ok(());
+        }
+        let hashed_keys: Vec<_> = expected_values
+            .iter()
+            .map(|(key, _)| key.hashed_key())
+            .collect();
+        let mut connection = self.acquire_replica_connection().await?;
+        let actual_values = connection
+            .storage_web3_dal()
+            .get_values(&hashed_keys)
+            .await
+            .context("failed getting storage values")?;
+        drop(connection);
+
+        for (key, expected_value) in expected_values {
+            let actual_value = actual_values
+                .get(&key.hashed_key())
+                .copied()
+                .unwrap_or_default();
+            if actual_value != expected_value {
+                return Err(SubmitTxError::ConditionsNotMet(format!(
+                    "storage slot {:?} of account {:?} has unexpected value",
+                    key.key(),
+                    key.address()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    async fn submit_tx_inner(
+        &self,
+        tx: L2Tx,
+        conditions: Option<&TransactionConditions>,
+        block_args: BlockArgs,
     ) -> Result<(L2TxSubmissionResult, VmExecutionResultAndLogs), SubmitTxError> {
         let tx_hash = tx.hash();
         let stage_latency = SANDBOX_METRICS.start_tx_submit_stage(tx_hash, SubmitTxStage::Validate);
@@ -337,7 +458,11 @@ impl TxSender {
             .await
             .context("cannot get batch fee input")?;
 
-        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = self
+            .0
+            .vm_concurrency_limiter
+            .acquire(VmPermitClass::Interactive)
+            .await;
         let action = SandboxAction::Execution {
             fee_input,
             tx: tx.clone(),
@@ -382,12 +507,36 @@ impl TxSender {
             SANDBOX_METRICS.start_tx_submit_stage(tx_hash, SubmitTxStage::DbInsert);
         self.ensure_tx_executable(&tx.clone().into(), &execution_output.metrics, true)?;
 
-        let validation_traces = validation_result?;
-        let submission_res_handle = self
-            .0
-            .tx_sink
-            .submit_tx(&tx, execution_output.metrics, validation_traces)
-            .await?;
+        let mut validation_traces = validation_result?;
+        let submission_res_handle = if let Some(conditions) = conditions {
+            // Conditions are persisted together with the transaction, so that they are re-checked
+            // by the state keeper against the L2 block including the transaction.
+            if conditions.timestamp_min.is_some() || conditions.timestamp_max.is_some() {
+                let start = conditions.timestamp_min.map_or(0, |min| min.as_u64());
+                let end = conditions
+                    .timestamp_max
+                    .map_or(u64::MAX, |max| max.as_u64().saturating_add(1));
+                validation_traces.apply_timestamp_asserter_range(start..end);
+            }
+            if conditions.block_number_min.is_some() || conditions.block_number_max.is_some() {
+                let start = conditions.block_number_min.map_or(0, |min| min.as_u64());
+                let end = conditions
+                    .block_number_max
+                    .map_or(u64::MAX, |max| max.as_u64().saturating_add(1));
+                validation_traces.l2_block_number_range = Some(start..end);
+            }
+            validation_traces.expected_storage_values = Self::expected_storage_values(conditions)?;
+
+            self.0
+                .tx_sink
+                .submit_conditional_tx(&tx, conditions, execution_output.metrics, validation_traces)
+                .await?
+        } else {
+            self.0
+                .tx_sink
+                .submit_tx(&tx, execution_output.metrics, validation_traces)
+                .await?
+        };
 
         match submission_res_handle {
             L2TxSubmissionResult::AlreadyExecuted => {
@@ -425,6 +574,12 @@ impl TxSender {
         tx: &L2Tx,
         protocol_version: ProtocolVersionId,
     ) -> Result<(), SubmitTxError> {
+        if let Some(tx_policy) = &self.0.tx_policy {
+            tx_policy
+                .check_l2_tx(tx)
+                .map_err(|violation| SubmitTxError::DeniedByPolicy(violation.to_string()))?;
+        }
+
         // This check is intended to ensure that the gas-related values will be safe to convert to u64 in the future computations.
         let max_gas = U256::from(u64::MAX);
         if tx.common_data.fee.gas_limit > max_gas
@@ -605,7 +760,34 @@ impl TxSender {
         call: L2Tx,
         state_override: Option<StateOverride>,
     ) -> Result<Vec<u8>, SubmitTxError> {
-        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let (output, _) = self
+            .execute_call(
+                block_args,
+                call_overrides,
+                call,
+                state_override,
+                OneshotTracingParams::default(),
+                VmPermitClass::Interactive,
+            )
+            .await?;
+        output.vm.into_api_call_result()
+    }
+
+    /// Executes a call in the sandbox with the specified tracers without checking its result. Returns the execution output
+    /// together with the fee input used for execution.
+    ///
+    /// `permit_class` should be [`VmPermitClass::Heavy`] for calls executed with expensive tracing, so that they
+    /// cannot starve latency-sensitive requests.
+    pub(crate) async fn execute_call(
+        &self,
+        block_args: BlockArgs,
+        call_overrides: CallOverrides,
+        call: L2Tx,
+        state_override: Option<StateOverride>,
+        tracing_params: OneshotTracingParams,
+        permit_class: VmPermitClass,
+    ) -> Result<(SandboxExecutionOutput, BatchFeeInput), SubmitTxError> {
+        let vm_permit = self.0.vm_concurrency_limiter.acquire(permit_class).await;
         let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
 
         let mut connection;
@@ -627,14 +809,14 @@ impl TxSender {
             call,
             fee_input,
             enforced_base_fee: call_overrides.enforced_base_fee,
-            tracing_params: OneshotTracingParams::default(),
+            tracing_params,
         };
         let result = self
             .0
             .executor
             .execute_in_sandbox(vm_permit, connection, action, &block_args, state_override)
             .await?;
-        result.vm.into_api_call_result()
+        Ok((result, fee_input))
     }
 
     pub async fn gas_price(&self) -> anyhow::Result<u64> {
@@ -221,16 +221,47 @@ impl TxProxy {
         }
     }
 
-    async fn submit_tx_impl(&self, tx: &L2Tx) -> EnrichedClientResult<H256> {
+    async fn submit_tx_impl(
+        &self,
+        tx: &L2Tx,
+        conditions: Option<&api::TransactionConditions>,
+    ) -> EnrichedClientResult<H256> {
         let input_data = tx.common_data.input_data().expect("raw tx is absent");
         let raw_tx = zksync_types::web3::Bytes(input_data.to_vec());
         let tx_hash = tx.hash();
         tracing::info!("Proxying tx {tx_hash:?}");
-        self.client
-            .send_raw_transaction(raw_tx)
-            .rpc_context("send_raw_transaction")
-            .with_arg("tx_hash", &tx_hash)
-            .await
+        if let Some(conditions) = conditions {
+            // Conditions must be enforced by the main node, so they are forwarded with the transaction.
+            self.client
+                .send_raw_transaction_conditional(raw_tx, conditions.clone())
+                .rpc_context("send_raw_transaction_conditional")
+                .with_arg("tx_hash", &tx_hash)
+                .await
+        } else {
+            self.client
+                .send_raw_transaction(raw_tx)
+                .rpc_context("send_raw_transaction")
+                .with_arg("tx_hash", &tx_hash)
+                .await
+        }
+    }
+
+    async fn submit_and_cache_tx(
+        &self,
+        tx: &L2Tx,
+        conditions: Option<&api::TransactionConditions>,
+    ) -> Result<L2TxSubmissionResult, SubmitTxError> {
+        // We're running an external node: we have to proxy the transaction to the main node.
+        // But before we do that, save the tx to cache in case someone will request it
+        // Before it reaches the main node.
+        self.tx_cache.push(tx.clone()).await;
+        if let Err(err) = self.submit_tx_impl(tx, conditions).await {
+            // Remove the transaction from the cache on failure so that it doesn't occupy space in the cache indefinitely.
+            self.tx_cache.remove(tx.hash()).await;
+            return Err(err.into());
+        }
+        APP_METRICS.processed_txs[&TxStage::Proxied].inc();
+        Ok(L2TxSubmissionResult::Proxied)
     }
 
     async fn find_tx(
@@ -311,17 +342,17 @@ impl TxSink for TxProxy {
         _execution_metrics: TransactionExecutionMetrics,
         _validation_traces: ValidationTraces,
     ) -> Result<L2TxSubmissionResult, SubmitTxError> {
-        // We're running an external node: we have to proxy the transaction to the main node.
-        // But before we do that, save the tx to cache in case someone will request it
-        // Before it reaches the main node.
-        self.tx_cache.push(tx.clone()).await;
-        if let Err(err) = self.submit_tx_impl(tx).await {
-            // Remove the transaction from the cache on failure so that it doesn't occupy space in the cache indefinitely.
-            self.tx_cache.remove(tx.hash()).await;
-            return Err(err.into());
-        }
-        APP_METRICS.processed_txs[&TxStage::Proxied].inc();
-        Ok(L2TxSubmissionResult::Proxied)
+        self.submit_and_cache_tx(tx, None).await
+    }
+
+    async fn submit_conditional_tx(
+        &self,
+        tx: &L2Tx,
+        conditions: &api::TransactionConditions,
+        _execution_metrics: TransactionExecutionMetrics,
+        _validation_traces: ValidationTraces,
+    ) -> Result<L2TxSubmissionResult, SubmitTxError> {
+        self.submit_and_cache_tx(tx, Some(conditions)).await
     }
 
     async fn lookup_pending_nonce(
@@ -69,6 +69,12 @@ pub enum SubmitTxError {
     Internal(#[from] anyhow::Error),
     #[error("transaction failed block.timestamp assertion")]
     FailedBlockTimestampAssertion,
+    #[error("transaction conditions not met: {0}")]
+    ConditionsNotMet(String),
+    #[error("unsupported transaction conditions: {0}")]
+    UnsupportedConditions(String),
+    #[error("transaction denied by policy: {0}")]
+    DeniedByPolicy(String),
 }
 
 impl SubmitTxError {
@@ -99,6 +105,9 @@ impl SubmitTxError {
             Self::ProxyError(_) => "proxy-error",
             Self::Internal(_) => "internal",
             Self::FailedBlockTimestampAssertion => "failed-block-timestamp-assertion",
+            Self::ConditionsNotMet(_) => "conditions-not-met",
+            Self::UnsupportedConditions(_) => "unsupported-conditions",
+            Self::DeniedByPolicy(_) => "denied-by-policy",
         }
     }
 
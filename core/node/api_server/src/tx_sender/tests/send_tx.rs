@@ -344,6 +344,7 @@ async fn submit_tx_with_validation_traces(actual_range: Range<u64>, expected_ran
         assert_eq!(tx.hash(), tx_hash);
         ValidationTraces {
             timestamp_asserter_range: Some(actual_range.clone()),
+            ..ValidationTraces::default()
         }
     });
 
@@ -1,7 +1,7 @@
 use zksync_dal::{transactions_dal::L2TxSubmissionResult, Connection, Core};
 use zksync_multivm::interface::{tracer::ValidationTraces, TransactionExecutionMetrics};
 use zksync_types::{
-    api::{Transaction, TransactionDetails, TransactionId},
+    api::{Transaction, TransactionConditions, TransactionDetails, TransactionId},
     l2::L2Tx,
     Address, Nonce, H256,
 };
@@ -31,6 +31,19 @@ pub trait TxSink: std::fmt::Debug + Send + Sync + 'static {
         validation_traces: ValidationTraces,
     ) -> Result<L2TxSubmissionResult, SubmitTxError>;
 
+    /// Ensures that a transaction submitted via `eth_sendRawTransactionConditional` is propagated to the mempool.
+    /// By default, delegates to [`Self::submit_tx()`]; the conditions are persisted as a part of `validation_traces`.
+    async fn submit_conditional_tx(
+        &self,
+        tx: &L2Tx,
+        _conditions: &TransactionConditions,
+        execution_metrics: TransactionExecutionMetrics,
+        validation_traces: ValidationTraces,
+    ) -> Result<L2TxSubmissionResult, SubmitTxError> {
+        self.submit_tx(tx, execution_metrics, validation_traces)
+            .await
+    }
+
     /// Attempts to look up the pending nonce for the account in the sink-specific storage.
     /// By default, returns `Ok(None)`.
     async fn lookup_pending_nonce(
@@ -18,7 +18,7 @@ use governor::{
 use once_cell::sync::OnceCell;
 use pin_project_lite::pin_project;
 use rand::{rngs::SmallRng, RngCore, SeedableRng};
-use tokio::sync::watch;
+use tokio::{sync::watch, task::futures::TaskLocalFuture};
 use tracing::instrument::{Instrument, Instrumented};
 use vise::{
     Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, GaugeGuard, Histogram, Metrics,
@@ -30,7 +30,13 @@ use zksync_web3_decl::jsonrpsee::{
 };
 
 use super::metadata::{MethodCall, MethodTracer};
-use crate::web3::metrics::{ObservedRpcParams, API_METRICS};
+use crate::{
+    execution_sandbox::{with_vm_client, VmClient},
+    web3::{
+        metrics::{ObservedRpcParams, API_METRICS},
+        rate_limiter::{ApiRateLimiter, ClientInfo},
+    },
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "transport", rename_all = "snake_case")]
@@ -88,23 +94,154 @@ where
             // Note: if required, we can extract data on rate limiting from the error.
             if rate_limiter.check_n(num_requests).is_err() {
                 METRICS.rate_limited[&self.transport].inc();
-
-                let rp = MethodResponse::error(
-                    request.id,
-                    ErrorObject::borrowed(
-                        ErrorCode::ServerError(http::StatusCode::TOO_MANY_REQUESTS.as_u16().into())
-                            .code(),
-                        "Too many requests",
-                        None,
-                    ),
-                );
-                return ResponseFuture::ready(rp);
+                return ResponseFuture::ready(too_many_requests_response(request));
             }
         }
         ResponseFuture::future(self.inner.call(request))
     }
 }
 
+fn too_many_requests_response(request: Request<'_>) -> MethodResponse {
+    MethodResponse::error(
+        request.id,
+        ErrorObject::borrowed(
+            ErrorCode::ServerError(http::StatusCode::TOO_MANY_REQUESTS.as_u16().into()).code(),
+            "Too many requests",
+            None,
+        ),
+    )
+}
+
+/// HTTP-level middleware that extracts [`ClientInfo`] from request headers and stores it in request extensions,
+/// so that it's available to [`RateLimitMiddleware`] and [`VmClientMiddleware`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ClientInfoLayer {
+    trusted_proxy_hops: usize,
+}
+
+impl ClientInfoLayer {
+    pub(crate) fn new(trusted_proxy_hops: usize) -> Self {
+        Self { trusted_proxy_hops }
+    }
+}
+
+impl<S> tower::Layer<S> for ClientInfoLayer {
+    type Service = ClientInfoService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ClientInfoService {
+            inner,
+            trusted_proxy_hops: self.trusted_proxy_hops,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ClientInfoService<S> {
+    inner: S,
+    trusted_proxy_hops: usize,
+}
+
+impl<S, B> tower::Service<http::Request<B>> for ClientInfoService<S>
+where
+    S: tower::Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: http::Request<B>) -> Self::Future {
+        let client_info = ClientInfo::from_headers(request.headers(), self.trusted_proxy_hops);
+        request.extensions_mut().insert(client_info);
+        self.inner.call(request)
+    }
+}
+
+/// Middleware enforcing per-client rate limits defined by [`ApiRateLimiter`]. Unlike [`LimitMiddleware`],
+/// limits are shared among all sessions and servers using the same limiter.
+pub(crate) struct RateLimitMiddleware<S> {
+    inner: S,
+    rate_limiter: ApiRateLimiter,
+}
+
+impl<S> RateLimitMiddleware<S> {
+    pub(crate) fn new(inner: S, rate_limiter: ApiRateLimiter) -> Self {
+        Self {
+            inner,
+            rate_limiter,
+        }
+    }
+}
+
+impl<'a, S> RpcServiceT<'a> for RateLimitMiddleware<S>
+where
+    S: Send + Sync + RpcServiceT<'a>,
+{
+    type Future = ResponseFuture<S::Future>;
+
+    fn call(&self, request: Request<'a>) -> Self::Future {
+        let client_info = request.extensions().get::<ClientInfo>();
+        if !self.rate_limiter.check(client_info, request.method_name()) {
+            return ResponseFuture::ready(too_many_requests_response(request));
+        }
+        ResponseFuture::future(self.inner.call(request))
+    }
+}
+
+/// RPC-level middleware attributing VM permits acquired by the method handler to the client,
+/// so that per-client VM concurrency limits can be enforced.
+///
+/// API keys are only trusted if they are known to the rate limiter; otherwise, the client is identified
+/// by its IP address (if available), or is considered anonymous.
+#[derive(Debug)]
+pub(crate) struct VmClientMiddleware<S> {
+    inner: S,
+    rate_limiter: Option<ApiRateLimiter>,
+}
+
+impl<S> VmClientMiddleware<S> {
+    pub(crate) fn new(inner: S, rate_limiter: Option<ApiRateLimiter>) -> Self {
+        Self {
+            inner,
+            rate_limiter,
+        }
+    }
+
+    fn vm_client(&self, client_info: Option<&ClientInfo>) -> VmClient {
+        let Some(client_info) = client_info else {
+            return VmClient::Anonymous;
+        };
+        let known_api_key = client_info.api_key().filter(|&key| {
+            self.rate_limiter
+                .as_ref()
+                .is_some_and(|limiter| limiter.is_known_api_key(key))
+        });
+        if let Some(key) = known_api_key {
+            VmClient::ApiKey(key.into())
+        } else if let Some(ip) = client_info.ip() {
+            VmClient::Ip(ip)
+        } else {
+            VmClient::Anonymous
+        }
+    }
+}
+
+impl<'a, S> RpcServiceT<'a> for VmClientMiddleware<S>
+where
+    S: Send + Sync + RpcServiceT<'a>,
+{
+    type Future = TaskLocalFuture<VmClient, S::Future>;
+
+    fn call(&self, request: Request<'a>) -> Self::Future {
+        let client = self.vm_client(request.extensions().get::<ClientInfo>());
+        with_vm_client(client, self.inner.call(request))
+    }
+}
+
 /// RPC-level middleware that adds [`MethodCall`] metadata to method logic. Method handlers can then access this metadata
 /// using [`MethodTracer`], which is a part of `RpcState`. When the handler completes or is dropped, the results are reported
 /// as metrics.
@@ -258,12 +395,23 @@ where
     }
 }
 
-/// Tracks the timestamp of the last call to the RPC. Used during server shutdown to start dropping new traffic
-/// only after this is coordinated by the external load balancer.
-#[derive(Debug, Clone, Default)]
+/// Tracks the timestamp of the last call to the RPC and the number of in-flight calls. Used during server shutdown
+/// to start dropping new traffic only after this is coordinated by the external load balancer, and to drain
+/// in-flight calls afterwards.
+#[derive(Debug, Clone)]
 pub(crate) struct TrafficTracker {
     // We use `OnceCell` to not track requests before the server starts shutting down.
     last_call_sender: Arc<OnceCell<watch::Sender<Instant>>>,
+    in_flight_calls: Arc<watch::Sender<usize>>,
+}
+
+impl Default for TrafficTracker {
+    fn default() -> Self {
+        Self {
+            last_call_sender: Arc::default(),
+            in_flight_calls: Arc::new(watch::channel(0).0),
+        }
+    }
 }
 
 impl TrafficTracker {
@@ -273,6 +421,25 @@ impl TrafficTracker {
         }
     }
 
+    fn start_call(&self) -> InFlightCallGuard {
+        self.in_flight_calls.send_modify(|count| *count += 1);
+        InFlightCallGuard {
+            in_flight_calls: self.in_flight_calls.clone(),
+        }
+    }
+
+    /// Returns the current number of in-flight calls.
+    pub fn in_flight_calls(&self) -> usize {
+        *self.in_flight_calls.borrow()
+    }
+
+    /// Waits until there are no in-flight calls.
+    pub async fn wait_for_in_flight_calls(&self) {
+        let mut in_flight_calls = self.in_flight_calls.subscribe();
+        // `unwrap()` is safe: the sender is held by `self`, so it cannot be dropped.
+        in_flight_calls.wait_for(|&count| count == 0).await.unwrap();
+    }
+
     /// Waits until no new requests are received during the specified interval.
     pub async fn wait_for_no_requests(self, interval_without_requests: Duration) {
         let mut last_call_subscriber = self
@@ -303,6 +470,36 @@ impl TrafficTracker {
     }
 }
 
+/// Decrements the number of in-flight calls in [`TrafficTracker`] on drop.
+#[derive(Debug)]
+pub(crate) struct InFlightCallGuard {
+    in_flight_calls: Arc<watch::Sender<usize>>,
+}
+
+impl Drop for InFlightCallGuard {
+    fn drop(&mut self) {
+        self.in_flight_calls.send_modify(|count| *count -= 1);
+    }
+}
+
+pin_project! {
+    /// Call future tracked by [`TrafficTracker`].
+    #[derive(Debug)]
+    pub(crate) struct WithInFlightGuard<F> {
+        #[pin]
+        inner: F,
+        guard: InFlightCallGuard,
+    }
+}
+
+impl<F: Future> Future for WithInFlightGuard<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().inner.poll(cx)
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct ShutdownMiddleware<S> {
     inner: S,
@@ -322,11 +519,14 @@ impl<'a, S> RpcServiceT<'a> for ShutdownMiddleware<S>
 where
     S: Send + Sync + RpcServiceT<'a>,
 {
-    type Future = S::Future;
+    type Future = WithInFlightGuard<S::Future>;
 
     fn call(&self, request: Request<'a>) -> Self::Future {
         self.traffic_tracker.reset();
-        self.inner.call(request)
+        WithInFlightGuard {
+            guard: self.traffic_tracker.start_call(),
+            inner: self.inner.call(request),
+        }
     }
 }
 
@@ -412,4 +612,22 @@ mod tests {
         let elapsed = now.elapsed();
         assert!(elapsed >= Duration::from_millis(15), "{elapsed:?}");
     }
+
+    #[tokio::test]
+    async fn tracking_in_flight_calls() {
+        let traffic_tracker = TrafficTracker::default();
+        traffic_tracker.wait_for_in_flight_calls().await;
+
+        let guards: Vec<_> = (0..3).map(|_| traffic_tracker.start_call()).collect();
+        assert_eq!(traffic_tracker.in_flight_calls(), 3);
+
+        let wait = traffic_tracker.wait_for_in_flight_calls();
+        tokio::pin!(wait);
+        for guard in guards {
+            assert!(futures::poll!(wait.as_mut()).is_pending());
+            drop(guard);
+        }
+        wait.await;
+        assert_eq!(traffic_tracker.in_flight_calls(), 0);
+    }
 }
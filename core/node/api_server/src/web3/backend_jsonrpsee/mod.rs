@@ -10,7 +10,8 @@ use zksync_web3_decl::{
 pub(crate) use self::{
     metadata::{MethodMetadata, MethodTracer},
     middleware::{
-        CorrelationMiddleware, LimitMiddleware, MetadataLayer, ShutdownMiddleware, TrafficTracker,
+        ClientInfoLayer, CorrelationMiddleware, LimitMiddleware, MetadataLayer,
+        RateLimitMiddleware, ShutdownMiddleware, TrafficTracker, VmClientMiddleware,
     },
 };
 use crate::tx_sender::SubmitTxError;
@@ -26,19 +27,23 @@ impl MethodTracer {
         self.observe_error(&err);
 
         let data = match &err {
-            Web3Error::SubmitTransactionError(_, data) => Some(format!("0x{}", hex::encode(data))),
-            Web3Error::ProxyError(_) => Some("0x".to_owned()),
+            Web3Error::SubmitTransactionError(_, data) => Some(serde_json::Value::String(format!(
+                "0x{}",
+                hex::encode(data)
+            ))),
+            Web3Error::ProxyError(_) => Some(serde_json::Value::String("0x".to_owned())),
+            Web3Error::Pruned(pruned) => serde_json::to_value(pruned).ok(),
             _ => None,
         };
         let code = match err {
             Web3Error::MethodNotImplemented => ErrorCode::MethodNotFound.code(),
             Web3Error::InternalError(_) => ErrorCode::InternalError.code(),
             Web3Error::NoBlock
-            | Web3Error::PrunedBlock(_)
-            | Web3Error::PrunedL1Batch(_)
+            | Web3Error::Pruned(_)
             | Web3Error::TooManyTopics
             | Web3Error::FilterNotFound
             | Web3Error::InvalidFilterBlockHash
+            | Web3Error::UnsupportedTracer(_)
             | Web3Error::LogsLimitExceeded(_, _, _) => ErrorCode::InvalidParams.code(),
             Web3Error::SubmitTransactionError(_, _)
             | Web3Error::SerializationError(_)
@@ -1,5 +1,9 @@
 use zksync_types::{
-    api::{BlockId, BlockNumber, CallTracerBlockResult, CallTracerResult, TracerConfig},
+    api::{
+        execution_trace::{ExecutionTrace, ExecutionTraceConfig},
+        BlockId, BlockNumber, CallTracerBlockResult, CallTracerResult, TraceCallConfig,
+        TracerConfig,
+    },
     transaction_request::CallRequest,
     H256,
 };
@@ -36,13 +40,24 @@ impl DebugNamespaceServer for DebugNamespace {
         &self,
         request: CallRequest,
         block: Option<BlockId>,
-        options: Option<TracerConfig>,
+        options: Option<TraceCallConfig>,
     ) -> RpcResult<CallTracerResult> {
         self.debug_trace_call_impl(request, block, options)
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn trace_call_execution(
+        &self,
+        request: CallRequest,
+        block: Option<BlockId>,
+        options: Option<ExecutionTraceConfig>,
+    ) -> RpcResult<ExecutionTrace> {
+        self.debug_trace_call_execution_impl(request, block, options)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn trace_transaction(
         &self,
         tx_hash: H256,
@@ -52,4 +67,14 @@ impl DebugNamespaceServer for DebugNamespace {
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
+
+    async fn trace_transaction_execution(
+        &self,
+        tx_hash: H256,
+        options: Option<ExecutionTraceConfig>,
+    ) -> RpcResult<Option<ExecutionTrace>> {
+        self.debug_trace_transaction_execution_impl(tx_hash, options)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
 }
@@ -1,7 +1,8 @@
 use zksync_types::{
     api::{
         state_override::StateOverride, Block, BlockId, BlockIdVariant, BlockNumber, FeeHistory,
-        Log, Transaction, TransactionId, TransactionReceipt, TransactionVariant,
+        Log, Transaction, TransactionConditions, TransactionId, TransactionReceipt,
+        TransactionVariant,
     },
     transaction_request::CallRequest,
     web3::{Bytes, Index, SyncState, U64Number},
@@ -223,6 +224,16 @@ impl EthNamespaceServer for EthNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn send_raw_transaction_conditional(
+        &self,
+        tx_bytes: Bytes,
+        conditions: TransactionConditions,
+    ) -> RpcResult<H256> {
+        self.send_raw_transaction_conditional_impl(tx_bytes, conditions)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn syncing(&self) -> RpcResult<SyncState> {
         Ok(self.syncing_impl())
     }
@@ -1,11 +1,18 @@
 use zksync_types::{
-    api::{ChainAggProof, TeeProof, TransactionExecutionInfo},
+    api::{
+        gas_profile::GasProfile, state_override::StateOverride, BaseTokenRatioAuditEntry,
+        ChainAggProof, ContractStorageUsage, L1TxProcessingStatus, LogsCursor, LogsPage,
+        PipelineCongestion, PriorityOpInfo, StagedBaseSystemContract, TeeProof,
+        TransactionExecutionInfo, TransactionSimulation,
+    },
     tee_types::TeeType,
-    L1BatchNumber, L2ChainId, H256,
+    transaction_request::CallRequest,
+    L1BatchNumber, L2ChainId, PriorityOpId, ProtocolVersionId, H256,
 };
 use zksync_web3_decl::{
     jsonrpsee::core::{async_trait, RpcResult},
     namespaces::UnstableNamespaceServer,
+    types::Filter,
 };
 
 use crate::web3::namespaces::UnstableNamespace;
@@ -40,4 +47,90 @@ impl UnstableNamespaceServer for UnstableNamespace {
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
+
+    async fn get_logs_paginated(
+        &self,
+        filter: Filter,
+        cursor: Option<LogsCursor>,
+        page_size: Option<usize>,
+    ) -> RpcResult<LogsPage> {
+        self.get_logs_paginated_impl(filter, cursor, page_size)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn simulate_transaction(
+        &self,
+        req: CallRequest,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<TransactionSimulation> {
+        self.simulate_transaction_impl(req, state_override)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn gas_profile(
+        &self,
+        req: CallRequest,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<GasProfile> {
+        self.gas_profile_impl(req, state_override)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_priority_queue(
+        &self,
+        from_id: Option<PriorityOpId>,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<PriorityOpInfo>> {
+        self.get_priority_queue_impl(from_id, limit)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_l1_tx_processing_status(
+        &self,
+        tx_hash: H256,
+    ) -> RpcResult<Option<L1TxProcessingStatus>> {
+        self.get_l1_tx_processing_status_impl(tx_hash)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_pipeline_congestion(&self) -> RpcResult<Option<PipelineCongestion>> {
+        self.get_pipeline_congestion_impl()
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_staged_base_system_contracts(
+        &self,
+        protocol_version: Option<ProtocolVersionId>,
+    ) -> RpcResult<Vec<StagedBaseSystemContract>> {
+        self.get_staged_base_system_contracts_impl(protocol_version)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_base_token_ratio_audit(
+        &self,
+        from_timestamp: Option<u64>,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<BaseTokenRatioAuditEntry>> {
+        self.get_base_token_ratio_audit_impl(from_timestamp, limit)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_top_pubdata_consumers(
+        &self,
+        from_batch: L1BatchNumber,
+        to_batch: L1BatchNumber,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<ContractStorageUsage>> {
+        self.get_top_pubdata_consumers_impl(from_batch, to_batch, limit)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
 }
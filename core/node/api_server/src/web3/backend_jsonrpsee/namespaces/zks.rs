@@ -3,9 +3,9 @@ use std::collections::HashMap;
 use zksync_multivm::interface::VmEvent;
 use zksync_types::{
     api::{
-        state_override::StateOverride, ApiStorageLog, BlockDetails, BridgeAddresses,
-        L1BatchDetails, L2ToL1LogProof, Log, Proof, ProtocolVersion, TransactionDetailedResult,
-        TransactionDetails,
+        state_override::StateOverride, ApiStorageLog, BatchStateDiff, BlockDetails, BlockNumber,
+        BridgeAddresses, BridgeTransfersPage, L1BatchDetails, L2ToL1LogProof, Log, Proof,
+        ProtocolVersion, TransactionDetailedResult, TransactionDetails, ZksFeeHistory,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -148,6 +148,15 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_batch_state_diff(
+        &self,
+        batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<BatchStateDiff>> {
+        self.get_batch_state_diff_impl(batch_number)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_bytecode_by_hash(&self, hash: H256) -> RpcResult<Option<Vec<u8>>> {
         self.get_bytecode_by_hash_impl(hash)
             .await
@@ -172,6 +181,16 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_fee_history(
+        &self,
+        block_count: U64,
+        newest_block: BlockNumber,
+    ) -> RpcResult<ZksFeeHistory> {
+        self.get_fee_history_impl(block_count.as_u64(), newest_block)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_protocol_version(
         &self,
         version_id: Option<u16>,
@@ -227,6 +246,17 @@ impl ZksNamespaceServer for ZksNamespace {
             })
             .map_err(|err| self.current_method().map_err(err))
     }
+
+    async fn get_bridge_transfers(
+        &self,
+        account: Address,
+        cursor: Option<U64>,
+        page_size: Option<usize>,
+    ) -> RpcResult<BridgeTransfersPage> {
+        self.get_bridge_transfers_impl(account, cursor, page_size)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
 }
 
 fn map_event(vm_event: &VmEvent) -> Log {
@@ -0,0 +1,232 @@
+//! Cache for results of `eth_call` and `eth_estimateGas` requests.
+
+use std::{
+    collections::HashSet,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use lru::LruCache;
+use serde::Serialize;
+use vise::EncodeLabelValue;
+use zksync_types::{web3::keccak256, L2BlockNumber, H256, U256};
+
+use super::metrics::{CallResultCacheOutcome, CALL_RESULT_CACHE_METRICS};
+
+/// RPC method which results can be cached by [`CallResultCache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+pub enum CachedCallMethod {
+    #[metrics(name = "eth_call")]
+    Call,
+    #[metrics(name = "eth_estimateGas")]
+    EstimateGas,
+}
+
+impl CachedCallMethod {
+    const ALL: [Self; 2] = [Self::Call, Self::EstimateGas];
+
+    fn rpc_name(self) -> &'static str {
+        match self {
+            Self::Call => "eth_call",
+            Self::EstimateGas => "eth_estimateGas",
+        }
+    }
+}
+
+/// Key of a cached call result. Besides the call parameters, it includes the block the call was executed on,
+/// so that the cached results are naturally invalidated once a new L2 block is sealed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct CallCacheKey {
+    method: CachedCallMethod,
+    block_number: L2BlockNumber,
+    /// Pending block args resolve to the next L2 block number, but use the state of the latest sealed block.
+    /// Thus, they must not be mixed with the results for a sealed block with the same number.
+    is_pending: bool,
+    /// Gas price used for the call (only relevant for gas estimation).
+    gas_price: u64,
+    params_hash: H256,
+}
+
+impl CallCacheKey {
+    pub fn new(
+        method: CachedCallMethod,
+        block_number: L2BlockNumber,
+        is_pending: bool,
+        gas_price: u64,
+        params: &impl Serialize,
+    ) -> Self {
+        let params = serde_json::to_vec(params).expect("failed serializing call params");
+        Self {
+            method,
+            block_number,
+            is_pending,
+            gas_price,
+            params_hash: H256(keccak256(&params)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum CachedCallResult {
+    Call(Vec<u8>),
+    EstimateGas(U256),
+}
+
+/// Bounded LRU cache for results of `eth_call` and `eth_estimateGas` requests allowing to serve identical requests
+/// without re-running the VM.
+///
+/// Only successful results of calls without state overrides are cached. Results are keyed by the call parameters
+/// and the resolved block, so they are effectively invalidated once the state changes (i.e., a new L2 block is sealed).
+/// Block parameters that can change without a new L2 block being sealed (e.g., the timestamp and fee input
+/// of the pending block) are considered stable for the purposes of caching.
+///
+/// The cache is not persisted and is not invalidated on block reverts; the node is expected to restart after a revert.
+#[derive(Debug, Clone)]
+pub struct CallResultCache {
+    inner: Arc<Mutex<LruCache<CallCacheKey, CachedCallResult>>>,
+    enabled_methods: HashSet<CachedCallMethod>,
+}
+
+impl CallResultCache {
+    /// Creates a cache with the specified capacity (number of entries). Returns `None` if the capacity is zero,
+    /// or if all cacheable methods are excluded.
+    pub fn new(capacity: usize, excluded_methods: &[String]) -> anyhow::Result<Option<Self>> {
+        for method in excluded_methods {
+            anyhow::ensure!(
+                CachedCallMethod::ALL
+                    .iter()
+                    .any(|cached| cached.rpc_name() == method),
+                "method `{method}` cannot be excluded from call result caching; supported methods are {:?}",
+                CachedCallMethod::ALL.map(CachedCallMethod::rpc_name)
+            );
+        }
+
+        let Some(capacity) = NonZeroUsize::new(capacity) else {
+            return Ok(None);
+        };
+        let enabled_methods: HashSet<_> = CachedCallMethod::ALL
+            .into_iter()
+            .filter(|method| {
+                !excluded_methods
+                    .iter()
+                    .any(|name| name == method.rpc_name())
+            })
+            .collect();
+        if enabled_methods.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            inner: Arc::new(Mutex::new(LruCache::new(capacity))),
+            enabled_methods,
+        }))
+    }
+
+    pub(crate) fn is_enabled(&self, method: CachedCallMethod) -> bool {
+        self.enabled_methods.contains(&method)
+    }
+
+    fn get(&self, key: &CallCacheKey) -> Option<CachedCallResult> {
+        if !self.is_enabled(key.method) {
+            return None;
+        }
+        let result = self.inner.lock().unwrap().get(key).cloned();
+        let outcome = if result.is_some() {
+            CallResultCacheOutcome::Hit
+        } else {
+            CallResultCacheOutcome::Miss
+        };
+        CALL_RESULT_CACHE_METRICS.requests[&(key.method, outcome)].inc();
+        result
+    }
+
+    fn insert(&self, key: CallCacheKey, result: CachedCallResult) {
+        if !self.is_enabled(key.method) {
+            return;
+        }
+        let mut inner = self.inner.lock().unwrap();
+        inner.put(key, result);
+        CALL_RESULT_CACHE_METRICS.len.set(inner.len());
+    }
+
+    pub(crate) fn get_call(&self, key: &CallCacheKey) -> Option<Vec<u8>> {
+        match self.get(key)? {
+            CachedCallResult::Call(output) => Some(output),
+            CachedCallResult::EstimateGas(_) => None,
+        }
+    }
+
+    pub(crate) fn insert_call(&self, key: CallCacheKey, output: Vec<u8>) {
+        self.insert(key, CachedCallResult::Call(output));
+    }
+
+    pub(crate) fn get_gas_estimate(&self, key: &CallCacheKey) -> Option<U256> {
+        match self.get(key)? {
+            CachedCallResult::EstimateGas(gas) => Some(gas),
+            CachedCallResult::Call(_) => None,
+        }
+    }
+
+    pub(crate) fn insert_gas_estimate(&self, key: CallCacheKey, gas: U256) {
+        self.insert(key, CachedCallResult::EstimateGas(gas));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call_key(block_number: u32, is_pending: bool, data: &str) -> CallCacheKey {
+        CallCacheKey::new(
+            CachedCallMethod::Call,
+            L2BlockNumber(block_number),
+            is_pending,
+            0,
+            &data,
+        )
+    }
+
+    #[test]
+    fn caching_call_results() {
+        let cache = CallResultCache::new(2, &[]).unwrap().unwrap();
+        let key = call_key(1, false, "0x01");
+        assert_eq!(cache.get_call(&key), None);
+        cache.insert_call(key.clone(), vec![1, 2, 3]);
+        assert_eq!(cache.get_call(&key), Some(vec![1, 2, 3]));
+
+        // Different block or params should not hit the cache.
+        assert_eq!(cache.get_call(&call_key(2, false, "0x01")), None);
+        assert_eq!(cache.get_call(&call_key(1, true, "0x01")), None);
+        assert_eq!(cache.get_call(&call_key(1, false, "0x02")), None);
+
+        // Check that the least recently used entry is evicted.
+        cache.insert_call(call_key(2, false, "0x01"), vec![4]);
+        cache.insert_call(call_key(3, false, "0x01"), vec![5]);
+        assert_eq!(cache.get_call(&key), None);
+        assert_eq!(cache.get_call(&call_key(3, false, "0x01")), Some(vec![5]));
+    }
+
+    #[test]
+    fn excluding_methods() {
+        let cache = CallResultCache::new(10, &["eth_estimateGas".to_owned()])
+            .unwrap()
+            .unwrap();
+        assert!(cache.is_enabled(CachedCallMethod::Call));
+        assert!(!cache.is_enabled(CachedCallMethod::EstimateGas));
+
+        let key = CallCacheKey::new(
+            CachedCallMethod::EstimateGas,
+            L2BlockNumber(1),
+            true,
+            100,
+            &"0x01",
+        );
+        cache.insert_gas_estimate(key.clone(), 21_000.into());
+        assert_eq!(cache.get_gas_estimate(&key), None);
+
+        let all_methods = ["eth_call".to_owned(), "eth_estimateGas".to_owned()];
+        assert!(CallResultCache::new(10, &all_methods).unwrap().is_none());
+        assert!(CallResultCache::new(0, &[]).unwrap().is_none());
+        CallResultCache::new(10, &["eth_getBalance".to_owned()]).unwrap_err();
+    }
+}
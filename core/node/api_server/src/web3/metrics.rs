@@ -10,8 +10,8 @@ use zksync_types::api;
 use zksync_web3_decl::error::Web3Error;
 
 use super::{
-    backend_jsonrpsee::MethodMetadata, ApiTransport, InternalApiConfig, OptionalApiParams,
-    TypedFilter,
+    backend_jsonrpsee::MethodMetadata, call_result_cache::CachedCallMethod, ApiTransport,
+    InternalApiConfig, OptionalApiParams, TypedFilter,
 };
 use crate::utils::ReportFilter;
 
@@ -170,6 +170,7 @@ enum Web3ErrorKind {
     FilterNotFound,
     LogsLimitExceeded,
     InvalidFilterBlockHash,
+    UnsupportedTracer,
     TreeApiUnavailable,
     Internal,
 }
@@ -178,7 +179,7 @@ impl Web3ErrorKind {
     fn new(err: &Web3Error) -> Self {
         match err {
             Web3Error::NoBlock => Self::NoBlock,
-            Web3Error::PrunedBlock(_) | Web3Error::PrunedL1Batch(_) => Self::Pruned,
+            Web3Error::Pruned(_) => Self::Pruned,
             Web3Error::SubmitTransactionError(..) => Self::SubmitTransaction,
             Web3Error::ProxyError(_) => Self::Proxy,
             Web3Error::SerializationError(_) => Self::TransactionSerialization,
@@ -186,6 +187,7 @@ impl Web3ErrorKind {
             Web3Error::FilterNotFound => Self::FilterNotFound,
             Web3Error::LogsLimitExceeded(..) => Self::LogsLimitExceeded,
             Web3Error::InvalidFilterBlockHash => Self::InvalidFilterBlockHash,
+            Web3Error::UnsupportedTracer(_) => Self::UnsupportedTracer,
             Web3Error::TreeApiUnavailable => Self::TreeApiUnavailable,
             Web3Error::InternalError(_) | Web3Error::MethodNotImplemented => Self::Internal,
         }
@@ -220,6 +222,9 @@ struct Web3ConfigLabels {
     fee_history_limit: u64,
     filters_limit: Option<usize>,
     subscriptions_limit: Option<usize>,
+    max_subscriptions_per_connection: Option<u32>,
+    subscription_buffer_capacity: Option<usize>,
+    max_buffered_notifications_per_connection: Option<usize>,
     #[metrics(unit = Unit::Bytes)]
     batch_request_size_limit: Option<usize>,
     #[metrics(unit = Unit::Bytes)]
@@ -285,6 +290,13 @@ impl ApiMetrics {
             fee_history_limit: config.fee_history_limit,
             filters_limit: optional.filters_limit,
             subscriptions_limit: optional.subscriptions_limit,
+            max_subscriptions_per_connection: optional
+                .max_subscriptions_per_connection
+                .map(Into::into),
+            subscription_buffer_capacity: optional.subscription_buffer_capacity.map(Into::into),
+            max_buffered_notifications_per_connection: optional
+                .max_buffered_notifications_per_connection
+                .map(Into::into),
             batch_request_size_limit: optional.batch_request_size_limit,
             response_body_size_limit: optional
                 .response_body_size_limit
@@ -421,6 +433,7 @@ pub enum SubscriptionType {
     Blocks,
     Txs,
     Logs,
+    L1BatchEvents,
 }
 
 #[derive(Debug, Metrics)]
@@ -429,7 +442,7 @@ pub(super) struct PubSubMetrics {
     /// Latency to load new events from Postgres before broadcasting them to subscribers.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub db_poll_latency: Family<SubscriptionType, Histogram<Duration>>,
-    /// Latency to send an atomic batch of events to a single subscriber.
+    /// Time spent by a notification in the subscription buffer before it's sent to the subscriber.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub notify_subscribers_latency: Family<SubscriptionType, Histogram<Duration>>,
     /// Total number of events sent to all subscribers of a certain type.
@@ -445,8 +458,15 @@ pub(super) struct PubSubMetrics {
     /// Number of skipped broadcast messages.
     #[metrics(buckets = Buckets::exponential(1.0..=128.0, 2.0))]
     pub skipped_broadcast_messages: Family<SubscriptionType, Histogram<u64>>,
-    /// Number of subscribers dropped because of a send timeout.
-    pub subscriber_send_timeouts: Family<SubscriptionType, Counter>,
+    /// Number of notifications dropped for slow subscribers, either individually or when closing the subscription.
+    pub dropped_notifications: Family<SubscriptionType, Counter>,
+    /// Number of subscriptions closed because they didn't keep up with notifications.
+    pub closed_slow_subscribers: Family<SubscriptionType, Counter>,
+    /// Number of historical logs sent to subscribers replaying logs from a certain L2 block.
+    pub replayed_logs: Counter,
+    /// Time for a subscriber replaying logs to catch up with the latest sealed L2 block.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub logs_replay_latency: Histogram<Duration>,
 }
 
 #[vise::register]
@@ -504,6 +524,78 @@ pub(super) struct MempoolCacheMetrics {
 #[vise::register]
 pub(super) static MEMPOOL_CACHE_METRICS: vise::Global<MempoolCacheMetrics> = vise::Global::new();
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(super) enum CallResultCacheOutcome {
+    Hit,
+    Miss,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "api_web3_call_result_cache")]
+pub(super) struct CallResultCacheMetrics {
+    /// Number of cache lookups grouped by the RPC method and outcome.
+    #[metrics(labels = ["method", "outcome"])]
+    pub requests: LabeledFamily<(CachedCallMethod, CallResultCacheOutcome), Counter, 2>,
+    /// Current number of entries in the cache.
+    pub len: Gauge<usize>,
+}
+
+#[vise::register]
+pub(super) static CALL_RESULT_CACHE_METRICS: vise::Global<CallResultCacheMetrics> =
+    vise::Global::new();
+
+/// Cost of an RPC method used by the API rate limiter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(super) enum MethodCost {
+    Cheap,
+    Expensive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(super) enum RateLimitedBy {
+    Ip,
+    ApiKey,
+    /// Shared quota for clients without an identifiable IP address.
+    Anonymous,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(super) struct RateLimitLabels {
+    pub limited_by: RateLimitedBy,
+    pub cost: MethodCost,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "api_web3_rate_limit")]
+pub(super) struct RateLimitMetrics {
+    /// Number of requests rejected by the rate limiter.
+    pub rejected: Family<RateLimitLabels, Counter>,
+    /// Number of IP addresses tracked by the rate limiter.
+    pub tracked_ips: Gauge<usize>,
+}
+
+#[vise::register]
+pub(super) static RATE_LIMIT_METRICS: vise::Global<RateLimitMetrics> = vise::Global::new();
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "api_replicas")]
+pub(super) struct ReplicaMetrics {
+    /// Lag of a replica behind the most up-to-date replica, measured in L2 blocks.
+    #[metrics(labels = ["replica"])]
+    pub lag: LabeledFamily<String, Gauge<u64>>,
+    /// Whether a replica is considered healthy (1) or not (0).
+    #[metrics(labels = ["replica"])]
+    pub healthy: LabeledFamily<String, Gauge<u64>>,
+    /// Number of connections acquired from a replica other than the preferred one.
+    pub fallbacks: Counter,
+}
+
+#[vise::register]
+pub(super) static REPLICA_METRICS: vise::Global<ReplicaMetrics> = vise::Global::new();
+
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
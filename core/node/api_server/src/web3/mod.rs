@@ -1,4 +1,10 @@
-use std::{collections::HashSet, net::SocketAddr, num::NonZeroU32, sync::Arc, time::Duration};
+use std::{
+    collections::HashSet,
+    net::SocketAddr,
+    num::{NonZeroU32, NonZeroUsize},
+    sync::Arc,
+    time::Duration,
+};
 
 use anyhow::Context as _;
 use chrono::NaiveDateTime;
@@ -8,8 +14,14 @@ use tokio::{
     sync::{mpsc, oneshot, watch, Mutex},
     task::JoinHandle,
 };
-use tower_http::{cors::CorsLayer, metrics::InFlightRequestsLayer};
-use zksync_config::configs::api::{MaxResponseSize, MaxResponseSizeOverrides};
+use tower_http::{
+    compression::CompressionLayer,
+    cors::{AllowOrigin, CorsLayer},
+    metrics::InFlightRequestsLayer,
+};
+use zksync_config::configs::api::{
+    MaxResponseSize, MaxResponseSizeOverrides, SlowSubscriberPolicy,
+};
 use zksync_dal::{helpers::wait_for_l1_batch, ConnectionPool, Core};
 use zksync_health_check::{HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_metadata_calculator::api_server::TreeApiClient;
@@ -25,16 +37,17 @@ use zksync_web3_decl::{
     namespaces::{
         DebugNamespaceServer, EnNamespaceServer, EthNamespaceServer, EthPubSubServer,
         NetNamespaceServer, SnapshotsNamespaceServer, UnstableNamespaceServer, Web3NamespaceServer,
-        ZksNamespaceServer,
+        ZksNamespaceServer, ZksPubSubServer,
     },
     types::Filter,
 };
 
 use self::{
     backend_jsonrpsee::{
-        CorrelationMiddleware, LimitMiddleware, MetadataLayer, MethodTracer, ShutdownMiddleware,
-        TrafficTracker,
+        ClientInfoLayer, CorrelationMiddleware, LimitMiddleware, MetadataLayer, MethodTracer,
+        RateLimitMiddleware, ShutdownMiddleware, TrafficTracker, VmClientMiddleware,
     },
+    call_result_cache::CallResultCache,
     mempool_cache::MempoolCache,
     metrics::API_METRICS,
     namespaces::{
@@ -42,6 +55,8 @@ use self::{
         UnstableNamespace, Web3Namespace, ZksNamespace,
     },
     pubsub::{EthSubscribe, EthSubscriptionIdProvider, PubSubEvent},
+    rate_limiter::ApiRateLimiter,
+    replicas::ReplicaPools,
     state::{Filters, InternalApiConfig, RpcState, SealedL2BlockNumber},
 };
 use crate::{
@@ -51,10 +66,13 @@ use crate::{
 };
 
 pub mod backend_jsonrpsee;
+pub mod call_result_cache;
 pub mod mempool_cache;
 pub(super) mod metrics;
 pub mod namespaces;
 mod pubsub;
+pub mod rate_limiter;
+pub mod replicas;
 pub mod state;
 pub mod testonly;
 #[cfg(test)]
@@ -114,6 +132,20 @@ impl Namespace {
     ];
 }
 
+/// Compression algorithms that can be used for HTTP responses. Compression is applied only if the client
+/// accepts the corresponding encoding.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ResponseCompression {
+    pub gzip: bool,
+    pub brotli: bool,
+}
+
+impl ResponseCompression {
+    fn is_enabled(self) -> bool {
+        self.gzip || self.brotli
+    }
+}
+
 /// Handles to the initialized API server.
 #[derive(Debug)]
 pub struct ApiServerHandles {
@@ -130,12 +162,23 @@ struct OptionalApiParams {
     sync_state: Option<SyncState>,
     filters_limit: Option<usize>,
     subscriptions_limit: Option<usize>,
+    max_subscriptions_per_connection: Option<NonZeroU32>,
+    subscription_buffer_capacity: Option<NonZeroUsize>,
+    max_buffered_notifications_per_connection: Option<NonZeroUsize>,
+    slow_subscriber_policy: SlowSubscriberPolicy,
     batch_request_size_limit: Option<usize>,
+    request_body_size_limit: Option<usize>,
     response_body_size_limit: Option<MaxResponseSize>,
+    cors_allowed_origins: Vec<String>,
+    response_compression: ResponseCompression,
     websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    rate_limiter: Option<ApiRateLimiter>,
+    replica_pools: Option<ReplicaPools>,
     tree_api: Option<Arc<dyn TreeApiClient>>,
     mempool_cache: Option<MempoolCache>,
+    call_result_cache: Option<CallResultCache>,
     extended_tracing: bool,
+    archive_peer_available: bool,
     pub_sub_events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
 
@@ -225,16 +268,60 @@ impl ApiBuilder {
         self
     }
 
+    /// Limits the number of active subscriptions per WebSocket connection. Has no effect on the HTTP server.
+    pub fn with_max_subscriptions_per_connection(mut self, limit: NonZeroU32) -> Self {
+        self.optional.max_subscriptions_per_connection = Some(limit);
+        self
+    }
+
+    /// Sets the capacity of notification buffers for individual subscriptions, and the policy applied to subscribers
+    /// with a full buffer (i.e., ones consuming notifications slower than they are produced). Has no effect
+    /// on the HTTP server.
+    pub fn with_subscription_buffer(
+        mut self,
+        capacity: NonZeroUsize,
+        slow_subscriber_policy: SlowSubscriberPolicy,
+    ) -> Self {
+        self.optional.subscription_buffer_capacity = Some(capacity);
+        self.optional.slow_subscriber_policy = slow_subscriber_policy;
+        self
+    }
+
+    /// Limits the total number of notifications buffered for all subscriptions of a single WebSocket connection.
+    /// Has no effect on the HTTP server.
+    pub fn with_max_buffered_notifications_per_connection(mut self, limit: NonZeroUsize) -> Self {
+        self.optional.max_buffered_notifications_per_connection = Some(limit);
+        self
+    }
+
     pub fn with_batch_request_size_limit(mut self, batch_request_size_limit: usize) -> Self {
         self.optional.batch_request_size_limit = Some(batch_request_size_limit);
         self
     }
 
+    pub fn with_request_body_size_limit(mut self, request_body_size_limit: usize) -> Self {
+        self.optional.request_body_size_limit = Some(request_body_size_limit);
+        self
+    }
+
     pub fn with_response_body_size_limit(mut self, max_response_size: MaxResponseSize) -> Self {
         self.optional.response_body_size_limit = Some(max_response_size);
         self
     }
 
+    /// Restricts origins allowed to make cross-origin requests to the HTTP server. If not called or called
+    /// with an empty list, requests from any origin are allowed. Has no effect on the WS server.
+    pub fn with_cors_allowed_origins(mut self, origins: Vec<String>) -> Self {
+        self.optional.cors_allowed_origins = origins;
+        self
+    }
+
+    /// Enables compression of HTTP server responses. Has no effect on the WS server.
+    pub fn with_response_compression(mut self, compression: ResponseCompression) -> Self {
+        self.optional.response_compression = compression;
+        self
+    }
+
     pub fn with_websocket_requests_per_minute_limit(
         mut self,
         websocket_requests_per_minute_limit: NonZeroU32,
@@ -244,6 +331,18 @@ impl ApiBuilder {
         self
     }
 
+    pub fn with_rate_limiter(mut self, rate_limiter: ApiRateLimiter) -> Self {
+        self.optional.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Routes read queries of the server to the specified replica pools instead of the pool
+    /// provided in the constructor.
+    pub fn with_replica_pools(mut self, replica_pools: ReplicaPools) -> Self {
+        self.optional.replica_pools = Some(replica_pools);
+        self
+    }
+
     pub fn with_sync_state(mut self, sync_state: SyncState) -> Self {
         self.optional.sync_state = Some(sync_state);
         self
@@ -259,6 +358,13 @@ impl ApiBuilder {
         self
     }
 
+    /// Specifies whether pruned data can be requested from an archive peer of the node (e.g., the main node
+    /// for external nodes). This is reported to clients when they request pruned data.
+    pub fn with_archive_peer(mut self, available: bool) -> Self {
+        self.optional.archive_peer_available = available;
+        self
+    }
+
     pub fn enable_api_namespaces(mut self, namespaces: Vec<Namespace>) -> Self {
         self.namespaces = Some(namespaces);
         self
@@ -275,6 +381,11 @@ impl ApiBuilder {
         self
     }
 
+    pub fn with_call_result_cache(mut self, cache: CallResultCache) -> Self {
+        self.optional.call_result_cache = Some(cache);
+        self
+    }
+
     pub fn with_extended_tracing(mut self, extended_tracing: bool) -> Self {
         self.optional.extended_tracing = extended_tracing;
         self
@@ -353,8 +464,9 @@ impl ApiServer {
 
     async fn build_rpc_state(self) -> anyhow::Result<RpcState> {
         let mut storage = self.pool.connection_tagged("api").await?;
-        let start_info =
-            BlockStartInfo::new(&mut storage, self.pruning_info_refresh_interval).await?;
+        let start_info = BlockStartInfo::new(&mut storage, self.pruning_info_refresh_interval)
+            .await?
+            .with_archive_peer(self.optional.archive_peer_available);
         drop(storage);
 
         // Disable filter API for HTTP endpoints, WS endpoints are unaffected by the `filters_disabled` flag
@@ -371,11 +483,13 @@ impl ApiServer {
             current_method: self.method_tracer,
             installed_filters,
             connection_pool: self.pool,
+            replica_pools: self.optional.replica_pools,
             tx_sender: self.tx_sender,
             sync_state: self.optional.sync_state,
             api_config: self.config,
             start_info,
             mempool_cache: self.optional.mempool_cache,
+            call_result_cache: self.optional.call_result_cache,
             last_sealed_l2_block: self.sealed_l2_block_handle,
             bridge_addresses_handle: self.bridge_addresses_handle,
             tree_api: self.optional.tree_api,
@@ -393,8 +507,10 @@ impl ApiServer {
         // Collect all the methods into a single RPC module.
         let mut rpc = RpcModule::new(());
         if let Some(pub_sub) = pub_sub {
-            rpc.merge(pub_sub.into_rpc())
+            rpc.merge(EthPubSubServer::into_rpc(pub_sub.clone()))
                 .context("cannot merge eth pubsub namespace")?;
+            rpc.merge(ZksPubSubServer::into_rpc(pub_sub))
+                .context("cannot merge zks pubsub namespace")?;
         }
 
         if namespaces.contains(&Namespace::Debug) {
@@ -492,16 +608,19 @@ impl ApiServer {
         let pub_sub = if matches!(transport, ApiTransport::WebSocket(_))
             && self.namespaces.contains(&Namespace::Pubsub)
         {
-            let mut pub_sub = EthSubscribe::new();
+            let mut pub_sub = EthSubscribe::new(self.pool.clone());
+            pub_sub.set_logs_replay_limit(self.config.req_entities_limit);
+            if let Some(capacity) = self.optional.subscription_buffer_capacity {
+                pub_sub.set_buffer_limits(capacity, self.optional.slow_subscriber_policy);
+            }
+            if let Some(limit) = self.optional.max_buffered_notifications_per_connection {
+                pub_sub.set_connection_buffer_limit(limit);
+            }
             if let Some(sender) = &self.optional.pub_sub_events_sender {
                 pub_sub.set_events_sender(sender.clone());
             }
 
-            tasks.extend(pub_sub.spawn_notifiers(
-                self.pool.clone(),
-                self.polling_interval,
-                stop_receiver.clone(),
-            ));
+            tasks.extend(pub_sub.spawn_notifiers(self.polling_interval, stop_receiver.clone()));
             Some(pub_sub)
         } else {
             None
@@ -619,6 +738,10 @@ impl ApiServer {
             .map_or(BatchRequestConfig::Unlimited, |limit| {
                 BatchRequestConfig::Limit(limit as u32)
             });
+        let request_body_size_limit = self
+            .optional
+            .request_body_size_limit
+            .map(|limit| limit.try_into().unwrap_or(u32::MAX));
         let (response_body_size_limit, max_response_size_overrides) =
             if let Some(limit) = &self.optional.response_body_size_limit {
                 (limit.global as u32, limit.overrides.clone())
@@ -626,7 +749,11 @@ impl ApiServer {
                 (u32::MAX, MaxResponseSizeOverrides::empty())
             };
         let websocket_requests_per_minute_limit = self.optional.websocket_requests_per_minute_limit;
+        let rate_limiter = self.optional.rate_limiter.clone();
+        let cors_allowed_origins = self.optional.cors_allowed_origins.clone();
+        let response_compression = self.optional.response_compression;
         let subscriptions_limit = self.optional.subscriptions_limit;
+        let max_subscriptions_per_connection = self.optional.max_subscriptions_per_connection;
         let vm_barrier = self.optional.vm_barrier.clone();
         let health_updater = self.health_updater.clone();
         let method_tracer = self.method_tracer.clone();
@@ -645,14 +772,44 @@ impl ApiServer {
         let rpc = Self::override_method_response_sizes(rpc, &max_response_size_overrides)?;
 
         // Setup CORS.
+        let allowed_origins = if cors_allowed_origins.is_empty() {
+            AllowOrigin::any()
+        } else {
+            let origins = cors_allowed_origins
+                .iter()
+                .map(|origin| {
+                    origin
+                        .parse::<http::HeaderValue>()
+                        .with_context(|| format!("invalid CORS origin `{origin}`"))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            AllowOrigin::list(origins)
+        };
         let cors = is_http.then(|| {
             CorsLayer::new()
                 // Allow `POST` when accessing the resource
                 .allow_methods([http::Method::POST])
-                // Allow requests from any origin
-                .allow_origin(tower_http::cors::Any)
+                .allow_origin(allowed_origins)
                 .allow_headers([http::header::CONTENT_TYPE])
         });
+        // Setup response compression. The layer is always added so that the middleware type doesn't depend
+        // on the configuration; with all algorithms disabled, it passes responses through unchanged.
+        let compression = if !response_compression.is_enabled() {
+            ResponseCompression::default()
+        } else if is_http {
+            tracing::info!("Enabled response compression for {transport_str} API server: {response_compression:?}");
+            response_compression
+        } else {
+            tracing::warn!(
+                "Response compression is not supported for {transport_str} API server; ignoring"
+            );
+            ResponseCompression::default()
+        };
+        let compression_layer = CompressionLayer::new()
+            .gzip(compression.gzip)
+            .br(compression.brotli)
+            // zstd compression is not exposed in configs, but would be enabled by default via feature unification.
+            .zstd(false);
         // Setup metrics for the number of in-flight requests.
         let (in_flight_requests, counter) = InFlightRequestsLayer::pair();
         tokio::spawn(
@@ -661,10 +818,16 @@ impl ApiServer {
                 future::ready(())
             }),
         );
-        // Assemble server middleware.
+        // Assemble server middleware. Client IP addresses are only used for rate limiting, so proxy headers
+        // are not trusted if rate limiting is disabled.
+        let trusted_proxy_hops = rate_limiter
+            .as_ref()
+            .map_or(0, ApiRateLimiter::trusted_proxy_hops);
         let middleware = tower::ServiceBuilder::new()
             .layer(in_flight_requests)
-            .option_layer(cors);
+            .option_layer(cors)
+            .layer(compression_layer)
+            .layer(ClientInfoLayer::new(trusted_proxy_hops));
 
         // Settings shared by HTTP and WS servers.
         let max_connections = !is_http
@@ -680,6 +843,7 @@ impl ApiServer {
         };
         let traffic_tracker = TrafficTracker::default();
         let traffic_tracker_for_middleware = traffic_tracker.clone();
+        let drain_tracker = traffic_tracker.clone();
 
         // **Important.** The ordering of layers matters! Layers added first will receive the request earlier
         // (i.e., are outermost in the call chain).
@@ -692,19 +856,29 @@ impl ApiServer {
                 extended_tracing.then(|| tower::layer::layer_fn(CorrelationMiddleware::new)),
             )
             .layer(metadata_layer)
-            // We want to capture limit middleware errors with `metadata_layer`; hence, `LimitMiddleware` is placed after it.
+            // We want to capture limit middleware errors with `metadata_layer`; hence, `LimitMiddleware` and `RateLimitMiddleware`
+            // are placed after it.
             .option_layer((!is_http).then(|| {
                 tower::layer::layer_fn(move |svc| {
                     LimitMiddleware::new(svc, websocket_requests_per_minute_limit)
                 })
-            }));
+            }))
+            .option_layer(rate_limiter.clone().map(|rate_limiter| {
+                tower::layer::layer_fn(move |svc| {
+                    RateLimitMiddleware::new(svc, rate_limiter.clone())
+                })
+            }))
+            .layer_fn(move |svc| VmClientMiddleware::new(svc, rate_limiter.clone()));
 
-        let server_builder = ServerBuilder::default()
+        let mut server_builder = ServerBuilder::default()
             .max_connections(max_connections as u32)
             .set_http_middleware(middleware)
             .max_response_body_size(response_body_size_limit)
             .set_batch_request_config(batch_request_config)
             .set_rpc_middleware(rpc_middleware);
+        if let Some(limit) = request_body_size_limit {
+            server_builder = server_builder.max_request_body_size(limit);
+        }
 
         let (local_addr, server_handle) = if is_http {
             // HTTP-specific settings
@@ -716,6 +890,9 @@ impl ApiServer {
             (server.local_addr(), server.start(rpc))
         } else {
             // WS-specific settings
+            if let Some(limit) = max_subscriptions_per_connection {
+                server_builder = server_builder.max_subscriptions_per_connection(limit.get());
+            }
             let server = server_builder
                 .set_id_provider(EthSubscriptionIdProvider)
                 .build(addr)
@@ -738,6 +915,7 @@ impl ApiServer {
         // TODO (QIT-26): While `Arc<HealthUpdater>` is stored in `self`, we rely on the fact that `self` is consumed and
         // dropped by `self.build_rpc_module` above, so we should still have just one strong reference.
         let closing_health_updater = Arc::downgrade(&health_updater);
+        let (force_stop_sender, force_stop_receiver) = oneshot::channel();
         tokio::spawn(async move {
             if stop_receiver.changed().await.is_err() {
                 tracing::warn!(
@@ -776,9 +954,34 @@ impl ApiServer {
                 closing_vm_barrier.close();
             }
             close_handle.stop().ok();
+
+            // The server no longer accepts new connections, but in-flight calls are still being processed.
+            let in_flight_calls = drain_tracker.in_flight_calls();
+            tracing::info!(
+                "Draining {in_flight_calls} in-flight calls on {transport_str} JSON-RPC server"
+            );
+            let drain_result = tokio::time::timeout(
+                GRACEFUL_SHUTDOWN_TIMEOUT,
+                drain_tracker.wait_for_in_flight_calls(),
+            )
+            .await;
+            if drain_result.is_err() {
+                tracing::warn!(
+                    "{} in-flight calls on {transport_str} JSON-RPC server didn't finish after {GRACEFUL_SHUTDOWN_TIMEOUT:?}; \
+                     forcing shutdown anyway",
+                    drain_tracker.in_flight_calls()
+                );
+                force_stop_sender.send(()).ok();
+            }
         });
 
-        server_handle.stopped().await;
+        tokio::select! {
+            () = server_handle.stopped() => { /* the server has stopped gracefully */ }
+            // If the sender is dropped, the drain was successful; we just continue waiting for the server to stop.
+            Ok(()) = force_stop_receiver => {
+                tracing::warn!("Forcefully stopped {transport_str} JSON-RPC server");
+            }
+        }
         drop(health_updater);
         tracing::info!("{transport_str} JSON-RPC server stopped");
         if let Some(vm_barrier) = vm_barrier {
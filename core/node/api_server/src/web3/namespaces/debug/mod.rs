@@ -0,0 +1,738 @@
+use std::{collections::HashMap, slice, sync::Arc};
+
+use anyhow::Context as _;
+use zksync_dal::{CoreDal, DalError};
+use zksync_multivm::{
+    interface::{Call, CallType, ExecutionResult, OneshotTracingParams},
+    tracers::ExecutionFrameCollector,
+};
+use zksync_system_constants::{
+    ACCOUNT_CODE_STORAGE_ADDRESS, ECRECOVER_PRECOMPILE_ADDRESS, EC_ADD_PRECOMPILE_ADDRESS,
+    EC_MUL_PRECOMPILE_ADDRESS, EC_PAIRING_PRECOMPILE_ADDRESS, KECCAK256_PRECOMPILE_ADDRESS,
+    MAX_ENCODED_TX_SIZE, P256VERIFY_PRECOMPILE_ADDRESS, SECP256R1_VERIFY_PRECOMPILE_ADDRESS,
+    SHA256_PRECOMPILE_ADDRESS,
+};
+use zksync_types::{
+    api::{
+        execution_trace::{ExecutionTrace, ExecutionTraceConfig},
+        state_override::{Bytecode, OverrideAccount, OverrideState, StateOverride},
+        BlockId, BlockNumber, CallTracerBlockResult, CallTracerResult, DebugCall, DebugCallType,
+        FourByteTrace, PrestateTrace, ResultDebugCall, ResultFourByteTrace, ResultPrestateTrace,
+        SupportedTracers, TraceCallConfig, TracerConfig,
+    },
+    bytecode::BytecodeHash,
+    debug_flat_call::{Action, CallResult, CallTraceMeta, DebugCallFlat, ResultDebugCallFlat},
+    h256_to_address,
+    l2::L2Tx,
+    transaction_request::CallRequest,
+    web3, Address, L2BlockNumber, StorageLogWithPreviousValue, H256, U256,
+};
+use zksync_web3_decl::error::Web3Error;
+
+use self::prestate::AccessedState;
+use crate::{
+    execution_sandbox::{BlockArgs, SandboxAction, SandboxExecutionOutput, VmPermitClass},
+    web3::{backend_jsonrpsee::MethodTracer, state::RpcState},
+};
+
+mod prestate;
+
+/// Maximum number of frames returned by a single `debug_trace*Execution` call. Longer traces are paged
+/// through using `fromFrame`.
+const MAX_EXECUTION_TRACE_FRAMES: usize = 10_000;
+
+/// Precompiles, calls to which are not reported by `4byteTracer` (same as in Geth).
+const PRECOMPILE_ADDRESSES: [Address; 8] = [
+    KECCAK256_PRECOMPILE_ADDRESS,
+    SHA256_PRECOMPILE_ADDRESS,
+    ECRECOVER_PRECOMPILE_ADDRESS,
+    SECP256R1_VERIFY_PRECOMPILE_ADDRESS,
+    P256VERIFY_PRECOMPILE_ADDRESS,
+    EC_ADD_PRECOMPILE_ADDRESS,
+    EC_MUL_PRECOMPILE_ADDRESS,
+    EC_PAIRING_PRECOMPILE_ADDRESS,
+];
+
+#[derive(Debug, Clone)]
+pub(crate) struct DebugNamespace {
+    state: RpcState,
+}
+
+impl DebugNamespace {
+    pub async fn new(state: RpcState) -> anyhow::Result<Self> {
+        Ok(Self { state })
+    }
+
+    /// Maps a traced call for tracers not requiring re-execution. Returns an error for other tracers;
+    /// these must be handled by the caller.
+    pub(crate) fn map_call(
+        call: Call,
+        meta: CallTraceMeta,
+        tracer_option: TracerConfig,
+    ) -> Result<CallTracerResult, Web3Error> {
+        let max_depth = tracer_option.tracer_config.effective_max_depth();
+        Ok(match tracer_option.tracer {
+            SupportedTracers::CallTracer => {
+                CallTracerResult::CallTrace(Self::map_default_call(call, max_depth))
+            }
+            SupportedTracers::FlatCallTracer => {
+                let mut calls = vec![];
+                let mut traces = vec![meta.index_in_block];
+                Self::flatten_call(call, &mut calls, &mut traces, max_depth, &meta);
+                CallTracerResult::FlatCallTrace(calls)
+            }
+            SupportedTracers::FourByteTracer => {
+                CallTracerResult::FourByteTrace(Self::four_byte_trace(&call))
+            }
+            SupportedTracers::PrestateTracer => {
+                return Err(Web3Error::UnsupportedTracer(tracer_option.tracer.name()));
+            }
+        })
+    }
+
+    /// Maps a call to the `callTracer` output. Calls nested deeper than `max_depth` are omitted.
+    pub(crate) fn map_default_call(call: Call, max_depth: Option<usize>) -> DebugCall {
+        let calls = if max_depth == Some(0) {
+            vec![]
+        } else {
+            call.calls
+                .into_iter()
+                .map(|call| Self::map_default_call(call, max_depth.map(|depth| depth - 1)))
+                .collect()
+        };
+        let debug_type = match call.r#type {
+            CallType::Call(_) => DebugCallType::Call,
+            CallType::Create => DebugCallType::Create,
+            CallType::NearCall => unreachable!("We have to filter our near calls before"),
+        };
+        DebugCall {
+            r#type: debug_type,
+            from: call.from,
+            to: call.to,
+            gas: U256::from(call.gas),
+            gas_used: U256::from(call.gas_used),
+            value: call.value,
+            output: web3::Bytes::from(call.output),
+            input: web3::Bytes::from(call.input),
+            error: call.error,
+            revert_reason: call.revert_reason,
+            calls,
+        }
+    }
+
+    fn flatten_call(
+        call: Call,
+        calls: &mut Vec<DebugCallFlat>,
+        trace_address: &mut Vec<usize>,
+        max_depth: Option<usize>,
+        meta: &CallTraceMeta,
+    ) {
+        let subtraces = call.calls.len();
+        let debug_type = match call.r#type {
+            CallType::Call(_) => DebugCallType::Call,
+            CallType::Create => DebugCallType::Create,
+            CallType::NearCall => unreachable!("We have to filter our near calls before"),
+        };
+
+        let (result, error) = match (call.revert_reason, call.error) {
+            (Some(revert_reason), _) => {
+                // If revert_reason exists, it takes priority over VM error
+                (None, Some(revert_reason))
+            }
+            (None, Some(vm_error)) => {
+                // If no revert_reason but VM error exists
+                (None, Some(vm_error))
+            }
+            (None, None) => (
+                Some(CallResult {
+                    output: web3::Bytes::from(call.output),
+                    gas_used: U256::from(call.gas_used),
+                }),
+                None,
+            ),
+        };
+
+        calls.push(DebugCallFlat {
+            action: Action {
+                call_type: debug_type,
+                from: call.from,
+                to: call.to,
+                gas: U256::from(call.gas),
+                value: call.value,
+                input: web3::Bytes::from(call.input),
+            },
+            result,
+            subtraces,
+            error,
+            trace_address: trace_address.clone(), // Clone the current trace address
+            transaction_position: meta.index_in_block,
+            transaction_hash: meta.tx_hash,
+            block_number: meta.block_number,
+            block_hash: meta.block_hash,
+            r#type: DebugCallType::Call,
+        });
+
+        if max_depth != Some(0) {
+            for (number, call) in call.calls.into_iter().enumerate() {
+                trace_address.push(number);
+                let max_depth = max_depth.map(|depth| depth - 1);
+                Self::flatten_call(call, calls, trace_address, max_depth, meta);
+                trace_address.pop();
+            }
+        }
+    }
+
+    /// Counts selectors of all calls in the call tree, keyed by the selector and the call data size.
+    fn four_byte_trace(call: &Call) -> FourByteTrace {
+        let mut trace = FourByteTrace::new();
+        Self::collect_selectors(call, &mut trace);
+        trace
+    }
+
+    fn collect_selectors(call: &Call, trace: &mut FourByteTrace) {
+        let is_selector_call = matches!(call.r#type, CallType::Call(_))
+            && call.input.len() >= 4
+            && !PRECOMPILE_ADDRESSES.contains(&call.to);
+        if is_selector_call {
+            let key = format!(
+                "0x{}-{}",
+                hex::encode(&call.input[..4]),
+                call.input.len() - 4
+            );
+            *trace.entry(key).or_default() += 1;
+        }
+        for call in &call.calls {
+            Self::collect_selectors(call, trace);
+        }
+    }
+
+    fn collect_accounts(call: &Call, accounts: &mut Vec<Address>) {
+        accounts.extend([call.from, call.to]);
+        for call in &call.calls {
+            Self::collect_accounts(call, accounts);
+        }
+    }
+
+    /// Builds `prestateTracer` output from the storage logs and the call trees of an executed call or transaction.
+    /// `accounts` are additional accounts which base token balances should be recognized (e.g., the fee account).
+    async fn prestate_trace(
+        &self,
+        calls: &[Call],
+        mut accounts: Vec<Address>,
+        storage_logs: &[StorageLogWithPreviousValue],
+        diff_mode: bool,
+    ) -> Result<PrestateTrace, Web3Error> {
+        for call in calls {
+            Self::collect_accounts(call, &mut accounts);
+        }
+        let mut trace = AccessedState::new(storage_logs, accounts).into_trace(diff_mode);
+
+        let states = match &mut trace {
+            PrestateTrace::Default(accounts) => vec![accounts],
+            PrestateTrace::Diff { pre, post } => vec![pre, post],
+        };
+        let mut connection = self.state.acquire_connection().await?;
+        for account in states.into_iter().flat_map(|state| state.values_mut()) {
+            let Some(code_hash) = account.code_hash.filter(|hash| !hash.is_zero()) else {
+                continue;
+            };
+            // Bytecodes supplied via state overrides are not persisted and are thus not returned.
+            account.code = connection
+                .factory_deps_dal()
+                .get_sealed_factory_dep(code_hash)
+                .await
+                .map_err(DalError::generalize)?
+                .map(web3::Bytes::from);
+        }
+        Ok(trace)
+    }
+
+    pub(crate) fn current_method(&self) -> &MethodTracer {
+        &self.state.current_method
+    }
+
+    pub async fn debug_trace_block_impl(
+        &self,
+        block_id: BlockId,
+        options: Option<TracerConfig>,
+    ) -> Result<CallTracerBlockResult, Web3Error> {
+        self.current_method().set_block_id(block_id);
+        if matches!(block_id, BlockId::Number(BlockNumber::Pending)) {
+            // See `EthNamespace::get_block_impl()` for an explanation why this check is needed.
+            return Ok(CallTracerBlockResult::CallTrace(vec![]));
+        }
+
+        let mut connection = self.state.acquire_connection().await?;
+        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        self.current_method()
+            .set_block_diff(self.state.last_sealed_l2_block.diff(block_number));
+
+        let options = options.unwrap_or_default();
+        if options.tracer == SupportedTracers::PrestateTracer {
+            drop(connection);
+            let traces = self
+                .replay_prestate_traces(block_number, None, options.tracer_config.diff_mode)
+                .await?;
+            return Ok(CallTracerBlockResult::PrestateTrace(traces));
+        }
+
+        let call_traces = connection
+            .blocks_web3_dal()
+            .get_traces_for_l2_block(block_number)
+            .await
+            .map_err(DalError::generalize)?;
+
+        let max_depth = options.tracer_config.effective_max_depth();
+        let result = match options.tracer {
+            SupportedTracers::CallTracer => CallTracerBlockResult::CallTrace(
+                call_traces
+                    .into_iter()
+                    .map(|(call, _)| ResultDebugCall {
+                        result: Self::map_default_call(call, max_depth),
+                    })
+                    .collect(),
+            ),
+            SupportedTracers::FlatCallTracer => {
+                let res = call_traces
+                    .into_iter()
+                    .map(|(call, meta)| {
+                        let mut traces = vec![meta.index_in_block];
+                        let mut flat_calls = vec![];
+                        Self::flatten_call(call, &mut flat_calls, &mut traces, max_depth, &meta);
+                        ResultDebugCallFlat {
+                            tx_hash: meta.tx_hash,
+                            result: flat_calls,
+                        }
+                    })
+                    .collect();
+                CallTracerBlockResult::FlatCallTrace(res)
+            }
+            SupportedTracers::FourByteTracer => CallTracerBlockResult::FourByteTrace(
+                call_traces
+                    .into_iter()
+                    .map(|(call, meta)| ResultFourByteTrace {
+                        tx_hash: meta.tx_hash,
+                        result: Self::four_byte_trace(&call),
+                    })
+                    .collect(),
+            ),
+            SupportedTracers::PrestateTracer => unreachable!("handled above"),
+        };
+        Ok(result)
+    }
+
+    pub async fn debug_trace_transaction_impl(
+        &self,
+        tx_hash: H256,
+        options: Option<TracerConfig>,
+    ) -> Result<Option<CallTracerResult>, Web3Error> {
+        let options = options.unwrap_or_default();
+        let mut connection = self.state.acquire_connection().await?;
+        let call_trace = connection
+            .transactions_dal()
+            .get_call_trace(tx_hash)
+            .await
+            .map_err(DalError::generalize)?;
+        drop(connection);
+        let Some((call_trace, meta)) = call_trace else {
+            return Ok(None);
+        };
+
+        if options.tracer == SupportedTracers::PrestateTracer {
+            let block_number = L2BlockNumber(meta.block_number);
+            let diff_mode = options.tracer_config.diff_mode;
+            let trace = self
+                .replay_prestate_traces(block_number, Some(tx_hash), diff_mode)
+                .await?
+                .pop()
+                .with_context(|| format!("transaction {tx_hash:?} was not replayed"))?;
+            return Ok(Some(CallTracerResult::PrestateTrace(trace.result)));
+        }
+        Self::map_call(call_trace, meta, options).map(Some)
+    }
+
+    /// Re-executes transactions in the specified L2 block in the sandbox and returns `prestateTracer` output for them.
+    /// If `last_tx_hash` is specified, only the trace for this transaction is returned.
+    async fn replay_prestate_traces(
+        &self,
+        block_number: L2BlockNumber,
+        last_tx_hash: Option<H256>,
+        diff_mode: bool,
+    ) -> Result<Vec<ResultPrestateTrace>, Web3Error> {
+        let tracing_params = || OneshotTracingParams {
+            trace_calls: true,
+            ..OneshotTracingParams::default()
+        };
+        let (fee_account, outputs) = self
+            .replay_transactions(block_number, last_tx_hash, tracing_params)
+            .await?;
+
+        let mut traces = Vec::with_capacity(outputs.len());
+        for (tx_hash, output) in outputs {
+            let trace = self
+                .prestate_trace(
+                    &output.call_traces,
+                    vec![fee_account],
+                    &output.vm.logs.storage_logs,
+                    diff_mode,
+                )
+                .await?;
+            traces.push(ResultPrestateTrace {
+                tx_hash,
+                result: trace,
+            });
+        }
+        Ok(traces)
+    }
+
+    /// Re-executes transactions in the specified L2 block in the sandbox. If `last_tx_hash` is specified,
+    /// only transactions up to and including this transaction are executed, and only this transaction is traced
+    /// using `tracing_params`; otherwise, all transactions are traced. Returns the fee account of the block
+    /// and execution outputs for the traced transactions.
+    ///
+    /// Transactions are executed on top of the state after the previous L2 block, with storage writes
+    /// (and bytecodes deployed) by the preceding transactions in the block applied as state overrides. Note that
+    /// the block context (e.g., the block timestamp) visible to transactions may differ from the original one.
+    async fn replay_transactions(
+        &self,
+        block_number: L2BlockNumber,
+        last_tx_hash: Option<H256>,
+        tracing_params: impl Fn() -> OneshotTracingParams,
+    ) -> Result<(Address, Vec<(H256, SandboxExecutionOutput)>), Web3Error> {
+        let Some(prev_block_number) = block_number.0.checked_sub(1) else {
+            // The genesis block doesn't contain transactions that could be replayed.
+            return Ok((Address::zero(), vec![]));
+        };
+
+        let mut connection = self.state.acquire_connection().await?;
+        let header = connection
+            .blocks_dal()
+            .get_l2_block_header(block_number)
+            .await
+            .map_err(DalError::generalize)?
+            .with_context(|| format!("missing header for L2 block #{block_number}"))?;
+        let mut transactions = connection
+            .transactions_web3_dal()
+            .get_raw_l2_block_transactions(block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        if let Some(tx_hash) = last_tx_hash {
+            let position = transactions
+                .iter()
+                .position(|tx| tx.hash() == tx_hash)
+                .with_context(|| {
+                    format!("transaction {tx_hash:?} is missing in L2 block #{block_number}")
+                })?;
+            transactions.truncate(position + 1);
+        }
+        let prev_block_id = BlockId::Number(BlockNumber::Number(prev_block_number.into()));
+        let block_args = self
+            .state
+            .resolve_block_args(&mut connection, prev_block_id)
+            .await?;
+        drop(connection);
+
+        let mut state_diff = HashMap::<Address, HashMap<H256, H256>>::new();
+        let mut published_bytecodes = HashMap::new();
+        let mut outputs = vec![];
+        let tx_count = transactions.len();
+        for (i, tx) in transactions.into_iter().enumerate() {
+            let tx_hash = tx.hash();
+            let is_traced = last_tx_hash.is_none() || i + 1 == tx_count;
+            let state_override = Self::replay_state_override(&state_diff, &published_bytecodes);
+            published_bytecodes.extend(
+                tx.execute
+                    .factory_deps
+                    .iter()
+                    .map(|dep| (BytecodeHash::for_bytecode(dep).value(), dep.clone())),
+            );
+
+            let action = SandboxAction::Replay {
+                tx,
+                fee_input: header.batch_fee_input,
+                base_fee: header.base_fee_per_gas,
+                tracing_params: if is_traced {
+                    tracing_params()
+                } else {
+                    OneshotTracingParams::default()
+                },
+            };
+            let result = self
+                .execute_action(action, &block_args, Some(state_override))
+                .await?;
+
+            let storage_logs = &result.vm.logs.storage_logs;
+            for log in storage_logs.iter().filter(|log| log.log.is_write()) {
+                let key = &log.log.key;
+                state_diff
+                    .entry(*key.address())
+                    .or_default()
+                    .insert(*key.key(), log.log.value);
+            }
+            if is_traced {
+                outputs.push((tx_hash, result));
+            }
+        }
+        Ok((header.fee_account_address, outputs))
+    }
+
+    /// Builds state overrides for replaying a transaction after the transactions with the specified cumulative
+    /// storage writes and published bytecodes.
+    fn replay_state_override(
+        state_diff: &HashMap<Address, HashMap<H256, H256>>,
+        published_bytecodes: &HashMap<H256, Vec<u8>>,
+    ) -> StateOverride {
+        let mut overrides: HashMap<_, _> = state_diff
+            .iter()
+            .map(|(&address, diff)| {
+                let account = OverrideAccount {
+                    state: Some(OverrideState::StateDiff(diff.clone())),
+                    ..OverrideAccount::default()
+                };
+                (address, account)
+            })
+            .collect();
+
+        // Bytecodes of contracts deployed by the preceding transactions are not available in the storage
+        // of the previous L2 block, so they are supplied as code overrides.
+        let deployed_contracts = state_diff
+            .get(&ACCOUNT_CODE_STORAGE_ADDRESS)
+            .into_iter()
+            .flatten();
+        for (key, code_hash) in deployed_contracts {
+            let Some(bytecode) = published_bytecodes.get(code_hash) else {
+                continue;
+            };
+            if let Ok(bytecode) = Bytecode::new(bytecode.clone()) {
+                overrides.entry(h256_to_address(key)).or_default().code = Some(bytecode);
+            }
+        }
+        StateOverride::new(overrides)
+    }
+
+    pub async fn debug_trace_call_impl(
+        &self,
+        request: CallRequest,
+        block_id: Option<BlockId>,
+        options: Option<TraceCallConfig>,
+    ) -> Result<CallTracerResult, Web3Error> {
+        let TraceCallConfig {
+            tracer: options,
+            state_overrides,
+        } = options.unwrap_or_default();
+        let trace_calls = match options.tracer {
+            // We don't need properly trace if we only need top call
+            SupportedTracers::CallTracer | SupportedTracers::FlatCallTracer => {
+                options.tracer_config.effective_max_depth() != Some(0)
+            }
+            // Called accounts are used to recognize balances in the prestate.
+            SupportedTracers::FourByteTracer | SupportedTracers::PrestateTracer => true,
+        };
+        let tracing_params = OneshotTracingParams {
+            trace_calls,
+            ..OneshotTracingParams::default()
+        };
+        let (call, block_args, result) = self
+            .execute_call(request, block_id, state_overrides, tracing_params)
+            .await?;
+
+        let (output, revert_reason) = match result.vm.result {
+            ExecutionResult::Success { output, .. } => (output, None),
+            ExecutionResult::Revert { output } => (vec![], Some(output.to_string())),
+            ExecutionResult::Halt { reason } => {
+                return Err(Web3Error::SubmitTransactionError(
+                    reason.to_string(),
+                    vec![],
+                ))
+            }
+        };
+        let call = Call::new_high_level(
+            call.common_data.fee.gas_limit.as_u64(),
+            result.vm.statistics.gas_used,
+            call.execute.value,
+            call.execute.calldata,
+            output,
+            revert_reason,
+            result.call_traces,
+        );
+        if options.tracer == SupportedTracers::PrestateTracer {
+            let trace = self
+                .prestate_trace(
+                    slice::from_ref(&call),
+                    vec![],
+                    &result.vm.logs.storage_logs,
+                    options.tracer_config.diff_mode,
+                )
+                .await?;
+            return Ok(CallTracerResult::PrestateTrace(trace));
+        }
+
+        let number = block_args.resolved_block_number();
+        let meta = CallTraceMeta {
+            block_number: number.0,
+            // It's a call request, it's safe to everything as default
+            ..Default::default()
+        };
+        Self::map_call(call, meta, options)
+    }
+
+    pub async fn debug_trace_call_execution_impl(
+        &self,
+        request: CallRequest,
+        block_id: Option<BlockId>,
+        options: Option<ExecutionTraceConfig>,
+    ) -> Result<ExecutionTrace, Web3Error> {
+        let options = options.unwrap_or_default();
+        let collector = Self::execution_frame_collector(&options);
+        let tracing_params = OneshotTracingParams {
+            trace_execution: Some(collector.clone()),
+            ..OneshotTracingParams::default()
+        };
+        let (_, _, result) = self
+            .execute_call(request, block_id, options.state_overrides, tracing_params)
+            .await?;
+        Ok(Self::execution_trace(&collector, result.vm.result))
+    }
+
+    pub async fn debug_trace_transaction_execution_impl(
+        &self,
+        tx_hash: H256,
+        options: Option<ExecutionTraceConfig>,
+    ) -> Result<Option<ExecutionTrace>, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        let chain_id = self.state.api_config.l2_chain_id;
+        let transaction = connection
+            .transactions_web3_dal()
+            .get_transaction_by_hash(tx_hash, chain_id)
+            .await
+            .map_err(DalError::generalize)?;
+        drop(connection);
+        // Pending transactions cannot be replayed.
+        let Some(block_number) = transaction.and_then(|tx| tx.block_number) else {
+            return Ok(None);
+        };
+        let block_number = L2BlockNumber(block_number.as_u32());
+
+        let options = options.unwrap_or_default();
+        let collector = Self::execution_frame_collector(&options);
+        let tracing_params = || OneshotTracingParams {
+            trace_execution: Some(collector.clone()),
+            ..OneshotTracingParams::default()
+        };
+        let (_, mut outputs) = self
+            .replay_transactions(block_number, Some(tx_hash), tracing_params)
+            .await?;
+        let (_, result) = outputs
+            .pop()
+            .with_context(|| format!("transaction {tx_hash:?} was not replayed"))?;
+        Ok(Some(Self::execution_trace(&collector, result.vm.result)))
+    }
+
+    fn execution_frame_collector(options: &ExecutionTraceConfig) -> Arc<ExecutionFrameCollector> {
+        let max_frames = options
+            .max_frames
+            .unwrap_or(MAX_EXECUTION_TRACE_FRAMES)
+            .min(MAX_EXECUTION_TRACE_FRAMES);
+        let from_frame = options.from_frame.unwrap_or(0);
+        Arc::new(ExecutionFrameCollector::new(from_frame, max_frames))
+    }
+
+    fn execution_trace(
+        collector: &ExecutionFrameCollector,
+        result: ExecutionResult,
+    ) -> ExecutionTrace {
+        let revert_reason = match result {
+            ExecutionResult::Success { .. } => None,
+            ExecutionResult::Revert { output } => Some(output.to_string()),
+            ExecutionResult::Halt { reason } => Some(reason.to_string()),
+        };
+        let (frames, truncated) = collector.take();
+        let next_frame = frames
+            .last()
+            .filter(|_| truncated)
+            .map(|frame| frame.index + 1);
+        ExecutionTrace {
+            frames,
+            truncated,
+            next_frame,
+            revert_reason,
+        }
+    }
+
+    /// Executes a call in the sandbox with the specified tracers. Returns the executed transaction,
+    /// resolved block args and the execution output.
+    async fn execute_call(
+        &self,
+        mut request: CallRequest,
+        block_id: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        tracing_params: OneshotTracingParams,
+    ) -> Result<(L2Tx, BlockArgs, SandboxExecutionOutput), Web3Error> {
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
+        self.current_method().set_block_id(block_id);
+
+        let mut connection = self.state.acquire_connection().await?;
+        let block_args = self
+            .state
+            .resolve_block_args(&mut connection, block_id)
+            .await?;
+        self.current_method().set_block_diff(
+            self.state
+                .last_sealed_l2_block
+                .diff_with_block_args(&block_args),
+        );
+        if request.gas.is_none() {
+            request.gas = Some(block_args.default_eth_call_gas(&mut connection).await?);
+        }
+
+        let fee_input = if block_args.resolves_to_latest_sealed_l2_block() {
+            // It is important to drop a DB connection before calling the provider, since it acquires a connection internally
+            // on the main node.
+            drop(connection);
+            self.state.tx_sender.scaled_batch_fee_input().await?
+        } else {
+            let fee_input = block_args.historical_fee_input(&mut connection).await?;
+            drop(connection);
+            fee_input
+        };
+
+        let call_overrides = request.get_call_overrides()?;
+        let call = L2Tx::from_request(
+            request.into(),
+            MAX_ENCODED_TX_SIZE,
+            block_args.use_evm_emulator(),
+        )?;
+
+        let action = SandboxAction::Call {
+            call: call.clone(),
+            fee_input,
+            enforced_base_fee: call_overrides.enforced_base_fee,
+            tracing_params,
+        };
+        let result = self
+            .execute_action(action, &block_args, state_overrides)
+            .await?;
+        Ok((call, block_args, result))
+    }
+
+    async fn execute_action(
+        &self,
+        action: SandboxAction,
+        block_args: &BlockArgs,
+        state_overrides: Option<StateOverride>,
+    ) -> Result<SandboxExecutionOutput, Web3Error> {
+        let vm_permit = self
+            .state
+            .tx_sender
+            .vm_concurrency_limiter()
+            .acquire(VmPermitClass::Heavy)
+            .await;
+        let vm_permit = vm_permit.context("cannot acquire VM permit")?;
+
+        let connection = self.state.acquire_connection().await?;
+        let executor = &self.state.tx_sender.0.executor;
+        Ok(executor
+            .execute_in_sandbox(vm_permit, connection, action, block_args, state_overrides)
+            .await?)
+    }
+}
@@ -0,0 +1,217 @@
+//! Building `prestateTracer` output from storage logs produced by the VM.
+//!
+//! Unlike in Ethereum, account balances, nonces and bytecode hashes are stored in the storage of system contracts,
+//! so they are extracted from the storage logs of these contracts. All other accessed slots are returned as the storage
+//! of the corresponding account.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use zksync_system_constants::{
+    ACCOUNT_CODE_STORAGE_ADDRESS, L2_BASE_TOKEN_ADDRESS, NONCE_HOLDER_ADDRESS,
+};
+use zksync_types::{
+    api::{PrestateAccount, PrestateTrace},
+    h256_to_address, h256_to_u256,
+    utils::{decompose_full_nonce, storage_key_for_eth_balance},
+    Address, StorageKey, StorageLogWithPreviousValue, H256,
+};
+
+/// Values of a storage slot accessed during execution.
+#[derive(Debug, Clone, Copy)]
+struct SlotValues {
+    initial: H256,
+    last: H256,
+}
+
+/// Storage slots accessed during execution.
+#[derive(Debug)]
+pub(super) struct AccessedState {
+    slots: HashMap<StorageKey, SlotValues>,
+    /// Maps base token balance keys to the owning accounts.
+    balance_keys: HashMap<H256, Address>,
+}
+
+impl AccessedState {
+    /// Creates the state from storage logs. `accounts` are addresses known to participate in execution (e.g., from
+    /// the call trace); they are used to recognize base token balances, since balance keys are hashes.
+    pub fn new(
+        storage_logs: &[StorageLogWithPreviousValue],
+        accounts: impl IntoIterator<Item = Address>,
+    ) -> Self {
+        let mut slots = HashMap::<_, SlotValues>::new();
+        for log in storage_logs {
+            let initial = if log.log.is_write() {
+                log.previous_value
+            } else {
+                log.log.value
+            };
+            slots
+                .entry(log.log.key)
+                .or_insert(SlotValues {
+                    initial,
+                    last: initial,
+                })
+                .last = log.log.value;
+        }
+
+        let mut accounts: HashSet<_> = accounts.into_iter().collect();
+        for key in slots.keys() {
+            accounts.insert(*key.address());
+            if Self::is_account_keyed(key) {
+                accounts.insert(h256_to_address(key.key()));
+            }
+        }
+        let balance_keys = accounts
+            .into_iter()
+            .map(|address| (*storage_key_for_eth_balance(&address).key(), address))
+            .collect();
+        Self {
+            slots,
+            balance_keys,
+        }
+    }
+
+    /// Checks whether the key is keyed by an account address in a system contract storage.
+    fn is_account_keyed(key: &StorageKey) -> bool {
+        [NONCE_HOLDER_ADDRESS, ACCOUNT_CODE_STORAGE_ADDRESS].contains(key.address())
+    }
+
+    /// Returns the state before execution (if `diff_mode` is not set), or the state modified by execution
+    /// before and after execution.
+    pub fn into_trace(self, diff_mode: bool) -> PrestateTrace {
+        if diff_mode {
+            let modified_slots = || {
+                self.slots
+                    .iter()
+                    .filter(|(_, values)| values.initial != values.last)
+            };
+            let pre =
+                self.build_accounts(modified_slots().map(|(key, values)| (key, values.initial)));
+            let post =
+                self.build_accounts(modified_slots().map(|(key, values)| (key, values.last)));
+            PrestateTrace::Diff { pre, post }
+        } else {
+            let slots = self.slots.iter().map(|(key, values)| (key, values.initial));
+            PrestateTrace::Default(self.build_accounts(slots))
+        }
+    }
+
+    fn build_accounts<'a>(
+        &self,
+        slots: impl Iterator<Item = (&'a StorageKey, H256)>,
+    ) -> BTreeMap<Address, PrestateAccount> {
+        let mut accounts = BTreeMap::<_, PrestateAccount>::new();
+        for (key, value) in slots {
+            let address = *key.address();
+            if address == NONCE_HOLDER_ADDRESS {
+                let (tx_nonce, _) = decompose_full_nonce(h256_to_u256(value));
+                let account = accounts.entry(h256_to_address(key.key())).or_default();
+                account.nonce = Some(tx_nonce.low_u64());
+            } else if address == ACCOUNT_CODE_STORAGE_ADDRESS {
+                let account = accounts.entry(h256_to_address(key.key())).or_default();
+                account.code_hash = Some(value);
+            } else if let Some(owner) = self
+                .balance_keys
+                .get(key.key())
+                .filter(|_| address == L2_BASE_TOKEN_ADDRESS)
+            {
+                accounts.entry(*owner).or_default().balance = Some(h256_to_u256(value));
+            } else {
+                let account = accounts.entry(address).or_default();
+                account.storage.insert(*key.key(), value);
+            }
+        }
+        accounts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{
+        get_code_key, get_nonce_key, AccountTreeId, StorageLog, StorageLogKind, U256,
+    };
+
+    use super::*;
+
+    fn log(
+        kind: StorageLogKind,
+        key: StorageKey,
+        value: H256,
+        previous_value: H256,
+    ) -> StorageLogWithPreviousValue {
+        StorageLogWithPreviousValue {
+            log: StorageLog { kind, key, value },
+            previous_value,
+        }
+    }
+
+    #[test]
+    fn building_prestate_trace() {
+        let sender = Address::repeat_byte(1);
+        let contract = Address::repeat_byte(2);
+        let slot = StorageKey::new(AccountTreeId::new(contract), H256::repeat_byte(0xaa));
+        let code_hash = H256::repeat_byte(0xcc);
+        let storage_logs = [
+            log(
+                StorageLogKind::RepeatedWrite,
+                get_nonce_key(&sender),
+                H256::from_low_u64_be(6),
+                H256::from_low_u64_be(5),
+            ),
+            log(
+                StorageLogKind::Read,
+                get_code_key(&contract),
+                code_hash,
+                code_hash,
+            ),
+            log(
+                StorageLogKind::Read,
+                slot,
+                H256::from_low_u64_be(1),
+                H256::from_low_u64_be(1),
+            ),
+            log(
+                StorageLogKind::RepeatedWrite,
+                slot,
+                H256::from_low_u64_be(2),
+                H256::from_low_u64_be(1),
+            ),
+            log(
+                StorageLogKind::RepeatedWrite,
+                storage_key_for_eth_balance(&sender),
+                H256::from_low_u64_be(90),
+                H256::from_low_u64_be(100),
+            ),
+        ];
+
+        let state = AccessedState::new(&storage_logs, [sender, contract]);
+        let PrestateTrace::Default(accounts) = state.into_trace(false) else {
+            panic!("unexpected trace");
+        };
+        assert_eq!(accounts.len(), 2);
+        let sender_state = &accounts[&sender];
+        assert_eq!(sender_state.nonce, Some(5));
+        assert_eq!(sender_state.balance, Some(U256::from(100)));
+        assert!(sender_state.storage.is_empty());
+        let contract_state = &accounts[&contract];
+        assert_eq!(contract_state.code_hash, Some(code_hash));
+        assert_eq!(
+            contract_state.storage,
+            BTreeMap::from([(H256::repeat_byte(0xaa), H256::from_low_u64_be(1))])
+        );
+
+        let state = AccessedState::new(&storage_logs, [sender, contract]);
+        let PrestateTrace::Diff { pre, post } = state.into_trace(true) else {
+            panic!("unexpected trace");
+        };
+        assert_eq!(pre[&sender].nonce, Some(5));
+        assert_eq!(post[&sender].nonce, Some(6));
+        assert_eq!(post[&sender].balance, Some(U256::from(90)));
+        // The code hash isn't modified.
+        assert_eq!(pre[&contract].code_hash, None);
+        assert_eq!(
+            post[&contract].storage,
+            BTreeMap::from([(H256::repeat_byte(0xaa), H256::from_low_u64_be(2))])
+        );
+    }
+}
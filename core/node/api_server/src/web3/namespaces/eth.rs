@@ -4,7 +4,7 @@ use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
         state_override::StateOverride, BlockId, BlockNumber, FeeHistory, GetLogsFilter,
-        Transaction, TransactionId, TransactionReceipt, TransactionVariant,
+        Transaction, TransactionConditions, TransactionId, TransactionReceipt, TransactionVariant,
     },
     bytecode::{trim_padded_evm_bytecode, BytecodeHash, BytecodeMarker},
     l2::{L2Tx, TransactionType},
@@ -23,12 +23,50 @@ use crate::{
     execution_sandbox::BlockArgs,
     tx_sender::BinarySearchKind,
     utils::open_readonly_transaction,
-    web3::{backend_jsonrpsee::MethodTracer, metrics::API_METRICS, state::RpcState, TypedFilter},
+    web3::{
+        backend_jsonrpsee::MethodTracer,
+        call_result_cache::{CachedCallMethod, CallCacheKey, CallResultCache},
+        metrics::API_METRICS,
+        state::RpcState,
+        TypedFilter,
+    },
 };
 
 pub const EVENT_TOPIC_NUMBER_LIMIT: usize = 4;
 pub const PROTOCOL_VERSION: &str = "zks/1";
 
+/// Converts the address and topic conditions of `filter` into a DAL filter for the specified block range.
+pub(crate) fn get_logs_filter(
+    filter: &Filter,
+    from_block: L2BlockNumber,
+    to_block: L2BlockNumber,
+) -> Result<GetLogsFilter, Web3Error> {
+    let addresses = if let Some(addresses) = &filter.address {
+        addresses.0.clone()
+    } else {
+        vec![]
+    };
+    let topics = if let Some(topics) = &filter.topics {
+        if topics.len() > EVENT_TOPIC_NUMBER_LIMIT {
+            return Err(Web3Error::TooManyTopics);
+        }
+        let topics_by_idx = topics
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, topics)| Some((idx as u32 + 1, topics.as_ref()?.0.clone())));
+        topics_by_idx.collect::<Vec<_>>()
+    } else {
+        vec![]
+    };
+
+    Ok(GetLogsFilter {
+        from_block,
+        to_block,
+        addresses,
+        topics,
+    })
+}
+
 #[derive(Debug)]
 pub(crate) struct EthNamespace {
     state: RpcState,
@@ -78,6 +116,24 @@ impl EthNamespace {
         }
         drop(connection);
 
+        let cached = self
+            .call_result_cache(CachedCallMethod::Call, state_override.as_ref())
+            .map(|cache| {
+                let key = CallCacheKey::new(
+                    CachedCallMethod::Call,
+                    block_args.resolved_block_number(),
+                    block_args.is_pending(),
+                    0,
+                    &request,
+                );
+                (cache, key)
+            });
+        if let Some((cache, key)) = &cached {
+            if let Some(output) = cache.get_call(key) {
+                return Ok(output.into());
+            }
+        }
+
         let call_overrides = request.get_call_overrides()?;
         let tx = L2Tx::from_request(
             request.into(),
@@ -91,9 +147,23 @@ impl EthNamespace {
             .tx_sender
             .eth_call(block_args, call_overrides, tx, state_override)
             .await?;
+        if let Some((cache, key)) = cached {
+            cache.insert_call(key, call_result.clone());
+        }
         Ok(call_result.into())
     }
 
+    /// Returns the call result cache if results of the specified method can be cached. Calls with state overrides
+    /// are never cached, since their results don't solely depend on the block state.
+    fn call_result_cache(
+        &self,
+        method: CachedCallMethod,
+        state_override: Option<&StateOverride>,
+    ) -> Option<&CallResultCache> {
+        let cache = self.state.call_result_cache.as_ref()?;
+        (state_override.is_none() && cache.is_enabled(method)).then_some(cache)
+    }
+
     pub async fn estimate_gas_impl(
         &self,
         request: CallRequest,
@@ -117,6 +187,28 @@ impl EthNamespace {
         let mut connection = self.state.acquire_connection().await?;
         let block_args = BlockArgs::pending(&mut connection).await?;
         drop(connection);
+
+        // When we're estimating fee, we are trying to deduce values related to fee, so we should
+        // not consider provided ones.
+        let gas_price = self.state.tx_sender.gas_price().await?;
+        let cached = self
+            .call_result_cache(CachedCallMethod::EstimateGas, state_override.as_ref())
+            .map(|cache| {
+                let key = CallCacheKey::new(
+                    CachedCallMethod::EstimateGas,
+                    block_args.resolved_block_number(),
+                    block_args.is_pending(),
+                    gas_price,
+                    &request_with_gas_per_pubdata_overridden,
+                );
+                (cache, key)
+            });
+        if let Some((cache, key)) = &cached {
+            if let Some(gas_limit) = cache.get_gas_estimate(key) {
+                return Ok(gas_limit);
+            }
+        }
+
         let mut tx: L2Tx = L2Tx::from_request(
             request_with_gas_per_pubdata_overridden.into(),
             self.state.api_config.max_tx_size,
@@ -129,9 +221,6 @@ impl EthNamespace {
             tx.common_data.transaction_type = TransactionType::EIP712Transaction;
         }
 
-        // When we're estimating fee, we are trying to deduce values related to fee, so we should
-        // not consider provided ones.
-        let gas_price = self.state.tx_sender.gas_price().await?;
         tx.common_data.fee.max_fee_per_gas = gas_price.into();
         tx.common_data.fee.max_priority_fee_per_gas = tx.common_data.fee.max_fee_per_gas;
 
@@ -153,6 +242,9 @@ impl EthNamespace {
                 search_kind,
             )
             .await?;
+        if let Some((cache, key)) = cached {
+            cache.insert_gas_estimate(key, fee.gas_limit);
+        }
         Ok(fee.gas_limit)
     }
 
@@ -670,6 +762,31 @@ impl EthNamespace {
         })
     }
 
+    pub async fn send_raw_transaction_conditional_impl(
+        &self,
+        tx_bytes: Bytes,
+        conditions: TransactionConditions,
+    ) -> Result<H256, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        let block_args = BlockArgs::pending(&mut connection).await?;
+        drop(connection);
+        let (mut tx, hash) = self
+            .state
+            .parse_transaction_bytes(&tx_bytes.0, &block_args)?;
+        tx.set_input(tx_bytes.0, hash);
+
+        let submit_result = self
+            .state
+            .tx_sender
+            .submit_conditional_tx(tx, &conditions, block_args)
+            .await;
+        submit_result.map(|_| hash).map_err(|err| {
+            tracing::debug!("Send raw conditional transaction error: {err}");
+            API_METRICS.submit_tx_error[&err.prom_error_code()].inc();
+            err.into()
+        })
+    }
+
     pub fn accounts_impl(&self) -> Vec<Address> {
         Vec::new()
     }
@@ -805,23 +922,6 @@ impl EthNamespace {
             }
 
             TypedFilter::Events(filter, from_block) => {
-                let addresses = if let Some(addresses) = &filter.address {
-                    addresses.0.clone()
-                } else {
-                    vec![]
-                };
-                let topics = if let Some(topics) = &filter.topics {
-                    if topics.len() > EVENT_TOPIC_NUMBER_LIMIT {
-                        return Err(Web3Error::TooManyTopics);
-                    }
-                    let topics_by_idx = topics.iter().enumerate().filter_map(|(idx, topics)| {
-                        Some((idx as u32 + 1, topics.as_ref()?.0.clone()))
-                    });
-                    topics_by_idx.collect::<Vec<_>>()
-                } else {
-                    vec![]
-                };
-
                 let mut to_block = self
                     .state
                     .resolve_filter_block_number(filter.to_block)
@@ -835,12 +935,7 @@ impl EthNamespace {
                     );
                 }
 
-                let get_logs_filter = GetLogsFilter {
-                    from_block: *from_block,
-                    to_block,
-                    addresses,
-                    topics,
-                };
+                let get_logs_filter = get_logs_filter(filter, *from_block, to_block)?;
 
                 let mut storage = self.state.acquire_connection().await?;
 
@@ -55,11 +55,13 @@ impl SnapshotsNamespace {
 
         let chunks = snapshot_files
             .into_iter()
+            .zip(snapshot_metadata.storage_logs_checksums)
             .enumerate()
-            .filter_map(|(chunk_id, filepath)| {
+            .filter_map(|(chunk_id, (filepath, checksum))| {
                 Some(SnapshotStorageLogsChunkMetadata {
                     chunk_id: chunk_id as u64,
                     filepath: filepath?,
+                    checksum,
                 })
             })
             .collect();
@@ -70,12 +72,15 @@ impl SnapshotsNamespace {
             .map_err(DalError::generalize)?
             .with_context(|| format!("missing L2 blocks for L1 batch #{l1_batch_number}"))?;
 
-        Ok(Some(SnapshotHeader {
+        let mut header = SnapshotHeader {
             version: snapshot_metadata.version.into(),
             l1_batch_number: snapshot_metadata.l1_batch_number,
             l2_block_number,
             storage_logs_chunks: chunks,
             factory_deps_filepath: snapshot_metadata.factory_deps_filepath,
-        }))
+            manifest_hash: None,
+        };
+        header.manifest_hash = header.compute_manifest_hash();
+        Ok(Some(header))
     }
 }
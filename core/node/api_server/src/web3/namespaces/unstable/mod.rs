@@ -1,3 +1,4 @@
+use anyhow::Context as _;
 use chrono::{DateTime, Utc};
 use itertools::Itertools;
 use utils::{
@@ -6,15 +7,31 @@ use utils::{
 use zksync_crypto_primitives::hasher::keccak::KeccakHasher;
 use zksync_dal::{CoreDal, DalError};
 use zksync_mini_merkle_tree::MiniMerkleTree;
+use zksync_multivm::{interface::OneshotTracingParams, utils::derive_base_fee_and_gas_per_pubdata};
 use zksync_types::{
-    api::{ChainAggProof, TeeProof, TransactionExecutionInfo},
+    api::{
+        gas_profile::GasProfile, state_override::StateOverride, BaseTokenRatioAuditEntry, BlockId,
+        BlockNumber, ChainAggProof, ContractStorageUsage, L1TxProcessingStatus, LogsCursor,
+        LogsPage, PipelineCongestion, PriorityOpInfo, StagedBaseSystemContract, TeeProof,
+        TransactionExecutionInfo, TransactionSimulation,
+    },
+    l2::L2Tx,
     tee_types::TeeType,
-    L1BatchNumber, L2ChainId,
+    transaction_request::CallRequest,
+    L1BatchNumber, L2BlockNumber, L2ChainId, PriorityOpId, ProtocolVersionId, U256,
+};
+use zksync_web3_decl::{
+    error::Web3Error,
+    types::{Filter, H256},
 };
-use zksync_web3_decl::{error::Web3Error, types::H256};
 
-use crate::web3::{backend_jsonrpsee::MethodTracer, RpcState};
+use super::eth::get_logs_filter;
+use crate::{
+    execution_sandbox::{SandboxExecutionOutput, VmPermitClass},
+    web3::{backend_jsonrpsee::MethodTracer, RpcState},
+};
 
+mod simulation;
 mod utils;
 
 #[derive(Debug)]
@@ -139,4 +156,228 @@ impl UnstableNamespace {
             chain_id_leaf_proof_mask: chain_id_leaf_proof_mask as u64,
         }))
     }
+
+    pub async fn get_logs_paginated_impl(
+        &self,
+        mut filter: Filter,
+        cursor: Option<LogsCursor>,
+        page_size: Option<usize>,
+    ) -> Result<LogsPage, Web3Error> {
+        let max_page_size = self.state.api_config.req_entities_limit;
+        let page_size = page_size.map_or(max_page_size, |size| size.clamp(1, max_page_size));
+
+        self.state.resolve_filter_block_hash(&mut filter).await?;
+        let (from_block, to_block) = self.state.resolve_filter_block_range(&filter).await?;
+        let latest_block = self
+            .state
+            .resolve_filter_block_number(Some(BlockNumber::Latest))
+            .await?;
+        let get_logs_filter = get_logs_filter(&filter, from_block, to_block.min(latest_block))?;
+
+        let mut storage = self.state.acquire_connection().await?;
+        // Request an extra log to find out whether there are more pages.
+        let mut logs = storage
+            .events_web3_dal()
+            .get_logs_after(
+                &get_logs_filter,
+                cursor.map(|cursor| (cursor.block_number, cursor.log_index)),
+                page_size + 1,
+            )
+            .await
+            .map_err(DalError::generalize)?;
+
+        let next_cursor = if logs.len() > page_size {
+            logs.truncate(page_size);
+            let last_log = logs.last().unwrap(); // `page_size` is positive, so `logs` is not empty
+            Some(LogsCursor {
+                block_number: L2BlockNumber(
+                    last_log
+                        .block_number
+                        .context("missing block number")?
+                        .as_u32(),
+                ),
+                log_index: last_log.log_index.context("missing log index")?.as_u32(),
+            })
+        } else {
+            None
+        };
+        Ok(LogsPage { logs, next_cursor })
+    }
+
+    /// Executes a call in the pending block, like `eth_call` does. Returns the execution output together with
+    /// the gas limit of the call and the gas per pubdata byte it was executed with.
+    async fn execute_pending_call(
+        &self,
+        mut request: CallRequest,
+        state_override: Option<StateOverride>,
+        tracing_params: OneshotTracingParams,
+    ) -> Result<(SandboxExecutionOutput, U256, u64), Web3Error> {
+        let block_id = BlockId::Number(BlockNumber::Pending);
+        self.current_method().set_block_id(block_id);
+
+        let mut connection = self.state.acquire_connection().await?;
+        let block_args = self
+            .state
+            .resolve_block_args(&mut connection, block_id)
+            .await?;
+        if request.gas.is_none() {
+            request.gas = Some(block_args.default_eth_call_gas(&mut connection).await?);
+        }
+        drop(connection);
+
+        let call_overrides = request.get_call_overrides()?;
+        let tx = L2Tx::from_request(
+            request.into(),
+            self.state.api_config.max_tx_size,
+            block_args.use_evm_emulator(),
+        )?;
+        let gas_limit = tx.common_data.fee.gas_limit;
+        let protocol_version = block_args.protocol_version();
+        let (output, fee_input) = self
+            .state
+            .tx_sender
+            .execute_call(
+                block_args,
+                call_overrides,
+                tx,
+                state_override,
+                tracing_params,
+                VmPermitClass::Heavy,
+            )
+            .await?;
+        let (_, gas_per_pubdata) =
+            derive_base_fee_and_gas_per_pubdata(fee_input, protocol_version.into());
+        Ok((output, gas_limit, gas_per_pubdata))
+    }
+
+    pub async fn simulate_transaction_impl(
+        &self,
+        request: CallRequest,
+        state_override: Option<StateOverride>,
+    ) -> Result<TransactionSimulation, Web3Error> {
+        let (output, _, gas_per_pubdata) = self
+            .execute_pending_call(request, state_override, OneshotTracingParams::default())
+            .await?;
+        Ok(simulation::build_simulation(output.vm, gas_per_pubdata))
+    }
+
+    pub async fn gas_profile_impl(
+        &self,
+        request: CallRequest,
+        state_override: Option<StateOverride>,
+    ) -> Result<GasProfile, Web3Error> {
+        let tracing_params = OneshotTracingParams {
+            profile_gas: true,
+            ..OneshotTracingParams::default()
+        };
+        let (output, gas_limit, gas_per_pubdata) = self
+            .execute_pending_call(request, state_override, tracing_params)
+            .await?;
+        Ok(simulation::build_gas_profile(
+            output.vm,
+            output.gas_profile,
+            gas_limit,
+            gas_per_pubdata,
+        ))
+    }
+
+    pub async fn get_priority_queue_impl(
+        &self,
+        from_id: Option<PriorityOpId>,
+        limit: Option<usize>,
+    ) -> Result<Vec<PriorityOpInfo>, Web3Error> {
+        let max_limit = self.state.api_config.req_entities_limit;
+        let limit = limit.map_or(max_limit, |limit| limit.clamp(1, max_limit));
+
+        let mut storage = self.state.acquire_connection().await?;
+        let queue = storage
+            .transactions_web3_dal()
+            .get_priority_queue(from_id.unwrap_or_default(), limit)
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(queue)
+    }
+
+    pub async fn get_l1_tx_processing_status_impl(
+        &self,
+        tx_hash: H256,
+    ) -> Result<Option<L1TxProcessingStatus>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        let status = storage
+            .transactions_web3_dal()
+            .get_l1_tx_processing_status(tx_hash)
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(status)
+    }
+
+    pub async fn get_pipeline_congestion_impl(
+        &self,
+    ) -> Result<Option<PipelineCongestion>, Web3Error> {
+        Ok(self
+            .state
+            .tx_sender
+            .0
+            .batch_fee_input_provider
+            .get_pipeline_congestion()
+            .await?)
+    }
+
+    pub async fn get_staged_base_system_contracts_impl(
+        &self,
+        protocol_version: Option<ProtocolVersionId>,
+    ) -> Result<Vec<StagedBaseSystemContract>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        let contracts = storage
+            .staged_base_system_contracts_dal()
+            .get_staged_contracts(protocol_version)
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(contracts)
+    }
+
+    pub async fn get_base_token_ratio_audit_impl(
+        &self,
+        from_timestamp: Option<u64>,
+        limit: Option<usize>,
+    ) -> Result<Vec<BaseTokenRatioAuditEntry>, Web3Error> {
+        let max_limit = self.state.api_config.req_entities_limit;
+        let limit = limit.map_or(max_limit, |limit| limit.clamp(1, max_limit));
+        let from = i64::try_from(from_timestamp.unwrap_or(0))
+            .ok()
+            .and_then(|timestamp| DateTime::<Utc>::from_timestamp(timestamp, 0));
+        let Some(from) = from else {
+            // Out-of-range timestamps are far in the future, so no ratios can match.
+            return Ok(vec![]);
+        };
+
+        let mut storage = self.state.acquire_connection().await?;
+        let entries = storage
+            .base_token_dal()
+            .get_ratio_audit(&from.naive_utc(), limit)
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(entries)
+    }
+
+    pub async fn get_top_pubdata_consumers_impl(
+        &self,
+        from_batch: L1BatchNumber,
+        to_batch: L1BatchNumber,
+        limit: Option<usize>,
+    ) -> Result<Vec<ContractStorageUsage>, Web3Error> {
+        if from_batch > to_batch {
+            return Ok(vec![]);
+        }
+        let max_limit = self.state.api_config.req_entities_limit;
+        let limit = limit.map_or(max_limit, |limit| limit.clamp(1, max_limit));
+
+        let mut storage = self.state.acquire_connection().await?;
+        let consumers = storage
+            .storage_analytics_dal()
+            .get_top_pubdata_consumers(from_batch..=to_batch, limit)
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(consumers)
+    }
 }
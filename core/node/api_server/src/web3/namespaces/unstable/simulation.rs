@@ -0,0 +1,322 @@
+//! Conversion of VM execution results into the `unstable_simulateTransaction` and `unstable_gasProfile` responses.
+
+use std::collections::HashMap;
+
+use zksync_multivm::interface::{
+    ContractGasUsage, ExecutionResult, VmEvent, VmExecutionResultAndLogs,
+};
+use zksync_types::{
+    api::{
+        gas_profile::GasProfile, BalanceChange, SimulatedGasBreakdown, SimulatedLog, StorageDiff,
+        TokenTransfer, TransactionSimulation,
+    },
+    h256_to_address,
+    web3::keccak256,
+    StorageKey, StorageLogWithPreviousValue, H256, U256,
+};
+
+/// Signature of the ERC-20 `Transfer(address,address,uint256)` event. The same event is emitted by the base token contract.
+fn transfer_event_signature() -> H256 {
+    H256(keccak256(b"Transfer(address,address,uint256)"))
+}
+
+/// Decodes an ERC-20 transfer from the event. ERC-721 transfers have the same signature, but
+/// a different number of indexed topics, so they are not decoded.
+fn decode_transfer(event: &VmEvent) -> Option<TokenTransfer> {
+    let [signature, from, to] = event.indexed_topics.as_slice() else {
+        return None;
+    };
+    if *signature != transfer_event_signature() || event.value.len() != 32 {
+        return None;
+    }
+    Some(TokenTransfer {
+        token: event.address,
+        from: h256_to_address(from),
+        to: h256_to_address(to),
+        amount: U256::from_big_endian(&event.value),
+    })
+}
+
+fn balance_changes<'a>(transfers: impl Iterator<Item = &'a TokenTransfer>) -> Vec<BalanceChange> {
+    let mut changes = vec![];
+    let mut change_indices = HashMap::new();
+    for transfer in transfers {
+        for (account, is_sender) in [(transfer.from, true), (transfer.to, false)] {
+            let idx = *change_indices
+                .entry((transfer.token, account))
+                .or_insert_with(|| {
+                    changes.push(BalanceChange {
+                        token: transfer.token,
+                        account,
+                        received: U256::zero(),
+                        sent: U256::zero(),
+                    });
+                    changes.len() - 1
+                });
+            let change = &mut changes[idx];
+            if is_sender {
+                change.sent = change.sent.saturating_add(transfer.amount);
+            } else {
+                change.received = change.received.saturating_add(transfer.amount);
+            }
+        }
+    }
+    changes
+}
+
+/// Collects net changes of storage slots in the order of first writes. Slots restored to their initial values are skipped.
+fn storage_diffs(storage_logs: &[StorageLogWithPreviousValue]) -> Vec<StorageDiff> {
+    let mut diffs: Vec<(StorageKey, H256, H256)> = vec![];
+    let mut diff_indices = HashMap::<StorageKey, usize>::new();
+    for log in storage_logs.iter().filter(|log| log.log.is_write()) {
+        if let Some(&idx) = diff_indices.get(&log.log.key) {
+            diffs[idx].2 = log.log.value;
+        } else {
+            diff_indices.insert(log.log.key, diffs.len());
+            diffs.push((log.log.key, log.previous_value, log.log.value));
+        }
+    }
+
+    diffs
+        .into_iter()
+        .filter(|(_, previous_value, new_value)| previous_value != new_value)
+        .map(|(key, previous_value, new_value)| StorageDiff {
+            address: *key.address(),
+            key: *key.key(),
+            previous_value,
+            new_value,
+        })
+        .collect()
+}
+
+fn execution_outcome(result: ExecutionResult) -> (bool, Vec<u8>, Option<String>) {
+    match result {
+        ExecutionResult::Success { output } => (true, output, None),
+        ExecutionResult::Revert { output } => (
+            false,
+            output.encoded_data(),
+            Some(output.to_user_friendly_string()),
+        ),
+        ExecutionResult::Halt { reason } => (false, vec![], Some(reason.to_string())),
+    }
+}
+
+pub(super) fn build_simulation(
+    vm_result: VmExecutionResultAndLogs,
+    gas_per_pubdata: u64,
+) -> TransactionSimulation {
+    let (success, output, revert_reason) = execution_outcome(vm_result.result);
+
+    let logs: Vec<_> = vm_result
+        .logs
+        .events
+        .iter()
+        .map(|event| SimulatedLog {
+            address: event.address,
+            topics: event.indexed_topics.clone(),
+            data: event.value.clone().into(),
+            transfer: decode_transfer(event),
+        })
+        .collect();
+    let balance_changes = balance_changes(logs.iter().filter_map(|log| log.transfer.as_ref()));
+
+    let pubdata_published = vm_result.statistics.pubdata_published;
+    TransactionSimulation {
+        success,
+        output: output.into(),
+        revert_reason,
+        logs,
+        balance_changes,
+        storage_diffs: storage_diffs(&vm_result.logs.storage_logs),
+        gas: SimulatedGasBreakdown {
+            // `gas_used` includes the gas spent on pubdata, so it cannot be used here.
+            execution_gas: vm_result.statistics.computational_gas_used.into(),
+            pubdata_gas: U256::from(pubdata_published) * U256::from(gas_per_pubdata),
+            pubdata_published: pubdata_published.into(),
+            gas_per_pubdata: gas_per_pubdata.into(),
+        },
+    }
+}
+
+pub(super) fn build_gas_profile(
+    vm_result: VmExecutionResultAndLogs,
+    contracts: Vec<ContractGasUsage>,
+    gas_limit: U256,
+    gas_per_pubdata: u64,
+) -> GasProfile {
+    let (success, _, revert_reason) = execution_outcome(vm_result.result);
+    // `statistics.gas_used` is the computational gas spent by the VM (including L2 block setup), and it doesn't include
+    // the gas charged for pubdata, so the charged gas is derived from the refund instead.
+    let gas_used = gas_limit.saturating_sub(vm_result.refunds.gas_refunded.into());
+    let pubdata_published = vm_result.statistics.pubdata_published;
+    let pubdata_gas = U256::from(pubdata_published) * U256::from(gas_per_pubdata);
+    // Self gas of all profiled functions sums up to the gas used by the far calls made by the bootloader
+    // while processing the transaction.
+    let attributed_gas: u64 = contracts.iter().map(|usage| usage.self_gas_used).sum();
+    GasProfile {
+        success,
+        revert_reason,
+        gas_used,
+        pubdata_gas,
+        bootloader_gas: gas_used
+            .saturating_sub(pubdata_gas)
+            .saturating_sub(attributed_gas.into()),
+        pubdata_published: pubdata_published.into(),
+        gas_per_pubdata: gas_per_pubdata.into(),
+        contracts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{
+        address_to_h256, u256_to_h256, AccountTreeId, Address, L1BatchNumber, StorageLog,
+    };
+
+    use super::*;
+
+    fn transfer_event(token: Address, from: Address, to: Address, amount: u64) -> VmEvent {
+        VmEvent {
+            location: (L1BatchNumber(1), 0),
+            address: token,
+            indexed_topics: vec![
+                transfer_event_signature(),
+                address_to_h256(&from),
+                address_to_h256(&to),
+            ],
+            value: u256_to_h256(amount.into()).0.to_vec(),
+        }
+    }
+
+    #[test]
+    fn decoding_transfers() {
+        let token = Address::repeat_byte(1);
+        let alice = Address::repeat_byte(2);
+        let bob = Address::repeat_byte(3);
+        let event = transfer_event(token, alice, bob, 100);
+        assert_eq!(
+            decode_transfer(&event),
+            Some(TokenTransfer {
+                token,
+                from: alice,
+                to: bob,
+                amount: 100.into(),
+            })
+        );
+
+        let mut nft_event = event.clone();
+        nft_event.indexed_topics.push(H256::from_low_u64_be(1));
+        nft_event.value.clear();
+        assert_eq!(decode_transfer(&nft_event), None);
+
+        let mut other_event = event;
+        other_event.indexed_topics[0] = H256::repeat_byte(0xff);
+        assert_eq!(decode_transfer(&other_event), None);
+    }
+
+    #[test]
+    fn aggregating_balance_changes() {
+        let token = Address::repeat_byte(1);
+        let alice = Address::repeat_byte(2);
+        let bob = Address::repeat_byte(3);
+        let transfers = [
+            transfer_event(token, alice, bob, 100),
+            transfer_event(token, bob, alice, 30),
+            transfer_event(token, alice, bob, 5),
+        ];
+        let transfers: Vec<_> = transfers.iter().filter_map(decode_transfer).collect();
+
+        let changes = balance_changes(transfers.iter());
+        assert_eq!(
+            changes,
+            [
+                BalanceChange {
+                    token,
+                    account: alice,
+                    received: 30.into(),
+                    sent: 105.into(),
+                },
+                BalanceChange {
+                    token,
+                    account: bob,
+                    received: 105.into(),
+                    sent: 30.into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn collecting_storage_diffs() {
+        let address = Address::repeat_byte(1);
+        let key =
+            |idx: u64| StorageKey::new(AccountTreeId::new(address), H256::from_low_u64_be(idx));
+        let log = |idx: u64, previous: u64, value: u64| StorageLogWithPreviousValue {
+            log: StorageLog::new_write_log(key(idx), H256::from_low_u64_be(value)),
+            previous_value: H256::from_low_u64_be(previous),
+        };
+        let storage_logs = [
+            log(1, 0, 1),
+            StorageLogWithPreviousValue {
+                log: StorageLog::new_read_log(key(2), H256::zero()),
+                previous_value: H256::zero(),
+            },
+            log(3, 5, 6),
+            log(1, 1, 2),
+            // Restored to the initial value
+            log(3, 6, 5),
+        ];
+
+        let diffs = storage_diffs(&storage_logs);
+        assert_eq!(
+            diffs,
+            [StorageDiff {
+                address,
+                key: H256::from_low_u64_be(1),
+                previous_value: H256::zero(),
+                new_value: H256::from_low_u64_be(2),
+            }]
+        );
+    }
+
+    #[test]
+    fn building_simulation() {
+        let mut vm_result = VmExecutionResultAndLogs::mock_success();
+        vm_result.statistics.gas_used = 1_000_000;
+        vm_result.statistics.computational_gas_used = 200_000;
+        vm_result.statistics.pubdata_published = 100;
+
+        let simulation = build_simulation(vm_result, 800);
+        assert!(simulation.success);
+        assert_eq!(simulation.gas.execution_gas, 200_000.into());
+        assert_eq!(simulation.gas.pubdata_gas, 80_000.into());
+        assert_eq!(simulation.gas.pubdata_published, 100.into());
+        assert_eq!(simulation.gas.gas_per_pubdata, 800.into());
+    }
+
+    #[test]
+    fn building_gas_profile() {
+        let mut vm_result = VmExecutionResultAndLogs::mock_success();
+        // Computational gas used by the VM must not be used for the profile.
+        vm_result.statistics.gas_used = 1_000_000;
+        vm_result.statistics.pubdata_published = 10;
+        vm_result.refunds.gas_refunded = 9_000;
+        let usage = |contract: u8, self_gas_used: u64| ContractGasUsage {
+            contract: Address::repeat_byte(contract),
+            selector: None,
+            calls: 1,
+            gas_used: self_gas_used,
+            self_gas_used,
+            pubdata_bytes: 0,
+        };
+        let contracts = vec![usage(1, 500), usage(2, 200)];
+
+        let profile = build_gas_profile(vm_result, contracts.clone(), 20_000.into(), 50);
+        assert!(profile.success);
+        assert_eq!(profile.gas_used, 11_000.into());
+        assert_eq!(profile.pubdata_gas, 500.into());
+        assert_eq!(profile.bootloader_gas, 9_800.into());
+        assert_eq!(profile.pubdata_published, 10.into());
+        assert_eq!(profile.contracts, contracts);
+    }
+}
@@ -1,33 +1,34 @@
 use std::collections::HashMap;
 
 use anyhow::Context as _;
-use zksync_crypto_primitives::hasher::{keccak::KeccakHasher, Hasher};
 use zksync_dal::{Connection, Core, CoreDal, DalError};
 use zksync_metadata_calculator::api_server::TreeApiError;
-use zksync_mini_merkle_tree::MiniMerkleTree;
 use zksync_multivm::interface::VmExecutionResultAndLogs;
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     address_to_h256,
     api::{
-        state_override::StateOverride, BlockDetails, BridgeAddresses, GetLogsFilter,
-        L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion, StorageProof, TransactionDetails,
+        state_override::StateOverride, BatchStateDiff, BlockDetails, BlockId, BlockNumber,
+        BridgeAddresses, BridgeTransfersPage, GetLogsFilter, L1BatchDetails, L2ToL1LogProof, Proof,
+        ProtocolVersion, StateDiffEntry, StorageProof, TransactionDetails, ZksFeeHistory,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
     h256_to_u256,
     l1::L1Tx,
     l2::L2Tx,
-    l2_to_l1_log::{l2_to_l1_logs_tree_size, L2ToL1Log, LOG_PROOF_SUPPORTED_METADATA_VERSION},
+    l2_to_l1_log::{l2_to_l1_log_proof, L2ToL1Log},
     tokens::ETHEREUM_ADDRESS,
     transaction_request::CallRequest,
+    u256_to_h256,
     utils::storage_key_for_standard_token_balance,
     web3::Bytes,
+    writes::compress_state_diffs,
     AccountTreeId, L1BatchNumber, L2BlockNumber, ProtocolVersionId, StorageKey, Transaction,
     L1_MESSENGER_ADDRESS, L2_BASE_TOKEN_ADDRESS, REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_BYTE, U256, U64,
 };
 use zksync_web3_decl::{
-    error::Web3Error,
+    error::{PrunedDataKind, Web3Error},
     types::{Address, Token, H256},
 };
 
@@ -331,44 +332,23 @@ impl ZksNamespace {
             return Ok(None);
         };
 
-        let merkle_tree_leaves = all_l1_logs_in_batch.iter().map(L2ToL1Log::to_bytes);
-
         let protocol_version = batch_with_metadata
             .header
             .protocol_version
             .unwrap_or_else(ProtocolVersionId::last_potentially_undefined);
-        let tree_size = l2_to_l1_logs_tree_size(protocol_version);
-
-        let (local_root, proof) = MiniMerkleTree::new(merkle_tree_leaves, Some(tree_size))
-            .merkle_root_and_path(l1_log_index);
-
-        if protocol_version.is_pre_gateway() {
-            return Ok(Some(L2ToL1LogProof {
-                proof,
-                root: local_root,
-                id: l1_log_index as u32,
-            }));
-        }
-
-        let aggregated_root = batch_with_metadata
-            .metadata
-            .aggregation_root
-            .expect("`aggregation_root` must be present for post-gateway branch");
-        let root = KeccakHasher.compress(&local_root, &aggregated_root);
-
-        let mut log_leaf_proof = proof;
-        log_leaf_proof.push(aggregated_root);
 
-        let Some(sl_chain_id) = storage
-            .eth_sender_dal()
-            .get_batch_commit_chain_id(l1_batch_number)
-            .await
-            .map_err(DalError::generalize)?
-        else {
-            return Ok(None);
-        };
+        let batch_chain_proof = if protocol_version.is_pre_gateway() {
+            None
+        } else {
+            let Some(sl_chain_id) = storage
+                .eth_sender_dal()
+                .get_batch_commit_chain_id(l1_batch_number)
+                .await
+                .map_err(DalError::generalize)?
+            else {
+                return Ok(None);
+            };
 
-        let (batch_proof_len, batch_chain_proof) =
             if sl_chain_id.0 != self.state.api_config.l1_chain_id.0 {
                 let Some(batch_chain_proof) = storage
                     .blocks_dal()
@@ -378,31 +358,19 @@ impl ZksNamespace {
                 else {
                     return Ok(None);
                 };
-
-                (batch_chain_proof.batch_proof_len, batch_chain_proof.proof)
+                Some(batch_chain_proof)
             } else {
-                (0, Vec::new())
-            };
-
-        let proof = {
-            let mut metadata = [0u8; 32];
-            metadata[0] = LOG_PROOF_SUPPORTED_METADATA_VERSION;
-            metadata[1] = log_leaf_proof.len() as u8;
-            metadata[2] = batch_proof_len as u8;
-
-            let mut result = vec![H256(metadata)];
-
-            result.extend(log_leaf_proof);
-            result.extend(batch_chain_proof);
-
-            result
+                None
+            }
         };
 
-        Ok(Some(L2ToL1LogProof {
-            proof,
-            root,
-            id: l1_log_index as u32,
-        }))
+        Ok(Some(l2_to_l1_log_proof(
+            &all_l1_logs_in_batch,
+            l1_log_index,
+            protocol_version,
+            batch_with_metadata.metadata.aggregation_root,
+            batch_chain_proof,
+        )))
     }
 
     pub async fn get_l2_to_l1_log_proof_impl(
@@ -531,11 +499,90 @@ impl ZksNamespace {
             .ensure_not_pruned(batch_number, &mut storage)
             .await?;
 
-        Ok(storage
+        let Some(mut details) = storage
             .blocks_web3_dal()
             .get_l1_batch_details(batch_number)
             .await
-            .map_err(DalError::generalize)?)
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+        let commitment_mode = storage
+            .blocks_dal()
+            .get_l1_batch_commitment_mode(batch_number)
+            .await
+            .map_err(DalError::generalize)?;
+        // Fall back to the configured mode for batches for which the mode isn't tracked in Postgres.
+        details.commitment_mode = Some(
+            commitment_mode.unwrap_or(self.state.api_config.l1_batch_commit_data_generator_mode),
+        );
+        Ok(Some(details))
+    }
+
+    /// Returns the state diff of the specified L1 batch. The diff is reconstructed from storage logs in the same way
+    /// as in the commitment generator, so it matches the state diff published in the batch pubdata.
+    /// Returns `None` for batches that are not sealed yet, and for pre-Boojum batches that didn't publish state diffs.
+    pub async fn get_batch_state_diff_impl(
+        &self,
+        batch_number: L1BatchNumber,
+    ) -> Result<Option<BatchStateDiff>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        self.state
+            .start_info
+            .ensure_not_pruned(batch_number, &mut storage)
+            .await?;
+        // Open a readonly transaction to have a consistent view of Postgres
+        let mut storage = open_readonly_transaction(&mut storage).await?;
+
+        let Some(header) = storage
+            .blocks_dal()
+            .get_l1_batch_header(batch_number)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+        let is_pre_boojum = header
+            .protocol_version
+            .map_or(true, |version| version.is_pre_boojum());
+        if is_pre_boojum {
+            return Ok(None);
+        }
+
+        let state_diff = storage
+            .storage_logs_dal()
+            .get_l1_batch_state_diff(batch_number)
+            .await
+            .map_err(DalError::generalize)?;
+        drop(storage);
+
+        let mut initial_writes = vec![];
+        let mut repeated_writes = vec![];
+        for write in &state_diff {
+            let record = &write.record;
+            let entry = StateDiffEntry {
+                address: record.address,
+                key: u256_to_h256(record.key),
+                derived_key: H256(record.derived_key),
+                enumeration_index: write.enumeration_index,
+                initial_value: u256_to_h256(record.initial_value),
+                final_value: u256_to_h256(record.final_value),
+                compressed: record.compress().into(),
+            };
+            if write.is_initial {
+                initial_writes.push(entry);
+            } else {
+                repeated_writes.push(entry);
+            }
+        }
+        let records = state_diff.into_iter().map(|write| write.record).collect();
+
+        Ok(Some(BatchStateDiff {
+            l1_batch_number: batch_number,
+            initial_writes,
+            repeated_writes,
+            compressed_state_diffs: compress_state_diffs(records).into(),
+        }))
     }
 
     pub async fn get_bytecode_by_hash_impl(
@@ -607,13 +654,26 @@ impl ZksNamespace {
             Ok(proofs) => proofs,
             Err(TreeApiError::NotReady(_)) => return Err(Web3Error::TreeApiUnavailable),
             Err(TreeApiError::NoVersion(err)) => {
-                return if err.missing_version > err.version_count {
-                    Ok(None)
-                } else {
-                    Err(Web3Error::InternalError(anyhow::anyhow!(
-                        "L1 batch #{l1_batch_number} is pruned in Merkle tree, but not in Postgres"
-                    )))
+                if err.missing_version >= err.version_count {
+                    return Ok(None);
+                }
+                // The tree is pruned independently of Postgres, so the batch may be retained in Postgres,
+                // but not in the tree. Report the first L1 batch for which proofs can be obtained.
+                let first_retained_l1_batch = match tree_api.get_info().await {
+                    Ok(info) => info.min_l1_batch_number,
+                    Err(err) => {
+                        tracing::info!("Failed getting Merkle tree info: {err:#}");
+                        None
+                    }
                 };
+                let mut pruned = self
+                    .state
+                    .start_info
+                    .pruned_data(PrunedDataKind::L1Batch, &mut storage)
+                    .await?;
+                pruned.first_retained_l1_batch =
+                    first_retained_l1_batch.unwrap_or(l1_batch_number + 1);
+                return Err(Web3Error::Pruned(pruned));
             }
             Err(TreeApiError::Internal(err)) => return Err(Web3Error::InternalError(err)),
             Err(_) => {
@@ -662,6 +722,52 @@ impl ZksNamespace {
             .into_pubdata_independent())
     }
 
+    pub async fn get_fee_history_impl(
+        &self,
+        block_count: u64,
+        newest_block: BlockNumber,
+    ) -> Result<ZksFeeHistory, Web3Error> {
+        self.current_method()
+            .set_block_id(BlockId::Number(newest_block));
+
+        // Limit `block_count` in the same way as for `eth_feeHistory`.
+        let block_count = block_count.clamp(1, self.state.api_config.fee_history_limit);
+
+        let mut storage = self.state.acquire_connection().await?;
+        let newest_l2_block = self
+            .state
+            .resolve_block(&mut storage, BlockId::Number(newest_block))
+            .await?;
+        let from_block = newest_l2_block
+            .0
+            .saturating_sub((block_count - 1).try_into().unwrap_or(u32::MAX));
+        let fee_inputs = storage
+            .blocks_web3_dal()
+            .get_fee_inputs_for_l2_blocks(L2BlockNumber(from_block), newest_l2_block)
+            .await
+            .map_err(DalError::generalize)?;
+
+        let oldest_block = L2BlockNumber(newest_l2_block.0 + 1 - fee_inputs.len() as u32);
+        let mut history = ZksFeeHistory {
+            oldest_block,
+            base_fee_per_gas: Vec::with_capacity(fee_inputs.len()),
+            fair_l2_gas_price: Vec::with_capacity(fee_inputs.len()),
+            fair_pubdata_price: Vec::with_capacity(fee_inputs.len()),
+            l1_gas_price: Vec::with_capacity(fee_inputs.len()),
+        };
+        for (base_fee_per_gas, fee_input) in fee_inputs {
+            history.base_fee_per_gas.push(base_fee_per_gas);
+            history
+                .fair_l2_gas_price
+                .push(fee_input.fair_l2_gas_price().into());
+            history
+                .fair_pubdata_price
+                .push(fee_input.fair_pubdata_price().into());
+            history.l1_gas_price.push(fee_input.l1_gas_price().into());
+        }
+        Ok(history)
+    }
+
     #[tracing::instrument(skip(self, tx_bytes))]
     pub async fn send_raw_transaction_with_detailed_output_impl(
         &self,
@@ -682,4 +788,37 @@ impl ZksNamespace {
             err.into()
         })
     }
+
+    pub async fn get_bridge_transfers_impl(
+        &self,
+        account: Address,
+        cursor: Option<U64>,
+        page_size: Option<usize>,
+    ) -> Result<BridgeTransfersPage, Web3Error> {
+        let max_page_size = self.state.api_config.req_entities_limit;
+        let page_size = page_size.map_or(max_page_size, |size| size.clamp(1, max_page_size));
+
+        let mut storage = self.state.acquire_connection().await?;
+        // Request an extra transfer to find out whether there are more pages.
+        let mut transfers = storage
+            .bridge_events_dal()
+            .get_bridge_transfers(account, cursor.map(|id| id.as_u64()), page_size + 1)
+            .await
+            .map_err(DalError::generalize)?;
+
+        let next_cursor = if transfers.len() > page_size {
+            transfers.truncate(page_size);
+            // `page_size` is positive, so `transfers` is not empty
+            transfers.last().map(|&(id, _)| id.into())
+        } else {
+            None
+        };
+        Ok(BridgeTransfersPage {
+            transfers: transfers
+                .into_iter()
+                .map(|(_, transfer)| transfer)
+                .collect(),
+            next_cursor,
+        })
+    }
 }
@@ -1,33 +1,57 @@
 //! (Largely) backend-agnostic logic for dealing with Web3 subscriptions.
+//!
+//! Each subscription buffers notifications in a bounded queue of a configurable capacity. If a client consumes
+//! notifications slower than they are produced, the queue eventually fills up, and the configured [`SlowSubscriberPolicy`]
+//! is applied: the subscription is either closed, or the oldest buffered notifications are dropped. Either way,
+//! a single slow client cannot stall notifiers or other subscribers.
+//!
+//! Log subscriptions created with `zks_subscribeLogsFrom` additionally replay historical logs before switching
+//! to live notifications; see [`LogsReplay`] for details.
 
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, VecDeque},
+    future,
+    hash::{Hash, Hasher},
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use anyhow::Context as _;
 use chrono::NaiveDateTime;
 use futures::FutureExt;
 use tokio::{
     sync::{broadcast, mpsc, watch},
     task::JoinHandle,
-    time::{interval, Duration},
+    time::{interval, Duration, Instant},
 };
 use tracing::Instrument as _;
+use zksync_config::configs::api::SlowSubscriberPolicy;
 use zksync_dal::{ConnectionPool, Core, CoreDal};
-use zksync_types::{L2BlockNumber, H128, H256};
+use zksync_types::{L1BatchNumber, L2BlockNumber, H128, H256};
 use zksync_web3_decl::{
     jsonrpsee::{
         core::{server::SubscriptionMessage, SubscriptionResult},
         server::IdProvider,
         types::{error::ErrorCode, ErrorObject, SubscriptionId},
-        PendingSubscriptionSink, SendTimeoutError, SubscriptionSink,
+        PendingSubscriptionSink, SubscriptionSink,
     },
-    namespaces::EthPubSubServer,
-    types::{BlockHeader, Log, PubSubFilter, PubSubResult},
+    namespaces::{EthPubSubServer, ZksPubSubServer},
+    types::{BlockHeader, Filter, L1BatchEvent, L1BatchEventKind, Log, PubSubFilter, PubSubResult},
 };
 
 use super::{
     metrics::{SubscriptionType, PUB_SUB_METRICS},
-    namespaces::eth::EVENT_TOPIC_NUMBER_LIMIT,
+    namespaces::eth::{get_logs_filter, EVENT_TOPIC_NUMBER_LIMIT},
 };
 
 const BROADCAST_CHANNEL_CAPACITY: usize = 1024;
 const SUBSCRIPTION_SINK_SEND_TIMEOUT: Duration = Duration::from_secs(1);
+const DEFAULT_SUBSCRIPTION_BUFFER_CAPACITY: usize = 1024;
+const DEFAULT_MAX_BUFFERED_NOTIFICATIONS_PER_CONNECTION: usize = 4096;
+const DEFAULT_LOGS_REPLAY_LIMIT: usize = 1024;
 
 #[derive(Debug, Clone, Copy)]
 pub struct EthSubscriptionIdProvider;
@@ -47,6 +71,299 @@ pub enum PubSubEvent {
     L2BlockAdvanced(SubscriptionType, L2BlockNumber),
 }
 
+/// Budget of buffered notifications shared by all subscriptions of a single WebSocket connection.
+#[derive(Debug)]
+pub(super) struct ConnectionBudget {
+    limit: usize,
+    used: Arc<AtomicUsize>,
+    /// Used to remove the budget from the registry once the last subscription of the connection is closed.
+    registry: Option<(ConnectionBudgets, u64)>,
+}
+
+impl ConnectionBudget {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            used: Arc::default(),
+            registry: None,
+        }
+    }
+
+    /// Creates another handle to the same budget.
+    #[cfg(test)]
+    pub fn shared(&self) -> Self {
+        Self {
+            limit: self.limit,
+            used: self.used.clone(),
+            registry: self.registry.clone(),
+        }
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.used.load(Ordering::Relaxed) >= self.limit
+    }
+
+    fn acquire(&self, count: usize) {
+        self.used.fetch_add(count, Ordering::Relaxed);
+    }
+
+    fn release(&self, count: usize) {
+        self.used.fetch_sub(count, Ordering::Relaxed);
+    }
+}
+
+impl Drop for ConnectionBudget {
+    fn drop(&mut self) {
+        if let Some((registry, connection_key)) = &self.registry {
+            let mut budgets = registry.lock().expect("connection budgets are poisoned");
+            // The registry holds the only other reference to the counter.
+            if Arc::strong_count(&self.used) == 2 {
+                budgets.remove(connection_key);
+            }
+        }
+    }
+}
+
+/// Buffered notification counters for all WebSocket connections with active subscriptions, keyed by the hash
+/// of the connection ID.
+type ConnectionBudgets = Arc<Mutex<HashMap<u64, Arc<AtomicUsize>>>>;
+
+/// Bounded buffer of notifications for a single subscription.
+#[derive(Debug)]
+pub(super) struct SubscriptionBuffer {
+    subscription_type: SubscriptionType,
+    capacity: usize,
+    policy: SlowSubscriberPolicy,
+    connection_budget: Option<ConnectionBudget>,
+    items: VecDeque<(PubSubResult, Instant)>,
+    /// Time since which the front notification is being sent to the subscriber.
+    front_sent_since: Option<Instant>,
+}
+
+impl Drop for SubscriptionBuffer {
+    fn drop(&mut self) {
+        if let Some(budget) = &self.connection_budget {
+            budget.release(self.items.len());
+        }
+    }
+}
+
+impl SubscriptionBuffer {
+    pub fn new(
+        subscription_type: SubscriptionType,
+        capacity: usize,
+        policy: SlowSubscriberPolicy,
+    ) -> Self {
+        Self {
+            subscription_type,
+            capacity,
+            policy,
+            connection_budget: None,
+            items: VecDeque::new(),
+            front_sent_since: None,
+        }
+    }
+
+    /// Shares the specified budget of buffered notifications with other subscriptions of the same connection.
+    /// If the budget is exhausted, the buffer is treated as full.
+    pub fn with_connection_budget(mut self, budget: ConnectionBudget) -> Self {
+        self.connection_budget = Some(budget);
+        self
+    }
+
+    fn is_full(&self) -> bool {
+        self.items.len() >= self.capacity
+            || self
+                .connection_budget
+                .as_ref()
+                .is_some_and(ConnectionBudget::is_exhausted)
+    }
+
+    pub fn front(&self) -> Option<&PubSubResult> {
+        self.items.front().map(|(item, _)| item)
+    }
+
+    /// Returns the deadline for sending the front notification to the subscriber.
+    fn send_deadline(&self) -> Option<Instant> {
+        self.front_sent_since
+            .map(|since| since + SUBSCRIPTION_SINK_SEND_TIMEOUT)
+    }
+
+    pub fn pop_front(&mut self) -> Option<PubSubResult> {
+        let (item, buffered_at) = self.items.pop_front()?;
+        if let Some(budget) = &self.connection_budget {
+            budget.release(1);
+        }
+        self.front_sent_since = (!self.items.is_empty()).then(Instant::now);
+        PUB_SUB_METRICS.notify_subscribers_latency[&self.subscription_type]
+            .observe(buffered_at.elapsed());
+        Some(item)
+    }
+
+    /// Adds a notification to the buffer applying the slow subscriber policy if the buffer is full.
+    /// Returns `false` if the subscription should be closed.
+    #[must_use = "subscription must be closed if the buffer overflows"]
+    pub fn push(&mut self, item: PubSubResult) -> bool {
+        if self.is_full() {
+            match self.policy {
+                SlowSubscriberPolicy::Close => {
+                    // Buffered notifications are lost together with the new one.
+                    let dropped_count = self.items.len() as u64 + 1;
+                    PUB_SUB_METRICS.dropped_notifications[&self.subscription_type]
+                        .inc_by(dropped_count);
+                    PUB_SUB_METRICS.closed_slow_subscribers[&self.subscription_type].inc();
+                    if let Some(budget) = &self.connection_budget {
+                        budget.release(self.items.len());
+                    }
+                    self.items.clear();
+                    self.front_sent_since = None;
+                    return false;
+                }
+                SlowSubscriberPolicy::DropOldest => {
+                    PUB_SUB_METRICS.dropped_notifications[&self.subscription_type].inc();
+                    if self.items.pop_front().is_none() {
+                        // The connection budget is exhausted by other subscriptions; drop the new notification.
+                        return true;
+                    }
+                    if let Some(budget) = &self.connection_budget {
+                        budget.release(1);
+                    }
+                }
+            }
+        }
+        if let Some(budget) = &self.connection_budget {
+            budget.acquire(1);
+        }
+        if self.items.is_empty() {
+            self.front_sent_since = Some(Instant::now());
+        }
+        self.items.push_back((item, Instant::now()));
+        true
+    }
+
+    /// Handles a gap in notifications caused by lagging behind the broadcast channel.
+    /// Returns `false` if the subscription should be closed.
+    #[must_use = "subscription must be closed on lag, depending on the policy"]
+    pub fn handle_lag(&mut self, skipped_message_count: u64) -> bool {
+        PUB_SUB_METRICS.skipped_broadcast_messages[&self.subscription_type]
+            .observe(skipped_message_count);
+        match self.policy {
+            SlowSubscriberPolicy::Close => {
+                PUB_SUB_METRICS.closed_slow_subscribers[&self.subscription_type].inc();
+                false
+            }
+            SlowSubscriberPolicy::DropOldest => true,
+        }
+    }
+}
+
+/// Replay of historical logs for a log subscription, which is performed before the subscription switches
+/// to live notifications.
+///
+/// To not miss any logs, the subscriber subscribes to live notifications *before* the replay starts. Replay
+/// then proceeds in rounds: each round discards live notifications received so far, loads the latest sealed
+/// L2 block and streams logs up to this block from Postgres. Since discarded notifications can only contain
+/// logs from L2 blocks sealed before the block loaded afterwards, they are covered by the replay. Once a round
+/// finds no new L2 blocks, the replay is finished, and live notifications for already replayed L2 blocks are skipped.
+#[derive(Debug)]
+struct LogsReplay {
+    connection_pool: ConnectionPool<Core>,
+    from_block: L2BlockNumber,
+}
+
+impl LogsReplay {
+    /// Maximum number of logs loaded from Postgres at once.
+    const PAGE_SIZE: usize = 1_000;
+
+    /// Runs the replay. Returns the first L2 block not covered by the replay, or `None` if the subscription
+    /// was closed during the replay.
+    async fn run(
+        self,
+        sink: &SubscriptionSink,
+        receiver: &mut broadcast::Receiver<Vec<PubSubResult>>,
+        filter: &PubSubFilter,
+    ) -> anyhow::Result<Option<L2BlockNumber>> {
+        let latency = PUB_SUB_METRICS.logs_replay_latency.start();
+        let mut next_block = self.from_block;
+        loop {
+            // Live notifications received so far are covered by the replay below.
+            loop {
+                match receiver.try_recv() {
+                    Ok(_) | Err(broadcast::error::TryRecvError::Lagged(_)) => {}
+                    Err(broadcast::error::TryRecvError::Empty) => break,
+                    Err(broadcast::error::TryRecvError::Closed) => return Ok(None),
+                }
+            }
+
+            let mut storage = self.connection_pool.connection_tagged("api").await?;
+            let sealed_l2_block = storage.blocks_dal().get_sealed_l2_block_number().await?;
+            drop(storage);
+            let Some(sealed_l2_block) = sealed_l2_block.filter(|&number| number >= next_block)
+            else {
+                latency.observe();
+                return Ok(Some(next_block));
+            };
+
+            if !self
+                .replay_blocks(sink, filter, next_block, sealed_l2_block)
+                .await?
+            {
+                return Ok(None);
+            }
+            tracing::debug!(
+                "Replayed logs for L2 blocks #{next_block}..=#{sealed_l2_block} to subscription {:?}",
+                sink.subscription_id()
+            );
+            next_block = sealed_l2_block + 1;
+        }
+    }
+
+    /// Streams logs from the specified inclusive range of L2 blocks. Returns `false` if the subscription was closed.
+    async fn replay_blocks(
+        &self,
+        sink: &SubscriptionSink,
+        filter: &PubSubFilter,
+        from_block: L2BlockNumber,
+        to_block: L2BlockNumber,
+    ) -> anyhow::Result<bool> {
+        let filter = Filter {
+            address: filter.address.clone(),
+            topics: filter.topics.clone(),
+            ..Filter::default()
+        };
+        let get_logs_filter = get_logs_filter(&filter, from_block, to_block)?;
+
+        let mut cursor = None;
+        loop {
+            let mut storage = self.connection_pool.connection_tagged("api").await?;
+            let logs = storage
+                .events_web3_dal()
+                .get_logs_after(&get_logs_filter, cursor, Self::PAGE_SIZE)
+                .await?;
+            // Do not hold a DB connection while sending logs to a potentially slow subscriber.
+            drop(storage);
+
+            let is_last_page = logs.len() < Self::PAGE_SIZE;
+            if let Some(last_log) = logs.last() {
+                let block_number = last_log.block_number.context("missing block number")?;
+                let log_index = last_log.log_index.context("missing log index")?;
+                cursor = Some((L2BlockNumber(block_number.as_u32()), log_index.as_u32()));
+            }
+            for log in logs {
+                let message = SubscriptionMessage::from_json(&PubSubResult::Log(log))
+                    .expect("PubSubResult always serializable to json;qed");
+                if sink.send(message).await.is_err() {
+                    return Ok(false);
+                }
+                PUB_SUB_METRICS.replayed_logs.inc();
+            }
+            if is_last_page {
+                return Ok(true);
+            }
+        }
+    }
+}
+
 /// Manager of notifications for a certain type of subscriptions.
 #[derive(Debug)]
 struct PubSubNotifier {
@@ -229,30 +546,179 @@ impl PubSubNotifier {
             .await
             .map_err(Into::into)
     }
+
+    async fn notify_l1_batch_events(
+        self,
+        stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        // Only batches advancing after the notifier has started are reported. Stages without any batches
+        // at this point (e.g., no batches committed yet) are reported starting from the first batch sealed
+        // after the notifier has started, rather than replaying the entire chain.
+        let mut last_statuses = self.l1_batch_statuses().await?;
+        let first_l1_batch = last_statuses[0]
+            .1
+            .map_or(L1BatchNumber(0), |number| number + 1);
+        let mut timer = interval(self.polling_interval);
+        loop {
+            if *stop_receiver.borrow() {
+                tracing::info!("Stop signal received, pubsub_l1_batch_notifier is shutting down");
+                break;
+            }
+            timer.tick().await;
+
+            let db_latency =
+                PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::L1BatchEvents].start();
+            let statuses = self.l1_batch_statuses().await?;
+            let new_events = self
+                .new_l1_batch_events(first_l1_batch, &last_statuses, &statuses)
+                .await?;
+            db_latency.observe();
+
+            last_statuses = statuses;
+            if !new_events.is_empty() {
+                let new_events = new_events
+                    .into_iter()
+                    .map(PubSubResult::L1BatchEvent)
+                    .collect();
+                self.send_pub_sub_results(new_events, SubscriptionType::L1BatchEvents);
+            }
+            self.emit_event(PubSubEvent::NotifyIterationFinished(
+                SubscriptionType::L1BatchEvents,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the last L1 batch for each lifecycle stage, in the order of [`L1BatchEventKind`]s.
+    async fn l1_batch_statuses(
+        &self,
+    ) -> anyhow::Result<[(L1BatchEventKind, Option<L1BatchNumber>); 4]> {
+        let mut storage = self.connection_pool.connection_tagged("api").await?;
+        let mut blocks_dal = storage.blocks_dal();
+        Ok([
+            (
+                L1BatchEventKind::Sealed,
+                blocks_dal.get_sealed_l1_batch_number().await?,
+            ),
+            (
+                L1BatchEventKind::Committed,
+                blocks_dal
+                    .get_number_of_last_l1_batch_committed_on_eth()
+                    .await?,
+            ),
+            (
+                L1BatchEventKind::Proven,
+                blocks_dal
+                    .get_number_of_last_l1_batch_proven_on_eth()
+                    .await?,
+            ),
+            (
+                L1BatchEventKind::Executed,
+                blocks_dal
+                    .get_number_of_last_l1_batch_executed_on_eth()
+                    .await?,
+            ),
+        ])
+    }
+
+    async fn new_l1_batch_events(
+        &self,
+        first_l1_batch: L1BatchNumber,
+        last_statuses: &[(L1BatchEventKind, Option<L1BatchNumber>)],
+        statuses: &[(L1BatchEventKind, Option<L1BatchNumber>)],
+    ) -> anyhow::Result<Vec<L1BatchEvent>> {
+        let mut storage = self.connection_pool.connection_tagged("api").await?;
+        let mut events = vec![];
+        for (&(kind, last_number), &(_, number)) in last_statuses.iter().zip(statuses) {
+            let Some(number) = number else {
+                continue;
+            };
+            let first_new_number = last_number.map_or(first_l1_batch, |number| number + 1);
+            for l1_batch_number in first_new_number.0..=number.0 {
+                let l1_batch_number = L1BatchNumber(l1_batch_number);
+                let l1_tx_hash = if kind == L1BatchEventKind::Sealed {
+                    None
+                } else {
+                    let details = storage
+                        .blocks_web3_dal()
+                        .get_l1_batch_details(l1_batch_number)
+                        .await?;
+                    details.and_then(|details| match kind {
+                        L1BatchEventKind::Committed => details.base.commit_tx_hash,
+                        L1BatchEventKind::Proven => details.base.prove_tx_hash,
+                        L1BatchEventKind::Executed => details.base.execute_tx_hash,
+                        L1BatchEventKind::Sealed => None,
+                    })
+                };
+                events.push(L1BatchEvent {
+                    l1_batch_number,
+                    event: kind,
+                    l1_tx_hash,
+                });
+            }
+        }
+        events.sort_by_key(|event| event.l1_batch_number);
+        Ok(events)
+    }
 }
 
 /// Subscription support for Web3 APIs.
+#[derive(Clone)]
 pub(super) struct EthSubscribe {
     blocks: broadcast::Sender<Vec<PubSubResult>>,
     transactions: broadcast::Sender<Vec<PubSubResult>>,
     logs: broadcast::Sender<Vec<PubSubResult>>,
+    l1_batch_events: broadcast::Sender<Vec<PubSubResult>>,
+    connection_pool: ConnectionPool<Core>,
+    buffer_capacity: usize,
+    slow_subscriber_policy: SlowSubscriberPolicy,
+    max_buffered_notifications_per_connection: usize,
+    connection_budgets: ConnectionBudgets,
+    logs_replay_limit: usize,
     events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
 
 impl EthSubscribe {
-    pub fn new() -> Self {
+    pub fn new(connection_pool: ConnectionPool<Core>) -> Self {
         let (blocks, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
         let (transactions, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
         let (logs, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
+        let (l1_batch_events, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
 
         Self {
             blocks,
             transactions,
             logs,
+            l1_batch_events,
+            connection_pool,
+            buffer_capacity: DEFAULT_SUBSCRIPTION_BUFFER_CAPACITY,
+            slow_subscriber_policy: SlowSubscriberPolicy::default(),
+            max_buffered_notifications_per_connection:
+                DEFAULT_MAX_BUFFERED_NOTIFICATIONS_PER_CONNECTION,
+            connection_budgets: ConnectionBudgets::default(),
+            logs_replay_limit: DEFAULT_LOGS_REPLAY_LIMIT,
             events_sender: None,
         }
     }
 
+    /// Sets the capacity of notification buffers for individual subscriptions, and the policy applied
+    /// to subscriptions with a full buffer.
+    pub fn set_buffer_limits(&mut self, capacity: NonZeroUsize, policy: SlowSubscriberPolicy) {
+        self.buffer_capacity = capacity.get();
+        self.slow_subscriber_policy = policy;
+    }
+
+    /// Sets the maximum total number of notifications buffered for all subscriptions of a single connection.
+    pub fn set_connection_buffer_limit(&mut self, limit: NonZeroUsize) {
+        self.max_buffered_notifications_per_connection = limit.get();
+    }
+
+    /// Sets the maximum number of logs replayed by `zks_subscribeLogsFrom` subscriptions. Should be set to
+    /// the `eth_getLogs` limit (`req_entities_limit`).
+    pub fn set_logs_replay_limit(&mut self, limit: usize) {
+        self.logs_replay_limit = limit;
+    }
+
     pub fn set_events_sender(&mut self, sender: mpsc::UnboundedSender<PubSubEvent>) {
         self.events_sender = Some(sender);
     }
@@ -266,18 +732,86 @@ impl EthSubscribe {
         .await;
     }
 
+    fn new_buffer(
+        &self,
+        subscription_type: SubscriptionType,
+        sink: &SubscriptionSink,
+    ) -> SubscriptionBuffer {
+        let mut hasher = DefaultHasher::new();
+        sink.connection_id().hash(&mut hasher);
+        let connection_key = hasher.finish();
+
+        let used = self
+            .connection_budgets
+            .lock()
+            .expect("connection budgets are poisoned")
+            .entry(connection_key)
+            .or_default()
+            .clone();
+        let budget = ConnectionBudget {
+            limit: self.max_buffered_notifications_per_connection,
+            used,
+            registry: Some((self.connection_budgets.clone(), connection_key)),
+        };
+        SubscriptionBuffer::new(
+            subscription_type,
+            self.buffer_capacity,
+            self.slow_subscriber_policy,
+        )
+        .with_connection_budget(budget)
+    }
+
     async fn run_subscriber(
+        mut buffer: SubscriptionBuffer,
         sink: SubscriptionSink,
-        subscription_type: SubscriptionType,
         mut receiver: broadcast::Receiver<Vec<PubSubResult>>,
         filter: Option<PubSubFilter>,
+        replay: Option<LogsReplay>,
     ) {
+        let subscription_type = buffer.subscription_type;
         let _guard = PUB_SUB_METRICS.active_subscribers[&subscription_type].inc_guard(1);
         let lifetime_latency = PUB_SUB_METRICS.subscriber_lifetime[&subscription_type].start();
+
+        let mut first_live_block = L2BlockNumber(0);
+        if let Some(replay) = replay {
+            let filter = filter.clone().unwrap_or_default();
+            match replay.run(&sink, &mut receiver, &filter).await {
+                Ok(Some(next_block)) => first_live_block = next_block,
+                Ok(None) => {
+                    lifetime_latency.observe();
+                    return;
+                }
+                Err(err) => {
+                    tracing::warn!("Failed replaying logs, closing subscription: {err:#}");
+                    lifetime_latency.observe();
+                    return;
+                }
+            }
+        }
         let closed = sink.closed().fuse();
         tokio::pin!(closed);
 
         loop {
+            let next_message = buffer.front().map(|item| {
+                SubscriptionMessage::from_json(item)
+                    .expect("PubSubResult always serializable to json;qed")
+            });
+            // Sending to the sink is cancellation-safe: if another `select!` branch completes first,
+            // the message is not sent and remains in the buffer.
+            let send_next = async {
+                match next_message {
+                    Some(message) => sink.send(message).await,
+                    None => future::pending().await,
+                }
+            };
+            let send_deadline = buffer.send_deadline();
+            let send_timeout = async {
+                match send_deadline {
+                    Some(deadline) => tokio::time::sleep_until(deadline).await,
+                    None => future::pending().await,
+                }
+            };
+
             tokio::select! {
                 new_items_result = receiver.recv() => {
                     let new_items = match new_items_result {
@@ -288,25 +822,36 @@ impl EthSubscribe {
                             break;
                         }
                         Err(broadcast::error::RecvError::Lagged(message_count)) => {
-                            PUB_SUB_METRICS
-                                .skipped_broadcast_messages[&subscription_type]
-                                .observe(message_count);
+                            if buffer.handle_lag(message_count) {
+                                continue;
+                            }
                             break;
                         }
                     };
 
-                    let handle_result = Self::handle_new_items(
-                        &sink,
-                        subscription_type,
-                        new_items,
-                        filter.as_ref()
-                    )
-                    .await;
-                    if handle_result.is_err() {
-                        PUB_SUB_METRICS.subscriber_send_timeouts[&subscription_type].inc();
+                    let mut new_items = new_items.into_iter().filter(|item| {
+                        Self::matches_filter(item, filter.as_ref(), first_live_block)
+                    });
+                    if !new_items.all(|item| buffer.push(item)) {
                         break;
                     }
                 }
+                send_result = send_next => {
+                    if send_result.is_err() {
+                        // The subscription is closed by the client.
+                        break;
+                    }
+                    buffer.pop_front();
+                    PUB_SUB_METRICS.notify[&subscription_type].inc();
+                }
+                () = send_timeout => {
+                    tracing::debug!(
+                        "Timed out sending notification to subscription {:?}; closing it",
+                        sink.subscription_id()
+                    );
+                    PUB_SUB_METRICS.closed_slow_subscribers[&subscription_type].inc();
+                    break;
+                }
                 _ = &mut closed => {
                     break;
                 }
@@ -315,34 +860,21 @@ impl EthSubscribe {
         lifetime_latency.observe();
     }
 
-    async fn handle_new_items(
-        sink: &SubscriptionSink,
-        subscription_type: SubscriptionType,
-        new_items: Vec<PubSubResult>,
+    fn matches_filter(
+        item: &PubSubResult,
         filter: Option<&PubSubFilter>,
-    ) -> Result<(), SendTimeoutError> {
-        let notify_latency = PUB_SUB_METRICS.notify_subscribers_latency[&subscription_type].start();
-        for item in new_items {
-            if let PubSubResult::Log(log) = &item {
-                if let Some(filter) = &filter {
-                    if !filter.matches(log) {
-                        continue;
-                    }
-                }
+        first_block: L2BlockNumber,
+    ) -> bool {
+        match item {
+            PubSubResult::Log(log) => {
+                // Logs from L2 blocks before `first_block` were already replayed.
+                let is_new = log
+                    .block_number
+                    .map_or(true, |number| number.as_u32() >= first_block.0);
+                is_new && filter.map_or(true, |filter| filter.matches(log))
             }
-
-            sink.send_timeout(
-                SubscriptionMessage::from_json(&item)
-                    .expect("PubSubResult always serializable to json;qed"),
-                SUBSCRIPTION_SINK_SEND_TIMEOUT,
-            )
-            .await?;
-
-            PUB_SUB_METRICS.notify[&subscription_type].inc();
+            _ => true,
         }
-
-        notify_latency.observe();
-        Ok(())
     }
 
     #[tracing::instrument(level = "debug", skip(self, pending_sink))]
@@ -359,8 +891,14 @@ impl EthSubscribe {
                 };
                 let blocks_rx = self.blocks.subscribe();
                 tokio::spawn(
-                    Self::run_subscriber(sink, SubscriptionType::Blocks, blocks_rx, None)
-                        .in_current_span(),
+                    Self::run_subscriber(
+                        self.new_buffer(SubscriptionType::Blocks, &sink),
+                        sink,
+                        blocks_rx,
+                        None,
+                        None,
+                    )
+                    .in_current_span(),
                 );
 
                 Some(SubscriptionType::Blocks)
@@ -371,8 +909,14 @@ impl EthSubscribe {
                 };
                 let transactions_rx = self.transactions.subscribe();
                 tokio::spawn(
-                    Self::run_subscriber(sink, SubscriptionType::Txs, transactions_rx, None)
-                        .in_current_span(),
+                    Self::run_subscriber(
+                        self.new_buffer(SubscriptionType::Txs, &sink),
+                        sink,
+                        transactions_rx,
+                        None,
+                        None,
+                    )
+                    .in_current_span(),
                 );
                 Some(SubscriptionType::Txs)
             }
@@ -389,8 +933,14 @@ impl EthSubscribe {
                     };
                     let logs_rx = self.logs.subscribe();
                     tokio::spawn(
-                        Self::run_subscriber(sink, SubscriptionType::Logs, logs_rx, Some(filter))
-                            .in_current_span(),
+                        Self::run_subscriber(
+                            self.new_buffer(SubscriptionType::Logs, &sink),
+                            sink,
+                            logs_rx,
+                            Some(filter),
+                            None,
+                        )
+                        .in_current_span(),
                     );
                     Some(SubscriptionType::Logs)
                 }
@@ -422,18 +972,152 @@ impl EthSubscribe {
         }
     }
 
+    #[tracing::instrument(level = "debug", skip(self, pending_sink))]
+    pub async fn sub_l1_batch_events(&self, pending_sink: PendingSubscriptionSink) {
+        let Ok(sink) = pending_sink.accept().await else {
+            return;
+        };
+        let l1_batch_events_rx = self.l1_batch_events.subscribe();
+        tokio::spawn(
+            Self::run_subscriber(
+                self.new_buffer(SubscriptionType::L1BatchEvents, &sink),
+                sink,
+                l1_batch_events_rx,
+                None,
+                None,
+            )
+            .in_current_span(),
+        );
+
+        if let Some(sender) = &self.events_sender {
+            sender
+                .send(PubSubEvent::Subscribed(SubscriptionType::L1BatchEvents))
+                .ok();
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip(self, pending_sink))]
+    pub async fn sub_logs_from(
+        &self,
+        pending_sink: PendingSubscriptionSink,
+        from_block: L2BlockNumber,
+        filter: Option<PubSubFilter>,
+    ) {
+        let filter = filter.unwrap_or_default();
+        let topic_count = filter.topics.as_ref().map_or(0, Vec::len);
+        if topic_count > EVENT_TOPIC_NUMBER_LIMIT {
+            Self::reject(pending_sink).await;
+            return;
+        }
+
+        let rejection_reason = match self.validate_logs_replay(from_block, &filter).await {
+            Ok(reason) => reason,
+            Err(err) => {
+                tracing::warn!("Failed validating logs replay: {err:#}");
+                pending_sink
+                    .reject(ErrorObject::borrowed(
+                        ErrorCode::InternalError.code(),
+                        "Internal error",
+                        None,
+                    ))
+                    .await;
+                return;
+            }
+        };
+        if let Some(reason) = rejection_reason {
+            pending_sink
+                .reject(ErrorObject::owned(
+                    ErrorCode::InvalidParams.code(),
+                    format!("Rejecting subscription - {reason}"),
+                    None::<()>,
+                ))
+                .await;
+            return;
+        }
+
+        let Ok(sink) = pending_sink.accept().await else {
+            return;
+        };
+        // Live notifications must be subscribed to before the replay starts so that no logs are missed.
+        let logs_rx = self.logs.subscribe();
+        let replay = LogsReplay {
+            connection_pool: self.connection_pool.clone(),
+            from_block,
+        };
+        tokio::spawn(
+            Self::run_subscriber(
+                self.new_buffer(SubscriptionType::Logs, &sink),
+                sink,
+                logs_rx,
+                Some(filter),
+                Some(replay),
+            )
+            .in_current_span(),
+        );
+
+        if let Some(sender) = &self.events_sender {
+            sender
+                .send(PubSubEvent::Subscribed(SubscriptionType::Logs))
+                .ok();
+        }
+    }
+
+    /// Checks whether logs can be replayed from the specified L2 block. Returns the reason to reject
+    /// the subscription, if any.
+    ///
+    /// The replay is subject to the same limits as `eth_getLogs`: unless it covers a single L2 block, it must return
+    /// at most `req_entities_limit` logs. Otherwise, clients should fetch older logs using `eth_getLogs` and subscribe
+    /// from a later L2 block.
+    async fn validate_logs_replay(
+        &self,
+        from_block: L2BlockNumber,
+        filter: &PubSubFilter,
+    ) -> anyhow::Result<Option<String>> {
+        let mut storage = self.connection_pool.connection_tagged("api").await?;
+        let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        let first_retained_block = pruning_info
+            .last_soft_pruned
+            .map_or(L2BlockNumber(0), |info| info.l2_block + 1);
+        if from_block < first_retained_block {
+            return Ok(Some(format!(
+                "L2 block #{from_block} is pruned; first retained L2 block is #{first_retained_block}"
+            )));
+        }
+
+        let sealed_l2_block = storage.blocks_dal().get_sealed_l2_block_number().await?;
+        let Some(sealed_l2_block) = sealed_l2_block.filter(|&number| number > from_block) else {
+            return Ok(None);
+        };
+        let filter = Filter {
+            address: filter.address.clone(),
+            topics: filter.topics.clone(),
+            ..Filter::default()
+        };
+        let get_logs_filter = get_logs_filter(&filter, from_block, sealed_l2_block)?;
+        let limit_block = storage
+            .events_web3_dal()
+            .get_log_block_number(&get_logs_filter, self.logs_replay_limit)
+            .await?;
+        Ok(limit_block.map(|limit_block| {
+            format!(
+                "replaying logs from L2 block #{from_block} would return more than {} results; \
+                 get logs for L2 blocks before #{limit_block} using `eth_getLogs`",
+                self.logs_replay_limit
+            )
+        }))
+    }
+
     /// Spawns notifier tasks. This should be called once per instance.
     pub fn spawn_notifiers(
         &self,
-        connection_pool: ConnectionPool<Core>,
         polling_interval: Duration,
         stop_receiver: watch::Receiver<bool>,
     ) -> Vec<JoinHandle<anyhow::Result<()>>> {
-        let mut notifier_tasks = Vec::with_capacity(3);
+        let mut notifier_tasks = Vec::with_capacity(4);
 
         let notifier = PubSubNotifier {
             sender: self.blocks.clone(),
-            connection_pool: connection_pool.clone(),
+            connection_pool: self.connection_pool.clone(),
             polling_interval,
             events_sender: self.events_sender.clone(),
         };
@@ -442,7 +1126,7 @@ impl EthSubscribe {
 
         let notifier = PubSubNotifier {
             sender: self.transactions.clone(),
-            connection_pool: connection_pool.clone(),
+            connection_pool: self.connection_pool.clone(),
             polling_interval,
             events_sender: self.events_sender.clone(),
         };
@@ -451,11 +1135,20 @@ impl EthSubscribe {
 
         let notifier = PubSubNotifier {
             sender: self.logs.clone(),
-            connection_pool,
+            connection_pool: self.connection_pool.clone(),
             polling_interval,
             events_sender: self.events_sender.clone(),
         };
-        let notifier_task = tokio::spawn(notifier.notify_logs(stop_receiver));
+        let notifier_task = tokio::spawn(notifier.notify_logs(stop_receiver.clone()));
+        notifier_tasks.push(notifier_task);
+
+        let notifier = PubSubNotifier {
+            sender: self.l1_batch_events.clone(),
+            connection_pool: self.connection_pool.clone(),
+            polling_interval,
+            events_sender: self.events_sender.clone(),
+        };
+        let notifier_task = tokio::spawn(notifier.notify_l1_batch_events(stop_receiver));
 
         notifier_tasks.push(notifier_task);
         notifier_tasks
@@ -474,3 +1167,21 @@ impl EthPubSubServer for EthSubscribe {
         Ok(())
     }
 }
+
+#[async_trait::async_trait]
+impl ZksPubSubServer for EthSubscribe {
+    async fn subscribe_batch_events(&self, pending: PendingSubscriptionSink) -> SubscriptionResult {
+        self.sub_l1_batch_events(pending).await;
+        Ok(())
+    }
+
+    async fn subscribe_logs_from(
+        &self,
+        pending: PendingSubscriptionSink,
+        from_block: L2BlockNumber,
+        filter: Option<PubSubFilter>,
+    ) -> SubscriptionResult {
+        self.sub_logs_from(pending, from_block, filter).await;
+        Ok(())
+    }
+}
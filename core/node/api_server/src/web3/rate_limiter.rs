@@ -0,0 +1,365 @@
+//! Per-client rate limiting for Web3 API servers.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    net::IpAddr,
+    sync::Arc,
+    time::Duration,
+};
+
+use governor::{DefaultDirectRateLimiter, DefaultKeyedRateLimiter, Quota, RateLimiter};
+use http::HeaderMap;
+use tokio::sync::watch;
+use zksync_config::configs::api::Web3RateLimitsConfig;
+
+use super::metrics::{MethodCost, RateLimitLabels, RateLimitedBy, RATE_LIMIT_METRICS};
+
+/// Header containing the API key of the client.
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// Information about the client sending a request, extracted from HTTP headers. For WebSocket connections,
+/// headers of the upgrade request are used.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ClientInfo {
+    ip: Option<IpAddr>,
+    api_key: Option<String>,
+}
+
+impl ClientInfo {
+    /// Extracts client info from request headers. With `trusted_proxy_hops` reverse proxies in front of the server,
+    /// the client IP address is the `trusted_proxy_hops`-th entry from the right in `x-forwarded-for`; entries
+    /// to the left of it are controlled by the client and are ignored. If `trusted_proxy_hops` is 0, or the header
+    /// contains fewer entries (i.e., the request has bypassed some of the proxies), the IP address is not extracted.
+    pub(crate) fn from_headers(headers: &HeaderMap, trusted_proxy_hops: usize) -> Self {
+        let ip = trusted_proxy_hops
+            .checked_sub(1)
+            .and_then(|idx_from_right| Self::extract_ip(headers, idx_from_right));
+        let api_key = headers
+            .get(API_KEY_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned);
+        Self { ip, api_key }
+    }
+
+    fn extract_ip(headers: &HeaderMap, idx_from_right: usize) -> Option<IpAddr> {
+        let mut forwarded_values = headers.get_all("x-forwarded-for").iter().peekable();
+        if forwarded_values.peek().is_some() {
+            // Proxies may either append to the existing header or add a new one, so all headers are considered.
+            let values: Vec<_> = forwarded_values
+                .map(|value| value.to_str().ok())
+                .collect::<Option<_>>()?;
+            let entries = values.iter().flat_map(|value| value.split(','));
+            entries.rev().nth(idx_from_right)?.trim().parse().ok()
+        } else if idx_from_right == 0 {
+            // `x-real-ip` is set by the proxy directly connected to the server.
+            headers.get("x-real-ip")?.to_str().ok()?.trim().parse().ok()
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn ip(&self) -> Option<IpAddr> {
+        self.ip
+    }
+
+    pub(crate) fn api_key(&self) -> Option<&str> {
+        self.api_key.as_deref()
+    }
+}
+
+#[derive(Debug, Default)]
+struct MethodClassifier {
+    expensive_methods: HashSet<String>,
+    expensive_prefixes: Vec<String>,
+}
+
+impl MethodClassifier {
+    fn new(methods: Vec<String>) -> Self {
+        let mut this = Self::default();
+        for method in methods {
+            if let Some(prefix) = method.strip_suffix('*') {
+                this.expensive_prefixes.push(prefix.to_owned());
+            } else {
+                this.expensive_methods.insert(method);
+            }
+        }
+        this
+    }
+
+    fn cost(&self, method: &str) -> MethodCost {
+        let is_expensive = self.expensive_methods.contains(method)
+            || self
+                .expensive_prefixes
+                .iter()
+                .any(|prefix| method.starts_with(prefix.as_str()));
+        if is_expensive {
+            MethodCost::Expensive
+        } else {
+            MethodCost::Cheap
+        }
+    }
+}
+
+struct ApiKeyLimiters {
+    cheap: DefaultDirectRateLimiter,
+    expensive: DefaultDirectRateLimiter,
+}
+
+struct Inner {
+    classifier: MethodClassifier,
+    trusted_proxy_hops: usize,
+    cheap_by_ip: DefaultKeyedRateLimiter<IpAddr>,
+    expensive_by_ip: DefaultKeyedRateLimiter<IpAddr>,
+    /// Shared limiters for clients without a known API key and without an identifiable IP address.
+    anonymous: ApiKeyLimiters,
+    api_keys: HashMap<String, ApiKeyLimiters>,
+}
+
+impl fmt::Debug for Inner {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Inner")
+            .field("classifier", &self.classifier)
+            .field("trusted_proxy_hops", &self.trusted_proxy_hops)
+            .field("api_keys_count", &self.api_keys.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Rate limiter shared among all Web3 API servers.
+///
+/// Requests are split into cheap and expensive ones depending on the called method; each kind has a separate quota.
+/// Clients with a known API key use quotas configured for the key; other clients are limited by their IP address.
+/// Requests without a known API key and without an identifiable IP address share a single anonymous quota,
+/// so that they cannot bypass rate limiting.
+#[derive(Debug, Clone)]
+pub struct ApiRateLimiter(Arc<Inner>);
+
+impl ApiRateLimiter {
+    pub fn new(config: &Web3RateLimitsConfig) -> Self {
+        let api_keys = config
+            .api_keys
+            .iter()
+            .map(|quota| {
+                let limiters = ApiKeyLimiters {
+                    cheap: RateLimiter::direct(Quota::per_minute(quota.requests_per_minute)),
+                    expensive: RateLimiter::direct(Quota::per_minute(
+                        quota.expensive_requests_per_minute,
+                    )),
+                };
+                (quota.key.clone(), limiters)
+            })
+            .collect();
+
+        Self(Arc::new(Inner {
+            classifier: MethodClassifier::new(config.expensive_methods()),
+            trusted_proxy_hops: config.trusted_proxy_hops,
+            cheap_by_ip: RateLimiter::keyed(Quota::per_minute(config.requests_per_minute_per_ip)),
+            expensive_by_ip: RateLimiter::keyed(Quota::per_minute(
+                config.expensive_requests_per_minute_per_ip,
+            )),
+            anonymous: ApiKeyLimiters {
+                cheap: RateLimiter::direct(Quota::per_minute(config.requests_per_minute_per_ip)),
+                expensive: RateLimiter::direct(Quota::per_minute(
+                    config.expensive_requests_per_minute_per_ip,
+                )),
+            },
+            api_keys,
+        }))
+    }
+
+    /// Returns the number of trusted reverse proxies used to extract [`ClientInfo`] from request headers.
+    pub(crate) fn trusted_proxy_hops(&self) -> usize {
+        self.0.trusted_proxy_hops
+    }
+
+    /// Checks whether the API key is configured for the limiter. Unknown keys are self-asserted by clients
+    /// and must not be used to identify them.
+    pub(crate) fn is_known_api_key(&self, key: &str) -> bool {
+        self.0.api_keys.contains_key(key)
+    }
+
+    /// Checks whether a call to `method` by the specified client fits into the client's quota.
+    /// Rejections are reported as metrics.
+    pub(crate) fn check(&self, client: Option<&ClientInfo>, method: &str) -> bool {
+        let cost = self.0.classifier.cost(method);
+        let api_key_limiters = client
+            .and_then(|client| client.api_key.as_ref())
+            .and_then(|key| self.0.api_keys.get(key));
+        let (is_allowed, limited_by) = if let Some(limiters) = api_key_limiters {
+            let limiter = match cost {
+                MethodCost::Cheap => &limiters.cheap,
+                MethodCost::Expensive => &limiters.expensive,
+            };
+            (limiter.check().is_ok(), RateLimitedBy::ApiKey)
+        } else if let Some(ip) = client.and_then(|client| client.ip.as_ref()) {
+            let limiter = match cost {
+                MethodCost::Cheap => &self.0.cheap_by_ip,
+                MethodCost::Expensive => &self.0.expensive_by_ip,
+            };
+            (limiter.check_key(ip).is_ok(), RateLimitedBy::Ip)
+        } else {
+            let limiter = match cost {
+                MethodCost::Cheap => &self.0.anonymous.cheap,
+                MethodCost::Expensive => &self.0.anonymous.expensive,
+            };
+            (limiter.check().is_ok(), RateLimitedBy::Anonymous)
+        };
+
+        if !is_allowed {
+            let labels = RateLimitLabels { limited_by, cost };
+            RATE_LIMIT_METRICS.rejected[&labels].inc();
+        }
+        is_allowed
+    }
+
+    /// Returns a task that will periodically remove stale per-IP limiter states.
+    pub fn cleanup_task(&self, interval: Duration) -> ApiRateLimiterCleanupTask {
+        ApiRateLimiterCleanupTask {
+            inner: self.0.clone(),
+            interval,
+        }
+    }
+}
+
+/// Task cleaning up [`ApiRateLimiter`] state. Should be spawned as a Tokio task (exactly one task for the limiter).
+#[derive(Debug)]
+pub struct ApiRateLimiterCleanupTask {
+    inner: Arc<Inner>,
+    interval: Duration,
+}
+
+impl ApiRateLimiterCleanupTask {
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow() {
+            for limiter in [&self.inner.cheap_by_ip, &self.inner.expensive_by_ip] {
+                limiter.retain_recent();
+                limiter.shrink_to_fit();
+            }
+            RATE_LIMIT_METRICS
+                .tracked_ips
+                .set(self.inner.cheap_by_ip.len() + self.inner.expensive_by_ip.len());
+
+            // Error here corresponds to a timeout w/o `stop_receiver` changed; we're OK with this.
+            tokio::time::timeout(self.interval, stop_receiver.changed())
+                .await
+                .ok();
+        }
+        tracing::debug!("Stopping API rate limiter cleanup");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use zksync_config::configs::api::ApiKeyQuota;
+
+    use super::*;
+
+    fn client(ip: &str, api_key: Option<&str>) -> ClientInfo {
+        ClientInfo {
+            ip: Some(ip.parse().unwrap()),
+            api_key: api_key.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn extracting_client_info() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(ClientInfo::from_headers(&headers, 1), ClientInfo::default());
+
+        headers.insert("x-real-ip", "10.0.0.1".parse().unwrap());
+        assert_eq!(
+            ClientInfo::from_headers(&headers, 1),
+            client("10.0.0.1", None)
+        );
+        assert_eq!(ClientInfo::from_headers(&headers, 0), ClientInfo::default());
+        assert_eq!(ClientInfo::from_headers(&headers, 2), ClientInfo::default());
+
+        // The leftmost address is controlled by the client, so it must not be trusted.
+        headers.insert("x-forwarded-for", "6.6.6.6, 1.2.3.4".parse().unwrap());
+        headers.insert(API_KEY_HEADER, "key".parse().unwrap());
+        assert_eq!(
+            ClientInfo::from_headers(&headers, 1),
+            client("1.2.3.4", Some("key"))
+        );
+        assert_eq!(
+            ClientInfo::from_headers(&headers, 2),
+            client("6.6.6.6", Some("key"))
+        );
+        let info = ClientInfo::from_headers(&headers, 3);
+        assert_eq!(info.ip, None);
+        let info = ClientInfo::from_headers(&headers, 0);
+        assert_eq!(info.ip, None);
+
+        // Addresses appended by the proxies in separate headers.
+        headers.append("x-forwarded-for", "10.0.0.1".parse().unwrap());
+        assert_eq!(
+            ClientInfo::from_headers(&headers, 2),
+            client("1.2.3.4", Some("key"))
+        );
+    }
+
+    #[test]
+    fn classifying_methods() {
+        let config = Web3RateLimitsConfig {
+            requests_per_minute_per_ip: NonZeroU32::MIN,
+            expensive_requests_per_minute_per_ip: NonZeroU32::MIN,
+            expensive_methods: vec![],
+            api_keys: vec![],
+            trusted_proxy_hops: 1,
+        };
+        let classifier = MethodClassifier::new(config.expensive_methods());
+        assert_eq!(classifier.cost("eth_call"), MethodCost::Expensive);
+        assert_eq!(classifier.cost("debug_traceCall"), MethodCost::Expensive);
+        assert_eq!(classifier.cost("zks_getProof"), MethodCost::Expensive);
+        assert_eq!(classifier.cost("eth_blockNumber"), MethodCost::Cheap);
+        assert_eq!(classifier.cost("eth_callMany"), MethodCost::Cheap);
+    }
+
+    #[test]
+    fn limiting_requests() {
+        let config = Web3RateLimitsConfig {
+            requests_per_minute_per_ip: NonZeroU32::new(2).unwrap(),
+            expensive_requests_per_minute_per_ip: NonZeroU32::MIN,
+            expensive_methods: vec![],
+            api_keys: vec![ApiKeyQuota {
+                key: "key".to_owned(),
+                requests_per_minute: NonZeroU32::new(5).unwrap(),
+                expensive_requests_per_minute: NonZeroU32::new(3).unwrap(),
+            }],
+            trusted_proxy_hops: 1,
+        };
+        let limiter = ApiRateLimiter::new(&config);
+
+        let ip_client = client("1.2.3.4", None);
+        assert!(limiter.check(Some(&ip_client), "eth_call"));
+        assert!(!limiter.check(Some(&ip_client), "eth_call"));
+        // Cheap methods have a separate quota.
+        assert!(limiter.check(Some(&ip_client), "eth_blockNumber"));
+        assert!(limiter.check(Some(&ip_client), "eth_blockNumber"));
+        assert!(!limiter.check(Some(&ip_client), "eth_blockNumber"));
+        // Other IPs are not affected.
+        assert!(limiter.check(Some(&client("1.2.3.5", None)), "eth_call"));
+
+        // API key quotas are not affected by IP limits.
+        let key_client = client("1.2.3.4", Some("key"));
+        for _ in 0..3 {
+            assert!(limiter.check(Some(&key_client), "eth_call"));
+        }
+        assert!(!limiter.check(Some(&key_client), "eth_call"));
+        // Unknown API keys are treated as missing.
+        assert!(!limiter.check(Some(&client("1.2.3.4", Some("unknown"))), "eth_call"));
+
+        // Unidentified clients share the anonymous quota.
+        assert!(limiter.check(Some(&ClientInfo::default()), "eth_call"));
+        assert!(!limiter.check(Some(&ClientInfo::default()), "eth_call"));
+        assert!(!limiter.check(None, "eth_call"));
+        assert!(limiter.check(None, "eth_blockNumber"));
+        assert!(limiter.check(Some(&ClientInfo::default()), "eth_blockNumber"));
+        assert!(!limiter.check(None, "eth_blockNumber"));
+    }
+}
@@ -0,0 +1,237 @@
+//! Lag-aware routing of read queries among multiple Postgres replicas.
+
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use tokio::sync::watch;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalResult};
+use zksync_types::L2BlockNumber;
+
+use super::metrics::REPLICA_METRICS;
+
+/// Lag value used for replicas that are considered unhealthy.
+const UNHEALTHY_LAG: u64 = u64::MAX;
+
+struct Replica {
+    pool: ConnectionPool<Core>,
+    /// Lag behind the most up-to-date replica in L2 blocks, or [`UNHEALTHY_LAG`] if the replica is unhealthy.
+    lag: AtomicU64,
+}
+
+impl Replica {
+    fn lag(&self) -> u64 {
+        self.lag.load(Ordering::Relaxed)
+    }
+
+    fn set_lag(&self, idx: usize, lag: u64) {
+        self.lag.store(lag, Ordering::Relaxed);
+        let is_healthy = lag != UNHEALTHY_LAG;
+        REPLICA_METRICS.healthy[&idx.to_string()].set(is_healthy.into());
+        if is_healthy {
+            REPLICA_METRICS.lag[&idx.to_string()].set(lag);
+        }
+    }
+}
+
+impl fmt::Debug for Replica {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Replica")
+            .field("pool", &self.pool)
+            .field("lag", &self.lag())
+            .finish()
+    }
+}
+
+/// Set of replica connection pools used by Web3 API servers.
+///
+/// Connections are acquired from the healthy replica with the least lag; if acquiring a connection fails,
+/// the replica is marked as unhealthy and other replicas are tried in the order of increasing lag.
+/// Replica lags are refreshed by [`ReplicaLagProbeTask`], which also brings unhealthy replicas back.
+#[derive(Debug, Clone)]
+pub struct ReplicaPools(Arc<[Replica]>);
+
+impl ReplicaPools {
+    /// Creates replica pools. Initially, all replicas are considered healthy and up-to-date.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pools` is empty.
+    pub fn new(pools: Vec<ConnectionPool<Core>>) -> Self {
+        assert!(
+            !pools.is_empty(),
+            "At least one replica pool must be provided"
+        );
+        let replicas = pools.into_iter().map(|pool| Replica {
+            pool,
+            lag: AtomicU64::new(0),
+        });
+        Self(replicas.collect())
+    }
+
+    /// Returns replica indices ordered by increasing lag. Unhealthy replicas go last.
+    fn ordered_replicas(&self) -> Vec<usize> {
+        let mut indices: Vec<_> = (0..self.0.len()).collect();
+        indices.sort_by_key(|&idx| self.0[idx].lag());
+        indices
+    }
+
+    pub(crate) async fn connection_tagged(
+        &self,
+        requester: &'static str,
+    ) -> DalResult<Connection<'static, Core>> {
+        let mut last_err = None;
+        for (attempt, idx) in self.ordered_replicas().into_iter().enumerate() {
+            let replica = &self.0[idx];
+            match replica.pool.connection_tagged(requester).await {
+                Ok(connection) => {
+                    if attempt > 0 {
+                        REPLICA_METRICS.fallbacks.inc();
+                    }
+                    return Ok(connection);
+                }
+                Err(err) => {
+                    tracing::warn!("Failed acquiring connection to replica #{idx}, marking it as unhealthy: {err}");
+                    replica.set_lag(idx, UNHEALTHY_LAG);
+                    last_err = Some(err);
+                }
+            }
+        }
+        // `unwrap()` is safe: there's at least one replica, so we've either returned or recorded an error
+        Err(last_err.unwrap())
+    }
+
+    /// Returns a task that will periodically probe replica health and lag.
+    pub fn lag_probe_task(&self, interval: Duration) -> ReplicaLagProbeTask {
+        ReplicaLagProbeTask {
+            replicas: self.clone(),
+            interval,
+        }
+    }
+
+    async fn probe_replica(replica: &Replica, timeout: Duration) -> anyhow::Result<L2BlockNumber> {
+        let probe = async {
+            let mut connection = replica.pool.connection_tagged("api_replica_probe").await?;
+            let block_number = connection.blocks_dal().get_sealed_l2_block_number().await?;
+            anyhow::Ok(block_number.unwrap_or(L2BlockNumber(0)))
+        };
+        tokio::time::timeout(timeout, probe)
+            .await
+            .map_err(|_| anyhow::anyhow!("timed out probing replica"))?
+    }
+
+    async fn update_lags(&self, timeout: Duration) {
+        let probes = self
+            .0
+            .iter()
+            .map(|replica| Self::probe_replica(replica, timeout));
+        let block_numbers = futures::future::join_all(probes).await;
+        let max_block_number = block_numbers
+            .iter()
+            .filter_map(|res| res.as_ref().ok())
+            .max()
+            .copied();
+
+        for (idx, (replica, block_number)) in self.0.iter().zip(block_numbers).enumerate() {
+            match block_number {
+                Ok(block_number) => {
+                    let max_block_number = max_block_number.unwrap_or(block_number);
+                    replica.set_lag(idx, (max_block_number.0 - block_number.0).into());
+                }
+                Err(err) => {
+                    tracing::warn!("Failed probing replica #{idx}: {err:#}");
+                    replica.set_lag(idx, UNHEALTHY_LAG);
+                }
+            }
+        }
+    }
+}
+
+/// Task probing [`ReplicaPools`] health and lag. Should be spawned as a Tokio task (exactly one task for the pools).
+#[derive(Debug)]
+pub struct ReplicaLagProbeTask {
+    replicas: ReplicaPools,
+    interval: Duration,
+}
+
+impl ReplicaLagProbeTask {
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow() {
+            self.replicas.update_lags(self.interval).await;
+            // Error here corresponds to a timeout w/o `stop_receiver` changed; we're OK with this.
+            tokio::time::timeout(self.interval, stop_receiver.changed())
+                .await
+                .ok();
+        }
+        tracing::debug!("Stopping replica lag probing");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_node_test_utils::create_l2_block;
+    use zksync_types::ProtocolVersion;
+
+    use super::*;
+
+    async fn prepare_replica(l2_block_count: u32) -> ConnectionPool<Core> {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in 0..l2_block_count {
+            storage
+                .blocks_dal()
+                .insert_l2_block(&create_l2_block(number))
+                .await
+                .unwrap();
+        }
+        pool
+    }
+
+    #[tokio::test]
+    async fn routing_to_least_lagged_replica() {
+        let stale_replica = prepare_replica(1).await;
+        let fresh_replica = prepare_replica(3).await;
+        let replicas = ReplicaPools::new(vec![stale_replica, fresh_replica]);
+        assert_eq!(replicas.ordered_replicas(), [0, 1]);
+
+        replicas.update_lags(Duration::from_secs(10)).await;
+        assert_eq!(replicas.0[0].lag(), 2);
+        assert_eq!(replicas.0[1].lag(), 0);
+        assert_eq!(replicas.ordered_replicas(), [1, 0]);
+
+        let mut connection = replicas.connection_tagged("test").await.unwrap();
+        let block_number = connection
+            .blocks_dal()
+            .get_sealed_l2_block_number()
+            .await
+            .unwrap();
+        assert_eq!(block_number, Some(L2BlockNumber(2)));
+    }
+
+    #[tokio::test]
+    async fn unhealthy_replicas_go_last() {
+        let replicas = ReplicaPools::new(vec![
+            prepare_replica(1).await,
+            prepare_replica(1).await,
+            prepare_replica(1).await,
+        ]);
+        replicas.0[0].set_lag(0, UNHEALTHY_LAG);
+        replicas.0[2].set_lag(2, 1);
+        assert_eq!(replicas.ordered_replicas(), [1, 2, 0]);
+
+        replicas.update_lags(Duration::from_secs(10)).await;
+        assert_eq!(replicas.ordered_replicas(), [0, 1, 2]);
+    }
+}
@@ -8,7 +8,7 @@ use std::{
 };
 
 use anyhow::Context as _;
-use futures::TryFutureExt;
+use futures::{FutureExt, TryFutureExt};
 use lru::LruCache;
 use tokio::sync::{Mutex, RwLock};
 use vise::GaugeGuard;
@@ -23,12 +23,17 @@ use zksync_types::{
     api, commitment::L1BatchCommitmentMode, l2::L2Tx, transaction_request::CallRequest, Address,
     L1BatchNumber, L1ChainId, L2BlockNumber, L2ChainId, H256, U256, U64,
 };
-use zksync_web3_decl::{error::Web3Error, types::Filter};
+use zksync_web3_decl::{
+    error::{PrunedDataKind, Web3Error},
+    types::Filter,
+};
 
 use super::{
     backend_jsonrpsee::MethodTracer,
+    call_result_cache::CallResultCache,
     mempool_cache::MempoolCache,
     metrics::{FilterType, FILTER_METRICS},
+    replicas::ReplicaPools,
     TypedFilter,
 };
 use crate::{
@@ -63,7 +68,7 @@ impl From<L1BatchNumber> for PruneQuery {
 impl From<BlockArgsError> for Web3Error {
     fn from(value: BlockArgsError) -> Self {
         match value {
-            BlockArgsError::Pruned(l2_block) => Web3Error::PrunedBlock(l2_block),
+            BlockArgsError::Pruned(pruned) => Web3Error::Pruned(pruned),
             BlockArgsError::Missing => Web3Error::NoBlock,
             BlockArgsError::Database(error) => Web3Error::InternalError(error),
         }
@@ -79,9 +84,15 @@ impl BlockStartInfo {
         match query.into() {
             PruneQuery::BlockId(id) => Ok(self.ensure_not_pruned_block(id, storage).await?),
             PruneQuery::L1Batch(number) => {
-                let first_l1_batch = self.first_l1_batch(storage).await?;
-                if number < first_l1_batch {
-                    return Err(Web3Error::PrunedL1Batch(first_l1_batch));
+                let mut pruned = self.pruned_data(PrunedDataKind::L1Batch, storage).await?;
+                if number < pruned.first_retained_l1_batch {
+                    pruned.snapshot_available = storage
+                        .snapshots_dal()
+                        .get_snapshot_metadata(number)
+                        .await
+                        .map_err(DalError::generalize)?
+                        .is_some_and(|snapshot| snapshot.is_complete());
+                    return Err(Web3Error::Pruned(pruned));
                 }
                 Ok(())
             }
@@ -234,6 +245,8 @@ pub(crate) struct RpcState {
     pub(super) current_method: Arc<MethodTracer>,
     pub(super) installed_filters: Option<Arc<Mutex<Filters>>>,
     pub(super) connection_pool: ConnectionPool<Core>,
+    /// If set, connections for read queries are acquired from these pools instead of `connection_pool`.
+    pub(super) replica_pools: Option<ReplicaPools>,
     pub(super) tree_api: Option<Arc<dyn TreeApiClient>>,
     pub(super) tx_sender: TxSender,
     pub(super) sync_state: Option<SyncState>,
@@ -242,6 +255,7 @@ pub(crate) struct RpcState {
     /// from a snapshot.
     pub(super) start_info: BlockStartInfo,
     pub(super) mempool_cache: Option<MempoolCache>,
+    pub(super) call_result_cache: Option<CallResultCache>,
     pub(super) last_sealed_l2_block: SealedL2BlockNumber,
     pub(super) bridge_addresses_handle: BridgeAddressesHandle,
 }
@@ -283,9 +297,11 @@ impl RpcState {
     pub(crate) fn acquire_connection(
         &self,
     ) -> impl Future<Output = Result<Connection<'static, Core>, Web3Error>> + '_ {
-        self.connection_pool
-            .connection_tagged("api")
-            .map_err(|err| err.generalize().into())
+        let connection = match &self.replica_pools {
+            Some(replica_pools) => replica_pools.connection_tagged("api").left_future(),
+            None => self.connection_pool.connection_tagged("api").right_future(),
+        };
+        connection.map_err(|err| err.generalize().into())
     }
 
     /// Resolves the specified block ID to a block number, which is guaranteed to be present in the node storage.
@@ -334,13 +350,7 @@ impl RpcState {
         connection: &mut Connection<'_, Core>,
         block: api::BlockId,
     ) -> Result<BlockArgs, Web3Error> {
-        BlockArgs::new(connection, block, &self.start_info)
-            .await
-            .map_err(|err| match err {
-                BlockArgsError::Pruned(number) => Web3Error::PrunedBlock(number),
-                BlockArgsError::Missing => Web3Error::NoBlock,
-                BlockArgsError::Database(err) => Web3Error::InternalError(err),
-            })
+        Ok(BlockArgs::new(connection, block, &self.start_info).await?)
     }
 
     pub async fn resolve_filter_block_number(
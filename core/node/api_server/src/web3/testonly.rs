@@ -3,7 +3,11 @@
 use std::{pin::Pin, time::Instant};
 
 use tokio::sync::watch;
-use zksync_config::configs::{api::Web3JsonRpcConfig, chain::StateKeeperConfig, wallets::Wallets};
+use zksync_config::configs::{
+    api::{Web3JsonRpcConfig, Web3SubscriptionLimitsConfig},
+    chain::StateKeeperConfig,
+    wallets::Wallets,
+};
 use zksync_dal::ConnectionPool;
 use zksync_health_check::CheckHealth;
 use zksync_node_fee_model::MockBatchFeeParamsProvider;
@@ -109,6 +113,7 @@ pub struct TestServerBuilder {
     tx_executor: MockOneshotExecutor,
     executor_options: Option<SandboxExecutorOptions>,
     method_tracer: Arc<MethodTracer>,
+    subscription_limits: Web3SubscriptionLimitsConfig,
 }
 
 impl TestServerBuilder {
@@ -120,6 +125,7 @@ impl TestServerBuilder {
             tx_executor: MockOneshotExecutor::default(),
             executor_options: None,
             method_tracer: Arc::default(),
+            subscription_limits: Web3SubscriptionLimitsConfig::default(),
         }
     }
 
@@ -143,6 +149,13 @@ impl TestServerBuilder {
         self
     }
 
+    /// Sets subscription limits for the WS server. Only limits explicitly set in the config are applied.
+    #[must_use]
+    pub fn with_subscription_limits(mut self, limits: Web3SubscriptionLimitsConfig) -> Self {
+        self.subscription_limits = limits;
+        self
+    }
+
     /// Builds an HTTP server.
     pub async fn build_http(self, stop_receiver: watch::Receiver<bool>) -> ApiServerHandles {
         self.spawn_server(ApiTransportLabel::Http, None, stop_receiver)
@@ -176,6 +189,7 @@ impl TestServerBuilder {
             pool,
             api_config,
             method_tracer,
+            subscription_limits,
         } = self;
 
         let tx_executor = if let Some(options) = executor_options {
@@ -206,6 +220,18 @@ impl TestServerBuilder {
                         websocket_requests_per_minute_limit,
                     );
                 }
+                if let Some(limit) = subscription_limits.max_subscriptions_per_connection {
+                    builder = builder.with_max_subscriptions_per_connection(limit);
+                }
+                if let Some(capacity) = subscription_limits.buffer_capacity {
+                    builder = builder.with_subscription_buffer(
+                        capacity,
+                        subscription_limits.slow_subscriber_policy,
+                    );
+                }
+                if let Some(limit) = subscription_limits.max_buffered_notifications_per_connection {
+                    builder = builder.with_max_buffered_notifications_per_connection(limit);
+                }
                 builder
             }
         };
@@ -1,9 +1,16 @@
 //! Tests for the `debug` Web3 namespace.
 
-use zksync_multivm::interface::{Call, TransactionExecutionResult};
+use std::collections::BTreeMap;
+
+use zksync_multivm::interface::{
+    Call, TransactionExecutionResult, TxExecutionMode, VmExecutionLogs, VmExecutionResultAndLogs,
+};
 use zksync_types::{
-    api::{CallTracerConfig, SupportedTracers, TracerConfig},
-    BOOTLOADER_ADDRESS,
+    api::{
+        CallTracerConfig, FourByteTrace, PrestateAccount, PrestateTrace, SupportedTracers,
+        TracerConfig,
+    },
+    StorageLogKind, StorageLogWithPreviousValue, BOOTLOADER_ADDRESS,
 };
 use zksync_web3_decl::{
     client::{DynClient, L2},
@@ -73,7 +80,7 @@ impl HttpTest for TraceBlockTest {
                 let expected_calls: Vec<_> = tx_result
                     .call_traces
                     .iter()
-                    .map(|call| DebugNamespace::map_default_call(call.clone(), false))
+                    .map(|call| DebugNamespace::map_default_call(call.clone(), None))
                     .collect();
                 assert_eq!(result.calls, expected_calls);
             }
@@ -131,9 +138,7 @@ impl HttpTest for TraceBlockFlatTest {
                         number,
                         Some(TracerConfig {
                             tracer: SupportedTracers::FlatCallTracer,
-                            tracer_config: CallTracerConfig {
-                                only_top_call: false,
-                            },
+                            tracer_config: CallTracerConfig::default(),
                         }),
                     )
                     .await?
@@ -171,9 +176,7 @@ impl HttpTest for TraceBlockFlatTest {
                 missing_block_number,
                 Some(TracerConfig {
                     tracer: SupportedTracers::FlatCallTracer,
-                    tracer_config: CallTracerConfig {
-                        only_top_call: false,
-                    },
+                    tracer_config: CallTracerConfig::default(),
                 }),
             )
             .await
@@ -216,7 +219,7 @@ impl HttpTest for TraceTransactionTest {
         let expected_calls: Vec<_> = tx_results[0]
             .call_traces
             .iter()
-            .map(|call| DebugNamespace::map_default_call(call.clone(), false))
+            .map(|call| DebugNamespace::map_default_call(call.clone(), None))
             .collect();
 
         let result = client
@@ -238,6 +241,189 @@ async fn tracing_transaction() {
     test_http_server(TraceTransactionTest).await;
 }
 
+#[derive(Debug)]
+struct TraceTransactionWithBuiltinTracersTest;
+
+#[async_trait]
+impl HttpTest for TraceTransactionWithBuiltinTracersTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let tx_results = [execute_l2_transaction_with_traces(0)];
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &tx_results).await?;
+        drop(storage);
+        let tx_hash = tx_results[0].hash;
+
+        let options = TracerConfig {
+            tracer: SupportedTracers::FourByteTracer,
+            tracer_config: CallTracerConfig::default(),
+        };
+        let trace = client
+            .trace_transaction(tx_hash, Some(options))
+            .await?
+            .context("no transaction traces")?
+            .unwrap_four_byte();
+        // Only the second call has call data with a selector (`b"input"`).
+        let expected_key = format!("0x{}-1", hex::encode(b"inpu"));
+        assert_eq!(trace, FourByteTrace::from([(expected_key, 1)]));
+
+        let options = TracerConfig {
+            tracer: SupportedTracers::CallTracer,
+            tracer_config: CallTracerConfig {
+                max_depth: Some(0),
+                ..CallTracerConfig::default()
+            },
+        };
+        let result = client
+            .trace_transaction(tx_hash, Some(options))
+            .await?
+            .context("no transaction traces")?
+            .unwrap_default();
+        assert_eq!(result.to, BOOTLOADER_ADDRESS);
+        assert!(result.calls.is_empty(), "{result:?}");
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn tracing_transaction_with_builtin_tracers() {
+    test_http_server(TraceTransactionWithBuiltinTracersTest).await;
+}
+
+/// Checks that `prestateTracer` re-executes transactions in the block with the original fee params.
+#[derive(Debug)]
+struct TracePrestateTest {
+    tx_results: [TransactionExecutionResult; 2],
+}
+
+impl TracePrestateTest {
+    const SLOT: H256 = H256::repeat_byte(0xaa);
+    const CONTRACT: Address = Address::repeat_byte(0xcc);
+
+    fn new() -> Self {
+        Self {
+            tx_results: [0, 1].map(execute_l2_transaction_with_traces),
+        }
+    }
+
+    fn slot_log(
+        kind: StorageLogKind,
+        value: u64,
+        previous_value: u64,
+    ) -> StorageLogWithPreviousValue {
+        StorageLogWithPreviousValue {
+            log: StorageLog {
+                kind,
+                key: StorageKey::new(AccountTreeId::new(Self::CONTRACT), Self::SLOT),
+                value: H256::from_low_u64_be(value),
+            },
+            previous_value: H256::from_low_u64_be(previous_value),
+        }
+    }
+
+    fn contract_storage(value: u64) -> BTreeMap<Address, PrestateAccount> {
+        let account = PrestateAccount {
+            storage: BTreeMap::from([(Self::SLOT, H256::from_low_u64_be(value))]),
+            ..PrestateAccount::default()
+        };
+        BTreeMap::from([(Self::CONTRACT, account)])
+    }
+}
+
+#[async_trait]
+impl HttpTest for TracePrestateTest {
+    fn transaction_executor(&self) -> MockOneshotExecutor {
+        let first_tx_hash = self.tx_results[0].hash;
+        let expected_header = create_l2_block(1);
+        let mut tx_executor = MockOneshotExecutor::default();
+        tx_executor.set_full_tx_responses(move |tx, env| {
+            assert_eq!(env.system.execution_mode, TxExecutionMode::VerifyExecute);
+            assert_eq!(
+                env.l1_batch.enforced_base_fee,
+                Some(expected_header.base_fee_per_gas)
+            );
+            assert_eq!(env.l1_batch.fee_input, expected_header.batch_fee_input);
+
+            // The first transaction writes the slot, and the second one reads and overwrites it.
+            let storage_logs = if tx.hash() == first_tx_hash {
+                vec![Self::slot_log(StorageLogKind::InitialWrite, 1, 0)]
+            } else {
+                vec![
+                    Self::slot_log(StorageLogKind::Read, 1, 1),
+                    Self::slot_log(StorageLogKind::RepeatedWrite, 2, 1),
+                ]
+            };
+            VmExecutionResultAndLogs {
+                logs: VmExecutionLogs {
+                    storage_logs,
+                    ..VmExecutionLogs::default()
+                },
+                ..VmExecutionResultAndLogs::mock_success()
+            }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &self.tx_results).await?;
+        drop(storage);
+        let tx_hashes = self.tx_results.each_ref().map(|result| result.hash);
+
+        let mut options = TracerConfig {
+            tracer: SupportedTracers::PrestateTracer,
+            tracer_config: CallTracerConfig::default(),
+        };
+        let trace = client
+            .trace_transaction(tx_hashes[1], Some(options))
+            .await?
+            .context("no transaction traces")?
+            .unwrap_prestate();
+        assert_eq!(trace, PrestateTrace::Default(Self::contract_storage(1)));
+
+        let traces = client
+            .trace_block_by_number(1_u32.into(), Some(options))
+            .await?
+            .unwrap_prestate();
+        let traced_hashes: Vec<_> = traces.iter().map(|trace| trace.tx_hash).collect();
+        assert_eq!(traced_hashes, tx_hashes);
+        assert_eq!(
+            traces[0].result,
+            PrestateTrace::Default(Self::contract_storage(0))
+        );
+        assert_eq!(traces[1].result, trace);
+
+        options.tracer_config.diff_mode = true;
+        let trace = client
+            .trace_transaction(tx_hashes[1], Some(options))
+            .await?
+            .context("no transaction traces")?
+            .unwrap_prestate();
+        assert_eq!(
+            trace,
+            PrestateTrace::Diff {
+                pre: Self::contract_storage(1),
+                post: Self::contract_storage(2),
+            }
+        );
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn tracing_transaction_with_prestate_tracer() {
+    test_http_server(TracePrestateTest::new()).await;
+}
+
 #[derive(Debug)]
 struct TraceBlockTestWithSnapshotRecovery;
 
@@ -17,7 +17,11 @@ use zksync_config::{
     GenesisConfig,
 };
 use zksync_contracts::BaseSystemContracts;
-use zksync_dal::{transactions_dal::L2TxSubmissionResult, Connection, ConnectionPool, CoreDal};
+use zksync_dal::{
+    bridge_events_dal::{BridgeEvent, BridgeEventLocation},
+    transactions_dal::L2TxSubmissionResult,
+    Connection, ConnectionPool, CoreDal,
+};
 use zksync_multivm::interface::{
     tracer::ValidationTraces, TransactionExecutionMetrics, TransactionExecutionResult,
     TxExecutionStatus, VmEvent, VmExecutionMetrics,
@@ -37,6 +41,7 @@ use zksync_types::{
         testonly::{PADDED_EVM_BYTECODE, PROCESSED_EVM_BYTECODE},
         BytecodeHash,
     },
+    commitment::L1BatchCommitmentMode,
     fee_model::{BatchFeeInput, FeeParams},
     get_nonce_key,
     l2::L2Tx,
@@ -52,6 +57,7 @@ use zksync_types::{
 use zksync_vm_executor::oneshot::MockOneshotExecutor;
 use zksync_web3_decl::{
     client::{Client, DynClient, L2},
+    error::{PrunedData, PrunedDataKind},
     jsonrpsee::{
         core::{client::ClientT, params::BatchRequestBuilder, ClientError},
         http_client::HttpClient,
@@ -575,7 +581,11 @@ fn assert_pruned_block_error(error: &ClientError, first_retained_block: L2BlockN
                 .contains(&format!("first retained block is {first_retained_block}")),
             "{error:?}"
         );
-        assert!(error.data().is_none(), "{error:?}");
+        let pruned = parse_pruned_data(error);
+        assert_eq!(pruned.kind, PrunedDataKind::L2Block);
+        assert_eq!(pruned.first_retained_block, first_retained_block);
+        assert!(!pruned.snapshot_available);
+        assert!(!pruned.archive_peer_available);
     } else {
         panic!("Unexpected error: {error:?}");
     }
@@ -631,6 +641,8 @@ impl HttpTest for L1BatchMethodsWithSnapshotRecovery {
             .await?
             .context("no details for sealed L1 batch")?;
         assert_eq!(details.number, l1_batch_number);
+        // The batch is pre-gateway, so the commitment mode should be taken from the config.
+        assert_eq!(details.commitment_mode, Some(L1BatchCommitmentMode::Rollup));
 
         let details_for_future_batch = client.get_l1_batch_details(l1_batch_number + 1).await?;
         assert!(
@@ -657,12 +669,21 @@ fn assert_pruned_l1_batch_error(error: &ClientError, first_retained_l1_batch: L1
             )),
             "{error:?}"
         );
-        assert!(error.data().is_none(), "{error:?}");
+        let pruned = parse_pruned_data(error);
+        assert_eq!(pruned.kind, PrunedDataKind::L1Batch);
+        assert_eq!(pruned.first_retained_l1_batch, first_retained_l1_batch);
+        assert!(!pruned.snapshot_available);
+        assert!(!pruned.archive_peer_available);
     } else {
         panic!("Unexpected error: {error:?}");
     }
 }
 
+fn parse_pruned_data(error: &ErrorObjectOwned) -> PrunedData {
+    let data = error.data().unwrap_or_else(|| panic!("no data: {error:?}"));
+    serde_json::from_str(data.get()).unwrap_or_else(|err| panic!("invalid data: {err}"))
+}
+
 #[tokio::test]
 async fn l1_batch_methods_with_snapshot_recovery() {
     test_http_server(L1BatchMethodsWithSnapshotRecovery).await;
@@ -1028,6 +1049,135 @@ async fn getting_all_account_balances() {
     test_http_server(AllAccountBalancesTest).await;
 }
 
+#[derive(Debug)]
+struct BatchStateDiffTest;
+
+impl BatchStateDiffTest {
+    const ADDRESS: Address = Address::repeat_byte(0x11);
+
+    /// Inserts initial writes for all slots touched in the specified batch that weren't written before.
+    async fn insert_initial_writes(
+        storage: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        let touched_slots = storage
+            .storage_logs_dal()
+            .get_touched_slots_for_executed_l1_batch(l1_batch_number)
+            .await?;
+        let hashed_keys: Vec<_> = touched_slots.keys().map(StorageKey::hashed_key).collect();
+        let existing_writes = storage
+            .storage_logs_dal()
+            .get_l1_batches_and_indices_for_initial_writes(&hashed_keys)
+            .await?;
+        let new_keys: Vec<_> = hashed_keys
+            .into_iter()
+            .filter(|key| !existing_writes.contains_key(key))
+            .collect();
+        storage
+            .storage_logs_dedup_dal()
+            .insert_initial_writes(l1_batch_number, &new_keys)
+            .await?;
+        Ok(())
+    }
+
+    fn assert_compressed_diffs(state_diff: &api::BatchStateDiff) {
+        let compressed = &state_diff.compressed_state_diffs.0;
+        // Header: compression version, 3-byte length, enumeration index size, and 2-byte number of initial writes.
+        assert_eq!(
+            compressed[5..7],
+            (state_diff.initial_writes.len() as u16).to_be_bytes()
+        );
+        let entries: Vec<u8> = state_diff
+            .initial_writes
+            .iter()
+            .chain(&state_diff.repeated_writes)
+            .flat_map(|entry| entry.compressed.0.clone())
+            .collect();
+        assert_eq!(compressed[7..], entries);
+    }
+}
+
+#[async_trait]
+impl HttpTest for BatchStateDiffTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let state_diff = client.get_batch_state_diff(L1BatchNumber(1)).await?;
+        assert_eq!(state_diff, None);
+
+        let mut storage = pool.connection().await?;
+        let key = StorageKey::new(AccountTreeId::new(Self::ADDRESS), H256::zero());
+        for number in [1, 2] {
+            store_l2_block(&mut storage, L2BlockNumber(number), &[]).await?;
+            let log = StorageLog::new_write_log(key, H256::from_low_u64_be(number.into()));
+            storage
+                .storage_logs_dal()
+                .insert_storage_logs(L2BlockNumber(number), &[log])
+                .await?;
+            seal_l1_batch(&mut storage, L1BatchNumber(number)).await?;
+            Self::insert_initial_writes(&mut storage, L1BatchNumber(number)).await?;
+        }
+        let enumeration_index = storage
+            .storage_logs_dedup_dal()
+            .get_enumeration_index_for_key(key.hashed_key())
+            .await?
+            .unwrap();
+
+        let state_diff = client
+            .get_batch_state_diff(L1BatchNumber(1))
+            .await?
+            .context("no state diff for L1 batch #1")?;
+        assert_eq!(state_diff.l1_batch_number, L1BatchNumber(1));
+        let entry = state_diff
+            .initial_writes
+            .iter()
+            .find(|entry| entry.address == Self::ADDRESS)
+            .context("no initial write")?;
+        assert_eq!(entry.key, H256::zero());
+        assert_eq!(entry.derived_key, key.hashed_key());
+        assert_eq!(entry.enumeration_index, enumeration_index);
+        assert_eq!(entry.initial_value, H256::zero());
+        assert_eq!(entry.final_value, H256::from_low_u64_be(1));
+        // Initial writes are compressed using the derived key.
+        assert_eq!(entry.compressed.0[..32], *key.hashed_key().as_bytes());
+        Self::assert_compressed_diffs(&state_diff);
+
+        let state_diff = client
+            .get_batch_state_diff(L1BatchNumber(2))
+            .await?
+            .context("no state diff for L1 batch #2")?;
+        assert!(state_diff
+            .initial_writes
+            .iter()
+            .all(|entry| entry.address != Self::ADDRESS));
+        let entry = state_diff
+            .repeated_writes
+            .iter()
+            .find(|entry| entry.address == Self::ADDRESS)
+            .context("no repeated write")?;
+        assert_eq!(entry.enumeration_index, enumeration_index);
+        assert_eq!(entry.initial_value, H256::from_low_u64_be(1));
+        assert_eq!(entry.final_value, H256::from_low_u64_be(2));
+        // Repeated writes are compressed using the enumeration index.
+        assert_eq!(
+            entry.compressed.0[..4],
+            (enumeration_index as u32).to_be_bytes()
+        );
+        Self::assert_compressed_diffs(&state_diff);
+
+        let state_diff = client.get_batch_state_diff(L1BatchNumber(3)).await?;
+        assert_eq!(state_diff, None);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_batch_state_diff() {
+    test_http_server(BatchStateDiffTest).await;
+}
+
 #[derive(Debug, Default)]
 struct RpcCallsTracingTest {
     tracer: Arc<MethodTracer>,
@@ -1401,3 +1551,136 @@ impl HttpTest for FeeHistoryTest {
 async fn getting_fee_history() {
     test_http_server(FeeHistoryTest).await;
 }
+
+#[derive(Debug)]
+struct ZksFeeHistoryTest;
+
+#[async_trait]
+impl HttpTest for ZksFeeHistoryTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut connection = pool.connection().await?;
+        let block1 = L2BlockHeader {
+            batch_fee_input: scaled_sensible_fee_input(1.0),
+            base_fee_per_gas: 100,
+            ..create_l2_block(1)
+        };
+        store_custom_l2_block(&mut connection, &block1, &[]).await?;
+        let block2 = L2BlockHeader {
+            batch_fee_input: scaled_sensible_fee_input(2.0),
+            base_fee_per_gas: 200,
+            ..create_l2_block(2)
+        };
+        store_custom_l2_block(&mut connection, &block2, &[]).await?;
+
+        let history = client
+            .get_fee_history(2.into(), api::BlockNumber::Latest)
+            .await?;
+        assert_eq!(history.oldest_block, L2BlockNumber(1));
+        assert_eq!(history.base_fee_per_gas, [100, 200].map(U256::from));
+        let fee_inputs = [block1.batch_fee_input, block2.batch_fee_input];
+        assert_eq!(
+            history.fair_l2_gas_price,
+            fee_inputs.map(|input| U64::from(input.fair_l2_gas_price()))
+        );
+        assert_eq!(
+            history.fair_pubdata_price,
+            fee_inputs.map(|input| U64::from(input.fair_pubdata_price()))
+        );
+        assert_eq!(
+            history.l1_gas_price,
+            fee_inputs.map(|input| U64::from(input.l1_gas_price()))
+        );
+
+        // Blocks 0..=1
+        let history = client
+            .get_fee_history(1_000.into(), api::BlockNumber::Number(1.into()))
+            .await?;
+        assert_eq!(history.oldest_block, L2BlockNumber(0));
+        assert_eq!(history.base_fee_per_gas, [0, 100].map(U256::from));
+        assert_eq!(history.fair_l2_gas_price.len(), 2);
+
+        // Non-existing newest block.
+        let err = client
+            .get_fee_history(1.into(), api::BlockNumber::Number(100.into()))
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            ClientError::Call(err) if err.code() == INVALID_PARAMS_CODE
+        );
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_zks_fee_history() {
+    test_http_server(ZksFeeHistoryTest).await;
+}
+
+#[derive(Debug)]
+struct GetBridgeTransfersTest;
+
+#[async_trait]
+impl HttpTest for GetBridgeTransfersTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let account = Address::repeat_byte(0x11);
+        let events: Vec<_> = (0..5)
+            .map(|i| BridgeEvent {
+                kind: api::BridgeTransferKind::DepositInitiated,
+                // Every other event belongs to another account.
+                l1_address: Some(if i % 2 == 0 {
+                    account
+                } else {
+                    Address::repeat_byte(0x22)
+                }),
+                l2_address: Some(Address::repeat_byte(0x33)),
+                l1_token: Some(Address::repeat_byte(0x44)),
+                l2_token: None,
+                amount: U256::from(i),
+                tx_hash: H256::from_low_u64_be(i),
+                location: BridgeEventLocation::L1 {
+                    block_number: 100,
+                    log_index: i as u32,
+                },
+            })
+            .collect();
+        let mut storage = pool.connection().await?;
+        storage
+            .bridge_events_dal()
+            .insert_bridge_events(&events)
+            .await?;
+        drop(storage);
+
+        let page = client.get_bridge_transfers(account, None, Some(2)).await?;
+        let amounts: Vec<_> = page.transfers.iter().map(|tr| tr.amount).collect();
+        assert_eq!(amounts, [U256::from(0), U256::from(2)]);
+        assert_eq!(page.transfers[0].l1_block_number, Some(100.into()));
+        let cursor = page.next_cursor.expect("no next cursor");
+
+        let page = client
+            .get_bridge_transfers(account, Some(cursor), Some(2))
+            .await?;
+        let amounts: Vec<_> = page.transfers.iter().map(|tr| tr.amount).collect();
+        assert_eq!(amounts, [U256::from(4)]);
+        assert_eq!(page.next_cursor, None);
+
+        let page = client
+            .get_bridge_transfers(Address::repeat_byte(0x33), None, None)
+            .await?;
+        assert_eq!(page.transfers.len(), 5);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_bridge_transfers() {
+    test_http_server(GetBridgeTransfersTest).await;
+}
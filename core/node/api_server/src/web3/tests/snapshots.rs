@@ -55,7 +55,12 @@ impl HttpTest for SnapshotBasicsTest {
             let path = format!("file:///storage_logs/chunk{chunk_id}");
             storage
                 .snapshots_dal()
-                .add_storage_logs_filepath_for_snapshot(L1BatchNumber(1), chunk_id, &path)
+                .add_storage_logs_filepath_for_snapshot(
+                    L1BatchNumber(1),
+                    chunk_id,
+                    &path,
+                    H256::repeat_byte(chunk_id as u8),
+                )
                 .await?;
         }
 
@@ -90,7 +95,14 @@ impl HttpTest for SnapshotBasicsTest {
         for chunk in &snapshot_header.storage_logs_chunks {
             assert!(self.chunk_ids.contains(&chunk.chunk_id));
             assert!(chunk.filepath.starts_with("file:///storage_logs/"));
+            assert_eq!(
+                chunk.checksum,
+                Some(H256::repeat_byte(chunk.chunk_id as u8))
+            );
         }
+        let manifest_hash = snapshot_header.compute_manifest_hash();
+        assert!(manifest_hash.is_some());
+        assert_eq!(snapshot_header.manifest_hash, manifest_hash);
         Ok(())
     }
 }
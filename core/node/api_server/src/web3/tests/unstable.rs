@@ -1,6 +1,6 @@
 //! Tests for the `unstable` Web3 namespace.
 
-use zksync_types::tee_types::TeeType;
+use zksync_types::{api::LogsCursor, tee_types::TeeType};
 use zksync_web3_decl::namespaces::UnstableNamespaceClient;
 
 use super::*;
@@ -62,3 +62,60 @@ impl HttpTest for GetTeeProofsTest {
 async fn get_tee_proofs() {
     test_http_server(GetTeeProofsTest::new()).await;
 }
+
+#[derive(Debug)]
+struct GetLogsPaginatedTest;
+
+#[async_trait]
+impl HttpTest for GetLogsPaginatedTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut storage = pool.connection().await?;
+        let (_, first_events) = store_events(&mut storage, 1, 0).await?;
+        let (_, second_events) = store_events(&mut storage, 2, 4).await?;
+        drop(storage);
+        let events: Vec<_> = first_events.iter().chain(&second_events).collect();
+
+        let mut all_logs = vec![];
+        let mut cursor = None;
+        let mut page_count = 0;
+        loop {
+            let page = client
+                .get_logs_paginated(Filter::default(), cursor, Some(3))
+                .await?;
+            assert!(page.logs.len() <= 3, "{page:?}");
+            all_logs.extend(page.logs);
+            page_count += 1;
+            match page.next_cursor {
+                Some(next_cursor) => cursor = Some(next_cursor),
+                None => break,
+            }
+        }
+        assert_eq!(page_count, 3);
+        assert_logs_match(&all_logs, &events);
+
+        // Pagination should respect filters.
+        let address_filter = Filter {
+            address: Some(Address::repeat_byte(23).into()),
+            ..Filter::default()
+        };
+        let cursor = LogsCursor {
+            block_number: L2BlockNumber(1),
+            log_index: 0,
+        };
+        let page = client
+            .get_logs_paginated(address_filter, Some(cursor), None)
+            .await?;
+        assert_logs_match(&page.logs, &[events[3], events[4], events[7]]);
+        assert_eq!(page.next_cursor, None);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn get_logs_paginated() {
+    test_http_server(GetLogsPaginatedTest).await;
+}
@@ -17,7 +17,8 @@ use zksync_multivm::interface::{
 use zksync_types::{
     api::ApiStorageLog, fee_model::BatchFeeInput, get_intrinsic_constants,
     transaction_request::CallRequest, u256_to_h256, vm::FastVmMode, K256PrivateKey, L2ChainId,
-    PackedEthSignature, StorageLogKind, StorageLogWithPreviousValue, Transaction, U256,
+    PackedEthSignature, StorageLogKind, StorageLogWithPreviousValue, Transaction,
+    TransactionTimeRangeConstraint, U256,
 };
 use zksync_vm_executor::oneshot::{
     BaseSystemContractsProvider, ContractsKind, MockOneshotExecutor, OneshotEnvParameters,
@@ -525,6 +526,128 @@ async fn send_raw_transaction_fails_without_to_address() {
     test_http_server(SendRawTransactionWithoutToAddressTest).await;
 }
 
+#[derive(Debug)]
+struct SendRawTransactionConditionalTest;
+
+impl SendRawTransactionConditionalTest {
+    fn assert_error(error: &ClientError, expected_message: &str) {
+        if let ClientError::Call(error) = error {
+            assert!(error.message().contains(expected_message), "{error:?}");
+        } else {
+            panic!("Unexpected error: {error:?}");
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTest for SendRawTransactionConditionalTest {
+    fn transaction_executor(&self) -> MockOneshotExecutor {
+        SendRawTransactionTest {
+            snapshot_recovery: false,
+        }
+        .transaction_executor()
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let balance_log = SendRawTransactionTest::balance_storage_log();
+        let mut storage = pool.connection().await?;
+        storage
+            .storage_logs_dal()
+            .append_storage_logs(L2BlockNumber(0), &[balance_log])
+            .await?;
+        drop(storage);
+
+        let (tx_bytes, tx_hash) = SendRawTransactionTest::transaction_bytes_and_hash(true);
+        let storage_root_conditions = api::TransactionConditions {
+            known_accounts: HashMap::from([(
+                *balance_log.key.address(),
+                api::KnownAccountState::StorageRoot(H256::repeat_byte(1)),
+            )]),
+            ..api::TransactionConditions::default()
+        };
+        let err = client
+            .send_raw_transaction_conditional(tx_bytes.clone().into(), storage_root_conditions)
+            .await
+            .unwrap_err();
+        Self::assert_error(&err, "unsupported transaction conditions");
+
+        let balance_conditions = |value| api::TransactionConditions {
+            known_accounts: HashMap::from([(
+                *balance_log.key.address(),
+                api::KnownAccountState::Slots(HashMap::from([(*balance_log.key.key(), value)])),
+            )]),
+            ..api::TransactionConditions::default()
+        };
+        let err = client
+            .send_raw_transaction_conditional(
+                tx_bytes.clone().into(),
+                balance_conditions(H256::zero()),
+            )
+            .await
+            .unwrap_err();
+        Self::assert_error(&err, "conditions not met");
+
+        let block_conditions = api::TransactionConditions {
+            block_number_min: Some(100.into()),
+            ..api::TransactionConditions::default()
+        };
+        let err = client
+            .send_raw_transaction_conditional(tx_bytes.clone().into(), block_conditions)
+            .await
+            .unwrap_err();
+        Self::assert_error(&err, "conditions not met");
+
+        let expired_conditions = api::TransactionConditions {
+            timestamp_max: Some(1.into()),
+            ..api::TransactionConditions::default()
+        };
+        let err = client
+            .send_raw_transaction_conditional(tx_bytes.clone().into(), expired_conditions)
+            .await
+            .unwrap_err();
+        Self::assert_error(&err, "conditions not met");
+
+        let conditions = api::TransactionConditions {
+            block_number_max: Some(100.into()),
+            timestamp_min: Some(1.into()),
+            ..balance_conditions(balance_log.value)
+        };
+        let send_result = client
+            .send_raw_transaction_conditional(tx_bytes.into(), conditions)
+            .await?;
+        assert_eq!(send_result, tx_hash);
+
+        // Check that the conditions are persisted to be enforced by the state keeper.
+        let storage_tx = pool
+            .connection()
+            .await?
+            .transactions_dal()
+            .get_storage_tx_by_hash(tx_hash)
+            .await?
+            .expect("transaction is not persisted");
+        let range_start = storage_tx.timestamp_asserter_range_start.unwrap();
+        assert_eq!(range_start.and_utc().timestamp(), 1);
+        assert!(storage_tx.timestamp_asserter_range_end.is_some());
+        assert_eq!(storage_tx.l2_block_number_range_start, Some(0));
+        assert_eq!(storage_tx.l2_block_number_range_end, Some(101));
+        let constraint = TransactionTimeRangeConstraint::from(&storage_tx);
+        assert_eq!(
+            constraint.expected_storage_values,
+            [(balance_log.key, balance_log.value)]
+        );
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn send_raw_transaction_conditional() {
+    test_http_server(SendRawTransactionConditionalTest).await;
+}
+
 #[derive(Debug)]
 struct SendRawTransactionTestWithEvmEmulator;
 
@@ -6,9 +6,15 @@ use assert_matches::assert_matches;
 use async_trait::async_trait;
 use http::StatusCode;
 use tokio::sync::watch;
-use zksync_config::configs::chain::NetworkConfig;
+use zksync_config::configs::{
+    api::{SlowSubscriberPolicy, Web3SubscriptionLimitsConfig},
+    chain::NetworkConfig,
+};
 use zksync_dal::ConnectionPool;
-use zksync_types::{api, Address, Bloom, L1BatchNumber, H160, H256, U64};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType, api, Address, Bloom, L1BatchNumber, H160, H256,
+    U64,
+};
 use zksync_web3_decl::{
     client::{WsClient, L2},
     jsonrpsee::{
@@ -18,13 +24,17 @@ use zksync_web3_decl::{
             ClientError,
         },
         rpc_params,
+        types::error::ErrorCode,
     },
     namespaces::{EthNamespaceClient, ZksNamespaceClient},
-    types::{BlockHeader, Bytes, PubSubFilter},
+    types::{BlockHeader, Bytes, L1BatchEvent, L1BatchEventKind, PubSubFilter, PubSubResult},
 };
 
 use super::*;
-use crate::web3::metrics::SubscriptionType;
+use crate::web3::{
+    metrics::SubscriptionType,
+    pubsub::{ConnectionBudget, SubscriptionBuffer},
+};
 
 async fn wait_for_subscription(
     events: &mut mpsc::UnboundedReceiver<PubSubEvent>,
@@ -109,10 +119,9 @@ async fn notifiers_start_after_snapshot_recovery() {
 
     let (stop_sender, stop_receiver) = watch::channel(false);
     let (events_sender, mut events_receiver) = mpsc::unbounded_channel();
-    let mut subscribe_logic = EthSubscribe::new();
+    let mut subscribe_logic = EthSubscribe::new(pool.clone());
     subscribe_logic.set_events_sender(events_sender);
-    let notifier_handles =
-        subscribe_logic.spawn_notifiers(pool.clone(), POLL_INTERVAL, stop_receiver);
+    let notifier_handles = subscribe_logic.spawn_notifiers(POLL_INTERVAL, stop_receiver);
     assert!(!notifier_handles.is_empty());
 
     // Wait a little doing nothing and check that notifier tasks are still active (i.e., have not panicked).
@@ -160,13 +169,21 @@ trait WsTest: Send + Sync {
     fn websocket_requests_per_minute_limit(&self) -> Option<NonZeroU32> {
         None
     }
+
+    fn web3_config(&self) -> Web3JsonRpcConfig {
+        Web3JsonRpcConfig::for_tests()
+    }
+
+    fn subscription_limits(&self) -> Web3SubscriptionLimitsConfig {
+        Web3SubscriptionLimitsConfig::default()
+    }
 }
 
 async fn test_ws_server(test: impl WsTest) {
     let pool = ConnectionPool::<Core>::test_pool().await;
     let network_config = NetworkConfig::for_tests();
     let contracts_config = ContractsConfig::for_tests();
-    let web3_config = Web3JsonRpcConfig::for_tests();
+    let web3_config = test.web3_config();
     let genesis_config = GenesisConfig::for_tests();
     let api_config = InternalApiConfig::new(&web3_config, &contracts_config, &genesis_config);
     let mut storage = pool.connection().await.unwrap();
@@ -178,6 +195,7 @@ async fn test_ws_server(test: impl WsTest) {
 
     let (stop_sender, stop_receiver) = watch::channel(false);
     let (mut server_handles, pub_sub_events) = TestServerBuilder::new(pool.clone(), api_config)
+        .with_subscription_limits(test.subscription_limits())
         .build_ws(test.websocket_requests_per_minute_limit(), stop_receiver)
         .await;
 
@@ -344,6 +362,93 @@ async fn basic_subscriptions_after_snapshot_recovery() {
     .await;
 }
 
+#[derive(Debug)]
+struct L1BatchEventsSubscriptionTest;
+
+#[async_trait]
+impl WsTest for L1BatchEventsSubscriptionTest {
+    async fn test(
+        &self,
+        client: &WsClient<L2>,
+        pool: &ConnectionPool<Core>,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        wait_for_notifiers(&mut pub_sub_events, &[SubscriptionType::L1BatchEvents]).await;
+
+        let mut subscription = client
+            .subscribe::<L1BatchEvent, _>(
+                "zks_subscribeBatchEvents",
+                rpc_params![],
+                "zks_unsubscribeBatchEvents",
+            )
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::L1BatchEvents).await;
+
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &[]).await?;
+        seal_l1_batch(&mut storage, L1BatchNumber(1)).await?;
+        drop(storage);
+
+        let received_event = tokio::time::timeout(TEST_TIMEOUT, subscription.next())
+            .await
+            .context("Timed out waiting for L1 batch event")?
+            .context("L1 batch events subscription terminated")??;
+        assert_eq!(
+            received_event,
+            L1BatchEvent {
+                l1_batch_number: L1BatchNumber(1),
+                event: L1BatchEventKind::Sealed,
+                l1_tx_hash: None,
+            }
+        );
+
+        let stages = [
+            (AggregatedActionType::Commit, L1BatchEventKind::Committed),
+            (
+                AggregatedActionType::PublishProofOnchain,
+                L1BatchEventKind::Proven,
+            ),
+            (AggregatedActionType::Execute, L1BatchEventKind::Executed),
+        ];
+        for (i, (action_type, event_kind)) in stages.into_iter().enumerate() {
+            let tx_hash = H256::from_low_u64_be(i as u64 + 1);
+            pool.connection()
+                .await?
+                .eth_sender_dal()
+                .insert_bogus_confirmed_eth_tx(
+                    L1BatchNumber(1),
+                    action_type,
+                    tx_hash,
+                    chrono::Utc::now(),
+                    None,
+                )
+                .await?;
+
+            let received_event = tokio::time::timeout(TEST_TIMEOUT, subscription.next())
+                .await
+                .context("Timed out waiting for L1 batch event")?
+                .context("L1 batch events subscription terminated")??;
+            // Genesis batch must not be reported even though no batches were committed before.
+            assert_eq!(
+                received_event,
+                L1BatchEvent {
+                    l1_batch_number: L1BatchNumber(1),
+                    event: event_kind,
+                    l1_tx_hash: Some(tx_hash),
+                }
+            );
+        }
+
+        subscription.unsubscribe().await?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn l1_batch_events_subscription() {
+    test_ws_server(L1BatchEventsSubscriptionTest).await;
+}
+
 #[derive(Debug)]
 struct LogSubscriptionsTest {
     snapshot_recovery: bool,
@@ -491,6 +596,126 @@ async fn log_subscriptions_after_snapshot_recovery() {
     .await;
 }
 
+#[derive(Debug)]
+struct LogsReplayTest;
+
+#[async_trait]
+impl WsTest for LogsReplayTest {
+    async fn test(
+        &self,
+        client: &WsClient<L2>,
+        pool: &ConnectionPool<Core>,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        wait_for_notifiers(&mut pub_sub_events, &[SubscriptionType::Logs]).await;
+
+        let mut storage = pool.connection().await?;
+        store_events(&mut storage, 1, 0).await?;
+        let (_, events_in_block2) = store_events(&mut storage, 2, 4).await?;
+
+        let topic_filter = PubSubFilter {
+            address: None,
+            topics: Some(vec![Some(H256::repeat_byte(42).into())]),
+        };
+        let params = rpc_params![L2BlockNumber(2), topic_filter];
+        let mut subscription = client
+            .subscribe::<api::Log, _>("zks_subscribeLogsFrom", params, "zks_unsubscribeLogsFrom")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::Logs).await;
+        // Depending on timing, logs from this block are either replayed or sent as live notifications.
+        let (_, events_in_block3) = store_events(&mut storage, 3, 8).await?;
+        drop(storage);
+
+        let logs = collect_logs(&mut subscription, 4).await?;
+        let block_numbers: Vec<_> = logs.iter().map(|log| log.block_number).collect();
+        let expected_block_numbers = [2, 2, 3, 3].map(|number| Some(U64::from(number)));
+        assert_eq!(block_numbers, expected_block_numbers);
+        let expected_events = [
+            &events_in_block2[1],
+            &events_in_block2[3],
+            &events_in_block3[1],
+            &events_in_block3[3],
+        ];
+        assert_logs_match(&logs, &expected_events);
+
+        wait_for_notifiers(&mut pub_sub_events, &[SubscriptionType::Logs]).await;
+        // Check that no logs were sent twice.
+        tokio::time::timeout(POLL_INTERVAL, subscription.next())
+            .await
+            .unwrap_err();
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn logs_replay() {
+    test_ws_server(LogsReplayTest).await;
+}
+
+#[derive(Debug)]
+struct LogsReplayLimitTest;
+
+#[async_trait]
+impl WsTest for LogsReplayLimitTest {
+    fn web3_config(&self) -> Web3JsonRpcConfig {
+        Web3JsonRpcConfig {
+            req_entities_limit: Some(3),
+            ..Web3JsonRpcConfig::for_tests()
+        }
+    }
+
+    async fn test(
+        &self,
+        client: &WsClient<L2>,
+        pool: &ConnectionPool<Core>,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        wait_for_notifiers(&mut pub_sub_events, &[SubscriptionType::Logs]).await;
+
+        let mut storage = pool.connection().await?;
+        store_events(&mut storage, 1, 0).await?;
+        let (_, events_in_block2) = store_events(&mut storage, 2, 4).await?;
+        drop(storage);
+
+        let topic_filter = PubSubFilter {
+            address: None,
+            topics: Some(vec![Some(H256::repeat_byte(42).into())]),
+        };
+        // 4 logs match the filter, which exceeds the limit.
+        let params = rpc_params![L2BlockNumber(1), topic_filter.clone()];
+        let err = client
+            .subscribe::<api::Log, _>("zks_subscribeLogsFrom", params, "zks_unsubscribeLogsFrom")
+            .await
+            .unwrap_err();
+        let ClientError::Call(err) = err else {
+            panic!("Unexpected error: {err:?}");
+        };
+        assert_eq!(err.code(), ErrorCode::InvalidParams.code());
+        assert!(err.message().contains("more than 3 results"), "{err:?}");
+
+        // Replaying a single L2 block is not limited, same as with `eth_getLogs`.
+        let params = rpc_params![L2BlockNumber(2), PubSubFilter::default()];
+        let mut subscription = client
+            .subscribe::<api::Log, _>("zks_subscribeLogsFrom", params, "zks_unsubscribeLogsFrom")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::Logs).await;
+        let logs = collect_logs(&mut subscription, 4).await?;
+        assert_logs_match(&logs, &events_in_block2.iter().collect::<Vec<_>>());
+
+        let params = rpc_params![L2BlockNumber(1), PubSubFilter::default()];
+        client
+            .subscribe::<api::Log, _>("zks_subscribeLogsFrom", params, "zks_unsubscribeLogsFrom")
+            .await
+            .unwrap_err();
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn logs_replay_limit() {
+    test_ws_server(LogsReplayLimitTest).await;
+}
+
 #[derive(Debug)]
 struct LogSubscriptionsWithNewBlockTest;
 
@@ -735,3 +960,148 @@ impl WsTest for BatchGetsRateLimitedTest {
 async fn batch_rate_limiting() {
     test_ws_server(BatchGetsRateLimitedTest).await;
 }
+
+#[derive(Debug)]
+struct SubscriptionLimitsTest;
+
+#[async_trait]
+impl WsTest for SubscriptionLimitsTest {
+    async fn test(
+        &self,
+        client: &WsClient<L2>,
+        pool: &ConnectionPool<Core>,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        wait_for_notifiers(&mut pub_sub_events, &[SubscriptionType::Blocks]).await;
+
+        let _blocks_subscription = client
+            .subscribe::<BlockHeader, _>(
+                "eth_subscribe",
+                rpc_params!["newHeads"],
+                "eth_unsubscribe",
+            )
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::Blocks).await;
+
+        // The connection is limited to a single subscription.
+        let err = client
+            .subscribe::<BlockHeader, _>(
+                "eth_subscribe",
+                rpc_params!["newHeads"],
+                "eth_unsubscribe",
+            )
+            .await
+            .unwrap_err();
+        assert_matches!(err, ClientError::Call(_));
+
+        // Store 2 blocks at once so that they are broadcast in a single notification batch,
+        // overflowing the subscription buffer.
+        let mut storage = pool.connection().await?;
+        let mut transaction = storage.start_transaction().await?;
+        store_l2_block(&mut transaction, L2BlockNumber(1), &[]).await?;
+        store_l2_block(&mut transaction, L2BlockNumber(2), &[]).await?;
+        transaction.commit().await?;
+        drop(storage);
+
+        // The overflowing subscription should be closed, which frees the subscription slot of the connection.
+        tokio::time::timeout(TEST_TIMEOUT, async {
+            loop {
+                let result = client
+                    .subscribe::<BlockHeader, _>(
+                        "eth_subscribe",
+                        rpc_params!["newHeads"],
+                        "eth_unsubscribe",
+                    )
+                    .await;
+                match result {
+                    Ok(_) => break,
+                    Err(ClientError::Call(_)) => tokio::time::sleep(POLL_INTERVAL).await,
+                    Err(err) => panic!("Unexpected error subscribing: {err}"),
+                }
+            }
+        })
+        .await
+        .context("Timed out waiting for the overflowing subscription to be closed")?;
+        Ok(())
+    }
+
+    fn subscription_limits(&self) -> Web3SubscriptionLimitsConfig {
+        Web3SubscriptionLimitsConfig {
+            max_subscriptions_per_connection: Some(NonZeroU32::new(1).unwrap()),
+            buffer_capacity: Some(NonZeroUsize::new(1).unwrap()),
+            max_buffered_notifications_per_connection: None,
+            slow_subscriber_policy: SlowSubscriberPolicy::Close,
+        }
+    }
+}
+
+#[tokio::test]
+async fn subscription_limits() {
+    test_ws_server(SubscriptionLimitsTest).await;
+}
+
+fn buffered_tx_hashes(buffer: &mut SubscriptionBuffer) -> Vec<H256> {
+    let mut hashes = vec![];
+    while let Some(item) = buffer.pop_front() {
+        let PubSubResult::TxHash(hash) = item else {
+            panic!("unexpected buffered item: {item:?}");
+        };
+        hashes.push(hash);
+    }
+    hashes
+}
+
+#[test]
+fn subscription_buffer_with_drop_oldest_policy() {
+    let mut buffer =
+        SubscriptionBuffer::new(SubscriptionType::Txs, 2, SlowSubscriberPolicy::DropOldest);
+    for i in 1..=3 {
+        assert!(buffer.push(PubSubResult::TxHash(H256::from_low_u64_be(i))));
+    }
+    assert_matches!(buffer.front(), Some(PubSubResult::TxHash(_)));
+    assert!(buffer.handle_lag(10));
+
+    let hashes = buffered_tx_hashes(&mut buffer);
+    assert_eq!(hashes, [H256::from_low_u64_be(2), H256::from_low_u64_be(3)]);
+    assert!(buffer.front().is_none());
+}
+
+#[test]
+fn subscription_buffer_with_close_policy() {
+    let mut buffer = SubscriptionBuffer::new(SubscriptionType::Txs, 2, SlowSubscriberPolicy::Close);
+    for i in 1..=2 {
+        assert!(buffer.push(PubSubResult::TxHash(H256::from_low_u64_be(i))));
+    }
+    assert!(!buffer.push(PubSubResult::TxHash(H256::from_low_u64_be(3))));
+    assert!(buffered_tx_hashes(&mut buffer).is_empty());
+
+    let mut buffer = SubscriptionBuffer::new(SubscriptionType::Txs, 2, SlowSubscriberPolicy::Close);
+    assert!(!buffer.handle_lag(1));
+}
+
+#[test]
+fn subscription_buffers_share_connection_budget() {
+    let budget = ConnectionBudget::new(3);
+    let mut first_buffer =
+        SubscriptionBuffer::new(SubscriptionType::Txs, 2, SlowSubscriberPolicy::Close)
+            .with_connection_budget(budget.shared());
+    let mut second_buffer =
+        SubscriptionBuffer::new(SubscriptionType::Txs, 2, SlowSubscriberPolicy::Close)
+            .with_connection_budget(budget.shared());
+
+    for i in 1..=2 {
+        assert!(first_buffer.push(PubSubResult::TxHash(H256::from_low_u64_be(i))));
+    }
+    assert!(second_buffer.push(PubSubResult::TxHash(H256::from_low_u64_be(3))));
+    // The second buffer has spare capacity, but the connection budget is exhausted.
+    assert!(!second_buffer.push(PubSubResult::TxHash(H256::from_low_u64_be(4))));
+
+    // Budget is released once notifications are sent.
+    assert_eq!(buffered_tx_hashes(&mut first_buffer).len(), 2);
+    let mut second_buffer =
+        SubscriptionBuffer::new(SubscriptionType::Txs, 2, SlowSubscriberPolicy::Close)
+            .with_connection_budget(budget.shared());
+    for i in 5..=6 {
+        assert!(second_buffer.push(PubSubResult::TxHash(H256::from_low_u64_be(i))));
+    }
+}
@@ -5,7 +5,10 @@ use tokio::{sync::watch, time::sleep};
 use zksync_config::configs::base_token_adjuster::BaseTokenAdjusterConfig;
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_external_price_api::PriceAPIClient;
-use zksync_types::{base_token_ratio::BaseTokenAPIRatio, Address};
+use zksync_types::{
+    base_token_ratio::{BaseTokenAPIRatio, BaseTokenRatioQuote},
+    Address,
+};
 
 use crate::{
     base_token_l1_behaviour::BaseTokenL1Behaviour,
@@ -68,12 +71,14 @@ impl BaseTokenRatioPersister {
 
     async fn loop_iteration(&mut self) -> anyhow::Result<()> {
         // TODO(PE-148): Consider shifting retry upon adding external API redundancy.
-        let new_ratio = self.retry_fetch_ratio().await?;
-        self.persist_ratio(new_ratio).await?;
+        let (new_ratio, quotes) = self.retry_fetch_ratio().await?;
+        self.persist_ratio(new_ratio, &quotes).await?;
         self.l1_behaviour.update_l1(new_ratio).await
     }
 
-    async fn retry_fetch_ratio(&self) -> anyhow::Result<BaseTokenAPIRatio> {
+    async fn retry_fetch_ratio(
+        &self,
+    ) -> anyhow::Result<(BaseTokenAPIRatio, Vec<BaseTokenRatioQuote>)> {
         let sleep_duration = self.config.price_fetching_sleep_duration();
         let max_retries = self.config.price_fetching_max_attempts;
         let mut last_error = None;
@@ -82,10 +87,10 @@ impl BaseTokenRatioPersister {
             let start_time = Instant::now();
             match self
                 .price_api_client
-                .fetch_ratio(self.base_token_address)
+                .fetch_ratio_with_quotes(self.base_token_address)
                 .await
             {
-                Ok(ratio) => {
+                Ok((ratio, quotes)) => {
                     METRICS.external_price_api_latency[&OperationResultLabels {
                         result: OperationResult::Success,
                     }]
@@ -93,7 +98,7 @@ impl BaseTokenRatioPersister {
                     METRICS
                         .ratio
                         .set((ratio.numerator.get() as f64) / (ratio.denominator.get() as f64));
-                    return Ok(ratio);
+                    return Ok((ratio, quotes));
                 }
                 Err(err) => {
                     tracing::warn!(
@@ -117,14 +122,24 @@ impl BaseTokenRatioPersister {
             .unwrap_or_else(|| anyhow::anyhow!(error_message)))
     }
 
-    async fn persist_ratio(&self, api_ratio: BaseTokenAPIRatio) -> anyhow::Result<usize> {
+    /// Persists the ratio together with the quotes it was derived from, so that ratios applied
+    /// in batch fee inputs can be audited.
+    async fn persist_ratio(
+        &self,
+        api_ratio: BaseTokenAPIRatio,
+        quotes: &[BaseTokenRatioQuote],
+    ) -> anyhow::Result<usize> {
         let mut conn = self
             .pool
             .connection_tagged("base_token_ratio_persister")
             .await
             .context("Failed to obtain connection to the database")?;
+        let mut transaction = conn
+            .start_transaction()
+            .await
+            .context("Failed to start a database transaction")?;
 
-        let id = conn
+        let id = transaction
             .base_token_dal()
             .insert_token_ratio(
                 api_ratio.numerator,
@@ -133,6 +148,15 @@ impl BaseTokenRatioPersister {
             )
             .await
             .context("Failed to insert base token ratio into the database")?;
+        transaction
+            .base_token_dal()
+            .insert_ratio_audit(id, self.price_api_client.source_name(), quotes)
+            .await
+            .context("Failed to insert base token ratio audit into the database")?;
+        transaction
+            .commit()
+            .await
+            .context("Failed to commit the database transaction")?;
 
         Ok(id)
     }
@@ -15,7 +15,8 @@ use zksync_types::fee_model::BaseTokenConversionRatio;
 #[derive(Debug, Clone)]
 pub struct DBBaseTokenRatioProvider {
     pub pool: ConnectionPool<Core>,
-    pub latest_ratio: Arc<RwLock<BaseTokenConversionRatio>>,
+    /// Latest ratio together with its ID in the DB (`None` for the default ratio).
+    latest_ratio: Arc<RwLock<(Option<u32>, BaseTokenConversionRatio)>>,
     config: BaseTokenAdjusterConfig,
 }
 
@@ -40,7 +41,7 @@ impl DBBaseTokenRatioProvider {
         Ok(fetcher)
     }
 
-    fn get_latest_ratio(&self) -> BaseTokenConversionRatio {
+    fn get_latest_ratio(&self) -> (Option<u32>, BaseTokenConversionRatio) {
         *self.latest_ratio.read().unwrap()
     }
 
@@ -72,17 +73,20 @@ impl DBBaseTokenRatioProvider {
             .await;
 
         let ratio = match latest_storage_ratio {
-            Ok(Some(latest_storage_price)) => BaseTokenConversionRatio {
-                numerator: latest_storage_price.numerator,
-                denominator: latest_storage_price.denominator,
-            },
+            Ok(Some(latest_storage_price)) => (
+                Some(latest_storage_price.id),
+                BaseTokenConversionRatio {
+                    numerator: latest_storage_price.numerator,
+                    denominator: latest_storage_price.denominator,
+                },
+            ),
             Ok(None) => {
                 // TODO(PE-136): Insert initial ratio from genesis.
                 // Though the DB should be populated very soon after the server starts, it is possible
                 // to have no ratios in the DB right after genesis. Having initial ratios in the DB
                 // from the genesis stage will eliminate this possibility.
                 tracing::warn!("No latest price found in the database. Using default ratio.");
-                BaseTokenConversionRatio::default()
+                (None, BaseTokenConversionRatio::default())
             }
             Err(err) => anyhow::bail!("Failed to get latest base token ratio: {:?}", err),
         };
@@ -95,7 +99,11 @@ impl DBBaseTokenRatioProvider {
 #[async_trait]
 impl BaseTokenRatioProvider for DBBaseTokenRatioProvider {
     fn get_conversion_ratio(&self) -> BaseTokenConversionRatio {
-        self.get_latest_ratio()
+        self.get_latest_ratio().1
+    }
+
+    fn get_conversion_ratio_id(&self) -> Option<u32> {
+        self.get_latest_ratio().0
     }
 }
 
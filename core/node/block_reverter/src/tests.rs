@@ -241,18 +241,18 @@ async fn create_mock_snapshot(
             l1_batch_number,
             chunk_id,
         };
-        let key = object_store
-            .put(
-                key,
-                &SnapshotStorageLogsChunk::<H256> {
-                    storage_logs: vec![],
-                },
-            )
-            .await
-            .unwrap();
+        let chunk = SnapshotStorageLogsChunk::<H256> {
+            storage_logs: vec![],
+        };
+        let key = object_store.put(key, &chunk).await.unwrap();
         storage
             .snapshots_dal()
-            .add_storage_logs_filepath_for_snapshot(l1_batch_number, chunk_id, &key)
+            .add_storage_logs_filepath_for_snapshot(
+                l1_batch_number,
+                chunk_id,
+                &key,
+                chunk.checksum(),
+            )
             .await
             .unwrap();
     }
@@ -0,0 +1,230 @@
+//! Parsing bridge events emitted on L1 and L2.
+
+use anyhow::Context as _;
+use zksync_dal::bridge_events_dal::{BridgeEvent, BridgeEventLocation};
+use zksync_types::{
+    api::{BridgeTransferKind, Log},
+    h256_to_address, h256_to_u256,
+    web3::keccak256,
+    Address, L2BlockNumber, L2ChainId, H256, L2_BASE_TOKEN_ADDRESS, U256,
+};
+
+/// Emitted by the L2 shared bridge when a deposit is finalized.
+pub(crate) const FINALIZE_DEPOSIT_EVENT: &str = "FinalizeDeposit(address,address,address,uint256)";
+/// Emitted by the L2 shared bridge when an ERC20 withdrawal is initiated.
+pub(crate) const WITHDRAWAL_INITIATED_EVENT: &str =
+    "WithdrawalInitiated(address,address,address,uint256)";
+/// Emitted by the L2 base token contract when the base token is minted for an L1 -> L2 transaction.
+pub(crate) const BASE_TOKEN_MINT_EVENT: &str = "Mint(address,uint256)";
+/// Emitted by the L2 base token contract when a base token withdrawal is initiated.
+pub(crate) const BASE_TOKEN_WITHDRAWAL_EVENT: &str = "Withdrawal(address,address,uint256)";
+/// Emitted by the L2 base token contract when a base token withdrawal with an additional message is initiated.
+pub(crate) const BASE_TOKEN_WITHDRAWAL_WITH_MESSAGE_EVENT: &str =
+    "WithdrawalWithMessage(address,address,uint256,bytes)";
+/// Emitted by the L1 shared bridge when a deposit is initiated via the bridgehub.
+pub(crate) const DEPOSIT_INITIATED_EVENT: &str =
+    "BridgehubDepositInitiated(uint256,bytes32,address,address,address,uint256)";
+/// Emitted by the L1 shared bridge when a withdrawal is finalized.
+pub(crate) const WITHDRAWAL_FINALIZED_EVENT: &str =
+    "WithdrawalFinalizedSharedBridge(uint256,address,address,uint256)";
+
+pub(crate) fn event_topic(signature: &str) -> H256 {
+    H256(keccak256(signature.as_bytes()))
+}
+
+fn topic_address(log: &Log, index: usize) -> Option<Address> {
+    log.topics.get(index).map(h256_to_address)
+}
+
+/// Reads a 32-byte word with the specified index from non-indexed event data.
+fn data_word(log: &Log, index: usize) -> Option<H256> {
+    let word = log.data.0.get(index * 32..(index + 1) * 32)?;
+    Some(H256::from_slice(word))
+}
+
+fn data_address(log: &Log, index: usize) -> Option<Address> {
+    data_word(log, index).map(|word| h256_to_address(&word))
+}
+
+fn data_uint(log: &Log, index: usize) -> Option<U256> {
+    data_word(log, index).map(h256_to_u256)
+}
+
+/// Bridge event parsed from a log, without its location.
+#[derive(Debug)]
+struct ParsedEvent {
+    kind: BridgeTransferKind,
+    l1_address: Option<Address>,
+    l2_address: Option<Address>,
+    l1_token: Option<Address>,
+    l2_token: Option<Address>,
+    amount: U256,
+}
+
+impl ParsedEvent {
+    fn into_bridge_event(self, tx_hash: H256, location: BridgeEventLocation) -> BridgeEvent {
+        BridgeEvent {
+            kind: self.kind,
+            l1_address: self.l1_address,
+            l2_address: self.l2_address,
+            l1_token: self.l1_token,
+            l2_token: self.l2_token,
+            amount: self.amount,
+            tx_hash,
+            location,
+        }
+    }
+}
+
+/// Returns the block number, the index of the log in the block and the transaction hash for the log.
+fn log_location(log: &Log) -> anyhow::Result<(u64, u32, H256)> {
+    let block_number = log.block_number.context("missing block number")?;
+    let log_index = log.log_index.context("missing log index")?;
+    let tx_hash = log.transaction_hash.context("missing transaction hash")?;
+    Ok((block_number.as_u64(), log_index.as_u32(), tx_hash))
+}
+
+/// Parser for bridge events emitted on L2 by the L2 shared bridges and by the L2 base token contract.
+#[derive(Debug, Clone)]
+pub(crate) struct L2EventParser {
+    /// Addresses of L2 shared bridges, including the legacy one.
+    l2_bridge_addresses: Vec<Address>,
+}
+
+impl L2EventParser {
+    pub fn new(l2_bridge_addresses: Vec<Address>) -> Self {
+        Self {
+            l2_bridge_addresses,
+        }
+    }
+
+    /// Returns addresses of contracts emitting parsed events.
+    pub fn addresses(&self) -> Vec<Address> {
+        let mut addresses = self.l2_bridge_addresses.clone();
+        addresses.push(L2_BASE_TOKEN_ADDRESS);
+        addresses
+    }
+
+    /// Returns topics of parsed events.
+    pub fn topics() -> Vec<H256> {
+        [
+            FINALIZE_DEPOSIT_EVENT,
+            WITHDRAWAL_INITIATED_EVENT,
+            BASE_TOKEN_MINT_EVENT,
+            BASE_TOKEN_WITHDRAWAL_EVENT,
+            BASE_TOKEN_WITHDRAWAL_WITH_MESSAGE_EVENT,
+        ]
+        .into_iter()
+        .map(event_topic)
+        .collect()
+    }
+
+    /// Parses an event emitted on L2. Returns `Ok(None)` if the log isn't a bridge event or has an unexpected format.
+    pub fn parse(&self, log: &Log) -> anyhow::Result<Option<BridgeEvent>> {
+        let Some(parsed) = self.parse_inner(log) else {
+            return Ok(None);
+        };
+        let (block_number, event_index, tx_hash) = log_location(log)?;
+        let location = BridgeEventLocation::L2 {
+            block_number: L2BlockNumber(block_number.try_into().context("block number overflow")?),
+            event_index,
+        };
+        Ok(Some(parsed.into_bridge_event(tx_hash, location)))
+    }
+
+    fn parse_inner(&self, log: &Log) -> Option<ParsedEvent> {
+        let topic = *log.topics.first()?;
+        if self.l2_bridge_addresses.contains(&log.address) {
+            if topic == event_topic(FINALIZE_DEPOSIT_EVENT) {
+                return Some(ParsedEvent {
+                    kind: BridgeTransferKind::DepositFinalized,
+                    l1_address: Some(topic_address(log, 1)?),
+                    l2_address: Some(topic_address(log, 2)?),
+                    l1_token: None,
+                    l2_token: Some(topic_address(log, 3)?),
+                    amount: data_uint(log, 0)?,
+                });
+            } else if topic == event_topic(WITHDRAWAL_INITIATED_EVENT) {
+                return Some(ParsedEvent {
+                    kind: BridgeTransferKind::WithdrawalInitiated,
+                    l1_address: Some(topic_address(log, 2)?),
+                    l2_address: Some(topic_address(log, 1)?),
+                    l1_token: None,
+                    l2_token: Some(topic_address(log, 3)?),
+                    amount: data_uint(log, 0)?,
+                });
+            }
+        } else if log.address == L2_BASE_TOKEN_ADDRESS {
+            if topic == event_topic(BASE_TOKEN_MINT_EVENT) {
+                // The L1 sender isn't known for base token deposits.
+                return Some(ParsedEvent {
+                    kind: BridgeTransferKind::DepositFinalized,
+                    l1_address: None,
+                    l2_address: Some(topic_address(log, 1)?),
+                    l1_token: None,
+                    l2_token: Some(L2_BASE_TOKEN_ADDRESS),
+                    amount: data_uint(log, 0)?,
+                });
+            } else if topic == event_topic(BASE_TOKEN_WITHDRAWAL_EVENT)
+                || topic == event_topic(BASE_TOKEN_WITHDRAWAL_WITH_MESSAGE_EVENT)
+            {
+                return Some(ParsedEvent {
+                    kind: BridgeTransferKind::WithdrawalInitiated,
+                    l1_address: Some(topic_address(log, 2)?),
+                    l2_address: Some(topic_address(log, 1)?),
+                    l1_token: None,
+                    l2_token: Some(L2_BASE_TOKEN_ADDRESS),
+                    amount: data_uint(log, 0)?,
+                });
+            }
+        }
+        None
+    }
+}
+
+/// Parses an event emitted on L1 by the shared bridge. Events for chains other than `l2_chain_id` are ignored.
+/// Returns `Ok(None)` if the log isn't a bridge event for the chain or has an unexpected format.
+pub(crate) fn parse_l1_event(
+    log: &Log,
+    l2_chain_id: L2ChainId,
+) -> anyhow::Result<Option<BridgeEvent>> {
+    let Some(parsed) = parse_l1_event_inner(log, l2_chain_id) else {
+        return Ok(None);
+    };
+    let (block_number, log_index, tx_hash) = log_location(log)?;
+    let location = BridgeEventLocation::L1 {
+        block_number,
+        log_index,
+    };
+    Ok(Some(parsed.into_bridge_event(tx_hash, location)))
+}
+
+fn parse_l1_event_inner(log: &Log, l2_chain_id: L2ChainId) -> Option<ParsedEvent> {
+    let topic = *log.topics.first()?;
+    let chain_id = h256_to_u256(*log.topics.get(1)?);
+    if chain_id != l2_chain_id.as_u64().into() {
+        return None;
+    }
+
+    if topic == event_topic(DEPOSIT_INITIATED_EVENT) {
+        Some(ParsedEvent {
+            kind: BridgeTransferKind::DepositInitiated,
+            l1_address: Some(topic_address(log, 3)?),
+            l2_address: Some(data_address(log, 0)?),
+            l1_token: Some(data_address(log, 1)?),
+            l2_token: None,
+            amount: data_uint(log, 2)?,
+        })
+    } else if topic == event_topic(WITHDRAWAL_FINALIZED_EVENT) {
+        Some(ParsedEvent {
+            kind: BridgeTransferKind::WithdrawalFinalized,
+            l1_address: Some(topic_address(log, 2)?),
+            l2_address: None,
+            l1_token: Some(topic_address(log, 3)?),
+            l2_token: None,
+            amount: data_uint(log, 0)?,
+        })
+    } else {
+        None
+    }
+}
@@ -0,0 +1,78 @@
+//! Handling bridge events emitted on L1.
+
+use std::slice;
+
+use zksync_config::configs::eth_watch::EthWatchCustomEvent;
+use zksync_dal::{Connection, Core, CoreDal};
+use zksync_eth_watch::{CustomEvent, CustomEventHandler};
+use zksync_types::{Address, L2ChainId};
+
+use crate::{
+    events::{parse_l1_event, DEPOSIT_INITIATED_EVENT, WITHDRAWAL_FINALIZED_EVENT},
+    metrics::{EventKind, Layer, METRICS},
+};
+
+/// Handler for bridge events emitted by the L1 shared bridge, which is registered in the Ethereum watcher
+/// via [`EthWatch::with_bridge_events()`](zksync_eth_watch::EthWatch::with_bridge_events()).
+/// Relies on the watcher to detect L1 reorgs; events from the reorged blocks are removed on rollback.
+#[derive(Debug)]
+pub struct L1BridgeEventHandler {
+    l2_chain_id: L2ChainId,
+}
+
+impl L1BridgeEventHandler {
+    /// Name of the handler in the configs of watched events.
+    pub const NAME: &'static str = "bridge_indexer";
+
+    pub fn new(l2_chain_id: L2ChainId) -> Self {
+        Self { l2_chain_id }
+    }
+
+    /// Returns configs for the events handled by this handler emitted by the L1 shared bridge with the specified address.
+    pub fn watched_events(l1_shared_bridge_address: Address) -> Vec<EthWatchCustomEvent> {
+        [DEPOSIT_INITIATED_EVENT, WITHDRAWAL_FINALIZED_EVENT]
+            .into_iter()
+            .map(|signature| EthWatchCustomEvent {
+                contract_address: l1_shared_bridge_address,
+                event_signature: signature.to_owned(),
+                handler: Self::NAME.to_owned(),
+            })
+            .collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl CustomEventHandler for L1BridgeEventHandler {
+    async fn handle_event(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        event: &CustomEvent,
+    ) -> anyhow::Result<()> {
+        let Some(bridge_event) = parse_l1_event(&event.log, self.l2_chain_id)? else {
+            return Ok(());
+        };
+        tracing::debug!("Indexing L1 bridge event: {bridge_event:?}");
+        storage
+            .bridge_events_dal()
+            .insert_bridge_events(slice::from_ref(&bridge_event))
+            .await?;
+        METRICS.events[&EventKind::from(bridge_event.kind)].inc();
+        Ok(())
+    }
+
+    async fn rollback(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        from_block: u64,
+    ) -> anyhow::Result<bool> {
+        let removed_count = storage
+            .bridge_events_dal()
+            .rollback_l1_events(from_block)
+            .await?;
+        tracing::info!(
+            "Rolled back {removed_count} bridge events from L1 blocks starting from #{from_block}"
+        );
+        METRICS.rollbacks[&Layer::L1].inc();
+        Ok(false)
+    }
+}
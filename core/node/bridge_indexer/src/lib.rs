@@ -0,0 +1,197 @@
+//! Component indexing bridge events (deposits, withdrawal initiations and finalizations) emitted on L1 and L2.
+//! Indexed events are persisted in Postgres and are served per account by `zks_getBridgeTransfers`.
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_config::ContractsConfig;
+use zksync_dal::{bridge_events_dal::BridgeEvent, Connection, ConnectionPool, Core, CoreDal};
+use zksync_types::{api::GetLogsFilter, L2BlockNumber, H256};
+
+pub use self::l1::L1BridgeEventHandler;
+use self::{
+    events::L2EventParser,
+    metrics::{EventKind, Layer, METRICS},
+};
+
+mod events;
+mod l1;
+mod metrics;
+#[cfg(test)]
+mod tests;
+
+/// Component indexing bridge events emitted on L2 by the L2 shared bridges and by the L2 base token contract.
+/// Events emitted on L1 are indexed by [`L1BridgeEventHandler`] registered in the Ethereum watcher.
+///
+/// Sealed L2 blocks are processed sequentially in chunks starting from the earliest block in the database.
+/// After each chunk, the last processed block is checkpointed together with its hash. Indexed events and checkpoints
+/// are removed together with the L2 block on reverts and pruning; additionally, if a checkpointed block is replaced
+/// (e.g., after a revert on an external node), events are re-indexed starting from this block.
+#[derive(Debug)]
+pub struct BridgeIndexer {
+    pool: ConnectionPool<Core>,
+    parser: L2EventParser,
+    poll_interval: Duration,
+}
+
+impl BridgeIndexer {
+    const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+    /// Maximum number of L2 blocks processed in a single iteration.
+    const MAX_L2_BLOCKS_PER_ITERATION: u32 = 1_000;
+    /// Maximum number of logs loaded from Postgres at once.
+    const LOGS_PAGE_SIZE: usize = 1_000;
+
+    pub fn new(pool: ConnectionPool<Core>, contracts: &ContractsConfig) -> Self {
+        let l2_bridge_addresses = [
+            contracts.l2_shared_bridge_addr,
+            contracts.l2_legacy_shared_bridge_addr,
+        ];
+        Self {
+            pool,
+            parser: L2EventParser::new(l2_bridge_addresses.into_iter().flatten().collect()),
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    async fn l2_block_hash(
+        storage: &mut Connection<'_, Core>,
+        l2_block_number: L2BlockNumber,
+    ) -> anyhow::Result<Option<H256>> {
+        let header = storage
+            .blocks_dal()
+            .get_l2_block_header(l2_block_number)
+            .await?;
+        Ok(header.map(|header| header.hash))
+    }
+
+    /// Collects bridge events from the specified inclusive range of L2 blocks.
+    async fn collect_events(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        from_block: L2BlockNumber,
+        to_block: L2BlockNumber,
+    ) -> anyhow::Result<Vec<BridgeEvent>> {
+        let filter = GetLogsFilter {
+            from_block,
+            to_block,
+            addresses: self.parser.addresses(),
+            topics: vec![(1, L2EventParser::topics())],
+        };
+
+        let mut events = vec![];
+        let mut cursor = None;
+        loop {
+            let logs = storage
+                .events_web3_dal()
+                .get_logs_after(&filter, cursor, Self::LOGS_PAGE_SIZE)
+                .await?;
+            for log in &logs {
+                let event = self
+                    .parser
+                    .parse(log)
+                    .with_context(|| format!("failed parsing log {log:?}"))?;
+                events.extend(event);
+            }
+
+            let Some(last_log) = logs.last().filter(|_| logs.len() == Self::LOGS_PAGE_SIZE) else {
+                break;
+            };
+            let block_number = last_log.block_number.context("missing block number")?;
+            let log_index = last_log.log_index.context("missing log index")?;
+            cursor = Some((L2BlockNumber(block_number.as_u32()), log_index.as_u32()));
+        }
+        Ok(events)
+    }
+
+    /// Indexes the next chunk of sealed L2 blocks if it's available. Returns `true` if any progress was made.
+    async fn process_next_step(&self) -> anyhow::Result<bool> {
+        let mut storage = self.pool.connection_tagged("bridge_indexer").await?;
+        let last_checkpoint = storage.bridge_events_dal().get_last_l2_checkpoint().await?;
+        if let Some((number, hash)) = last_checkpoint {
+            if Self::l2_block_hash(&mut storage, number).await? != Some(hash) {
+                tracing::warn!(
+                    "L2 block #{number} was replaced after its bridge events were indexed; re-indexing events \
+                     starting from this block"
+                );
+                storage
+                    .bridge_events_dal()
+                    .rollback_l2_events(number.0.checked_sub(1).map(L2BlockNumber))
+                    .await?;
+                METRICS.rollbacks[&Layer::L2].inc();
+                return Ok(true);
+            }
+        }
+
+        let Some(sealed_l2_block) = storage.blocks_dal().get_sealed_l2_block_number().await? else {
+            return Ok(false);
+        };
+        let earliest_l2_block = storage
+            .blocks_dal()
+            .get_earliest_l2_block_number()
+            .await?
+            .context("no L2 blocks in storage, although there is a sealed block")?;
+        // L2 blocks may be pruned while the component isn't running.
+        let from_block = last_checkpoint
+            .map_or(earliest_l2_block, |(number, _)| number + 1)
+            .max(earliest_l2_block);
+        if from_block > sealed_l2_block {
+            return Ok(false);
+        }
+        let to_block = sealed_l2_block.min(from_block + (Self::MAX_L2_BLOCKS_PER_ITERATION - 1));
+        let to_block_hash = Self::l2_block_hash(&mut storage, to_block)
+            .await?
+            .with_context(|| format!("L2 block #{to_block} disappeared from storage"))?;
+
+        let events = self
+            .collect_events(&mut storage, from_block, to_block)
+            .await
+            .with_context(|| {
+                format!("failed collecting bridge events for L2 blocks #{from_block}..=#{to_block}")
+            })?;
+
+        let mut transaction = storage.start_transaction().await?;
+        // The L2 block may be replaced while events are being collected, e.g. after a revert on an external node.
+        // Since reverts remove a suffix of L2 blocks, it's sufficient to check the last block in the range.
+        if Self::l2_block_hash(&mut transaction, to_block).await? != Some(to_block_hash) {
+            tracing::info!(
+                "L2 block #{to_block} was replaced while indexing bridge events; retrying"
+            );
+            return Ok(false);
+        }
+        transaction
+            .bridge_events_dal()
+            .insert_bridge_events(&events)
+            .await?;
+        transaction
+            .bridge_events_dal()
+            .insert_l2_checkpoint(to_block, to_block_hash)
+            .await?;
+        transaction.commit().await?;
+
+        tracing::debug!(
+            "Indexed {} bridge events in L2 blocks #{from_block}..=#{to_block}",
+            events.len()
+        );
+        for event in &events {
+            METRICS.events[&EventKind::from(event.kind)].inc();
+        }
+        METRICS.last_indexed_l2_block.set(to_block.0.into());
+        Ok(true)
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow_and_update() {
+            if self.process_next_step().await? {
+                continue;
+            }
+            // We don't check the result: if a stop signal is received, we'll return at the start
+            // of the next iteration.
+            tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                .await
+                .ok();
+        }
+        tracing::info!("Stop signal received, bridge indexer is shutting down");
+        Ok(())
+    }
+}
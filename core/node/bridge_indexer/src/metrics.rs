@@ -0,0 +1,45 @@
+//! Bridge indexer metrics.
+
+use vise::{Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Metrics};
+use zksync_types::api::BridgeTransferKind;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "kind", rename_all = "snake_case")]
+pub(super) enum EventKind {
+    DepositInitiated,
+    DepositFinalized,
+    WithdrawalInitiated,
+    WithdrawalFinalized,
+}
+
+impl From<BridgeTransferKind> for EventKind {
+    fn from(kind: BridgeTransferKind) -> Self {
+        match kind {
+            BridgeTransferKind::DepositInitiated => Self::DepositInitiated,
+            BridgeTransferKind::DepositFinalized => Self::DepositFinalized,
+            BridgeTransferKind::WithdrawalInitiated => Self::WithdrawalInitiated,
+            BridgeTransferKind::WithdrawalFinalized => Self::WithdrawalFinalized,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "layer", rename_all = "snake_case")]
+pub(super) enum Layer {
+    L1,
+    L2,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "bridge_indexer")]
+pub(super) struct BridgeIndexerMetrics {
+    /// Number of indexed bridge events grouped by the event kind.
+    pub events: Family<EventKind, Counter>,
+    /// Number of rollbacks of indexed events caused by L1 reorgs or L2 block reverts.
+    pub rollbacks: Family<Layer, Counter>,
+    /// Last L2 block with indexed events.
+    pub last_indexed_l2_block: Gauge<u64>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<BridgeIndexerMetrics> = vise::Global::new();
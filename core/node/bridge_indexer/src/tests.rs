@@ -0,0 +1,278 @@
+use zksync_dal::bridge_events_dal::BridgeEventLocation;
+use zksync_node_test_utils::create_l2_block;
+use zksync_types::{
+    address_to_h256,
+    api::{BridgeTransfer, BridgeTransferKind, Log},
+    tx::IncludedTxLocation,
+    u256_to_h256,
+    web3::Bytes,
+    Address, L1BatchNumber, L2ChainId, ProtocolVersion, L2_BASE_TOKEN_ADDRESS, U256, U64,
+};
+use zksync_vm_interface::VmEvent;
+
+use super::*;
+use crate::events::{
+    event_topic, parse_l1_event, BASE_TOKEN_MINT_EVENT, DEPOSIT_INITIATED_EVENT,
+    FINALIZE_DEPOSIT_EVENT, WITHDRAWAL_FINALIZED_EVENT, WITHDRAWAL_INITIATED_EVENT,
+};
+
+const L1_SENDER: Address = Address::repeat_byte(0x11);
+const L2_RECEIVER: Address = Address::repeat_byte(0x22);
+const L1_TOKEN: Address = Address::repeat_byte(0x33);
+const L2_TOKEN: Address = Address::repeat_byte(0x44);
+
+fn encode_words(words: &[H256]) -> Vec<u8> {
+    words.iter().flat_map(|word| word.0).collect()
+}
+
+fn mock_log(address: Address, topics: Vec<H256>, data: Vec<u8>, block_number: u64) -> Log {
+    Log {
+        address,
+        topics,
+        data: Bytes(data),
+        block_hash: None,
+        block_number: Some(block_number.into()),
+        l1_batch_number: None,
+        transaction_hash: Some(H256::repeat_byte(1)),
+        transaction_index: None,
+        log_index: Some(3.into()),
+        transaction_log_index: None,
+        log_type: None,
+        removed: None,
+        block_timestamp: None,
+    }
+}
+
+#[test]
+fn parsing_l2_events() {
+    let contracts = ContractsConfig::for_tests();
+    let l2_bridge_address = contracts.l2_shared_bridge_addr.unwrap();
+    let parser = L2EventParser::new(vec![l2_bridge_address]);
+
+    let log = mock_log(
+        l2_bridge_address,
+        vec![
+            event_topic(FINALIZE_DEPOSIT_EVENT),
+            address_to_h256(&L1_SENDER),
+            address_to_h256(&L2_RECEIVER),
+            address_to_h256(&L2_TOKEN),
+        ],
+        encode_words(&[u256_to_h256(100.into())]),
+        5,
+    );
+    let event = parser.parse(&log).unwrap().expect("not parsed");
+    assert_eq!(event.kind, BridgeTransferKind::DepositFinalized);
+    assert_eq!(event.l1_address, Some(L1_SENDER));
+    assert_eq!(event.l2_address, Some(L2_RECEIVER));
+    assert_eq!(event.l2_token, Some(L2_TOKEN));
+    assert_eq!(event.amount, 100.into());
+    assert_eq!(
+        event.location,
+        BridgeEventLocation::L2 {
+            block_number: L2BlockNumber(5),
+            event_index: 3,
+        }
+    );
+
+    let log = mock_log(
+        L2_BASE_TOKEN_ADDRESS,
+        vec![
+            event_topic(BASE_TOKEN_MINT_EVENT),
+            address_to_h256(&L2_RECEIVER),
+        ],
+        encode_words(&[u256_to_h256(42.into())]),
+        5,
+    );
+    let event = parser.parse(&log).unwrap().expect("not parsed");
+    assert_eq!(event.kind, BridgeTransferKind::DepositFinalized);
+    assert_eq!(event.l1_address, None);
+    assert_eq!(event.l2_token, Some(L2_BASE_TOKEN_ADDRESS));
+
+    // Events emitted by other contracts are ignored.
+    let mut log = log;
+    log.address = Address::repeat_byte(0x42);
+    assert!(parser.parse(&log).unwrap().is_none());
+    // Events with an unexpected format are ignored as well.
+    log.address = l2_bridge_address;
+    log.topics[0] = event_topic(WITHDRAWAL_INITIATED_EVENT);
+    assert!(parser.parse(&log).unwrap().is_none());
+}
+
+#[test]
+fn parsing_l1_events() {
+    let chain_id = L2ChainId::default();
+    let chain_id_topic = u256_to_h256(chain_id.as_u64().into());
+    let l1_bridge_address = Address::repeat_byte(0x0e);
+
+    let log = mock_log(
+        l1_bridge_address,
+        vec![
+            event_topic(DEPOSIT_INITIATED_EVENT),
+            chain_id_topic,
+            H256::repeat_byte(0xaa),
+            address_to_h256(&L1_SENDER),
+        ],
+        encode_words(&[
+            address_to_h256(&L2_RECEIVER),
+            address_to_h256(&L1_TOKEN),
+            u256_to_h256(1_000.into()),
+        ]),
+        100,
+    );
+    let event = parse_l1_event(&log, chain_id).unwrap().expect("not parsed");
+    assert_eq!(event.kind, BridgeTransferKind::DepositInitiated);
+    assert_eq!(event.l1_address, Some(L1_SENDER));
+    assert_eq!(event.l2_address, Some(L2_RECEIVER));
+    assert_eq!(event.l1_token, Some(L1_TOKEN));
+    assert_eq!(event.amount, 1_000.into());
+    assert_eq!(
+        event.location,
+        BridgeEventLocation::L1 {
+            block_number: 100,
+            log_index: 3,
+        }
+    );
+
+    // Events for other chains are ignored.
+    let other_chain_id = L2ChainId::from(chain_id.as_u64() as u32 + 1);
+    assert!(parse_l1_event(&log, other_chain_id).unwrap().is_none());
+
+    let log = mock_log(
+        l1_bridge_address,
+        vec![
+            event_topic(WITHDRAWAL_FINALIZED_EVENT),
+            chain_id_topic,
+            address_to_h256(&L1_SENDER),
+            address_to_h256(&L1_TOKEN),
+        ],
+        encode_words(&[u256_to_h256(5.into())]),
+        101,
+    );
+    let event = parse_l1_event(&log, chain_id).unwrap().expect("not parsed");
+    assert_eq!(event.kind, BridgeTransferKind::WithdrawalFinalized);
+    assert_eq!(event.l1_address, Some(L1_SENDER));
+    assert_eq!(event.l2_address, None);
+    assert_eq!(event.amount, 5.into());
+}
+
+fn deposit_event(l2_bridge_address: Address, block_number: u32, amount: u64) -> VmEvent {
+    VmEvent {
+        location: (L1BatchNumber(0), 0),
+        address: l2_bridge_address,
+        indexed_topics: vec![
+            event_topic(FINALIZE_DEPOSIT_EVENT),
+            address_to_h256(&L1_SENDER),
+            address_to_h256(&L2_RECEIVER),
+            address_to_h256(&L2_TOKEN),
+        ],
+        value: u256_to_h256(U256::from(amount) + block_number).0.to_vec(),
+    }
+}
+
+/// Stores an L2 block with the specified hash and a single transaction emitting the specified events.
+async fn store_l2_block(
+    storage: &mut Connection<'_, Core>,
+    number: u32,
+    hash: H256,
+    events: &[VmEvent],
+) {
+    let mut header = create_l2_block(number);
+    header.hash = hash;
+    storage.blocks_dal().insert_l2_block(&header).await.unwrap();
+    let location = IncludedTxLocation {
+        tx_hash: H256::from_low_u64_be(number.into()),
+        tx_index_in_l2_block: 0,
+        tx_initiator_address: Address::repeat_byte(2),
+    };
+    storage
+        .events_dal()
+        .save_events(
+            L2BlockNumber(number),
+            &[(location, events.iter().collect())],
+        )
+        .await
+        .unwrap();
+}
+
+async fn get_transfers(storage: &mut Connection<'_, Core>) -> Vec<BridgeTransfer> {
+    let transfers = storage
+        .bridge_events_dal()
+        .get_bridge_transfers(L2_RECEIVER, None, 100)
+        .await
+        .unwrap();
+    transfers
+        .into_iter()
+        .map(|(_, transfer)| transfer)
+        .collect()
+}
+
+#[tokio::test]
+async fn indexing_l2_events_with_reverts() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    storage
+        .protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion::default())
+        .await
+        .unwrap();
+
+    let contracts = ContractsConfig::for_tests();
+    let l2_bridge_address = contracts.l2_shared_bridge_addr.unwrap();
+    let indexer = BridgeIndexer::new(pool.clone(), &contracts);
+    assert!(!indexer.process_next_step().await.unwrap());
+
+    for number in 1..=2 {
+        let events = [
+            deposit_event(l2_bridge_address, number, 100),
+            // Event emitted by an unrelated contract
+            deposit_event(Address::repeat_byte(0x42), number, 100),
+        ];
+        store_l2_block(
+            &mut storage,
+            number,
+            H256::from_low_u64_be(number.into()),
+            &events,
+        )
+        .await;
+    }
+
+    assert!(indexer.process_next_step().await.unwrap());
+    assert!(!indexer.process_next_step().await.unwrap());
+    let transfers = get_transfers(&mut storage).await;
+    let amounts_and_blocks: Vec<_> = transfers
+        .iter()
+        .map(|transfer| (transfer.amount, transfer.l2_block_number))
+        .collect();
+    assert_eq!(
+        amounts_and_blocks,
+        [
+            (101.into(), Some(L2BlockNumber(1))),
+            (102.into(), Some(L2BlockNumber(2))),
+        ]
+    );
+    assert_eq!(
+        storage
+            .bridge_events_dal()
+            .get_last_l2_checkpoint()
+            .await
+            .unwrap(),
+        Some((L2BlockNumber(2), H256::from_low_u64_be(2)))
+    );
+
+    // Revert the last L2 block and replace it with another one.
+    storage
+        .blocks_dal()
+        .delete_l2_blocks(L2BlockNumber(1))
+        .await
+        .unwrap();
+    assert_eq!(get_transfers(&mut storage).await.len(), 1);
+    let events = [deposit_event(l2_bridge_address, 2, 1_000)];
+    store_l2_block(&mut storage, 2, H256::repeat_byte(0xff), &events).await;
+
+    assert!(indexer.process_next_step().await.unwrap());
+    let transfers = get_transfers(&mut storage).await;
+    let amounts: Vec<_> = transfers.iter().map(|transfer| transfer.amount).collect();
+    assert_eq!(amounts, [U256::from(101), U256::from(1_002)]);
+    assert_eq!(transfers[1].l2_block_number, Some(L2BlockNumber(2)));
+    assert_eq!(transfers[1].l1_block_number, None::<U64>);
+}
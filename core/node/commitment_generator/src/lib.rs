@@ -1,22 +1,24 @@
 use std::{num::NonZeroU32, ops, sync::Arc, time::Duration};
 
 use anyhow::Context;
-use itertools::Itertools;
 use tokio::{sync::watch, task::JoinHandle};
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
-use zksync_l1_contract_interface::i_executor::commit::kzg::pubdata_to_blob_commitments;
 use zksync_types::{
     blob::num_blobs_required,
     commitment::{
         AuxCommitments, BlobHash, CommitmentCommonInput, CommitmentInput, L1BatchAuxiliaryOutput,
         L1BatchCommitment, L1BatchCommitmentArtifacts, L1BatchCommitmentMode,
     },
-    h256_to_u256,
-    writes::{InitialStorageWrite, RepeatedStorageWrite, StateDiffRecord},
-    L1BatchNumber, ProtocolVersionId, StorageKey, H256, U256,
+    u256_to_h256,
+    writes::{InitialStorageWrite, RepeatedStorageWrite},
+    L1BatchNumber, ProtocolVersionId, H256, U256,
 };
 
+pub use crate::pubdata::{
+    BlobPubdataCommitmentScheme, KeccakPubdataCommitmentScheme, NoPubdataCommitmentScheme,
+    PubdataCommitmentScheme,
+};
 use crate::{
     metrics::{CommitmentStage, METRICS},
     utils::{
@@ -26,6 +28,7 @@ use crate::{
 };
 
 mod metrics;
+mod pubdata;
 #[cfg(test)]
 mod tests;
 mod utils;
@@ -37,6 +40,8 @@ const SLEEP_INTERVAL: Duration = Duration::from_millis(100);
 #[derive(Debug)]
 pub struct CommitmentGenerator {
     computer: Arc<dyn CommitmentComputer>,
+    /// Custom pubdata commitment scheme. If not set, the default scheme for the batch commitment mode is used.
+    pubdata_commitment_scheme: Option<Arc<dyn PubdataCommitmentScheme>>,
     connection_pool: ConnectionPool<Core>,
     health_updater: HealthUpdater,
     commitment_mode: L1BatchCommitmentMode,
@@ -44,13 +49,16 @@ pub struct CommitmentGenerator {
 }
 
 impl CommitmentGenerator {
-    /// Creates a commitment generator with the provided mode.
+    /// Creates a commitment generator with the provided mode. The mode is only used for L1 batches
+    /// that don't have a commitment mode persisted in Postgres. Pubdata commitments are computed using
+    /// the default scheme for the batch mode (KZG commitments for rollups and zero commitments for validiums).
     pub fn new(
         connection_pool: ConnectionPool<Core>,
         commitment_mode: L1BatchCommitmentMode,
     ) -> Self {
         Self {
             computer: Arc::new(RealCommitmentComputer),
+            pubdata_commitment_scheme: None,
             connection_pool,
             health_updater: ReactiveHealthCheck::new("commitment_generator").1,
             commitment_mode,
@@ -71,6 +79,41 @@ impl CommitmentGenerator {
         self.parallelism = parallelism;
     }
 
+    /// Sets the scheme used to commit to batch pubdata. Should be used by chains with DA validators
+    /// expecting pubdata commitments other than the default ones for the commitment mode.
+    pub fn set_pubdata_commitment_scheme(&mut self, scheme: Arc<dyn PubdataCommitmentScheme>) {
+        self.pubdata_commitment_scheme = Some(scheme);
+    }
+
+    fn pubdata_commitment_scheme(
+        &self,
+        commitment_mode: L1BatchCommitmentMode,
+    ) -> Arc<dyn PubdataCommitmentScheme> {
+        if let Some(scheme) = &self.pubdata_commitment_scheme {
+            return scheme.clone();
+        }
+        match commitment_mode {
+            L1BatchCommitmentMode::Rollup => Arc::new(BlobPubdataCommitmentScheme),
+            L1BatchCommitmentMode::Validium => Arc::new(NoPubdataCommitmentScheme),
+        }
+    }
+
+    /// Returns the commitment mode persisted for the L1 batch, falling back to the configured mode.
+    async fn batch_commitment_mode(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<L1BatchCommitmentMode> {
+        let mut connection = self
+            .connection_pool
+            .connection_tagged("commitment_generator")
+            .await?;
+        let mode = connection
+            .blocks_dal()
+            .get_l1_batch_commitment_mode(l1_batch_number)
+            .await?;
+        Ok(mode.unwrap_or(self.commitment_mode))
+    }
+
     /// Returns a health check for this generator.
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
@@ -151,6 +194,7 @@ impl CommitmentGenerator {
     async fn prepare_input(
         &self,
         l1_batch_number: L1BatchNumber,
+        commitment_mode: L1BatchCommitmentMode,
     ) -> anyhow::Result<CommitmentInput> {
         tracing::info!("Started preparing commitment input for L1 batch #{l1_batch_number}");
 
@@ -182,43 +226,26 @@ impl CommitmentGenerator {
             evm_emulator_code_hash: header.base_system_contracts_hashes.evm_emulator,
             protocol_version,
         };
-        let touched_slots = connection
-            .storage_logs_dal()
-            .get_touched_slots_for_executed_l1_batch(l1_batch_number)
-            .await?;
-        let touched_hashed_keys: Vec<_> =
-            touched_slots.keys().map(|key| key.hashed_key()).collect();
-        let previous_values = connection
+        let state_diff = connection
             .storage_logs_dal()
-            .get_previous_storage_values(&touched_hashed_keys, l1_batch_number)
-            .await?;
-        let l1_batches_for_initial_writes = connection
-            .storage_logs_dal()
-            .get_l1_batches_and_indices_for_initial_writes(&touched_hashed_keys)
+            .get_l1_batch_state_diff(l1_batch_number)
             .await?;
         drop(connection);
 
-        let mut input = if protocol_version.is_pre_boojum() {
+        let input = if protocol_version.is_pre_boojum() {
             let mut initial_writes = Vec::new();
             let mut repeated_writes = Vec::new();
-            for (key, value) in touched_slots.into_iter().sorted_by_key(|(key, _)| *key) {
-                let hashed_key = key.hashed_key();
-                if previous_values[&hashed_key].unwrap_or_default() != value {
-                    let (initial_write_l1_batch_number, index) =
-                        l1_batches_for_initial_writes[&hashed_key];
-                    assert!(
-                        initial_write_l1_batch_number <= l1_batch_number,
-                        "Slot {hashed_key:?} was changed in L1 batch {l1_batch_number} but in DB L1 batch of initial write is greater"
-                    );
-                    if initial_write_l1_batch_number == l1_batch_number {
-                        initial_writes.push(InitialStorageWrite {
-                            index,
-                            key: key.hashed_key_u256(),
-                            value,
-                        });
-                    } else {
-                        repeated_writes.push(RepeatedStorageWrite { index, value });
-                    }
+            for write in state_diff {
+                let index = write.enumeration_index;
+                let value = u256_to_h256(write.record.final_value);
+                if write.is_initial {
+                    initial_writes.push(InitialStorageWrite {
+                        index,
+                        key: U256::from_big_endian(&write.record.derived_key),
+                        value,
+                    });
+                } else {
+                    repeated_writes.push(RepeatedStorageWrite { index, value });
                 }
             }
 
@@ -232,53 +259,23 @@ impl CommitmentGenerator {
                 .calculate_aux_commitments(header.number, protocol_version)
                 .await?;
 
-            let mut state_diffs = Vec::new();
-            for (key, value) in touched_slots {
-                let hashed_key = key.hashed_key();
-                let prev_value = previous_values[&hashed_key].unwrap_or_default();
-                if prev_value != value {
-                    let (initial_write_l1_batch_number, index) =
-                        l1_batches_for_initial_writes[&hashed_key];
-                    assert!(
-                        initial_write_l1_batch_number <= l1_batch_number,
-                        "Slot {hashed_key:?} was changed in L1 batch {l1_batch_number} but in DB L1 batch of initial write is greater"
-                    );
-                    if initial_write_l1_batch_number == l1_batch_number {
-                        state_diffs.push(StateDiffRecord {
-                            address: *key.address(),
-                            key: h256_to_u256(*key.key()),
-                            derived_key: StorageKey::raw_hashed_key(key.address(), key.key()),
-                            enumeration_index: 0u64,
-                            initial_value: U256::default(),
-                            final_value: h256_to_u256(value),
-                        });
-                    } else {
-                        state_diffs.push(StateDiffRecord {
-                            address: *key.address(),
-                            key: h256_to_u256(*key.key()),
-                            derived_key: StorageKey::raw_hashed_key(key.address(), key.key()),
-                            enumeration_index: index,
-                            initial_value: h256_to_u256(prev_value),
-                            final_value: h256_to_u256(value),
-                        });
-                    }
-                }
-            }
-            state_diffs.sort_unstable_by_key(|rec| (rec.address, rec.key));
+            // Writes are already ordered as in the published state diff.
+            let state_diffs: Vec<_> = state_diff.into_iter().map(|write| write.record).collect();
 
             let blob_hashes = if protocol_version.is_post_1_4_2() {
                 let pubdata_input = header.pubdata_input.with_context(|| {
                     format!("`pubdata_input` is missing for L1 batch #{l1_batch_number}")
                 })?;
 
-                let commitments = pubdata_to_blob_commitments(
-                    num_blobs_required(&protocol_version),
-                    &pubdata_input,
-                );
-                let linear_hashes = pubdata_to_blob_linear_hashes(
-                    num_blobs_required(&protocol_version),
-                    pubdata_input,
+                let blob_count = num_blobs_required(&protocol_version);
+                let scheme = self.pubdata_commitment_scheme(commitment_mode);
+                let commitments = scheme.blob_commitments(blob_count, &pubdata_input)?;
+                anyhow::ensure!(
+                    commitments.len() == blob_count,
+                    "Pubdata commitment scheme {scheme:?} returned {} commitments, expected {blob_count}",
+                    commitments.len()
                 );
+                let linear_hashes = pubdata_to_blob_linear_hashes(blob_count, pubdata_input);
 
                 commitments
                     .into_iter()
@@ -312,7 +309,6 @@ impl CommitmentGenerator {
             }
         };
 
-        self.tweak_input(&mut input);
         Ok(input)
     }
 
@@ -323,14 +319,15 @@ impl CommitmentGenerator {
     ) -> anyhow::Result<L1BatchCommitmentArtifacts> {
         let latency =
             METRICS.generate_commitment_latency_stage[&CommitmentStage::PrepareInput].start();
-        let input = self.prepare_input(l1_batch_number).await?;
+        let commitment_mode = self.batch_commitment_mode(l1_batch_number).await?;
+        let input = self.prepare_input(l1_batch_number, commitment_mode).await?;
         let latency = latency.observe();
         tracing::debug!("Prepared commitment input for L1 batch #{l1_batch_number} in {latency:?}");
 
         let latency =
             METRICS.generate_commitment_latency_stage[&CommitmentStage::Calculate].start();
         let mut commitment = L1BatchCommitment::new(input);
-        self.post_process_commitment(&mut commitment);
+        Self::post_process_commitment(commitment_mode, &mut commitment);
         let artifacts = commitment.artifacts();
         let latency = latency.observe();
         tracing::debug!(
@@ -382,22 +379,11 @@ impl CommitmentGenerator {
         Ok(())
     }
 
-    fn tweak_input(&self, input: &mut CommitmentInput) {
-        match (self.commitment_mode, input) {
-            (L1BatchCommitmentMode::Rollup, _) => {
-                // Do nothing
-            }
-            (L1BatchCommitmentMode::Validium, CommitmentInput::PostBoojum { blob_hashes, .. }) => {
-                for hashes in blob_hashes {
-                    hashes.commitment = H256::zero();
-                }
-            }
-            (L1BatchCommitmentMode::Validium, _) => { /* Do nothing */ }
-        }
-    }
-
-    fn post_process_commitment(&self, commitment: &mut L1BatchCommitment) {
-        match (self.commitment_mode, &mut commitment.auxiliary_output) {
+    fn post_process_commitment(
+        commitment_mode: L1BatchCommitmentMode,
+        commitment: &mut L1BatchCommitment,
+    ) {
+        match (commitment_mode, &mut commitment.auxiliary_output) {
             (
                 L1BatchCommitmentMode::Validium,
                 L1BatchAuxiliaryOutput::PostBoojum { blob_hashes, .. },
@@ -443,7 +429,7 @@ impl CommitmentGenerator {
     /// processed by the Merkle tree (or a tree fetcher), with a previously configured max parallelism.
     pub async fn run(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         tracing::info!(
-            "Starting commitment generator with mode {:?} and parallelism {}",
+            "Starting commitment generator with default mode {:?} and parallelism {}",
             self.commitment_mode,
             self.parallelism
         );
@@ -0,0 +1,64 @@
+//! Pluggable schemes of committing to batch pubdata.
+
+use std::fmt;
+
+use zksync_l1_contract_interface::i_executor::commit::kzg::{
+    pubdata_to_blob_commitments, ZK_SYNC_BYTES_PER_BLOB,
+};
+use zksync_types::{web3::keccak256, H256};
+
+/// Scheme of committing to the pubdata of an L1 batch. The pubdata is split into blobs, and the commitment
+/// to each blob is included into the batch commitment (as a part of the auxiliary output), so the scheme must match
+/// the one checked by the DA validator of the chain on L1.
+///
+/// - All methods are considered to be blocking.
+/// - Returned errors are considered unrecoverable (i.e., they bubble up and lead to commitment generator termination).
+pub trait PubdataCommitmentScheme: fmt::Debug + Send + Sync + 'static {
+    /// Computes commitments to `blob_count` blobs the `pubdata` is split into. The returned `Vec` must have
+    /// exactly `blob_count` elements; commitments to unused blobs should be zero.
+    fn blob_commitments(&self, blob_count: usize, pubdata: &[u8]) -> anyhow::Result<Vec<H256>>;
+}
+
+/// KZG commitments to EIP-4844 blobs. Used by rollups.
+#[derive(Debug)]
+pub struct BlobPubdataCommitmentScheme;
+
+impl PubdataCommitmentScheme for BlobPubdataCommitmentScheme {
+    fn blob_commitments(&self, blob_count: usize, pubdata: &[u8]) -> anyhow::Result<Vec<H256>> {
+        Ok(pubdata_to_blob_commitments(blob_count, pubdata))
+    }
+}
+
+/// Zero commitments. Used by validiums whose DA validators don't check pubdata commitments.
+#[derive(Debug)]
+pub struct NoPubdataCommitmentScheme;
+
+impl PubdataCommitmentScheme for NoPubdataCommitmentScheme {
+    fn blob_commitments(&self, blob_count: usize, _pubdata: &[u8]) -> anyhow::Result<Vec<H256>> {
+        Ok(vec![H256::zero(); blob_count])
+    }
+}
+
+/// Keccak-256 hashes of blobs, each padded with zeros to the blob size. Can be used by validiums
+/// whose DA validators only check pubdata hashes.
+#[derive(Debug)]
+pub struct KeccakPubdataCommitmentScheme;
+
+impl PubdataCommitmentScheme for KeccakPubdataCommitmentScheme {
+    fn blob_commitments(&self, blob_count: usize, pubdata: &[u8]) -> anyhow::Result<Vec<H256>> {
+        let chunks = pubdata.chunks(ZK_SYNC_BYTES_PER_BLOB);
+        anyhow::ensure!(
+            chunks.len() <= blob_count,
+            "pubdata of {} bytes doesn't fit into {blob_count} blobs",
+            pubdata.len()
+        );
+
+        let mut commitments = vec![H256::zero(); blob_count];
+        for (commitment, chunk) in commitments.iter_mut().zip(chunks) {
+            let mut blob = chunk.to_vec();
+            blob.resize(ZK_SYNC_BYTES_PER_BLOB, 0);
+            *commitment = H256(keccak256(&blob));
+        }
+        Ok(commitments)
+    }
+}
@@ -205,6 +205,36 @@ async fn commitment_generator_normal_operation() {
     generator_handle.await.unwrap().unwrap();
 }
 
+#[tokio::test]
+async fn commitment_generator_uses_persisted_commitment_mode() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    seal_l1_batch(&mut storage, L1BatchNumber(1)).await;
+    save_l1_batch_tree_data(&mut storage, L1BatchNumber(1)).await;
+
+    let generator = create_commitment_generator(pool.clone());
+    let rollup_artifacts = generator.process_batch(L1BatchNumber(1)).await.unwrap();
+    let mut validium_generator = create_commitment_generator(pool.clone());
+    validium_generator.commitment_mode = L1BatchCommitmentMode::Validium;
+    let validium_artifacts = validium_generator
+        .process_batch(L1BatchNumber(1))
+        .await
+        .unwrap();
+    assert_ne!(rollup_artifacts, validium_artifacts);
+
+    // The mode persisted for the batch should take precedence over the configured one.
+    storage
+        .blocks_dal()
+        .set_l1_batch_commitment_mode(L1BatchNumber(1), L1BatchCommitmentMode::Validium)
+        .await
+        .unwrap();
+    let artifacts = generator.process_batch(L1BatchNumber(1)).await.unwrap();
+    assert_eq!(artifacts, validium_artifacts);
+}
+
 #[tokio::test]
 async fn commitment_generator_bulk_processing() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -321,6 +351,30 @@ impl From<SerdeVmEvent> for VmEvent {
     }
 }
 
+#[test]
+fn pubdata_commitment_schemes() {
+    let pubdata = vec![1_u8; 100];
+    let commitments = NoPubdataCommitmentScheme
+        .blob_commitments(2, &pubdata)
+        .unwrap();
+    assert_eq!(commitments, [H256::zero(); 2]);
+
+    // Hash-only commitments must coincide with linear hashes of blobs.
+    let commitments = KeccakPubdataCommitmentScheme
+        .blob_commitments(2, &pubdata)
+        .unwrap();
+    assert_eq!(
+        commitments,
+        pubdata_to_blob_linear_hashes(2, pubdata.clone())
+    );
+    assert_ne!(commitments[0], H256::zero());
+    assert_eq!(commitments[1], H256::zero());
+
+    KeccakPubdataCommitmentScheme
+        .blob_commitments(0, &pubdata)
+        .unwrap_err();
+}
+
 #[test]
 fn test_convert_vm_events_to_log_queries() {
     let cases: Vec<serde_json::Value> = vec![
@@ -1,5 +1,8 @@
 //! Configuration utilities for the consensus component.
-use std::collections::{BTreeMap, HashMap};
+use std::{
+    collections::{BTreeMap, HashMap},
+    num::NonZeroU64,
+};
 
 use anyhow::Context as _;
 use secrecy::{ExposeSecret as _, Secret};
@@ -45,6 +48,7 @@ pub(super) struct GenesisSpec {
     pub(super) attesters: Option<attester::Committee>,
     pub(super) leader_selection: validator::LeaderSelectionMode,
     pub(super) registry_address: Option<ethabi::Address>,
+    pub(super) attester_committee_epoch: Option<NonZeroU64>,
     pub(super) seed_peers: BTreeMap<node::PublicKey, net::Host>,
 }
 
@@ -57,6 +61,7 @@ impl GenesisSpec {
             attesters: cfg.genesis.attesters.clone(),
             leader_selection: cfg.genesis.leader_selection.clone(),
             registry_address: cfg.registry_address,
+            attester_committee_epoch: cfg.attester_committee_epoch,
             seed_peers: cfg.seed_peers.clone(),
         }
     }
@@ -101,6 +106,7 @@ impl GenesisSpec {
                 Some(attester::Committee::new(attesters).context("attesters")?)
             },
             registry_address: x.registry_address,
+            attester_committee_epoch: x.attester_committee_epoch,
             seed_peers: x
                 .seed_peers
                 .iter()
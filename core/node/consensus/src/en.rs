@@ -6,7 +6,9 @@ use zksync_consensus_executor::{self as executor, attestation};
 use zksync_consensus_roles::{attester, validator};
 use zksync_consensus_storage::{BlockStore, PersistentBlockStore as _};
 use zksync_dal::consensus_dal;
-use zksync_node_sync::{fetcher::FetchedBlock, sync_action::ActionQueueSender, SyncState};
+use zksync_node_sync::{
+    fetcher::FetchedBlock, sync_action::ActionQueueSender, SyncPath, SyncState,
+};
 use zksync_types::L2BlockNumber;
 use zksync_web3_decl::{
     client::{DynClient, L2},
@@ -22,7 +24,8 @@ use crate::{
     storage::{self, ConnectionPool},
 };
 
-/// Whenever more than FALLBACK_FETCHER_THRESHOLD certificates are missing,
+/// Whenever more than FALLBACK_FETCHER_THRESHOLD certificates are missing
+/// (e.g., because p2p syncing has stalled due to a loss of the validator quorum),
 /// the fallback fetcher is active.
 pub(crate) const FALLBACK_FETCHER_THRESHOLD: u64 = 10;
 
@@ -51,6 +54,7 @@ impl EN {
             "external node attester mode"
         );
 
+        self.sync_state.set_sync_path(SyncPath::Consensus);
         let res: ctx::Result<()> = scope::run!(ctx, |ctx, s| async {
             // Update sync state in the background.
             s.spawn_bg(self.fetch_state_loop(ctx));
@@ -179,6 +183,7 @@ impl EN {
             WARNING: this node is using ZKsync API synchronization, which will be deprecated soon. \
             Please follow this instruction to switch to p2p synchronization: \
             https://github.com/matter-labs/zksync-era/blob/main/docs/guides/external-node/10_decentralization.md");
+        self.sync_state.set_sync_path(SyncPath::JsonRpc);
         let res: ctx::Result<()> = scope::run!(ctx, |ctx, s| async {
             // Update sync state in the background.
             s.spawn_bg(self.fetch_state_loop(ctx));
@@ -208,8 +213,14 @@ impl EN {
         attestation: Arc<attestation::Controller>,
     ) -> ctx::Result<()> {
         const POLL_INTERVAL: time::Duration = time::Duration::seconds(5);
-        let registry = registry::Registry::new(cfg.genesis.clone(), self.pool.clone()).await;
+        let registry = registry::Registry::new(
+            cfg.genesis.clone(),
+            cfg.attester_committee_epoch,
+            self.pool.clone(),
+        )
+        .await;
         let mut next = attester::BatchNumber(0);
+        let mut prev_committee = None;
         loop {
             let status = loop {
                 match self
@@ -253,6 +264,11 @@ impl EN {
                 tracing::info!("attestation not required");
                 continue;
             };
+            METRICS.observe_attester_committee(
+                &mut prev_committee,
+                status.next_batch_to_attest,
+                &committee,
+            );
             let committee = Arc::new(committee);
             // Persist the derived committee.
             self.pool
@@ -339,6 +355,7 @@ impl EN {
             .proto_fmt(&genesis.0)
             .context("deserialize()")?,
             registry_address: None,
+            attester_committee_epoch: None,
             seed_peers: [].into(),
         })
     }
@@ -381,6 +398,7 @@ impl EN {
     }
 
     /// Fetches blocks from the main node directly whenever the EN is lagging behind too much.
+    /// The active sync path is reported via [`SyncState`] so that it's visible in health checks and metrics.
     pub(crate) async fn fallback_block_fetcher(
         &self,
         ctx: &ctx::Ctx,
@@ -389,17 +407,26 @@ impl EN {
         const MAX_CONCURRENT_REQUESTS: usize = 30;
         scope::run!(ctx, |ctx, s| async {
             let (send, mut recv) = ctx::channel::bounded(MAX_CONCURRENT_REQUESTS);
-            // TODO: metrics.
             s.spawn::<()>(async {
                 let send = send;
                 let is_lagging =
                     |main| main >= store.persisted().borrow().next() + FALLBACK_FETCHER_THRESHOLD;
                 let mut next = store.next_block(ctx).await.wrap("next_block()")?;
                 loop {
+                    let main_node_block = self.sync_state.get_main_node_block();
+                    if !is_lagging(validator::BlockNumber(main_node_block.0.into())) {
+                        self.sync_state.set_sync_path(SyncPath::Consensus);
+                    }
                     // Wait until p2p syncing is lagging.
                     self.sync_state
                         .wait_for_main_node_block(ctx, is_lagging)
                         .await?;
+                    if self.sync_state.sync_path() != Some(SyncPath::JsonRpc) {
+                        tracing::warn!(
+                            "p2p syncing is lagging behind the main node; falling back to fetching blocks via JSON-RPC"
+                        );
+                        self.sync_state.set_sync_path(SyncPath::JsonRpc);
+                    }
                     // Determine the next block to fetch and wait for it to be available.
                     next = next.max(store.next_block(ctx).await.wrap("next_block()")?);
                     self.sync_state
@@ -1,5 +1,9 @@
 //! Consensus related metrics.
 
+use std::collections::BTreeSet;
+
+use zksync_consensus_roles::attester;
+
 #[derive(Debug, vise::Metrics)]
 #[metrics(prefix = "zksync_node_consensus")]
 pub(crate) struct Metrics {
@@ -7,6 +11,59 @@ pub(crate) struct Metrics {
     /// It is used only as a fallback when the p2p syncing is disabled or falling behind.
     /// so it shouldn't be increasing under normal circumstances if p2p syncing is enabled.
     pub fetch_block: vise::Counter,
+    /// Number of attesters in the committee for the last batch to attest.
+    pub attester_committee_size: vise::Gauge<usize>,
+    /// Total weight of the attester committee for the last batch to attest.
+    pub attester_committee_weight: vise::Gauge<u64>,
+    /// Number of times the attester committee has changed, as read from the consensus registry contract.
+    pub attester_committee_changes: vise::Counter,
+    /// Number of attesters from the committee that didn't sign a collected batch certificate.
+    /// Only reported by the main node.
+    pub missed_attestations: vise::Counter,
+}
+
+impl Metrics {
+    /// Reports the attester committee for `batch`, logging whether it differs from the previously observed one.
+    pub(crate) fn observe_attester_committee(
+        &self,
+        prev_committee: &mut Option<attester::Committee>,
+        batch: attester::BatchNumber,
+        committee: &attester::Committee,
+    ) {
+        if prev_committee.as_ref() != Some(committee) {
+            if prev_committee.is_some() {
+                tracing::info!(
+                    "attester committee changed at batch {batch:?}; new committee has {} attesters",
+                    committee.len()
+                );
+                self.attester_committee_changes.inc();
+            }
+            *prev_committee = Some(committee.clone());
+        }
+        self.attester_committee_size.set(committee.len());
+        self.attester_committee_weight.set(committee.total_weight());
+    }
+
+    /// Reports attesters from the `committee` that didn't sign the certificate.
+    pub(crate) fn observe_batch_certificate(
+        &self,
+        committee: &attester::Committee,
+        qc: &attester::BatchQC,
+    ) {
+        let signed: BTreeSet<_> = qc.signatures.keys().collect();
+        let missed = committee
+            .iter()
+            .filter(|attester| !signed.contains(&attester.key))
+            .count();
+        if missed > 0 {
+            tracing::info!(
+                "{missed} attester(s) out of {} didn't sign certificate for batch {:?}",
+                committee.len(),
+                qc.message.number
+            );
+        }
+        self.missed_attestations.inc_by(missed as u64);
+    }
 }
 
 #[vise::register]
@@ -9,7 +9,9 @@ use zksync_consensus_storage::BlockStore;
 use zksync_dal::consensus_dal;
 
 use crate::{
-    config, registry,
+    config,
+    metrics::METRICS,
+    registry,
     storage::{ConnectionPool, Store},
 };
 
@@ -118,9 +120,11 @@ async fn run_attestation_controller(
     attestation: Arc<attestation::Controller>,
 ) -> ctx::Result<()> {
     const POLL_INTERVAL: time::Duration = time::Duration::seconds(5);
-    let registry = registry::Registry::new(cfg.genesis, pool.clone()).await;
+    let registry =
+        registry::Registry::new(cfg.genesis, cfg.attester_committee_epoch, pool.clone()).await;
     let registry_addr = cfg.registry_address.map(registry::Address::new);
     let mut next = attester::BatchNumber(0);
+    let mut prev_committee = None;
     loop {
         // After regenesis it might happen that the batch number for the first block
         // is not immediately known (the first block was not produced yet),
@@ -156,6 +160,11 @@ async fn run_attestation_controller(
             tracing::info!("attestation not required");
             continue;
         };
+        METRICS.observe_attester_committee(
+            &mut prev_committee,
+            status.next_batch_to_attest,
+            &committee,
+        );
         let committee = Arc::new(committee);
         // Persist the derived committee.
         pool.connection(ctx)
@@ -175,7 +184,7 @@ async fn run_attestation_controller(
                     number: status.next_batch_to_attest,
                     genesis: status.genesis,
                 },
-                committee,
+                committee: committee.clone(),
             }))
             .await
             .context("start_attestation()")?;
@@ -189,6 +198,7 @@ async fn run_attestation_controller(
             "collected certificate for batch {:?}",
             status.next_batch_to_attest
         );
+        METRICS.observe_batch_certificate(&committee, &qc);
         pool.connection(ctx)
             .await
             .wrap("connection()")?
@@ -1,3 +1,5 @@
+use std::{num::NonZeroU64, sync::Mutex};
+
 use anyhow::Context as _;
 use zksync_concurrency::{ctx, error::Wrap as _};
 use zksync_consensus_crypto::ByteFmt;
@@ -31,34 +33,67 @@ pub type Address = crate::abi::Address<abi::ConsensusRegistry>;
 pub(crate) struct Registry {
     contract: abi::ConsensusRegistry,
     genesis: validator::Genesis,
+    attester_committee_epoch: Option<NonZeroU64>,
+    /// Committee read at the last requested batch defining a committee.
+    cached_committee: Mutex<Option<(Address, attester::BatchNumber, attester::Committee)>>,
     vm: VM,
 }
 
 impl Registry {
-    pub async fn new(genesis: validator::Genesis, pool: ConnectionPool) -> Self {
+    pub async fn new(
+        genesis: validator::Genesis,
+        attester_committee_epoch: Option<NonZeroU64>,
+        pool: ConnectionPool,
+    ) -> Self {
         Self {
             contract: abi::ConsensusRegistry::load(),
             genesis,
+            attester_committee_epoch,
+            cached_committee: Mutex::default(),
             vm: VM::new(pool).await,
         }
     }
 
+    /// Batch at which the state of the contract defines the attester committee for `attested_batch`.
+    /// If the attester committee epoch is configured, this is the first batch of the epoch
+    /// containing the batch preceding `attested_batch`, so that committee changes take effect only
+    /// at epoch boundaries. Returns `None` for batch 0, which doesn't need attestation.
+    fn batch_defining_committee(
+        &self,
+        attested_batch: attester::BatchNumber,
+    ) -> Option<attester::BatchNumber> {
+        let prev_batch = attested_batch.prev()?;
+        Some(match self.attester_committee_epoch {
+            Some(epoch) => attester::BatchNumber(prev_batch.0 / epoch.get() * epoch.get()),
+            None => prev_batch,
+        })
+    }
+
     /// Attester committee for the given batch.
     /// It reads committee from the contract.
     /// Falls back to committee specified in the genesis.
+    ///
+    /// The validator committee is not read from the contract; changing it requires a hard fork.
     pub async fn attester_committee_for(
         &self,
         ctx: &ctx::Ctx,
         address: Option<Address>,
         attested_batch: attester::BatchNumber,
     ) -> ctx::Result<Option<attester::Committee>> {
-        let Some(batch_defining_committee) = attested_batch.prev() else {
+        let Some(batch_defining_committee) = self.batch_defining_committee(attested_batch) else {
             // Batch 0 doesn't need attestation.
             return Ok(None);
         };
         let Some(address) = address else {
             return Ok(self.genesis.attesters.clone());
         };
+        if let Some((cached_address, cached_batch, committee)) =
+            &*self.cached_committee.lock().unwrap()
+        {
+            if *cached_address == address && *cached_batch == batch_defining_committee {
+                return Ok(Some(committee.clone()));
+            }
+        }
         let raw = self
             .vm
             .call(
@@ -73,8 +108,10 @@ impl Registry {
         for a in raw {
             attesters.push(decode_weighted_attester(&a).context("decode_weighted_attester()")?);
         }
-        Ok(Some(
-            attester::Committee::new(attesters.into_iter()).context("Committee::new()")?,
-        ))
+        let committee =
+            attester::Committee::new(attesters.into_iter()).context("Committee::new()")?;
+        *self.cached_committee.lock().unwrap() =
+            Some((address, batch_defining_committee, committee.clone()));
+        Ok(Some(committee))
     }
 }
@@ -21,6 +21,26 @@ fn test_consensus_registry_abi() {
     c.call(abi::Owner).test().unwrap();
 }
 
+#[tokio::test]
+async fn test_attester_committee_epoch() {
+    let rng = &mut rand::thread_rng();
+    let setup = Setup::new(rng, 3);
+    let pool = ConnectionPool::test(false, ProtocolVersionId::latest()).await;
+    let registry = Registry::new(setup.genesis.clone(), NonZeroU64::new(10), pool).await;
+
+    assert_eq!(
+        registry.batch_defining_committee(attester::BatchNumber(0)),
+        None
+    );
+    for (attested_batch, defining_batch) in [(1, 0), (10, 0), (11, 10), (20, 10), (21, 20)] {
+        assert_eq!(
+            registry.batch_defining_committee(attester::BatchNumber(attested_batch)),
+            Some(attester::BatchNumber(defining_batch)),
+            "{attested_batch}"
+        );
+    }
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_attester_committee() {
     zksync_concurrency::testonly::abort_on_panic();
@@ -32,7 +52,7 @@ async fn test_attester_committee() {
 
     scope::run!(ctx, |ctx, s| async {
         let pool = ConnectionPool::test(false, ProtocolVersionId::latest()).await;
-        let registry = Registry::new(setup.genesis.clone(), pool.clone()).await;
+        let registry = Registry::new(setup.genesis.clone(), None, pool.clone()).await;
 
         // If the registry contract address is not specified,
         // then the committee from genesis should be returned.
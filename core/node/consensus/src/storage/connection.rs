@@ -282,6 +282,7 @@ impl<'a> Connection<'a> {
             }
             .with_hash(),
             registry_address: spec.registry_address,
+            attester_committee_epoch: spec.attester_committee_epoch,
             seed_peers: spec.seed_peers.clone(),
         };
 
@@ -104,6 +104,7 @@ pub(super) fn new_configs(rng: &mut impl Rng, setup: &Setup, seed_peers: usize)
             .collect(),
         leader: config::ValidatorPublicKey(setup.validator_keys[0].public().encode()),
         registry_address: None,
+        attester_committee_epoch: None,
         seed_peers: net_cfgs[..seed_peers]
             .iter()
             .map(|c| {
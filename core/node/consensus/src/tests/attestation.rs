@@ -47,6 +47,7 @@ async fn test_attestation_status_api(version: ProtocolVersionId) {
             &consensus_dal::GlobalConfig {
                 genesis: setup.genesis.clone(),
                 registry_address: None,
+                attester_committee_epoch: None,
                 seed_peers: [].into(),
             },
         )
@@ -151,7 +152,7 @@ async fn test_multiple_attesters(version: ProtocolVersionId) {
 
         tracing::info!("deploy registry with 1 attester");
         let attesters: Vec<_> = setup.genesis.attesters.as_ref().unwrap().iter().collect();
-        let registry = Registry::new(setup.genesis.clone(), validator_pool.clone()).await;
+        let registry = Registry::new(setup.genesis.clone(), None, validator_pool.clone()).await;
         let (registry_addr, tx) = registry.deploy(account);
         cfgs[0]
             .config
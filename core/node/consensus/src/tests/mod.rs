@@ -41,6 +41,7 @@ async fn test_verify_pregenesis_block(version: ProtocolVersionId) {
     let cfg = consensus_dal::GlobalConfig {
         genesis: setup.genesis.clone(),
         registry_address: None,
+        attester_committee_epoch: None,
         seed_peers: [].into(),
     };
 
@@ -144,6 +145,7 @@ async fn test_validator_block_store(version: ProtocolVersionId) {
             &consensus_dal::GlobalConfig {
                 genesis: setup.genesis.clone(),
                 registry_address: None,
+                attester_committee_epoch: None,
                 seed_peers: [].into(),
             },
         )
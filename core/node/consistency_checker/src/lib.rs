@@ -1,23 +1,31 @@
-use std::{borrow::Cow, cmp::Ordering, collections::HashSet, fmt, time::Duration};
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::Arc,
+    time::Duration,
+};
 
 use anyhow::Context as _;
 use serde::Serialize;
 use tokio::sync::watch;
 use zksync_contracts::{
     bridgehub_contract, POST_BOOJUM_COMMIT_FUNCTION, POST_SHARED_BRIDGE_COMMIT_FUNCTION,
+    POST_SHARED_BRIDGE_EXECUTE_FUNCTION, POST_SHARED_BRIDGE_PROVE_FUNCTION,
     PRE_BOOJUM_COMMIT_FUNCTION,
 };
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_eth_client::{
     clients::{DynClient, L1},
-    CallFunctionArgs, ContractCallError, EnrichedClientError, EthInterface,
+    CallFunctionArgs, ContractCallError, EnrichedClientError, EthInterface, ExecutedTxStatus,
 };
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_l1_contract_interface::{
     i_executor::{
-        commit::kzg::ZK_SYNC_BYTES_PER_BLOB,
+        commit::{data_generator::L1BatchCommitDataGenerator, kzg::ZK_SYNC_BYTES_PER_BLOB},
         structures::{
-            CommitBatchInfo, StoredBatchInfo, PUBDATA_SOURCE_BLOBS, PUBDATA_SOURCE_CALLDATA,
+            StoredBatchInfo, PUBDATA_SOURCE_BLOBS, PUBDATA_SOURCE_CALLDATA,
             PUBDATA_SOURCE_CUSTOM_PRE_GATEWAY, SUPPORTED_ENCODING_VERSION,
         },
     },
@@ -25,12 +33,13 @@ use zksync_l1_contract_interface::{
 };
 use zksync_shared_metrics::{CheckerComponent, EN_METRICS};
 use zksync_types::{
+    aggregated_operations::AggregatedActionType,
     commitment::{L1BatchCommitmentMode, L1BatchWithMetadata},
     ethabi,
     ethabi::{ParamType, Token},
     pubdata_da::PubdataSendingMode,
-    Address, L1BatchNumber, L2ChainId, ProtocolVersionId, SLChainId, H256, L2_BRIDGEHUB_ADDRESS,
-    U256,
+    web3, Address, L1BatchNumber, L2ChainId, ProtocolVersionId, SLChainId, H256,
+    L2_BRIDGEHUB_ADDRESS, U256,
 };
 
 #[cfg(test)]
@@ -43,7 +52,7 @@ enum CheckError {
     #[error("error calling L1 contract")]
     ContractCall(#[from] ContractCallError),
     /// Error that is caused by the main node providing incorrect information etc.
-    #[error("failed validating commit transaction: {0}")]
+    #[error("failed validating L1 transaction: {0}")]
     Validation(anyhow::Error),
     /// Error that is caused by violating invariants internal to *this* node (e.g., not having expected data in Postgres).
     #[error("internal error: {0}")]
@@ -61,6 +70,46 @@ impl CheckError {
     }
 }
 
+/// Stage of the L1 batch life cycle on the settlement layer following the batch commitment. Transactions
+/// for these stages are checked after the commit transaction for the same batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum BatchStage {
+    Prove,
+    Execute,
+}
+
+impl BatchStage {
+    const ALL: [Self; 2] = [Self::Prove, Self::Execute];
+
+    fn tx_name(self) -> &'static str {
+        match self {
+            Self::Prove => "prove",
+            Self::Execute => "execute",
+        }
+    }
+
+    fn event_name(self) -> &'static str {
+        match self {
+            Self::Prove => "BlocksVerification",
+            Self::Execute => "BlockExecution",
+        }
+    }
+
+    fn action_type(self) -> AggregatedActionType {
+        match self {
+            Self::Prove => AggregatedActionType::PublishProofOnchain,
+            Self::Execute => AggregatedActionType::Execute,
+        }
+    }
+
+    fn metrics_component(self) -> CheckerComponent {
+        match self {
+            Self::Prove => CheckerComponent::ConsistencyCheckerProve,
+            Self::Execute => CheckerComponent::ConsistencyCheckerExecute,
+        }
+    }
+}
+
 /// Handler of life cycle events emitted by [`ConsistencyChecker`].
 trait HandleConsistencyCheckerEvent: fmt::Debug + Send + Sync {
     fn initialize(&mut self);
@@ -69,6 +118,8 @@ trait HandleConsistencyCheckerEvent: fmt::Debug + Send + Sync {
 
     fn update_checked_batch(&mut self, last_checked_batch: L1BatchNumber);
 
+    fn update_checked_stage(&mut self, stage: BatchStage, last_checked_batch: L1BatchNumber);
+
     fn report_inconsistent_batch(&mut self, number: L1BatchNumber, err: &anyhow::Error);
 }
 
@@ -79,6 +130,10 @@ struct ConsistencyCheckerDetails {
     first_checked_batch: Option<L1BatchNumber>,
     #[serde(skip_serializing_if = "Option::is_none")]
     last_checked_batch: Option<L1BatchNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_checked_prove_batch: Option<L1BatchNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_checked_execute_batch: Option<L1BatchNumber>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     inconsistent_batches: Vec<L1BatchNumber>,
 }
@@ -130,6 +185,20 @@ impl HandleConsistencyCheckerEvent for ConsistencyCheckerHealthUpdater {
         self.inner.update(self.current_details.health());
     }
 
+    fn update_checked_stage(&mut self, stage: BatchStage, last_checked_batch: L1BatchNumber) {
+        tracing::info!(
+            "{} transaction for L1 batch #{last_checked_batch} is consistent with L1",
+            stage.tx_name()
+        );
+        EN_METRICS.last_correct_batch[&stage.metrics_component()].set(last_checked_batch.0.into());
+        let details = &mut self.current_details;
+        match stage {
+            BatchStage::Prove => details.last_checked_prove_batch = Some(last_checked_batch),
+            BatchStage::Execute => details.last_checked_execute_batch = Some(last_checked_batch),
+        }
+        self.inner.update(self.current_details.health());
+    }
+
     fn report_inconsistent_batch(&mut self, number: L1BatchNumber, err: &anyhow::Error) {
         tracing::warn!("L1 batch #{number} is inconsistent with L1: {err:?}");
         self.current_details.inconsistent_batches.push(number);
@@ -158,11 +227,12 @@ struct LocalL1BatchCommitData {
 
 impl LocalL1BatchCommitData {
     /// Returns `Ok(None)` if Postgres doesn't contain all data necessary to check L1 commitment
-    /// for the specified batch.
+    /// for the specified batch. `default_commitment_mode` is used if the commitment mode
+    /// for the batch cannot be determined from Postgres.
     async fn new(
         storage: &mut Connection<'_, Core>,
         batch_number: L1BatchNumber,
-        commitment_mode: L1BatchCommitmentMode,
+        default_commitment_mode: L1BatchCommitmentMode,
     ) -> anyhow::Result<Option<Self>> {
         let Some(commit_tx_id) = storage
             .blocks_dal()
@@ -187,6 +257,11 @@ impl LocalL1BatchCommitData {
         else {
             return Ok(None);
         };
+        let commitment_mode = storage
+            .blocks_dal()
+            .get_l1_batch_commitment_mode(batch_number)
+            .await?
+            .unwrap_or(default_commitment_mode);
 
         let this = Self {
             l1_batch,
@@ -231,7 +306,11 @@ impl LocalL1BatchCommitData {
     }
 
     /// All returned errors are validation errors.
-    fn verify_commitment(&self, reference: &ethabi::Token) -> anyhow::Result<()> {
+    fn verify_commitment(
+        &self,
+        reference: &ethabi::Token,
+        data_generator: &dyn L1BatchCommitDataGenerator,
+    ) -> anyhow::Result<()> {
         let protocol_version = self
             .l1_batch
             .header
@@ -257,8 +336,7 @@ impl LocalL1BatchCommitData {
             );
         }
 
-        let local_token =
-            CommitBatchInfo::new(self.commitment_mode, &self.l1_batch, da).into_token();
+        let local_token = data_generator.l1_commit_batch(&self.l1_batch, da);
         anyhow::ensure!(
             local_token == *reference,
             "Locally reproduced commitment differs from the reference obtained from L1; \
@@ -268,6 +346,75 @@ impl LocalL1BatchCommitData {
     }
 }
 
+/// Data for a prove or execute transaction of an L1 batch loaded from Postgres.
+#[derive(Debug)]
+struct LocalL1BatchStageData {
+    stored_batch_info: StoredBatchInfo,
+    protocol_version: Option<ProtocolVersionId>,
+    tx_hash: H256,
+    sl_chain_id: Option<SLChainId>,
+}
+
+impl LocalL1BatchStageData {
+    /// Returns `Ok(None)` if the transaction for the specified stage is not known or not confirmed yet.
+    async fn new(
+        storage: &mut Connection<'_, Core>,
+        batch_number: L1BatchNumber,
+        stage: BatchStage,
+    ) -> anyhow::Result<Option<Self>> {
+        let tx_id = match stage {
+            BatchStage::Prove => {
+                storage
+                    .blocks_dal()
+                    .get_eth_prove_tx_id(batch_number)
+                    .await?
+            }
+            BatchStage::Execute => {
+                storage
+                    .blocks_dal()
+                    .get_eth_execute_tx_id(batch_number)
+                    .await?
+            }
+        };
+        let Some(tx_id) = tx_id else {
+            return Ok(None);
+        };
+        let tx_id = tx_id as u32;
+        let Some(tx_hash) = storage
+            .eth_sender_dal()
+            .get_confirmed_tx_hash_by_eth_tx_id(tx_id)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let sl_chain_id = storage.eth_sender_dal().get_eth_tx_chain_id(tx_id).await?;
+
+        let Some(l1_batch) = storage
+            .blocks_dal()
+            .get_l1_batch_metadata(batch_number)
+            .await?
+        else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            stored_batch_info: StoredBatchInfo::from(&l1_batch),
+            protocol_version: l1_batch.header.protocol_version,
+            tx_hash,
+            sl_chain_id,
+        }))
+    }
+
+    fn is_pre_shared_bridge(&self) -> bool {
+        self.protocol_version
+            .map_or(true, |version| version.is_pre_shared_bridge())
+    }
+
+    fn is_pre_gateway(&self) -> bool {
+        self.protocol_version
+            .map_or(true, |version| version.is_pre_gateway())
+    }
+}
+
 /// Determines which DA source was used in the `reference` commitment. It's assumed that the commitment was created
 /// using `CommitBatchInfo::into_token()`.
 ///
@@ -383,7 +530,11 @@ pub struct ConsistencyChecker {
     l1_data_mismatch_behavior: L1DataMismatchBehavior,
     pool: ConnectionPool<Core>,
     health_check: ReactiveHealthCheck,
+    /// Commitment mode used for batches for which the mode cannot be determined from Postgres.
     commitment_mode: L1BatchCommitmentMode,
+    /// Generator of commit data for L1 batches. Must match the one used by `eth_sender` on the main node.
+    /// If not set, the generator is chosen based on the commitment mode of each checked batch.
+    commit_data_generator: Option<Arc<dyn L1BatchCommitDataGenerator>>,
 }
 
 impl ConsistencyChecker {
@@ -437,9 +588,20 @@ impl ConsistencyChecker {
             pool,
             health_check,
             commitment_mode,
+            commit_data_generator: None,
         })
     }
 
+    /// Overrides the generator of L1 batch commit data. By default, the generator is chosen based
+    /// on the commitment mode of each checked batch.
+    pub fn with_commit_data_generator(
+        mut self,
+        generator: Arc<dyn L1BatchCommitDataGenerator>,
+    ) -> Self {
+        self.commit_data_generator = Some(generator);
+        self
+    }
+
     pub fn with_l1_diamond_proxy_addr(mut self, address: Address) -> Self {
         self.l1_chain_data.diamond_proxy_addr = Some(address);
         self
@@ -467,40 +629,9 @@ impl ConsistencyChecker {
             .get_batch_commit_chain_id(batch_number)
             .await
             .map_err(|err| CheckError::Internal(err.into()))?;
-        let chain_data = match sl_chain_id {
-            Some(chain_id) => {
-                let Some(chain_data) = self.chain_data_by_id(chain_id) else {
-                    return Err(CheckError::Validation(anyhow::anyhow!(
-                        "failed to find client for chain id {chain_id}"
-                    )));
-                };
-                chain_data
-            }
-            None => &self.l1_chain_data,
-        };
-        let commit_tx_status = chain_data
-            .client
-            .get_tx_status(commit_tx_hash)
-            .await?
-            .with_context(|| {
-                format!(
-                    "receipt for tx {commit_tx_hash:?} not found on target chain with id {}",
-                    chain_data.chain_id
-                )
-            })
-            .map_err(CheckError::Validation)?;
-        if !commit_tx_status.success {
-            let err = anyhow::anyhow!("main node gave us a failed commit tx {commit_tx_hash:?}");
-            return Err(CheckError::Validation(err));
-        }
-
-        // We can't get tx calldata from the DB because it can be fake.
-        let commit_tx = chain_data
-            .client
-            .get_tx(commit_tx_hash)
-            .await?
-            .with_context(|| format!("commit transaction {commit_tx_hash:?} not found on L1"))
-            .map_err(CheckError::Internal)?; // we've got a transaction receipt previously, thus an internal error
+        let (chain_data, commit_tx_status, commit_tx) = self
+            .fetch_successful_tx(sl_chain_id, commit_tx_hash, "commit")
+            .await?;
 
         if let Some(diamond_proxy_addr) = chain_data.diamond_proxy_addr {
             let event = self
@@ -555,27 +686,324 @@ impl ConsistencyChecker {
                 .map_err(CheckError::Internal)?
         };
 
+        let commit_data_generator = self
+            .commit_data_generator
+            .clone()
+            .unwrap_or_else(|| <dyn L1BatchCommitDataGenerator>::for_mode(local.commitment_mode));
         let commitment = Self::extract_commit_data(
             &commit_tx.input.0,
             commit_function,
             batch_number,
             local.is_pre_gateway(),
+            commit_data_generator.as_ref(),
         )
         .with_context(|| {
             format!("failed extracting commit data for transaction {commit_tx_hash:?}")
         })
         .map_err(CheckError::Validation)?;
         local
-            .verify_commitment(&commitment)
+            .verify_commitment(&commitment, commit_data_generator.as_ref())
             .map_err(CheckError::Validation)
     }
 
+    /// Fetches a transaction and its receipt from the settlement layer, checking that the transaction has succeeded.
+    async fn fetch_successful_tx(
+        &self,
+        sl_chain_id: Option<SLChainId>,
+        tx_hash: H256,
+        tx_name: &str,
+    ) -> Result<(&SLChainAccess, ExecutedTxStatus, web3::Transaction), CheckError> {
+        let chain_data = match sl_chain_id {
+            Some(chain_id) => {
+                let Some(chain_data) = self.chain_data_by_id(chain_id) else {
+                    return Err(CheckError::Validation(anyhow::anyhow!(
+                        "failed to find client for chain id {chain_id}"
+                    )));
+                };
+                chain_data
+            }
+            None => &self.l1_chain_data,
+        };
+        let tx_status = chain_data
+            .client
+            .get_tx_status(tx_hash)
+            .await?
+            .with_context(|| {
+                format!(
+                    "receipt for tx {tx_hash:?} not found on target chain with id {}",
+                    chain_data.chain_id
+                )
+            })
+            .map_err(CheckError::Validation)?;
+        if !tx_status.success {
+            let err = anyhow::anyhow!("main node gave us a failed {tx_name} tx {tx_hash:?}");
+            return Err(CheckError::Validation(err));
+        }
+
+        // We can't get tx calldata from the DB because it can be fake.
+        let tx = chain_data
+            .client
+            .get_tx(tx_hash)
+            .await?
+            .with_context(|| format!("{tx_name} transaction {tx_hash:?} not found on L1"))
+            .map_err(CheckError::Internal)?; // we've got a transaction receipt previously, thus an internal error
+        Ok((chain_data, tx_status, tx))
+    }
+
+    /// Checks a prove or execute transaction for the specified L1 batch: the transaction must have succeeded,
+    /// emitted the expected event for the batch, and its calldata must reference the locally stored batch info.
+    async fn check_stage(
+        &self,
+        stage: BatchStage,
+        batch_number: L1BatchNumber,
+        local: &LocalL1BatchStageData,
+    ) -> Result<(), CheckError> {
+        let tx_hash = local.tx_hash;
+        let tx_name = stage.tx_name();
+        tracing::info!("Checking {tx_name} tx {tx_hash:?} for L1 batch #{batch_number}");
+
+        let (chain_data, tx_status, tx) = self
+            .fetch_successful_tx(local.sl_chain_id, tx_hash, tx_name)
+            .await?;
+
+        if let Some(diamond_proxy_addr) = chain_data.diamond_proxy_addr {
+            let event_name = stage.event_name();
+            let event = self
+                .contract
+                .event(event_name)
+                .with_context(|| format!("`{event_name}` event not found for ZKsync L1 contract"))
+                .map_err(CheckError::Internal)?;
+
+            let batch = U256::from(batch_number.0);
+            let mut parsed_logs = tx_status.receipt.logs.into_iter().filter_map(|log| {
+                if log.address != diamond_proxy_addr {
+                    return None;
+                }
+                event
+                    .parse_log_whole(ethabi::RawLog {
+                        topics: log.topics,
+                        data: log.data.0,
+                    })
+                    .ok()
+            });
+            let has_batch_log = parsed_logs.any(|log| {
+                let uint_param = |name: &str| {
+                    log.params
+                        .iter()
+                        .find(|param| param.name == name)
+                        .and_then(|param| param.value.clone().into_uint())
+                };
+                match stage {
+                    BatchStage::Prove => {
+                        let prev = uint_param("previousLastVerifiedBatch");
+                        let current = uint_param("currentLastVerifiedBatch");
+                        matches!((prev, current), (Some(prev), Some(current)) if prev < batch && batch <= current)
+                    }
+                    BatchStage::Execute => uint_param("batchNumber") == Some(batch),
+                }
+            });
+            if !has_batch_log {
+                let err = anyhow::anyhow!(
+                    "{tx_name} transaction {tx_hash:?} does not contain `{event_name}` event log for L1 batch #{batch_number}"
+                );
+                return Err(CheckError::Validation(err));
+            }
+        }
+
+        if local.is_pre_shared_bridge() {
+            tracing::debug!(
+                "Skipping {tx_name} calldata check for pre-shared bridge L1 batch #{batch_number}"
+            );
+            return Ok(());
+        }
+
+        let function = match (stage, local.is_pre_gateway()) {
+            (BatchStage::Prove, true) => &*POST_SHARED_BRIDGE_PROVE_FUNCTION,
+            (BatchStage::Execute, true) => &*POST_SHARED_BRIDGE_EXECUTE_FUNCTION,
+            (BatchStage::Prove, false) => self
+                .contract
+                .function("proveBatchesSharedBridge")
+                .context("L1 contract does not have `proveBatchesSharedBridge` function")
+                .map_err(CheckError::Internal)?,
+            (BatchStage::Execute, false) => self
+                .contract
+                .function("executeBatchesSharedBridge")
+                .context("L1 contract does not have `executeBatchesSharedBridge` function")
+                .map_err(CheckError::Internal)?,
+        };
+        let reference = Self::extract_stored_batch_info(
+            stage,
+            &tx.input.0,
+            function,
+            batch_number,
+            local.is_pre_gateway(),
+        )
+        .with_context(|| {
+            format!("failed extracting batch info for {tx_name} transaction {tx_hash:?}")
+        })
+        .map_err(CheckError::Validation)?;
+
+        if reference != local.stored_batch_info {
+            let err = anyhow::anyhow!(
+                "Batch info in {tx_name} transaction {tx_hash:?} differs from the locally stored one; \
+                 local: {:?}, reference: {reference:?}",
+                local.stored_batch_info
+            );
+            return Err(CheckError::Validation(err));
+        }
+        Ok(())
+    }
+
+    /// Extracts info for the specified L1 batch from the calldata of a prove or execute transaction.
+    /// All returned errors are validation errors.
+    fn extract_stored_batch_info(
+        stage: BatchStage,
+        tx_input_data: &[u8],
+        function: &ethabi::Function,
+        batch_number: L1BatchNumber,
+        pre_gateway: bool,
+    ) -> anyhow::Result<StoredBatchInfo> {
+        let expected_solidity_selector = function.short_signature();
+        let actual_solidity_selector = tx_input_data
+            .get(..4)
+            .context("transaction calldata is too short")?;
+        anyhow::ensure!(
+            expected_solidity_selector == actual_solidity_selector,
+            "unexpected Solidity function selector: expected {expected_solidity_selector:?}, got {actual_solidity_selector:?}"
+        );
+
+        let input_tokens = function
+            .decode_input(&tx_input_data[4..])
+            .with_context(|| {
+                format!(
+                    "Failed decoding calldata for L1 {} function",
+                    stage.tx_name()
+                )
+            })?;
+        let batches_token = if pre_gateway {
+            // `proveBatchesSharedBridge(chainId, prevBatch, committedBatches, proof)` and
+            // `executeBatchesSharedBridge(chainId, batchesData)`
+            let index = match stage {
+                BatchStage::Prove => 2,
+                BatchStage::Execute => 1,
+            };
+            input_tokens.into_iter().nth(index)
+        } else {
+            // Post-gateway functions have `(chainId, processFrom, processTo, data)` signature, where `data`
+            // is a versioned ABI encoding of the function arguments.
+            let Some(Token::Bytes(data)) = input_tokens.into_iter().last() else {
+                anyhow::bail!(
+                    "Unexpected signature for L1 {} function: last token is not bytes",
+                    stage.tx_name()
+                );
+            };
+            let (version, encoded_data) = data.split_first().context("empty encoded data")?;
+            anyhow::ensure!(
+                *version == SUPPORTED_ENCODING_VERSION,
+                "Unexpected encoding version: {version}"
+            );
+            let batches_schema = ParamType::Array(Box::new(StoredBatchInfo::schema()));
+            let (schema, index) = match stage {
+                BatchStage::Prove => (vec![StoredBatchInfo::schema(), batches_schema], 1),
+                BatchStage::Execute => (vec![batches_schema], 0),
+            };
+            ethabi::decode(&schema, encoded_data)
+                .context("Failed to decode encoded data")?
+                .into_iter()
+                .nth(index)
+        };
+        let batches = batches_token
+            .and_then(Token::into_array)
+            .with_context(|| format!("Unexpected signature for L1 {} function", stage.tx_name()))?;
+
+        let mut batch_numbers = Vec::with_capacity(batches.len());
+        for token in batches {
+            let info = StoredBatchInfo::from_token(token).context("malformed stored batch info")?;
+            if info.batch_number == u64::from(batch_number.0) {
+                return Ok(info);
+            }
+            batch_numbers.push(info.batch_number);
+        }
+        anyhow::bail!(
+            "Malformed {} data; it should reference L1 batch #{batch_number}, but it actually references batches {batch_numbers:?}",
+            stage.tx_name()
+        )
+    }
+
+    /// Checks prove and execute transactions for L1 batches with already checked commit transactions (i.e., batches
+    /// preceding `next_commit_batch`). For each stage, checks stop at the first batch without a known transaction.
+    async fn check_later_stages(
+        &mut self,
+        next_batches: &mut HashMap<BatchStage, L1BatchNumber>,
+        next_commit_batch: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        for stage in BatchStage::ALL {
+            let next_batch = next_batches.get_mut(&stage).unwrap();
+            while *next_batch < next_commit_batch {
+                let batch_number = *next_batch;
+                let mut storage = self.pool.connection().await?;
+                let local = LocalL1BatchStageData::new(&mut storage, batch_number, stage).await?;
+                drop(storage);
+                let Some(local) = local else {
+                    break;
+                };
+
+                match self.check_stage(stage, batch_number, &local).await {
+                    Ok(()) => {
+                        let mut storage = self.pool.connection().await?;
+                        storage
+                            .blocks_dal()
+                            .set_consistency_checker_last_processed_l1_batch_for_action(
+                                stage.action_type(),
+                                batch_number,
+                            )
+                            .await?;
+                        self.event_handler.update_checked_stage(stage, batch_number);
+                    }
+                    Err(CheckError::Validation(err)) => {
+                        let err =
+                            err.context(format!("{} transaction is inconsistent", stage.tx_name()));
+                        self.event_handler
+                            .report_inconsistent_batch(batch_number, &err);
+                        match &self.l1_data_mismatch_behavior {
+                            #[cfg(test)]
+                            L1DataMismatchBehavior::Bail => {
+                                let context =
+                                    format!("L1 batch #{batch_number} is inconsistent with L1");
+                                return Err(err.context(context));
+                            }
+                            L1DataMismatchBehavior::Log => { /* proceed to the next batch */ }
+                        }
+                    }
+                    Err(err) if err.is_retriable() => {
+                        tracing::warn!(
+                            "Transient error while verifying {} tx for L1 batch #{batch_number}; will retry after a delay: {:#}",
+                            stage.tx_name(),
+                            anyhow::Error::from(err)
+                        );
+                        break;
+                    }
+                    Err(other_err) => {
+                        let context = format!(
+                            "failed verifying consistency of {} tx for L1 batch #{batch_number}",
+                            stage.tx_name()
+                        );
+                        return Err(anyhow::Error::from(other_err).context(context));
+                    }
+                }
+                *next_batch += 1;
+            }
+        }
+        Ok(())
+    }
+
     /// All returned errors are validation errors.
     fn extract_commit_data(
         commit_tx_input_data: &[u8],
         commit_function: &ethabi::Function,
         batch_number: L1BatchNumber,
         pre_gateway: bool,
+        data_generator: &dyn L1BatchCommitDataGenerator,
     ) -> anyhow::Result<ethabi::Token> {
         let expected_solidity_selector = commit_function.short_signature();
         let actual_solidity_selector = &commit_tx_input_data[..4];
@@ -613,7 +1041,7 @@ impl ConsistencyChecker {
             let decoded_data = ethabi::decode(
                 &[
                     StoredBatchInfo::schema(),
-                    ParamType::Array(Box::new(CommitBatchInfo::post_gateway_schema())),
+                    ParamType::Array(Box::new(data_generator.commit_batch_schema())),
                 ],
                 encoded_data,
             )
@@ -738,19 +1166,32 @@ impl ConsistencyChecker {
             .saturating_sub(self.max_batches_to_recheck)
             .into();
 
-        let last_processed_batch = self
-            .pool
-            .connection()
-            .await?
+        let mut storage = self.pool.connection().await?;
+        let last_processed_batch = storage
             .blocks_dal()
             .get_consistency_checker_last_processed_l1_batch()
             .await?;
-
         // We shouldn't check batches not present in the storage, and skip the genesis batch since
         // it's not committed on L1.
-        let first_batch_to_check = first_batch_to_check
-            .max(earliest_l1_batch_number)
-            .max(L1BatchNumber(last_processed_batch.0 + 1));
+        let first_batch_to_check = first_batch_to_check.max(earliest_l1_batch_number);
+
+        // Prove and execute transactions are checked independently of commit transactions, so they have separate cursors.
+        let mut next_batches_by_stage = HashMap::new();
+        for stage in BatchStage::ALL {
+            let last_processed_batch = storage
+                .blocks_dal()
+                .get_consistency_checker_last_processed_l1_batch_for_action(stage.action_type())
+                .await?;
+            let next_batch = first_batch_to_check.max(last_processed_batch + 1);
+            tracing::info!(
+                "Starting {} tx checks from L1 batch #{next_batch}",
+                stage.tx_name()
+            );
+            next_batches_by_stage.insert(stage, next_batch);
+        }
+        drop(storage);
+
+        let first_batch_to_check = first_batch_to_check.max(last_processed_batch + 1);
         tracing::info!(
             "Last committed L1 batch is #{last_committed_batch}; starting checks from L1 batch #{first_batch_to_check}"
         );
@@ -759,6 +1200,9 @@ impl ConsistencyChecker {
 
         let mut batch_number = first_batch_to_check;
         while !*stop_receiver.borrow_and_update() {
+            self.check_later_stages(&mut next_batches_by_stage, batch_number)
+                .await?;
+
             let mut storage = self.pool.connection().await?;
             // The batch might be already committed but not yet processed by the external node's tree
             // OR the batch might be processed by the external node's tree but not yet committed.
@@ -8,10 +8,18 @@ use tokio::sync::mpsc;
 use zksync_config::GenesisConfig;
 use zksync_dal::Connection;
 use zksync_eth_client::{clients::MockSettlementLayer, EthInterface, Options};
-use zksync_l1_contract_interface::{i_executor::methods::CommitBatches, Tokenizable, Tokenize};
+use zksync_l1_contract_interface::{
+    i_executor::{
+        commit::data_generator::RollupModeL1BatchCommitDataGenerator,
+        methods::{CommitBatches, ExecuteBatches, ProveBatches},
+        structures::CommitBatchInfo,
+    },
+    Tokenizable, Tokenize,
+};
 use zksync_node_genesis::{insert_genesis_batch, mock_genesis_config, GenesisParams};
 use zksync_node_test_utils::{
-    create_l1_batch, create_l1_batch_metadata, l1_batch_metadata_to_commitment_artifacts,
+    create_l1_batch, create_l1_batch_metadata, create_l2_block,
+    l1_batch_metadata_to_commitment_artifacts,
 };
 use zksync_types::{
     aggregated_operations::AggregatedActionType, commitment::L1BatchWithMetadata,
@@ -57,6 +65,16 @@ pub(crate) fn create_pre_boojum_l1_batch_with_metadata(number: u32) -> L1BatchWi
 pub(crate) fn build_commit_tx_input_data(
     batches: &[L1BatchWithMetadata],
     mode: L1BatchCommitmentMode,
+) -> Vec<u8> {
+    build_commit_tx_input_data_with_generator(
+        batches,
+        <dyn L1BatchCommitDataGenerator>::for_mode(mode).as_ref(),
+    )
+}
+
+fn build_commit_tx_input_data_with_generator(
+    batches: &[L1BatchWithMetadata],
+    data_generator: &dyn L1BatchCommitDataGenerator,
 ) -> Vec<u8> {
     let protocol_version = batches[0].header.protocol_version.unwrap();
     let contract = zksync_contracts::hyperchain_contract();
@@ -68,7 +86,7 @@ pub(crate) fn build_commit_tx_input_data(
         last_committed_l1_batch: &batches[0],
         l1_batches: batches,
         pubdata_da: PubdataSendingMode::Calldata,
-        mode,
+        data_generator,
     }
     .into_tokens();
 
@@ -90,6 +108,41 @@ pub(crate) fn build_commit_tx_input_data(
     }
 }
 
+fn build_stage_tx_input_data(stage: BatchStage, batches: &[L1BatchWithMetadata]) -> Vec<u8> {
+    let protocol_version = batches[0].header.protocol_version.unwrap();
+    let contract = zksync_contracts::hyperchain_contract();
+    let mut tokens = vec![Token::Uint(ERA_CHAIN_ID.into())];
+    let function = match stage {
+        BatchStage::Prove => {
+            let prove_batches = ProveBatches {
+                prev_l1_batch: create_l1_batch_with_metadata(batches[0].header.number.0 - 1),
+                l1_batches: batches.to_vec(),
+                proofs: vec![],
+                should_verify: false,
+            };
+            tokens.extend((&prove_batches).into_tokens());
+            if protocol_version.is_pre_gateway() {
+                &*POST_SHARED_BRIDGE_PROVE_FUNCTION
+            } else {
+                contract.function("proveBatchesSharedBridge").unwrap()
+            }
+        }
+        BatchStage::Execute => {
+            let execute_batches = ExecuteBatches {
+                l1_batches: batches.to_vec(),
+                priority_ops_proofs: vec![],
+            };
+            tokens.extend((&execute_batches).into_tokens());
+            if protocol_version.is_pre_gateway() {
+                &*POST_SHARED_BRIDGE_EXECUTE_FUNCTION
+            } else {
+                contract.function("executeBatchesSharedBridge").unwrap()
+            }
+        }
+    };
+    function.encode_input(&tokens).unwrap()
+}
+
 pub(crate) async fn create_mock_checker(
     client: MockSettlementLayer,
     pool: ConnectionPool<Core>,
@@ -113,6 +166,7 @@ pub(crate) async fn create_mock_checker(
         l1_data_mismatch_behavior: L1DataMismatchBehavior::Bail,
         pool,
         commitment_mode,
+        commit_data_generator: None,
         health_check,
     }
 }
@@ -175,6 +229,10 @@ impl HandleConsistencyCheckerEvent for mpsc::UnboundedSender<L1BatchNumber> {
         self.send(last_checked_batch).ok();
     }
 
+    fn update_checked_stage(&mut self, _stage: BatchStage, _last_checked_batch: L1BatchNumber) {
+        // Do nothing
+    }
+
     fn report_inconsistent_batch(&mut self, number: L1BatchNumber, err: &anyhow::Error) {
         panic!("Error on batch #{number}: {err}");
     }
@@ -202,6 +260,7 @@ fn build_commit_tx_input_data_is_correct(commitment_mode: L1BatchCommitmentMode)
                 .protocol_version
                 .map(|v| v.is_pre_gateway())
                 .unwrap_or(true),
+            <dyn L1BatchCommitDataGenerator>::for_mode(commitment_mode).as_ref(),
         )
         .unwrap();
         assert_eq!(
@@ -211,6 +270,42 @@ fn build_commit_tx_input_data_is_correct(commitment_mode: L1BatchCommitmentMode)
     }
 }
 
+#[test_casing(2, BatchStage::ALL)]
+#[test]
+fn extracting_stored_batch_info(stage: BatchStage) {
+    let batches: Vec<_> = (1..=3).map(create_l1_batch_with_metadata).collect();
+    let input_data = build_stage_tx_input_data(stage, &batches);
+    let function = match stage {
+        BatchStage::Prove => &*POST_SHARED_BRIDGE_PROVE_FUNCTION,
+        BatchStage::Execute => &*POST_SHARED_BRIDGE_EXECUTE_FUNCTION,
+    };
+
+    for batch in &batches {
+        let info = ConsistencyChecker::extract_stored_batch_info(
+            stage,
+            &input_data,
+            function,
+            batch.header.number,
+            true,
+            &RollupModeL1BatchCommitDataGenerator,
+        )
+        .unwrap();
+        assert_eq!(info, StoredBatchInfo::from(batch));
+    }
+
+    for bogus_l1_batch in [0, 4, 100] {
+        ConsistencyChecker::extract_stored_batch_info(
+            stage,
+            &input_data,
+            function,
+            L1BatchNumber(bogus_l1_batch),
+            true,
+            &RollupModeL1BatchCommitDataGenerator,
+        )
+        .unwrap_err();
+    }
+}
+
 #[test]
 fn extracting_commit_data_for_boojum_batch() {
     let commit_function = &*POST_BOOJUM_COMMIT_FUNCTION;
@@ -223,6 +318,7 @@ fn extracting_commit_data_for_boojum_batch() {
         commit_function,
         L1BatchNumber(4_470),
         true,
+        &RollupModeL1BatchCommitDataGenerator,
     )
     .unwrap();
 
@@ -237,6 +333,7 @@ fn extracting_commit_data_for_boojum_batch() {
             commit_function,
             L1BatchNumber(bogus_l1_batch),
             true,
+            &RollupModeL1BatchCommitDataGenerator,
         )
         .unwrap_err();
     }
@@ -255,6 +352,7 @@ fn extracting_commit_data_for_multiple_batches() {
             commit_function,
             L1BatchNumber(l1_batch),
             true,
+            &RollupModeL1BatchCommitDataGenerator,
         )
         .unwrap();
 
@@ -270,6 +368,7 @@ fn extracting_commit_data_for_multiple_batches() {
             commit_function,
             L1BatchNumber(bogus_l1_batch),
             true,
+            &RollupModeL1BatchCommitDataGenerator,
         )
         .unwrap_err();
     }
@@ -286,6 +385,7 @@ fn extracting_commit_data_for_pre_boojum_batch() {
         &PRE_BOOJUM_COMMIT_FUNCTION,
         L1BatchNumber(200_000),
         true,
+        &RollupModeL1BatchCommitDataGenerator,
     )
     .unwrap();
 
@@ -295,6 +395,191 @@ fn extracting_commit_data_for_pre_boojum_batch() {
     );
 }
 
+/// Commit data generator emulating a fork with an extra field in commit data.
+#[derive(Debug)]
+struct ExtendedCommitDataGenerator;
+
+impl L1BatchCommitDataGenerator for ExtendedCommitDataGenerator {
+    fn l1_commit_batch(
+        &self,
+        l1_batch: &L1BatchWithMetadata,
+        pubdata_da: PubdataSendingMode,
+    ) -> Token {
+        let token = RollupModeL1BatchCommitDataGenerator.l1_commit_batch(l1_batch, pubdata_da);
+        let Token::Tuple(mut tokens) = token else {
+            unreachable!("unexpected commit data: {token:?}");
+        };
+        // Keep the batch number first and DA input last, as they are inspected separately.
+        tokens.insert(tokens.len() - 1, Token::FixedBytes(vec![0xaa; 32]));
+        Token::Tuple(tokens)
+    }
+
+    fn commit_batch_schema(&self) -> ParamType {
+        let ParamType::Tuple(mut params) = CommitBatchInfo::post_gateway_schema() else {
+            unreachable!();
+        };
+        params.insert(params.len() - 1, ParamType::FixedBytes(32));
+        ParamType::Tuple(params)
+    }
+}
+
+#[test]
+fn verifying_commitment_with_custom_data_generator() {
+    let local = LocalL1BatchCommitData {
+        l1_batch: create_l1_batch_with_metadata(1),
+        commit_tx_hash: H256::zero(),
+        commitment_mode: L1BatchCommitmentMode::Rollup,
+    };
+    let reference =
+        ExtendedCommitDataGenerator.l1_commit_batch(&local.l1_batch, PubdataSendingMode::Calldata);
+
+    local
+        .verify_commitment(&reference, &ExtendedCommitDataGenerator)
+        .unwrap();
+    let err = local
+        .verify_commitment(&reference, &RollupModeL1BatchCommitDataGenerator)
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("differs from the reference"),
+        "{err:#}"
+    );
+}
+
+#[tokio::test]
+async fn loading_local_commit_data_with_per_batch_commitment_mode() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    let gateway_version = ProtocolVersionId::Version27;
+    for minor in [ProtocolVersionId::latest(), gateway_version] {
+        let version = ProtocolVersion {
+            version: ProtocolSemanticVersion {
+                minor,
+                patch: 0.into(),
+            },
+            ..ProtocolVersion::default()
+        };
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(&version)
+            .await
+            .unwrap();
+    }
+
+    // The first batch is pre-gateway, so its commitment mode should be taken from the config. The second batch
+    // is committed in the validium mode, which differs from the configured one.
+    let l1_batches = [
+        create_l1_batch_with_metadata(1),
+        create_l1_batch_with_metadata(2),
+    ];
+    let mut first_l2_block = create_l2_block(1);
+    first_l2_block.pubdata_params.pubdata_type = L1BatchCommitmentMode::Validium;
+    let mut second_l2_block = create_l2_block(2);
+    second_l2_block.protocol_version = Some(gateway_version);
+    second_l2_block.pubdata_params.pubdata_type = L1BatchCommitmentMode::Validium;
+    let commit_tx_hash_by_l1_batch = l1_batches
+        .iter()
+        .map(|batch| {
+            (
+                batch.header.number,
+                H256::repeat_byte(batch.header.number.0 as u8),
+            )
+        })
+        .collect();
+
+    for (l1_batch, l2_block) in l1_batches.iter().zip([first_l2_block, second_l2_block]) {
+        storage
+            .blocks_dal()
+            .insert_l2_block(&l2_block)
+            .await
+            .unwrap();
+        for save_action in [
+            SaveAction::InsertBatch(l1_batch),
+            SaveAction::SaveMetadata(l1_batch),
+            SaveAction::InsertCommitTx(l1_batch.header.number),
+        ] {
+            save_action
+                .apply(
+                    &mut storage,
+                    &commit_tx_hash_by_l1_batch,
+                    &Default::default(),
+                )
+                .await;
+        }
+        storage
+            .blocks_dal()
+            .mark_l2_blocks_as_executed_in_l1_batch(l1_batch.header.number)
+            .await
+            .unwrap();
+    }
+
+    let mut commitment_modes = vec![];
+    for l1_batch in &l1_batches {
+        let local = LocalL1BatchCommitData::new(
+            &mut storage,
+            l1_batch.header.number,
+            L1BatchCommitmentMode::Rollup,
+        )
+        .await
+        .unwrap()
+        .expect("no local commit data");
+        assert_eq!(
+            local.commit_tx_hash,
+            commit_tx_hash_by_l1_batch[&l1_batch.header.number]
+        );
+        commitment_modes.push(local.commitment_mode);
+    }
+    assert_eq!(
+        commitment_modes,
+        [
+            L1BatchCommitmentMode::Rollup,
+            L1BatchCommitmentMode::Validium
+        ]
+    );
+}
+
+#[test]
+fn extracting_and_verifying_commit_data_with_custom_data_generator() {
+    let contract = zksync_contracts::hyperchain_contract();
+    let commit_function = contract.function("commitBatchesSharedBridge").unwrap();
+    let batches = vec![
+        create_l1_batch_with_metadata(1),
+        create_l1_batch_with_metadata(2),
+    ];
+    assert!(!batches[0].header.protocol_version.unwrap().is_pre_gateway());
+    let commit_tx_input_data =
+        build_commit_tx_input_data_with_generator(&batches, &ExtendedCommitDataGenerator);
+
+    for batch in &batches {
+        let local = LocalL1BatchCommitData {
+            l1_batch: batch.clone(),
+            commit_tx_hash: H256::zero(),
+            commitment_mode: L1BatchCommitmentMode::Rollup,
+        };
+        let commit_data = ConsistencyChecker::extract_commit_data(
+            &commit_tx_input_data,
+            commit_function,
+            batch.header.number,
+            false,
+            &ExtendedCommitDataGenerator,
+        )
+        .unwrap();
+        local
+            .verify_commitment(&commit_data, &ExtendedCommitDataGenerator)
+            .unwrap();
+
+        // The default schema doesn't cover the extra field, so the extracted data cannot be verified.
+        ConsistencyChecker::extract_commit_data(
+            &commit_tx_input_data,
+            commit_function,
+            batch.header.number,
+            false,
+            &RollupModeL1BatchCommitDataGenerator,
+        )
+        .and_then(|commit_data| local.verify_commitment(&commit_data, &ExtendedCommitDataGenerator))
+        .unwrap_err();
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum SaveAction<'a> {
     InsertBatch(&'a L1BatchWithMetadata),
@@ -784,6 +1069,160 @@ async fn checker_functions_after_snapshot_recovery(
     checker_task.await.unwrap().unwrap();
 }
 
+fn l1_batches_verification_log(batches: &[L1BatchWithMetadata]) -> Log {
+    let event_hash = zksync_contracts::hyperchain_contract()
+        .event("BlocksVerification")
+        .unwrap()
+        .signature();
+    let first_batch = batches[0].header.number.0;
+    let last_batch = batches.last().unwrap().header.number.0;
+    Log {
+        topics: vec![
+            event_hash,
+            H256::from_low_u64_be((first_batch - 1).into()), // previous last verified batch
+            H256::from_low_u64_be(last_batch.into()),        // current last verified batch
+        ],
+        ..l1_batch_commit_log(&batches[0])
+    }
+}
+
+fn l1_batch_execution_log(l1_batch: &L1BatchWithMetadata) -> Log {
+    let event_hash = zksync_contracts::hyperchain_contract()
+        .event("BlockExecution")
+        .unwrap()
+        .signature();
+    let mut log = l1_batch_commit_log(l1_batch);
+    log.topics[0] = event_hash;
+    log
+}
+
+/// Sends a transaction with the specified calldata and logs to the mock settlement layer, returning its hash.
+async fn send_mock_tx(
+    client: &MockSettlementLayer,
+    input_data: Vec<u8>,
+    nonce: usize,
+    logs: Vec<Log>,
+) -> H256 {
+    let signed_tx = client
+        .sign_prepared_tx(
+            input_data,
+            VALIDATOR_TIMELOCK_ADDR,
+            Options {
+                nonce: Some(nonce.into()),
+                ..Options::default()
+            },
+        )
+        .unwrap();
+    client.as_ref().send_raw_tx(signed_tx.raw_tx).await.unwrap();
+    client.execute_tx(signed_tx.hash, true, 1).with_logs(logs);
+    signed_tx.hash
+}
+
+#[test_casing(2, [false, true])]
+#[tokio::test]
+async fn checker_verifies_prove_and_execute_txs(corrupt_execute_data: bool) {
+    let commitment_mode = L1BatchCommitmentMode::Rollup;
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+
+    let l1_batches: Vec<_> = (1..=3).map(create_l1_batch_with_metadata).collect();
+    let client = create_mock_ethereum();
+    let commit_tx_hash = send_mock_tx(
+        &client,
+        build_commit_tx_input_data(&l1_batches, commitment_mode),
+        0,
+        l1_batches.iter().map(l1_batch_commit_log).collect(),
+    )
+    .await;
+    let prove_tx_hash = send_mock_tx(
+        &client,
+        build_stage_tx_input_data(BatchStage::Prove, &l1_batches),
+        1,
+        vec![l1_batches_verification_log(&l1_batches)],
+    )
+    .await;
+
+    let mut executed_batches = l1_batches.clone();
+    if corrupt_execute_data {
+        executed_batches[1].header.timestamp += 1;
+    }
+    let execute_tx_hash = send_mock_tx(
+        &client,
+        build_stage_tx_input_data(BatchStage::Execute, &executed_batches),
+        2,
+        l1_batches.iter().map(l1_batch_execution_log).collect(),
+    )
+    .await;
+
+    for l1_batch in &l1_batches {
+        let number = l1_batch.header.number;
+        SaveAction::InsertBatch(l1_batch)
+            .apply(&mut storage, &HashMap::new(), &HashMap::new())
+            .await;
+        SaveAction::SaveMetadata(l1_batch)
+            .apply(&mut storage, &HashMap::new(), &HashMap::new())
+            .await;
+        for (tx_type, tx_hash) in [
+            (AggregatedActionType::Commit, commit_tx_hash),
+            (AggregatedActionType::PublishProofOnchain, prove_tx_hash),
+            (AggregatedActionType::Execute, execute_tx_hash),
+        ] {
+            storage
+                .eth_sender_dal()
+                .insert_bogus_confirmed_eth_tx(number, tx_type, tx_hash, chrono::Utc::now(), None)
+                .await
+                .unwrap();
+        }
+    }
+    drop(storage);
+
+    let checker = create_mock_checker(client, pool.clone(), commitment_mode).await;
+    let mut health_check = checker.health_check().clone();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let checker_task = tokio::spawn(checker.run(stop_receiver));
+
+    if corrupt_execute_data {
+        let err = tokio::time::timeout(Duration::from_secs(30), checker_task)
+            .await
+            .expect("Timed out waiting for checker to stop")
+            .unwrap()
+            .unwrap_err();
+        let err = format!("{err:#}");
+        assert!(err.contains("L1 batch #2 is inconsistent"), "{err}");
+        assert!(err.contains("execute transaction"), "{err}");
+        return;
+    }
+
+    health_check
+        .wait_for(|health| {
+            let Some(details) = health.details() else {
+                return false;
+            };
+            details["last_checked_prove_batch"] == 3 && details["last_checked_execute_batch"] == 3
+        })
+        .await;
+    stop_sender.send_replace(true);
+    checker_task.await.unwrap().unwrap();
+
+    // Check that cursors for all stages are persisted.
+    let mut storage = pool.connection().await.unwrap();
+    for action in [
+        AggregatedActionType::Commit,
+        AggregatedActionType::PublishProofOnchain,
+        AggregatedActionType::Execute,
+    ] {
+        let last_processed_batch = storage
+            .blocks_dal()
+            .get_consistency_checker_last_processed_l1_batch_for_action(action)
+            .await
+            .unwrap();
+        assert_eq!(last_processed_batch, L1BatchNumber(3), "{action:?}");
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum IncorrectDataKind {
     MissingStatus,
@@ -47,6 +47,10 @@ impl RestApi {
                 "/contract_verification/vyper_versions",
                 axum::routing::get(Self::vyper_versions),
             )
+            .route(
+                "/contract_verification/export",
+                axum::routing::get(Self::export_verification_info),
+            )
             .route(
                 "/contract_verification/:id",
                 axum::routing::get(Self::verification_request_status),
@@ -2,22 +2,35 @@ use std::{collections::HashSet, iter, sync::Arc};
 
 use anyhow::Context as _;
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
+use serde::Deserialize;
 use zksync_dal::{CoreDal, DalError};
 use zksync_types::{
     bytecode::BytecodeMarker,
     contract_verification_api::{
-        CompilerVersions, VerificationIncomingRequest, VerificationInfo, VerificationRequestStatus,
+        CompilerVersions, ExportedVerificationInfo, VerificationIncomingRequest, VerificationInfo,
+        VerificationRequestStatus,
     },
     Address,
 };
 
 use super::{api_decl::RestApi, metrics::METRICS};
 
+/// Maximum number of entries returned by a single export request.
+const MAX_EXPORT_PAGE_SIZE: usize = 100;
+
+/// Query parameters of the verification info export.
+#[derive(Debug, Deserialize)]
+pub(crate) struct ExportQuery {
+    /// Only entries with contract addresses greater than this one are returned.
+    after: Option<Address>,
+    limit: Option<usize>,
+}
+
 #[derive(Debug)]
 pub(crate) enum ApiError {
     IncorrectCompilerVersions,
@@ -231,4 +244,44 @@ impl RestApi {
         method_latency.observe();
         Ok(Json(info))
     }
+
+    /// Exports a page of verification info ordered by contract address, together with hashes of deployed bytecodes.
+    #[tracing::instrument(skip(self_))]
+    pub async fn export_verification_info(
+        State(self_): State<Arc<Self>>,
+        Query(query): Query<ExportQuery>,
+    ) -> ApiResult<Vec<ExportedVerificationInfo>> {
+        let method_latency = METRICS.call[&"contract_verification_export"].start();
+        let limit = query
+            .limit
+            .unwrap_or(MAX_EXPORT_PAGE_SIZE)
+            .min(MAX_EXPORT_PAGE_SIZE);
+        let mut storage = self_
+            .replica_connection_pool
+            .connection_tagged("api")
+            .await?;
+        let infos = storage
+            .contract_verification_dal()
+            .get_verification_info_page(query.after, limit)
+            .await?;
+        let deployed_contracts = storage
+            .storage_logs_dal()
+            .filter_deployed_contracts(
+                infos.iter().map(|info| info.request.req.contract_address),
+                None,
+            )
+            .await?;
+        let exported = infos
+            .into_iter()
+            .map(|info| {
+                let address = info.request.req.contract_address;
+                ExportedVerificationInfo {
+                    bytecode_hash: deployed_contracts.get(&address).map(|&(_, hash)| hash),
+                    info,
+                }
+            })
+            .collect();
+        method_latency.observe();
+        Ok(Json(exported))
+    }
 }
@@ -13,7 +13,9 @@ use zksync_dal::{Connection, Core, CoreDal};
 use zksync_node_test_utils::create_l2_block;
 use zksync_types::{
     bytecode::{BytecodeHash, BytecodeMarker},
-    contract_verification_api::CompilerVersions,
+    contract_verification_api::{
+        testonly::mock_verification_info, CompilerVersions, ExportedVerificationInfo,
+    },
     get_code_key, Address, L2BlockNumber, ProtocolVersion, StorageLog,
 };
 
@@ -354,3 +356,50 @@ async fn mismatched_compiler_type() {
     let error_message = str::from_utf8(&error_message).unwrap();
     assert_eq!(error_message, ApiError::IncorrectCompilerVersions.message());
 }
+
+#[tokio::test]
+async fn exporting_verification_info() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    prepare_storage(&mut storage).await;
+    let deployed_address = Address::repeat_byte(0x23);
+    let missing_address = Address::repeat_byte(0x24);
+    mock_deploy_contract(&mut storage, deployed_address, BytecodeMarker::EraVm).await;
+    for address in [deployed_address, missing_address] {
+        storage
+            .contract_verification_dal()
+            .import_verification_info(&mock_verification_info(address), false)
+            .await
+            .unwrap();
+    }
+
+    let router = RestApi::new(pool.clone(), pool).into_router();
+    let req = Request::builder()
+        .method(Method::GET)
+        .uri("/contract_verification/export?limit=1")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.clone().oneshot(req).await.unwrap();
+    let page: Vec<ExportedVerificationInfo> =
+        serde_json::from_value(json_response(response).await).unwrap();
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].info.request.req.contract_address, deployed_address);
+    assert_eq!(
+        page[0].bytecode_hash,
+        Some(BytecodeHash::for_bytecode(&[0; 32]).value())
+    );
+
+    let req = Request::builder()
+        .method(Method::GET)
+        .uri(format!(
+            "/contract_verification/export?after={deployed_address:?}"
+        ))
+        .body(Body::empty())
+        .unwrap();
+    let response = router.oneshot(req).await.unwrap();
+    let page: Vec<ExportedVerificationInfo> =
+        serde_json::from_value(json_response(response).await).unwrap();
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].info.request.req.contract_address, missing_address);
+    assert_eq!(page[0].bytecode_hash, None);
+}
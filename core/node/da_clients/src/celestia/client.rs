@@ -65,11 +65,7 @@ impl DataAvailabilityClient for CelestiaClient {
         let blob = Blob::new(namespace, data).map_err(to_non_retriable_da_error)?;
 
         let commitment = blob.commitment;
-        let blob_tx = self
-            .client
-            .prepare(vec![blob])
-            .await
-            .map_err(to_non_retriable_da_error)?;
+        let blob_tx = self.client.prepare(vec![blob]).await?;
 
         let blob_tx_hash = BlobTxHash::compute(&blob_tx);
         let height = self
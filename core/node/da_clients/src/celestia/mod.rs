@@ -12,6 +12,12 @@ pub mod cosmos {
         include!("generated/cosmos.auth.v1beta1.rs");
     }
 
+    pub mod bank {
+        pub mod v1beta1 {
+            include!("generated/cosmos.bank.v1beta1.rs");
+        }
+    }
+
     pub mod base {
         pub mod abci {
             include!("generated/cosmos.base.abci.v1beta1.rs");
@@ -9,6 +9,7 @@ use prost::{bytes::Bytes, Message, Name};
 use secp256k1::{PublicKey, Secp256k1, SecretKey};
 use sha2::Digest;
 use tonic::transport::Channel;
+use zksync_da_client::types::DAError;
 
 use super::{
     celestia_proto::{
@@ -20,6 +21,7 @@ use super::{
             query_client::QueryClient as AuthQueryClient, BaseAccount, QueryAccountRequest,
             QueryParamsRequest as QueryAuthParamsRequest,
         },
+        bank::v1beta1::{query_client::QueryClient as BankQueryClient, QueryBalanceRequest},
         base::{
             node::{
                 service_client::ServiceClient as MinGasPriceClient,
@@ -37,6 +39,7 @@ use super::{
     },
     tendermint::types::{Blob as PbBlob, BlobTx},
 };
+use crate::utils::{to_non_retriable_da_error, to_retriable_da_error};
 
 const UNITS_SUFFIX: &str = "utia";
 pub const ADDRESS_LENGTH: usize = 20;
@@ -69,16 +72,21 @@ impl RawCelestiaClient {
         })
     }
 
-    /// Prepares a blob transaction for the given blobs.
-    pub(crate) async fn prepare(&self, blobs: Vec<Blob>) -> anyhow::Result<BlobTx> {
-        let (gas_per_blob_byte, tx_size_cost_per_byte, min_gas_price, base_account) = tokio::try_join!(
-            self.get_gas_per_blob_byte(),
-            self.fetch_tx_size_cost_per_byte(),
-            self.fetch_min_gas_price(),
-            self.fetch_account(),
-        )?;
+    /// Prepares a blob transaction for the given blobs. Errors querying the node are retriable;
+    /// insufficient account balance is not.
+    pub(crate) async fn prepare(&self, blobs: Vec<Blob>) -> std::result::Result<BlobTx, DAError> {
+        let (gas_per_blob_byte, tx_size_cost_per_byte, min_gas_price, base_account, balance) =
+            tokio::try_join!(
+                self.get_gas_per_blob_byte(),
+                self.fetch_tx_size_cost_per_byte(),
+                self.fetch_min_gas_price(),
+                self.fetch_account(),
+                self.fetch_balance(),
+            )
+            .map_err(to_retriable_da_error)?;
 
-        let msg_pay_for_blobs = new_msg_pay_for_blobs(blobs.as_slice(), self.address.clone())?;
+        let msg_pay_for_blobs = new_msg_pay_for_blobs(blobs.as_slice(), self.address.clone())
+            .map_err(to_non_retriable_da_error)?;
 
         let gas_limit = estimate_gas(
             &msg_pay_for_blobs.blob_sizes,
@@ -86,6 +94,7 @@ impl RawCelestiaClient {
             tx_size_cost_per_byte,
         );
         let fee = calculate_fee(min_gas_price, gas_limit);
+        check_balance(&self.address, balance, fee).map_err(to_non_retriable_da_error)?;
 
         let signed_tx = new_signed_tx(
             &msg_pay_for_blobs,
@@ -231,6 +240,35 @@ impl RawCelestiaClient {
         ))
     }
 
+    /// Fetches the balance of the current address in `utia`.
+    async fn fetch_balance(&self) -> anyhow::Result<u64> {
+        let mut bank_query_client = BankQueryClient::new(self.grpc_channel.clone());
+        let request = QueryBalanceRequest {
+            address: self.address.clone(),
+            denom: UNITS_SUFFIX.to_string(),
+        };
+
+        let response = bank_query_client.balance(request).await.map_err(|status| {
+            anyhow::anyhow!(
+                "failed to get account balance, code: {}, message: {}",
+                status.code(),
+                status.message()
+            )
+        })?;
+
+        // A missing balance means that the account has never been funded.
+        let Some(balance) = response.into_inner().balance else {
+            return Ok(0);
+        };
+        balance.amount.parse::<u64>().map_err(|err| {
+            anyhow::anyhow!(
+                "failed to parse account balance, amount: {}, err: {}",
+                balance.amount,
+                err
+            )
+        })
+    }
+
     /// Broadcasts the transaction and returns the transaction hash.
     async fn broadcast_tx(
         &self,
@@ -469,6 +507,25 @@ fn new_signed_tx(
     }
 }
 
+/// Checks that the account has enough funds to pay the `fee`, and warns if the balance is running low.
+fn check_balance(address: &str, balance: u64, fee: u64) -> anyhow::Result<()> {
+    // Number of blob submissions with the same fee covered by the balance, below which a warning is logged.
+    const LOW_BALANCE_SUBMISSION_COUNT: u64 = 100;
+
+    if balance < fee {
+        anyhow::bail!(
+            "insufficient funds to submit blob: balance {balance}{UNITS_SUFFIX}, required fee {fee}{UNITS_SUFFIX}; \
+             fund the Celestia account {address}"
+        );
+    }
+    if balance < fee.saturating_mul(LOW_BALANCE_SUBMISSION_COUNT) {
+        tracing::warn!(
+            "Celestia account {address} balance is running low: {balance}{UNITS_SUFFIX}, last fee {fee}{UNITS_SUFFIX}"
+        );
+    }
+    Ok(())
+}
+
 /// Returns the fee for the signed tx.
 fn calculate_fee(min_gas_price: f64, gas_limit: u64) -> u64 {
     let calculated_fee = (min_gas_price * gas_limit as f64).ceil() as u64;
@@ -600,3 +657,33 @@ impl Display for BlobTxHash {
         write!(formatter, "{}", hex::encode(self.0))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tonic::transport::Endpoint;
+
+    use super::*;
+
+    #[test]
+    fn checking_balance() {
+        check_balance("celestia1test", 1_000_000, 1_000).unwrap();
+        // Low balance is only warned about.
+        check_balance("celestia1test", 1_000, 1_000).unwrap();
+
+        let err = check_balance("celestia1test", 999, 1_000).unwrap_err();
+        let err = err.to_string();
+        assert!(err.contains("insufficient funds"), "{err}");
+        assert!(err.contains("celestia1test"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn node_errors_when_preparing_tx_are_retriable() {
+        // Nothing should listen on this port, so all node queries will fail.
+        let grpc_channel = Endpoint::from_static("http://127.0.0.1:1").connect_lazy();
+        let client =
+            RawCelestiaClient::new(grpc_channel, "01".repeat(32), "mocha-4".to_owned()).unwrap();
+
+        let err = client.prepare(vec![]).await.unwrap_err();
+        assert!(err.is_retriable(), "{err:?}");
+    }
+}
@@ -10,7 +10,6 @@ use zksync_da_client::{
 };
 
 use super::sdk::RawEigenClient;
-use crate::utils::to_non_retriable_da_error;
 
 #[derive(Debug, Clone)]
 pub struct EigenClient {
@@ -42,11 +41,7 @@ impl DataAvailabilityClient for EigenClient {
         _: u32, // batch number
         data: Vec<u8>,
     ) -> Result<DispatchResponse, DAError> {
-        let blob_id = self
-            .client
-            .dispatch_blob(data)
-            .await
-            .map_err(to_non_retriable_da_error)?;
+        let blob_id = self.client.dispatch_blob(data).await?;
 
         Ok(DispatchResponse::from(blob_id))
     }
@@ -0,0 +1,49 @@
+//! Metrics for the EigenDA client.
+
+use std::time::Duration;
+
+use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics, Unit};
+
+/// Buckets for blob confirmation latency (from 1 second to ~30 minutes).
+const CONFIRMATION_LATENCIES: Buckets = Buckets::values(&[
+    1.0, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 600.0, 900.0, 1_800.0,
+]);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "kind", rename_all = "snake_case")]
+pub(super) enum DispersalErrorKind {
+    /// gRPC / transport error when talking to the disperser.
+    Rpc,
+    /// Disperser reported that the blob has failed or didn't gather enough signatures.
+    Rejected,
+    /// Blob wasn't confirmed in time.
+    Timeout,
+    /// Returned DA certificate didn't pass verification.
+    InvalidCertificate,
+    /// Other unexpected errors (e.g., malformed replies).
+    Other,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_da_clients_eigen")]
+pub(super) struct EigenClientMetrics {
+    /// Size of the padded blob sent to the disperser.
+    #[metrics(buckets = Buckets::exponential(1_024.0..=16.0 * 1_024.0 * 1_024.0, 2.0), unit = Unit::Bytes)]
+    pub blob_size: Histogram<usize>,
+    /// Latency of the authenticated dispersal request (until the disperser acknowledges the blob).
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub dispersal_latency: Histogram<Duration>,
+    /// Latency between the blob acknowledgement and its confirmation by EigenDA.
+    #[metrics(buckets = CONFIRMATION_LATENCIES, unit = Unit::Seconds)]
+    pub confirmation_latency: Histogram<Duration>,
+    /// Number of blob status polls performed until the blob is confirmed.
+    #[metrics(buckets = Buckets::exponential(1.0..=1_024.0, 2.0))]
+    pub status_polls: Histogram<usize>,
+    /// Number of successfully dispatched and verified blobs.
+    pub dispatched_blobs: Counter,
+    /// Number of dispersal errors grouped by their kind.
+    pub dispersal_errors: Family<DispersalErrorKind, Counter>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<EigenClientMetrics> = vise::Global::new();
@@ -1,4 +1,5 @@
 mod client;
+mod metrics;
 mod sdk;
 
 pub use self::client::EigenClient;
@@ -1,19 +1,25 @@
-use std::{str::FromStr, time::Duration};
+use std::{
+    str::FromStr,
+    time::{Duration, Instant},
+};
 
+use anyhow::Context as _;
 use secp256k1::{ecdsa::RecoverableSignature, SecretKey};
 use tokio::sync::mpsc;
 use tokio_stream::{wrappers::ReceiverStream, StreamExt};
 use tonic::{
     transport::{Channel, ClientTlsConfig, Endpoint},
-    Streaming,
+    Code, Streaming,
 };
+use zksync_da_client::types::DAError;
 
+use super::metrics::{DispersalErrorKind, METRICS};
 use crate::eigen::{
     disperser,
     disperser::{
         authenticated_request::Payload::{AuthenticationData, DisperseRequest},
         disperser_client::DisperserClient,
-        AuthenticatedReply, BlobAuthHeader, BlobVerificationProof, DisperseBlobReply,
+        AuthenticatedReply, BlobAuthHeader, BlobInfo, BlobVerificationProof, DisperseBlobReply,
     },
 };
 
@@ -27,6 +33,12 @@ pub struct RawEigenClient {
 
 pub(crate) const DATA_CHUNK_SIZE: usize = 32;
 
+/// Quorums that every blob must be confirmed in (ETH and EIGEN quorums). Mirrors `quorumNumbersRequired`
+/// of the `EigenDAServiceManager` contract, which is checked when the certificate is verified on L1.
+const REQUIRED_QUORUMS: [u32; 2] = [0, 1];
+/// Maximum time to wait for the blob to be confirmed by EigenDA after it was dispersed.
+const BLOB_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
 impl RawEigenClient {
     pub(crate) const BUFFER_SIZE: usize = 1000;
 
@@ -52,12 +64,41 @@ impl RawEigenClient {
         })
     }
 
-    pub async fn dispatch_blob(&self, data: Vec<u8>) -> anyhow::Result<String> {
+    /// Disperses the blob, waits until it's confirmed by EigenDA and verifies the returned DA certificate.
+    /// Returns the blob ID in the `{batch_id}:{blob_index}` format.
+    pub async fn dispatch_blob(&self, data: Vec<u8>) -> Result<String, DAError> {
+        let padded_data = convert_by_padding_empty_byte(&data);
+        let min_data_length = padded_data.len().div_ceil(DATA_CHUNK_SIZE);
+        METRICS.blob_size.observe(padded_data.len());
+
+        let started_at = Instant::now();
+        let disperse_reply = self
+            .disperse_blob(padded_data)
+            .await
+            .map_err(classify_dispersal_error)?;
+        METRICS.dispersal_latency.observe(started_at.elapsed());
+
+        let started_at = Instant::now();
+        let blob_info = self.await_for_inclusion(disperse_reply).await?;
+        METRICS.confirmation_latency.observe(started_at.elapsed());
+
+        let verification_proof = verify_blob_info(blob_info, min_data_length)
+            .map_err(|err| dispersal_error(DispersalErrorKind::InvalidCertificate, err, false))?;
+        let blob_id = format!(
+            "{}:{}",
+            verification_proof.batch_id, verification_proof.blob_index
+        );
+        METRICS.dispatched_blobs.inc();
+        tracing::info!("Blob dispatch confirmed, blob id: {}", blob_id);
+
+        Ok(blob_id)
+    }
+
+    async fn disperse_blob(&self, padded_data: Vec<u8>) -> anyhow::Result<DisperseBlobReply> {
         let mut client_clone = self.client.clone();
         let (tx, rx) = mpsc::channel(Self::BUFFER_SIZE);
 
         let response_stream = client_clone.disperse_blob_authenticated(ReceiverStream::new(rx));
-        let padded_data = convert_by_padding_empty_byte(&data);
 
         // 1. send DisperseBlobRequest
         self.disperse_data(padded_data, &tx).await?;
@@ -76,26 +117,14 @@ impl RawEigenClient {
         let reply = response_stream
             .next()
             .await
-            .ok_or_else(|| anyhow::anyhow!("No response from server"))?
-            .unwrap()
+            .ok_or_else(|| anyhow::anyhow!("No response from server"))??
             .payload
             .ok_or_else(|| anyhow::anyhow!("No payload in response"))?;
 
         let disperser::authenticated_reply::Payload::DisperseReply(disperse_reply) = reply else {
             return Err(anyhow::anyhow!("Unexpected response from server"));
         };
-
-        // 5. poll for blob status until it reaches the Confirmed state
-        let verification_proof = self
-            .await_for_inclusion(client_clone, disperse_reply)
-            .await?;
-        let blob_id = format!(
-            "{}:{}",
-            verification_proof.batch_id, verification_proof.blob_index
-        );
-        tracing::info!("Blob dispatch confirmed, blob id: {}", blob_id);
-
-        Ok(blob_id)
+        Ok(disperse_reply)
     }
 
     async fn disperse_data(
@@ -171,46 +200,195 @@ impl RawEigenClient {
         }
     }
 
+    /// Polls the blob status until it reaches the `Confirmed` or `Finalized` state. Transient RPC errors
+    /// are retried until [`BLOB_CONFIRMATION_TIMEOUT`] elapses.
     async fn await_for_inclusion(
         &self,
-        mut client: DisperserClient<Channel>,
         disperse_blob_reply: DisperseBlobReply,
-    ) -> anyhow::Result<BlobVerificationProof> {
+    ) -> Result<BlobInfo, DAError> {
+        let mut client = self.client.clone();
         let polling_request = disperser::BlobStatusRequest {
             request_id: disperse_blob_reply.request_id,
         };
+        let started_at = Instant::now();
+        let mut polls = 0_usize;
 
         loop {
+            if started_at.elapsed() > BLOB_CONFIRMATION_TIMEOUT {
+                let err = anyhow::anyhow!(
+                    "Blob was not confirmed in {BLOB_CONFIRMATION_TIMEOUT:?} after dispersal"
+                );
+                return Err(dispersal_error(DispersalErrorKind::Timeout, err, true));
+            }
+
             tokio::time::sleep(self.polling_interval).await;
-            let resp = client
-                .get_blob_status(polling_request.clone())
-                .await?
-                .into_inner();
+            polls += 1;
+            let resp = match client.get_blob_status(polling_request.clone()).await {
+                Ok(resp) => resp.into_inner(),
+                Err(status) if is_retriable_status(&status) => {
+                    tracing::warn!("Transient error polling blob status, will retry: {status}");
+                    continue;
+                }
+                Err(status) => return Err(classify_dispersal_error(status.into())),
+            };
 
-            match disperser::BlobStatus::try_from(resp.status)? {
+            let status = disperser::BlobStatus::try_from(resp.status).map_err(|err| {
+                dispersal_error(DispersalErrorKind::Other, anyhow::Error::new(err), false)
+            })?;
+            match status {
                 disperser::BlobStatus::Processing | disperser::BlobStatus::Dispersing => {}
+                // A fresh dispersal of the same blob may succeed, so these errors are retriable.
                 disperser::BlobStatus::Failed => {
-                    return Err(anyhow::anyhow!("Blob dispatch failed"))
+                    let err = anyhow::anyhow!("Blob dispatch failed");
+                    return Err(dispersal_error(DispersalErrorKind::Rejected, err, true));
                 }
                 disperser::BlobStatus::InsufficientSignatures => {
-                    return Err(anyhow::anyhow!("Insufficient signatures"))
+                    let err = anyhow::anyhow!("Insufficient signatures");
+                    return Err(dispersal_error(DispersalErrorKind::Rejected, err, true));
                 }
                 disperser::BlobStatus::Confirmed | disperser::BlobStatus::Finalized => {
-                    let verification_proof = resp
-                        .info
-                        .ok_or_else(|| anyhow::anyhow!("No blob header in response"))?
-                        .blob_verification_proof
-                        .ok_or_else(|| anyhow::anyhow!("No blob verification proof in response"))?;
-
-                    return Ok(verification_proof);
+                    METRICS.status_polls.observe(polls);
+                    return resp.info.ok_or_else(|| {
+                        let err = anyhow::anyhow!("No blob info in response");
+                        dispersal_error(DispersalErrorKind::Other, err, false)
+                    });
                 }
 
-                _ => return Err(anyhow::anyhow!("Received unknown blob status")),
+                _ => {
+                    let err = anyhow::anyhow!("Received unknown blob status");
+                    return Err(dispersal_error(DispersalErrorKind::Other, err, false));
+                }
             }
         }
     }
 }
 
+fn dispersal_error(kind: DispersalErrorKind, error: anyhow::Error, is_retriable: bool) -> DAError {
+    METRICS.dispersal_errors[&kind].inc();
+    DAError {
+        error,
+        is_retriable,
+    }
+}
+
+fn is_retriable_status(status: &tonic::Status) -> bool {
+    matches!(
+        status.code(),
+        Code::Unavailable
+            | Code::DeadlineExceeded
+            | Code::ResourceExhausted
+            | Code::Aborted
+            | Code::Cancelled
+            | Code::Unknown
+            | Code::Internal
+    )
+}
+
+/// Classifies an error occurred while communicating with the disperser.
+fn classify_dispersal_error(error: anyhow::Error) -> DAError {
+    if let Some(status) = error.downcast_ref::<tonic::Status>() {
+        let is_retriable = is_retriable_status(status);
+        dispersal_error(DispersalErrorKind::Rpc, error, is_retriable)
+    } else if error.downcast_ref::<tonic::transport::Error>().is_some() {
+        dispersal_error(DispersalErrorKind::Rpc, error, true)
+    } else {
+        dispersal_error(DispersalErrorKind::Other, error, false)
+    }
+}
+
+/// Verifies the DA certificate returned by the disperser against the expectations of the L1 certificate
+/// verification: the blob must be confirmed in all required quorums with at least the confirmation threshold
+/// of the stake signed, and the certificate must reference a batch confirmed on L1.
+///
+/// The Merkle inclusion proof of the blob header in the batch is not checked here; it's verified on L1.
+fn verify_blob_info(
+    blob_info: BlobInfo,
+    min_data_length: usize,
+) -> anyhow::Result<BlobVerificationProof> {
+    let blob_header = blob_info
+        .blob_header
+        .context("No blob header in response")?;
+    let proof = blob_info
+        .blob_verification_proof
+        .context("No blob verification proof in response")?;
+    let batch_metadata = proof
+        .batch_metadata
+        .as_ref()
+        .context("No batch metadata in blob verification proof")?;
+    let batch_header = batch_metadata
+        .batch_header
+        .as_ref()
+        .context("No batch header in batch metadata")?;
+
+    anyhow::ensure!(
+        blob_header.commitment.is_some(),
+        "No KZG commitment in blob header"
+    );
+    anyhow::ensure!(
+        blob_header.data_length as usize >= min_data_length,
+        "Blob length in the certificate ({} symbols) is less than the length of the dispersed data ({} symbols)",
+        blob_header.data_length,
+        min_data_length
+    );
+    anyhow::ensure!(
+        batch_metadata.batch_header_hash.len() == 32,
+        "Invalid batch header hash length: {}",
+        batch_metadata.batch_header_hash.len()
+    );
+    anyhow::ensure!(
+        batch_metadata.confirmation_block_number > 0,
+        "Batch is not confirmed on L1"
+    );
+    anyhow::ensure!(
+        proof.quorum_indexes.len() == blob_header.blob_quorum_params.len(),
+        "Mismatch between the number of quorum indexes ({}) and blob quorum params ({})",
+        proof.quorum_indexes.len(),
+        blob_header.blob_quorum_params.len()
+    );
+    anyhow::ensure!(
+        batch_header.quorum_numbers.len() == batch_header.quorum_signed_percentages.len(),
+        "Mismatch between the number of quorums and signed percentages in batch header"
+    );
+
+    for (param, &quorum_index) in blob_header
+        .blob_quorum_params
+        .iter()
+        .zip(&proof.quorum_indexes)
+    {
+        let quorum_index = usize::from(quorum_index);
+        let quorum_number = *batch_header
+            .quorum_numbers
+            .get(quorum_index)
+            .with_context(|| format!("Quorum index {quorum_index} is out of bounds"))?;
+        anyhow::ensure!(
+            u32::from(quorum_number) == param.quorum_number,
+            "Quorum number mismatch: batch header has {quorum_number}, blob header has {}",
+            param.quorum_number
+        );
+        anyhow::ensure!(
+            param.confirmation_threshold_percentage > param.adversary_threshold_percentage,
+            "Confirmation threshold for quorum {quorum_number} is not greater than adversary threshold"
+        );
+        let signed_percentage = batch_header.quorum_signed_percentages[quorum_index];
+        anyhow::ensure!(
+            u32::from(signed_percentage) >= param.confirmation_threshold_percentage,
+            "Quorum {quorum_number} has insufficient signed stake: {signed_percentage}% < {}%",
+            param.confirmation_threshold_percentage
+        );
+    }
+
+    for required_quorum in REQUIRED_QUORUMS {
+        anyhow::ensure!(
+            blob_header
+                .blob_quorum_params
+                .iter()
+                .any(|param| param.quorum_number == required_quorum),
+            "Blob is not confirmed in required quorum {required_quorum}"
+        );
+    }
+    Ok(proof)
+}
+
 fn get_account_id(secret_key: &SecretKey) -> String {
     let public_key =
         secp256k1::PublicKey::from_secret_key(&secp256k1::Secp256k1::new(), secret_key);
@@ -244,3 +422,126 @@ fn convert_by_padding_empty_byte(data: &[u8]) -> Vec<u8> {
     valid_data.truncate(valid_end);
     valid_data
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eigen::{
+        common::G1Commitment,
+        disperser::{BatchHeader, BatchMetadata, BlobHeader, BlobQuorumParam},
+    };
+
+    fn quorum_param(quorum_number: u32) -> BlobQuorumParam {
+        BlobQuorumParam {
+            quorum_number,
+            adversary_threshold_percentage: 33,
+            confirmation_threshold_percentage: 55,
+            chunk_length: 1,
+        }
+    }
+
+    fn mock_blob_info() -> BlobInfo {
+        BlobInfo {
+            blob_header: Some(BlobHeader {
+                commitment: Some(G1Commitment {
+                    x: vec![1; 32],
+                    y: vec![2; 32],
+                }),
+                data_length: 4,
+                blob_quorum_params: vec![quorum_param(0), quorum_param(1)],
+            }),
+            blob_verification_proof: Some(BlobVerificationProof {
+                batch_id: 10,
+                blob_index: 3,
+                batch_metadata: Some(BatchMetadata {
+                    batch_header: Some(BatchHeader {
+                        batch_root: vec![0; 32],
+                        quorum_numbers: vec![1, 0],
+                        quorum_signed_percentages: vec![80, 70],
+                        reference_block_number: 100,
+                    }),
+                    signatory_record_hash: vec![0; 32],
+                    fee: vec![],
+                    confirmation_block_number: 110,
+                    batch_header_hash: vec![0; 32],
+                }),
+                inclusion_proof: vec![],
+                quorum_indexes: vec![1, 0],
+            }),
+        }
+    }
+
+    fn batch_header_mut(blob_info: &mut BlobInfo) -> &mut BatchHeader {
+        blob_info
+            .blob_verification_proof
+            .as_mut()
+            .unwrap()
+            .batch_metadata
+            .as_mut()
+            .unwrap()
+            .batch_header
+            .as_mut()
+            .unwrap()
+    }
+
+    #[test]
+    fn verifying_valid_blob_info() {
+        let proof = verify_blob_info(mock_blob_info(), 4).unwrap();
+        assert_eq!((proof.batch_id, proof.blob_index), (10, 3));
+    }
+
+    #[test]
+    fn verifying_blob_info_with_insufficient_length() {
+        let err = verify_blob_info(mock_blob_info(), 5).unwrap_err();
+        assert!(err.to_string().contains("less than the length"), "{err}");
+    }
+
+    #[test]
+    fn verifying_blob_info_with_insufficient_signatures() {
+        let mut blob_info = mock_blob_info();
+        batch_header_mut(&mut blob_info).quorum_signed_percentages = vec![80, 50];
+        let err = verify_blob_info(blob_info, 4).unwrap_err();
+        assert!(
+            err.to_string().contains("insufficient signed stake"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn verifying_blob_info_with_mismatched_quorums() {
+        let mut blob_info = mock_blob_info();
+        batch_header_mut(&mut blob_info).quorum_numbers = vec![0, 1];
+        let err = verify_blob_info(blob_info, 4).unwrap_err();
+        assert!(err.to_string().contains("Quorum number mismatch"), "{err}");
+    }
+
+    #[test]
+    fn verifying_blob_info_without_required_quorum() {
+        let mut blob_info = mock_blob_info();
+        let blob_header = blob_info.blob_header.as_mut().unwrap();
+        blob_header.blob_quorum_params.pop();
+        blob_info
+            .blob_verification_proof
+            .as_mut()
+            .unwrap()
+            .quorum_indexes
+            .pop();
+        let err = verify_blob_info(blob_info, 4).unwrap_err();
+        assert!(err.to_string().contains("required quorum 1"), "{err}");
+    }
+
+    #[test]
+    fn verifying_unconfirmed_blob_info() {
+        let mut blob_info = mock_blob_info();
+        blob_info
+            .blob_verification_proof
+            .as_mut()
+            .unwrap()
+            .batch_metadata
+            .as_mut()
+            .unwrap()
+            .confirmation_block_number = 0;
+        let err = verify_blob_info(blob_info, 4).unwrap_err();
+        assert!(err.to_string().contains("not confirmed"), "{err}");
+    }
+}
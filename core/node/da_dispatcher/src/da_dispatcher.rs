@@ -10,13 +10,18 @@ use zksync_da_client::{
     DataAvailabilityClient,
 };
 use zksync_dal::{ConnectionPool, Core, CoreDal};
-use zksync_types::L1BatchNumber;
+use zksync_types::{pubdata_da::DataAvailabilityBlob, L1BatchNumber};
 
 use crate::metrics::METRICS;
 
+#[cfg(test)]
+mod tests;
+
 #[derive(Debug)]
 pub struct DataAvailabilityDispatcher {
     client: Box<dyn DataAvailabilityClient>,
+    /// Client that pubdata is additionally dispatched to in the redundancy mode.
+    fallback_client: Option<Box<dyn DataAvailabilityClient>>,
     pool: ConnectionPool<Core>,
     config: DADispatcherConfig,
 }
@@ -31,21 +36,39 @@ impl DataAvailabilityDispatcher {
             pool,
             config,
             client,
+            fallback_client: None,
         }
     }
 
+    /// Enables the redundancy mode: pubdata is dispatched both to the primary and to the provided fallback client.
+    /// Dispatching to either client doesn't depend on the other one. If the primary client doesn't provide inclusion
+    /// data within the configured SLA, it's taken from the fallback client instead.
+    pub fn with_fallback_client(
+        mut self,
+        fallback_client: Box<dyn DataAvailabilityClient>,
+    ) -> Self {
+        self.fallback_client = Some(fallback_client);
+        self
+    }
+
     pub async fn run(self, mut stop_receiver: Receiver<bool>) -> anyhow::Result<()> {
         loop {
             if *stop_receiver.borrow() {
                 break;
             }
 
-            let subtasks = futures::future::join(
+            let subtasks = futures::future::join3(
                 async {
                     if let Err(err) = self.dispatch().await {
                         tracing::error!("dispatch error {err:?}");
                     }
                 },
+                async {
+                    if let Err(err) = self.dispatch_to_fallback().await {
+                        METRICS.fallback_dispatch_failures.inc();
+                        tracing::error!("fallback dispatch error {err:?}");
+                    }
+                },
                 async {
                     if let Err(err) = self.poll_for_inclusion().await {
                         tracing::error!("poll_for_inclusion error {err:?}");
@@ -139,15 +162,22 @@ impl DataAvailabilityDispatcher {
         let inclusion_data = if self.config.use_dummy_inclusion_data() {
             Some(InclusionData { data: vec![] })
         } else {
-            self.client
+            let inclusion_data = self
+                .client
                 .get_inclusion_data(blob_info.blob_id.as_str())
-                .await
-                .with_context(|| {
-                    format!(
-                        "failed to get inclusion data for blob_id: {}, batch_number: {}",
-                        blob_info.blob_id, blob_info.l1_batch_number
-                    )
-                })?
+                .await;
+            match inclusion_data {
+                Ok(Some(inclusion_data)) => Some(inclusion_data),
+                primary_result => match self.get_fallback_inclusion_data(&blob_info).await? {
+                    Some(inclusion_data) => Some(inclusion_data),
+                    None => primary_result.with_context(|| {
+                        format!(
+                            "failed to get inclusion data for blob_id: {}, batch_number: {}",
+                            blob_info.blob_id, blob_info.l1_batch_number
+                        )
+                    })?,
+                },
+            }
         };
 
         let Some(inclusion_data) = inclusion_data else {
@@ -179,6 +209,94 @@ impl DataAvailabilityDispatcher {
 
         Ok(())
     }
+
+    /// Dispatches the blobs to the fallback client (if any), and saves the fallback blob_id in the database.
+    /// Runs independently of [`Self::dispatch()`], so that neither client blocks dispatching to the other one.
+    async fn dispatch_to_fallback(&self) -> anyhow::Result<()> {
+        let Some(fallback_client) = &self.fallback_client else {
+            return Ok(());
+        };
+
+        let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
+        let batches = conn
+            .data_availability_dal()
+            .get_ready_for_fallback_da_dispatch_l1_batches(
+                self.config.max_rows_to_dispatch() as usize
+            )
+            .await?;
+        drop(conn);
+
+        for batch in batches {
+            let dispatch_response = retry(self.config.max_retries(), batch.l1_batch_number, || {
+                fallback_client.dispatch_blob(batch.l1_batch_number.0, batch.pubdata.clone())
+            })
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to dispatch a blob with batch_number: {} to the fallback DA client",
+                    batch.l1_batch_number
+                )
+            })?;
+            let sent_at = Utc::now().naive_utc();
+
+            let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
+            conn.data_availability_dal()
+                .insert_l1_batch_fallback_da(
+                    batch.l1_batch_number,
+                    dispatch_response.blob_id.as_str(),
+                    sent_at,
+                )
+                .await?;
+            drop(conn);
+
+            tracing::info!(
+                "Dispatched a DA blob for batch_number: {} to the fallback DA client",
+                batch.l1_batch_number
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Returns inclusion data from the fallback client if the primary client has missed its inclusion SLA.
+    async fn get_fallback_inclusion_data(
+        &self,
+        blob_info: &DataAvailabilityBlob,
+    ) -> anyhow::Result<Option<InclusionData>> {
+        let (Some(fallback_client), Some(inclusion_sla)) =
+            (&self.fallback_client, self.config.inclusion_sla())
+        else {
+            return Ok(None);
+        };
+        let Some(fallback_blob_id) = &blob_info.fallback_blob_id else {
+            return Ok(None);
+        };
+        let elapsed = Utc::now().signed_duration_since(blob_info.sent_at);
+        if elapsed
+            .to_std()
+            .map_or(true, |elapsed| elapsed < inclusion_sla)
+        {
+            return Ok(None);
+        }
+
+        let inclusion_data = fallback_client
+            .get_inclusion_data(fallback_blob_id)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to get fallback inclusion data for blob_id: {fallback_blob_id}, batch_number: {}",
+                    blob_info.l1_batch_number
+                )
+            })?;
+        if inclusion_data.is_some() {
+            METRICS.inclusion_failovers.inc();
+            tracing::warn!(
+                "Primary DA client missed inclusion SLA ({inclusion_sla:?}) for batch_number: {}; using inclusion data from the fallback DA client",
+                blob_info.l1_batch_number
+            );
+        }
+        Ok(inclusion_data)
+    }
 }
 
 async fn retry<T, Fut, F>(
@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use zksync_da_client::types::DispatchResponse;
+use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+use zksync_node_test_utils::create_l1_batch;
+
+use super::*;
+
+#[derive(Debug, Clone)]
+struct MockDAClient {
+    blob_id_prefix: &'static str,
+    fail_dispatch: bool,
+    inclusion_data: Option<Vec<u8>>,
+    dispatched_batches: Arc<std::sync::Mutex<Vec<u32>>>,
+}
+
+impl MockDAClient {
+    fn new(blob_id_prefix: &'static str) -> Self {
+        Self {
+            blob_id_prefix,
+            fail_dispatch: false,
+            inclusion_data: None,
+            dispatched_batches: Arc::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl DataAvailabilityClient for MockDAClient {
+    async fn dispatch_blob(
+        &self,
+        batch_number: u32,
+        _data: Vec<u8>,
+    ) -> Result<DispatchResponse, DAError> {
+        if self.fail_dispatch {
+            return Err(DAError {
+                error: anyhow::anyhow!("dispatch failed"),
+                is_retriable: false,
+            });
+        }
+        self.dispatched_batches.lock().unwrap().push(batch_number);
+        Ok(format!("{}{batch_number}", self.blob_id_prefix).into())
+    }
+
+    async fn get_inclusion_data(&self, _blob_id: &str) -> Result<Option<InclusionData>, DAError> {
+        Ok(self
+            .inclusion_data
+            .clone()
+            .map(|data| InclusionData { data }))
+    }
+
+    fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
+        Box::new(self.clone())
+    }
+
+    fn blob_size_limit(&self) -> Option<usize> {
+        None
+    }
+}
+
+fn test_config(inclusion_sla_ms: Option<u64>) -> DADispatcherConfig {
+    DADispatcherConfig {
+        use_dummy_inclusion_data: Some(false),
+        inclusion_sla_ms,
+        ..DADispatcherConfig::for_tests()
+    }
+}
+
+async fn prepare_storage(pool: &ConnectionPool<Core>) {
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    let mut header = create_l1_batch(1);
+    header.pubdata_input = Some(vec![1; 32]);
+    storage
+        .blocks_dal()
+        .insert_mock_l1_batch(&header)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn fallback_dispatch_does_not_depend_on_primary_client() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    prepare_storage(&pool).await;
+
+    let primary_client = MockDAClient {
+        fail_dispatch: true,
+        ..MockDAClient::new("primary-")
+    };
+    let fallback_client = MockDAClient::new("fallback-");
+    let dispatcher =
+        DataAvailabilityDispatcher::new(pool.clone(), test_config(None), Box::new(primary_client))
+            .with_fallback_client(Box::new(fallback_client.clone()));
+
+    dispatcher.dispatch().await.unwrap_err();
+    dispatcher.dispatch_to_fallback().await.unwrap();
+    assert_eq!(*fallback_client.dispatched_batches.lock().unwrap(), [1]);
+
+    let mut storage = pool.connection().await.unwrap();
+    let ready_for_fallback = storage
+        .data_availability_dal()
+        .get_ready_for_fallback_da_dispatch_l1_batches(10)
+        .await
+        .unwrap();
+    assert!(ready_for_fallback.is_empty());
+    // The primary dispatch is still pending.
+    let ready_for_primary = storage
+        .data_availability_dal()
+        .get_ready_for_da_dispatch_l1_batches(10)
+        .await
+        .unwrap();
+    assert_eq!(ready_for_primary.len(), 1);
+    assert_eq!(ready_for_primary[0].l1_batch_number, L1BatchNumber(1));
+}
+
+#[tokio::test]
+async fn inclusion_data_fails_over_to_fallback_client_after_sla() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    prepare_storage(&pool).await;
+
+    // The primary client never provides inclusion data.
+    let primary_client = MockDAClient::new("primary-");
+    let fallback_client = MockDAClient {
+        inclusion_data: Some(vec![42; 4]),
+        ..MockDAClient::new("fallback-")
+    };
+
+    // The SLA isn't missed yet, so there should be no failover.
+    let dispatcher = DataAvailabilityDispatcher::new(
+        pool.clone(),
+        test_config(Some(3_600_000)),
+        Box::new(primary_client.clone()),
+    )
+    .with_fallback_client(Box::new(fallback_client.clone()));
+    dispatcher.dispatch().await.unwrap();
+    dispatcher.dispatch_to_fallback().await.unwrap();
+    dispatcher.poll_for_inclusion().await.unwrap();
+
+    let mut storage = pool.connection().await.unwrap();
+    let blob = storage
+        .data_availability_dal()
+        .get_first_da_blob_awaiting_inclusion()
+        .await
+        .unwrap()
+        .expect("no blob awaiting inclusion");
+    assert_eq!(blob.l1_batch_number, L1BatchNumber(1));
+    assert_eq!(blob.blob_id, "primary-1");
+    assert_eq!(blob.fallback_blob_id.as_deref(), Some("fallback-1"));
+
+    let dispatcher = DataAvailabilityDispatcher::new(
+        pool.clone(),
+        test_config(Some(0)),
+        Box::new(primary_client),
+    )
+    .with_fallback_client(Box::new(fallback_client));
+    dispatcher.poll_for_inclusion().await.unwrap();
+
+    let blob = storage
+        .data_availability_dal()
+        .get_first_da_blob_awaiting_inclusion()
+        .await
+        .unwrap();
+    assert!(blob.is_none(), "{blob:?}");
+}
+
+#[tokio::test]
+async fn inclusion_data_does_not_fail_over_without_fallback_blob() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    prepare_storage(&pool).await;
+
+    let primary_client = MockDAClient::new("primary-");
+    let fallback_client = MockDAClient {
+        fail_dispatch: true,
+        inclusion_data: Some(vec![42; 4]),
+        ..MockDAClient::new("fallback-")
+    };
+    let dispatcher = DataAvailabilityDispatcher::new(
+        pool.clone(),
+        test_config(Some(0)),
+        Box::new(primary_client),
+    )
+    .with_fallback_client(Box::new(fallback_client));
+    dispatcher.dispatch().await.unwrap();
+    dispatcher.dispatch_to_fallback().await.unwrap_err();
+    dispatcher.poll_for_inclusion().await.unwrap();
+
+    let mut storage = pool.connection().await.unwrap();
+    let blob = storage
+        .data_availability_dal()
+        .get_first_da_blob_awaiting_inclusion()
+        .await
+        .unwrap()
+        .expect("no blob awaiting inclusion");
+    assert_eq!(blob.fallback_blob_id, None);
+}
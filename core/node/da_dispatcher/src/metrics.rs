@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use vise::{Buckets, Gauge, Histogram, Metrics, Unit};
+use vise::{Buckets, Counter, Gauge, Histogram, Metrics, Unit};
 
 /// Buckets for `blob_dispatch_latency` (from 0.1 to 120 seconds).
 const DISPATCH_LATENCIES: Buckets =
@@ -27,6 +27,10 @@ pub(super) struct DataAvailabilityDispatcherMetrics {
     pub last_dispatched_l1_batch: Gauge<usize>,
     /// Last L1 batch that has its inclusion finalized by DA layer.
     pub last_included_l1_batch: Gauge<usize>,
+    /// Number of blobs that failed to be dispatched to the fallback DA client.
+    pub fallback_dispatch_failures: Counter,
+    /// Number of L1 batches for which inclusion data was taken from the fallback DA client.
+    pub inclusion_failovers: Counter,
 }
 
 #[vise::register]
@@ -9,7 +9,8 @@ use anyhow::Context as _;
 use serde::{Deserialize, Serialize};
 use tokio::sync::watch;
 use zksync_dal::{
-    pruning_dal::{HardPruningInfo, PruningInfo, SoftPruningInfo},
+    partitions_dal::PartitionedTable,
+    pruning_dal::{HardPruningEstimate, HardPruningInfo, PruningInfo, SoftPruningInfo},
     Connection, ConnectionPool, Core, CoreDal,
 };
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
@@ -19,7 +20,8 @@ use self::{
     metrics::{ConditionOutcome, PruneType, METRICS},
     prune_conditions::{
         ConsistencyCheckerProcessedBatch, L1BatchExistsCondition, L1BatchOlderThanPruneCondition,
-        NextL1BatchHasMetadataCondition, NextL1BatchWasExecutedCondition, PruneCondition,
+        NextL1BatchHasMetadataCondition, NextL1BatchWasExecutedCondition,
+        NoIncompleteSnapshotsCondition, PruneCondition,
     },
 };
 
@@ -39,6 +41,16 @@ pub struct DbPrunerConfig {
     /// Minimum age of an L1 batch in order for it to be eligible for pruning. Setting this to zero
     /// will effectively disable this pruning criterion.
     pub minimum_l1_batch_age: Duration,
+    /// Maximum age of an L1 batch retained in Postgres. If set, L1 batches older than this age are pruned
+    /// even if there are less than [`Self::pruned_batch_chunk_size`] of them, as long as they satisfy all other
+    /// prune conditions. Should be greater than [`Self::minimum_l1_batch_age`] to have any effect.
+    pub maximum_l1_batch_age: Option<Duration>,
+    /// Whether L1 batches must be processed by the consistency checker in order to be pruned. Should be disabled
+    /// on the main node, which doesn't run the consistency checker.
+    pub require_consistency_check: bool,
+    /// If set, the pruner doesn't remove any data. Instead, it periodically estimates the amount of data
+    /// that could be pruned and reports it via logs and metrics.
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -82,6 +94,9 @@ pub struct DbPruner {
     connection_pool: ConnectionPool<Core>,
     health_updater: HealthUpdater,
     prune_conditions: Vec<Arc<dyn PruneCondition>>,
+    /// Condition signalling that an L1 batch is beyond the retention period and should be pruned regardless
+    /// of the chunk size.
+    max_age_condition: Option<Arc<dyn PruneCondition>>,
 }
 
 impl DbPruner {
@@ -96,10 +111,15 @@ impl DbPruner {
             Arc::new(NextL1BatchWasExecutedCondition {
                 pool: connection_pool.clone(),
             }),
-            Arc::new(ConsistencyCheckerProcessedBatch {
+            Arc::new(NoIncompleteSnapshotsCondition {
                 pool: connection_pool.clone(),
             }),
         ];
+        if config.require_consistency_check {
+            conditions.push(Arc::new(ConsistencyCheckerProcessedBatch {
+                pool: connection_pool.clone(),
+            }));
+        }
         if config.minimum_l1_batch_age > Duration::ZERO {
             // Do not add a condition if it's trivial in order to not clutter logs.
             conditions.push(Arc::new(L1BatchOlderThanPruneCondition {
@@ -108,7 +128,23 @@ impl DbPruner {
             }));
         }
 
-        Self::with_conditions(config, connection_pool, conditions)
+        let max_age_condition = config.maximum_l1_batch_age.map(|maximum_age| {
+            if maximum_age <= config.minimum_l1_batch_age {
+                tracing::warn!(
+                    "Maximum L1 batch age {maximum_age:?} doesn't exceed the minimum age {:?}; \
+                     L1 batches will be retained for at least the minimum age",
+                    config.minimum_l1_batch_age
+                );
+            }
+            Arc::new(L1BatchOlderThanPruneCondition {
+                minimum_age: maximum_age,
+                pool: connection_pool.clone(),
+            }) as Arc<dyn PruneCondition>
+        });
+
+        let mut this = Self::with_conditions(config, connection_pool, conditions);
+        this.max_age_condition = max_age_condition;
+        this
     }
 
     fn with_conditions(
@@ -121,6 +157,7 @@ impl DbPruner {
             connection_pool,
             health_updater: ReactiveHealthCheck::new("db_pruner").1,
             prune_conditions,
+            max_age_condition: None,
         }
     }
 
@@ -164,6 +201,59 @@ impl DbPruner {
         result
     }
 
+    /// Checks whether the specified L1 batch is beyond the retention period. Always returns `false`
+    /// if the maximum L1 batch age is not configured.
+    async fn is_l1_batch_expired(&self, l1_batch_number: L1BatchNumber) -> bool {
+        let Some(condition) = &self.max_age_condition else {
+            return false;
+        };
+        match condition.is_batch_prunable(l1_batch_number).await {
+            Ok(is_expired) => is_expired,
+            Err(err) => {
+                tracing::warn!("Error checking expiration of L1 batch #{l1_batch_number}: {err}");
+                false
+            }
+        }
+    }
+
+    /// Finds the last L1 batch among `start + step`, `start + 2 * step`, ..., `start + max_steps * step`
+    /// satisfying prune conditions. If `require_expiration` is set, the batch must also be beyond the retention period.
+    async fn find_last_prunable_step(
+        &self,
+        start: L1BatchNumber,
+        step: u32,
+        max_steps: u32,
+        require_expiration: bool,
+    ) -> Option<L1BatchNumber> {
+        // Prune conditions (and expiration) are monotonic (if an L1 batch is prunable, all preceding batches are prunable as well),
+        // so we can use binary search. Invariant: `low` steps are prunable, and `high + 1` steps are not.
+        let (mut low, mut high) = (0, max_steps);
+        while low < high {
+            let mid = low + (high - low + 1) / 2;
+            let l1_batch_number = start + mid * step;
+            let is_prunable = (!require_expiration
+                || self.is_l1_batch_expired(l1_batch_number).await)
+                && self.is_l1_batch_prunable(l1_batch_number).await;
+            if is_prunable {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        (low > 0).then(|| start + low * step)
+    }
+
+    /// Finds the last L1 batch in the incomplete chunk after `start` that can be pruned because it's beyond
+    /// the retention period.
+    async fn find_last_expired_l1_batch(&self, start: L1BatchNumber) -> Option<L1BatchNumber> {
+        if self.max_age_condition.is_none() {
+            return None;
+        }
+        let max_steps = self.config.pruned_batch_chunk_size.max(1) - 1;
+        self.find_last_prunable_step(start, 1, max_steps, true)
+            .await
+    }
+
     async fn update_l1_batches_metric(&self) -> anyhow::Result<()> {
         let mut storage = self.connection_pool.connection_tagged("db_pruner").await?;
         let first_l1_batch = storage.blocks_dal().get_earliest_l1_batch_number().await?;
@@ -186,18 +276,97 @@ impl DbPruner {
         self.health_updater.update(health);
     }
 
+    /// Finds the last L1 batch that soft pruning would reach given the current prune conditions and chunk size.
+    async fn find_last_prunable_l1_batch(&self) -> anyhow::Result<Option<L1BatchNumber>> {
+        let mut storage = self.connection_pool.connection_tagged("db_pruner").await?;
+        let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        let sealed_l1_batch = storage.blocks_dal().get_sealed_l1_batch_number().await?;
+        drop(storage);
+        let Some(sealed_l1_batch) = sealed_l1_batch else {
+            return Ok(None);
+        };
+
+        let start = pruning_info
+            .last_soft_pruned
+            .map_or(L1BatchNumber(0), |info| info.l1_batch);
+        let chunk_size = self.config.pruned_batch_chunk_size.max(1);
+        let max_chunks = sealed_l1_batch.0.saturating_sub(start.0) / chunk_size;
+
+        let last_chunk_boundary = self
+            .find_last_prunable_step(start, chunk_size, max_chunks, false)
+            .await;
+        let expired_l1_batch = self
+            .find_last_expired_l1_batch(last_chunk_boundary.unwrap_or(start))
+            .await;
+        Ok(expired_l1_batch.or(last_chunk_boundary))
+    }
+
+    /// Estimates the amount of data that would be pruned if the pruner wasn't in the dry-run mode.
+    async fn estimate_pruning(&self) -> anyhow::Result<()> {
+        let Some(last_l1_batch) = self.find_last_prunable_l1_batch().await? else {
+            tracing::info!("Pruning dry run: no L1 batches can be pruned");
+            METRICS.observe_pruning_estimate(0, 0, HardPruningEstimate::default());
+            return Ok(());
+        };
+
+        let mut storage = self.connection_pool.connection_tagged("db_pruner").await?;
+        let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        let (first_l1_batch, first_l2_block) = match pruning_info.last_soft_pruned {
+            Some(info) => (info.l1_batch + 1, info.l2_block + 1),
+            None => (L1BatchNumber(0), L2BlockNumber(0)),
+        };
+        let (_, last_l2_block) = storage
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(last_l1_batch)
+            .await?
+            .with_context(|| {
+                format!("L1 batch #{last_l1_batch} is prunable, but has no L2 blocks")
+            })?;
+        let estimate = storage
+            .pruning_dal()
+            .estimate_hard_pruning(first_l2_block..=last_l2_block)
+            .await?;
+
+        tracing::info!(
+            "Pruning dry run: L1 batches {first_l1_batch}..={last_l1_batch} and L2 blocks {first_l2_block}..={last_l2_block} \
+             can be pruned; estimated data to be removed: {estimate:?}"
+        );
+        METRICS.observe_pruning_estimate(
+            (last_l1_batch.0 + 1)
+                .saturating_sub(first_l1_batch.0)
+                .into(),
+            (last_l2_block.0 + 1)
+                .saturating_sub(first_l2_block.0)
+                .into(),
+            estimate,
+        );
+        Ok(())
+    }
+
     async fn soft_prune(&self, storage: &mut Connection<'_, Core>) -> anyhow::Result<bool> {
         let start = Instant::now();
         let mut transaction = storage.start_transaction().await?;
 
         let mut current_pruning_info = transaction.pruning_dal().get_pruning_info().await?;
-        let next_l1_batch_to_prune = current_pruning_info
+        let last_soft_pruned_l1_batch = current_pruning_info
             .last_soft_pruned
-            .map_or(L1BatchNumber(0), |info| info.l1_batch)
-            + self.config.pruned_batch_chunk_size;
+            .map_or(L1BatchNumber(0), |info| info.l1_batch);
+        let mut next_l1_batch_to_prune =
+            last_soft_pruned_l1_batch + self.config.pruned_batch_chunk_size;
         if !self.is_l1_batch_prunable(next_l1_batch_to_prune).await {
-            METRICS.pruning_chunk_duration[&PruneType::NoOp].observe(start.elapsed());
-            return Ok(false);
+            // The chunk is incomplete, but its start may be beyond the retention period.
+            let Some(expired_l1_batch) = self
+                .find_last_expired_l1_batch(last_soft_pruned_l1_batch)
+                .await
+            else {
+                METRICS.pruning_chunk_duration[&PruneType::NoOp].observe(start.elapsed());
+                return Ok(false);
+            };
+            tracing::info!(
+                "L1 batches up to #{expired_l1_batch} are beyond the retention period; pruning them ignoring chunk size"
+            );
+            METRICS.expired_l1_batch_prunes.inc();
+            next_l1_batch_to_prune = expired_l1_batch;
         }
 
         let (_, next_l2_block_to_prune) = transaction
@@ -232,6 +401,21 @@ impl DbPruner {
         stop_receiver: &mut watch::Receiver<bool>,
     ) -> anyhow::Result<PruningIterationOutcome> {
         let latency = METRICS.pruning_chunk_duration[&PruneType::Hard].start();
+
+        // Partitions containing only pruned events can be dropped wholesale, which is much cheaper than deleting rows
+        // and doesn't leave any work for vacuuming. This is done outside the pruning transaction, so that the lock
+        // on `events` required to detach partitions is only held briefly. Storage logs cannot be pruned this way
+        // since the pruned range may contain latest values for some storage slots.
+        let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        let dropped_event_partitions = if let Some(soft_pruned) = pruning_info.last_soft_pruned {
+            storage
+                .partitions_dal()
+                .drop_partitions_before(PartitionedTable::Events, soft_pruned.l2_block + 1)
+                .await?
+        } else {
+            vec![]
+        };
+
         let mut transaction = storage.start_transaction().await?;
 
         let mut current_pruning_info = transaction.pruning_dal().get_pruning_info().await?;
@@ -251,7 +435,7 @@ impl DbPruner {
             })?;
 
         let mut dal = transaction.pruning_dal();
-        let stats = tokio::select! {
+        let mut stats = tokio::select! {
             result = dal.hard_prune_batches_range(
                 soft_pruned.l1_batch,
                 soft_pruned.l2_block,
@@ -265,6 +449,7 @@ impl DbPruner {
                 return Ok(PruningIterationOutcome::Interrupted);
             }
         };
+        stats.dropped_event_partitions = dropped_event_partitions.len() as u64;
         METRICS.observe_hard_pruning(stats);
 
         dal.insert_hard_pruning_log(
@@ -294,6 +479,11 @@ impl DbPruner {
         let mut storage = self.connection_pool.connection_tagged("db_pruner").await?;
         let current_pruning_info = storage.pruning_dal().get_pruning_info().await?;
         self.update_health(current_pruning_info);
+        if self.config.dry_run {
+            drop(storage);
+            self.estimate_pruning().await?;
+            return Ok(PruningIterationOutcome::NoOp);
+        }
 
         // If this `if` is not entered, it means that the node has restarted after soft pruning
         if current_pruning_info.is_caught_up() {
@@ -325,6 +515,9 @@ impl DbPruner {
                 .map(ToString::to_string)
                 .collect::<Vec<_>>()
         );
+        if self.config.dry_run {
+            tracing::warn!("Postgres pruning runs in the dry-run mode; no data will be removed");
+        }
 
         while !*stop_receiver.borrow_and_update() {
             if let Err(err) = self.update_l1_batches_metric().await {
@@ -3,7 +3,7 @@ use std::time::Duration;
 use vise::{
     Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit,
 };
-use zksync_dal::pruning_dal::HardPruningStats;
+use zksync_dal::pruning_dal::{HardPruningEstimate, HardPruningStats};
 
 use crate::prune_conditions::PruneCondition;
 
@@ -24,6 +24,7 @@ enum PrunedEntityType {
     Event,
     L2ToL1Log,
     CallTrace,
+    EventPartition,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
@@ -53,11 +54,17 @@ pub(super) struct DbPrunerMetrics {
     pub pruning_chunk_duration: Family<PruneType, Histogram<Duration>>,
     /// Number of not-pruned L1 batches.
     pub not_pruned_l1_batches_count: Gauge<u64>,
+    /// Number of soft pruning iterations pruning an incomplete chunk of L1 batches because they are beyond
+    /// the retention period.
+    pub expired_l1_batch_prunes: Counter,
     /// Number of entities deleted during a single hard pruning iteration, grouped by entity type.
     #[metrics(buckets = ENTITY_COUNT_BUCKETS)]
     deleted_entities: Family<PrunedEntityType, Histogram<u64>>,
     /// Number of times a certain condition has resulted in a specific outcome (succeeded, failed, or errored).
     condition_outcomes: Family<ConditionOutcomeLabels, Counter>,
+    /// Estimated number of entities that can be pruned given the current prune conditions, grouped by entity type.
+    /// Only reported in the dry-run mode.
+    estimated_prunable_entities: Family<PrunedEntityType, Gauge<u64>>,
 }
 
 impl DbPrunerMetrics {
@@ -69,11 +76,13 @@ impl DbPrunerMetrics {
             deleted_events,
             deleted_call_traces,
             deleted_l2_to_l1_logs,
+            dropped_event_partitions,
         } = stats;
         tracing::info!(
             "Performed pruning of database, deleted {deleted_l1_batches} L1 batches, {deleted_l2_blocks} L2 blocks, \
              {deleted_storage_logs} storage logs, \
-             {deleted_events} events, {deleted_call_traces} call traces, {deleted_l2_to_l1_logs} L2-to-L1 logs"
+             {deleted_events} events, {deleted_call_traces} call traces, {deleted_l2_to_l1_logs} L2-to-L1 logs, \
+             dropped {dropped_event_partitions} event partitions"
         );
 
         self.deleted_entities[&PrunedEntityType::L1Batch].observe(deleted_l1_batches);
@@ -82,6 +91,25 @@ impl DbPrunerMetrics {
         self.deleted_entities[&PrunedEntityType::Event].observe(deleted_events);
         self.deleted_entities[&PrunedEntityType::L2ToL1Log].observe(deleted_l2_to_l1_logs);
         self.deleted_entities[&PrunedEntityType::CallTrace].observe(deleted_call_traces);
+        self.deleted_entities[&PrunedEntityType::EventPartition].observe(dropped_event_partitions);
+    }
+
+    pub fn observe_pruning_estimate(
+        &self,
+        l1_batches: u64,
+        l2_blocks: u64,
+        estimate: HardPruningEstimate,
+    ) {
+        let HardPruningEstimate {
+            storage_logs,
+            events,
+            call_traces,
+        } = estimate;
+        self.estimated_prunable_entities[&PrunedEntityType::L1Batch].set(l1_batches);
+        self.estimated_prunable_entities[&PrunedEntityType::L2Block].set(l2_blocks);
+        self.estimated_prunable_entities[&PrunedEntityType::StorageLog].set(storage_logs);
+        self.estimated_prunable_entities[&PrunedEntityType::Event].set(events);
+        self.estimated_prunable_entities[&PrunedEntityType::CallTrace].set(call_traces);
     }
 
     pub fn observe_condition(&self, condition: &dyn PruneCondition, outcome: ConditionOutcome) {
@@ -145,6 +145,38 @@ impl PruneCondition for L1BatchExistsCondition {
     }
 }
 
+/// Interlock with snapshot generation. Pruning an L1 batch following the snapshot L1 batch may remove storage logs
+/// required to create the snapshot, so such batches are not prunable until the snapshot is complete.
+#[derive(Debug)]
+pub(super) struct NoIncompleteSnapshotsCondition {
+    pub pool: ConnectionPool<Core>,
+}
+
+impl fmt::Display for NoIncompleteSnapshotsCondition {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("L1 batch doesn't follow incomplete snapshots")
+    }
+}
+
+#[async_trait]
+impl PruneCondition for NoIncompleteSnapshotsCondition {
+    fn metric_label(&self) -> &'static str {
+        "no_incomplete_snapshots"
+    }
+
+    async fn is_batch_prunable(&self, l1_batch_number: L1BatchNumber) -> anyhow::Result<bool> {
+        let mut storage = self.pool.connection_tagged("db_pruner").await?;
+        let oldest_incomplete_snapshot = storage
+            .snapshots_dal()
+            .get_oldest_incomplete_snapshot_l1_batch()
+            .await?;
+        let is_prunable = oldest_incomplete_snapshot.map_or(true, |snapshot_l1_batch| {
+            l1_batch_number <= snapshot_l1_batch
+        });
+        Ok(is_prunable)
+    }
+}
+
 #[derive(Debug)]
 pub(super) struct ConsistencyCheckerProcessedBatch {
     pub pool: ConnectionPool<Core>,
@@ -12,7 +12,8 @@ use zksync_node_test_utils::{
     l1_batch_metadata_to_commitment_artifacts,
 };
 use zksync_types::{
-    aggregated_operations::AggregatedActionType, L2BlockNumber, ProtocolVersion, H256,
+    aggregated_operations::AggregatedActionType, snapshots::SnapshotVersion, L2BlockNumber,
+    ProtocolVersion, H256,
 };
 
 use super::*;
@@ -78,6 +79,9 @@ async fn is_l1_batch_prunable_works() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 1,
             minimum_l1_batch_age: Duration::ZERO,
+            maximum_l1_batch_age: None,
+            require_consistency_check: true,
+            dry_run: false,
         },
         ConnectionPool::test_pool().await,
         vec![failing_check, other_failing_check],
@@ -148,6 +152,9 @@ async fn hard_pruning_ignores_conditions_checks() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 5,
             minimum_l1_batch_age: Duration::ZERO,
+            maximum_l1_batch_age: None,
+            require_consistency_check: true,
+            dry_run: false,
         },
         pool.clone(),
         vec![nothing_prunable_check],
@@ -197,6 +204,9 @@ async fn pruner_catches_up_with_hard_pruning_up_to_soft_pruning_boundary_ignorin
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 5,
             minimum_l1_batch_age: Duration::ZERO,
+            maximum_l1_batch_age: None,
+            require_consistency_check: true,
+            dry_run: false,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -235,6 +245,9 @@ async fn unconstrained_pruner_with_fresh_database() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            maximum_l1_batch_age: None,
+            require_consistency_check: true,
+            dry_run: false,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -275,6 +288,9 @@ async fn pruning_blocked_after_first_chunk() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            maximum_l1_batch_age: None,
+            require_consistency_check: true,
+            dry_run: false,
         },
         pool.clone(),
         vec![first_chunk_prunable_check],
@@ -302,6 +318,71 @@ async fn pruning_blocked_after_first_chunk() {
     );
 }
 
+#[test(tokio::test)]
+async fn pruning_expired_l1_batches_ignoring_chunk_size() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    insert_l2_blocks(&mut conn, 10, 2).await;
+
+    let mut prunable_check = ConditionMock::name("first 7 batches prunable");
+    for number in 1..=7 {
+        prunable_check = prunable_check.with_response(L1BatchNumber(number), true);
+    }
+    let mut expired_check = ConditionMock::name("first 6 batches expired");
+    for number in 1..=9 {
+        expired_check = expired_check.with_response(L1BatchNumber(number), number <= 6);
+    }
+
+    let mut pruner = DbPruner::with_conditions(
+        DbPrunerConfig {
+            removal_delay: Duration::ZERO,
+            pruned_batch_chunk_size: 5,
+            minimum_l1_batch_age: Duration::ZERO,
+            maximum_l1_batch_age: Some(Duration::from_secs(1)),
+            require_consistency_check: true,
+            dry_run: false,
+        },
+        pool.clone(),
+        vec![Arc::new(prunable_check)],
+    );
+    pruner.max_age_condition = Some(Arc::new(expired_check));
+
+    let (_stop_sender, mut stop_receiver) = watch::channel(false);
+    pruner
+        .run_single_iteration(&mut stop_receiver)
+        .await
+        .unwrap();
+    assert_eq!(
+        test_pruning_info(5, 11),
+        conn.pruning_dal().get_pruning_info().await.unwrap()
+    );
+
+    // The next chunk is not prunable, but L1 batch #6 is expired.
+    assert_eq!(
+        pruner.find_last_prunable_l1_batch().await.unwrap(),
+        Some(L1BatchNumber(6))
+    );
+    pruner
+        .run_single_iteration(&mut stop_receiver)
+        .await
+        .unwrap();
+    assert_eq!(
+        test_pruning_info(6, 13),
+        conn.pruning_dal().get_pruning_info().await.unwrap()
+    );
+
+    // L1 batch #7 is prunable, but not expired.
+    let outcome = pruner
+        .run_single_iteration(&mut stop_receiver)
+        .await
+        .unwrap();
+    assert_matches!(outcome, PruningIterationOutcome::NoOp);
+    assert_eq!(
+        test_pruning_info(6, 13),
+        conn.pruning_dal().get_pruning_info().await.unwrap()
+    );
+}
+
 #[tokio::test]
 async fn pruner_is_resistant_to_errors() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -315,6 +396,9 @@ async fn pruner_is_resistant_to_errors() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            maximum_l1_batch_age: None,
+            require_consistency_check: true,
+            dry_run: false,
         },
         pool.clone(),
         vec![erroneous_condition],
@@ -473,6 +557,34 @@ async fn real_conditions_work_as_expected() {
     );
 }
 
+#[tokio::test]
+async fn snapshot_interlock_condition() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    let condition = NoIncompleteSnapshotsCondition { pool: pool.clone() };
+    assert!(condition.is_batch_prunable(L1BatchNumber(5)).await.unwrap());
+
+    storage
+        .snapshots_dal()
+        .add_snapshot(
+            SnapshotVersion::Version1,
+            L1BatchNumber(3),
+            1,
+            "factory_deps",
+        )
+        .await
+        .unwrap();
+    assert!(condition.is_batch_prunable(L1BatchNumber(3)).await.unwrap());
+    assert!(!condition.is_batch_prunable(L1BatchNumber(4)).await.unwrap());
+
+    storage
+        .snapshots_dal()
+        .add_storage_logs_filepath_for_snapshot(L1BatchNumber(3), 0, "chunk", H256::zero())
+        .await
+        .unwrap();
+    assert!(condition.is_batch_prunable(L1BatchNumber(5)).await.unwrap());
+}
+
 #[tokio::test]
 async fn pruner_with_real_conditions() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -485,6 +597,9 @@ async fn pruner_with_real_conditions() {
         removal_delay: Duration::from_millis(10), // non-zero to not have a tight loop in `DbPruner::run()`
         pruned_batch_chunk_size: 1,
         minimum_l1_batch_age: Duration::ZERO,
+        maximum_l1_batch_age: None,
+        require_consistency_check: true,
+        dry_run: false,
     };
     let pruner = DbPruner::new(config, pool.clone());
     let mut health_check = pruner.health_check();
@@ -530,6 +645,44 @@ async fn pruner_with_real_conditions() {
     pruner_handle.await.unwrap().unwrap();
 }
 
+#[test(tokio::test)]
+async fn dry_run_pruner_does_not_prune_data() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    insert_l2_blocks(&mut conn, 10, 2).await;
+
+    let condition = ConditionMock::name("first two chunks prunable")
+        .with_response(L1BatchNumber(3), true)
+        .with_response(L1BatchNumber(6), true)
+        .with_response(L1BatchNumber(9), false);
+    let pruner = DbPruner::with_conditions(
+        DbPrunerConfig {
+            removal_delay: Duration::ZERO,
+            pruned_batch_chunk_size: 3,
+            minimum_l1_batch_age: Duration::ZERO,
+            maximum_l1_batch_age: None,
+            require_consistency_check: true,
+            dry_run: true,
+        },
+        pool.clone(),
+        vec![Arc::new(condition)],
+    );
+
+    let last_prunable_l1_batch = pruner.find_last_prunable_l1_batch().await.unwrap();
+    assert_eq!(last_prunable_l1_batch, Some(L1BatchNumber(6)));
+
+    let (_stop_sender, mut stop_receiver) = watch::channel(false);
+    let outcome = pruner
+        .run_single_iteration(&mut stop_receiver)
+        .await
+        .unwrap();
+    assert_matches!(outcome, PruningIterationOutcome::NoOp);
+    assert_eq!(
+        conn.pruning_dal().get_pruning_info().await.unwrap(),
+        PruningInfo::default()
+    );
+}
+
 #[tokio::test]
 async fn pruning_iteration_timely_shuts_down() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -541,6 +694,9 @@ async fn pruning_iteration_timely_shuts_down() {
             removal_delay: Duration::MAX, // intentionally chosen so that pruning iterations stuck
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            maximum_l1_batch_age: None,
+            require_consistency_check: true,
+            dry_run: false,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -570,6 +726,9 @@ async fn pruner_timely_shuts_down() {
             removal_delay: Duration::MAX, // intentionally chosen so that pruning iterations stuck
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            maximum_l1_batch_age: None,
+            require_consistency_check: true,
+            dry_run: false,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
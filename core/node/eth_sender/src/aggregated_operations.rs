@@ -2,17 +2,22 @@ use std::ops;
 
 use zksync_l1_contract_interface::i_executor::methods::{ExecuteBatches, ProveBatches};
 use zksync_types::{
-    aggregated_operations::AggregatedActionType, commitment::L1BatchWithMetadata,
-    pubdata_da::PubdataSendingMode, L1BatchNumber, ProtocolVersionId,
+    aggregated_operations::AggregatedActionType,
+    commitment::{L1BatchCommitmentMode, L1BatchWithMetadata},
+    pubdata_da::PubdataSendingMode,
+    L1BatchNumber, ProtocolVersionId,
 };
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
 pub enum AggregatedOperation {
+    /// Commits L1 batches after the specified last committed batch. All committed batches share
+    /// the specified commitment mode.
     Commit(
         L1BatchWithMetadata,
         Vec<L1BatchWithMetadata>,
         PubdataSendingMode,
+        L1BatchCommitmentMode,
     ),
     PublishProofOnchain(ProveBatches),
     Execute(ExecuteBatches),
@@ -29,7 +34,7 @@ impl AggregatedOperation {
 
     pub fn l1_batch_range(&self) -> ops::RangeInclusive<L1BatchNumber> {
         let batches = match self {
-            Self::Commit(_, l1_batches, _) => l1_batches,
+            Self::Commit(_, l1_batches, ..) => l1_batches,
             Self::PublishProofOnchain(op) => &op.l1_batches,
             Self::Execute(op) => &op.l1_batches,
         };
@@ -52,7 +57,7 @@ impl AggregatedOperation {
 
     pub fn protocol_version(&self) -> ProtocolVersionId {
         match self {
-            Self::Commit(_, l1_batches, _) => l1_batches[0].header.protocol_version.unwrap(),
+            Self::Commit(_, l1_batches, ..) => l1_batches[0].header.protocol_version.unwrap(),
             Self::PublishProofOnchain(op) => op.l1_batches[0].header.protocol_version.unwrap(),
             Self::Execute(op) => op.l1_batches[0].header.protocol_version.unwrap(),
         }
@@ -215,14 +215,21 @@ impl Aggregator {
         base_system_contracts_hashes: BaseSystemContractsHashes,
         protocol_version_id: ProtocolVersionId,
     ) -> Option<AggregatedOperation> {
-        let mut blocks_dal = storage.blocks_dal();
-        let last_committed_l1_batch = blocks_dal
+        let last_committed_l1_batch = storage
+            .blocks_dal()
             .get_last_committed_to_eth_l1_batch()
             .await
             .unwrap()?;
 
+        // Batches are committed using the commitment mode persisted when they were sealed, so that batches sealed
+        // before and after the chain switches between rollup and validium modes are committed correctly.
+        let first_l1_batch_to_commit = last_committed_l1_batch.header.number + 1;
+        let commitment_mode = self
+            .l1_batch_commitment_mode(storage, first_l1_batch_to_commit)
+            .await;
         let ready_for_commit_l1_batches = if protocol_version_id.is_pre_boojum() {
-            blocks_dal
+            storage
+                .blocks_dal()
                 .pre_boojum_get_ready_for_commit_l1_batches(
                     limit,
                     base_system_contracts_hashes.bootloader,
@@ -232,18 +239,32 @@ impl Aggregator {
                 .await
                 .unwrap()
         } else {
-            blocks_dal
+            storage
+                .blocks_dal()
                 .get_ready_for_commit_l1_batches(
                     limit,
                     base_system_contracts_hashes.bootloader,
                     base_system_contracts_hashes.default_aa,
                     protocol_version_id,
-                    self.commitment_mode != L1BatchCommitmentMode::Rollup,
+                    commitment_mode != L1BatchCommitmentMode::Rollup,
                 )
                 .await
                 .unwrap()
         };
 
+        // A single commit operation can only include batches with the same commitment mode.
+        let mut same_mode_l1_batches = Vec::with_capacity(ready_for_commit_l1_batches.len());
+        for batch in ready_for_commit_l1_batches {
+            let batch_mode = self
+                .l1_batch_commitment_mode(storage, batch.header.number)
+                .await;
+            if batch_mode != commitment_mode {
+                break;
+            }
+            same_mode_l1_batches.push(batch);
+        }
+        let ready_for_commit_l1_batches = same_mode_l1_batches;
+
         // Check that the L1 batches that are selected are sequential
         ready_for_commit_l1_batches
             .iter()
@@ -264,10 +285,29 @@ impl Aggregator {
         .await;
 
         batches.map(|batches| {
-            AggregatedOperation::Commit(last_committed_l1_batch, batches, self.pubdata_da)
+            AggregatedOperation::Commit(
+                last_committed_l1_batch,
+                batches,
+                self.pubdata_da,
+                commitment_mode,
+            )
         })
     }
 
+    /// Returns the commitment mode persisted for the L1 batch, falling back to the configured mode.
+    async fn l1_batch_commitment_mode(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> L1BatchCommitmentMode {
+        storage
+            .blocks_dal()
+            .get_l1_batch_commitment_mode(l1_batch_number)
+            .await
+            .unwrap()
+            .unwrap_or(self.commitment_mode)
+    }
+
     async fn load_dummy_proof_operations(
         storage: &mut Connection<'_, Core>,
         is_4844_mode: bool,
@@ -500,6 +540,8 @@ impl Aggregator {
         self.pubdata_da
     }
 
+    /// Returns the configured commitment mode. It is used for L1 batches that don't have a commitment mode
+    /// persisted in Postgres.
     pub fn mode(&self) -> L1BatchCommitmentMode {
         self.commitment_mode
     }
@@ -9,6 +9,8 @@ pub enum EthSenderError {
     ContractCall(#[from] ContractCallError),
     #[error("Token parsing error: {0}")]
     Parse(#[from] contract::Error),
+    #[error("Leader lease `{0}` is not held by this node")]
+    LeaseNotHeld(String),
 }
 
 impl EthSenderError {
@@ -1,12 +1,18 @@
+use std::sync::Arc;
+
 use tokio::sync::watch;
+use tracing::Instrument as _;
 use zksync_config::configs::eth_sender::SenderConfig;
 use zksync_contracts::BaseSystemContractsHashes;
-use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_dal::{node_leases_dal::NodeLease, Connection, ConnectionPool, Core, CoreDal};
 use zksync_eth_client::{BoundEthInterface, CallFunctionArgs};
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_l1_contract_interface::{
     i_executor::{
-        commit::kzg::{KzgInfo, ZK_SYNC_BYTES_PER_BLOB},
+        commit::{
+            data_generator::L1BatchCommitDataGenerator,
+            kzg::{KzgInfo, ZK_SYNC_BYTES_PER_BLOB},
+        },
         methods::CommitBatches,
     },
     multicall3::{Multicall3Call, Multicall3Result},
@@ -25,6 +31,7 @@ use zksync_types::{
     web3::{contract::Error as Web3ContractError, BlockNumber},
     Address, L2ChainId, ProtocolVersionId, SLChainId, H256, U256,
 };
+use zksync_vlog::opentelemetry::{l1_batch_range_span, L1BatchStage};
 
 use super::aggregated_operations::AggregatedOperation;
 use crate::{
@@ -67,6 +74,10 @@ pub struct EthTxAggregator {
     pool: ConnectionPool<Core>,
     settlement_mode: SettlementMode,
     sl_chain_id: SLChainId,
+    /// Generator of commit data for L1 batches. Must match the one used by the consistency checker.
+    commit_data_generator: Option<Arc<dyn L1BatchCommitDataGenerator>>,
+    /// If set, eth_txs are only saved while this lease is held by the node.
+    lease_fence: Option<NodeLease>,
     health_updater: HealthUpdater,
 }
 
@@ -122,10 +133,29 @@ impl EthTxAggregator {
             pool,
             settlement_mode,
             sl_chain_id,
+            commit_data_generator: None,
+            lease_fence: None,
             health_updater: ReactiveHealthCheck::new("eth_tx_aggregator").1,
         }
     }
 
+    /// Overrides the generator of L1 batch commit data. By default, the generator is chosen based
+    /// on the commitment mode of the committed L1 batches.
+    pub fn with_commit_data_generator(
+        mut self,
+        generator: Arc<dyn L1BatchCommitDataGenerator>,
+    ) -> Self {
+        self.commit_data_generator = Some(generator);
+        self
+    }
+
+    /// Makes saving eth_txs conditional on the specified lease being held by this node. This prevents
+    /// assigning the same nonce to different eth_txs by multiple main node instances sharing the database.
+    pub fn with_lease_fence(mut self, lease: NodeLease) -> Self {
+        self.lease_fence = Some(lease);
+        self
+    }
+
     pub async fn run(mut self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         self.health_updater
             .update(Health::from(HealthStatus::Ready));
@@ -428,7 +458,11 @@ impl EthTxAggregator {
                 return Ok(());
             }
             let is_gateway = self.settlement_mode.is_gateway();
-            let tx = self.save_eth_tx(storage, &agg_op, is_gateway).await?;
+            let span = Self::l1_batch_lifecycle_span(&agg_op);
+            let tx = self
+                .save_eth_tx(storage, &agg_op, is_gateway)
+                .instrument(span)
+                .await?;
             Self::report_eth_tx_saving(storage, &agg_op, &tx).await;
 
             self.health_updater.update(
@@ -441,6 +475,16 @@ impl EthTxAggregator {
         Ok(())
     }
 
+    fn l1_batch_lifecycle_span(aggregated_op: &AggregatedOperation) -> tracing::Span {
+        let stage = match aggregated_op.get_action_type() {
+            AggregatedActionType::Commit => L1BatchStage::CommitTxCreated,
+            AggregatedActionType::PublishProofOnchain => L1BatchStage::ProveTxCreated,
+            AggregatedActionType::Execute => L1BatchStage::ExecuteTxCreated,
+        };
+        let range = aggregated_op.l1_batch_range();
+        l1_batch_range_span(stage, range.start().0..=range.end().0)
+    }
+
     async fn report_eth_tx_saving(
         storage: &mut Connection<'_, Core>,
         aggregated_op: &AggregatedOperation,
@@ -453,7 +497,7 @@ impl EthTxAggregator {
             aggregated_op.get_action_caption()
         );
 
-        if let AggregatedOperation::Commit(_, l1_batches, _) = aggregated_op {
+        if let AggregatedOperation::Commit(_, l1_batches, ..) = aggregated_op {
             for batch in l1_batches {
                 METRICS.pubdata_size[&PubdataKind::StateDiffs]
                     .observe(batch.metadata.state_diffs_compressed.len());
@@ -479,12 +523,21 @@ impl EthTxAggregator {
         let is_op_pre_gateway = op.protocol_version().is_pre_gateway();
 
         let (calldata, sidecar) = match op {
-            AggregatedOperation::Commit(last_committed_l1_batch, l1_batches, pubdata_da) => {
+            AggregatedOperation::Commit(
+                last_committed_l1_batch,
+                l1_batches,
+                pubdata_da,
+                commitment_mode,
+            ) => {
+                let commit_data_generator =
+                    self.commit_data_generator.clone().unwrap_or_else(|| {
+                        <dyn L1BatchCommitDataGenerator>::for_mode(*commitment_mode)
+                    });
                 let commit_batches = CommitBatches {
                     last_committed_l1_batch,
                     l1_batches,
                     pubdata_da: *pubdata_da,
-                    mode: self.aggregator.mode(),
+                    data_generator: commit_data_generator.as_ref(),
                 };
                 let commit_data_base = commit_batches.into_tokens();
 
@@ -577,6 +630,17 @@ impl EthTxAggregator {
         is_gateway: bool,
     ) -> Result<EthTx, EthSenderError> {
         let mut transaction = storage.start_transaction().await.unwrap();
+        if let Some(lease) = &self.lease_fence {
+            // Locks the lease until the transaction is committed.
+            let is_held = transaction
+                .node_leases_dal()
+                .check_lease(lease)
+                .await
+                .unwrap();
+            if !is_held {
+                return Err(EthSenderError::LeaseNotHeld(lease.name.clone()));
+            }
+        }
         let op_type = aggregated_op.get_action_type();
         // We may be using a custom sender for commit transactions, so use this
         // var whatever it actually is: a `None` for single-addr operator or `Some`
@@ -589,7 +653,11 @@ impl EthTxAggregator {
         let encoded_aggregated_op = self.encode_aggregated_op(aggregated_op);
         let l1_batch_number_range = aggregated_op.l1_batch_range();
 
-        let eth_tx_predicted_gas = match (op_type, is_gateway, self.aggregator.mode()) {
+        let commitment_mode = match aggregated_op {
+            AggregatedOperation::Commit(.., commitment_mode) => *commitment_mode,
+            _ => self.aggregator.mode(),
+        };
+        let eth_tx_predicted_gas = match (op_type, is_gateway, commitment_mode) {
             (AggregatedActionType::Execute, false, _) => Some(
                 L1GasCriterion::total_execute_gas_amount(
                     &mut transaction,
@@ -4,15 +4,20 @@ use std::{
 };
 
 use tokio::sync::watch;
+use tracing::Instrument as _;
 use zksync_config::configs::eth_sender::SenderConfig;
-use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_dal::{node_leases_dal::NodeLease, Connection, ConnectionPool, Core, CoreDal};
 use zksync_eth_client::{
     encode_blob_tx_with_sidecar, BoundEthInterface, ExecutedTxStatus, RawTransactionBytes,
 };
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_node_fee_model::l1_gas_price::TxParamsProvider;
 use zksync_shared_metrics::BlockL1Stage;
-use zksync_types::{eth_sender::EthTx, Address, L1BlockNumber, H256, U256};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType, eth_sender::EthTx, Address, L1BlockNumber, H256,
+    U256,
+};
+use zksync_vlog::opentelemetry::{l1_batch_range_span, L1BatchStage};
 
 use super::{metrics::METRICS, EthSenderError};
 use crate::{
@@ -35,6 +40,8 @@ pub struct EthTxManager {
     config: SenderConfig,
     fees_oracle: Box<dyn EthFeesOracle>,
     pool: ConnectionPool<Core>,
+    /// If set, transactions are only sent while this lease is held by the node.
+    lease_fence: Option<NodeLease>,
     health_updater: HealthUpdater,
 }
 
@@ -70,10 +77,18 @@ impl EthTxManager {
             config,
             fees_oracle: Box::new(fees_oracle),
             pool,
+            lease_fence: None,
             health_updater: ReactiveHealthCheck::new("eth_tx_manager").1,
         }
     }
 
+    /// Makes sending transactions conditional on the specified lease being held by this node. This prevents
+    /// sending conflicting transactions by multiple main node instances sharing the database.
+    pub fn with_lease_fence(mut self, lease: NodeLease) -> Self {
+        self.lease_fence = Some(lease);
+        self
+    }
+
     #[cfg(test)]
     pub(crate) fn l1_interface(&self) -> &dyn AbstractL1Interface {
         self.l1_interface.as_ref()
@@ -210,7 +225,21 @@ impl EthTxManager {
             ));
         }
 
-        if let Some(tx_history_id) = storage
+        // The transaction is recorded and sent in a single DB transaction, so that the lease (if any) cannot be
+        // taken over in between; otherwise, the new leader could send a conflicting transaction with the same nonce.
+        let mut transaction = storage.start_transaction().await.unwrap();
+        if let Some(lease) = &self.lease_fence {
+            let is_held = transaction
+                .node_leases_dal()
+                .check_lease(lease)
+                .await
+                .unwrap();
+            if !is_held {
+                return Err(EthSenderError::LeaseNotHeld(lease.name.clone()));
+            }
+        }
+
+        if let Some(tx_history_id) = transaction
             .eth_sender_dal()
             .insert_tx_history(
                 tx.id,
@@ -225,7 +254,12 @@ impl EthTxManager {
             .unwrap()
         {
             if let Err(error) = self
-                .send_raw_transaction(storage, tx_history_id, signed_tx.raw_tx, operator_type)
+                .send_raw_transaction(
+                    &mut transaction,
+                    tx_history_id,
+                    signed_tx.raw_tx,
+                    operator_type,
+                )
                 .await
             {
                 tracing::warn!(
@@ -239,6 +273,7 @@ impl EthTxManager {
                 );
             }
         }
+        transaction.commit().await.unwrap();
         Ok(signed_tx.hash)
     }
 
@@ -483,6 +518,45 @@ impl EthTxManager {
         storage: &mut Connection<'_, Core>,
         tx: &EthTx,
         tx_status: ExecutedTxStatus,
+    ) {
+        let span = Self::l1_batch_lifecycle_span(storage, tx).await;
+        self.save_confirmed_tx(storage, tx, tx_status)
+            .instrument(span)
+            .await;
+    }
+
+    /// Returns a span for confirming `tx` in the lifecycle traces of L1 batches covered by it.
+    async fn l1_batch_lifecycle_span(
+        storage: &mut Connection<'_, Core>,
+        tx: &EthTx,
+    ) -> tracing::Span {
+        let stage = match tx.tx_type {
+            AggregatedActionType::Commit => L1BatchStage::CommitTxConfirmed,
+            AggregatedActionType::PublishProofOnchain => L1BatchStage::ProveTxConfirmed,
+            AggregatedActionType::Execute => L1BatchStage::ExecuteTxConfirmed,
+        };
+        let l1_batch_numbers: Vec<_> = storage
+            .blocks_dal()
+            .get_l1_batches_statistics_for_eth_tx_id(tx.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|statistics| statistics.number.0)
+            .collect();
+        let (Some(&first), Some(&last)) =
+            (l1_batch_numbers.iter().min(), l1_batch_numbers.iter().max())
+        else {
+            // This should be only the case when some batches were reverted.
+            return tracing::Span::none();
+        };
+        l1_batch_range_span(stage, first..=last)
+    }
+
+    async fn save_confirmed_tx(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        tx: &EthTx,
+        tx_status: ExecutedTxStatus,
     ) {
         let tx_hash = tx_status.receipt.transaction_hash;
         let gas_used = tx_status
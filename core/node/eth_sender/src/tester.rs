@@ -118,6 +118,7 @@ pub(crate) struct EthSenderTester {
     pub aggregator: EthTxAggregator,
     pub gas_adjuster: Arc<GasAdjuster>,
     pub pubdata_sending_mode: PubdataSendingMode,
+    commitment_mode: L1BatchCommitmentMode,
     next_l1_batch_number_to_seal: L1BatchNumber,
     next_l1_batch_number_to_commit: L1BatchNumber,
     next_l1_batch_number_to_prove: L1BatchNumber,
@@ -296,6 +297,7 @@ impl EthSenderTester {
             gas_adjuster,
             conn: connection_pool,
             pubdata_sending_mode,
+            commitment_mode,
             next_l1_batch_number_to_seal: L1BatchNumber(0),
             next_l1_batch_number_to_commit: L1BatchNumber(1),
             next_l1_batch_number_to_execute: L1BatchNumber(1),
@@ -500,6 +502,7 @@ impl EthSenderTester {
                     .await,
             )],
             pubdata_mode,
+            self.commitment_mode,
         );
         self.next_l1_batch_number_to_commit += 1;
         self.save_operation(operation).await
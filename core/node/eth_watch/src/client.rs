@@ -35,12 +35,25 @@ pub trait EthClient: 'static + fmt::Debug + Send + Sync {
         retries_left: usize,
     ) -> EnrichedClientResult<Vec<Log>>;
 
+    /// Returns events with any of `topics1` emitted by any of the `contracts` in a given block range.
+    async fn get_contract_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+        contracts: Vec<Address>,
+        topics1: Vec<H256>,
+        retries_left: usize,
+    ) -> EnrichedClientResult<Vec<Log>>;
+
     /// Returns either finalized L1 block number or block number that satisfies `self.confirmations_for_eth_event` if it's set.
     async fn confirmed_block_number(&self) -> EnrichedClientResult<u64>;
 
     /// Returns finalized L1 block number.
     async fn finalized_block_number(&self) -> EnrichedClientResult<u64>;
 
+    /// Returns the hash of the block with the specified number in the canonical chain, or `None` if the block is missing.
+    async fn block_hash(&self, block_number: u64) -> EnrichedClientResult<Option<H256>>;
+
     async fn get_total_priority_txs(&self) -> Result<u64, ContractCallError>;
     /// Returns scheduler verification key hash by verifier address.
     async fn scheduler_vk_hash(&self, verifier_address: Address)
@@ -304,6 +317,18 @@ where
         .await
     }
 
+    async fn get_contract_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+        contracts: Vec<Address>,
+        topics1: Vec<H256>,
+        retries_left: usize,
+    ) -> EnrichedClientResult<Vec<Log>> {
+        self.get_events_inner(from, to, Some(topics1), None, Some(contracts), retries_left)
+            .await
+    }
+
     async fn confirmed_block_number(&self) -> EnrichedClientResult<u64> {
         if let Some(confirmations) = self.confirmations_for_eth_event {
             let latest_block_number = self.client.block_number().await?.as_u64();
@@ -329,6 +354,14 @@ where
         Ok(block_number.as_u64())
     }
 
+    async fn block_hash(&self, block_number: u64) -> EnrichedClientResult<Option<H256>> {
+        let block = self
+            .client
+            .block(BlockId::Number(BlockNumber::Number(block_number.into())))
+            .await?;
+        Ok(block.and_then(|block| block.hash))
+    }
+
     async fn get_total_priority_txs(&self) -> Result<u64, ContractCallError> {
         CallFunctionArgs::new("getTotalPriorityTxs", ())
             .for_contract(self.diamond_proxy_addr, &self.getters_facet_contract_abi)
@@ -454,6 +487,19 @@ impl EthClient for L2EthClientW {
             .await
     }
 
+    async fn get_contract_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+        contracts: Vec<Address>,
+        topics1: Vec<H256>,
+        retries_left: usize,
+    ) -> EnrichedClientResult<Vec<Log>> {
+        self.0
+            .get_contract_events(from, to, contracts, topics1, retries_left)
+            .await
+    }
+
     async fn confirmed_block_number(&self) -> EnrichedClientResult<u64> {
         self.0.confirmed_block_number().await
     }
@@ -462,6 +508,10 @@ impl EthClient for L2EthClientW {
         self.0.finalized_block_number().await
     }
 
+    async fn block_hash(&self, block_number: u64) -> EnrichedClientResult<Option<H256>> {
+        self.0.block_hash(block_number).await
+    }
+
     async fn get_total_priority_txs(&self) -> Result<u64, ContractCallError> {
         self.0.get_total_priority_txs().await
     }
@@ -15,7 +15,7 @@ use zksync_types::{
 
 use crate::{
     client::L2EthClient,
-    event_processors::{EventProcessor, EventProcessorError, EventsSource},
+    event_processors::{EventFilter, EventProcessor, EventProcessorError, EventsSource},
 };
 
 /// Listens to `AppendedChainBatchRoot` events and saves `BatchAndChainMerklePath` for batches.
@@ -181,12 +181,11 @@ impl EventProcessor for BatchRootProcessor {
         Ok(events_count)
     }
 
-    fn topic1(&self) -> H256 {
-        self.appended_chain_batch_root_signature
-    }
-
-    fn topic2(&self) -> Option<H256> {
-        Some(H256::from_low_u64_be(self.l2_chain_id.as_u64()))
+    fn event_filter(&self) -> EventFilter {
+        EventFilter::CoreContracts {
+            topic1: self.appended_chain_batch_root_signature,
+            topic2: Some(H256::from_low_u64_be(self.l2_chain_id.as_u64())),
+        }
     }
 
     fn event_source(&self) -> EventsSource {
@@ -0,0 +1,270 @@
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use anyhow::Context as _;
+use zksync_config::configs::eth_watch::EthWatchCustomEvent;
+use zksync_dal::{eth_watcher_dal::EventType, Connection, Core};
+use zksync_system_constants::PRIORITY_EXPIRATION;
+use zksync_types::{api::Log, ethabi, web3::keccak256, Address, H256};
+
+use crate::{
+    event_processors::{EventFilter, EventProcessor, EventProcessorError, EventsSource},
+    metrics::{CustomEventOutcome, METRICS},
+};
+
+/// Custom L1 event routed to a [`CustomEventHandler`].
+#[derive(Debug, Clone)]
+pub struct CustomEvent {
+    /// Signature of the event as specified in the config, e.g. `Paused(address)`.
+    pub signature: String,
+    /// Raw event log.
+    pub log: Log,
+}
+
+impl CustomEvent {
+    /// Address of the contract that emitted the event.
+    pub fn contract_address(&self) -> Address {
+        self.log.address
+    }
+
+    /// L1 block containing the event.
+    pub fn block_number(&self) -> Option<u64> {
+        self.log.block_number.map(|number| number.as_u64())
+    }
+
+    /// Decodes event params using the provided ABI.
+    pub fn decode(&self, event: &ethabi::Event) -> anyhow::Result<ethabi::Log> {
+        let raw_log = ethabi::RawLog {
+            topics: self.log.topics.clone(),
+            data: self.log.data.0.clone(),
+        };
+        event
+            .parse_log(raw_log)
+            .with_context(|| format!("failed decoding `{}` event", self.signature))
+    }
+}
+
+/// Handler for custom L1 events watched by [`EthWatch`](crate::EthWatch).
+///
+/// Events are delivered at least once: if the watcher restarts or a handler returns an error, events from
+/// the last partially processed L1 block may be delivered again.
+#[async_trait::async_trait]
+pub trait CustomEventHandler: 'static + fmt::Debug + Send + Sync {
+    /// Handles a single event.
+    async fn handle_event(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        event: &CustomEvent,
+    ) -> anyhow::Result<()>;
+
+    /// Rolls back the results of handling events from L1 blocks with number `>= from_block`, which were removed
+    /// from the canonical chain by a reorg. `storage` is a DB transaction, which is committed by the caller.
+    ///
+    /// Returns `true` if the node must be restarted for the rollback to take effect.
+    async fn rollback(
+        &self,
+        _storage: &mut Connection<'_, Core>,
+        _from_block: u64,
+    ) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+}
+
+/// Handler logging all received events. Useful for monitoring contracts (e.g., an emergency pause contract)
+/// without any further processing.
+#[derive(Debug)]
+pub struct LoggingEventHandler;
+
+impl LoggingEventHandler {
+    /// Name of the handler in [`CustomEventHandlers`].
+    pub const NAME: &'static str = "log";
+}
+
+#[async_trait::async_trait]
+impl CustomEventHandler for LoggingEventHandler {
+    async fn handle_event(
+        &self,
+        _storage: &mut Connection<'_, Core>,
+        event: &CustomEvent,
+    ) -> anyhow::Result<()> {
+        tracing::info!(
+            "Received `{}` event from contract {:?} in L1 block {:?}, tx {:?}: topics {:?}, data {:?}",
+            event.signature,
+            event.contract_address(),
+            event.block_number(),
+            event.log.transaction_hash,
+            event.log.topics,
+            event.log.data
+        );
+        Ok(())
+    }
+}
+
+/// Registry of named custom event handlers. [`LoggingEventHandler`] is always registered.
+#[derive(Debug, Clone)]
+pub struct CustomEventHandlers(HashMap<String, Arc<dyn CustomEventHandler>>);
+
+impl Default for CustomEventHandlers {
+    fn default() -> Self {
+        let mut this = Self(HashMap::new());
+        this.insert(LoggingEventHandler::NAME, Arc::new(LoggingEventHandler));
+        this
+    }
+}
+
+impl CustomEventHandlers {
+    /// Registers a handler with the specified name. Replaces a previously registered handler with the same name, if any.
+    pub fn insert(&mut self, name: impl Into<String>, handler: Arc<dyn CustomEventHandler>) {
+        self.0.insert(name.into(), handler);
+    }
+}
+
+#[derive(Debug)]
+struct WatchedEvent {
+    signature: String,
+    handler_name: String,
+    handler: Arc<dyn CustomEventHandler>,
+}
+
+/// Routes custom L1 events to the handlers specified in the config.
+#[derive(Debug)]
+pub(crate) struct CustomEventsProcessor {
+    events: HashMap<(Address, H256), WatchedEvent>,
+    event_type: EventType,
+    start_block: Option<u64>,
+}
+
+impl CustomEventsProcessor {
+    pub fn new(
+        configs: &[EthWatchCustomEvent],
+        handlers: &CustomEventHandlers,
+    ) -> anyhow::Result<Self> {
+        Self::with_cursor(EventType::CustomEvents, None, configs, handlers)
+    }
+
+    /// Creates a processor persisting its progress as `event_type` events. If `start_block` is specified,
+    /// events are processed starting from this block rather than from the default initial block.
+    pub fn with_cursor(
+        event_type: EventType,
+        start_block: Option<u64>,
+        configs: &[EthWatchCustomEvent],
+        handlers: &CustomEventHandlers,
+    ) -> anyhow::Result<Self> {
+        let mut events = HashMap::with_capacity(configs.len());
+        for config in configs {
+            let signature: String = config
+                .event_signature
+                .chars()
+                .filter(|ch| !ch.is_whitespace())
+                .collect();
+            anyhow::ensure!(
+                signature.contains('(') && signature.ends_with(')'),
+                "invalid event signature `{}`; expected a signature like `Paused(address)`",
+                config.event_signature
+            );
+            let handler = handlers.0.get(&config.handler).with_context(|| {
+                format!(
+                    "handler `{}` for event `{signature}` is not registered",
+                    config.handler
+                )
+            })?;
+
+            let topic = H256(keccak256(signature.as_bytes()));
+            let event = WatchedEvent {
+                signature,
+                handler_name: config.handler.clone(),
+                handler: handler.clone(),
+            };
+            let prev_event = events.insert((config.contract_address, topic), event);
+            anyhow::ensure!(
+                prev_event.is_none(),
+                "event `{}` for contract {:?} is configured multiple times",
+                config.event_signature,
+                config.contract_address
+            );
+        }
+        Ok(Self {
+            events,
+            event_type,
+            start_block,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl EventProcessor for CustomEventsProcessor {
+    async fn process_events(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        events: Vec<Log>,
+    ) -> Result<usize, EventProcessorError> {
+        let events_count = events.len();
+        for (i, log) in events.into_iter().enumerate() {
+            let topic = log.topics.first().copied().unwrap_or_default();
+            // The filter may match a topic configured for another watched contract; such events are skipped.
+            let Some(watched_event) = self.events.get(&(log.address, topic)) else {
+                continue;
+            };
+            let event = CustomEvent {
+                signature: watched_event.signature.clone(),
+                log,
+            };
+
+            if let Err(err) = watched_event.handler.handle_event(storage, &event).await {
+                // Progress is saved up to the last successfully handled event, so the event will be retried.
+                tracing::warn!(
+                    "Handler `{}` failed processing `{}` event {event:?}: {err:#}",
+                    watched_event.handler_name,
+                    event.signature
+                );
+                METRICS.custom_events[&CustomEventOutcome::Error].inc();
+                return Ok(i);
+            }
+            METRICS.custom_events[&CustomEventOutcome::Success].inc();
+        }
+        Ok(events_count)
+    }
+
+    fn event_filter(&self) -> EventFilter {
+        let mut addresses: Vec<_> = self.events.keys().map(|(address, _)| *address).collect();
+        addresses.sort_unstable();
+        addresses.dedup();
+        let mut topics1: Vec<_> = self.events.keys().map(|(_, topic)| *topic).collect();
+        topics1.sort_unstable();
+        topics1.dedup();
+        EventFilter::Contracts { addresses, topics1 }
+    }
+
+    fn event_source(&self) -> EventsSource {
+        EventsSource::L1
+    }
+
+    fn event_type(&self) -> EventType {
+        self.event_type
+    }
+
+    fn initial_block(&self, to_block: u64) -> u64 {
+        self.start_block
+            .unwrap_or_else(|| to_block.saturating_sub(PRIORITY_EXPIRATION))
+    }
+
+    async fn rollback(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        from_block: u64,
+    ) -> Result<bool, EventProcessorError> {
+        let mut restart_required = false;
+        let mut rolled_back_handlers = Vec::new();
+        for event in self.events.values() {
+            if rolled_back_handlers.contains(&event.handler_name) {
+                continue;
+            }
+            restart_required |= event
+                .handler
+                .rollback(storage, from_block)
+                .await
+                .with_context(|| format!("handler `{}` failed rollback", event.handler_name))?;
+            rolled_back_handlers.push(event.handler_name.clone());
+        }
+        Ok(restart_required)
+    }
+}
@@ -3,13 +3,15 @@ use std::sync::Arc;
 use anyhow::Context as _;
 use zksync_dal::{eth_watcher_dal::EventType, Connection, Core, CoreDal, DalError};
 use zksync_types::{
-    api::Log, ethabi::Contract, protocol_version::ProtocolSemanticVersion, ProtocolUpgrade, H256,
-    U256,
+    api::{Log, StagedContractStatus},
+    ethabi::Contract,
+    protocol_version::ProtocolSemanticVersion,
+    ProtocolUpgrade, H256, U256,
 };
 
 use crate::{
     client::EthClient,
-    event_processors::{EventProcessor, EventProcessorError, EventsSource},
+    event_processors::{EventFilter, EventProcessor, EventProcessorError, EventsSource},
     metrics::{PollStage, METRICS},
 };
 
@@ -18,6 +20,8 @@ use crate::{
 pub struct DecentralizedUpgradesEventProcessor {
     /// Last protocol version seen. Used to skip events for already known upgrade proposals.
     last_seen_protocol_version: ProtocolSemanticVersion,
+    /// Upgrades persisted by this processor together with the numbers of blocks they originate from.
+    persisted_upgrades: Vec<(ProtocolSemanticVersion, u64)>,
     update_upgrade_timestamp_signature: H256,
     sl_client: Arc<dyn EthClient>,
 }
@@ -30,6 +34,7 @@ impl DecentralizedUpgradesEventProcessor {
     ) -> Self {
         Self {
             last_seen_protocol_version,
+            persisted_upgrades: vec![],
             update_upgrade_timestamp_signature: chain_admin_contract
                 .event("UpdateUpgradeTimestamp")
                 .context("UpdateUpgradeTimestamp event is missing in ABI")
@@ -49,6 +54,7 @@ impl EventProcessor for DecentralizedUpgradesEventProcessor {
     ) -> Result<usize, EventProcessorError> {
         let mut upgrades = Vec::new();
         for event in &events {
+            let block_number = event.block_number.context("missing block number")?.as_u64();
             let version = event.topics.get(1).copied().context("missing topic 1")?;
             let timestamp: u64 = U256::from_big_endian(&event.data.0)
                 .try_into()
@@ -71,26 +77,26 @@ impl EventProcessor for DecentralizedUpgradesEventProcessor {
             } else {
                 None
             };
-            upgrades.push((upgrade, scheduler_vk_hash));
+            upgrades.push((upgrade, scheduler_vk_hash, block_number));
         }
 
         let new_upgrades: Vec<_> = upgrades
             .into_iter()
-            .skip_while(|(v, _)| v.version <= self.last_seen_protocol_version)
+            .skip_while(|(v, ..)| v.version <= self.last_seen_protocol_version)
             .collect();
 
-        let Some((last_upgrade, _)) = new_upgrades.last() else {
+        let Some((last_upgrade, ..)) = new_upgrades.last() else {
             return Ok(events.len());
         };
         let versions: Vec<_> = new_upgrades
             .iter()
-            .map(|(u, _)| u.version.to_string())
+            .map(|(u, ..)| u.version.to_string())
             .collect();
         tracing::debug!("Received upgrades with versions: {versions:?}");
 
         let last_version = last_upgrade.version;
         let stage_latency = METRICS.poll_eth_node[&PollStage::PersistUpgrades].start();
-        for (upgrade, scheduler_vk_hash) in new_upgrades {
+        for (upgrade, scheduler_vk_hash, block_number) in new_upgrades {
             let latest_semantic_version = storage
                 .protocol_versions_dal()
                 .latest_semantic_version()
@@ -112,6 +118,8 @@ impl EventProcessor for DecentralizedUpgradesEventProcessor {
                     })?;
 
                 let new_version = latest_version.apply_upgrade(upgrade, scheduler_vk_hash);
+                self.persisted_upgrades
+                    .push((new_version.version, block_number));
                 if new_version.version.minor == latest_semantic_version.minor {
                     // Only verification parameters may change if only patch is bumped.
                     assert_eq!(
@@ -125,6 +133,32 @@ impl EventProcessor for DecentralizedUpgradesEventProcessor {
                     .save_protocol_version_with_tx(&new_version)
                     .await
                     .map_err(DalError::generalize)?;
+
+                let staged_contracts = storage
+                    .staged_base_system_contracts_dal()
+                    .validate_staged_contracts(
+                        new_version.version.minor,
+                        &new_version.base_system_contracts_hashes,
+                    )
+                    .await
+                    .map_err(DalError::generalize)?;
+                for contract in staged_contracts {
+                    if contract.status == StagedContractStatus::Validated {
+                        tracing::info!(
+                            "Staged {} with hash {:?} is validated for protocol version {}",
+                            contract.kind,
+                            contract.bytecode_hash,
+                            new_version.version
+                        );
+                    } else {
+                        tracing::error!(
+                            "Staged {} with hash {:?} doesn't match protocol upgrade {}; it will not be used",
+                            contract.kind,
+                            contract.bytecode_hash,
+                            new_version.version
+                        );
+                    }
+                }
             }
         }
         stage_latency.observe();
@@ -133,8 +167,11 @@ impl EventProcessor for DecentralizedUpgradesEventProcessor {
         Ok(events.len())
     }
 
-    fn topic1(&self) -> H256 {
-        self.update_upgrade_timestamp_signature
+    fn event_filter(&self) -> EventFilter {
+        EventFilter::CoreContracts {
+            topic1: self.update_upgrade_timestamp_signature,
+            topic2: None,
+        }
     }
 
     fn event_source(&self) -> EventsSource {
@@ -144,4 +181,24 @@ impl EventProcessor for DecentralizedUpgradesEventProcessor {
     fn event_type(&self) -> EventType {
         EventType::ProtocolUpgrades
     }
+
+    async fn rollback(
+        &mut self,
+        _storage: &mut Connection<'_, Core>,
+        from_block: u64,
+    ) -> Result<bool, EventProcessorError> {
+        // Protocol versions may be used by the state keeper as soon as they are persisted, so they are never removed.
+        let reorged_upgrade = self
+            .persisted_upgrades
+            .iter()
+            .find(|(_, block_number)| *block_number >= from_block);
+        if let Some((version, block_number)) = reorged_upgrade {
+            let err = anyhow::anyhow!(
+                "protocol upgrade {version} from L1 block #{block_number} removed by a reorg is already persisted; \
+                 manual intervention is required"
+            );
+            return Err(err.into());
+        }
+        Ok(false)
+    }
 }
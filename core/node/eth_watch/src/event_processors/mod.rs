@@ -2,15 +2,20 @@ use std::fmt;
 
 use zksync_dal::{eth_watcher_dal::EventType, Connection, Core};
 use zksync_eth_client::{ContractCallError, EnrichedClientError};
-use zksync_types::{api::Log, H256};
+use zksync_system_constants::PRIORITY_EXPIRATION;
+use zksync_types::{api::Log, Address, H256};
 
+pub use self::custom_events::{
+    CustomEvent, CustomEventHandler, CustomEventHandlers, LoggingEventHandler,
+};
 pub(crate) use self::{
-    appended_chain_batch_root::BatchRootProcessor,
+    appended_chain_batch_root::BatchRootProcessor, custom_events::CustomEventsProcessor,
     decentralized_upgrades::DecentralizedUpgradesEventProcessor,
     priority_ops::PriorityOpsEventProcessor,
 };
 
 mod appended_chain_batch_root;
+mod custom_events;
 mod decentralized_upgrades;
 mod priority_ops;
 
@@ -32,12 +37,24 @@ pub(super) enum EventProcessorError {
     Internal(#[from] anyhow::Error),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(super) enum EventsSource {
     L1,
     SL,
 }
 
+/// Filter for events relevant to an [`EventProcessor`].
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum EventFilter {
+    /// Events with the specified topics emitted by the core contracts watched by the client.
+    CoreContracts { topic1: H256, topic2: Option<H256> },
+    /// Events with any of the specified first topics emitted by the specified contracts.
+    Contracts {
+        addresses: Vec<Address>,
+        topics1: Vec<H256>,
+    },
+}
+
 impl EventProcessorError {
     pub fn log_parse(source: impl Into<anyhow::Error>, log_kind: &'static str) -> Self {
         Self::LogParse {
@@ -51,7 +68,7 @@ impl EventProcessorError {
 /// feeds events to all processors one-by-one.
 #[async_trait::async_trait]
 pub(super) trait EventProcessor: 'static + fmt::Debug + Send + Sync {
-    /// Processes given events. All events are guaranteed to match [`Self::topic1()`] and [`Self::topic2()`].
+    /// Processes given events. All events are guaranteed to match [`Self::event_filter()`].
     /// Returns number of processed events, this result is used to update last processed block.
     async fn process_events(
         &mut self,
@@ -59,20 +76,38 @@ pub(super) trait EventProcessor: 'static + fmt::Debug + Send + Sync {
         events: Vec<Log>,
     ) -> Result<usize, EventProcessorError>;
 
-    /// Relevant topic1 which defines what events to be processed
-    fn topic1(&self) -> H256;
-
-    /// Relevant topic2 which defines what events to be processed
-    fn topic2(&self) -> Option<H256> {
-        None
-    }
+    /// Filter which defines what events to be processed.
+    fn event_filter(&self) -> EventFilter;
 
     fn event_source(&self) -> EventsSource;
 
     fn event_type(&self) -> EventType;
 
+    /// Returns the first block to process if the progress of the processor is not persisted yet.
+    /// By default, blocks which may contain non-expired priority operations are processed.
+    fn initial_block(&self, to_block: u64) -> u64 {
+        to_block.saturating_sub(PRIORITY_EXPIRATION)
+    }
+
     /// Whether processor expect events only from finalized blocks.
     fn only_finalized_block(&self) -> bool {
         false
     }
+
+    /// Rolls back the results of processing events from blocks with number `>= from_block`, which were removed
+    /// from the canonical chain by a reorg. `storage` is a DB transaction, which is committed by the caller.
+    ///
+    /// Returns `true` if the node must be restarted for the rollback to take effect, e.g. because rolled back data
+    /// is cached by other components.
+    async fn rollback(
+        &mut self,
+        _storage: &mut Connection<'_, Core>,
+        from_block: u64,
+    ) -> Result<bool, EventProcessorError> {
+        Err(anyhow::anyhow!(
+            "{:?} events cannot be rolled back (requested rollback from block {from_block})",
+            self.event_type()
+        )
+        .into())
+    }
 }
@@ -4,11 +4,11 @@ use anyhow::Context;
 use zksync_contracts::hyperchain_contract;
 use zksync_dal::{eth_watcher_dal::EventType, Connection, Core, CoreDal, DalError};
 use zksync_shared_metrics::{TxStage, APP_METRICS};
-use zksync_types::{api::Log, l1::L1Tx, PriorityOpId, H256};
+use zksync_types::{api::Log, l1::L1Tx, L1BlockNumber, PriorityOpId, H256};
 
 use crate::{
     client::EthClient,
-    event_processors::{EventProcessor, EventProcessorError, EventsSource},
+    event_processors::{EventFilter, EventProcessor, EventProcessorError, EventsSource},
     metrics::{PollStage, METRICS},
 };
 
@@ -109,8 +109,11 @@ impl EventProcessor for PriorityOpsEventProcessor {
         Ok(skipped_ops + ops_to_insert.len())
     }
 
-    fn topic1(&self) -> H256 {
-        self.new_priority_request_signature
+    fn event_filter(&self) -> EventFilter {
+        EventFilter::CoreContracts {
+            topic1: self.new_priority_request_signature,
+            topic2: None,
+        }
     }
 
     fn event_source(&self) -> EventsSource {
@@ -120,4 +123,50 @@ impl EventProcessor for PriorityOpsEventProcessor {
     fn event_type(&self) -> EventType {
         EventType::PriorityTransactions
     }
+
+    async fn rollback(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        from_block: u64,
+    ) -> Result<bool, EventProcessorError> {
+        let from_block = L1BlockNumber(
+            u32::try_from(from_block).context("L1 block number doesn't fit into u32")?,
+        );
+        // Affected operations are locked until the rollback is committed, so that the mempool cannot fetch them
+        // and the state keeper cannot mark them as executed concurrently.
+        let affected_ops = storage
+            .transactions_dal()
+            .lock_l1_txs_since(from_block)
+            .await
+            .map_err(DalError::generalize)?;
+        let executed_op = affected_ops
+            .iter()
+            .filter_map(|&(op_id, is_executed)| is_executed.then_some(op_id))
+            .min();
+        if let Some(op_id) = executed_op {
+            let err = anyhow::anyhow!(
+                "priority operation #{op_id} from L1 block removed by a reorg (>= {from_block}) is already executed; \
+                 manual intervention is required"
+            );
+            return Err(err.into());
+        }
+
+        let removed_ops = storage
+            .transactions_dal()
+            .remove_unexecuted_l1_txs_since(from_block)
+            .await
+            .map_err(DalError::generalize)?;
+        let Some(first_removed_id) = removed_ops.iter().map(|(op_id, _)| *op_id).min() else {
+            return Ok(false);
+        };
+        tracing::warn!(
+            "Removed {} priority operations starting from #{first_removed_id} originating from L1 blocks removed by a reorg",
+            removed_ops.len()
+        );
+        self.next_expected_priority_id = self.next_expected_priority_id.min(first_removed_id);
+        // Priority operations fetched into the mempool will only be purged from it after a restart. Until then,
+        // sealing an L2 block with any of them fails since they are no longer present in the DB.
+        let restart_required = removed_ops.iter().any(|&(_, in_mempool)| in_mempool);
+        Ok(restart_required)
+    }
 }
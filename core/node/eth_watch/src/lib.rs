@@ -1,23 +1,36 @@
 //! Ethereum watcher polls the Ethereum node for the relevant events, such as priority operations (aka L1 transactions),
 //! protocol upgrades etc.
 //! New events are accepted to the ZKsync network once they have the sufficient amount of L1 confirmations.
+//! If events are processed before their blocks are finalized, the watcher detects reorgs of the processed blocks
+//! and rolls back the results of processing events from the removed blocks.
 
-use std::{sync::Arc, time::Duration};
+use std::{
+    collections::{hash_map::Entry, BTreeMap, HashMap},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::Context as _;
 use tokio::sync::watch;
-use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
+use zksync_config::configs::eth_watch::EthWatchCustomEvent;
+use zksync_dal::{eth_watcher_dal::EventType, Connection, ConnectionPool, Core, CoreDal, DalError};
+use zksync_eth_client::EnrichedClientResult;
 use zksync_mini_merkle_tree::MiniMerkleTree;
-use zksync_system_constants::PRIORITY_EXPIRATION;
 use zksync_types::{
     ethabi::Contract, protocol_version::ProtocolSemanticVersion,
-    web3::BlockNumber as Web3BlockNumber, L1BatchNumber, L2ChainId, PriorityOpId,
+    web3::BlockNumber as Web3BlockNumber, L1BatchNumber, L2ChainId, PriorityOpId, H256,
 };
 
-pub use self::client::{EthClient, EthHttpQueryClient, L2EthClient};
+pub use self::{
+    client::{EthClient, EthHttpQueryClient, L2EthClient},
+    event_processors::{CustomEvent, CustomEventHandler, CustomEventHandlers, LoggingEventHandler},
+};
 use self::{
     client::{L2EthClientW, RETRY_LIMIT},
-    event_processors::{EventProcessor, EventProcessorError, PriorityOpsEventProcessor},
+    event_processors::{
+        CustomEventsProcessor, EventFilter, EventProcessor, EventProcessorError,
+        PriorityOpsEventProcessor,
+    },
     metrics::METRICS,
 };
 use crate::event_processors::{
@@ -38,6 +51,81 @@ struct EthWatchState {
     batch_merkle_tree: MiniMerkleTree<[u8; 96]>,
 }
 
+/// Minimum interval between checks of processed blocks for reorgs if there are no new blocks to process.
+const REORG_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Hashes of non-finalized blocks with events processed by a certain processor, and of blocks up to which
+/// events were processed. Used to detect reorgs affecting processed events. Not persisted, so reorgs happening
+/// while the watcher is not running are not detected.
+#[derive(Debug, Default)]
+struct ProcessedBlocks {
+    hashes: BTreeMap<u64, H256>,
+    last_checked_at: Option<Instant>,
+}
+
+impl ProcessedBlocks {
+    fn insert(&mut self, block_number: u64, block_hash: H256) {
+        self.hashes.insert(block_number, block_hash);
+    }
+
+    /// Checks whether processed blocks should be checked for reorgs. Blocks are always checked before processing
+    /// new blocks, so that events are never processed on top of removed blocks; otherwise, checks are throttled.
+    fn should_check(&self, has_new_blocks: bool) -> bool {
+        if self.hashes.is_empty() {
+            return false;
+        }
+        has_new_blocks
+            || self.last_checked_at.map_or(true, |checked_at| {
+                checked_at.elapsed() >= REORG_CHECK_INTERVAL
+            })
+    }
+
+    /// Checks processed blocks against the canonical chain. If a reorg is detected, returns the number
+    /// of the first block after the last processed block still present in the canonical chain.
+    async fn detect_reorg(
+        &mut self,
+        client: &dyn EthClient,
+        finalized_block: u64,
+    ) -> EnrichedClientResult<Option<u64>> {
+        self.last_checked_at = Some(Instant::now());
+        // Finalized blocks cannot be reorged.
+        self.hashes
+            .retain(|&block_number, _| block_number > finalized_block);
+        let Some((&last_block, &last_hash)) = self.hashes.last_key_value() else {
+            return Ok(None);
+        };
+        if client.block_hash(last_block).await? == Some(last_hash) {
+            // Since block hashes commit to parent hashes, all earlier blocks are still canonical as well.
+            return Ok(None);
+        }
+
+        // Blocks are only inserted if the chain wasn't reorged since the previous check, so canonical blocks
+        // form a prefix of processed blocks. The last block is known to be removed.
+        let blocks: Vec<_> = self
+            .hashes
+            .iter()
+            .map(|(&number, &hash)| (number, hash))
+            .collect();
+        let (mut canonical_count, mut removed_start) = (0, blocks.len() - 1);
+        while canonical_count < removed_start {
+            let mid = canonical_count + (removed_start - canonical_count) / 2;
+            let (block_number, block_hash) = blocks[mid];
+            if client.block_hash(block_number).await? == Some(block_hash) {
+                canonical_count = mid + 1;
+            } else {
+                removed_start = mid;
+            }
+        }
+        let last_canonical_block = match canonical_count.checked_sub(1) {
+            Some(idx) => blocks[idx].0,
+            None => finalized_block,
+        };
+        self.hashes
+            .retain(|&block_number, _| block_number <= last_canonical_block);
+        Ok(Some(last_canonical_block + 1))
+    }
+}
+
 /// Ethereum watcher component.
 #[derive(Debug)]
 pub struct EthWatch {
@@ -45,6 +133,8 @@ pub struct EthWatch {
     sl_client: Arc<dyn EthClient>,
     poll_interval: Duration,
     event_processors: Vec<Box<dyn EventProcessor>>,
+    /// Processed blocks for each processor in `event_processors`.
+    processed_blocks: Vec<ProcessedBlocks>,
     pool: ConnectionPool<Core>,
 }
 
@@ -91,15 +181,55 @@ impl EthWatch {
             );
             event_processors.push(Box::new(batch_root_processor));
         }
+        let processed_blocks = event_processors.iter().map(|_| ProcessedBlocks::default());
         Ok(Self {
             l1_client,
             sl_client,
             poll_interval,
+            processed_blocks: processed_blocks.collect(),
             event_processors,
             pool,
         })
     }
 
+    /// Adds processing of custom L1 events. Each event is routed to the handler from `handlers` specified
+    /// in the event config.
+    pub fn with_custom_events(
+        mut self,
+        events: &[EthWatchCustomEvent],
+        handlers: &CustomEventHandlers,
+    ) -> anyhow::Result<Self> {
+        if events.is_empty() {
+            return Ok(self);
+        }
+        let processor = CustomEventsProcessor::new(events, handlers)?;
+        tracing::info!("Watching custom L1 events: {processor:?}");
+        self.event_processors.push(Box::new(processor));
+        self.processed_blocks.push(ProcessedBlocks::default());
+        Ok(self)
+    }
+
+    /// Adds processing of L1 bridge events for the bridge indexer. Unlike [`Self::with_custom_events()`],
+    /// bridge events have a dedicated cursor, so that all of them are indexed starting from `start_block`
+    /// (e.g., the L1 shared bridge deployment block) independently of other events.
+    pub fn with_bridge_events(
+        mut self,
+        events: &[EthWatchCustomEvent],
+        handlers: &CustomEventHandlers,
+        start_block: u64,
+    ) -> anyhow::Result<Self> {
+        let processor = CustomEventsProcessor::with_cursor(
+            EventType::BridgeEvents,
+            Some(start_block),
+            events,
+            handlers,
+        )?;
+        tracing::info!("Watching L1 bridge events from block #{start_block}: {processor:?}");
+        self.event_processors.push(Box::new(processor));
+        self.processed_blocks.push(ProcessedBlocks::default());
+        Ok(self)
+    }
+
     #[tracing::instrument(name = "EthWatch::initialize_state", skip_all)]
     async fn initialize_state(
         storage: &mut Connection<'_, Core>,
@@ -175,42 +305,110 @@ impl EthWatch {
         &mut self,
         storage: &mut Connection<'_, Core>,
     ) -> Result<(), EventProcessorError> {
-        for processor in &mut self.event_processors {
+        // Finalized block numbers are shared by all processors with the same events source.
+        let mut finalized_blocks = HashMap::new();
+        let processors = self.event_processors.iter_mut();
+        for (processor, processed_blocks) in processors.zip(&mut self.processed_blocks) {
             let client = match processor.event_source() {
                 EventsSource::L1 => self.l1_client.as_ref(),
                 EventsSource::SL => self.sl_client.as_ref(),
             };
             let chain_id = client.chain_id().await?;
+            let finalized_block = match finalized_blocks.entry(processor.event_source()) {
+                Entry::Occupied(entry) => *entry.get(),
+                Entry::Vacant(entry) => *entry.insert(client.finalized_block_number().await?),
+            };
             let to_block = if processor.only_finalized_block() {
-                client.finalized_block_number().await?
+                finalized_block
             } else {
                 client.confirmed_block_number().await?
             };
 
-            let from_block = storage
+            let mut from_block = storage
                 .eth_watcher_dal()
                 .get_or_set_next_block_to_process(
                     processor.event_type(),
                     chain_id,
-                    to_block.saturating_sub(PRIORITY_EXPIRATION),
+                    processor.initial_block(to_block),
                 )
                 .await
                 .map_err(DalError::generalize)?;
 
+            let check_reorg = processed_blocks.should_check(from_block <= to_block);
+            // `to_block` hash is fetched both before checking for a reorg and after fetching events. Events are only
+            // processed if the hashes match, i.e., if the chain wasn't reorged in between.
+            let to_block_hash =
+                if (check_reorg || from_block <= to_block) && to_block > finalized_block {
+                    client.block_hash(to_block).await?
+                } else {
+                    None
+                };
+
+            let reorg_start = if check_reorg {
+                processed_blocks
+                    .detect_reorg(client, finalized_block)
+                    .await?
+            } else {
+                None
+            };
+            if let Some(reorg_start) = reorg_start.filter(|&block| block < from_block) {
+                tracing::warn!(
+                    "Detected reorg on chain {chain_id} starting from block #{reorg_start}; rolling back {:?} events",
+                    processor.event_type()
+                );
+                METRICS.reorgs.inc();
+
+                let mut transaction = storage
+                    .start_transaction()
+                    .await
+                    .map_err(DalError::generalize)?;
+                let restart_required = processor.rollback(&mut transaction, reorg_start).await?;
+                transaction
+                    .eth_watcher_dal()
+                    .update_next_block_to_process(processor.event_type(), chain_id, reorg_start)
+                    .await
+                    .map_err(DalError::generalize)?;
+                transaction.commit().await.map_err(DalError::generalize)?;
+                if restart_required {
+                    let err = anyhow::anyhow!(
+                        "rolling back {:?} events requires restarting the node",
+                        processor.event_type()
+                    );
+                    return Err(err.into());
+                }
+                from_block = reorg_start;
+            }
+
             // There are no new blocks so there is nothing to be done
             if from_block > to_block {
                 continue;
             }
 
-            let processor_events = client
-                .get_events(
-                    Web3BlockNumber::Number(from_block.into()),
-                    Web3BlockNumber::Number(to_block.into()),
-                    processor.topic1(),
-                    processor.topic2(),
-                    RETRY_LIMIT,
-                )
-                .await?;
+            let (from, to) = (
+                Web3BlockNumber::Number(from_block.into()),
+                Web3BlockNumber::Number(to_block.into()),
+            );
+            let processor_events = match processor.event_filter() {
+                EventFilter::CoreContracts { topic1, topic2 } => {
+                    client
+                        .get_events(from, to, topic1, topic2, RETRY_LIMIT)
+                        .await?
+                }
+                EventFilter::Contracts { addresses, topics1 } => {
+                    client
+                        .get_contract_events(from, to, addresses, topics1, RETRY_LIMIT)
+                        .await?
+                }
+            };
+            if let Some(hash) = to_block_hash {
+                if client.block_hash(to_block).await? != Some(hash) {
+                    tracing::info!(
+                        "Block #{to_block} on chain {chain_id} was reorged while fetching {:?} events; retrying",
+                        processor.event_type()
+                    );
+                    continue;
+                }
+            }
             let processed_events_count = processor
                 .process_events(storage, processor_events.clone())
                 .await?;
@@ -237,6 +435,22 @@ impl EthWatch {
                 )
                 .await
                 .map_err(DalError::generalize)?;
+
+            // Blocks with processed events are recorded, so that a reorg only rolls back events from the removed blocks.
+            for event in &processor_events[..processed_events_count] {
+                let (Some(block_number), Some(block_hash)) = (event.block_number, event.block_hash)
+                else {
+                    continue;
+                };
+                if block_number.as_u64() > finalized_block {
+                    processed_blocks.insert(block_number.as_u64(), block_hash);
+                }
+            }
+            if next_block_to_process == to_block + 1 {
+                if let Some(hash) = to_block_hash {
+                    processed_blocks.insert(to_block, hash);
+                }
+            }
         }
         Ok(())
     }
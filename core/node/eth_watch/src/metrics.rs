@@ -11,6 +11,13 @@ pub(super) enum PollStage {
     PersistUpgrades,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "outcome", rename_all = "snake_case")]
+pub(super) enum CustomEventOutcome {
+    Success,
+    Error,
+}
+
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "server_eth_watch")]
 pub(super) struct EthWatcherMetrics {
@@ -19,6 +26,10 @@ pub(super) struct EthWatcherMetrics {
     /// Latency of polling and processing events split by stage.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub poll_eth_node: Family<PollStage, Histogram<Duration>>,
+    /// Number of detected reorgs affecting processed events.
+    pub reorgs: Counter,
+    /// Number of custom events passed to handlers, split by the handling outcome.
+    pub custom_events: Family<CustomEventOutcome, Counter>,
 }
 
 #[vise::register]
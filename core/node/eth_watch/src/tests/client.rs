@@ -13,7 +13,7 @@ use zksync_types::{
     ethabi::Token,
     l1::L1Tx,
     u256_to_h256,
-    web3::{contract::Tokenizable, BlockNumber},
+    web3::{contract::Tokenizable, keccak256, BlockNumber},
     Address, L1BatchNumber, L2ChainId, ProtocolUpgrade, SLChainId, Transaction, H256, U256, U64,
 };
 
@@ -25,11 +25,16 @@ pub struct FakeEthClientData {
     diamond_upgrades: HashMap<u64, Vec<Log>>,
     upgrade_timestamp: HashMap<u64, Vec<Log>>,
     last_finalized_block_number: u64,
+    last_confirmed_block_number: u64,
+    /// Starting blocks of emulated reorgs.
+    reorgs: Vec<u64>,
+    block_hash_requests: usize,
     chain_id: SLChainId,
     processed_priority_transactions_count: u64,
     chain_log_proofs: HashMap<L1BatchNumber, ChainAggProof>,
     batch_roots: HashMap<u64, Vec<Log>>,
     chain_roots: HashMap<u64, H256>,
+    custom_events: HashMap<u64, Vec<Log>>,
 }
 
 impl FakeEthClientData {
@@ -39,11 +44,15 @@ impl FakeEthClientData {
             diamond_upgrades: Default::default(),
             upgrade_timestamp: Default::default(),
             last_finalized_block_number: 0,
+            last_confirmed_block_number: 0,
+            reorgs: vec![],
+            block_hash_requests: 0,
             chain_id,
             processed_priority_transactions_count: 0,
             chain_log_proofs: Default::default(),
             batch_roots: Default::default(),
             chain_roots: Default::default(),
+            custom_events: Default::default(),
         }
     }
 
@@ -79,6 +88,43 @@ impl FakeEthClientData {
         self.processed_priority_transactions_count = number;
     }
 
+    fn block_hash(&self, block_number: u64) -> H256 {
+        let reorg_count = self
+            .reorgs
+            .iter()
+            .filter(|&&start| start <= block_number)
+            .count();
+        let mut hash = H256::from_low_u64_be(block_number);
+        hash.0[0] = reorg_count as u8;
+        hash
+    }
+
+    fn with_block_hash(&self, mut log: Log) -> Log {
+        let block_number = log.block_number.expect("no block number in log").as_u64();
+        log.block_hash = Some(self.block_hash(block_number));
+        log
+    }
+
+    fn reorg(&mut self, from_block: u64) {
+        self.reorgs.push(from_block);
+        let removed_txs: usize = self
+            .transactions
+            .iter()
+            .filter(|(&block, _)| block >= from_block)
+            .map(|(_, logs)| logs.len())
+            .sum();
+        self.processed_priority_transactions_count -= removed_txs as u64;
+        for logs in [
+            &mut self.transactions,
+            &mut self.diamond_upgrades,
+            &mut self.upgrade_timestamp,
+            &mut self.batch_roots,
+            &mut self.custom_events,
+        ] {
+            logs.retain(|&block, _| block < from_block);
+        }
+    }
+
     fn add_batch_roots(&mut self, batch_roots: &[(u64, u64, H256)]) {
         for (sl_block, l2_batch_number, batch_root) in batch_roots {
             self.batch_roots
@@ -88,6 +134,16 @@ impl FakeEthClientData {
         }
     }
 
+    fn add_custom_events(&mut self, events: &[Log]) {
+        for event in events {
+            let block_number = event.block_number.expect("no block number").as_u64();
+            self.custom_events
+                .entry(block_number)
+                .or_default()
+                .push(event.clone());
+        }
+    }
+
     fn add_chain_roots(&mut self, chain_roots: &[(u64, H256)]) {
         for (batch, root) in chain_roots {
             self.chain_roots.insert(*batch, *root);
@@ -128,6 +184,19 @@ impl MockEthClient {
             .set_last_finalized_block_number(number);
     }
 
+    pub async fn set_last_confirmed_block_number(&mut self, number: u64) {
+        self.inner.write().await.last_confirmed_block_number = number;
+    }
+
+    /// Emulates a reorg removing all blocks starting from `from_block` together with their events.
+    pub async fn reorg(&mut self, from_block: u64) {
+        self.inner.write().await.reorg(from_block);
+    }
+
+    pub async fn block_hash_requests(&self) -> usize {
+        self.inner.read().await.block_hash_requests
+    }
+
     pub async fn set_processed_priority_transactions_count(&mut self, number: u64) {
         self.inner
             .write()
@@ -150,6 +219,10 @@ impl MockEthClient {
         self.inner.write().await.add_batch_roots(batch_roots);
     }
 
+    pub async fn add_custom_events(&mut self, events: &[Log]) {
+        self.inner.write().await.add_custom_events(events);
+    }
+
     pub async fn add_chain_roots(&mut self, chain_roots: &[(u64, H256)]) {
         self.inner.write().await.add_chain_roots(chain_roots);
     }
@@ -177,18 +250,19 @@ impl EthClient for MockEthClient {
     ) -> EnrichedClientResult<Vec<Log>> {
         let from = self.block_to_number(from).await;
         let to = self.block_to_number(to).await;
+        let inner = self.inner.read().await;
         let mut logs = vec![];
         for number in from..=to {
-            if let Some(ops) = self.inner.read().await.transactions.get(&number) {
+            if let Some(ops) = inner.transactions.get(&number) {
                 logs.extend_from_slice(ops);
             }
-            if let Some(ops) = self.inner.read().await.diamond_upgrades.get(&number) {
+            if let Some(ops) = inner.diamond_upgrades.get(&number) {
                 logs.extend_from_slice(ops);
             }
-            if let Some(ops) = self.inner.read().await.upgrade_timestamp.get(&number) {
+            if let Some(ops) = inner.upgrade_timestamp.get(&number) {
                 logs.extend_from_slice(ops);
             }
-            if let Some(ops) = self.inner.read().await.batch_roots.get(&number) {
+            if let Some(ops) = inner.batch_roots.get(&number) {
                 logs.extend_from_slice(ops);
             }
         }
@@ -198,9 +272,34 @@ impl EthClient for MockEthClient {
                 log.topics.first() == Some(&topic1)
                     && (topic2.is_none() || log.topics.get(1) == topic2.as_ref())
             })
+            .map(|log| inner.with_block_hash(log))
             .collect())
     }
 
+    async fn get_contract_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+        contracts: Vec<Address>,
+        topics1: Vec<H256>,
+        _retries_left: usize,
+    ) -> EnrichedClientResult<Vec<Log>> {
+        let from = self.block_to_number(from).await;
+        let to = self.block_to_number(to).await;
+        let inner = self.inner.read().await;
+        let logs = (from..=to)
+            .filter_map(|number| inner.custom_events.get(&number))
+            .flatten()
+            .filter(|log| {
+                contracts.contains(&log.address)
+                    && log
+                        .topics
+                        .first()
+                        .is_some_and(|topic| topics1.contains(topic))
+            });
+        Ok(logs.map(|log| inner.with_block_hash(log.clone())).collect())
+    }
+
     async fn scheduler_vk_hash(
         &self,
         _verifier_address: Address,
@@ -213,7 +312,16 @@ impl EthClient for MockEthClient {
     }
 
     async fn confirmed_block_number(&self) -> EnrichedClientResult<u64> {
-        Ok(self.inner.read().await.last_finalized_block_number)
+        let inner = self.inner.read().await;
+        Ok(inner
+            .last_confirmed_block_number
+            .max(inner.last_finalized_block_number))
+    }
+
+    async fn block_hash(&self, block_number: u64) -> EnrichedClientResult<Option<H256>> {
+        let mut inner = self.inner.write().await;
+        inner.block_hash_requests += 1;
+        Ok(Some(inner.block_hash(block_number)))
     }
 
     async fn diamond_cut_by_version(
@@ -427,6 +535,29 @@ fn upgrade_timestamp_log(eth_block: u64) -> Log {
     }
 }
 
+pub(super) fn custom_event_log(
+    contract_address: Address,
+    signature: &str,
+    account: Address,
+    eth_block: u64,
+) -> Log {
+    Log {
+        address: contract_address,
+        topics: vec![H256(keccak256(signature.as_bytes()))],
+        data: ethabi::encode(&[Token::Address(account)]).into(),
+        block_hash: Some(H256::repeat_byte(0x11)),
+        block_number: Some(eth_block.into()),
+        l1_batch_number: None,
+        transaction_hash: Some(H256::random()),
+        transaction_index: Some(0u64.into()),
+        log_index: Some(0u64.into()),
+        transaction_log_index: Some(0u64.into()),
+        log_type: None,
+        removed: None,
+        block_timestamp: None,
+    }
+}
+
 fn upgrade_into_diamond_cut(upgrade: ProtocolUpgrade) -> Token {
     let abi::Transaction::L1 {
         tx, factory_deps, ..
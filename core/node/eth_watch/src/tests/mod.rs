@@ -1,13 +1,22 @@
-use std::convert::TryInto;
+use std::{
+    convert::TryInto,
+    slice,
+    sync::{Arc, Mutex},
+};
+
+use assert_matches::assert_matches;
 
+use zksync_config::configs::eth_watch::EthWatchCustomEvent;
 use zksync_contracts::chain_admin_contract;
-use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_dal::{eth_watcher_dal::EventType, Connection, ConnectionPool, Core, CoreDal};
+use zksync_system_constants::PRIORITY_EXPIRATION;
 use zksync_types::{
     abi,
     aggregated_operations::AggregatedActionType,
     api::ChainAggProof,
     block::L1BatchHeader,
     commitment::L1BatchCommitmentArtifacts,
+    ethabi,
     l1::{L1Tx, OpProcessingType, PriorityQueueType},
     l2_to_l1_log::BatchAndChainMerklePath,
     protocol_upgrade::{ProtocolUpgradeTx, ProtocolUpgradeTxCommonData},
@@ -16,7 +25,11 @@ use zksync_types::{
     ProtocolVersion, ProtocolVersionId, SLChainId, Transaction, H256, U256,
 };
 
-use crate::{tests::client::MockEthClient, EthWatch, L2EthClient};
+use crate::{
+    event_processors::EventProcessorError,
+    tests::client::{custom_event_log, MockEthClient},
+    CustomEvent, CustomEventHandler, CustomEventHandlers, EthWatch, L2EthClient,
+};
 
 mod client;
 
@@ -598,6 +611,301 @@ async fn test_batch_root_processor_restart() {
     assert_eq!(proof, "030000000900000000000000420000000000000030783030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303542000000000000003078323465653435363834376535373364313635613832333634306632303834383139636331613865333433316562633635633865363064333435343266313637324200000000000000307863633463343165646230633230333133343862323932623736386539626163316565386339326330396566386133323737633265636534303963313264383661420000000000000030783533656463316635616437396335393939626435373864666331333566396335316562643766616661343538356236346637316431356232646365316237323842000000000000003078303030303030303030303030303030303030303030303030303030303030306530303030303030303030303030303030303030303030303030303030303030334200000000000000307830303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030316639420000000000000030783031303230303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303042000000000000003078303932343932386331333737613663663234633339633264343666386562396466323365383131623236646333353237653534383339366664346531373362314200000000000000307833373561356266393039636230323134336533363935636136353865303634316537333961613539306630303034646261393335373263343463646239643264");
 }
 
+async fn get_priority_queue_blocks(storage: &mut Connection<'_, Core>) -> Vec<(u64, u32)> {
+    let queue = storage
+        .transactions_web3_dal()
+        .get_priority_queue(PriorityOpId(0), 100)
+        .await
+        .unwrap();
+    queue
+        .into_iter()
+        .map(|op| (op.serial_id.0, op.eth_block.unwrap().0))
+        .collect()
+}
+
+#[test_log::test(tokio::test)]
+async fn rolling_back_priority_ops_after_reorg() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_l1_test_watcher(connection_pool.clone()).await;
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client
+        .add_transactions(&[build_l1_tx(0, 10), build_l1_tx(1, 14)])
+        .await;
+    client.set_last_finalized_block_number(5).await;
+    client.set_last_confirmed_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(
+        get_priority_queue_blocks(&mut storage).await,
+        [(0, 10), (1, 14)]
+    );
+
+    // Move the second transaction to another block.
+    client.reorg(12).await;
+    client.add_transactions(&[build_l1_tx(1, 13)]).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(
+        get_priority_queue_blocks(&mut storage).await,
+        [(0, 10), (1, 13)]
+    );
+
+    // Reorg of finalized blocks is not detected.
+    client.set_last_finalized_block_number(15).await;
+    client.reorg(11).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(
+        get_priority_queue_blocks(&mut storage).await,
+        [(0, 10), (1, 13)]
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn reorg_not_affecting_processed_events() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_l1_test_watcher(connection_pool.clone()).await;
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client
+        .add_transactions(&[build_l1_tx(0, 10), build_l1_tx(1, 14)])
+        .await;
+    client.set_last_finalized_block_number(5).await;
+    client.set_last_confirmed_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    // Fetches transactions into the mempool.
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 2);
+
+    // The reorg removes a processed block, but not blocks with processed events, so no rollback is required.
+    client.reorg(15).await;
+    client.set_last_confirmed_block_number(16).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(
+        get_priority_queue_blocks(&mut storage).await,
+        [(0, 10), (1, 14)]
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn reorg_checks_are_throttled() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_l1_test_watcher(connection_pool.clone()).await;
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client.add_transactions(&[build_l1_tx(0, 10)]).await;
+    client.set_last_finalized_block_number(5).await;
+    client.set_last_confirmed_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    // There are no new blocks, and processed blocks were recently checked.
+    let block_hash_requests = client.block_hash_requests().await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(client.block_hash_requests().await, block_hash_requests);
+
+    client.set_last_confirmed_block_number(16).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert!(client.block_hash_requests().await > block_hash_requests);
+}
+
+#[test_log::test(tokio::test)]
+async fn rolling_back_priority_ops_fetched_into_mempool() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_l1_test_watcher(connection_pool.clone()).await;
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client
+        .add_transactions(&[build_l1_tx(0, 10), build_l1_tx(1, 14)])
+        .await;
+    client.set_last_finalized_block_number(5).await;
+    client.set_last_confirmed_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    // Fetches transactions into the mempool.
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 2);
+
+    client.reorg(12).await;
+    let err = watcher.loop_iteration(&mut storage).await.unwrap_err();
+    assert_matches!(err, EventProcessorError::Internal(_));
+    assert!(err.to_string().contains("restarting"), "{err}");
+    // Only the operation from the removed block is rolled back.
+    assert_eq!(get_priority_queue_blocks(&mut storage).await, [(0, 10)]);
+
+    // Emulate the node restart.
+    storage.transactions_dal().reset_mempool().await.unwrap();
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(get_priority_queue_blocks(&mut storage).await, [(0, 10)]);
+}
+
+/// Handler recording handled events as `(L1 block, contract, signature, account)` tuples.
+#[derive(Debug, Default)]
+struct RecordingEventHandler(Mutex<Vec<(u64, Address, String, Address)>>);
+
+#[async_trait::async_trait]
+impl CustomEventHandler for RecordingEventHandler {
+    async fn handle_event(
+        &self,
+        _storage: &mut Connection<'_, Core>,
+        event: &CustomEvent,
+    ) -> anyhow::Result<()> {
+        let name = event.signature.split('(').next().unwrap().to_owned();
+        let abi = ethabi::Event {
+            name,
+            inputs: vec![ethabi::EventParam {
+                name: "account".to_owned(),
+                kind: ethabi::ParamType::Address,
+                indexed: false,
+            }],
+            anonymous: false,
+        };
+        let decoded = event.decode(&abi)?;
+        let account = decoded.params[0].value.clone().into_address().unwrap();
+        self.0.lock().unwrap().push((
+            event.block_number().unwrap(),
+            event.contract_address(),
+            event.signature.clone(),
+            account,
+        ));
+        Ok(())
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn routing_custom_events_to_handlers() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+
+    let executor = Address::repeat_byte(0xe0);
+    let pause_contract = Address::repeat_byte(0xe1);
+    let event_configs = [
+        EthWatchCustomEvent {
+            contract_address: executor,
+            event_signature: "CallExecuted(address)".to_owned(),
+            handler: "record".to_owned(),
+        },
+        EthWatchCustomEvent {
+            contract_address: pause_contract,
+            event_signature: "Paused( address )".to_owned(),
+            handler: "record".to_owned(),
+        },
+    ];
+    let (watcher, _) = create_l1_test_watcher(connection_pool.clone()).await;
+    let mut handlers = CustomEventHandlers::default();
+    let err = watcher
+        .with_custom_events(&event_configs, &handlers)
+        .unwrap_err();
+    assert!(err.to_string().contains("not registered"), "{err}");
+
+    let (watcher, mut client) = create_l1_test_watcher(connection_pool.clone()).await;
+    let handler = Arc::new(RecordingEventHandler::default());
+    handlers.insert("record", handler.clone());
+    let mut watcher = watcher
+        .with_custom_events(&event_configs, &handlers)
+        .unwrap();
+
+    let account = Address::repeat_byte(1);
+    client
+        .add_custom_events(&[
+            custom_event_log(executor, "CallExecuted(address)", account, 3),
+            // Not configured for the executor contract
+            custom_event_log(executor, "Paused(address)", account, 4),
+            custom_event_log(pause_contract, "Paused(address)", account, 5),
+            // Not a watched contract
+            custom_event_log(Address::repeat_byte(0xff), "Paused(address)", account, 6),
+        ])
+        .await;
+    client.set_last_finalized_block_number(10).await;
+    let mut storage = connection_pool.connection().await.unwrap();
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    let handled_events = handler.0.lock().unwrap().clone();
+    assert_eq!(
+        handled_events,
+        [
+            (3, executor, "CallExecuted(address)".to_owned(), account),
+            (5, pause_contract, "Paused(address)".to_owned(), account),
+        ]
+    );
+
+    // Events should not be handled again.
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(handler.0.lock().unwrap().len(), 2);
+}
+
+#[test_log::test(tokio::test)]
+async fn bridge_events_are_processed_from_start_block() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+
+    let pause_contract = Address::repeat_byte(0xe1);
+    let bridge = Address::repeat_byte(0xb1);
+    let custom_event = EthWatchCustomEvent {
+        contract_address: pause_contract,
+        event_signature: "Paused(address)".to_owned(),
+        handler: "record".to_owned(),
+    };
+    let bridge_event = EthWatchCustomEvent {
+        contract_address: bridge,
+        event_signature: "Deposit(address)".to_owned(),
+        handler: "bridge".to_owned(),
+    };
+    let custom_handler = Arc::new(RecordingEventHandler::default());
+    let bridge_handler = Arc::new(RecordingEventHandler::default());
+    let mut handlers = CustomEventHandlers::default();
+    handlers.insert("record", custom_handler.clone());
+    handlers.insert("bridge", bridge_handler.clone());
+
+    let (watcher, mut client) = create_l1_test_watcher(connection_pool.clone()).await;
+    let mut watcher = watcher
+        .with_custom_events(slice::from_ref(&custom_event), &handlers)
+        .unwrap()
+        .with_bridge_events(slice::from_ref(&bridge_event), &handlers, 3)
+        .unwrap();
+
+    let account = Address::repeat_byte(1);
+    let last_block = PRIORITY_EXPIRATION + 10;
+    client
+        .add_custom_events(&[
+            custom_event_log(pause_contract, "Paused(address)", account, 5),
+            custom_event_log(bridge, "Deposit(address)", account, 2),
+            custom_event_log(bridge, "Deposit(address)", account, 5),
+            custom_event_log(pause_contract, "Paused(address)", account, last_block),
+            custom_event_log(bridge, "Deposit(address)", account, last_block),
+        ])
+        .await;
+    client.set_last_finalized_block_number(last_block).await;
+    let mut storage = connection_pool.connection().await.unwrap();
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    // Other custom events are processed starting from the default initial block.
+    let handled_events = custom_handler.0.lock().unwrap().clone();
+    assert_eq!(
+        handled_events,
+        [(
+            last_block,
+            pause_contract,
+            "Paused(address)".to_owned(),
+            account
+        )]
+    );
+    let handled_events = bridge_handler.0.lock().unwrap().clone();
+    assert_eq!(
+        handled_events,
+        [
+            (5, bridge, "Deposit(address)".to_owned(), account),
+            (last_block, bridge, "Deposit(address)".to_owned(), account),
+        ]
+    );
+
+    let bridge_cursor = storage
+        .eth_watcher_dal()
+        .get_or_set_next_block_to_process(EventType::BridgeEvents, SLChainId(42), 0)
+        .await
+        .unwrap();
+    assert_eq!(bridge_cursor, last_block + 1);
+}
+
 async fn get_all_db_txs(storage: &mut Connection<'_, Core>) -> Vec<Transaction> {
     storage.transactions_dal().reset_mempool().await.unwrap();
     storage
@@ -20,6 +20,10 @@ pub(crate) enum ProcessorError {
     Internal,
     #[error("Proof verification not possible anymore, batch is too old")]
     ProofIsGone,
+    #[error("Batch {0} is not executed on L1 yet, try again later")]
+    BatchNotExecuted(L1BatchNumber),
+    #[error("Verification data for batch {0} is not available")]
+    VerificationDataUnavailable(L1BatchNumber),
 }
 
 impl ProcessorError {
@@ -31,6 +35,8 @@ impl ProcessorError {
             Self::InvalidFile(_) => StatusCode::BAD_REQUEST,
             Self::BatchNotReady(_) => StatusCode::NOT_FOUND,
             Self::ProofIsGone => StatusCode::GONE,
+            Self::BatchNotExecuted(_) => StatusCode::NOT_FOUND,
+            Self::VerificationDataUnavailable(_) => StatusCode::NOT_FOUND,
         }
     }
 }
@@ -2,6 +2,8 @@ mod error;
 mod metrics;
 mod middleware;
 mod processor;
+#[cfg(test)]
+mod tests;
 mod types;
 
 use std::net::SocketAddr;
@@ -11,12 +13,13 @@ use axum::{
     extract::{Path, Request, State},
     middleware::Next,
     routing::{get, post},
-    Router,
+    Json, Router,
 };
 use error::ProcessorError;
 use tokio::sync::watch;
 use types::{ExternalProof, ProofGenerationDataResponse};
 use zksync_basic_types::L1BatchNumber;
+use zksync_prover_interface::api::ProofVerificationBundle;
 
 pub use crate::processor::Processor;
 use crate::{metrics::Method, middleware::MetricsMiddleware};
@@ -54,6 +57,11 @@ impl Api {
                 "/verify_proof/:l1_batch_number",
                 post(Api::verify_proof).layer(middleware_factory(Method::VerifyProof)),
             )
+            .route(
+                "/proof_verification_bundle/:l1_batch_number",
+                get(Api::proof_verification_bundle)
+                    .layer(middleware_factory(Method::GetProofVerificationBundle)),
+            )
             .with_state(processor);
 
         Self { router, port }
@@ -105,4 +113,14 @@ impl Api {
             .verify_proof(L1BatchNumber(l1_batch_number), proof)
             .await
     }
+
+    async fn proof_verification_bundle(
+        State(processor): State<Processor>,
+        Path(l1_batch_number): Path<u32>,
+    ) -> Result<Json<ProofVerificationBundle>, ProcessorError> {
+        processor
+            .proof_verification_bundle(L1BatchNumber(l1_batch_number))
+            .await
+            .map(Json)
+    }
 }
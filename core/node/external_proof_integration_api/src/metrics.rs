@@ -8,6 +8,7 @@ pub(crate) enum Method {
     GetLatestProofGenerationData,
     GetSpecificProofGenerationData,
     VerifyProof,
+    GetProofVerificationBundle,
 }
 
 #[derive(Debug, Metrics)]
@@ -1,12 +1,13 @@
 use std::sync::Arc;
 
 use zksync_basic_types::{
-    basic_fri_types::Eip4844Blobs, commitment::L1BatchCommitmentMode, L1BatchNumber,
+    basic_fri_types::Eip4844Blobs, commitment::L1BatchCommitmentMode,
+    protocol_version::ProtocolSemanticVersion, web3::keccak256, L1BatchNumber, H256, U256,
 };
 use zksync_dal::{ConnectionPool, Core, CoreDal};
-use zksync_object_store::ObjectStore;
+use zksync_object_store::{ObjectStore, ObjectStoreError};
 use zksync_prover_interface::{
-    api::ProofGenerationData,
+    api::{BatchCommitmentPreimage, ProofGenerationData, ProofVerificationBundle},
     inputs::{
         L1BatchMetadataHashes, VMRunWitnessInputData, WitnessInputData, WitnessInputMerklePaths,
     },
@@ -18,6 +19,15 @@ use crate::{
     types::{ExternalProof, ProofGenerationDataResponse},
 };
 
+/// Number of bits the batch proof public input is shifted by, so that it fits into the scalar field.
+/// Must be kept in sync with `PUBLIC_INPUT_SHIFT` in the L1 `Executor` contract.
+const PUBLIC_INPUT_SHIFT: usize = 32;
+
+fn batch_proof_public_input(previous_batch_commitment: H256, commitment: H256) -> U256 {
+    let hash = keccak256(&[previous_batch_commitment.as_bytes(), commitment.as_bytes()].concat());
+    U256::from_big_endian(&hash) >> PUBLIC_INPUT_SHIFT
+}
+
 /// Backend-agnostic implementation of the API logic.
 #[derive(Clone)]
 pub struct Processor {
@@ -87,6 +97,113 @@ impl Processor {
             .map(ProofGenerationDataResponse)
     }
 
+    /// Returns the final proof for an L1 batch executed on L1 together with all data needed to verify it
+    /// independently.
+    pub(crate) async fn proof_verification_bundle(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<ProofVerificationBundle, ProcessorError> {
+        tracing::debug!(
+            "Received request for proof verification bundle for batch: {l1_batch_number:?}"
+        );
+        // The genesis batch is never proven.
+        let previous_batch_number = l1_batch_number
+            .checked_sub(1)
+            .ok_or(ProcessorError::VerificationDataUnavailable(l1_batch_number))?;
+
+        let mut conn = self.pool.connection().await?;
+        let last_executed_batch = conn
+            .blocks_dal()
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await?;
+        if last_executed_batch.map_or(true, |number| number < l1_batch_number) {
+            return Err(ProcessorError::BatchNotExecuted(l1_batch_number));
+        }
+
+        let l1_batch = conn
+            .blocks_dal()
+            .get_l1_batch_metadata(l1_batch_number)
+            .await?
+            .ok_or(ProcessorError::VerificationDataUnavailable(l1_batch_number))?;
+        let previous_batch = conn
+            .blocks_dal()
+            .get_l1_batch_metadata(L1BatchNumber(previous_batch_number))
+            .await?
+            .ok_or(ProcessorError::VerificationDataUnavailable(l1_batch_number))?;
+        // The preimage is missing for batches processed before it started being persisted.
+        let aux_data = conn
+            .blocks_dal()
+            .get_l1_batch_aux_data_preimage(l1_batch_number)
+            .await?
+            .ok_or(ProcessorError::VerificationDataUnavailable(l1_batch_number))?;
+        let minor_version = l1_batch.header.protocol_version.ok_or_else(|| {
+            tracing::error!("Missing protocol version for executed batch {l1_batch_number}");
+            ProcessorError::Internal
+        })?;
+        // Proofs are stored per semantic version, so check all patches of the batch version, newest first.
+        let mut versions: Vec<_> = conn
+            .protocol_versions_dal()
+            .all_versions()
+            .await
+            .into_iter()
+            .filter(|version| version.minor == minor_version)
+            .collect();
+        versions.sort_unstable_by(|a, b| b.cmp(a));
+        // Release the connection while interacting with the object store.
+        drop(conn);
+
+        let (protocol_version, proof) = self.load_proof(l1_batch_number, &versions).await?;
+        let l1_verifier_config = self
+            .pool
+            .connection()
+            .await?
+            .protocol_versions_dal()
+            .l1_verifier_config_for_version(protocol_version)
+            .await
+            .ok_or_else(|| {
+                tracing::error!(
+                    "Missing L1 verifier config for protocol version {protocol_version}"
+                );
+                ProcessorError::Internal
+            })?;
+
+        let metadata = &l1_batch.metadata;
+        let previous_batch_commitment = previous_batch.metadata.commitment;
+        Ok(ProofVerificationBundle {
+            l1_batch_number,
+            protocol_version,
+            l1_verifier_config,
+            proof: Box::new(proof),
+            public_input: batch_proof_public_input(previous_batch_commitment, metadata.commitment),
+            previous_batch_commitment,
+            commitment: BatchCommitmentPreimage {
+                commitment: metadata.commitment,
+                pass_through_data_hash: metadata.pass_through_data_hash,
+                meta_parameters_hash: metadata.meta_parameters_hash,
+                aux_data_hash: metadata.aux_data_hash,
+                root_hash: metadata.root_hash,
+                rollup_last_leaf_index: metadata.rollup_last_leaf_index,
+                meta_parameters: metadata.block_meta_params.to_bytes(),
+                aux_data,
+            },
+        })
+    }
+
+    async fn load_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        versions: &[ProtocolSemanticVersion],
+    ) -> Result<(ProtocolSemanticVersion, L1BatchProofForL1), ProcessorError> {
+        for &version in versions {
+            match self.blob_store.get((l1_batch_number, version)).await {
+                Ok(proof) => return Ok((version, proof)),
+                Err(ObjectStoreError::KeyNotFound(_)) => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Err(ProcessorError::ProofIsGone)
+    }
+
     async fn latest_available_batch(&self) -> Result<L1BatchNumber, ProcessorError> {
         Ok(self
             .pool
@@ -0,0 +1,173 @@
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode},
+};
+use tower::ServiceExt;
+use zksync_basic_types::{
+    commitment::L1BatchCommitmentMode,
+    protocol_version::{ProtocolSemanticVersion, ProtocolVersionId},
+    web3::keccak256,
+    H256, U256,
+};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_node_test_utils::{
+    create_l1_batch, create_l1_batch_metadata, l1_batch_metadata_to_commitment_artifacts,
+};
+use zksync_object_store::MockObjectStore;
+use zksync_prover_interface::{api::ProofVerificationBundle, outputs::L1BatchProofForL1};
+use zksync_types::{aggregated_operations::AggregatedActionType, ProtocolVersion};
+
+use super::*;
+
+const PROOF: &[u8] =
+    include_bytes!("../../../lib/prover_interface/tests/l1_batch_proof_1_0_24_0.bin");
+
+async fn seal_l1_batch_with_metadata(pool: &ConnectionPool<Core>, number: u32) {
+    let mut storage = pool.connection().await.unwrap();
+    let l1_batch_number = L1BatchNumber(number);
+    storage
+        .blocks_dal()
+        .insert_mock_l1_batch(&create_l1_batch(number))
+        .await
+        .unwrap();
+    let metadata = create_l1_batch_metadata(number);
+    storage
+        .blocks_dal()
+        .save_l1_batch_tree_data(l1_batch_number, &metadata.tree_data())
+        .await
+        .unwrap();
+    let mut artifacts = l1_batch_metadata_to_commitment_artifacts(&metadata);
+    artifacts.aux_output_preimage = Some(vec![number as u8; 128]);
+    storage
+        .blocks_dal()
+        .save_l1_batch_commitment_artifacts(l1_batch_number, &artifacts)
+        .await
+        .unwrap();
+}
+
+async fn get_bundle(router: &Router, l1_batch_number: u32) -> axum::response::Response {
+    router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::GET)
+                .uri(format!("/proof_verification_bundle/{l1_batch_number}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn getting_proof_verification_bundle() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let protocol_version = ProtocolSemanticVersion {
+        minor: ProtocolVersionId::latest(),
+        patch: 0.into(),
+    };
+    let mut storage = pool.connection().await.unwrap();
+    storage
+        .protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion {
+            version: protocol_version,
+            ..ProtocolVersion::default()
+        })
+        .await
+        .unwrap();
+    drop(storage);
+    seal_l1_batch_with_metadata(&pool, 0).await;
+    seal_l1_batch_with_metadata(&pool, 1).await;
+
+    let blob_store = MockObjectStore::arc();
+    let proof: L1BatchProofForL1 = bincode::deserialize(PROOF).unwrap();
+    blob_store
+        .put((L1BatchNumber(1), protocol_version), &proof)
+        .await
+        .unwrap();
+    let processor = Processor::new(blob_store, pool.clone(), L1BatchCommitmentMode::Rollup);
+    let router = Api::new(processor, 0).router;
+
+    // The batch is not executed on L1 yet.
+    let response = get_bundle(&router, 1).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let mut storage = pool.connection().await.unwrap();
+    storage
+        .eth_sender_dal()
+        .insert_bogus_confirmed_eth_tx(
+            L1BatchNumber(1),
+            AggregatedActionType::Execute,
+            H256::repeat_byte(1),
+            chrono::Utc::now(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    // The genesis batch is never proven.
+    let response = get_bundle(&router, 0).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = get_bundle(&router, 1).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let bundle: ProofVerificationBundle = serde_json::from_slice(&body).unwrap();
+
+    let batch = storage
+        .blocks_dal()
+        .get_l1_batch_metadata(L1BatchNumber(1))
+        .await
+        .unwrap()
+        .unwrap();
+    let previous_batch = storage
+        .blocks_dal()
+        .get_l1_batch_metadata(L1BatchNumber(0))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(bundle.l1_batch_number, L1BatchNumber(1));
+    assert_eq!(bundle.protocol_version, protocol_version);
+    assert_eq!(
+        bundle.proof.aggregation_result_coords,
+        proof.aggregation_result_coords
+    );
+    assert_eq!(
+        bundle.previous_batch_commitment,
+        previous_batch.metadata.commitment
+    );
+    let commitment = &bundle.commitment;
+    assert_eq!(commitment.commitment, batch.metadata.commitment);
+    assert_eq!(commitment.aux_data_hash, batch.metadata.aux_data_hash);
+    assert_eq!(commitment.aux_data, [1; 128]);
+    assert_eq!(
+        commitment.meta_parameters,
+        batch.metadata.block_meta_params.to_bytes()
+    );
+    let expected_public_input = U256::from_big_endian(&keccak256(
+        &[
+            previous_batch.metadata.commitment.as_bytes(),
+            batch.metadata.commitment.as_bytes(),
+        ]
+        .concat(),
+    )) >> 32;
+    assert_eq!(bundle.public_input, expected_public_input);
+
+    // Batches without a proof are reported as gone.
+    seal_l1_batch_with_metadata(&pool, 2).await;
+    storage
+        .eth_sender_dal()
+        .insert_bogus_confirmed_eth_tx(
+            L1BatchNumber(2),
+            AggregatedActionType::Execute,
+            H256::repeat_byte(2),
+            chrono::Utc::now(),
+            None,
+        )
+        .await
+        .unwrap();
+    let response = get_bundle(&router, 2).await;
+    assert_eq!(response.status(), StatusCode::GONE);
+}
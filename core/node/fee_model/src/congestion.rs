@@ -0,0 +1,295 @@
+//! Backpressure applied when the proof / execution pipeline lags behind L1 batch sealing.
+
+use std::time::Duration;
+
+use tokio::sync::watch;
+use zksync_config::configs::chain::StateKeeperConfig;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_types::{api::PipelineCongestion, fee_model::BatchFeeInput, L1BatchNumber};
+
+/// Thresholds on the backlog of sealed L1 batches not proven or executed on L1. If any of the thresholds is exceeded,
+/// the pipeline is considered congested; the state keeper slows down L1 batch production, and the API bumps
+/// the L2 gas price. This prevents unbounded divergence between the sealed and finalized chain state.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PipelineBackpressure {
+    max_unproven_l1_batches: Option<u32>,
+    max_unexecuted_l1_batches: Option<u32>,
+    min_l1_batch_interval: Duration,
+    gas_price_multiplier: f64,
+}
+
+impl PipelineBackpressure {
+    const DEFAULT_MIN_L1_BATCH_INTERVAL: Duration = Duration::from_secs(60);
+    const DEFAULT_GAS_PRICE_MULTIPLIER: f64 = 1.5;
+
+    /// Creates backpressure based on the state keeper config. Returns `None` if no backlog thresholds are configured.
+    pub fn new(config: &StateKeeperConfig) -> Option<Self> {
+        if config.congestion_max_unproven_l1_batches.is_none()
+            && config.congestion_max_unexecuted_l1_batches.is_none()
+        {
+            return None;
+        }
+        Some(Self {
+            max_unproven_l1_batches: config.congestion_max_unproven_l1_batches,
+            max_unexecuted_l1_batches: config.congestion_max_unexecuted_l1_batches,
+            min_l1_batch_interval: config
+                .congestion_min_l1_batch_interval_ms
+                .map_or(Self::DEFAULT_MIN_L1_BATCH_INTERVAL, Duration::from_millis),
+            // Multipliers < 1 would make the L2 gas price *lower* during congestion, which makes no sense.
+            gas_price_multiplier: config
+                .congestion_gas_price_multiplier
+                .unwrap_or(Self::DEFAULT_GAS_PRICE_MULTIPLIER)
+                .max(1.0),
+        })
+    }
+
+    /// Minimum interval between opening L1 batches while the pipeline is congested.
+    pub fn min_l1_batch_interval(&self) -> Duration {
+        self.min_l1_batch_interval
+    }
+
+    /// Checks the current backlog of L1 batches.
+    pub async fn check(
+        &self,
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<PipelineCongestion> {
+        let sealed_l1_batch = storage
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await?
+            .unwrap_or_default();
+        // The genesis batch is never proven or executed on L1, so it's not included into the backlog.
+        let last_proven_l1_batch = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_proven_on_eth()
+            .await?
+            .unwrap_or_default();
+        let last_executed_l1_batch = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await?
+            .unwrap_or_default();
+
+        let backlog =
+            |last_l1_batch: L1BatchNumber| sealed_l1_batch.0.saturating_sub(last_l1_batch.0);
+        let unproven_l1_batches = backlog(last_proven_l1_batch);
+        let unexecuted_l1_batches = backlog(last_executed_l1_batch);
+        let is_congested = self
+            .max_unproven_l1_batches
+            .is_some_and(|max| unproven_l1_batches > max)
+            || self
+                .max_unexecuted_l1_batches
+                .is_some_and(|max| unexecuted_l1_batches > max);
+
+        Ok(PipelineCongestion {
+            sealed_l1_batch,
+            unproven_l1_batches,
+            unexecuted_l1_batches,
+            max_unproven_l1_batches: self.max_unproven_l1_batches,
+            max_unexecuted_l1_batches: self.max_unexecuted_l1_batches,
+            is_congested,
+            gas_price_multiplier: if is_congested {
+                self.gas_price_multiplier
+            } else {
+                1.0
+            },
+        })
+    }
+
+    /// Applies the congestion gas price bump to the fair L2 gas price in the provided fee input.
+    pub(crate) fn apply(
+        congestion: &PipelineCongestion,
+        fee_input: BatchFeeInput,
+    ) -> BatchFeeInput {
+        if !congestion.is_congested {
+            return fee_input;
+        }
+        let bump = |price: u64| (price as f64 * congestion.gas_price_multiplier) as u64;
+        match fee_input {
+            BatchFeeInput::L1Pegged(input) => {
+                BatchFeeInput::l1_pegged(input.l1_gas_price, bump(input.fair_l2_gas_price))
+            }
+            BatchFeeInput::PubdataIndependent(input) => BatchFeeInput::pubdata_independent(
+                input.l1_gas_price,
+                bump(input.fair_l2_gas_price),
+                input.fair_pubdata_price,
+            ),
+        }
+    }
+}
+
+/// Background task periodically checking [pipeline congestion](PipelineBackpressure) and broadcasting
+/// the result, so that consumers (e.g., the API fee input provider) don't need to query the L1 batch backlog
+/// on each request.
+#[derive(Debug)]
+pub struct PipelineCongestionMonitor {
+    backpressure: PipelineBackpressure,
+    pool: ConnectionPool<Core>,
+    poll_interval: Duration,
+    sender: watch::Sender<Option<PipelineCongestion>>,
+}
+
+impl PipelineCongestionMonitor {
+    const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+    pub fn new(backpressure: PipelineBackpressure, pool: ConnectionPool<Core>) -> Self {
+        Self {
+            backpressure,
+            pool,
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+            sender: watch::channel(None).0,
+        }
+    }
+
+    /// Returns a receiver for the latest checked congestion. The value is `None` until the first check completes.
+    pub fn subscribe(&self) -> watch::Receiver<Option<PipelineCongestion>> {
+        self.sender.subscribe()
+    }
+
+    async fn update(&self) -> anyhow::Result<()> {
+        let mut storage = self
+            .pool
+            .connection_tagged("pipeline_congestion_monitor")
+            .await?;
+        let congestion = self.backpressure.check(&mut storage).await?;
+        drop(storage);
+        self.sender.send_replace(Some(congestion));
+        Ok(())
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow_and_update() {
+            self.update().await?;
+            if tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, pipeline congestion monitor is shutting down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use zksync_types::{
+        block::L1BatchHeader, fee_model::FeeParams, ProtocolVersion, ProtocolVersionId,
+    };
+
+    use super::*;
+    use crate::{ApiFeeInputProvider, BatchFeeModelInputProvider, MockBatchFeeParamsProvider};
+
+    fn backpressure_config(max_unproven_l1_batches: Option<u32>) -> StateKeeperConfig {
+        StateKeeperConfig {
+            congestion_max_unproven_l1_batches: max_unproven_l1_batches,
+            congestion_gas_price_multiplier: Some(2.0),
+            ..StateKeeperConfig::for_tests()
+        }
+    }
+
+    #[test]
+    fn backpressure_is_disabled_without_thresholds() {
+        assert_eq!(PipelineBackpressure::new(&backpressure_config(None)), None);
+    }
+
+    #[tokio::test]
+    async fn checking_congestion() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+
+        let backpressure = PipelineBackpressure::new(&backpressure_config(Some(2))).unwrap();
+        let congestion = backpressure.check(&mut storage).await.unwrap();
+        assert!(!congestion.is_congested, "{congestion:?}");
+        assert_eq!(congestion.gas_price_multiplier, 1.0);
+
+        for number in 0..=3 {
+            let header = L1BatchHeader::new(
+                L1BatchNumber(number),
+                number.into(),
+                Default::default(),
+                ProtocolVersionId::latest(),
+            );
+            storage
+                .blocks_dal()
+                .insert_mock_l1_batch(&header)
+                .await
+                .unwrap();
+        }
+
+        let congestion = backpressure.check(&mut storage).await.unwrap();
+        assert_eq!(congestion.sealed_l1_batch, L1BatchNumber(3));
+        assert_eq!(congestion.unproven_l1_batches, 3);
+        assert_eq!(congestion.unexecuted_l1_batches, 3);
+        assert!(congestion.is_congested);
+        assert_eq!(congestion.gas_price_multiplier, 2.0);
+
+        let fee_input = BatchFeeInput::pubdata_independent(100, 1_000, 10);
+        assert_eq!(
+            PipelineBackpressure::apply(&congestion, fee_input),
+            BatchFeeInput::pubdata_independent(100, 2_000, 10)
+        );
+    }
+
+    #[tokio::test]
+    async fn api_fee_input_is_bumped_using_monitored_congestion() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in 0..=3 {
+            let header = L1BatchHeader::new(
+                L1BatchNumber(number),
+                number.into(),
+                Default::default(),
+                ProtocolVersionId::latest(),
+            );
+            storage
+                .blocks_dal()
+                .insert_mock_l1_batch(&header)
+                .await
+                .unwrap();
+        }
+
+        let backpressure = PipelineBackpressure::new(&backpressure_config(Some(2))).unwrap();
+        let monitor = PipelineCongestionMonitor::new(backpressure, pool.clone());
+        let mut congestion_receiver = monitor.subscribe();
+        let fee_params = FeeParams::sensible_v1_default();
+        let provider = ApiFeeInputProvider::new(
+            Arc::new(MockBatchFeeParamsProvider(fee_params)),
+            pool.clone(),
+        )
+        .with_congestion(monitor.subscribe());
+        let provider: &dyn BatchFeeModelInputProvider = &provider;
+
+        // Congestion isn't checked yet, so the fee input isn't bumped.
+        assert_eq!(provider.get_pipeline_congestion().await.unwrap(), None);
+        let fee_input = provider.get_batch_fee_input().await.unwrap();
+        assert_eq!(fee_input, fee_params.scale(1.0, 1.0));
+
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let monitor_task = tokio::spawn(monitor.run(stop_receiver));
+        congestion_receiver.wait_for(Option::is_some).await.unwrap();
+        let congestion = provider.get_pipeline_congestion().await.unwrap().unwrap();
+        assert!(congestion.is_congested);
+        let bumped_fee_input = provider.get_batch_fee_input().await.unwrap();
+        assert_eq!(
+            bumped_fee_input.fair_l2_gas_price(),
+            fee_input.fair_l2_gas_price() * 2
+        );
+
+        stop_sender.send_replace(true);
+        monitor_task.await.unwrap().unwrap();
+    }
+}
@@ -1,16 +1,81 @@
 //! Gas adjuster metrics.
 
-use vise::{Gauge, Metrics};
+use vise::{EncodeLabelSet, EncodeLabelValue, Gauge, Info, Metrics};
+use zksync_config::{configs::eth_sender::FeeSmoothingStrategy, GasAdjusterConfig};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+enum FeeSmoothingStrategyLabel {
+    Median,
+    Ema,
+    Percentile,
+}
+
+impl From<FeeSmoothingStrategy> for FeeSmoothingStrategyLabel {
+    fn from(strategy: FeeSmoothingStrategy) -> Self {
+        match strategy {
+            FeeSmoothingStrategy::Median => Self::Median,
+            FeeSmoothingStrategy::Ema => Self::Ema,
+            FeeSmoothingStrategy::Percentile => Self::Percentile,
+        }
+    }
+}
+
+/// Fee smoothing configuration used by the gas adjuster.
+#[derive(Debug, EncodeLabelSet)]
+pub(super) struct FeeSmoothingInfo {
+    strategy: FeeSmoothingStrategyLabel,
+    ema_alpha: Option<f64>,
+    percentile: Option<f64>,
+    max_fee_change_per_update: Option<f64>,
+    blob_base_fee_prediction_blocks: Option<u64>,
+}
+
+impl FeeSmoothingInfo {
+    pub fn new(config: &GasAdjusterConfig) -> Self {
+        let strategy = config.fee_smoothing_strategy;
+        Self {
+            strategy: strategy.into(),
+            ema_alpha: (strategy == FeeSmoothingStrategy::Ema)
+                .then_some(config.fee_smoothing_ema_alpha),
+            percentile: (strategy == FeeSmoothingStrategy::Percentile)
+                .then_some(config.fee_smoothing_percentile),
+            max_fee_change_per_update: config.max_fee_change_per_update,
+            blob_base_fee_prediction_blocks: config.blob_base_fee_prediction_blocks,
+        }
+    }
+}
 
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "server_gas_adjuster")]
 pub(super) struct GasAdjusterMetrics {
+    /// Fee smoothing configuration.
+    smoothing_info: Info<FeeSmoothingInfo>,
     pub current_base_fee_per_gas: Gauge<u64>,
     pub current_blob_base_fee: Gauge<u64>,
     pub current_l2_pubdata_price: Gauge<u64>,
     pub median_base_fee_per_gas: Gauge<u64>,
     pub median_blob_base_fee_per_gas: Gauge<u64>,
     pub median_blob_base_fee: Gauge<u64>,
+    /// Base fee per gas after applying the configured smoothing strategy.
+    pub smoothed_base_fee_per_gas: Gauge<u64>,
+    /// Blob base fee after applying the configured smoothing strategy.
+    pub smoothed_blob_base_fee: Gauge<u64>,
+    /// Blob base fee predicted for the configured number of blocks ahead.
+    pub predicted_blob_base_fee: Gauge<u64>,
+}
+
+impl GasAdjusterMetrics {
+    pub fn observe_smoothing_info(&self, info: FeeSmoothingInfo) {
+        tracing::info!("Using fee smoothing configuration: {info:?}");
+        if let Err(err) = self.smoothing_info.set(info) {
+            tracing::warn!(
+                "Error setting fee smoothing info {:?}; already set to {:?}",
+                err.into_inner(),
+                self.smoothing_info.get()
+            );
+        }
+    }
 }
 
 #[vise::register]
@@ -14,10 +14,14 @@ use zksync_types::{
 };
 use zksync_web3_decl::client::{DynClient, L1, L2};
 
-use self::metrics::METRICS;
+use self::{
+    metrics::{FeeSmoothingInfo, METRICS},
+    smoothing::{FeeSmoothing, GasSample, SmoothingStrategy},
+};
 use super::TxParamsProvider;
 
 mod metrics;
+mod smoothing;
 #[cfg(test)]
 mod tests;
 
@@ -58,6 +62,10 @@ impl From<Box<DynClient<L2>>> for GasAdjusterClient {
 /// This component keeps track of the median `base_fee` from the last `max_base_fee_samples` blocks
 /// and of the median `blob_base_fee` from the last `max_blob_base_fee_sample` blocks.
 /// It is used to adjust the base_fee of transactions sent to L1.
+///
+/// Fees used for pricing are smoothed according to the configured strategy (median, EMA or percentile
+/// over the sample window, optionally with bounded changes per update), so that L1 fee spikes
+/// don't immediately translate into L2 fee spikes.
 #[derive(Debug)]
 pub struct GasAdjuster {
     pub(super) base_fee_statistics: GasStatistics<u64>,
@@ -114,22 +122,28 @@ impl GasAdjuster {
             .base_fee_history(current_block, config.max_base_fee_samples)
             .await?;
 
+        let smoothing = FeeSmoothing::new(&config);
+        METRICS.observe_smoothing_info(FeeSmoothingInfo::new(&config));
+
         let base_fee_statistics = GasStatistics::new(
             config.max_base_fee_samples,
             current_block,
             fee_history.iter().map(|fee| fee.base_fee_per_gas),
+            smoothing,
         );
 
         let blob_base_fee_statistics = GasStatistics::new(
             config.num_samples_for_blob_base_fee_estimate,
             current_block,
             fee_history.iter().map(|fee| fee.base_fee_per_blob_gas),
+            smoothing,
         );
 
         let l2_pubdata_price_statistics = GasStatistics::new(
             config.num_samples_for_blob_base_fee_estimate,
             current_block,
             fee_history.iter().map(|fee| fee.l2_pubdata_price),
+            smoothing,
         );
 
         Ok(Self {
@@ -266,18 +280,15 @@ impl GasAdjuster {
             PubdataSendingMode::Blobs => {
                 const BLOB_GAS_PER_BYTE: u64 = 1; // `BYTES_PER_BLOB` = `GAS_PER_BLOB` = 2 ^ 17.
 
-                let blob_base_fee_median = self.blob_base_fee_statistics.median();
+                let blob_base_fee = self.estimate_blob_base_fee();
 
                 // Check if blob base fee overflows `u64` before converting. Can happen only in very extreme cases.
-                if blob_base_fee_median > U256::from(u64::MAX) {
+                if blob_base_fee > U256::from(u64::MAX) {
                     let max_allowed = self.config.max_blob_base_fee();
-                    tracing::error!("Blob base fee is too high: {blob_base_fee_median}, using max allowed: {max_allowed}");
+                    tracing::error!("Blob base fee is too high: {blob_base_fee}, using max allowed: {max_allowed}");
                     return max_allowed;
                 }
-                METRICS
-                    .median_blob_base_fee
-                    .set(blob_base_fee_median.as_u64());
-                let calculated_price = blob_base_fee_median.as_u64() as f64
+                let calculated_price = blob_base_fee.as_u64() as f64
                     * BLOB_GAS_PER_BYTE as f64
                     * self.config.internal_pubdata_pricing_multiplier;
 
@@ -292,11 +303,29 @@ impl GasAdjuster {
                 0
             }
             PubdataSendingMode::RelayedL2Calldata => {
-                self.cap_pubdata_fee(self.l2_pubdata_price_statistics.median().as_u64() as f64)
+                self.cap_pubdata_fee(self.l2_pubdata_price_statistics.smoothed().as_u64() as f64)
             }
         }
     }
 
+    /// Returns the smoothed blob base fee. If blob base fee prediction is enabled, the fee is additionally
+    /// raised to the predicted value if the latter is greater.
+    fn estimate_blob_base_fee(&self) -> U256 {
+        let median = self.blob_base_fee_statistics.median();
+        let smoothed = self.blob_base_fee_statistics.smoothed();
+        METRICS.median_blob_base_fee.set(saturating_u64(median));
+        METRICS.smoothed_blob_base_fee.set(saturating_u64(smoothed));
+
+        let Some(blocks_ahead) = self.config.blob_base_fee_prediction_blocks else {
+            return smoothed;
+        };
+        let predicted = self.blob_base_fee_statistics.predict(blocks_ahead);
+        METRICS
+            .predicted_blob_base_fee
+            .set(saturating_u64(predicted));
+        smoothed.max(predicted)
+    }
+
     fn cap_pubdata_fee(&self, pubdata_fee: f64) -> u64 {
         // We will treat the max blob base fee as the maximal fee that we can take for each byte of pubdata.
         let max_blob_base_fee = self.config.max_blob_base_fee();
@@ -330,7 +359,9 @@ impl TxParamsProvider for GasAdjuster {
         let scale_factor = a * b.powf(time_in_mempool_in_l1_blocks as f64);
         let median = self.base_fee_statistics.median();
         METRICS.median_base_fee_per_gas.set(median);
-        let new_fee = median as f64 * scale_factor;
+        let smoothed = self.base_fee_statistics.smoothed();
+        METRICS.smoothed_base_fee_per_gas.set(smoothed);
+        let new_fee = smoothed as f64 * scale_factor;
         new_fee as u64
     }
 
@@ -377,22 +408,42 @@ impl TxParamsProvider for GasAdjuster {
     }
 }
 
+fn saturating_u64(value: U256) -> u64 {
+    if value > U256::from(u64::MAX) {
+        u64::MAX
+    } else {
+        value.as_u64()
+    }
+}
+
 /// Helper structure responsible for collecting the data about recent transactions,
-/// calculating the median base fee.
+/// calculating the median and smoothed base fee.
 #[derive(Debug, Clone, Default)]
 pub(super) struct GasStatisticsInner<T> {
     samples: VecDeque<T>,
     median_cached: T,
+    smoothed_cached: T,
+    /// Exponential moving average of all samples; only maintained for the EMA smoothing strategy.
+    ema: Option<f64>,
+    smoothing: FeeSmoothing,
     max_samples: usize,
     last_processed_block: usize,
 }
 
-impl<T: Ord + Copy + Default> GasStatisticsInner<T> {
-    fn new(max_samples: usize, block: usize, fee_history: impl IntoIterator<Item = T>) -> Self {
+impl<T: GasSample> GasStatisticsInner<T> {
+    fn new(
+        max_samples: usize,
+        block: usize,
+        fee_history: impl IntoIterator<Item = T>,
+        smoothing: FeeSmoothing,
+    ) -> Self {
         let mut statistics = Self {
             max_samples,
             samples: VecDeque::with_capacity(max_samples),
             median_cached: T::default(),
+            smoothed_cached: T::default(),
+            ema: None,
+            smoothing,
             last_processed_block: 0,
         };
 
@@ -408,6 +459,20 @@ impl<T: Ord + Copy + Default> GasStatisticsInner<T> {
         self.median_cached
     }
 
+    fn smoothed(&self) -> T {
+        self.smoothed_cached
+    }
+
+    /// Predicts the fee `blocks_ahead` blocks after the last added sample.
+    fn predict(&self, blocks_ahead: u64) -> T {
+        match (self.samples.front(), self.samples.back()) {
+            (Some(&first), Some(&last)) => {
+                FeeSmoothing::predict(first, last, self.samples.len(), blocks_ahead)
+            }
+            _ => self.smoothed_cached,
+        }
+    }
+
     fn last_added_value(&self) -> T {
         self.samples.back().copied().unwrap_or(self.median_cached)
     }
@@ -427,18 +492,48 @@ impl<T: Ord + Copy + Default> GasStatisticsInner<T> {
             let (_, &mut median, _) = samples.select_nth_unstable(self.samples.len() / 2);
             self.median_cached = median;
         }
+        if processed_blocks > 0 {
+            let new_samples = processed_blocks.min(self.samples.len());
+            self.update_smoothed(&mut samples, new_samples);
+        }
+    }
+
+    fn update_smoothed(&mut self, samples: &mut [T], new_samples: usize) {
+        let target = match self.smoothing.strategy {
+            SmoothingStrategy::Median => self.median_cached,
+            SmoothingStrategy::Ema { alpha } => {
+                let new_samples = self.samples.iter().skip(self.samples.len() - new_samples);
+                self.ema = FeeSmoothing::update_ema(alpha, self.ema, new_samples.copied());
+                self.ema.map_or(self.median_cached, T::from_f64)
+            }
+            SmoothingStrategy::Percentile { percentile } => {
+                FeeSmoothing::percentile(samples, percentile)
+            }
+        };
+        let previous = self.smoothed_cached.to_f64();
+        self.smoothed_cached = if self.smoothing.max_change_per_update.is_some() {
+            T::from_f64(self.smoothing.bound_change(previous, target.to_f64()))
+        } else {
+            target
+        };
     }
 }
 
 #[derive(Debug, Default)]
 pub(super) struct GasStatistics<T>(RwLock<GasStatisticsInner<T>>);
 
-impl<T: Ord + Copy + Default> GasStatistics<T> {
-    pub fn new(max_samples: usize, block: usize, fee_history: impl IntoIterator<Item = T>) -> Self {
+impl<T: GasSample> GasStatistics<T> {
+    pub fn new(
+        max_samples: usize,
+        block: usize,
+        fee_history: impl IntoIterator<Item = T>,
+        smoothing: FeeSmoothing,
+    ) -> Self {
         Self(RwLock::new(GasStatisticsInner::new(
             max_samples,
             block,
             fee_history,
+            smoothing,
         )))
     }
 
@@ -446,6 +541,14 @@ impl<T: Ord + Copy + Default> GasStatistics<T> {
         self.0.read().unwrap().median()
     }
 
+    pub fn smoothed(&self) -> T {
+        self.0.read().unwrap().smoothed()
+    }
+
+    pub fn predict(&self, blocks_ahead: u64) -> T {
+        self.0.read().unwrap().predict(blocks_ahead)
+    }
+
     pub fn last_added_value(&self) -> T {
         self.0.read().unwrap().last_added_value()
     }
@@ -0,0 +1,135 @@
+//! Smoothing of fee samples collected by the gas adjuster.
+
+use zksync_config::{configs::eth_sender::FeeSmoothingStrategy, GasAdjusterConfig};
+use zksync_types::U256;
+
+/// Maximum per-block growth factor of the blob base fee according to EIP-4844 (12.5%).
+const MAX_BLOB_BASE_FEE_GROWTH_PER_BLOCK: f64 = 1.125;
+
+/// Fee sample that can be smoothed. Conversions to and from `f64` are lossy and saturating.
+pub(crate) trait GasSample: Ord + Copy + Default {
+    fn to_f64(self) -> f64;
+
+    fn from_f64(value: f64) -> Self;
+}
+
+impl GasSample for u64 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn from_f64(value: f64) -> Self {
+        // Float-to-int casts saturate and map NaN to 0.
+        value as u64
+    }
+}
+
+impl GasSample for U256 {
+    fn to_f64(self) -> f64 {
+        self.0
+            .iter()
+            .rev()
+            .fold(0.0, |acc, &limb| acc * 2.0_f64.powi(64) + limb as f64)
+    }
+
+    fn from_f64(value: f64) -> Self {
+        // Values used by the gas adjuster are capped by `u64` limits, so saturating at `u128::MAX` is fine.
+        U256::from(value as u128)
+    }
+}
+
+/// Strategy used to derive a single fee value from a window of samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum SmoothingStrategy {
+    Median,
+    /// Exponential moving average over all observed samples.
+    Ema {
+        alpha: f64,
+    },
+    /// Percentile over the sample window.
+    Percentile {
+        percentile: f64,
+    },
+}
+
+/// Smoothing parameters for [`GasStatistics`](super::GasStatistics).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct FeeSmoothing {
+    pub strategy: SmoothingStrategy,
+    /// Maximum relative change of the smoothed value per update.
+    pub max_change_per_update: Option<f64>,
+}
+
+impl Default for FeeSmoothing {
+    fn default() -> Self {
+        Self {
+            strategy: SmoothingStrategy::Median,
+            max_change_per_update: None,
+        }
+    }
+}
+
+impl FeeSmoothing {
+    pub fn new(config: &GasAdjusterConfig) -> Self {
+        let strategy = match config.fee_smoothing_strategy {
+            FeeSmoothingStrategy::Median => SmoothingStrategy::Median,
+            FeeSmoothingStrategy::Ema => SmoothingStrategy::Ema {
+                alpha: config.fee_smoothing_ema_alpha.clamp(0.0, 1.0),
+            },
+            FeeSmoothingStrategy::Percentile => SmoothingStrategy::Percentile {
+                percentile: config.fee_smoothing_percentile.clamp(0.0, 100.0),
+            },
+        };
+        Self {
+            strategy,
+            max_change_per_update: config
+                .max_fee_change_per_update
+                .map(|change| change.max(0.0)),
+        }
+    }
+
+    /// Updates the exponential moving average with new samples. Returns `None` if there are no samples.
+    pub fn update_ema<T: GasSample>(
+        alpha: f64,
+        ema: Option<f64>,
+        new_samples: impl Iterator<Item = T>,
+    ) -> Option<f64> {
+        new_samples.fold(ema, |ema, sample| {
+            let sample = sample.to_f64();
+            Some(ema.map_or(sample, |ema| alpha * sample + (1.0 - alpha) * ema))
+        })
+    }
+
+    /// Returns the sample at the specified percentile of `samples`, which must be non-empty.
+    pub fn percentile<T: GasSample>(samples: &mut [T], percentile: f64) -> T {
+        let max_index = samples.len() - 1;
+        let index = ((max_index as f64 * percentile / 100.0).round() as usize).min(max_index);
+        *samples.select_nth_unstable(index).1
+    }
+
+    /// Bounds the change of the smoothed value relative to its previous value.
+    pub fn bound_change(&self, previous: f64, target: f64) -> f64 {
+        match self.max_change_per_update {
+            // Without a meaningful previous value, there's nothing to bound the change against.
+            Some(max_change) if previous > 0.0 => target.clamp(
+                previous * (1.0 - max_change).max(0.0),
+                previous * (1.0 + max_change),
+            ),
+            _ => target,
+        }
+    }
+
+    /// Extrapolates the fee `blocks_ahead` blocks into the future based on the average per-block growth
+    /// between the first and the last sample. Growth is bounded by the maximum growth of the blob base fee.
+    pub fn predict<T: GasSample>(first: T, last: T, len: usize, blocks_ahead: u64) -> T {
+        let (first, last) = (first.to_f64(), last.to_f64());
+        if len < 2 || first <= 0.0 {
+            return T::from_f64(last);
+        }
+        let growth = (last / first).powf(1.0 / (len - 1) as f64).clamp(
+            1.0 / MAX_BLOB_BASE_FEE_GROWTH_PER_BLOCK,
+            MAX_BLOB_BASE_FEE_GROWTH_PER_BLOCK,
+        );
+        T::from_f64(last * growth.powf(blocks_ahead as f64))
+    }
+}
@@ -1,29 +1,35 @@
 use std::{collections::VecDeque, sync::RwLockReadGuard};
 
 use test_casing::test_casing;
-use zksync_config::GasAdjusterConfig;
+use zksync_config::{configs::eth_sender::FeeSmoothingStrategy, GasAdjusterConfig};
 use zksync_eth_client::{clients::MockSettlementLayer, BaseFees};
 use zksync_types::{
     commitment::L1BatchCommitmentMode, pubdata_da::PubdataSendingMode, settlement::SettlementMode,
+    U256,
 };
 use zksync_web3_decl::client::L2;
 
-use super::{GasAdjuster, GasStatistics, GasStatisticsInner};
+use super::{
+    smoothing::{FeeSmoothing, SmoothingStrategy},
+    GasAdjuster, GasStatistics, GasStatisticsInner,
+};
 use crate::l1_gas_price::GasAdjusterClient;
 
 /// Check that we compute the median correctly
 #[test]
 fn median() {
     // sorted: 4 4 6 7 8
-    assert_eq!(GasStatisticsInner::new(5, 5, [6, 4, 7, 8, 4]).median(), 6);
+    let stats = GasStatisticsInner::new(5, 5, [6_u64, 4, 7, 8, 4], FeeSmoothing::default());
+    assert_eq!(stats.median(), 6);
     // sorted: 4 4 8 10
-    assert_eq!(GasStatisticsInner::new(4, 4, [8, 4, 4, 10]).median(), 8);
+    let stats = GasStatisticsInner::new(4, 4, [8_u64, 4, 4, 10], FeeSmoothing::default());
+    assert_eq!(stats.median(), 8);
 }
 
 /// Check that we properly manage the block base fee queue
 #[test]
 fn samples_queue() {
-    let mut stats = GasStatisticsInner::new(5, 5, [6, 4, 7, 8, 4, 5]);
+    let mut stats = GasStatisticsInner::new(5, 5, [6_u64, 4, 7, 8, 4, 5], FeeSmoothing::default());
 
     assert_eq!(stats.samples, VecDeque::from([4, 7, 8, 4, 5]));
 
@@ -32,6 +38,106 @@ fn samples_queue() {
     assert_eq!(stats.samples, VecDeque::from([4, 5, 18, 18, 18]));
 }
 
+#[test]
+fn median_smoothing_matches_median() {
+    let mut stats = GasStatisticsInner::new(5, 5, [6_u64, 4, 7, 8, 4], FeeSmoothing::default());
+    assert_eq!(stats.smoothed(), 6);
+    stats.add_samples([100, 100]);
+    assert_eq!(stats.smoothed(), stats.median());
+}
+
+#[test]
+fn ema_smoothing() {
+    let smoothing = FeeSmoothing {
+        strategy: SmoothingStrategy::Ema { alpha: 0.5 },
+        max_change_per_update: None,
+    };
+    let mut stats = GasStatisticsInner::new(5, 2, [100_u64, 200], smoothing);
+    // 0.5 * 200 + 0.5 * 100
+    assert_eq!(stats.smoothed(), 150);
+
+    stats.add_samples([1_000]);
+    // 0.5 * 1000 + 0.5 * 150
+    assert_eq!(stats.smoothed(), 575);
+    // The median is unaffected by smoothing.
+    assert_eq!(stats.median(), 200);
+
+    // The EMA takes into account samples evicted from the window.
+    stats.add_samples([0, 0, 0, 0, 0]);
+    assert!(stats.smoothed() > 0, "{stats:?}");
+}
+
+#[test]
+fn percentile_smoothing() {
+    let smoothing = |percentile| FeeSmoothing {
+        strategy: SmoothingStrategy::Percentile { percentile },
+        max_change_per_update: None,
+    };
+    let fees = [50_u64, 10, 40, 20, 30];
+
+    assert_eq!(
+        GasStatisticsInner::new(5, 5, fees, smoothing(0.0)).smoothed(),
+        10
+    );
+    assert_eq!(
+        GasStatisticsInner::new(5, 5, fees, smoothing(25.0)).smoothed(),
+        20
+    );
+    assert_eq!(
+        GasStatisticsInner::new(5, 5, fees, smoothing(50.0)).smoothed(),
+        30
+    );
+    assert_eq!(
+        GasStatisticsInner::new(5, 5, fees, smoothing(90.0)).smoothed(),
+        50
+    );
+    assert_eq!(
+        GasStatisticsInner::new(5, 5, fees, smoothing(100.0)).smoothed(),
+        50
+    );
+}
+
+#[test]
+fn bounded_smoothing_changes() {
+    let smoothing = FeeSmoothing {
+        strategy: SmoothingStrategy::Median,
+        max_change_per_update: Some(0.1),
+    };
+    let mut stats = GasStatisticsInner::new(3, 3, [100_u64, 100, 100], smoothing);
+    // The first value is not bounded.
+    assert_eq!(stats.smoothed(), 100);
+
+    stats.add_samples([1_000, 1_000]);
+    assert_eq!(stats.median(), 1_000);
+    assert_eq!(stats.smoothed(), 110);
+    stats.add_samples([1_000]);
+    assert_eq!(stats.smoothed(), 121);
+
+    stats.add_samples([1, 1, 1]);
+    assert_eq!(stats.smoothed(), 108); // 121 * 0.9 = 108.9
+}
+
+#[test]
+fn blob_base_fee_prediction() {
+    let stats = GasStatisticsInner::new(
+        3,
+        3,
+        [100_u64, 110, 121].map(U256::from),
+        FeeSmoothing::default(),
+    );
+    // The fee grows by 10% per block.
+    assert_eq!(stats.predict(0), 121.into());
+    assert_eq!(stats.predict(2), 146.into());
+
+    // Growth is bounded by the maximum per-block blob base fee growth.
+    let stats = GasStatisticsInner::new(2, 2, [100_u64, 1_000], FeeSmoothing::default());
+    assert_eq!(stats.predict(1), 1_125);
+    // Decreasing fees are extrapolated as well.
+    let stats = GasStatisticsInner::new(2, 2, [1_000_u64, 1], FeeSmoothing::default());
+    assert_eq!(stats.predict(0), 1);
+    assert_eq!(stats.predict(1), 0);
+}
+
 const TEST_BLOCK_FEES: [u64; 10] = [0, 4, 6, 8, 7, 5, 5, 8, 10, 9];
 const TEST_BLOB_FEES: [u64; 10] = [
     0,
@@ -73,6 +179,11 @@ fn test_config(settlement_mode: SettlementMode) -> GasAdjusterConfig {
         internal_pubdata_pricing_multiplier: 1.0,
         max_blob_base_fee: None,
         settlement_mode,
+        fee_smoothing_strategy: FeeSmoothingStrategy::Median,
+        fee_smoothing_ema_alpha: GasAdjusterConfig::default_fee_smoothing_ema_alpha(),
+        fee_smoothing_percentile: GasAdjusterConfig::default_fee_smoothing_percentile(),
+        max_fee_change_per_update: None,
+        blob_base_fee_prediction_blocks: None,
     }
 }
 
@@ -2,20 +2,39 @@ use std::{fmt, fmt::Debug, sync::Arc};
 
 use anyhow::Context as _;
 use async_trait::async_trait;
+use tokio::sync::watch;
 use zksync_dal::{ConnectionPool, Core, CoreDal};
-use zksync_types::fee_model::{
-    BaseTokenConversionRatio, BatchFeeInput, FeeModelConfig, FeeParams, FeeParamsV1, FeeParamsV2,
+use zksync_types::{
+    api::PipelineCongestion,
+    fee_model::{
+        BaseTokenConversionRatio, BatchFeeInput, FeeModelConfig, FeeParams, FeeParamsV1,
+        FeeParamsV2,
+    },
 };
 
 use crate::l1_gas_price::GasAdjuster;
+pub use crate::{
+    congestion::{PipelineBackpressure, PipelineCongestionMonitor},
+    pubdata_pricing::{
+        CustomPubdataPricing, PubdataPricing, RollupPubdataPricing, ValidiumPubdataPricing,
+    },
+};
 
+mod congestion;
 pub mod l1_gas_price;
+mod pubdata_pricing;
 
 /// Trait responsible for providing numerator and denominator for adjusting gas price that is denominated
 /// in a non-eth base token
 #[async_trait]
 pub trait BaseTokenRatioProvider: Debug + Send + Sync + 'static {
     fn get_conversion_ratio(&self) -> BaseTokenConversionRatio;
+
+    /// Returns the ID of the persisted ratio returned by [`Self::get_conversion_ratio()`], or `None`
+    /// if the ratio isn't persisted (e.g., it is forced or a default one).
+    fn get_conversion_ratio_id(&self) -> Option<u32> {
+        None
+    }
 }
 
 /// Trait responsible for providing fee info for a batch
@@ -34,6 +53,18 @@ pub trait BatchFeeModelInputProvider: fmt::Debug + 'static + Send + Sync {
 
     /// Returns the fee model parameters using the denomination of the base token used (WEI for ETH).
     fn get_fee_model_params(&self) -> FeeParams;
+
+    /// Returns congestion of the proof / execution pipeline taken into account by this provider, or `None`
+    /// if the provider doesn't account for congestion.
+    async fn get_pipeline_congestion(&self) -> anyhow::Result<Option<PipelineCongestion>> {
+        Ok(None)
+    }
+
+    /// Returns the ID of the persisted base token ratio used in the fee model parameters, or `None`
+    /// if the provider doesn't use persisted ratios.
+    fn get_base_token_ratio_id(&self) -> Option<u32> {
+        None
+    }
 }
 
 impl dyn BatchFeeModelInputProvider {
@@ -50,6 +81,7 @@ impl dyn BatchFeeModelInputProvider {
 pub struct MainNodeFeeInputProvider {
     provider: Arc<GasAdjuster>,
     base_token_ratio_provider: Arc<dyn BaseTokenRatioProvider>,
+    pubdata_pricing: Arc<dyn PubdataPricing>,
     config: FeeModelConfig,
 }
 
@@ -61,12 +93,26 @@ impl BatchFeeModelInputProvider for MainNodeFeeInputProvider {
                 config,
                 l1_gas_price: self.provider.estimate_effective_gas_price(),
             }),
-            FeeModelConfig::V2(config) => FeeParams::V2(FeeParamsV2::new(
-                config,
-                self.provider.estimate_effective_gas_price(),
-                self.provider.estimate_effective_pubdata_price(),
-                self.base_token_ratio_provider.get_conversion_ratio(),
-            )),
+            FeeModelConfig::V2(config) => {
+                let l1_gas_price = self.provider.estimate_effective_gas_price();
+                let pubdata_price = self.pubdata_pricing.pubdata_price(
+                    l1_gas_price,
+                    self.provider.estimate_effective_pubdata_price(),
+                );
+                FeeParams::V2(FeeParamsV2::new(
+                    config,
+                    l1_gas_price,
+                    pubdata_price,
+                    self.base_token_ratio_provider.get_conversion_ratio(),
+                ))
+            }
+        }
+    }
+
+    fn get_base_token_ratio_id(&self) -> Option<u32> {
+        match self.config {
+            FeeModelConfig::V1(_) => None,
+            FeeModelConfig::V2(_) => self.base_token_ratio_provider.get_conversion_ratio_id(),
         }
     }
 }
@@ -80,17 +126,28 @@ impl MainNodeFeeInputProvider {
         Self {
             provider,
             base_token_ratio_provider,
+            pubdata_pricing: Arc::new(RollupPubdataPricing),
             config,
         }
     }
+
+    /// Sets the pubdata pricing curve used by the V2 fee model. By default, [`RollupPubdataPricing`] is used,
+    /// i.e., the pubdata price estimated by the gas adjuster is used as-is.
+    #[must_use]
+    pub fn with_pubdata_pricing(mut self, pubdata_pricing: Arc<dyn PubdataPricing>) -> Self {
+        self.pubdata_pricing = pubdata_pricing;
+        self
+    }
 }
 
 /// The fee model provider to be used in the API. It returns the maximum batch fee input between the projected main node one and
-/// the one from the last sealed L2 block.
+/// the one from the last sealed L2 block. If [pipeline backpressure](PipelineBackpressure) is configured, the fair L2 gas price
+/// is bumped while the pipeline is congested.
 #[derive(Debug)]
 pub struct ApiFeeInputProvider {
     inner: Arc<dyn BatchFeeModelInputProvider>,
     connection_pool: ConnectionPool<Core>,
+    congestion: Option<watch::Receiver<Option<PipelineCongestion>>>,
 }
 
 impl ApiFeeInputProvider {
@@ -101,8 +158,23 @@ impl ApiFeeInputProvider {
         Self {
             inner,
             connection_pool,
+            congestion: None,
         }
     }
+
+    /// Enables the congestion gas price bump based on the congestion checked by a [`PipelineCongestionMonitor`].
+    #[must_use]
+    pub fn with_congestion(
+        mut self,
+        congestion: watch::Receiver<Option<PipelineCongestion>>,
+    ) -> Self {
+        self.congestion = Some(congestion);
+        self
+    }
+
+    fn latest_congestion(&self) -> Option<PipelineCongestion> {
+        self.congestion.as_ref()?.borrow().clone()
+    }
 }
 
 #[async_trait]
@@ -117,23 +189,32 @@ impl BatchFeeModelInputProvider for ApiFeeInputProvider {
             .get_batch_fee_input_scaled(l1_gas_price_scale_factor, l1_pubdata_price_scale_factor)
             .await
             .context("cannot get batch fee input from base provider")?;
-        let last_l2_block_params = self
+        let mut storage = self
             .connection_pool
             .connection_tagged("api_fee_input_provider")
-            .await?
+            .await?;
+        let last_l2_block_params = storage
             .blocks_dal()
             .get_last_sealed_l2_block_header()
             .await?;
-
-        Ok(last_l2_block_params
+        let fee_input = last_l2_block_params
             .map(|header| inner_input.stricter(header.batch_fee_input))
-            .unwrap_or(inner_input))
+            .unwrap_or(inner_input);
+
+        Ok(match self.latest_congestion() {
+            Some(congestion) => PipelineBackpressure::apply(&congestion, fee_input),
+            None => fee_input,
+        })
     }
 
     /// Returns the fee model parameters.
     fn get_fee_model_params(&self) -> FeeParams {
         self.inner.get_fee_model_params()
     }
+
+    async fn get_pipeline_congestion(&self) -> anyhow::Result<Option<PipelineCongestion>> {
+        Ok(self.latest_congestion())
+    }
 }
 
 /// Mock [`BatchFeeModelInputProvider`] implementation that returns a constant value.
@@ -342,6 +423,49 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn applying_pubdata_pricing() {
+        let config = FeeModelConfig::V2(FeeModelConfigV2 {
+            minimal_l2_gas_price: 1_000,
+            compute_overhead_part: 1.0,
+            pubdata_overhead_part: 1.0,
+            batch_overhead_l1_gas: 1,
+            max_gas_per_batch: 1,
+            max_pubdata_per_batch: 1,
+        });
+        let ratio_provider = Arc::new(DummyTokenRatioProvider::new(BaseTokenConversionRatio {
+            numerator: NonZeroU64::new(1).unwrap(),
+            denominator: NonZeroU64::new(1).unwrap(),
+        }));
+        let pricings: [(Arc<dyn PubdataPricing>, u64); 3] = [
+            (Arc::new(RollupPubdataPricing), 3_000),
+            (Arc::new(ValidiumPubdataPricing::new(5)), 5),
+            (
+                Arc::new(CustomPubdataPricing::new(vec![(0, 0), (4_000, 2_000)]).unwrap()),
+                1_000,
+            ),
+        ];
+
+        for (pricing, expected_pubdata_price) in pricings {
+            let gas_adjuster = setup_gas_adjuster(2_000, 3_000).await;
+            let fee_provider = MainNodeFeeInputProvider::new(
+                Arc::new(gas_adjuster),
+                ratio_provider.clone(),
+                config,
+            )
+            .with_pubdata_pricing(pricing.clone());
+            let FeeParams::V2(params) = fee_provider.get_fee_model_params() else {
+                panic!("Unexpected fee params");
+            };
+            assert_eq!(params.l1_gas_price(), 2_000, "{pricing:?}");
+            assert_eq!(
+                params.l1_pubdata_price(),
+                expected_pubdata_price,
+                "{pricing:?}"
+            );
+        }
+    }
+
     // Helper function to create BaseFees.
     fn test_base_fees(block: u64, blob: U256, pubdata: U256) -> BaseFees {
         BaseFees {
@@ -0,0 +1,152 @@
+//! Pricing of pubdata used by the V2 fee model.
+
+use std::fmt;
+
+/// Curve determining the pubdata price (i.e., the price of publishing a single byte of pubdata) charged by the chain.
+///
+/// The curve is applied to the L1 prices estimated by [`GasAdjuster`](crate::l1_gas_price::GasAdjuster);
+/// its output is used in the V2 fee model params. All prices are denominated in wei.
+pub trait PubdataPricing: fmt::Debug + Send + Sync + 'static {
+    /// Returns the pubdata price given the estimated L1 gas price and the estimated L1 pubdata price
+    /// (the latter depends on the pubdata sending mode, e.g. blobs or calldata).
+    fn pubdata_price(&self, l1_gas_price: u64, l1_pubdata_price: u64) -> u64;
+}
+
+/// Pricing for rollups: pubdata is published on L1, so its price follows the L1 pubdata price estimate as-is.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RollupPubdataPricing;
+
+impl PubdataPricing for RollupPubdataPricing {
+    fn pubdata_price(&self, _l1_gas_price: u64, l1_pubdata_price: u64) -> u64 {
+        l1_pubdata_price
+    }
+}
+
+/// Pricing for validiums: pubdata is not published on L1, so its price is flat (zero by default).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidiumPubdataPricing {
+    price: u64,
+}
+
+impl ValidiumPubdataPricing {
+    /// Creates pricing with the specified flat price per pubdata byte.
+    pub fn new(price: u64) -> Self {
+        Self { price }
+    }
+}
+
+impl PubdataPricing for ValidiumPubdataPricing {
+    fn pubdata_price(&self, _l1_gas_price: u64, _l1_pubdata_price: u64) -> u64 {
+        self.price
+    }
+}
+
+/// Custom piecewise linear pricing curve mapping the L1 gas price to the pubdata price.
+///
+/// The price is linearly interpolated between the curve points; outside the range covered by the points,
+/// the price of the closest point is used. Thus, a curve with a single point is flat.
+#[derive(Debug, Clone)]
+pub struct CustomPubdataPricing {
+    /// `(l1_gas_price, pubdata_price)` pairs sorted by the L1 gas price.
+    points: Vec<(u64, u64)>,
+}
+
+impl CustomPubdataPricing {
+    /// Creates a curve from the `(l1_gas_price, pubdata_price)` points.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `points` is empty or isn't strictly increasing by the L1 gas price.
+    pub fn new(points: Vec<(u64, u64)>) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            !points.is_empty(),
+            "pubdata pricing curve must have at least one point"
+        );
+        anyhow::ensure!(
+            points.windows(2).all(|window| window[0].0 < window[1].0),
+            "pubdata pricing curve points must be strictly increasing by L1 gas price: {points:?}"
+        );
+        Ok(Self { points })
+    }
+
+    /// Creates a curve from the separate lists of L1 gas prices and corresponding pubdata prices, as specified
+    /// in the state keeper config.
+    pub fn from_config(l1_gas_prices: &[u64], pubdata_prices: &[u64]) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            l1_gas_prices.len() == pubdata_prices.len(),
+            "pubdata pricing curve has mismatched number of L1 gas prices ({}) and pubdata prices ({})",
+            l1_gas_prices.len(),
+            pubdata_prices.len()
+        );
+        let points = l1_gas_prices
+            .iter()
+            .copied()
+            .zip(pubdata_prices.iter().copied());
+        Self::new(points.collect())
+    }
+}
+
+impl PubdataPricing for CustomPubdataPricing {
+    fn pubdata_price(&self, l1_gas_price: u64, _l1_pubdata_price: u64) -> u64 {
+        let idx = self
+            .points
+            .partition_point(|&(gas_price, _)| gas_price <= l1_gas_price);
+        if idx == 0 {
+            return self.points[0].1;
+        }
+        let (start_gas_price, start_price) = self.points[idx - 1];
+        let Some(&(end_gas_price, end_price)) = self.points.get(idx) else {
+            return start_price;
+        };
+
+        // Use `u128` arithmetic to avoid overflows.
+        let offset = u128::from(l1_gas_price - start_gas_price);
+        let range = u128::from(end_gas_price - start_gas_price);
+        let interpolated = if end_price >= start_price {
+            u128::from(start_price) + u128::from(end_price - start_price) * offset / range
+        } else {
+            u128::from(start_price) - u128::from(start_price - end_price) * offset / range
+        };
+        // The interpolated value is always between `start_price` and `end_price`, so it fits into `u64`.
+        interpolated as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_pricing_curve() {
+        let pricing =
+            CustomPubdataPricing::new(vec![(100, 1_000), (200, 3_000), (400, 2_000)]).unwrap();
+        assert_eq!(pricing.pubdata_price(0, 0), 1_000);
+        assert_eq!(pricing.pubdata_price(100, 0), 1_000);
+        assert_eq!(pricing.pubdata_price(150, 0), 2_000);
+        assert_eq!(pricing.pubdata_price(200, 0), 3_000);
+        assert_eq!(pricing.pubdata_price(300, 0), 2_500);
+        assert_eq!(pricing.pubdata_price(400, 0), 2_000);
+        assert_eq!(pricing.pubdata_price(u64::MAX, 0), 2_000);
+
+        let flat_pricing = CustomPubdataPricing::new(vec![(0, 42)]).unwrap();
+        assert_eq!(flat_pricing.pubdata_price(0, 100), 42);
+        assert_eq!(flat_pricing.pubdata_price(1_000_000, 100), 42);
+    }
+
+    #[test]
+    fn custom_pricing_curve_with_extreme_values() {
+        let pricing = CustomPubdataPricing::new(vec![(0, 0), (u64::MAX, u64::MAX)]).unwrap();
+        assert_eq!(pricing.pubdata_price(0, 0), 0);
+        assert_eq!(pricing.pubdata_price(12_345, 0), 12_345);
+        assert_eq!(pricing.pubdata_price(u64::MAX, 0), u64::MAX);
+    }
+
+    #[test]
+    fn invalid_custom_pricing_curves() {
+        CustomPubdataPricing::new(vec![]).unwrap_err();
+        CustomPubdataPricing::new(vec![(100, 1), (100, 2)]).unwrap_err();
+        CustomPubdataPricing::new(vec![(200, 1), (100, 2)]).unwrap_err();
+        CustomPubdataPricing::from_config(&[100, 200], &[1]).unwrap_err();
+        CustomPubdataPricing::from_config(&[100, 200], &[1, 2]).unwrap();
+    }
+}
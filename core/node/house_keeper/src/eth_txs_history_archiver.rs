@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+
+use crate::{metrics::HOUSE_KEEPER_METRICS, periodic_job::PeriodicJob};
+
+/// Moves `eth_txs_history` entries superseded by confirmed L1 transactions (e.g., transactions resent with
+/// increased fees) to the `eth_txs_history_archive` table once they are older than the configured retention period.
+/// Archived entries can be restored using `EthSenderDal::restore_archived_tx_history()`.
+#[derive(Debug)]
+pub struct EthTxsHistoryArchiver {
+    retention: Duration,
+    connection_pool: ConnectionPool<Core>,
+}
+
+impl EthTxsHistoryArchiver {
+    const POLLING_INTERVAL_MS: u64 = 60_000;
+    /// Maximum number of entries archived in a single DB query.
+    const CHUNK_SIZE: usize = 1_000;
+
+    pub fn new(retention: Duration, connection_pool: ConnectionPool<Core>) -> Self {
+        Self {
+            retention,
+            connection_pool,
+        }
+    }
+
+    async fn archive_history(&self) -> anyhow::Result<usize> {
+        let mut conn = self
+            .connection_pool
+            .connection_tagged("house_keeper")
+            .await?;
+        let mut total_archived = 0;
+        loop {
+            let archived = conn
+                .eth_sender_dal()
+                .archive_tx_history(self.retention, Self::CHUNK_SIZE)
+                .await?;
+            total_archived += archived;
+            if archived < Self::CHUNK_SIZE {
+                break;
+            }
+        }
+        Ok(total_archived)
+    }
+}
+
+#[async_trait]
+impl PeriodicJob for EthTxsHistoryArchiver {
+    const SERVICE_NAME: &'static str = "EthTxsHistoryArchiver";
+
+    async fn run_routine_task(&mut self) -> anyhow::Result<()> {
+        let archived = self.archive_history().await?;
+        if archived > 0 {
+            tracing::info!("Archived {archived} `eth_txs_history` entries");
+        }
+        HOUSE_KEEPER_METRICS
+            .archived_eth_txs_history
+            .inc_by(archived as u64);
+        Ok(())
+    }
+
+    fn polling_interval_ms(&self) -> u64 {
+        Self::POLLING_INTERVAL_MS
+    }
+}
@@ -1,3 +1,5 @@
 pub mod blocks_state_reporter;
+pub mod eth_txs_history_archiver;
 mod metrics;
+pub mod partitions_maintainer;
 pub mod periodic_job;
@@ -1,4 +1,4 @@
-use vise::{Gauge, Metrics};
+use vise::{Counter, Gauge, Metrics};
 
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "fri_prover")]
@@ -9,3 +9,13 @@ pub(crate) struct FriProverMetrics {
 
 #[vise::register]
 pub(crate) static FRI_PROVER_METRICS: vise::Global<FriProverMetrics> = vise::Global::new();
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "house_keeper")]
+pub(crate) struct HouseKeeperMetrics {
+    /// Number of `eth_txs_history` entries moved to the archive table.
+    pub archived_eth_txs_history: Counter,
+}
+
+#[vise::register]
+pub(crate) static HOUSE_KEEPER_METRICS: vise::Global<HouseKeeperMetrics> = vise::Global::new();
@@ -0,0 +1,82 @@
+use std::num::NonZeroU32;
+
+use async_trait::async_trait;
+use zksync_dal::{partitions_dal::PartitionedTable, ConnectionPool, Core, CoreDal};
+use zksync_types::L2BlockNumber;
+
+use crate::periodic_job::PeriodicJob;
+
+/// Creates partitions for [partitioned tables](PartitionedTable) ahead of the sealed L2 blocks, so that data
+/// for new L2 blocks doesn't end up in the default partitions.
+#[derive(Debug)]
+pub struct TablePartitionsMaintainer {
+    partition_size: NonZeroU32,
+    connection_pool: ConnectionPool<Core>,
+}
+
+impl TablePartitionsMaintainer {
+    const POLLING_INTERVAL_MS: u64 = 60_000;
+    /// Number of partitions to create ahead of the partition containing the last sealed L2 block.
+    const PARTITIONS_AHEAD: u32 = 1;
+
+    pub fn new(partition_size: NonZeroU32, connection_pool: ConnectionPool<Core>) -> Self {
+        Self {
+            partition_size,
+            connection_pool,
+        }
+    }
+
+    async fn create_partitions(&self) -> anyhow::Result<()> {
+        let mut conn = self
+            .connection_pool
+            .connection_tagged("house_keeper")
+            .await?;
+        let Some(sealed_l2_block) = conn.blocks_dal().get_sealed_l2_block_number().await? else {
+            return Ok(());
+        };
+        let partition_size = self.partition_size.get();
+        let target_l2_block = sealed_l2_block
+            .0
+            .saturating_add(partition_size.saturating_mul(Self::PARTITIONS_AHEAD));
+
+        for table in PartitionedTable::ALL {
+            let partitions = conn.partitions_dal().get_partitions(table).await?;
+            let mut next_start = partitions
+                .iter()
+                .filter_map(|partition| partition.l2_blocks.as_ref())
+                .map(|range| range.end.0)
+                .max()
+                // All range partitions may have been dropped by pruning; in this case, start from the partition
+                // that would contain the sealed L2 block.
+                .unwrap_or(sealed_l2_block.0 / partition_size * partition_size);
+
+            while next_start <= target_l2_block {
+                let next_end = next_start.saturating_add(partition_size);
+                let range = L2BlockNumber(next_start)..L2BlockNumber(next_end);
+                let name = conn
+                    .partitions_dal()
+                    .create_partition(table, range.clone())
+                    .await?;
+                tracing::info!(
+                    "Created partition `{name}` of table `{}` for L2 blocks {range:?}",
+                    table.name()
+                );
+                next_start = next_end;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PeriodicJob for TablePartitionsMaintainer {
+    const SERVICE_NAME: &'static str = "TablePartitionsMaintainer";
+
+    async fn run_routine_task(&mut self) -> anyhow::Result<()> {
+        self.create_partitions().await
+    }
+
+    fn polling_interval_ms(&self) -> u64 {
+        Self::POLLING_INTERVAL_MS
+    }
+}
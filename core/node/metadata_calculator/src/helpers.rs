@@ -147,6 +147,7 @@ fn create_db_sync(config: &MetadataCalculatorConfig) -> anyhow::Result<RocksDBWr
         multi_get_chunk_size,
         memtable_capacity,
         stalled_writes_timeout,
+        rocksdb_profile,
         ..
     } = config;
 
@@ -154,7 +155,7 @@ fn create_db_sync(config: &MetadataCalculatorConfig) -> anyhow::Result<RocksDBWr
         "Initializing Merkle tree database at `{path}` (max open files: {max_open_files:?}) with {multi_get_chunk_size} multi-get chunk size, \
          {block_cache_capacity}B block cache (indices & filters included: {include_indices_and_filters_in_block_cache:?}), \
          {memtable_capacity}B memtable capacity, \
-         {stalled_writes_timeout:?} stalled writes timeout",
+         {stalled_writes_timeout:?} stalled writes timeout, {rocksdb_profile:?} RocksDB profile",
         path = path.display()
     );
 
@@ -166,6 +167,7 @@ fn create_db_sync(config: &MetadataCalculatorConfig) -> anyhow::Result<RocksDBWr
             large_memtable_capacity: Some(memtable_capacity),
             stalled_writes_retries: StalledWritesRetries::new(stalled_writes_timeout),
             max_open_files,
+            profile: rocksdb_profile,
         },
     )?;
     if cfg!(test) {
@@ -227,6 +229,8 @@ pub(super) struct AsyncTree {
 }
 
 impl AsyncTree {
+    /// Custom tree tag storing the recovery cursor in the `{next_chunk}/{chunk_count}` format.
+    const CHUNK_CURSOR_KEY: &'static str = "recovery.chunk_cursor";
     const INCONSISTENT_MSG: &'static str =
         "`AsyncTree` is in inconsistent state, which could occur after one of its async methods was cancelled or returned an error";
 
@@ -554,6 +558,68 @@ impl AsyncTreeRecovery {
         Ok(())
     }
 
+    /// Returns the persisted recovery cursor, i.e., the number of leading key chunks that are known to be recovered.
+    /// If the cursor was persisted for a different number of chunks, it is ignored.
+    pub async fn recovery_cursor(&mut self, chunk_count: u64) -> anyhow::Result<u64> {
+        let mut tree = self.inner.take().expect(Self::INCONSISTENT_MSG);
+        let (cursor, tree) = tokio::task::spawn_blocking(move || {
+            let cursor = tree
+                .update_custom_tags(|tags| tags.get(Self::CHUNK_CURSOR_KEY).cloned())
+                .context("failed reading Merkle tree tags")?;
+            anyhow::Ok((cursor, tree))
+        })
+        .await??;
+        self.inner = Some(tree);
+
+        let Some(cursor) = cursor else {
+            return Ok(0);
+        };
+        let (next_chunk, cursor_chunk_count) = cursor
+            .split_once('/')
+            .and_then(|(next_chunk, count)| {
+                Some((next_chunk.parse::<u64>().ok()?, count.parse::<u64>().ok()?))
+            })
+            .with_context(|| {
+                format!("error parsing recovery cursor `{cursor}` in Merkle tree tags")
+            })?;
+        if cursor_chunk_count != chunk_count {
+            tracing::warn!(
+                "Recovery cursor `{cursor}` in Merkle tree tags was persisted for a different number of chunks ({chunk_count}); ignoring it"
+            );
+            return Ok(0);
+        }
+        anyhow::ensure!(
+            next_chunk <= chunk_count,
+            "Recovery cursor `{cursor}` in Merkle tree tags is out of bounds"
+        );
+        Ok(next_chunk)
+    }
+
+    /// Persists the recovery cursor. Since tree updates are persisted in order, the cursor will not be persisted
+    /// before the preceding tree updates.
+    pub async fn set_recovery_cursor(
+        &mut self,
+        next_chunk: u64,
+        chunk_count: u64,
+    ) -> anyhow::Result<()> {
+        let mut tree = self.inner.take().expect(Self::INCONSISTENT_MSG);
+        let tree = tokio::task::spawn_blocking(move || {
+            tree.update_custom_tags(|tags| {
+                tags.insert(
+                    Self::CHUNK_CURSOR_KEY.to_owned(),
+                    format!("{next_chunk}/{chunk_count}"),
+                );
+            })
+            .context("failed updating Merkle tree tags")?;
+            anyhow::Ok(tree)
+        })
+        .await
+        .context("updating recovery cursor panicked")??;
+
+        self.inner = Some(tree);
+        Ok(())
+    }
+
     /// Returns an entry for the specified keys.
     pub async fn entries(&mut self, keys: Vec<Key>) -> Vec<TreeEntry> {
         let tree = self.inner.take().expect(Self::INCONSISTENT_MSG);
@@ -11,7 +11,7 @@ use anyhow::Context as _;
 use tokio::sync::{oneshot, watch};
 use zksync_config::configs::{
     chain::{OperationsManagerConfig, StateKeeperConfig},
-    database::{MerkleTreeConfig, MerkleTreeMode},
+    database::{MerkleTreeConfig, MerkleTreeMode, RocksdbProfile},
 };
 use zksync_dal::{ConnectionPool, Core};
 use zksync_health_check::{CheckHealth, HealthUpdater, ReactiveHealthCheck};
@@ -25,7 +25,7 @@ use self::{
 };
 pub use self::{
     helpers::{AsyncTreeReader, LazyAsyncTreeReader, MerkleTreeInfo},
-    pruning::MerkleTreePruningTask,
+    pruning::{MerkleTreePruningOptions, MerkleTreePruningTask},
     repair::StaleKeysRepairTask,
 };
 use crate::helpers::create_readonly_db;
@@ -92,6 +92,8 @@ pub struct MetadataCalculatorConfig {
     pub memtable_capacity: usize,
     /// Timeout to wait for the Merkle tree database to run compaction on stalled writes.
     pub stalled_writes_timeout: Duration,
+    /// RocksDB tuning profile for the tree.
+    pub rocksdb_profile: RocksdbProfile,
     /// Whether state keeper writes protective reads when it seals a batch.
     pub sealed_batches_have_protective_reads: bool,
     /// Configuration specific to the Merkle tree recovery.
@@ -115,6 +117,7 @@ impl MetadataCalculatorConfig {
             include_indices_and_filters_in_block_cache: false,
             memtable_capacity: merkle_tree_config.memtable_capacity(),
             stalled_writes_timeout: merkle_tree_config.stalled_writes_timeout(),
+            rocksdb_profile: merkle_tree_config.rocksdb_profile,
             sealed_batches_have_protective_reads: state_keeper_config
                 .protective_reads_persistence_enabled,
             // The main node isn't supposed to be recovered yet, so this value doesn't matter much
@@ -238,7 +238,6 @@ pub(super) enum RecoveryStage {
 pub(super) enum ChunkRecoveryStage {
     AcquireConnection,
     LoadEntries,
-    LockTree,
     ExtendTree,
 }
 
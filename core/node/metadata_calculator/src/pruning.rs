@@ -1,6 +1,6 @@
 //! Merkle tree pruning logic.
 
-use std::time::Duration;
+use std::{num::NonZeroU64, time::Duration};
 
 use anyhow::Context as _;
 use serde::Serialize;
@@ -36,6 +36,29 @@ impl From<MerkleTreePruningTaskHealth> for Health {
     }
 }
 
+/// Options for [`MerkleTreePruningTask`] complementing pruning according to the pruning entries in Postgres.
+#[derive(Debug, Clone, Copy)]
+pub struct MerkleTreePruningOptions {
+    /// Number of latest tree versions (= L1 batches) to retain. If set, older versions are pruned
+    /// even if they aren't pruned in Postgres. Similarly to the Postgres pruner, versions for L1 batches
+    /// not executed on L1 are never pruned.
+    pub retained_versions: Option<NonZeroU64>,
+    /// Delay between consecutive pruning iterations used to throttle pruning I/O.
+    pub iteration_delay: Duration,
+    /// Whether to compact the pruned part of the tree RocksDB once pruning catches up.
+    pub online_compaction: bool,
+}
+
+impl Default for MerkleTreePruningOptions {
+    fn default() -> Self {
+        Self {
+            retained_versions: None,
+            iteration_delay: Duration::ZERO,
+            online_compaction: false,
+        }
+    }
+}
+
 /// Task performing Merkle tree pruning according to the pruning entries in Postgres.
 #[derive(Debug)]
 #[must_use = "Task should `run()` in a managed Tokio task"]
@@ -44,6 +67,7 @@ pub struct MerkleTreePruningTask {
     pool: ConnectionPool<Core>,
     health_updater: HealthUpdater,
     poll_interval: Duration,
+    options: MerkleTreePruningOptions,
 }
 
 impl MerkleTreePruningTask {
@@ -57,13 +81,33 @@ impl MerkleTreePruningTask {
             pool,
             health_updater: ReactiveHealthCheck::new("tree_pruner").1,
             poll_interval,
+            options: MerkleTreePruningOptions::default(),
         }
     }
 
+    /// Sets additional pruning options.
+    pub fn with_options(mut self, options: MerkleTreePruningOptions) -> Self {
+        self.options = options;
+        self
+    }
+
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
     }
 
+    /// Returns the maximum tree version that can be pruned by the retained version count policy, i.e.
+    /// the last L1 batch executed on L1 (or 0 if there are no such batches).
+    async fn max_retention_version(&self) -> anyhow::Result<u64> {
+        let last_executed_l1_batch = self
+            .pool
+            .connection_tagged("metadata_calculator")
+            .await?
+            .blocks_dal()
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await?;
+        Ok(last_executed_l1_batch.map_or(0, |number| number.0.into()))
+    }
+
     pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         // The pruning task is "affected" (not functioning) until the Merkle tree is initialized.
         self.health_updater
@@ -94,6 +138,16 @@ impl MerkleTreePruningTask {
         // Pruner is not allocated a managed task because it is blocking; its cancellation awareness inherently
         // depends on the pruner handle (i.e., this task).
         pruner.set_poll_interval(self.poll_interval);
+        pruner.set_retained_version_count(self.options.retained_versions);
+        pruner.set_iteration_delay(self.options.iteration_delay);
+        pruner.set_online_compaction(self.options.online_compaction);
+        // Set the retention limit before the pruner is started so that it doesn't prune non-executed L1 batches.
+        if self.options.retained_versions.is_some() {
+            let max_retention_version = self.max_retention_version().await?;
+            pruner_handle
+                .set_max_retention_version(max_retention_version)
+                .ok(); // The pruner cannot stop before it's started
+        }
         let pruner_task_handle = tokio::task::spawn_blocking(|| pruner.run());
 
         while !*stop_receiver.borrow_and_update() {
@@ -101,6 +155,21 @@ impl MerkleTreePruningTask {
             let pruning_info = storage.pruning_dal().get_pruning_info().await?;
             drop(storage);
 
+            if self.options.retained_versions.is_some() {
+                let max_retention_version = self.max_retention_version().await?;
+                if pruner_handle
+                    .set_max_retention_version(max_retention_version)
+                    .is_err()
+                {
+                    self.health_updater
+                        .update(MerkleTreePruningTaskHealth::PruningStopped.into());
+                    tracing::error!("Merkle tree pruning thread unexpectedly stopped");
+                    return pruner_task_handle
+                        .await
+                        .context("Merkle tree pruning thread panicked")?;
+                }
+            }
+
             if let Some(pruned) = pruning_info.last_hard_pruned {
                 let target_retained_l1_batch_number = pruned.l1_batch + 1;
                 let target_retained_version = u64::from(target_retained_l1_batch_number.0);
@@ -11,12 +11,17 @@
 //! - Tree is ready for normal operation (i.e., it's not empty and is not recovering).
 //!
 //! If recovery is necessary, it starts / resumes by loading the Postgres snapshot in chunks
-//! and feeding each chunk to the tree. Chunks are loaded concurrently since this is the most
-//! I/O-heavy operation; the concurrency is naturally limited by the number of connections to
-//! Postgres in the supplied connection pool, but we explicitly use a [`Semaphore`] to control it
-//! in order to not run into DB timeout errors. Before starting recovery in chunks, we filter out
-//! chunks that have already been recovered by checking if the first key in a chunk is present
-//! in the tree. (Note that for this to work, chunks **must** always be defined in the same way.)
+//! and feeding each chunk to the tree. Chunks are loaded (and sanity-checked) by concurrent workers since
+//! this is the most I/O-heavy operation; the concurrency is naturally limited by the number of connections to
+//! Postgres in the supplied connection pool, but we explicitly limit it in order to not run into DB timeout errors.
+//! Loaded chunks are fed to the tree strictly in the order of their IDs, so that the tree is updated deterministically
+//! regardless of the loading order.
+//!
+//! After each chunk is fed to the tree, a recovery cursor (the number of leading chunks that are recovered) is persisted
+//! in the tree tags. Tree updates are persisted in order, so the cursor never gets ahead of the persisted tree data.
+//! Before starting recovery in chunks, we skip chunks before the cursor, and filter out the remaining chunks
+//! that have already been recovered by checking if the first key in a chunk is present in the tree.
+//! (Note that for this to work, chunks **must** always be defined in the same way.)
 //!
 //! The recovery logic is fault-tolerant and supports graceful shutdown. If recovery is interrupted,
 //! recovery of the remaining chunks will continue when Metadata calculator is restarted.
@@ -33,8 +38,8 @@ use std::{
 
 use anyhow::Context as _;
 use async_trait::async_trait;
-use futures::future;
-use tokio::sync::{watch, Mutex, Semaphore};
+use futures::{stream, StreamExt, TryStreamExt};
+use tokio::sync::{mpsc, watch};
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_health_check::HealthUpdater;
 use zksync_merkle_tree::TreeEntry;
@@ -263,14 +268,15 @@ impl AsyncTreeRecovery {
 
         let start_time = Instant::now();
         let chunk_count = options.chunk_count;
-        let chunks: Vec<_> = (0..chunk_count)
-            .map(|chunk_id| uniform_hashed_keys_chunk(chunk_id, chunk_count))
+        let concurrency_limit = options.concurrency_limit;
+        let recovery_cursor = self.recovery_cursor(chunk_count).await?;
+        let chunks: Vec<_> = (recovery_cursor..chunk_count)
+            .map(|chunk_id| (chunk_id, uniform_hashed_keys_chunk(chunk_id, chunk_count)))
             .collect();
         tracing::info!(
-            "Recovering Merkle tree from Postgres snapshot in {chunk_count} chunks with max concurrency {}. \
-             Be aware that enabling node pruning during recovery will probably result in a recovery error; always disable pruning \
-             until recovery is complete",
-            options.concurrency_limit
+            "Recovering Merkle tree from Postgres snapshot in {chunk_count} chunks (starting from chunk #{recovery_cursor}) \
+             with max concurrency {concurrency_limit}. Be aware that enabling node pruning during recovery will probably result \
+             in a recovery error; always disable pruning until recovery is complete"
         );
 
         let mut storage = pool.connection_tagged("metadata_calculator").await?;
@@ -286,23 +292,42 @@ impl AsyncTreeRecovery {
             remaining_chunks.len()
         );
 
-        let tree = Mutex::new(self);
-        let semaphore = Semaphore::new(options.concurrency_limit);
-        let chunk_tasks = remaining_chunks.into_iter().map(|chunk| async {
-            let _permit = semaphore
-                .acquire()
-                .await
-                .context("semaphore is never closed")?;
-            if Self::recover_key_chunk(&tree, init_params.l2_block, chunk, pool, stop_receiver)
-                .await?
-            {
+        // Chunks are loaded concurrently, but are sent to the tree in the order of their IDs.
+        let (loaded_chunks_sender, loaded_chunks_receiver) = mpsc::channel(concurrency_limit);
+        let load_chunks = async move {
+            let mut loaded_chunks = stream::iter(remaining_chunks)
+                .map(|(chunk_id, key_chunk)| async move {
+                    let entries =
+                        Self::load_key_chunk(init_params.l2_block, key_chunk, pool, stop_receiver)
+                            .await?;
+                    anyhow::Ok(entries.map(|entries| (chunk_id, entries)))
+                })
+                .buffered(concurrency_limit);
+            while let Some(loaded_chunk) = loaded_chunks.try_next().await? {
+                let Some(loaded_chunk) = loaded_chunk else {
+                    break; // Loading was interrupted by a stop signal
+                };
+                if loaded_chunks_sender.send(loaded_chunk).await.is_err() {
+                    break; // Tree extension has stopped, e.g. on a stop signal
+                }
+            }
+            anyhow::Ok(())
+        };
+        let extend_tree = async {
+            let mut loaded_chunks_receiver = loaded_chunks_receiver;
+            while let Some((chunk_id, entries)) = loaded_chunks_receiver.recv().await {
+                if *stop_receiver.borrow() {
+                    break;
+                }
+                self.recover_key_chunk(chunk_id, chunk_count, entries)
+                    .await?;
                 options.events.chunk_recovered().await;
             }
             anyhow::Ok(())
-        });
-        future::try_join_all(chunk_tasks).await?;
+        };
+        tokio::try_join!(load_chunks, extend_tree)?;
 
-        let mut tree = tree.into_inner();
+        let mut tree = self;
         if *stop_receiver.borrow() {
             // Waiting for persistence is mostly useful for tests. Normally, the tree database won't be used in the same process
             // after a stop signal is received, so there's no risk of data races with the background persistence thread.
@@ -338,13 +363,14 @@ impl AsyncTreeRecovery {
         &mut self,
         storage: &mut Connection<'_, Core>,
         snapshot_l2_block: L2BlockNumber,
-        key_chunks: &[ops::RangeInclusive<H256>],
-    ) -> anyhow::Result<Vec<ops::RangeInclusive<H256>>> {
+        key_chunks: &[(u64, ops::RangeInclusive<H256>)],
+    ) -> anyhow::Result<Vec<(u64, ops::RangeInclusive<H256>)>> {
         let chunk_starts_latency =
             RECOVERY_METRICS.latency[&RecoveryStage::LoadChunkStarts].start();
+        let key_ranges: Vec<_> = key_chunks.iter().map(|(_, range)| range.clone()).collect();
         let chunk_starts = storage
             .storage_logs_dal()
-            .get_chunk_starts_for_l2_block(snapshot_l2_block, key_chunks)
+            .get_chunk_starts_for_l2_block(snapshot_l2_block, &key_ranges)
             .await?;
         let chunk_starts_latency = chunk_starts_latency.observe();
         tracing::debug!(
@@ -393,21 +419,21 @@ impl AsyncTreeRecovery {
         Ok(())
     }
 
-    /// Returns `Ok(true)` if the chunk was recovered, `Ok(false)` if the recovery process was interrupted.
-    async fn recover_key_chunk(
-        tree: &Mutex<AsyncTreeRecovery>,
+    /// Loads tree entries for the specified key chunk from Postgres. Returns `Ok(None)` if the recovery process
+    /// was interrupted.
+    async fn load_key_chunk(
         snapshot_l2_block: L2BlockNumber,
         key_chunk: ops::RangeInclusive<H256>,
         pool: &ConnectionPool<Core>,
         stop_receiver: &watch::Receiver<bool>,
-    ) -> anyhow::Result<bool> {
+    ) -> anyhow::Result<Option<Vec<TreeEntry>>> {
         let acquire_connection_latency =
             RECOVERY_METRICS.chunk_latency[&ChunkRecoveryStage::AcquireConnection].start();
         let mut storage = pool.connection_tagged("metadata_calculator").await?;
         acquire_connection_latency.observe();
 
         if *stop_receiver.borrow() {
-            return Ok(false);
+            return Ok(None);
         }
 
         let entries_latency =
@@ -425,10 +451,6 @@ impl AsyncTreeRecovery {
             all_entries.len()
         );
 
-        if *stop_receiver.borrow() {
-            return Ok(false);
-        }
-
         // Sanity check: all entry keys must be distinct. Otherwise, we may end up writing non-final values
         // to the tree, since we don't enforce any ordering on entries besides by the hashed key.
         for window in all_entries.windows(2) {
@@ -450,22 +472,25 @@ impl AsyncTreeRecovery {
                 leaf_index: entry.leaf_index,
             })
             .collect();
-        let lock_tree_latency =
-            RECOVERY_METRICS.chunk_latency[&ChunkRecoveryStage::LockTree].start();
-        let mut tree = tree.lock().await;
-        lock_tree_latency.observe();
-
-        if *stop_receiver.borrow() {
-            return Ok(false);
-        }
+        Ok(Some(all_entries))
+    }
 
+    /// Extends the tree with entries for the specified chunk and advances the recovery cursor past it.
+    /// All chunks with lesser IDs must be recovered at this point.
+    async fn recover_key_chunk(
+        &mut self,
+        chunk_id: u64,
+        chunk_count: u64,
+        entries: Vec<TreeEntry>,
+    ) -> anyhow::Result<()> {
         let extend_tree_latency =
             RECOVERY_METRICS.chunk_latency[&ChunkRecoveryStage::ExtendTree].start();
-        tree.extend(all_entries).await?;
+        self.extend(entries).await?;
+        self.set_recovery_cursor(chunk_id + 1, chunk_count).await?;
         let extend_tree_latency = extend_tree_latency.observe();
         tracing::debug!(
-            "Extended Merkle tree with entries for chunk {key_chunk:?} in {extend_tree_latency:?}"
+            "Extended Merkle tree with entries for chunk #{chunk_id} in {extend_tree_latency:?}"
         );
-        Ok(true)
+        Ok(())
     }
 }
@@ -95,6 +95,52 @@ async fn basic_recovery_workflow() {
     }
 }
 
+#[test_casing(3, [4, 16, 60])]
+#[tokio::test]
+async fn concurrent_recovery_workflow(chunk_count: u64) {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let root_hash = prepare_storage_logs(pool.clone(), &temp_dir).await;
+    prune_storage(&pool, L1BatchNumber(1)).await;
+
+    let config = MetadataCalculatorRecoveryConfig::default();
+    let init_params = InitParameters::new(&pool, &config)
+        .await
+        .unwrap()
+        .expect("no init params");
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let tree_path = temp_dir.path().join("recovery");
+    let (tree, _) = create_tree_recovery(&tree_path, L1BatchNumber(1), &config).await;
+    let recovery_options = RecoveryOptions {
+        chunk_count,
+        concurrency_limit: 4,
+        events: Box::new(()),
+    };
+    let tree = tree
+        .recover(init_params, recovery_options, &pool, &stop_receiver)
+        .await
+        .unwrap()
+        .expect("Tree recovery unexpectedly aborted");
+    assert_eq!(tree.root_hash(), root_hash);
+}
+
+#[tokio::test]
+async fn recovery_cursor_persistence() {
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let config = MetadataCalculatorRecoveryConfig::default();
+    let (mut tree, _) = create_tree_recovery(temp_dir.path(), L1BatchNumber(1), &config).await;
+    assert_eq!(tree.recovery_cursor(10).await.unwrap(), 0);
+
+    tree.set_recovery_cursor(3, 10).await.unwrap();
+    assert_eq!(tree.recovery_cursor(10).await.unwrap(), 3);
+    tree.wait_for_persistence().await.unwrap();
+
+    let (mut tree, _) = create_tree_recovery(temp_dir.path(), L1BatchNumber(1), &config).await;
+    assert_eq!(tree.recovery_cursor(10).await.unwrap(), 3);
+    // The cursor should be ignored if the number of chunks has changed.
+    assert_eq!(tree.recovery_cursor(20).await.unwrap(), 0);
+}
+
 async fn prepare_storage_logs(pool: ConnectionPool<Core>, temp_dir: &TempDir) -> H256 {
     let mut storage = pool.connection().await.unwrap();
     insert_genesis_batch(&mut storage, &GenesisParams::mock())
@@ -290,6 +336,7 @@ async fn recovery_fault_tolerance(chunk_count: u64, case: FaultToleranceCase) {
     // Emulate a restart and recover 2 more chunks (or 1 + emulated persistence crash).
     let (mut tree, handle) = create_tree_recovery(&tree_path, L1BatchNumber(1), &config).await;
     assert_ne!(tree.root_hash().await, root_hash);
+    assert_eq!(tree.recovery_cursor(chunk_count).await.unwrap(), 1);
     let (stop_sender, stop_receiver) = watch::channel(false);
     let mut event_listener = TestEventListener::new(2, stop_sender).expect_recovered_chunks(1);
     let expected_recovered_chunks = if matches!(case, FaultToleranceCase::ParallelWithCrash) {
@@ -9,7 +9,7 @@ use test_casing::{test_casing, Product};
 use tokio::sync::{mpsc, watch};
 use zksync_config::configs::{
     chain::{OperationsManagerConfig, StateKeeperConfig},
-    database::{MerkleTreeConfig, MerkleTreeMode},
+    database::{MerkleTreeConfig, MerkleTreeMode, RocksdbProfile},
 };
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_health_check::{CheckHealth, HealthStatus};
@@ -56,6 +56,7 @@ pub(super) fn mock_config(db_path: &Path) -> MetadataCalculatorConfig {
         include_indices_and_filters_in_block_cache: false,
         memtable_capacity: 16 << 20,            // 16 MiB
         stalled_writes_timeout: Duration::ZERO, // writes should never be stalled in tests
+        rocksdb_profile: RocksdbProfile::default(),
         sealed_batches_have_protective_reads: true,
         recovery: MetadataCalculatorRecoveryConfig::default(),
     }
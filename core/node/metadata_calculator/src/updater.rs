@@ -5,6 +5,7 @@ use std::{ops, sync::Arc, time::Instant};
 use anyhow::Context as _;
 use futures::{future, FutureExt};
 use tokio::sync::watch;
+use tracing::Instrument as _;
 use zksync_config::configs::database::MerkleTreeMode;
 use zksync_dal::{helpers::wait_for_l1_batch, Connection, ConnectionPool, Core, CoreDal};
 use zksync_merkle_tree::domain::TreeMetadata;
@@ -13,6 +14,7 @@ use zksync_types::{
     block::{L1BatchHeader, L1BatchTreeData},
     L1BatchNumber,
 };
+use zksync_vlog::opentelemetry::{l1_batch_span, L1BatchStage};
 
 use super::{
     helpers::{AsyncTree, Delayer, L1BatchWithLogs},
@@ -116,7 +118,9 @@ impl TreeUpdater {
             };
             total_logs += current_l1_batch_data.storage_logs.len();
 
-            let process_l1_batch_task = self.process_l1_batch(current_l1_batch_data);
+            let process_l1_batch_task = self
+                .process_l1_batch(current_l1_batch_data)
+                .instrument(l1_batch_span(L1BatchStage::TreeUpdate, l1_batch_number.0));
             let load_next_l1_batch_task = async {
                 if l1_batch_number < last_l1_batch_number {
                     let next_l1_batch_number = l1_batch_number + 1;
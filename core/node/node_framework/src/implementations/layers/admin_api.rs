@@ -0,0 +1,111 @@
+use std::net::SocketAddr;
+
+use zksync_config::{configs::AdminApiSecrets, AdminApiConfig};
+use zksync_node_api_server::admin::AdminServer;
+
+use crate::{
+    implementations::resources::{
+        healthcheck::AppHealthCheckResource,
+        pools::{MasterPool, PoolResource},
+        state_keeper::StateKeeperPauseResource,
+    },
+    service::StopReceiver,
+    task::{Task, TaskId, TaskKind},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for the admin API server, allowing to control the running node.
+///
+/// State keeper control endpoints are only enabled if the state keeper layer is added before this layer.
+/// TEE attestation policy endpoints are only enabled via [`Self::with_tee_attestation_policy()`].
+/// If a graceful shutdown is requested via the API, the added task exits, which shuts down the node.
+#[derive(Debug)]
+pub struct AdminApiLayer {
+    config: AdminApiConfig,
+    secrets: AdminApiSecrets,
+    manage_tee_attestation_policy: bool,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
+    pub state_keeper_pause: Option<StateKeeperPauseResource>,
+    pub master_pool: Option<PoolResource<MasterPool>>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub task: AdminApiTask,
+}
+
+impl AdminApiLayer {
+    pub fn new(config: AdminApiConfig, secrets: AdminApiSecrets) -> Self {
+        Self {
+            config,
+            secrets,
+            manage_tee_attestation_policy: false,
+        }
+    }
+
+    /// Enables endpoints managing the TEE attestation policy of the proof data handler. Requires the master pool.
+    pub fn with_tee_attestation_policy(mut self, enabled: bool) -> Self {
+        self.manage_tee_attestation_policy = enabled;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for AdminApiLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "admin_api_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let bind_address = SocketAddr::from(([0, 0, 0, 0], self.config.http_port));
+        let mut server =
+            AdminServer::new(bind_address, self.secrets.auth_token, input.app_health.0);
+        if let Some(StateKeeperPauseResource(handle)) = input.state_keeper_pause {
+            server = server.with_state_keeper(handle);
+        }
+        if self.manage_tee_attestation_policy {
+            let master_pool = input.master_pool.ok_or_else(|| {
+                WiringError::Configuration(
+                    "managing TEE attestation policy requires the master pool".into(),
+                )
+            })?;
+            server = server.with_tee_attestation_policy(master_pool.get_singleton().await?);
+        }
+        Ok(Output {
+            task: AdminApiTask { server },
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct AdminApiTask {
+    server: AdminServer,
+}
+
+#[async_trait::async_trait]
+impl Task for AdminApiTask {
+    fn kind(&self) -> TaskKind {
+        // The node should be controllable even if it's waiting for preconditions.
+        TaskKind::UnconstrainedTask
+    }
+
+    fn id(&self) -> TaskId {
+        "admin_api_server".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.server.run(stop_receiver.0).await
+    }
+}
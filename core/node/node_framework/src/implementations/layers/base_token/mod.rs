@@ -2,8 +2,9 @@ use std::{str::FromStr, sync::Arc};
 
 use zksync_config::configs::ExternalPriceApiClientConfig;
 use zksync_external_price_api::{
+    aggregated_price_client::AggregatedPriceClient, chainlink_api::ChainlinkPriceClient,
     cmc_api::CmcPriceApiClient, coingecko_api::CoinGeckoPriceAPIClient,
-    forced_price_client::ForcedPriceClient, NoOpPriceAPIClient,
+    forced_price_client::ForcedPriceClient, NoOpPriceAPIClient, PriceAPIClient,
 };
 
 use crate::{
@@ -21,6 +22,8 @@ enum ExternalPriceApiKind {
     Forced,
     CoinGecko,
     CoinMarketCap,
+    Chainlink,
+    Aggregated,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -36,25 +39,45 @@ impl FromStr for ExternalPriceApiKind {
             "forced" => Self::Forced,
             "coingecko" => Self::CoinGecko,
             "coinmarketcap" => Self::CoinMarketCap,
+            "chainlink" => Self::Chainlink,
+            "aggregated" => Self::Aggregated,
             _ => return Err(UnknownExternalPriceApiClientSourceError(s.to_owned())),
         })
     }
 }
 
 impl ExternalPriceApiKind {
-    fn instantiate(&self, config: ExternalPriceApiClientConfig) -> PriceAPIClientResource {
-        PriceAPIClientResource(match self {
+    fn instantiate(
+        &self,
+        config: &ExternalPriceApiClientConfig,
+        aggregated_sources: &[(String, Self)],
+    ) -> Arc<dyn PriceAPIClient> {
+        match self {
             Self::NoOp => Arc::new(NoOpPriceAPIClient {}),
-            Self::Forced => Arc::new(ForcedPriceClient::new(config)),
-            Self::CoinGecko => Arc::new(CoinGeckoPriceAPIClient::new(config)),
-            Self::CoinMarketCap => Arc::new(CmcPriceApiClient::new(config)),
-        })
+            Self::Forced => Arc::new(ForcedPriceClient::new(config.clone())),
+            Self::CoinGecko => Arc::new(CoinGeckoPriceAPIClient::new(config.clone())),
+            Self::CoinMarketCap => Arc::new(CmcPriceApiClient::new(config.clone())),
+            Self::Chainlink => Arc::new(ChainlinkPriceClient::new(config.clone())),
+            Self::Aggregated => {
+                let aggregated_config = config
+                    .aggregated
+                    .as_ref()
+                    .expect("aggregated price client started with no config");
+                let sources = aggregated_sources
+                    .iter()
+                    .map(|(name, kind)| (name.clone(), kind.instantiate(config, &[])))
+                    .collect();
+                Arc::new(AggregatedPriceClient::new(sources, aggregated_config))
+            }
+        }
     }
 }
 
 #[derive(Debug)]
 pub struct ExternalPriceApiLayer {
     kind: ExternalPriceApiKind,
+    /// Sources used by the aggregated client; empty for other kinds.
+    aggregated_sources: Vec<(String, ExternalPriceApiKind)>,
     config: ExternalPriceApiClientConfig,
 }
 
@@ -62,8 +85,23 @@ impl TryFrom<ExternalPriceApiClientConfig> for ExternalPriceApiLayer {
     type Error = UnknownExternalPriceApiClientSourceError;
 
     fn try_from(config: ExternalPriceApiClientConfig) -> Result<Self, Self::Error> {
+        let kind: ExternalPriceApiKind = config.source.parse()?;
+        let mut aggregated_sources = vec![];
+        if kind == ExternalPriceApiKind::Aggregated {
+            let sources = config.aggregated.as_ref().map_or(&[][..], |x| &x.sources);
+            for source in sources {
+                let source_kind = source.parse()?;
+                // Nested aggregation is not supported.
+                if source_kind == ExternalPriceApiKind::Aggregated {
+                    return Err(UnknownExternalPriceApiClientSourceError(source.clone()));
+                }
+                aggregated_sources.push((source.clone(), source_kind));
+            }
+        }
+
         Ok(Self {
-            kind: config.source.parse()?,
+            kind,
+            aggregated_sources,
             config,
         })
     }
@@ -86,7 +124,10 @@ impl WiringLayer for ExternalPriceApiLayer {
 
     async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
         Ok(Output {
-            price_api_client: self.kind.instantiate(self.config),
+            price_api_client: PriceAPIClientResource(
+                self.kind
+                    .instantiate(&self.config, &self.aggregated_sources),
+            ),
         })
     }
 }
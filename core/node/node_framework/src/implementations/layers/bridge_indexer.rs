@@ -0,0 +1,67 @@
+use zksync_bridge_indexer::BridgeIndexer;
+use zksync_config::ContractsConfig;
+
+use crate::{
+    implementations::resources::pools::{MasterPool, PoolResource},
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for the bridge indexer.
+///
+/// Responsible for initializing and running of [`BridgeIndexer`] task, that indexes bridge events emitted on L2.
+/// Bridge events emitted on L1 are indexed by the Ethereum watcher; see [`EthWatchLayer::with_bridge_indexer()`].
+///
+/// [`EthWatchLayer::with_bridge_indexer()`]: super::eth_watch::EthWatchLayer::with_bridge_indexer()
+#[derive(Debug)]
+pub struct BridgeIndexerLayer {
+    contracts_config: ContractsConfig,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub bridge_indexer: BridgeIndexer,
+}
+
+impl BridgeIndexerLayer {
+    pub fn new(contracts_config: ContractsConfig) -> Self {
+        Self { contracts_config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for BridgeIndexerLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "bridge_indexer_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let pool = input.master_pool.get_singleton().await?;
+        let bridge_indexer = BridgeIndexer::new(pool, &self.contracts_config);
+        Ok(Output { bridge_indexer })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for BridgeIndexer {
+    fn id(&self) -> TaskId {
+        "bridge_indexer".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
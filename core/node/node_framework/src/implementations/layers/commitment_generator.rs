@@ -1,6 +1,6 @@
-use std::num::NonZero;
+use std::{num::NonZero, sync::Arc};
 
-use zksync_commitment_generator::CommitmentGenerator;
+use zksync_commitment_generator::{CommitmentGenerator, PubdataCommitmentScheme};
 use zksync_types::commitment::L1BatchCommitmentMode;
 
 use crate::{
@@ -21,6 +21,7 @@ use crate::{
 pub struct CommitmentGeneratorLayer {
     mode: L1BatchCommitmentMode,
     max_parallelism: Option<NonZero<u32>>,
+    pubdata_commitment_scheme: Option<Arc<dyn PubdataCommitmentScheme>>,
 }
 
 #[derive(Debug, FromContext)]
@@ -43,6 +44,7 @@ impl CommitmentGeneratorLayer {
         Self {
             mode,
             max_parallelism: None,
+            pubdata_commitment_scheme: None,
         }
     }
 
@@ -50,6 +52,15 @@ impl CommitmentGeneratorLayer {
         self.max_parallelism = max_parallelism;
         self
     }
+
+    /// Overrides the default pubdata commitment scheme for the commitment mode.
+    pub fn with_pubdata_commitment_scheme(
+        mut self,
+        scheme: Arc<dyn PubdataCommitmentScheme>,
+    ) -> Self {
+        self.pubdata_commitment_scheme = Some(scheme);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -72,6 +83,9 @@ impl WiringLayer for CommitmentGeneratorLayer {
         if let Some(max_parallelism) = self.max_parallelism {
             commitment_generator.set_max_parallelism(max_parallelism);
         }
+        if let Some(scheme) = self.pubdata_commitment_scheme {
+            commitment_generator.set_pubdata_commitment_scheme(scheme);
+        }
 
         input
             .app_health
@@ -5,6 +5,7 @@ use crate::{
     implementations::resources::{
         eth_interface::{EthInterfaceResource, GatewayEthInterfaceResource},
         healthcheck::AppHealthCheckResource,
+        l1_batch_commit_data_generator::L1BatchCommitDataGeneratorResource,
         pools::{MasterPool, PoolResource},
     },
     service::StopReceiver,
@@ -28,6 +29,7 @@ pub struct Input {
     pub l1_client: EthInterfaceResource,
     pub gateway_client: Option<GatewayEthInterfaceResource>,
     pub master_pool: PoolResource<MasterPool>,
+    pub commit_data_generator: Option<L1BatchCommitDataGeneratorResource>,
     #[context(default)]
     pub app_health: AppHealthCheckResource,
 }
@@ -71,7 +73,7 @@ impl WiringLayer for ConsistencyCheckerLayer {
 
         let singleton_pool = input.master_pool.get_singleton().await?;
 
-        let consistency_checker = ConsistencyChecker::new(
+        let mut consistency_checker = ConsistencyChecker::new(
             l1_client,
             gateway_client,
             self.max_batches_to_recheck,
@@ -82,6 +84,9 @@ impl WiringLayer for ConsistencyCheckerLayer {
         .await
         .map_err(WiringError::Internal)?
         .with_l1_diamond_proxy_addr(self.l1_diamond_proxy_addr);
+        if let Some(generator) = input.commit_data_generator {
+            consistency_checker = consistency_checker.with_commit_data_generator(generator.0);
+        }
 
         input
             .app_health
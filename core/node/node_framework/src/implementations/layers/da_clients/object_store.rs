@@ -3,7 +3,7 @@ use zksync_da_client::DataAvailabilityClient;
 use zksync_da_clients::object_store::ObjectStoreDAClient;
 
 use crate::{
-    implementations::resources::da_client::DAClientResource,
+    implementations::resources::da_client::{DAClientResource, FallbackDAClientResource},
     wiring_layer::{WiringError, WiringLayer},
     IntoContext,
 };
@@ -43,3 +43,40 @@ impl WiringLayer for ObjectStorageClientWiringLayer {
         })
     }
 }
+
+/// Wires the object store DA client used as a fallback in the DA dispatcher redundancy mode.
+#[derive(Debug)]
+pub struct FallbackObjectStorageClientWiringLayer {
+    config: ObjectStoreConfig,
+}
+
+impl FallbackObjectStorageClientWiringLayer {
+    pub fn new(config: ObjectStoreConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct FallbackOutput {
+    pub client: FallbackDAClientResource,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for FallbackObjectStorageClientWiringLayer {
+    type Input = ();
+    type Output = FallbackOutput;
+
+    fn layer_name(&self) -> &'static str {
+        "fallback_object_store_da_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        let client: Box<dyn DataAvailabilityClient> =
+            Box::new(ObjectStoreDAClient::new(self.config).await?);
+
+        Ok(FallbackOutput {
+            client: FallbackDAClientResource(client),
+        })
+    }
+}
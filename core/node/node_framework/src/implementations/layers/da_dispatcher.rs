@@ -3,7 +3,7 @@ use zksync_da_dispatcher::DataAvailabilityDispatcher;
 
 use crate::{
     implementations::resources::{
-        da_client::DAClientResource,
+        da_client::{DAClientResource, FallbackDAClientResource},
         pools::{MasterPool, PoolResource},
     },
     service::StopReceiver,
@@ -24,6 +24,7 @@ pub struct DataAvailabilityDispatcherLayer {
 pub struct Input {
     pub master_pool: PoolResource<MasterPool>,
     pub da_client: DAClientResource,
+    pub fallback_da_client: Option<FallbackDAClientResource>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -52,8 +53,8 @@ impl WiringLayer for DataAvailabilityDispatcherLayer {
     }
 
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
-        // A pool with size 2 is used here because there are 2 functions within a task that execute in parallel
-        let master_pool = input.master_pool.get_custom(2).await?;
+        // A pool with size 3 is used here because there are 3 functions within a task that execute in parallel
+        let master_pool = input.master_pool.get_custom(3).await?;
         let da_client = input.da_client.0;
 
         if let Some(limit) = da_client.blob_size_limit() {
@@ -65,8 +66,11 @@ impl WiringLayer for DataAvailabilityDispatcherLayer {
             }
         }
 
-        let da_dispatcher_task =
+        let mut da_dispatcher_task =
             DataAvailabilityDispatcher::new(master_pool, self.da_config, da_client);
+        if let Some(fallback_da_client) = input.fallback_da_client {
+            da_dispatcher_task = da_dispatcher_task.with_fallback_client(fallback_da_client.0);
+        }
 
         Ok(Output { da_dispatcher_task })
     }
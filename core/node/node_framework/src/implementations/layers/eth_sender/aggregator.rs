@@ -10,6 +10,8 @@ use crate::{
         circuit_breakers::CircuitBreakersResource,
         eth_interface::{BoundEthInterfaceForBlobsResource, BoundEthInterfaceResource},
         healthcheck::AppHealthCheckResource,
+        l1_batch_commit_data_generator::L1BatchCommitDataGeneratorResource,
+        leader_election::LeaderLeaseResource,
         object_store::ObjectStoreResource,
         pools::{MasterPool, PoolResource, ReplicaPool},
     },
@@ -32,7 +34,9 @@ use crate::{
 /// - `BoundEthInterfaceResource`
 /// - `BoundEthInterfaceForBlobsResource` (optional)
 /// - `ObjectStoreResource`
+/// - `L1BatchCommitDataGeneratorResource` (optional)
 /// - `CircuitBreakersResource` (adds a circuit breaker)
+/// - `LeaderLeaseResource` (optional; if present, `eth_txs` are only saved while the lease is held)
 ///
 /// ## Adds tasks
 ///
@@ -54,6 +58,8 @@ pub struct Input {
     pub eth_client: Option<BoundEthInterfaceResource>,
     pub eth_client_blobs: Option<BoundEthInterfaceForBlobsResource>,
     pub object_store: ObjectStoreResource,
+    pub commit_data_generator: Option<L1BatchCommitDataGeneratorResource>,
+    pub leader_lease: Option<LeaderLeaseResource>,
     #[context(default)]
     pub circuit_breakers: CircuitBreakersResource,
     #[context(default)]
@@ -116,7 +122,7 @@ impl WiringLayer for EthTxAggregatorLayer {
             self.settlement_mode,
         );
 
-        let eth_tx_aggregator = EthTxAggregator::new(
+        let mut eth_tx_aggregator = EthTxAggregator::new(
             master_pool.clone(),
             config.clone(),
             aggregator,
@@ -129,6 +135,12 @@ impl WiringLayer for EthTxAggregatorLayer {
             self.settlement_mode,
         )
         .await;
+        if let Some(generator) = input.commit_data_generator {
+            eth_tx_aggregator = eth_tx_aggregator.with_commit_data_generator(generator.0);
+        }
+        if let Some(LeaderLeaseResource(lease)) = input.leader_lease {
+            eth_tx_aggregator = eth_tx_aggregator.with_lease_fence(lease);
+        }
 
         // Insert circuit breaker.
         input
@@ -9,6 +9,7 @@ use crate::{
         eth_interface::{BoundEthInterfaceForBlobsResource, BoundEthInterfaceResource},
         gas_adjuster::GasAdjusterResource,
         healthcheck::AppHealthCheckResource,
+        leader_election::LeaderLeaseResource,
         pools::{MasterPool, PoolResource, ReplicaPool},
     },
     service::StopReceiver,
@@ -30,6 +31,7 @@ use crate::{
 /// - `BoundEthInterfaceForBlobsResource` (optional)
 /// - `TxParamsResource`
 /// - `CircuitBreakersResource` (adds a circuit breaker)
+/// - `LeaderLeaseResource` (optional; if present, transactions are only sent while the lease is held)
 ///
 /// ## Adds tasks
 ///
@@ -47,6 +49,7 @@ pub struct Input {
     pub eth_client: BoundEthInterfaceResource,
     pub eth_client_blobs: Option<BoundEthInterfaceForBlobsResource>,
     pub gas_adjuster: GasAdjusterResource,
+    pub leader_lease: Option<LeaderLeaseResource>,
     #[context(default)]
     pub circuit_breakers: CircuitBreakersResource,
     #[context(default)]
@@ -89,7 +92,7 @@ impl WiringLayer for EthTxManagerLayer {
 
         let gas_adjuster = input.gas_adjuster.0;
 
-        let eth_tx_manager = EthTxManager::new(
+        let mut eth_tx_manager = EthTxManager::new(
             master_pool,
             config,
             gas_adjuster,
@@ -109,6 +112,9 @@ impl WiringLayer for EthTxManagerLayer {
                 None
             },
         );
+        if let Some(LeaderLeaseResource(lease)) = input.leader_lease {
+            eth_tx_manager = eth_tx_manager.with_lease_fence(lease);
+        }
 
         // Insert circuit breaker.
         input
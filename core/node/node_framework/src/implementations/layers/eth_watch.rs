@@ -1,6 +1,9 @@
+use std::sync::Arc;
+
+use zksync_bridge_indexer::L1BridgeEventHandler;
 use zksync_config::{ContractsConfig, EthWatchConfig};
 use zksync_contracts::chain_admin_contract;
-use zksync_eth_watch::{EthHttpQueryClient, EthWatch};
+use zksync_eth_watch::{CustomEventHandler, CustomEventHandlers, EthHttpQueryClient, EthWatch};
 use zksync_types::L2ChainId;
 
 use crate::{
@@ -17,12 +20,17 @@ use crate::{
 /// Wiring layer for ethereum watcher
 ///
 /// Responsible for initializing and running of [`EthWatch`] component, that polls the Ethereum node for the relevant events,
-/// such as priority operations (aka L1 transactions), protocol upgrades etc.
+/// such as priority operations (aka L1 transactions), protocol upgrades etc. Custom events specified in the config
+/// are routed to the handlers registered in this layer. If bridge indexing is enabled, bridge events emitted
+/// by the L1 shared bridge are additionally routed to [`L1BridgeEventHandler`]; these events have a dedicated cursor
+/// starting from the configured L1 block.
 #[derive(Debug)]
 pub struct EthWatchLayer {
     eth_watch_config: EthWatchConfig,
     contracts_config: ContractsConfig,
     chain_id: L2ChainId,
+    custom_event_handlers: CustomEventHandlers,
+    index_bridge_events: bool,
 }
 
 #[derive(Debug, FromContext)]
@@ -49,8 +57,26 @@ impl EthWatchLayer {
             eth_watch_config,
             contracts_config,
             chain_id,
+            custom_event_handlers: CustomEventHandlers::default(),
+            index_bridge_events: false,
         }
     }
+
+    /// Registers a handler for custom events, which can be referenced in the config by `name`.
+    pub fn with_custom_event_handler(
+        mut self,
+        name: impl Into<String>,
+        handler: Arc<dyn CustomEventHandler>,
+    ) -> Self {
+        self.custom_event_handlers.insert(name, handler);
+        self
+    }
+
+    /// Enables indexing bridge events emitted on L1 for the bridge indexer.
+    pub fn with_bridge_indexer(mut self) -> Self {
+        self.index_bridge_events = true;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -77,7 +103,7 @@ impl WiringLayer for EthWatchLayer {
             self.eth_watch_config.confirmations_for_eth_event,
         );
 
-        let eth_watch = EthWatch::new(
+        let mut eth_watch = EthWatch::new(
             &chain_admin_contract(),
             Box::new(eth_client),
             None,
@@ -85,7 +111,41 @@ impl WiringLayer for EthWatchLayer {
             self.eth_watch_config.poll_interval(),
             self.chain_id,
         )
-        .await?;
+        .await?
+        .with_custom_events(
+            &self.eth_watch_config.custom_events,
+            &self.custom_event_handlers,
+        )
+        .map_err(|err| WiringError::Configuration(format!("{err:#}")))?;
+
+        if self.index_bridge_events {
+            let l1_shared_bridge_addr = self
+                .contracts_config
+                .l1_shared_bridge_proxy_addr
+                .ok_or_else(|| {
+                    WiringError::Configuration("L1 shared bridge address is not set".into())
+                })?;
+            let start_block = self
+                .eth_watch_config
+                .bridge_events_start_block
+                .ok_or_else(|| {
+                    WiringError::Configuration(
+                        "`bridge_events_start_block` must be set to index bridge events".into(),
+                    )
+                })?;
+            let mut handlers = CustomEventHandlers::default();
+            handlers.insert(
+                L1BridgeEventHandler::NAME,
+                Arc::new(L1BridgeEventHandler::new(self.chain_id)),
+            );
+            eth_watch = eth_watch
+                .with_bridge_events(
+                    &L1BridgeEventHandler::watched_events(l1_shared_bridge_addr),
+                    &handlers,
+                    start_block,
+                )
+                .map_err(|err| WiringError::Configuration(format!("{err:#}")))?;
+        }
 
         Ok(Output { eth_watch })
     }
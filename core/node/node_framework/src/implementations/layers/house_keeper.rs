@@ -1,10 +1,13 @@
+use std::num::NonZeroU32;
+
 use zksync_config::configs::house_keeper::HouseKeeperConfig;
 use zksync_house_keeper::{
-    blocks_state_reporter::L1BatchMetricsReporter, periodic_job::PeriodicJob,
+    blocks_state_reporter::L1BatchMetricsReporter, eth_txs_history_archiver::EthTxsHistoryArchiver,
+    partitions_maintainer::TablePartitionsMaintainer, periodic_job::PeriodicJob,
 };
 
 use crate::{
-    implementations::resources::pools::{PoolResource, ReplicaPool},
+    implementations::resources::pools::{MasterPool, PoolResource, ReplicaPool},
     service::StopReceiver,
     task::{Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
@@ -22,6 +25,7 @@ pub struct HouseKeeperLayer {
 #[context(crate = crate)]
 pub struct Input {
     pub replica_pool: PoolResource<ReplicaPool>,
+    pub master_pool: PoolResource<MasterPool>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -29,6 +33,10 @@ pub struct Input {
 pub struct Output {
     #[context(task)]
     pub l1_batch_metrics_reporter: L1BatchMetricsReporter,
+    #[context(task)]
+    pub table_partitions_maintainer: Option<TablePartitionsMaintainer>,
+    #[context(task)]
+    pub eth_txs_history_archiver: Option<EthTxsHistoryArchiver>,
 }
 
 impl HouseKeeperLayer {
@@ -59,8 +67,30 @@ impl WiringLayer for HouseKeeperLayer {
             replica_pool,
         );
 
+        let table_partitions_maintainer = match self.house_keeper_config.table_partition_size {
+            None => None,
+            Some(size) => {
+                let partition_size = NonZeroU32::new(size).ok_or_else(|| {
+                    WiringError::Configuration("`table_partition_size` must be positive".into())
+                })?;
+                // Creating partitions requires DDL statements, so the master pool must be used.
+                let master_pool = input.master_pool.get_singleton().await?;
+                Some(TablePartitionsMaintainer::new(partition_size, master_pool))
+            }
+        };
+
+        let eth_txs_history_archiver = match self.house_keeper_config.eth_txs_history_retention() {
+            None => None,
+            Some(retention) => {
+                let master_pool = input.master_pool.get_singleton().await?;
+                Some(EthTxsHistoryArchiver::new(retention, master_pool))
+            }
+        };
+
         Ok(Output {
             l1_batch_metrics_reporter,
+            table_partitions_maintainer,
+            eth_txs_history_archiver,
         })
     }
 }
@@ -75,3 +105,25 @@ impl Task for L1BatchMetricsReporter {
         (*self).run(stop_receiver.0).await
     }
 }
+
+#[async_trait::async_trait]
+impl Task for TablePartitionsMaintainer {
+    fn id(&self) -> TaskId {
+        "table_partitions_maintainer".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for EthTxsHistoryArchiver {
+    fn id(&self) -> TaskId {
+        "eth_txs_history_archiver".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use zksync_l1_contract_interface::i_executor::commit::data_generator::L1BatchCommitDataGenerator;
+use zksync_types::commitment::L1BatchCommitmentMode;
+
+use crate::{
+    implementations::resources::l1_batch_commit_data_generator::L1BatchCommitDataGeneratorResource,
+    wiring_layer::{WiringError, WiringLayer},
+    IntoContext,
+};
+
+/// Wiring layer providing the generator of L1 batch commit data shared by `eth_sender` and the consistency checker.
+///
+/// If this layer is not added, the components use the default generator for the configured commitment mode.
+/// Chains with modified pubdata layouts can use [`Self::custom()`] to supply their own generator.
+///
+/// ## Adds resources
+///
+/// - `L1BatchCommitDataGeneratorResource`
+#[derive(Debug)]
+pub struct L1BatchCommitDataGeneratorLayer {
+    generator: Arc<dyn L1BatchCommitDataGenerator>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub generator: L1BatchCommitDataGeneratorResource,
+}
+
+impl L1BatchCommitDataGeneratorLayer {
+    /// Creates a layer with the default generator for the specified commitment mode.
+    pub fn new(commitment_mode: L1BatchCommitmentMode) -> Self {
+        Self {
+            generator: <dyn L1BatchCommitDataGenerator>::for_mode(commitment_mode),
+        }
+    }
+
+    /// Creates a layer with a custom generator.
+    pub fn custom(generator: Arc<dyn L1BatchCommitDataGenerator>) -> Self {
+        Self { generator }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for L1BatchCommitDataGeneratorLayer {
+    type Input = ();
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "l1_batch_commit_data_generator_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        Ok(Output {
+            generator: L1BatchCommitDataGeneratorResource(self.generator),
+        })
+    }
+}
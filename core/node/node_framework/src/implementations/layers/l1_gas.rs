@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
 use zksync_config::configs::chain::{FeeModelVersion, StateKeeperConfig};
-use zksync_node_fee_model::{ApiFeeInputProvider, MainNodeFeeInputProvider};
+use zksync_node_fee_model::{
+    ApiFeeInputProvider, CustomPubdataPricing, MainNodeFeeInputProvider, PipelineBackpressure,
+    PipelineCongestionMonitor, PubdataPricing,
+};
 use zksync_types::fee_model::{FeeModelConfig, FeeModelConfigV1, FeeModelConfigV2};
 
 use crate::{
@@ -12,6 +15,8 @@ use crate::{
         l1_tx_params::TxParamsResource,
         pools::{PoolResource, ReplicaPool},
     },
+    service::StopReceiver,
+    task::{Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
     FromContext, IntoContext,
 };
@@ -21,6 +26,10 @@ use crate::{
 #[derive(Debug)]
 pub struct L1GasLayer {
     fee_model_config: FeeModelConfig,
+    /// L1 gas prices and corresponding pubdata prices of the custom pubdata pricing curve.
+    pubdata_price_curve: (Vec<u64>, Vec<u64>),
+    /// Backpressure bumping the L2 gas price in the API if the proof / execution pipeline is congested.
+    backpressure: Option<PipelineBackpressure>,
 }
 
 #[derive(Debug, FromContext)]
@@ -39,12 +48,24 @@ pub struct Output {
     pub sequencer_fee_input: SequencerFeeInputResource,
     pub api_fee_input: ApiFeeInputResource,
     pub l1_tx_params: TxParamsResource,
+    /// Only present if pipeline backpressure is configured.
+    #[context(task)]
+    pub congestion_monitor: Option<PipelineCongestionMonitor>,
 }
 
 impl L1GasLayer {
     pub fn new(state_keeper_config: &StateKeeperConfig) -> Self {
         Self {
             fee_model_config: Self::map_config(state_keeper_config),
+            pubdata_price_curve: (
+                state_keeper_config
+                    .pubdata_price_curve_l1_gas_prices
+                    .clone(),
+                state_keeper_config
+                    .pubdata_price_curve_pubdata_prices
+                    .clone(),
+            ),
+            backpressure: PipelineBackpressure::new(state_keeper_config),
         }
     }
 
@@ -77,22 +98,47 @@ impl WiringLayer for L1GasLayer {
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
         let ratio_provider = input.base_token_ratio_provider;
 
-        let main_fee_input_provider = Arc::new(MainNodeFeeInputProvider::new(
+        let mut main_fee_input_provider = MainNodeFeeInputProvider::new(
             input.gas_adjuster.0.clone(),
             ratio_provider.0,
             self.fee_model_config,
-        ));
+        );
+        let (l1_gas_prices, pubdata_prices) = &self.pubdata_price_curve;
+        if !l1_gas_prices.is_empty() || !pubdata_prices.is_empty() {
+            let pricing = CustomPubdataPricing::from_config(l1_gas_prices, pubdata_prices)
+                .map_err(|err| WiringError::Configuration(format!("{err:#}")))?;
+            let pricing: Arc<dyn PubdataPricing> = Arc::new(pricing);
+            main_fee_input_provider = main_fee_input_provider.with_pubdata_pricing(pricing);
+        }
+        let main_fee_input_provider = Arc::new(main_fee_input_provider);
 
         let replica_pool = input.replica_pool.get().await?;
-        let api_fee_input_provider = Arc::new(ApiFeeInputProvider::new(
-            main_fee_input_provider.clone(),
-            replica_pool,
-        ));
+        let congestion_monitor = self
+            .backpressure
+            .map(|backpressure| PipelineCongestionMonitor::new(backpressure, replica_pool.clone()));
+        let mut api_fee_input_provider =
+            ApiFeeInputProvider::new(main_fee_input_provider.clone(), replica_pool);
+        if let Some(monitor) = &congestion_monitor {
+            api_fee_input_provider = api_fee_input_provider.with_congestion(monitor.subscribe());
+        }
+        let api_fee_input_provider = Arc::new(api_fee_input_provider);
 
         Ok(Output {
             sequencer_fee_input: main_fee_input_provider.into(),
             api_fee_input: api_fee_input_provider.into(),
             l1_tx_params: input.gas_adjuster.0.into(),
+            congestion_monitor,
         })
     }
 }
+
+#[async_trait::async_trait]
+impl Task for PipelineCongestionMonitor {
+    fn id(&self) -> TaskId {
+        "pipeline_congestion_monitor".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -0,0 +1,404 @@
+use std::{
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use serde::Serialize;
+use zksync_config::configs::LeaderElectionConfig;
+use zksync_dal::{node_leases_dal::NodeLease, ConnectionPool, Core, CoreDal};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+
+use crate::{
+    implementations::resources::{
+        healthcheck::AppHealthCheckResource,
+        leader_election::LeaderLeaseResource,
+        pools::{MasterPool, PoolResource},
+    },
+    service::{ShutdownHook, StopReceiver},
+    task::{Task, TaskId, TaskKind},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for leader election among main node instances sharing the same Postgres database
+/// (aka high-availability mode).
+///
+/// Adds a precondition waiting until this instance acquires the lease, so that standby instances don't start
+/// any tasks except for unconstrained ones (e.g., the healthcheck server). Once the lease is acquired, the added task
+/// periodically renews it and exits with an error if the lease is lost, which shuts down the node. The lease is released
+/// on node shutdown, so that a standby instance can take over without waiting for the lease to expire.
+///
+/// Also provides [`LeaderLeaseResource`] used to fence all sequencer writers: L1 batch and L2 block sealing
+/// in the state keeper, and eth_txs creation / sending in the eth sender. Each of these writes is performed
+/// in a DB transaction that checks and locks the lease, so an instance that has lost the lease cannot persist
+/// anything even before the renewal task notices the loss and shuts down the node. Thus, this layer must be added
+/// before the state keeper and eth sender layers.
+#[derive(Debug)]
+pub struct LeaderElectionLayer {
+    config: LeaderElectionConfig,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub lease: LeaderLeaseResource,
+    #[context(task)]
+    pub acquisition_task: LeaseAcquisitionTask,
+    #[context(task)]
+    pub renewal_task: LeaseRenewalTask,
+    pub release_hook: ShutdownHook,
+}
+
+impl LeaderElectionLayer {
+    pub fn new(config: LeaderElectionConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for LeaderElectionLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "leader_election_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let instance_name = self.config.instance_name.as_deref().unwrap_or("main_node");
+        // The random suffix ensures that holder IDs are unique even if instances are misconfigured to have the same name,
+        // and that a restarted instance doesn't consider the lease of its previous incarnation as its own.
+        let lease = NodeLease {
+            name: self.config.lease_name.clone(),
+            holder: format!("{instance_name}/{:016x}", rand::random::<u64>()),
+        };
+        let pool = input.master_pool.get_custom(1).await?;
+        let (health_check, health_updater) = ReactiveHealthCheck::new("leader_election");
+        input
+            .app_health
+            .0
+            .insert_component(health_check)
+            .map_err(WiringError::internal)?;
+
+        let election = LeaderElection {
+            pool,
+            lease: lease.clone(),
+            ttl: self.config.lease_ttl(),
+            health_updater: Arc::new(health_updater),
+        };
+        let release_hook = ShutdownHook::new("leader_lease_release", {
+            let election = election.clone();
+            async move { election.release().await }
+        });
+        Ok(Output {
+            lease: LeaderLeaseResource(lease),
+            acquisition_task: LeaseAcquisitionTask(election.clone()),
+            renewal_task: LeaseRenewalTask(election),
+            release_hook,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum LeaderRole {
+    Standby,
+    Leader,
+}
+
+#[derive(Debug, Serialize)]
+struct LeaderElectionDetails<'a> {
+    role: LeaderRole,
+    lease: &'a str,
+    holder: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    current_leader: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct LeaderElection {
+    pool: ConnectionPool<Core>,
+    lease: NodeLease,
+    ttl: Duration,
+    health_updater: Arc<HealthUpdater>,
+}
+
+impl LeaderElection {
+    /// The lease is acquired / renewed several times during its TTL, so that a single failed attempt
+    /// (e.g., because of a transient DB error) doesn't lead to the lease being lost.
+    fn poll_interval(&self) -> Duration {
+        self.ttl / 3
+    }
+
+    fn update_health(&self, role: LeaderRole, current_leader: Option<String>) {
+        let details = LeaderElectionDetails {
+            role,
+            lease: &self.lease.name,
+            holder: &self.lease.holder,
+            current_leader,
+        };
+        self.health_updater
+            .update(Health::from(HealthStatus::Ready).with_details(details));
+    }
+
+    async fn try_acquire(&self) -> anyhow::Result<Result<(), Option<String>>> {
+        self.with_timeout(self.try_acquire_inner()).await
+    }
+
+    async fn try_acquire_inner(&self) -> anyhow::Result<Result<(), Option<String>>> {
+        let mut storage = self.pool.connection_tagged("leader_election").await?;
+        let mut dal = storage.node_leases_dal();
+        if dal.try_acquire_lease(&self.lease, self.ttl).await? {
+            return Ok(Ok(()));
+        }
+        let current_leader = dal.get_lease_holder(&self.lease.name).await?;
+        Ok(Err(current_leader))
+    }
+
+    /// Bounds the duration of a DB operation, so that a stalled connection (e.g., because of a network partition
+    /// between the node and Postgres) doesn't delay lease renewal past the lease expiration.
+    async fn with_timeout<T>(
+        &self,
+        operation: impl Future<Output = anyhow::Result<T>>,
+    ) -> anyhow::Result<T> {
+        let timeout = self.poll_interval();
+        tokio::time::timeout(timeout, operation)
+            .await
+            .map_err(|_| anyhow::anyhow!("timed out after {timeout:?}"))?
+    }
+
+    async fn acquire(&self, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        tracing::info!(
+            "Acquiring lease `{}` as `{}`",
+            self.lease.name,
+            self.lease.holder
+        );
+        while !*stop_receiver.0.borrow_and_update() {
+            match self.try_acquire().await {
+                Ok(Ok(())) => {
+                    tracing::info!(
+                        "Acquired lease `{}`; this instance is the leader",
+                        self.lease.name
+                    );
+                    self.update_health(LeaderRole::Leader, None);
+                    return Ok(());
+                }
+                Ok(Err(current_leader)) => {
+                    tracing::debug!(
+                        "Lease `{}` is held by {current_leader:?}; waiting",
+                        self.lease.name
+                    );
+                    self.update_health(LeaderRole::Standby, current_leader);
+                }
+                Err(err) => {
+                    tracing::warn!("Failed acquiring lease `{}`: {err:#}", self.lease.name);
+                }
+            }
+
+            // Error here corresponds to a timeout w/o `stop_receiver` changed; we're OK with this.
+            tokio::time::timeout(self.poll_interval(), stop_receiver.0.changed())
+                .await
+                .ok();
+        }
+        tracing::info!("Stop signal received, leader election is shutting down");
+        Ok(())
+    }
+
+    async fn renew(&self, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        // The lease was acquired by the precondition task right before this task was started.
+        let mut last_renewal = Instant::now();
+        while !*stop_receiver.0.borrow_and_update() {
+            tokio::time::timeout(self.poll_interval(), stop_receiver.0.changed())
+                .await
+                .ok();
+            if *stop_receiver.0.borrow() {
+                break;
+            }
+
+            let renewal_start = Instant::now();
+            let renewal_result = self
+                .with_timeout(async {
+                    let mut storage = self.pool.connection_tagged("leader_election").await?;
+                    let renewed = storage
+                        .node_leases_dal()
+                        .renew_lease(&self.lease, self.ttl)
+                        .await?;
+                    anyhow::Ok(renewed)
+                })
+                .await;
+
+            match renewal_result {
+                Ok(true) => {
+                    last_renewal = renewal_start;
+                }
+                Ok(false) => {
+                    anyhow::bail!(
+                        "lease `{}` was lost by `{}`; shutting down the node",
+                        self.lease.name,
+                        self.lease.holder
+                    );
+                }
+                Err(err) => {
+                    // We cannot be sure that we still hold the lease once its TTL has elapsed since the last renewal.
+                    if last_renewal.elapsed() >= self.ttl {
+                        return Err(err).with_context(|| {
+                            format!(
+                                "failed renewing lease `{}` before its expiration",
+                                self.lease.name
+                            )
+                        });
+                    }
+                    tracing::warn!("Failed renewing lease `{}`: {err:#}", self.lease.name);
+                }
+            }
+        }
+        tracing::info!("Stop signal received, leader lease renewal is shutting down");
+        Ok(())
+    }
+
+    async fn release(&self) -> anyhow::Result<()> {
+        let mut storage = self.pool.connection_tagged("leader_election").await?;
+        storage.node_leases_dal().release_lease(&self.lease).await?;
+        tracing::info!("Released lease `{}`", self.lease.name);
+        Ok(())
+    }
+}
+
+/// Precondition waiting until this node instance acquires the leader lease.
+#[derive(Debug)]
+pub struct LeaseAcquisitionTask(LeaderElection);
+
+#[async_trait::async_trait]
+impl Task for LeaseAcquisitionTask {
+    fn kind(&self) -> TaskKind {
+        TaskKind::Precondition
+    }
+
+    fn id(&self) -> TaskId {
+        "leader_election/acquisition".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.0.acquire(stop_receiver).await
+    }
+}
+
+/// Task periodically renewing the leader lease.
+#[derive(Debug)]
+pub struct LeaseRenewalTask(LeaderElection);
+
+#[async_trait::async_trait]
+impl Task for LeaseRenewalTask {
+    fn id(&self) -> TaskId {
+        "leader_election/renewal".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.0.renew(stop_receiver).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::watch;
+
+    use super::*;
+
+    const TTL: Duration = Duration::from_millis(300);
+
+    fn election(pool: &ConnectionPool<Core>, holder: &str) -> LeaderElection {
+        LeaderElection {
+            pool: pool.clone(),
+            lease: NodeLease {
+                name: "sequencer".to_owned(),
+                holder: holder.to_owned(),
+            },
+            ttl: TTL,
+            health_updater: Arc::new(ReactiveHealthCheck::new("leader_election").1),
+        }
+    }
+
+    #[tokio::test]
+    async fn standby_takes_over_after_leader_release() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let (leader, standby) = (election(&pool, "leader"), election(&pool, "standby"));
+        let (leader_stop_sender, leader_stop_receiver) = watch::channel(false);
+        let (_standby_stop_sender, standby_stop_receiver) = watch::channel(false);
+
+        leader
+            .acquire(StopReceiver(leader_stop_receiver.clone()))
+            .await
+            .unwrap();
+        let renewal_task = tokio::spawn({
+            let leader = leader.clone();
+            async move { leader.renew(StopReceiver(leader_stop_receiver)).await }
+        });
+        let acquisition_task = tokio::spawn({
+            let standby = standby.clone();
+            async move { standby.acquire(StopReceiver(standby_stop_receiver)).await }
+        });
+
+        // The leader renews the lease, so the standby instance cannot acquire it even after the lease TTL.
+        tokio::time::sleep(TTL * 3).await;
+        assert!(!acquisition_task.is_finished());
+
+        leader_stop_sender.send_replace(true);
+        renewal_task.await.unwrap().unwrap();
+        leader.release().await.unwrap();
+        tokio::time::timeout(TTL, acquisition_task)
+            .await
+            .expect("standby didn't acquire the released lease")
+            .unwrap()
+            .unwrap();
+
+        let mut storage = pool.connection().await.unwrap();
+        let dal = &mut storage.node_leases_dal();
+        assert!(dal.check_lease(&standby.lease).await.unwrap());
+        assert!(!dal.check_lease(&leader.lease).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn stalled_leader_steps_down_after_lease_expiration() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let (leader, standby) = (election(&pool, "leader"), election(&pool, "standby"));
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+
+        leader
+            .acquire(StopReceiver(stop_receiver.clone()))
+            .await
+            .unwrap();
+        // The leader doesn't renew the lease (e.g., because it's partitioned from the database),
+        // so the standby instance takes over after the lease expires.
+        tokio::time::timeout(
+            TTL * 3,
+            standby.acquire(StopReceiver(stop_receiver.clone())),
+        )
+        .await
+        .expect("standby didn't acquire the expired lease")
+        .unwrap();
+
+        // Once the connectivity is restored, the old leader must notice the lease loss.
+        let err = tokio::time::timeout(TTL * 3, leader.renew(StopReceiver(stop_receiver)))
+            .await
+            .expect("old leader didn't notice the lease loss")
+            .unwrap_err();
+        assert!(format!("{err:#}").contains("was lost"), "{err:#}");
+
+        // Writes fenced by the lease are rejected for the old leader.
+        let mut storage = pool.connection().await.unwrap();
+        let mut transaction = storage.start_transaction().await.unwrap();
+        let dal = &mut transaction.node_leases_dal();
+        assert!(!dal.check_lease(&leader.lease).await.unwrap());
+        assert!(dal.check_lease(&standby.lease).await.unwrap());
+    }
+}
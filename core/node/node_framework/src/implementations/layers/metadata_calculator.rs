@@ -7,8 +7,8 @@ use std::{
 use anyhow::Context as _;
 use zksync_config::configs::{api::MerkleTreeApiConfig, database::MerkleTreeMode};
 use zksync_metadata_calculator::{
-    LazyAsyncTreeReader, MerkleTreePruningTask, MerkleTreeReaderConfig, MetadataCalculator,
-    MetadataCalculatorConfig, StaleKeysRepairTask, TreeReaderTask,
+    LazyAsyncTreeReader, MerkleTreePruningOptions, MerkleTreePruningTask, MerkleTreeReaderConfig,
+    MetadataCalculator, MetadataCalculatorConfig, StaleKeysRepairTask, TreeReaderTask,
 };
 use zksync_storage::RocksDB;
 
@@ -31,6 +31,7 @@ pub struct MetadataCalculatorLayer {
     config: MetadataCalculatorConfig,
     tree_api_config: Option<MerkleTreeApiConfig>,
     pruning_config: Option<Duration>,
+    pruning_options: MerkleTreePruningOptions,
     stale_keys_repair_enabled: bool,
 }
 
@@ -69,6 +70,7 @@ impl MetadataCalculatorLayer {
             config,
             tree_api_config: None,
             pruning_config: None,
+            pruning_options: MerkleTreePruningOptions::default(),
             stale_keys_repair_enabled: false,
         }
     }
@@ -83,6 +85,13 @@ impl MetadataCalculatorLayer {
         self
     }
 
+    /// Sets additional options for the pruning task. Has no effect unless pruning is enabled
+    /// via [`Self::with_pruning_config()`].
+    pub fn with_pruning_options(mut self, pruning_options: MerkleTreePruningOptions) -> Self {
+        self.pruning_options = pruning_options;
+        self
+    }
+
     pub fn with_stale_keys_repair(mut self) -> Self {
         self.stale_keys_repair_enabled = true;
         self
@@ -142,7 +151,9 @@ impl WiringLayer for MetadataCalculatorLayer {
             .pruning_config
             .map(
                 |pruning_removal_delay| -> Result<MerkleTreePruningTask, WiringError> {
-                    let pruning_task = metadata_calculator.pruning_task(pruning_removal_delay);
+                    let pruning_task = metadata_calculator
+                        .pruning_task(pruning_removal_delay)
+                        .with_options(self.pruning_options);
                     app_health
                         .insert_component(pruning_task.health_check())
                         .map_err(|err| WiringError::Internal(err.into()))?;
@@ -1,6 +1,8 @@
+pub mod admin_api;
 pub mod base_token;
 pub mod batch_status_updater;
 pub mod block_reverter;
+pub mod bridge_indexer;
 pub mod circuit_breaker_checker;
 pub mod commitment_generator;
 pub mod consensus;
@@ -14,8 +16,10 @@ pub mod external_proof_integration_api;
 pub mod gas_adjuster;
 pub mod healtcheck_server;
 pub mod house_keeper;
+pub mod l1_batch_commit_data_generator;
 pub mod l1_batch_commitment_mode_validation;
 pub mod l1_gas;
+pub mod leader_election;
 pub mod logs_bloom_backfill;
 pub mod main_node_client;
 pub mod main_node_fee_params_fetcher;
@@ -32,8 +36,10 @@ pub mod query_eth_client;
 pub mod reorg_detector;
 pub mod sigint;
 pub mod state_keeper;
+pub mod storage_analytics;
 pub mod sync_state_updater;
 pub mod tree_data_fetcher;
 pub mod validate_chain_ids;
 pub mod vm_runner;
 pub mod web3_api;
+pub mod withdrawal_finalizer;
@@ -1,12 +1,15 @@
 use std::{num::NonZeroUsize, sync::Arc};
 
+use anyhow::Context as _;
+
 // Re-export to initialize the layer without having to depend on the crate directly.
-pub use zksync_node_storage_init::SnapshotRecoveryConfig;
 use zksync_node_storage_init::{
     external_node::{ExternalNodeGenesis, ExternalNodeReverter, ExternalNodeSnapshotRecovery},
     InitializeStorage, NodeInitializationStrategy, RevertStorage,
 };
+pub use zksync_node_storage_init::{RecoverySource, SnapshotRecoveryConfig};
 use zksync_types::L2ChainId;
+use zksync_web3_decl::client::{Client, DynClient, L2};
 
 use super::NodeInitializationStrategyResource;
 use crate::{
@@ -81,8 +84,18 @@ impl WiringLayer for ExternalNodeInitStrategyLayer {
                     .master_pool
                     .get_custom(self.max_postgres_concurrency.get() as u32 + 1)
                     .await?;
+                let recovery_client = match &recovery_config.source {
+                    RecoverySource::MainNode => client.clone(),
+                    RecoverySource::Peer(url) => {
+                        let peer_client = Client::http(url.clone())
+                            .context("failed creating JSON-RPC client for recovery peer")?
+                            .for_network(self.l2_chain_id.into())
+                            .build();
+                        Box::new(peer_client) as Box<DynClient<L2>>
+                    }
+                };
                 let recovery: Arc<dyn InitializeStorage> = Arc::new(ExternalNodeSnapshotRecovery {
-                    client: client.clone(),
+                    client: recovery_client,
                     pool: recovery_pool,
                     max_concurrency: self.max_postgres_concurrency,
                     recovery_config,
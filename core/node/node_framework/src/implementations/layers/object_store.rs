@@ -1,35 +1,104 @@
-use zksync_config::ObjectStoreConfig;
-use zksync_object_store::ObjectStoreFactory;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use zksync_config::{configs::object_store::S3Secrets, ObjectStoreConfig};
+use zksync_object_store::{ObjectStoreFactory, TieredObjectStore};
 
 use crate::{
     implementations::resources::object_store::ObjectStoreResource,
+    service::StopReceiver,
+    task::{Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
+    IntoContext,
 };
 
 /// Wiring layer for object store.
+///
+/// If the store configuration specifies tiers, also adds a task periodically running lifecycle management
+/// for the tiered store (migrating objects between tiers and removing expired objects).
 #[derive(Debug)]
 pub struct ObjectStoreLayer {
     config: ObjectStoreConfig,
+    s3_secrets: Option<S3Secrets>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub object_store: ObjectStoreResource,
+    #[context(task)]
+    pub lifecycle_task: Option<ObjectStoreLifecycleTask>,
 }
 
 impl ObjectStoreLayer {
     pub fn new(config: ObjectStoreConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            s3_secrets: None,
+        }
+    }
+
+    /// Sets credentials for S3 stores. Required if the store (or any of its tiers) is in the S3 mode.
+    pub fn with_s3_secrets(mut self, secrets: Option<S3Secrets>) -> Self {
+        self.s3_secrets = secrets;
+        self
     }
 }
 
 #[async_trait::async_trait]
 impl WiringLayer for ObjectStoreLayer {
     type Input = ();
-    type Output = ObjectStoreResource;
+    type Output = Output;
 
     fn layer_name(&self) -> &'static str {
         "object_store_layer"
     }
 
     async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
-        let object_store = ObjectStoreFactory::new(self.config).create_store().await?;
-        let resource = ObjectStoreResource(object_store);
-        Ok(resource)
+        let factory = ObjectStoreFactory::new(self.config).with_s3_secrets(self.s3_secrets);
+        let object_store = factory.create_store().await?;
+        let lifecycle_task = factory
+            .create_tiered_store()
+            .await?
+            .map(|store| ObjectStoreLifecycleTask { store });
+        Ok(Output {
+            object_store: ObjectStoreResource(object_store),
+            lifecycle_task,
+        })
+    }
+}
+
+/// Task periodically running lifecycle management for a tiered object store.
+#[derive(Debug)]
+pub struct ObjectStoreLifecycleTask {
+    store: Arc<TieredObjectStore>,
+}
+
+impl ObjectStoreLifecycleTask {
+    const INTERVAL: Duration = Duration::from_secs(3_600);
+}
+
+#[async_trait::async_trait]
+impl Task for ObjectStoreLifecycleTask {
+    fn id(&self) -> TaskId {
+        "object_store_lifecycle".into()
+    }
+
+    async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        while !*stop_receiver.0.borrow_and_update() {
+            match self.store.run_lifecycle(SystemTime::now()).await {
+                Ok(stats) => tracing::info!("Finished object store lifecycle iteration: {stats:?}"),
+                // Lifecycle management is not critical for node operation, so errors are not propagated.
+                Err(err) => tracing::warn!("Object store lifecycle iteration failed: {err}"),
+            }
+
+            // Error here corresponds to a timeout w/o `stop_receiver` changed; we're OK with this.
+            tokio::time::timeout(Self::INTERVAL, stop_receiver.0.changed())
+                .await
+                .ok();
+        }
+        Ok(())
     }
 }
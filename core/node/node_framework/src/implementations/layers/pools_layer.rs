@@ -96,6 +96,9 @@ impl WiringLayer for PoolsLayer {
             if let Some(threshold) = self.config.long_connection_threshold() {
                 ConnectionPool::<Core>::global_config().set_long_connection_threshold(threshold)?;
             }
+            if let Some(threshold) = self.config.leak_detection_threshold() {
+                ConnectionPool::<Core>::global_config().set_leak_detection_threshold(threshold)?;
+            }
         }
 
         let master_pool = if self.with_master {
@@ -9,6 +9,7 @@ use tokio::sync::RwLock;
 use zksync_dal::{
     metrics::PostgresMetrics, system_dal::DatabaseMigration, ConnectionPool, Core, CoreDal,
 };
+use zksync_db_connection::monitor::{ConnectionPoolsMonitor, ConnectionPoolsSummary};
 use zksync_health_check::{CheckHealth, Health, HealthStatus};
 
 use crate::{
@@ -26,6 +27,9 @@ use crate::{
 const TASK_EXECUTION_INTERVAL: Duration = Duration::from_secs(60);
 
 /// Wiring layer for the Postgres metrics exporter and healthcheck.
+///
+/// If connection leak detection is enabled, also adds a task reporting leaked connections
+/// and a healthcheck summarizing active connections per requester.
 #[derive(Debug)]
 pub struct PostgresLayer;
 
@@ -42,6 +46,8 @@ pub struct Input {
 pub struct Output {
     #[context(task)]
     pub metrics_task: PostgresMetricsScrapingTask,
+    #[context(task)]
+    pub leak_detection_task: Option<ConnectionLeakDetectionTask>,
 }
 
 #[async_trait::async_trait]
@@ -68,7 +74,36 @@ impl WiringLayer for PostgresLayer {
             }))
             .map_err(WiringError::internal)?;
 
-        Ok(Output { metrics_task })
+        let monitor = ConnectionPoolsMonitor::new();
+        if let Some(monitor) = monitor {
+            app_health
+                .insert_custom_component(Arc::new(ConnectionPoolsHealthCheck(monitor)))
+                .map_err(WiringError::internal)?;
+        }
+        let leak_detection_task = monitor.map(ConnectionLeakDetectionTask);
+
+        Ok(Output {
+            metrics_task,
+            leak_detection_task,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ConnectionLeakDetectionTask(ConnectionPoolsMonitor);
+
+#[async_trait::async_trait]
+impl Task for ConnectionLeakDetectionTask {
+    fn kind(&self) -> TaskKind {
+        TaskKind::UnconstrainedTask
+    }
+
+    fn id(&self) -> TaskId {
+        "postgres_connection_leak_detection".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.0.run(stop_receiver.0).await
     }
 }
 
@@ -111,6 +146,27 @@ impl From<DatabaseInfo> for Health {
     }
 }
 
+/// Healthcheck summarizing active connections across all pools grouped by requester.
+#[derive(Debug)]
+struct ConnectionPoolsHealthCheck(ConnectionPoolsMonitor);
+
+#[async_trait]
+impl CheckHealth for ConnectionPoolsHealthCheck {
+    fn name(&self) -> &'static str {
+        "connection_pools"
+    }
+
+    async fn check_health(&self) -> Health {
+        let summary: ConnectionPoolsSummary = self.0.summary();
+        let status = if summary.leaked_connections > 0 {
+            HealthStatus::Affected
+        } else {
+            HealthStatus::Ready
+        };
+        Health::from(status).with_details(summary)
+    }
+}
+
 #[derive(Debug)]
 struct DatabaseHealthCheck {
     polling_interval: Duration,
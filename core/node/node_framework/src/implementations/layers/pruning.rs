@@ -19,6 +19,9 @@ pub struct PruningLayer {
     pruning_removal_delay: Duration,
     pruning_chunk_size: u32,
     minimum_l1_batch_age: Duration,
+    maximum_l1_batch_age: Option<Duration>,
+    require_consistency_check: bool,
+    dry_run: bool,
 }
 
 #[derive(Debug, FromContext)]
@@ -46,8 +49,32 @@ impl PruningLayer {
             pruning_removal_delay,
             pruning_chunk_size,
             minimum_l1_batch_age,
+            maximum_l1_batch_age: None,
+            require_consistency_check: true,
+            dry_run: false,
         }
     }
+
+    /// Disables the prune condition requiring L1 batches to be processed by the consistency checker.
+    /// Should be used on the main node, which doesn't run the consistency checker.
+    pub fn without_consistency_check(mut self) -> Self {
+        self.require_consistency_check = false;
+        self
+    }
+
+    /// Sets the time-based retention period. L1 batches older than this age will be pruned regardless of the chunk size.
+    /// The Merkle tree (if pruning is enabled for it) follows Postgres pruning, so it is pruned according
+    /// to the same schedule.
+    pub fn with_maximum_l1_batch_age(mut self, age: Option<Duration>) -> Self {
+        self.maximum_l1_batch_age = age;
+        self
+    }
+
+    /// Sets the dry-run mode, in which the pruner only estimates the amount of prunable data.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -67,6 +94,9 @@ impl WiringLayer for PruningLayer {
                 removal_delay: self.pruning_removal_delay,
                 pruned_batch_chunk_size: self.pruning_chunk_size,
                 minimum_l1_batch_age: self.minimum_l1_batch_age,
+                maximum_l1_batch_age: self.maximum_l1_batch_age,
+                require_consistency_check: self.require_consistency_check,
+                dry_run: self.dry_run,
             },
             main_pool,
         );
@@ -1,8 +1,17 @@
+use zksync_node_framework_derive::{FromContext, IntoContext};
 use zksync_types::vm::FastVmMode;
-use zksync_vm_executor::batch::{BatchTracer, MainBatchExecutorFactory, TraceCalls};
+use zksync_vm_executor::batch::{
+    persisting_divergence_handler, BatchTracer, MainBatchExecutorFactory, TraceCalls,
+    VmDivergencesPersister,
+};
 
 use crate::{
-    implementations::resources::state_keeper::BatchExecutorResource,
+    implementations::resources::{
+        pools::{MasterPool, PoolResource},
+        state_keeper::BatchExecutorResource,
+    },
+    service::StopReceiver,
+    task::{Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
 };
 
@@ -12,6 +21,23 @@ pub struct MainBatchExecutorLayer {
     save_call_traces: bool,
     optional_bytecode_compression: bool,
     fast_vm_mode: FastVmMode,
+    shadow_sampling_ratio: f64,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    /// Used to persist VM divergences in batches sampled for shadow execution.
+    pub master_pool: Option<PoolResource<MasterPool>>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub executor: BatchExecutorResource,
+    /// Persists VM divergences detected in batches sampled for shadow execution.
+    #[context(task)]
+    pub divergences_persister: Option<VmDivergencesPersister>,
 }
 
 impl MainBatchExecutorLayer {
@@ -20,6 +46,7 @@ impl MainBatchExecutorLayer {
             save_call_traces,
             optional_bytecode_compression,
             fast_vm_mode: FastVmMode::default(),
+            shadow_sampling_ratio: 0.0,
         }
     }
 
@@ -28,27 +55,68 @@ impl MainBatchExecutorLayer {
         self
     }
 
-    fn create_executor<Tr: BatchTracer>(&self) -> BatchExecutorResource {
+    /// Sets the fraction of L1 batches executed in the shadow VM mode. Divergences are persisted to Postgres
+    /// if the master pool is available.
+    pub fn with_shadow_sampling_ratio(mut self, ratio: f64) -> Self {
+        self.shadow_sampling_ratio = ratio;
+        self
+    }
+
+    async fn create_executor<Tr: BatchTracer>(
+        &self,
+        master_pool: Option<PoolResource<MasterPool>>,
+    ) -> Result<Output, WiringError> {
         let mut executor = MainBatchExecutorFactory::<Tr>::new(self.optional_bytecode_compression);
         executor.set_fast_vm_mode(self.fast_vm_mode);
-        executor.into()
+        let mut divergences_persister = None;
+        if self.shadow_sampling_ratio > 0.0 {
+            executor.set_shadow_sampling_ratio(self.shadow_sampling_ratio);
+            if let Some(master_pool) = master_pool {
+                let pool = master_pool.get_singleton().await?;
+                let (handler, persister) = persisting_divergence_handler(pool);
+                executor.set_divergence_handler(handler);
+                divergences_persister = Some(persister);
+            }
+        }
+        Ok(Output {
+            executor: executor.into(),
+            divergences_persister,
+        })
     }
 }
 
 #[async_trait::async_trait]
 impl WiringLayer for MainBatchExecutorLayer {
-    type Input = ();
-    type Output = BatchExecutorResource;
+    type Input = Input;
+    type Output = Output;
 
     fn layer_name(&self) -> &'static str {
         "main_batch_executor_layer"
     }
 
-    async fn wire(self, (): Self::Input) -> Result<Self::Output, WiringError> {
-        Ok(if self.save_call_traces {
-            self.create_executor::<TraceCalls>()
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        if !(0.0..=1.0).contains(&self.shadow_sampling_ratio) {
+            return Err(WiringError::Configuration(format!(
+                "shadow sampling ratio must be in [0, 1], got {}",
+                self.shadow_sampling_ratio
+            )));
+        }
+
+        if self.save_call_traces {
+            self.create_executor::<TraceCalls>(input.master_pool).await
         } else {
-            self.create_executor::<()>()
-        })
+            self.create_executor::<()>(input.master_pool).await
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for VmDivergencesPersister {
+    fn id(&self) -> TaskId {
+        "vm_divergences_persister".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
     }
 }
@@ -3,14 +3,19 @@ use zksync_config::configs::{
     chain::{MempoolConfig, StateKeeperConfig},
     wallets,
 };
-use zksync_state_keeper::{MempoolFetcher, MempoolGuard, MempoolIO, SequencerSealer};
+use zksync_state_keeper::{
+    seal_criteria::SealCriterion, MempoolFetcher, MempoolGuard, MempoolIO, SequencerSealer,
+};
 use zksync_types::{commitment::L1BatchCommitmentMode, Address, L2ChainId};
 
 use crate::{
     implementations::resources::{
         fee_input::SequencerFeeInputResource,
         pools::{MasterPool, PoolResource},
-        state_keeper::{ConditionalSealerResource, StateKeeperIOResource},
+        state_keeper::{
+            ConditionalSealerResource, SealCriteriaResource, StateKeeperIOResource,
+            TxPolicyResource,
+        },
     },
     service::StopReceiver,
     task::{Task, TaskId},
@@ -24,6 +29,8 @@ use crate::{
 ///
 /// - `FeeInputResource`
 /// - `PoolResource<MasterPool>`
+/// - `SealCriteriaResource` (optional)
+/// - `TxPolicyResource` (optional)
 ///
 /// ## Adds resources
 ///
@@ -48,6 +55,8 @@ pub struct MempoolIOLayer {
 pub struct Input {
     pub fee_input: SequencerFeeInputResource,
     pub master_pool: PoolResource<MasterPool>,
+    pub custom_seal_criteria: Option<SealCriteriaResource>,
+    pub tx_policy: Option<TxPolicyResource>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -127,7 +136,7 @@ impl WiringLayer for MempoolIOLayer {
             .get_singleton()
             .await
             .context("Get master pool")?;
-        let io = MempoolIO::new(
+        let mut io = MempoolIO::new(
             mempool_guard,
             batch_fee_input_provider,
             mempool_db_pool,
@@ -138,9 +147,26 @@ impl WiringLayer for MempoolIOLayer {
             self.l2_da_validator_addr,
             self.l1_batch_commit_data_generator_mode,
         )?;
+        if let Some(TxPolicyResource(tx_policy)) = input.tx_policy {
+            io.set_tx_policy(tx_policy);
+        }
 
         // Create sealer.
-        let sealer = SequencerSealer::new(self.state_keeper_config);
+        let custom_seal_criteria = input.custom_seal_criteria.unwrap_or_default().0;
+        if !custom_seal_criteria.is_empty() {
+            tracing::info!(
+                "Using custom seal criteria: {:?}",
+                custom_seal_criteria
+                    .iter()
+                    .map(|criterion| criterion.prom_criterion_name())
+                    .collect::<Vec<_>>()
+            );
+        }
+        let sealer = SequencerSealer::new(self.state_keeper_config).with_custom_criteria(
+            custom_seal_criteria
+                .into_iter()
+                .map(|criterion| Box::new(criterion) as Box<dyn SealCriterion>),
+        );
 
         Ok(Output {
             state_keeper_io: io.into(),
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use anyhow::Context;
 use zksync_health_check::ReactiveHealthCheck;
 use zksync_state::AsyncCatchupTask;
-pub use zksync_state::RocksdbStorageOptions;
+pub use zksync_state::{RocksdbProfile, RocksdbStorageOptions};
 use zksync_state_keeper::{AsyncRocksdbCache, ZkSyncStateKeeper};
 use zksync_storage::RocksDB;
 
@@ -13,7 +13,7 @@ use crate::{
         pools::{MasterPool, PoolResource},
         state_keeper::{
             BatchExecutorResource, ConditionalSealerResource, OutputHandlerResource,
-            StateKeeperIOResource,
+            StateKeeperIOResource, StateKeeperPauseResource,
         },
     },
     service::{ShutdownHook, StopReceiver},
@@ -26,6 +26,8 @@ pub mod external_io;
 pub mod main_batch_executor;
 pub mod mempool_io;
 pub mod output_handler;
+pub mod seal_criteria;
+pub mod tx_policy;
 
 /// Wiring layer for the state keeper.
 #[derive(Debug)]
@@ -54,6 +56,7 @@ pub struct Output {
     #[context(task)]
     pub rocksdb_catchup: AsyncCatchupTask,
     pub rocksdb_termination_hook: ShutdownHook,
+    pub pause_handle: StateKeeperPauseResource,
 }
 
 impl StateKeeperLayer {
@@ -107,6 +110,7 @@ impl WiringLayer for StateKeeperLayer {
             Arc::new(storage_factory),
         );
 
+        let pause_handle = StateKeeperPauseResource(state_keeper.pause_handle());
         let state_keeper = StateKeeperTask { state_keeper };
 
         input
@@ -125,6 +129,7 @@ impl WiringLayer for StateKeeperLayer {
             state_keeper,
             rocksdb_catchup,
             rocksdb_termination_hook,
+            pause_handle,
         })
     }
 }
@@ -4,10 +4,11 @@ use zksync_state_keeper::{
     io::seal_logic::l2_block_seal_subtasks::L2BlockSealProcess, L2BlockSealerTask, OutputHandler,
     StateKeeperPersistence, TreeWritesPersistence,
 };
-use zksync_types::Address;
+use zksync_types::{commitment::L1BatchCommitmentMode, Address};
 
 use crate::{
     implementations::resources::{
+        leader_election::LeaderLeaseResource,
         pools::{MasterPool, PoolResource},
         state_keeper::OutputHandlerResource,
         sync_state::SyncStateResource,
@@ -25,6 +26,7 @@ use crate::{
 ///
 /// - `PoolResource<MasterPool>`
 /// - `SyncStateResource` (optional)
+/// - `LeaderLeaseResource` (optional; if present, L1 batches and L2 blocks are only sealed while the lease is held)
 ///
 /// ## Adds resources
 ///
@@ -45,6 +47,8 @@ pub struct OutputHandlerLayer {
     /// May be set to `false` for nodes that do not participate in the sequencing process (e.g. external nodes)
     /// or run `vm_runner_protective_reads` component.
     protective_reads_persistence_enabled: bool,
+    /// Commitment mode persisted for sealed pre-gateway L1 batches.
+    commitment_mode: Option<L1BatchCommitmentMode>,
 }
 
 #[derive(Debug, FromContext)]
@@ -52,6 +56,7 @@ pub struct OutputHandlerLayer {
 pub struct Input {
     pub master_pool: PoolResource<MasterPool>,
     pub sync_state: Option<SyncStateResource>,
+    pub leader_lease: Option<LeaderLeaseResource>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -72,6 +77,7 @@ impl OutputHandlerLayer {
             l2_block_seal_queue_capacity,
             pre_insert_txs: false,
             protective_reads_persistence_enabled: false,
+            commitment_mode: None,
         }
     }
 
@@ -87,6 +93,11 @@ impl OutputHandlerLayer {
         self.protective_reads_persistence_enabled = protective_reads_persistence_enabled;
         self
     }
+
+    pub fn with_commitment_mode(mut self, commitment_mode: L1BatchCommitmentMode) -> Self {
+        self.commitment_mode = Some(commitment_mode);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -107,7 +118,7 @@ impl WiringLayer for OutputHandlerLayer {
             .await
             .context("Get master pool")?;
 
-        let (mut persistence, l2_block_sealer) = StateKeeperPersistence::new(
+        let (mut persistence, mut l2_block_sealer) = StateKeeperPersistence::new(
             persistence_pool.clone(),
             self.l2_legacy_shared_bridge_addr,
             self.l2_block_seal_queue_capacity,
@@ -119,6 +130,13 @@ impl WiringLayer for OutputHandlerLayer {
         if !self.protective_reads_persistence_enabled {
             persistence = persistence.without_protective_reads();
         }
+        if let Some(commitment_mode) = self.commitment_mode {
+            persistence = persistence.with_commitment_mode(commitment_mode);
+        }
+        if let Some(LeaderLeaseResource(lease)) = input.leader_lease {
+            persistence = persistence.with_lease_fence(lease.clone());
+            l2_block_sealer = l2_block_sealer.with_lease_fence(lease);
+        }
 
         let tree_writes_persistence = TreeWritesPersistence::new(persistence_pool);
         let mut output_handler = OutputHandler::new(Box::new(persistence))
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use zksync_state_keeper::seal_criteria::SealCriterion;
+
+use crate::{
+    implementations::resources::state_keeper::SealCriteriaResource,
+    wiring_layer::{WiringError, WiringLayer},
+    IntoContext,
+};
+
+/// Wiring layer for custom L1 batch seal criteria applied by the main node state keeper in addition
+/// to the built-in ones.
+///
+/// ## Adds resources
+///
+/// - `SealCriteriaResource`
+#[derive(Debug, Default)]
+pub struct SealCriteriaLayer {
+    criteria: Vec<Arc<dyn SealCriterion>>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub seal_criteria: SealCriteriaResource,
+}
+
+impl SealCriteriaLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a custom seal criterion. Criteria are applied in the order they are added.
+    #[must_use]
+    pub fn with_criterion(mut self, criterion: impl SealCriterion) -> Self {
+        self.criteria.push(Arc::new(criterion));
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for SealCriteriaLayer {
+    type Input = ();
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "seal_criteria_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        Ok(Output {
+            seal_criteria: SealCriteriaResource(self.criteria),
+        })
+    }
+}
@@ -0,0 +1,78 @@
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::Context as _;
+use zksync_state_keeper::tx_policy::{TxPolicyEngine, TxPolicyReloadTask};
+
+use crate::{
+    implementations::resources::state_keeper::TxPolicyResource,
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    IntoContext,
+};
+
+/// Wiring layer for the transaction policy engine. The policy is loaded from a file and is reloaded
+/// each time the file is modified.
+///
+/// ## Adds resources
+///
+/// - `TxPolicyResource`
+///
+/// ## Adds tasks
+///
+/// - `TxPolicyReloadTask`
+#[derive(Debug)]
+pub struct TxPolicyLayer {
+    path: PathBuf,
+    reload_interval: Duration,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub tx_policy: TxPolicyResource,
+    #[context(task)]
+    pub reload_task: TxPolicyReloadTask,
+}
+
+impl TxPolicyLayer {
+    const DEFAULT_RELOAD_INTERVAL: Duration = Duration::from_secs(5);
+
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            reload_interval: Self::DEFAULT_RELOAD_INTERVAL,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for TxPolicyLayer {
+    type Input = ();
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "tx_policy_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        let (engine, reload_task) = TxPolicyEngine::load(self.path, self.reload_interval)
+            .await
+            .context("failed loading tx policy")?;
+        Ok(Output {
+            tx_policy: engine.into(),
+            reload_task,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for TxPolicyReloadTask {
+    fn id(&self) -> TaskId {
+        "state_keeper/tx_policy_reload".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
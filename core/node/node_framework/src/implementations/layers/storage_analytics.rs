@@ -0,0 +1,56 @@
+use zksync_storage_analytics::StorageAnalytics;
+
+use crate::{
+    implementations::resources::pools::{MasterPool, PoolResource},
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for storage analytics.
+///
+/// Responsible for initializing and running of [`StorageAnalytics`] task, that aggregates storage usage
+/// per contract for sealed L1 batches.
+#[derive(Debug)]
+pub struct StorageAnalyticsLayer;
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub storage_analytics: StorageAnalytics,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for StorageAnalyticsLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "storage_analytics_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let pool = input.master_pool.get_singleton().await?;
+        let storage_analytics = StorageAnalytics::new(pool);
+        Ok(Output { storage_analytics })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for StorageAnalytics {
+    fn id(&self) -> TaskId {
+        "storage_analytics".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
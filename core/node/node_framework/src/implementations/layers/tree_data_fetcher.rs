@@ -1,4 +1,4 @@
-use zksync_node_sync::tree_data_fetcher::TreeDataFetcher;
+use zksync_node_sync::tree_data_fetcher::{RootHashMismatchPolicy, TreeDataFetcher};
 use zksync_types::{Address, L2ChainId};
 
 use crate::{
@@ -19,6 +19,7 @@ use crate::{
 pub struct TreeDataFetcherLayer {
     l1_diamond_proxy_addr: Address,
     l2_chain_id: L2ChainId,
+    root_hash_mismatch_policy: RootHashMismatchPolicy,
 }
 
 #[derive(Debug, FromContext)]
@@ -44,8 +45,15 @@ impl TreeDataFetcherLayer {
         Self {
             l1_diamond_proxy_addr,
             l2_chain_id,
+            root_hash_mismatch_policy: RootHashMismatchPolicy::default(),
         }
     }
+
+    /// Sets the policy applied if a root hash fetched from the main node differs from the one committed on L1.
+    pub fn with_root_hash_mismatch_policy(mut self, policy: RootHashMismatchPolicy) -> Self {
+        self.root_hash_mismatch_policy = policy;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -74,7 +82,8 @@ impl WiringLayer for TreeDataFetcherLayer {
                 gateway_client,
                 self.l2_chain_id,
             )
-            .await?;
+            .await?
+            .with_root_hash_mismatch_policy(self.root_hash_mismatch_policy);
 
         // Insert healthcheck
         input
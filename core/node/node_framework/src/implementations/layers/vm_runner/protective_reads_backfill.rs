@@ -0,0 +1,85 @@
+use zksync_config::configs::vm_runner::ProtectiveReadsWriterConfig;
+use zksync_types::{L1BatchNumber, L2ChainId};
+use zksync_vm_runner::impls::ProtectiveReadsBackfill;
+
+use crate::{
+    implementations::resources::pools::{MasterPool, PoolResource},
+    service::StopReceiver,
+    task::{Task, TaskId, TaskKind},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for the protective reads backfill.
+///
+/// Backfills protective reads for all L1 batches up to and including the first batch processed by
+/// the protective reads writer (batches before it are considered processed by the writer).
+#[derive(Debug)]
+pub struct ProtectiveReadsBackfillLayer {
+    last_batch: L1BatchNumber,
+    zksync_network_id: L2ChainId,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub protective_reads_backfill: ProtectiveReadsBackfill,
+}
+
+impl ProtectiveReadsBackfillLayer {
+    pub fn new(
+        protective_reads_writer_config: &ProtectiveReadsWriterConfig,
+        zksync_network_id: L2ChainId,
+    ) -> Self {
+        Self {
+            last_batch: protective_reads_writer_config.first_processed_batch,
+            zksync_network_id,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for ProtectiveReadsBackfillLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "vm_runner_protective_reads_backfill"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        // One connection for the backfill itself and one for the Postgres storage used during batch execution.
+        let pool = input.master_pool.get_custom(2).await?;
+        let protective_reads_backfill = ProtectiveReadsBackfill::new(
+            pool,
+            self.zksync_network_id,
+            L1BatchNumber(0)..=self.last_batch,
+        )
+        .await?;
+        Ok(Output {
+            protective_reads_backfill,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for ProtectiveReadsBackfill {
+    fn kind(&self) -> TaskKind {
+        TaskKind::OneshotTask
+    }
+
+    fn id(&self) -> TaskId {
+        "vm_runner/protective_reads_backfill".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -1,12 +1,15 @@
 use std::time::Duration;
 
-use zksync_node_api_server::web3::mempool_cache::{MempoolCache, MempoolCacheUpdateTask};
+use zksync_node_api_server::web3::{
+    call_result_cache::CallResultCache,
+    mempool_cache::{MempoolCache, MempoolCacheUpdateTask},
+};
 use zksync_node_framework_derive::FromContext;
 
 use crate::{
     implementations::resources::{
         pools::{PoolResource, ReplicaPool},
-        web3_api::MempoolCacheResource,
+        web3_api::{CallResultCacheResource, MempoolCacheResource},
     },
     service::StopReceiver,
     task::{Task, TaskId},
@@ -64,6 +67,47 @@ impl WiringLayer for MempoolCacheLayer {
     }
 }
 
+/// Wiring layer for the API cache of `eth_call` / `eth_estimateGas` results shared among API servers.
+/// Doesn't add any resources if the cache is disabled (e.g., has zero capacity).
+#[derive(Debug)]
+pub struct CallResultCacheLayer {
+    capacity: usize,
+    excluded_methods: Vec<String>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct CallResultCacheOutput {
+    pub call_result_cache: Option<CallResultCacheResource>,
+}
+
+impl CallResultCacheLayer {
+    pub fn new(capacity: usize, excluded_methods: Vec<String>) -> Self {
+        Self {
+            capacity,
+            excluded_methods,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for CallResultCacheLayer {
+    type Input = ();
+    type Output = CallResultCacheOutput;
+
+    fn layer_name(&self) -> &'static str {
+        "call_result_cache_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        let cache = CallResultCache::new(self.capacity, &self.excluded_methods)
+            .map_err(|err| WiringError::Configuration(format!("{err:#}")))?;
+        Ok(CallResultCacheOutput {
+            call_result_cache: cache.map(CallResultCacheResource),
+        })
+    }
+}
+
 #[async_trait::async_trait]
 impl Task for MempoolCacheUpdateTask {
     fn id(&self) -> TaskId {
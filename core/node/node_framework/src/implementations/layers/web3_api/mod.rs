@@ -1,4 +1,6 @@
 pub mod caches;
+pub mod rate_limiter;
+pub mod replica_pools;
 pub mod server;
 pub mod tree_api_client;
 pub mod tx_sender;
@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use zksync_config::configs::api::Web3RateLimitsConfig;
+use zksync_node_api_server::web3::rate_limiter::{ApiRateLimiter, ApiRateLimiterCleanupTask};
+
+use crate::{
+    implementations::resources::web3_api::ApiRateLimiterResource,
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    IntoContext,
+};
+
+/// Wiring layer for the rate limiter shared among Web3 API servers.
+///
+/// ## Adds resources
+///
+/// - `ApiRateLimiterResource`
+///
+/// ## Adds tasks
+///
+/// - `ApiRateLimiterCleanupTask`
+#[derive(Debug)]
+pub struct ApiRateLimiterLayer {
+    config: Web3RateLimitsConfig,
+    cleanup_interval: Duration,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub rate_limiter: ApiRateLimiterResource,
+    #[context(task)]
+    pub cleanup_task: ApiRateLimiterCleanupTask,
+}
+
+impl ApiRateLimiterLayer {
+    const DEFAULT_CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+    pub fn new(config: Web3RateLimitsConfig) -> Self {
+        Self {
+            config,
+            cleanup_interval: Self::DEFAULT_CLEANUP_INTERVAL,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for ApiRateLimiterLayer {
+    type Input = ();
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "api_rate_limiter_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        let rate_limiter = ApiRateLimiter::new(&self.config);
+        let cleanup_task = rate_limiter.cleanup_task(self.cleanup_interval);
+        Ok(Output {
+            rate_limiter: rate_limiter.into(),
+            cleanup_task,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for ApiRateLimiterCleanupTask {
+    fn id(&self) -> TaskId {
+        "api_rate_limiter_cleanup_task".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use zksync_config::configs::PostgresConfig;
+use zksync_dal::{ConnectionPool, Core};
+use zksync_node_api_server::web3::replicas::{ReplicaLagProbeTask, ReplicaPools};
+use zksync_types::url::SensitiveUrl;
+
+use crate::{
+    implementations::resources::web3_api::ReplicaPoolsResource,
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    IntoContext,
+};
+
+/// Wiring layer for replica connection pools shared among Web3 API servers. Read queries of API servers
+/// are routed to the least-lagged healthy replica.
+///
+/// ## Adds resources
+///
+/// - `ReplicaPoolsResource`
+///
+/// ## Adds tasks
+///
+/// - `ReplicaLagProbeTask`
+#[derive(Debug)]
+pub struct ReplicaPoolsLayer {
+    config: PostgresConfig,
+    replica_urls: Vec<SensitiveUrl>,
+    probe_interval: Duration,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub replica_pools: ReplicaPoolsResource,
+    #[context(task)]
+    pub probe_task: ReplicaLagProbeTask,
+}
+
+impl ReplicaPoolsLayer {
+    const DEFAULT_PROBE_INTERVAL: Duration = Duration::from_secs(1);
+
+    pub fn new(config: PostgresConfig, replica_urls: Vec<SensitiveUrl>) -> Self {
+        Self {
+            config,
+            replica_urls,
+            probe_interval: Self::DEFAULT_PROBE_INTERVAL,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for ReplicaPoolsLayer {
+    type Input = ();
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "replica_pools_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        if self.replica_urls.is_empty() {
+            return Err(WiringError::Configuration(
+                "At least one replica URL should be provided".to_string(),
+            ));
+        }
+
+        let max_connections = self.config.max_connections()?;
+        let mut pools = Vec::with_capacity(self.replica_urls.len());
+        for url in self.replica_urls {
+            let mut builder = ConnectionPool::<Core>::builder(url, max_connections);
+            builder.set_statement_timeout(self.config.statement_timeout());
+            builder.set_acquire_timeout(self.config.acquire_timeout());
+            pools.push(builder.build().await?);
+        }
+        tracing::info!("Created {} replica pools for Web3 API servers", pools.len());
+
+        let replica_pools = ReplicaPools::new(pools);
+        let probe_task = replica_pools.lag_probe_task(self.probe_interval);
+        Ok(Output {
+            replica_pools: replica_pools.into(),
+            probe_task,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for ReplicaLagProbeTask {
+    fn id(&self) -> TaskId {
+        "replica_lag_probe_task".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -1,11 +1,15 @@
-use std::{num::NonZeroU32, time::Duration};
+use std::{
+    num::{NonZeroU32, NonZeroUsize},
+    time::Duration,
+};
 
 use tokio::{sync::oneshot, task::JoinHandle};
 use zksync_circuit_breaker::replication_lag::ReplicationLagChecker;
-use zksync_config::configs::api::MaxResponseSize;
+use zksync_config::configs::api::{MaxResponseSize, SlowSubscriberPolicy};
+use zksync_health_check::CheckHealth;
 use zksync_node_api_server::web3::{
     state::{BridgeAddressesHandle, InternalApiConfig, SealedL2BlockNumber},
-    ApiBuilder, ApiServer, Namespace,
+    ApiBuilder, ApiServer, Namespace, ResponseCompression,
 };
 
 use crate::{
@@ -19,7 +23,10 @@ use crate::{
             main_node_client::MainNodeClientResource,
             pools::{PoolResource, ReplicaPool},
             sync_state::SyncStateResource,
-            web3_api::{MempoolCacheResource, TreeApiClientResource, TxSenderResource},
+            web3_api::{
+                ApiRateLimiterResource, CallResultCacheResource, MempoolCacheResource,
+                ReplicaPoolsResource, TreeApiClientResource, TxSenderResource,
+            },
         },
     },
     service::StopReceiver,
@@ -37,8 +44,16 @@ pub struct Web3ServerOptionalConfig {
     pub namespaces: Option<Vec<Namespace>>,
     pub filters_limit: Option<usize>,
     pub subscriptions_limit: Option<usize>,
+    pub max_subscriptions_per_connection: Option<NonZeroU32>,
+    pub subscription_buffer_capacity: Option<NonZeroUsize>,
+    pub max_buffered_notifications_per_connection: Option<NonZeroUsize>,
+    pub slow_subscriber_policy: SlowSubscriberPolicy,
     pub batch_request_size_limit: Option<usize>,
+    pub request_body_size_limit: Option<usize>,
     pub response_body_size_limit: Option<MaxResponseSize>,
+    /// Origins allowed to make cross-origin requests; if empty, all origins are allowed.
+    pub cors_allowed_origins: Vec<String>,
+    pub response_compression: ResponseCompression,
     pub websocket_requests_per_minute_limit: Option<NonZeroU32>,
     pub with_extended_tracing: bool,
     // Used by circuit breaker.
@@ -47,6 +62,8 @@ pub struct Web3ServerOptionalConfig {
     pub pruning_info_refresh_interval: Option<Duration>,
     // Used by the external node.
     pub bridge_addresses_refresh_interval: Option<Duration>,
+    // Used by the external node.
+    pub archive_peer_available: bool,
     pub polling_interval: Option<Duration>,
 }
 
@@ -61,12 +78,28 @@ impl Web3ServerOptionalConfig {
         if let Some(subscriptions_limit) = self.subscriptions_limit {
             api_builder = api_builder.with_subscriptions_limit(subscriptions_limit);
         }
+        if let Some(limit) = self.max_subscriptions_per_connection {
+            api_builder = api_builder.with_max_subscriptions_per_connection(limit);
+        }
+        if let Some(capacity) = self.subscription_buffer_capacity {
+            api_builder =
+                api_builder.with_subscription_buffer(capacity, self.slow_subscriber_policy);
+        }
+        if let Some(limit) = self.max_buffered_notifications_per_connection {
+            api_builder = api_builder.with_max_buffered_notifications_per_connection(limit);
+        }
         if let Some(batch_request_size_limit) = self.batch_request_size_limit {
             api_builder = api_builder.with_batch_request_size_limit(batch_request_size_limit);
         }
+        if let Some(request_body_size_limit) = self.request_body_size_limit {
+            api_builder = api_builder.with_request_body_size_limit(request_body_size_limit);
+        }
         if let Some(response_body_size_limit) = self.response_body_size_limit {
             api_builder = api_builder.with_response_body_size_limit(response_body_size_limit);
         }
+        api_builder = api_builder
+            .with_cors_allowed_origins(self.cors_allowed_origins)
+            .with_response_compression(self.response_compression);
         if let Some(websocket_requests_per_minute_limit) = self.websocket_requests_per_minute_limit
         {
             api_builder = api_builder
@@ -79,7 +112,9 @@ impl Web3ServerOptionalConfig {
             api_builder =
                 api_builder.with_pruning_info_refresh_interval(pruning_info_refresh_interval);
         }
-        api_builder = api_builder.with_extended_tracing(self.with_extended_tracing);
+        api_builder = api_builder
+            .with_extended_tracing(self.with_extended_tracing)
+            .with_archive_peer(self.archive_peer_available);
         api_builder
     }
 }
@@ -100,6 +135,9 @@ enum Transport {
 /// - `SyncStateResource` (optional)
 /// - `TreeApiClientResource` (optional)
 /// - `MempoolCacheResource`
+/// - `CallResultCacheResource` (optional)
+/// - `ApiRateLimiterResource` (optional)
+/// - `ReplicaPoolsResource` (optional)
 /// - `CircuitBreakersResource` (adds a circuit breaker)
 /// - `AppHealthCheckResource` (adds a health check)
 ///
@@ -123,6 +161,9 @@ pub struct Input {
     pub sync_state: Option<SyncStateResource>,
     pub tree_api_client: Option<TreeApiClientResource>,
     pub mempool_cache: MempoolCacheResource,
+    pub call_result_cache: Option<CallResultCacheResource>,
+    pub rate_limiter: Option<ApiRateLimiterResource>,
+    pub replica_pools: Option<ReplicaPoolsResource>,
     #[context(default)]
     pub circuit_breakers: CircuitBreakersResource,
     #[context(default)]
@@ -192,6 +233,7 @@ impl WiringLayer for Web3ServerLayer {
         let MempoolCacheResource(mempool_cache) = input.mempool_cache;
         let sync_state = input.sync_state.map(|state| state.0);
         let tree_api_client = input.tree_api_client.map(|client| client.0);
+        let has_tree_api = tree_api_client.is_some();
 
         let sealed_l2_block_handle = SealedL2BlockNumber::default();
         let bridge_addresses_handle =
@@ -222,6 +264,15 @@ impl WiringLayer for Web3ServerLayer {
         if let Some(client) = tree_api_client {
             api_builder = api_builder.with_tree_api(client);
         }
+        if let Some(CallResultCacheResource(cache)) = input.call_result_cache {
+            api_builder = api_builder.with_call_result_cache(cache);
+        }
+        if let Some(ApiRateLimiterResource(rate_limiter)) = input.rate_limiter {
+            api_builder = api_builder.with_rate_limiter(rate_limiter);
+        }
+        if let Some(ReplicaPoolsResource(replica_pools)) = input.replica_pools {
+            api_builder = api_builder.with_replica_pools(replica_pools);
+        }
         match self.transport {
             Transport::Http => {
                 api_builder = api_builder.http(self.port);
@@ -240,11 +291,21 @@ impl WiringLayer for Web3ServerLayer {
 
         // Insert healthcheck.
         let api_health_check = server.health_check();
-        input
-            .app_health
-            .0
+        let api_component = api_health_check.name();
+        let app_health = &input.app_health.0;
+        app_health
             .insert_component(api_health_check)
             .map_err(WiringError::internal)?;
+        // Declare dependencies so that the health check pinpoints the cause if the API server is degraded.
+        // Only components that are actually registered are taken into account, so it's safe to list
+        // both the in-process tree reader and the HTTP tree API client.
+        let mut api_dependencies = vec!["database", "connection_pools"];
+        if has_tree_api {
+            api_dependencies.extend(["tree", "tree_api_http_client"]);
+        }
+        app_health
+            .add_dependencies(api_component, &api_dependencies)
+            .map_err(WiringError::internal)?;
 
         // Insert circuit breaker.
         input
@@ -2,7 +2,7 @@ use std::{sync::Arc, time::Duration};
 
 use tokio::sync::RwLock;
 use zksync_node_api_server::{
-    execution_sandbox::{VmConcurrencyBarrier, VmConcurrencyLimiter},
+    execution_sandbox::{VmConcurrencyBarrier, VmConcurrencyLimiter, VmConcurrencyLimiterConfig},
     tx_sender::{SandboxExecutorOptions, TxSenderBuilder, TxSenderConfig},
 };
 use zksync_state::{PostgresStorageCaches, PostgresStorageCachesTask};
@@ -18,7 +18,7 @@ use crate::{
         fee_input::ApiFeeInputResource,
         main_node_client::MainNodeClientResource,
         pools::{PoolResource, ReplicaPool},
-        state_keeper::ConditionalSealerResource,
+        state_keeper::{ConditionalSealerResource, TxPolicyResource},
         web3_api::{TxSenderResource, TxSinkResource},
     },
     service::StopReceiver,
@@ -33,6 +33,9 @@ pub struct PostgresStorageCachesConfig {
     pub initial_writes_cache_size: u64,
     pub latest_values_cache_size: u64,
     pub latest_values_max_block_lag: u32,
+    /// Capacity of the historical storage views cache. Set to 0 to disable historical views.
+    pub historical_views_cache_size: u64,
+    pub historical_views_max_block_lag: u32,
 }
 
 /// Wiring layer for the `TxSender`.
@@ -43,6 +46,7 @@ pub struct PostgresStorageCachesConfig {
 /// - `TxSinkResource`
 /// - `PoolResource<ReplicaPool>`
 /// - `ConditionalSealerResource` (optional)
+/// - `TxPolicyResource` (optional)
 /// - `FeeInputResource`
 ///
 /// ## Adds resources
@@ -59,6 +63,7 @@ pub struct TxSenderLayer {
     tx_sender_config: TxSenderConfig,
     postgres_storage_caches_config: PostgresStorageCachesConfig,
     max_vm_concurrency: usize,
+    vm_concurrency_limiter_config: VmConcurrencyLimiterConfig,
     whitelisted_tokens_for_aa_cache: bool,
     vm_mode: FastVmMode,
 }
@@ -71,6 +76,7 @@ pub struct Input {
     pub fee_input: ApiFeeInputResource,
     pub main_node_client: Option<MainNodeClientResource>,
     pub sealer: Option<ConditionalSealerResource>,
+    pub tx_policy: Option<TxPolicyResource>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -95,6 +101,7 @@ impl TxSenderLayer {
             tx_sender_config,
             postgres_storage_caches_config,
             max_vm_concurrency,
+            vm_concurrency_limiter_config: VmConcurrencyLimiterConfig::default(),
             whitelisted_tokens_for_aa_cache: false,
             vm_mode: FastVmMode::Old,
         }
@@ -114,6 +121,15 @@ impl TxSenderLayer {
         self.vm_mode = mode;
         self
     }
+
+    /// Sets per-client VM concurrency limits and weights of VM permit classes. By default, clients are not limited.
+    pub fn with_vm_concurrency_limiter_config(
+        mut self,
+        config: VmConcurrencyLimiterConfig,
+    ) -> Self {
+        self.vm_concurrency_limiter_config = config;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -148,14 +164,26 @@ impl WiringLayer for TxSenderLayer {
                     .latest_values_max_block_lag,
                 replica_pool.clone(),
             );
+            let historical_views_capacity = self
+                .postgres_storage_caches_config
+                .historical_views_cache_size;
+            if historical_views_capacity > 0 {
+                storage_caches.configure_historical_views_cache(
+                    historical_views_capacity,
+                    self.postgres_storage_caches_config
+                        .historical_views_max_block_lag,
+                );
+            }
             Some(update_task)
         } else {
             None
         };
 
         // Initialize `VmConcurrencyLimiter`.
-        let (vm_concurrency_limiter, vm_concurrency_barrier) =
-            VmConcurrencyLimiter::new(self.max_vm_concurrency);
+        let (vm_concurrency_limiter, vm_concurrency_barrier) = VmConcurrencyLimiter::with_config(
+            self.max_vm_concurrency,
+            self.vm_concurrency_limiter_config,
+        );
 
         // TODO (BFT-138): Allow to dynamically reload API contracts
         let config = self.tx_sender_config;
@@ -172,6 +200,9 @@ impl WiringLayer for TxSenderLayer {
         if let Some(sealer) = sealer {
             tx_sender = tx_sender.with_sealer(sealer);
         }
+        if let Some(TxPolicyResource(tx_policy)) = input.tx_policy {
+            tx_sender = tx_sender.with_tx_policy(tx_policy);
+        }
 
         // Add the task for updating the whitelisted tokens for the AA cache.
         let whitelisted_tokens_for_aa_update_task = if self.whitelisted_tokens_for_aa_cache {
@@ -0,0 +1,123 @@
+use zksync_config::{
+    configs::{wallets, WithdrawalFinalizerConfig},
+    ContractsConfig,
+};
+use zksync_eth_client::clients::PKSigningClient;
+use zksync_types::{L1ChainId, L2ChainId};
+use zksync_withdrawal_finalizer::WithdrawalFinalizer;
+
+use crate::{
+    implementations::resources::{
+        eth_interface::EthInterfaceResource,
+        l1_tx_params::TxParamsResource,
+        pools::{MasterPool, PoolResource},
+    },
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for the withdrawal finalizer.
+///
+/// Responsible for initializing and running of [`WithdrawalFinalizer`] task, that finalizes L2 -> L1 withdrawals
+/// on L1 after the containing L1 batches are executed. Finalization transactions are signed using the dedicated
+/// withdrawal finalizer wallet.
+#[derive(Debug)]
+pub struct WithdrawalFinalizerLayer {
+    config: WithdrawalFinalizerConfig,
+    contracts_config: ContractsConfig,
+    wallet: Option<wallets::WithdrawalFinalizer>,
+    l1_chain_id: L1ChainId,
+    l2_chain_id: L2ChainId,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
+    pub eth_client: EthInterfaceResource,
+    pub tx_params: TxParamsResource,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub withdrawal_finalizer: WithdrawalFinalizer,
+}
+
+impl WithdrawalFinalizerLayer {
+    pub fn new(
+        config: WithdrawalFinalizerConfig,
+        contracts_config: ContractsConfig,
+        wallet: Option<wallets::WithdrawalFinalizer>,
+        l1_chain_id: L1ChainId,
+        l2_chain_id: L2ChainId,
+    ) -> Self {
+        Self {
+            config,
+            contracts_config,
+            wallet,
+            l1_chain_id,
+            l2_chain_id,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for WithdrawalFinalizerLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "withdrawal_finalizer_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let wallet = self.wallet.ok_or_else(|| {
+            WiringError::Configuration("withdrawal finalizer wallet is not set".into())
+        })?;
+        let l1_shared_bridge_addr = self
+            .contracts_config
+            .l1_shared_bridge_proxy_addr
+            .ok_or_else(|| {
+                WiringError::Configuration("L1 shared bridge address is not set".into())
+            })?;
+        let pool = input.master_pool.get_singleton().await?;
+
+        let EthInterfaceResource(query_client) = input.eth_client;
+        let signing_client = PKSigningClient::new_raw(
+            wallet.wallet.private_key().clone(),
+            l1_shared_bridge_addr,
+            self.config.default_priority_fee_per_gas,
+            #[allow(clippy::useless_conversion)]
+            self.l1_chain_id.into(),
+            query_client.for_component("withdrawal_finalizer"),
+        );
+        let withdrawal_finalizer = WithdrawalFinalizer::new(
+            pool,
+            self.config,
+            Box::new(signing_client),
+            input.tx_params.0,
+            &self.contracts_config,
+            self.l1_chain_id,
+            self.l2_chain_id,
+        )
+        .map_err(|err| WiringError::Configuration(format!("{err:#}")))?;
+        Ok(Output {
+            withdrawal_finalizer,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for WithdrawalFinalizer {
+    fn id(&self) -> TaskId {
+        "withdrawal_finalizer".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -11,3 +11,13 @@ impl Resource for DAClientResource {
         "common/da_client".into()
     }
 }
+
+/// Represents a client of the DA solution that pubdata is additionally dispatched to in the redundancy mode.
+#[derive(Debug, Clone)]
+pub struct FallbackDAClientResource(pub Box<dyn DataAvailabilityClient>);
+
+impl Resource for FallbackDAClientResource {
+    fn name() -> String {
+        "common/fallback_da_client".into()
+    }
+}
@@ -0,0 +1,22 @@
+use std::sync::Arc;
+
+use zksync_l1_contract_interface::i_executor::commit::data_generator::L1BatchCommitDataGenerator;
+
+use crate::resource::Resource;
+
+/// A resource that provides [`L1BatchCommitDataGenerator`] implementation to the service. If present, it is used
+/// both by `eth_sender` and the consistency checker instead of the default generator for the commitment mode.
+#[derive(Debug, Clone)]
+pub struct L1BatchCommitDataGeneratorResource(pub Arc<dyn L1BatchCommitDataGenerator>);
+
+impl Resource for L1BatchCommitDataGeneratorResource {
+    fn name() -> String {
+        "common/l1_batch_commit_data_generator".into()
+    }
+}
+
+impl<T: L1BatchCommitDataGenerator> From<Arc<T>> for L1BatchCommitDataGeneratorResource {
+    fn from(generator: Arc<T>) -> Self {
+        Self(generator)
+    }
+}
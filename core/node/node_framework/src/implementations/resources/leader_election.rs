@@ -0,0 +1,14 @@
+use zksync_dal::node_leases_dal::NodeLease;
+
+use crate::resource::Resource;
+
+/// A resource that provides the leader [`NodeLease`] held by this node instance. Components mutating
+/// the shared node state can use it to ensure that they don't race with another instance.
+#[derive(Debug, Clone)]
+pub struct LeaderLeaseResource(pub NodeLease);
+
+impl Resource for LeaderLeaseResource {
+    fn name() -> String {
+        "common/leader_lease".into()
+    }
+}
@@ -6,7 +6,9 @@ pub mod eth_interface;
 pub mod fee_input;
 pub mod gas_adjuster;
 pub mod healthcheck;
+pub mod l1_batch_commit_data_generator;
 pub mod l1_tx_params;
+pub mod leader_election;
 pub mod main_node_client;
 pub mod object_store;
 pub mod pools;
@@ -1,7 +1,11 @@
 use std::sync::Arc;
 
 use zksync_state::OwnedStorage;
-use zksync_state_keeper::{seal_criteria::ConditionalSealer, OutputHandler, StateKeeperIO};
+use zksync_state_keeper::{
+    seal_criteria::{ConditionalSealer, SealCriterion},
+    tx_policy::TxPolicyEngine,
+    OutputHandler, StateKeeperIO, StateKeeperPauseHandle,
+};
 use zksync_vm_executor::interface::BatchExecutorFactory;
 
 use crate::resource::{Resource, Unique};
@@ -78,3 +82,40 @@ where
         Self(Arc::new(sealer))
     }
 }
+
+/// A resource that provides custom [`SealCriterion`]s applied by the main node state keeper in addition
+/// to the built-in ones. Provided by `SealCriteriaLayer`.
+#[derive(Debug, Clone, Default)]
+pub struct SealCriteriaResource(pub Vec<Arc<dyn SealCriterion>>);
+
+impl Resource for SealCriteriaResource {
+    fn name() -> String {
+        "state_keeper/seal_criteria".into()
+    }
+}
+
+/// A resource that provides [`TxPolicyEngine`] applied both on transaction submission and on batch building.
+#[derive(Debug, Clone)]
+pub struct TxPolicyResource(pub TxPolicyEngine);
+
+impl Resource for TxPolicyResource {
+    fn name() -> String {
+        "state_keeper/tx_policy".into()
+    }
+}
+
+impl From<TxPolicyEngine> for TxPolicyResource {
+    fn from(engine: TxPolicyEngine) -> Self {
+        Self(engine)
+    }
+}
+
+/// A resource that provides [`StateKeeperPauseHandle`] allowing to pause and resume the state keeper.
+#[derive(Debug, Clone)]
+pub struct StateKeeperPauseResource(pub StateKeeperPauseHandle);
+
+impl Resource for StateKeeperPauseResource {
+    fn name() -> String {
+        "state_keeper/pause_handle".into()
+    }
+}
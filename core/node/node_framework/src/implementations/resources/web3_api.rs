@@ -3,7 +3,10 @@ use std::sync::Arc;
 use zksync_metadata_calculator::api_server::TreeApiClient;
 use zksync_node_api_server::{
     tx_sender::{tx_sink::TxSink, TxSender},
-    web3::mempool_cache::MempoolCache,
+    web3::{
+        call_result_cache::CallResultCache, mempool_cache::MempoolCache,
+        rate_limiter::ApiRateLimiter, replicas::ReplicaPools,
+    },
 };
 
 use crate::resource::Resource;
@@ -71,3 +74,45 @@ impl From<MempoolCache> for MempoolCacheResource {
         Self(cache)
     }
 }
+
+/// A resource that provides [`CallResultCache`] shared among API servers.
+#[derive(Debug, Clone)]
+pub struct CallResultCacheResource(pub CallResultCache);
+
+impl Resource for CallResultCacheResource {
+    fn name() -> String {
+        "api/call_result_cache".into()
+    }
+}
+
+/// A resource that provides [`ApiRateLimiter`] shared among Web3 API servers.
+#[derive(Debug, Clone)]
+pub struct ApiRateLimiterResource(pub ApiRateLimiter);
+
+impl Resource for ApiRateLimiterResource {
+    fn name() -> String {
+        "api/rate_limiter".into()
+    }
+}
+
+impl From<ApiRateLimiter> for ApiRateLimiterResource {
+    fn from(limiter: ApiRateLimiter) -> Self {
+        Self(limiter)
+    }
+}
+
+/// A resource that provides [`ReplicaPools`] shared among Web3 API servers.
+#[derive(Debug, Clone)]
+pub struct ReplicaPoolsResource(pub ReplicaPools);
+
+impl Resource for ReplicaPoolsResource {
+    fn name() -> String {
+        "api/replica_pools".into()
+    }
+}
+
+impl From<ReplicaPools> for ReplicaPoolsResource {
+    fn from(pools: ReplicaPools) -> Self {
+        Self(pools)
+    }
+}
@@ -11,10 +11,11 @@ use zksync_snapshots_applier::{
 };
 use zksync_web3_decl::client::{DynClient, L2};
 
-use crate::{InitializeStorage, SnapshotRecoveryConfig};
+use crate::{InitializeStorage, RecoverySource, SnapshotRecoveryConfig};
 
 #[derive(Debug)]
 pub struct ExternalNodeSnapshotRecovery {
+    /// Client for the node specified by [`SnapshotRecoveryConfig::source`].
     pub client: Box<DynClient<L2>>,
     pub pool: ConnectionPool<Core>,
     pub max_concurrency: NonZeroUsize,
@@ -48,6 +49,9 @@ impl InitializeStorage for ExternalNodeSnapshotRecovery {
             max_concurrency: self.max_concurrency,
             ..SnapshotsApplierConfig::default()
         };
+        if let RecoverySource::Peer(url) = &self.recovery_config.source {
+            tracing::info!("Recovering from a snapshot announced by external node at {url:?}");
+        }
         let mut snapshots_applier_task = SnapshotsApplierTask::new(
             config,
             self.pool.clone(),
@@ -61,10 +65,18 @@ impl InitializeStorage for ExternalNodeSnapshotRecovery {
             );
             snapshots_applier_task.set_snapshot_l1_batch(snapshot_l1_batch);
         }
+        if let Some(manifest_hash) = self.recovery_config.manifest_hash {
+            tracing::info!("Pinning snapshot manifest hash to {manifest_hash:?}");
+            snapshots_applier_task.set_expected_manifest_hash(manifest_hash);
+        }
         if self.recovery_config.drop_storage_key_preimages {
             tracing::info!("Dropping storage key preimages for snapshot storage logs");
             snapshots_applier_task.drop_storage_key_preimages();
         }
+        snapshots_applier_task
+            .check_source_availability()
+            .await
+            .context("snapshot recovery source is unavailable")?;
         self.app_health
             .insert_component(snapshots_applier_task.health_check())?;
 
@@ -128,7 +140,9 @@ mod tests {
             pool,
             max_concurrency: NonZeroUsize::new(4).unwrap(),
             recovery_config: SnapshotRecoveryConfig {
+                source: RecoverySource::MainNode,
                 snapshot_l1_batch_override: None,
+                manifest_hash: None,
                 drop_storage_key_preimages: false,
                 object_store_config: None,
             },
@@ -3,7 +3,7 @@ use std::{future::Future, sync::Arc, time::Duration};
 use tokio::sync::watch;
 use zksync_config::ObjectStoreConfig;
 use zksync_dal::{ConnectionPool, Core, CoreDal as _};
-use zksync_types::L1BatchNumber;
+use zksync_types::{url::SensitiveUrl, L1BatchNumber, H256};
 
 pub use crate::traits::{InitializeStorage, RevertStorage};
 
@@ -11,10 +11,24 @@ pub mod external_node;
 pub mod main_node;
 mod traits;
 
+/// Source of snapshot metadata (the snapshot header, L1 batch and L2 block details, and tokens) during recovery.
+/// Snapshot data is always read from the object store. Recovery by reconstructing the state from L1 is not supported.
+#[derive(Debug, Clone)]
+pub enum RecoverySource {
+    /// Recover from a snapshot announced by the main node.
+    MainNode,
+    /// Recover from a snapshot announced by another external node with the specified JSON-RPC URL. The peer
+    /// must serve the `snapshots` namespace and share the snapshot object store.
+    Peer(SensitiveUrl),
+}
+
 #[derive(Debug)]
 pub struct SnapshotRecoveryConfig {
+    pub source: RecoverySource,
     /// If not specified, the latest snapshot will be used.
     pub snapshot_l1_batch_override: Option<L1BatchNumber>,
+    /// Expected manifest hash of the recovered snapshot, obtained from a trusted source.
+    pub manifest_hash: Option<H256>,
     pub drop_storage_key_preimages: bool,
     pub object_store_config: Option<ObjectStoreConfig>,
 }
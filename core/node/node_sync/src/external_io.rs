@@ -361,7 +361,7 @@ impl StateKeeperIO for ExternalIO {
     async fn wait_for_next_tx(
         &mut self,
         max_wait: Duration,
-        _l2_block_timestamp: u64,
+        _updates_manager: &UpdatesManager,
     ) -> anyhow::Result<Option<Transaction>> {
         tracing::debug!(
             "Waiting for the new tx, next action is {:?}",
@@ -16,7 +16,7 @@ pub use self::{
     client::{MainNodeClient, MainNodeHealthCheck},
     external_io::ExternalIO,
     sync_action::{ActionQueue, ActionQueueSender},
-    sync_state::SyncState,
+    sync_state::{SyncPath, SyncState},
 };
 
 /// Validation gas limit used by the external node.
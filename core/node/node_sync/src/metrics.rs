@@ -5,6 +5,8 @@ use std::time::Duration;
 use vise::{Buckets, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics};
 use zksync_types::aggregated_operations::AggregatedActionType;
 
+use crate::sync_state::SyncPath;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "stage", rename_all = "snake_case")]
 pub(super) enum FetchStage {
@@ -41,6 +43,8 @@ pub(super) struct FetcherMetrics {
     pub l1_batch: Family<L1BatchStage, Gauge<u64>>,
     // uses legacy naming for L2 blocks for compatibility reasons
     pub miniblock: Gauge<u64>,
+    /// Currently active path used to fetch L2 blocks (1 for the active path, 0 for others).
+    pub sync_path: Family<SyncPath, Gauge<u64>>,
 }
 
 #[vise::register]
@@ -15,6 +15,24 @@ use zksync_web3_decl::{
     namespaces::EthNamespaceClient,
 };
 
+use crate::metrics::FETCHER_METRICS;
+
+/// Path used by the external node to fetch L2 blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, vise::EncodeLabelValue)]
+#[serde(rename_all = "snake_case")]
+#[metrics(rename_all = "snake_case")]
+pub enum SyncPath {
+    /// Blocks are fetched via consensus p2p gossip.
+    Consensus,
+    /// Blocks are fetched from the main node via JSON-RPC. This is used if consensus is disabled,
+    /// or as a fallback if p2p syncing is lagging behind (e.g., because of a loss of the validator quorum).
+    JsonRpc,
+}
+
+impl SyncPath {
+    const ALL: [Self; 2] = [Self::Consensus, Self::JsonRpc];
+}
+
 /// `SyncState` is a structure that holds the state of the syncing process.
 /// The intended use case is to signalize to Web3 API whether the node is fully synced.
 /// Data inside is expected to be updated by both `MainNodeFetcher` (on last block available on the main node)
@@ -79,6 +97,29 @@ impl SyncState {
         self.0.borrow().is_synced().0
     }
 
+    /// Returns the currently active path used to fetch L2 blocks, if it was set.
+    pub fn sync_path(&self) -> Option<SyncPath> {
+        self.0.borrow().sync_path
+    }
+
+    /// Sets the currently active path used to fetch L2 blocks.
+    pub fn set_sync_path(&self, path: SyncPath) {
+        let prev_path = self.0.borrow().sync_path;
+        if prev_path == Some(path) {
+            return;
+        }
+        match prev_path {
+            Some(prev_path) => {
+                tracing::info!("Switched L2 block sync path from {prev_path:?} to {path:?}");
+            }
+            None => tracing::info!("Using {path:?} L2 block sync path"),
+        }
+        self.0.send_modify(|inner| inner.sync_path = Some(path));
+        for label in SyncPath::ALL {
+            FETCHER_METRICS.sync_path[&label].set((label == path).into());
+        }
+    }
+
     pub async fn run_updater(
         self,
         connection_pool: ConnectionPool<Core>,
@@ -138,6 +179,7 @@ impl StateKeeperOutputHandler for SyncState {
 pub(crate) struct SyncStateInner {
     pub(crate) main_node_block: Option<L2BlockNumber>,
     pub(crate) local_block: Option<L2BlockNumber>,
+    pub(crate) sync_path: Option<SyncPath>,
 }
 
 impl SyncStateInner {
@@ -209,6 +251,8 @@ impl From<&SyncStateInner> for Health {
             main_node_block: Option<L2BlockNumber>,
             #[serde(skip_serializing_if = "Option::is_none")]
             local_block: Option<L2BlockNumber>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            sync_path: Option<SyncPath>,
         }
 
         let (is_synced, block_diff) = state.is_synced();
@@ -223,6 +267,7 @@ impl From<&SyncStateInner> for Health {
             is_synced,
             main_node_block: state.main_node_block,
             local_block: state.local_block,
+            sync_path: state.sync_path,
         })
     }
 }
@@ -267,6 +312,25 @@ mod tests {
         assert!(!sync_state.is_synced());
     }
 
+    #[tokio::test]
+    async fn sync_path_is_reported_in_health() {
+        let sync_state = SyncState::default();
+        sync_state.set_local_block(L2BlockNumber(1));
+        sync_state.set_main_node_block(L2BlockNumber(1));
+        let health = sync_state.check_health().await;
+        let details = health.details().unwrap();
+        assert!(details.get("sync_path").is_none(), "{details:?}");
+
+        sync_state.set_sync_path(SyncPath::Consensus);
+        assert_eq!(sync_state.sync_path(), Some(SyncPath::Consensus));
+        let health = sync_state.check_health().await;
+        assert_eq!(health.details().unwrap()["sync_path"], "consensus");
+
+        sync_state.set_sync_path(SyncPath::JsonRpc);
+        let health = sync_state.check_health().await;
+        assert_eq!(health.details().unwrap()["sync_path"], "json_rpc");
+    }
+
     #[test]
     fn test_sync_state_doesnt_panic_on_local_block() {
         let sync_state = SyncState::default();
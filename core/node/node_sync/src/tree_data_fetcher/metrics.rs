@@ -7,7 +7,7 @@ use vise::{
     Info, Metrics, Unit,
 };
 
-use super::{StepOutcome, TreeDataFetcher, TreeDataFetcherError};
+use super::{provider::VerificationOutcome, StepOutcome, TreeDataFetcher, TreeDataFetcherError};
 
 #[derive(Debug, EncodeLabelSet)]
 struct TreeDataFetcherInfo {
@@ -43,6 +43,7 @@ pub(super) enum StepOutcomeLabel {
     UpdatedBatch,
     NoProgress,
     RemoteHashMissing,
+    RootHashNotVerified,
     PossibleReorg,
     TransientError,
 }
@@ -54,6 +55,13 @@ pub(super) enum TreeDataProviderSource {
     BatchDetailsRpc,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "result", rename_all = "snake_case")]
+pub(super) enum RootHashVerificationResult {
+    Match,
+    Mismatch,
+}
+
 const BLOCK_DIFF_BUCKETS: Buckets = Buckets::values(&[
     10.0, 20.0, 50.0, 100.0, 200.0, 500.0, 1_000.0, 2_000.0, 5_000.0, 10_000.0, 20_000.0, 50_000.0,
 ]);
@@ -79,6 +87,10 @@ pub(super) struct TreeDataFetcherMetrics {
     pub l1_commit_block_number_from_diff: Histogram<u64>,
     /// Number of root hashes fetched from a particular source.
     pub root_hash_sources: Family<TreeDataProviderSource, Counter>,
+    /// Number of root hashes fetched from the main node and verified against L1, grouped by the verification result.
+    pub root_hash_verifications: Family<RootHashVerificationResult, Counter>,
+    /// Last L1 batch with the root hash verified against L1.
+    pub last_verified_batch_number: Gauge<u64>,
 }
 
 impl TreeDataFetcherMetrics {
@@ -102,12 +114,25 @@ impl TreeDataFetcherMetrics {
             }
             Ok(StepOutcome::NoProgress) => StepOutcomeLabel::NoProgress,
             Ok(StepOutcome::RemoteHashMissing) => StepOutcomeLabel::RemoteHashMissing,
+            Ok(StepOutcome::RootHashNotVerified) => StepOutcomeLabel::RootHashNotVerified,
             Ok(StepOutcome::PossibleReorg) => StepOutcomeLabel::PossibleReorg,
             Err(err) if err.is_retriable() => StepOutcomeLabel::TransientError,
             Err(_) => return, // fatal error; the node will exit soon anyway
         };
         self.step_outcomes[&label].inc();
     }
+
+    pub fn observe_verification_outcome(&self, outcome: &VerificationOutcome) {
+        let result = match outcome {
+            VerificationOutcome::NoProgress => return,
+            VerificationOutcome::Verified(number) => {
+                self.last_verified_batch_number.set(number.0.into());
+                RootHashVerificationResult::Match
+            }
+            VerificationOutcome::Mismatch { .. } => RootHashVerificationResult::Mismatch,
+        };
+        self.root_hash_verifications[&result].inc();
+    }
 }
 
 #[vise::register]
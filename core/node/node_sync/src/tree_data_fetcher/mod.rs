@@ -7,11 +7,12 @@ use serde::Serialize;
 #[cfg(test)]
 use tokio::sync::mpsc;
 use tokio::sync::watch;
+pub use zksync_config::configs::en_config::RootHashMismatchPolicy;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_types::{
     block::{L1BatchTreeData, L2BlockHeader},
-    Address, L1BatchNumber, L2ChainId,
+    Address, L1BatchNumber, L2ChainId, H256,
 };
 use zksync_web3_decl::{
     client::{DynClient, L1, L2},
@@ -19,8 +20,10 @@ use zksync_web3_decl::{
 };
 
 use self::{
-    metrics::{ProcessingStage, TreeDataFetcherMetrics, METRICS},
-    provider::{L1DataProvider, MissingData, TreeDataProvider},
+    metrics::{ProcessingStage, TreeDataFetcherMetrics, TreeDataProviderSource, METRICS},
+    provider::{
+        L1DataProvider, L1RootHashVerifier, MissingData, TreeDataProvider, VerificationOutcome,
+    },
 };
 use crate::tree_data_fetcher::provider::CombinedDataProvider;
 
@@ -81,6 +84,8 @@ enum StepOutcome {
     UpdatedBatch(L1BatchNumber),
     NoProgress,
     RemoteHashMissing,
+    /// Root hash was fetched from the main node, but cannot be verified against L1 yet.
+    RootHashNotVerified,
     PossibleReorg,
 }
 
@@ -94,9 +99,18 @@ enum StepOutcome {
 /// (which is generally expected to be slower) will check that data returned by the main node is correct
 /// (i.e., "trust but verify" trust model). Additionally, the persisted data will be checked against L1 commitment transactions
 /// by Consistency checker.
+///
+/// If L1 data is configured, root hashes fetched from the main node are verified against root hashes in `BlockCommit` events
+/// once the corresponding batches are committed on L1. Depending on [`RootHashMismatchPolicy`], root hashes are either
+/// verified before being persisted, or persisted immediately and verified afterwards. The last verified L1 batch
+/// is persisted in Postgres, so verification resumes from it after a restart. This allows nodes running without
+/// a Merkle tree not to trust the main node.
 #[derive(Debug)]
 pub struct TreeDataFetcher {
     data_provider: CombinedDataProvider,
+    verifier: Option<L1RootHashVerifier>,
+    root_hash_mismatch_policy: RootHashMismatchPolicy,
+    last_root_hash_mismatch: Option<L1BatchNumber>,
     // Used in the Info metric
     diamond_proxy_address: Option<Address>,
     pool: ConnectionPool<Core>,
@@ -114,6 +128,9 @@ impl TreeDataFetcher {
     pub fn new(client: Box<DynClient<L2>>, pool: ConnectionPool<Core>) -> Self {
         Self {
             data_provider: CombinedDataProvider::new(client.for_component("tree_data_fetcher")),
+            verifier: None,
+            root_hash_mismatch_policy: RootHashMismatchPolicy::default(),
+            last_root_hash_mismatch: None,
             diamond_proxy_address: None,
             pool,
             metrics: &METRICS,
@@ -126,7 +143,8 @@ impl TreeDataFetcher {
 
     /// Attempts to fetch root hashes from L1 (namely, `BlockCommit` events emitted by the diamond proxy) if possible.
     /// The main node will still be used as a fallback in case communicating with L1 fails, or for newer batches,
-    /// which may not be committed on L1.
+    /// which may not be committed on L1. Root hashes fetched from the main node will be verified against L1
+    /// once the corresponding batches are committed; see [`Self::with_root_hash_mismatch_policy()`].
     pub async fn with_l1_data(
         mut self,
         l1_client: Box<DynClient<L1>>,
@@ -147,11 +165,19 @@ impl TreeDataFetcher {
             l2_chain_id,
         )
         .await?;
+        self.verifier = Some(L1RootHashVerifier::new(l1_provider.clone()));
         self.data_provider.set_l1(l1_provider);
         self.diamond_proxy_address = Some(l1_diamond_proxy_addr);
         Ok(self)
     }
 
+    /// Sets the policy applied to root hashes fetched from the main node, in particular, if such a root hash differs
+    /// from the one committed on L1. Has no effect unless L1 data is configured using [`Self::with_l1_data()`].
+    pub fn with_root_hash_mismatch_policy(mut self, policy: RootHashMismatchPolicy) -> Self {
+        self.root_hash_mismatch_policy = policy;
+        self
+    }
+
     /// Returns a health check for this fetcher.
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
@@ -211,6 +237,20 @@ impl TreeDataFetcher {
             return Ok(StepOutcome::NoProgress);
         };
 
+        let verify_before_persisting = self.verifier.is_some()
+            && self.root_hash_mismatch_policy == RootHashMismatchPolicy::Halt;
+        if verify_before_persisting {
+            let mut storage = self.pool.connection_tagged("tree_data_fetcher").await?;
+            let next_l1_batch_to_verify = L1RootHashVerifier::next_l1_batch(&mut storage).await?;
+            if next_l1_batch_to_verify < l1_batch_to_fetch {
+                tracing::debug!(
+                    "Waiting for persisted L1 batches #{next_l1_batch_to_verify}..#{l1_batch_to_fetch} \
+                     to be verified against L1 before fetching new tree data"
+                );
+                return Ok(StepOutcome::NoProgress);
+            }
+        }
+
         tracing::debug!("Fetching tree data for L1 batch #{l1_batch_to_fetch}");
         let stage_latency = self.metrics.stage_latency[&ProcessingStage::Fetch].start();
         let root_hash_result = self
@@ -246,8 +286,43 @@ impl TreeDataFetcher {
             }
         };
 
+        // Root hashes obtained from L1 don't need to be verified.
+        let source = self.data_provider.last_root_hash_source();
+        let mut is_verified = source == Some(TreeDataProviderSource::L1CommitEvent);
+        let verifier = self
+            .verifier
+            .as_mut()
+            .filter(|_| verify_before_persisting && !is_verified);
+        if let Some(verifier) = verifier {
+            let outcome = verifier
+                .verify(l1_batch_to_fetch, &last_l2_block_header, root_hash)
+                .await?;
+            self.metrics.observe_verification_outcome(&outcome);
+            match outcome {
+                VerificationOutcome::NoProgress => {
+                    tracing::debug!(
+                        "Root hash for L1 batch #{l1_batch_to_fetch} cannot be verified against L1 yet; \
+                         not persisting it"
+                    );
+                    return Ok(StepOutcome::RootHashNotVerified);
+                }
+                VerificationOutcome::Verified(_) => {
+                    is_verified = true;
+                }
+                VerificationOutcome::Mismatch {
+                    number,
+                    local_root_hash,
+                    l1_root_hash,
+                } => {
+                    let err = Self::root_hash_mismatch_error(number, local_root_hash, l1_root_hash);
+                    return Err(err.into());
+                }
+            }
+        }
+
         let stage_latency = self.metrics.stage_latency[&ProcessingStage::Persistence].start();
         let mut storage = self.pool.connection_tagged("tree_data_fetcher").await?;
+        let mut storage = storage.start_transaction().await?;
         let rollup_last_leaf_index = storage
             .storage_logs_dedup_dal()
             .max_enumeration_index_by_l1_batch(l1_batch_to_fetch)
@@ -262,14 +337,79 @@ impl TreeDataFetcher {
             .blocks_dal()
             .save_l1_batch_tree_data(l1_batch_to_fetch, &tree_data)
             .await?;
+        if is_verified && self.verifier.is_some() {
+            // Advance the verification cursor if possible, so that the root hash isn't verified again.
+            let next_l1_batch_to_verify = L1RootHashVerifier::next_l1_batch(&mut storage).await?;
+            if next_l1_batch_to_verify == l1_batch_to_fetch {
+                storage
+                    .blocks_dal()
+                    .set_tree_data_fetcher_last_verified_l1_batch(l1_batch_to_fetch)
+                    .await?;
+            }
+        }
+        storage.commit().await?;
         stage_latency.observe();
         tracing::debug!("Updated L1 batch #{l1_batch_to_fetch} with tree data: {tree_data:?}");
         Ok(StepOutcome::UpdatedBatch(l1_batch_to_fetch))
     }
 
+    fn root_hash_mismatch_error(
+        number: L1BatchNumber,
+        local_root_hash: H256,
+        l1_root_hash: H256,
+    ) -> anyhow::Error {
+        anyhow::anyhow!(
+            "Root hash for L1 batch #{number} fetched from the main node ({local_root_hash:?}) \
+             differs from the root hash committed on L1 ({l1_root_hash:?})"
+        )
+    }
+
+    async fn verify_root_hash(&mut self) -> Result<(), TreeDataFetcherError> {
+        let Some(verifier) = &mut self.verifier else {
+            return Ok(());
+        };
+        let outcome = verifier.step().await?;
+        self.metrics.observe_verification_outcome(&outcome);
+        let number = match outcome {
+            VerificationOutcome::NoProgress => return Ok(()),
+            VerificationOutcome::Verified(number) => {
+                tracing::debug!("Verified root hash for L1 batch #{number} against L1");
+                number
+            }
+            VerificationOutcome::Mismatch {
+                number,
+                local_root_hash,
+                l1_root_hash,
+            } => {
+                let err = Self::root_hash_mismatch_error(number, local_root_hash, l1_root_hash);
+                match self.root_hash_mismatch_policy {
+                    RootHashMismatchPolicy::Halt => return Err(err.into()),
+                    RootHashMismatchPolicy::Log => {
+                        tracing::error!("{err}");
+                        self.last_root_hash_mismatch = Some(number);
+                        number
+                    }
+                }
+            }
+        };
+
+        let mut storage = self.pool.connection_tagged("tree_data_fetcher").await?;
+        storage
+            .blocks_dal()
+            .set_tree_data_fetcher_last_verified_l1_batch(number)
+            .await?;
+        Ok(())
+    }
+
     fn update_health(&self, last_updated_l1_batch: Option<L1BatchNumber>) {
-        let health = TreeDataFetcherHealth::Ready {
-            last_updated_l1_batch,
+        let health = if let Some(number) = self.last_root_hash_mismatch {
+            TreeDataFetcherHealth::Affected {
+                error: format!("Root hash mismatch with L1 for L1 batch #{number}"),
+            }
+        } else {
+            TreeDataFetcherHealth::Ready {
+                last_updated_l1_batch,
+            }
         };
         self.health_updater.update(health.into());
     }
@@ -294,7 +434,11 @@ impl TreeDataFetcher {
                     self.update_health(last_updated_l1_batch);
                     false
                 }
-                Ok(StepOutcome::NoProgress | StepOutcome::RemoteHashMissing) => {
+                Ok(
+                    StepOutcome::NoProgress
+                    | StepOutcome::RemoteHashMissing
+                    | StepOutcome::RootHashNotVerified,
+                ) => {
                     // Update health status even if no progress was made to timely clear a previously set
                     // "affected" health.
                     self.update_health(last_updated_l1_batch);
@@ -326,6 +470,19 @@ impl TreeDataFetcher {
                 }
             };
 
+            match self.verify_root_hash().await {
+                Ok(()) => {}
+                Err(err) if err.is_retriable() => {
+                    tracing::warn!(
+                        "Transient error verifying root hash against L1, will retry: {err:?}"
+                    );
+                }
+                Err(err) => {
+                    tracing::error!("Fatal error verifying root hash against L1: {err:?}");
+                    return Err(err.into());
+                }
+            }
+
             if need_to_sleep
                 && tokio::time::timeout(self.poll_interval, stop_receiver.changed())
                     .await
@@ -1,9 +1,12 @@
-use std::fmt;
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
 
 use anyhow::Context;
 use async_trait::async_trait;
 use zksync_contracts::bridgehub_contract;
-use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalResult};
 use zksync_eth_client::{CallFunctionArgs, EthInterface};
 use zksync_system_constants::L2_BRIDGEHUB_ADDRESS;
 use zksync_types::{
@@ -18,7 +21,7 @@ use zksync_web3_decl::{
 
 use super::{
     metrics::{ProcessingStage, TreeDataProviderSource, METRICS},
-    TreeDataFetcherError, TreeDataFetcherResult,
+    TreeDataFetcher, TreeDataFetcherError, TreeDataFetcherResult,
 };
 
 #[cfg(test)]
@@ -97,7 +100,7 @@ struct PastL1BatchInfo {
     chain_id: SLChainId,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct SLChainAccess {
     client: Box<DynClient<L1>>,
     chain_id: SLChainId,
@@ -114,7 +117,7 @@ struct SLChainAccess {
 /// for a certain L1 batch is relatively close to L1 batch sealing. Thus, the provider finds an approximate L1 block number
 /// for the event using binary search, or uses an L1 block number of the `BlockCommit` event for the previously queried L1 batch
 /// (provided it's not too far behind the seal timestamp of the batch).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(super) struct L1DataProvider {
     l1_chain_data: SLChainAccess,
     gateway_chain_data: Option<SLChainAccess>,
@@ -234,6 +237,11 @@ impl L1DataProvider {
         Ok((number, block.timestamp))
     }
 
+    /// Forgets about the previously processed L1 batch, so that the provider can be queried for earlier batches.
+    fn reset(&mut self) {
+        self.past_l1_batch = None;
+    }
+
     fn chain_data_by_id(&self, searched_chain_id: SLChainId) -> Option<&SLChainAccess> {
         if searched_chain_id == self.l1_chain_data.chain_id {
             Some(&self.l1_chain_data)
@@ -381,6 +389,8 @@ pub(super) struct CombinedDataProvider {
     l1: Option<L1DataProvider>,
     // Generic to allow for tests.
     rpc: Box<dyn TreeDataProvider>,
+    /// Source of the last successfully fetched root hash.
+    last_root_hash_source: Option<TreeDataProviderSource>,
 }
 
 impl CombinedDataProvider {
@@ -388,9 +398,14 @@ impl CombinedDataProvider {
         Self {
             l1: None,
             rpc: Box::new(fallback),
+            last_root_hash_source: None,
         }
     }
 
+    pub fn last_root_hash_source(&self) -> Option<TreeDataProviderSource> {
+        self.last_root_hash_source
+    }
+
     pub fn set_l1(&mut self, l1: L1DataProvider) {
         self.l1 = Some(l1);
     }
@@ -426,6 +441,7 @@ impl TreeDataProvider for CombinedDataProvider {
                 }
                 Ok(Ok(root_hash)) => {
                     METRICS.root_hash_sources[&TreeDataProviderSource::L1CommitEvent].inc();
+                    self.last_root_hash_source = Some(TreeDataProviderSource::L1CommitEvent);
                     return Ok(Ok(root_hash));
                 }
                 Ok(Err(missing_data)) => {
@@ -441,7 +457,143 @@ impl TreeDataProvider for CombinedDataProvider {
         stage_latency.observe();
         if matches!(rpc_result, Ok(Ok(_))) {
             METRICS.root_hash_sources[&TreeDataProviderSource::BatchDetailsRpc].inc();
+            self.last_root_hash_source = Some(TreeDataProviderSource::BatchDetailsRpc);
         }
         rpc_result
     }
 }
+
+/// Outcome of a single [`L1RootHashVerifier`] step.
+#[derive(Debug, PartialEq)]
+pub(super) enum VerificationOutcome {
+    /// There are no L1 batches to verify, or the next batch cannot be verified yet (e.g., because it's not committed on L1).
+    NoProgress,
+    /// Root hash of the L1 batch matches the one committed on L1.
+    Verified(L1BatchNumber),
+    /// Root hash of the L1 batch differs from the one committed on L1.
+    Mismatch {
+        number: L1BatchNumber,
+        local_root_hash: H256,
+        l1_root_hash: H256,
+    },
+}
+
+/// Verifier of root hashes fetched from a trusted source (i.e., the main node) against root hashes in `BlockCommit` events
+/// emitted when the corresponding L1 batches are committed on L1.
+///
+/// Persisted L1 batches are verified sequentially, starting from the batch after the verification cursor persisted
+/// in Postgres, so that verification resumes after a node restart. The cursor is advanced by [`TreeDataFetcher`].
+#[derive(Debug)]
+pub(super) struct L1RootHashVerifier {
+    l1: L1DataProvider,
+    /// Last L1 batch successfully queried from L1.
+    last_queried_l1_batch: Option<L1BatchNumber>,
+    retry_interval: Duration,
+    /// Earliest time to query L1 again; set if the previous query didn't succeed.
+    retry_at: Option<Instant>,
+    /// Earliest time to check Postgres for unverified L1 batches again; set if there were no such batches.
+    poll_at: Option<Instant>,
+}
+
+impl L1RootHashVerifier {
+    /// Interval between attempts to verify an L1 batch that cannot be verified yet. Since the verifier may need
+    /// to binary-search L1 blocks on each attempt, it shouldn't be too small.
+    const DEFAULT_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+    pub fn new(l1: L1DataProvider) -> Self {
+        Self {
+            l1,
+            last_queried_l1_batch: None,
+            retry_interval: Self::DEFAULT_RETRY_INTERVAL,
+            retry_at: None,
+            poll_at: None,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_retry_interval(mut self, interval: Duration) -> Self {
+        self.retry_interval = interval;
+        self
+    }
+
+    /// Returns the next L1 batch to verify based on the persisted verification cursor.
+    pub async fn next_l1_batch(storage: &mut Connection<'_, Core>) -> DalResult<L1BatchNumber> {
+        let last_verified_l1_batch = storage
+            .blocks_dal()
+            .get_tree_data_fetcher_last_verified_l1_batch()
+            .await?;
+        // L1 batches before the earliest one may be absent because of snapshot recovery or pruning.
+        let earliest_l1_batch = storage.blocks_dal().get_earliest_l1_batch_number().await?;
+        Ok(
+            earliest_l1_batch.map_or(last_verified_l1_batch + 1, |earliest| {
+                earliest.max(last_verified_l1_batch + 1)
+            }),
+        )
+    }
+
+    /// Verifies the root hash of the next persisted L1 batch after the verification cursor.
+    pub async fn step(&mut self) -> TreeDataFetcherResult<VerificationOutcome> {
+        let now = Instant::now();
+        if self.poll_at.is_some_and(|poll_at| now < poll_at) {
+            return Ok(VerificationOutcome::NoProgress);
+        }
+
+        let mut storage = self.l1.pool.connection_tagged("tree_data_fetcher").await?;
+        let number = Self::next_l1_batch(&mut storage).await?;
+        let tree_data = storage.blocks_dal().get_l1_batch_tree_data(number).await?;
+        let Some(tree_data) = tree_data else {
+            // All persisted L1 batches are verified.
+            self.poll_at = Some(now + self.retry_interval);
+            return Ok(VerificationOutcome::NoProgress);
+        };
+        self.poll_at = None;
+        let last_l2_block = TreeDataFetcher::get_last_l2_block(&mut storage, number).await?;
+        drop(storage);
+
+        self.verify(number, &last_l2_block, tree_data.hash).await
+    }
+
+    /// Verifies the specified root hash for an L1 batch against L1.
+    pub async fn verify(
+        &mut self,
+        number: L1BatchNumber,
+        last_l2_block: &L2BlockHeader,
+        root_hash: H256,
+    ) -> TreeDataFetcherResult<VerificationOutcome> {
+        let now = Instant::now();
+        if self.retry_at.is_some_and(|retry_at| now < retry_at) {
+            return Ok(VerificationOutcome::NoProgress);
+        }
+        // Delay the next attempt unless the verification succeeds; this also applies to transient errors.
+        self.retry_at = Some(now + self.retry_interval);
+
+        if self
+            .last_queried_l1_batch
+            .is_some_and(|last_number| last_number >= number)
+        {
+            // The L1 provider requires monotonically increasing L1 batch numbers; they can go back after a revert.
+            self.l1.reset();
+        }
+        let l1_root_hash = match self.l1.batch_details(number, last_l2_block).await? {
+            Ok(root_hash) => root_hash,
+            Err(missing_data) => {
+                tracing::debug!(
+                    "Cannot verify root hash for L1 batch #{number} yet: {missing_data}"
+                );
+                return Ok(VerificationOutcome::NoProgress);
+            }
+        };
+        self.retry_at = None;
+        self.last_queried_l1_batch = Some(number);
+
+        Ok(if l1_root_hash == root_hash {
+            VerificationOutcome::Verified(number)
+        } else {
+            VerificationOutcome::Mismatch {
+                number,
+                local_root_hash: root_hash,
+                l1_root_hash,
+            }
+        })
+    }
+}
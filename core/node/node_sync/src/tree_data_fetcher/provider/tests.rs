@@ -1,5 +1,7 @@
 //! Tests for tree data providers.
 
+use std::ops;
+
 use assert_matches::assert_matches;
 use once_cell::sync::Lazy;
 use test_casing::test_casing;
@@ -8,15 +10,18 @@ use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_node_test_utils::create_l2_block;
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
-    api, ethabi,
+    api,
+    block::L1BatchTreeData,
+    ethabi,
     web3::{BlockId, CallRequest},
     L2BlockNumber, ProtocolVersionId,
 };
 use zksync_web3_decl::client::MockClient;
 
 use super::*;
-use crate::tree_data_fetcher::tests::{
-    get_last_l2_block, seal_l1_batch_with_timestamp, MockMainNodeClient,
+use crate::tree_data_fetcher::{
+    tests::{get_last_l2_block, seal_l1_batch_with_timestamp, FetcherHarness, MockMainNodeClient},
+    RootHashMismatchPolicy, StepOutcome,
 };
 
 const L1_DIAMOND_PROXY_ADDRESS: Address = Address::repeat_byte(0x22);
@@ -73,6 +78,7 @@ impl L2Parameters {
                 Ok(root_hash.map(|&hash| api::L1BatchDetails {
                     number,
                     base: mock_block_details_base(number.0, Some(hash)),
+                    commitment_mode: None,
                 }))
             })
             .method("zks_getBlockDetails", move |number: L2BlockNumber| {
@@ -519,3 +525,217 @@ async fn combined_data_provider_errors() {
     assert_eq!(root_hash, H256::repeat_byte(2));
     assert!(provider.l1.is_none());
 }
+
+async fn seal_l1_batches_for_verification(
+    storage: &mut Connection<'_, Core>,
+    numbers: ops::RangeInclusive<u32>,
+) {
+    for number in numbers {
+        let timestamp = 50_000 + u64::from(number) * 1_000;
+        seal_l1_batch_with_timestamp(storage, L1BatchNumber(number), timestamp).await;
+    }
+}
+
+async fn last_verified_l1_batch(pool: &ConnectionPool<Core>) -> L1BatchNumber {
+    let mut storage = pool.connection().await.unwrap();
+    storage
+        .blocks_dal()
+        .get_tree_data_fetcher_last_verified_l1_batch()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn verifying_root_hashes_against_l1() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    seal_l1_batches_for_verification(&mut storage, 1..=3).await;
+    for number in 1..=3 {
+        // The root hash for L1 batch #2 diverges from the one committed on L1.
+        let root_hash = if number == 2 {
+            H256::repeat_byte(0xff)
+        } else {
+            H256::repeat_byte(number as u8)
+        };
+        let tree_data = L1BatchTreeData {
+            hash: root_hash,
+            rollup_last_leaf_index: number.into(),
+        };
+        storage
+            .blocks_dal()
+            .save_l1_batch_tree_data(L1BatchNumber(number), &tree_data)
+            .await
+            .unwrap();
+    }
+    // L1 batch #3 is not committed on L1 yet.
+    let mut eth_params = EthereumParameters::new_l1(1_000_000);
+    eth_params.push_commit(L1BatchNumber(1), 51_500);
+    eth_params.push_commit(L1BatchNumber(2), 52_500);
+
+    let l1_provider = create_l1_data_provider(Box::new(eth_params.client()), pool.clone()).await;
+    let mut verifier = L1RootHashVerifier::new(l1_provider).with_retry_interval(Duration::ZERO);
+    // The verifier doesn't advance the persisted cursor by itself.
+    for _ in 0..2 {
+        assert_eq!(
+            verifier.step().await.unwrap(),
+            VerificationOutcome::Verified(L1BatchNumber(1))
+        );
+    }
+
+    storage
+        .blocks_dal()
+        .set_tree_data_fetcher_last_verified_l1_batch(L1BatchNumber(1))
+        .await
+        .unwrap();
+    assert_eq!(
+        verifier.step().await.unwrap(),
+        VerificationOutcome::Mismatch {
+            number: L1BatchNumber(2),
+            local_root_hash: H256::repeat_byte(0xff),
+            l1_root_hash: H256::repeat_byte(2),
+        }
+    );
+
+    storage
+        .blocks_dal()
+        .set_tree_data_fetcher_last_verified_l1_batch(L1BatchNumber(2))
+        .await
+        .unwrap();
+    for _ in 0..2 {
+        assert_eq!(
+            verifier.step().await.unwrap(),
+            VerificationOutcome::NoProgress
+        );
+    }
+
+    // Check that the verifier can go back, e.g. after a revert.
+    let last_l2_block = get_last_l2_block(&mut storage, L1BatchNumber(1)).await;
+    let outcome = verifier
+        .verify(L1BatchNumber(1), &last_l2_block, H256::repeat_byte(1))
+        .await
+        .unwrap();
+    assert_eq!(outcome, VerificationOutcome::Verified(L1BatchNumber(1)));
+}
+
+#[test_casing(2, [false, true])]
+#[tokio::test]
+async fn verifying_root_hashes_before_persisting(diverging_root_hash: bool) {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    seal_l1_batches_for_verification(&mut storage, 1..=3).await;
+
+    let mut main_node = MockMainNodeClient::default();
+    for number in 1..=3 {
+        let root_hash = if diverging_root_hash && number == 2 {
+            H256::repeat_byte(0xff)
+        } else {
+            H256::repeat_byte(number as u8)
+        };
+        main_node.insert_batch(L1BatchNumber(number), root_hash);
+    }
+    // L1 batch #3 is not committed on L1 yet.
+    let mut eth_params = EthereumParameters::new_l1(1_000_000);
+    eth_params.push_commit(L1BatchNumber(1), 51_500);
+    eth_params.push_commit(L1BatchNumber(2), 52_500);
+    let l1_provider = create_l1_data_provider(Box::new(eth_params.client()), pool.clone()).await;
+
+    // Root hashes are always fetched from the main node, so that all of them need to be verified.
+    let mut fetcher = FetcherHarness::new(main_node, pool.clone()).fetcher;
+    fetcher.verifier =
+        Some(L1RootHashVerifier::new(l1_provider).with_retry_interval(Duration::ZERO));
+    fetcher.root_hash_mismatch_policy = RootHashMismatchPolicy::Halt;
+
+    let outcome = fetcher.step().await.unwrap();
+    assert_matches!(outcome, StepOutcome::UpdatedBatch(L1BatchNumber(1)));
+    assert_eq!(last_verified_l1_batch(&pool).await, L1BatchNumber(1));
+
+    if diverging_root_hash {
+        let err = fetcher.step().await.unwrap_err();
+        assert_matches!(err, TreeDataFetcherError::Internal(_));
+    } else {
+        let outcome = fetcher.step().await.unwrap();
+        assert_matches!(outcome, StepOutcome::UpdatedBatch(L1BatchNumber(2)));
+        assert_eq!(last_verified_l1_batch(&pool).await, L1BatchNumber(2));
+
+        let outcome = fetcher.step().await.unwrap();
+        assert_matches!(outcome, StepOutcome::RootHashNotVerified);
+    }
+
+    // Unverified root hashes must not be persisted.
+    let unverified_l1_batch = if diverging_root_hash { 2 } else { 3 };
+    let tree_data = storage
+        .blocks_dal()
+        .get_l1_batch_tree_data(L1BatchNumber(unverified_l1_batch))
+        .await
+        .unwrap();
+    assert_eq!(tree_data, None);
+}
+
+#[tokio::test]
+async fn verifying_persisted_root_hashes_after_restart() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    seal_l1_batches_for_verification(&mut storage, 1..=3).await;
+
+    let mut main_node = MockMainNodeClient::default();
+    for number in 1..=3 {
+        // The root hash for L1 batch #2 diverges from the one committed on L1.
+        let root_hash = if number == 2 {
+            H256::repeat_byte(0xff)
+        } else {
+            H256::repeat_byte(number as u8)
+        };
+        main_node.insert_batch(L1BatchNumber(number), root_hash);
+    }
+    let mut eth_params = EthereumParameters::new_l1(1_000_000);
+    eth_params.push_commit(L1BatchNumber(1), 51_500);
+    eth_params.push_commit(L1BatchNumber(2), 52_500);
+    let create_verifier = || async {
+        let l1_provider =
+            create_l1_data_provider(Box::new(eth_params.client()), pool.clone()).await;
+        L1RootHashVerifier::new(l1_provider).with_retry_interval(Duration::ZERO)
+    };
+
+    let mut fetcher = FetcherHarness::new(main_node, pool.clone()).fetcher;
+    fetcher.verifier = Some(create_verifier().await);
+    fetcher.root_hash_mismatch_policy = RootHashMismatchPolicy::Log;
+    // With the `Log` policy, root hashes are persisted before they are verified.
+    for number in 1..=3 {
+        let outcome = fetcher.step().await.unwrap();
+        assert_matches!(outcome, StepOutcome::UpdatedBatch(n) if n.0 == number);
+    }
+    assert_eq!(last_verified_l1_batch(&pool).await, L1BatchNumber(0));
+
+    fetcher.verify_root_hash().await.unwrap();
+    assert_eq!(last_verified_l1_batch(&pool).await, L1BatchNumber(1));
+    assert_eq!(fetcher.last_root_hash_mismatch, None);
+
+    // Emulate the node restart; verification should resume from the persisted cursor.
+    let mut fetcher = FetcherHarness::new(MockMainNodeClient::default(), pool.clone()).fetcher;
+    fetcher.verifier = Some(create_verifier().await);
+    fetcher.root_hash_mismatch_policy = RootHashMismatchPolicy::Log;
+    fetcher.verify_root_hash().await.unwrap();
+    assert_eq!(last_verified_l1_batch(&pool).await, L1BatchNumber(2));
+    assert_eq!(fetcher.last_root_hash_mismatch, Some(L1BatchNumber(2)));
+
+    // L1 batch #3 is not committed on L1 yet.
+    fetcher.verify_root_hash().await.unwrap();
+    assert_eq!(last_verified_l1_batch(&pool).await, L1BatchNumber(2));
+
+    // The cursor must be reset on revert.
+    storage
+        .blocks_dal()
+        .delete_l1_batches(L1BatchNumber(1))
+        .await
+        .unwrap();
+    assert_eq!(last_verified_l1_batch(&pool).await, L1BatchNumber(1));
+}
@@ -105,18 +105,21 @@ pub(super) async fn get_last_l2_block(
 }
 
 #[derive(Debug)]
-struct FetcherHarness {
-    fetcher: TreeDataFetcher,
+pub(super) struct FetcherHarness {
+    pub fetcher: TreeDataFetcher,
     updates_receiver: mpsc::UnboundedReceiver<L1BatchNumber>,
     metrics: &'static TreeDataFetcherMetrics,
 }
 
 impl FetcherHarness {
-    fn new(client: impl TreeDataProvider, pool: ConnectionPool<Core>) -> Self {
+    pub fn new(client: impl TreeDataProvider, pool: ConnectionPool<Core>) -> Self {
         let (updates_sender, updates_receiver) = mpsc::unbounded_channel();
         let metrics = &*Box::leak(Box::<TreeDataFetcherMetrics>::default());
         let fetcher = TreeDataFetcher {
             data_provider: CombinedDataProvider::new(client),
+            verifier: None,
+            root_hash_mismatch_policy: RootHashMismatchPolicy::default(),
+            last_root_hash_mismatch: None,
             diamond_proxy_address: None,
             pool: pool.clone(),
             metrics,
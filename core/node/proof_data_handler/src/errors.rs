@@ -9,6 +9,9 @@ pub(crate) enum RequestProcessorError {
     GeneralError(String),
     ObjectStore(ObjectStoreError),
     Dal(DalError),
+    InvalidRequest(String),
+    NotFound(String),
+    TooManyRequests(String),
 }
 
 impl From<DalError> for RequestProcessorError {
@@ -41,6 +44,9 @@ impl IntoResponse for RequestProcessorError {
                     "Failed fetching/saving from db".to_owned(),
                 )
             }
+            Self::InvalidRequest(err) => (StatusCode::BAD_REQUEST, err),
+            Self::NotFound(err) => (StatusCode::NOT_FOUND, err),
+            Self::TooManyRequests(err) => (StatusCode::TOO_MANY_REQUESTS, err),
         };
         (status_code, message).into_response()
     }
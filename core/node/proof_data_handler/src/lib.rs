@@ -1,15 +1,24 @@
 use std::{net::SocketAddr, sync::Arc};
 
 use anyhow::Context as _;
-use axum::{extract::Path, http::StatusCode, response::IntoResponse, routing::post, Json, Router};
+use axum::{
+    body::Bytes,
+    extract::{DefaultBodyLimit, Path},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
 use request_processor::RequestProcessor;
+use resumable::ResumableRequestProcessor;
+use tee_attestation::AttestationPolicy;
 use tee_request_processor::TeeRequestProcessor;
 use tokio::sync::watch;
 use zksync_config::configs::ProofDataHandlerConfig;
 use zksync_dal::{ConnectionPool, Core};
 use zksync_object_store::ObjectStore;
 use zksync_prover_interface::api::{
-    ProofGenerationDataRequest, RegisterTeeAttestationRequest, SubmitProofRequest,
+    ArtifactInfo, ProofGenerationDataRequest, RegisterTeeAttestationRequest, SubmitProofRequest,
     SubmitTeeProofRequest, TeeProofGenerationDataRequest,
 };
 use zksync_types::{commitment::L1BatchCommitmentMode, L2ChainId};
@@ -20,6 +29,8 @@ mod tests;
 mod errors;
 mod metrics;
 mod request_processor;
+mod resumable;
+mod tee_attestation;
 mod tee_request_processor;
 
 pub async fn run_server(
@@ -28,31 +39,53 @@ pub async fn run_server(
     connection_pool: ConnectionPool<Core>,
     commitment_mode: L1BatchCommitmentMode,
     l2_chain_id: L2ChainId,
-    mut stop_receiver: watch::Receiver<bool>,
+    stop_receiver: watch::Receiver<bool>,
 ) -> anyhow::Result<()> {
     let bind_address = SocketAddr::from(([0, 0, 0, 0], config.http_port));
-    tracing::info!("Starting proof data handler server on {bind_address}");
+    let attestation_policy =
+        if config.tee_config.tee_support && config.tee_config.tee_attestation_verification {
+            Some(AttestationPolicy::new(
+                connection_pool.clone(),
+                tee_attestation::default_quote_verifier()?,
+                config.tee_config.tee_max_tcb_age(),
+            ))
+        } else {
+            None
+        };
+
     let app = create_proof_processing_router(
         blob_store,
         connection_pool,
         config,
         commitment_mode,
         l2_chain_id,
+        attestation_policy,
     );
+    serve("proof data handler", bind_address, app, stop_receiver).await
+}
 
+async fn serve(
+    name: &str,
+    bind_address: SocketAddr,
+    app: Router,
+    mut stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<()> {
+    tracing::info!("Starting {name} server on {bind_address}");
     let listener = tokio::net::TcpListener::bind(bind_address)
         .await
-        .with_context(|| format!("Failed binding proof data handler server to {bind_address}"))?;
+        .with_context(|| format!("Failed binding {name} server to {bind_address}"))?;
     axum::serve(listener, app)
         .with_graceful_shutdown(async move {
             if stop_receiver.changed().await.is_err() {
-                tracing::warn!("Stop signal sender for proof data handler server was dropped without sending a signal");
+                tracing::warn!(
+                    "Stop signal sender for {name} server was dropped without sending a signal"
+                );
             }
-            tracing::info!("Stop signal received, proof data handler server is shutting down");
+            tracing::info!("Stop signal received, {name} server is shutting down");
         })
         .await
-        .context("Proof data handler server failed")?;
-    tracing::info!("Proof data handler server shut down");
+        .with_context(|| format!("{name} server failed"))?;
+    tracing::info!("{name} server shut down");
     Ok(())
 }
 
@@ -62,6 +95,7 @@ fn create_proof_processing_router(
     config: ProofDataHandlerConfig,
     commitment_mode: L1BatchCommitmentMode,
     l2_chain_id: L2ChainId,
+    attestation_policy: Option<AttestationPolicy>,
 ) -> Router {
     let get_proof_gen_processor = RequestProcessor::new(
         blob_store.clone(),
@@ -70,6 +104,14 @@ fn create_proof_processing_router(
         commitment_mode,
     );
     let submit_proof_processor = get_proof_gen_processor.clone();
+    let resumable_processor =
+        ResumableRequestProcessor::new(get_proof_gen_processor.clone(), blob_store.clone());
+    let lock_proof_gen_processor = resumable_processor.clone();
+    let download_proof_gen_processor = resumable_processor.clone();
+    let start_upload_processor = resumable_processor.clone();
+    let upload_status_processor = resumable_processor.clone();
+    let upload_chunk_processor = resumable_processor;
+
     let mut router = Router::new()
         .route(
             "/proof_generation_data",
@@ -92,11 +134,54 @@ fn create_proof_processing_router(
                         .await
                 },
             ),
+        )
+        // v2 API supporting resumable transfers; see the `resumable` module for details.
+        .route(
+            "/v2/proof_generation_data",
+            post(
+                move || async move { lock_proof_gen_processor.lock_proof_generation_data().await },
+            ),
+        )
+        .route(
+            "/v2/proof_generation_data/:l1_batch_number",
+            get(
+                move |l1_batch_number: Path<u32>, headers: HeaderMap| async move {
+                    download_proof_gen_processor
+                        .download_proof_generation_data(l1_batch_number, &headers)
+                        .await
+                },
+            ),
+        )
+        .route(
+            "/v2/proofs/:l1_batch_number",
+            post(
+                move |l1_batch_number: Path<u32>, payload: Json<ArtifactInfo>| async move {
+                    start_upload_processor
+                        .start_upload(l1_batch_number, payload)
+                        .await
+                },
+            )
+            .get(move |l1_batch_number: Path<u32>| async move {
+                upload_status_processor.upload_status(l1_batch_number)
+            })
+            .patch(
+                move |l1_batch_number: Path<u32>, headers: HeaderMap, chunk: Bytes| async move {
+                    upload_chunk_processor
+                        .upload_chunk(l1_batch_number, &headers, chunk)
+                        .await
+                },
+            )
+            .layer(DefaultBodyLimit::max(resumable::MAX_CHUNK_SIZE)),
         );
 
     if config.tee_config.tee_support {
-        let get_tee_proof_gen_processor =
-            TeeRequestProcessor::new(blob_store, connection_pool, config.clone(), l2_chain_id);
+        let get_tee_proof_gen_processor = TeeRequestProcessor::new(
+            blob_store,
+            connection_pool,
+            config.clone(),
+            l2_chain_id,
+            attestation_policy,
+        );
         let submit_tee_proof_processor = get_tee_proof_gen_processor.clone();
         let register_tee_attestation_processor = get_tee_proof_gen_processor.clone();
 
@@ -1,6 +1,6 @@
 use std::{fmt, time::Duration};
 
-use vise::{EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics, Unit};
+use vise::{Counter, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics, Unit};
 use zksync_object_store::bincode;
 use zksync_prover_interface::inputs::WitnessInputData;
 use zksync_types::tee_types::TeeType;
@@ -19,6 +19,19 @@ pub(super) struct ProofDataHandlerMetrics {
     pub total_blob_size_in_mb: Histogram<u64>,
     #[metrics(buckets = vise::Buckets::LATENCIES, unit = Unit::Seconds)]
     pub tee_proof_roundtrip_time: Family<MetricsTeeType, Histogram<Duration>>,
+    /// Number of TEE attestations rejected by the attestation policy.
+    pub tee_attestation_rejections: Family<AttestationRejectionReason, Counter>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "reason", rename_all = "snake_case")]
+pub(crate) enum AttestationRejectionReason {
+    MalformedQuote,
+    InvalidSignature,
+    UnacceptableTcbStatus,
+    StaleTcb,
+    DisallowedMeasurement,
+    PubkeyMismatch,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use axum::{extract::Path, Json};
+use tracing::Instrument as _;
 use zksync_config::configs::ProofDataHandlerConfig;
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_object_store::ObjectStore;
@@ -19,6 +20,7 @@ use zksync_types::{
     web3::keccak256,
     L1BatchNumber, ProtocolVersionId, H256, STATE_DIFF_HASH_KEY_PRE_GATEWAY,
 };
+use zksync_vlog::opentelemetry::{l1_batch_span, L1BatchStage};
 
 use crate::{errors::RequestProcessorError, metrics::METRICS};
 
@@ -59,6 +61,10 @@ impl RequestProcessor {
 
         let proof_generation_data = self
             .proof_generation_data_for_existing_batch(l1_batch_number)
+            .instrument(l1_batch_span(
+                L1BatchStage::ProofGenerationDataServed,
+                l1_batch_number.0,
+            ))
             .await;
 
         // If we weren't able to fetch all the data, we should unlock the batch before returning.
@@ -74,7 +80,9 @@ impl RequestProcessor {
     }
 
     /// Will choose a batch that has all the required data and isn't picked up by any prover yet.
-    async fn lock_batch_for_proving(&self) -> Result<Option<L1BatchNumber>, RequestProcessorError> {
+    pub(crate) async fn lock_batch_for_proving(
+        &self,
+    ) -> Result<Option<L1BatchNumber>, RequestProcessorError> {
         self.pool
             .connection()
             .await
@@ -86,7 +94,7 @@ impl RequestProcessor {
     }
 
     /// Marks the batch as 'unpicked', allowing it to be picked up by another prover.
-    async fn unlock_batch(
+    pub(crate) async fn unlock_batch(
         &self,
         l1_batch_number: L1BatchNumber,
     ) -> Result<(), RequestProcessorError> {
@@ -100,6 +108,21 @@ impl RequestProcessor {
             .map_err(RequestProcessorError::Dal)
     }
 
+    /// Checks whether the batch is locked for proving and is not proven yet.
+    pub(crate) async fn is_batch_locked_for_proving(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<bool, RequestProcessorError> {
+        self.pool
+            .connection()
+            .await
+            .map_err(RequestProcessorError::Dal)?
+            .proof_generation_dal()
+            .is_batch_locked_for_proving(l1_batch_number)
+            .await
+            .map_err(RequestProcessorError::Dal)
+    }
+
     /// Will fetch all the required data for the batch and return it.
     ///
     /// ## Panics
@@ -107,7 +130,7 @@ impl RequestProcessor {
     /// Expects all the data to be present in the database.
     /// Will panic if any of the required data is missing.
     #[tracing::instrument(skip(self))]
-    async fn proof_generation_data_for_existing_batch(
+    pub(crate) async fn proof_generation_data_for_existing_batch(
         &self,
         l1_batch_number: L1BatchNumber,
     ) -> Result<ProofGenerationData, RequestProcessorError> {
@@ -199,7 +222,18 @@ impl RequestProcessor {
         Json(payload): Json<SubmitProofRequest>,
     ) -> Result<Json<SubmitProofResponse>, RequestProcessorError> {
         tracing::info!("Received proof for block number: {:?}", l1_batch_number);
-        let l1_batch_number = L1BatchNumber(l1_batch_number);
+        let span = l1_batch_span(L1BatchStage::ProofSubmitted, l1_batch_number);
+        self.save_submitted_proof(L1BatchNumber(l1_batch_number), payload)
+            .instrument(span)
+            .await?;
+        Ok(Json(SubmitProofResponse::Success))
+    }
+
+    async fn save_submitted_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        payload: SubmitProofRequest,
+    ) -> Result<(), RequestProcessorError> {
         match payload {
             SubmitProofRequest::Proof(proof) => {
                 let blob_url = self
@@ -300,7 +334,6 @@ impl RequestProcessor {
                     .map_err(RequestProcessorError::Dal)?;
             }
         }
-
-        Ok(Json(SubmitProofResponse::Success))
+        Ok(())
     }
 }
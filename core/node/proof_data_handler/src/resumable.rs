@@ -0,0 +1,737 @@
+//! Resumable transfers for the v2 proof data handler API.
+//!
+//! Proof generation data is served as a bincode-serialized [`ProofGenerationData`] supporting HTTP range requests,
+//! so that an interrupted download can be continued from the last received byte. Proofs are uploaded in chunks;
+//! the server tracks the number of received bytes, so that an interrupted upload can be resumed from this offset.
+//! Both downloads and uploads are accompanied by the Keccak-256 hash of the artifact, which is checked by the receiver
+//! once all bytes are transferred.
+//!
+//! Only batches locked for proving can be downloaded or uploaded. Recently prepared downloads are cached in memory;
+//! on a cache miss, proof generation data is regenerated from Postgres and the object store. Uploaded chunks
+//! are persisted in the object store, so that only upload metadata is kept in memory. This metadata is lost
+//! on server restart; in this case, the prover should start an upload anew. Uploads without activity
+//! for [`UPLOAD_EXPIRATION`] are discarded, and the number of concurrent uploads is capped.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    ops,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    body::Bytes,
+    extract::Path,
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use zksync_object_store::{bincode, Bucket, ObjectStore};
+use zksync_prover_interface::api::{
+    ArtifactInfo, ProofGenerationData, ProofGenerationDataInfo, SubmitProofRequest, UploadStatus,
+};
+use zksync_types::{web3::keccak256, L1BatchNumber, H256};
+
+use crate::{errors::RequestProcessorError, request_processor::RequestProcessor};
+
+/// Header specifying the offset of an uploaded chunk.
+pub(crate) const UPLOAD_OFFSET_HEADER: &str = "upload-offset";
+/// Header with the hex-encoded Keccak-256 hash of the entire downloaded artifact.
+pub(crate) const CONTENT_HASH_HEADER: &str = "x-content-hash";
+/// Maximum size of an uploaded chunk.
+pub(crate) const MAX_CHUNK_SIZE: usize = 16 << 20;
+/// Maximum size of an uploaded artifact. Uploaded proofs are compressed and are much smaller than this limit;
+/// the entire artifact is loaded into memory once the upload is completed.
+const MAX_UPLOAD_SIZE: u64 = 64 << 20;
+/// Maximum number of uploads in progress.
+const MAX_CONCURRENT_UPLOADS: usize = 16;
+/// Uploads without activity for this duration are discarded.
+const UPLOAD_EXPIRATION: Duration = Duration::from_secs(3_600);
+/// Maximum number of proof generation data artifacts kept in memory for downloads.
+const MAX_CACHED_DOWNLOADS: usize = 4;
+/// Maximum number of recently completed uploads remembered, so that retrying a completed upload
+/// (e.g., because the response to the last chunk was lost) succeeds.
+const MAX_COMPLETED_UPLOADS: usize = 16;
+
+#[derive(Debug)]
+struct CachedDownload {
+    l1_batch_number: L1BatchNumber,
+    content_hash: H256,
+    bytes: Bytes,
+}
+
+/// Chunks of an upload persisted in the object store.
+#[derive(Debug, Clone, Copy)]
+struct UploadedChunks {
+    l1_batch_number: L1BatchNumber,
+    artifact: ArtifactInfo,
+    count: usize,
+}
+
+impl UploadedChunks {
+    fn key(&self, index: usize) -> String {
+        format!(
+            "{}_{:?}_{index}.bin",
+            self.l1_batch_number, self.artifact.content_hash
+        )
+    }
+
+    fn keys(&self) -> impl Iterator<Item = String> + '_ {
+        (0..self.count).map(|index| self.key(index))
+    }
+}
+
+#[derive(Debug)]
+struct Upload {
+    artifact: ArtifactInfo,
+    /// Number of received bytes.
+    offset: u64,
+    /// Number of chunks persisted in the object store.
+    chunk_count: usize,
+    /// Set while a chunk is being persisted; other chunks are rejected in the meantime.
+    is_persisting_chunk: bool,
+    last_activity: Instant,
+}
+
+impl Upload {
+    fn new(artifact: ArtifactInfo, now: Instant) -> Self {
+        Self {
+            artifact,
+            offset: 0,
+            chunk_count: 0,
+            is_persisting_chunk: false,
+            last_activity: now,
+        }
+    }
+
+    fn status(&self) -> UploadStatus {
+        UploadStatus {
+            artifact: self.artifact,
+            offset: self.offset,
+        }
+    }
+
+    fn chunks(&self, l1_batch_number: L1BatchNumber) -> UploadedChunks {
+        UploadedChunks {
+            l1_batch_number,
+            artifact: self.artifact,
+            count: self.chunk_count,
+        }
+    }
+
+    fn is_expired(&self, now: Instant) -> bool {
+        !self.is_persisting_chunk
+            && now.saturating_duration_since(self.last_activity) >= UPLOAD_EXPIRATION
+    }
+}
+
+/// Outcome of persisting an uploaded chunk.
+#[derive(Debug)]
+enum ChunkOutcome {
+    /// The upload was restarted while the chunk was being persisted. The chunk should be removed unless
+    /// its key is reused by the restarted upload.
+    Orphaned {
+        is_key_reused: bool,
+    },
+    InProgress(UploadStatus),
+    Completed(Upload),
+}
+
+#[derive(Debug, Default)]
+struct TransfersState {
+    downloads: VecDeque<CachedDownload>,
+    uploads: HashMap<L1BatchNumber, Upload>,
+    completed_uploads: VecDeque<(L1BatchNumber, ArtifactInfo)>,
+}
+
+impl TransfersState {
+    fn cache_download(&mut self, download: CachedDownload) {
+        self.downloads
+            .retain(|cached| cached.l1_batch_number != download.l1_batch_number);
+        if self.downloads.len() >= MAX_CACHED_DOWNLOADS {
+            self.downloads.pop_front();
+        }
+        self.downloads.push_back(download);
+    }
+
+    fn complete_upload(&mut self, l1_batch_number: L1BatchNumber, artifact: ArtifactInfo) {
+        self.downloads
+            .retain(|download| download.l1_batch_number != l1_batch_number);
+        if self.completed_uploads.len() >= MAX_COMPLETED_UPLOADS {
+            self.completed_uploads.pop_front();
+        }
+        self.completed_uploads
+            .push_back((l1_batch_number, artifact));
+    }
+
+    fn completed_upload_status(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        artifact: ArtifactInfo,
+    ) -> Option<UploadStatus> {
+        self.completed_uploads
+            .contains(&(l1_batch_number, artifact))
+            .then_some(UploadStatus {
+                artifact,
+                offset: artifact.size,
+            })
+    }
+
+    /// Removes expired uploads and returns their chunks.
+    fn remove_expired_uploads(&mut self, now: Instant) -> Vec<UploadedChunks> {
+        let mut expired_chunks = vec![];
+        self.uploads.retain(|&l1_batch_number, upload| {
+            let is_expired = upload.is_expired(now);
+            if is_expired {
+                tracing::info!(
+                    "Upload for L1 batch #{l1_batch_number} has expired: {:?}",
+                    upload.status()
+                );
+                expired_chunks.push(upload.chunks(l1_batch_number));
+            }
+            !is_expired
+        });
+        expired_chunks
+    }
+
+    /// Starts or resumes an upload. Returns the upload status together with chunks of the replaced upload (if any),
+    /// or `None` if there are too many concurrent uploads.
+    fn start_upload(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        artifact: ArtifactInfo,
+        now: Instant,
+    ) -> Option<(UploadStatus, Option<UploadedChunks>)> {
+        if let Some(upload) = self.uploads.get_mut(&l1_batch_number) {
+            upload.last_activity = now;
+            if upload.artifact == artifact {
+                return Some((upload.status(), None));
+            }
+            tracing::info!(
+                "Restarting upload for L1 batch #{l1_batch_number} with another artifact {artifact:?}"
+            );
+            let replaced_chunks = upload.chunks(l1_batch_number);
+            *upload = Upload::new(artifact, now);
+            return Some((upload.status(), Some(replaced_chunks)));
+        }
+
+        if self.uploads.len() >= MAX_CONCURRENT_UPLOADS {
+            return None;
+        }
+        let upload = Upload::new(artifact, now);
+        let status = upload.status();
+        self.uploads.insert(l1_batch_number, upload);
+        Some((status, None))
+    }
+}
+
+/// Processor for the v2 proof data handler API.
+#[derive(Clone)]
+pub(crate) struct ResumableRequestProcessor {
+    inner: RequestProcessor,
+    blob_store: Arc<dyn ObjectStore>,
+    state: Arc<Mutex<TransfersState>>,
+}
+
+impl ResumableRequestProcessor {
+    pub(crate) fn new(inner: RequestProcessor, blob_store: Arc<dyn ObjectStore>) -> Self {
+        Self {
+            inner,
+            blob_store,
+            state: Arc::default(),
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, TransfersState> {
+        self.state.lock().expect("transfers state is poisoned")
+    }
+
+    /// Generates proof generation data for the batch and caches it for download.
+    async fn prepare_download(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<(Bytes, ArtifactInfo), RequestProcessorError> {
+        let data = self
+            .inner
+            .proof_generation_data_for_existing_batch(l1_batch_number)
+            .await?;
+        let bytes = bincode::serialize::<ProofGenerationData>(&data).map_err(|err| {
+            RequestProcessorError::GeneralError(format!(
+                "failed serializing proof generation data: {err}"
+            ))
+        })?;
+        let artifact = ArtifactInfo {
+            size: bytes.len() as u64,
+            content_hash: H256(keccak256(&bytes)),
+        };
+        let bytes = Bytes::from(bytes);
+
+        self.state().cache_download(CachedDownload {
+            l1_batch_number,
+            content_hash: artifact.content_hash,
+            bytes: bytes.clone(),
+        });
+        tracing::info!(
+            "Prepared proof generation data for L1 batch #{l1_batch_number} for download: {artifact:?}"
+        );
+        Ok((bytes, artifact))
+    }
+
+    /// Locks a batch for proving and prepares its proof generation data for download.
+    pub(crate) async fn lock_proof_generation_data(
+        &self,
+    ) -> Result<Json<Option<ProofGenerationDataInfo>>, RequestProcessorError> {
+        let Some(l1_batch_number) = self.inner.lock_batch_for_proving().await? else {
+            return Ok(Json(None)); // no batches pending to be proven
+        };
+
+        let artifact = match self.prepare_download(l1_batch_number).await {
+            Ok((_, artifact)) => artifact,
+            Err(err) => {
+                self.inner.unlock_batch(l1_batch_number).await?;
+                return Err(err);
+            }
+        };
+        Ok(Json(Some(ProofGenerationDataInfo {
+            l1_batch_number,
+            artifact,
+        })))
+    }
+
+    /// Serves proof generation data for a batch locked for proving, taking into account the `Range` header.
+    /// If the data was evicted from the cache, it is regenerated; generation is deterministic, so the regenerated
+    /// data has the same hash.
+    pub(crate) async fn download_proof_generation_data(
+        &self,
+        Path(l1_batch_number): Path<u32>,
+        headers: &HeaderMap,
+    ) -> Result<Response, RequestProcessorError> {
+        let l1_batch_number = L1BatchNumber(l1_batch_number);
+        let cached = self
+            .state()
+            .downloads
+            .iter()
+            .find(|download| download.l1_batch_number == l1_batch_number)
+            .map(|download| (download.bytes.clone(), download.content_hash));
+        let (bytes, content_hash) = if let Some(cached) = cached {
+            cached
+        } else {
+            if !self
+                .inner
+                .is_batch_locked_for_proving(l1_batch_number)
+                .await?
+            {
+                return Err(RequestProcessorError::NotFound(format!(
+                    "L1 batch #{l1_batch_number} is not locked for proving"
+                )));
+            }
+            let (bytes, artifact) = self.prepare_download(l1_batch_number).await?;
+            (bytes, artifact.content_hash)
+        };
+
+        let size = bytes.len() as u64;
+        let range = headers
+            .get(header::RANGE)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| parse_range(value, size))
+            .transpose();
+        let range = match range {
+            Ok(range) => range.flatten(),
+            Err(()) => {
+                let content_range = HeaderValue::from_str(&format!("bytes */{size}")).unwrap();
+                return Ok((
+                    StatusCode::RANGE_NOT_SATISFIABLE,
+                    [(header::CONTENT_RANGE, content_range)],
+                )
+                    .into_response());
+            }
+        };
+
+        let content_hash = HeaderValue::from_str(&format!("{content_hash:?}")).unwrap();
+        let common_headers = [
+            (header::ACCEPT_RANGES, HeaderValue::from_static("bytes")),
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/octet-stream"),
+            ),
+            (
+                header::HeaderName::from_static(CONTENT_HASH_HEADER),
+                content_hash,
+            ),
+        ];
+        Ok(match range {
+            Some(range) => {
+                let content_range = format!("bytes {}-{}/{size}", range.start, range.end - 1);
+                let content_range = HeaderValue::from_str(&content_range).unwrap();
+                let chunk = bytes.slice(range.start as usize..range.end as usize);
+                (
+                    StatusCode::PARTIAL_CONTENT,
+                    common_headers,
+                    [(header::CONTENT_RANGE, content_range)],
+                    chunk,
+                )
+                    .into_response()
+            }
+            None => (StatusCode::OK, common_headers, bytes).into_response(),
+        })
+    }
+
+    /// Starts an upload of a bincode-serialized [`SubmitProofRequest`] for a batch locked for proving. If an upload
+    /// of the same artifact is in progress or was recently completed, it is resumed (i.e., the returned offset
+    /// may be non-zero).
+    pub(crate) async fn start_upload(
+        &self,
+        Path(l1_batch_number): Path<u32>,
+        Json(artifact): Json<ArtifactInfo>,
+    ) -> Result<Json<UploadStatus>, RequestProcessorError> {
+        let l1_batch_number = L1BatchNumber(l1_batch_number);
+        if artifact.size == 0 || artifact.size > MAX_UPLOAD_SIZE {
+            return Err(RequestProcessorError::InvalidRequest(format!(
+                "uploaded artifact size must be in 1..={MAX_UPLOAD_SIZE} bytes"
+            )));
+        }
+        let completed_status = self
+            .state()
+            .completed_upload_status(l1_batch_number, artifact);
+        if let Some(status) = completed_status {
+            return Ok(Json(status));
+        }
+        if !self
+            .inner
+            .is_batch_locked_for_proving(l1_batch_number)
+            .await?
+        {
+            return Err(RequestProcessorError::InvalidRequest(format!(
+                "L1 batch #{l1_batch_number} is not locked for proving"
+            )));
+        }
+
+        let (started_upload, expired_chunks) = {
+            let mut state = self.state();
+            let now = Instant::now();
+            let expired_chunks = state.remove_expired_uploads(now);
+            let started_upload = state.start_upload(l1_batch_number, artifact, now);
+            (started_upload, expired_chunks)
+        };
+        for chunks in expired_chunks {
+            self.remove_chunks(chunks).await;
+        }
+
+        let Some((status, replaced_chunks)) = started_upload else {
+            return Err(RequestProcessorError::TooManyRequests(format!(
+                "too many concurrent uploads (max {MAX_CONCURRENT_UPLOADS}); retry later"
+            )));
+        };
+        if let Some(chunks) = replaced_chunks {
+            self.remove_chunks(chunks).await;
+        }
+        Ok(Json(status))
+    }
+
+    pub(crate) fn upload_status(
+        &self,
+        Path(l1_batch_number): Path<u32>,
+    ) -> Result<Json<UploadStatus>, RequestProcessorError> {
+        let l1_batch_number = L1BatchNumber(l1_batch_number);
+        let state = self.state();
+        let upload = state.uploads.get(&l1_batch_number).ok_or_else(|| {
+            RequestProcessorError::NotFound(format!(
+                "no upload in progress for L1 batch #{l1_batch_number}"
+            ))
+        })?;
+        Ok(Json(upload.status()))
+    }
+
+    /// Persists an uploaded chunk in the object store. If the chunk offset doesn't match the number of received bytes,
+    /// or another chunk is being persisted, responds with 409 Conflict and the current upload status.
+    /// Once all bytes are received, the proof is submitted.
+    pub(crate) async fn upload_chunk(
+        &self,
+        Path(l1_batch_number): Path<u32>,
+        headers: &HeaderMap,
+        chunk: Bytes,
+    ) -> Result<Response, RequestProcessorError> {
+        let l1_batch_number = L1BatchNumber(l1_batch_number);
+        let offset = headers
+            .get(UPLOAD_OFFSET_HEADER)
+            .and_then(|value| value.to_str().ok()?.parse::<u64>().ok())
+            .ok_or_else(|| {
+                RequestProcessorError::InvalidRequest(format!(
+                    "missing or invalid `{UPLOAD_OFFSET_HEADER}` header"
+                ))
+            })?;
+        if chunk.is_empty() {
+            return Err(RequestProcessorError::InvalidRequest(
+                "uploaded chunk is empty".to_owned(),
+            ));
+        }
+
+        let chunks = {
+            let mut state = self.state();
+            let upload = state.uploads.get_mut(&l1_batch_number).ok_or_else(|| {
+                RequestProcessorError::NotFound(format!(
+                    "no upload in progress for L1 batch #{l1_batch_number}"
+                ))
+            })?;
+            let status = upload.status();
+            if upload.is_persisting_chunk || offset != status.offset {
+                return Ok((StatusCode::CONFLICT, Json(status)).into_response());
+            }
+            if status.offset + chunk.len() as u64 > status.artifact.size {
+                return Err(RequestProcessorError::InvalidRequest(format!(
+                    "chunk exceeds the declared artifact size {}",
+                    status.artifact.size
+                )));
+            }
+            upload.is_persisting_chunk = true;
+            upload.last_activity = Instant::now();
+            upload.chunks(l1_batch_number)
+        };
+
+        let chunk_len = chunk.len() as u64;
+        let key = chunks.key(chunks.count);
+        let put_result = self
+            .blob_store
+            .put_raw(Bucket::ProofUploads, &key, chunk.to_vec())
+            .await;
+
+        let outcome = {
+            let mut state = self.state();
+            let upload = state
+                .uploads
+                .get_mut(&l1_batch_number)
+                .filter(|upload| upload.artifact == chunks.artifact && upload.is_persisting_chunk);
+            match upload {
+                None => {
+                    let is_key_reused = state
+                        .uploads
+                        .get(&l1_batch_number)
+                        .is_some_and(|upload| upload.artifact == chunks.artifact);
+                    ChunkOutcome::Orphaned { is_key_reused }
+                }
+                Some(upload) => {
+                    upload.is_persisting_chunk = false;
+                    upload.last_activity = Instant::now();
+                    if put_result.is_ok() {
+                        upload.offset += chunk_len;
+                        upload.chunk_count += 1;
+                    }
+                    if upload.offset < upload.artifact.size {
+                        ChunkOutcome::InProgress(upload.status())
+                    } else {
+                        ChunkOutcome::Completed(state.uploads.remove(&l1_batch_number).unwrap())
+                    }
+                }
+            }
+        };
+
+        let upload = match outcome {
+            ChunkOutcome::Orphaned { is_key_reused } => {
+                if put_result.is_ok() && !is_key_reused {
+                    self.remove_chunk(&key).await;
+                }
+                return Err(RequestProcessorError::NotFound(format!(
+                    "upload for L1 batch #{l1_batch_number} was restarted"
+                )));
+            }
+            ChunkOutcome::InProgress(status) => {
+                put_result.map_err(RequestProcessorError::ObjectStore)?;
+                return Ok(Json(status).into_response());
+            }
+            ChunkOutcome::Completed(upload) => upload,
+        };
+
+        let chunks = upload.chunks(l1_batch_number);
+        let submit_result = self.submit_uploaded_proof(&chunks).await;
+        self.remove_chunks(chunks).await;
+        submit_result?;
+
+        self.state()
+            .complete_upload(l1_batch_number, upload.artifact);
+        Ok(Json(upload.status()).into_response())
+    }
+
+    async fn submit_uploaded_proof(
+        &self,
+        chunks: &UploadedChunks,
+    ) -> Result<(), RequestProcessorError> {
+        let mut bytes = Vec::with_capacity(chunks.artifact.size as usize);
+        for key in chunks.keys() {
+            let chunk = self
+                .blob_store
+                .get_raw(Bucket::ProofUploads, &key)
+                .await
+                .map_err(RequestProcessorError::ObjectStore)?;
+            bytes.extend_from_slice(&chunk);
+        }
+
+        let content_hash = H256(keccak256(&bytes));
+        if content_hash != chunks.artifact.content_hash {
+            return Err(RequestProcessorError::InvalidRequest(format!(
+                "hash of the uploaded artifact {content_hash:?} differs from the declared hash {:?}; \
+                 the upload should be restarted",
+                chunks.artifact.content_hash
+            )));
+        }
+        let request: SubmitProofRequest = bincode::deserialize(&bytes).map_err(|err| {
+            RequestProcessorError::InvalidRequest(format!(
+                "failed deserializing uploaded proof: {err}"
+            ))
+        })?;
+        drop(bytes);
+
+        self.inner
+            .submit_proof(Path(chunks.l1_batch_number.0), Json(request))
+            .await?;
+        Ok(())
+    }
+
+    async fn remove_chunks(&self, chunks: UploadedChunks) {
+        for key in chunks.keys() {
+            self.remove_chunk(&key).await;
+        }
+    }
+
+    async fn remove_chunk(&self, key: &str) {
+        if let Err(err) = self.blob_store.remove_raw(Bucket::ProofUploads, key).await {
+            tracing::warn!("Failed removing uploaded proof chunk `{key}`: {err}");
+        }
+    }
+}
+
+/// Parses a single-range `Range` header value. Returns `Ok(None)` for unsupported range formats (e.g., multiple ranges),
+/// in which case the entire artifact should be returned, and `Err(())` if the range is not satisfiable.
+fn parse_range(value: &str, size: u64) -> Result<Option<ops::Range<u64>>, ()> {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return Ok(None);
+    };
+    if spec.contains(',') {
+        return Ok(None);
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return Ok(None);
+    };
+    let (start, end) = (start.trim(), end.trim());
+
+    let range = if start.is_empty() {
+        // Suffix range: the last `end` bytes.
+        let suffix_len: u64 = end.parse().map_err(drop)?;
+        if suffix_len == 0 {
+            return Err(());
+        }
+        size.saturating_sub(suffix_len)..size
+    } else {
+        let start: u64 = start.parse().map_err(drop)?;
+        let end = if end.is_empty() {
+            size
+        } else {
+            let inclusive_end: u64 = end.parse().map_err(drop)?;
+            if inclusive_end < start {
+                return Err(());
+            }
+            inclusive_end.saturating_add(1).min(size)
+        };
+        start..end
+    };
+
+    if range.start >= size {
+        return Err(());
+    }
+    Ok(Some(range))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_artifact(byte: u8) -> ArtifactInfo {
+        ArtifactInfo {
+            size: 100,
+            content_hash: H256::repeat_byte(byte),
+        }
+    }
+
+    #[test]
+    fn parsing_ranges() {
+        assert_eq!(parse_range("bytes=0-99", 1_000), Ok(Some(0..100)));
+        assert_eq!(parse_range("bytes=500-", 1_000), Ok(Some(500..1_000)));
+        assert_eq!(parse_range("bytes=900-2000", 1_000), Ok(Some(900..1_000)));
+        assert_eq!(parse_range("bytes=-100", 1_000), Ok(Some(900..1_000)));
+        assert_eq!(parse_range("bytes=-2000", 1_000), Ok(Some(0..1_000)));
+
+        assert_eq!(parse_range("bytes=0-1,5-10", 1_000), Ok(None));
+        assert_eq!(parse_range("items=0-10", 1_000), Ok(None));
+
+        assert_eq!(parse_range("bytes=1000-", 1_000), Err(()));
+        assert_eq!(parse_range("bytes=10-5", 1_000), Err(()));
+        assert_eq!(parse_range("bytes=-0", 1_000), Err(()));
+        assert_eq!(parse_range("bytes=a-b", 1_000), Err(()));
+    }
+
+    #[test]
+    fn capping_concurrent_uploads() {
+        let mut state = TransfersState::default();
+        let now = Instant::now();
+        for i in 0..MAX_CONCURRENT_UPLOADS as u32 {
+            let (status, replaced_chunks) = state
+                .start_upload(L1BatchNumber(i), mock_artifact(1), now)
+                .unwrap();
+            assert_eq!(status.offset, 0);
+            assert!(replaced_chunks.is_none());
+        }
+        let new_batch = L1BatchNumber(MAX_CONCURRENT_UPLOADS as u32);
+        assert!(state
+            .start_upload(new_batch, mock_artifact(1), now)
+            .is_none());
+
+        // Existing uploads can still be resumed or restarted.
+        let (status, replaced_chunks) = state
+            .start_upload(L1BatchNumber(0), mock_artifact(1), now)
+            .unwrap();
+        assert_eq!(status.artifact, mock_artifact(1));
+        assert!(replaced_chunks.is_none());
+        let (status, replaced_chunks) = state
+            .start_upload(L1BatchNumber(0), mock_artifact(2), now)
+            .unwrap();
+        assert_eq!(status.artifact, mock_artifact(2));
+        assert_eq!(replaced_chunks.unwrap().artifact, mock_artifact(1));
+    }
+
+    #[test]
+    fn expiring_uploads() {
+        let mut state = TransfersState::default();
+        let now = Instant::now();
+        state
+            .start_upload(L1BatchNumber(1), mock_artifact(1), now)
+            .unwrap();
+        state
+            .start_upload(L1BatchNumber(2), mock_artifact(2), now)
+            .unwrap();
+        let upload = state.uploads.get_mut(&L1BatchNumber(2)).unwrap();
+        upload.offset = 10;
+        upload.chunk_count = 1;
+
+        let later = now + UPLOAD_EXPIRATION / 2;
+        state
+            .start_upload(L1BatchNumber(1), mock_artifact(1), later)
+            .unwrap();
+        assert!(state.remove_expired_uploads(later).is_empty());
+
+        let expired_chunks = state.remove_expired_uploads(now + UPLOAD_EXPIRATION);
+        assert_eq!(expired_chunks.len(), 1);
+        assert_eq!(expired_chunks[0].l1_batch_number, L1BatchNumber(2));
+        let expired_keys: Vec<_> = expired_chunks[0].keys().collect();
+        assert_eq!(expired_keys, [expired_chunks[0].key(0)]);
+        assert_eq!(
+            state.uploads.keys().copied().collect::<Vec<_>>(),
+            [L1BatchNumber(1)]
+        );
+
+        // Uploads with a chunk being persisted never expire.
+        let upload = state.uploads.get_mut(&L1BatchNumber(1)).unwrap();
+        upload.is_persisting_chunk = true;
+        assert!(state
+            .remove_expired_uploads(later + UPLOAD_EXPIRATION * 2)
+            .is_empty());
+    }
+}
@@ -0,0 +1,426 @@
+//! Checking TEE attestation quotes against the attestation policy.
+//!
+//! The policy requires that:
+//!
+//! - The attestation is an Intel SGX or TDX quote (v3 or v4).
+//! - The quote signature and its certificate chain are valid w.r.t. Intel DCAP collateral, and the collateral
+//!   hasn't expired.
+//! - The TCB status of the attested platform is acceptable (see [`TcbStatus::is_acceptable()`]), and the TCB level
+//!   of the platform is not older than the configured maximum age.
+//! - The enclave measurement in the quote (`MRENCLAVE` for SGX, `MRTD` for TDX) is in the allowed list
+//!   stored in Postgres. The list can be updated via the admin API without restarting the server.
+//! - The report data in the quote commits to the public key being registered, i.e., starts with the key
+//!   (for uncompressed secp256k1 keys, the `0x04` prefix is omitted).
+
+use std::{fmt, sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use chrono::{DateTime, Utc};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+
+use crate::{
+    errors::RequestProcessorError,
+    metrics::{AttestationRejectionReason, METRICS},
+};
+
+const QUOTE_HEADER_LEN: usize = 48;
+const SGX_TEE_TYPE: u32 = 0;
+const TDX_TEE_TYPE: u32 = 0x81;
+
+/// Offsets and lengths of fields in the SGX enclave report body.
+const SGX_REPORT_BODY_LEN: usize = 384;
+const SGX_MRENCLAVE_OFFSET: usize = 64;
+const SGX_MRENCLAVE_LEN: usize = 32;
+const SGX_REPORT_DATA_OFFSET: usize = 320;
+/// Offsets and lengths of fields in the TDX 1.0 TD report body.
+const TDX_REPORT_BODY_LEN: usize = 584;
+const TDX_MRTD_OFFSET: usize = 136;
+const TDX_MRTD_LEN: usize = 48;
+const TDX_REPORT_DATA_OFFSET: usize = 520;
+
+const REPORT_DATA_LEN: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum QuoteKind {
+    Sgx,
+    Tdx,
+}
+
+/// Fields of an attestation quote relevant for the attestation policy.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ParsedQuote<'a> {
+    pub kind: QuoteKind,
+    pub measurement: &'a [u8],
+    pub report_data: &'a [u8],
+}
+
+impl<'a> ParsedQuote<'a> {
+    pub(crate) fn parse(quote: &'a [u8]) -> Option<Self> {
+        let header = quote.get(..QUOTE_HEADER_LEN)?;
+        let version = u16::from_le_bytes([header[0], header[1]]);
+        let tee_type = u32::from_le_bytes(header[4..8].try_into().unwrap());
+        let kind = match (version, tee_type) {
+            (3 | 4, SGX_TEE_TYPE) => QuoteKind::Sgx,
+            (4, TDX_TEE_TYPE) => QuoteKind::Tdx,
+            _ => return None,
+        };
+
+        let (body_len, measurement_offset, measurement_len, report_data_offset) = match kind {
+            QuoteKind::Sgx => (
+                SGX_REPORT_BODY_LEN,
+                SGX_MRENCLAVE_OFFSET,
+                SGX_MRENCLAVE_LEN,
+                SGX_REPORT_DATA_OFFSET,
+            ),
+            QuoteKind::Tdx => (
+                TDX_REPORT_BODY_LEN,
+                TDX_MRTD_OFFSET,
+                TDX_MRTD_LEN,
+                TDX_REPORT_DATA_OFFSET,
+            ),
+        };
+        let body = quote.get(QUOTE_HEADER_LEN..QUOTE_HEADER_LEN + body_len)?;
+        Some(Self {
+            kind,
+            measurement: &body[measurement_offset..measurement_offset + measurement_len],
+            report_data: &body[report_data_offset..report_data_offset + REPORT_DATA_LEN],
+        })
+    }
+
+    fn commits_to_pubkey(&self, pubkey: &[u8]) -> bool {
+        let pubkey = match pubkey {
+            [0x04, rest @ ..] if pubkey.len() == REPORT_DATA_LEN + 1 => rest,
+            _ => pubkey,
+        };
+        !pubkey.is_empty() && self.report_data.starts_with(pubkey)
+    }
+}
+
+/// TCB status of an attested platform as reported by Intel DCAP quote verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(feature = "dcap"), allow(dead_code))] // only constructed by the DCAP verifier
+pub(crate) enum TcbStatus {
+    UpToDate,
+    SwHardeningNeeded,
+    ConfigNeeded,
+    ConfigAndSwHardeningNeeded,
+    OutOfDate,
+    OutOfDateConfigNeeded,
+    Invalid,
+}
+
+impl TcbStatus {
+    /// Statuses for which the platform firmware / microcode is up to date are accepted; the remaining advisories
+    /// concern the enclave software or platform configuration, and are mitigated by the measurement allowlist.
+    /// Platforms with outdated TCB or revoked / invalid quotes are rejected.
+    pub(crate) fn is_acceptable(self) -> bool {
+        matches!(
+            self,
+            Self::UpToDate
+                | Self::SwHardeningNeeded
+                | Self::ConfigNeeded
+                | Self::ConfigAndSwHardeningNeeded
+        )
+    }
+}
+
+/// Outcome of a successful cryptographic verification of a quote.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(not(feature = "dcap"), allow(dead_code))] // only constructed by the DCAP verifier
+pub(crate) struct QuoteVerification {
+    pub tcb_status: TcbStatus,
+    /// Date of the TCB level matched by the attested platform.
+    pub tcb_date: DateTime<Utc>,
+    /// Whether any of the collateral used for verification has expired.
+    pub collateral_expired: bool,
+}
+
+/// Verifier of quote signatures and certificate chains. Verification may perform blocking I/O
+/// (e.g., fetching collateral from Intel PCS), so it's run on a blocking thread.
+pub(crate) trait QuoteVerifier: fmt::Debug + Send + Sync + 'static {
+    fn verify(&self, quote: &[u8], now: DateTime<Utc>) -> anyhow::Result<QuoteVerification>;
+}
+
+/// Quote verifier using Intel DCAP quote verification library via `teepot`.
+#[cfg(feature = "dcap")]
+#[derive(Debug)]
+pub(crate) struct DcapQuoteVerifier;
+
+#[cfg(feature = "dcap")]
+impl QuoteVerifier for DcapQuoteVerifier {
+    fn verify(&self, quote: &[u8], now: DateTime<Utc>) -> anyhow::Result<QuoteVerification> {
+        use teepot::quote::{
+            tcblevel::TcbLevel, tee_qv_get_collateral, verify_quote_with_collateral,
+        };
+
+        let collateral = tee_qv_get_collateral(quote)
+            .map_err(|err| anyhow::anyhow!("failed getting quote collateral: {err}"))?;
+        let result = verify_quote_with_collateral(quote, Some(&collateral), now.timestamp())
+            .map_err(|err| anyhow::anyhow!("failed verifying quote: {err}"))?;
+        let tcb_status = match TcbLevel::from(result.result) {
+            TcbLevel::Ok => TcbStatus::UpToDate,
+            TcbLevel::SwHardeningNeeded => TcbStatus::SwHardeningNeeded,
+            TcbLevel::ConfigNeeded => TcbStatus::ConfigNeeded,
+            TcbLevel::ConfigAndSwHardeningNeeded => TcbStatus::ConfigAndSwHardeningNeeded,
+            TcbLevel::OutOfDate => TcbStatus::OutOfDate,
+            TcbLevel::OutOfDateConfigNeeded => TcbStatus::OutOfDateConfigNeeded,
+            _ => TcbStatus::Invalid,
+        };
+        let tcb_date = DateTime::from_timestamp(result.tcb_level_date_tag, 0)
+            .context("TCB level date is out of range")?;
+        Ok(QuoteVerification {
+            tcb_status,
+            tcb_date,
+            collateral_expired: result.collateral_expired,
+        })
+    }
+}
+
+/// Returns the quote verifier used in production, or an error if the server is built without DCAP support.
+pub(crate) fn default_quote_verifier() -> anyhow::Result<Arc<dyn QuoteVerifier>> {
+    #[cfg(feature = "dcap")]
+    {
+        Ok(Arc::new(DcapQuoteVerifier))
+    }
+    #[cfg(not(feature = "dcap"))]
+    {
+        anyhow::bail!(
+            "TEE attestation verification requires the proof data handler to be built with the `dcap` feature"
+        )
+    }
+}
+
+/// Checks TEE attestations against the attestation policy (see the module docs).
+#[derive(Debug, Clone)]
+pub(crate) struct AttestationPolicy {
+    pool: ConnectionPool<Core>,
+    verifier: Arc<dyn QuoteVerifier>,
+    max_tcb_age: Duration,
+}
+
+impl AttestationPolicy {
+    pub(crate) fn new(
+        pool: ConnectionPool<Core>,
+        verifier: Arc<dyn QuoteVerifier>,
+        max_tcb_age: Duration,
+    ) -> Self {
+        Self {
+            pool,
+            verifier,
+            max_tcb_age,
+        }
+    }
+
+    async fn verify_quote(&self, quote: &[u8]) -> Result<(), RequestProcessorError> {
+        let verifier = self.verifier.clone();
+        let quote = quote.to_vec();
+        let now = Utc::now();
+        let verification = tokio::task::spawn_blocking(move || verifier.verify(&quote, now))
+            .await
+            .map_err(|err| RequestProcessorError::GeneralError(err.to_string()))?;
+        let verification = verification.map_err(|err| {
+            reject(
+                AttestationRejectionReason::InvalidSignature,
+                format!("quote verification failed: {err:#}"),
+            )
+        })?;
+
+        if verification.collateral_expired {
+            return Err(reject(
+                AttestationRejectionReason::InvalidSignature,
+                "collateral used to verify the quote has expired".into(),
+            ));
+        }
+        if !verification.tcb_status.is_acceptable() {
+            return Err(reject(
+                AttestationRejectionReason::UnacceptableTcbStatus,
+                format!(
+                    "TCB status {:?} of the attested platform is not acceptable",
+                    verification.tcb_status
+                ),
+            ));
+        }
+        let tcb_age = (now - verification.tcb_date).to_std().unwrap_or_default();
+        if tcb_age > self.max_tcb_age {
+            return Err(reject(
+                AttestationRejectionReason::StaleTcb,
+                format!(
+                    "TCB level of the attested platform dated {} is older than the allowed {:?}",
+                    verification.tcb_date, self.max_tcb_age
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn check(
+        &self,
+        attestation: &[u8],
+        pubkey: &[u8],
+    ) -> Result<(), RequestProcessorError> {
+        let Some(quote) = ParsedQuote::parse(attestation) else {
+            return Err(reject(
+                AttestationRejectionReason::MalformedQuote,
+                "attestation is not a supported SGX / TDX quote".into(),
+            ));
+        };
+        self.verify_quote(attestation).await?;
+
+        let allowed_measurements = self
+            .pool
+            .connection_tagged("tee_request_processor")
+            .await?
+            .tee_proof_generation_dal()
+            .get_allowed_measurements()
+            .await?;
+        if !allowed_measurements
+            .iter()
+            .any(|allowed| allowed == quote.measurement)
+        {
+            return Err(reject(
+                AttestationRejectionReason::DisallowedMeasurement,
+                format!(
+                    "enclave measurement 0x{} in {:?} quote is not allowed",
+                    hex::encode(quote.measurement),
+                    quote.kind
+                ),
+            ));
+        }
+
+        if !quote.commits_to_pubkey(pubkey) {
+            return Err(reject(
+                AttestationRejectionReason::PubkeyMismatch,
+                "quote report data does not commit to the registered public key".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn reject(reason: AttestationRejectionReason, message: String) -> RequestProcessorError {
+    tracing::warn!("Rejected TEE attestation ({reason:?}): {message}");
+    METRICS.tee_attestation_rejections[&reason].inc();
+    RequestProcessorError::InvalidRequest(message)
+}
+
+#[cfg(test)]
+pub(crate) fn mock_sgx_quote(measurement: &[u8; SGX_MRENCLAVE_LEN], report_data: &[u8]) -> Vec<u8> {
+    let mut quote = vec![0_u8; QUOTE_HEADER_LEN + SGX_REPORT_BODY_LEN + 64];
+    quote[0] = 3; // version
+    let body = &mut quote[QUOTE_HEADER_LEN..];
+    body[SGX_MRENCLAVE_OFFSET..SGX_MRENCLAVE_OFFSET + SGX_MRENCLAVE_LEN]
+        .copy_from_slice(measurement);
+    body[SGX_REPORT_DATA_OFFSET..SGX_REPORT_DATA_OFFSET + report_data.len()]
+        .copy_from_slice(report_data);
+    quote
+}
+
+/// Quote verifier returning a fixed verification outcome.
+#[cfg(test)]
+#[derive(Debug)]
+pub(crate) struct MockQuoteVerifier(pub Result<QuoteVerification, String>);
+
+#[cfg(test)]
+impl MockQuoteVerifier {
+    pub(crate) fn valid() -> Self {
+        Self(Ok(QuoteVerification {
+            tcb_status: TcbStatus::UpToDate,
+            tcb_date: Utc::now() - chrono::Duration::days(30),
+            collateral_expired: false,
+        }))
+    }
+}
+
+#[cfg(test)]
+impl QuoteVerifier for MockQuoteVerifier {
+    fn verify(&self, _quote: &[u8], _now: DateTime<Utc>) -> anyhow::Result<QuoteVerification> {
+        self.0.clone().map_err(anyhow::Error::msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_sgx_quote() {
+        let quote = mock_sgx_quote(&[1; 32], &[2; 20]);
+        let parsed = ParsedQuote::parse(&quote).unwrap();
+        assert_eq!(parsed.kind, QuoteKind::Sgx);
+        assert_eq!(parsed.measurement, [1; 32]);
+        assert!(parsed.commits_to_pubkey(&[2; 20]));
+        assert!(!parsed.commits_to_pubkey(&[3; 20]));
+        assert!(!parsed.commits_to_pubkey(&[]));
+
+        assert_eq!(ParsedQuote::parse(&quote[..100]), None);
+        let mut unsupported_quote = quote;
+        unsupported_quote[0] = 5;
+        assert_eq!(ParsedQuote::parse(&unsupported_quote), None);
+    }
+
+    #[test]
+    fn parsing_tdx_quote() {
+        let mut quote = vec![0_u8; QUOTE_HEADER_LEN + TDX_REPORT_BODY_LEN];
+        quote[0] = 4;
+        quote[4] = TDX_TEE_TYPE as u8;
+        let body = &mut quote[QUOTE_HEADER_LEN..];
+        body[TDX_MRTD_OFFSET..TDX_MRTD_OFFSET + TDX_MRTD_LEN].copy_from_slice(&[1; 48]);
+        body[TDX_REPORT_DATA_OFFSET..].copy_from_slice(&[2; 64]);
+
+        let parsed = ParsedQuote::parse(&quote).unwrap();
+        assert_eq!(parsed.kind, QuoteKind::Tdx);
+        assert_eq!(parsed.measurement, [1; 48]);
+        let mut uncompressed_key = vec![0x04];
+        uncompressed_key.extend_from_slice(&[2; 64]);
+        assert!(parsed.commits_to_pubkey(&uncompressed_key));
+    }
+
+    async fn check_with_verifier(verifier: MockQuoteVerifier) -> Result<(), RequestProcessorError> {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        pool.connection()
+            .await
+            .unwrap()
+            .tee_proof_generation_dal()
+            .set_allowed_measurements(&[vec![1; 32]])
+            .await
+            .unwrap();
+        let policy =
+            AttestationPolicy::new(pool, Arc::new(verifier), Duration::from_secs(86_400 * 365));
+        let pubkey = [5; 33];
+        policy
+            .check(&mock_sgx_quote(&[1; 32], &pubkey), &pubkey)
+            .await
+    }
+
+    #[tokio::test]
+    async fn checking_quote_verification_outcome() {
+        check_with_verifier(MockQuoteVerifier::valid())
+            .await
+            .unwrap_or_else(|_| panic!("valid quote rejected"));
+
+        let invalid = MockQuoteVerifier(Err("bogus signature".into()));
+        let err = check_with_verifier(invalid).await.unwrap_err();
+        assert_matches!(err, RequestProcessorError::InvalidRequest(msg) if msg.contains("bogus signature"));
+
+        let mut verification = MockQuoteVerifier::valid().0.unwrap();
+        verification.collateral_expired = true;
+        let err = check_with_verifier(MockQuoteVerifier(Ok(verification)))
+            .await
+            .unwrap_err();
+        assert_matches!(err, RequestProcessorError::InvalidRequest(msg) if msg.contains("expired"));
+
+        let mut verification = MockQuoteVerifier::valid().0.unwrap();
+        verification.tcb_status = TcbStatus::OutOfDate;
+        let err = check_with_verifier(MockQuoteVerifier(Ok(verification)))
+            .await
+            .unwrap_err();
+        assert_matches!(err, RequestProcessorError::InvalidRequest(msg) if msg.contains("OutOfDate"));
+
+        let mut verification = MockQuoteVerifier::valid().0.unwrap();
+        verification.tcb_date = Utc::now() - chrono::Duration::days(400);
+        let err = check_with_verifier(MockQuoteVerifier(Ok(verification)))
+            .await
+            .unwrap_err();
+        assert_matches!(err, RequestProcessorError::InvalidRequest(msg) if msg.contains("older than"));
+    }
+}
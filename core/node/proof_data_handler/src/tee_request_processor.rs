@@ -20,7 +20,7 @@ use zksync_prover_interface::{
 use zksync_types::{tee_types::TeeType, L1BatchNumber, L2ChainId};
 use zksync_vm_executor::storage::L1BatchParamsProvider;
 
-use crate::{errors::RequestProcessorError, metrics::METRICS};
+use crate::{errors::RequestProcessorError, metrics::METRICS, tee_attestation::AttestationPolicy};
 
 #[derive(Clone)]
 pub(crate) struct TeeRequestProcessor {
@@ -28,6 +28,7 @@ pub(crate) struct TeeRequestProcessor {
     pool: ConnectionPool<Core>,
     config: ProofDataHandlerConfig,
     l2_chain_id: L2ChainId,
+    attestation_policy: Option<AttestationPolicy>,
 }
 
 impl TeeRequestProcessor {
@@ -36,12 +37,14 @@ impl TeeRequestProcessor {
         pool: ConnectionPool<Core>,
         config: ProofDataHandlerConfig,
         l2_chain_id: L2ChainId,
+        attestation_policy: Option<AttestationPolicy>,
     ) -> Self {
         Self {
             blob_store,
             pool,
             config,
             l2_chain_id,
+            attestation_policy,
         }
     }
 
@@ -251,6 +254,10 @@ impl TeeRequestProcessor {
     ) -> Result<Json<RegisterTeeAttestationResponse>, RequestProcessorError> {
         tracing::info!("Received attestation: {:?}", payload);
 
+        if let Some(policy) = &self.attestation_policy {
+            policy.check(&payload.attestation, &payload.pubkey).await?;
+        }
+
         let mut connection = self.pool.connection_tagged("tee_request_processor").await?;
         let mut dal = connection.tee_proof_generation_dal();
 
@@ -1,3 +1,5 @@
+use std::{sync::Arc, time::Duration};
+
 use axum::{
     body::Body,
     http::{self, Method, Request, StatusCode},
@@ -7,14 +9,28 @@ use axum::{
 use serde_json::json;
 use tower::ServiceExt;
 use zksync_config::configs::{ProofDataHandlerConfig, TeeConfig};
-use zksync_dal::{ConnectionPool, CoreDal};
-use zksync_object_store::MockObjectStore;
-use zksync_prover_interface::api::SubmitTeeProofRequest;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_node_test_utils::{
+    create_l1_batch, create_l1_batch_metadata, l1_batch_metadata_to_commitment_artifacts,
+};
+use zksync_object_store::{bincode, Bucket, MockObjectStore, ObjectStore};
+use zksync_prover_interface::{
+    api::{
+        ArtifactInfo, ProofGenerationData, ProofGenerationDataInfo, RegisterTeeAttestationRequest,
+        SubmitProofRequest, SubmitTeeProofRequest, UploadStatus,
+    },
+    inputs::{VMRunWitnessInputData, WitnessInputMerklePaths},
+};
 use zksync_types::{
-    commitment::L1BatchCommitmentMode, tee_types::TeeType, L1BatchNumber, L2ChainId,
+    commitment::L1BatchCommitmentMode, protocol_version::ProtocolSemanticVersion,
+    tee_types::TeeType, web3::keccak256, L1BatchNumber, L2ChainId, ProtocolVersion,
+    ProtocolVersionId, H256,
 };
 
-use crate::create_proof_processing_router;
+use crate::{
+    create_proof_processing_router,
+    tee_attestation::{mock_sgx_quote, AttestationPolicy, MockQuoteVerifier},
+};
 
 #[tokio::test]
 async fn request_tee_proof_inputs() {
@@ -31,10 +47,13 @@ async fn request_tee_proof_inputs() {
                 first_tee_processed_batch: L1BatchNumber(0),
                 tee_proof_generation_timeout_in_secs: 600,
                 tee_batch_permanently_ignored_timeout_in_hours: 10 * 24,
+                tee_attestation_verification: false,
+                tee_max_tcb_age_in_days: 365,
             },
         },
         L1BatchCommitmentMode::Rollup,
         L2ChainId::default(),
+        None,
     );
     let test_cases = vec![
         (json!({ "tee_type": "sgx" }), StatusCode::NO_CONTENT),
@@ -91,10 +110,13 @@ async fn submit_tee_proof() {
                 first_tee_processed_batch: L1BatchNumber(0),
                 tee_proof_generation_timeout_in_secs: 600,
                 tee_batch_permanently_ignored_timeout_in_hours: 10 * 24,
+                tee_attestation_verification: false,
+                tee_max_tcb_age_in_days: 365,
             },
         },
         L1BatchCommitmentMode::Rollup,
         L2ChainId::default(),
+        None,
     );
 
     // this should fail because we haven't saved the attestation for the pubkey yet
@@ -197,3 +219,384 @@ async fn send_submit_tee_proof_request(
         .await
         .unwrap()
 }
+
+#[tokio::test]
+async fn registering_tee_attestation_with_policy() {
+    let db_conn_pool = ConnectionPool::test_pool().await;
+    let app = create_proof_processing_router(
+        MockObjectStore::arc(),
+        db_conn_pool.clone(),
+        ProofDataHandlerConfig {
+            http_port: 1337,
+            proof_generation_timeout_in_secs: 10,
+            tee_config: TeeConfig {
+                tee_support: true,
+                tee_attestation_verification: true,
+                ..TeeConfig::default()
+            },
+        },
+        L1BatchCommitmentMode::Rollup,
+        L2ChainId::default(),
+        Some(AttestationPolicy::new(
+            db_conn_pool.clone(),
+            Arc::new(MockQuoteVerifier::valid()),
+            Duration::from_secs(86_400 * 365),
+        )),
+    );
+    let pubkey = vec![5; 33];
+    let request = RegisterTeeAttestationRequest {
+        attestation: mock_sgx_quote(&[1; 32], &pubkey),
+        pubkey: pubkey.clone(),
+    };
+
+    // The measurement is not allowed yet.
+    let response = send_register_tee_attestation_request(&app, &request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let mut conn = db_conn_pool.connection().await.unwrap();
+    conn.tee_proof_generation_dal()
+        .set_allowed_measurements(&[vec![1; 32], vec![2; 48]])
+        .await
+        .unwrap();
+    let allowed_measurements = conn
+        .tee_proof_generation_dal()
+        .get_allowed_measurements()
+        .await
+        .unwrap();
+    assert_eq!(allowed_measurements, [vec![1; 32], vec![2; 48]]);
+
+    // The quote doesn't commit to the public key.
+    let malicious_request = RegisterTeeAttestationRequest {
+        attestation: request.attestation.clone(),
+        pubkey: vec![6; 33],
+    };
+    let response = send_register_tee_attestation_request(&app, &malicious_request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = send_register_tee_attestation_request(&app, &request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+async fn send_register_tee_attestation_request(
+    app: &Router,
+    request: &RegisterTeeAttestationRequest,
+) -> Response {
+    let req_body = Body::from(serde_json::to_vec(request).unwrap());
+    app.clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/tee/register_attestation")
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(req_body)
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+/// Seals the genesis L1 batch and L1 batch #1 with all data necessary to generate a proof for the latter.
+async fn prepare_batch_for_proving(pool: &ConnectionPool<Core>, blob_store: &dyn ObjectStore) {
+    let mut conn = pool.connection().await.unwrap();
+    conn.protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion {
+            version: ProtocolSemanticVersion {
+                minor: ProtocolVersionId::latest(),
+                patch: 0.into(),
+            },
+            ..ProtocolVersion::default()
+        })
+        .await
+        .unwrap();
+    for number in [0, 1] {
+        let l1_batch_number = L1BatchNumber(number);
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch(number))
+            .await
+            .unwrap();
+        let metadata = create_l1_batch_metadata(number);
+        conn.blocks_dal()
+            .save_l1_batch_tree_data(l1_batch_number, &metadata.tree_data())
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .save_l1_batch_commitment_artifacts(
+                l1_batch_number,
+                &l1_batch_metadata_to_commitment_artifacts(&metadata),
+            )
+            .await
+            .unwrap();
+    }
+
+    let l1_batch_number = L1BatchNumber(1);
+    let vm_run_data = VMRunWitnessInputData {
+        l1_batch_number,
+        used_bytecodes: Default::default(),
+        initial_heap_content: vec![(0, 1.into()); 1_000],
+        protocol_version: ProtocolVersionId::latest(),
+        bootloader_code: vec![[1; 32]; 100],
+        default_account_code_hash: Default::default(),
+        evm_emulator_code_hash: None,
+        storage_refunds: vec![],
+        pubdata_costs: vec![],
+        witness_block_state: Default::default(),
+    };
+    let vm_run_data_url = blob_store.put(l1_batch_number, &vm_run_data).await.unwrap();
+    let merkle_paths_url = blob_store
+        .put(l1_batch_number, &WitnessInputMerklePaths::new(1))
+        .await
+        .unwrap();
+    conn.proof_generation_dal()
+        .insert_proof_generation_details(l1_batch_number)
+        .await
+        .unwrap();
+    conn.proof_generation_dal()
+        .save_vm_runner_artifacts_metadata(l1_batch_number, &vm_run_data_url)
+        .await
+        .unwrap();
+    conn.proof_generation_dal()
+        .save_merkle_paths_artifacts_metadata(l1_batch_number, &merkle_paths_url)
+        .await
+        .unwrap();
+}
+
+fn create_resumable_router(
+    pool: &ConnectionPool<Core>,
+    blob_store: &Arc<dyn ObjectStore>,
+) -> Router {
+    create_proof_processing_router(
+        blob_store.clone(),
+        pool.clone(),
+        ProofDataHandlerConfig {
+            http_port: 1337,
+            proof_generation_timeout_in_secs: 10,
+            tee_config: TeeConfig::default(),
+        },
+        L1BatchCommitmentMode::Validium,
+        L2ChainId::default(),
+        None,
+    )
+}
+
+#[tokio::test]
+async fn resumable_proof_generation_data_download() {
+    let pool = ConnectionPool::test_pool().await;
+    let blob_store = MockObjectStore::arc();
+    prepare_batch_for_proving(&pool, &*blob_store).await;
+    let app = create_resumable_router(&pool, &blob_store);
+
+    // The batch is not locked for proving yet.
+    let uri = "/v2/proof_generation_data/1";
+    let response = send_download_request(&app, uri, None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/v2/proof_generation_data")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let info: Option<ProofGenerationDataInfo> =
+        serde_json::from_slice(&read_body(response).await).unwrap();
+    let info = info.unwrap();
+    assert_eq!(info.l1_batch_number, L1BatchNumber(1));
+    let size = info.artifact.size;
+
+    let response = send_download_request(&app, uri, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["x-content-hash"],
+        format!("{:?}", info.artifact.content_hash)
+    );
+    let data = read_body(response).await;
+    assert_eq!(data.len() as u64, size);
+    assert_eq!(H256(keccak256(&data)), info.artifact.content_hash);
+    let parsed_data: ProofGenerationData = bincode::deserialize(&data).unwrap();
+    assert_eq!(parsed_data.l1_batch_number, L1BatchNumber(1));
+
+    // Download the data in 2 parts, as if the download was interrupted.
+    let split = data.len() / 3;
+    let range = format!("bytes=0-{}", split - 1);
+    let response = send_download_request(&app, uri, Some(&range)).await;
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(
+        response.headers()[http::header::CONTENT_RANGE],
+        format!("bytes 0-{}/{size}", split - 1)
+    );
+    assert_eq!(read_body(response).await, data[..split]);
+
+    let range = format!("bytes={split}-");
+    let response = send_download_request(&app, uri, Some(&range)).await;
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(
+        response.headers()[http::header::CONTENT_RANGE],
+        format!("bytes {split}-{}/{size}", size - 1)
+    );
+    assert_eq!(read_body(response).await, data[split..]);
+
+    let range = format!("bytes={size}-");
+    let response = send_download_request(&app, uri, Some(&range)).await;
+    assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+    assert_eq!(
+        response.headers()[http::header::CONTENT_RANGE],
+        format!("bytes */{size}")
+    );
+
+    // Data missing from the cache (here, because of a server restart) should be regenerated.
+    let app = create_resumable_router(&pool, &blob_store);
+    let range = format!("bytes={split}-");
+    let response = send_download_request(&app, uri, Some(&range)).await;
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(
+        response.headers()["x-content-hash"],
+        format!("{:?}", info.artifact.content_hash)
+    );
+    assert_eq!(read_body(response).await, data[split..]);
+
+    let response = send_download_request(&app, "/v2/proof_generation_data/2", None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+async fn send_download_request(app: &Router, uri: &str, range: Option<&str>) -> Response {
+    let mut request = Request::builder().method(Method::GET).uri(uri);
+    if let Some(range) = range {
+        request = request.header(http::header::RANGE, range);
+    }
+    app.clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+async fn read_body(response: Response) -> Vec<u8> {
+    axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap()
+        .to_vec()
+}
+
+#[tokio::test]
+async fn resumable_proof_upload() {
+    let batch_number = L1BatchNumber(1);
+    let db_conn_pool = ConnectionPool::test_pool().await;
+    let blob_store = MockObjectStore::arc();
+    prepare_batch_for_proving(&db_conn_pool, &*blob_store).await;
+    let app = create_resumable_router(&db_conn_pool, &blob_store);
+
+    let uri = format!("/v2/proofs/{batch_number}");
+    let proof = bincode::serialize(&SubmitProofRequest::SkippedProofGeneration).unwrap();
+    let artifact = ArtifactInfo {
+        size: proof.len() as u64,
+        content_hash: H256(keccak256(&proof)),
+    };
+    let (first_chunk, second_chunk) = proof.split_at(proof.len() / 2);
+
+    let response = send_upload_chunk_request(&app, &uri, 0, first_chunk).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // Uploads are only accepted for batches locked for proving.
+    let response = send_start_upload_request(&app, &uri, &artifact).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let mut conn = db_conn_pool.connection().await.unwrap();
+    let locked_batch = conn
+        .proof_generation_dal()
+        .lock_batch_for_proving(Duration::MAX)
+        .await
+        .unwrap();
+    assert_eq!(locked_batch, Some(batch_number));
+
+    let response = send_start_upload_request(&app, &uri, &artifact).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_upload_status(response).await.offset, 0);
+
+    let response = send_upload_chunk_request(&app, &uri, 0, first_chunk).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let status = read_upload_status(response).await;
+    assert_eq!(status.offset, first_chunk.len() as u64);
+    // Chunks should be persisted in the object store.
+    let stored_chunks = blob_store
+        .list_raw(Bucket::ProofUploads, None, usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(stored_chunks.len(), 1);
+
+    // Resending the chunk (e.g., because the response was lost) should lead to a conflict.
+    let response = send_upload_chunk_request(&app, &uri, 0, first_chunk).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    assert_eq!(read_upload_status(response).await, status);
+
+    // Restarting the upload of the same artifact should resume it.
+    let response = send_start_upload_request(&app, &uri, &artifact).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_upload_status(response).await, status);
+
+    let response = send_upload_chunk_request(&app, &uri, status.offset, second_chunk).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_upload_status(response).await.offset, artifact.size);
+
+    // Retrying a completed upload (e.g., because the response was lost) should succeed.
+    let response = send_start_upload_request(&app, &uri, &artifact).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_upload_status(response).await.offset, artifact.size);
+
+    // The batch should be marked as skipped, and uploaded chunks should be removed.
+    let unpicked_batch = conn
+        .proof_generation_dal()
+        .get_oldest_unpicked_batch()
+        .await
+        .unwrap();
+    assert_eq!(unpicked_batch, None);
+    assert!(!conn
+        .proof_generation_dal()
+        .is_batch_locked_for_proving(batch_number)
+        .await
+        .unwrap());
+    let stored_chunks = blob_store
+        .list_raw(Bucket::ProofUploads, None, usize::MAX)
+        .await
+        .unwrap();
+    assert!(stored_chunks.is_empty(), "{stored_chunks:?}");
+}
+
+async fn send_start_upload_request(app: &Router, uri: &str, artifact: &ArtifactInfo) -> Response {
+    let req_body = Body::from(serde_json::to_vec(artifact).unwrap());
+    app.clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri(uri)
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(req_body)
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+async fn send_upload_chunk_request(app: &Router, uri: &str, offset: u64, chunk: &[u8]) -> Response {
+    app.clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::PATCH)
+                .uri(uri)
+                .header(http::header::CONTENT_TYPE, "application/octet-stream")
+                .header("upload-offset", offset.to_string())
+                .body(Body::from(chunk.to_vec()))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+async fn read_upload_status(response: Response) -> UploadStatus {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
@@ -173,6 +173,10 @@ pub enum InteractionType {
 #[metrics(label = "component", rename_all = "snake_case")]
 pub enum CheckerComponent {
     ConsistencyChecker,
+    /// Consistency checks for prove transactions.
+    ConsistencyCheckerProve,
+    /// Consistency checks for execute transactions.
+    ConsistencyCheckerExecute,
     ReorgDetector,
 }
 
@@ -188,7 +192,7 @@ pub struct ExternalNodeMetrics {
     pub synced: Gauge<u64>,
     /// Current sync lag of the external node.
     pub sync_lag: Gauge<u64>,
-    /// Number of the last L1 batch checked by the re-org detector or consistency checker.
+    /// Number of the last L1 batch checked by the re-org detector or consistency checker (separately for each checked L1 transaction kind).
     pub last_correct_batch: Family<CheckerComponent, Gauge<u64>>,
     /// Number of the last L2 block checked by the re-org detector.
     pub last_correct_l2_block: Family<CheckerComponent, Gauge<u64>>,
@@ -12,13 +12,14 @@ use zksync_contracts::BaseSystemContracts;
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_mempool::L2TxFilter;
 use zksync_multivm::{interface::Halt, utils::derive_base_fee_and_gas_per_pubdata};
-use zksync_node_fee_model::BatchFeeModelInputProvider;
+use zksync_node_fee_model::{BatchFeeModelInputProvider, PipelineBackpressure};
 use zksync_types::{
     block::UnsealedL1BatchHeader,
     commitment::{L1BatchCommitmentMode, PubdataParams},
     protocol_upgrade::ProtocolUpgradeTx,
     utils::display_timestamp,
-    Address, L1BatchNumber, L2BlockNumber, L2ChainId, ProtocolVersionId, Transaction, H256, U256,
+    Address, L1BatchNumber, L2BlockNumber, L2ChainId, ProtocolVersionId, StorageKey, Transaction,
+    H256, U256,
 };
 use zksync_vm_executor::storage::L1BatchParamsProvider;
 
@@ -33,6 +34,7 @@ use crate::{
     seal_criteria::{
         IoSealCriteria, L2BlockMaxPayloadSizeSealer, TimeoutSealer, UnexecutableReason,
     },
+    tx_policy::TxPolicyEngine,
     updates::UpdatesManager,
     utils::millis_since_epoch,
     MempoolGuard,
@@ -59,6 +61,8 @@ pub struct MempoolIO {
     chain_id: L2ChainId,
     l2_da_validator_address: Option<Address>,
     pubdata_type: L1BatchCommitmentMode,
+    tx_policy: Option<TxPolicyEngine>,
+    backpressure: Option<PipelineBackpressure>,
 }
 
 impl IoSealCriteria for MempoolIO {
@@ -179,6 +183,13 @@ impl StateKeeperIO for MempoolIO {
             }));
         }
 
+        if let Some(delay) = self.congestion_delay(cursor).await? {
+            let delay = delay.min(max_wait);
+            KEEPER_METRICS.congestion_delay.observe(delay);
+            tokio::time::sleep(delay).await;
+            return Ok(None);
+        }
+
         let deadline = Instant::now() + max_wait;
 
         // Block until at least one transaction in the mempool can match the filter (or timeout happens).
@@ -238,9 +249,10 @@ impl StateKeeperIO for MempoolIO {
                 continue;
             }
 
-            self.pool
-                .connection_tagged("state_keeper")
-                .await?
+            let base_token_ratio_id = self.batch_fee_input_provider.get_base_token_ratio_id();
+            let mut storage = self.pool.connection_tagged("state_keeper").await?;
+            let mut transaction = storage.start_transaction().await?;
+            transaction
                 .blocks_dal()
                 .insert_l1_batch(UnsealedL1BatchHeader {
                     number: cursor.l1_batch,
@@ -250,6 +262,14 @@ impl StateKeeperIO for MempoolIO {
                     fee_input: self.filter.fee_input,
                 })
                 .await?;
+            if let Some(ratio_id) = base_token_ratio_id {
+                // Link the ratio to the batch so that the applied conversion ratio can be audited.
+                transaction
+                    .base_token_dal()
+                    .set_l1_batch_ratio_id(cursor.l1_batch, ratio_id)
+                    .await?;
+            }
+            transaction.commit().await?;
 
             return Ok(Some(L1BatchParams {
                 protocol_version,
@@ -293,8 +313,10 @@ impl StateKeeperIO for MempoolIO {
     async fn wait_for_next_tx(
         &mut self,
         max_wait: Duration,
-        l2_block_timestamp: u64,
+        updates_manager: &UpdatesManager,
     ) -> anyhow::Result<Option<Transaction>> {
+        let l2_block_timestamp = updates_manager.l2_block.timestamp;
+        let l2_block_number = u64::from(updates_manager.l2_block.number.0);
         let started_at = Instant::now();
         while started_at.elapsed() <= max_wait {
             let get_latency = KEEPER_METRICS.get_tx_from_mempool.start();
@@ -331,6 +353,47 @@ impl StateKeeperIO for MempoolIO {
                     continue;
                 }
 
+                // Same for the L2 block number and storage conditions of `eth_sendRawTransactionConditional`.
+                let matches_block_range = constraint
+                    .l2_block_number_range
+                    .map_or(true, |x| x.contains(&l2_block_number));
+                if !matches_block_range {
+                    let reason = format!(
+                        "L2 block number {l2_block_number} is outside of the allowed range"
+                    );
+                    self.reject(&tx, UnexecutableReason::ConditionsNotMet(reason))
+                        .await?;
+                    continue;
+                }
+
+                let unmet_storage_condition = self
+                    .find_unmet_storage_condition(
+                        &constraint.expected_storage_values,
+                        updates_manager,
+                    )
+                    .await?;
+                if let Some(key) = unmet_storage_condition {
+                    let reason = format!(
+                        "storage slot {:?} of account {:?} has unexpected value",
+                        key.key(),
+                        key.address()
+                    );
+                    self.reject(&tx, UnexecutableReason::ConditionsNotMet(reason))
+                        .await?;
+                    continue;
+                }
+
+                // Transactions may have been accepted by the API before the policy was changed.
+                let policy_check = self.tx_policy.as_ref().map(|policy| policy.check(&tx));
+                if let Some(Err(violation)) = policy_check {
+                    self.reject(
+                        &tx,
+                        UnexecutableReason::DeniedByPolicy(violation.to_string()),
+                    )
+                    .await?;
+                    continue;
+                }
+
                 return Ok(Some(tx));
             } else {
                 tokio::time::sleep(self.delay_interval).await;
@@ -515,9 +578,95 @@ impl MempoolIO {
             chain_id,
             l2_da_validator_address,
             pubdata_type,
+            tx_policy: None,
+            backpressure: PipelineBackpressure::new(config),
         })
     }
 
+    /// Returns the first key with a value differing from the expected one. Values written in the current L1 batch
+    /// are taken from `updates_manager` since they may be not persisted yet.
+    async fn find_unmet_storage_condition(
+        &self,
+        expected_values: &[(StorageKey, H256)],
+        updates_manager: &UpdatesManager,
+    ) -> anyhow::Result<Option<StorageKey>> {
+        if expected_values.is_empty() {
+            return Ok(None);
+        }
+
+        let deduplicator = &updates_manager.storage_writes_deduplicator;
+        let persisted_keys: Vec<_> = expected_values
+            .iter()
+            .filter(|(key, _)| deduplicator.written_value(key).is_none())
+            .map(|(key, _)| key.hashed_key())
+            .collect();
+        let persisted_values = if persisted_keys.is_empty() {
+            HashMap::new()
+        } else {
+            let mut storage = self.pool.connection_tagged("state_keeper").await?;
+            storage
+                .storage_web3_dal()
+                .get_values(&persisted_keys)
+                .await?
+        };
+
+        for (key, expected_value) in expected_values {
+            let actual_value = deduplicator
+                .written_value(key)
+                .or_else(|| persisted_values.get(&key.hashed_key()).copied())
+                .unwrap_or_default();
+            if actual_value != *expected_value {
+                return Ok(Some(*key));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Sets the policy applied to transactions taken from the mempool. Transactions violating the policy are rejected.
+    pub fn set_tx_policy(&mut self, tx_policy: TxPolicyEngine) {
+        self.tx_policy = Some(tx_policy);
+    }
+
+    /// Returns the remaining delay before a new L1 batch can be opened if the proof / execution pipeline is congested.
+    /// While congested, L1 batches are opened no more often than once per the configured interval.
+    async fn congestion_delay(&self, cursor: &IoCursor) -> anyhow::Result<Option<Duration>> {
+        let Some(backpressure) = &self.backpressure else {
+            return Ok(None);
+        };
+        // The previous L2 block is the fictive block of the previous L1 batch, so its timestamp approximates
+        // the time the batch was sealed.
+        let next_batch_timestamp_millis = cursor.prev_l2_block_timestamp * 1_000
+            + backpressure.min_l1_batch_interval().as_millis() as u64;
+        let current_timestamp_millis = millis_since_epoch() as u64;
+        if current_timestamp_millis >= next_batch_timestamp_millis {
+            return Ok(None);
+        }
+
+        let mut storage = self.pool.connection_tagged("state_keeper").await?;
+        let congestion = backpressure.check(&mut storage).await?;
+        drop(storage);
+        KEEPER_METRICS
+            .unproven_l1_batches
+            .set(congestion.unproven_l1_batches.into());
+        KEEPER_METRICS
+            .unexecuted_l1_batches
+            .set(congestion.unexecuted_l1_batches.into());
+        if !congestion.is_congested {
+            return Ok(None);
+        }
+
+        tracing::debug!(
+            "Proof / execution pipeline is congested ({} unproven, {} unexecuted L1 batches); \
+             delaying opening L1 batch #{}",
+            congestion.unproven_l1_batches,
+            congestion.unexecuted_l1_batches,
+            cursor.l1_batch
+        );
+        Ok(Some(Duration::from_millis(
+            next_batch_timestamp_millis - current_timestamp_millis,
+        )))
+    }
+
     fn pubdata_params(&self, protocol_version: ProtocolVersionId) -> anyhow::Result<PubdataParams> {
         let pubdata_params = match (
             protocol_version.is_pre_gateway(),
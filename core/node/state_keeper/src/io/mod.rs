@@ -15,7 +15,10 @@ pub use self::{
     output_handler::{OutputHandler, StateKeeperOutputHandler},
     persistence::{L2BlockSealerTask, StateKeeperPersistence, TreeWritesPersistence},
 };
-use super::seal_criteria::{IoSealCriteria, UnexecutableReason};
+use super::{
+    seal_criteria::{IoSealCriteria, UnexecutableReason},
+    updates::UpdatesManager,
+};
 
 pub mod common;
 pub(crate) mod mempool;
@@ -137,10 +140,13 @@ pub trait StateKeeperIO: 'static + Send + Sync + fmt::Debug + IoSealCriteria {
 
     /// Blocks for up to `max_wait` until the next transaction is available for execution.
     /// Returns `None` if no transaction became available until the timeout.
+    ///
+    /// `updates_manager` describes the L2 block the transaction will be included in; it can be used
+    /// to reject transactions with unmet inclusion constraints.
     async fn wait_for_next_tx(
         &mut self,
         max_wait: Duration,
-        l2_block_timestamp: u64,
+        updates_manager: &UpdatesManager,
     ) -> anyhow::Result<Option<Transaction>>;
     /// Marks the transaction as "not executed", so it can be retrieved from the IO again.
     async fn rollback(&mut self, tx: Transaction) -> anyhow::Result<()>;
@@ -5,9 +5,13 @@ use std::{sync::Arc, time::Instant};
 use anyhow::Context as _;
 use async_trait::async_trait;
 use tokio::sync::{mpsc, oneshot};
-use zksync_dal::{ConnectionPool, Core, CoreDal};
+use tracing::Instrument as _;
+use zksync_dal::{node_leases_dal::NodeLease, ConnectionPool, Core, CoreDal};
 use zksync_shared_metrics::{BlockStage, APP_METRICS};
-use zksync_types::{u256_to_h256, writes::TreeWrite, Address, ProtocolVersionId};
+use zksync_types::{
+    commitment::L1BatchCommitmentMode, u256_to_h256, writes::TreeWrite, Address, ProtocolVersionId,
+};
+use zksync_vlog::opentelemetry::{l1_batch_span, L1BatchStage};
 
 use crate::{
     io::{
@@ -35,6 +39,8 @@ pub struct StateKeeperPersistence {
     latest_completion_receiver: Option<oneshot::Receiver<()>>,
     // If true, `submit_l2_block()` will wait for the operation to complete.
     is_sync: bool,
+    lease_fence: Option<NodeLease>,
+    commitment_mode: Option<L1BatchCommitmentMode>,
 }
 
 impl StateKeeperPersistence {
@@ -85,6 +91,7 @@ impl StateKeeperPersistence {
         let (commands_sender, commands_receiver) = mpsc::channel(command_capacity);
         let sealer = L2BlockSealerTask {
             pool: pool.clone(),
+            lease_fence: None,
             is_sync,
             commands_sender: commands_sender.downgrade(),
             commands_receiver,
@@ -97,6 +104,8 @@ impl StateKeeperPersistence {
             commands_sender,
             latest_completion_receiver: None,
             is_sync,
+            lease_fence: None,
+            commitment_mode: None,
         };
         Ok((this, sealer))
     }
@@ -113,6 +122,23 @@ impl StateKeeperPersistence {
         self
     }
 
+    /// Makes L1 batch sealing conditional on the specified lease being held by this node. This prevents
+    /// the same L1 batch being sealed by multiple main node instances sharing the database.
+    ///
+    /// L2 blocks are sealed by [`L2BlockSealerTask`], which must be fenced separately
+    /// using [`L2BlockSealerTask::with_lease_fence()`].
+    pub fn with_lease_fence(mut self, lease: NodeLease) -> Self {
+        self.lease_fence = Some(lease);
+        self
+    }
+
+    /// Sets the commitment mode persisted for sealed pre-gateway L1 batches. For post-gateway batches,
+    /// the mode is always taken from the batch pubdata params.
+    pub fn with_commitment_mode(mut self, mode: L1BatchCommitmentMode) -> Self {
+        self.commitment_mode = Some(mode);
+        self
+    }
+
     /// Submits a new sealing `command` to the sealer that this handle is attached to.
     ///
     /// If there are currently too many unprocessed commands, this method will wait until
@@ -207,7 +233,10 @@ impl StateKeeperOutputHandler for StateKeeperPersistence {
                 self.pool.clone(),
                 self.l2_legacy_shared_bridge_addr,
                 self.insert_protective_reads,
+                self.lease_fence.as_ref(),
+                self.commitment_mode,
             )
+            .instrument(l1_batch_span(L1BatchStage::Seal, batch_number.0))
             .await
             .with_context(|| format!("cannot persist L1 batch #{batch_number}"))?;
         APP_METRICS.block_number[&BlockStage::Sealed].set(batch_number.0.into());
@@ -219,6 +248,7 @@ impl StateKeeperOutputHandler for StateKeeperPersistence {
 #[derive(Debug)]
 pub struct L2BlockSealerTask {
     pool: ConnectionPool<Core>,
+    lease_fence: Option<NodeLease>,
     is_sync: bool,
     // Weak sender handle to get queue capacity stats.
     commands_sender: mpsc::WeakSender<Completable<L2BlockSealCommand>>,
@@ -226,6 +256,13 @@ pub struct L2BlockSealerTask {
 }
 
 impl L2BlockSealerTask {
+    /// Makes L2 block sealing conditional on the specified lease being held by this node. Fenced L2 blocks
+    /// are sealed in a single DB transaction, rather than by several parallel subtasks.
+    pub fn with_lease_fence(mut self, lease: NodeLease) -> Self {
+        self.lease_fence = Some(lease);
+        self
+    }
+
     /// Seals L2 blocks as they are received from the [`StateKeeperPersistence`]. This should be run
     /// on a separate Tokio task.
     pub async fn run(mut self) -> anyhow::Result<()> {
@@ -244,7 +281,10 @@ impl L2BlockSealerTask {
         // Commands must be processed sequentially: a later L2 block cannot be saved before
         // an earlier one.
         while let Some(completable) = self.next_command().await {
-            completable.command.seal(self.pool.clone()).await?;
+            completable
+                .command
+                .seal(self.pool.clone(), self.lease_fence.as_ref())
+                .await?;
             if let Some(delta) = l2_block_seal_delta {
                 L2_BLOCK_METRICS.seal_delta.observe(delta.elapsed());
             }
@@ -575,7 +615,10 @@ mod tests {
             StateKeeperPersistence::new(pool.clone(), Some(Address::default()), 1)
                 .await
                 .unwrap();
-        persistence = persistence.with_tx_insertion().without_protective_reads();
+        persistence = persistence
+            .with_tx_insertion()
+            .without_protective_reads()
+            .with_commitment_mode(L1BatchCommitmentMode::Validium);
         let mut output_handler = OutputHandler::new(Box::new(persistence));
         tokio::spawn(l2_block_sealer.run());
 
@@ -607,6 +650,14 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(protective_reads, HashSet::new());
+
+        // The batch has a pre-gateway protocol version, so the configured commitment mode should be persisted.
+        let commitment_mode = storage
+            .blocks_dal()
+            .get_l1_batch_commitment_mode(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(commitment_mode, Some(L1BatchCommitmentMode::Validium));
     }
 
     #[tokio::test]
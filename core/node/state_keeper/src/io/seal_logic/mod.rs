@@ -2,13 +2,13 @@
 //! It contains the logic of the block sealing, which is used by both the mempool-based and external node IO.
 
 use std::{
-    ops,
+    fmt, ops,
     time::{Duration, Instant},
 };
 
 use anyhow::Context as _;
 use itertools::Itertools;
-use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_dal::{node_leases_dal::NodeLease, Connection, ConnectionPool, Core, CoreDal};
 use zksync_multivm::{
     interface::{DeduplicatedWritesMetrics, TransactionExecutionResult, VmEvent},
     utils::{
@@ -19,6 +19,7 @@ use zksync_multivm::{
 use zksync_shared_metrics::{BlockStage, L2BlockStage, APP_METRICS};
 use zksync_types::{
     block::{build_bloom, L1BatchHeader, L2BlockHeader},
+    commitment::L1BatchCommitmentMode,
     helpers::unix_timestamp_ms,
     l2_to_l1_log::UserL2ToL1Log,
     tx::IncludedTxLocation,
@@ -39,15 +40,42 @@ use crate::{
 
 pub mod l2_block_seal_subtasks;
 
+/// Checks that `lease` is held by this node and locks it until the end of the `transaction`, so that the lease
+/// cannot be taken over by another node while `action` is being persisted.
+async fn check_lease_fence(
+    transaction: &mut Connection<'_, Core>,
+    lease: &NodeLease,
+    action: impl fmt::Display,
+) -> anyhow::Result<()> {
+    let is_held = transaction.node_leases_dal().check_lease(lease).await?;
+    anyhow::ensure!(
+        is_held,
+        "lease `{}` is not held by this node (`{}`); refusing to {action} \
+         to prevent it being persisted by multiple nodes",
+        lease.name,
+        lease.holder
+    );
+    Ok(())
+}
+
 impl UpdatesManager {
     /// Persists an L1 batch in the storage.
     /// This action includes a creation of an empty "fictive" L2 block that contains
     /// the events generated during the bootloader "tip phase". Returns updates for this fictive L2 block.
+    ///
+    /// If `lease_fence` is specified, the batch is only persisted if the lease is held by this node
+    /// for the entire duration of the DB transaction.
+    ///
+    /// `default_commitment_mode` is persisted as the batch commitment mode for pre-gateway batches, for which
+    /// pubdata params are not used and thus don't reflect the actual mode. If it's not specified, the mode
+    /// is only persisted for post-gateway batches.
     pub(super) async fn seal_l1_batch(
         &self,
         pool: ConnectionPool<Core>,
         l2_legacy_shared_bridge_addr: Option<Address>,
         insert_protective_reads: bool,
+        lease_fence: Option<&NodeLease>,
+        default_commitment_mode: Option<L1BatchCommitmentMode>,
     ) -> anyhow::Result<()> {
         let started_at = Instant::now();
         let finished_batch = self
@@ -64,7 +92,11 @@ impl UpdatesManager {
         );
 
         let mut connection = pool.connection_tagged("state_keeper").await?;
-        let transaction = connection.start_transaction().await?;
+        let mut transaction = connection.start_transaction().await?;
+        if let Some(lease) = lease_fence {
+            let action = format_args!("seal L1 batch #{}", self.l1_batch.number);
+            check_lease_fence(&mut transaction, lease, action).await?;
+        }
 
         // We rely on the fact that fictive L2 block and L1 batch data is saved in the same transaction.
         let mut strategy = SealStrategy::Sequential(transaction);
@@ -150,6 +182,12 @@ impl UpdatesManager {
                 self.pending_execution_metrics().circuit_statistic,
             )
             .await?;
+        if let Some(commitment_mode) = self.commitment_mode(default_commitment_mode) {
+            transaction
+                .blocks_dal()
+                .set_l1_batch_commitment_mode(self.l1_batch.number, commitment_mode)
+                .await?;
+        }
         progress.observe(None);
 
         let progress = L1_BATCH_METRICS.start(L1BatchSealStage::SetL1BatchNumberForL2Blocks);
@@ -320,11 +358,35 @@ impl<'pool> SealStrategy<'pool> {
 }
 
 impl L2BlockSealCommand {
-    pub(super) async fn seal(&self, pool: ConnectionPool<Core>) -> anyhow::Result<()> {
+    /// Persists this L2 block. If `lease_fence` is specified, the L2 block is only persisted if the lease is held
+    /// by this node for the entire duration of the DB transaction; this requires sealing all L2 block data
+    /// sequentially in a single transaction.
+    pub(super) async fn seal(
+        &self,
+        pool: ConnectionPool<Core>,
+        lease_fence: Option<&NodeLease>,
+    ) -> anyhow::Result<()> {
         let l2_block_number = self.l2_block.number;
-        self.seal_inner(&mut SealStrategy::Parallel(&pool), false)
+        let Some(lease) = lease_fence else {
+            return self
+                .seal_inner(&mut SealStrategy::Parallel(&pool), false)
+                .await
+                .with_context(|| format!("failed sealing L2 block #{l2_block_number}"));
+        };
+
+        let mut connection = pool.connection_tagged("state_keeper").await?;
+        let mut transaction = connection.start_transaction().await?;
+        let action = format_args!("seal L2 block #{l2_block_number}");
+        check_lease_fence(&mut transaction, lease, action).await?;
+        let mut strategy = SealStrategy::Sequential(transaction);
+        self.seal_inner(&mut strategy, false)
             .await
-            .with_context(|| format!("failed sealing L2 block #{l2_block_number}"))
+            .with_context(|| format!("failed sealing L2 block #{l2_block_number}"))?;
+        let SealStrategy::Sequential(transaction) = strategy else {
+            panic!("Sealing L2 block should not mutate type of strategy");
+        };
+        transaction.commit().await?;
+        Ok(())
     }
 
     /// Seals an L2 block with the given number.
@@ -1,8 +1,9 @@
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use test_casing::test_casing;
+use zksync_config::configs::chain::StateKeeperConfig;
 use zksync_contracts::BaseSystemContractsHashes;
-use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_dal::{node_leases_dal::NodeLease, Connection, ConnectionPool, Core, CoreDal};
 use zksync_mempool::L2TxFilter;
 use zksync_multivm::{
     interface::{
@@ -21,7 +22,8 @@ use zksync_types::{
     protocol_upgrade::ProtocolUpgradeTx,
     protocol_version::ProtocolSemanticVersion,
     AccountTreeId, Address, L1BatchNumber, L2BlockNumber, L2ChainId, ProtocolVersion,
-    ProtocolVersionId, StorageKey, TransactionTimeRangeConstraint, H256, U256,
+    ProtocolVersionId, StorageKey, StorageLog, StorageLogWithPreviousValue,
+    TransactionTimeRangeConstraint, H256, U256,
 };
 
 use self::tester::Tester;
@@ -29,7 +31,10 @@ use crate::{
     io::{seal_logic::l2_block_seal_subtasks::L2BlockSealProcess, StateKeeperIO},
     mempool_actor::l2_tx_filter,
     testonly::BASE_SYSTEM_CONTRACTS,
-    tests::{create_execution_result, create_transaction, seconds_since_epoch, Query},
+    tests::{
+        create_execution_result, create_transaction, default_l1_batch_env, default_system_env,
+        seconds_since_epoch, Query,
+    },
     updates::{L2BlockSealCommand, L2BlockUpdates, UpdatesManager},
     StateKeeperOutputHandler, StateKeeperPersistence,
 };
@@ -313,7 +318,10 @@ async fn processing_storage_logs_when_sealing_l2_block() {
         .save_protocol_version_with_tx(&ProtocolVersion::default())
         .await
         .unwrap();
-    seal_command.seal(connection_pool.clone()).await.unwrap();
+    seal_command
+        .seal(connection_pool.clone(), None)
+        .await
+        .unwrap();
     let mut conn = connection_pool.connection().await.unwrap();
 
     // Manually mark the L2 block as executed so that getting touched slots from it works
@@ -385,7 +393,7 @@ async fn processing_events_when_sealing_l2_block() {
         .save_protocol_version_with_tx(&ProtocolVersion::default())
         .await
         .unwrap();
-    seal_command.seal(pool.clone()).await.unwrap();
+    seal_command.seal(pool.clone(), None).await.unwrap();
     let mut conn = pool.connection().await.unwrap();
 
     let logs = conn
@@ -401,6 +409,78 @@ async fn processing_events_when_sealing_l2_block() {
     }
 }
 
+#[tokio::test]
+async fn fenced_l2_block_sealing() {
+    let pool = ConnectionPool::<Core>::constrained_test_pool(2).await;
+    let mut conn = pool.connection().await.unwrap();
+    conn.protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion::default())
+        .await
+        .unwrap();
+    let lease = NodeLease {
+        name: "sequencer".to_owned(),
+        holder: "main_node".to_owned(),
+    };
+    let l2_block_number = L2BlockNumber(3);
+    let mut l2_block = L2BlockUpdates::new(
+        0,
+        l2_block_number,
+        H256::zero(),
+        1,
+        ProtocolVersionId::latest(),
+    );
+    l2_block.extend_from_executed_transaction(
+        create_transaction(10, 100),
+        create_execution_result([]),
+        VmExecutionMetrics::default(),
+        vec![],
+        vec![],
+    );
+    let seal_command = create_block_seal_command(L1BatchNumber(2), l2_block);
+
+    let err = seal_command
+        .seal(pool.clone(), Some(&lease))
+        .await
+        .unwrap_err();
+    assert!(format!("{err:#}").contains("not held"), "{err:#}");
+    let header = conn
+        .blocks_dal()
+        .get_l2_block_header(l2_block_number)
+        .await
+        .unwrap();
+    assert!(header.is_none());
+
+    let other_lease = NodeLease {
+        holder: "standby".to_owned(),
+        ..lease.clone()
+    };
+    conn.node_leases_dal()
+        .try_acquire_lease(&other_lease, Duration::from_secs(60))
+        .await
+        .unwrap();
+    seal_command
+        .seal(pool.clone(), Some(&lease))
+        .await
+        .unwrap_err();
+
+    conn.node_leases_dal()
+        .release_lease(&other_lease)
+        .await
+        .unwrap();
+    conn.node_leases_dal()
+        .try_acquire_lease(&lease, Duration::from_secs(60))
+        .await
+        .unwrap();
+    seal_command.seal(pool.clone(), Some(&lease)).await.unwrap();
+    let header = conn
+        .blocks_dal()
+        .get_l2_block_header(l2_block_number)
+        .await
+        .unwrap()
+        .expect("L2 block is not persisted");
+    assert_eq!(header.number, l2_block_number);
+}
+
 fn bytecode_publishing_events(
     l1_batch_number: L1BatchNumber,
     tx_index: u32,
@@ -502,7 +582,7 @@ async fn processing_dynamic_factory_deps_when_sealing_l2_block() {
         .save_protocol_version_with_tx(&ProtocolVersion::default())
         .await
         .unwrap();
-    seal_command.seal(pool.clone()).await.unwrap();
+    seal_command.seal(pool.clone(), None).await.unwrap();
 
     let mut conn = pool.connection().await.unwrap();
     let persisted_factory_deps = conn
@@ -782,6 +862,9 @@ async fn test_mempool_with_timestamp_assertion() {
         .duration_since(UNIX_EPOCH)
         .expect("Time went backwards")
         .as_secs();
+    let l1_batch_env = default_l1_batch_env(2, system_time, Address::default());
+    let updates_manager =
+        UpdatesManager::new(&l1_batch_env, &default_system_env(), Default::default());
 
     // inserting 3 transactions - a good one, sandwiched in between two bad ones. The good one should
     // be returned by wait_for_next_tx, while two bad ones should be rejected.
@@ -814,14 +897,14 @@ async fn test_mempool_with_timestamp_assertion() {
     insert_l2_transaction(&mut storage, &rejected_tx_2).await;
 
     let tx = mempool
-        .wait_for_next_tx(Duration::from_secs(2), system_time)
+        .wait_for_next_tx(Duration::from_secs(2), &updates_manager)
         .await
         .unwrap()
         .expect("No expected transaction in the mempool");
     assert_eq!(expected_tx.hash(), tx.hash());
 
     let next_tx = mempool
-        .wait_for_next_tx(Duration::from_secs(2), system_time)
+        .wait_for_next_tx(Duration::from_secs(2), &updates_manager)
         .await
         .expect("Should be no more transactions in the mempool");
     assert!(next_tx.is_none());
@@ -850,6 +933,115 @@ async fn test_mempool_with_timestamp_assertion() {
     );
 }
 
+#[tokio::test]
+async fn test_mempool_with_transaction_conditions() {
+    let connection_pool = ConnectionPool::<Core>::constrained_test_pool(2).await;
+    let tester = Tester::new(L1BatchCommitmentMode::Rollup);
+    let mut storage = connection_pool.connection().await.unwrap();
+
+    tester.genesis(&connection_pool).await;
+    let tx_result = tester
+        .insert_l2_block(&connection_pool, 1, 5, BatchFeeInput::l1_pegged(55, 555))
+        .await;
+    tester
+        .insert_sealed_batch(&connection_pool, 1, &[tx_result])
+        .await;
+
+    let want_filter = l2_tx_filter(
+        &tester.create_batch_fee_input_provider().await,
+        ProtocolVersionId::latest().into(),
+    )
+    .await
+    .unwrap();
+    let (mut mempool, mut guard) = tester.create_test_mempool_io(connection_pool).await;
+    mempool.initialize().await.unwrap();
+
+    // The transaction will be included into L2 block #2, which has written to `written_key`.
+    let l1_batch_env = default_l1_batch_env(2, seconds_since_epoch(), Address::default());
+    let mut updates_manager =
+        UpdatesManager::new(&l1_batch_env, &default_system_env(), Default::default());
+    let written_key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(1)), H256::zero());
+    let written_value = H256::repeat_byte(0xff);
+    updates_manager
+        .storage_writes_deduplicator
+        .apply(&[StorageLogWithPreviousValue {
+            log: StorageLog::new_write_log(written_key, written_value),
+            previous_value: H256::zero(),
+        }]);
+    let untouched_key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(2)), H256::zero());
+
+    let rejected_tx_1 = tester.insert_tx(
+        &mut guard,
+        want_filter.fee_per_gas,
+        want_filter.gas_per_pubdata,
+        TransactionTimeRangeConstraint {
+            l2_block_number_range: Some(3..10),
+            ..TransactionTimeRangeConstraint::default()
+        },
+    );
+    let expected_tx = tester.insert_tx(
+        &mut guard,
+        want_filter.fee_per_gas,
+        want_filter.gas_per_pubdata,
+        TransactionTimeRangeConstraint {
+            l2_block_number_range: Some(2..3),
+            expected_storage_values: vec![
+                (written_key, written_value),
+                (untouched_key, H256::zero()),
+            ],
+            ..TransactionTimeRangeConstraint::default()
+        },
+    );
+    let rejected_tx_2 = tester.insert_tx(
+        &mut guard,
+        want_filter.fee_per_gas,
+        want_filter.gas_per_pubdata,
+        TransactionTimeRangeConstraint {
+            expected_storage_values: vec![(written_key, H256::zero())],
+            ..TransactionTimeRangeConstraint::default()
+        },
+    );
+    insert_l2_transaction(&mut storage, &rejected_tx_1).await;
+    insert_l2_transaction(&mut storage, &expected_tx).await;
+    insert_l2_transaction(&mut storage, &rejected_tx_2).await;
+
+    let tx = mempool
+        .wait_for_next_tx(Duration::from_secs(2), &updates_manager)
+        .await
+        .unwrap()
+        .expect("No expected transaction in the mempool");
+    assert_eq!(expected_tx.hash(), tx.hash());
+
+    let next_tx = mempool
+        .wait_for_next_tx(Duration::from_secs(2), &updates_manager)
+        .await
+        .expect("Should be no more transactions in the mempool");
+    assert!(next_tx.is_none());
+
+    let rejected_storage_tx_1 = storage
+        .transactions_dal()
+        .get_storage_tx_by_hash(rejected_tx_1.hash())
+        .await
+        .unwrap()
+        .expect("Failed to find transaction");
+    assert_eq!(
+        "rejected: Transaction conditions not met: L2 block number 2 is outside of the allowed range",
+        rejected_storage_tx_1.error.unwrap()
+    );
+
+    let rejected_storage_tx_2 = storage
+        .transactions_dal()
+        .get_storage_tx_by_hash(rejected_tx_2.hash())
+        .await
+        .unwrap()
+        .expect("Failed to find transaction");
+    let error = rejected_storage_tx_2.error.unwrap();
+    assert!(
+        error.starts_with("rejected: Transaction conditions not met: storage slot"),
+        "{error}"
+    );
+}
+
 #[tokio::test]
 async fn test_batch_params_with_protocol_upgrade_tx() {
     let connection_pool = ConnectionPool::<Core>::constrained_test_pool(2).await;
@@ -896,6 +1088,61 @@ async fn test_batch_params_with_protocol_upgrade_tx() {
     assert!(new_batch_params.is_some());
 }
 
+/// Checks that opening a new L1 batch is delayed while the proof / execution pipeline is congested.
+#[tokio::test]
+async fn new_batch_is_delayed_on_congested_pipeline() {
+    let connection_pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let mut tester = Tester::new(L1BatchCommitmentMode::Rollup);
+    tester.genesis(&connection_pool).await;
+    // The sealed batch is neither proven nor executed. Its timestamp is recent, so the minimum interval
+    // between L1 batches hasn't elapsed yet.
+    tester.set_timestamp(seconds_since_epoch());
+    let tx_result = tester
+        .insert_l2_block(&connection_pool, 1, 5, BatchFeeInput::l1_pegged(55, 555))
+        .await;
+    tester
+        .insert_sealed_batch(&connection_pool, 1, &[tx_result])
+        .await;
+    let want_filter = l2_tx_filter(
+        &tester.create_batch_fee_input_provider().await,
+        ProtocolVersionId::latest().into(),
+    )
+    .await
+    .unwrap();
+
+    let max_wait = Duration::from_secs(2);
+    for (max_unproven_l1_batches, is_congested) in [(0, true), (1, false)] {
+        let config = StateKeeperConfig {
+            congestion_max_unproven_l1_batches: Some(max_unproven_l1_batches),
+            congestion_min_l1_batch_interval_ms: Some(60_000),
+            ..tester.state_keeper_config()
+        };
+        let (mut mempool, mut guard) = tester
+            .create_test_mempool_io_with_config(connection_pool.clone(), &config)
+            .await;
+        let (io_cursor, _) = mempool.initialize().await.unwrap();
+        tester.insert_tx(
+            &mut guard,
+            want_filter.fee_per_gas,
+            want_filter.gas_per_pubdata,
+            TransactionTimeRangeConstraint::default(),
+        );
+
+        let started_at = Instant::now();
+        let params = mempool
+            .wait_for_new_batch_params(&io_cursor, max_wait)
+            .await
+            .unwrap();
+        if is_congested {
+            // The batch isn't opened despite a matching transaction in the mempool.
+            assert!(params.is_none());
+            assert!(started_at.elapsed() >= max_wait);
+        } else {
+            assert!(params.is_some());
+        }
+    }
+}
+
 async fn insert_l2_transaction(storage: &mut Connection<'_, Core>, tx: &L2Tx) {
     storage
         .transactions_dal()
@@ -82,7 +82,7 @@ impl Tester {
             num_samples_for_blob_base_fee_estimate: 10,
             internal_pubdata_pricing_multiplier: 1.0,
             max_blob_base_fee: None,
-            settlement_mode: Default::default(),
+            ..GasAdjusterConfig::default()
         };
 
         GasAdjuster::new(
@@ -117,9 +117,26 @@ impl Tester {
         100
     }
 
+    pub(super) fn state_keeper_config(&self) -> StateKeeperConfig {
+        StateKeeperConfig {
+            minimal_l2_gas_price: self.minimal_l2_gas_price(),
+            validation_computational_gas_limit: BATCH_COMPUTATIONAL_GAS_LIMIT,
+            ..StateKeeperConfig::for_tests()
+        }
+    }
+
     pub(super) async fn create_test_mempool_io(
         &self,
         pool: ConnectionPool<Core>,
+    ) -> (MempoolIO, MempoolGuard) {
+        self.create_test_mempool_io_with_config(pool, &self.state_keeper_config())
+            .await
+    }
+
+    pub(super) async fn create_test_mempool_io_with_config(
+        &self,
+        pool: ConnectionPool<Core>,
+        config: &StateKeeperConfig,
     ) -> (MempoolIO, MempoolGuard) {
         let gas_adjuster = Arc::new(self.create_gas_adjuster().await);
         let batch_fee_input_provider = MainNodeFeeInputProvider::new(
@@ -136,17 +153,12 @@ impl Tester {
         );
 
         let mempool = MempoolGuard::new(PriorityOpId(0), 100);
-        let config = StateKeeperConfig {
-            minimal_l2_gas_price: self.minimal_l2_gas_price(),
-            validation_computational_gas_limit: BATCH_COMPUTATIONAL_GAS_LIMIT,
-            ..StateKeeperConfig::for_tests()
-        };
         let wallets = Wallets::for_tests();
         let io = MempoolIO::new(
             mempool.clone(),
             Arc::new(batch_fee_input_provider),
             pool,
-            &config,
+            config,
             wallets.state_keeper.unwrap().fee_account.address(),
             Duration::from_secs(1),
             L2ChainId::from(270),
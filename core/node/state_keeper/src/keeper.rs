@@ -55,6 +55,36 @@ impl Error {
     }
 }
 
+/// Handle allowing to pause and resume transaction processing by [`ZkSyncStateKeeper`] without restarting the node.
+///
+/// While paused, the state keeper doesn't execute new transactions and doesn't seal L2 blocks or L1 batches;
+/// the pending batch state is retained, so processing continues from the same point once resumed.
+#[derive(Debug, Clone)]
+pub struct StateKeeperPauseHandle(Arc<watch::Sender<bool>>);
+
+impl Default for StateKeeperPauseHandle {
+    fn default() -> Self {
+        Self(Arc::new(watch::channel(false).0))
+    }
+}
+
+impl StateKeeperPauseHandle {
+    /// Pauses the state keeper. Returns `false` if the state keeper was already paused.
+    pub fn pause(&self) -> bool {
+        !self.0.send_replace(true)
+    }
+
+    /// Resumes the state keeper. Returns `false` if the state keeper wasn't paused.
+    pub fn resume(&self) -> bool {
+        self.0.send_replace(false)
+    }
+
+    /// Checks whether the state keeper is paused.
+    pub fn is_paused(&self) -> bool {
+        *self.0.borrow()
+    }
+}
+
 /// State keeper represents a logic layer of L1 batch / L2 block processing flow.
 /// It's responsible for taking all the data from the `StateKeeperIO`, feeding it into `BatchExecutor` objects
 /// and calling `SealManager` to decide whether an L2 block or L1 batch should be sealed.
@@ -64,6 +94,12 @@ impl Error {
 ///
 /// You can think of it as a state machine that runs over a sequence of incoming transactions, turning them into
 /// a sequence of executed L2 blocks and batches.
+///
+/// Transactions are executed strictly one by one in a single batch VM. Besides storage, a transaction depends
+/// on the bootloader state left by previous transactions (its index in the batch, gas and pubdata accounting,
+/// refunds, rolling transaction hashes), so speculatively executing transactions with disjoint storage access sets
+/// in parallel VM instances and merging the results would produce batches different from the ones
+/// the bootloader would produce and prove.
 #[derive(Debug)]
 pub struct ZkSyncStateKeeper {
     io: Box<dyn StateKeeperIO>,
@@ -72,6 +108,7 @@ pub struct ZkSyncStateKeeper {
     sealer: Arc<dyn ConditionalSealer>,
     storage_factory: Arc<dyn ReadStorageFactory>,
     health_updater: HealthUpdater,
+    pause_handle: StateKeeperPauseHandle,
 }
 
 impl ZkSyncStateKeeper {
@@ -89,9 +126,15 @@ impl ZkSyncStateKeeper {
             sealer,
             storage_factory,
             health_updater: ReactiveHealthCheck::new("state_keeper").1,
+            pause_handle: StateKeeperPauseHandle::default(),
         }
     }
 
+    /// Returns a handle that can be used to pause and resume this state keeper.
+    pub fn pause_handle(&self) -> StateKeeperPauseHandle {
+        self.pause_handle.clone()
+    }
+
     pub async fn run(mut self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         match self.run_inner(stop_receiver).await {
             Ok(_) => unreachable!(),
@@ -377,6 +420,27 @@ impl ZkSyncStateKeeper {
         }
     }
 
+    async fn wait_while_paused(&self, stop_receiver: &watch::Receiver<bool>) -> Result<(), Error> {
+        let mut pause_receiver = self.pause_handle.0.subscribe();
+        if !*pause_receiver.borrow_and_update() {
+            return Ok(());
+        }
+
+        tracing::info!("State keeper is paused; waiting for it to be resumed");
+        let mut stop_receiver = stop_receiver.clone();
+        while *pause_receiver.borrow_and_update() {
+            if is_canceled(&stop_receiver) {
+                return Err(Error::Canceled);
+            }
+            tokio::select! {
+                _ = pause_receiver.changed() => {}
+                _ = stop_receiver.changed() => {}
+            }
+        }
+        tracing::info!("State keeper is resumed");
+        Ok(())
+    }
+
     #[tracing::instrument(
         skip_all,
         fields(
@@ -572,6 +636,7 @@ impl ZkSyncStateKeeper {
         }
 
         while !is_canceled(stop_receiver) {
+            self.wait_while_paused(stop_receiver).await?;
             let full_latency = KEEPER_METRICS.process_l1_batch_loop_iteration.start();
 
             if self
@@ -609,7 +674,7 @@ impl ZkSyncStateKeeper {
             let waiting_latency = KEEPER_METRICS.waiting_for_tx.start();
             let Some(tx) = self
                 .io
-                .wait_for_next_tx(POLL_WAIT_DURATION, updates_manager.l2_block.timestamp)
+                .wait_for_next_tx(POLL_WAIT_DURATION, updates_manager)
                 .instrument(info_span!("wait_for_next_tx"))
                 .await
                 .context("error waiting for next transaction")?
@@ -3,7 +3,7 @@ pub use self::{
         mempool::MempoolIO, L2BlockParams, L2BlockSealerTask, OutputHandler, StateKeeperIO,
         StateKeeperOutputHandler, StateKeeperPersistence, TreeWritesPersistence,
     },
-    keeper::ZkSyncStateKeeper,
+    keeper::{StateKeeperPauseHandle, ZkSyncStateKeeper},
     mempool_actor::MempoolFetcher,
     seal_criteria::SequencerSealer,
     state_keeper_storage::AsyncRocksdbCache,
@@ -22,6 +22,7 @@ mod state_keeper_storage;
 pub mod testonly;
 #[cfg(test)]
 pub(crate) mod tests;
+pub mod tx_policy;
 pub(crate) mod types;
 pub mod updates;
 pub(crate) mod utils;
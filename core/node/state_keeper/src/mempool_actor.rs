@@ -198,6 +198,7 @@ mod tests {
         stuck_tx_timeout: 0,
         remove_stuck_txs: false,
         delay_interval: 10,
+        tx_policy_path: None,
     };
 
     #[tokio::test]
@@ -96,6 +96,13 @@ pub struct StateKeeperMetrics {
     /// The time it takes to wait for new L2 block parameters
     #[metrics(buckets = Buckets::LATENCIES)]
     pub wait_for_l2_block_params: Histogram<Duration>,
+    /// Number of sealed L1 batches not proven on L1, as observed when opening a new batch with pipeline backpressure enabled.
+    pub unproven_l1_batches: Gauge<u64>,
+    /// Number of sealed L1 batches not executed on L1, as observed when opening a new batch with pipeline backpressure enabled.
+    pub unexecuted_l1_batches: Gauge<u64>,
+    /// Delays of opening new L1 batches because of the congested proof / execution pipeline.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub congestion_delay: Histogram<Duration>,
 }
 
 fn vm_revert_reason_as_metric_label(reason: &VmRevertReason) -> &'static str {
@@ -37,7 +37,8 @@ pub trait ConditionalSealer: 'static + fmt::Debug + Send + Sync {
 }
 
 /// Implementation of [`ConditionalSealer`] used by the main node.
-/// Internally uses a set of [`SealCriterion`]s to determine whether the batch should be sealed.
+/// Internally uses a set of [`SealCriterion`]s to determine whether the batch should be sealed; besides
+/// built-in criteria, the set may contain custom ones added via [`Self::with_custom_criteria()`].
 ///
 /// The checks are deterministic, i.e., should depend solely on execution metrics and [`StateKeeperConfig`].
 /// Non-deterministic seal criteria are expressed using [`IoSealCriteria`](super::IoSealCriteria).
@@ -126,6 +127,15 @@ impl SequencerSealer {
         Self { config, sealers }
     }
 
+    /// Adds custom criteria to be checked after the built-in ones.
+    pub fn with_custom_criteria(
+        mut self,
+        criteria: impl IntoIterator<Item = Box<dyn SealCriterion>>,
+    ) -> Self {
+        self.sealers.extend(criteria);
+        self
+    }
+
     #[cfg(test)]
     pub(crate) fn with_sealers(
         config: StateKeeperConfig,
@@ -177,3 +187,64 @@ impl ConditionalSealer for NoopSealer {
         SealResolution::NoSeal
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal_criteria::UnexecutableReason;
+
+    /// Seals the batch after the specified number of L1 transactions.
+    #[derive(Debug)]
+    struct L1TxCountCriterion(usize);
+
+    impl SealCriterion for L1TxCountCriterion {
+        fn should_seal(
+            &self,
+            _config: &StateKeeperConfig,
+            _block_open_timestamp_ms: u128,
+            _tx_count: usize,
+            l1_tx_count: usize,
+            _block_data: &SealData,
+            tx_data: &SealData,
+            _protocol_version: ProtocolVersionId,
+        ) -> SealResolution {
+            if tx_data.cumulative_size() > 1_000 {
+                SealResolution::Unexecutable(UnexecutableReason::LargeEncodingSize)
+            } else if l1_tx_count >= self.0 {
+                SealResolution::IncludeAndSeal
+            } else {
+                SealResolution::NoSeal
+            }
+        }
+
+        fn prom_criterion_name(&self) -> &'static str {
+            "l1_tx_count"
+        }
+    }
+
+    #[test]
+    fn custom_seal_criterion() {
+        let sealer = SequencerSealer::with_sealers(StateKeeperConfig::default(), vec![])
+            .with_custom_criteria([Box::new(L1TxCountCriterion(2)) as Box<dyn SealCriterion>]);
+        let data = SealData::default();
+        let protocol_version = ProtocolVersionId::latest();
+
+        let resolution = sealer.should_seal_l1_batch(1, 0, 5, 1, &data, &data, protocol_version);
+        assert_eq!(resolution, SealResolution::NoSeal);
+        let resolution = sealer.should_seal_l1_batch(1, 0, 5, 2, &data, &data, protocol_version);
+        assert_eq!(resolution, SealResolution::IncludeAndSeal);
+        assert_eq!(
+            sealer.find_unexecutable_reason(&data, protocol_version),
+            None
+        );
+
+        let large_tx_data = SealData {
+            cumulative_size: 2_000,
+            ..SealData::default()
+        };
+        assert_eq!(
+            sealer.find_unexecutable_reason(&large_tx_data, protocol_version),
+            Some("l1_tx_count")
+        );
+    }
+}
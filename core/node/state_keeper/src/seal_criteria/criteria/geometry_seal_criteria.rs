@@ -10,7 +10,25 @@ use crate::seal_criteria::{SealCriterion, SealData, SealResolution, Unexecutable
 // Collected vm execution metrics should fit into geometry limits.
 // Otherwise witness generation will fail and proof won't be generated.
 
+/// Estimates the number of base layer circuits the next transaction in the batch will use.
+///
+/// The estimate is based on the circuit statistics collected by VM tracers for the transactions already
+/// executed in the batch: it's the maximum of the average usage per transaction and the usage
+/// of the last executed transaction.
+fn estimate_next_tx_circuits(block_data: &SealData, tx_data: &SealData, tx_count: usize) -> usize {
+    let last_tx_circuits = tx_data.execution_metrics.circuit_statistic.total();
+    if tx_count == 0 {
+        return last_tx_circuits;
+    }
+    let batch_circuits = block_data.execution_metrics.circuit_statistic.total_f32();
+    let average_tx_circuits = (batch_circuits / tx_count as f32).ceil() as usize;
+    last_tx_circuits.max(average_tx_circuits)
+}
+
 /// Checks whether we should exclude the transaction because we don't have enough circuits for it.
+///
+/// If [`StateKeeperConfig::seal_on_predicted_circuits`] is set, also seals the batch in advance
+/// if the next transaction is expected to exceed the circuits capacity of the batch.
 #[derive(Debug)]
 pub struct CircuitsCriterion;
 
@@ -19,7 +37,7 @@ impl SealCriterion for CircuitsCriterion {
         &self,
         config: &StateKeeperConfig,
         _block_open_timestamp_ms: u128,
-        _tx_count: usize,
+        tx_count: usize,
         _l1_tx_count: usize,
         block_data: &SealData,
         tx_data: &SealData,
@@ -59,6 +77,13 @@ impl SealCriterion for CircuitsCriterion {
             SealResolution::ExcludeAndSeal
         } else if used_circuits_batch + batch_tip_circuit_overhead >= include_and_seal_bound {
             SealResolution::IncludeAndSeal
+        } else if config.seal_on_predicted_circuits
+            && used_circuits_batch
+                + batch_tip_circuit_overhead
+                + estimate_next_tx_circuits(block_data, tx_data, tx_count)
+                >= config.max_circuits_per_batch
+        {
+            SealResolution::IncludeAndSeal
         } else {
             SealResolution::NoSeal
         }
@@ -231,4 +256,90 @@ mod tests {
 
         test_unexecutable_tx_resolution(tx_execution_metrics, &CircuitsCriterion, protocol_version);
     }
+
+    fn seal_data(main_vm_circuits: f32) -> SealData {
+        SealData {
+            execution_metrics: VmExecutionMetrics {
+                circuit_statistic: CircuitStatistic {
+                    main_vm: main_vm_circuits,
+                    ..CircuitStatistic::default()
+                },
+                ..VmExecutionMetrics::default()
+            },
+            ..SealData::default()
+        }
+    }
+
+    #[test]
+    fn estimating_next_tx_circuits() {
+        assert_eq!(
+            estimate_next_tx_circuits(&seal_data(100.0), &seal_data(10.0), 5),
+            20
+        );
+        assert_eq!(
+            estimate_next_tx_circuits(&seal_data(100.0), &seal_data(50.0), 5),
+            50
+        );
+        assert_eq!(
+            estimate_next_tx_circuits(&seal_data(10.5), &seal_data(1.5), 10),
+            2
+        );
+    }
+
+    #[test]
+    fn sealing_on_predicted_circuits() {
+        let should_seal = |seal_on_predicted_circuits: bool,
+                           block_circuits: usize,
+                           tx_circuits: usize,
+                           tx_count: usize| {
+            let config = StateKeeperConfig {
+                close_block_at_geometry_percentage: 1.0,
+                reject_tx_at_geometry_percentage: 1.0,
+                max_circuits_per_batch: MAX_CIRCUITS_PER_BATCH,
+                seal_on_predicted_circuits,
+                ..Default::default()
+            };
+            let protocol_version = ProtocolVersionId::latest();
+            let batch_tip_circuit_overhead =
+                circuit_statistics_bootloader_batch_tip_overhead(protocol_version.into());
+            let block_circuits = block_circuits - batch_tip_circuit_overhead;
+            CircuitsCriterion.should_seal(
+                &config,
+                0,
+                tx_count,
+                0,
+                &seal_data(block_circuits as f32),
+                &seal_data(tx_circuits as f32),
+                protocol_version,
+            )
+        };
+
+        // Transactions using ~1,000 circuits each
+        assert_eq!(should_seal(true, 10_000, 1_000, 10), SealResolution::NoSeal);
+        assert_eq!(should_seal(true, 28_500, 1_000, 29), SealResolution::NoSeal);
+        assert_eq!(
+            should_seal(true, 29_000, 1_000, 29),
+            SealResolution::IncludeAndSeal
+        );
+        // Large last transaction
+        assert_eq!(
+            should_seal(true, 25_000, 5_000, 25),
+            SealResolution::IncludeAndSeal
+        );
+        // Batch over capacity
+        assert_eq!(
+            should_seal(true, 30_000, 1_000, 30),
+            SealResolution::ExcludeAndSeal
+        );
+
+        // Predicted circuits are ignored unless enabled in the config
+        assert_eq!(
+            should_seal(false, 29_000, 1_000, 29),
+            SealResolution::NoSeal
+        );
+        assert_eq!(
+            should_seal(false, 25_000, 5_000, 25),
+            SealResolution::NoSeal
+        );
+    }
 }
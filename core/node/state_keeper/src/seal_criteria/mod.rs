@@ -10,7 +10,7 @@
 //! Maintaining all the criteria in one place has proven itself to be very error-prone,
 //! thus now every criterion is independent of the others.
 
-use std::fmt;
+use std::{fmt, sync::Arc};
 
 use zksync_config::configs::chain::StateKeeperConfig;
 use zksync_multivm::{
@@ -63,6 +63,8 @@ pub enum UnexecutableReason {
     BootloaderOutOfGas,
     NotEnoughGasProvided,
     TooMuchUserL2L1Logs,
+    DeniedByPolicy(String),
+    ConditionsNotMet(String),
 }
 
 impl UnexecutableReason {
@@ -78,6 +80,8 @@ impl UnexecutableReason {
             UnexecutableReason::BootloaderOutOfGas => "BootloaderOutOfGas",
             UnexecutableReason::NotEnoughGasProvided => "NotEnoughGasProvided",
             UnexecutableReason::TooMuchUserL2L1Logs => "TooMuchUserL2L1Logs",
+            UnexecutableReason::DeniedByPolicy(_) => "DeniedByPolicy",
+            UnexecutableReason::ConditionsNotMet(_) => "ConditionsNotMet",
         }
     }
 }
@@ -103,6 +107,10 @@ impl fmt::Display for UnexecutableReason {
             UnexecutableReason::BootloaderOutOfGas => write!(f, "Bootloader out of gas"),
             UnexecutableReason::NotEnoughGasProvided => write!(f, "Not enough gas provided"),
             UnexecutableReason::TooMuchUserL2L1Logs => write!(f, "Too much user l2 l1 logs"),
+            UnexecutableReason::DeniedByPolicy(reason) => write!(f, "Denied by policy: {reason}"),
+            UnexecutableReason::ConditionsNotMet(reason) => {
+                write!(f, "Transaction conditions not met: {reason}")
+            }
         }
     }
 }
@@ -177,9 +185,34 @@ impl SealData {
             gas_remaining: tx_metrics.gas_remaining,
         }
     }
+
+    /// Returns VM execution metrics (cumulative for block data).
+    pub fn execution_metrics(&self) -> &VmExecutionMetrics {
+        &self.execution_metrics
+    }
+
+    /// Returns the bootloader encoding size of transactions in bytes.
+    pub fn cumulative_size(&self) -> usize {
+        self.cumulative_size
+    }
+
+    /// Returns metrics for deduplicated storage writes.
+    pub fn writes_metrics(&self) -> &DeduplicatedWritesMetrics {
+        &self.writes_metrics
+    }
+
+    /// Returns the amount of gas remaining after execution.
+    pub fn gas_remaining(&self) -> u32 {
+        self.gas_remaining
+    }
 }
 
-pub(super) trait SealCriterion: fmt::Debug + Send + Sync + 'static {
+/// Deterministic criterion deciding whether an L1 batch should be sealed after executing a transaction.
+///
+/// Besides built-in criteria, custom criteria can be supplied to [`SequencerSealer`] (e.g., to enforce
+/// a chain-specific pubdata budget). Since the sealing decision affects the produced batches, criteria
+/// must depend solely on the provided arguments.
+pub trait SealCriterion: fmt::Debug + Send + Sync + 'static {
     #[allow(clippy::too_many_arguments)]
     fn should_seal(
         &self,
@@ -197,6 +230,33 @@ pub(super) trait SealCriterion: fmt::Debug + Send + Sync + 'static {
     fn prom_criterion_name(&self) -> &'static str;
 }
 
+impl<T: SealCriterion + ?Sized> SealCriterion for Arc<T> {
+    fn should_seal(
+        &self,
+        config: &StateKeeperConfig,
+        block_open_timestamp_ms: u128,
+        tx_count: usize,
+        l1_tx_count: usize,
+        block_data: &SealData,
+        tx_data: &SealData,
+        protocol_version: ProtocolVersionId,
+    ) -> SealResolution {
+        (**self).should_seal(
+            config,
+            block_open_timestamp_ms,
+            tx_count,
+            l1_tx_count,
+            block_data,
+            tx_data,
+            protocol_version,
+        )
+    }
+
+    fn prom_criterion_name(&self) -> &'static str {
+        (**self).prom_criterion_name()
+    }
+}
+
 /// I/O-dependent seal criteria.
 pub trait IoSealCriteria {
     /// Checks whether an L1 batch should be sealed unconditionally (i.e., regardless of metrics
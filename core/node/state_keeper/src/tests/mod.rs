@@ -538,3 +538,40 @@ async fn protocol_upgrade() {
         .run(sealer)
         .await;
 }
+
+#[tokio::test]
+async fn paused_state_keeper_does_not_process_transactions() {
+    let scenario = TestScenario::new()
+        .next_tx("First tx", random_tx(1), successful_exec())
+        .l2_block_sealed("L2 block 1")
+        .batch_sealed("Batch 1");
+    let batch_executor = TestBatchExecutorBuilder::new(&scenario);
+    // The state keeper is stopped externally, so the stop signal sent by IO is ignored.
+    let (io, output_handler) = TestIO::new(watch::channel(false).0, scenario);
+    let state_keeper = ZkSyncStateKeeper::new(
+        Box::new(io),
+        Box::new(batch_executor),
+        output_handler,
+        Arc::new(SequencerSealer::default()),
+        Arc::new(MockReadStorageFactory),
+    );
+    let pause_handle = state_keeper.pause_handle();
+    assert!(pause_handle.pause());
+    assert!(!pause_handle.pause());
+    assert!(pause_handle.is_paused());
+
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let state_keeper_task = tokio::spawn(state_keeper.run(stop_receiver));
+    tokio::time::sleep(POLL_WAIT_DURATION).await;
+    // If the state keeper processed the transaction, the test IO would panic on the unexpected scenario item.
+    assert!(!state_keeper_task.is_finished());
+
+    stop_sender.send_replace(true);
+    tokio::time::timeout(POLL_WAIT_DURATION * 5, state_keeper_task)
+        .await
+        .expect("paused state keeper didn't stop")
+        .unwrap()
+        .unwrap();
+    assert!(pause_handle.resume());
+    assert!(!pause_handle.is_paused());
+}
@@ -0,0 +1,433 @@
+//! Policy engine allowing operators to deny or allow L2 transactions by their sender, target contract
+//! or function selector without code changes.
+
+use std::{
+    collections::HashSet,
+    fmt,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Context as _;
+use serde::Deserialize;
+use tokio::sync::watch;
+use zksync_types::{l2::L2Tx, Address, ExecuteTransactionCommon, Transaction, H256};
+
+/// Target used for audit logs produced by the policy engine.
+const AUDIT_LOG_TARGET: &str = "tx_policy_audit";
+
+/// Function selector (the first 4 bytes of transaction calldata).
+type Selector = [u8; 4];
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawPolicyRules {
+    #[serde(default)]
+    senders: HashSet<Address>,
+    #[serde(default)]
+    contracts: HashSet<Address>,
+    #[serde(default)]
+    selectors: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawTxPolicy {
+    #[serde(default)]
+    deny: RawPolicyRules,
+    #[serde(default)]
+    allow: Option<RawPolicyRules>,
+}
+
+#[derive(Debug, Default)]
+struct PolicyRules {
+    senders: HashSet<Address>,
+    contracts: HashSet<Address>,
+    selectors: HashSet<Selector>,
+}
+
+impl TryFrom<RawPolicyRules> for PolicyRules {
+    type Error = anyhow::Error;
+
+    fn try_from(raw: RawPolicyRules) -> Result<Self, Self::Error> {
+        let selectors = raw.selectors.iter().map(|selector| {
+            let bytes = hex::decode(selector.strip_prefix("0x").unwrap_or(selector))
+                .with_context(|| format!("selector `{selector}` is not a hex string"))?;
+            Selector::try_from(bytes.as_slice())
+                .map_err(|_| anyhow::anyhow!("selector `{selector}` does not have 4 bytes"))
+        });
+        Ok(Self {
+            senders: raw.senders,
+            contracts: raw.contracts,
+            selectors: selectors.collect::<anyhow::Result<_>>()?,
+        })
+    }
+}
+
+impl PolicyRules {
+    fn matches_any(
+        &self,
+        sender: Address,
+        contract: Option<Address>,
+        selector: Option<Selector>,
+    ) -> bool {
+        self.senders.contains(&sender)
+            || contract.is_some_and(|address| self.contracts.contains(&address))
+            || selector.is_some_and(|selector| self.selectors.contains(&selector))
+    }
+}
+
+/// Reason why a transaction violates the [`TxPolicy`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TxPolicyViolation {
+    /// Transaction sender is in the deny list.
+    DeniedSender(Address),
+    /// Transaction target contract is in the deny list.
+    DeniedContract(Address),
+    /// Called function selector is in the deny list.
+    DeniedSelector(Selector),
+    /// Allow list is specified, and the transaction doesn't match any of its entries.
+    NotAllowed,
+}
+
+impl fmt::Display for TxPolicyViolation {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DeniedSender(address) => write!(formatter, "sender {address:?} is denied"),
+            Self::DeniedContract(address) => write!(formatter, "contract {address:?} is denied"),
+            Self::DeniedSelector(selector) => {
+                write!(formatter, "selector 0x{} is denied", hex::encode(selector))
+            }
+            Self::NotAllowed => formatter.write_str("transaction is not in the allow list"),
+        }
+    }
+}
+
+/// Transaction policy loaded from a JSON file with the following format:
+///
+/// ```json
+/// {
+///   "deny": { "senders": ["0x..."], "contracts": ["0x..."], "selectors": ["0xa9059cbb"] },
+///   "allow": { "senders": ["0x..."] }
+/// }
+/// ```
+///
+/// A transaction is denied if it matches any entry in the deny list. If the allow list is specified, a transaction
+/// must additionally match at least one of its entries. The policy only applies to L2 transactions; L1 and upgrade
+/// transactions cannot be censored by the sequencer.
+#[derive(Debug, Default)]
+pub struct TxPolicy {
+    deny: PolicyRules,
+    allow: Option<PolicyRules>,
+}
+
+impl TxPolicy {
+    /// Parses a policy from its JSON representation.
+    pub fn from_json(json: &[u8]) -> anyhow::Result<Self> {
+        let raw: RawTxPolicy = serde_json::from_slice(json).context("malformed policy")?;
+        Ok(Self {
+            deny: raw.deny.try_into().context("invalid deny list")?,
+            allow: raw
+                .allow
+                .map(PolicyRules::try_from)
+                .transpose()
+                .context("invalid allow list")?,
+        })
+    }
+
+    fn check_inner(
+        &self,
+        sender: Address,
+        contract: Option<Address>,
+        calldata: &[u8],
+    ) -> Result<(), TxPolicyViolation> {
+        let selector = calldata
+            .get(..4)
+            .map(|bytes| Selector::try_from(bytes).unwrap());
+        if self.deny.senders.contains(&sender) {
+            return Err(TxPolicyViolation::DeniedSender(sender));
+        }
+        if let Some(contract) = contract.filter(|address| self.deny.contracts.contains(address)) {
+            return Err(TxPolicyViolation::DeniedContract(contract));
+        }
+        if let Some(selector) = selector.filter(|selector| self.deny.selectors.contains(selector)) {
+            return Err(TxPolicyViolation::DeniedSelector(selector));
+        }
+
+        match &self.allow {
+            Some(allow) if !allow.matches_any(sender, contract, selector) => {
+                Err(TxPolicyViolation::NotAllowed)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks whether the L2 transaction satisfies this policy.
+    pub fn check_l2_tx(&self, tx: &L2Tx) -> Result<(), TxPolicyViolation> {
+        self.check_inner(
+            tx.initiator_account(),
+            tx.recipient_account(),
+            tx.execute.calldata(),
+        )
+    }
+
+    /// Checks whether the transaction satisfies this policy. Non-L2 transactions always satisfy the policy.
+    pub fn check(&self, tx: &Transaction) -> Result<(), TxPolicyViolation> {
+        if !matches!(tx.common_data, ExecuteTransactionCommon::L2(_)) {
+            return Ok(());
+        }
+        self.check_inner(
+            tx.initiator_account(),
+            tx.recipient_account(),
+            tx.execute.calldata(),
+        )
+    }
+}
+
+/// Shared [`TxPolicy`] that is hot-reloaded from a file by [`TxPolicyReloadTask`]. Denied transactions
+/// are logged with the `tx_policy_audit` target.
+#[derive(Debug, Clone)]
+pub struct TxPolicyEngine {
+    policy: Arc<RwLock<Arc<TxPolicy>>>,
+}
+
+impl TxPolicyEngine {
+    /// Creates an engine with a fixed policy.
+    pub fn new(policy: TxPolicy) -> Self {
+        Self {
+            policy: Arc::new(RwLock::new(Arc::new(policy))),
+        }
+    }
+
+    /// Loads the policy from the specified file. Returns the engine together with a task reloading
+    /// the policy each time the file is modified.
+    pub async fn load(
+        path: PathBuf,
+        reload_interval: Duration,
+    ) -> anyhow::Result<(Self, TxPolicyReloadTask)> {
+        let (policy, modified_at) = TxPolicyReloadTask::read_policy(&path).await?;
+        tracing::info!(target: AUDIT_LOG_TARGET, "Loaded tx policy from `{}`: {policy:?}", path.display());
+        let this = Self::new(policy);
+        let task = TxPolicyReloadTask {
+            engine: this.clone(),
+            path,
+            reload_interval,
+            modified_at,
+        };
+        Ok((this, task))
+    }
+
+    fn current(&self) -> Arc<TxPolicy> {
+        self.policy.read().expect("tx policy is poisoned").clone()
+    }
+
+    fn replace(&self, policy: TxPolicy) {
+        *self.policy.write().expect("tx policy is poisoned") = Arc::new(policy);
+    }
+
+    fn audit(tx_hash: H256, sender: Address, stage: &str, violation: &TxPolicyViolation) {
+        tracing::info!(
+            target: AUDIT_LOG_TARGET,
+            tx_hash = ?tx_hash,
+            sender = ?sender,
+            stage,
+            "Transaction denied by policy: {violation}"
+        );
+    }
+
+    /// Checks the L2 transaction submitted via API.
+    pub fn check_l2_tx(&self, tx: &L2Tx) -> Result<(), TxPolicyViolation> {
+        let result = self.current().check_l2_tx(tx);
+        if let Err(violation) = &result {
+            Self::audit(tx.hash(), tx.initiator_account(), "api", violation);
+        }
+        result
+    }
+
+    /// Checks the transaction taken from the mempool.
+    pub fn check(&self, tx: &Transaction) -> Result<(), TxPolicyViolation> {
+        let result = self.current().check(tx);
+        if let Err(violation) = &result {
+            Self::audit(tx.hash(), tx.initiator_account(), "mempool", violation);
+        }
+        result
+    }
+}
+
+/// Task reloading [`TxPolicyEngine`] policy when the policy file is modified. If the modified file
+/// cannot be parsed, the previous policy stays in effect.
+#[derive(Debug)]
+pub struct TxPolicyReloadTask {
+    engine: TxPolicyEngine,
+    path: PathBuf,
+    reload_interval: Duration,
+    modified_at: SystemTime,
+}
+
+impl TxPolicyReloadTask {
+    async fn read_policy(path: &Path) -> anyhow::Result<(TxPolicy, SystemTime)> {
+        let modified_at = Self::modification_time(path).await?;
+        let json = tokio::fs::read(path)
+            .await
+            .with_context(|| format!("failed reading tx policy from `{}`", path.display()))?;
+        let policy = TxPolicy::from_json(&json)
+            .with_context(|| format!("failed parsing tx policy from `{}`", path.display()))?;
+        Ok((policy, modified_at))
+    }
+
+    async fn modification_time(path: &Path) -> anyhow::Result<SystemTime> {
+        let metadata = tokio::fs::metadata(path)
+            .await
+            .with_context(|| format!("failed getting metadata for `{}`", path.display()))?;
+        Ok(metadata.modified()?)
+    }
+
+    async fn reload_if_modified(&mut self) -> anyhow::Result<()> {
+        if Self::modification_time(&self.path).await? == self.modified_at {
+            return Ok(());
+        }
+        let (policy, modified_at) = Self::read_policy(&self.path).await?;
+        tracing::info!(
+            target: AUDIT_LOG_TARGET,
+            "Reloaded tx policy from `{}`: {policy:?}",
+            self.path.display()
+        );
+        self.engine.replace(policy);
+        self.modified_at = modified_at;
+        Ok(())
+    }
+
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow() {
+            if let Err(err) = self.reload_if_modified().await {
+                tracing::warn!(
+                    "Failed reloading tx policy; the previous policy stays in effect: {err:#}"
+                );
+            }
+            // Error here corresponds to a timeout w/o `stop_receiver` changed; we're OK with this.
+            tokio::time::timeout(self.reload_interval, stop_receiver.changed())
+                .await
+                .ok();
+        }
+        tracing::info!("Stop signal received, tx policy reload task is shutting down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::Nonce;
+
+    use super::*;
+
+    fn l2_tx(sender: Address, contract: Address, calldata: Vec<u8>) -> L2Tx {
+        let mut tx = L2Tx::new(
+            Some(contract),
+            calldata,
+            Nonce(0),
+            Default::default(),
+            sender,
+            0.into(),
+            vec![],
+            Default::default(),
+        );
+        tx.set_input(vec![], H256::repeat_byte(1));
+        tx
+    }
+
+    #[test]
+    fn parsing_policy() {
+        let policy =
+            TxPolicy::from_json(br#"{ "deny": { "selectors": ["0xa9059cbb"] } }"#).unwrap();
+        assert_eq!(
+            policy.deny.selectors,
+            HashSet::from([[0xa9, 0x05, 0x9c, 0xbb]])
+        );
+        assert!(policy.allow.is_none());
+
+        let err = TxPolicy::from_json(br#"{ "deny": { "selectors": ["0x12"] } }"#).unwrap_err();
+        assert!(format!("{err:#}").contains("4 bytes"), "{err:#}");
+        TxPolicy::from_json(br#"{ "unknown": {} }"#).unwrap_err();
+    }
+
+    #[test]
+    fn checking_transactions() {
+        let denied_sender = Address::repeat_byte(1);
+        let denied_contract = Address::repeat_byte(2);
+        let allowed_sender = Address::repeat_byte(3);
+        let allowed_contract = Address::repeat_byte(4);
+        let json = format!(
+            r#"{{
+                "deny": {{ "senders": ["{denied_sender:?}"], "contracts": ["{denied_contract:?}"], "selectors": ["0xdeadbeef"] }},
+                "allow": {{ "senders": ["{allowed_sender:?}"], "contracts": ["{allowed_contract:?}"] }}
+            }}"#
+        );
+        let policy = TxPolicy::from_json(json.as_bytes()).unwrap();
+
+        let tx = l2_tx(denied_sender, allowed_contract, vec![]);
+        assert_eq!(
+            policy.check_l2_tx(&tx),
+            Err(TxPolicyViolation::DeniedSender(denied_sender))
+        );
+        let tx = l2_tx(allowed_sender, denied_contract, vec![]);
+        assert_eq!(
+            policy.check_l2_tx(&tx),
+            Err(TxPolicyViolation::DeniedContract(denied_contract))
+        );
+        let tx = l2_tx(
+            allowed_sender,
+            allowed_contract,
+            vec![0xde, 0xad, 0xbe, 0xef, 0],
+        );
+        assert_eq!(
+            policy.check_l2_tx(&tx),
+            Err(TxPolicyViolation::DeniedSelector([0xde, 0xad, 0xbe, 0xef]))
+        );
+        let tx = l2_tx(
+            Address::repeat_byte(0xff),
+            Address::repeat_byte(0xfe),
+            vec![],
+        );
+        assert_eq!(policy.check_l2_tx(&tx), Err(TxPolicyViolation::NotAllowed));
+
+        let tx = l2_tx(allowed_sender, Address::repeat_byte(0xfe), vec![1, 2, 3, 4]);
+        policy.check_l2_tx(&tx).unwrap();
+        policy.check(&tx.clone().into()).unwrap();
+        let tx = l2_tx(Address::repeat_byte(0xff), allowed_contract, vec![]);
+        policy.check_l2_tx(&tx).unwrap();
+    }
+
+    #[tokio::test]
+    async fn reloading_policy() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("policy.json");
+        tokio::fs::write(&path, "{}").await.unwrap();
+        let (engine, mut task) = TxPolicyEngine::load(path.clone(), Duration::from_millis(10))
+            .await
+            .unwrap();
+
+        let sender = Address::repeat_byte(1);
+        let tx = l2_tx(sender, Address::repeat_byte(2), vec![]);
+        engine.check_l2_tx(&tx).unwrap();
+
+        let json = format!(r#"{{ "deny": {{ "senders": ["{sender:?}"] }} }}"#);
+        tokio::fs::write(&path, json).await.unwrap();
+        // Make sure that the modification time changes even on file systems with coarse time resolution.
+        task.modified_at = SystemTime::UNIX_EPOCH;
+        task.reload_if_modified().await.unwrap();
+        assert_eq!(
+            engine.check_l2_tx(&tx),
+            Err(TxPolicyViolation::DeniedSender(sender))
+        );
+
+        // Malformed policies are not applied.
+        tokio::fs::write(&path, "???").await.unwrap();
+        task.modified_at = SystemTime::UNIX_EPOCH;
+        task.reload_if_modified().await.unwrap_err();
+        assert_eq!(
+            engine.check_l2_tx(&tx),
+            Err(TxPolicyViolation::DeniedSender(sender))
+        );
+    }
+}
@@ -7,8 +7,9 @@ use zksync_multivm::{
     utils::{get_batch_base_fee, StorageWritesDeduplicator},
 };
 use zksync_types::{
-    commitment::PubdataParams, fee_model::BatchFeeInput, Address, L1BatchNumber, L2BlockNumber,
-    ProtocolVersionId, Transaction,
+    commitment::{L1BatchCommitmentMode, PubdataParams},
+    fee_model::BatchFeeInput,
+    Address, L1BatchNumber, L2BlockNumber, ProtocolVersionId, Transaction,
 };
 
 pub(crate) use self::{l1_batch_updates::L1BatchUpdates, l2_block_updates::L2BlockUpdates};
@@ -195,6 +196,19 @@ impl UpdatesManager {
     pub(crate) fn pending_txs_encoding_size(&self) -> usize {
         self.l1_batch.txs_encoding_size + self.l2_block.txs_encoding_size
     }
+
+    /// Returns the commitment mode of the current L1 batch. Pubdata params are only used starting from
+    /// the gateway protocol version, so for earlier batches, the mode is taken from `default_mode`.
+    pub(crate) fn commitment_mode(
+        &self,
+        default_mode: Option<L1BatchCommitmentMode>,
+    ) -> Option<L1BatchCommitmentMode> {
+        if self.protocol_version.is_pre_gateway() {
+            default_mode
+        } else {
+            Some(self.pubdata_params.pubdata_type)
+        }
+    }
 }
 
 /// Command to seal an L2 block containing all necessary data for it.
@@ -0,0 +1,132 @@
+//! Analytics component aggregating storage usage (storage slot writes and published state diffs)
+//! per contract and L1 batch. Allows operators to identify contracts responsible for pubdata cost spikes.
+
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_dal::{
+    storage_analytics_dal::ContractStorageStats, Connection, ConnectionPool, Core, CoreDal,
+};
+use zksync_types::{Address, L1BatchNumber};
+
+use self::metrics::METRICS;
+
+mod metrics;
+#[cfg(test)]
+mod tests;
+
+/// Component aggregating storage usage for sealed L1 batches. Processes batches sequentially starting from
+/// the earliest batch in the database; processed batches are tracked in Postgres, so the component can be restarted
+/// without losing progress. Aggregated data is removed together with the L1 batch on reverts and pruning.
+#[derive(Debug)]
+pub struct StorageAnalytics {
+    pool: ConnectionPool<Core>,
+    poll_interval: Duration,
+}
+
+impl StorageAnalytics {
+    const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+    pub fn new(pool: ConnectionPool<Core>) -> Self {
+        Self {
+            pool,
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    async fn next_l1_batch_to_process(
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<Option<L1BatchNumber>> {
+        let Some(sealed_l1_batch) = storage.blocks_dal().get_sealed_l1_batch_number().await? else {
+            return Ok(None);
+        };
+        let earliest_l1_batch = storage
+            .blocks_dal()
+            .get_earliest_l1_batch_number()
+            .await?
+            .context("no L1 batches in storage, although there is a sealed batch")?;
+        let last_processed_l1_batch = storage
+            .storage_analytics_dal()
+            .get_last_processed_l1_batch()
+            .await?;
+        // Batches may be pruned while the component isn't running.
+        let next_l1_batch = last_processed_l1_batch
+            .map_or(earliest_l1_batch, |number| number + 1)
+            .max(earliest_l1_batch);
+        Ok((next_l1_batch <= sealed_l1_batch).then_some(next_l1_batch))
+    }
+
+    /// Computes storage usage per contract for the specified sealed L1 batch. Only storage slots with the value changed
+    /// by the batch are taken into account, since only they are published in state diffs.
+    async fn compute_l1_batch_stats(
+        storage: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Vec<ContractStorageStats>> {
+        let state_diff = storage
+            .storage_logs_dal()
+            .get_l1_batch_state_diff(l1_batch_number)
+            .await?;
+        let mut stats = HashMap::<Address, ContractStorageStats>::new();
+        for write in state_diff {
+            let address = write.record.address;
+            let contract_stats = stats.entry(address).or_insert(ContractStorageStats {
+                address,
+                ..ContractStorageStats::default()
+            });
+            contract_stats.storage_writes += 1;
+            contract_stats.initial_writes += u32::from(write.is_initial);
+            contract_stats.pubdata_bytes += write.record.compress().len() as u64;
+        }
+
+        let mut stats: Vec<_> = stats.into_values().collect();
+        stats.sort_unstable_by_key(|stats| stats.address);
+        Ok(stats)
+    }
+
+    /// Processes the next L1 batch if it's available. Returns the number of the processed batch.
+    async fn process_next_l1_batch(&self) -> anyhow::Result<Option<L1BatchNumber>> {
+        let mut storage = self.pool.connection_tagged("storage_analytics").await?;
+        let Some(l1_batch_number) = Self::next_l1_batch_to_process(&mut storage).await? else {
+            return Ok(None);
+        };
+
+        let latency = METRICS.l1_batch_processing_latency.start();
+        let stats = Self::compute_l1_batch_stats(&mut storage, l1_batch_number)
+            .await
+            .with_context(|| {
+                format!("failed computing storage stats for L1 batch #{l1_batch_number}")
+            })?;
+        let mut transaction = storage.start_transaction().await?;
+        transaction
+            .storage_analytics_dal()
+            .insert_l1_batch_stats(l1_batch_number, &stats)
+            .await?;
+        transaction.commit().await?;
+        let latency = latency.observe();
+
+        tracing::debug!(
+            "Processed storage stats for L1 batch #{l1_batch_number} with {} contracts in {latency:?}",
+            stats.len()
+        );
+        METRICS
+            .last_processed_l1_batch
+            .set(l1_batch_number.0.into());
+        Ok(Some(l1_batch_number))
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow_and_update() {
+            if self.process_next_l1_batch().await?.is_some() {
+                continue;
+            }
+            // We don't check the result: if a stop signal is received, we'll return at the start
+            // of the next iteration.
+            tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                .await
+                .ok();
+        }
+        tracing::info!("Stop signal received, storage analytics is shutting down");
+        Ok(())
+    }
+}
@@ -0,0 +1,18 @@
+//! Storage analytics metrics.
+
+use std::time::Duration;
+
+use vise::{Buckets, Gauge, Histogram, Metrics, Unit};
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "storage_analytics")]
+pub(super) struct StorageAnalyticsMetrics {
+    /// Latency of computing and persisting storage stats for a single L1 batch.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub l1_batch_processing_latency: Histogram<Duration>,
+    /// Last L1 batch with persisted storage stats.
+    pub last_processed_l1_batch: Gauge<u64>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<StorageAnalyticsMetrics> = vise::Global::new();
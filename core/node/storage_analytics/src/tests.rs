@@ -0,0 +1,86 @@
+use zksync_dal::fixtures::{ChainFixture, ChainFixtureParams};
+use zksync_types::{AccountTreeId, StorageKey, StorageLog, H256};
+
+use super::*;
+
+/// Seals L1 batches with a single L2 block without transactions each; the storage logs of the block
+/// are taken from `logs_by_l1_batch`.
+async fn seal_l1_batches(storage: &mut Connection<'_, Core>, logs_by_l1_batch: &[&[StorageLog]]) {
+    let mut fixture = ChainFixture::generate(ChainFixtureParams {
+        l1_batch_count: logs_by_l1_batch.len() as u32,
+        l2_blocks_per_l1_batch: 1,
+        txs_per_l2_block: 0,
+        ..ChainFixtureParams::default()
+    });
+    for (l1_batch, logs) in fixture.l1_batches.iter_mut().zip(logs_by_l1_batch) {
+        l1_batch.l2_blocks[0].storage_logs = logs.to_vec();
+    }
+    fixture.insert(storage).await;
+}
+
+fn write_log(address: Address, slot: u64, value: u64) -> StorageLog {
+    let key = StorageKey::new(AccountTreeId::new(address), H256::from_low_u64_be(slot));
+    StorageLog::new_write_log(key, H256::from_low_u64_be(value))
+}
+
+#[tokio::test]
+async fn processing_l1_batches() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    let first_address = Address::repeat_byte(1);
+    let second_address = Address::repeat_byte(2);
+    let first_batch_logs = [
+        write_log(first_address, 0, 1),
+        write_log(first_address, 1, 2),
+        write_log(second_address, 0, 3),
+    ];
+    let second_batch_logs = [
+        // Repeated write
+        write_log(first_address, 0, 10),
+        // No-op write; isn't published
+        write_log(second_address, 0, 3),
+    ];
+    seal_l1_batches(&mut storage, &[&first_batch_logs, &second_batch_logs]).await;
+
+    let analytics = StorageAnalytics::new(pool.clone());
+    for expected_number in [1, 2] {
+        let processed = analytics.process_next_l1_batch().await.unwrap();
+        assert_eq!(processed, Some(L1BatchNumber(expected_number)));
+    }
+    assert_eq!(analytics.process_next_l1_batch().await.unwrap(), None);
+
+    let batch_stats = storage
+        .storage_analytics_dal()
+        .get_top_pubdata_consumers(L1BatchNumber(1)..=L1BatchNumber(1), 10)
+        .await
+        .unwrap();
+    assert_eq!(batch_stats.len(), 2);
+    assert_eq!(batch_stats[0].address, first_address);
+    assert_eq!(batch_stats[0].storage_writes, 2);
+    assert_eq!(batch_stats[0].initial_writes, 2);
+    assert_eq!(batch_stats[1].address, second_address);
+    assert_eq!(batch_stats[1].storage_writes, 1);
+    assert_eq!(batch_stats[1].initial_writes, 1);
+    assert!(batch_stats[0].pubdata_bytes > batch_stats[1].pubdata_bytes);
+
+    let batch_stats = storage
+        .storage_analytics_dal()
+        .get_top_pubdata_consumers(L1BatchNumber(2)..=L1BatchNumber(2), 10)
+        .await
+        .unwrap();
+    assert_eq!(batch_stats.len(), 1);
+    assert_eq!(batch_stats[0].address, first_address);
+    assert_eq!(batch_stats[0].storage_writes, 1);
+    assert_eq!(batch_stats[0].initial_writes, 0);
+    // Repeated writes are published with an enumeration index instead of the full derived key.
+    assert!(batch_stats[0].pubdata_bytes < 64);
+}
+
+#[tokio::test]
+async fn analytics_task_stops_on_signal() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let task = tokio::spawn(StorageAnalytics::new(pool).run(stop_receiver));
+    stop_sender.send_replace(true);
+    task.await.unwrap().unwrap();
+}
@@ -3,6 +3,9 @@
 mod bwip;
 mod playground;
 mod protective_reads;
+mod protective_reads_backfill;
+mod replay;
+mod upgrade_dry_run;
 
 pub use self::{
     bwip::{
@@ -13,4 +16,9 @@ pub use self::{
         VmPlaygroundStorageOptions, VmPlaygroundTasks,
     },
     protective_reads::{ProtectiveReadsIo, ProtectiveReadsWriter, ProtectiveReadsWriterTasks},
+    protective_reads_backfill::ProtectiveReadsBackfill,
+    replay::{BatchReplayer, StorageWriteMismatch},
+    upgrade_dry_run::{
+        ContractCodeChange, UpgradeDryRunReport, UpgradeDryRunner, UpgradeTxOutcome,
+    },
 };
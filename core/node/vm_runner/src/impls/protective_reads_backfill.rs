@@ -0,0 +1,120 @@
+use std::{ops, time::Instant};
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::{L1BatchNumber, L2ChainId, StorageLog};
+use zksync_vm_executor::batch::MainBatchExecutorFactory;
+
+use super::replay::execute_batch;
+use crate::storage::{PostgresLoader, StorageLoader};
+
+/// Backfills protective reads for historical L1 batches that don't have them persisted, e.g. because
+/// the node ran with protective reads persistence in the state keeper and [`ProtectiveReadsWriter`](super::ProtectiveReadsWriter)
+/// both disabled.
+///
+/// Batches are re-executed one by one on top of Postgres storage, so backfilling doesn't require a RocksDB cache.
+/// Batches that already have protective reads are skipped, so the backfill can be safely restarted.
+#[derive(Debug)]
+pub struct ProtectiveReadsBackfill {
+    pool: ConnectionPool<Core>,
+    loader: PostgresLoader,
+    batch_executor_factory: MainBatchExecutorFactory<()>,
+    range: ops::RangeInclusive<L1BatchNumber>,
+}
+
+impl ProtectiveReadsBackfill {
+    /// Number of batches without protective reads queried from Postgres at once.
+    const BATCH_QUERY_LIMIT: usize = 100;
+
+    /// Creates a backfill for the specified range of L1 batches. The genesis batch is always skipped
+    /// since it cannot be re-executed.
+    pub async fn new(
+        pool: ConnectionPool<Core>,
+        chain_id: L2ChainId,
+        range: ops::RangeInclusive<L1BatchNumber>,
+    ) -> anyhow::Result<Self> {
+        let mut loader = PostgresLoader::new(pool.clone(), chain_id).await?;
+        // Snapshots cannot be used for the batches being backfilled anyway since they rely on protective reads.
+        loader.shadow_snapshots(false);
+        let (start, end) = range.into_inner();
+        Ok(Self {
+            pool,
+            loader,
+            batch_executor_factory: MainBatchExecutorFactory::new(false),
+            range: start.max(L1BatchNumber(1))..=end,
+        })
+    }
+
+    /// Runs the backfill until all batches in the range are processed, or a stop signal is received.
+    pub async fn run(mut self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let (mut next_batch, last_batch) = self.range.clone().into_inner();
+        tracing::info!(
+            "Starting protective reads backfill for L1 batches {next_batch}..={last_batch}"
+        );
+
+        while next_batch <= last_batch {
+            let mut conn = self
+                .pool
+                .connection_tagged("protective_reads_backfill")
+                .await?;
+            let batches = conn
+                .storage_logs_dedup_dal()
+                .get_l1_batches_without_protective_reads(
+                    next_batch,
+                    last_batch,
+                    Self::BATCH_QUERY_LIMIT,
+                )
+                .await?;
+            drop(conn);
+
+            let Some(&last_queried_batch) = batches.last() else {
+                break;
+            };
+            for number in batches {
+                if *stop_receiver.borrow() {
+                    tracing::info!("Received stop signal, protective reads backfill is shut down");
+                    return Ok(());
+                }
+                self.backfill_batch(number).await?;
+            }
+            next_batch = last_queried_batch + 1;
+        }
+
+        tracing::info!("Finished protective reads backfill for L1 batches up to {last_batch}");
+        Ok(())
+    }
+
+    async fn backfill_batch(&mut self, number: L1BatchNumber) -> anyhow::Result<()> {
+        let started_at = Instant::now();
+        let Some((batch_data, storage)) = self.loader.load_batch(number).await? else {
+            // Can happen e.g. for the first batch after snapshot recovery.
+            tracing::warn!("Cannot load inputs for L1 batch #{number}; skipping it");
+            return Ok(());
+        };
+        let (finished_batch, _) =
+            execute_batch(&mut self.batch_executor_factory, batch_data, storage)
+                .await
+                .with_context(|| format!("failed re-executing L1 batch #{number}"))?;
+        let protective_reads: Vec<StorageLog> = finished_batch
+            .final_execution_state
+            .deduplicated_storage_logs
+            .into_iter()
+            .filter(|log| !log.is_write())
+            .collect();
+
+        let mut conn = self
+            .pool
+            .connection_tagged("protective_reads_backfill")
+            .await?;
+        conn.storage_logs_dedup_dal()
+            .insert_protective_reads(number, &protective_reads)
+            .await?;
+        tracing::info!(
+            "Backfilled {} protective reads for L1 batch #{number} in {:?}",
+            protective_reads.len(),
+            started_at.elapsed()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,340 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops,
+    time::Instant,
+};
+
+use anyhow::Context as _;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_state::OwnedStorage;
+use zksync_types::{vm::FastVmMode, L1BatchNumber, L2BlockNumber, L2ChainId, StorageLog, H256};
+use zksync_vm_executor::batch::MainBatchExecutorFactory;
+use zksync_vm_interface::{executor::BatchExecutorFactory, FinishedL1Batch, L2BlockEnv};
+
+use crate::{
+    storage::{PostgresLoader, StorageLoader},
+    BatchExecuteData,
+};
+
+/// Mismatch of a single storage slot between the persisted and replayed L1 batch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StorageWriteMismatch {
+    /// Hashed key of the slot.
+    pub hashed_key: H256,
+    /// Value persisted in Postgres, or `None` if the slot wasn't written to in the persisted batch.
+    pub persisted: Option<H256>,
+    /// Value produced by replaying the batch, or `None` if the slot wasn't written to during replay.
+    pub replayed: Option<H256>,
+}
+
+/// Storage writes (hashed key -> value) produced by each L2 block of an L1 batch.
+pub(super) type L2BlockWrites = Vec<(L2BlockNumber, HashMap<H256, H256>)>;
+
+/// Replays L1 batches persisted in Postgres and checks that the replay produces the same outputs.
+///
+/// Batches are replayed using inputs recorded by the state keeper: transactions in their execution order,
+/// L2 block timestamps, fee inputs and the protocol version of each batch. Replay checks that
+/// storage writes in each L2 block are identical to the persisted ones (so that a divergence is pinpointed
+/// to the first diverging L2 block), that final storage writes for the batch are identical (which means that
+/// the resulting state root hash is identical as well), and that user L2-to-L1 logs are identical.
+#[derive(Debug)]
+pub struct BatchReplayer {
+    pool: ConnectionPool<Core>,
+    loader: PostgresLoader,
+    batch_executor_factory: MainBatchExecutorFactory<()>,
+}
+
+impl BatchReplayer {
+    /// Creates a new replayer.
+    pub async fn new(
+        pool: ConnectionPool<Core>,
+        chain_id: L2ChainId,
+        vm_mode: FastVmMode,
+    ) -> anyhow::Result<Self> {
+        let mut loader = PostgresLoader::new(pool.clone(), chain_id).await?;
+        // Replay should check determinism of execution, not consistency of storage snapshots.
+        loader.shadow_snapshots(false);
+        let mut batch_executor_factory = MainBatchExecutorFactory::new(false);
+        batch_executor_factory.set_fast_vm_mode(vm_mode);
+        Ok(Self {
+            pool,
+            loader,
+            batch_executor_factory,
+        })
+    }
+
+    /// Replays all L1 batches in the specified range, stopping on the first divergence.
+    pub async fn replay_range(
+        &mut self,
+        range: ops::RangeInclusive<L1BatchNumber>,
+    ) -> anyhow::Result<()> {
+        let (start, end) = range.into_inner();
+        anyhow::ensure!(
+            start > L1BatchNumber(0),
+            "genesis L1 batch cannot be replayed"
+        );
+        for number in start.0..=end.0 {
+            self.replay_batch(L1BatchNumber(number)).await?;
+        }
+        Ok(())
+    }
+
+    /// Replays a single L1 batch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the batch inputs cannot be loaded, or if the replay diverges from the persisted batch.
+    pub async fn replay_batch(&mut self, number: L1BatchNumber) -> anyhow::Result<()> {
+        let started_at = Instant::now();
+        let (batch_data, storage) = self
+            .loader
+            .load_batch(number)
+            .await?
+            .with_context(|| format!("L1 batch #{number} is not sealed or was pruned"))?;
+        let (finished_batch, l2_block_writes) =
+            execute_batch(&mut self.batch_executor_factory, batch_data, storage)
+                .await
+                .with_context(|| format!("failed replaying L1 batch #{number}"))?;
+
+        let mut conn = self.pool.connection_tagged("vm_runner").await?;
+        let persisted_l2_block_writes = conn
+            .storage_logs_dal()
+            .get_touched_slots_by_l2_block(number)
+            .await?;
+        let persisted_writes = conn
+            .storage_logs_dal()
+            .get_touched_slots_for_l1_batch(number)
+            .await?;
+        let header = conn
+            .blocks_dal()
+            .get_l1_batch_header(number)
+            .await?
+            .with_context(|| format!("L1 batch #{number} disappeared from storage"))?;
+        drop(conn);
+
+        if let Some((l2_block_number, mismatches)) =
+            first_l2_block_mismatch(&persisted_l2_block_writes, &l2_block_writes)
+        {
+            anyhow::bail!(
+                "replay of L1 batch #{number} diverged in L2 block #{l2_block_number} in {} storage slots; \
+                 first mismatches: {:?}",
+                mismatches.len(),
+                &mismatches[..mismatches.len().min(10)]
+            );
+        }
+
+        let final_state = &finished_batch.final_execution_state;
+        let replayed_writes = write_values(&final_state.deduplicated_storage_logs);
+        let mismatches = diff_storage_writes(&persisted_writes, &replayed_writes);
+        if !mismatches.is_empty() {
+            anyhow::bail!(
+                "replay of L1 batch #{number} diverged in {} storage slots; first mismatches: {:?}",
+                mismatches.len(),
+                &mismatches[..mismatches.len().min(10)]
+            );
+        }
+        anyhow::ensure!(
+            header.l2_to_l1_logs == final_state.user_l2_to_l1_logs,
+            "replay of L1 batch #{number} diverged in user L2-to-L1 logs: persisted {:?}, replayed {:?}",
+            header.l2_to_l1_logs,
+            final_state.user_l2_to_l1_logs
+        );
+
+        tracing::info!(
+            "Replayed L1 batch #{number} with {} storage writes in {:?}",
+            persisted_writes.len(),
+            started_at.elapsed()
+        );
+        Ok(())
+    }
+}
+
+/// Executes an L1 batch from scratch (i.e., without using RocksDB cache) using the provided inputs.
+/// Besides the finished batch, returns storage writes produced by each L2 block, attributed to L2 blocks
+/// in the same way as by the state keeper.
+pub(super) async fn execute_batch(
+    batch_executor_factory: &mut MainBatchExecutorFactory<()>,
+    batch_data: BatchExecuteData,
+    storage: OwnedStorage,
+) -> anyhow::Result<(FinishedL1Batch, L2BlockWrites)> {
+    let mut batch_executor = batch_executor_factory.init_batch(
+        storage,
+        batch_data.l1_batch_env,
+        batch_data.system_env,
+        batch_data.pubdata_params,
+    );
+
+    let l2_block_count = batch_data.l2_blocks.len();
+    let mut l2_block_writes = Vec::with_capacity(l2_block_count);
+    let mut has_fictive_l2_block = false;
+    for (i, l2_block) in batch_data.l2_blocks.into_iter().enumerate() {
+        has_fictive_l2_block = i + 1 == l2_block_count && l2_block.txs.is_empty();
+        let block_env = L2BlockEnv::from_l2_block_data(&l2_block);
+        if i > 0 {
+            // First L2 block in every batch is already preloaded
+            batch_executor
+                .start_next_l2_block(block_env)
+                .await
+                .with_context(|| {
+                    format!("failed starting L2 block with {block_env:?} in batch executor")
+                })?;
+        }
+
+        let mut writes = HashMap::new();
+        for tx in l2_block.txs {
+            let tx_hash = tx.hash();
+            let exec_result = batch_executor
+                .execute_tx(tx)
+                .await
+                .with_context(|| format!("failed executing transaction {tx_hash:?}"))?;
+            anyhow::ensure!(
+                !exec_result.was_halted(),
+                "transaction {tx_hash:?} in L2 block #{} was halted during re-execution",
+                l2_block.number
+            );
+            let logs = &exec_result.tx_result.logs.storage_logs;
+            writes.extend(write_values(logs.iter().map(|log| &log.log)));
+        }
+        l2_block_writes.push((l2_block.number, writes));
+    }
+
+    let (finished_batch, _) = batch_executor
+        .finish_batch()
+        .await
+        .context("failed executing batch tip")?;
+    if has_fictive_l2_block {
+        // Writes in the fictive L2 block are produced by the batch tip.
+        let logs = &finished_batch.block_tip_execution_result.logs.storage_logs;
+        let (_, writes) = l2_block_writes.last_mut().unwrap();
+        writes.extend(write_values(logs.iter().map(|log| &log.log)));
+    }
+    Ok((finished_batch, l2_block_writes))
+}
+
+/// Returns the latest written value for each slot written to in `logs`.
+fn write_values<'a>(logs: impl IntoIterator<Item = &'a StorageLog>) -> HashMap<H256, H256> {
+    logs.into_iter()
+        .filter(|log| log.is_write())
+        .map(|log| (log.key.hashed_key(), log.value))
+        .collect()
+}
+
+/// Returns the first replayed L2 block with writes diverging from the persisted ones.
+fn first_l2_block_mismatch(
+    persisted: &BTreeMap<L2BlockNumber, HashMap<H256, H256>>,
+    replayed: &L2BlockWrites,
+) -> Option<(L2BlockNumber, Vec<StorageWriteMismatch>)> {
+    let no_writes = HashMap::new();
+    replayed.iter().find_map(|(number, replayed_writes)| {
+        let persisted_writes = persisted.get(number).unwrap_or(&no_writes);
+        let mismatches = diff_storage_writes(persisted_writes, replayed_writes);
+        (!mismatches.is_empty()).then_some((*number, mismatches))
+    })
+}
+
+fn diff_storage_writes(
+    persisted: &HashMap<H256, H256>,
+    replayed: &HashMap<H256, H256>,
+) -> Vec<StorageWriteMismatch> {
+    let mut mismatches: Vec<_> = persisted
+        .iter()
+        .filter(|(key, value)| replayed.get(key) != Some(value))
+        .map(|(&hashed_key, &value)| StorageWriteMismatch {
+            hashed_key,
+            persisted: Some(value),
+            replayed: replayed.get(&hashed_key).copied(),
+        })
+        .collect();
+    mismatches.extend(
+        replayed
+            .iter()
+            .filter(|(key, _)| !persisted.contains_key(key))
+            .map(|(&hashed_key, &value)| StorageWriteMismatch {
+                hashed_key,
+                persisted: None,
+                replayed: Some(value),
+            }),
+    );
+    mismatches.sort_unstable_by_key(|mismatch| mismatch.hashed_key);
+    mismatches
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{AccountTreeId, Address, StorageKey};
+
+    use super::*;
+
+    #[test]
+    fn diffing_storage_writes() {
+        let key = |idx: u64| {
+            StorageKey::new(
+                AccountTreeId::new(Address::repeat_byte(1)),
+                H256::from_low_u64_be(idx),
+            )
+        };
+        let replayed = [
+            StorageLog::new_write_log(key(1), H256::repeat_byte(1)),
+            StorageLog::new_read_log(key(2), H256::repeat_byte(2)),
+            StorageLog::new_write_log(key(3), H256::repeat_byte(3)),
+            StorageLog::new_write_log(key(4), H256::repeat_byte(4)),
+        ];
+        let replayed = write_values(&replayed);
+        let mut persisted = HashMap::from([
+            (key(1).hashed_key(), H256::repeat_byte(1)),
+            (key(3).hashed_key(), H256::repeat_byte(3)),
+            (key(4).hashed_key(), H256::repeat_byte(4)),
+        ]);
+        assert_eq!(diff_storage_writes(&persisted, &replayed), []);
+
+        persisted.insert(key(3).hashed_key(), H256::repeat_byte(0xff));
+        persisted.insert(key(5).hashed_key(), H256::repeat_byte(5));
+        persisted.remove(&key(4).hashed_key());
+        let mut expected = vec![
+            StorageWriteMismatch {
+                hashed_key: key(3).hashed_key(),
+                persisted: Some(H256::repeat_byte(0xff)),
+                replayed: Some(H256::repeat_byte(3)),
+            },
+            StorageWriteMismatch {
+                hashed_key: key(4).hashed_key(),
+                persisted: None,
+                replayed: Some(H256::repeat_byte(4)),
+            },
+            StorageWriteMismatch {
+                hashed_key: key(5).hashed_key(),
+                persisted: Some(H256::repeat_byte(5)),
+                replayed: None,
+            },
+        ];
+        expected.sort_unstable_by_key(|mismatch| mismatch.hashed_key);
+        assert_eq!(diff_storage_writes(&persisted, &replayed), expected);
+    }
+
+    #[test]
+    fn finding_first_diverging_l2_block() {
+        let writes = |idx: u8| HashMap::from([(H256::repeat_byte(idx), H256::repeat_byte(idx))]);
+        let replayed = vec![
+            (L2BlockNumber(1), writes(1)),
+            (L2BlockNumber(2), HashMap::new()),
+            (L2BlockNumber(3), writes(3)),
+        ];
+        let mut persisted =
+            BTreeMap::from([(L2BlockNumber(1), writes(1)), (L2BlockNumber(3), writes(3))]);
+        assert_eq!(first_l2_block_mismatch(&persisted, &replayed), None);
+
+        // Write attributed to another L2 block.
+        persisted.insert(L2BlockNumber(2), writes(3));
+        persisted.remove(&L2BlockNumber(3));
+        let (number, mismatches) = first_l2_block_mismatch(&persisted, &replayed).unwrap();
+        assert_eq!(number, L2BlockNumber(2));
+        assert_eq!(
+            mismatches,
+            [StorageWriteMismatch {
+                hashed_key: H256::repeat_byte(3),
+                persisted: Some(H256::repeat_byte(3)),
+                replayed: None,
+            }]
+        );
+    }
+}
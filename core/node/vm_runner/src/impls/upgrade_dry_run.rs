@@ -0,0 +1,345 @@
+use std::{collections::HashMap, time::Instant};
+
+use anyhow::Context as _;
+use zksync_contracts::{BaseSystemContracts, BaseSystemContractsHashes, SystemContractCode};
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_state::OwnedStorage;
+use zksync_types::{
+    bytecode::BytecodeHash, h256_to_address, protocol_version::ProtocolSemanticVersion, Address,
+    L2ChainId, ProtocolUpgrade, StorageLog, Transaction, ACCOUNT_CODE_STORAGE_ADDRESS, H256,
+};
+use zksync_vm_executor::{batch::MainBatchExecutorFactory, storage::l1_batch_params};
+use zksync_vm_interface::{executor::BatchExecutorFactory, ExecutionResult};
+
+/// Change of the deployed code of a contract caused by a protocol upgrade.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContractCodeChange {
+    pub address: Address,
+    /// Code hash before the upgrade, or `None` if the contract wasn't deployed.
+    pub old_hash: Option<H256>,
+    /// Code hash after the upgrade.
+    pub new_hash: H256,
+}
+
+/// Outcome of executing the L2 upgrade transaction.
+#[derive(Debug, Clone)]
+pub struct UpgradeTxOutcome {
+    pub hash: H256,
+    pub result: ExecutionResult,
+    pub gas_used: u64,
+}
+
+/// Report produced by [`UpgradeDryRunner`].
+#[derive(Debug, Clone)]
+pub struct UpgradeDryRunReport {
+    /// Protocol version of the latest sealed L2 block the upgrade was applied on top of.
+    pub from_version: ProtocolSemanticVersion,
+    pub to_version: ProtocolSemanticVersion,
+    pub old_base_system_contracts: BaseSystemContractsHashes,
+    pub new_base_system_contracts: BaseSystemContractsHashes,
+    /// Outcome of the upgrade transaction, or `None` if the upgrade doesn't have one.
+    pub upgrade_tx: Option<UpgradeTxOutcome>,
+    /// Result of executing the batch tip after the upgrade transaction.
+    pub batch_tip_result: ExecutionResult,
+    /// Changes of deployed contract code (normally, force deployments of system contracts), ordered by address.
+    pub code_changes: Vec<ContractCodeChange>,
+}
+
+impl UpgradeDryRunReport {
+    /// Checks whether the upgrade was executed without failures.
+    pub fn is_successful(&self) -> bool {
+        let tx_succeeded = self
+            .upgrade_tx
+            .as_ref()
+            .map_or(true, |tx| !tx.result.is_failed());
+        tx_succeeded && !self.batch_tip_result.is_failed()
+    }
+}
+
+/// Executes a pending protocol upgrade on top of the latest sealed L2 block without persisting any changes.
+///
+/// The upgrade is executed in a new L1 batch following the latest sealed one, with base system contracts and
+/// the upgrade transaction taken from the upgrade. Bytecodes of new base system contracts are looked up among
+/// factory deps of the upgrade transaction, sealed factory deps and base system contracts staged ahead of the upgrade.
+#[derive(Debug)]
+pub struct UpgradeDryRunner {
+    pool: ConnectionPool<Core>,
+    chain_id: L2ChainId,
+    batch_executor_factory: MainBatchExecutorFactory<()>,
+}
+
+impl UpgradeDryRunner {
+    pub fn new(pool: ConnectionPool<Core>, chain_id: L2ChainId) -> Self {
+        Self {
+            pool,
+            chain_id,
+            batch_executor_factory: MainBatchExecutorFactory::new(false),
+        }
+    }
+
+    /// Dry-runs the specified upgrade.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the upgrade cannot be applied to the current state (e.g., it's not newer than
+    /// the current protocol version, or bytecodes of base system contracts are missing), or if the batch executor
+    /// fails. Reverts and halts of the upgrade transaction are not errors; they are reported in [`UpgradeDryRunReport`].
+    pub async fn dry_run(
+        &mut self,
+        upgrade: ProtocolUpgrade,
+    ) -> anyhow::Result<UpgradeDryRunReport> {
+        let started_at = Instant::now();
+        let mut conn = self.pool.connection_tagged("vm_runner").await?;
+        let l1_batch_number = conn
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await?
+            .context("no sealed L1 batches")?;
+        let (_, last_l2_block_number) = conn
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(l1_batch_number)
+            .await?
+            .with_context(|| format!("L2 blocks for L1 batch #{l1_batch_number} are missing"))?;
+        let last_l2_block = conn
+            .blocks_dal()
+            .get_l2_block_header(last_l2_block_number)
+            .await?
+            .with_context(|| format!("L2 block #{last_l2_block_number} disappeared"))?;
+        // The previous batch hash only influences the batch commitment, so it's fine to use a dummy value
+        // if the Merkle tree lags behind.
+        let previous_batch_hash = conn
+            .blocks_dal()
+            .get_l1_batch_state_root(l1_batch_number)
+            .await?
+            .unwrap_or_default();
+
+        let current_minor_version = last_l2_block
+            .protocol_version
+            .context("latest L2 block doesn't have protocol version")?;
+        let current_version = conn
+            .protocol_versions_dal()
+            .get_protocol_version_with_latest_patch(current_minor_version)
+            .await?
+            .with_context(|| format!("protocol version {current_minor_version} is missing"))?;
+        anyhow::ensure!(
+            upgrade.version > current_version.version,
+            "upgrade to {} is not newer than the current protocol version {}",
+            upgrade.version,
+            current_version.version
+        );
+
+        let new_version = current_version.apply_upgrade(upgrade, None);
+        let upgrade_tx_deps = new_version
+            .tx
+            .as_ref()
+            .map(|tx| tx.execute.factory_deps.as_slice())
+            .unwrap_or_default();
+        let base_system_contracts = load_base_system_contracts(
+            &mut conn,
+            &new_version.base_system_contracts_hashes,
+            upgrade_tx_deps,
+        )
+        .await?;
+        drop(conn);
+
+        let l1_batch_timestamp = unix_timestamp().max(last_l2_block.timestamp + 1);
+        let (system_env, l1_batch_env) = l1_batch_params(
+            l1_batch_number + 1,
+            last_l2_block.fee_account_address,
+            l1_batch_timestamp,
+            previous_batch_hash,
+            last_l2_block.batch_fee_input,
+            last_l2_block_number + 1,
+            last_l2_block.hash,
+            base_system_contracts,
+            u32::MAX,
+            new_version.version.minor,
+            1,
+            self.chain_id,
+        );
+        let storage_conn = self.pool.connection_tagged("vm_runner").await?;
+        let storage = OwnedStorage::postgres(storage_conn, l1_batch_number).await?;
+        let mut batch_executor = self.batch_executor_factory.init_batch(
+            storage.into(),
+            l1_batch_env,
+            system_env,
+            last_l2_block.pubdata_params,
+        );
+
+        let upgrade_tx = if let Some(tx) = new_version.tx {
+            let tx = Transaction::from(tx);
+            let hash = tx.hash();
+            let result = batch_executor
+                .execute_tx(tx)
+                .await
+                .with_context(|| format!("failed executing upgrade transaction {hash:?}"))?;
+            Some(UpgradeTxOutcome {
+                hash,
+                result: result.tx_result.result.clone(),
+                gas_used: result.tx_result.statistics.gas_used,
+            })
+        } else {
+            None
+        };
+        let (finished_batch, _) = batch_executor
+            .finish_batch()
+            .await
+            .context("failed executing batch tip")?;
+
+        let logs = &finished_batch
+            .final_execution_state
+            .deduplicated_storage_logs;
+        let code_keys: Vec<_> = code_writes(logs).map(|log| log.key.hashed_key()).collect();
+        let mut conn = self.pool.connection_tagged("vm_runner").await?;
+        let old_values = conn
+            .storage_logs_dal()
+            .get_storage_values(&code_keys, last_l2_block_number)
+            .await?;
+        let code_changes = collect_code_changes(logs, &old_values);
+
+        tracing::info!(
+            "Dry-ran upgrade from {} to {} in {:?}",
+            current_version.version,
+            new_version.version,
+            started_at.elapsed()
+        );
+        Ok(UpgradeDryRunReport {
+            from_version: current_version.version,
+            to_version: new_version.version,
+            old_base_system_contracts: current_version.base_system_contracts_hashes,
+            new_base_system_contracts: new_version.base_system_contracts_hashes,
+            upgrade_tx,
+            batch_tip_result: finished_batch.block_tip_execution_result.result,
+            code_changes,
+        })
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("incorrect system time")
+        .as_secs()
+}
+
+async fn load_base_system_contracts(
+    conn: &mut Connection<'_, Core>,
+    hashes: &BaseSystemContractsHashes,
+    upgrade_tx_deps: &[Vec<u8>],
+) -> anyhow::Result<BaseSystemContracts> {
+    let upgrade_tx_deps: HashMap<_, _> = upgrade_tx_deps
+        .iter()
+        .map(|bytecode| (BytecodeHash::for_bytecode(bytecode).value(), bytecode))
+        .collect();
+
+    let mut contracts = vec![];
+    let all_hashes = [
+        Some(hashes.bootloader),
+        Some(hashes.default_aa),
+        hashes.evm_emulator,
+    ];
+    for hash in all_hashes.into_iter().flatten() {
+        let code = if let Some(&bytecode) = upgrade_tx_deps.get(&hash) {
+            bytecode.clone()
+        } else if let Some(bytecode) = conn.factory_deps_dal().get_sealed_factory_dep(hash).await? {
+            bytecode
+        } else {
+            conn.staged_base_system_contracts_dal()
+                .get_staged_bytecode(hash)
+                .await?
+                .with_context(|| {
+                    format!(
+                        "bytecode with hash {hash:?} is neither included into the upgrade, nor present in the database; \
+                         stage it ahead of the upgrade"
+                    )
+                })?
+        };
+        contracts.push(SystemContractCode { code, hash });
+    }
+
+    let mut contracts = contracts.into_iter();
+    Ok(BaseSystemContracts {
+        bootloader: contracts.next().unwrap(),
+        default_aa: contracts.next().unwrap(),
+        evm_emulator: contracts.next(),
+    })
+}
+
+/// Returns writes to the account code storage, i.e., changes of deployed contract code.
+fn code_writes(logs: &[StorageLog]) -> impl Iterator<Item = &StorageLog> + '_ {
+    logs.iter()
+        .filter(|log| log.is_write() && *log.key.address() == ACCOUNT_CODE_STORAGE_ADDRESS)
+}
+
+fn collect_code_changes(
+    logs: &[StorageLog],
+    old_values: &HashMap<H256, Option<H256>>,
+) -> Vec<ContractCodeChange> {
+    let mut changes: Vec<_> = code_writes(logs)
+        .filter_map(|log| {
+            let old_hash = old_values
+                .get(&log.key.hashed_key())
+                .copied()
+                .flatten()
+                .filter(|hash| !hash.is_zero());
+            (old_hash != Some(log.value)).then(|| ContractCodeChange {
+                address: h256_to_address(log.key.key()),
+                old_hash,
+                new_hash: log.value,
+            })
+        })
+        .collect();
+    changes.sort_unstable_by_key(|change| change.address);
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{AccountTreeId, StorageKey};
+
+    use super::*;
+
+    #[test]
+    fn collecting_code_changes() {
+        let code_key = |address: u64| {
+            StorageKey::new(
+                AccountTreeId::new(ACCOUNT_CODE_STORAGE_ADDRESS),
+                H256::from_low_u64_be(address),
+            )
+        };
+        let other_key = StorageKey::new(
+            AccountTreeId::new(Address::repeat_byte(1)),
+            H256::from_low_u64_be(0x8002),
+        );
+        let logs = [
+            StorageLog::new_write_log(code_key(0x8008), H256::repeat_byte(1)),
+            StorageLog::new_write_log(code_key(0x8002), H256::repeat_byte(2)),
+            StorageLog::new_write_log(code_key(0x8003), H256::repeat_byte(3)),
+            StorageLog::new_read_log(code_key(0x8004), H256::repeat_byte(4)),
+            StorageLog::new_write_log(other_key, H256::repeat_byte(5)),
+        ];
+        let old_values = HashMap::from([
+            (code_key(0x8002).hashed_key(), Some(H256::repeat_byte(0xff))),
+            // Unchanged code must not be reported.
+            (code_key(0x8003).hashed_key(), Some(H256::repeat_byte(3))),
+            (code_key(0x8008).hashed_key(), Some(H256::zero())),
+        ]);
+
+        let changes = collect_code_changes(&logs, &old_values);
+        assert_eq!(
+            changes,
+            [
+                ContractCodeChange {
+                    address: Address::from_low_u64_be(0x8002),
+                    old_hash: Some(H256::repeat_byte(0xff)),
+                    new_hash: H256::repeat_byte(2),
+                },
+                ContractCodeChange {
+                    address: Address::from_low_u64_be(0x8008),
+                    old_hash: None,
+                    new_hash: H256::repeat_byte(1),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use tokio::sync::watch;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+use zksync_test_contracts::Account;
+use zksync_types::{L1BatchNumber, L2ChainId};
+
+use super::*;
+use crate::impls::ProtectiveReadsBackfill;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn backfilling_protective_reads() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    let genesis_params = GenesisParams::mock();
+    insert_genesis_batch(&mut conn, &genesis_params)
+        .await
+        .unwrap();
+    let mut accounts = vec![Account::random(), Account::random()];
+    fund(&mut conn, &accounts).await;
+    store_l1_batches(&mut conn, 1..=3, &genesis_params, &mut accounts)
+        .await
+        .unwrap();
+    drop(conn);
+    storage_writer::write_storage_logs(pool.clone(), false).await;
+
+    let mut conn = pool.connection().await.unwrap();
+    let batches_without_reads = conn
+        .storage_logs_dedup_dal()
+        .get_l1_batches_without_protective_reads(L1BatchNumber(1), L1BatchNumber(3), 10)
+        .await
+        .unwrap();
+    assert_eq!(
+        batches_without_reads,
+        [L1BatchNumber(1), L1BatchNumber(2), L1BatchNumber(3)]
+    );
+
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let backfill = ProtectiveReadsBackfill::new(
+        pool.clone(),
+        L2ChainId::default(),
+        L1BatchNumber(0)..=L1BatchNumber(2),
+    )
+    .await
+    .unwrap();
+    backfill.run(stop_receiver.clone()).await.unwrap();
+
+    let batches_without_reads = conn
+        .storage_logs_dedup_dal()
+        .get_l1_batches_without_protective_reads(L1BatchNumber(1), L1BatchNumber(3), 10)
+        .await
+        .unwrap();
+    assert_eq!(batches_without_reads, [L1BatchNumber(3)]);
+    let reads = conn
+        .storage_logs_dedup_dal()
+        .get_protective_reads_for_l1_batch(L1BatchNumber(1))
+        .await
+        .unwrap();
+    assert!(!reads.is_empty());
+
+    // Restarting the backfill should only process the remaining batch.
+    let backfill = ProtectiveReadsBackfill::new(
+        pool.clone(),
+        L2ChainId::default(),
+        L1BatchNumber(1)..=L1BatchNumber(3),
+    )
+    .await
+    .unwrap();
+    backfill.run(stop_receiver).await.unwrap();
+    let batches_without_reads = conn
+        .storage_logs_dedup_dal()
+        .get_l1_batches_without_protective_reads(L1BatchNumber(1), L1BatchNumber(3), 10)
+        .await
+        .unwrap();
+    assert_eq!(batches_without_reads, []);
+}
@@ -0,0 +1,671 @@
+//! Component finalizing L2 -> L1 withdrawals on L1 once the L1 batch containing a withdrawal is executed.
+//! Withdrawals of the base token and of ERC20 tokens bridged via the shared bridge are supported.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_config::{
+    configs::{withdrawal_finalizer::WithdrawalTokenLimit, WithdrawalFinalizerConfig},
+    ContractsConfig,
+};
+use zksync_contracts::L1_SHARED_BRIDGE_CONTRACT;
+use zksync_dal::{
+    withdrawal_finalizer_dal::{
+        WithdrawalFinalization, WithdrawalFinalizationStatus, WithdrawalFinalizationTx,
+    },
+    Connection, ConnectionPool, Core, CoreDal,
+};
+use zksync_eth_client::{BoundEthInterface, CallFunctionArgs, Options};
+use zksync_node_fee_model::l1_gas_price::TxParamsProvider;
+use zksync_types::{
+    ethabi::Token,
+    h256_to_address,
+    l2_to_l1_log::{l2_to_l1_log_proof, L2ToL1Log},
+    web3::{keccak256, BlockNumber},
+    Address, L1BatchNumber, L1ChainId, L2ChainId, ProtocolVersionId, H256, L1_MESSENGER_ADDRESS,
+    U256,
+};
+use zksync_vm_interface::VmEvent;
+
+use self::{
+    metrics::{WithdrawalStatus, METRICS},
+    withdrawal::{ParsedWithdrawal, WithdrawalParser},
+};
+
+mod metrics;
+#[cfg(test)]
+mod tests;
+mod withdrawal;
+
+/// Withdrawal found in an L1 batch.
+#[derive(Debug)]
+struct BatchWithdrawal {
+    /// Index of the L2-to-L1 log for the withdrawal message among all L2-to-L1 logs in the batch.
+    l2_message_index: u32,
+    l2_tx_number_in_batch: u16,
+    message: Vec<u8>,
+    parsed: ParsedWithdrawal,
+}
+
+/// Component finalizing withdrawals from executed L1 batches by calling `finalizeWithdrawal` on the L1 shared bridge.
+///
+/// Batches are processed sequentially starting from the earliest batch in the database (or the configured start batch,
+/// whichever is greater). Only withdrawals of the configured tokens with amounts within the configured limits
+/// are finalized, and the number of sent transactions per hour is capped. At most one finalization
+/// transaction is in flight at any time; the next withdrawal is processed only after the transaction is mined.
+/// If the transaction isn't mined in the configured number of L1 blocks, it is resent with the same nonce
+/// and bumped fees.
+/// Processing progress and the outcome for each withdrawal are persisted in Postgres, so the component can be
+/// restarted without sending duplicate transactions.
+#[derive(Debug)]
+pub struct WithdrawalFinalizer {
+    pool: ConnectionPool<Core>,
+    config: WithdrawalFinalizerConfig,
+    token_limits: HashMap<Address, WithdrawalTokenLimit>,
+    parser: WithdrawalParser,
+    l1_client: Box<dyn BoundEthInterface>,
+    tx_params: Arc<dyn TxParamsProvider>,
+    l1_shared_bridge_address: Address,
+    l1_chain_id: L1ChainId,
+    l2_chain_id: L2ChainId,
+}
+
+impl WithdrawalFinalizer {
+    pub fn new(
+        pool: ConnectionPool<Core>,
+        config: WithdrawalFinalizerConfig,
+        l1_client: Box<dyn BoundEthInterface>,
+        tx_params: Arc<dyn TxParamsProvider>,
+        contracts: &ContractsConfig,
+        l1_chain_id: L1ChainId,
+        l2_chain_id: L2ChainId,
+    ) -> anyhow::Result<Self> {
+        let l1_shared_bridge_address = contracts
+            .l1_shared_bridge_proxy_addr
+            .context("L1 shared bridge address is not set")?;
+        let base_token_address = contracts
+            .base_token_addr
+            .context("base token address is not set")?;
+        let l2_bridge_addresses = [
+            contracts.l2_shared_bridge_addr,
+            contracts.l2_legacy_shared_bridge_addr,
+        ];
+        let parser = WithdrawalParser::new(
+            base_token_address,
+            l2_bridge_addresses.into_iter().flatten().collect(),
+        );
+        let token_limits = config
+            .token_limits
+            .iter()
+            .map(|limit| (limit.l1_token_address, limit.clone()))
+            .collect();
+
+        Ok(Self {
+            pool,
+            config,
+            token_limits,
+            parser,
+            l1_client,
+            tx_params,
+            l1_shared_bridge_address,
+            l1_chain_id,
+            l2_chain_id,
+        })
+    }
+
+    fn poll_interval(&self) -> Duration {
+        self.config.poll_interval()
+    }
+
+    async fn next_l1_batch_to_process(
+        &self,
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<Option<L1BatchNumber>> {
+        let Some(last_executed_l1_batch) = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await?
+        else {
+            return Ok(None);
+        };
+        let earliest_l1_batch = storage
+            .blocks_dal()
+            .get_earliest_l1_batch_number()
+            .await?
+            .context("no L1 batches in storage, although there is an executed batch")?;
+        let last_processed_l1_batch = storage
+            .withdrawal_finalizer_dal()
+            .get_last_processed_l1_batch()
+            .await?;
+        // Batches may be pruned while the component isn't running.
+        let first_l1_batch = earliest_l1_batch.max(self.config.start_l1_batch.unwrap_or_default());
+        let next_l1_batch = last_processed_l1_batch
+            .map_or(first_l1_batch, |number| number + 1)
+            .max(first_l1_batch);
+        Ok((next_l1_batch <= last_executed_l1_batch).then_some(next_l1_batch))
+    }
+
+    /// Extracts withdrawals from the L2-to-L1 logs of the specified L1 batch.
+    async fn extract_withdrawals(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+        logs: &[L2ToL1Log],
+    ) -> anyhow::Result<Vec<BatchWithdrawal>> {
+        let events = storage
+            .events_dal()
+            .get_vm_events_for_l1_batch(l1_batch_number)
+            .await?
+            .with_context(|| format!("L1 batch #{l1_batch_number} is not in storage"))?;
+        let messages: HashMap<_, _> = VmEvent::extract_long_l2_to_l1_messages(&events)
+            .into_iter()
+            .map(|message| (H256(keccak256(&message)), message))
+            .collect();
+
+        let mut withdrawals = vec![];
+        for (i, log) in logs.iter().enumerate() {
+            // Messages sent via the L1 messenger have the sender address as the key and the message hash as the value.
+            let sender = h256_to_address(&log.key);
+            if log.sender != L1_MESSENGER_ADDRESS || !self.parser.is_withdrawal_sender(sender) {
+                continue;
+            }
+            let message = messages.get(&log.value).with_context(|| {
+                format!(
+                    "message with hash {:?} from log #{i} in L1 batch #{l1_batch_number} is missing",
+                    log.value
+                )
+            })?;
+            let Some(parsed) = self.parser.parse(sender, message) else {
+                tracing::warn!(
+                    "Message from log #{i} in L1 batch #{l1_batch_number} sent by {sender:?} has an unsupported format; skipping"
+                );
+                METRICS.withdrawals[&WithdrawalStatus::Unsupported].inc();
+                continue;
+            };
+            withdrawals.push(BatchWithdrawal {
+                l2_message_index: i as u32,
+                l2_tx_number_in_batch: log.tx_number_in_block,
+                message: message.clone(),
+                parsed,
+            });
+        }
+        Ok(withdrawals)
+    }
+
+    async fn is_withdrawal_finalized(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        l2_message_index: u32,
+    ) -> anyhow::Result<bool> {
+        let args = (
+            U256::from(self.l2_chain_id.as_u64()),
+            U256::from(l1_batch_number.0),
+            U256::from(l2_message_index),
+        );
+        CallFunctionArgs::new("isWithdrawalFinalized", args)
+            .for_contract(self.l1_shared_bridge_address, &L1_SHARED_BRIDGE_CONTRACT)
+            .call((*self.l1_client).as_ref())
+            .await
+            .context("failed calling `isWithdrawalFinalized`")
+    }
+
+    /// Builds the Merkle proof of the withdrawal message in the format expected by the L1 shared bridge.
+    async fn message_proof(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+        logs: &[L2ToL1Log],
+        l2_message_index: u32,
+    ) -> anyhow::Result<Vec<H256>> {
+        let batch_with_metadata = storage
+            .blocks_dal()
+            .get_l1_batch_metadata(l1_batch_number)
+            .await?
+            .with_context(|| format!("metadata for L1 batch #{l1_batch_number} is missing"))?;
+        let protocol_version = batch_with_metadata
+            .header
+            .protocol_version
+            .unwrap_or_else(ProtocolVersionId::last_potentially_undefined);
+
+        let batch_chain_proof = if protocol_version.is_pre_gateway() {
+            None
+        } else {
+            let sl_chain_id = storage
+                .eth_sender_dal()
+                .get_batch_commit_chain_id(l1_batch_number)
+                .await?
+                .with_context(|| {
+                    format!("settlement layer for L1 batch #{l1_batch_number} is unknown")
+                })?;
+            if sl_chain_id.0 != self.l1_chain_id.0 {
+                let path = storage
+                    .blocks_dal()
+                    .get_l1_batch_chain_merkle_path(l1_batch_number)
+                    .await?
+                    .with_context(|| {
+                        format!("chain Merkle path for L1 batch #{l1_batch_number} is missing")
+                    })?;
+                Some(path)
+            } else {
+                None
+            }
+        };
+
+        let proof = l2_to_l1_log_proof(
+            logs,
+            l2_message_index as usize,
+            protocol_version,
+            batch_with_metadata.metadata.aggregation_root,
+            batch_chain_proof,
+        );
+        Ok(proof.proof)
+    }
+
+    /// Sends a `finalizeWithdrawal` transaction to L1. If `replaced_tx` is specified, the transaction replaces it,
+    /// i.e., reuses its nonce and has bumped fees.
+    async fn send_finalization_tx(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        withdrawal: &BatchWithdrawal,
+        proof: Vec<H256>,
+        replaced_tx: Option<&WithdrawalFinalizationTx>,
+    ) -> anyhow::Result<WithdrawalFinalizationTx> {
+        let proof = proof
+            .into_iter()
+            .map(|hash| Token::FixedBytes(hash.0.to_vec()))
+            .collect();
+        let calldata = L1_SHARED_BRIDGE_CONTRACT
+            .function("finalizeWithdrawal")
+            .context("`finalizeWithdrawal` function must be present in the L1 shared bridge")?
+            .encode_input(&[
+                Token::Uint(self.l2_chain_id.as_u64().into()),
+                Token::Uint(l1_batch_number.0.into()),
+                Token::Uint(withdrawal.l2_message_index.into()),
+                Token::Uint(withdrawal.l2_tx_number_in_batch.into()),
+                Token::Bytes(withdrawal.message.clone()),
+                Token::Array(proof),
+            ])
+            .context("failed encoding `finalizeWithdrawal` input")?;
+
+        let l1_client = (*self.l1_client).as_ref();
+        let sent_at_block = l1_client
+            .block_number()
+            .await
+            .context("failed getting L1 block number")?
+            .as_u64();
+        let nonce = if let Some(replaced_tx) = replaced_tx {
+            replaced_tx.nonce
+        } else {
+            l1_client
+                .nonce_at_for_account(self.l1_client.sender_account(), BlockNumber::Latest)
+                .await
+                .context("failed getting transaction count")?
+                .as_u64()
+        };
+        let base_fee_per_gas = self.tx_params.get_base_fee(0);
+        let mut priority_fee_per_gas = self.tx_params.get_priority_fee();
+        let mut max_fee_per_gas = base_fee_per_gas + priority_fee_per_gas;
+        if let Some(replaced_tx) = replaced_tx {
+            // Replacement transactions must have both fees bumped by at least 10%.
+            priority_fee_per_gas =
+                priority_fee_per_gas.max(bump_fee(replaced_tx.priority_fee_per_gas));
+            max_fee_per_gas = max_fee_per_gas
+                .max(bump_fee(replaced_tx.max_fee_per_gas))
+                .max(priority_fee_per_gas);
+        }
+        let options = Options {
+            gas: Some(U256::from(self.config.max_tx_gas)),
+            nonce: Some(nonce.into()),
+            max_fee_per_gas: Some(U256::from(max_fee_per_gas)),
+            max_priority_fee_per_gas: Some(U256::from(priority_fee_per_gas)),
+            ..Default::default()
+        };
+
+        let signed_tx = self
+            .l1_client
+            .sign_prepared_tx_for_addr(calldata, self.l1_shared_bridge_address, options)
+            .await
+            .context("cannot sign a `finalizeWithdrawal` transaction")?;
+        let hash = l1_client
+            .send_raw_tx(signed_tx.raw_tx)
+            .await
+            .context("failed sending `finalizeWithdrawal` transaction")?;
+        Ok(WithdrawalFinalizationTx {
+            hash,
+            nonce,
+            sent_at_block,
+            max_fee_per_gas,
+            priority_fee_per_gas,
+        })
+    }
+
+    /// Processes a single withdrawal and persists the outcome. Returns the processing status.
+    async fn process_withdrawal(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+        logs: &[L2ToL1Log],
+        withdrawal: &BatchWithdrawal,
+    ) -> anyhow::Result<WithdrawalFinalizationStatus> {
+        let ParsedWithdrawal {
+            l1_token,
+            l1_receiver,
+            amount,
+        } = withdrawal.parsed;
+        let limit_status = match self.token_limits.get(&l1_token) {
+            None => Some(WithdrawalFinalizationStatus::UnlistedToken),
+            Some(limit) if amount < limit.min_amount => {
+                Some(WithdrawalFinalizationStatus::BelowLimit)
+            }
+            Some(limit) if amount > limit.max_amount => {
+                Some(WithdrawalFinalizationStatus::OverLimit)
+            }
+            Some(_) => None,
+        };
+
+        let (status, l1_tx) = if let Some(status) = limit_status {
+            (status, None)
+        } else if self
+            .is_withdrawal_finalized(l1_batch_number, withdrawal.l2_message_index)
+            .await?
+        {
+            (WithdrawalFinalizationStatus::AlreadyFinalized, None)
+        } else {
+            let proof = self
+                .message_proof(storage, l1_batch_number, logs, withdrawal.l2_message_index)
+                .await?;
+            let tx = self
+                .send_finalization_tx(l1_batch_number, withdrawal, proof, None)
+                .await?;
+            (WithdrawalFinalizationStatus::Sent, Some(tx))
+        };
+
+        tracing::info!(
+            "Processed withdrawal of {amount} of token {l1_token:?} to {l1_receiver:?} (L1 batch #{l1_batch_number}, \
+             message #{}): {status}, L1 tx: {:?}",
+            withdrawal.l2_message_index,
+            l1_tx.map(|tx| tx.hash)
+        );
+        let finalization = WithdrawalFinalization {
+            l1_batch_number,
+            l2_message_index: withdrawal.l2_message_index,
+            l2_tx_number_in_batch: withdrawal.l2_tx_number_in_batch,
+            l1_token,
+            l1_receiver,
+            amount,
+            status,
+            l1_tx,
+        };
+        storage
+            .withdrawal_finalizer_dal()
+            .insert_withdrawal_finalization(&finalization)
+            .await?;
+        METRICS.withdrawals[&status.into()].inc();
+        Ok(status)
+    }
+
+    /// Processes withdrawals in the specified L1 batch until a finalization transaction is sent or all withdrawals
+    /// are processed.
+    async fn process_l1_batch(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        let logs = storage
+            .blocks_web3_dal()
+            .get_l2_to_l1_logs(l1_batch_number)
+            .await?;
+        let withdrawals = self
+            .extract_withdrawals(storage, l1_batch_number, &logs)
+            .await?;
+        let processed_indices: HashSet<_> = storage
+            .withdrawal_finalizer_dal()
+            .get_withdrawal_finalizations(l1_batch_number)
+            .await?
+            .into_iter()
+            .map(|finalization| finalization.l2_message_index)
+            .collect();
+
+        for withdrawal in &withdrawals {
+            if processed_indices.contains(&withdrawal.l2_message_index) {
+                continue;
+            }
+            let status = self
+                .process_withdrawal(storage, l1_batch_number, &logs, withdrawal)
+                .await?;
+            if status == WithdrawalFinalizationStatus::Sent {
+                return Ok(());
+            }
+        }
+
+        storage
+            .withdrawal_finalizer_dal()
+            .mark_l1_batch_as_processed(l1_batch_number, withdrawals.len())
+            .await?;
+        tracing::debug!(
+            "Processed {} withdrawals in L1 batch #{l1_batch_number}",
+            withdrawals.len()
+        );
+        METRICS
+            .last_processed_l1_batch
+            .set(l1_batch_number.0.into());
+        Ok(())
+    }
+
+    /// Resends the finalization transaction with the same nonce and bumped fees.
+    async fn resend_finalization_tx(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        finalization: &WithdrawalFinalization,
+        replaced_tx: &WithdrawalFinalizationTx,
+    ) -> anyhow::Result<()> {
+        let l1_batch_number = finalization.l1_batch_number;
+        let logs = storage
+            .blocks_web3_dal()
+            .get_l2_to_l1_logs(l1_batch_number)
+            .await?;
+        let withdrawals = self
+            .extract_withdrawals(storage, l1_batch_number, &logs)
+            .await?;
+        let withdrawal = withdrawals
+            .iter()
+            .find(|withdrawal| withdrawal.l2_message_index == finalization.l2_message_index)
+            .with_context(|| {
+                format!(
+                    "withdrawal #{} is missing in L1 batch #{l1_batch_number}",
+                    finalization.l2_message_index
+                )
+            })?;
+        let proof = self
+            .message_proof(storage, l1_batch_number, &logs, withdrawal.l2_message_index)
+            .await?;
+        let tx = self
+            .send_finalization_tx(l1_batch_number, withdrawal, proof, Some(replaced_tx))
+            .await?;
+        tracing::info!(
+            "Resent `finalizeWithdrawal` transaction {:?} not mined since L1 block #{} as {:?} (L1 batch #{l1_batch_number}, \
+             message #{}), max fee per gas: {}, priority fee per gas: {}",
+            replaced_tx.hash,
+            replaced_tx.sent_at_block,
+            tx.hash,
+            withdrawal.l2_message_index,
+            tx.max_fee_per_gas,
+            tx.priority_fee_per_gas
+        );
+        storage
+            .withdrawal_finalizer_dal()
+            .set_withdrawal_finalization_tx(l1_batch_number, withdrawal.l2_message_index, &tx)
+            .await?;
+        METRICS.resent_txs.inc();
+        Ok(())
+    }
+
+    /// Checks the status of the sent finalization transaction. Returns `false` if the transaction is not mined yet.
+    async fn check_pending_finalization(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        finalization: &WithdrawalFinalization,
+    ) -> anyhow::Result<bool> {
+        let tx = finalization
+            .l1_tx
+            .context("sent withdrawal finalization has no L1 transaction")?;
+        let tx_hash = tx.hash;
+        let l1_client = (*self.l1_client).as_ref();
+        let receipt = l1_client
+            .tx_receipt(tx_hash)
+            .await
+            .context("failed getting receipt for `finalizeWithdrawal` transaction")?;
+        let Some(receipt) = receipt else {
+            let latest_nonce = l1_client
+                .nonce_at_for_account(self.l1_client.sender_account(), BlockNumber::Latest)
+                .await
+                .context("failed getting transaction count")?
+                .as_u64();
+            if latest_nonce > tx.nonce {
+                // The nonce was consumed by another transaction, e.g., by one of the replaced transactions.
+                // Check the outcome directly on L1.
+                let is_finalized = self
+                    .is_withdrawal_finalized(
+                        finalization.l1_batch_number,
+                        finalization.l2_message_index,
+                    )
+                    .await?;
+                let status = if is_finalized {
+                    WithdrawalFinalizationStatus::Finalized
+                } else {
+                    tracing::warn!(
+                        "Nonce {} of `finalizeWithdrawal` transaction {tx_hash:?} for L1 batch #{}, message #{} \
+                         was consumed, but the withdrawal is not finalized",
+                        tx.nonce,
+                        finalization.l1_batch_number,
+                        finalization.l2_message_index
+                    );
+                    WithdrawalFinalizationStatus::Failed
+                };
+                self.set_finalization_status(storage, finalization, status)
+                    .await?;
+                return Ok(true);
+            }
+
+            let l1_block_number = l1_client
+                .block_number()
+                .await
+                .context("failed getting L1 block number")?
+                .as_u64();
+            if l1_block_number >= tx.sent_at_block + self.config.resend_after_l1_blocks {
+                self.resend_finalization_tx(storage, finalization, &tx)
+                    .await?;
+                return Ok(true);
+            }
+            return Ok(false);
+        };
+
+        let status = if receipt.status == Some(1.into()) {
+            WithdrawalFinalizationStatus::Finalized
+        } else {
+            let reason = (*self.l1_client)
+                .as_ref()
+                .failure_reason(tx_hash)
+                .await
+                .context("failed getting failure reason of `finalizeWithdrawal` transaction")?;
+            tracing::warn!(
+                "`finalizeWithdrawal` transaction {tx_hash:?} for L1 batch #{}, message #{} failed: {reason:?}",
+                finalization.l1_batch_number,
+                finalization.l2_message_index
+            );
+            WithdrawalFinalizationStatus::Failed
+        };
+        self.set_finalization_status(storage, finalization, status)
+            .await?;
+        Ok(true)
+    }
+
+    async fn set_finalization_status(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        finalization: &WithdrawalFinalization,
+        status: WithdrawalFinalizationStatus,
+    ) -> anyhow::Result<()> {
+        storage
+            .withdrawal_finalizer_dal()
+            .set_withdrawal_finalization_status(
+                finalization.l1_batch_number,
+                finalization.l2_message_index,
+                status,
+            )
+            .await?;
+        METRICS.withdrawals[&status.into()].inc();
+        Ok(())
+    }
+
+    /// Performs a single processing step. Returns `true` if progress was made and the next step can be performed
+    /// immediately.
+    async fn process_next_step(&self) -> anyhow::Result<bool> {
+        let mut storage = self.pool.connection_tagged("withdrawal_finalizer").await?;
+        let pending_finalization = storage
+            .withdrawal_finalizer_dal()
+            .get_pending_withdrawal_finalization()
+            .await?;
+        if let Some(finalization) = pending_finalization {
+            return self
+                .check_pending_finalization(&mut storage, &finalization)
+                .await;
+        }
+
+        let max_finalizations_per_hour = self.config.max_finalizations_per_hour as usize;
+        if max_finalizations_per_hour > 0 {
+            let sent_finalizations = storage
+                .withdrawal_finalizer_dal()
+                .count_finalizations_sent_in_last_hour()
+                .await?;
+            if sent_finalizations >= max_finalizations_per_hour {
+                tracing::debug!(
+                    "Sent {sent_finalizations} finalization transactions in the last hour, which reaches the limit \
+                     ({max_finalizations_per_hour}); waiting"
+                );
+                return Ok(false);
+            }
+        }
+
+        let Some(l1_batch_number) = self.next_l1_batch_to_process(&mut storage).await? else {
+            return Ok(false);
+        };
+        self.process_l1_batch(&mut storage, l1_batch_number)
+            .await
+            .with_context(|| {
+                format!("failed processing withdrawals in L1 batch #{l1_batch_number}")
+            })?;
+        Ok(true)
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow_and_update() {
+            match self.process_next_step().await {
+                Ok(true) => continue,
+                // No progress was made; wait for the next poll.
+                Ok(false) => {}
+                Err(err) => {
+                    // Errors are usually caused by transient L1 / DB issues, so they shouldn't stop the node.
+                    tracing::warn!("Failed processing withdrawals, will retry: {err:#}");
+                }
+            }
+            // We don't check the result: if a stop signal is received, we'll return at the start
+            // of the next iteration.
+            tokio::time::timeout(self.poll_interval(), stop_receiver.changed())
+                .await
+                .ok();
+        }
+        tracing::info!("Stop signal received, withdrawal finalizer is shutting down");
+        Ok(())
+    }
+}
+
+/// Bumps a fee by 20%, which is above the minimum bump of 10% required by L1 nodes to replace a transaction.
+fn bump_fee(fee: u64) -> u64 {
+    fee.saturating_add(fee / 5).max(fee.saturating_add(1))
+}
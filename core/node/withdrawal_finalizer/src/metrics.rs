@@ -0,0 +1,46 @@
+//! Withdrawal finalizer metrics.
+
+use vise::{Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Metrics};
+use zksync_dal::withdrawal_finalizer_dal::WithdrawalFinalizationStatus;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "status", rename_all = "snake_case")]
+pub(super) enum WithdrawalStatus {
+    Sent,
+    Finalized,
+    Failed,
+    AlreadyFinalized,
+    OverLimit,
+    BelowLimit,
+    UnlistedToken,
+    /// Message from a withdrawal sender that has an unsupported format.
+    Unsupported,
+}
+
+impl From<WithdrawalFinalizationStatus> for WithdrawalStatus {
+    fn from(status: WithdrawalFinalizationStatus) -> Self {
+        match status {
+            WithdrawalFinalizationStatus::Sent => Self::Sent,
+            WithdrawalFinalizationStatus::Finalized => Self::Finalized,
+            WithdrawalFinalizationStatus::Failed => Self::Failed,
+            WithdrawalFinalizationStatus::AlreadyFinalized => Self::AlreadyFinalized,
+            WithdrawalFinalizationStatus::OverLimit => Self::OverLimit,
+            WithdrawalFinalizationStatus::BelowLimit => Self::BelowLimit,
+            WithdrawalFinalizationStatus::UnlistedToken => Self::UnlistedToken,
+        }
+    }
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "withdrawal_finalizer")]
+pub(super) struct WithdrawalFinalizerMetrics {
+    /// Number of processed withdrawals grouped by the processing outcome.
+    pub withdrawals: Family<WithdrawalStatus, Counter>,
+    /// Number of finalization transactions resent with bumped fees because they weren't mined in time.
+    pub resent_txs: Counter,
+    /// Last L1 batch with all withdrawals processed.
+    pub last_processed_l1_batch: Gauge<u64>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<WithdrawalFinalizerMetrics> = vise::Global::new();
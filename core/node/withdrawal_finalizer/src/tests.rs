@@ -0,0 +1,444 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use zksync_config::configs::withdrawal_finalizer::WithdrawalTokenLimit;
+use zksync_dal::fixtures::{ChainFixture, ChainFixtureParams};
+use zksync_eth_client::clients::MockSettlementLayer;
+use zksync_types::{
+    address_to_h256, ethabi, l2_to_l1_log::UserL2ToL1Log, tx::IncludedTxLocation, SLChainId,
+    L2_BASE_TOKEN_ADDRESS,
+};
+
+use super::*;
+
+const L1_CHAIN_ID: L1ChainId = L1ChainId(9);
+const ERC20_TOKEN: Address = Address::repeat_byte(0x55);
+const RECEIVER: Address = Address::repeat_byte(0x66);
+
+#[derive(Debug)]
+struct MockTxParams;
+
+impl TxParamsProvider for MockTxParams {
+    fn get_base_fee(&self, _time_in_mempool_in_l1_blocks: u32) -> u64 {
+        10
+    }
+
+    fn get_priority_fee(&self) -> u64 {
+        1
+    }
+
+    fn get_next_block_minimal_base_fee(&self) -> u64 {
+        10
+    }
+
+    fn get_blob_tx_base_fee(&self) -> u64 {
+        10
+    }
+
+    fn get_blob_tx_blob_base_fee(&self) -> u64 {
+        10
+    }
+
+    fn get_blob_tx_priority_fee(&self) -> u64 {
+        1
+    }
+
+    fn get_gateway_tx_base_fee(&self) -> u64 {
+        10
+    }
+
+    fn get_gateway_tx_pubdata_price(&self) -> u64 {
+        10
+    }
+}
+
+fn selector(signature: &str) -> Vec<u8> {
+    keccak256(signature.as_bytes())[..4].to_vec()
+}
+
+fn base_token_message(amount: u64) -> Vec<u8> {
+    let mut message = selector("finalizeEthWithdrawal(uint256,uint256,uint16,bytes,bytes32[])");
+    message.extend_from_slice(RECEIVER.as_bytes());
+    message.extend_from_slice(&H256::from_low_u64_be(amount).0);
+    message
+}
+
+fn erc20_message(amount: u64) -> Vec<u8> {
+    token_message(ERC20_TOKEN, amount)
+}
+
+fn token_message(l1_token: Address, amount: u64) -> Vec<u8> {
+    let mut message = selector("finalizeWithdrawal(uint256,uint256,uint16,bytes,bytes32[])");
+    message.extend_from_slice(RECEIVER.as_bytes());
+    message.extend_from_slice(l1_token.as_bytes());
+    message.extend_from_slice(&H256::from_low_u64_be(amount).0);
+    message
+}
+
+/// Allows to finalize base token withdrawals of any amount.
+fn base_token_limit(contracts: &ContractsConfig) -> WithdrawalTokenLimit {
+    WithdrawalTokenLimit {
+        l1_token_address: contracts.base_token_addr.unwrap(),
+        min_amount: 0.into(),
+        max_amount: U256::MAX,
+    }
+}
+
+#[test]
+fn parsing_withdrawal_messages() {
+    let contracts = ContractsConfig::for_tests();
+    let l2_bridge_address = contracts.l2_shared_bridge_addr.unwrap();
+    let parser = WithdrawalParser::new(contracts.base_token_addr.unwrap(), vec![l2_bridge_address]);
+    assert!(parser.is_withdrawal_sender(L2_BASE_TOKEN_ADDRESS));
+    assert!(parser.is_withdrawal_sender(l2_bridge_address));
+    assert!(!parser.is_withdrawal_sender(Address::repeat_byte(0x42)));
+
+    let parsed = parser
+        .parse(L2_BASE_TOKEN_ADDRESS, &base_token_message(100))
+        .unwrap();
+    assert_eq!(
+        parsed,
+        ParsedWithdrawal {
+            l1_token: contracts.base_token_addr.unwrap(),
+            l1_receiver: RECEIVER,
+            amount: 100.into(),
+        }
+    );
+    // `withdrawWithMessage` appends extra data to the message.
+    let mut message_with_data = base_token_message(100);
+    message_with_data.extend_from_slice(&[1; 40]);
+    assert_eq!(
+        parser.parse(L2_BASE_TOKEN_ADDRESS, &message_with_data),
+        Some(parsed)
+    );
+
+    let parsed = parser.parse(l2_bridge_address, &erc20_message(42)).unwrap();
+    assert_eq!(
+        parsed,
+        ParsedWithdrawal {
+            l1_token: ERC20_TOKEN,
+            l1_receiver: RECEIVER,
+            amount: 42.into(),
+        }
+    );
+
+    // Messages with mismatched selectors or lengths are not parsed.
+    assert_eq!(
+        parser.parse(l2_bridge_address, &base_token_message(1)),
+        None
+    );
+    assert_eq!(
+        parser.parse(L2_BASE_TOKEN_ADDRESS, &erc20_message(1)[..40]),
+        None
+    );
+    assert_eq!(
+        parser.parse(Address::repeat_byte(0x42), &erc20_message(1)),
+        None
+    );
+}
+
+/// Stores executed L1 batches with a single L2 block each. Each block sends the messages from `messages_by_l1_batch`
+/// via the L1 messenger.
+async fn seal_executed_l1_batches(
+    storage: &mut Connection<'_, Core>,
+    messages_by_l1_batch: &[&[(Address, Vec<u8>)]],
+) {
+    let mut fixture = ChainFixture::generate(ChainFixtureParams {
+        l1_batch_count: messages_by_l1_batch.len() as u32,
+        l2_blocks_per_l1_batch: 1,
+        txs_per_l2_block: 0,
+        executed_on: Some(SLChainId(L1_CHAIN_ID.0)),
+        ..ChainFixtureParams::default()
+    });
+    let message_event_signature = H256(keccak256(b"L1MessageSent(address,bytes32,bytes)"));
+    let location = IncludedTxLocation {
+        tx_hash: H256::repeat_byte(1),
+        tx_index_in_l2_block: 0,
+        tx_initiator_address: Address::repeat_byte(2),
+    };
+
+    for (l1_batch, messages) in fixture.l1_batches.iter_mut().zip(messages_by_l1_batch) {
+        let l1_batch_number = l1_batch.header.number;
+        let (events, logs): (Vec<_>, Vec<_>) = messages
+            .iter()
+            .enumerate()
+            .map(|(i, (sender, message))| {
+                let message_hash = H256(keccak256(message));
+                let event = VmEvent {
+                    location: (l1_batch_number, i as u32),
+                    address: L1_MESSENGER_ADDRESS,
+                    indexed_topics: vec![
+                        message_event_signature,
+                        address_to_h256(sender),
+                        message_hash,
+                    ],
+                    value: ethabi::encode(&[Token::Bytes(message.clone())]),
+                };
+                let log = UserL2ToL1Log(L2ToL1Log {
+                    shard_id: 0,
+                    is_service: true,
+                    tx_number_in_block: i as u16,
+                    sender: L1_MESSENGER_ADDRESS,
+                    key: address_to_h256(sender),
+                    value: message_hash,
+                });
+                (event, log)
+            })
+            .unzip();
+        let l2_block = &mut l1_batch.l2_blocks[0];
+        l2_block.events.push((location, events));
+        l2_block.l2_to_l1_logs.push((location, logs));
+    }
+    fixture.insert(storage).await;
+}
+
+/// Creates a mock L1 client reporting the withdrawal with the specified message index as finalized.
+fn mock_l1_client(finalized_message_index: u32) -> MockSettlementLayer {
+    MockSettlementLayer::builder()
+        .with_call_handler(move |call, _block_id| {
+            let function = L1_SHARED_BRIDGE_CONTRACT
+                .function("isWithdrawalFinalized")
+                .unwrap();
+            let data = &call.data.as_ref().unwrap().0;
+            assert_eq!(data[..4], function.short_signature());
+            let args = function.decode_input(&data[4..]).unwrap();
+            let message_index = args[2].clone().into_uint().unwrap();
+            Token::Bool(message_index == finalized_message_index.into())
+        })
+        .build()
+}
+
+#[tokio::test]
+async fn finalizing_withdrawals() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+
+    let contracts = ContractsConfig::for_tests();
+    let l2_bridge_address = contracts.l2_shared_bridge_addr.unwrap();
+    let messages = [
+        (L2_BASE_TOKEN_ADDRESS, base_token_message(100)),
+        // Message from a non-withdrawal sender
+        (Address::repeat_byte(0x42), vec![1; 32]),
+        (l2_bridge_address, erc20_message(10)),
+        (l2_bridge_address, erc20_message(1_000)),
+        // Message from a withdrawal sender with an unsupported format
+        (L2_BASE_TOKEN_ADDRESS, vec![2; 16]),
+        (l2_bridge_address, erc20_message(1)),
+        (
+            l2_bridge_address,
+            token_message(Address::repeat_byte(0x77), 10),
+        ),
+    ];
+    seal_executed_l1_batches(&mut storage, &[&messages]).await;
+
+    let config = WithdrawalFinalizerConfig {
+        token_limits: vec![
+            base_token_limit(&contracts),
+            WithdrawalTokenLimit {
+                l1_token_address: ERC20_TOKEN,
+                min_amount: 5.into(),
+                max_amount: 500.into(),
+            },
+        ],
+        ..WithdrawalFinalizerConfig::default()
+    };
+    let l1_client = mock_l1_client(2);
+    let finalizer = WithdrawalFinalizer::new(
+        pool.clone(),
+        config,
+        Box::new(l1_client.clone()),
+        Arc::new(MockTxParams),
+        &contracts,
+        L1_CHAIN_ID,
+        L2ChainId::default(),
+    )
+    .unwrap();
+
+    // The first step should send a finalization transaction for the base token withdrawal.
+    assert!(finalizer.process_next_step().await.unwrap());
+    assert_eq!(l1_client.sent_tx_count(), 1);
+    let pending = storage
+        .withdrawal_finalizer_dal()
+        .get_pending_withdrawal_finalization()
+        .await
+        .unwrap()
+        .expect("no pending finalization");
+    assert_eq!(pending.l2_message_index, 0);
+    assert_eq!(pending.l1_token, contracts.base_token_addr.unwrap());
+    assert_eq!(pending.amount, 100.into());
+
+    // The transaction isn't mined yet, so no progress should be made.
+    assert!(!finalizer.process_next_step().await.unwrap());
+    l1_client.execute_tx(pending.l1_tx.unwrap().hash, true, 1);
+    assert!(finalizer.process_next_step().await.unwrap());
+
+    // Remaining withdrawals are already finalized, outside the limits or of an unlisted token,
+    // so no transactions should be sent.
+    assert!(finalizer.process_next_step().await.unwrap());
+    assert_eq!(l1_client.sent_tx_count(), 1);
+    assert!(!finalizer.process_next_step().await.unwrap());
+
+    let finalizations = storage
+        .withdrawal_finalizer_dal()
+        .get_withdrawal_finalizations(L1BatchNumber(1))
+        .await
+        .unwrap();
+    let statuses: Vec<_> = finalizations
+        .iter()
+        .map(|finalization| (finalization.l2_message_index, finalization.status))
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            (0, WithdrawalFinalizationStatus::Finalized),
+            (2, WithdrawalFinalizationStatus::AlreadyFinalized),
+            (3, WithdrawalFinalizationStatus::OverLimit),
+            (5, WithdrawalFinalizationStatus::BelowLimit),
+            (6, WithdrawalFinalizationStatus::UnlistedToken),
+        ]
+    );
+    assert_eq!(
+        storage
+            .withdrawal_finalizer_dal()
+            .get_last_processed_l1_batch()
+            .await
+            .unwrap(),
+        Some(L1BatchNumber(1))
+    );
+}
+
+async fn pending_finalization_tx(storage: &mut Connection<'_, Core>) -> WithdrawalFinalizationTx {
+    storage
+        .withdrawal_finalizer_dal()
+        .get_pending_withdrawal_finalization()
+        .await
+        .unwrap()
+        .expect("no pending finalization")
+        .l1_tx
+        .unwrap()
+}
+
+#[tokio::test]
+async fn resending_stuck_finalization_tx() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    let messages = [(L2_BASE_TOKEN_ADDRESS, base_token_message(100))];
+    seal_executed_l1_batches(&mut storage, &[&messages]).await;
+
+    let is_finalized = Arc::new(AtomicBool::new(false));
+    let l1_client = MockSettlementLayer::builder()
+        .with_call_handler({
+            let is_finalized = is_finalized.clone();
+            move |_call, _block_id| Token::Bool(is_finalized.load(Ordering::SeqCst))
+        })
+        .build();
+    let contracts = ContractsConfig::for_tests();
+    let config = WithdrawalFinalizerConfig {
+        resend_after_l1_blocks: 5,
+        token_limits: vec![base_token_limit(&contracts)],
+        ..WithdrawalFinalizerConfig::default()
+    };
+    let finalizer = WithdrawalFinalizer::new(
+        pool.clone(),
+        config,
+        Box::new(l1_client.clone()),
+        Arc::new(MockTxParams),
+        &contracts,
+        L1_CHAIN_ID,
+        L2ChainId::default(),
+    )
+    .unwrap();
+
+    assert!(finalizer.process_next_step().await.unwrap());
+    let original_tx = pending_finalization_tx(&mut storage).await;
+    assert_eq!(original_tx.max_fee_per_gas, 11);
+    assert_eq!(original_tx.priority_fee_per_gas, 1);
+
+    // The transaction is not resent until enough L1 blocks pass.
+    l1_client.advance_block_number(4);
+    assert!(!finalizer.process_next_step().await.unwrap());
+    assert_eq!(l1_client.sent_tx_count(), 1);
+    l1_client.advance_block_number(1);
+    assert!(finalizer.process_next_step().await.unwrap());
+    assert_eq!(l1_client.sent_tx_count(), 2);
+
+    let resent_tx = pending_finalization_tx(&mut storage).await;
+    assert_ne!(resent_tx.hash, original_tx.hash);
+    assert_eq!(resent_tx.nonce, original_tx.nonce);
+    assert!(resent_tx.max_fee_per_gas > original_tx.max_fee_per_gas);
+    assert!(resent_tx.priority_fee_per_gas > original_tx.priority_fee_per_gas);
+    assert!(!finalizer.process_next_step().await.unwrap());
+
+    // The original transaction is mined, consuming the nonce.
+    l1_client.execute_tx(original_tx.hash, true, 1);
+    is_finalized.store(true, Ordering::SeqCst);
+    assert!(finalizer.process_next_step().await.unwrap());
+    let finalizations = storage
+        .withdrawal_finalizer_dal()
+        .get_withdrawal_finalizations(L1BatchNumber(1))
+        .await
+        .unwrap();
+    assert_eq!(
+        finalizations[0].status,
+        WithdrawalFinalizationStatus::Finalized
+    );
+}
+
+#[tokio::test]
+async fn limiting_processed_batches_and_sent_transactions() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    let messages = [
+        (L2_BASE_TOKEN_ADDRESS, base_token_message(100)),
+        (L2_BASE_TOKEN_ADDRESS, base_token_message(200)),
+    ];
+    seal_executed_l1_batches(&mut storage, &[&messages, &messages]).await;
+
+    let contracts = ContractsConfig::for_tests();
+    let config = WithdrawalFinalizerConfig {
+        token_limits: vec![base_token_limit(&contracts)],
+        max_finalizations_per_hour: 1,
+        start_l1_batch: Some(L1BatchNumber(2)),
+        ..WithdrawalFinalizerConfig::default()
+    };
+    let l1_client = mock_l1_client(u32::MAX);
+    let finalizer = WithdrawalFinalizer::new(
+        pool.clone(),
+        config,
+        Box::new(l1_client.clone()),
+        Arc::new(MockTxParams),
+        &contracts,
+        L1_CHAIN_ID,
+        L2ChainId::default(),
+    )
+    .unwrap();
+
+    // L1 batch #1 is before the start batch, so it should be skipped.
+    assert!(finalizer.process_next_step().await.unwrap());
+    assert_eq!(l1_client.sent_tx_count(), 1);
+    let pending = storage
+        .withdrawal_finalizer_dal()
+        .get_pending_withdrawal_finalization()
+        .await
+        .unwrap()
+        .expect("no pending finalization");
+    assert_eq!(pending.l1_batch_number, L1BatchNumber(2));
+    assert_eq!(pending.l2_message_index, 0);
+    l1_client.execute_tx(pending.l1_tx.unwrap().hash, true, 1);
+    assert!(finalizer.process_next_step().await.unwrap());
+
+    // The hourly limit on sent transactions is reached, so the second withdrawal shouldn't be processed.
+    assert!(!finalizer.process_next_step().await.unwrap());
+    assert_eq!(l1_client.sent_tx_count(), 1);
+    let finalizations = storage
+        .withdrawal_finalizer_dal()
+        .get_withdrawal_finalizations(L1BatchNumber(2))
+        .await
+        .unwrap();
+    assert_eq!(finalizations.len(), 1);
+    let finalizations = storage
+        .withdrawal_finalizer_dal()
+        .get_withdrawal_finalizations(L1BatchNumber(1))
+        .await
+        .unwrap();
+    assert!(finalizations.is_empty());
+}
@@ -0,0 +1,83 @@
+//! Parsing withdrawal messages sent from L2 to L1.
+
+use zksync_types::{web3::keccak256, Address, L2_BASE_TOKEN_ADDRESS, U256};
+
+/// Withdrawal parsed from an L2 -> L1 message.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ParsedWithdrawal {
+    pub l1_token: Address,
+    pub l1_receiver: Address,
+    pub amount: U256,
+}
+
+fn selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Parser for withdrawal messages sent by the L2 base token contract and by the L2 shared bridges.
+#[derive(Debug, Clone)]
+pub(crate) struct WithdrawalParser {
+    /// L1 address of the base token.
+    base_token_address: Address,
+    /// Addresses of L2 shared bridges, including the legacy one.
+    l2_bridge_addresses: Vec<Address>,
+}
+
+impl WithdrawalParser {
+    pub fn new(base_token_address: Address, l2_bridge_addresses: Vec<Address>) -> Self {
+        Self {
+            base_token_address,
+            l2_bridge_addresses,
+        }
+    }
+
+    /// Checks whether messages from the specified L2 sender should be parsed as withdrawals.
+    pub fn is_withdrawal_sender(&self, sender: Address) -> bool {
+        sender == L2_BASE_TOKEN_ADDRESS || self.l2_bridge_addresses.contains(&sender)
+    }
+
+    /// Parses a message sent by a withdrawal sender. Returns `None` if the message has an unsupported format.
+    pub fn parse(&self, sender: Address, message: &[u8]) -> Option<ParsedWithdrawal> {
+        if sender == L2_BASE_TOKEN_ADDRESS {
+            self.parse_base_token_withdrawal(message)
+        } else if self.l2_bridge_addresses.contains(&sender) {
+            Self::parse_erc20_withdrawal(message)
+        } else {
+            None
+        }
+    }
+
+    /// Parses a message with the `abi.encodePacked(finalizeEthWithdrawal.selector, receiver, amount)` layout.
+    /// The message may contain additional data (e.g., for `withdrawWithMessage`), which is ignored.
+    fn parse_base_token_withdrawal(&self, message: &[u8]) -> Option<ParsedWithdrawal> {
+        const MIN_LEN: usize = 4 + 20 + 32;
+
+        let expected_selector =
+            selector("finalizeEthWithdrawal(uint256,uint256,uint16,bytes,bytes32[])");
+        if message.len() < MIN_LEN || message[..4] != expected_selector {
+            return None;
+        }
+        Some(ParsedWithdrawal {
+            l1_token: self.base_token_address,
+            l1_receiver: Address::from_slice(&message[4..24]),
+            amount: U256::from_big_endian(&message[24..56]),
+        })
+    }
+
+    /// Parses a message with the `abi.encodePacked(finalizeWithdrawal.selector, receiver, l1Token, amount)` layout.
+    fn parse_erc20_withdrawal(message: &[u8]) -> Option<ParsedWithdrawal> {
+        const LEN: usize = 4 + 20 + 20 + 32;
+
+        let expected_selector =
+            selector("finalizeWithdrawal(uint256,uint256,uint16,bytes,bytes32[])");
+        if message.len() != LEN || message[..4] != expected_selector {
+            return None;
+        }
+        Some(ParsedWithdrawal {
+            l1_receiver: Address::from_slice(&message[4..24]),
+            l1_token: Address::from_slice(&message[24..44]),
+            amount: U256::from_big_endian(&message[44..76]),
+        })
+    }
+}
@@ -1,24 +1,36 @@
 use std::{
-    path::PathBuf,
+    net::SocketAddr,
+    path::{Path, PathBuf},
     sync::Arc,
     time::{Duration, Instant},
 };
 
 use anyhow::Context as _;
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
 use clap::Parser;
 use shivini::{ProverContext, ProverContextConfig};
+use tokio::sync::watch;
 use tokio_util::sync::CancellationToken;
-use zksync_circuit_prover::{FinalizationHintsCache, SetupDataCache, PROVER_BINARY_METRICS};
-use zksync_circuit_prover_service::job_runner::{circuit_prover_runner, WvgRunnerBuilder};
+use zksync_circuit_prover::PROVER_BINARY_METRICS;
+use zksync_circuit_prover_service::{
+    job_runner::{circuit_prover_runner, WvgRunnerBuilder},
+    warm_up::{self, PreloadedData, Readiness, ReadinessDetails},
+};
 use zksync_config::{
     configs::{FriProverConfig, ObservabilityConfig},
     ObjectStoreConfig,
 };
-use zksync_core_leftovers::temp_config_store::{load_database_secrets, load_general_config};
+use zksync_core_leftovers::temp_config_store::{
+    load_database_secrets, load_general_config, load_s3_secrets,
+};
+use zksync_health_check::{AppHealth, AppHealthCheck};
 use zksync_object_store::{ObjectStore, ObjectStoreFactory};
 use zksync_prover_dal::{ConnectionPool, Prover};
-use zksync_prover_fri_types::PROVER_PROTOCOL_SEMANTIC_VERSION;
-use zksync_prover_keystore::keystore::Keystore;
+use zksync_prover_fri_types::{CircuitWrapper, PROVER_PROTOCOL_SEMANTIC_VERSION};
+use zksync_prover_keystore::{
+    keystore::Keystore,
+    setup_data_downloader::{SetupDataDownloader, SetupDataKind},
+};
 use zksync_utils::wait_for_tasks::ManagedTasks;
 use zksync_vlog::prometheus::PrometheusExporterConfig;
 
@@ -56,6 +68,14 @@ struct Cli {
     /// None corresponds to allocating all available VRAM.
     #[arg(short = 'm', long)]
     pub(crate) max_allocation: Option<usize>,
+    /// Port to serve the `/health` endpoint on. The `circuit_prover` component becomes ready
+    /// once warm-up is finished and jobs are being picked.
+    #[arg(long)]
+    pub(crate) health_check_port: Option<u16>,
+    /// Path to a bincode-serialized circuit (as stored in the `ProverJobsFri` bucket) to prove on startup
+    /// in order to warm up the GPU. If not set, only setup data & finalization hints are preloaded.
+    #[arg(long)]
+    pub(crate) warm_up_circuit_path: Option<PathBuf>,
 }
 
 #[tokio::main]
@@ -68,26 +88,65 @@ async fn main() -> anyhow::Result<()> {
         .install()
         .context("failed to install observability")?;
 
-    let (connection_pool, object_store, prover_context, setup_data_cache, hints) = load_resources(
+    // Metrics are exported during warm-up as well, so that it can be monitored (and readiness is reported correctly).
+    let exporter_config = PrometheusExporterConfig::pull(prover_config.prometheus_port);
+    let (metrics_stop_sender, metrics_stop_receiver) = tokio::sync::watch::channel(false);
+    let mut tasks = vec![tokio::spawn(
+        exporter_config.run(metrics_stop_receiver.clone()),
+    )];
+
+    // Health check is served during warm-up as well, reporting the prover as not ready.
+    let readiness = Readiness::new();
+    if let Some(port) = opt.health_check_port {
+        let app_health_check = Arc::new(AppHealthCheck::default());
+        app_health_check
+            .insert_component(readiness.health_check())
+            .context("failed to insert circuit prover health check")?;
+        tasks.push(tokio::spawn(run_health_check_server(
+            SocketAddr::from(([0, 0, 0, 0], port)),
+            app_health_check,
+            metrics_stop_receiver,
+        )));
+    }
+
+    let (connection_pool, object_store, prover_context, preloaded_data) = load_resources(
         opt.secrets_path,
         opt.max_allocation,
         object_store_config,
         prover_config.setup_data_path.into(),
+        prover_config.setup_data_download_url,
     )
     .await
     .context("failed to load configs")?;
 
+    let warm_up_proof_time = match opt.warm_up_circuit_path {
+        Some(path) => {
+            let circuit_wrapper = load_warm_up_circuit(&path).with_context(|| {
+                format!("failed to load warm-up circuit from {}", path.display())
+            })?;
+            let elapsed = warm_up::warm_up_gpu(circuit_wrapper, &preloaded_data)
+                .await
+                .context("failed to warm up GPU")?;
+            Some(elapsed)
+        }
+        None => None,
+    };
+    let PreloadedData {
+        setup_data_cache,
+        finalization_hints_cache: hints,
+    } = preloaded_data;
+    let readiness_details = ReadinessDetails {
+        setup_data_count: setup_data_cache.len(),
+        finalization_hints_count: hints.len(),
+        warm_up_proof_time,
+    };
+
     PROVER_BINARY_METRICS
         .startup_time
         .observe(start_time.elapsed());
 
     let cancellation_token = CancellationToken::new();
 
-    let exporter_config = PrometheusExporterConfig::pull(prover_config.prometheus_port);
-    let (metrics_stop_sender, metrics_stop_receiver) = tokio::sync::watch::channel(false);
-
-    let mut tasks = vec![tokio::spawn(exporter_config.run(metrics_stop_receiver))];
-
     let (witness_vector_sender, witness_vector_receiver) = tokio::sync::mpsc::channel(CHANNEL_SIZE);
 
     tracing::info!(
@@ -124,6 +183,7 @@ async fn main() -> anyhow::Result<()> {
     );
 
     tasks.extend(circuit_prover_runner.run());
+    readiness.set_ready(readiness_details);
 
     let mut tasks = ManagedTasks::new(tasks);
     tokio::select! {
@@ -140,6 +200,7 @@ async fn main() -> anyhow::Result<()> {
             }
         }
     }
+    readiness.set_shutting_down();
     let shutdown_time = Instant::now();
     tasks.complete(GRACEFUL_SHUTDOWN_DURATION).await;
     PROVER_BINARY_METRICS
@@ -178,20 +239,23 @@ fn load_configs(
 /// - connection pool - necessary to pick & store jobs from database
 /// - object store - necessary  for loading and storing artifacts to object store
 /// - prover context - necessary for circuit proving; VRAM allocation
-/// - setup data - necessary for circuit proving
+/// - setup data - necessary for circuit proving; missing setup data is downloaded if the download URL is set
 /// - finalization hints - necessary for generating witness vectors
+///
+/// Setup data & finalization hints are preloaded for all circuits during warm-up, before any jobs are picked.
 async fn load_resources(
     secrets_path: Option<PathBuf>,
     max_gpu_vram_allocation: Option<usize>,
     object_store_config: ObjectStoreConfig,
     setup_data_path: PathBuf,
+    setup_data_download_url: Option<String>,
 ) -> anyhow::Result<(
     ConnectionPool<Prover>,
     Arc<dyn ObjectStore>,
     ProverContext,
-    SetupDataCache,
-    FinalizationHintsCache,
+    PreloadedData,
 )> {
+    let s3_secrets = load_s3_secrets(secrets_path.clone()).context("failed to load S3 secrets")?;
     let database_secrets =
         load_database_secrets(secrets_path).context("failed to load database secrets")?;
     let database_url = database_secrets
@@ -205,6 +269,7 @@ async fn load_resources(
         .context("failed to build connection pool")?;
 
     let object_store = ObjectStoreFactory::new(object_store_config)
+        .with_s3_secrets(s3_secrets)
         .create_store()
         .await
         .context("failed to create object store")?;
@@ -217,27 +282,70 @@ async fn load_resources(
         None => ProverContext::create().context("failed initializing gpu prover context")?,
     };
 
-    tracing::info!("Loading setup data from disk...");
-
     let keystore = Keystore::locate().with_setup_path(Some(setup_data_path));
-    let setup_data_cache = keystore
-        .load_all_setup_key_mapping()
-        .await
-        .context("failed to load setup key mapping")?;
+    if let Some(url) = setup_data_download_url {
+        tracing::info!("Downloading missing setup data...");
+        SetupDataDownloader::new(&url, SetupDataKind::Gpu)
+            .await?
+            .download_missing(&keystore)
+            .await
+            .context("failed to download setup data")?;
+    }
 
-    tracing::info!("Loading finalization hints from disk...");
-    let finalization_hints = keystore
-        .load_all_finalization_hints_mapping()
+    let preloaded_data = warm_up::warm_up(&keystore)
         .await
-        .context("failed to load finalization hints mapping")?;
-
-    tracing::info!("Finished loading mappings from disk.");
+        .context("failed to warm up circuit prover")?;
 
     Ok((
         connection_pool,
         object_store,
         prover_context,
-        setup_data_cache,
-        finalization_hints,
+        preloaded_data,
     ))
 }
+
+/// Loads the circuit used for GPU warm-up. The format matches circuits stored in the `ProverJobsFri` bucket.
+fn load_warm_up_circuit(path: &Path) -> anyhow::Result<CircuitWrapper> {
+    let bytes = std::fs::read(path)?;
+    bincode::deserialize(&bytes).context("failed to deserialize circuit")
+}
+
+async fn check_health(
+    app_health_check: State<Arc<AppHealthCheck>>,
+) -> (StatusCode, Json<AppHealth>) {
+    let response = app_health_check.check_health().await;
+    let response_code = if response.is_healthy() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (response_code, Json(response))
+}
+
+/// Serves the `/health` endpoint; it responds with 503 until the circuit prover is ready to pick jobs.
+async fn run_health_check_server(
+    bind_address: SocketAddr,
+    app_health_check: Arc<AppHealthCheck>,
+    mut stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<()> {
+    tracing::info!("Starting healthcheck server on {bind_address}");
+    let app = Router::new()
+        .route("/health", get(check_health))
+        .with_state(app_health_check);
+    let listener = tokio::net::TcpListener::bind(bind_address)
+        .await
+        .with_context(|| format!("Failed binding healthcheck server to {bind_address}"))?;
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async move {
+            if stop_receiver.changed().await.is_err() {
+                tracing::warn!(
+                    "Stop signal sender for healthcheck server was dropped without sending a signal"
+                );
+            }
+            tracing::info!("Stop signal received, healthcheck server is shutting down");
+        })
+        .await
+        .context("Healthcheck server failed")?;
+    tracing::info!("Healthcheck server shut down");
+    Ok(())
+}
@@ -1,9 +1,13 @@
-use std::{sync::Arc, time::Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::Context as _;
 use async_trait::async_trait;
 use circuit_sequencer_api::proof::FinalProof;
 use tokio::task::JoinHandle;
+use uuid::Uuid;
 #[cfg(feature = "gpu")]
 use wrapper_prover::{Bn256, GPUWrapperConfigs, WrapperProver, DEFAULT_WRAPPER_CONFIG};
 #[cfg(not(feature = "gpu"))]
@@ -29,6 +33,9 @@ use zksync_types::{protocol_version::ProtocolSemanticVersion, L1BatchNumber};
 
 use crate::metrics::METRICS;
 
+/// Several compressor instances can run concurrently against the same database. Each instance
+/// periodically sends heartbeats for the job it processes; jobs without recent heartbeats are considered
+/// abandoned (e.g., because the instance crashed) and are requeued by other instances.
 pub struct ProofCompressor {
     blob_store: Arc<dyn ObjectStore>,
     pool: ConnectionPool<Prover>,
@@ -36,9 +43,17 @@ pub struct ProofCompressor {
     max_attempts: u32,
     protocol_version: ProtocolSemanticVersion,
     keystore: Keystore,
+    heartbeat_interval: Duration,
+    heartbeat_timeout: Duration,
+    /// Name of the pod running the compressor; used as a metrics label.
+    pod_name: String,
+    /// Unique identifier of the compressor instance recorded in `picked_by` for jobs. Includes a random component,
+    /// so that jobs picked before a restart of the same pod are not considered owned by the restarted instance.
+    instance: String,
 }
 
 impl ProofCompressor {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         blob_store: Arc<dyn ObjectStore>,
         pool: ConnectionPool<Prover>,
@@ -46,7 +61,11 @@ impl ProofCompressor {
         max_attempts: u32,
         protocol_version: ProtocolSemanticVersion,
         keystore: Keystore,
+        heartbeat_interval: Duration,
+        heartbeat_timeout: Duration,
     ) -> Self {
+        let pod_name = get_current_pod_name();
+        let instance = format!("{pod_name}-{}", Uuid::new_v4());
         Self {
             blob_store,
             pool,
@@ -54,6 +73,10 @@ impl ProofCompressor {
             max_attempts,
             protocol_version,
             keystore,
+            heartbeat_interval,
+            heartbeat_timeout,
+            pod_name,
+            instance,
         }
     }
 
@@ -114,6 +137,44 @@ impl ProofCompressor {
         }
         array
     }
+
+    /// Periodically records that this instance is still processing the job, so that other instances don't reclaim it.
+    /// Returns once the job is no longer owned by this instance.
+    async fn send_heartbeats(
+        pool: ConnectionPool<Prover>,
+        l1_batch_number: L1BatchNumber,
+        instance: String,
+        pod_name: String,
+        heartbeat_interval: Duration,
+    ) {
+        let mut interval = tokio::time::interval(heartbeat_interval);
+        // The first tick completes immediately; the heartbeat was already recorded when the job was picked.
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let mut conn = match pool.connection().await {
+                Ok(conn) => conn,
+                Err(err) => {
+                    tracing::warn!(
+                        "Failed to send heartbeat for L1 batch {l1_batch_number}: {err:#}"
+                    );
+                    continue;
+                }
+            };
+            let is_owned = conn
+                .fri_proof_compressor_dal()
+                .update_proof_compression_job_heartbeat(l1_batch_number, &instance)
+                .await;
+            if !is_owned {
+                tracing::warn!(
+                    "Proof compression job for L1 batch {l1_batch_number} is no longer owned by {instance}; \
+                     it was likely reclaimed by another instance"
+                );
+                METRICS.jobs_lost[&pod_name].inc();
+                return;
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -125,14 +186,27 @@ impl JobProcessor for ProofCompressor {
 
     async fn get_next_job(&self) -> anyhow::Result<Option<(Self::JobId, Self::Job)>> {
         let mut conn = self.pool.connection().await.unwrap();
-        let pod_name = get_current_pod_name();
+        let reclaimed_jobs = conn
+            .fri_proof_compressor_dal()
+            .reclaim_abandoned_jobs(self.heartbeat_timeout, self.max_attempts)
+            .await;
+        for job in &reclaimed_jobs {
+            tracing::info!(
+                "Reclaimed abandoned proof compression job for L1 batch {} previously picked by {:?}",
+                job.id,
+                job.picked_by
+            );
+        }
+        METRICS.jobs_reclaimed[&self.pod_name].inc_by(reclaimed_jobs.len() as u64);
+
         let Some(l1_batch_number) = conn
             .fri_proof_compressor_dal()
-            .get_next_proof_compression_job(&pod_name, self.protocol_version)
+            .get_next_proof_compression_job(&self.instance, self.protocol_version)
             .await
         else {
             return Ok(None);
         };
+        METRICS.jobs_picked[&self.pod_name].inc();
         let Some(fri_proof_id) = conn
             .fri_prover_jobs_dal()
             .get_scheduler_proof_job_id(l1_batch_number)
@@ -159,24 +233,51 @@ impl JobProcessor for ProofCompressor {
     }
 
     async fn save_failure(&self, job_id: Self::JobId, _started_at: Instant, error: String) {
-        self.pool
+        METRICS.busy[&self.pod_name].set(0);
+        let is_owned = self
+            .pool
             .connection()
             .await
             .unwrap()
             .fri_proof_compressor_dal()
-            .mark_proof_compression_job_failed(&error, job_id)
+            .mark_proof_compression_job_failed(&error, job_id, &self.instance)
             .await;
+        if !is_owned {
+            tracing::warn!(
+                "Not marking proof compression job for L1 batch {job_id} as failed: it is no longer owned by {}",
+                self.instance
+            );
+            METRICS.jobs_lost[&self.pod_name].inc();
+        }
     }
 
     async fn process_job(
         &self,
-        _job_id: &L1BatchNumber,
+        job_id: &L1BatchNumber,
         job: ZkSyncRecursionLayerProof,
         _started_at: Instant,
     ) -> JoinHandle<anyhow::Result<Self::JobArtifacts>> {
+        METRICS.busy[&self.pod_name].set(1);
         let compression_mode = self.compression_mode;
         let keystore = self.keystore.clone();
-        tokio::task::spawn_blocking(move || Self::compress_proof(job, compression_mode, keystore))
+        let mut compression = tokio::task::spawn_blocking(move || {
+            Self::compress_proof(job, compression_mode, keystore)
+        });
+        let heartbeats = Self::send_heartbeats(
+            self.pool.clone(),
+            *job_id,
+            self.instance.clone(),
+            self.pod_name.clone(),
+            self.heartbeat_interval,
+        );
+        tokio::spawn(async move {
+            let result = tokio::select! {
+                result = &mut compression => result,
+                () = heartbeats => compression.await,
+            };
+            // Propagate panics, so that they are reported the same way as for non-wrapped tasks.
+            result.unwrap_or_else(|err| std::panic::resume_unwind(err.into_panic()))
+        })
     }
 
     async fn save_result(
@@ -186,6 +287,8 @@ impl JobProcessor for ProofCompressor {
         artifacts: FinalProof,
     ) -> anyhow::Result<()> {
         METRICS.compression_time.observe(started_at.elapsed());
+        METRICS.instance_compression_time[&self.pod_name].observe(started_at.elapsed());
+        METRICS.busy[&self.pod_name].set(0);
         tracing::info!(
             "Finished fri proof compression for job: {job_id} took: {:?}",
             started_at.elapsed()
@@ -213,13 +316,26 @@ impl JobProcessor for ProofCompressor {
             .blob_save_time
             .observe(blob_save_started_at.elapsed());
 
-        self.pool
+        let is_owned = self
+            .pool
             .connection()
             .await
             .unwrap()
             .fri_proof_compressor_dal()
-            .mark_proof_compression_job_successful(job_id, started_at.elapsed(), &blob_url)
+            .mark_proof_compression_job_successful(
+                job_id,
+                started_at.elapsed(),
+                &blob_url,
+                &self.instance,
+            )
             .await;
+        if !is_owned {
+            tracing::warn!(
+                "Not marking proof compression job for L1 batch {job_id} as successful: it is no longer owned by {}",
+                self.instance
+            );
+            METRICS.jobs_lost[&self.pod_name].inc();
+        }
         Ok(())
     }
 
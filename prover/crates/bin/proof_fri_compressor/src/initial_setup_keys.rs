@@ -1,5 +1,8 @@
 use std::{fs::create_dir_all, io::Cursor, path::Path, time::Duration};
 
+use zksync_prover_fri_types::PROVER_PROTOCOL_SEMANTIC_VERSION;
+use zksync_prover_keystore::setup_data_downloader::{setup_data_hash, SetupDataManifest};
+
 #[tracing::instrument(skip_all)]
 fn download_initial_setup(key_download_url: &str) -> reqwest::Result<Vec<u8>> {
     tracing::info!("Downloading initial setup from {:?}", key_download_url);
@@ -33,6 +36,29 @@ fn download_initial_setup(key_download_url: &str) -> reqwest::Result<Vec<u8>> {
         .and_then(|response| response.bytes().map(|bytes| bytes.to_vec()))
 }
 
+/// Verifies the downloaded initial setup against the hash recorded in the setup data manifest.
+fn verify_initial_setup(initial_setup_key_path: &str, bytes: &[u8]) {
+    let file_name = Path::new(initial_setup_key_path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_else(|| panic!("Invalid initial setup path: {initial_setup_key_path:?}"));
+    let expected_hash = SetupDataManifest::embedded()
+        .expected_compressor_hash(PROVER_PROTOCOL_SEMANTIC_VERSION, file_name);
+    let Some(expected_hash) = expected_hash else {
+        tracing::warn!(
+            "Setup data manifest has no hash for `{file_name}` for protocol version {PROVER_PROTOCOL_SEMANTIC_VERSION}; \
+             downloaded initial setup is not verified"
+        );
+        return;
+    };
+
+    let hash = setup_data_hash(bytes);
+    assert_eq!(
+        hash, expected_hash,
+        "Hash mismatch for downloaded initial setup `{file_name}`"
+    );
+}
+
 #[tracing::instrument(skip_all)]
 pub fn download_initial_setup_keys_if_not_present(
     initial_setup_key_path: &str,
@@ -47,6 +73,7 @@ pub fn download_initial_setup_keys_if_not_present(
     }
 
     let bytes = download_initial_setup(key_download_url).expect("Failed downloading initial setup");
+    verify_initial_setup(initial_setup_key_path, &bytes);
     let initial_setup_key_dir = Path::new(initial_setup_key_path).parent().unwrap();
     create_dir_all(initial_setup_key_dir).unwrap_or_else(|_| {
         panic!(
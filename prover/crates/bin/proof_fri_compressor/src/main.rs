@@ -6,7 +6,9 @@ use std::{env, time::Duration};
 use anyhow::Context as _;
 use clap::Parser;
 use tokio::sync::{oneshot, watch};
-use zksync_core_leftovers::temp_config_store::{load_database_secrets, load_general_config};
+use zksync_core_leftovers::temp_config_store::{
+    load_database_secrets, load_general_config, load_s3_secrets,
+};
 use zksync_env_config::object_store::ProverObjectStoreConfig;
 use zksync_object_store::ObjectStoreFactory;
 use zksync_prover_dal::{ConnectionPool, Prover};
@@ -42,6 +44,7 @@ async fn main() -> anyhow::Result<()> {
     let opt = Cli::parse();
 
     let general_config = load_general_config(opt.config_path).context("general config")?;
+    let s3_secrets = load_s3_secrets(opt.secrets_path.clone()).context("S3 secrets")?;
     let database_secrets = load_database_secrets(opt.secrets_path).context("database secrets")?;
 
     let observability_config = general_config
@@ -66,6 +69,7 @@ async fn main() -> anyhow::Result<()> {
             .context("ProverObjectStoreConfig")?,
     );
     let blob_store = ObjectStoreFactory::new(object_store_config.0)
+        .with_s3_secrets(s3_secrets)
         .create_store()
         .await?;
 
@@ -76,6 +80,12 @@ async fn main() -> anyhow::Result<()> {
         .expect("ProverConfig doesn't exist");
     let keystore =
         Keystore::locate().with_setup_path(Some(prover_config.setup_data_path.clone().into()));
+    anyhow::ensure!(
+        config.heartbeat_interval() < config.heartbeat_timeout(),
+        "Proof compressor heartbeat interval ({:?}) must be less than heartbeat timeout ({:?})",
+        config.heartbeat_interval(),
+        config.heartbeat_timeout()
+    );
     let proof_compressor = ProofCompressor::new(
         blob_store,
         pool,
@@ -83,6 +93,8 @@ async fn main() -> anyhow::Result<()> {
         config.max_attempts,
         protocol_version,
         keystore,
+        config.heartbeat_interval(),
+        config.heartbeat_timeout(),
     );
 
     let (stop_sender, stop_receiver) = watch::channel(false);
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use vise::{Buckets, Histogram, Metrics};
+use vise::{Buckets, Counter, Gauge, Histogram, LabeledFamily, Metrics};
 
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "prover_fri_proof_fri_compressor")]
@@ -11,6 +11,20 @@ pub(crate) struct ProofFriCompressorMetrics {
     pub compression_time: Histogram<Duration>,
     #[metrics(buckets = Buckets::LATENCIES)]
     pub blob_save_time: Histogram<Duration>,
+    /// Number of jobs picked by the compressor instance.
+    #[metrics(labels = ["instance"])]
+    pub jobs_picked: LabeledFamily<String, Counter>,
+    /// Number of abandoned jobs requeued by the compressor instance.
+    #[metrics(labels = ["instance"])]
+    pub jobs_reclaimed: LabeledFamily<String, Counter>,
+    /// Number of jobs that were reclaimed by other instances while the compressor instance was processing them.
+    #[metrics(labels = ["instance"])]
+    pub jobs_lost: LabeledFamily<String, Counter>,
+    /// Whether the compressor instance is processing a job.
+    #[metrics(labels = ["instance"])]
+    pub busy: LabeledFamily<String, Gauge<u64>>,
+    #[metrics(buckets = Buckets::LATENCIES, labels = ["instance"])]
+    pub instance_compression_time: LabeledFamily<String, Histogram<Duration>>,
 }
 
 #[vise::register]
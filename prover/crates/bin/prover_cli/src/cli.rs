@@ -2,8 +2,8 @@ use clap::{command, Args, Parser, Subcommand};
 use zksync_types::url::SensitiveUrl;
 
 use crate::commands::{
-    config, debug_proof, delete, get_file_info, insert_batch, insert_version, requeue, restart,
-    stats, status::StatusCommand,
+    config, debug_proof, delete, get_file_info, insert_batch, insert_version, replay_batch,
+    requeue, restart, stats, status::StatusCommand,
 };
 
 pub const VERSION_STRING: &str = env!("CARGO_PKG_VERSION");
@@ -30,6 +30,7 @@ impl ProverCLI {
             ProverCommand::Stats(args) => stats::run(args, self.config).await?,
             ProverCommand::InsertVersion(args) => insert_version::run(args, self.config).await?,
             ProverCommand::InsertBatch(args) => insert_batch::run(args, self.config).await?,
+            ProverCommand::ReplayBatch(args) => replay_batch::run(args, self.config).await?,
         };
         Ok(())
     }
@@ -62,4 +63,6 @@ pub enum ProverCommand {
     Stats(stats::Options),
     InsertVersion(insert_version::Args),
     InsertBatch(insert_batch::Args),
+    #[command(about = "Copies a batch from another environment and queues it for proving")]
+    ReplayBatch(replay_batch::Args),
 }
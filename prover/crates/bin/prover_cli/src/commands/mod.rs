@@ -4,6 +4,7 @@ pub(crate) mod delete;
 pub(crate) mod get_file_info;
 pub(crate) mod insert_batch;
 pub(crate) mod insert_version;
+pub(crate) mod replay_batch;
 pub(crate) mod requeue;
 pub(crate) mod restart;
 pub(crate) mod stats;
@@ -0,0 +1,154 @@
+use anyhow::Context as _;
+use clap::Args as ClapArgs;
+use zksync_basic_types::{
+    protocol_version::{ProtocolSemanticVersion, VersionPatch},
+    L1BatchNumber,
+};
+use zksync_config::{configs::object_store::ObjectStoreMode, ObjectStoreConfig};
+use zksync_db_connection::connection_pool::ConnectionPool;
+use zksync_object_store::{ObjectStore, ObjectStoreFactory};
+use zksync_prover_dal::{Prover, ProverDal};
+use zksync_prover_interface::inputs::WitnessInputData;
+use zksync_types::url::SensitiveUrl;
+
+use crate::cli::ProverCLIConfig;
+
+/// Copies witness inputs of a batch from the source environment to the target environment (the one
+/// pointed to by the CLI DB URL) and queues the batch for proving there. Useful to reproduce prover
+/// failures outside of the environment they happened in.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Batch to replay.
+    #[clap(short, long)]
+    pub number: L1BatchNumber,
+    /// Prover DB URL of the source environment. Used to determine the protocol version of the batch.
+    #[clap(long, env("PLI__SOURCE_DB_URL"))]
+    pub source_db_url: SensitiveUrl,
+    /// Object store of the source environment: either a `gs://` bucket URL or a path to
+    /// a file-backed object store.
+    #[clap(long)]
+    pub source_object_store: String,
+    /// Object store of the target environment: either a `gs://` bucket URL or a path to
+    /// a file-backed object store.
+    #[clap(long)]
+    pub target_object_store: String,
+    /// Path to GCS credentials used for both object stores. If not set, default credentials are used.
+    #[clap(long)]
+    pub gcs_credential_file_path: Option<String>,
+    /// Protocol version patch to prove the batch with in the target environment. Witness inputs are
+    /// compatible across patches of the same minor version. Defaults to the patch used in the source environment.
+    #[clap(long)]
+    pub target_patch: Option<u32>,
+}
+
+pub async fn run(args: Args, config: ProverCLIConfig) -> anyhow::Result<()> {
+    let source_pool = ConnectionPool::<Prover>::singleton(args.source_db_url)
+        .build()
+        .await
+        .context("failed to build a source prover_connection_pool")?;
+    let mut source_conn = source_pool.connection().await.unwrap();
+    let target_pool = ConnectionPool::<Prover>::singleton(config.db_url)
+        .build()
+        .await
+        .context("failed to build a target prover_connection_pool")?;
+    let mut target_conn = target_pool.connection().await.unwrap();
+
+    let batch_number = args.number;
+    if source_conn
+        .fri_witness_generator_dal()
+        .get_basic_witness_generator_job_for_batch(batch_number)
+        .await
+        .is_none()
+    {
+        anyhow::bail!("batch {batch_number} is not present in the source environment");
+    }
+    if target_conn
+        .fri_witness_generator_dal()
+        .get_basic_witness_generator_job_for_batch(batch_number)
+        .await
+        .is_some()
+    {
+        anyhow::bail!(
+            "batch {batch_number} is already present in the target environment; delete it first"
+        );
+    }
+
+    let source_version = source_conn
+        .fri_witness_generator_dal()
+        .protocol_version_for_l1_batch(batch_number)
+        .await;
+    let target_version = ProtocolSemanticVersion::new(
+        source_version.minor,
+        args.target_patch.map_or(source_version.patch, VersionPatch),
+    );
+    if target_conn
+        .fri_protocol_versions_dal()
+        .vk_commitments_for(target_version)
+        .await
+        .is_none()
+    {
+        anyhow::bail!(
+            "protocol version {target_version} is not present in the target environment; insert it with `insert-version` first"
+        );
+    }
+    if target_version != source_version {
+        println!("Remapping protocol version {source_version} to {target_version}");
+    }
+
+    let gcs_credential_file_path = args.gcs_credential_file_path.as_deref();
+    let source_store = create_object_store(&args.source_object_store, gcs_credential_file_path)
+        .await
+        .context("failed to create source object store")?;
+    let target_store = create_object_store(&args.target_object_store, gcs_credential_file_path)
+        .await
+        .context("failed to create target object store")?;
+
+    let witness_inputs: WitnessInputData = source_store
+        .get(batch_number)
+        .await
+        .context("failed to load witness inputs from the source object store")?;
+    let witness_inputs_blob_url = target_store
+        .put(batch_number, &witness_inputs)
+        .await
+        .context("failed to save witness inputs to the target object store")?;
+
+    target_conn
+        .fri_witness_generator_dal()
+        .save_witness_inputs(batch_number, &witness_inputs_blob_url, target_version)
+        .await;
+
+    println!("Batch {batch_number} is queued for proving with protocol version {target_version}");
+    Ok(())
+}
+
+fn object_store_config(url: &str, gcs_credential_file_path: Option<&str>) -> ObjectStoreConfig {
+    let mode = if let Some(bucket_base_url) = url.strip_prefix("gs://") {
+        let bucket_base_url = bucket_base_url.trim_end_matches('/').to_owned();
+        match gcs_credential_file_path {
+            Some(path) => ObjectStoreMode::GCSWithCredentialFile {
+                bucket_base_url,
+                gcs_credential_file_path: path.to_owned(),
+            },
+            None => ObjectStoreMode::GCS { bucket_base_url },
+        }
+    } else {
+        ObjectStoreMode::FileBacked {
+            file_backed_base_path: url.to_owned(),
+        }
+    };
+    ObjectStoreConfig {
+        mode,
+        max_retries: 5,
+        local_mirror_path: None,
+        tiers: vec![],
+    }
+}
+
+async fn create_object_store(
+    url: &str,
+    gcs_credential_file_path: Option<&str>,
+) -> anyhow::Result<std::sync::Arc<dyn ObjectStore>> {
+    ObjectStoreFactory::new(object_store_config(url, gcs_credential_file_path))
+        .create_store()
+        .await
+}
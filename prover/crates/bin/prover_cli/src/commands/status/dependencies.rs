@@ -0,0 +1,204 @@
+use std::collections::BTreeMap;
+
+use anyhow::Context as _;
+use circuit_definitions::zkevm_circuits::scheduler::aux::BaseLayerCircuitType;
+use clap::Args as ClapArgs;
+use colored::*;
+use zksync_prover_dal::{Connection, ConnectionPool, Prover, ProverDal};
+use zksync_types::{
+    basic_fri_types::AggregationRound,
+    prover_dal::{ProverJobFriInfo, ProverJobStatus, WitnessJobStatus},
+    L1BatchNumber,
+};
+
+use super::utils::Status;
+use crate::cli::ProverCLIConfig;
+
+/// Shows which proofs the recursion tip and scheduler jobs of the batches are waiting for.
+#[derive(ClapArgs)]
+pub struct Args {
+    #[clap(short = 'n', num_args = 1.., required = true)]
+    batches: Vec<L1BatchNumber>,
+}
+
+pub(crate) async fn run(args: Args, config: ProverCLIConfig) -> anyhow::Result<()> {
+    let prover_connection_pool = ConnectionPool::<Prover>::singleton(config.db_url)
+        .build()
+        .await
+        .context("failed to build a prover_connection_pool")?;
+    let mut conn = prover_connection_pool
+        .connection()
+        .await
+        .context("failed to get a connection")?;
+
+    for batch in args.batches {
+        println!("== {} ==", format!("Batch {} Dependencies", batch.0).bold());
+        display_recursion_tip_dependencies(batch, &mut conn).await;
+        display_scheduler_dependencies(batch, &mut conn).await;
+    }
+    Ok(())
+}
+
+fn is_proven(job: &ProverJobFriInfo) -> bool {
+    matches!(job.status, ProverJobStatus::Successful(_))
+}
+
+/// Formats the number of missing proofs among `jobs`.
+fn pending_proofs<'a>(name: &str, jobs: impl Iterator<Item = &'a ProverJobFriInfo>) -> String {
+    let (total, proven) = jobs.fold((0, 0), |(total, proven), job| {
+        (total + 1, proven + usize::from(is_proven(job)))
+    });
+    format!("waiting for {} of {total} {name}", total - proven)
+}
+
+async fn display_recursion_tip_dependencies(
+    batch_number: L1BatchNumber,
+    conn: &mut Connection<'_, Prover>,
+) {
+    let name = "Recursion Tip".bold();
+    let Some(job) = conn
+        .fri_witness_generator_dal()
+        .get_recursion_tip_witness_generator_jobs_for_batch(batch_number)
+        .await
+    else {
+        println!("{name}: {}", Status::JobsNotFound);
+        return;
+    };
+    if !matches!(job.status, WitnessJobStatus::WaitingForProofs) {
+        println!("{name}: {}", Status::from(job.status));
+        return;
+    }
+
+    let node_prover_jobs = conn
+        .fri_prover_jobs_dal()
+        .get_prover_jobs_stats_for_batch(batch_number, AggregationRound::NodeAggregation)
+        .await;
+    let final_node_proofs: BTreeMap<_, _> = node_prover_jobs
+        .iter()
+        .filter(|job| job.is_node_final_proof)
+        .map(|job| (job.circuit_id, job))
+        .collect();
+    let proven = final_node_proofs
+        .values()
+        .filter(|job| is_proven(job))
+        .count();
+    let expected = job.number_of_final_node_jobs as usize;
+    println!(
+        "v {name}: waiting for {} of {expected} final node proofs",
+        expected.saturating_sub(proven)
+    );
+
+    let mut leaf_jobs = conn
+        .fri_witness_generator_dal()
+        .get_leaf_witness_generator_jobs_for_batch(batch_number)
+        .await;
+    leaf_jobs.sort_by_key(|job| job.circuit_id);
+    let node_jobs = conn
+        .fri_witness_generator_dal()
+        .get_node_witness_generator_jobs_for_batch(batch_number)
+        .await;
+    let leaf_prover_jobs = conn
+        .fri_prover_jobs_dal()
+        .get_prover_jobs_stats_for_batch(batch_number, AggregationRound::LeafAggregation)
+        .await;
+    let basic_prover_jobs = conn
+        .fri_prover_jobs_dal()
+        .get_prover_jobs_stats_for_batch(batch_number, AggregationRound::BasicCircuits)
+        .await;
+
+    // Walk the proof tree of each circuit top-down to find the stage that blocks its final node proof.
+    for leaf_job in leaf_jobs {
+        let circuit_id = leaf_job.circuit_id;
+        let blocker = if let Some(proof) = final_node_proofs.get(&circuit_id) {
+            if is_proven(proof) {
+                continue;
+            }
+            format!(
+                "final node proof (job {}): {}",
+                proof.id,
+                Status::from(proof.status.clone())
+            )
+        } else if let Some(node_job) = node_jobs
+            .iter()
+            .filter(|job| job.circuit_id == circuit_id)
+            .max_by_key(|job| job.depth)
+        {
+            if matches!(node_job.status, WitnessJobStatus::Successful(_)) {
+                let proofs = node_prover_jobs
+                    .iter()
+                    .filter(|job| job.circuit_id == circuit_id && job.depth == node_job.depth);
+                format!(
+                    "node proofs (depth {}): {}",
+                    node_job.depth,
+                    pending_proofs("proofs", proofs)
+                )
+            } else {
+                format!(
+                    "node witness generator (depth {}): {}",
+                    node_job.depth,
+                    Status::from(node_job.status.clone())
+                )
+            }
+        } else {
+            match &leaf_job.status {
+                WitnessJobStatus::WaitingForProofs => {
+                    let proofs = basic_prover_jobs
+                        .iter()
+                        .filter(|job| job.circuit_id == circuit_id);
+                    format!(
+                        "leaf witness generator: {}",
+                        pending_proofs("basic proofs", proofs)
+                    )
+                }
+                WitnessJobStatus::Successful(_) => {
+                    let proofs = leaf_prover_jobs
+                        .iter()
+                        .filter(|job| job.circuit_id == circuit_id);
+                    format!("leaf proofs: {}", pending_proofs("proofs", proofs))
+                }
+                status => format!("leaf witness generator: {}", Status::from(status.clone())),
+            }
+        };
+        println!(
+            "   > {}: {blocker}",
+            format!(
+                "{:?}",
+                BaseLayerCircuitType::from_numeric_value(circuit_id as u8)
+            )
+            .bold()
+        );
+    }
+}
+
+async fn display_scheduler_dependencies(
+    batch_number: L1BatchNumber,
+    conn: &mut Connection<'_, Prover>,
+) {
+    let name = "Scheduler".bold();
+    let Some(job) = conn
+        .fri_witness_generator_dal()
+        .get_scheduler_witness_generator_jobs_for_batch(batch_number)
+        .await
+    else {
+        println!("{name}: {}", Status::JobsNotFound);
+        return;
+    };
+    if !matches!(job.status, WitnessJobStatus::WaitingForProofs) {
+        println!("{name}: {}", Status::from(job.status));
+        return;
+    }
+
+    let recursion_tip_proofs = conn
+        .fri_prover_jobs_dal()
+        .get_prover_jobs_stats_for_batch(batch_number, AggregationRound::RecursionTip)
+        .await;
+    if let Some(proof) = recursion_tip_proofs.first() {
+        println!(
+            "v {name}: waiting for recursion tip proof (job {}): {}",
+            proof.id,
+            Status::from(proof.status.clone())
+        );
+    } else {
+        println!("v {name}: waiting for recursion tip proof (not created yet)");
+    }
+}
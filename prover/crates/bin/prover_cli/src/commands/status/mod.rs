@@ -3,12 +3,15 @@ use clap::Subcommand;
 use crate::cli::ProverCLIConfig;
 
 pub(crate) mod batch;
+pub(crate) mod dependencies;
 pub(crate) mod l1;
 pub mod utils;
 
 #[derive(Subcommand)]
 pub enum StatusCommand {
     Batch(batch::Args),
+    /// Shows which proofs the recursion tip and scheduler stages of batches are waiting for.
+    Dependencies(dependencies::Args),
     L1,
 }
 
@@ -16,6 +19,7 @@ impl StatusCommand {
     pub(crate) async fn run(self, config: ProverCLIConfig) -> anyhow::Result<()> {
         match self {
             StatusCommand::Batch(args) => batch::run(args, config).await,
+            StatusCommand::Dependencies(args) => dependencies::run(args, config).await,
             StatusCommand::L1 => l1::run().await,
         }
     }
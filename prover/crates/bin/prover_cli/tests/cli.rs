@@ -19,6 +19,18 @@ fn pli_help_succeeds() {
         .success();
 }
 
+#[test]
+#[doc = "prover_cli replay-batch"]
+fn pli_replay_batch_without_source_fails() {
+    Command::cargo_bin("prover_cli")
+        .unwrap()
+        .arg("replay-batch")
+        .arg("--number=1")
+        .env_remove("PLI__SOURCE_DB_URL")
+        .assert()
+        .failure();
+}
+
 #[tokio::test]
 #[doc = "prover_cli config"]
 async fn pli_config_succeeds() {
@@ -11,7 +11,9 @@ use tokio::{
     task::JoinHandle,
 };
 use zksync_config::configs::{DatabaseSecrets, FriProverConfig};
-use zksync_core_leftovers::temp_config_store::{load_database_secrets, load_general_config};
+use zksync_core_leftovers::temp_config_store::{
+    load_database_secrets, load_general_config, load_s3_secrets,
+};
 use zksync_env_config::FromEnv;
 use zksync_object_store::{ObjectStore, ObjectStoreFactory};
 use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
@@ -58,6 +60,7 @@ async fn main() -> anyhow::Result<()> {
     let opt = Cli::parse();
 
     let general_config = load_general_config(opt.config_path).context("general config")?;
+    let s3_secrets = load_s3_secrets(opt.secrets_path.clone()).context("S3 secrets")?;
     let database_secrets = load_database_secrets(opt.secrets_path).context("database secrets")?;
 
     let observability_config = general_config
@@ -89,7 +92,8 @@ async fn main() -> anyhow::Result<()> {
         .prover_object_store
         .clone()
         .context("prover object store config")?;
-    let object_store_factory = ObjectStoreFactory::new(prover_object_store_config);
+    let object_store_factory =
+        ObjectStoreFactory::new(prover_object_store_config).with_s3_secrets(s3_secrets.clone());
     let public_object_store_config = prover_config
         .public_object_store
         .clone()
@@ -98,6 +102,7 @@ async fn main() -> anyhow::Result<()> {
         false => None,
         true => Some(
             ObjectStoreFactory::new(public_object_store_config)
+                .with_s3_secrets(s3_secrets)
                 .create_store()
                 .await?,
         ),
@@ -1,11 +1,23 @@
 use std::sync::Arc;
 
-use serde::{de::DeserializeOwned, Serialize};
+use anyhow::Context as _;
+use reqwest::{header, StatusCode};
 use zksync_object_store::ObjectStore;
 use zksync_prover_dal::{ConnectionPool, Prover};
+use zksync_prover_interface::api::{ArtifactInfo, UploadStatus};
+use zksync_types::{web3::keccak256, H256};
+
+/// Header specifying the offset of an uploaded chunk.
+const UPLOAD_OFFSET_HEADER: &str = "upload-offset";
+/// Size of chunks used to upload artifacts.
+const UPLOAD_CHUNK_SIZE: usize = 4 << 20;
+/// Maximum number of attempts to transfer an artifact. Each attempt continues the transfer from the point
+/// where the previous attempt was interrupted.
+const MAX_TRANSFER_ATTEMPTS: usize = 5;
 
 /// A tiny wrapper over the reqwest client that also stores
-/// the objects commonly needed when interacting with prover API.
+/// the objects commonly needed when interacting with prover API. Artifacts are transferred
+/// using the v2 proof data handler API, which allows resuming interrupted transfers.
 #[derive(Debug)]
 pub(crate) struct ProverApiClient {
     pub(crate) blob_store: Arc<dyn ObjectStore>,
@@ -28,24 +40,158 @@ impl ProverApiClient {
         }
     }
 
-    pub(crate) async fn send_http_request<Req, Resp>(
+    /// Downloads an artifact from the v2 API, continuing an interrupted download using range requests.
+    /// The downloaded artifact is checked against `artifact`.
+    pub(crate) async fn download_artifact(
+        &self,
+        endpoint: &str,
+        artifact: ArtifactInfo,
+    ) -> anyhow::Result<Vec<u8>> {
+        tracing::info!("Downloading {artifact:?} from {endpoint}");
+
+        let mut bytes = Vec::with_capacity(artifact.size as usize);
+        for attempt in 1..=MAX_TRANSFER_ATTEMPTS {
+            match self.download_remaining_bytes(endpoint, &mut bytes).await {
+                Ok(()) if bytes.len() as u64 >= artifact.size => break,
+                Ok(()) => {
+                    tracing::warn!(
+                        "Download from {endpoint} ended prematurely at byte {} (attempt {attempt})",
+                        bytes.len()
+                    );
+                }
+                Err(err) if attempt < MAX_TRANSFER_ATTEMPTS => {
+                    tracing::warn!(
+                        "Download from {endpoint} was interrupted at byte {} (attempt {attempt}): {err}",
+                        bytes.len()
+                    );
+                }
+                Err(err) => {
+                    return Err(err).with_context(|| format!("failed downloading from {endpoint}"));
+                }
+            }
+        }
+
+        anyhow::ensure!(
+            bytes.len() as u64 == artifact.size,
+            "downloaded {} bytes from {endpoint}, while {} bytes were expected",
+            bytes.len(),
+            artifact.size
+        );
+        let content_hash = H256(keccak256(&bytes));
+        anyhow::ensure!(
+            content_hash == artifact.content_hash,
+            "hash of the artifact downloaded from {endpoint} {content_hash:?} differs from the expected {:?}",
+            artifact.content_hash
+        );
+        Ok(bytes)
+    }
+
+    async fn download_remaining_bytes(
         &self,
-        request: Req,
         endpoint: &str,
-    ) -> Result<Resp, reqwest::Error>
-    where
-        Req: Serialize,
-        Resp: DeserializeOwned,
-    {
-        tracing::info!("Sending request to {}", endpoint);
-
-        self.client
+        bytes: &mut Vec<u8>,
+    ) -> reqwest::Result<()> {
+        let mut request = self.client.get(endpoint);
+        if !bytes.is_empty() {
+            request = request.header(header::RANGE, format!("bytes={}-", bytes.len()));
+        }
+        let mut response = request.send().await?.error_for_status()?;
+        if response.status() != StatusCode::PARTIAL_CONTENT {
+            // The entire artifact is returned.
+            bytes.clear();
+        }
+        while let Some(chunk) = response.chunk().await? {
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok(())
+    }
+
+    /// Uploads an artifact to the v2 API in chunks. If the upload is interrupted, it is continued from the offset
+    /// reported by the server.
+    pub(crate) async fn upload_artifact(
+        &self,
+        endpoint: &str,
+        bytes: &[u8],
+    ) -> anyhow::Result<UploadStatus> {
+        let artifact = ArtifactInfo {
+            size: bytes.len() as u64,
+            content_hash: H256(keccak256(bytes)),
+        };
+        tracing::info!("Uploading {artifact:?} to {endpoint}");
+
+        let mut status = self.start_upload(endpoint, artifact).await?;
+        let mut attempt = 1;
+        while status.offset < artifact.size {
+            let start = status.offset as usize;
+            let end = (start + UPLOAD_CHUNK_SIZE).min(bytes.len());
+            match self
+                .upload_chunk(endpoint, status.offset, &bytes[start..end])
+                .await
+            {
+                Ok(new_status) => {
+                    anyhow::ensure!(
+                        new_status.artifact == artifact && new_status.offset <= artifact.size,
+                        "unexpected upload status returned by {endpoint}: {new_status:?}"
+                    );
+                    status = new_status;
+                }
+                Err(err) if attempt < MAX_TRANSFER_ATTEMPTS => {
+                    tracing::warn!(
+                        "Upload to {endpoint} was interrupted at byte {} (attempt {attempt}): {err}",
+                        status.offset
+                    );
+                    attempt += 1;
+                    // The chunk may have been received by the server, so we resume from the server offset.
+                    status = self.start_upload(endpoint, artifact).await?;
+                }
+                Err(err) => {
+                    return Err(err).with_context(|| format!("failed uploading to {endpoint}"));
+                }
+            }
+        }
+        Ok(status)
+    }
+
+    async fn start_upload(
+        &self,
+        endpoint: &str,
+        artifact: ArtifactInfo,
+    ) -> anyhow::Result<UploadStatus> {
+        let status: UploadStatus = self
+            .client
             .post(endpoint)
-            .json(&request)
+            .json(&artifact)
             .send()
             .await?
             .error_for_status()?
-            .json::<Resp>()
-            .await
+            .json()
+            .await?;
+        anyhow::ensure!(
+            status.artifact == artifact && status.offset <= artifact.size,
+            "unexpected upload status returned by {endpoint}: {status:?}"
+        );
+        Ok(status)
+    }
+
+    async fn upload_chunk(
+        &self,
+        endpoint: &str,
+        offset: u64,
+        chunk: &[u8],
+    ) -> reqwest::Result<UploadStatus> {
+        let response = self
+            .client
+            .patch(endpoint)
+            .header(UPLOAD_OFFSET_HEADER, offset.to_string())
+            .header(header::CONTENT_TYPE, "application/octet-stream")
+            .body(chunk.to_vec())
+            .send()
+            .await?;
+        if response.status() == StatusCode::CONFLICT {
+            // The offset doesn't match the server one, e.g. because the response to the previous chunk was lost.
+            // The response contains the actual upload status.
+            return response.json().await;
+        }
+        response.error_for_status()?.json().await
     }
 }
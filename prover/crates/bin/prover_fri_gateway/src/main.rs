@@ -6,7 +6,9 @@ use proof_gen_data_fetcher::ProofGenDataFetcher;
 use proof_submitter::ProofSubmitter;
 use tokio::sync::{oneshot, watch};
 use traits::PeriodicApi as _;
-use zksync_core_leftovers::temp_config_store::{load_database_secrets, load_general_config};
+use zksync_core_leftovers::temp_config_store::{
+    load_database_secrets, load_general_config, load_s3_secrets,
+};
 use zksync_env_config::object_store::ProverObjectStoreConfig;
 use zksync_object_store::ObjectStoreFactory;
 use zksync_prover_dal::{ConnectionPool, Prover};
@@ -24,6 +26,7 @@ async fn main() -> anyhow::Result<()> {
     let opt = Cli::parse();
 
     let general_config = load_general_config(opt.config_path).context("general config")?;
+    let s3_secrets = load_s3_secrets(opt.secrets_path.clone()).context("S3 secrets")?;
     let database_secrets = load_database_secrets(opt.secrets_path).context("database secrets")?;
 
     let observability_config = general_config
@@ -50,7 +53,7 @@ async fn main() -> anyhow::Result<()> {
             .prover_object_store
             .context("object store")?,
     );
-    let store_factory = ObjectStoreFactory::new(object_store_config.0);
+    let store_factory = ObjectStoreFactory::new(object_store_config.0).with_s3_secrets(s3_secrets);
 
     let proof_submitter = ProofSubmitter::new(
         store_factory.create_store().await?,
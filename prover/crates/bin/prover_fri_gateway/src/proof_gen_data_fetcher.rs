@@ -1,11 +1,10 @@
 use std::sync::Arc;
 
+use anyhow::Context as _;
 use async_trait::async_trait;
-use zksync_object_store::ObjectStore;
+use zksync_object_store::{bincode, ObjectStore};
 use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
-use zksync_prover_interface::api::{
-    ProofGenerationData, ProofGenerationDataRequest, ProofGenerationDataResponse,
-};
+use zksync_prover_interface::api::{ProofGenerationData, ProofGenerationDataInfo};
 
 use crate::{client::ProverApiClient, traits::PeriodicApi};
 
@@ -14,8 +13,9 @@ use crate::{client::ProverApiClient, traits::PeriodicApi};
 #[derive(Debug)]
 pub struct ProofGenDataFetcher(ProverApiClient);
 
-/// The path to the API endpoint that returns the next proof generation data.
-const PROOF_GENERATION_DATA_PATH: &str = "/proof_generation_data";
+/// The path to the API endpoint that locks the next batch for proving. Proof generation data for the locked batch
+/// is downloaded from `{PROOF_GENERATION_DATA_PATH}/{l1_batch_number}`.
+const PROOF_GENERATION_DATA_PATH: &str = "/v2/proof_generation_data";
 
 impl ProofGenDataFetcher {
     pub(crate) fn new(
@@ -58,35 +58,51 @@ impl ProofGenDataFetcher {
 #[async_trait]
 impl PeriodicApi for ProofGenDataFetcher {
     type JobId = ();
-    type Request = ProofGenerationDataRequest;
-    type Response = ProofGenerationDataResponse;
+    type Request = ();
+    type Response = Option<ProofGenerationData>;
 
     const SERVICE_NAME: &'static str = "ProofGenDataFetcher";
 
-    async fn get_next_request(&self) -> Option<(Self::JobId, ProofGenerationDataRequest)> {
-        Some(((), ProofGenerationDataRequest {}))
+    async fn get_next_request(&self) -> Option<(Self::JobId, Self::Request)> {
+        Some(((), ()))
     }
 
-    async fn send_request(
-        &self,
-        _: (),
-        request: ProofGenerationDataRequest,
-    ) -> reqwest::Result<Self::Response> {
-        self.0.send_http_request(request, &self.0.api_url).await
+    async fn send_request(&self, _: (), _: ()) -> anyhow::Result<Self::Response> {
+        let info: Option<ProofGenerationDataInfo> = self
+            .0
+            .client
+            .post(&self.0.api_url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let Some(info) = info else {
+            return Ok(None);
+        };
+
+        let endpoint = format!("{}/{}", self.0.api_url, info.l1_batch_number);
+        let bytes = self.0.download_artifact(&endpoint, info.artifact).await?;
+        let data: ProofGenerationData =
+            bincode::deserialize(&bytes).context("failed deserializing proof generation data")?;
+        anyhow::ensure!(
+            data.l1_batch_number == info.l1_batch_number,
+            "downloaded proof generation data is for L1 batch #{}, while #{} was expected",
+            data.l1_batch_number,
+            info.l1_batch_number
+        );
+        Ok(Some(data))
     }
 
     async fn handle_response(&self, _: (), response: Self::Response) {
         match response {
-            ProofGenerationDataResponse::Success(Some(data)) => {
+            Some(data) => {
                 tracing::info!("Received proof gen data for: {:?}", data.l1_batch_number);
-                self.save_proof_gen_data(*data).await;
+                self.save_proof_gen_data(data).await;
             }
-            ProofGenerationDataResponse::Success(None) => {
+            None => {
                 tracing::info!("There are currently no pending batches to be proven");
             }
-            ProofGenerationDataResponse::Error(err) => {
-                tracing::error!("Failed to get proof gen data: {:?}", err);
-            }
         }
     }
 }
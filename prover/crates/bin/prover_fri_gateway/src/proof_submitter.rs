@@ -1,15 +1,17 @@
 use std::sync::Arc;
 
+use anyhow::Context as _;
 use async_trait::async_trait;
-use zksync_object_store::ObjectStore;
+use zksync_object_store::{bincode, ObjectStore};
 use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
-use zksync_prover_interface::api::{SubmitProofRequest, SubmitProofResponse};
+use zksync_prover_interface::api::{SubmitProofRequest, UploadStatus};
 use zksync_types::{prover_dal::ProofCompressionJobStatus, L1BatchNumber};
 
 use crate::{client::ProverApiClient, traits::PeriodicApi};
 
-/// The path to the API endpoint that submits the proof.
-const SUBMIT_PROOF_PATH: &str = "/submit_proof";
+/// The path to the API endpoint that uploads the proof. The proof for a batch is uploaded
+/// to `{SUBMIT_PROOF_PATH}/{l1_batch_number}`.
+const SUBMIT_PROOF_PATH: &str = "/v2/proofs";
 
 /// Poller structure that will periodically check the database for new proofs to submit.
 /// Once a new proof is detected, it will be sent to the prover API.
@@ -76,7 +78,7 @@ impl ProofSubmitter {
 impl PeriodicApi for ProofSubmitter {
     type JobId = L1BatchNumber;
     type Request = SubmitProofRequest;
-    type Response = SubmitProofResponse;
+    type Response = UploadStatus;
     const SERVICE_NAME: &'static str = "ProofSubmitter";
 
     async fn get_next_request(&self) -> Option<(Self::JobId, SubmitProofRequest)> {
@@ -88,9 +90,10 @@ impl PeriodicApi for ProofSubmitter {
         &self,
         job_id: Self::JobId,
         request: SubmitProofRequest,
-    ) -> reqwest::Result<Self::Response> {
+    ) -> anyhow::Result<Self::Response> {
         let endpoint = format!("{}/{job_id}", self.0.api_url);
-        self.0.send_http_request(request, &endpoint).await
+        let bytes = bincode::serialize(&request).context("failed serializing proof")?;
+        self.0.upload_artifact(&endpoint, &bytes).await
     }
 
     async fn handle_response(&self, job_id: L1BatchNumber, response: Self::Response) {
@@ -21,7 +21,7 @@ pub(crate) trait PeriodicApi: Sync + Send + 'static + Sized {
         &self,
         job_id: Self::JobId,
         request: Self::Request,
-    ) -> reqwest::Result<Self::Response>;
+    ) -> anyhow::Result<Self::Response>;
 
     /// Handles the response from the API.
     async fn handle_response(&self, job_id: Self::JobId, response: Self::Response);
@@ -51,7 +51,7 @@ pub(crate) trait PeriodicApi: Sync + Send + 'static + Sized {
                     }
                     Err(err) => {
                         METRICS.http_error[&Self::SERVICE_NAME].inc();
-                        tracing::error!("HTTP request failed due to error: {}", err);
+                        tracing::error!("HTTP request failed due to error: {err:#}");
                     }
                 }
             }
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
 use axum::{
     http::StatusCode,
@@ -14,9 +14,12 @@ use zksync_types::{
     prover_dal::JobCountStatistics,
 };
 
+use crate::scaling_signals::{collect_scaling_signals, VersionedScalingSignals};
+
 #[derive(Debug, Clone)]
 pub struct AutoscalerQueueReporter {
     connection_pool: ConnectionPool<Prover>,
+    target_backlog: Duration,
 }
 
 #[derive(Default, Debug, Serialize, Deserialize)]
@@ -37,8 +40,21 @@ pub struct VersionedQueueReport {
 }
 
 impl AutoscalerQueueReporter {
-    pub fn new(connection_pool: ConnectionPool<Prover>) -> Self {
-        Self { connection_pool }
+    pub fn new(connection_pool: ConnectionPool<Prover>, target_backlog: Duration) -> Self {
+        Self {
+            connection_pool,
+            target_backlog,
+        }
+    }
+
+    pub async fn get_scaling_signals(
+        &self,
+    ) -> Result<Json<Vec<VersionedScalingSignals>>, ProcessorError> {
+        tracing::debug!("Received request to get scaling signals");
+
+        let mut connection = self.connection_pool.connection().await?;
+        let signals = collect_scaling_signals(&mut connection, self.target_backlog).await;
+        Ok(Json(signals))
     }
 
     pub async fn get_report(&self) -> Result<Json<Vec<VersionedQueueReport>>, ProcessorError> {
@@ -130,13 +146,22 @@ impl AutoscalerQueueReporter {
     }
 }
 
-pub fn get_queue_reporter_router(connection_pool: ConnectionPool<Prover>) -> Router {
-    let autoscaler_queue_reporter = AutoscalerQueueReporter::new(connection_pool);
-
-    Router::new().route(
-        "/queue_report",
-        get(move || async move { autoscaler_queue_reporter.get_report().await }),
-    )
+pub fn get_queue_reporter_router(
+    connection_pool: ConnectionPool<Prover>,
+    target_backlog: Duration,
+) -> Router {
+    let autoscaler_queue_reporter = AutoscalerQueueReporter::new(connection_pool, target_backlog);
+    let reporter_for_signals = autoscaler_queue_reporter.clone();
+
+    Router::new()
+        .route(
+            "/queue_report",
+            get(move || async move { autoscaler_queue_reporter.get_report().await }),
+        )
+        .route(
+            "/scaling_signals",
+            get(move || async move { reporter_for_signals.get_scaling_signals().await }),
+        )
 }
 
 pub enum ProcessorError {
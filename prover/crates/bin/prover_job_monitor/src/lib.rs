@@ -3,5 +3,6 @@ pub mod autoscaler_queue_reporter;
 pub mod job_requeuer;
 pub(crate) mod metrics;
 pub mod queue_reporter;
+pub mod scaling_signals;
 pub mod task_wiring;
 pub mod witness_job_queuer;
@@ -19,6 +19,7 @@ use zksync_prover_job_monitor::{
     queue_reporter::{
         ProofCompressorQueueReporter, ProverQueueReporter, WitnessGeneratorQueueReporter,
     },
+    scaling_signals::ScalingSignalsReporter,
     task_wiring::TaskRunner,
     witness_job_queuer::WitnessJobQueuer,
 };
@@ -107,7 +108,11 @@ async fn main() -> anyhow::Result<()> {
         .with_context(|| format!("Failed binding PJM server to {bind_address}"))?;
 
     let mut receiver = stop_receiver.clone();
-    let app = axum::serve(listener, get_queue_reporter_router(connection_pool))
+    let router = get_queue_reporter_router(
+        connection_pool,
+        prover_job_monitor_config.scaling_signals_target_backlog(),
+    );
+    let app = axum::serve(listener, router)
         .with_graceful_shutdown(async move {
             if receiver.changed().await.is_err() {
                 tracing::warn!(
@@ -212,6 +217,14 @@ fn get_tasks(
         witness_generator_queue_reporter,
     );
 
+    let scaling_signals_reporter =
+        ScalingSignalsReporter::new(prover_job_monitor_config.scaling_signals_target_backlog());
+    task_runner.add(
+        "ScalingSignalsReporter",
+        prover_job_monitor_config.prover_queue_reporter_run_interval(),
+        scaling_signals_reporter,
+    );
+
     // witness job queuer
     let witness_job_queuer = WitnessJobQueuer {};
     task_runner.add(
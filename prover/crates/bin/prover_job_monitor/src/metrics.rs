@@ -118,7 +118,34 @@ pub(crate) struct ServerMetrics {
     pub node_fri_witness_generator_waiting_to_queued_jobs_transitions: Counter<u64>,
     pub recursion_tip_witness_generator_waiting_to_queued_jobs_transitions: Counter<u64>,
     pub scheduler_witness_generator_waiting_to_queued_jobs_transitions: Counter<u64>,
+    /// Number of recursion tip / scheduler witness generator jobs waiting for proofs.
+    #[metrics(labels = ["round"])]
+    pub witness_generator_waiting_for_proofs_jobs: LabeledFamily<&'static str, Gauge<u64>>,
+    /// Total number of proofs that recursion tip / scheduler witness generator jobs are waiting for.
+    #[metrics(labels = ["round"])]
+    pub witness_generator_missing_proof_dependencies: LabeledFamily<&'static str, Gauge<u64>>,
+    /// Oldest batch with a recursion tip / scheduler witness generator job waiting for proofs.
+    #[metrics(labels = ["round"])]
+    pub witness_generator_oldest_waiting_for_proofs_batch: LabeledFamily<&'static str, Gauge<u64>>,
 }
 
 #[vise::register]
 pub(crate) static SERVER_METRICS: vise::Global<ServerMetrics> = vise::Global::new();
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "prover_job_monitor_scaling")]
+pub(crate) struct ScalingSignalsMetrics {
+    /// Estimated GPU time needed to prove all queued and in-progress circuits.
+    #[metrics(labels = ["round", "protocol_version"])]
+    pub backlog_seconds: LabeledFamily<(&'static str, String), Gauge<f64>, 2>,
+    /// Average time to prove a single circuit during the last hour.
+    #[metrics(labels = ["round"])]
+    pub average_proving_time_seconds: LabeledFamily<&'static str, Gauge<f64>>,
+    /// Number of GPU provers needed to clear the backlog within the target time.
+    #[metrics(labels = ["protocol_version"])]
+    pub desired_gpu_count: LabeledFamily<String, Gauge<u64>>,
+}
+
+#[vise::register]
+pub(crate) static SCALING_SIGNALS_METRICS: vise::Global<ScalingSignalsMetrics> =
+    vise::Global::new();
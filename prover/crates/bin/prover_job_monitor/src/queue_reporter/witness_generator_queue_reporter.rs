@@ -1,8 +1,9 @@
 use async_trait::async_trait;
 use zksync_prover_dal::{Connection, Prover, ProverDal};
 use zksync_types::{
-    basic_fri_types::AggregationRound, protocol_version::ProtocolSemanticVersion,
-    prover_dal::JobCountStatistics,
+    basic_fri_types::AggregationRound,
+    protocol_version::ProtocolSemanticVersion,
+    prover_dal::{JobCountStatistics, ProofDependencies},
 };
 
 use crate::{metrics::SERVER_METRICS, task_wiring::Task};
@@ -48,6 +49,36 @@ impl WitnessGeneratorQueueReporter {
         )]
             .set(stats.in_progress as u64);
     }
+
+    /// Reports progress of recursion tip / scheduler jobs waiting for proofs, so that it's visible
+    /// which batches are blocked and on how many proofs.
+    fn emit_dependency_metrics_for_round(
+        round: AggregationRound,
+        dependencies: &[ProofDependencies],
+    ) {
+        for batch_dependencies in dependencies {
+            tracing::debug!(
+                "{} job for batch {} is waiting for {} of {} proofs.",
+                round,
+                batch_dependencies.l1_batch_number,
+                batch_dependencies.missing(),
+                batch_dependencies.expected
+            );
+        }
+
+        let round_label = round.as_str();
+        let missing: usize = dependencies.iter().map(ProofDependencies::missing).sum();
+        SERVER_METRICS.witness_generator_waiting_for_proofs_jobs[&round_label]
+            .set(dependencies.len() as u64);
+        SERVER_METRICS.witness_generator_missing_proof_dependencies[&round_label]
+            .set(missing as u64);
+        // Dependencies are ordered by batch number.
+        let oldest_batch = dependencies
+            .first()
+            .map_or(0, |dependencies| dependencies.l1_batch_number.0);
+        SERVER_METRICS.witness_generator_oldest_waiting_for_proofs_batch[&round_label]
+            .set(oldest_batch.into());
+    }
 }
 
 #[async_trait]
@@ -63,6 +94,23 @@ impl Task for WitnessGeneratorQueueReporter {
             }
         }
 
+        let recursion_tip_dependencies = connection
+            .fri_witness_generator_dal()
+            .get_recursion_tip_dependencies()
+            .await;
+        Self::emit_dependency_metrics_for_round(
+            AggregationRound::RecursionTip,
+            &recursion_tip_dependencies,
+        );
+        let scheduler_dependencies = connection
+            .fri_witness_generator_dal()
+            .get_scheduler_dependencies()
+            .await;
+        Self::emit_dependency_metrics_for_round(
+            AggregationRound::Scheduler,
+            &scheduler_dependencies,
+        );
+
         Ok(())
     }
 }
@@ -0,0 +1,303 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use zksync_prover_dal::{Connection, Prover, ProverDal};
+use zksync_types::{
+    basic_fri_types::AggregationRound, protocol_version::ProtocolSemanticVersion,
+    prover_dal::JobCountStatistics,
+};
+
+use crate::{metrics::SCALING_SIGNALS_METRICS, task_wiring::Task};
+
+/// Window over which average proving times are computed.
+const PROVING_TIME_WINDOW: Duration = Duration::from_secs(3_600);
+
+/// Scaling signals for a single aggregation round.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoundScalingSignals {
+    pub aggregation_round: AggregationRound,
+    /// Witness generator jobs for the round.
+    pub witness_jobs: JobCountStatistics,
+    /// Prover jobs (i.e., circuits to be proven) for the round.
+    pub prover_jobs: JobCountStatistics,
+    /// Average time to prove a circuit in the round during the last hour. `None` if no circuits
+    /// were proven during this time.
+    pub average_proving_time_secs: Option<f64>,
+    /// Estimated GPU time needed to prove all queued and in-progress circuits in the round.
+    /// `None` if the average proving time is unknown.
+    pub backlog_secs: Option<f64>,
+}
+
+/// Scaling signals for a single protocol version.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionedScalingSignals {
+    pub version: ProtocolSemanticVersion,
+    pub rounds: Vec<RoundScalingSignals>,
+    /// Estimated GPU time needed to prove all circuits with known proving time.
+    pub backlog_secs: f64,
+    /// Number of GPU provers needed to clear the backlog within the target time. Is at least 1
+    /// if there are circuits to be proven, even if their proving time is unknown.
+    pub desired_gpu_count: u64,
+}
+
+impl VersionedScalingSignals {
+    fn new(
+        version: ProtocolSemanticVersion,
+        jobs_by_round: &HashMap<AggregationRound, (JobCountStatistics, JobCountStatistics)>,
+        average_proving_times: &HashMap<AggregationRound, Duration>,
+        target_backlog: Duration,
+    ) -> Self {
+        let rounds: Vec<_> = AggregationRound::ALL_ROUNDS
+            .into_iter()
+            .map(|round| {
+                let (witness_jobs, prover_jobs) =
+                    jobs_by_round.get(&round).copied().unwrap_or_default();
+                let average_proving_time_secs =
+                    average_proving_times.get(&round).map(Duration::as_secs_f64);
+                let backlog_secs =
+                    average_proving_time_secs.map(|secs| secs * prover_jobs.all() as f64);
+                RoundScalingSignals {
+                    aggregation_round: round,
+                    witness_jobs,
+                    prover_jobs,
+                    average_proving_time_secs,
+                    backlog_secs,
+                }
+            })
+            .collect();
+
+        let backlog_secs: f64 = rounds.iter().filter_map(|round| round.backlog_secs).sum();
+        let target_backlog_secs = target_backlog.max(Duration::from_secs(1)).as_secs_f64();
+        let mut desired_gpu_count = (backlog_secs / target_backlog_secs).ceil() as u64;
+        let has_pending_prover_jobs = rounds.iter().any(|round| round.prover_jobs.all() > 0);
+        if has_pending_prover_jobs {
+            desired_gpu_count = desired_gpu_count.max(1);
+        }
+
+        Self {
+            version,
+            rounds,
+            backlog_secs,
+            desired_gpu_count,
+        }
+    }
+
+    fn emit_metrics(&self) {
+        let version = self.version.to_string();
+        for round in &self.rounds {
+            // Unknown backlog is reported as 0 so that a previously reported value doesn't linger.
+            SCALING_SIGNALS_METRICS.backlog_seconds
+                [&(round.aggregation_round.as_str(), version.clone())]
+                .set(round.backlog_secs.unwrap_or(0.0));
+        }
+        SCALING_SIGNALS_METRICS.desired_gpu_count[&version].set(self.desired_gpu_count);
+    }
+}
+
+/// Emits metrics for the provided signals. Gauges for protocol versions without pending jobs
+/// (i.e., reported previously, but absent from `signals`) are reset to 0.
+fn emit_metrics(signals: &[VersionedScalingSignals]) {
+    let reported_versions: HashSet<_> = signals
+        .iter()
+        .map(|signals| signals.version.to_string())
+        .collect();
+    for (labels, gauge) in SCALING_SIGNALS_METRICS.backlog_seconds.to_entries() {
+        if !reported_versions.contains(&labels.1) {
+            gauge.set(0.0);
+        }
+    }
+    for (version, gauge) in SCALING_SIGNALS_METRICS.desired_gpu_count.to_entries() {
+        if !reported_versions.contains(&version) {
+            gauge.set(0);
+        }
+    }
+
+    for signals in signals {
+        signals.emit_metrics();
+    }
+}
+
+/// Collects scaling signals for all protocol versions with pending witness generator or prover jobs.
+/// Signals are ordered by protocol version.
+pub async fn collect_scaling_signals(
+    connection: &mut Connection<'_, Prover>,
+    target_backlog: Duration,
+) -> Vec<VersionedScalingSignals> {
+    let mut jobs = BTreeMap::<
+        ProtocolSemanticVersion,
+        HashMap<AggregationRound, (JobCountStatistics, JobCountStatistics)>,
+    >::new();
+
+    for round in AggregationRound::ALL_ROUNDS {
+        let stats = connection
+            .fri_witness_generator_dal()
+            .get_witness_jobs_stats(round)
+            .await;
+        for (protocol_version, stats) in stats {
+            let entry = jobs
+                .entry(protocol_version)
+                .or_default()
+                .entry(round)
+                .or_default();
+            entry.0 = stats;
+        }
+    }
+
+    let stats = connection
+        .fri_prover_jobs_dal()
+        .get_prover_jobs_stats()
+        .await;
+    for (protocol_version, circuit_stats) in stats {
+        let jobs_by_round = jobs.entry(protocol_version).or_default();
+        for (tuple, stats) in circuit_stats {
+            let round = AggregationRound::from(tuple.aggregation_round);
+            let entry = &mut jobs_by_round.entry(round).or_default().1;
+            entry.queued += stats.queued;
+            entry.in_progress += stats.in_progress;
+        }
+    }
+
+    let average_proving_times = connection
+        .fri_prover_jobs_dal()
+        .get_average_proving_times(PROVING_TIME_WINDOW)
+        .await;
+    for round in AggregationRound::ALL_ROUNDS {
+        // Rounds without proven circuits in the window are reported as 0 rather than keeping a stale value.
+        let time = average_proving_times
+            .get(&round)
+            .copied()
+            .unwrap_or_default();
+        SCALING_SIGNALS_METRICS.average_proving_time_seconds[&round.as_str()]
+            .set(time.as_secs_f64());
+    }
+
+    jobs.into_iter()
+        .map(|(version, jobs_by_round)| {
+            VersionedScalingSignals::new(
+                version,
+                &jobs_by_round,
+                &average_proving_times,
+                target_backlog,
+            )
+        })
+        .collect()
+}
+
+/// `ScalingSignalsReporter` is a task that reports scaling signals for autoscalers as metrics.
+/// The same signals are available via the `/scaling_signals` HTTP endpoint.
+#[derive(Debug)]
+pub struct ScalingSignalsReporter {
+    target_backlog: Duration,
+}
+
+impl ScalingSignalsReporter {
+    pub fn new(target_backlog: Duration) -> Self {
+        Self { target_backlog }
+    }
+}
+
+#[async_trait]
+impl Task for ScalingSignalsReporter {
+    async fn invoke(&self, connection: &mut Connection<Prover>) -> anyhow::Result<()> {
+        let signals = collect_scaling_signals(connection, self.target_backlog).await;
+        emit_metrics(&signals);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::protocol_version::ProtocolVersionId;
+
+    use super::*;
+
+    fn stats(queued: usize, in_progress: usize) -> JobCountStatistics {
+        JobCountStatistics {
+            queued,
+            in_progress,
+        }
+    }
+
+    #[test]
+    fn computing_desired_gpu_count() {
+        let version = ProtocolSemanticVersion::default();
+        let jobs_by_round = HashMap::from([
+            (
+                AggregationRound::BasicCircuits,
+                (stats(1, 1), stats(100, 20)),
+            ),
+            (
+                AggregationRound::LeafAggregation,
+                (stats(0, 0), stats(5, 5)),
+            ),
+        ]);
+        let average_proving_times =
+            HashMap::from([(AggregationRound::BasicCircuits, Duration::from_secs(10))]);
+
+        let signals = VersionedScalingSignals::new(
+            version,
+            &jobs_by_round,
+            &average_proving_times,
+            Duration::from_secs(300),
+        );
+        assert_eq!(signals.rounds.len(), AggregationRound::ALL_ROUNDS.len());
+        assert_eq!(signals.rounds[0].backlog_secs, Some(1_200.0));
+        assert_eq!(signals.rounds[1].backlog_secs, None);
+        assert_eq!(signals.backlog_secs, 1_200.0);
+        assert_eq!(signals.desired_gpu_count, 4);
+
+        // Pending circuits with unknown proving time should still require a GPU.
+        let signals = VersionedScalingSignals::new(
+            version,
+            &jobs_by_round,
+            &HashMap::new(),
+            Duration::from_secs(300),
+        );
+        assert_eq!(signals.backlog_secs, 0.0);
+        assert_eq!(signals.desired_gpu_count, 1);
+
+        let signals = VersionedScalingSignals::new(
+            version,
+            &HashMap::new(),
+            &average_proving_times,
+            Duration::from_secs(300),
+        );
+        assert_eq!(signals.desired_gpu_count, 0);
+    }
+
+    #[test]
+    fn metrics_are_reset_for_absent_versions() {
+        let version = ProtocolSemanticVersion {
+            minor: ProtocolVersionId::latest(),
+            patch: 999.into(),
+        };
+        let jobs_by_round =
+            HashMap::from([(AggregationRound::BasicCircuits, (stats(0, 0), stats(10, 0)))]);
+        let average_proving_times =
+            HashMap::from([(AggregationRound::BasicCircuits, Duration::from_secs(60))]);
+        let signals = VersionedScalingSignals::new(
+            version,
+            &jobs_by_round,
+            &average_proving_times,
+            Duration::from_secs(300),
+        );
+        emit_metrics(&[signals]);
+
+        let backlog_labels = (
+            AggregationRound::BasicCircuits.as_str(),
+            version.to_string(),
+        );
+        let backlog = &SCALING_SIGNALS_METRICS.backlog_seconds[&backlog_labels];
+        assert_eq!(backlog.get(), 600.0);
+        let gpu_count = &SCALING_SIGNALS_METRICS.desired_gpu_count[&version.to_string()];
+        assert_eq!(gpu_count.get(), 2);
+
+        emit_metrics(&[]);
+        assert_eq!(backlog.get(), 0.0);
+        assert_eq!(gpu_count.get(), 0);
+    }
+}
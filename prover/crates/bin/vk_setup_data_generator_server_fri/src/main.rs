@@ -25,6 +25,7 @@ use zksync_prover_fri_types::{
 };
 use zksync_prover_keystore::{
     keystore::Keystore,
+    setup_data_downloader::{SetupDataDownloader, SetupDataKind},
     setup_data_generator::{CPUSetupDataGenerator, GPUSetupDataGenerator, SetupDataGenerator},
 };
 
@@ -174,6 +175,22 @@ enum Command {
         #[command(flatten)]
         options: GeneratorOptions,
     },
+    /// Downloads missing setup keys and verifies them against the setup data manifest
+    /// embedded into this binary.
+    #[command(name = "download-sk")]
+    DownloadSetupKeys {
+        /// Location to download keys from. Either a base `http(s)://` URL containing keys for the current
+        /// protocol version, or a `gs://<bucket>` URL of a GCS bucket containing keys for all protocol versions.
+        #[arg(long)]
+        url: String,
+        /// If true, downloads setup keys used by the GPU prover.
+        #[arg(long)]
+        gpu: bool,
+        #[arg(long)]
+        path: Option<String>,
+        #[arg(long)]
+        setup_path: Option<String>,
+    },
     /// Generates and updates the commitments - used by the verification contracts.
     #[command(name = "update-commitments")]
     UpdateCommitments {
@@ -262,6 +279,29 @@ fn main() -> anyhow::Result<()> {
             read_and_update_contract_toml(&keystore, dryrun)
         }
 
+        Command::DownloadSetupKeys {
+            url,
+            gpu,
+            path,
+            setup_path,
+        } => {
+            let keystore = keystore_from_optional_path(path, setup_path);
+            let kind = if gpu {
+                SetupDataKind::Gpu
+            } else {
+                SetupDataKind::Cpu
+            };
+            let downloaded = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?
+                .block_on(async {
+                    let downloader = SetupDataDownloader::new(&url, kind).await?;
+                    downloader.download_missing(&keystore).await
+                })
+                .context("download_missing()")?;
+            tracing::info!("Downloaded {downloaded} setup keys");
+            Ok(())
+        }
         Command::GenerateSetupKeys { options } => {
             let generator = CPUSetupDataGenerator {
                 keystore: keystore_from_optional_path(
@@ -9,7 +9,9 @@ use futures::{channel::mpsc, executor::block_on, SinkExt, StreamExt};
 use jemallocator::Jemalloc;
 use structopt::StructOpt;
 use tokio::sync::watch;
-use zksync_core_leftovers::temp_config_store::{load_database_secrets, load_general_config};
+use zksync_core_leftovers::temp_config_store::{
+    load_database_secrets, load_general_config, load_s3_secrets,
+};
 use zksync_env_config::object_store::ProverObjectStoreConfig;
 use zksync_object_store::ObjectStoreFactory;
 use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
@@ -96,6 +98,7 @@ async fn main() -> anyhow::Result<()> {
 
     let general_config = load_general_config(opt.config_path).context("general config")?;
 
+    let s3_secrets = load_s3_secrets(opt.secrets_path.clone()).context("S3 secrets")?;
     let database_secrets = load_database_secrets(opt.secrets_path).context("database secrets")?;
 
     let observability_config = general_config
@@ -113,7 +116,8 @@ async fn main() -> anyhow::Result<()> {
             .context("object store")?
             .clone(),
     );
-    let store_factory = ObjectStoreFactory::new(object_store_config.0);
+    let store_factory =
+        ObjectStoreFactory::new(object_store_config.0).with_s3_secrets(s3_secrets.clone());
     let config = general_config
         .witness_generator_config
         .context("witness generator config")?
@@ -200,6 +204,7 @@ async fn main() -> anyhow::Result<()> {
                         .clone()
                         .expect("public_object_store"),
                 )
+                .with_s3_secrets(s3_secrets.clone())
                 .create_store()
                 .await?,
             ),
@@ -5,14 +5,17 @@ use zkevm_test_harness::{
     },
     zk_evm::blake2::Blake2s256,
 };
-use zksync_prover_interface::inputs::{StorageLogMetadata, WitnessInputMerklePaths};
+use zksync_prover_interface::inputs::{
+    CompactMerklePaths, StorageLogMetadata, WitnessInputMerklePaths,
+};
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct PrecalculatedMerklePathsProvider {
     // We keep the root hash of the last processed leaf, as it is needed by the the witness generator.
     pub root_hash: [u8; 32],
-    // The ordered list of expected leaves to be interacted with
-    pub pending_leaves: Vec<StorageLogMetadata>,
+    // The ordered list of expected leaves to be interacted with. Merkle paths are stored in the compact form
+    // and are expanded one at a time, so that we don't hold full paths for the entire batch in memory.
+    pub pending_leaves: CompactMerklePaths,
     // The index that would be assigned to the next new leaf
     pub next_enumeration_index: u64,
     // For every Storage Write Log we expect two invocations: `get_leaf` and `insert_leaf`.
@@ -26,7 +29,7 @@ impl PrecalculatedMerklePathsProvider {
         tracing::debug!("Initializing PrecalculatedMerklePathsProvider. Initial root_hash: {:?}, initial next_enumeration_index: {:?}", root_hash, next_enumeration_index);
         Self {
             root_hash,
-            pending_leaves: input.into_merkle_paths().collect(),
+            pending_leaves: input.into_compact_merkle_paths(),
             next_enumeration_index,
             is_get_leaf_invoked: false,
         }
@@ -65,12 +68,24 @@ impl BinarySparseStorageTree<256, 32, 32, 8, 32, Blake2s256, ZkSyncStorageLeaf>
             !self.is_get_leaf_invoked,
             "`get_leaf()` invoked more than once or get_leaf is invoked when insert_leaf was expected"
         );
-        let next = self.pending_leaves.first().unwrap_or_else(|| {
-            panic!(
-                "invoked `get_leaf({:?})` with empty `pending_leaves`",
-                index
-            )
-        });
+        let is_write = self
+            .pending_leaves
+            .front()
+            .unwrap_or_else(|| {
+                panic!(
+                    "invoked `get_leaf({:?})` with empty `pending_leaves`",
+                    index
+                )
+            })
+            .is_write;
+        let next = if is_write {
+            // If it is a write, the next invocation will be `insert_leaf` with the very same parameters
+            self.pending_leaves.peek_expanded()
+        } else {
+            // If it is a read, the next invocation will relate to the next `pending_leaf`
+            self.pending_leaves.next()
+        };
+        let next = next.expect("`pending_leaves` checked to be non-empty");
         self.root_hash = next.root_hash;
 
         assert_eq!(
@@ -86,19 +101,15 @@ impl BinarySparseStorageTree<256, 32, 32, 8, 32, Blake2s256, ZkSyncStorageLeaf>
             },
             first_write: next.first_write,
             index: *index,
-            merkle_path: next.clone().into_merkle_paths_array(),
+            merkle_path: next.into_merkle_paths_array(),
         };
 
-        if next.is_write {
-            // If it is a write, the next invocation will be `insert_leaf` with the very same parameters
+        if is_write {
             self.is_get_leaf_invoked = true;
             if res.first_write {
                 res.leaf.index = 0;
             }
-        } else {
-            // If it is a read, the next invocation will relate to the next `pending_leaf`
-            self.pending_leaves.remove(0);
-        };
+        }
 
         res
     }
@@ -119,7 +130,12 @@ impl BinarySparseStorageTree<256, 32, 32, 8, 32, Blake2s256, ZkSyncStorageLeaf>
             self.is_get_leaf_invoked,
             "`get_leaf()` is expected to be invoked before `insert_leaf()`"
         );
-        let next = self.pending_leaves.remove(0);
+        let next = self.pending_leaves.next().unwrap_or_else(|| {
+            panic!(
+                "invoked `insert_leaf({:?})` with empty `pending_leaves`",
+                index
+            )
+        });
         self.root_hash = next.root_hash;
 
         assert!(
@@ -100,6 +100,10 @@ impl JobManager for BasicCircuits {
         })
     }
 
+    fn l1_batch_number(job: &Self::Job) -> L1BatchNumber {
+        job.block_number
+    }
+
     async fn prepare_job(
         metadata: L1BatchNumber,
         object_store: &dyn ObjectStore,
@@ -3,12 +3,14 @@ use std::{marker::PhantomData, sync::Arc, time::Instant};
 use anyhow::Context;
 use async_trait::async_trait;
 use tokio::task::JoinHandle;
+use tracing::Instrument as _;
 use zksync_config::configs::FriWitnessGeneratorConfig;
 use zksync_object_store::ObjectStore;
 use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
 use zksync_prover_keystore::keystore::Keystore;
 use zksync_queued_job_processor::JobProcessor;
-use zksync_types::protocol_version::ProtocolSemanticVersion;
+use zksync_types::{protocol_version::ProtocolSemanticVersion, L1BatchNumber};
+use zksync_vlog::opentelemetry::{l1_batch_span, L1BatchStage};
 
 use crate::artifacts::ArtifactsManager;
 
@@ -42,6 +44,9 @@ pub trait JobManager: ArtifactsManager {
         started_at: Instant,
     ) -> anyhow::Result<Self::OutputArtifacts>;
 
+    /// Returns the number of the L1 batch the job belongs to.
+    fn l1_batch_number(job: &Self::Job) -> L1BatchNumber;
+
     async fn prepare_job(
         metadata: Self::Metadata,
         object_store: &dyn ObjectStore,
@@ -136,9 +141,16 @@ where
     ) -> JoinHandle<anyhow::Result<Self::JobArtifacts>> {
         let object_store = self.object_store.clone();
         let max_circuits_in_flight = self.config.max_circuits_in_flight;
-        tokio::spawn(async move {
-            R::process_job(job, object_store, max_circuits_in_flight, started_at).await
-        })
+        let span = l1_batch_span(
+            L1BatchStage::WitnessGeneration(R::ROUND.as_str()),
+            R::l1_batch_number(&job).0,
+        );
+        tokio::spawn(
+            async move {
+                R::process_job(job, object_store, max_circuits_in_flight, started_at).await
+            }
+            .instrument(span),
+        )
     }
 
     #[tracing::instrument(skip_all, fields(job_id = %job_id))]
@@ -197,6 +197,10 @@ impl JobManager for NodeAggregation {
         })
     }
 
+    fn l1_batch_number(job: &Self::Job) -> L1BatchNumber {
+        job.block_number
+    }
+
     #[tracing::instrument(
         skip_all,
         fields(l1_batch = % metadata.block_number, circuit_id = % metadata.circuit_id)
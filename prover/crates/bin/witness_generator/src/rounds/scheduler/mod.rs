@@ -113,6 +113,10 @@ impl JobManager for Scheduler {
         })
     }
 
+    fn l1_batch_number(job: &Self::Job) -> L1BatchNumber {
+        job.block_number
+    }
+
     #[tracing::instrument(
         skip_all,
         fields(l1_batch = %metadata.l1_batch_number)
@@ -5,7 +5,9 @@ use zkevm_test_harness::{
     kzg::KzgSettings,
     witness::tree::{BinarySparseStorageTree, ZkSyncStorageLeaf},
 };
-use zksync_prover_interface::inputs::{StorageLogMetadata, WitnessInputMerklePaths};
+use zksync_prover_interface::inputs::{
+    CompactMerklePaths, StorageLogMetadata, WitnessInputMerklePaths,
+};
 use zksync_types::U256;
 
 use super::precalculated_merkle_paths_provider::PrecalculatedMerklePathsProvider;
@@ -92,7 +94,7 @@ fn create_provider() -> PrecalculatedMerklePathsProvider {
 #[test]
 fn test_filter_renumerate_all_first_writes() {
     let mut provider = create_provider();
-    for log in &mut provider.pending_leaves {
+    for log in provider.pending_leaves.iter_mut() {
         log.first_write = true;
     }
 
@@ -108,7 +110,7 @@ fn test_filter_renumerate_all_first_writes() {
 #[test]
 fn test_filter_renumerate_all_repeated_writes() {
     let mut provider = create_provider();
-    for log in &mut provider.pending_leaves {
+    for log in provider.pending_leaves.iter_mut() {
         log.first_write = false;
     }
 
@@ -160,13 +162,15 @@ fn test_filter_renumerate_panic_when_indices_and_pending_leaves_are_of_different
 }
 
 #[test]
-fn vec_and_vec_deque_serializations_are_compatible() {
-    let logs = create_provider().pending_leaves;
-    let serialized = bincode::serialize(&logs).unwrap();
+fn vec_and_compact_serializations_are_compatible() {
+    let pending_leaves = create_provider().pending_leaves;
+    let serialized = bincode::serialize(&pending_leaves).unwrap();
     let logs_vec: Vec<StorageLogMetadata> = bincode::deserialize(&serialized).unwrap();
-    assert_eq!(logs, logs_vec);
-    let serialized_deque = bincode::serialize(&logs_vec).unwrap();
-    assert_eq!(serialized_deque, serialized);
+    assert_eq!(logs_vec, pending_leaves.clone().collect::<Vec<_>>());
+    let serialized_vec = bincode::serialize(&logs_vec).unwrap();
+    assert_eq!(serialized_vec, serialized);
+    let deserialized: CompactMerklePaths = bincode::deserialize(&serialized_vec).unwrap();
+    assert_eq!(deserialized, pending_leaves);
 }
 
 #[test]
@@ -189,11 +193,20 @@ fn provider_serialization() {
 
 #[test]
 fn initializing_provider_with_compacted_merkle_paths() {
-    let mut provider = create_provider();
-    for log in &mut provider.pending_leaves {
-        let empty_merkle_paths = iter::repeat([0; 32]).take(255);
-        log.merkle_paths.splice(0..0, empty_merkle_paths);
+    let mut job = WitnessInputMerklePaths::new(4);
+    for (mut log, merkle_path) in LOGS_AND_PATHS {
+        log.merkle_paths = iter::repeat([0; 32]).take(255).collect();
+        log.merkle_paths.push(merkle_path);
+        job.push_merkle_path(log);
     }
+    let mut provider = PrecalculatedMerklePathsProvider::new(job, [0_u8; 32]);
+    // Only the first path should be stored in full.
+    let path_lengths: Vec<_> = provider
+        .pending_leaves
+        .iter()
+        .map(|log| log.merkle_paths.len())
+        .collect();
+    assert_eq!(path_lengths, [256, 1, 1]);
 
     // First log entry: read
     let query = provider.get_leaf(&[0; 32]);
@@ -6,7 +6,9 @@ use std::time::Duration;
 use anyhow::Context as _;
 use clap::Parser;
 use tokio::sync::{oneshot, watch};
-use zksync_core_leftovers::temp_config_store::{load_database_secrets, load_general_config};
+use zksync_core_leftovers::temp_config_store::{
+    load_database_secrets, load_general_config, load_s3_secrets,
+};
 use zksync_env_config::object_store::ProverObjectStoreConfig;
 use zksync_object_store::ObjectStoreFactory;
 use zksync_prover_dal::ConnectionPool;
@@ -44,6 +46,7 @@ async fn main() -> anyhow::Result<()> {
     let opt = Cli::parse();
 
     let general_config = load_general_config(opt.config_path).context("general config")?;
+    let s3_secrets = load_s3_secrets(opt.secrets_path.clone()).context("S3 secrets")?;
     let database_secrets = load_database_secrets(opt.secrets_path).context("database secrets")?;
 
     let observability_config = general_config
@@ -70,6 +73,7 @@ async fn main() -> anyhow::Result<()> {
             .context("object store")?,
     );
     let object_store = ObjectStoreFactory::new(object_store_config.0)
+        .with_s3_secrets(s3_secrets)
         .create_store()
         .await?;
     let circuit_ids_for_round_to_be_proven = general_config
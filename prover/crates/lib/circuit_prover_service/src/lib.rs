@@ -4,4 +4,5 @@ mod gpu_circuit_prover;
 pub mod job_runner;
 mod metrics;
 mod types;
+pub mod warm_up;
 mod witness_vector_generator;
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use vise::{Buckets, Histogram, Metrics};
+use vise::{Buckets, Gauge, Histogram, Metrics};
 
 /// Metrics for witness vector generator execution
 #[derive(Debug, Metrics)]
@@ -40,6 +40,14 @@ pub struct CircuitProverMetrics {
     /// How long does it take finish a prover job from witness vector to circuit prover?
     #[metrics(buckets = Buckets::LATENCIES)]
     pub full_time: Histogram<Duration>,
+    /// How long does it take to preload data before picking jobs?
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub warm_up_time: Histogram<Duration>,
+    /// How long does it take to generate the GPU warm-up proof?
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub warm_up_proof_time: Histogram<Duration>,
+    /// 1 if the prover finished warm-up and is picking jobs, 0 otherwise.
+    pub ready: Gauge<u64>,
 }
 
 #[vise::register]
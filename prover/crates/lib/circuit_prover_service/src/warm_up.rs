@@ -0,0 +1,217 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use serde::Serialize;
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_prover_fri_types::{
+    circuit_definitions::boojum::cs::implementations::setup::FinalizationHintsForProver,
+    CircuitWrapper, ProverServiceDataKey,
+};
+use zksync_prover_keystore::{keystore::Keystore, GoldilocksGpuProverSetupData};
+
+use crate::{metrics::CIRCUIT_PROVER_METRICS, types::circuit::Circuit};
+
+/// Data preloaded during warm-up, shared by all jobs.
+#[derive(Debug)]
+pub struct PreloadedData {
+    pub setup_data_cache: HashMap<ProverServiceDataKey, Arc<GoldilocksGpuProverSetupData>>,
+    pub finalization_hints_cache: HashMap<ProverServiceDataKey, Arc<FinalizationHintsForProver>>,
+}
+
+/// Warm-up phase of the circuit prover, to be run before any jobs are picked.
+/// Preloads finalization hints & setup data for all supported circuits and checks that none are missing,
+/// so that the first jobs neither wait for data to be loaded nor fail halfway because of missing data.
+pub async fn warm_up(keystore: &Keystore) -> anyhow::Result<PreloadedData> {
+    let start_time = Instant::now();
+    tracing::info!("Started circuit prover warm-up");
+
+    let (setup_data_cache, finalization_hints_cache) = tokio::try_join!(
+        keystore.load_all_setup_key_mapping(),
+        keystore.load_all_finalization_hints_mapping(),
+    )
+    .context("failed to load setup data or finalization hints")?;
+
+    for key in ProverServiceDataKey::all_boojum() {
+        // Both WVG & GPU circuit prover access data by the crypto setup key.
+        let key = key.crypto_setup_key();
+        anyhow::ensure!(
+            setup_data_cache.contains_key(&key),
+            "setup data for circuit {} is not loaded",
+            key.name()
+        );
+        anyhow::ensure!(
+            finalization_hints_cache.contains_key(&key),
+            "finalization hints for circuit {} are not loaded",
+            key.name()
+        );
+    }
+
+    tracing::info!(
+        "Finished circuit prover warm-up in {:?}; loaded setup data for {} and finalization hints for {} circuits",
+        start_time.elapsed(),
+        setup_data_cache.len(),
+        finalization_hints_cache.len()
+    );
+    CIRCUIT_PROVER_METRICS
+        .warm_up_time
+        .observe(start_time.elapsed());
+    Ok(PreloadedData {
+        setup_data_cache,
+        finalization_hints_cache,
+    })
+}
+
+/// GPU warm-up, to be run after [`warm_up()`] and before any jobs are picked.
+/// Proves `circuit_wrapper` using the same witness vector generation & GPU proving path as jobs do and discards the proof,
+/// so that the first job doesn't pay for initializing GPU kernels & memory pools.
+///
+/// NOTE: Requires `ProverContext` to be initialized. Partial circuits (i.e., RAM permutation circuits with witness
+/// stored separately) are not supported, as they can't be hydrated without the object store.
+pub async fn warm_up_gpu(
+    circuit_wrapper: CircuitWrapper,
+    preloaded_data: &PreloadedData,
+) -> anyhow::Result<Duration> {
+    let start_time = Instant::now();
+    let (circuit, key) = match circuit_wrapper {
+        CircuitWrapper::Base(circuit) => {
+            let key = ProverServiceDataKey::new_basic(circuit.numeric_circuit_type());
+            (Circuit::Base(circuit), key)
+        }
+        CircuitWrapper::Recursive(circuit) => {
+            let key = ProverServiceDataKey::new_recursive(circuit.numeric_circuit_type());
+            (Circuit::Recursive(circuit), key)
+        }
+        CircuitWrapper::BasePartial(_) => {
+            anyhow::bail!("partial circuits are not supported for GPU warm-up")
+        }
+    };
+    let key = key.crypto_setup_key();
+    tracing::info!("Started GPU warm-up with circuit {}", key.name());
+
+    let finalization_hints = preloaded_data
+        .finalization_hints_cache
+        .get(&key)
+        .with_context(|| {
+            format!(
+                "finalization hints for circuit {} are not loaded",
+                key.name()
+            )
+        })?
+        .clone();
+    let setup_data = preloaded_data
+        .setup_data_cache
+        .get(&key)
+        .with_context(|| format!("setup data for circuit {} is not loaded", key.name()))?
+        .clone();
+    tokio::task::spawn_blocking(move || {
+        let witness_vector = circuit
+            .synthesize_vector(finalization_hints)
+            .context("failed to generate witness vector")?;
+        circuit
+            .prove(witness_vector, setup_data)
+            .context("failed to gpu prove circuit")
+    })
+    .await
+    .context("GPU warm-up panicked")??;
+
+    let elapsed = start_time.elapsed();
+    tracing::info!("Finished GPU warm-up in {elapsed:?}");
+    CIRCUIT_PROVER_METRICS.warm_up_proof_time.observe(elapsed);
+    Ok(elapsed)
+}
+
+/// Details reported by the circuit prover health check once it's ready.
+#[derive(Debug, Serialize)]
+pub struct ReadinessDetails {
+    pub setup_data_count: usize,
+    pub finalization_hints_count: usize,
+    /// Duration of the GPU warm-up proof, if one was generated.
+    pub warm_up_proof_time: Option<Duration>,
+}
+
+/// Readiness of the circuit prover, i.e. whether it finished warm-up and is picking jobs.
+/// Reported as the `circuit_prover` health check component and the `circuit_prover_ready` metric.
+#[derive(Debug)]
+pub struct Readiness {
+    health_updater: HealthUpdater,
+}
+
+impl Readiness {
+    /// Creates readiness in the not ready state.
+    pub fn new() -> Self {
+        let (_, health_updater) = ReactiveHealthCheck::new("circuit_prover");
+        CIRCUIT_PROVER_METRICS.ready.set(0);
+        Self { health_updater }
+    }
+
+    /// Returns the health check reflecting this readiness.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    /// Marks the circuit prover as ready to pick jobs.
+    pub fn set_ready(&self, details: ReadinessDetails) {
+        self.health_updater
+            .update(Health::from(HealthStatus::Ready).with_details(details));
+        CIRCUIT_PROVER_METRICS.ready.set(1);
+    }
+
+    /// Marks the circuit prover as shutting down, i.e. no longer picking jobs.
+    pub fn set_shutting_down(&self) {
+        self.health_updater
+            .update(HealthStatus::ShuttingDown.into());
+        CIRCUIT_PROVER_METRICS.ready.set(0);
+    }
+}
+
+impl Default for Readiness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_health_check::CheckHealth;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn readiness_is_reported_via_health_check() {
+        let readiness = Readiness::new();
+        let health_check = readiness.health_check();
+        assert_eq!(health_check.name(), "circuit_prover");
+        assert_eq!(
+            health_check.check_health().await.status(),
+            HealthStatus::NotReady
+        );
+        assert_eq!(CIRCUIT_PROVER_METRICS.ready.get(), 0);
+
+        readiness.set_ready(ReadinessDetails {
+            setup_data_count: 1,
+            finalization_hints_count: 1,
+            warm_up_proof_time: None,
+        });
+        let health = health_check.check_health().await;
+        assert_eq!(health.status(), HealthStatus::Ready);
+        assert_eq!(health.details().unwrap()["setup_data_count"], 1);
+        assert_eq!(CIRCUIT_PROVER_METRICS.ready.get(), 1);
+
+        readiness.set_shutting_down();
+        assert_eq!(
+            health_check.check_health().await.status(),
+            HealthStatus::ShuttingDown
+        );
+        assert_eq!(CIRCUIT_PROVER_METRICS.ready.get(), 0);
+
+        drop(readiness);
+        assert_eq!(
+            health_check.check_health().await.status(),
+            HealthStatus::ShutDown
+        );
+    }
+}
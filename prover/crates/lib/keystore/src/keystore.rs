@@ -24,7 +24,7 @@ use zksync_utils::env::Workspace;
 
 #[cfg(feature = "gpu")]
 use crate::GoldilocksGpuProverSetupData;
-use crate::{GoldilocksProverSetupData, VkCommitments};
+use crate::{setup_data_downloader::SetupDataManifest, GoldilocksProverSetupData, VkCommitments};
 
 #[derive(Debug, Clone, Copy)]
 pub enum ProverServiceDataType {
@@ -103,7 +103,7 @@ impl Keystore {
         &self.basedir
     }
 
-    fn get_file_path(
+    pub(crate) fn get_file_path(
         &self,
         key: ProverServiceDataKey,
         service_data_type: ProverServiceDataType,
@@ -469,6 +469,22 @@ impl Keystore {
         Self::save_json_pretty(self.get_base_path().join("commitments.json"), &commitments)
     }
 
+    /// Loads the setup data manifest from the base path. Returns an empty manifest if it doesn't exist yet.
+    pub fn load_setup_data_manifest(&self) -> anyhow::Result<SetupDataManifest> {
+        let filepath = self.get_base_path().join("setup_data_manifest.json");
+        if !filepath.exists() {
+            return Ok(SetupDataManifest::default());
+        }
+        Self::load_json_from_file(filepath)
+    }
+
+    pub fn save_setup_data_manifest(&self, manifest: &SetupDataManifest) -> anyhow::Result<()> {
+        Self::save_json_pretty(
+            self.get_base_path().join("setup_data_manifest.json"),
+            manifest,
+        )
+    }
+
     /// Async loads mapping of all circuits to setup key, if successful
     #[cfg(feature = "gpu")]
     pub async fn load_all_setup_key_mapping(
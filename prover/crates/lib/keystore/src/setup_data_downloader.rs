@@ -0,0 +1,530 @@
+//! Downloading of setup data for new prover machines.
+//!
+//! Setup data files are large (several GBs in total), so they are not stored in the repository.
+//! Instead, their Keccak-256 hashes are recorded in the setup data manifest (`setup_data_manifest.json`
+//! in the keys directory) for each protocol version when the keys are generated, and the manifest is embedded
+//! into prover binaries. Downloaded files are only placed into the keystore if their hashes match the manifest.
+//!
+//! Besides setup data for boojum circuits, the manifest records hashes of the keys used by the proof compressor
+//! (e.g., the universal setup), which are downloaded by the compressor itself.
+
+use std::{collections::BTreeMap, path::Path, sync::Arc};
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use tokio::{fs, io::AsyncWriteExt};
+use zksync_basic_types::{protocol_version::ProtocolSemanticVersion, H256};
+use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode};
+use zksync_object_store::{Bucket, ObjectStore, ObjectStoreFactory};
+use zksync_prover_fri_types::{ProverServiceDataKey, PROVER_PROTOCOL_SEMANTIC_VERSION};
+
+use crate::keystore::{Keystore, ProverServiceDataType};
+
+/// Setup data manifest for the circuits this binary was built with.
+const EMBEDDED_MANIFEST: &str = include_str!("../../../../data/keys/setup_data_manifest.json");
+
+/// Kind of setup data. CPU and GPU provers use different formats of setup data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SetupDataKind {
+    Cpu,
+    Gpu,
+}
+
+impl SetupDataKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Cpu => "cpu",
+            Self::Gpu => "gpu",
+        }
+    }
+}
+
+/// Keccak-256 hashes of setup data files for a single protocol version, keyed by the file name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SetupDataHashes {
+    #[serde(default)]
+    pub cpu: BTreeMap<String, H256>,
+    #[serde(default)]
+    pub gpu: BTreeMap<String, H256>,
+    /// Keys used by the proof compressor.
+    #[serde(default)]
+    pub compressor: BTreeMap<String, H256>,
+}
+
+impl SetupDataHashes {
+    fn setup_data(&self, kind: SetupDataKind) -> &BTreeMap<String, H256> {
+        match kind {
+            SetupDataKind::Cpu => &self.cpu,
+            SetupDataKind::Gpu => &self.gpu,
+        }
+    }
+}
+
+/// Hashes of setup data files for all supported protocol versions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SetupDataManifest {
+    pub versions: BTreeMap<ProtocolSemanticVersion, SetupDataHashes>,
+}
+
+impl SetupDataManifest {
+    /// Returns the manifest embedded into the binary at compile time.
+    pub fn embedded() -> Self {
+        serde_json::from_str(EMBEDDED_MANIFEST).expect("embedded setup data manifest is invalid")
+    }
+
+    pub fn expected_hash(
+        &self,
+        protocol_version: ProtocolSemanticVersion,
+        kind: SetupDataKind,
+        file_name: &str,
+    ) -> Option<H256> {
+        let hashes = self.versions.get(&protocol_version)?;
+        hashes.setup_data(kind).get(file_name).copied()
+    }
+
+    pub fn expected_compressor_hash(
+        &self,
+        protocol_version: ProtocolSemanticVersion,
+        file_name: &str,
+    ) -> Option<H256> {
+        let hashes = self.versions.get(&protocol_version)?;
+        hashes.compressor.get(file_name).copied()
+    }
+
+    pub fn insert(
+        &mut self,
+        protocol_version: ProtocolSemanticVersion,
+        kind: SetupDataKind,
+        file_name: String,
+        hash: H256,
+    ) {
+        let hashes = self.versions.entry(protocol_version).or_default();
+        let hashes = match kind {
+            SetupDataKind::Cpu => &mut hashes.cpu,
+            SetupDataKind::Gpu => &mut hashes.gpu,
+        };
+        hashes.insert(file_name, hash);
+    }
+}
+
+/// Computes the hash of setup data as recorded in [`SetupDataManifest`].
+pub fn setup_data_hash(data: &[u8]) -> H256 {
+    H256::from_slice(&Keccak256::digest(data))
+}
+
+/// Source of setup data files.
+#[derive(Debug)]
+enum SetupDataSource {
+    /// HTTP(S) directory containing setup data files for a specific protocol version and [`SetupDataKind`].
+    Http {
+        client: reqwest::Client,
+        base_url: String,
+    },
+    /// Object store containing setup data in [`Bucket::SetupData`] for all protocol versions.
+    ObjectStore(Arc<dyn ObjectStore>),
+}
+
+impl SetupDataSource {
+    /// Parses the source from its URL. GCS buckets (`gs://` URLs) are accessed via an authenticated object store,
+    /// so they don't need to be public.
+    async fn new(url: &str) -> anyhow::Result<Self> {
+        if let Some(bucket) = url.strip_prefix("gs://") {
+            let bucket = bucket.trim_end_matches('/');
+            anyhow::ensure!(
+                !bucket.is_empty() && !bucket.contains('/'),
+                "unsupported setup data URL `{url}`; expected a `gs://<bucket>` URL without a path"
+            );
+            let config = ObjectStoreConfig {
+                mode: ObjectStoreMode::GCS {
+                    bucket_base_url: bucket.to_owned(),
+                },
+                max_retries: 5,
+                local_mirror_path: None,
+                tiers: vec![],
+            };
+            let store = ObjectStoreFactory::new(config)
+                .create_store()
+                .await
+                .with_context(|| format!("failed creating object store for {url}"))?;
+            Ok(Self::ObjectStore(store))
+        } else if url.starts_with("http://") || url.starts_with("https://") {
+            Ok(Self::Http {
+                client: reqwest::Client::new(),
+                base_url: url.trim_end_matches('/').to_owned(),
+            })
+        } else {
+            anyhow::bail!(
+                "unsupported setup data URL `{url}`; expected an `http(s)://` or `gs://` URL"
+            );
+        }
+    }
+}
+
+/// Downloads setup data missing in a [`Keystore`] and verifies it against the [`SetupDataManifest`].
+///
+/// Files are fetched from one of the following locations:
+///
+/// - An HTTP(S) directory, which must contain setup data for the protocol version and [`SetupDataKind`]
+///   of the downloader, with the same file names as in the keystore.
+/// - A GCS bucket (`gs://<bucket>`) or another [`ObjectStore`]. Setup data is stored in the `setup_data` bucket
+///   with keys `<protocol_version>_<kind>_<file_name>`, e.g. `0.25.0_gpu_setup_basic_1_data.bin`.
+#[derive(Debug)]
+pub struct SetupDataDownloader {
+    source: SetupDataSource,
+    kind: SetupDataKind,
+    protocol_version: ProtocolSemanticVersion,
+    manifest: SetupDataManifest,
+}
+
+impl SetupDataDownloader {
+    /// Creates a downloader from the specified URL verifying data against the embedded manifest.
+    pub async fn new(url: &str, kind: SetupDataKind) -> anyhow::Result<Self> {
+        let source = SetupDataSource::new(url).await?;
+        Ok(Self::from_source(source, kind))
+    }
+
+    /// Creates a downloader fetching setup data from the provided object store.
+    pub fn from_object_store(store: Arc<dyn ObjectStore>, kind: SetupDataKind) -> Self {
+        Self::from_source(SetupDataSource::ObjectStore(store), kind)
+    }
+
+    fn from_source(source: SetupDataSource, kind: SetupDataKind) -> Self {
+        Self {
+            source,
+            kind,
+            protocol_version: PROVER_PROTOCOL_SEMANTIC_VERSION,
+            manifest: SetupDataManifest::embedded(),
+        }
+    }
+
+    /// Overrides the manifest used to verify downloaded data.
+    pub fn with_manifest(mut self, manifest: SetupDataManifest) -> Self {
+        self.manifest = manifest;
+        self
+    }
+
+    /// Downloads setup data for all boojum circuits missing in the keystore.
+    /// Returns the number of downloaded files.
+    ///
+    /// Fails without downloading anything if the manifest doesn't contain hashes for some of the missing files.
+    pub async fn download_missing(&self, keystore: &Keystore) -> anyhow::Result<usize> {
+        let missing_keys: Vec<_> = ProverServiceDataKey::all_boojum()
+            .into_iter()
+            .filter(|key| !keystore.is_setup_data_present(key))
+            .collect();
+        if missing_keys.is_empty() {
+            tracing::info!("All setup data is present; nothing to download");
+            return Ok(0);
+        }
+
+        let mut downloads = Vec::with_capacity(missing_keys.len());
+        for key in missing_keys {
+            let path = keystore.get_file_path(key, ProverServiceDataType::SetupData);
+            let file_name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .with_context(|| format!("invalid setup data path: {path:?}"))?
+                .to_owned();
+            let expected_hash = self
+                .manifest
+                .expected_hash(self.protocol_version, self.kind, &file_name)
+                .with_context(|| {
+                    format!(
+                        "setup data manifest has no {:?} hash for `{file_name}` for protocol version {}; \
+                         refusing to download unverified data",
+                        self.kind, self.protocol_version
+                    )
+                })?;
+            downloads.push((path, file_name, expected_hash));
+        }
+
+        tracing::info!("Downloading {} missing setup data files", downloads.len());
+        for (path, file_name, expected_hash) in &downloads {
+            self.download(file_name, path, *expected_hash)
+                .await
+                .with_context(|| format!("failed downloading setup data `{file_name}`"))?;
+        }
+        Ok(downloads.len())
+    }
+
+    /// Downloads a single file to a temporary location and moves it to `path` once its hash is verified,
+    /// so that interrupted or corrupted downloads never end up in the keystore.
+    async fn download(
+        &self,
+        file_name: &str,
+        path: &Path,
+        expected_hash: H256,
+    ) -> anyhow::Result<()> {
+        let tmp_path = path.with_extension("bin.partial");
+        let mut file = fs::File::create(&tmp_path)
+            .await
+            .with_context(|| format!("failed creating {tmp_path:?}"))?;
+        let mut hasher = Keccak256::new();
+
+        match &self.source {
+            SetupDataSource::Http { client, base_url } => {
+                let url = format!("{base_url}/{file_name}");
+                tracing::info!("Downloading setup data from {url} to {path:?}");
+                let mut response = client.get(&url).send().await?.error_for_status()?;
+                while let Some(chunk) = response.chunk().await? {
+                    hasher.update(&chunk);
+                    file.write_all(&chunk)
+                        .await
+                        .with_context(|| format!("failed writing to {tmp_path:?}"))?;
+                }
+            }
+            SetupDataSource::ObjectStore(store) => {
+                let key = format!(
+                    "{}_{}_{file_name}",
+                    self.protocol_version,
+                    self.kind.as_str()
+                );
+                tracing::info!("Downloading setup data from object store key `{key}` to {path:?}");
+                let data = store.get_raw(Bucket::SetupData, &key).await?;
+                hasher.update(&data);
+                file.write_all(&data)
+                    .await
+                    .with_context(|| format!("failed writing to {tmp_path:?}"))?;
+            }
+        }
+        file.sync_all().await?;
+        drop(file);
+
+        let hash = H256::from_slice(&hasher.finalize());
+        if hash != expected_hash {
+            fs::remove_file(&tmp_path).await.ok();
+            anyhow::bail!(
+                "hash mismatch for setup data `{file_name}`: expected {expected_hash:?}, got {hash:?}"
+            );
+        }
+        fs::rename(&tmp_path, path)
+            .await
+            .with_context(|| format!("failed moving {tmp_path:?} to {path:?}"))?;
+        tracing::info!("Downloaded and verified setup data at {path:?}");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use tokio::{
+        io::{AsyncBufReadExt, BufReader},
+        net::TcpListener,
+    };
+    use zksync_object_store::MockObjectStore;
+
+    use super::*;
+
+    #[test]
+    fn embedded_manifest_is_valid() {
+        let manifest = SetupDataManifest::embedded();
+        assert!(
+            manifest
+                .versions
+                .contains_key(&PROVER_PROTOCOL_SEMANTIC_VERSION),
+            "setup data manifest must be updated for protocol version {PROVER_PROTOCOL_SEMANTIC_VERSION}"
+        );
+    }
+
+    #[tokio::test]
+    async fn parsing_source_urls() {
+        let source = SetupDataSource::new("https://example.com/keys/")
+            .await
+            .unwrap();
+        match &source {
+            SetupDataSource::Http { base_url, .. } => {
+                assert_eq!(base_url, "https://example.com/keys");
+            }
+            SetupDataSource::ObjectStore(_) => panic!("unexpected source: {source:?}"),
+        }
+        SetupDataSource::new("/local/path").await.unwrap_err();
+        SetupDataSource::new("gs://bucket/path").await.unwrap_err();
+    }
+
+    #[test]
+    fn manifest_serialization() {
+        let mut manifest = SetupDataManifest::default();
+        let hash = setup_data_hash(b"setup data");
+        manifest.insert(
+            PROVER_PROTOCOL_SEMANTIC_VERSION,
+            SetupDataKind::Gpu,
+            "setup_basic_1_data.bin".to_owned(),
+            hash,
+        );
+
+        let json = serde_json::to_value(&manifest).unwrap();
+        let version = PROVER_PROTOCOL_SEMANTIC_VERSION.to_string();
+        assert_eq!(
+            json[&version]["gpu"]["setup_basic_1_data.bin"],
+            serde_json::to_value(hash).unwrap()
+        );
+        let manifest: SetupDataManifest = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            manifest.expected_hash(
+                PROVER_PROTOCOL_SEMANTIC_VERSION,
+                SetupDataKind::Gpu,
+                "setup_basic_1_data.bin"
+            ),
+            Some(hash)
+        );
+        assert_eq!(
+            manifest.expected_hash(
+                PROVER_PROTOCOL_SEMANTIC_VERSION,
+                SetupDataKind::Cpu,
+                "setup_basic_1_data.bin"
+            ),
+            None
+        );
+        assert_eq!(
+            manifest.expected_hash(
+                ProtocolSemanticVersion::default(),
+                SetupDataKind::Gpu,
+                "setup_basic_1_data.bin"
+            ),
+            None
+        );
+    }
+
+    /// Returns mock setup data for all boojum circuits keyed by the file name, and the corresponding manifest.
+    fn mock_setup_data(keystore: &Keystore) -> (HashMap<String, Vec<u8>>, SetupDataManifest) {
+        let mut files = HashMap::new();
+        let mut manifest = SetupDataManifest::default();
+        for key in ProverServiceDataKey::all_boojum() {
+            let path = keystore.get_file_path(key, ProverServiceDataType::SetupData);
+            let file_name = path.file_name().unwrap().to_str().unwrap().to_owned();
+            let data = format!("setup data for {file_name}").into_bytes();
+            manifest.insert(
+                PROVER_PROTOCOL_SEMANTIC_VERSION,
+                SetupDataKind::Gpu,
+                file_name.clone(),
+                setup_data_hash(&data),
+            );
+            files.insert(file_name, data);
+        }
+        (files, manifest)
+    }
+
+    fn assert_setup_data(keystore: &Keystore, files: &HashMap<String, Vec<u8>>) {
+        for key in ProverServiceDataKey::all_boojum() {
+            let path = keystore.get_file_path(key, ProverServiceDataType::SetupData);
+            let file_name = path.file_name().unwrap().to_str().unwrap();
+            assert_eq!(std::fs::read(&path).unwrap(), files[file_name]);
+        }
+    }
+
+    /// Serves the provided files over HTTP. Returns the base URL of the server.
+    async fn serve_files(files: HashMap<String, Vec<u8>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut reader = BufReader::new(&mut stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).await.unwrap();
+                // Skip request headers.
+                let mut line = String::new();
+                while reader.read_line(&mut line).await.unwrap() > 2 {
+                    line.clear();
+                }
+
+                let path = request_line.split_whitespace().nth(1).unwrap();
+                let file_name = path.trim_start_matches("/keys/");
+                let (status, body) = match files.get(file_name) {
+                    Some(data) => ("200 OK", data.as_slice()),
+                    None => ("404 Not Found", &[][..]),
+                };
+                let header = format!(
+                    "HTTP/1.1 {status}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                    body.len()
+                );
+                stream.write_all(header.as_bytes()).await.unwrap();
+                stream.write_all(body).await.unwrap();
+            }
+        });
+        format!("http://{local_addr}/keys")
+    }
+
+    #[tokio::test]
+    async fn downloading_setup_data_over_http() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let keystore = Keystore::new(temp_dir.path().to_owned());
+        let (files, manifest) = mock_setup_data(&keystore);
+        let url = serve_files(files.clone()).await;
+
+        let downloader = SetupDataDownloader::new(&url, SetupDataKind::Gpu)
+            .await
+            .unwrap();
+        // The embedded manifest cannot be used since hashes don't match.
+        let downloader = downloader.with_manifest(manifest);
+        let downloaded = downloader.download_missing(&keystore).await.unwrap();
+        assert_eq!(downloaded, files.len());
+        assert_setup_data(&keystore, &files);
+
+        let downloaded = downloader.download_missing(&keystore).await.unwrap();
+        assert_eq!(downloaded, 0);
+    }
+
+    #[tokio::test]
+    async fn downloading_setup_data_from_object_store() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let keystore = Keystore::new(temp_dir.path().to_owned());
+        let (files, manifest) = mock_setup_data(&keystore);
+        let store = MockObjectStore::arc();
+        let mut corrupted_file = None;
+        for (file_name, data) in &files {
+            let key = format!("{PROVER_PROTOCOL_SEMANTIC_VERSION}_gpu_{file_name}");
+            let data = if corrupted_file.is_none() {
+                corrupted_file = Some(file_name.clone());
+                b"corrupted".to_vec()
+            } else {
+                data.clone()
+            };
+            store.put_raw(Bucket::SetupData, &key, data).await.unwrap();
+        }
+
+        let downloader = SetupDataDownloader::from_object_store(store.clone(), SetupDataKind::Gpu)
+            .with_manifest(manifest);
+        let err = downloader
+            .download_missing(&keystore)
+            .await
+            .unwrap_err()
+            .to_string();
+        let corrupted_file = corrupted_file.unwrap();
+        assert!(err.contains(&corrupted_file), "{err}");
+        let corrupted_path = temp_dir.path().join(&corrupted_file);
+        assert!(!corrupted_path.exists());
+        assert!(!corrupted_path.with_extension("bin.partial").exists());
+
+        let key = format!("{PROVER_PROTOCOL_SEMANTIC_VERSION}_gpu_{corrupted_file}");
+        store
+            .put_raw(Bucket::SetupData, &key, files[&corrupted_file].clone())
+            .await
+            .unwrap();
+        downloader.download_missing(&keystore).await.unwrap();
+        assert_setup_data(&keystore, &files);
+    }
+
+    #[tokio::test]
+    async fn refusing_to_download_unverified_data() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let keystore = Keystore::new(temp_dir.path().to_owned());
+        let downloader =
+            SetupDataDownloader::from_object_store(MockObjectStore::arc(), SetupDataKind::Gpu)
+                .with_manifest(SetupDataManifest::default());
+        let err = downloader
+            .download_missing(&keystore)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("refusing to download unverified data"),
+            "{err}"
+        );
+    }
+}
@@ -10,7 +10,7 @@ use zkevm_test_harness::{
     },
     data_source::SetupDataSource,
 };
-use zksync_prover_fri_types::ProverServiceDataKey;
+use zksync_prover_fri_types::{ProverServiceDataKey, PROVER_PROTOCOL_SEMANTIC_VERSION};
 #[cfg(feature = "gpu")]
 use {
     crate::GpuProverSetupData,
@@ -19,7 +19,11 @@ use {
     zksync_prover_fri_types::circuit_definitions::boojum::worker::Worker,
 };
 
-use crate::{keystore::Keystore, GoldilocksProverSetupData};
+use crate::{
+    keystore::Keystore,
+    setup_data_downloader::{setup_data_hash, SetupDataKind},
+    GoldilocksProverSetupData,
+};
 
 /// Internal helper function, that calls the test harness to generate the setup data.
 /// It also does a final sanity check to make sure that verification keys didn't change.
@@ -75,7 +79,9 @@ pub trait SetupDataGenerator {
 
     fn keystore(&self) -> &Keystore;
 
-    /// Generates and stores the setup keys.
+    fn kind(&self) -> SetupDataKind;
+
+    /// Generates and stores the setup keys, recording their hash in the setup data manifest.
     /// Returns the md5 check sum of the stored file.
     fn generate_and_write_setup_data(
         &self,
@@ -97,6 +103,20 @@ pub trait SetupDataGenerator {
             self.keystore()
                 .save_setup_data_for_circuit_type(circuit, &serialized)
                 .context("save_setup_data()")?;
+
+            let mut manifest = self
+                .keystore()
+                .load_setup_data_manifest()
+                .context("load_setup_data_manifest()")?;
+            manifest.insert(
+                PROVER_PROTOCOL_SEMANTIC_VERSION,
+                self.kind(),
+                format!("setup_{}_data.bin", circuit.name()),
+                setup_data_hash(&serialized),
+            );
+            self.keystore()
+                .save_setup_data_manifest(&manifest)
+                .context("save_setup_data_manifest()")?;
         } else {
             tracing::warn!("Dry run - not writing the key");
         }
@@ -138,6 +158,10 @@ impl SetupDataGenerator for CPUSetupDataGenerator {
     fn keystore(&self) -> &Keystore {
         &self.keystore
     }
+
+    fn kind(&self) -> SetupDataKind {
+        SetupDataKind::Cpu
+    }
 }
 
 pub struct GPUSetupDataGenerator {
@@ -188,4 +212,8 @@ impl SetupDataGenerator for GPUSetupDataGenerator {
     fn keystore(&self) -> &Keystore {
         &self.keystore
     }
+
+    fn kind(&self) -> SetupDataKind {
+        SetupDataKind::Gpu
+    }
 }
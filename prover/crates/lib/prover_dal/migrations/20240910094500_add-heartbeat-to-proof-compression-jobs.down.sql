@@ -0,0 +1 @@
+ALTER TABLE proof_compression_jobs_fri DROP COLUMN IF EXISTS heartbeat_at;
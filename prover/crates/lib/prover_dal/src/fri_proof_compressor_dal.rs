@@ -64,6 +64,7 @@ impl FriProofCompressorDal<'_, '_> {
                 attempts = attempts + 1,
                 updated_at = NOW(),
                 processing_started_at = NOW(),
+                heartbeat_at = NOW(),
                 picked_by = $3
             WHERE
                 l1_batch_number = (
@@ -97,6 +98,81 @@ impl FriProofCompressorDal<'_, '_> {
         .map(|row| L1BatchNumber(row.l1_batch_number as u32))
     }
 
+    /// Records that `picked_by` is still working on the job. Returns `false` if the job is no longer
+    /// in progress by `picked_by` (e.g., it was reclaimed by another compressor instance).
+    pub async fn update_proof_compression_job_heartbeat(
+        &mut self,
+        block_number: L1BatchNumber,
+        picked_by: &str,
+    ) -> bool {
+        sqlx::query!(
+            r#"
+            UPDATE proof_compression_jobs_fri
+            SET
+                heartbeat_at = NOW()
+            WHERE
+                l1_batch_number = $1
+                AND status = $2
+                AND picked_by = $3
+            "#,
+            i64::from(block_number.0),
+            ProofCompressionJobStatus::InProgress.to_string(),
+            picked_by,
+        )
+        .execute(self.storage.conn())
+        .await
+        .unwrap()
+        .rows_affected()
+            > 0
+    }
+
+    /// Requeues in-progress jobs whose compressor instances stopped sending heartbeats,
+    /// so that they can be picked by other instances.
+    pub async fn reclaim_abandoned_jobs(
+        &mut self,
+        heartbeat_timeout: Duration,
+        max_attempts: u32,
+    ) -> Vec<StuckJobs> {
+        let heartbeat_timeout = pg_interval_from_duration(heartbeat_timeout);
+        sqlx::query!(
+            r#"
+            UPDATE proof_compression_jobs_fri
+            SET
+                status = $1,
+                updated_at = NOW(),
+                processing_started_at = NOW(),
+                heartbeat_at = NULL
+            WHERE
+                status = $2
+                AND heartbeat_at < NOW() - $3::INTERVAL
+                AND attempts < $4
+            RETURNING
+            l1_batch_number,
+            status,
+            attempts,
+            error,
+            picked_by
+            "#,
+            ProofCompressionJobStatus::Queued.to_string(),
+            ProofCompressionJobStatus::InProgress.to_string(),
+            &heartbeat_timeout,
+            max_attempts as i32,
+        )
+        .fetch_all(self.storage.conn())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| StuckJobs {
+            id: row.l1_batch_number as u64,
+            status: row.status,
+            attempts: row.attempts as u64,
+            circuit_id: None,
+            error: row.error,
+            picked_by: row.picked_by,
+        })
+        .collect()
+    }
+
     pub async fn get_proof_compression_job_attempts(
         &mut self,
         l1_batch_number: L1BatchNumber,
@@ -119,12 +195,15 @@ impl FriProofCompressorDal<'_, '_> {
         Ok(attempts)
     }
 
+    /// Marks the job as successful. Returns `false` if the job is no longer in progress by `picked_by`
+    /// (e.g., it was reclaimed by another compressor instance), in which case the job is not updated.
     pub async fn mark_proof_compression_job_successful(
         &mut self,
         block_number: L1BatchNumber,
         time_taken: Duration,
         l1_proof_blob_url: &str,
-    ) {
+        picked_by: &str,
+    ) -> bool {
         sqlx::query!(
             r#"
             UPDATE proof_compression_jobs_fri
@@ -135,22 +214,31 @@ impl FriProofCompressorDal<'_, '_> {
                 l1_proof_blob_url = $3
             WHERE
                 l1_batch_number = $4
+                AND status = $5
+                AND picked_by = $6
             "#,
             ProofCompressionJobStatus::Successful.to_string(),
             duration_to_naive_time(time_taken),
             l1_proof_blob_url,
-            i64::from(block_number.0)
+            i64::from(block_number.0),
+            ProofCompressionJobStatus::InProgress.to_string(),
+            picked_by,
         )
         .execute(self.storage.conn())
         .await
-        .unwrap();
+        .unwrap()
+        .rows_affected()
+            > 0
     }
 
+    /// Marks the job as failed. Returns `false` if the job is no longer in progress by `picked_by`
+    /// (e.g., it was reclaimed by another compressor instance), in which case the job is not updated.
     pub async fn mark_proof_compression_job_failed(
         &mut self,
         error: &str,
         block_number: L1BatchNumber,
-    ) {
+        picked_by: &str,
+    ) -> bool {
         sqlx::query!(
             r#"
             UPDATE proof_compression_jobs_fri
@@ -160,18 +248,20 @@ impl FriProofCompressorDal<'_, '_> {
                 updated_at = NOW()
             WHERE
                 l1_batch_number = $3
-                AND status != $4
-                AND status != $5
+                AND status = $4
+                AND picked_by = $5
             "#,
             ProofCompressionJobStatus::Failed.to_string(),
             error,
             i64::from(block_number.0),
-            ProofCompressionJobStatus::Successful.to_string(),
-            ProofCompressionJobStatus::SentToServer.to_string(),
+            ProofCompressionJobStatus::InProgress.to_string(),
+            picked_by,
         )
         .execute(self.storage.conn())
         .await
-        .unwrap();
+        .unwrap()
+        .rows_affected()
+            > 0
     }
 
     pub async fn get_least_proven_block_not_sent_to_server(
@@ -383,6 +473,7 @@ impl FriProofCompressorDal<'_, '_> {
             processing_started_at: row.processing_started_at,
             time_taken: row.time_taken,
             picked_by: row.picked_by,
+            heartbeat_at: row.heartbeat_at,
         })
     }
 
@@ -459,3 +550,119 @@ impl FriProofCompressorDal<'_, '_> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zksync_basic_types::protocol_version::L1VerifierConfig;
+
+    use super::*;
+    use crate::{ConnectionPool, ProverDal};
+
+    async fn insert_and_pick_job(
+        conn: &mut Connection<'_, Prover>,
+        l1_batch_number: L1BatchNumber,
+        picked_by: &str,
+    ) {
+        let protocol_version = ProtocolSemanticVersion::default();
+        let mut dal = conn.fri_proof_compressor_dal();
+        dal.insert_proof_compression_job(l1_batch_number, "fri_proof", protocol_version)
+            .await;
+        let picked_job = dal
+            .get_next_proof_compression_job(picked_by, protocol_version)
+            .await;
+        assert_eq!(picked_job, Some(l1_batch_number));
+    }
+
+    #[tokio::test]
+    async fn reclaiming_abandoned_jobs() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.fri_protocol_versions_dal()
+            .save_prover_protocol_version(
+                ProtocolSemanticVersion::default(),
+                L1VerifierConfig::default(),
+            )
+            .await;
+        let l1_batch_number = L1BatchNumber(1);
+        insert_and_pick_job(&mut conn, l1_batch_number, "first").await;
+
+        let mut dal = conn.fri_proof_compressor_dal();
+        let reclaimed_jobs = dal
+            .reclaim_abandoned_jobs(Duration::from_secs(60), 10)
+            .await;
+        assert!(reclaimed_jobs.is_empty(), "{reclaimed_jobs:?}");
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let reclaimed_jobs = dal.reclaim_abandoned_jobs(Duration::ZERO, 10).await;
+        assert_eq!(reclaimed_jobs.len(), 1, "{reclaimed_jobs:?}");
+        assert_eq!(reclaimed_jobs[0].id, 1);
+        assert_eq!(reclaimed_jobs[0].picked_by.as_deref(), Some("first"));
+
+        // The previous owner of the job must not be able to update it.
+        assert!(
+            !dal.update_proof_compression_job_heartbeat(l1_batch_number, "first")
+                .await
+        );
+        assert!(
+            !dal.mark_proof_compression_job_successful(
+                l1_batch_number,
+                Duration::from_secs(1),
+                "l1_proof",
+                "first"
+            )
+            .await
+        );
+        assert!(
+            !dal.mark_proof_compression_job_failed("error", l1_batch_number, "first")
+                .await
+        );
+
+        let picked_job = dal
+            .get_next_proof_compression_job("second", ProtocolSemanticVersion::default())
+            .await;
+        assert_eq!(picked_job, Some(l1_batch_number));
+        assert!(
+            dal.update_proof_compression_job_heartbeat(l1_batch_number, "second")
+                .await
+        );
+        assert!(
+            dal.mark_proof_compression_job_successful(
+                l1_batch_number,
+                Duration::from_secs(1),
+                "l1_proof",
+                "second"
+            )
+            .await
+        );
+        let job = dal
+            .get_proof_compression_job_for_batch(l1_batch_number)
+            .await
+            .unwrap();
+        assert!(
+            matches!(job.status, ProofCompressionJobStatus::Successful),
+            "{job:?}"
+        );
+        assert_eq!(job.picked_by.as_deref(), Some("second"));
+        assert_eq!(job.attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn abandoned_jobs_are_not_reclaimed_after_max_attempts() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.fri_protocol_versions_dal()
+            .save_prover_protocol_version(
+                ProtocolSemanticVersion::default(),
+                L1VerifierConfig::default(),
+            )
+            .await;
+        insert_and_pick_job(&mut conn, L1BatchNumber(1), "first").await;
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let reclaimed_jobs = conn
+            .fri_proof_compressor_dal()
+            .reclaim_abandoned_jobs(Duration::ZERO, 1)
+            .await;
+        assert!(reclaimed_jobs.is_empty(), "{reclaimed_jobs:?}");
+    }
+}
@@ -658,6 +658,41 @@ impl FriProverDal<'_, '_> {
         }
     }
 
+    /// Returns the average time taken to prove a single circuit in each aggregation round, among circuits
+    /// proven during the specified `window` (i.e., updated no earlier than `window` ago).
+    /// Rounds without proven circuits during the window are omitted.
+    pub async fn get_average_proving_times(
+        &mut self,
+        window: Duration,
+    ) -> HashMap<AggregationRound, Duration> {
+        let window = pg_interval_from_duration(window);
+        sqlx::query!(
+            r#"
+            SELECT
+                aggregation_round,
+                AVG(EXTRACT(EPOCH FROM time_taken))::DOUBLE PRECISION AS "average_secs!"
+            FROM
+                prover_jobs_fri
+            WHERE
+                status = 'successful'
+                AND time_taken IS NOT NULL
+                AND updated_at > NOW() - $1::INTERVAL
+            GROUP BY
+                aggregation_round
+            "#,
+            &window
+        )
+        .fetch_all(self.storage.conn())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| {
+            let round = AggregationRound::from(row.aggregation_round as u8);
+            (round, Duration::from_secs_f64(row.average_secs.max(0.0)))
+        })
+        .collect()
+    }
+
     pub async fn min_unproved_l1_batch_number(&mut self) -> HashMap<(u8, u8), L1BatchNumber> {
         {
             sqlx::query!(
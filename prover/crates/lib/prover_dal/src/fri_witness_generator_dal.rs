@@ -9,8 +9,8 @@ use zksync_basic_types::{
     prover_dal::{
         BasicWitnessGeneratorJobInfo, JobCountStatistics, LeafAggregationJobMetadata,
         LeafWitnessGeneratorJobInfo, NodeAggregationJobMetadata, NodeWitnessGeneratorJobInfo,
-        ProofGenerationTime, RecursionTipWitnessGeneratorJobInfo, SchedulerWitnessGeneratorJobInfo,
-        StuckJobs, WitnessJobStatus,
+        ProofDependencies, ProofGenerationTime, RecursionTipWitnessGeneratorJobInfo,
+        SchedulerWitnessGeneratorJobInfo, StuckJobs, WitnessJobStatus,
     },
     L1BatchNumber,
 };
@@ -814,6 +814,83 @@ impl FriWitnessGeneratorDal<'_, '_> {
         .collect()
     }
 
+    /// Returns dependencies (i.e., final node proofs) of recursion tip jobs waiting for proofs, ordered by batch number.
+    pub async fn get_recursion_tip_dependencies(&mut self) -> Vec<ProofDependencies> {
+        sqlx::query!(
+            r#"
+            SELECT
+                rtwj.l1_batch_number,
+                rtwj.number_of_final_node_jobs,
+                COUNT(prover_jobs_fri.id) FILTER (
+                    WHERE
+                    prover_jobs_fri.status = 'successful'
+                ) AS "proven_final_node_jobs!"
+            FROM
+                recursion_tip_witness_jobs_fri rtwj
+            LEFT JOIN prover_jobs_fri
+                ON
+                    rtwj.l1_batch_number = prover_jobs_fri.l1_batch_number
+                    AND prover_jobs_fri.aggregation_round = $1
+                    AND prover_jobs_fri.is_node_final_proof = TRUE
+            WHERE
+                rtwj.status = 'waiting_for_proofs'
+            GROUP BY
+                rtwj.l1_batch_number,
+                rtwj.number_of_final_node_jobs
+            ORDER BY
+                rtwj.l1_batch_number
+            "#,
+            AggregationRound::NodeAggregation as i16,
+        )
+        .fetch_all(self.storage.conn())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| ProofDependencies {
+            l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+            expected: row.number_of_final_node_jobs as usize,
+            proven: row.proven_final_node_jobs as usize,
+        })
+        .collect()
+    }
+
+    /// Returns dependencies (i.e., the recursion tip proof) of scheduler jobs waiting for proofs, ordered by batch number.
+    pub async fn get_scheduler_dependencies(&mut self) -> Vec<ProofDependencies> {
+        sqlx::query!(
+            r#"
+            SELECT
+                swj.l1_batch_number,
+                COUNT(prover_jobs_fri.id) FILTER (
+                    WHERE
+                    prover_jobs_fri.status = 'successful'
+                ) AS "proven_recursion_tip_jobs!"
+            FROM
+                scheduler_witness_jobs_fri swj
+            LEFT JOIN prover_jobs_fri
+                ON
+                    swj.l1_batch_number = prover_jobs_fri.l1_batch_number
+                    AND prover_jobs_fri.aggregation_round = $1
+            WHERE
+                swj.status = 'waiting_for_proofs'
+            GROUP BY
+                swj.l1_batch_number
+            ORDER BY
+                swj.l1_batch_number
+            "#,
+            AggregationRound::RecursionTip as i16,
+        )
+        .fetch_all(self.storage.conn())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| ProofDependencies {
+            l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+            expected: 1,
+            proven: row.proven_recursion_tip_jobs as usize,
+        })
+        .collect()
+    }
+
     pub async fn requeue_stuck_leaf_jobs(
         &mut self,
         processing_timeout: Duration,
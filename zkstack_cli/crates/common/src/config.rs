@@ -1,3 +1,4 @@
+use clap::ValueEnum;
 use once_cell::sync::OnceCell;
 
 static CONFIG: OnceCell<GlobalConfig> = OnceCell::new();
@@ -17,4 +18,15 @@ pub struct GlobalConfig {
     pub verbose: bool,
     pub chain_name: Option<String>,
     pub ignore_prerequisites: bool,
+    pub output: OutputFormat,
+}
+
+/// Format of the results printed by the commands to stdout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable logs only
+    #[default]
+    Text,
+    /// Results are additionally printed to stdout as JSON
+    Json,
 }
@@ -1,5 +1,7 @@
 use std::path::PathBuf;
 
+use anyhow::Context as _;
+use ethers::types::Address;
 use xshell::{cmd, Shell};
 
 use crate::cmd::Cmd;
@@ -18,6 +20,47 @@ pub fn build_l2_contracts(shell: Shell, link_to_code: PathBuf) -> anyhow::Result
     .run()?)
 }
 
+/// Returns the standard JSON compiler input of an L2 contract, as used by `forge` for verification.
+/// `contract` is the contract identifier in the `<path>:<name>` format.
+pub fn l2_contract_standard_json_input(
+    shell: &Shell,
+    link_to_code: PathBuf,
+    address: Address,
+    contract: &str,
+) -> anyhow::Result<serde_json::Map<String, serde_json::Value>> {
+    let _dir_guard = shell.push_dir(link_to_code.join("contracts/l2-contracts"));
+    let address = format!("{address:#x}");
+    let output = Cmd::new(cmd!(
+        shell,
+        "forge verify-contract {address} {contract} --zksync --show-standard-json-input"
+    ))
+    .run_with_output()?;
+    serde_json::from_slice(&output.stdout).context("invalid standard JSON input")
+}
+
+/// Returns the `(zksolc, solc)` compiler versions configured for the L2 contracts.
+pub fn l2_contracts_compiler_versions(
+    shell: &Shell,
+    link_to_code: PathBuf,
+) -> anyhow::Result<(Option<String>, String)> {
+    let foundry_toml = shell.read_file(link_to_code.join("contracts/l2-contracts/foundry.toml"))?;
+    let foundry_toml: toml::Table = toml::from_str(&foundry_toml)?;
+    let profile = foundry_toml
+        .get("profile")
+        .and_then(|profiles| profiles.get("default"))
+        .context("default foundry profile is missing")?;
+    let solc = profile
+        .get("solc")
+        .or_else(|| profile.get("solc_version"))
+        .and_then(toml::Value::as_str)
+        .context("solc version is not set in the default foundry profile")?;
+    let zksolc = profile
+        .get("zksync")
+        .and_then(|zksync| zksync.get("zksolc"))
+        .and_then(toml::Value::as_str);
+    Ok((zksolc.map(str::to_owned), solc.to_owned()))
+}
+
 pub fn build_system_contracts(shell: Shell, link_to_code: PathBuf) -> anyhow::Result<()> {
     let _dir_guard = shell.push_dir(link_to_code.join("contracts/system-contracts"));
     // Do not update era-contract's lockfile to avoid dirty submodule
@@ -31,7 +31,7 @@ pub async fn distribute_eth(
     chain_id: u64,
     amount: u128,
 ) -> anyhow::Result<()> {
-    let client = create_ethers_client(main_wallet.private_key.unwrap(), l1_rpc, Some(chain_id))?;
+    let client = create_ethers_client(main_wallet.local_signer()?, l1_rpc, Some(chain_id))?;
     let mut pending_txs = vec![];
     let mut nonce = client.get_transaction_count(client.address(), None).await?;
     for address in addresses {
@@ -89,7 +89,7 @@ pub async fn mint_token(
     amount: u128,
 ) -> anyhow::Result<()> {
     let client = Arc::new(
-        create_ethers_client(main_wallet.private_key.unwrap(), l1_rpc, Some(chain_id))?
+        create_ethers_client(main_wallet.local_signer()?, l1_rpc, Some(chain_id))?
             .nonce_manager(main_wallet.address),
     );
 
@@ -1,4 +1,6 @@
 use std::{
+    fs,
+    io::Write,
     path::{Path, PathBuf},
     str::FromStr,
 };
@@ -7,7 +9,7 @@ use clap::{Parser, ValueEnum};
 use ethers::{
     core::types::Bytes,
     middleware::Middleware,
-    prelude::{LocalWallet, Signer},
+    prelude::{Http, LocalWallet, Provider, Signer},
     types::{Address, H256, U256},
     utils::{hex, hex::ToHex},
 };
@@ -15,10 +17,7 @@ use serde::{Deserialize, Serialize};
 use strum::Display;
 use xshell::{cmd, Shell};
 
-use crate::{
-    cmd::{Cmd, CmdResult},
-    ethereum::create_ethers_client,
-};
+use crate::cmd::{Cmd, CmdResult};
 
 /// Forge is a wrapper around the forge binary.
 pub struct Forge {
@@ -42,6 +41,7 @@ impl Forge {
             base_path: self.path.clone(),
             script_path: path.to_path_buf(),
             args,
+            keystore_password: None,
         }
     }
 }
@@ -51,11 +51,26 @@ pub struct ForgeScript {
     base_path: PathBuf,
     script_path: PathBuf,
     args: ForgeScriptArgs,
+    keystore_password: Option<String>,
 }
 
 impl ForgeScript {
     /// Run the forge script command.
     pub fn run(mut self, shell: &Shell) -> anyhow::Result<()> {
+        // The keystore password is passed in a temporary file, so that it doesn't show up in the process list.
+        // The file is removed once the directory guard is dropped.
+        let _password_dir = match self.keystore_password.take() {
+            Some(password) => {
+                let dir = shell.create_temp_dir()?;
+                let path = dir.path().join("keystore_password");
+                write_password_file(&path, &password)?;
+                self.args.add_arg(ForgeScriptArg::PasswordFile {
+                    path: path.display().to_string(),
+                });
+                Some(dir)
+            }
+            None => None,
+        };
         let _dir_guard = shell.push_dir(&self.base_path);
         let script_path = self.script_path.as_os_str();
         let args_no_resume = self.args.build();
@@ -142,6 +157,24 @@ impl ForgeScript {
         self
     }
 
+    /// Sign the transactions with an encrypted keystore.
+    pub fn with_keystore(mut self, path: &Path, password: String) -> Self {
+        self.args.add_arg(ForgeScriptArg::Keystore {
+            path: path.display().to_string(),
+        });
+        self.keystore_password = Some(password);
+        self
+    }
+
+    /// Sign the transactions with a Ledger account.
+    pub fn with_ledger(mut self, derivation_path: String) -> Self {
+        self.args.add_arg(ForgeScriptArg::Ledger);
+        self.args.add_arg(ForgeScriptArg::MnemonicDerivationPaths {
+            path: derivation_path,
+        });
+        self
+    }
+
     // Do not start the script if balance is not enough
     pub fn private_key(&self) -> Option<LocalWallet> {
         self.args.args.iter().find_map(|a| {
@@ -166,18 +199,26 @@ impl ForgeScript {
     }
 
     pub fn address(&self) -> Option<Address> {
-        self.private_key().map(|k| k.address())
+        self.private_key().map(|k| k.address()).or_else(|| {
+            self.args.args.iter().find_map(|a| {
+                if let ForgeScriptArg::Sender { address } = a {
+                    Address::from_str(address).ok()
+                } else {
+                    None
+                }
+            })
+        })
     }
 
     pub async fn get_the_balance(&self) -> anyhow::Result<Option<U256>> {
         let Some(rpc_url) = self.rpc_url() else {
             return Ok(None);
         };
-        let Some(private_key) = self.private_key() else {
+        let Some(address) = self.address() else {
             return Ok(None);
         };
-        let client = create_ethers_client(private_key, rpc_url, None)?;
-        let balance = client.get_balance(client.address(), None).await?;
+        let provider = Provider::<Http>::try_from(rpc_url)?;
+        let balance = provider.get_balance(address, None).await?;
         Ok(Some(balance))
     }
 }
@@ -230,6 +271,19 @@ pub enum ForgeScriptArg {
     PrivateKey {
         private_key: String,
     },
+    #[strum(to_string = "keystore={path}")]
+    Keystore {
+        path: String,
+    },
+    #[strum(to_string = "password-file={path}")]
+    PasswordFile {
+        path: String,
+    },
+    Ledger,
+    #[strum(to_string = "mnemonic-derivation-paths={path}")]
+    MnemonicDerivationPaths {
+        path: String,
+    },
     #[strum(to_string = "rpc-url={url}")]
     RpcUrl {
         url: String,
@@ -427,3 +481,13 @@ fn check_error(cmd_result: &CmdResult<()>, error_text: &str) -> bool {
     }
     false
 }
+
+/// Writes the keystore password to a file readable only by the current user.
+fn write_password_file(path: &Path, password: &str) -> anyhow::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(password.as_bytes())?;
+    Ok(())
+}
@@ -21,5 +21,5 @@ pub use prerequisites::{
     check_general_prerequisites, check_prerequisites, GCLOUD_PREREQUISITE, GPU_PREREQUISITES,
     PROVER_CLI_PREREQUISITE,
 };
-pub use prompt::{init_prompt_theme, Prompt, PromptConfirm, PromptSelect};
+pub use prompt::{init_prompt_theme, Prompt, PromptConfirm, PromptPassword, PromptSelect};
 pub use term::{error, logger, spinner};
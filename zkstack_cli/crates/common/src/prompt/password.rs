@@ -0,0 +1,17 @@
+use cliclack::Password;
+
+pub struct PromptPassword {
+    inner: Password,
+}
+
+impl PromptPassword {
+    pub fn new(question: &str) -> Self {
+        Self {
+            inner: Password::new(question).mask('▪'),
+        }
+    }
+
+    pub fn ask(mut self) -> String {
+        self.inner.interact().unwrap()
+    }
+}
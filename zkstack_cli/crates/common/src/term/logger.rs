@@ -1,10 +1,14 @@
 use std::fmt::Display;
 
+use anyhow::Context as _;
 use cliclack::{intro as cliclak_intro, log, outro as cliclak_outro, Theme, ThemeState};
 use console::{style, Emoji, Term};
 use serde::Serialize;
 
-use crate::prompt::CliclackTheme;
+use crate::{
+    config::{global_config, OutputFormat},
+    prompt::CliclackTheme,
+};
 
 const S_BAR: Emoji = Emoji("│", "|");
 
@@ -62,6 +66,19 @@ pub fn error_note(msg: &str, content: &str) {
     term_write(note);
 }
 
+/// Prints the result of a command to stdout as single-line JSON if `--output json` is set.
+///
+/// Logs are written to stderr, but external tools run by the command (e.g., `forge`) inherit stdout and may write
+/// to it as well. The result is printed after the tools have finished, so scripts should parse the last line of stdout.
+pub fn output(obj: impl Serialize) -> anyhow::Result<()> {
+    if global_config().output != OutputFormat::Json {
+        return Ok(());
+    }
+    let json = serde_json::to_string(&obj).context("failed to serialize command output")?;
+    println!("{json}");
+    Ok(())
+}
+
 pub fn object_to_string(obj: impl Serialize) -> String {
     let json = serde_json::to_value(obj).unwrap();
 
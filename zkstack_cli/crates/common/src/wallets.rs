@@ -1,3 +1,6 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
 use ethers::{
     core::rand::{CryptoRng, Rng},
     signers::{coins_bip39::English, LocalWallet, MnemonicBuilder, Signer},
@@ -6,16 +9,34 @@ use ethers::{
 use serde::{Deserialize, Serialize};
 use types::parse_h256;
 
+use crate::PromptPassword;
+
+/// Environment variable with the password of the keystore files, prompted if not set.
+pub const KEYSTORE_PASSWORD_ENV: &str = "ZKSTACK_KEYSTORE_PASSWORD";
+
 #[derive(Serialize, Deserialize)]
 struct WalletSerde {
     pub address: Address,
     pub private_key: Option<H256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer: Option<WalletSigner>,
+}
+
+/// Signer used instead of a raw private key stored in the wallets config.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WalletSigner {
+    /// Encrypted JSON keystore file.
+    Keystore { path: PathBuf },
+    /// Ledger hardware wallet account with the given derivation path.
+    Ledger { derivation_path: String },
 }
 
 #[derive(Debug, Clone)]
 pub struct Wallet {
     pub address: Address,
     pub private_key: Option<LocalWallet>,
+    pub signer: Option<WalletSigner>,
 }
 
 impl<'de> Deserialize<'de> for Wallet {
@@ -25,6 +46,7 @@ impl<'de> Deserialize<'de> for Wallet {
             None => Self {
                 address: x.address,
                 private_key: None,
+                signer: x.signer,
             },
             Some(k) => {
                 let k = LocalWallet::from_bytes(k.as_bytes()).map_err(serde::de::Error::custom)?;
@@ -46,6 +68,7 @@ impl Serialize for Wallet {
         WalletSerde {
             address: self.address,
             private_key: self.private_key_h256(),
+            signer: self.signer.clone(),
         }
         .serialize(s)
     }
@@ -66,6 +89,44 @@ impl Wallet {
         Self {
             address: private_key.address(),
             private_key: Some(private_key),
+            signer: None,
+        }
+    }
+
+    /// Creates a wallet signing with an external signer, so its private key is not stored.
+    pub fn with_signer(address: Address, signer: WalletSigner) -> Self {
+        Self {
+            address,
+            private_key: None,
+            signer: Some(signer),
+        }
+    }
+
+    /// Returns the local signer of the wallet, decrypting the keystore if needed.
+    /// Hardware wallets can only be used by forge scripts.
+    pub fn local_signer(&self) -> anyhow::Result<LocalWallet> {
+        if let Some(private_key) = &self.private_key {
+            return Ok(private_key.clone());
+        }
+        match &self.signer {
+            Some(WalletSigner::Keystore { path }) => {
+                let wallet = LocalWallet::decrypt_keystore(path, keystore_password())
+                    .with_context(|| format!("Failed to decrypt keystore {}", path.display()))?;
+                anyhow::ensure!(
+                    wallet.address() == self.address,
+                    "Keystore {} doesn't match wallet address {:#x}",
+                    path.display(),
+                    self.address
+                );
+                Ok(wallet)
+            }
+            Some(WalletSigner::Ledger { .. }) => {
+                anyhow::bail!(
+                    "Wallet {:#x} is a Ledger account, which can only sign forge scripts",
+                    self.address
+                )
+            }
+            None => anyhow::bail!("Private key of wallet {:#x} is not set", self.address),
         }
     }
 
@@ -81,10 +142,17 @@ impl Wallet {
         Self {
             address: Address::zero(),
             private_key: None,
+            signer: None,
         }
     }
 }
 
+/// Returns the keystore password from the environment, prompting for it otherwise.
+pub fn keystore_password() -> String {
+    std::env::var(KEYSTORE_PASSWORD_ENV)
+        .unwrap_or_else(|_| PromptPassword::new("Keystore password").ask())
+}
+
 #[test]
 fn test_load_localhost_wallets() {
     let wallet = Wallet::from_mnemonic(
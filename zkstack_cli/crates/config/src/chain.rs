@@ -11,14 +11,14 @@ use zksync_basic_types::L2ChainId;
 use crate::{
     consts::{
         CONFIG_NAME, CONTRACTS_FILE, EN_CONFIG_FILE, GENERAL_FILE, GENESIS_FILE,
-        L1_CONTRACTS_FOUNDRY, SECRETS_FILE, WALLETS_FILE,
+        L1_CONTRACTS_FOUNDRY, SECRETS_FILE, TOKENS_FILE, WALLETS_FILE,
     },
     create_localhost_wallets,
     traits::{
         FileConfigWithDefaultName, ReadConfig, ReadConfigWithBasePath, SaveConfig,
         SaveConfigWithBasePath, ZkStackConfig,
     },
-    ContractsConfig, GeneralConfig, GenesisConfig, SecretsConfig, WalletsConfig,
+    ContractsConfig, GeneralConfig, GenesisConfig, SecretsConfig, TokensConfig, WalletsConfig,
 };
 
 /// Chain configuration file. This file is created in the chain
@@ -42,6 +42,10 @@ pub struct ChainConfigInternal {
     pub legacy_bridge: Option<bool>,
     #[serde(default)] // for backward compatibility
     pub evm_emulator: bool,
+    /// Last L1 batch settled on L1 before the chain migrates its settlement to the Gateway. Recorded at the `pause`
+    /// migration stage and checked against Postgres and L1 at the `finalize` stage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway_migration_l1_batch: Option<u32>,
 }
 
 /// Chain configuration file. This file is created in the chain
@@ -64,6 +68,7 @@ pub struct ChainConfig {
     pub shell: OnceCell<Shell>,
     pub legacy_bridge: Option<bool>,
     pub evm_emulator: bool,
+    pub gateway_migration_l1_batch: Option<u32>,
 }
 
 impl Serialize for ChainConfig {
@@ -108,6 +113,15 @@ impl ChainConfig {
         SecretsConfig::read_with_base_path(self.get_shell(), &self.configs)
     }
 
+    /// Returns the bridged test tokens, or an empty config if no tokens were deployed.
+    pub fn get_tokens_config(&self) -> anyhow::Result<TokensConfig> {
+        let path = self.path_to_tokens_config();
+        if !self.get_shell().path_exists(&path) {
+            return Ok(TokensConfig::default());
+        }
+        TokensConfig::read(self.get_shell(), path)
+    }
+
     pub fn path_to_general_config(&self) -> PathBuf {
         self.configs.join(GENERAL_FILE)
     }
@@ -128,6 +142,10 @@ impl ChainConfig {
         self.configs.join(SECRETS_FILE)
     }
 
+    pub fn path_to_tokens_config(&self) -> PathBuf {
+        self.configs.join(TOKENS_FILE)
+    }
+
     pub fn save_general_config(&self, general_config: &GeneralConfig) -> anyhow::Result<()> {
         general_config.save_with_base_path(self.get_shell(), &self.configs)
     }
@@ -161,6 +179,7 @@ impl ChainConfig {
             wallet_creation: self.wallet_creation,
             legacy_bridge: self.legacy_bridge,
             evm_emulator: self.evm_emulator,
+            gateway_migration_l1_batch: self.gateway_migration_l1_batch,
         }
     }
 }
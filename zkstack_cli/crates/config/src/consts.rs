@@ -8,6 +8,8 @@ pub const SECRETS_FILE: &str = "secrets.yaml";
 pub const GENERAL_FILE: &str = "general.yaml";
 /// Name of the genesis config file
 pub const GENESIS_FILE: &str = "genesis.yaml";
+/// Name of the custom genesis state file
+pub const CUSTOM_GENESIS_STATE_FILE: &str = "genesis_state.bin";
 
 // Name of external node specific config
 pub const EN_CONFIG_FILE: &str = "external_node.yaml";
@@ -20,6 +22,10 @@ pub(crate) const INITIAL_DEPLOYMENT_FILE: &str = "initial_deployments.yaml";
 pub(crate) const ERC20_DEPLOYMENT_FILE: &str = "erc20_deployments.yaml";
 /// Name of the contracts file
 pub const CONTRACTS_FILE: &str = "contracts.yaml";
+/// Name of the file with the test tokens bridged to the chain
+pub const TOKENS_FILE: &str = "tokens.yaml";
+/// Name of the file with verification request ids of the L2 contracts
+pub const L2_CONTRACTS_VERIFICATION_FILE: &str = "l2_contracts_verification.yaml";
 /// Main repository for the ZKsync project
 pub const ZKSYNC_ERA_GIT_REPO: &str = "https://github.com/matter-labs/zksync-era";
 /// Name of the docker-compose file inside zksync repository
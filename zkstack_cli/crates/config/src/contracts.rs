@@ -1,5 +1,8 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use ethers::types::{Address, H256};
 use serde::{Deserialize, Serialize};
+use strum::EnumIter;
 
 use crate::{
     consts::CONTRACTS_FILE,
@@ -117,6 +120,22 @@ impl ContractsConfig {
         self.l2.timestamp_asserter_addr = Some(timestamp_asserter_output.timestamp_asserter);
         Ok(())
     }
+
+    pub fn is_l2_step_completed(&self, step: L2DeploymentStep) -> bool {
+        self.l2.completed_steps.contains(&step)
+    }
+
+    pub fn mark_l2_step_completed(&mut self, step: L2DeploymentStep) {
+        self.l2.completed_steps.insert(step);
+    }
+
+    pub fn set_custom_l2_contracts(
+        &mut self,
+        contracts: impl IntoIterator<Item = (String, Address)>,
+    ) -> anyhow::Result<()> {
+        self.l2.custom.extend(contracts);
+        Ok(())
+    }
 }
 
 impl FileConfigWithDefaultName for ContractsConfig {
@@ -170,4 +189,34 @@ pub struct L2Contracts {
     pub multicall3: Option<Address>,
     pub legacy_shared_bridge_addr: Option<Address>,
     pub timestamp_asserter_addr: Option<Address>,
+    /// Contracts deployed by user-supplied L2 deploy scripts.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom: BTreeMap<String, Address>,
+    /// Deployment steps that were completed, skipped when the deployment is re-run.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub completed_steps: BTreeSet<L2DeploymentStep>,
+}
+
+/// Step of the L2 contracts deployment, in the order they are executed.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    EnumIter,
+    strum::Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum L2DeploymentStep {
+    InitializeBridges,
+    DefaultUpgrader,
+    ConsensusRegistry,
+    Multicall3,
+    TimestampAsserter,
 }
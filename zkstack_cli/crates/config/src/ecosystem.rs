@@ -22,7 +22,7 @@ use crate::{
         output::{ERC20Tokens, Erc20Token},
     },
     traits::{FileConfigWithDefaultName, ReadConfig, SaveConfig, ZkStackConfig},
-    ChainConfig, ChainConfigInternal, ContractsConfig, WalletsConfig,
+    ChainConfig, ChainConfigInternal, ContractsConfig, PortRegistry, WalletsConfig,
 };
 
 /// Ecosystem configuration file. This file is created in the chain
@@ -39,6 +39,8 @@ struct EcosystemConfigInternal {
     pub era_chain_id: L2ChainId,
     pub prover_version: ProverMode,
     pub wallet_creation: WalletCreation,
+    #[serde(default, skip_serializing_if = "PortRegistry::is_empty")]
+    pub ports: PortRegistry,
 }
 
 /// Ecosystem configuration file. This file is created in the chain
@@ -55,6 +57,7 @@ pub struct EcosystemConfig {
     pub era_chain_id: L2ChainId,
     pub prover_version: ProverMode,
     pub wallet_creation: WalletCreation,
+    pub ports: PortRegistry,
     pub shell: OnceCell<Shell>,
 }
 
@@ -85,6 +88,7 @@ impl ReadConfig for EcosystemConfig {
             era_chain_id: config.era_chain_id,
             prover_version: config.prover_version,
             wallet_creation: config.wallet_creation,
+            ports: config.ports,
             shell: Default::default(),
         })
     }
@@ -179,6 +183,7 @@ impl EcosystemConfig {
                 .unwrap_or_else(|| self.get_chain_artifacts_path(name)),
             legacy_bridge: config.legacy_bridge,
             evm_emulator: config.evm_emulator,
+            gateway_migration_l1_batch: config.gateway_migration_l1_batch,
         })
     }
 
@@ -269,6 +274,7 @@ impl EcosystemConfig {
             era_chain_id: self.era_chain_id,
             prover_version: self.prover_version,
             wallet_creation: self.wallet_creation,
+            ports: self.ports.clone(),
         }
     }
 }
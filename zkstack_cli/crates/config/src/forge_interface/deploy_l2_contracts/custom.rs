@@ -0,0 +1,104 @@
+use std::path::{Path, PathBuf};
+
+use ethers::types::Address;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::input::DeployL2ContractsInput;
+use crate::{consts::L1_CONTRACTS_FOUNDRY, traits::ZkStackConfig};
+
+/// Forge script supplied by the chain operator to deploy additional L2 contracts
+/// (e.g. custom paymasters or bridges) through the same pipeline as the built-in L2 contracts.
+///
+/// The script reads its input from [`Self::input_path`] and is expected to write its output
+/// to [`Self::output_path`].
+pub trait CustomL2DeployScript {
+    type Input: Serialize + ZkStackConfig;
+    type Output: DeserializeOwned + Clone + ZkStackConfig;
+
+    /// Unique name of the script, used to name its input and output files.
+    fn name(&self) -> &str;
+
+    /// Path to the script. Relative paths are resolved against the L1 contracts foundry project.
+    fn script(&self) -> PathBuf;
+
+    /// Function of the script to run instead of `run()`.
+    fn signature(&self) -> Option<&str> {
+        None
+    }
+
+    /// Builds the script input. `base` contains the values passed to the built-in L2 deployment
+    /// script, so that custom scripts can reuse them.
+    fn input(&self, base: &DeployL2ContractsInput) -> anyhow::Result<Self::Input>;
+
+    /// Named addresses from the script output, which are stored in the chain contracts config.
+    fn deployed_contracts(&self, output: &Self::Output) -> Vec<(String, Address)>;
+
+    // Path to the input file for forge script
+    fn input_path(&self, link_to_code: &Path) -> PathBuf {
+        link_to_code
+            .join(L1_CONTRACTS_FOUNDRY)
+            .join(format!("script-config/config-deploy-{}.toml", self.name()))
+    }
+
+    // Path to the output file for forge script
+    fn output_path(&self, link_to_code: &Path) -> PathBuf {
+        link_to_code
+            .join(L1_CONTRACTS_FOUNDRY)
+            .join(format!("script-out/output-deploy-{}.toml", self.name()))
+    }
+}
+
+/// Untyped TOML config of an [`ExternalL2DeployScript`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExternalScriptConfig(pub serde_json::Map<String, serde_json::Value>);
+
+impl ZkStackConfig for ExternalScriptConfig {}
+
+/// Custom L2 deploy script registered from the command line, with schemaless input and output.
+///
+/// The input consists of the fields of [`DeployL2ContractsInput`], overridden by the values
+/// provided by the user. All the top-level address values of the output are recorded
+/// in the contracts config.
+#[derive(Debug, Clone)]
+pub struct ExternalL2DeployScript {
+    pub name: String,
+    pub script: PathBuf,
+    pub signature: Option<String>,
+    pub input: ExternalScriptConfig,
+}
+
+impl CustomL2DeployScript for ExternalL2DeployScript {
+    type Input = ExternalScriptConfig;
+    type Output = ExternalScriptConfig;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn script(&self) -> PathBuf {
+        self.script.clone()
+    }
+
+    fn signature(&self) -> Option<&str> {
+        self.signature.as_deref()
+    }
+
+    fn input(&self, base: &DeployL2ContractsInput) -> anyhow::Result<Self::Input> {
+        let serde_json::Value::Object(mut input) = serde_json::to_value(base)? else {
+            anyhow::bail!("DeployL2ContractsInput is not serialized as a map");
+        };
+        input.extend(self.input.0.clone());
+        Ok(ExternalScriptConfig(input))
+    }
+
+    fn deployed_contracts(&self, output: &Self::Output) -> Vec<(String, Address)> {
+        output
+            .0
+            .iter()
+            .filter_map(|(name, value)| {
+                let address = value.as_str()?.parse().ok()?;
+                Some((format!("{}.{name}", self.name), address))
+            })
+            .collect()
+    }
+}
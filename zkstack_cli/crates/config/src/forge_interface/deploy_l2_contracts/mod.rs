@@ -1,2 +1,3 @@
+pub mod custom;
 pub mod input;
 pub mod output;
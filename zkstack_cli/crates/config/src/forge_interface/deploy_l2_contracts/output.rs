@@ -1,7 +1,12 @@
+use std::collections::BTreeMap;
+
 use ethers::types::Address;
 use serde::{Deserialize, Serialize};
 
-use crate::traits::ZkStackConfig;
+use crate::{
+    consts::L2_CONTRACTS_VERIFICATION_FILE,
+    traits::{FileConfigWithDefaultName, ZkStackConfig},
+};
 
 impl ZkStackConfig for InitializeBridgeOutput {}
 impl ZkStackConfig for DefaultL2UpgradeOutput {}
@@ -9,6 +14,7 @@ impl ZkStackConfig for ConsensusRegistryOutput {}
 impl ZkStackConfig for Multicall3Output {}
 
 impl ZkStackConfig for TimestampAsserterOutput {}
+impl ZkStackConfig for L2ContractsVerificationOutput {}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InitializeBridgeOutput {
@@ -36,3 +42,13 @@ pub struct Multicall3Output {
 pub struct TimestampAsserterOutput {
     pub timestamp_asserter: Address,
 }
+
+/// Ids of the requests submitted to the chain contract verifier for the deployed L2 contracts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct L2ContractsVerificationOutput {
+    pub verification_ids: BTreeMap<String, usize>,
+}
+
+impl FileConfigWithDefaultName for L2ContractsVerificationOutput {
+    const FILE_NAME: &'static str = L2_CONTRACTS_VERIFICATION_FILE;
+}
@@ -1,5 +1,8 @@
 use std::path::Path;
 
+use anyhow::Context as _;
+use ethers::types::Bytes;
+use serde::{Deserialize, Serialize};
 use xshell::Shell;
 use zksync_basic_types::L1ChainId;
 pub use zksync_config::GenesisConfig;
@@ -7,7 +10,7 @@ use zksync_protobuf_config::{encode_yaml_repr, read_yaml_repr};
 
 use crate::{
     consts::GENESIS_FILE,
-    traits::{FileConfigWithDefaultName, ReadConfig, SaveConfig},
+    traits::{FileConfigWithDefaultName, ReadConfig, SaveConfig, ZkStackConfig},
     ChainConfig,
 };
 
@@ -39,3 +42,35 @@ impl ReadConfig for GenesisConfig {
         read_yaml_repr::<zksync_protobuf_config::proto::genesis::Genesis>(&path, false)
     }
 }
+
+/// Portable genesis of a chain, allowing to re-create an identical chain without re-running
+/// the deployment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenesisExport {
+    /// Genesis config (genesis root, protocol version, base system contract hashes, etc.)
+    /// in the `genesis.yaml` format.
+    pub genesis: String,
+    /// Initial storage logs and factory deps (including system contracts)
+    /// in the format produced by `custom_genesis_export`.
+    pub state: Bytes,
+}
+
+impl ZkStackConfig for GenesisExport {}
+
+impl GenesisExport {
+    pub fn new(genesis: &GenesisConfig, state: Vec<u8>) -> anyhow::Result<Self> {
+        let genesis = encode_yaml_repr::<zksync_protobuf_config::proto::genesis::Genesis>(genesis)?;
+        Ok(Self {
+            genesis: String::from_utf8(genesis)?,
+            state: state.into(),
+        })
+    }
+
+    pub fn genesis_config(&self) -> anyhow::Result<GenesisConfig> {
+        zksync_protobuf::serde::Deserialize {
+            deny_unknown_fields: false,
+        }
+        .proto_repr_from_yaml::<zksync_protobuf_config::proto::genesis::Genesis>(&self.genesis)
+        .context("invalid genesis config")
+    }
+}
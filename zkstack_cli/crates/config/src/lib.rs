@@ -7,7 +7,10 @@ pub use file_config::*;
 pub use general::*;
 pub use genesis::*;
 pub use manipulations::*;
+pub use ports::*;
+pub use protocol_upgrade::*;
 pub use secrets::*;
+pub use tokens::*;
 pub use wallet_creation::*;
 pub use wallets::*;
 pub use zksync_protobuf_config::{encode_yaml_repr, read_yaml_repr};
@@ -21,7 +24,10 @@ mod file_config;
 mod general;
 mod genesis;
 mod manipulations;
+mod ports;
+mod protocol_upgrade;
 mod secrets;
+mod tokens;
 mod wallet_creation;
 mod wallets;
 
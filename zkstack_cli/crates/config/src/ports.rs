@@ -0,0 +1,146 @@
+use std::{collections::BTreeMap, fmt, path::Path};
+
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use xshell::Shell;
+
+/// Ports allocated to the chains of the ecosystem.
+///
+/// The registry is stored in the ecosystem config and maps an owner (a chain name, or
+/// the external node of a chain, see [`PortRegistry::external_node_owner`]) to the ports
+/// it uses, keyed by the path of the port in the config file (e.g. `api:web3_json_rpc:http_port`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PortRegistry(BTreeMap<String, BTreeMap<String, u16>>);
+
+/// Port that is used by an owner while being already allocated to another one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortConflict {
+    pub port: u16,
+    pub description: String,
+    pub allocated_to: String,
+    pub allocated_as: String,
+}
+
+impl fmt::Display for PortConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "port {} ({}) is already allocated to {} ({})",
+            self.port, self.description, self.allocated_to, self.allocated_as
+        )
+    }
+}
+
+impl PortRegistry {
+    pub fn external_node_owner(chain_name: &str) -> String {
+        format!("{chain_name}/external_node")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn ports(&self, owner: &str) -> Option<&BTreeMap<String, u16>> {
+        self.0.get(owner)
+    }
+
+    pub fn set_ports(&mut self, owner: &str, ports: BTreeMap<String, u16>) {
+        self.0.insert(owner.to_string(), ports);
+    }
+
+    pub fn remove(&mut self, owner: &str) -> Option<BTreeMap<String, u16>> {
+        self.0.remove(owner)
+    }
+
+    /// Iterates over `(owner, description, port)` of all allocated ports, skipping the ports
+    /// of `exclude` owner.
+    pub fn allocated_ports<'a>(
+        &'a self,
+        exclude: Option<&'a str>,
+    ) -> impl Iterator<Item = (&'a str, &'a str, u16)> + 'a {
+        self.0
+            .iter()
+            .filter(move |(owner, _)| Some(owner.as_str()) != exclude)
+            .flat_map(|(owner, ports)| {
+                ports
+                    .iter()
+                    .map(move |(desc, port)| (owner.as_str(), desc.as_str(), *port))
+            })
+    }
+
+    /// Returns the ports of `owner` that are already allocated to other owners.
+    pub fn conflicts(&self, owner: &str, ports: &BTreeMap<String, u16>) -> Vec<PortConflict> {
+        let mut conflicts = vec![];
+        for (allocated_to, allocated_as, allocated_port) in self.allocated_ports(Some(owner)) {
+            for (description, port) in ports {
+                if *port == allocated_port {
+                    conflicts.push(PortConflict {
+                        port: *port,
+                        description: description.clone(),
+                        allocated_to: allocated_to.to_string(),
+                        allocated_as: allocated_as.to_string(),
+                    });
+                }
+            }
+        }
+        conflicts.sort_by_key(|conflict| conflict.port);
+        conflicts
+    }
+
+    /// Fails if any of the ports of `owner` is already allocated to another owner.
+    pub fn validate(&self, owner: &str, ports: &BTreeMap<String, u16>) -> anyhow::Result<()> {
+        let conflicts = self.conflicts(owner, ports);
+        if conflicts.is_empty() {
+            return Ok(());
+        }
+        let conflicts: Vec<_> = conflicts.iter().map(ToString::to_string).collect();
+        anyhow::bail!(
+            "Port conflicts detected for {owner}:\n{}",
+            conflicts.join("\n")
+        )
+    }
+}
+
+/// Reads all ports from a YAML config file, i.e. values of the keys ending with `port`.
+pub fn read_ports_from_yaml(
+    shell: &Shell,
+    path: impl AsRef<Path>,
+) -> anyhow::Result<BTreeMap<String, u16>> {
+    let value: Value = serde_yaml::from_str(&shell.read_file(path)?)?;
+    Ok(collect_ports_from_yaml(&value))
+}
+
+/// Collects all ports from a YAML value, keyed by their path (e.g. `api:web3_json_rpc:http_port`).
+pub fn collect_ports_from_yaml(value: &Value) -> BTreeMap<String, u16> {
+    let mut ports = BTreeMap::new();
+    collect_ports(value, "", &mut ports);
+    ports
+}
+
+fn collect_ports(value: &Value, path: &str, ports: &mut BTreeMap<String, u16>) {
+    match value {
+        Value::Mapping(map) => {
+            for (key, val) in map {
+                let key = key.as_str().unwrap_or_default();
+                let new_path = if path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{path}:{key}")
+                };
+                if key.ends_with("port") {
+                    if let Some(port) = val.as_u64().and_then(|p| u16::try_from(p).ok()) {
+                        ports.insert(new_path.clone(), port);
+                    }
+                }
+                collect_ports(val, &new_path, ports);
+            }
+        }
+        Value::Sequence(seq) => {
+            for (index, val) in seq.iter().enumerate() {
+                collect_ports(val, &format!("{path}:{index}"), ports);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,35 @@
+use ethers::types::{Address, Bytes, H256};
+use serde::{Deserialize, Serialize};
+use types::ProtocolSemanticVersion;
+
+use crate::traits::ZkStackConfig;
+
+/// Description of a new protocol version, used to generate the upgrade calldata.
+///
+/// Only L1 changes are supported: the upgrade doesn't contain an L2 upgrade transaction.
+/// Hashes and addresses that are not set are left unchanged by the upgrade.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtocolUpgradeDescription {
+    pub protocol_version: ProtocolSemanticVersion,
+    /// Upgrade contract to delegate-call, defaults to the default upgrade of the ecosystem.
+    pub upgrade_address: Option<Address>,
+    pub bootloader_hash: Option<H256>,
+    pub default_aa_hash: Option<H256>,
+    pub verifier: Option<Address>,
+    pub verifier_params: Option<VerifierParams>,
+    #[serde(default)]
+    pub l1_contracts_upgrade_calldata: Bytes,
+    #[serde(default)]
+    pub post_upgrade_calldata: Bytes,
+    #[serde(default)]
+    pub upgrade_timestamp: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct VerifierParams {
+    pub recursion_node_level_vk_hash: H256,
+    pub recursion_leaf_level_vk_hash: H256,
+    pub recursion_circuits_set_vks_hash: H256,
+}
+
+impl ZkStackConfig for ProtocolUpgradeDescription {}
@@ -0,0 +1,31 @@
+use std::collections::BTreeMap;
+
+use ethers::types::Address;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    consts::TOKENS_FILE,
+    traits::{FileConfigWithDefaultName, ZkStackConfig},
+};
+
+/// Test tokens deployed on L1 and bridged to the chain, keyed by symbol.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TokensConfig {
+    pub tokens: BTreeMap<String, BridgedToken>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BridgedToken {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u64,
+    pub l1_address: Address,
+    /// Address of the token on L2, set once the token has been bridged.
+    pub l2_address: Option<Address>,
+}
+
+impl FileConfigWithDefaultName for TokensConfig {
+    const FILE_NAME: &'static str = TOKENS_FILE;
+}
+
+impl ZkStackConfig for TokensConfig {}
@@ -1,6 +1,11 @@
+use std::collections::BTreeMap;
+
+use clap::ValueEnum;
 use common::wallets::Wallet;
+use ethers::types::Address;
 use rand::{CryptoRng, Rng};
 use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
 
 use crate::{
     consts::WALLETS_FILE,
@@ -17,7 +22,48 @@ pub struct WalletsConfig {
     pub token_multiplier_setter: Option<Wallet>,
 }
 
+/// Role of a wallet in the wallets config.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, EnumIter, strum::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum WalletRole {
+    Deployer,
+    Operator,
+    BlobOperator,
+    FeeAccount,
+    Governor,
+    TokenMultiplierSetter,
+}
+
 impl WalletsConfig {
+    pub fn wallet(&self, role: WalletRole) -> Option<&Wallet> {
+        match role {
+            WalletRole::Deployer => self.deployer.as_ref(),
+            WalletRole::Operator => Some(&self.operator),
+            WalletRole::BlobOperator => Some(&self.blob_operator),
+            WalletRole::FeeAccount => Some(&self.fee_account),
+            WalletRole::Governor => Some(&self.governor),
+            WalletRole::TokenMultiplierSetter => self.token_multiplier_setter.as_ref(),
+        }
+    }
+
+    pub fn set_wallet(&mut self, role: WalletRole, wallet: Wallet) {
+        match role {
+            WalletRole::Deployer => self.deployer = Some(wallet),
+            WalletRole::Operator => self.operator = wallet,
+            WalletRole::BlobOperator => self.blob_operator = wallet,
+            WalletRole::FeeAccount => self.fee_account = wallet,
+            WalletRole::Governor => self.governor = wallet,
+            WalletRole::TokenMultiplierSetter => self.token_multiplier_setter = Some(wallet),
+        }
+    }
+
+    /// Addresses of the set wallets keyed by their role, without exposing private keys.
+    pub fn addresses(&self) -> BTreeMap<String, Address> {
+        WalletRole::iter()
+            .filter_map(|role| Some((role.to_string(), self.wallet(role)?.address)))
+            .collect()
+    }
+
     /// Generate random wallets
     pub fn random(rng: &mut (impl CryptoRng + Rng)) -> Self {
         Self {
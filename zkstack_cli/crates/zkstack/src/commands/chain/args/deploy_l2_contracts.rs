@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use common::forge::ForgeScriptArgs;
+use config::{
+    forge_interface::deploy_l2_contracts::custom::{ExternalL2DeployScript, ExternalScriptConfig},
+    traits::ReadConfig,
+};
+use serde::{Deserialize, Serialize};
+use xshell::Shell;
+
+use crate::messages::{
+    MSG_CUSTOM_L2_SCRIPT_INPUT_HELP, MSG_CUSTOM_L2_SCRIPT_NAME_HELP,
+    MSG_CUSTOM_L2_SCRIPT_PATH_HELP, MSG_CUSTOM_L2_SCRIPT_SIGNATURE_HELP,
+    MSG_DEPLOY_L2_CONTRACTS_FORCE_HELP, MSG_DEPLOY_L2_CONTRACTS_VERIFY_HELP,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Parser)]
+pub struct DeployL2ContractsArgs {
+    #[clap(long, help = MSG_DEPLOY_L2_CONTRACTS_FORCE_HELP)]
+    pub force: bool,
+    #[clap(long, help = MSG_DEPLOY_L2_CONTRACTS_VERIFY_HELP)]
+    pub verify_with_contract_verifier: bool,
+    /// All ethereum environment related arguments
+    #[clap(flatten)]
+    #[serde(flatten)]
+    pub forge_args: ForgeScriptArgs,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Parser)]
+pub struct DeployCustomL2ContractsArgs {
+    #[clap(long, help = MSG_CUSTOM_L2_SCRIPT_NAME_HELP)]
+    pub name: String,
+    #[clap(long, help = MSG_CUSTOM_L2_SCRIPT_PATH_HELP)]
+    pub script: PathBuf,
+    #[clap(long, help = MSG_CUSTOM_L2_SCRIPT_INPUT_HELP)]
+    pub input: Option<PathBuf>,
+    #[clap(long, help = MSG_CUSTOM_L2_SCRIPT_SIGNATURE_HELP)]
+    pub signature: Option<String>,
+    /// All ethereum environment related arguments
+    #[clap(flatten)]
+    #[serde(flatten)]
+    pub forge_args: ForgeScriptArgs,
+}
+
+impl DeployCustomL2ContractsArgs {
+    pub fn script(&self, shell: &Shell) -> anyhow::Result<ExternalL2DeployScript> {
+        let input = match &self.input {
+            Some(path) => ExternalScriptConfig::read(shell, path)?,
+            None => ExternalScriptConfig::default(),
+        };
+        Ok(ExternalL2DeployScript {
+            name: self.name.clone(),
+            script: self.script.clone(),
+            signature: self.signature.clone(),
+            input,
+        })
+    }
+}
@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use ethers::types::Address;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    commands::chain::args::init::InitArgs,
+    messages::{
+        MSG_JOIN_BRIDGEHUB_DEPLOYMENT_BLOCK_HELP, MSG_JOIN_ECOSYSTEM_OWNER_HELP, MSG_JOIN_OUT_HELP,
+        MSG_JOIN_TIMEOUT_HELP,
+    },
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Parser)]
+pub struct JoinArgs {
+    #[clap(long, help = MSG_JOIN_ECOSYSTEM_OWNER_HELP)]
+    pub ecosystem_owner: Option<Address>,
+    #[clap(long, short, help = MSG_JOIN_OUT_HELP)]
+    pub out: Option<PathBuf>,
+    #[clap(long, default_value_t = 3600, help = MSG_JOIN_TIMEOUT_HELP)]
+    pub timeout: u64,
+    #[clap(long, help = MSG_JOIN_BRIDGEHUB_DEPLOYMENT_BLOCK_HELP)]
+    pub bridgehub_deployment_block: Option<u64>,
+    #[clap(flatten)]
+    #[serde(flatten)]
+    pub init_args: InitArgs,
+}
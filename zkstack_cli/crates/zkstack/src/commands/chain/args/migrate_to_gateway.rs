@@ -0,0 +1,24 @@
+use clap::{Parser, ValueEnum};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::messages::{MSG_GATEWAY_MIGRATION_STAGE_HELP, MSG_GATEWAY_RPC_URL_HELP};
+
+/// Stages of the settlement layer migration from L1 to the Gateway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+pub enum GatewayMigrationStage {
+    /// Stop committing new batches, so that already committed ones can be proved and executed.
+    Prepare,
+    /// Pause aggregation of new L1 transactions once all committed batches are executed.
+    Pause,
+    /// Switch the settlement mode to the Gateway once all in-flight L1 transactions are confirmed.
+    Finalize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Parser)]
+pub struct MigrateToGatewayArgs {
+    #[clap(long, value_enum, help = MSG_GATEWAY_MIGRATION_STAGE_HELP)]
+    pub stage: GatewayMigrationStage,
+    #[clap(long, help = MSG_GATEWAY_RPC_URL_HELP)]
+    pub gateway_rpc_url: Option<Url>,
+}
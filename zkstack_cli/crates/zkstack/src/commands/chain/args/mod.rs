@@ -1,4 +1,8 @@
 pub mod build_transactions;
 pub mod create;
+pub mod deploy_l2_contracts;
 pub mod genesis;
 pub mod init;
+pub mod join;
+pub mod migrate_to_gateway;
+pub mod upgrade;
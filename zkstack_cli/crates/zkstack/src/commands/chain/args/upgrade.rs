@@ -0,0 +1,18 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+use crate::messages::{
+    MSG_UPGRADE_DB_CHECK_TIMEOUT_HELP, MSG_UPGRADE_DESCRIPTION_HELP, MSG_UPGRADE_SKIP_DB_CHECK_HELP,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Parser)]
+pub struct ChainUpgradeArgs {
+    #[clap(long, short, help = MSG_UPGRADE_DESCRIPTION_HELP)]
+    pub description: PathBuf,
+    #[clap(long, default_value_t = false, help = MSG_UPGRADE_SKIP_DB_CHECK_HELP)]
+    pub skip_db_check: bool,
+    #[clap(long, default_value_t = 120, help = MSG_UPGRADE_DB_CHECK_TIMEOUT_HELP)]
+    pub timeout: u64,
+}
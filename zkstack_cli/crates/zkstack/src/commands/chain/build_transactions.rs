@@ -18,9 +18,9 @@ use crate::{
     },
 };
 
-const REGISTER_CHAIN_TXNS_FILE_SRC: &str =
+pub(super) const REGISTER_CHAIN_TXNS_FILE_SRC: &str =
     "contracts/l1-contracts/broadcast/RegisterHyperchain.s.sol/9/dry-run/run-latest.json";
-const REGISTER_CHAIN_TXNS_FILE_DST: &str = "register-hyperchain-txns.json";
+pub(super) const REGISTER_CHAIN_TXNS_FILE_DST: &str = "register-hyperchain-txns.json";
 
 const SCRIPT_CONFIG_FILE_SRC: &str =
     "contracts/l1-contracts/script-config/register-hyperchain.toml";
@@ -1,12 +1,14 @@
-use std::cell::OnceCell;
+use std::{cell::OnceCell, collections::BTreeMap};
 
 use anyhow::Context;
 use common::{logger, spinner::Spinner};
 use config::{
     create_local_configs_dir, create_wallets,
-    traits::{ReadConfigWithBasePath, SaveConfigWithBasePath},
-    ChainConfig, EcosystemConfig, GenesisConfig,
+    traits::{FileConfigWithDefaultName, ReadConfigWithBasePath, SaveConfigWithBasePath},
+    ChainConfig, EcosystemConfig, GeneralConfig, GenesisConfig,
 };
+use ethers::types::Address;
+use serde::Serialize;
 use xshell::Shell;
 use zksync_basic_types::L2ChainId;
 
@@ -17,7 +19,7 @@ use crate::{
         MSG_CREATING_CHAIN_CONFIGURATIONS_SPINNER, MSG_EVM_EMULATOR_HASH_MISSING_ERR,
         MSG_SELECTED_CONFIG,
     },
-    utils::link_to_code::resolve_link_to_code,
+    utils::{link_to_code::resolve_link_to_code, ports::EcosystemPortsScanner},
 };
 
 pub fn run(args: ChainCreateArgs, shell: &Shell) -> anyhow::Result<()> {
@@ -48,19 +50,32 @@ fn create(
     let set_as_default = args.set_as_default;
     create_chain_inner(args, ecosystem_config, shell)?;
     if set_as_default {
-        ecosystem_config.default_chain = name;
-        ecosystem_config.save_with_base_path(shell, ".")?;
+        ecosystem_config.default_chain = name.clone();
     }
+    ecosystem_config.save_with_base_path(shell, ".")?;
     spinner.finish();
 
     logger::success(MSG_CHAIN_CREATED);
 
+    let chain_config = ecosystem_config.load_chain(Some(name))?;
+    logger::output(ChainCreateOutput {
+        wallets: chain_config.get_wallets_config()?.addresses(),
+        chain: chain_config,
+    })?;
+
     Ok(())
 }
 
+#[derive(Serialize)]
+struct ChainCreateOutput {
+    #[serde(flatten)]
+    chain: ChainConfig,
+    wallets: BTreeMap<String, Address>,
+}
+
 pub(crate) fn create_chain_inner(
     args: ChainCreateArgsFinal,
-    ecosystem_config: &EcosystemConfig,
+    ecosystem_config: &mut EcosystemConfig,
     shell: &Shell,
 ) -> anyhow::Result<()> {
     if args.legacy_bridge {
@@ -103,6 +118,7 @@ pub(crate) fn create_chain_inner(
         shell: OnceCell::from(shell.clone()),
         legacy_bridge,
         evm_emulator: args.evm_emulator,
+        gateway_migration_l1_batch: None,
     };
 
     create_wallets(
@@ -115,5 +131,16 @@ pub(crate) fn create_chain_inner(
     )?;
 
     chain_config.save_with_base_path(shell, chain_path)?;
+
+    // Reserve ports for the chain, so that chains created later don't reuse them
+    let mut ecosystem_ports = EcosystemPortsScanner::scan(shell, Some(&default_chain_name))?;
+    let ports = ecosystem_ports.allocate_ports_for_template(
+        shell,
+        &GeneralConfig::get_path_with_base_path(EcosystemConfig::default_configs_path(
+            &link_to_code,
+        )),
+        internal_id,
+    )?;
+    ecosystem_config.ports.set_ports(&default_chain_name, ports);
     Ok(())
 }
@@ -4,11 +4,13 @@ use anyhow::Context;
 use common::{
     contracts::build_l2_contracts,
     forge::{Forge, ForgeScriptArgs},
+    logger,
     spinner::Spinner,
 };
 use config::{
     forge_interface::{
         deploy_l2_contracts::{
+            custom::CustomL2DeployScript,
             input::DeployL2ContractsInput,
             output::{
                 ConsensusRegistryOutput, DefaultL2UpgradeOutput, InitializeBridgeOutput,
@@ -17,21 +19,32 @@ use config::{
         },
         script_params::DEPLOY_L2_CONTRACTS_SCRIPT_PARAMS,
     },
-    traits::{ReadConfig, SaveConfig, SaveConfigWithBasePath},
-    ChainConfig, ContractsConfig, EcosystemConfig,
+    traits::{ReadConfig, SaveConfig, SaveConfigWithBasePath, ZkStackConfig},
+    ChainConfig, ContractsConfig, EcosystemConfig, L2DeploymentStep,
 };
+use serde::Serialize;
+use strum::IntoEnumIterator;
 use xshell::Shell;
 
 use crate::{
+    commands::chain::{
+        args::deploy_l2_contracts::DeployCustomL2ContractsArgs,
+        verify_l2_contracts::verify_l2_deployment_step,
+    },
     messages::{
-        MSG_CHAIN_NOT_INITIALIZED, MSG_DEPLOYING_L2_CONTRACT_SPINNER,
-        MSG_L1_SECRETS_MUST_BE_PRESENTED,
+        msg_l2_deployment_step_skipped, MSG_CHAIN_NOT_INITIALIZED,
+        MSG_DEPLOYING_L2_CONTRACT_SPINNER, MSG_L1_SECRETS_MUST_BE_PRESENTED,
     },
     utils::forge::{check_the_balance, fill_forge_private_key, WalletOwner},
 };
 
 pub enum Deploy2ContractsOption {
-    All,
+    /// Deploy all contracts, skipping the steps completed by a previous run unless `force` is set.
+    /// If `verify` is set, the deployed contracts are submitted to the chain contract verifier.
+    All {
+        force: bool,
+        verify: bool,
+    },
     Upgrader,
     InitiailizeBridges,
     ConsensusRegistry,
@@ -53,249 +66,216 @@ pub async fn run(
 
     let spinner = Spinner::new(MSG_DEPLOYING_L2_CONTRACT_SPINNER);
 
-    match deploy_option {
-        Deploy2ContractsOption::All => {
+    let step = match deploy_option {
+        Deploy2ContractsOption::All { force, verify } => {
             deploy_l2_contracts(
                 shell,
                 &chain_config,
                 &ecosystem_config,
                 &mut contracts,
                 args,
+                force,
+                verify,
             )
             .await?;
+            None
         }
-        Deploy2ContractsOption::Upgrader => {
-            deploy_upgrader(
-                shell,
-                &chain_config,
-                &ecosystem_config,
-                &mut contracts,
-                args,
-            )
-            .await?;
-        }
-        Deploy2ContractsOption::ConsensusRegistry => {
-            deploy_consensus_registry(
-                shell,
-                &chain_config,
-                &ecosystem_config,
-                &mut contracts,
-                args,
-            )
-            .await?;
-        }
-        Deploy2ContractsOption::Multicall3 => {
-            deploy_multicall3(
-                shell,
-                &chain_config,
-                &ecosystem_config,
-                &mut contracts,
-                args,
-            )
-            .await?;
-        }
-        Deploy2ContractsOption::TimestampAsserter => {
-            deploy_timestamp_asserter(
-                shell,
-                &chain_config,
-                &ecosystem_config,
-                &mut contracts,
-                args,
-            )
-            .await?;
-        }
-        Deploy2ContractsOption::InitiailizeBridges => {
-            initialize_bridges(
-                shell,
-                &chain_config,
-                &ecosystem_config,
-                &mut contracts,
-                args,
-            )
-            .await?
-        }
+        Deploy2ContractsOption::Upgrader => Some(L2DeploymentStep::DefaultUpgrader),
+        Deploy2ContractsOption::InitiailizeBridges => Some(L2DeploymentStep::InitializeBridges),
+        Deploy2ContractsOption::ConsensusRegistry => Some(L2DeploymentStep::ConsensusRegistry),
+        Deploy2ContractsOption::Multicall3 => Some(L2DeploymentStep::Multicall3),
+        Deploy2ContractsOption::TimestampAsserter => Some(L2DeploymentStep::TimestampAsserter),
+    };
+    if let Some(step) = step {
+        build_l2_contracts(shell.clone(), ecosystem_config.link_to_code.clone())?;
+        deploy_step(
+            shell,
+            &chain_config,
+            &ecosystem_config,
+            &mut contracts,
+            args,
+            step,
+        )
+        .await?;
+        contracts.mark_l2_step_completed(step);
     }
 
     contracts.save_with_base_path(shell, &chain_config.configs)?;
     spinner.finish();
+    logger::output(&contracts)?;
 
     Ok(())
 }
 
-/// Build the L2 contracts, deploy one or all of them with `forge`, then update the config
-/// by reading one or all outputs written by the deploy scripts.
-async fn build_and_deploy(
-    shell: &Shell,
-    chain_config: &ChainConfig,
-    ecosystem_config: &EcosystemConfig,
-    forge_args: ForgeScriptArgs,
-    signature: Option<&str>,
-    mut update_config: impl FnMut(&Shell, &Path) -> anyhow::Result<()>,
-) -> anyhow::Result<()> {
-    build_l2_contracts(shell.clone(), ecosystem_config.link_to_code.clone())?;
-    call_forge(shell, chain_config, ecosystem_config, forge_args, signature).await?;
-    update_config(
-        shell,
-        &DEPLOY_L2_CONTRACTS_SCRIPT_PARAMS.output(&chain_config.link_to_code),
-    )?;
-    Ok(())
-}
+pub async fn run_custom(args: DeployCustomL2ContractsArgs, shell: &Shell) -> anyhow::Result<()> {
+    // Read the script input before the shell moves to the ecosystem directory
+    let script = args.script(shell)?;
+    let ecosystem_config = EcosystemConfig::from_file(shell)?;
+    let chain_config = ecosystem_config
+        .load_current_chain()
+        .context(MSG_CHAIN_NOT_INITIALIZED)?;
 
-pub async fn initialize_bridges(
-    shell: &Shell,
-    chain_config: &ChainConfig,
-    ecosystem_config: &EcosystemConfig,
-    contracts_config: &mut ContractsConfig,
-    forge_args: ForgeScriptArgs,
-) -> anyhow::Result<()> {
-    let signature = if let Some(true) = chain_config.legacy_bridge {
-        Some("runDeployLegacySharedBridge")
-    } else {
-        Some("runDeploySharedBridge")
-    };
-    build_and_deploy(
-        shell,
-        chain_config,
-        ecosystem_config,
-        forge_args,
-        signature,
-        |shell, out| {
-            contracts_config.set_l2_shared_bridge(&InitializeBridgeOutput::read(shell, out)?)
-        },
-    )
-    .await
-}
+    let mut contracts = chain_config.get_contracts_config()?;
 
-pub async fn deploy_upgrader(
-    shell: &Shell,
-    chain_config: &ChainConfig,
-    ecosystem_config: &EcosystemConfig,
-    contracts_config: &mut ContractsConfig,
-    forge_args: ForgeScriptArgs,
-) -> anyhow::Result<()> {
-    build_and_deploy(
+    let spinner = Spinner::new(MSG_DEPLOYING_L2_CONTRACT_SPINNER);
+    deploy_custom_l2_contracts(
         shell,
-        chain_config,
-        ecosystem_config,
-        forge_args,
-        Some("runDefaultUpgrader"),
-        |shell, out| {
-            contracts_config.set_default_l2_upgrade(&DefaultL2UpgradeOutput::read(shell, out)?)
-        },
+        &chain_config,
+        &ecosystem_config,
+        &mut contracts,
+        &script,
+        args.forge_args,
     )
-    .await
-}
+    .await?;
+    contracts.save_with_base_path(shell, &chain_config.configs)?;
+    spinner.finish();
+    logger::output(&contracts)?;
 
-pub async fn deploy_consensus_registry(
-    shell: &Shell,
-    chain_config: &ChainConfig,
-    ecosystem_config: &EcosystemConfig,
-    contracts_config: &mut ContractsConfig,
-    forge_args: ForgeScriptArgs,
-) -> anyhow::Result<()> {
-    build_and_deploy(
-        shell,
-        chain_config,
-        ecosystem_config,
-        forge_args,
-        Some("runDeployConsensusRegistry"),
-        |shell, out| {
-            contracts_config.set_consensus_registry(&ConsensusRegistryOutput::read(shell, out)?)
-        },
-    )
-    .await
+    Ok(())
 }
 
-pub async fn deploy_multicall3(
+/// Deploy all L2 contracts step by step, skipping the steps completed by a previous run
+/// unless `force` is set. If `verify` is set, the contracts deployed by each step are submitted
+/// to the chain contract verifier.
+///
+/// The contracts config is saved after every step, so that a partially failed deployment
+/// can be resumed by re-running it. A step is only recorded as completed once its contracts
+/// are verified, so that a failed verification is retried on the next run.
+pub async fn deploy_l2_contracts(
     shell: &Shell,
     chain_config: &ChainConfig,
     ecosystem_config: &EcosystemConfig,
     contracts_config: &mut ContractsConfig,
     forge_args: ForgeScriptArgs,
+    force: bool,
+    verify: bool,
 ) -> anyhow::Result<()> {
-    build_and_deploy(
-        shell,
-        chain_config,
-        ecosystem_config,
-        forge_args,
-        Some("runDeployMulticall3"),
-        |shell, out| contracts_config.set_multicall3(&Multicall3Output::read(shell, out)?),
-    )
-    .await
+    build_l2_contracts(shell.clone(), ecosystem_config.link_to_code.clone())?;
+    for step in L2DeploymentStep::iter() {
+        if !force && contracts_config.is_l2_step_completed(step) {
+            logger::info(msg_l2_deployment_step_skipped(step));
+            continue;
+        }
+        deploy_step(
+            shell,
+            chain_config,
+            ecosystem_config,
+            contracts_config,
+            forge_args.clone(),
+            step,
+        )
+        .await?;
+        // The deploy script output only contains the contracts of the last step.
+        if verify {
+            verify_l2_deployment_step(shell, chain_config, ecosystem_config, step).await?;
+        }
+        contracts_config.mark_l2_step_completed(step);
+        contracts_config.save_with_base_path(shell, &chain_config.configs)?;
+    }
+    Ok(())
 }
 
-pub async fn deploy_timestamp_asserter(
+/// Deploy the contracts of a single step with `forge` (the L2 contracts must already be built),
+/// then update the config from the output of the deploy script.
+async fn deploy_step(
     shell: &Shell,
     chain_config: &ChainConfig,
     ecosystem_config: &EcosystemConfig,
     contracts_config: &mut ContractsConfig,
     forge_args: ForgeScriptArgs,
+    step: L2DeploymentStep,
 ) -> anyhow::Result<()> {
-    build_and_deploy(
+    let signature = match step {
+        L2DeploymentStep::InitializeBridges => {
+            if let Some(true) = chain_config.legacy_bridge {
+                "runDeployLegacySharedBridge"
+            } else {
+                "runDeploySharedBridge"
+            }
+        }
+        L2DeploymentStep::DefaultUpgrader => "runDefaultUpgrader",
+        L2DeploymentStep::ConsensusRegistry => "runDeployConsensusRegistry",
+        L2DeploymentStep::Multicall3 => "runDeployMulticall3",
+        L2DeploymentStep::TimestampAsserter => "runDeployTimestampAsserter",
+    };
+    let input = DeployL2ContractsInput::new(chain_config, ecosystem_config.era_chain_id)?;
+    call_forge(
         shell,
         chain_config,
         ecosystem_config,
         forge_args,
-        Some("runDeployTimestampAsserter"),
-        |shell, out| {
-            contracts_config
-                .set_timestamp_asserter_addr(&TimestampAsserterOutput::read(shell, out)?)
-        },
+        &input,
+        &DEPLOY_L2_CONTRACTS_SCRIPT_PARAMS.input(&chain_config.link_to_code),
+        &DEPLOY_L2_CONTRACTS_SCRIPT_PARAMS.script(),
+        Some(signature),
     )
-    .await
+    .await?;
+
+    let out = DEPLOY_L2_CONTRACTS_SCRIPT_PARAMS.output(&chain_config.link_to_code);
+    match step {
+        L2DeploymentStep::InitializeBridges => {
+            contracts_config.set_l2_shared_bridge(&InitializeBridgeOutput::read(shell, out)?)?
+        }
+        L2DeploymentStep::DefaultUpgrader => {
+            contracts_config.set_default_l2_upgrade(&DefaultL2UpgradeOutput::read(shell, out)?)?
+        }
+        L2DeploymentStep::ConsensusRegistry => {
+            contracts_config.set_consensus_registry(&ConsensusRegistryOutput::read(shell, out)?)?
+        }
+        L2DeploymentStep::Multicall3 => {
+            contracts_config.set_multicall3(&Multicall3Output::read(shell, out)?)?
+        }
+        L2DeploymentStep::TimestampAsserter => contracts_config
+            .set_timestamp_asserter_addr(&TimestampAsserterOutput::read(shell, out)?)?,
+    }
+    Ok(())
 }
 
-pub async fn deploy_l2_contracts(
+/// Build the L2 contracts, then deploy contracts with a user-supplied script and record
+/// the deployed addresses in the contracts config.
+pub async fn deploy_custom_l2_contracts<S: CustomL2DeployScript>(
     shell: &Shell,
     chain_config: &ChainConfig,
     ecosystem_config: &EcosystemConfig,
     contracts_config: &mut ContractsConfig,
+    script: &S,
     forge_args: ForgeScriptArgs,
 ) -> anyhow::Result<()> {
-    let signature = if let Some(true) = chain_config.legacy_bridge {
-        Some("runWithLegacyBridge")
-    } else {
-        None
-    };
-    build_and_deploy(
+    build_l2_contracts(shell.clone(), ecosystem_config.link_to_code.clone())?;
+    let base_input = DeployL2ContractsInput::new(chain_config, ecosystem_config.era_chain_id)?;
+    let input = script.input(&base_input)?;
+    call_forge(
         shell,
         chain_config,
         ecosystem_config,
         forge_args,
-        signature,
-        |shell, out| {
-            contracts_config.set_l2_shared_bridge(&InitializeBridgeOutput::read(shell, out)?)?;
-            contracts_config.set_default_l2_upgrade(&DefaultL2UpgradeOutput::read(shell, out)?)?;
-            contracts_config.set_consensus_registry(&ConsensusRegistryOutput::read(shell, out)?)?;
-            contracts_config.set_multicall3(&Multicall3Output::read(shell, out)?)?;
-            contracts_config
-                .set_timestamp_asserter_addr(&TimestampAsserterOutput::read(shell, out)?)?;
-            Ok(())
-        },
+        &input,
+        &script.input_path(&chain_config.link_to_code),
+        &script.script(),
+        script.signature(),
     )
-    .await
+    .await?;
+    let output = S::Output::read(shell, script.output_path(&chain_config.link_to_code))?;
+    contracts_config.set_custom_l2_contracts(script.deployed_contracts(&output))
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn call_forge(
     shell: &Shell,
     chain_config: &ChainConfig,
     ecosystem_config: &EcosystemConfig,
     forge_args: ForgeScriptArgs,
+    input: &(impl Serialize + ZkStackConfig),
+    input_path: &Path,
+    script: &Path,
     signature: Option<&str>,
 ) -> anyhow::Result<()> {
-    let input = DeployL2ContractsInput::new(chain_config, ecosystem_config.era_chain_id)?;
     let foundry_contracts_path = chain_config.path_to_foundry();
     let secrets = chain_config.get_secrets_config()?;
-    input.save(
-        shell,
-        DEPLOY_L2_CONTRACTS_SCRIPT_PARAMS.input(&chain_config.link_to_code),
-    )?;
+    input.save(shell, input_path)?;
 
     let mut forge = Forge::new(&foundry_contracts_path)
-        .script(
-            &DEPLOY_L2_CONTRACTS_SCRIPT_PARAMS.script(),
-            forge_args.clone(),
-        )
+        .script(script, forge_args.clone())
         .with_ffi()
         .with_rpc_url(
             secrets
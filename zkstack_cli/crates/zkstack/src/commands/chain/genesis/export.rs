@@ -0,0 +1,94 @@
+use anyhow::Context;
+use common::{cmd::Cmd, logger, spinner::Spinner};
+use config::{
+    traits::{ReadConfig, SaveConfig, SaveConfigWithBasePath},
+    update_from_chain_config, EcosystemConfig, GenesisConfig, GenesisExport,
+    CUSTOM_GENESIS_STATE_FILE, GENESIS_FILE,
+};
+use xshell::{cmd, Shell};
+
+use crate::{
+    commands::chain::args::genesis::{ExportGenesisArgs, ImportGenesisArgs},
+    messages::{
+        msg_genesis_chain_id_mismatch, msg_genesis_exported, MSG_CHAIN_NOT_INITIALIZED,
+        MSG_DATABASE_MUST_BE_PRESENTED, MSG_EXPORTING_GENESIS_SPINNER,
+        MSG_FAILED_TO_EXPORT_GENESIS_ERR, MSG_GENESIS_IMPORTED,
+    },
+};
+
+/// Exports the genesis state of the current chain using the `custom_genesis_export` tool.
+/// The chain database must contain only the genesis batch.
+pub async fn export(args: ExportGenesisArgs, shell: &Shell) -> anyhow::Result<()> {
+    // Resolve the output path before the shell moves to the ecosystem directory
+    let output = shell.current_dir().join(&args.output);
+    let ecosystem_config = EcosystemConfig::from_file(shell)?;
+    let chain_config = ecosystem_config
+        .load_current_chain()
+        .context(MSG_CHAIN_NOT_INITIALIZED)?;
+    let database_url = chain_config
+        .get_secrets_config()?
+        .database
+        .and_then(|database| database.server_url)
+        .context(MSG_DATABASE_MUST_BE_PRESENTED)?;
+    let database_url = database_url.expose_str();
+
+    // The export tool updates the genesis config in place, so it's run on a copy.
+    let temp_dir = shell.create_temp_dir()?;
+    let genesis_path = temp_dir.path().join(GENESIS_FILE);
+    let state_path = temp_dir.path().join(CUSTOM_GENESIS_STATE_FILE);
+    chain_config
+        .get_genesis_config()?
+        .save(shell, &genesis_path)?;
+
+    let spinner = Spinner::new(MSG_EXPORTING_GENESIS_SPINNER);
+    {
+        let _dir_guard = shell.push_dir(&chain_config.link_to_code);
+        Cmd::new(cmd!(
+            shell,
+            "cargo run --release --bin custom_genesis_export -- --database-url={database_url} --genesis-config-path={genesis_path} --output-path={state_path}"
+        ))
+        .run()
+        .context(MSG_FAILED_TO_EXPORT_GENESIS_ERR)?;
+    }
+    spinner.finish();
+
+    let mut genesis = GenesisConfig::read(shell, &genesis_path)?;
+    genesis.custom_genesis_state_path = None;
+    let state = shell.read_binary_file(&state_path)?;
+    GenesisExport::new(&genesis, state)?.save(shell, &output)?;
+
+    logger::outro(msg_genesis_exported(&output));
+    Ok(())
+}
+
+/// Imports a genesis exported by [`export()`] into the current chain. The genesis is applied
+/// on the next run of the server genesis.
+pub async fn import(args: ImportGenesisArgs, shell: &Shell) -> anyhow::Result<()> {
+    // Resolve the input path before the shell moves to the ecosystem directory
+    let input = shell.current_dir().join(&args.input);
+    let ecosystem_config = EcosystemConfig::from_file(shell)?;
+    let chain_config = ecosystem_config
+        .load_current_chain()
+        .context(MSG_CHAIN_NOT_INITIALIZED)?;
+
+    let export = GenesisExport::read(shell, &input)?;
+    let mut genesis = export.genesis_config()?;
+    anyhow::ensure!(
+        genesis.l2_chain_id == chain_config.chain_id,
+        msg_genesis_chain_id_mismatch(genesis.l2_chain_id.as_u64(), chain_config.chain_id.as_u64())
+    );
+
+    let state_path = chain_config.configs.join(CUSTOM_GENESIS_STATE_FILE);
+    shell.write_file(&state_path, export.state.as_ref())?;
+    update_from_chain_config(&mut genesis, &chain_config)?;
+    genesis.custom_genesis_state_path = Some(
+        state_path
+            .to_str()
+            .context("invalid genesis state path")?
+            .to_owned(),
+    );
+    genesis.save_with_base_path(shell, &chain_config.configs)?;
+
+    logger::outro(MSG_GENESIS_IMPORTED);
+    Ok(())
+}
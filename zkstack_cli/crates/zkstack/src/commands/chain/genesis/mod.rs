@@ -17,6 +17,7 @@ use crate::{
 
 // Genesis subcommands
 pub mod database;
+pub mod export;
 pub mod server;
 
 #[derive(Subcommand, Debug, Clone)]
@@ -21,7 +21,7 @@ use crate::{
     },
     utils::{
         consensus::{generate_consensus_keys, get_consensus_secrets, get_genesis_specs},
-        ports::EcosystemPortsScanner,
+        ports::{register_ports, EcosystemPortsScanner},
     },
 };
 
@@ -45,7 +45,7 @@ pub async fn init_configs(
     chain_config: &ChainConfig,
 ) -> anyhow::Result<ContractsConfig> {
     // Port scanner should run before copying configs to avoid marking initial ports as assigned
-    let mut ecosystem_ports = EcosystemPortsScanner::scan(shell)?;
+    let mut ecosystem_ports = EcosystemPortsScanner::scan(shell, Some(&chain_config.name))?;
     copy_configs(shell, &ecosystem_config.link_to_code, &chain_config.configs)?;
 
     if !init_args.no_port_reallocation {
@@ -55,6 +55,11 @@ pub async fn init_configs(
             chain_config.id,
         )?;
     }
+    register_ports(
+        shell,
+        &chain_config.name,
+        &chain_config.path_to_general_config(),
+    )?;
 
     let mut general_config = chain_config.get_general_config()?;
 
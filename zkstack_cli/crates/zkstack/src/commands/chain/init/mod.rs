@@ -16,6 +16,7 @@ use crate::{
         deploy_l2_contracts, deploy_paymaster,
         genesis::genesis,
         init::configs::init_configs,
+        join::{register_chain_externally, ExternalRegistration},
         register_chain::register_chain,
         set_token_multiplier_setter::set_token_multiplier_setter,
         setup_legacy_bridge::setup_legacy_bridge,
@@ -71,11 +72,36 @@ async fn run_init(args: InitArgs, shell: &Shell) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// How the chain is registered on the Bridgehub.
+pub(crate) enum ChainRegistration {
+    /// Registered by the ecosystem governor wallet.
+    Governor,
+    /// Registered by the owner of an existing ecosystem, see `zkstack chain join`.
+    External(ExternalRegistration),
+}
+
 pub async fn init(
     init_args: &InitArgsFinal,
     shell: &Shell,
     ecosystem_config: &EcosystemConfig,
     chain_config: &ChainConfig,
+) -> anyhow::Result<()> {
+    init_with_registration(
+        init_args,
+        shell,
+        ecosystem_config,
+        chain_config,
+        ChainRegistration::Governor,
+    )
+    .await
+}
+
+pub(crate) async fn init_with_registration(
+    init_args: &InitArgsFinal,
+    shell: &Shell,
+    ecosystem_config: &EcosystemConfig,
+    chain_config: &ChainConfig,
+    registration: ChainRegistration,
 ) -> anyhow::Result<()> {
     // Initialize configs
     let init_configs_args = InitConfigsArgsFinal::from_chain_init_args(init_args);
@@ -86,21 +112,37 @@ pub async fn init(
     distribute_eth(ecosystem_config, chain_config, init_args.l1_rpc_url.clone()).await?;
     mint_base_token(ecosystem_config, chain_config, init_args.l1_rpc_url.clone()).await?;
 
-    // Register chain on BridgeHub (run by L1 Governor)
-    let spinner = Spinner::new(MSG_REGISTERING_CHAIN_SPINNER);
-    register_chain(
-        shell,
-        init_args.forge_args.clone(),
-        ecosystem_config,
-        chain_config,
-        &mut contracts_config,
-        init_args.l1_rpc_url.clone(),
-        None,
-        true,
-    )
-    .await?;
+    match registration {
+        // Register chain on BridgeHub (run by L1 Governor)
+        ChainRegistration::Governor => {
+            let spinner = Spinner::new(MSG_REGISTERING_CHAIN_SPINNER);
+            register_chain(
+                shell,
+                init_args.forge_args.clone(),
+                ecosystem_config,
+                chain_config,
+                &mut contracts_config,
+                init_args.l1_rpc_url.clone(),
+                None,
+                true,
+            )
+            .await?;
+            spinner.finish();
+        }
+        // Wait for the ecosystem owner to register the chain
+        ChainRegistration::External(registration) => {
+            register_chain_externally(
+                shell,
+                init_args,
+                ecosystem_config,
+                chain_config,
+                &mut contracts_config,
+                &registration,
+            )
+            .await?;
+        }
+    }
     contracts_config.save_with_base_path(shell, &chain_config.configs)?;
-    spinner.finish();
 
     // Accept ownership for DiamondProxy (run by L2 Governor)
     let spinner = Spinner::new(MSG_ACCEPTING_ADMIN_SPINNER);
@@ -158,6 +200,8 @@ pub async fn init(
         ecosystem_config,
         &mut contracts_config,
         init_args.forge_args.clone(),
+        false,
+        false,
     )
     .await?;
     contracts_config.save_with_base_path(shell, &chain_config.configs)?;
@@ -0,0 +1,284 @@
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::Context;
+use common::{git, logger, spinner::Spinner};
+use config::{
+    forge_interface::register_chain::output::RegisterChainOutput, ChainConfig, ContractsConfig,
+    EcosystemConfig,
+};
+use ethers::{
+    abi::parse_abi,
+    contract::BaseContract,
+    providers::{Http, Middleware, Provider},
+    types::{Address, BlockId, BlockNumber, Filter, Log, TransactionRequest, H256, U256},
+    utils::hex::ToHex,
+};
+use lazy_static::lazy_static;
+use xshell::Shell;
+
+use crate::{
+    commands::chain::{
+        args::{init::InitArgsFinal, join::JoinArgs},
+        build_transactions::{REGISTER_CHAIN_TXNS_FILE_DST, REGISTER_CHAIN_TXNS_FILE_SRC},
+        init::{init_with_registration, ChainRegistration},
+        register_chain::register_chain,
+    },
+    consts::DEFAULT_UNSIGNED_TRANSACTIONS_DIR,
+    messages::{
+        msg_join_searching_new_chain_event, msg_join_send_transactions,
+        MSG_BUILDING_CHAIN_REGISTRATION_TXNS_SPINNER, MSG_CHAIN_INITIALIZED,
+        MSG_CHAIN_NOT_FOUND_ERR, MSG_JOIN_ALREADY_REGISTERED, MSG_JOIN_BRIDGEHUB_NOT_DEPLOYED_ERR,
+        MSG_JOIN_NEW_CHAIN_EVENT_NOT_FOUND_ERR, MSG_JOIN_TIMEOUT_ERR, MSG_JOIN_WAITING_SPINNER,
+        MSG_SELECTED_CONFIG,
+    },
+};
+
+const JOIN_SUBDIR: &str = "join";
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// Maximum number of L1 blocks covered by a single `eth_getLogs` request.
+const LOGS_PAGE_SIZE: u64 = 10_000;
+
+lazy_static! {
+    static ref BRIDGEHUB: BaseContract = BaseContract::from(
+        parse_abi(&[
+            "function owner() external view returns (address)",
+            "function getHyperchain(uint256 _chainId) external view returns (address)",
+            "event NewChain(uint256 indexed chainId, address stateTransitionManager, address indexed chainGovernance)",
+        ])
+        .unwrap(),
+    );
+    static ref GETTERS_FACET: BaseContract = BaseContract::from(
+        parse_abi(&[
+            "function getAdmin() external view returns (address)",
+            "function getPendingAdmin() external view returns (address)",
+        ])
+        .unwrap(),
+    );
+}
+
+/// Registration of the chain by the owner of an existing ecosystem.
+pub(crate) struct ExternalRegistration {
+    /// Owner of the Bridgehub, sender of the registration transactions.
+    pub ecosystem_owner: Option<Address>,
+    /// Directory to write the registration transactions to.
+    pub out: PathBuf,
+    pub timeout: Duration,
+    /// L1 block the Bridgehub was deployed at; found from L1 if not set.
+    pub bridgehub_deployment_block: Option<u64>,
+}
+
+pub(crate) async fn run(args: JoinArgs, shell: &Shell) -> anyhow::Result<()> {
+    let ecosystem_config = EcosystemConfig::from_file(shell)?;
+    let chain_config = ecosystem_config
+        .load_current_chain()
+        .context(MSG_CHAIN_NOT_FOUND_ERR)?;
+    let init_args = args.init_args.fill_values_with_prompt(&chain_config);
+    let registration = ExternalRegistration {
+        ecosystem_owner: args.ecosystem_owner,
+        out: args.out.unwrap_or_else(|| {
+            PathBuf::from(DEFAULT_UNSIGNED_TRANSACTIONS_DIR)
+                .join(JOIN_SUBDIR)
+                .join(&chain_config.name)
+        }),
+        timeout: Duration::from_secs(args.timeout),
+        bridgehub_deployment_block: args.bridgehub_deployment_block,
+    };
+
+    logger::note(MSG_SELECTED_CONFIG, logger::object_to_string(&chain_config));
+    git::submodule_update(shell, ecosystem_config.link_to_code.clone())?;
+
+    init_with_registration(
+        &init_args,
+        shell,
+        &ecosystem_config,
+        &chain_config,
+        ChainRegistration::External(registration),
+    )
+    .await?;
+
+    logger::success(MSG_CHAIN_INITIALIZED);
+    Ok(())
+}
+
+/// Builds the registration transactions for the ecosystem owner, waits until they are executed
+/// and fills the chain contracts from the registration events.
+pub(crate) async fn register_chain_externally(
+    shell: &Shell,
+    init_args: &InitArgsFinal,
+    ecosystem_config: &EcosystemConfig,
+    chain_config: &ChainConfig,
+    contracts: &mut ContractsConfig,
+    registration: &ExternalRegistration,
+) -> anyhow::Result<()> {
+    let provider = Provider::<Http>::try_from(init_args.l1_rpc_url.as_str())?;
+    let bridgehub = contracts.ecosystem_contracts.bridgehub_proxy_addr;
+    let chain_id = U256::from(chain_config.chain_id.as_u64());
+    // Registration events are searched from the current block, unless the chain was already
+    // registered by a previous run.
+    let mut from_block = provider.get_block_number().await?.as_u64();
+
+    if get_hyperchain(&provider, bridgehub, chain_id).await? == Address::zero() {
+        let owner = match registration.ecosystem_owner {
+            Some(owner) => owner,
+            None => call(&provider, &BRIDGEHUB, bridgehub, "owner", ()).await?,
+        };
+
+        let spinner = Spinner::new(MSG_BUILDING_CHAIN_REGISTRATION_TXNS_SPINNER);
+        register_chain(
+            shell,
+            init_args.forge_args.clone(),
+            ecosystem_config,
+            chain_config,
+            contracts,
+            init_args.l1_rpc_url.clone(),
+            Some(owner.encode_hex_upper()),
+            false,
+        )
+        .await?;
+        shell.create_dir(&registration.out)?;
+        let transactions_path = registration.out.join(REGISTER_CHAIN_TXNS_FILE_DST);
+        shell.copy_file(
+            ecosystem_config
+                .link_to_code
+                .join(REGISTER_CHAIN_TXNS_FILE_SRC),
+            &transactions_path,
+        )?;
+        spinner.finish();
+        logger::info(msg_join_send_transactions(&transactions_path, owner));
+
+        let spinner = Spinner::new(MSG_JOIN_WAITING_SPINNER);
+        wait_for_registration(&provider, bridgehub, chain_id, registration.timeout).await?;
+        spinner.finish();
+    } else {
+        logger::info(MSG_JOIN_ALREADY_REGISTERED);
+        from_block = match registration.bridgehub_deployment_block {
+            Some(block) => block,
+            None => find_deployment_block(&provider, bridgehub).await?,
+        };
+    }
+
+    // Addresses of the dry run may differ from the actual ones, so they are recovered from L1.
+    let diamond_proxy_addr = get_hyperchain(&provider, bridgehub, chain_id).await?;
+    let new_chain_event = BRIDGEHUB.abi().event("NewChain")?;
+    let filter = Filter::new()
+        .address(bridgehub)
+        .topic0(new_chain_event.signature())
+        .topic1(H256::from_uint(&chain_id));
+    logger::info(msg_join_searching_new_chain_event(from_block));
+    let log = find_last_log(&provider, filter, from_block)
+        .await?
+        .context(MSG_JOIN_NEW_CHAIN_EVENT_NOT_FOUND_ERR)?;
+    let governance_addr = Address::from(log.topics[2]);
+
+    let pending_admin: Address = call(
+        &provider,
+        &GETTERS_FACET,
+        diamond_proxy_addr,
+        "getPendingAdmin",
+        (),
+    )
+    .await?;
+    let chain_admin_addr = if pending_admin != Address::zero() {
+        pending_admin
+    } else {
+        call(
+            &provider,
+            &GETTERS_FACET,
+            diamond_proxy_addr,
+            "getAdmin",
+            (),
+        )
+        .await?
+    };
+
+    contracts.set_chain_contracts(&RegisterChainOutput {
+        diamond_proxy_addr,
+        governance_addr,
+        chain_admin_addr,
+    });
+    Ok(())
+}
+
+async fn wait_for_registration(
+    provider: &Provider<Http>,
+    bridgehub: Address,
+    chain_id: U256,
+    timeout: Duration,
+) -> anyhow::Result<()> {
+    tokio::time::timeout(timeout, async {
+        loop {
+            if get_hyperchain(provider, bridgehub, chain_id).await? != Address::zero() {
+                return anyhow::Ok(());
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    })
+    .await
+    .context(MSG_JOIN_TIMEOUT_ERR)?
+}
+
+/// Finds the first L1 block with the contract code deployed at `address` using binary search.
+async fn find_deployment_block(provider: &Provider<Http>, address: Address) -> anyhow::Result<u64> {
+    let has_code = |block: u64| async move {
+        let block = BlockId::Number(BlockNumber::Number(block.into()));
+        let code = provider.get_code(address, Some(block)).await?;
+        anyhow::Ok(!code.is_empty())
+    };
+
+    let (mut low, mut high) = (0, provider.get_block_number().await?.as_u64());
+    anyhow::ensure!(has_code(high).await?, MSG_JOIN_BRIDGEHUB_NOT_DEPLOYED_ERR);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if has_code(mid).await? {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    Ok(low)
+}
+
+/// Returns the last log matching `filter` emitted at or after `from_block`. Logs are queried in pages
+/// of [`LOGS_PAGE_SIZE`] blocks starting from the latest block, so that RPC providers limiting
+/// the block range of `eth_getLogs` are supported.
+async fn find_last_log(
+    provider: &Provider<Http>,
+    filter: Filter,
+    from_block: u64,
+) -> anyhow::Result<Option<Log>> {
+    let mut to_block = provider.get_block_number().await?.as_u64();
+    while to_block >= from_block {
+        let page_start = to_block.saturating_sub(LOGS_PAGE_SIZE - 1).max(from_block);
+        let page_filter = filter.clone().from_block(page_start).to_block(to_block);
+        if let Some(log) = provider.get_logs(&page_filter).await?.into_iter().last() {
+            return Ok(Some(log));
+        }
+        if page_start == 0 {
+            break;
+        }
+        to_block = page_start - 1;
+    }
+    Ok(None)
+}
+
+async fn get_hyperchain(
+    provider: &Provider<Http>,
+    bridgehub: Address,
+    chain_id: U256,
+) -> anyhow::Result<Address> {
+    call(provider, &BRIDGEHUB, bridgehub, "getHyperchain", chain_id).await
+}
+
+async fn call<T: ethers::abi::Tokenize, R: ethers::abi::Detokenize>(
+    provider: &Provider<Http>,
+    contract: &BaseContract,
+    address: Address,
+    function: &str,
+    args: T,
+) -> anyhow::Result<R> {
+    let tx = TransactionRequest::new()
+        .to(address)
+        .data(contract.encode(function, args)?);
+    let output = provider.call(&tx.into(), None).await?;
+    Ok(contract.decode_output(function, output)?)
+}
@@ -0,0 +1,217 @@
+use anyhow::Context;
+use common::logger;
+use config::{traits::SaveConfigWithBasePath, EcosystemConfig, SecretsConfig};
+use ethers::{
+    abi::parse_abi,
+    contract::BaseContract,
+    prelude::{Http, Provider},
+    providers::Middleware,
+    types::{Address, TransactionRequest, U256},
+};
+use lazy_static::lazy_static;
+use sqlx::{Connection, PgConnection};
+use url::Url;
+use xshell::Shell;
+use zksync_basic_types::{settlement::SettlementMode, url::SensitiveUrl};
+use zksync_config::configs::eth_sender::SenderConfig;
+
+use crate::{
+    commands::chain::args::migrate_to_gateway::{GatewayMigrationStage, MigrateToGatewayArgs},
+    messages::{
+        msg_gateway_migration_batches_committed_after_pause, msg_gateway_migration_finalized,
+        msg_gateway_migration_inflight_txs, msg_gateway_migration_l1_state_mismatch,
+        msg_gateway_migration_unexecuted_batches, MSG_CHAIN_NOT_INITIALIZED,
+        MSG_DATABASE_MUST_BE_PRESENTED, MSG_ETH_CONFIG_MISSING_ERR,
+        MSG_GATEWAY_MIGRATION_ALREADY_FINALIZED, MSG_GATEWAY_MIGRATION_NOT_PAUSED,
+        MSG_GATEWAY_MIGRATION_NOT_PREPARED, MSG_GATEWAY_MIGRATION_PAUSED,
+        MSG_GATEWAY_MIGRATION_PREPARED, MSG_GATEWAY_RPC_URL_MISSING_ERR,
+        MSG_L1_SECRETS_MUST_BE_PRESENTED,
+    },
+};
+
+lazy_static! {
+    static ref GETTERS_FACET: BaseContract = BaseContract::from(
+        parse_abi(&[
+            "function getTotalBatchesCommitted() external view returns (uint256)",
+            "function getTotalBatchesExecuted() external view returns (uint256)",
+        ])
+        .unwrap(),
+    );
+}
+
+/// Runs a stage of the chain settlement migration from L1 to the Gateway. The server has to be restarted
+/// after each stage for the changed configs to take effect.
+pub async fn run(args: MigrateToGatewayArgs, shell: &Shell) -> anyhow::Result<()> {
+    let ecosystem_config = EcosystemConfig::from_file(shell)?;
+    let mut chain_config = ecosystem_config
+        .load_current_chain()
+        .context(MSG_CHAIN_NOT_INITIALIZED)?;
+    let mut general_config = chain_config.get_general_config()?;
+    let mut secrets = chain_config.get_secrets_config()?;
+    let db_url = secrets
+        .database
+        .as_ref()
+        .and_then(|database| database.server_url.as_ref())
+        .context(MSG_DATABASE_MUST_BE_PRESENTED)?
+        .expose_url()
+        .clone();
+    let mut connection = PgConnection::connect(db_url.as_str()).await?;
+
+    let eth = general_config
+        .eth
+        .as_mut()
+        .context(MSG_ETH_CONFIG_MISSING_ERR)?;
+    let sender = eth.sender.as_mut().context(MSG_ETH_CONFIG_MISSING_ERR)?;
+    let settlement_mode = eth
+        .gas_adjuster
+        .as_ref()
+        .context(MSG_ETH_CONFIG_MISSING_ERR)?
+        .settlement_mode;
+    anyhow::ensure!(
+        settlement_mode != SettlementMode::Gateway,
+        MSG_GATEWAY_MIGRATION_ALREADY_FINALIZED
+    );
+
+    match args.stage {
+        GatewayMigrationStage::Prepare => {
+            set_aggregation_flags(sender, false, true);
+            chain_config.save_general_config(&general_config)?;
+            logger::success(MSG_GATEWAY_MIGRATION_PREPARED);
+        }
+        GatewayMigrationStage::Pause => {
+            anyhow::ensure!(
+                sender.tx_aggregation_only_prove_and_execute,
+                MSG_GATEWAY_MIGRATION_NOT_PREPARED
+            );
+            ensure_all_batches_executed(&mut connection).await?;
+            let last_l1_batch = get_last_committed_batch(&mut connection).await?;
+
+            set_aggregation_flags(sender, true, true);
+            chain_config.save_general_config(&general_config)?;
+            chain_config.gateway_migration_l1_batch = Some(last_l1_batch);
+            let chain_path = ecosystem_config.chains.join(&chain_config.name);
+            chain_config.save_with_base_path(shell, chain_path)?;
+            logger::success(MSG_GATEWAY_MIGRATION_PAUSED);
+        }
+        GatewayMigrationStage::Finalize => {
+            let gateway_rpc_url = args
+                .gateway_rpc_url
+                .context(MSG_GATEWAY_RPC_URL_MISSING_ERR)?;
+            let last_l1_batch = chain_config
+                .gateway_migration_l1_batch
+                .filter(|_| {
+                    sender.tx_aggregation_paused && sender.tx_aggregation_only_prove_and_execute
+                })
+                .context(MSG_GATEWAY_MIGRATION_NOT_PAUSED)?;
+
+            // Batches must not have been committed after the `pause` stage, and all of them must be executed.
+            ensure_all_batches_executed(&mut connection).await?;
+            let last_committed_batch = get_last_committed_batch(&mut connection).await?;
+            anyhow::ensure!(
+                last_committed_batch == last_l1_batch,
+                msg_gateway_migration_batches_committed_after_pause(
+                    last_l1_batch,
+                    last_committed_batch
+                )
+            );
+            // Same condition as checked by the eth sender before it starts sending transactions to the Gateway.
+            let inflight_txs: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM eth_txs \
+                 WHERE confirmed_eth_tx_history_id IS NULL AND is_gateway = FALSE",
+            )
+            .fetch_one(&mut connection)
+            .await?;
+            anyhow::ensure!(
+                inflight_txs == 0,
+                msg_gateway_migration_inflight_txs(inflight_txs)
+            );
+
+            // Postgres state could be stale or diverge from L1, so check the diamond proxy as well.
+            let l1_rpc_url = secrets
+                .l1
+                .as_ref()
+                .context(MSG_L1_SECRETS_MUST_BE_PRESENTED)?
+                .l1_rpc_url
+                .expose_str()
+                .to_string();
+            let diamond_proxy = chain_config.get_contracts_config()?.l1.diamond_proxy_addr;
+            let committed_on_l1 =
+                get_total_batches(&l1_rpc_url, diamond_proxy, "getTotalBatchesCommitted").await?;
+            let executed_on_l1 =
+                get_total_batches(&l1_rpc_url, diamond_proxy, "getTotalBatchesExecuted").await?;
+            anyhow::ensure!(
+                committed_on_l1 == U256::from(last_l1_batch)
+                    && executed_on_l1 == U256::from(last_l1_batch),
+                msg_gateway_migration_l1_state_mismatch(
+                    last_l1_batch,
+                    committed_on_l1,
+                    executed_on_l1
+                )
+            );
+
+            set_aggregation_flags(sender, false, false);
+            eth.gas_adjuster
+                .as_mut()
+                .context(MSG_ETH_CONFIG_MISSING_ERR)?
+                .settlement_mode = SettlementMode::Gateway;
+            chain_config.save_general_config(&general_config)?;
+            set_gateway_rpc_url(&mut secrets, gateway_rpc_url)?;
+            secrets.save_with_base_path(shell, &chain_config.configs)?;
+
+            logger::success(msg_gateway_migration_finalized(last_l1_batch));
+        }
+    }
+
+    let _ = connection.close().await;
+    Ok(())
+}
+
+async fn ensure_all_batches_executed(connection: &mut PgConnection) -> anyhow::Result<()> {
+    let unexecuted_batches: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM l1_batches \
+         WHERE eth_commit_tx_id IS NOT NULL AND eth_execute_tx_id IS NULL",
+    )
+    .fetch_one(connection)
+    .await?;
+    anyhow::ensure!(
+        unexecuted_batches == 0,
+        msg_gateway_migration_unexecuted_batches(unexecuted_batches)
+    );
+    Ok(())
+}
+
+/// Returns the last L1 batch committed to L1, or the genesis batch if no batches were committed.
+async fn get_last_committed_batch(connection: &mut PgConnection) -> anyhow::Result<u32> {
+    let last_committed_batch: Option<i64> =
+        sqlx::query_scalar("SELECT MAX(number) FROM l1_batches WHERE eth_commit_tx_id IS NOT NULL")
+            .fetch_one(connection)
+            .await?;
+    Ok(last_committed_batch.map_or(0, |number| number as u32))
+}
+
+async fn get_total_batches(
+    l1_rpc_url: &str,
+    diamond_proxy: Address,
+    getter: &str,
+) -> anyhow::Result<U256> {
+    let provider = Provider::<Http>::try_from(l1_rpc_url)?;
+    let tx = TransactionRequest::new()
+        .to(diamond_proxy)
+        .data(GETTERS_FACET.encode(getter, ())?);
+    let output = provider.call(&tx.into(), None).await?;
+    Ok(GETTERS_FACET.decode_output(getter, output)?)
+}
+
+fn set_aggregation_flags(sender: &mut SenderConfig, paused: bool, only_prove_and_execute: bool) {
+    sender.tx_aggregation_paused = paused;
+    sender.tx_aggregation_only_prove_and_execute = only_prove_and_execute;
+}
+
+fn set_gateway_rpc_url(secrets: &mut SecretsConfig, gateway_rpc_url: Url) -> anyhow::Result<()> {
+    secrets
+        .l1
+        .as_mut()
+        .context(MSG_L1_SECRETS_MUST_BE_PRESENTED)?
+        .gateway_rpc_url = Some(SensitiveUrl::from(gateway_rpc_url));
+    Ok(())
+}
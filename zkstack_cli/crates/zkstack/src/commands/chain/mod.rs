@@ -1,13 +1,20 @@
 use ::common::forge::ForgeScriptArgs;
-use args::build_transactions::BuildTransactionsArgs;
 pub(crate) use args::create::ChainCreateArgsFinal;
+use args::{
+    build_transactions::BuildTransactionsArgs,
+    deploy_l2_contracts::{DeployCustomL2ContractsArgs, DeployL2ContractsArgs},
+    genesis::{ExportGenesisArgs, ImportGenesisArgs},
+    join::JoinArgs,
+    migrate_to_gateway::MigrateToGatewayArgs,
+    upgrade::ChainUpgradeArgs,
+};
 use clap::{command, Subcommand};
 pub(crate) use create::create_chain_inner;
 use xshell::Shell;
 
 use crate::commands::chain::{
     args::create::ChainCreateArgs, deploy_l2_contracts::Deploy2ContractsOption,
-    genesis::GenesisCommand, init::ChainInitCommand,
+    genesis::GenesisCommand, init::ChainInitCommand, snapshot::SnapshotCommands,
 };
 
 mod accept_chain_ownership;
@@ -20,9 +27,14 @@ pub mod deploy_paymaster;
 mod enable_evm_emulator;
 pub mod genesis;
 pub mod init;
+mod join;
+mod migrate_to_gateway;
 pub mod register_chain;
 mod set_token_multiplier_setter;
 mod setup_legacy_bridge;
+pub mod snapshot;
+mod upgrade;
+mod verify_l2_contracts;
 
 #[derive(Subcommand, Debug)]
 pub enum ChainCommands {
@@ -34,15 +46,23 @@ pub enum ChainCommands {
     Init(Box<ChainInitCommand>),
     /// Run server genesis
     Genesis(GenesisCommand),
+    /// Export the genesis state of the chain into a portable file
+    ExportGenesis(ExportGenesisArgs),
+    /// Import a genesis state exported by `export-genesis` into the chain
+    ImportGenesis(ImportGenesisArgs),
     /// Register a new chain on L1 (executed by L1 governor).
     /// This command deploys and configures Governance, ChainAdmin, and DiamondProxy contracts,
     /// registers chain with BridgeHub and sets pending admin for DiamondProxy.
     /// Note: After completion, L2 governor can accept ownership by running `accept-chain-ownership`
     #[command(alias = "register")]
     RegisterChain(ForgeScriptArgs),
+    /// Register the chain on an existing ecosystem owned by someone else and initialize it.
+    /// Registration transactions are written for the ecosystem owner to execute; once the chain
+    /// appears on the BridgeHub, initialization continues with the registered contracts.
+    Join(JoinArgs),
     /// Deploy all L2 contracts (executed by L1 governor).
     #[command(alias = "l2")]
-    DeployL2Contracts(ForgeScriptArgs),
+    DeployL2Contracts(DeployL2ContractsArgs),
     /// Accept ownership of L2 chain (executed by L2 governor).
     /// This command should be run after `register-chain` to accept ownership of newly created
     /// DiamondProxy contract.
@@ -66,10 +86,21 @@ pub enum ChainCommands {
     /// Deploy paymaster smart contract
     #[command(alias = "paymaster")]
     DeployPaymaster(ForgeScriptArgs),
+    /// Deploy custom L2 contracts with a user-supplied forge script (executed by L1 governor)
+    #[command(alias = "custom")]
+    DeployCustomL2Contracts(DeployCustomL2ContractsArgs),
     /// Update Token Multiplier Setter address on L1
     UpdateTokenMultiplierSetter(ForgeScriptArgs),
     /// Enable EVM emulation on chain (Not supported yet)
     EnableEvmEmulator(ForgeScriptArgs),
+    /// Create, list and restore snapshots of the chain state
+    #[command(subcommand)]
+    Snapshot(SnapshotCommands),
+    /// Upgrade the chain to a new protocol version (executed by L1 and L2 governors)
+    Upgrade(ChainUpgradeArgs),
+    /// Migrate the chain settlement from L1 to the Gateway. Each stage updates the chain configs
+    /// and has to be followed by a server restart
+    MigrateToGateway(MigrateToGatewayArgs),
 }
 
 pub(crate) async fn run(shell: &Shell, args: ChainCommands) -> anyhow::Result<()> {
@@ -78,9 +109,16 @@ pub(crate) async fn run(shell: &Shell, args: ChainCommands) -> anyhow::Result<()
         ChainCommands::Init(args) => init::run(*args, shell).await,
         ChainCommands::BuildTransactions(args) => build_transactions::run(args, shell).await,
         ChainCommands::Genesis(args) => genesis::run(args, shell).await,
+        ChainCommands::ExportGenesis(args) => genesis::export::export(args, shell).await,
+        ChainCommands::ImportGenesis(args) => genesis::export::import(args, shell).await,
         ChainCommands::RegisterChain(args) => register_chain::run(args, shell).await,
+        ChainCommands::Join(args) => join::run(args, shell).await,
         ChainCommands::DeployL2Contracts(args) => {
-            deploy_l2_contracts::run(args, shell, Deploy2ContractsOption::All).await
+            let option = Deploy2ContractsOption::All {
+                force: args.force,
+                verify: args.verify_with_contract_verifier,
+            };
+            deploy_l2_contracts::run(args.forge_args, shell, option).await
         }
         ChainCommands::AcceptChainOwnership(args) => accept_chain_ownership::run(args, shell).await,
         ChainCommands::DeployConsensusRegistry(args) => {
@@ -99,9 +137,15 @@ pub(crate) async fn run(shell: &Shell, args: ChainCommands) -> anyhow::Result<()
             deploy_l2_contracts::run(args, shell, Deploy2ContractsOption::InitiailizeBridges).await
         }
         ChainCommands::DeployPaymaster(args) => deploy_paymaster::run(args, shell).await,
+        ChainCommands::DeployCustomL2Contracts(args) => {
+            deploy_l2_contracts::run_custom(args, shell).await
+        }
         ChainCommands::UpdateTokenMultiplierSetter(args) => {
             set_token_multiplier_setter::run(args, shell).await
         }
         ChainCommands::EnableEvmEmulator(args) => enable_evm_emulator::run(args, shell).await,
+        ChainCommands::Snapshot(args) => snapshot::run(shell, args).await,
+        ChainCommands::Upgrade(args) => upgrade::run(args, shell).await,
+        ChainCommands::MigrateToGateway(args) => migrate_to_gateway::run(args, shell).await,
     }
 }
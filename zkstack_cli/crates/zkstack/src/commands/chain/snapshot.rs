@@ -0,0 +1,171 @@
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+use common::{cmd::Cmd, logger, PromptConfirm};
+use config::{
+    traits::{ConfigWithL2RpcUrl, ReadConfigWithBasePath, SaveConfigWithBasePath},
+    ChainConfig, EcosystemConfig, GeneralConfig,
+};
+use ethers::{
+    providers::{Http, Provider},
+    types::H256,
+};
+use serde::Deserialize;
+use xshell::{cmd, Shell};
+use zksync_basic_types::L1BatchNumber;
+use zksync_config::configs::snapshot_recovery::{
+    PostgresRecoveryConfig, SnapshotRecoveryConfig, TreeRecoveryConfig,
+};
+
+use crate::{
+    commands::external_node,
+    messages::{
+        msg_snapshot_info, msg_snapshot_restore_prepared, MSG_CHAIN_NOT_INITIALIZED,
+        MSG_EXTERNAL_NODE_CONFIG_NOT_INITIALIZED, MSG_FAILED_TO_RUN_SNAPSHOT_CREATOR_ERR,
+        MSG_NO_SNAPSHOTS_FOUND, MSG_RUNNING_SNAPSHOT_CREATOR,
+        MSG_SNAPSHOT_CREATOR_CONFIG_MISSING_ERR, MSG_SNAPSHOT_RESTORE_ABORTED,
+        MSG_SNAPSHOT_RESTORE_DROP_DB_PROMPT, MSG_SNAPSHOT_RESTORE_FORCE_HELP,
+        MSG_SNAPSHOT_RESTORE_L1_BATCH_HELP,
+    },
+};
+
+#[derive(Subcommand, Debug)]
+pub enum SnapshotCommands {
+    /// Create a snapshot of the chain state and upload it to the configured object store
+    Create,
+    /// List the snapshots available on the main node
+    List,
+    /// Prepare the external node to be initialized from a snapshot on the next run
+    Restore(RestoreSnapshotArgs),
+}
+
+#[derive(Debug, Parser)]
+pub struct RestoreSnapshotArgs {
+    #[clap(long, help = MSG_SNAPSHOT_RESTORE_L1_BATCH_HELP)]
+    pub l1_batch: Option<u32>,
+    #[clap(long, help = MSG_SNAPSHOT_RESTORE_FORCE_HELP)]
+    pub force: bool,
+}
+
+pub(crate) async fn run(shell: &Shell, args: SnapshotCommands) -> anyhow::Result<()> {
+    let ecosystem = EcosystemConfig::from_file(shell)?;
+    let chain = ecosystem
+        .load_current_chain()
+        .context(MSG_CHAIN_NOT_INITIALIZED)?;
+
+    match args {
+        SnapshotCommands::Create => create(shell, &chain),
+        SnapshotCommands::List => list(&chain).await,
+        SnapshotCommands::Restore(args) => restore(shell, &chain, args).await,
+    }
+}
+
+pub(crate) fn create(shell: &Shell, chain: &ChainConfig) -> anyhow::Result<()> {
+    let config_path = chain.path_to_general_config();
+    let secrets_path = chain.path_to_secrets_config();
+
+    logger::info(MSG_RUNNING_SNAPSHOT_CREATOR);
+
+    let _dir_guard = shell.push_dir(&chain.link_to_code);
+    let mut cmd = Cmd::new(cmd!(shell, "cargo run --bin snapshots_creator --release -- --config-path={config_path} --secrets-path={secrets_path}"))
+        .env("RUST_LOG", "snapshots_creator=debug");
+
+    cmd = cmd.with_force_run();
+    cmd.run().context(MSG_FAILED_TO_RUN_SNAPSHOT_CREATOR_ERR)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AllSnapshots {
+    snapshots_l1_batch_numbers: Vec<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SnapshotHeader {
+    l1_batch_number: u32,
+    #[serde(rename = "miniblockNumber")]
+    l2_block_number: u32,
+    storage_logs_chunks: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct L1BatchDetails {
+    root_hash: Option<H256>,
+}
+
+async fn list(chain: &ChainConfig) -> anyhow::Result<()> {
+    let l2_rpc_url = chain.get_general_config()?.get_l2_rpc_url()?;
+    let provider = Provider::<Http>::try_from(l2_rpc_url.as_str())?;
+
+    let all_snapshots: AllSnapshots = provider
+        .request("snapshots_getAllSnapshots", ())
+        .await
+        .context("snapshots_getAllSnapshots")?;
+    if all_snapshots.snapshots_l1_batch_numbers.is_empty() {
+        logger::info(MSG_NO_SNAPSHOTS_FOUND);
+        return Ok(());
+    }
+
+    for l1_batch_number in all_snapshots.snapshots_l1_batch_numbers {
+        let header: Option<SnapshotHeader> = provider
+            .request("snapshots_getSnapshot", [l1_batch_number])
+            .await
+            .context("snapshots_getSnapshot")?;
+        let Some(header) = header else {
+            continue;
+        };
+        let details: Option<L1BatchDetails> = provider
+            .request("zks_getL1BatchDetails", [header.l1_batch_number])
+            .await
+            .context("zks_getL1BatchDetails")?;
+        let root_hash = details.and_then(|details| details.root_hash);
+        logger::info(msg_snapshot_info(
+            header.l1_batch_number,
+            header.l2_block_number,
+            header.storage_logs_chunks.len(),
+            root_hash,
+        ));
+    }
+    Ok(())
+}
+
+async fn restore(
+    shell: &Shell,
+    chain: &ChainConfig,
+    args: RestoreSnapshotArgs,
+) -> anyhow::Result<()> {
+    let en_configs_path = chain
+        .external_node_config_path
+        .clone()
+        .context(MSG_EXTERNAL_NODE_CONFIG_NOT_INITIALIZED)?;
+    // Snapshots are read from the same object store the main node uploads them to.
+    let object_store = chain
+        .get_general_config()?
+        .snapshot_creator
+        .and_then(|config| config.object_store)
+        .context(MSG_SNAPSHOT_CREATOR_CONFIG_MISSING_ERR)?;
+
+    // Recovery is only possible into an empty database, so the existing one has to be dropped.
+    if !args.force && !PromptConfirm::new(MSG_SNAPSHOT_RESTORE_DROP_DB_PROMPT).ask() {
+        logger::info(MSG_SNAPSHOT_RESTORE_ABORTED);
+        return Ok(());
+    }
+
+    let mut general_en = GeneralConfig::read_with_base_path(shell, &en_configs_path)?;
+    general_en.snapshot_recovery = Some(SnapshotRecoveryConfig {
+        enabled: true,
+        l1_batch: args.l1_batch.map(L1BatchNumber),
+        manifest_hash: None,
+        peer_url: None,
+        drop_storage_key_preimages: false,
+        tree: TreeRecoveryConfig::default(),
+        postgres: PostgresRecoveryConfig::default(),
+        object_store: Some(object_store),
+    });
+    general_en.save_with_base_path(shell, &en_configs_path)?;
+
+    external_node::init::init(shell, chain).await?;
+    logger::outro(msg_snapshot_restore_prepared(args.l1_batch));
+    Ok(())
+}
@@ -0,0 +1,334 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use common::{ethereum::create_ethers_client, logger, spinner::Spinner};
+use config::{traits::ReadConfig, EcosystemConfig, ProtocolUpgradeDescription};
+use ethers::{
+    abi::{parse_abi, Token},
+    contract::BaseContract,
+    core::k256::ecdsa::SigningKey,
+    middleware::SignerMiddleware,
+    prelude::{Http, Provider, Wallet},
+    providers::Middleware,
+    types::{Address, Bytes, TransactionRequest, H256, U256},
+};
+use lazy_static::lazy_static;
+use sqlx::{Connection, PgConnection};
+use types::ProtocolSemanticVersion;
+use url::Url;
+use xshell::Shell;
+
+use crate::{
+    commands::chain::args::upgrade::ChainUpgradeArgs,
+    messages::{
+        msg_chain_upgraded, msg_upgrade_not_active_on_l1, msg_upgrade_not_picked_up_by_node,
+        msg_upgrade_tx_failed, msg_upgrade_version, msg_upgrade_version_not_newer,
+        MSG_CHAIN_GOVERNOR_PRIVATE_KEY_MISSING_ERR, MSG_CHAIN_NOT_INITIALIZED,
+        MSG_DATABASE_MUST_BE_PRESENTED, MSG_ECOSYSTEM_GOVERNOR_PRIVATE_KEY_MISSING_ERR,
+        MSG_L1_SECRETS_MUST_BE_PRESENTED, MSG_UPGRADE_EXECUTING_SPINNER,
+        MSG_UPGRADE_SCHEDULING_SPINNER, MSG_UPGRADE_WAITING_FOR_NODE_SPINNER,
+    },
+};
+
+type Client = SignerMiddleware<Provider<Http>, Wallet<SigningKey>>;
+
+const DIAMOND_CUT_DATA: &str = "((address,uint8,bool,bytes4[])[],address,bytes)";
+
+lazy_static! {
+    static ref DEFAULT_UPGRADE: BaseContract = BaseContract::from(
+        parse_abi(&[
+            "function upgrade(((uint256,uint256,uint256,uint256,uint256,uint256,uint256,uint256,uint256,uint256,uint256[4],bytes,bytes,uint256[],bytes,bytes),bytes[],bytes32,bytes32,address,(bytes32,bytes32,bytes32),bytes,bytes,uint256,uint256) _proposedUpgrade) external returns (bytes32)",
+        ])
+        .unwrap(),
+    );
+    static ref STATE_TRANSITION_MANAGER: BaseContract = BaseContract::from(
+        parse_abi(&[format!(
+            "function setNewVersionUpgrade({DIAMOND_CUT_DATA} _cutData, uint256 _oldProtocolVersion, uint256 _oldProtocolVersionDeadline, uint256 _newProtocolVersion) external"
+        )
+        .as_str()])
+        .unwrap(),
+    );
+    static ref ADMIN_FACET: BaseContract = BaseContract::from(
+        parse_abi(&[
+            format!(
+                "function upgradeChainFromVersion(uint256 _protocolVersion, {DIAMOND_CUT_DATA} _cutData) external"
+            )
+            .as_str(),
+            "function getProtocolVersion() external view returns (uint256)",
+        ])
+        .unwrap(),
+    );
+    static ref GOVERNANCE: BaseContract = BaseContract::from(
+        parse_abi(&[
+            "function scheduleTransparent(((address,uint256,bytes)[],bytes32,bytes32) _operation, uint256 _delay) external",
+            "function execute(((address,uint256,bytes)[],bytes32,bytes32) _operation) external payable",
+        ])
+        .unwrap(),
+    );
+    static ref CHAIN_ADMIN: BaseContract = BaseContract::from(
+        parse_abi(&[
+            "function setUpgradeTimestamp(uint256 _protocolVersion, uint256 _upgradeTimestamp) external",
+            "function multicall((address,uint256,bytes)[] _calls, bool _requireSuccess) external payable",
+        ])
+        .unwrap(),
+    );
+}
+
+pub async fn run(args: ChainUpgradeArgs, shell: &Shell) -> anyhow::Result<()> {
+    let ecosystem_config = EcosystemConfig::from_file(shell)?;
+    let chain_config = ecosystem_config
+        .load_current_chain()
+        .context(MSG_CHAIN_NOT_INITIALIZED)?;
+    let description = ProtocolUpgradeDescription::read(shell, &args.description)?;
+
+    let ecosystem_contracts = ecosystem_config.get_contracts_config()?;
+    let chain_contracts = chain_config.get_contracts_config()?;
+    let secrets = chain_config.get_secrets_config()?;
+    let l1_rpc_url = secrets
+        .l1
+        .context(MSG_L1_SECRETS_MUST_BE_PRESENTED)?
+        .l1_rpc_url
+        .expose_str()
+        .to_string();
+    let l1_chain_id = ecosystem_config.l1_network.chain_id();
+    let diamond_proxy = chain_contracts.l1.diamond_proxy_addr;
+
+    let old_version = get_protocol_version(&l1_rpc_url, diamond_proxy).await?;
+    let new_version = description.protocol_version;
+    anyhow::ensure!(
+        new_version > old_version,
+        msg_upgrade_version_not_newer(&old_version, &new_version)
+    );
+    logger::info(msg_upgrade_version(&old_version, &new_version));
+
+    let upgrade_address = description
+        .upgrade_address
+        .unwrap_or(ecosystem_contracts.l1.default_upgrade_addr);
+    let cut_data = diamond_cut_data(upgrade_address, &description)?;
+
+    let spinner = Spinner::new(MSG_UPGRADE_SCHEDULING_SPINNER);
+    let ecosystem_governor = ecosystem_config
+        .get_wallets()?
+        .governor
+        .local_signer()
+        .context(MSG_ECOSYSTEM_GOVERNOR_PRIVATE_KEY_MISSING_ERR)?;
+    let client = create_ethers_client(ecosystem_governor, l1_rpc_url.clone(), Some(l1_chain_id))?;
+    let set_new_version_upgrade = encode(
+        &STATE_TRANSITION_MANAGER,
+        "setNewVersionUpgrade",
+        &[
+            cut_data.clone(),
+            Token::Uint(old_version.pack()),
+            // The old protocol version has no deadline
+            Token::Uint(U256::MAX),
+            Token::Uint(new_version.pack()),
+        ],
+    )?;
+    let operation = governance_operation(
+        ecosystem_contracts
+            .ecosystem_contracts
+            .state_transition_proxy_addr,
+        set_new_version_upgrade,
+    );
+    let governance = ecosystem_contracts.l1.governance_addr;
+    let schedule = encode(
+        &GOVERNANCE,
+        "scheduleTransparent",
+        &[operation.clone(), Token::Uint(U256::zero())],
+    )?;
+    send_transaction(&client, governance, schedule, "scheduleTransparent").await?;
+    let execute = encode(&GOVERNANCE, "execute", &[operation])?;
+    send_transaction(&client, governance, execute, "execute").await?;
+    spinner.finish();
+
+    let spinner = Spinner::new(MSG_UPGRADE_EXECUTING_SPINNER);
+    let chain_governor = chain_config
+        .get_wallets_config()?
+        .governor
+        .local_signer()
+        .context(MSG_CHAIN_GOVERNOR_PRIVATE_KEY_MISSING_ERR)?;
+    let client = create_ethers_client(chain_governor, l1_rpc_url.clone(), Some(l1_chain_id))?;
+    let chain_admin = chain_contracts.l1.chain_admin_addr;
+    let set_timestamp = encode(
+        &CHAIN_ADMIN,
+        "setUpgradeTimestamp",
+        &[
+            Token::Uint(new_version.pack()),
+            Token::Uint(description.upgrade_timestamp.into()),
+        ],
+    )?;
+    send_transaction(&client, chain_admin, set_timestamp, "setUpgradeTimestamp").await?;
+    let upgrade_chain = encode(
+        &ADMIN_FACET,
+        "upgradeChainFromVersion",
+        &[Token::Uint(old_version.pack()), cut_data],
+    )?;
+    let multicall = encode(
+        &CHAIN_ADMIN,
+        "multicall",
+        &[
+            Token::Array(vec![call(diamond_proxy, upgrade_chain)]),
+            Token::Bool(true),
+        ],
+    )?;
+    send_transaction(&client, chain_admin, multicall, "upgradeChainFromVersion").await?;
+    spinner.finish();
+
+    let active_version = get_protocol_version(&l1_rpc_url, diamond_proxy).await?;
+    anyhow::ensure!(
+        active_version == new_version,
+        msg_upgrade_not_active_on_l1(&new_version, &active_version)
+    );
+
+    if !args.skip_db_check {
+        let spinner = Spinner::new(MSG_UPGRADE_WAITING_FOR_NODE_SPINNER);
+        let db_url = secrets
+            .database
+            .and_then(|database| database.server_url)
+            .context(MSG_DATABASE_MUST_BE_PRESENTED)?;
+        wait_for_protocol_version_in_db(
+            db_url.expose_url(),
+            &new_version,
+            Duration::from_secs(args.timeout),
+        )
+        .await?;
+        spinner.finish();
+    }
+
+    logger::success(msg_chain_upgraded(&new_version));
+    Ok(())
+}
+
+/// Builds the diamond cut that delegate-calls the upgrade contract with the described changes.
+/// The upgrade doesn't contain an L2 upgrade transaction, so the transaction type is zero.
+fn diamond_cut_data(
+    upgrade_address: Address,
+    description: &ProtocolUpgradeDescription,
+) -> anyhow::Result<Token> {
+    let l2_upgrade_tx = Token::Tuple(
+        std::iter::repeat(Token::Uint(U256::zero()))
+            .take(10)
+            .chain([
+                Token::FixedArray(vec![Token::Uint(U256::zero()); 4]),
+                Token::Bytes(vec![]),
+                Token::Bytes(vec![]),
+                Token::Array(vec![]),
+                Token::Bytes(vec![]),
+                Token::Bytes(vec![]),
+            ])
+            .collect(),
+    );
+    let verifier_params = description.verifier_params.unwrap_or_default();
+    let proposed_upgrade = Token::Tuple(vec![
+        l2_upgrade_tx,
+        Token::Array(vec![]),
+        fixed_bytes(description.bootloader_hash.unwrap_or_default()),
+        fixed_bytes(description.default_aa_hash.unwrap_or_default()),
+        Token::Address(description.verifier.unwrap_or_default()),
+        Token::Tuple(vec![
+            fixed_bytes(verifier_params.recursion_node_level_vk_hash),
+            fixed_bytes(verifier_params.recursion_leaf_level_vk_hash),
+            fixed_bytes(verifier_params.recursion_circuits_set_vks_hash),
+        ]),
+        Token::Bytes(description.l1_contracts_upgrade_calldata.to_vec()),
+        Token::Bytes(description.post_upgrade_calldata.to_vec()),
+        Token::Uint(description.upgrade_timestamp.into()),
+        Token::Uint(description.protocol_version.pack()),
+    ]);
+    let init_calldata = encode(&DEFAULT_UPGRADE, "upgrade", &[proposed_upgrade])?;
+
+    Ok(Token::Tuple(vec![
+        Token::Array(vec![]),
+        Token::Address(upgrade_address),
+        Token::Bytes(init_calldata.to_vec()),
+    ]))
+}
+
+fn governance_operation(target: Address, data: Bytes) -> Token {
+    Token::Tuple(vec![
+        Token::Array(vec![call(target, data)]),
+        fixed_bytes(H256::zero()),
+        fixed_bytes(H256::zero()),
+    ])
+}
+
+fn call(target: Address, data: Bytes) -> Token {
+    Token::Tuple(vec![
+        Token::Address(target),
+        Token::Uint(U256::zero()),
+        Token::Bytes(data.to_vec()),
+    ])
+}
+
+fn fixed_bytes(hash: H256) -> Token {
+    Token::FixedBytes(hash.as_bytes().to_vec())
+}
+
+fn encode(contract: &BaseContract, name: &str, args: &[Token]) -> anyhow::Result<Bytes> {
+    let function = contract.abi().function(name)?;
+    Ok(function.encode_input(args)?.into())
+}
+
+async fn send_transaction(
+    client: &Client,
+    to: Address,
+    data: Bytes,
+    operation: &str,
+) -> anyhow::Result<()> {
+    let tx = TransactionRequest::new().to(to).data(data);
+    let pending_tx = client
+        .send_transaction(tx, None)
+        .await
+        .context(operation.to_string())?;
+    let tx_hash = pending_tx.tx_hash();
+    let receipt = pending_tx
+        .await?
+        .with_context(|| msg_upgrade_tx_failed(operation, tx_hash))?;
+    anyhow::ensure!(
+        receipt.status == Some(1.into()),
+        msg_upgrade_tx_failed(operation, tx_hash)
+    );
+    logger::debug(format!("{operation} succeeded, tx_hash={tx_hash:?}"));
+    Ok(())
+}
+
+async fn get_protocol_version(
+    l1_rpc_url: &str,
+    diamond_proxy: Address,
+) -> anyhow::Result<ProtocolSemanticVersion> {
+    let provider = Provider::<Http>::try_from(l1_rpc_url)?;
+    let tx = TransactionRequest::new()
+        .to(diamond_proxy)
+        .data(ADMIN_FACET.encode("getProtocolVersion", ())?);
+    let output = provider.call(&tx.into(), None).await?;
+    let packed: U256 = ADMIN_FACET.decode_output("getProtocolVersion", output)?;
+    ProtocolSemanticVersion::try_from_packed(packed).map_err(|err| anyhow::anyhow!(err))
+}
+
+/// Waits until the node persists the new protocol version, which happens once it processes
+/// the upgrade event from L1.
+async fn wait_for_protocol_version_in_db(
+    db_url: &Url,
+    version: &ProtocolSemanticVersion,
+    timeout: Duration,
+) -> anyhow::Result<()> {
+    let mut connection = PgConnection::connect(db_url.as_str()).await?;
+    let query = async {
+        loop {
+            let exists: bool = sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM protocol_patches WHERE minor = $1 AND patch = $2)",
+            )
+            .bind(version.minor as i32)
+            .bind(version.patch.0 as i32)
+            .fetch_one(&mut connection)
+            .await?;
+            if exists {
+                return anyhow::Ok(());
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+    };
+    tokio::time::timeout(timeout, query)
+        .await
+        .map_err(|_| anyhow::anyhow!(msg_upgrade_not_picked_up_by_node(version)))?
+}
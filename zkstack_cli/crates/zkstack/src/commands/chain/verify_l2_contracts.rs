@@ -0,0 +1,164 @@
+use std::path::Path;
+
+use anyhow::Context;
+use common::{
+    contracts::{l2_contract_standard_json_input, l2_contracts_compiler_versions},
+    logger,
+};
+use config::{
+    forge_interface::{
+        deploy_l2_contracts::output::{
+            ConsensusRegistryOutput, DefaultL2UpgradeOutput, InitializeBridgeOutput,
+            L2ContractsVerificationOutput, Multicall3Output,
+        },
+        script_params::DEPLOY_L2_CONTRACTS_SCRIPT_PARAMS,
+    },
+    traits::{
+        ConfigWithL2RpcUrl, FileConfigWithDefaultName, ReadConfig, ReadConfigWithBasePath,
+        SaveConfigWithBasePath,
+    },
+    ChainConfig, EcosystemConfig, L2DeploymentStep,
+};
+use ethers::{
+    abi::{encode, Token},
+    types::{Address, Bytes},
+};
+use serde_json::json;
+use url::Url;
+use xshell::Shell;
+
+use crate::messages::{
+    msg_l2_contract_verification_submitted, MSG_CONTRACT_VERIFIER_CONFIG_MISSING_ERR,
+    MSG_CONTRACT_VERIFIER_URL_ERR, MSG_VERIFYING_L2_CONTRACTS,
+};
+
+/// L2 contract deployed by the `DeployL2Contracts` forge script.
+struct DeployedL2Contract {
+    name: &'static str,
+    /// Contract identifier in the `<path>:<name>` format.
+    identifier: &'static str,
+    address: Address,
+    constructor_arguments: Bytes,
+}
+
+/// Reads the contract deployed by `step` from the deploy script output. Returns `None` for the steps
+/// with contracts that are not verified.
+fn deployed_contract(
+    shell: &Shell,
+    output: &Path,
+    ecosystem_config: &EcosystemConfig,
+    step: L2DeploymentStep,
+) -> anyhow::Result<Option<DeployedL2Contract>> {
+    Ok(Some(match step {
+        L2DeploymentStep::InitializeBridges => DeployedL2Contract {
+            name: "l2_shared_bridge_implementation",
+            identifier: "contracts/bridge/L2SharedBridge.sol:L2SharedBridge",
+            address: InitializeBridgeOutput::read(shell, output)?.l2_shared_bridge_implementation,
+            constructor_arguments: encode(&[Token::Uint(
+                ecosystem_config.era_chain_id.as_u64().into(),
+            )])
+            .into(),
+        },
+        L2DeploymentStep::ConsensusRegistry => DeployedL2Contract {
+            name: "consensus_registry_implementation",
+            identifier: "contracts/ConsensusRegistry.sol:ConsensusRegistry",
+            address: ConsensusRegistryOutput::read(shell, output)?
+                .consensus_registry_implementation,
+            constructor_arguments: Bytes::default(),
+        },
+        L2DeploymentStep::Multicall3 => DeployedL2Contract {
+            name: "multicall3",
+            identifier: "contracts/dev-contracts/Multicall3.sol:Multicall3",
+            address: Multicall3Output::read(shell, output)?.multicall3,
+            constructor_arguments: Bytes::default(),
+        },
+        L2DeploymentStep::DefaultUpgrader => DeployedL2Contract {
+            name: "l2_default_upgrader",
+            identifier: "contracts/ForceDeployUpgrader.sol:ForceDeployUpgrader",
+            address: DefaultL2UpgradeOutput::read(shell, output)?.l2_default_upgrader,
+            constructor_arguments: Bytes::default(),
+        },
+        L2DeploymentStep::TimestampAsserter => return Ok(None),
+    }))
+}
+
+/// Returns the URL of the contract verification API. The API is expected to be served on the same host
+/// as the chain JSON-RPC API.
+fn contract_verification_url(chain_config: &ChainConfig) -> anyhow::Result<Url> {
+    let general_config = chain_config.get_general_config()?;
+    let verifier_port = general_config
+        .contract_verifier
+        .as_ref()
+        .context(MSG_CONTRACT_VERIFIER_CONFIG_MISSING_ERR)?
+        .port;
+    let mut url = general_config.get_l2_rpc_url()?;
+    url.set_port(Some(verifier_port))
+        .ok()
+        .context(MSG_CONTRACT_VERIFIER_URL_ERR)?;
+    url.set_path("contract_verification");
+    Ok(url)
+}
+
+/// Submits the L2 contract deployed by `step` of the `DeployL2Contracts` forge script to the chain
+/// contract verifier and records the id of the verification request. Must be called right after
+/// the step, while the script output still contains its contracts.
+pub async fn verify_l2_deployment_step(
+    shell: &Shell,
+    chain_config: &ChainConfig,
+    ecosystem_config: &EcosystemConfig,
+    step: L2DeploymentStep,
+) -> anyhow::Result<()> {
+    let output = DEPLOY_L2_CONTRACTS_SCRIPT_PARAMS.output(&chain_config.link_to_code);
+    let Some(contract) = deployed_contract(shell, &output, ecosystem_config, step)? else {
+        return Ok(());
+    };
+    logger::info(MSG_VERIFYING_L2_CONTRACTS);
+
+    let url = contract_verification_url(chain_config)?;
+    let (zksolc_version, solc_version) =
+        l2_contracts_compiler_versions(shell, chain_config.link_to_code.clone())?;
+    let source_code = l2_contract_standard_json_input(
+        shell,
+        chain_config.link_to_code.clone(),
+        contract.address,
+        contract.identifier,
+    )?;
+    let request = json!({
+        "contractAddress": contract.address,
+        "codeFormat": "solidity-standard-json-input",
+        "sourceCode": source_code,
+        "contractName": contract.identifier,
+        "compilerZksolcVersion": zksolc_version,
+        "compilerSolcVersion": solc_version,
+        "optimizationUsed": true,
+        "constructorArguments": contract.constructor_arguments,
+        "isSystem": false,
+    });
+    let response = reqwest::Client::new()
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(serde_json::to_vec(&request)?)
+        .send()
+        .await?
+        .error_for_status()
+        .with_context(|| format!("failed submitting {} for verification", contract.name))?;
+    let id: usize = serde_json::from_slice(&response.bytes().await?)?;
+    logger::info(msg_l2_contract_verification_submitted(
+        contract.name,
+        contract.address,
+        id,
+    ));
+
+    let verification_path =
+        L2ContractsVerificationOutput::get_path_with_base_path(&chain_config.configs);
+    let mut verification = if shell.path_exists(verification_path) {
+        L2ContractsVerificationOutput::read_with_base_path(shell, &chain_config.configs)?
+    } else {
+        L2ContractsVerificationOutput::default()
+    };
+    verification
+        .verification_ids
+        .insert(contract.name.to_owned(), id);
+    verification.save_with_base_path(shell, &chain_config.configs)?;
+    Ok(())
+}
@@ -12,11 +12,11 @@ use ethers::{
     middleware::{Middleware, NonceManagerMiddleware, SignerMiddleware},
     providers::{Http, JsonRpcClient, PendingTransaction, Provider, ProviderError, RawCall as _},
     signers::{LocalWallet, Signer as _},
-    types::{Address, BlockId, H256},
+    types::{Address, BlockId, Bytes, TransactionRequest, H256},
 };
 use tokio::time::MissedTickBehavior;
 use xshell::Shell;
-use zksync_consensus_crypto::ByteFmt;
+use zksync_consensus_crypto::{ByteFmt, Text, TextFmt};
 use zksync_consensus_roles::{attester, validator};
 
 use crate::{commands::args::WaitArgs, messages, utils::consensus::parse_attester_committee};
@@ -47,6 +47,14 @@ fn decode_weighted_attester(
     })
 }
 
+fn decode_validator_key(k: &abi::Bls12381PublicKey) -> anyhow::Result<validator::PublicKey> {
+    let mut x = vec![];
+    x.extend(k.a);
+    x.extend(k.b);
+    x.extend(k.c);
+    ByteFmt::decode(&x)
+}
+
 fn encode_attester_key(k: &attester::PublicKey) -> abi::Secp256K1PublicKey {
     let b: [u8; 33] = ByteFmt::encode(k).try_into().unwrap();
     abi::Secp256K1PublicKey {
@@ -86,6 +94,64 @@ pub struct SetAttesterCommitteeCommand {
     from_file: Option<PathBuf>,
 }
 
+#[derive(clap::Args, Debug)]
+pub struct TxArgs {
+    /// Sends the transaction signed by the governor wallet.
+    /// Otherwise only the calldata is printed, so that it can be submitted
+    /// by the consensus registry owner.
+    #[clap(long)]
+    send: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct AddNodeCommand {
+    /// Address of the node owner.
+    #[clap(long)]
+    node_owner: Address,
+    /// Validator public key (`validator:public:bls12_381:...`).
+    #[clap(long)]
+    validator_key: String,
+    /// Validator proof of possession (`validator:pop:bls12_381:...`).
+    #[clap(long)]
+    validator_pop: String,
+    #[clap(long, default_value_t = 1)]
+    validator_weight: u32,
+    /// Attester public key (`attester:public:secp256k1:...`).
+    #[clap(long)]
+    attester_key: String,
+    #[clap(long, default_value_t = 1)]
+    attester_weight: u32,
+    #[clap(flatten)]
+    tx: TxArgs,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct RemoveNodeCommand {
+    /// Address of the node owner.
+    #[clap(long)]
+    node_owner: Address,
+    #[clap(flatten)]
+    tx: TxArgs,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct RotateKeysCommand {
+    /// Address of the node owner.
+    #[clap(long)]
+    node_owner: Address,
+    /// New validator public key (`validator:public:bls12_381:...`).
+    #[clap(long, requires = "validator_pop")]
+    validator_key: Option<String>,
+    /// Proof of possession of the new validator key (`validator:pop:bls12_381:...`).
+    #[clap(long, requires = "validator_key")]
+    validator_pop: Option<String>,
+    /// New attester public key (`attester:public:secp256k1:...`).
+    #[clap(long, required_unless_present = "validator_key")]
+    attester_key: Option<String>,
+    #[clap(flatten)]
+    tx: TxArgs,
+}
+
 #[derive(clap::Subcommand, Debug)]
 pub enum Command {
     /// Sets the attester committee in the consensus registry contract to
@@ -95,6 +161,14 @@ pub enum Command {
     GetAttesterCommittee,
     /// Wait until the consensus registry contract is deployed to L2.
     WaitForRegistry(WaitArgs),
+    /// Lists the nodes registered in the consensus registry contract.
+    ListNodes,
+    /// Adds a node to the consensus registry contract.
+    AddNode(AddNodeCommand),
+    /// Removes a node from the consensus registry contract.
+    RemoveNode(RemoveNodeCommand),
+    /// Replaces the validator and/or attester key of a registered node.
+    RotateKeys(RotateKeysCommand),
 }
 
 /// Collection of sent transactions.
@@ -133,6 +207,55 @@ impl TxSet {
     }
 }
 
+/// Consensus registry entry of a single node, as returned by `nodes()`.
+#[derive(Debug)]
+struct RegisteredNode {
+    owner: Address,
+    validator_key: validator::PublicKey,
+    validator_weight: u32,
+    validator_active: bool,
+    attester_key: attester::PublicKey,
+    attester_weight: u32,
+    attester_active: bool,
+}
+
+impl RegisteredNode {
+    fn decode(owner: Address, node: &abi::NodesReturn) -> anyhow::Result<Self> {
+        Ok(Self {
+            owner,
+            validator_key: decode_validator_key(&node.validator_latest.pub_key)
+                .context("decode_validator_key()")?,
+            validator_weight: node.validator_latest.weight,
+            validator_active: node.validator_latest.active,
+            attester_key: decode_attester_key(&node.attester_latest.pub_key)
+                .context("decode_attester_key()")?,
+            attester_weight: node.attester_latest.weight,
+            attester_active: node.attester_latest.active,
+        })
+    }
+}
+
+fn print_nodes(nodes: &[RegisteredNode]) {
+    logger::success(
+        nodes
+            .iter()
+            .map(|n| {
+                format!(
+                    "owner: {:#x}\n  validator: {} (weight: {}, active: {})\n  attester: {} (weight: {}, active: {})",
+                    n.owner,
+                    n.validator_key.encode(),
+                    n.validator_weight,
+                    n.validator_active,
+                    n.attester_key.encode(),
+                    n.attester_weight,
+                    n.attester_active,
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    );
+}
+
 fn print_attesters(committee: &attester::Committee) {
     logger::success(
         committee
@@ -334,14 +457,170 @@ impl Setup {
         .await
     }
 
+    /// Fetches all the nodes which are not marked as removed.
+    async fn list_nodes(&self) -> anyhow::Result<Vec<RegisteredNode>> {
+        let provider = Arc::new(self.provider().context("provider()")?);
+        let block_id = self.last_block(&provider).await.context("last_block()")?;
+        let consensus_registry = self
+            .consensus_registry(provider.clone())
+            .context("consensus_registry()")?;
+        let mut multicall = self.multicall(provider).context("multicall()")?;
+        let n: usize = consensus_registry
+            .num_nodes()
+            .call_raw()
+            .block(block_id)
+            .await
+            .context("num_nodes()")?
+            .try_into()
+            .ok()
+            .context("num_nodes() overflow")?;
+
+        multicall.block = Some(block_id);
+        let node_owners: Vec<Address> = multicall
+            .add_calls(
+                false,
+                (0..n).map(|i| consensus_registry.node_owners(i.into())),
+            )
+            .call_array()
+            .await
+            .context("node_owners()")?;
+        multicall.clear_calls();
+        let nodes: Vec<abi::NodesReturn> = multicall
+            .add_calls(
+                false,
+                node_owners
+                    .iter()
+                    .map(|addr| consensus_registry.nodes(*addr)),
+            )
+            .call_array()
+            .await
+            .context("nodes()")?;
+        node_owners
+            .into_iter()
+            .zip(&nodes)
+            .filter(|(_, node)| !node.attester_latest.removed && !node.validator_latest.removed)
+            .map(|(owner, node)| RegisteredNode::decode(owner, node))
+            .collect()
+    }
+
+    /// Encodes a registry call, without requiring a signer.
+    fn calldata<M: Middleware, D: Detokenize>(
+        name: &'static str,
+        call: FunctionCall<Arc<M>, M, D>,
+    ) -> anyhow::Result<(&'static str, Bytes)> {
+        Ok((name, call.calldata().context(name)?))
+    }
+
+    /// Either sends the given registry calls with the governor wallet and waits for them
+    /// to complete, or prints their calldata if `--send` is not set.
+    async fn submit(&self, args: &TxArgs, calls: Vec<(&'static str, Bytes)>) -> anyhow::Result<()> {
+        let to = self.consensus_registry_addr()?;
+        if !args.send {
+            for (name, calldata) in calls {
+                logger::info(messages::msg_consensus_registry_calldata(
+                    name, to, &calldata,
+                ));
+            }
+            return Ok(());
+        }
+        let provider = self.provider().context("provider()")?;
+        let governor = self.governor().context("governor()")?;
+        let signer = self.signer(
+            governor
+                .local_signer()
+                .context(messages::MSG_GOVERNOR_PRIVATE_KEY_NOT_SET)?,
+        )?;
+        let mut txs = TxSet::default();
+        for (name, calldata) in calls {
+            let tx = TransactionRequest::new().to(to).data(calldata);
+            let h = signer
+                .send_transaction(tx, None)
+                .await
+                .context(name)?
+                .tx_hash();
+            txs.0.push((h, name));
+        }
+        txs.wait(&provider).await.context("wait()")?;
+        Ok(())
+    }
+
+    fn read_only_consensus_registry(
+        &self,
+    ) -> anyhow::Result<abi::ConsensusRegistry<Provider<Http>>> {
+        let provider = Arc::new(self.provider().context("provider()")?);
+        self.consensus_registry(provider)
+    }
+
+    async fn add_node(&self, opts: &AddNodeCommand) -> anyhow::Result<()> {
+        let validator_key: validator::PublicKey = Text::new(&opts.validator_key)
+            .decode()
+            .context("validator_key")?;
+        let validator_pop: validator::ProofOfPossession = Text::new(&opts.validator_pop)
+            .decode()
+            .context("validator_pop")?;
+        let attester_key: attester::PublicKey = Text::new(&opts.attester_key)
+            .decode()
+            .context("attester_key")?;
+        let consensus_registry = self
+            .read_only_consensus_registry()
+            .context("consensus_registry()")?;
+        let call = Self::calldata(
+            "add",
+            consensus_registry.add(
+                opts.node_owner,
+                opts.validator_weight,
+                encode_validator_key(&validator_key),
+                encode_validator_pop(&validator_pop),
+                opts.attester_weight,
+                encode_attester_key(&attester_key),
+            ),
+        )?;
+        self.submit(&opts.tx, vec![call]).await
+    }
+
+    async fn remove_node(&self, opts: &RemoveNodeCommand) -> anyhow::Result<()> {
+        let consensus_registry = self
+            .read_only_consensus_registry()
+            .context("consensus_registry()")?;
+        let call = Self::calldata("remove", consensus_registry.remove(opts.node_owner))?;
+        self.submit(&opts.tx, vec![call]).await
+    }
+
+    async fn rotate_keys(&self, opts: &RotateKeysCommand) -> anyhow::Result<()> {
+        let consensus_registry = self
+            .read_only_consensus_registry()
+            .context("consensus_registry()")?;
+        let mut calls = vec![];
+        if let (Some(key), Some(pop)) = (&opts.validator_key, &opts.validator_pop) {
+            let key: validator::PublicKey = Text::new(key).decode().context("validator_key")?;
+            let pop: validator::ProofOfPossession =
+                Text::new(pop).decode().context("validator_pop")?;
+            calls.push(Self::calldata(
+                "change_validator_key",
+                consensus_registry.change_validator_key(
+                    opts.node_owner,
+                    encode_validator_key(&key),
+                    encode_validator_pop(&pop),
+                ),
+            )?);
+        }
+        if let Some(key) = &opts.attester_key {
+            let key: attester::PublicKey = Text::new(key).decode().context("attester_key")?;
+            calls.push(Self::calldata(
+                "change_attester_key",
+                consensus_registry.change_attester_key(opts.node_owner, encode_attester_key(&key)),
+            )?);
+        }
+        self.submit(&opts.tx, calls).await
+    }
+
     async fn set_attester_committee(&self, want: &attester::Committee) -> anyhow::Result<()> {
         let provider = self.provider().context("provider()")?;
         let block_id = self.last_block(&provider).await.context("last_block()")?;
         let governor = self.governor().context("governor()")?;
         let signer = self.signer(
             governor
-                .private_key
-                .clone()
+                .local_signer()
                 .context(messages::MSG_GOVERNOR_PRIVATE_KEY_NOT_SET)?,
         )?;
         let consensus_registry = self
@@ -472,6 +751,13 @@ impl Command {
                 let verbose = global_config().verbose;
                 setup.wait_for_registry_contract(&args, verbose).await?;
             }
+            Self::ListNodes => {
+                let nodes = setup.list_nodes().await?;
+                print_nodes(&nodes);
+            }
+            Self::AddNode(opts) => setup.add_node(&opts).await?,
+            Self::RemoveNode(opts) => setup.remove_node(&opts).await?,
+            Self::RotateKeys(opts) => setup.rotate_keys(&opts).await?,
         }
         Ok(())
     }
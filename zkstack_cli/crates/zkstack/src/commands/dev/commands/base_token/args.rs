@@ -0,0 +1,21 @@
+use clap::Parser;
+use ethers::types::Address;
+
+use crate::commands::dev::messages::{
+    MSG_BASE_TOKEN_AMOUNT_HELP, MSG_BASE_TOKEN_L1_RPC_URL_HELP, MSG_BASE_TOKEN_RECEIVERS_HELP,
+    MSG_BASE_TOKEN_SKIP_RATIO_HELP, MSG_BASE_TOKEN_SKIP_SMOKE_TEST_HELP,
+};
+
+#[derive(Debug, Parser)]
+pub struct BaseTokenArgs {
+    #[clap(long, value_delimiter = ',', help = MSG_BASE_TOKEN_RECEIVERS_HELP)]
+    pub receivers: Vec<Address>,
+    #[clap(long, default_value = "100", help = MSG_BASE_TOKEN_AMOUNT_HELP)]
+    pub amount: String,
+    #[clap(long, help = MSG_BASE_TOKEN_SKIP_RATIO_HELP)]
+    pub skip_ratio: bool,
+    #[clap(long, help = MSG_BASE_TOKEN_SKIP_SMOKE_TEST_HELP)]
+    pub skip_smoke_test: bool,
+    #[clap(long, help = MSG_BASE_TOKEN_L1_RPC_URL_HELP)]
+    pub l1_rpc_url: Option<String>,
+}
@@ -0,0 +1,362 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use args::BaseTokenArgs;
+use common::{logger, spinner::Spinner, wallets::Wallet};
+use config::{traits::ConfigWithL2RpcUrl, ChainConfig, ContractsConfig, EcosystemConfig};
+use ethers::{
+    abi::{parse_abi, Token},
+    contract::BaseContract,
+    providers::{Http, Middleware, Provider},
+    types::{Address, TransactionReceipt, TransactionRequest, H160, U256},
+    utils::parse_units,
+};
+use lazy_static::lazy_static;
+use types::{BaseToken, L1Network};
+use xshell::Shell;
+
+use crate::commands::dev::messages::{
+    msg_base_token_no_code_err, msg_base_token_verified, msg_base_token_withdrawal_sent,
+    MSG_BASE_TOKEN_AMOUNT_TOO_LARGE_ERR, MSG_BASE_TOKEN_DEPOSIT_TIMEOUT_ERR,
+    MSG_BASE_TOKEN_ETH_ERR, MSG_BASE_TOKEN_FUNDING_SPINNER, MSG_BASE_TOKEN_MINTING_SPINNER,
+    MSG_BASE_TOKEN_NO_MULTIPLIER_SETTER_ERR, MSG_BASE_TOKEN_SETTING_RATIO_SPINNER,
+    MSG_BASE_TOKEN_SMOKE_DEPOSIT_SPINNER, MSG_BASE_TOKEN_SMOKE_WITHDRAWAL_SPINNER,
+    MSG_BASE_TOKEN_VERIFYING_SPINNER, MSG_CHAIN_NOT_FOUND_ERR,
+};
+
+pub mod args;
+
+/// Gas limit of the deposit transactions on L2, enough for a plain transfer.
+const DEPOSIT_L2_GAS_LIMIT: u64 = 1_000_000;
+const REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_LIMIT: u64 = 800;
+/// Address of the L2 base token system contract.
+const L2_BASE_TOKEN_ADDRESS: H160 = H160([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x80, 0x0a,
+]);
+const DEPOSIT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const DEPOSIT_TIMEOUT: Duration = Duration::from_secs(300);
+
+lazy_static! {
+    static ref BRIDGEHUB: BaseContract = BaseContract::from(
+        parse_abi(&[
+            "function requestL2TransactionDirect((uint256,uint256,address,uint256,bytes,uint256,uint256,bytes[],address) _request) external payable returns (bytes32)",
+            "function l2TransactionBaseCost(uint256 _chainId, uint256 _gasPrice, uint256 _l2GasLimit, uint256 _l2GasPerPubdataByteLimit) external view returns (uint256)",
+        ])
+        .unwrap(),
+    );
+    static ref ERC20: BaseContract = BaseContract::from(
+        parse_abi(&[
+            "function approve(address spender, uint256 amount) external returns (bool)",
+            "function decimals() external view returns (uint8)",
+            "function symbol() external view returns (string)",
+        ])
+        .unwrap(),
+    );
+    static ref CHAIN_ADMIN: BaseContract = BaseContract::from(
+        parse_abi(&[
+            "function setTokenMultiplier(address _chainContract, uint128 _nominator, uint128 _denominator) external",
+        ])
+        .unwrap(),
+    );
+    static ref L2_BASE_TOKEN: BaseContract = BaseContract::from(
+        parse_abi(&["function withdraw(address _l1Receiver) external payable"]).unwrap(),
+    );
+}
+
+pub async fn run(shell: &Shell, args: BaseTokenArgs) -> anyhow::Result<()> {
+    let ecosystem_config = EcosystemConfig::from_file(shell)?;
+    let chain_config = ecosystem_config
+        .load_current_chain()
+        .context(MSG_CHAIN_NOT_FOUND_ERR)?;
+    anyhow::ensure!(
+        chain_config.base_token != BaseToken::eth(),
+        MSG_BASE_TOKEN_ETH_ERR
+    );
+    let l1_rpc_url = match args.l1_rpc_url {
+        Some(url) => url,
+        None => chain_config
+            .get_secrets_config()?
+            .l1
+            .context("No L1 secrets available")?
+            .l1_rpc_url
+            .expose_str()
+            .to_string(),
+    };
+    let l1_chain_id = ecosystem_config.l1_network.chain_id();
+    let base_token = chain_config.base_token.address;
+    let contracts = chain_config.get_contracts_config()?;
+    let chain_wallets = chain_config.get_wallets_config()?;
+    // Base tokens are funded from the ecosystem governor, the same as in `chain init`.
+    let governor = ecosystem_config.get_wallets()?.governor;
+
+    let spinner = Spinner::new(MSG_BASE_TOKEN_VERIFYING_SPINNER);
+    let l1_provider = Provider::<Http>::try_from(l1_rpc_url.as_str())?;
+    let (symbol, decimals) = verify_base_token(&l1_provider, base_token).await?;
+    spinner.finish();
+    logger::info(msg_base_token_verified(&symbol, decimals));
+
+    if !args.skip_ratio {
+        let spinner = Spinner::new(MSG_BASE_TOKEN_SETTING_RATIO_SPINNER);
+        let setter = chain_wallets
+            .token_multiplier_setter
+            .context(MSG_BASE_TOKEN_NO_MULTIPLIER_SETTER_ERR)?;
+        set_token_multiplier(
+            &setter,
+            &contracts,
+            &chain_config.base_token,
+            &l1_rpc_url,
+            l1_chain_id,
+        )
+        .await?;
+        spinner.finish();
+    }
+
+    let amount: U256 = parse_units(&args.amount, decimals as u32)?.into();
+    let mut receivers = args.receivers;
+    if receivers.is_empty() {
+        receivers.push(chain_wallets.governor.address);
+    }
+    let deposits_count = receivers.len() + usize::from(!args.skip_smoke_test);
+
+    if ecosystem_config.l1_network == L1Network::Localhost {
+        // Test tokens deployed on localhost can be minted by anyone. Twice the amount covers
+        // the L2 transaction costs paid in the base token.
+        let mint_amount = amount
+            .checked_mul(U256::from(deposits_count) * 2)
+            .filter(|mint_amount| *mint_amount <= U256::from(u128::MAX))
+            .context(MSG_BASE_TOKEN_AMOUNT_TOO_LARGE_ERR)?
+            .as_u128();
+        let spinner = Spinner::new(MSG_BASE_TOKEN_MINTING_SPINNER);
+        common::ethereum::mint_token(
+            governor.clone(),
+            base_token,
+            vec![governor.address],
+            l1_rpc_url.clone(),
+            l1_chain_id,
+            mint_amount,
+        )
+        .await?;
+        spinner.finish();
+    }
+
+    let spinner = Spinner::new(MSG_BASE_TOKEN_FUNDING_SPINNER);
+    for receiver in &receivers {
+        deposit(
+            &chain_config,
+            &contracts,
+            &governor,
+            &l1_rpc_url,
+            l1_chain_id,
+            base_token,
+            amount,
+            *receiver,
+        )
+        .await?;
+    }
+    spinner.finish();
+
+    if !args.skip_smoke_test {
+        smoke_test(
+            &chain_config,
+            &contracts,
+            &governor,
+            &chain_wallets.governor,
+            &l1_rpc_url,
+            l1_chain_id,
+            base_token,
+            amount,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Checks that the base token is a deployed ERC20 contract and returns its symbol and decimals.
+async fn verify_base_token(
+    provider: &Provider<Http>,
+    base_token: Address,
+) -> anyhow::Result<(String, u8)> {
+    let code = provider.get_code(base_token, None).await?;
+    anyhow::ensure!(!code.is_empty(), msg_base_token_no_code_err(&base_token));
+
+    let call = TransactionRequest::new()
+        .to(base_token)
+        .data(ERC20.encode("symbol", ())?);
+    let symbol: String = ERC20.decode_output("symbol", provider.call(&call.into(), None).await?)?;
+    let call = TransactionRequest::new()
+        .to(base_token)
+        .data(ERC20.encode("decimals", ())?);
+    let decimals: u8 = ERC20.decode_output("decimals", provider.call(&call.into(), None).await?)?;
+    Ok((symbol, decimals))
+}
+
+/// Sets the base token conversion ratio from the chain config through the chain admin.
+async fn set_token_multiplier(
+    setter: &Wallet,
+    contracts: &ContractsConfig,
+    base_token: &BaseToken,
+    l1_rpc_url: &str,
+    l1_chain_id: u64,
+) -> anyhow::Result<()> {
+    let client = common::ethereum::create_ethers_client(
+        setter.local_signer()?,
+        l1_rpc_url.to_string(),
+        Some(l1_chain_id),
+    )?;
+    let tx = TransactionRequest::new()
+        .to(contracts.l1.chain_admin_addr)
+        .data(CHAIN_ADMIN.encode(
+            "setTokenMultiplier",
+            (
+                contracts.l1.diamond_proxy_addr,
+                base_token.nominator as u128,
+                base_token.denominator as u128,
+            ),
+        )?);
+    let receipt = client.send_transaction(tx, None).await?.await?;
+    check_receipt(receipt, "setTokenMultiplier")
+}
+
+/// Deposits `amount` of the base token to `receiver` on L2 with a direct L2 transaction.
+#[allow(clippy::too_many_arguments)]
+async fn deposit(
+    chain_config: &ChainConfig,
+    contracts: &ContractsConfig,
+    wallet: &Wallet,
+    l1_rpc_url: &str,
+    l1_chain_id: u64,
+    base_token: Address,
+    amount: U256,
+    receiver: Address,
+) -> anyhow::Result<()> {
+    let client = common::ethereum::create_ethers_client(
+        wallet.local_signer()?,
+        l1_rpc_url.to_string(),
+        Some(l1_chain_id),
+    )?;
+    let bridgehub = contracts.ecosystem_contracts.bridgehub_proxy_addr;
+    let chain_id = U256::from(chain_config.chain_id.as_u64());
+
+    // The base cost is denominated in the base token, so it is minted on top of the amount.
+    let gas_price = client.get_gas_price().await?;
+    let base_cost_call = TransactionRequest::new()
+        .to(bridgehub)
+        .data(BRIDGEHUB.encode(
+            "l2TransactionBaseCost",
+            (
+                chain_id,
+                gas_price,
+                U256::from(DEPOSIT_L2_GAS_LIMIT),
+                U256::from(REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_LIMIT),
+            ),
+        )?);
+    let base_cost: U256 = BRIDGEHUB.decode_output(
+        "l2TransactionBaseCost",
+        client.call(&base_cost_call.into(), None).await?,
+    )?;
+    let mint_value = amount
+        .checked_add(base_cost)
+        .context(MSG_BASE_TOKEN_AMOUNT_TOO_LARGE_ERR)?;
+
+    let approve = TransactionRequest::new()
+        .to(base_token)
+        .data(ERC20.encode("approve", (contracts.bridges.shared.l1_address, mint_value))?);
+    let receipt = client.send_transaction(approve, None).await?.await?;
+    check_receipt(receipt, "approve")?;
+
+    let request = Token::Tuple(vec![
+        Token::Uint(chain_id),
+        Token::Uint(mint_value),
+        Token::Address(receiver),
+        Token::Uint(amount),
+        Token::Bytes(vec![]),
+        Token::Uint(DEPOSIT_L2_GAS_LIMIT.into()),
+        Token::Uint(REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_LIMIT.into()),
+        Token::Array(vec![]),
+        Token::Address(receiver),
+    ]);
+    let calldata = BRIDGEHUB
+        .abi()
+        .function("requestL2TransactionDirect")?
+        .encode_input(&[request])?;
+    let tx = TransactionRequest::new()
+        .to(bridgehub)
+        .gas_price(gas_price)
+        .data(calldata);
+    let receipt = client.send_transaction(tx, None).await?.await?;
+    check_receipt(receipt, "deposit")
+}
+
+/// Deposits the base token to the chain governor, waits until it arrives on L2 and withdraws
+/// half of it back to L1.
+#[allow(clippy::too_many_arguments)]
+async fn smoke_test(
+    chain_config: &ChainConfig,
+    contracts: &ContractsConfig,
+    l1_wallet: &Wallet,
+    l2_wallet: &Wallet,
+    l1_rpc_url: &str,
+    l1_chain_id: u64,
+    base_token: Address,
+    amount: U256,
+) -> anyhow::Result<()> {
+    let l2_rpc_url = chain_config.get_general_config()?.get_l2_rpc_url()?;
+    let l2_provider = Provider::new(Http::new(l2_rpc_url.clone()));
+
+    let spinner = Spinner::new(MSG_BASE_TOKEN_SMOKE_DEPOSIT_SPINNER);
+    let balance_before = l2_provider.get_balance(l2_wallet.address, None).await?;
+    deposit(
+        chain_config,
+        contracts,
+        l1_wallet,
+        l1_rpc_url,
+        l1_chain_id,
+        base_token,
+        amount,
+        l2_wallet.address,
+    )
+    .await?;
+    let expected_balance = balance_before + amount;
+    tokio::time::timeout(DEPOSIT_TIMEOUT, async {
+        loop {
+            let balance = l2_provider.get_balance(l2_wallet.address, None).await?;
+            if balance >= expected_balance {
+                return anyhow::Ok(());
+            }
+            tokio::time::sleep(DEPOSIT_POLL_INTERVAL).await;
+        }
+    })
+    .await
+    .context(MSG_BASE_TOKEN_DEPOSIT_TIMEOUT_ERR)??;
+    spinner.finish();
+
+    let spinner = Spinner::new(MSG_BASE_TOKEN_SMOKE_WITHDRAWAL_SPINNER);
+    let client = common::ethereum::create_ethers_client(
+        l2_wallet.local_signer()?,
+        l2_rpc_url.to_string(),
+        Some(chain_config.chain_id.as_u64()),
+    )?;
+    let tx = TransactionRequest::new()
+        .to(L2_BASE_TOKEN_ADDRESS)
+        .value(amount / 2)
+        .data(L2_BASE_TOKEN.encode("withdraw", l1_wallet.address)?);
+    let receipt = client.send_transaction(tx, None).await?.await?;
+    let tx_hash = receipt.as_ref().map(|receipt| receipt.transaction_hash);
+    check_receipt(receipt, "withdraw")?;
+    spinner.finish();
+    if let Some(tx_hash) = tx_hash {
+        logger::success(msg_base_token_withdrawal_sent(&tx_hash));
+    }
+    Ok(())
+}
+
+fn check_receipt(receipt: Option<TransactionReceipt>, tx_name: &str) -> anyhow::Result<()> {
+    let receipt = receipt.with_context(|| format!("{tx_name} transaction was dropped"))?;
+    anyhow::ensure!(
+        receipt.status == Some(1.into()),
+        "{tx_name} transaction failed, tx_hash={:?}",
+        receipt.transaction_hash
+    );
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use clap::Parser;
+use common::forge::ForgeScriptArgs;
+use ethers::types::Address;
+
+use crate::commands::dev::messages::{
+    MSG_DEPLOY_ERC20_AMOUNT_HELP, MSG_DEPLOY_ERC20_BRIDGE_HELP, MSG_DEPLOY_ERC20_L1_RPC_URL_HELP,
+    MSG_DEPLOY_ERC20_RECEIVER_HELP,
+};
+
+#[derive(Debug, Parser)]
+pub struct DeployErc20Args {
+    #[clap(long, help = MSG_DEPLOY_ERC20_BRIDGE_HELP)]
+    pub bridge: bool,
+    #[clap(long, default_value = "100", requires = "bridge", help = MSG_DEPLOY_ERC20_AMOUNT_HELP)]
+    pub amount: String,
+    #[clap(long, requires = "bridge", help = MSG_DEPLOY_ERC20_RECEIVER_HELP)]
+    pub receiver: Option<Address>,
+    #[clap(long, help = MSG_DEPLOY_ERC20_L1_RPC_URL_HELP)]
+    pub l1_rpc_url: Option<String>,
+    /// All ethereum environment related arguments
+    #[clap(flatten)]
+    pub forge_args: ForgeScriptArgs,
+}
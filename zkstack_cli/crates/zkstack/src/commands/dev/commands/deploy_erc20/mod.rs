@@ -0,0 +1,237 @@
+use anyhow::Context;
+use args::DeployErc20Args;
+use common::{logger, spinner::Spinner, wallets::Wallet};
+use config::{
+    traits::{ConfigWithL2RpcUrl, SaveConfigWithBasePath},
+    BridgedToken, ChainConfig, ContractsConfig, EcosystemConfig,
+};
+use ethers::{
+    abi::{encode, parse_abi, Token},
+    contract::BaseContract,
+    providers::{Http, Middleware, Provider},
+    types::{Address, TransactionReceipt, TransactionRequest, U256},
+    utils::parse_units,
+};
+use lazy_static::lazy_static;
+use types::BaseToken;
+use xshell::Shell;
+
+use crate::commands::{
+    dev::messages::{
+        msg_deploy_erc20_bridging_spinner, msg_deploy_erc20_tokens_saved, MSG_CHAIN_NOT_FOUND_ERR,
+        MSG_DEPLOY_ERC20_CUSTOM_BASE_TOKEN_ERR,
+    },
+    ecosystem::{create_configs::create_erc20_deployment_config, init::deploy_erc20},
+};
+
+pub mod args;
+
+/// Gas limit of the deposits on L2, enough to deploy the bridged token on the first deposit.
+const DEPOSIT_L2_GAS_LIMIT: u64 = 10_000_000;
+const REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_LIMIT: u64 = 800;
+
+lazy_static! {
+    static ref BRIDGEHUB: BaseContract = BaseContract::from(
+        parse_abi(&[
+            "function requestL2TransactionTwoBridges((uint256,uint256,uint256,uint256,uint256,address,address,uint256,bytes) _request) external payable returns (bytes32)",
+            "function l2TransactionBaseCost(uint256 _chainId, uint256 _gasPrice, uint256 _l2GasLimit, uint256 _l2GasPerPubdataByteLimit) external view returns (uint256)",
+        ])
+        .unwrap(),
+    );
+    static ref ERC20: BaseContract = BaseContract::from(
+        parse_abi(&["function approve(address spender, uint256 amount) external returns (bool)"])
+            .unwrap(),
+    );
+    static ref L2_SHARED_BRIDGE: BaseContract = BaseContract::from(
+        parse_abi(&["function l2TokenAddress(address _l1Token) external view returns (address)"])
+            .unwrap(),
+    );
+}
+
+pub async fn run(shell: &Shell, args: DeployErc20Args) -> anyhow::Result<()> {
+    let ecosystem_config = EcosystemConfig::from_file(shell)?;
+    let chain_config = ecosystem_config
+        .load_current_chain()
+        .context(MSG_CHAIN_NOT_FOUND_ERR)?;
+    let l1_rpc_url = match args.l1_rpc_url {
+        Some(url) => url,
+        None => chain_config
+            .get_secrets_config()?
+            .l1
+            .context("No L1 secrets available")?
+            .l1_rpc_url
+            .expose_str()
+            .to_string(),
+    };
+
+    let erc20_deployment_config = match ecosystem_config.get_erc20_deployment_config() {
+        Ok(config) => config,
+        Err(_) => create_erc20_deployment_config(shell, &ecosystem_config.config)?,
+    };
+    let deployed = deploy_erc20(
+        shell,
+        &erc20_deployment_config,
+        &ecosystem_config,
+        &ecosystem_config.get_contracts_config()?,
+        args.forge_args,
+        l1_rpc_url.clone(),
+    )
+    .await?;
+
+    let mut tokens_config = chain_config.get_tokens_config()?;
+    for token in deployed.tokens.values() {
+        tokens_config.tokens.insert(
+            token.symbol.clone(),
+            BridgedToken {
+                name: token.name.clone(),
+                symbol: token.symbol.clone(),
+                decimals: token.decimals,
+                l1_address: token.address,
+                l2_address: None,
+            },
+        );
+    }
+
+    if args.bridge {
+        anyhow::ensure!(
+            chain_config.base_token == BaseToken::eth(),
+            MSG_DEPLOY_ERC20_CUSTOM_BASE_TOKEN_ERR
+        );
+        // Deployed tokens are minted to the ecosystem governor.
+        let governor = ecosystem_config.get_wallets()?.governor;
+        let receiver = args.receiver.unwrap_or(governor.address);
+        let contracts = chain_config.get_contracts_config()?;
+        let l2_rpc_url = chain_config.get_general_config()?.get_l2_rpc_url()?;
+        let l2_provider = Provider::new(Http::new(l2_rpc_url));
+        let l2_shared_bridge = contracts
+            .bridges
+            .shared
+            .l2_address
+            .context("L2 shared bridge is not deployed")?;
+
+        // Only the tokens deployed by this run are bridged; the tokens config may contain other tokens.
+        for token in deployed.tokens.values() {
+            let spinner = Spinner::new(&msg_deploy_erc20_bridging_spinner(&token.symbol));
+            let amount = parse_units(&args.amount, token.decimals as u32)?.into();
+            deposit(
+                &ecosystem_config,
+                &chain_config,
+                &contracts,
+                &governor,
+                &l1_rpc_url,
+                token.address,
+                amount,
+                receiver,
+            )
+            .await?;
+            let l2_address =
+                l2_token_address(&l2_provider, l2_shared_bridge, token.address).await?;
+            if let Some(bridged_token) = tokens_config.tokens.get_mut(&token.symbol) {
+                bridged_token.l2_address = Some(l2_address);
+            }
+            spinner.finish();
+        }
+    }
+
+    tokens_config.save_with_base_path(shell, &chain_config.configs)?;
+    logger::output(&tokens_config)?;
+    logger::success(msg_deploy_erc20_tokens_saved(
+        &chain_config.path_to_tokens_config(),
+    ));
+    Ok(())
+}
+
+/// Deposits `amount` of an L1 token to `receiver` on L2 through the shared bridge.
+#[allow(clippy::too_many_arguments)]
+async fn deposit(
+    ecosystem_config: &EcosystemConfig,
+    chain_config: &ChainConfig,
+    contracts: &ContractsConfig,
+    wallet: &Wallet,
+    l1_rpc_url: &str,
+    token: Address,
+    amount: U256,
+    receiver: Address,
+) -> anyhow::Result<()> {
+    let client = common::ethereum::create_ethers_client(
+        wallet.local_signer()?,
+        l1_rpc_url.to_string(),
+        Some(ecosystem_config.l1_network.chain_id()),
+    )?;
+    let bridgehub = contracts.ecosystem_contracts.bridgehub_proxy_addr;
+    let l1_shared_bridge = contracts.bridges.shared.l1_address;
+    let chain_id = U256::from(chain_config.chain_id.as_u64());
+
+    let approve = TransactionRequest::new()
+        .to(token)
+        .data(ERC20.encode("approve", (l1_shared_bridge, amount))?);
+    let receipt = client.send_transaction(approve, None).await?.await?;
+    check_receipt(receipt, "approve")?;
+
+    let gas_price = client.get_gas_price().await?;
+    let base_cost_call = TransactionRequest::new()
+        .to(bridgehub)
+        .data(BRIDGEHUB.encode(
+            "l2TransactionBaseCost",
+            (
+                chain_id,
+                gas_price,
+                U256::from(DEPOSIT_L2_GAS_LIMIT),
+                U256::from(REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_LIMIT),
+            ),
+        )?);
+    let base_cost: U256 = BRIDGEHUB.decode_output(
+        "l2TransactionBaseCost",
+        client.call(&base_cost_call.into(), None).await?,
+    )?;
+
+    let second_bridge_calldata = encode(&[
+        Token::Address(token),
+        Token::Uint(amount),
+        Token::Address(receiver),
+    ]);
+    let request = Token::Tuple(vec![
+        Token::Uint(chain_id),
+        Token::Uint(base_cost),
+        Token::Uint(U256::zero()),
+        Token::Uint(DEPOSIT_L2_GAS_LIMIT.into()),
+        Token::Uint(REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_LIMIT.into()),
+        Token::Address(receiver),
+        Token::Address(l1_shared_bridge),
+        Token::Uint(U256::zero()),
+        Token::Bytes(second_bridge_calldata),
+    ]);
+    let calldata = BRIDGEHUB
+        .abi()
+        .function("requestL2TransactionTwoBridges")?
+        .encode_input(&[request])?;
+    let tx = TransactionRequest::new()
+        .to(bridgehub)
+        .value(base_cost)
+        .gas_price(gas_price)
+        .data(calldata);
+    let receipt = client.send_transaction(tx, None).await?.await?;
+    check_receipt(receipt, "deposit")
+}
+
+fn check_receipt(receipt: Option<TransactionReceipt>, tx_name: &str) -> anyhow::Result<()> {
+    let receipt = receipt.with_context(|| format!("{tx_name} transaction was dropped"))?;
+    anyhow::ensure!(
+        receipt.status == Some(1.into()),
+        "{tx_name} transaction failed, tx_hash={:?}",
+        receipt.transaction_hash
+    );
+    Ok(())
+}
+
+async fn l2_token_address(
+    provider: &Provider<Http>,
+    l2_shared_bridge: Address,
+    l1_token: Address,
+) -> anyhow::Result<Address> {
+    let call = TransactionRequest::new()
+        .to(l2_shared_bridge)
+        .data(L2_SHARED_BRIDGE.encode("l2TokenAddress", l1_token)?);
+    let output = provider.call(&call.into(), None).await?;
+    Ok(L2_SHARED_BRIDGE.decode_output("l2TokenAddress", output)?)
+}
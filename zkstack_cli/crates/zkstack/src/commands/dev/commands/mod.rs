@@ -1,12 +1,15 @@
+pub mod base_token;
 pub mod clean;
 pub mod config_writer;
 pub mod contracts;
 pub mod database;
+pub mod deploy_erc20;
 pub mod fmt;
 pub mod genesis;
 pub mod lint;
 pub(crate) mod lint_utils;
 pub mod prover;
+pub mod rich_accounts;
 pub mod send_transactions;
 pub mod snapshot;
 pub(crate) mod sql_fmt;
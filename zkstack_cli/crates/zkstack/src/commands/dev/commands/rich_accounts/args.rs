@@ -0,0 +1,30 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use ethers::types::Address;
+
+use crate::commands::dev::messages::{
+    MSG_RICH_ACCOUNTS_ADDRESSES_HELP, MSG_RICH_ACCOUNTS_AMOUNT_HELP, MSG_RICH_ACCOUNTS_COUNT_HELP,
+    MSG_RICH_ACCOUNTS_ERA_TEST_NODE_HELP, MSG_RICH_ACCOUNTS_ERA_TEST_NODE_URL_HELP,
+    MSG_RICH_ACCOUNTS_FILE_HELP, MSG_RICH_ACCOUNTS_L1_ONLY_HELP,
+};
+
+const DEFAULT_ERA_TEST_NODE_URL: &str = "http://127.0.0.1:8011";
+
+#[derive(Debug, Parser)]
+pub struct RichAccountsArgs {
+    #[clap(long, value_delimiter = ',', help = MSG_RICH_ACCOUNTS_ADDRESSES_HELP)]
+    pub addresses: Vec<Address>,
+    #[clap(long, help = MSG_RICH_ACCOUNTS_FILE_HELP)]
+    pub addresses_file: Option<PathBuf>,
+    #[clap(long, default_value_t = 10, help = MSG_RICH_ACCOUNTS_COUNT_HELP)]
+    pub count: u32,
+    #[clap(long, default_value = "100", help = MSG_RICH_ACCOUNTS_AMOUNT_HELP)]
+    pub amount: String,
+    #[clap(long, conflicts_with = "era_test_node", help = MSG_RICH_ACCOUNTS_L1_ONLY_HELP)]
+    pub l1_only: bool,
+    #[clap(long, help = MSG_RICH_ACCOUNTS_ERA_TEST_NODE_HELP)]
+    pub era_test_node: bool,
+    #[clap(long, default_value = DEFAULT_ERA_TEST_NODE_URL, help = MSG_RICH_ACCOUNTS_ERA_TEST_NODE_URL_HELP)]
+    pub era_test_node_url: String,
+}
@@ -0,0 +1,228 @@
+use std::str::FromStr;
+
+use anyhow::Context;
+use args::RichAccountsArgs;
+use common::{logger, spinner::Spinner, wallets::Wallet};
+use config::{ChainConfig, EcosystemConfig};
+use ethers::{
+    abi::{parse_abi, Token},
+    contract::BaseContract,
+    providers::{Http, Middleware, Provider},
+    types::{Address, TransactionRequest, U256},
+    utils::parse_ether,
+};
+use lazy_static::lazy_static;
+use types::BaseToken;
+use xshell::Shell;
+
+use crate::commands::dev::{
+    commands::test::utils::{TestWallets, TEST_WALLETS_PATH},
+    messages::{
+        msg_rich_accounts_funded, msg_rich_accounts_invalid_address_err, MSG_CHAIN_NOT_FOUND_ERR,
+        MSG_DESERIALIZE_TEST_WALLETS_ERR, MSG_RICH_ACCOUNTS_AMOUNT_TOO_LARGE_ERR,
+        MSG_RICH_ACCOUNTS_CUSTOM_BASE_TOKEN_ERR, MSG_RICH_ACCOUNTS_DEPOSITING_SPINNER,
+        MSG_RICH_ACCOUNTS_FUNDING_L1_SPINNER, MSG_RICH_ACCOUNTS_INVALID_AMOUNT_ERR,
+        MSG_RICH_ACCOUNTS_SETTING_BALANCES_SPINNER,
+    },
+};
+
+pub mod args;
+
+/// Gas limit of the deposit transactions on L2, enough for a plain transfer.
+const DEPOSIT_L2_GAS_LIMIT: u64 = 1_000_000;
+const REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_LIMIT: u64 = 800;
+
+lazy_static! {
+    static ref BRIDGEHUB: BaseContract = BaseContract::from(
+        parse_abi(&[
+            "function requestL2TransactionDirect((uint256,uint256,address,uint256,bytes,uint256,uint256,bytes[],address) _request) external payable returns (bytes32)",
+            "function l2TransactionBaseCost(uint256 _chainId, uint256 _gasPrice, uint256 _l2GasLimit, uint256 _l2GasPerPubdataByteLimit) external view returns (uint256)",
+        ])
+        .unwrap(),
+    );
+}
+
+pub async fn run(shell: &Shell, args: RichAccountsArgs) -> anyhow::Result<()> {
+    let ecosystem_config = EcosystemConfig::from_file(shell)?;
+    let amount = parse_ether(&args.amount).context(MSG_RICH_ACCOUNTS_INVALID_AMOUNT_ERR)?;
+
+    let wallets_path = ecosystem_config.link_to_code.join(TEST_WALLETS_PATH);
+    let test_wallets: TestWallets = serde_json::from_str(shell.read_file(&wallets_path)?.as_ref())
+        .context(MSG_DESERIALIZE_TEST_WALLETS_ERR)?;
+    let addresses = addresses(shell, &args, &test_wallets)?;
+
+    if args.era_test_node {
+        let spinner = Spinner::new(MSG_RICH_ACCOUNTS_SETTING_BALANCES_SPINNER);
+        set_era_test_node_balances(&args.era_test_node_url, &addresses, amount).await?;
+        spinner.finish();
+        logger::success(msg_rich_accounts_funded(addresses.len(), &args.amount));
+        return Ok(());
+    }
+
+    let chain_config = ecosystem_config
+        .load_current_chain()
+        .context(MSG_CHAIN_NOT_FOUND_ERR)?;
+    let l1_rpc_url = chain_config
+        .get_secrets_config()?
+        .l1
+        .context("No L1 secrets available")?
+        .l1_rpc_url
+        .expose_str()
+        .to_owned();
+    let main_wallet = test_wallets.get_main_wallet()?;
+    // L1 funding amount is passed as `u128`.
+    let l1_amount = if amount <= U256::from(u128::MAX) {
+        amount.as_u128()
+    } else {
+        anyhow::bail!(MSG_RICH_ACCOUNTS_AMOUNT_TOO_LARGE_ERR);
+    };
+
+    let spinner = Spinner::new(MSG_RICH_ACCOUNTS_FUNDING_L1_SPINNER);
+    common::ethereum::distribute_eth(
+        main_wallet.clone(),
+        addresses.clone(),
+        l1_rpc_url.clone(),
+        ecosystem_config.l1_network.chain_id(),
+        l1_amount,
+    )
+    .await?;
+    spinner.finish();
+
+    if !args.l1_only {
+        let spinner = Spinner::new(MSG_RICH_ACCOUNTS_DEPOSITING_SPINNER);
+        deposit(
+            &ecosystem_config,
+            &chain_config,
+            main_wallet,
+            &addresses,
+            amount,
+            l1_rpc_url,
+        )
+        .await?;
+        spinner.finish();
+    }
+
+    logger::success(msg_rich_accounts_funded(addresses.len(), &args.amount));
+    Ok(())
+}
+
+/// Addresses passed through the arguments, or the rich wallets of the test mnemonic.
+fn addresses(
+    shell: &Shell,
+    args: &RichAccountsArgs,
+    test_wallets: &TestWallets,
+) -> anyhow::Result<Vec<Address>> {
+    let mut addresses = args.addresses.clone();
+    if let Some(path) = &args.addresses_file {
+        for line in shell.read_file(path)?.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let address = Address::from_str(line)
+                .with_context(|| msg_rich_accounts_invalid_address_err(line))?;
+            addresses.push(address);
+        }
+    }
+    if addresses.is_empty() {
+        addresses = test_wallets
+            .get_rich_wallets(args.count)?
+            .into_iter()
+            .map(|wallet| wallet.address)
+            .collect();
+    }
+    Ok(addresses)
+}
+
+/// Deposits `amount` to every address with direct L2 transactions from the main wallet.
+async fn deposit(
+    ecosystem_config: &EcosystemConfig,
+    chain_config: &ChainConfig,
+    main_wallet: Wallet,
+    addresses: &[Address],
+    amount: U256,
+    l1_rpc_url: String,
+) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        chain_config.base_token == BaseToken::eth(),
+        MSG_RICH_ACCOUNTS_CUSTOM_BASE_TOKEN_ERR
+    );
+    let bridgehub = chain_config
+        .get_contracts_config()?
+        .ecosystem_contracts
+        .bridgehub_proxy_addr;
+    let chain_id = chain_config.chain_id.as_u64();
+    let client = common::ethereum::create_ethers_client(
+        main_wallet.local_signer()?,
+        l1_rpc_url,
+        Some(ecosystem_config.l1_network.chain_id()),
+    )?;
+
+    let gas_price = client.get_gas_price().await?;
+    let base_cost_call = TransactionRequest::new()
+        .to(bridgehub)
+        .data(BRIDGEHUB.encode(
+            "l2TransactionBaseCost",
+            (
+                U256::from(chain_id),
+                gas_price,
+                U256::from(DEPOSIT_L2_GAS_LIMIT),
+                U256::from(REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_LIMIT),
+            ),
+        )?);
+    let base_cost: U256 = BRIDGEHUB.decode_output(
+        "l2TransactionBaseCost",
+        client.call(&base_cost_call.into(), None).await?,
+    )?;
+
+    for address in addresses {
+        let mint_value = amount
+            .checked_add(base_cost)
+            .context(MSG_RICH_ACCOUNTS_AMOUNT_TOO_LARGE_ERR)?;
+        let request = Token::Tuple(vec![
+            Token::Uint(chain_id.into()),
+            Token::Uint(mint_value),
+            Token::Address(*address),
+            Token::Uint(amount),
+            Token::Bytes(vec![]),
+            Token::Uint(DEPOSIT_L2_GAS_LIMIT.into()),
+            Token::Uint(REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_LIMIT.into()),
+            Token::Array(vec![]),
+            Token::Address(*address),
+        ]);
+        let calldata = BRIDGEHUB
+            .abi()
+            .function("requestL2TransactionDirect")?
+            .encode_input(&[request])?;
+        let tx = TransactionRequest::new()
+            .to(bridgehub)
+            .value(mint_value)
+            .gas_price(gas_price)
+            .data(calldata);
+        let receipt = client
+            .send_transaction(tx, None)
+            .await?
+            .await?
+            .context("Deposit transaction was dropped")?;
+        anyhow::ensure!(
+            receipt.status == Some(1.into()),
+            "Deposit to {address:#x} failed, tx_hash={:?}",
+            receipt.transaction_hash
+        );
+    }
+    Ok(())
+}
+
+async fn set_era_test_node_balances(
+    url: &str,
+    addresses: &[Address],
+    amount: U256,
+) -> anyhow::Result<()> {
+    let provider = Provider::<Http>::try_from(url)?;
+    for address in addresses {
+        let _: bool = provider
+            .request("hardhat_setBalance", (address, amount))
+            .await?;
+    }
+    Ok(())
+}
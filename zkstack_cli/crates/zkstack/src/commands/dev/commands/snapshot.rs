@@ -1,10 +1,9 @@
 use anyhow::Context;
 use clap::Subcommand;
-use common::{cmd::Cmd, logger};
 use config::EcosystemConfig;
-use xshell::{cmd, Shell};
+use xshell::Shell;
 
-use crate::commands::dev::messages::{MSG_CHAIN_NOT_FOUND_ERR, MSG_RUNNING_SNAPSHOT_CREATOR};
+use crate::commands::{chain::snapshot, dev::messages::MSG_CHAIN_NOT_FOUND_ERR};
 
 #[derive(Subcommand, Debug)]
 pub enum SnapshotCommands {
@@ -27,14 +26,5 @@ async fn create(shell: &Shell) -> anyhow::Result<()> {
         .load_current_chain()
         .context(MSG_CHAIN_NOT_FOUND_ERR)?;
 
-    let config_path = chain.path_to_general_config();
-    let secrets_path = chain.path_to_secrets_config();
-
-    logger::info(MSG_RUNNING_SNAPSHOT_CREATOR);
-
-    let mut cmd = Cmd::new(cmd!(shell, "cargo run --bin snapshots_creator --release -- --config-path={config_path} --secrets-path={secrets_path}"))
-        .env("RUST_LOG", "snapshots_creator=debug");
-
-    cmd = cmd.with_force_run();
-    cmd.run().context("Snapshot")
+    snapshot::create(shell, &chain)
 }
@@ -4,7 +4,7 @@ use anyhow::Context;
 use args::{StatusArgs, StatusSubcommands};
 use common::logger;
 use draw::{bordered_boxes, format_port_info};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use utils::deslugify;
 use xshell::Shell;
@@ -23,13 +23,13 @@ mod utils;
 
 const STATUS_READY: &str = "ready";
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 struct StatusResponse {
     status: String,
     components: HashMap<String, Component>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 struct Component {
     status: String,
     details: Option<Value>,
@@ -41,6 +41,7 @@ fn print_status(health_check_url: String) -> anyhow::Result<()> {
 
     let status_response: StatusResponse =
         serde_json::from_str(&response).context(msg_failed_parse_response(&response))?;
+    logger::output(&status_response)?;
 
     if status_response.status.to_lowercase() == STATUS_READY {
         logger::success(msg_system_status(&status_response.status));
@@ -93,8 +94,9 @@ fn print_status(health_check_url: String) -> anyhow::Result<()> {
 }
 
 fn print_ports(shell: &Shell) -> anyhow::Result<()> {
-    let ports = EcosystemPortsScanner::scan(shell)?;
+    let ports = EcosystemPortsScanner::scan(shell, None)?;
     let grouped_ports = ports.group_by_file_path();
+    logger::output(&grouped_ports)?;
 
     let mut all_port_lines: Vec<String> = Vec::new();
 
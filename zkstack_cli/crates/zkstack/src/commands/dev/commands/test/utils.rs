@@ -36,6 +36,11 @@ impl TestWallets {
         self.get(0)
     }
 
+    /// Wallets derived from the test mnemonic after the main one.
+    pub fn get_rich_wallets(&self, count: u32) -> anyhow::Result<Vec<Wallet>> {
+        (1..=count).map(|id| self.get(id)).collect()
+    }
+
     pub fn get_test_wallet(&self, chain_config: &ChainConfig) -> anyhow::Result<Wallet> {
         self.get(chain_config.id)
     }
@@ -1,3 +1,7 @@
+use std::path::Path;
+
+use ethers::types::{Address, H256};
+
 use super::commands::lint_utils::Target;
 
 // Ecosystem related messages
@@ -161,9 +165,6 @@ pub(super) const MSG_CONTRACTS_CLEANING: &str =
 pub(super) const MSG_CONTRACTS_CLEANING_FINISHED: &str =
     "Contracts building and deployment artifacts are cleaned up";
 
-/// Snapshot creator related messages
-pub(super) const MSG_RUNNING_SNAPSHOT_CREATOR: &str = "Running snapshot creator";
-
 // Lint related messages
 pub(super) fn msg_running_linters_for_files(targets: &[Target]) -> String {
     let targets: Vec<String> = targets.iter().map(|e| format!(".{}", e)).collect();
@@ -253,3 +254,99 @@ pub(super) fn msg_not_ready_components(components: &str) -> String {
 
 // Genesis
 pub(super) const MSG_GENESIS_FILE_GENERATION_STARTED: &str = "Regenerate genesis file";
+
+// Rich accounts related messages
+pub(super) const MSG_RICH_ACCOUNTS_ABOUT: &str =
+    "Fund addresses with ETH on L1 and L2, by default the rich wallets of the test mnemonic";
+pub(super) const MSG_RICH_ACCOUNTS_ADDRESSES_HELP: &str = "Addresses to fund, comma separated";
+pub(super) const MSG_RICH_ACCOUNTS_FILE_HELP: &str = "File with addresses to fund, one per line";
+pub(super) const MSG_RICH_ACCOUNTS_COUNT_HELP: &str =
+    "Number of test mnemonic wallets to fund when no addresses are provided";
+pub(super) const MSG_RICH_ACCOUNTS_AMOUNT_HELP: &str = "Amount of ETH to fund each address with";
+pub(super) const MSG_RICH_ACCOUNTS_L1_ONLY_HELP: &str = "Fund addresses only on L1";
+pub(super) const MSG_RICH_ACCOUNTS_ERA_TEST_NODE_HELP: &str =
+    "Set balances directly on an era-test-node instead of funding through L1";
+pub(super) const MSG_RICH_ACCOUNTS_ERA_TEST_NODE_URL_HELP: &str = "URL of the era-test-node";
+pub(super) const MSG_RICH_ACCOUNTS_FUNDING_L1_SPINNER: &str = "Funding addresses on L1...";
+pub(super) const MSG_RICH_ACCOUNTS_DEPOSITING_SPINNER: &str = "Depositing to addresses on L2...";
+pub(super) const MSG_RICH_ACCOUNTS_SETTING_BALANCES_SPINNER: &str =
+    "Setting balances on era-test-node...";
+pub(super) const MSG_RICH_ACCOUNTS_CUSTOM_BASE_TOKEN_ERR: &str =
+    "Deposits are only supported for chains with ETH as the base token";
+pub(super) const MSG_RICH_ACCOUNTS_INVALID_AMOUNT_ERR: &str = "Invalid amount of ETH";
+pub(super) const MSG_RICH_ACCOUNTS_AMOUNT_TOO_LARGE_ERR: &str = "Amount of ETH is too large";
+
+pub(super) fn msg_rich_accounts_funded(count: usize, amount: &str) -> String {
+    format!("Funded {count} addresses with {amount} ETH")
+}
+
+pub(super) fn msg_rich_accounts_invalid_address_err(line: &str) -> String {
+    format!("Invalid address in addresses file: {line}")
+}
+
+// Deploy ERC20 related messages
+pub(super) const MSG_DEPLOY_ERC20_ABOUT: &str =
+    "Deploy test ERC20 tokens on L1 and optionally bridge them to the chain";
+pub(super) const MSG_DEPLOY_ERC20_BRIDGE_HELP: &str =
+    "Register the tokens with the shared bridge by depositing them to L2";
+pub(super) const MSG_DEPLOY_ERC20_AMOUNT_HELP: &str =
+    "Amount of each token to deposit to L2, in token units";
+pub(super) const MSG_DEPLOY_ERC20_RECEIVER_HELP: &str =
+    "L2 receiver of the deposits, defaults to the ecosystem governor";
+pub(super) const MSG_DEPLOY_ERC20_L1_RPC_URL_HELP: &str =
+    "L1 RPC URL, defaults to the one from the chain secrets";
+pub(super) const MSG_DEPLOY_ERC20_CUSTOM_BASE_TOKEN_ERR: &str =
+    "Bridging tokens is only supported for chains with ETH as the base token";
+
+pub(super) fn msg_deploy_erc20_bridging_spinner(symbol: &str) -> String {
+    format!("Bridging {symbol} to L2...")
+}
+
+pub(super) fn msg_deploy_erc20_tokens_saved(path: &Path) -> String {
+    format!("Tokens config saved to {}", path.display())
+}
+
+// Base token related messages
+pub(super) const MSG_BASE_TOKEN_ABOUT: &str =
+    "Verify the custom base token of the chain, set its conversion ratio, fund wallets through the shared bridge and smoke test deposits and withdrawals";
+pub(super) const MSG_BASE_TOKEN_RECEIVERS_HELP: &str =
+    "L2 addresses to fund with the base token, comma separated, defaults to the chain governor";
+pub(super) const MSG_BASE_TOKEN_AMOUNT_HELP: &str =
+    "Amount of the base token to deposit to each receiver, in token units";
+pub(super) const MSG_BASE_TOKEN_SKIP_RATIO_HELP: &str =
+    "Do not set the base token conversion ratio on L1";
+pub(super) const MSG_BASE_TOKEN_SKIP_SMOKE_TEST_HELP: &str =
+    "Do not run the deposit and withdrawal smoke test";
+pub(super) const MSG_BASE_TOKEN_L1_RPC_URL_HELP: &str =
+    "L1 RPC URL, defaults to the one from the chain secrets";
+pub(super) const MSG_BASE_TOKEN_ETH_ERR: &str =
+    "The chain uses ETH as the base token, nothing to do";
+pub(super) const MSG_BASE_TOKEN_NO_MULTIPLIER_SETTER_ERR: &str =
+    "Token multiplier setter wallet is not configured, run `zkstack chain set-token-multiplier-setter` first";
+pub(super) const MSG_BASE_TOKEN_VERIFYING_SPINNER: &str = "Verifying base token contract on L1...";
+pub(super) const MSG_BASE_TOKEN_SETTING_RATIO_SPINNER: &str =
+    "Setting base token conversion ratio...";
+pub(super) const MSG_BASE_TOKEN_MINTING_SPINNER: &str = "Minting base token on L1...";
+pub(super) const MSG_BASE_TOKEN_FUNDING_SPINNER: &str = "Depositing base token to L2 receivers...";
+pub(super) const MSG_BASE_TOKEN_SMOKE_DEPOSIT_SPINNER: &str =
+    "Smoke test: waiting for the base token deposit on L2...";
+pub(super) const MSG_BASE_TOKEN_SMOKE_WITHDRAWAL_SPINNER: &str =
+    "Smoke test: withdrawing base token from L2...";
+pub(super) const MSG_BASE_TOKEN_DEPOSIT_TIMEOUT_ERR: &str =
+    "Base token deposit was not processed on L2 in time";
+pub(super) const MSG_BASE_TOKEN_AMOUNT_TOO_LARGE_ERR: &str =
+    "Base token amount to mint for all deposits is too large";
+
+pub(super) fn msg_base_token_no_code_err(address: &Address) -> String {
+    format!("No contract is deployed at the base token address {address:#x}")
+}
+
+pub(super) fn msg_base_token_verified(symbol: &str, decimals: u8) -> String {
+    format!("Base token {symbol} with {decimals} decimals is deployed on L1")
+}
+
+pub(super) fn msg_base_token_withdrawal_sent(tx_hash: &H256) -> String {
+    format!(
+        "Withdrawal {tx_hash:#x} is included on L2, it can be finalized on L1 once its batch is executed"
+    )
+}
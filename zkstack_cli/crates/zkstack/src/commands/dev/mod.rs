@@ -4,15 +4,17 @@ use messages::MSG_STATUS_ABOUT;
 use xshell::Shell;
 
 use self::commands::{
-    clean::CleanCommands, config_writer::ConfigWriterArgs, contracts::ContractsArgs,
-    database::DatabaseCommands, fmt::FmtArgs, lint::LintArgs, prover::ProverCommands,
+    base_token::args::BaseTokenArgs, clean::CleanCommands, config_writer::ConfigWriterArgs,
+    contracts::ContractsArgs, database::DatabaseCommands, deploy_erc20::args::DeployErc20Args,
+    fmt::FmtArgs, lint::LintArgs, prover::ProverCommands, rich_accounts::args::RichAccountsArgs,
     send_transactions::args::SendTransactionsArgs, snapshot::SnapshotCommands, test::TestCommands,
 };
 use crate::commands::dev::messages::{
-    MSG_CONFIG_WRITER_ABOUT, MSG_CONTRACTS_ABOUT, MSG_GENERATE_GENESIS_ABOUT,
-    MSG_PROVER_VERSION_ABOUT, MSG_SEND_TXNS_ABOUT, MSG_SUBCOMMAND_CLEAN,
-    MSG_SUBCOMMAND_DATABASE_ABOUT, MSG_SUBCOMMAND_FMT_ABOUT, MSG_SUBCOMMAND_LINT_ABOUT,
-    MSG_SUBCOMMAND_SNAPSHOTS_CREATOR_ABOUT, MSG_SUBCOMMAND_TESTS_ABOUT,
+    MSG_BASE_TOKEN_ABOUT, MSG_CONFIG_WRITER_ABOUT, MSG_CONTRACTS_ABOUT, MSG_DEPLOY_ERC20_ABOUT,
+    MSG_GENERATE_GENESIS_ABOUT, MSG_PROVER_VERSION_ABOUT, MSG_RICH_ACCOUNTS_ABOUT,
+    MSG_SEND_TXNS_ABOUT, MSG_SUBCOMMAND_CLEAN, MSG_SUBCOMMAND_DATABASE_ABOUT,
+    MSG_SUBCOMMAND_FMT_ABOUT, MSG_SUBCOMMAND_LINT_ABOUT, MSG_SUBCOMMAND_SNAPSHOTS_CREATOR_ABOUT,
+    MSG_SUBCOMMAND_TESTS_ABOUT,
 };
 
 mod commands;
@@ -45,6 +47,12 @@ pub enum DevCommands {
     SendTransactions(SendTransactionsArgs),
     #[command(about = MSG_STATUS_ABOUT)]
     Status(StatusArgs),
+    #[command(about = MSG_RICH_ACCOUNTS_ABOUT, alias = "rich")]
+    RichAccounts(RichAccountsArgs),
+    #[command(about = MSG_DEPLOY_ERC20_ABOUT)]
+    DeployErc20(DeployErc20Args),
+    #[command(about = MSG_BASE_TOKEN_ABOUT)]
+    BaseToken(BaseTokenArgs),
     #[command(about = MSG_GENERATE_GENESIS_ABOUT, alias = "genesis")]
     GenerateGenesis,
 }
@@ -64,6 +72,9 @@ pub async fn run(shell: &Shell, args: DevCommands) -> anyhow::Result<()> {
             commands::send_transactions::run(shell, args).await?
         }
         DevCommands::Status(args) => commands::status::run(shell, args).await?,
+        DevCommands::RichAccounts(args) => commands::rich_accounts::run(shell, args).await?,
+        DevCommands::DeployErc20(args) => commands::deploy_erc20::run(shell, args).await?,
+        DevCommands::BaseToken(args) => commands::base_token::run(shell, args).await?,
         DevCommands::GenerateGenesis => commands::genesis::run(shell).await?,
     }
     Ok(())
@@ -64,7 +64,7 @@ fn create(args: EcosystemCreateArgs, shell: &Shell) -> anyhow::Result<()> {
     create_erc20_deployment_config(shell, &configs_path)?;
     create_apps_config(shell, &configs_path)?;
 
-    let ecosystem_config = EcosystemConfig {
+    let mut ecosystem_config = EcosystemConfig {
         name: ecosystem_name.clone(),
         l1_network: args.l1_network,
         link_to_code: link_to_code.clone(),
@@ -75,6 +75,7 @@ fn create(args: EcosystemCreateArgs, shell: &Shell) -> anyhow::Result<()> {
         default_chain: default_chain_name.clone(),
         prover_version: chain_config.prover_version,
         wallet_creation: args.wallet_creation,
+        ports: Default::default(),
         shell: shell.clone().into(),
     };
 
@@ -91,7 +92,8 @@ fn create(args: EcosystemCreateArgs, shell: &Shell) -> anyhow::Result<()> {
     spinner.finish();
 
     let spinner = Spinner::new(MSG_CREATING_DEFAULT_CHAIN_SPINNER);
-    create_chain_inner(chain_config, &ecosystem_config, shell)?;
+    create_chain_inner(chain_config, &mut ecosystem_config, shell)?;
+    ecosystem_config.save_with_base_path(shell, ".")?;
     spinner.finish();
 
     if args.start_containers {
@@ -123,7 +123,7 @@ async fn init_ecosystem(
     Ok(contracts)
 }
 
-async fn deploy_erc20(
+pub(crate) async fn deploy_erc20(
     shell: &Shell,
     erc20_deployment_config: &Erc20DeploymentConfig,
     ecosystem_config: &EcosystemConfig,
@@ -28,7 +28,7 @@ pub(crate) async fn run(shell: &Shell) -> anyhow::Result<()> {
         Some(ref chain_name) => vec![chain_name.clone()],
         None => ecosystem_config.list_of_chains(),
     };
-    let mut ports = EcosystemPortsScanner::scan(shell)?;
+    let mut ports = EcosystemPortsScanner::scan(shell, None)?;
     // Initialize chains one by one
     let mut explorer_config = ExplorerConfig::read_or_create_default(shell)?;
     for chain_name in chains_enabled.iter() {
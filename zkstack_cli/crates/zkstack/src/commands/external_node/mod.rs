@@ -7,7 +7,7 @@ use crate::commands::args::WaitArgs;
 
 mod args;
 mod build;
-mod init;
+pub mod init;
 mod prepare_configs;
 mod run;
 mod wait;
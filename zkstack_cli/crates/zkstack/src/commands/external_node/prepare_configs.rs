@@ -6,7 +6,7 @@ use config::{
     external_node::ENConfig,
     set_rocks_db_config,
     traits::{FileConfigWithDefaultName, SaveConfigWithBasePath},
-    ChainConfig, EcosystemConfig, GeneralConfig, SecretsConfig,
+    ChainConfig, EcosystemConfig, GeneralConfig, PortRegistry, SecretsConfig,
 };
 use xshell::Shell;
 use zksync_basic_types::url::SensitiveUrl;
@@ -26,7 +26,7 @@ use crate::{
     },
     utils::{
         consensus::node_public_key,
-        ports::EcosystemPortsScanner,
+        ports::{register_ports, EcosystemPortsScanner},
         rocks_db::{recreate_rocksdb_dirs, RocksDBDirOption},
     },
 };
@@ -57,7 +57,8 @@ fn prepare_configs(
     en_configs_path: &Path,
     args: PrepareConfigFinal,
 ) -> anyhow::Result<()> {
-    let mut ports = EcosystemPortsScanner::scan(shell)?;
+    let en_owner = PortRegistry::external_node_owner(&config.name);
+    let mut ports = EcosystemPortsScanner::scan(shell, Some(&en_owner))?;
     let genesis = config.get_genesis_config()?;
     let general = config.get_general_config()?;
     let en_config = ENConfig {
@@ -75,6 +76,7 @@ fn prepare_configs(
         )?,
         main_node_rate_limit_rps: None,
         bridge_addresses_refresh_interval_sec: None,
+        tree_data_fetcher_root_hash_mismatch_policy: Default::default(),
     };
     let mut general_en = general.clone();
     general_en.consensus_config = None;
@@ -108,6 +110,7 @@ fn prepare_configs(
             server_url: Some(args.db.full_url().into()),
             prover_url: None,
             server_replica_url: None,
+            server_replica_urls: vec![],
         }),
         l1: Some(L1Secrets {
             l1_rpc_url: SensitiveUrl::from_str(&args.l1_rpc_url).context("l1_rpc_url")?,
@@ -135,6 +138,11 @@ fn prepare_configs(
         &ConsensusConfig::get_path_with_base_path(en_configs_path),
         offset,
     )?;
+    register_ports(
+        shell,
+        &en_owner,
+        &GeneralConfig::get_path_with_base_path(en_configs_path),
+    )?;
 
     Ok(())
 }
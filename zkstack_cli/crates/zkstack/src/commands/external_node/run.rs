@@ -1,12 +1,17 @@
 use anyhow::Context;
 use common::logger;
-use config::{ChainConfig, EcosystemConfig};
+use config::{
+    traits::FileConfigWithDefaultName, ChainConfig, EcosystemConfig, GeneralConfig, PortRegistry,
+};
 use xshell::Shell;
 
 use crate::{
     commands::external_node::{args::run::RunExternalNodeArgs, init},
     external_node::RunExternalNode,
-    messages::{MSG_CHAIN_NOT_INITIALIZED, MSG_STARTING_EN},
+    messages::{
+        MSG_CHAIN_NOT_INITIALIZED, MSG_EXTERNAL_NODE_CONFIG_NOT_INITIALIZED, MSG_STARTING_EN,
+    },
+    utils::ports::validate_ports,
 };
 
 pub async fn run(shell: &Shell, args: RunExternalNodeArgs) -> anyhow::Result<()> {
@@ -16,6 +21,17 @@ pub async fn run(shell: &Shell, args: RunExternalNodeArgs) -> anyhow::Result<()>
         .load_current_chain()
         .context(MSG_CHAIN_NOT_INITIALIZED)?;
 
+    let en_configs_path = chain_config
+        .external_node_config_path
+        .clone()
+        .context(MSG_EXTERNAL_NODE_CONFIG_NOT_INITIALIZED)?;
+    validate_ports(
+        shell,
+        &ecosystem_config,
+        &PortRegistry::external_node_owner(&chain_config.name),
+        &GeneralConfig::get_path_with_base_path(en_configs_path),
+    )?;
+
     logger::info(MSG_STARTING_EN);
 
     run_external_node(args, &chain_config, shell).await?;
@@ -12,3 +12,4 @@ pub mod portal;
 pub mod prover;
 pub mod server;
 pub mod update;
+pub mod wallet;
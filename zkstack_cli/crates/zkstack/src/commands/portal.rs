@@ -57,13 +57,26 @@ async fn build_portal_chain_config(
                 .await?,
         )
     };
-    let tokens = vec![TokenConfig {
+    let mut tokens = vec![TokenConfig {
         address: L2_BASE_TOKEN_ADDRESS.to_string(),
         l1_address: Some(base_token_addr.to_string()),
         symbol: base_token_info.symbol,
         decimals: base_token_info.decimals,
         name: Some(base_token_info.name.to_string()),
     }];
+    // Test tokens bridged with `zkstack dev deploy-erc20 --bridge`
+    for token in chain_config.get_tokens_config()?.tokens.into_values() {
+        let Some(l2_address) = token.l2_address else {
+            continue;
+        };
+        tokens.push(TokenConfig {
+            address: format!("{:?}", l2_address),
+            l1_address: Some(format!("{:?}", token.l1_address)),
+            symbol: token.symbol,
+            decimals: token.decimals as u8,
+            name: Some(token.name),
+        });
+    }
     // Build hyperchain config
     Ok(PortalChainConfig {
         network: NetworkConfig {
@@ -122,6 +122,7 @@ fn get_object_store_config(
             },
             max_retries: PROVER_STORE_MAX_RETRIES,
             local_mirror_path: None,
+            tiers: vec![],
         }),
         Some(ProofStorageConfig::GCSCreateBucket(config)) => {
             Some(create_gcs_bucket(shell, config)?)
@@ -177,6 +178,7 @@ fn init_file_backed_proof_storage(
         },
         max_retries: PROVER_STORE_MAX_RETRIES,
         local_mirror_path: None,
+        tiers: vec![],
     };
 
     Ok(object_store_config)
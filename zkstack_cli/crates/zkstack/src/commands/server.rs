@@ -18,6 +18,7 @@ use crate::{
         MSG_FAILED_TO_BUILD_SERVER_ERR, MSG_FAILED_TO_RUN_SERVER_ERR, MSG_STARTING_SERVER,
         MSG_WAITING_FOR_SERVER,
     },
+    utils::ports::validate_ports,
 };
 
 pub async fn run(shell: &Shell, args: ServerArgs) -> anyhow::Result<()> {
@@ -27,7 +28,15 @@ pub async fn run(shell: &Shell, args: ServerArgs) -> anyhow::Result<()> {
         .context(MSG_CHAIN_NOT_INITIALIZED)?;
 
     match ServerCommand::from(args) {
-        ServerCommand::Run(args) => run_server(args, &chain_config, shell),
+        ServerCommand::Run(args) => {
+            validate_ports(
+                shell,
+                &ecosystem_config,
+                &chain_config.name,
+                &chain_config.path_to_general_config(),
+            )?;
+            run_server(args, &chain_config, shell)
+        }
         ServerCommand::Build => build_server(&chain_config, shell),
         ServerCommand::Wait(args) => wait_for_server(args, &chain_config).await,
     }
@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+
+use clap::{ArgGroup, Parser};
+use config::WalletRole;
+use ethers::types::{Address, H256};
+
+use crate::{
+    consts::DEFAULT_LEDGER_DERIVATION_PATH,
+    messages::{
+        MSG_WALLET_ADDRESS_HELP, MSG_WALLET_CREATE_KEYSTORE_HELP, MSG_WALLET_DERIVATION_PATH_HELP,
+        MSG_WALLET_ECOSYSTEM_HELP, MSG_WALLET_KEYSTORE_PATH_HELP, MSG_WALLET_LEDGER_HELP,
+        MSG_WALLET_PRIVATE_KEY_HELP, MSG_WALLET_ROLE_HELP,
+    },
+};
+
+#[derive(Debug, Parser)]
+pub struct WalletTargetArgs {
+    #[clap(long, help = MSG_WALLET_ECOSYSTEM_HELP)]
+    pub ecosystem: bool,
+}
+
+#[derive(Debug, Parser)]
+pub struct CreateWalletArgs {
+    #[clap(long, value_enum, help = MSG_WALLET_ROLE_HELP)]
+    pub role: WalletRole,
+    #[clap(long, help = MSG_WALLET_CREATE_KEYSTORE_HELP)]
+    pub keystore: bool,
+    #[clap(flatten)]
+    pub target: WalletTargetArgs,
+}
+
+#[derive(Debug, Parser)]
+#[clap(group(ArgGroup::new("source").required(true).args(["private_key", "keystore", "ledger"])))]
+pub struct ImportWalletArgs {
+    #[clap(long, value_enum, help = MSG_WALLET_ROLE_HELP)]
+    pub role: WalletRole,
+    #[clap(long, help = MSG_WALLET_PRIVATE_KEY_HELP)]
+    pub private_key: Option<H256>,
+    #[clap(long, help = MSG_WALLET_KEYSTORE_PATH_HELP)]
+    pub keystore: Option<PathBuf>,
+    #[clap(long, help = MSG_WALLET_LEDGER_HELP)]
+    pub ledger: bool,
+    #[clap(long, requires = "ledger", help = MSG_WALLET_ADDRESS_HELP)]
+    pub address: Option<Address>,
+    #[clap(long, default_value = DEFAULT_LEDGER_DERIVATION_PATH, help = MSG_WALLET_DERIVATION_PATH_HELP)]
+    pub derivation_path: String,
+    #[clap(flatten)]
+    pub target: WalletTargetArgs,
+}
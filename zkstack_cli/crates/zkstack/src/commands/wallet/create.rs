@@ -0,0 +1,43 @@
+use common::{
+    logger,
+    wallets::{keystore_password, Wallet, WalletSigner},
+};
+use config::{
+    traits::{ReadConfig, SaveConfig},
+    WalletsConfig,
+};
+use ethers::{
+    core::rand::thread_rng,
+    signers::{LocalWallet, Signer},
+};
+use xshell::Shell;
+
+use super::{args::CreateWalletArgs, keystores_dir, wallets_path};
+use crate::messages::msg_wallet_saved;
+
+pub fn run(shell: &Shell, args: CreateWalletArgs) -> anyhow::Result<()> {
+    let path = wallets_path(shell, &args.target)?;
+    let mut wallets = WalletsConfig::read(shell, &path)?;
+
+    let wallet = if args.keystore {
+        let dir = keystores_dir(&path);
+        shell.create_dir(&dir)?;
+        let name = format!("{}.json", args.role);
+        let (local_wallet, _) =
+            LocalWallet::new_keystore(&dir, &mut thread_rng(), keystore_password(), Some(&name))?;
+        Wallet::with_signer(
+            local_wallet.address(),
+            WalletSigner::Keystore {
+                path: dir.join(name),
+            },
+        )
+    } else {
+        Wallet::random(&mut thread_rng())
+    };
+
+    let address = wallet.address;
+    wallets.set_wallet(args.role, wallet);
+    wallets.save(shell, &path)?;
+    logger::success(msg_wallet_saved(args.role, address, &path));
+    Ok(())
+}
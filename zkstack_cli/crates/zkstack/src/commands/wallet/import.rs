@@ -0,0 +1,48 @@
+use anyhow::Context;
+use common::{
+    logger,
+    wallets::{keystore_password, Wallet, WalletSigner},
+};
+use config::{
+    traits::{ReadConfig, SaveConfig},
+    WalletsConfig,
+};
+use ethers::signers::{LocalWallet, Signer};
+use xshell::Shell;
+
+use super::{args::ImportWalletArgs, wallets_path};
+use crate::messages::{msg_wallet_saved, MSG_WALLET_LEDGER_ADDRESS_MISSING_ERR};
+
+pub fn run(shell: &Shell, args: ImportWalletArgs) -> anyhow::Result<()> {
+    // Resolve the keystore path before the shell moves to the ecosystem directory
+    let keystore = args.keystore.map(|path| shell.current_dir().join(path));
+    let path = wallets_path(shell, &args.target)?;
+    let mut wallets = WalletsConfig::read(shell, &path)?;
+
+    let wallet = if let Some(private_key) = args.private_key {
+        Wallet::new(LocalWallet::from_bytes(private_key.as_bytes())?)
+    } else if let Some(keystore) = keystore {
+        let local_wallet = LocalWallet::decrypt_keystore(&keystore, keystore_password())
+            .with_context(|| format!("Failed to decrypt keystore {}", keystore.display()))?;
+        Wallet::with_signer(
+            local_wallet.address(),
+            WalletSigner::Keystore { path: keystore },
+        )
+    } else {
+        let address = args
+            .address
+            .context(MSG_WALLET_LEDGER_ADDRESS_MISSING_ERR)?;
+        Wallet::with_signer(
+            address,
+            WalletSigner::Ledger {
+                derivation_path: args.derivation_path,
+            },
+        )
+    };
+
+    let address = wallet.address;
+    wallets.set_wallet(args.role, wallet);
+    wallets.save(shell, &path)?;
+    logger::success(msg_wallet_saved(args.role, address, &path));
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use common::{logger, wallets::WalletSigner};
+use config::{traits::ReadConfig, WalletRole, WalletsConfig};
+use strum::IntoEnumIterator;
+use xshell::Shell;
+
+use super::{args::WalletTargetArgs, wallets_path};
+use crate::messages::{msg_wallet_info, MSG_WALLET_NOT_SET};
+
+pub fn run(shell: &Shell, args: WalletTargetArgs) -> anyhow::Result<()> {
+    let path = wallets_path(shell, &args)?;
+    let wallets = WalletsConfig::read(shell, &path)?;
+
+    for role in WalletRole::iter() {
+        let Some(wallet) = wallets.wallet(role) else {
+            continue;
+        };
+        let signer = match (&wallet.signer, &wallet.private_key) {
+            (Some(WalletSigner::Keystore { path }), _) => format!("keystore {}", path.display()),
+            (Some(WalletSigner::Ledger { derivation_path }), _) => {
+                format!("ledger {derivation_path}")
+            }
+            (None, Some(_)) => "private key".to_string(),
+            (None, None) => MSG_WALLET_NOT_SET.to_string(),
+        };
+        logger::info(msg_wallet_info(role, wallet.address, &signer));
+    }
+    Ok(())
+}
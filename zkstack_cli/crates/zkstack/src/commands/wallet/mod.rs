@@ -0,0 +1,56 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use args::{CreateWalletArgs, ImportWalletArgs, WalletTargetArgs};
+use clap::Subcommand;
+use config::{traits::FileConfigWithDefaultName, EcosystemConfig, WalletsConfig};
+use xshell::Shell;
+
+use crate::messages::MSG_CHAIN_NOT_INITIALIZED;
+
+mod args;
+mod create;
+mod import;
+mod inspect;
+
+#[derive(Subcommand, Debug)]
+pub enum WalletCommands {
+    /// Create a new random wallet for the given role
+    Create(CreateWalletArgs),
+    /// Import a private key, an encrypted keystore or a Ledger account for the given role
+    Import(ImportWalletArgs),
+    /// Show the wallets and how they sign transactions
+    Inspect(WalletTargetArgs),
+}
+
+pub(crate) fn run(shell: &Shell, args: WalletCommands) -> anyhow::Result<()> {
+    match args {
+        WalletCommands::Create(args) => create::run(shell, args),
+        WalletCommands::Import(args) => import::run(shell, args),
+        WalletCommands::Inspect(args) => inspect::run(shell, args),
+    }
+}
+
+/// Path to the wallets config of the current chain, or of the ecosystem.
+fn wallets_path(shell: &Shell, target: &WalletTargetArgs) -> anyhow::Result<PathBuf> {
+    let ecosystem_config = EcosystemConfig::from_file(shell)?;
+    if target.ecosystem {
+        return Ok(WalletsConfig::get_path_with_base_path(
+            &ecosystem_config.config,
+        ));
+    }
+    let chain_config = ecosystem_config
+        .load_current_chain()
+        .context(MSG_CHAIN_NOT_INITIALIZED)?;
+    Ok(WalletsConfig::get_path_with_base_path(
+        &chain_config.configs,
+    ))
+}
+
+/// Keystores are stored next to the wallets config they belong to.
+fn keystores_dir(wallets_path: &Path) -> PathBuf {
+    wallets_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join("keystores")
+}
@@ -6,7 +6,7 @@ use commands::{
 };
 use common::{
     check_general_prerequisites,
-    config::{global_config, init_global_config, GlobalConfig},
+    config::{global_config, init_global_config, GlobalConfig, OutputFormat},
     error::log_error,
     init_prompt_theme, logger,
     version::version_message,
@@ -17,6 +17,7 @@ use xshell::Shell;
 use crate::commands::{
     args::ServerArgs, chain::ChainCommands, consensus, ecosystem::EcosystemCommands,
     explorer::ExplorerCommands, external_node::ExternalNodeCommands, prover::ProverCommands,
+    wallet::WalletCommands,
 };
 
 pub mod accept_ownership;
@@ -76,6 +77,9 @@ pub enum ZkStackSubcommands {
     /// Consensus utilities
     #[command(subcommand)]
     Consensus(consensus::Command),
+    /// Wallet management
+    #[command(subcommand, alias = "w")]
+    Wallet(WalletCommands),
     /// Update ZKsync
     #[command(alias = "u")]
     Update(UpdateArgs),
@@ -96,6 +100,9 @@ struct ZkStackGlobalArgs {
     /// Ignores prerequisites checks
     #[clap(long, global = true)]
     ignore_prerequisites: bool,
+    /// Format of the command results printed to stdout
+    #[clap(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
 }
 
 #[tokio::main]
@@ -148,6 +155,7 @@ async fn run_subcommand(zkstack_args: ZkStack) -> anyhow::Result<()> {
         ZkStackSubcommands::Explorer(args) => commands::explorer::run(&shell, args).await?,
         ZkStackSubcommands::Consensus(cmd) => cmd.run(&shell).await?,
         ZkStackSubcommands::Portal => commands::portal::run(&shell).await?,
+        ZkStackSubcommands::Wallet(args) => commands::wallet::run(&shell, args)?,
         ZkStackSubcommands::Update(args) => commands::update::run(&shell, args).await?,
         ZkStackSubcommands::Markdown => {
             clap_markdown::print_help_markdown::<ZkStack>();
@@ -173,6 +181,7 @@ fn init_global_config_inner(shell: &Shell, zkstack_args: &ZkStackGlobalArgs) ->
         verbose: zkstack_args.verbose,
         chain_name: zkstack_args.chain.clone(),
         ignore_prerequisites: zkstack_args.ignore_prerequisites,
+        output: zkstack_args.output,
     });
     Ok(())
 }
@@ -1,9 +1,11 @@
 use std::{fmt, path::Path, time::Duration};
 
+use config::{L2DeploymentStep, WalletRole};
 use ethers::{
-    types::{Address, H160, U256},
+    types::{Address, Bytes, H160, H256, U256},
     utils::format_ether,
 };
+use types::ProtocolSemanticVersion;
 use url::Url;
 use zksync_consensus_roles::attester;
 
@@ -212,6 +214,185 @@ pub(super) const MSG_FAILED_TO_DROP_SERVER_DATABASE_ERR: &str = "Failed to drop
 pub(super) const MSG_INITIALIZING_PROVER_DATABASE: &str = "Initializing prover database";
 pub(super) const MSG_FAILED_TO_DROP_PROVER_DATABASE_ERR: &str = "Failed to drop prover database";
 pub(super) const MSG_GENESIS_DATABASES_INITIALIZED: &str = "Databases initialized successfully";
+pub(super) const MSG_EXPORT_GENESIS_OUTPUT_HELP: &str =
+    "Path to the exported genesis file (yaml, toml or json)";
+pub(super) const MSG_IMPORT_GENESIS_INPUT_HELP: &str =
+    "Path to the genesis file exported by `zkstack chain export-genesis`";
+pub(super) const MSG_EXPORTING_GENESIS_SPINNER: &str =
+    "Exporting genesis state. Building the export tool may take a lot of time...";
+pub(super) const MSG_FAILED_TO_EXPORT_GENESIS_ERR: &str = "Failed to export genesis state";
+pub(super) const MSG_GENESIS_IMPORTED: &str =
+    "Genesis imported successfully. Run `zkstack chain genesis` to initialize the database";
+
+pub(super) fn msg_genesis_exported(path: &Path) -> String {
+    format!("Genesis exported to {}", path.display())
+}
+
+pub(super) fn msg_genesis_chain_id_mismatch(exported: u64, chain: u64) -> String {
+    format!("Exported genesis belongs to chain {exported}, while the current chain id is {chain}")
+}
+
+/// Chain snapshot related messages
+pub(super) const MSG_RUNNING_SNAPSHOT_CREATOR: &str = "Running snapshot creator";
+pub(super) const MSG_FAILED_TO_RUN_SNAPSHOT_CREATOR_ERR: &str = "Failed to run snapshot creator";
+pub(super) const MSG_SNAPSHOT_CREATOR_CONFIG_MISSING_ERR: &str =
+    "Snapshot creator object store is not configured";
+pub(super) const MSG_NO_SNAPSHOTS_FOUND: &str = "No snapshots found";
+pub(super) const MSG_SNAPSHOT_RESTORE_L1_BATCH_HELP: &str =
+    "L1 batch number of the snapshot to restore. Defaults to the latest snapshot";
+pub(super) const MSG_SNAPSHOT_RESTORE_FORCE_HELP: &str =
+    "Drop the external node database without asking for confirmation";
+pub(super) const MSG_SNAPSHOT_RESTORE_DROP_DB_PROMPT: &str =
+    "Restoring from a snapshot drops the external node database. Continue?";
+pub(super) const MSG_SNAPSHOT_RESTORE_ABORTED: &str = "Snapshot restore aborted";
+
+pub(super) fn msg_snapshot_info(
+    l1_batch_number: u32,
+    l2_block_number: u32,
+    chunks: usize,
+    root_hash: Option<H256>,
+) -> String {
+    let root_hash = root_hash.map_or_else(|| "unknown".to_string(), |hash| format!("{hash:?}"));
+    format!(
+        "L1 batch {l1_batch_number}: L2 block {l2_block_number}, {chunks} storage log chunks, root hash {root_hash}"
+    )
+}
+
+pub(super) fn msg_snapshot_restore_prepared(l1_batch: Option<u32>) -> String {
+    let snapshot = l1_batch.map_or_else(
+        || "the latest snapshot".to_string(),
+        |n| format!("snapshot for L1 batch {n}"),
+    );
+    format!("External node database is initialized. The node will recover from {snapshot} on the next run")
+}
+
+/// Chain upgrade related messages
+pub(super) const MSG_UPGRADE_DESCRIPTION_HELP: &str =
+    "Path to the description of the new protocol version (yaml, toml or json)";
+pub(super) const MSG_UPGRADE_SKIP_DB_CHECK_HELP: &str =
+    "Skip checking that the node has picked up the new protocol version";
+pub(super) const MSG_UPGRADE_DB_CHECK_TIMEOUT_HELP: &str =
+    "Time in seconds to wait for the node to pick up the new protocol version";
+pub(super) const MSG_UPGRADE_SCHEDULING_SPINNER: &str =
+    "Scheduling and executing the upgrade with the ecosystem governance...";
+pub(super) const MSG_UPGRADE_EXECUTING_SPINNER: &str =
+    "Executing the upgrade on the chain with the chain admin...";
+pub(super) const MSG_UPGRADE_WAITING_FOR_NODE_SPINNER: &str =
+    "Waiting for the node to pick up the new protocol version...";
+pub(super) const MSG_ECOSYSTEM_GOVERNOR_PRIVATE_KEY_MISSING_ERR: &str =
+    "Private key of the ecosystem governor is not set";
+pub(super) const MSG_CHAIN_GOVERNOR_PRIVATE_KEY_MISSING_ERR: &str =
+    "Private key of the chain governor is not set";
+
+pub(super) fn msg_upgrade_version(
+    old: &ProtocolSemanticVersion,
+    new: &ProtocolSemanticVersion,
+) -> String {
+    format!("Upgrading chain from protocol version {old} to {new}")
+}
+
+pub(super) fn msg_upgrade_version_not_newer(
+    old: &ProtocolSemanticVersion,
+    new: &ProtocolSemanticVersion,
+) -> String {
+    format!("New protocol version {new} must be greater than the current one {old}")
+}
+
+pub(super) fn msg_upgrade_tx_failed(operation: &str, tx_hash: H256) -> String {
+    format!("Transaction for {operation} failed, tx_hash={tx_hash:?}")
+}
+
+pub(super) fn msg_upgrade_not_active_on_l1(
+    expected: &ProtocolSemanticVersion,
+    actual: &ProtocolSemanticVersion,
+) -> String {
+    format!("Diamond proxy reports protocol version {actual}, expected {expected}")
+}
+
+pub(super) fn msg_upgrade_not_picked_up_by_node(version: &ProtocolSemanticVersion) -> String {
+    format!("Node has not picked up protocol version {version} in time")
+}
+
+pub(super) fn msg_chain_upgraded(version: &ProtocolSemanticVersion) -> String {
+    format!("Chain upgraded to protocol version {version}")
+}
+
+/// Gateway migration related messages
+pub(super) const MSG_GATEWAY_MIGRATION_STAGE_HELP: &str =
+    "Migration stage to run. Stages must be run in order \
+    (prepare, pause, finalize), restarting the server after each of them";
+pub(super) const MSG_GATEWAY_RPC_URL_HELP: &str =
+    "RPC URL of the Gateway, required for the finalize stage";
+pub(super) const MSG_GATEWAY_RPC_URL_MISSING_ERR: &str =
+    "Gateway RPC URL must be provided for the finalize stage";
+pub(super) const MSG_ETH_CONFIG_MISSING_ERR: &str = "Eth sender or gas adjuster config is not set";
+pub(super) const MSG_GATEWAY_MIGRATION_PREPARED: &str = "Eth sender will only prove and execute \
+    already committed batches. Restart the server, and run the `pause` stage once all committed batches are executed";
+pub(super) const MSG_GATEWAY_MIGRATION_PAUSED: &str = "Eth sender aggregation is paused. Restart \
+    the server, and run the `finalize` stage once all in-flight L1 transactions are confirmed";
+
+pub(super) fn msg_gateway_migration_unexecuted_batches(count: i64) -> String {
+    format!("There are still {count} committed but not executed L1 batches; wait for them to be executed")
+}
+
+pub(super) fn msg_gateway_migration_inflight_txs(count: i64) -> String {
+    format!("There are still {count} in-flight L1 transactions; wait for them to be confirmed")
+}
+
+pub(super) const MSG_GATEWAY_MIGRATION_ALREADY_FINALIZED: &str =
+    "Chain settlement is already switched to the Gateway";
+pub(super) const MSG_GATEWAY_MIGRATION_NOT_PREPARED: &str =
+    "The `prepare` stage must be run before the `pause` stage";
+pub(super) const MSG_GATEWAY_MIGRATION_NOT_PAUSED: &str =
+    "The `pause` stage must be run before the `finalize` stage";
+
+pub(super) fn msg_gateway_migration_batches_committed_after_pause(
+    paused_at: u32,
+    last_committed: u32,
+) -> String {
+    format!(
+        "L1 batches were committed after the `pause` stage (last committed batch at pause: {paused_at}, now: {last_committed}); \
+         run the `pause` stage again"
+    )
+}
+
+pub(super) fn msg_gateway_migration_l1_state_mismatch(
+    l1_batch: u32,
+    committed_on_l1: U256,
+    executed_on_l1: U256,
+) -> String {
+    format!(
+        "Diamond proxy state doesn't match the last L1 batch {l1_batch} recorded at the `pause` stage \
+         (committed on L1: {committed_on_l1}, executed on L1: {executed_on_l1}); wait for pending L1 transactions"
+    )
+}
+
+pub(super) fn msg_gateway_migration_finalized(l1_batch: u32) -> String {
+    format!("Chain settlement is switched to the Gateway (last L1 batch settled on L1: {l1_batch}). Restart the server to apply the changes")
+}
+
+/// Wallet management related messages
+pub(super) const MSG_WALLET_ROLE_HELP: &str = "Role of the wallet";
+pub(super) const MSG_WALLET_ECOSYSTEM_HELP: &str =
+    "Manage ecosystem wallets instead of the wallets of the chain";
+pub(super) const MSG_WALLET_CREATE_KEYSTORE_HELP: &str =
+    "Store the private key in an encrypted keystore instead of the wallets config";
+pub(super) const MSG_WALLET_PRIVATE_KEY_HELP: &str = "Private key to import";
+pub(super) const MSG_WALLET_KEYSTORE_PATH_HELP: &str = "Path to the encrypted keystore to import";
+pub(super) const MSG_WALLET_LEDGER_HELP: &str = "Sign with a Ledger account";
+pub(super) const MSG_WALLET_ADDRESS_HELP: &str = "Address of the Ledger account";
+pub(super) const MSG_WALLET_DERIVATION_PATH_HELP: &str = "HD derivation path of the Ledger account";
+pub(super) const MSG_WALLET_LEDGER_ADDRESS_MISSING_ERR: &str =
+    "Address of the Ledger account must be provided with --address";
+pub(super) const MSG_WALLET_NOT_SET: &str = "not set";
+
+pub(super) fn msg_wallet_saved(role: WalletRole, address: Address, path: &Path) -> String {
+    format!("Wallet {role} {address:#x} saved to {}", path.display())
+}
+
+pub(super) fn msg_wallet_info(role: WalletRole, address: Address, signer: &str) -> String {
+    format!("{role}: {address:#x} ({signer})")
+}
 
 /// Chain update related messages
 pub(super) const MSG_WALLETS_CONFIG_MUST_BE_PRESENT: &str = "Wallets configuration must be present";
@@ -250,6 +431,22 @@ pub(super) fn msg_explorer_db_name_prompt(chain_name: &str) -> String {
 
 /// Chain initialize bridges related messages
 pub(super) const MSG_DEPLOYING_L2_CONTRACT_SPINNER: &str = "Deploying l2 contracts";
+pub(super) const MSG_CUSTOM_L2_SCRIPT_NAME_HELP: &str =
+    "Unique name of the script, used for its config files and for the deployed contract names";
+pub(super) const MSG_CUSTOM_L2_SCRIPT_PATH_HELP: &str =
+    "Path to the forge script, absolute or relative to contracts/l1-contracts";
+pub(super) const MSG_CUSTOM_L2_SCRIPT_INPUT_HELP: &str =
+    "TOML file with additional input for the script";
+pub(super) const MSG_CUSTOM_L2_SCRIPT_SIGNATURE_HELP: &str =
+    "Function of the script to run instead of run()";
+pub(super) const MSG_DEPLOY_L2_CONTRACTS_FORCE_HELP: &str =
+    "Redeploy all contracts, including the ones deployed by a previous run";
+pub(super) const MSG_DEPLOY_L2_CONTRACTS_VERIFY_HELP: &str =
+    "Submit the deployed contracts to the chain contract verifier";
+
+pub(super) fn msg_l2_deployment_step_skipped(step: L2DeploymentStep) -> String {
+    format!("Skipping L2 deployment step {step}: already completed, use --force to redeploy")
+}
 
 /// Chain deploy paymaster related messages
 pub(super) const MSG_DEPLOYING_PAYMASTER: &str = "Deploying paymaster";
@@ -262,6 +459,36 @@ pub(super) const MSG_CHAIN_TXN_MISSING_CONTRACT_CONFIG: &str =
     "Missing contract.yaml, please be sure to run this command within initialized ecosystem";
 pub(super) const MSG_CHAIN_TRANSACTIONS_BUILT: &str = "Chain transactions successfully built";
 
+/// Chain join related messages
+pub(super) const MSG_JOIN_ECOSYSTEM_OWNER_HELP: &str =
+    "Owner of the ecosystem BridgeHub executing the registration, read from L1 if not set";
+pub(super) const MSG_JOIN_OUT_HELP: &str = "Output directory for the registration transactions";
+pub(super) const MSG_JOIN_TIMEOUT_HELP: &str =
+    "Time to wait for the ecosystem owner to register the chain, in seconds";
+pub(super) const MSG_JOIN_BRIDGEHUB_DEPLOYMENT_BLOCK_HELP: &str =
+    "L1 block the ecosystem BridgeHub was deployed at, used to search for registration events. Found via binary search (requires an archive L1 node) if not set";
+pub(super) const MSG_JOIN_WAITING_SPINNER: &str =
+    "Waiting for the ecosystem owner to register the chain...";
+pub(super) const MSG_JOIN_ALREADY_REGISTERED: &str =
+    "Chain is already registered on the BridgeHub, continuing initialization";
+pub(super) const MSG_JOIN_TIMEOUT_ERR: &str =
+    "Chain was not registered by the ecosystem owner in time, re-run the command to keep waiting";
+pub(super) const MSG_JOIN_NEW_CHAIN_EVENT_NOT_FOUND_ERR: &str =
+    "NewChain event for the chain was not found on the BridgeHub";
+pub(super) const MSG_JOIN_BRIDGEHUB_NOT_DEPLOYED_ERR: &str =
+    "BridgeHub contract is not deployed on L1";
+
+pub(super) fn msg_join_searching_new_chain_event(from_block: u64) -> String {
+    format!("Searching for the NewChain event starting from L1 block #{from_block}")
+}
+
+pub(super) fn msg_join_send_transactions(path: &Path, owner: Address) -> String {
+    format!(
+        "Registration transactions were written to {}, they have to be executed by the ecosystem owner {owner:#x}",
+        path.display()
+    )
+}
+
 /// Run server related messages
 pub(super) const MSG_SERVER_COMPONENTS_HELP: &str = "Components of server to run";
 pub(super) const MSG_ENABLE_CONSENSUS_HELP: &str = "Enable consensus";
@@ -462,6 +689,20 @@ pub(super) const MSG_RUNNING_CONTRACT_VERIFIER: &str = "Running contract verifie
 pub(super) const MSG_FAILED_TO_BUILD_CONTRACT_VERIFIER_ERR: &str =
     "Failed to build contract verifier";
 pub(super) const MSG_FAILED_TO_RUN_CONTRACT_VERIFIER_ERR: &str = "Failed to run contract verifier";
+pub(super) const MSG_CONTRACT_VERIFIER_CONFIG_MISSING_ERR: &str =
+    "Contract verifier config is missing";
+pub(super) const MSG_CONTRACT_VERIFIER_URL_ERR: &str =
+    "Failed to build contract verifier URL from the L2 RPC URL";
+pub(super) const MSG_VERIFYING_L2_CONTRACTS: &str =
+    "Submitting L2 contract to the contract verifier";
+
+pub(super) fn msg_l2_contract_verification_submitted(
+    name: &str,
+    address: Address,
+    id: usize,
+) -> String {
+    format!("Submitted {name} ({address:#x}) for verification, request id: {id}")
+}
 pub(super) const MSG_INVALID_ARCH_ERR: &str = "Invalid arch";
 pub(super) const MSG_GET_ZKSOLC_RELEASES_ERR: &str = "Failed to get zksolc releases";
 pub(super) const MSG_FETCHING_ZKSOLC_RELEASES_SPINNER: &str = "Fetching zksolc releases...";
@@ -589,6 +830,10 @@ pub(super) fn msg_wait_consensus_registry_started_polling(addr: Address, url: &U
     format!("Starting polling L2 HTTP RPC at {url} for code at {addr:?}")
 }
 
+pub(super) fn msg_consensus_registry_calldata(name: &str, to: Address, calldata: &Bytes) -> String {
+    format!("{name}: send to {to:#x} with calldata {calldata}")
+}
+
 pub(super) fn msg_consensus_registry_wait_success(addr: Address, code_len: usize) -> String {
     format!("Consensus registry is deployed at {addr:?}: {code_len} bytes")
 }
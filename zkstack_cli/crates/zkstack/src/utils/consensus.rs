@@ -78,6 +78,7 @@ pub fn get_genesis_specs(
         attesters: vec![attester],
         leader,
         registry_address: None,
+        attester_committee_epoch: None,
         seed_peers: [].into(),
     }
 }
@@ -1,5 +1,8 @@
 use anyhow::Context as _;
-use common::{forge::ForgeScript, wallets::Wallet};
+use common::{
+    forge::ForgeScript,
+    wallets::{keystore_password, Wallet, WalletSigner},
+};
 use ethers::types::U256;
 
 use crate::{
@@ -7,6 +10,7 @@ use crate::{
     messages::{msg_address_doesnt_have_enough_money_prompt, msg_wallet_private_key_not_set},
 };
 
+#[derive(Clone, Copy)]
 pub enum WalletOwner {
     Governor,
     Deployer,
@@ -17,14 +21,22 @@ pub fn fill_forge_private_key(
     wallet: Option<&Wallet>,
     wallet_owner: WalletOwner,
 ) -> anyhow::Result<ForgeScript> {
-    if !forge.wallet_args_passed() {
-        forge = forge.with_private_key(
-            wallet
-                .and_then(|w| w.private_key_h256())
-                .context(msg_wallet_private_key_not_set(wallet_owner))?,
-        );
+    if forge.wallet_args_passed() {
+        return Ok(forge);
+    }
+    let wallet = wallet.context(msg_wallet_private_key_not_set(wallet_owner))?;
+    if let Some(private_key) = wallet.private_key_h256() {
+        return Ok(forge.with_private_key(private_key));
     }
-    Ok(forge)
+    forge = match wallet
+        .signer
+        .as_ref()
+        .context(msg_wallet_private_key_not_set(wallet_owner))?
+    {
+        WalletSigner::Keystore { path } => forge.with_keystore(path, keystore_password()),
+        WalletSigner::Ledger { derivation_path } => forge.with_ledger(derivation_path.clone()),
+    };
+    Ok(forge.with_sender(format!("{:#x}", wallet.address)))
 }
 
 pub async fn check_the_balance(forge: &ForgeScript) -> anyhow::Result<()> {
@@ -1,10 +1,21 @@
-use std::{collections::HashMap, fmt, net::SocketAddr, ops::Range, path::Path};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    net::SocketAddr,
+    ops::Range,
+    path::Path,
+};
 
 use anyhow::{bail, Context, Result};
 use config::{
-    explorer_compose::ExplorerBackendPorts, EcosystemConfig, DEFAULT_EXPLORER_API_PORT,
-    DEFAULT_EXPLORER_DATA_FETCHER_PORT, DEFAULT_EXPLORER_WORKER_PORT,
+    collect_ports_from_yaml,
+    explorer_compose::ExplorerBackendPorts,
+    read_ports_from_yaml,
+    traits::{FileConfigWithDefaultName, SaveConfigWithBasePath},
+    EcosystemConfig, DEFAULT_EXPLORER_API_PORT, DEFAULT_EXPLORER_DATA_FETCHER_PORT,
+    DEFAULT_EXPLORER_WORKER_PORT,
 };
+use serde::Serialize;
 use serde_yaml::Value;
 use url::Url;
 use xshell::Shell;
@@ -15,7 +26,7 @@ pub struct EcosystemPorts {
     pub ports: HashMap<u16, Vec<PortInfo>>,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
 pub struct PortInfo {
     pub port: u16,
     pub file_path: String,
@@ -103,6 +114,20 @@ impl EcosystemPorts {
         Ok(())
     }
 
+    /// Allocates ports for a new chain based on the template config, without writing it,
+    /// and returns the allocated ports keyed by their path in the config.
+    pub fn allocate_ports_for_template(
+        &mut self,
+        shell: &Shell,
+        template_path: &Path,
+        chain_number: u32,
+    ) -> Result<BTreeMap<String, u16>> {
+        let mut value: Value = serde_yaml::from_str(&shell.read_file(template_path)?)?;
+        let offset = (chain_number - 1) * 100;
+        self.traverse_allocate_ports_in_yaml(&mut value, offset)?;
+        Ok(collect_ports_from_yaml(&value))
+    }
+
     fn traverse_allocate_ports_in_yaml(
         &mut self,
         value: &mut Value,
@@ -205,7 +230,9 @@ pub struct EcosystemPortsScanner;
 impl EcosystemPortsScanner {
     /// Scans the ecosystem directory for YAML files and extracts port information.
     /// Specifically, it looks for keys ending with "port" or "ports" and collects their values.
-    pub fn scan(shell: &Shell) -> Result<EcosystemPorts> {
+    /// Ports recorded in the ecosystem port registry are also marked as assigned,
+    /// except for the ones of the `exclude` owner.
+    pub fn scan(shell: &Shell, exclude: Option<&str>) -> Result<EcosystemPorts> {
         let ecosystem_config = EcosystemConfig::from_file(shell)?;
 
         // Create a list of directories to scan:
@@ -232,6 +259,15 @@ impl EcosystemPortsScanner {
             }
         }
 
+        for (owner, description, port) in ecosystem_config.ports.allocated_ports(exclude) {
+            let info = PortInfo {
+                port,
+                file_path: EcosystemConfig::FILE_NAME.to_string(),
+                description: format!("{owner}:{description}"),
+            };
+            ecosystem_ports.add_port_info(port, info);
+        }
+
         Ok(ecosystem_ports)
     }
 
@@ -371,6 +407,26 @@ impl EcosystemPortsScanner {
     }
 }
 
+/// Records the ports of the config file in the ecosystem port registry under `owner`.
+pub fn register_ports(shell: &Shell, owner: &str, config_path: &Path) -> Result<()> {
+    let mut ecosystem_config = EcosystemConfig::from_file(shell)?;
+    let ports = read_ports_from_yaml(shell, config_path)?;
+    ecosystem_config.ports.set_ports(owner, ports);
+    ecosystem_config.save_with_base_path(shell, ".")
+}
+
+/// Checks that the ports of the config file are not allocated to other owners
+/// in the ecosystem port registry.
+pub fn validate_ports(
+    shell: &Shell,
+    ecosystem_config: &EcosystemConfig,
+    owner: &str,
+    config_path: &Path,
+) -> Result<()> {
+    let ports = read_ports_from_yaml(shell, config_path)?;
+    ecosystem_config.ports.validate(owner, &ports)
+}
+
 pub trait ConfigWithChainPorts {
     fn get_default_ports(&self) -> anyhow::Result<HashMap<String, u16>>;
     fn set_ports(&mut self, ports: HashMap<String, u16>) -> Result<()>;